@@ -1,38 +1,55 @@
 use axum::{
+    extract::DefaultBodyLimit,
     middleware,
     routing::{delete, get, patch, post, put},
-    Router,
+    Extension, Router,
 };
+use persistence::backend::{Database, DeviceStore, LocationStore};
 use sqlx::PgPool;
 use std::sync::Arc;
 use std::time::Duration;
+use tower::ServiceBuilder;
 use tower_http::{
     compression::CompressionLayer,
     cors::{Any, CorsLayer},
+    decompression::RequestDecompressionLayer,
     timeout::TimeoutLayer,
     trace::TraceLayer,
 };
 
 use crate::config::Config;
+use crate::log_store::LogStore;
 use crate::middleware::{
-    auth_rate_limit_middleware, metrics_handler, metrics_middleware, rate_limit_middleware,
-    require_admin, require_auth, require_b2b, require_geofence_events, require_geofences,
-    require_movement_tracking, require_proximity_alerts, require_webhooks,
-    security_headers_middleware, trace_id, version_check, AuthRateLimiterState,
-    ExportRateLimiterState, RateLimiterState,
+    api_usage_middleware, auth_rate_limit_middleware, concurrency_limit, csrf_protection,
+    export_statement_timeout, mask_observer_locations, metrics_handler, metrics_middleware,
+    rate_limit_middleware, require_admin, require_auth, require_b2b, require_blob_storage,
+    require_geofence_events, require_geofences, require_movement_tracking,
+    require_proximity_alerts, require_self_service_orgs, require_webhooks,
+    security_headers_middleware, statement_timeout, trace_id, version_check, AuthRateLimiterState,
+    ConcurrencyLimits, ExportRateLimiterState, GroupTokenRateLimiterState, RateLimiterState,
+    RouteClass, StatementTimeouts,
 };
+use crate::preflight::PreflightReport;
 use crate::routes::{
     admin, admin_geofences, admin_groups, admin_locations, admin_managed_users, admin_migrations,
-    admin_unlock_requests, admin_users, analytics, api_keys, app_usage, audit_logs, auth,
-    bulk_import, compliance, dashboard, data_subject_requests, device_policies, device_settings,
-    devices, enrollment, enrollment_tokens, fleet, frontend, geofence_events, geofences, groups,
-    health, invites, locations, movement_events, openapi, org_invitations, org_webhooks,
-    organization_settings, organizations, permissions, privacy, proximity_alerts, public_config,
-    roles, system_config, system_roles, trips, users, versioning, webhooks,
+    admin_password_hashes, admin_unlock_requests, admin_users, analytics, api_keys, app_usage,
+    audit_logs, auth, bulk_import, commutes, compliance, dashboard, data_subject_requests,
+    device_agent, device_command_macros, device_policies, device_push_tokens, device_settings,
+    device_telemetry, devices, effective_access, enrollment, enrollment_tokens, fleet, frontend,
+    geofence_events, geofence_templates, geofences, group_api_tokens, group_exports,
+    group_hierarchy, group_messages, group_notification_preferences, group_roles, groups, health,
+    invites, location_imports, location_sharing, locations, movement_events, openapi,
+    org_invitations, org_ownership_transfer, org_webhooks, organization_exports,
+    organization_settings, organizations, permissions, privacy, privacy_zones, proximity_alerts,
+    public_config, roles, settings_diff, system_config, system_roles, tenant_logs, trip_edits,
+    trip_purposes, trip_shares, trips, users, versioning, webhooks,
 };
+use crate::services::api_usage::ApiUsageRecorder;
 use crate::services::cookies::CookieHelper;
+use crate::services::device_agent::AgentRegistry;
 use crate::services::fcm::FcmNotificationService;
 use crate::services::map_matching::MapMatchingClient;
+use crate::services::webhook_secret::WebhookSecrets;
 use domain::services::{MockNotificationService, NotificationService};
 
 #[derive(Clone)]
@@ -42,6 +59,10 @@ pub struct AppState {
     pub rate_limiter: Option<Arc<RateLimiterState>>,
     /// Export rate limiter for per-organization audit log exports
     pub export_rate_limiter: Option<Arc<ExportRateLimiterState>>,
+    /// Export rate limiter for per-device trip exports
+    pub trip_export_rate_limiter: Option<Arc<ExportRateLimiterState>>,
+    /// Rate limiter for group API tokens, each with its own limit
+    pub group_token_rate_limiter: Arc<GroupTokenRateLimiterState>,
     /// Forgot password rate limiter (per-IP)
     pub forgot_password_rate_limiter: Option<Arc<AuthRateLimiterState>>,
     /// Request verification rate limiter (per-IP)
@@ -52,9 +73,62 @@ pub struct AppState {
     pub notification_service: Arc<dyn NotificationService>,
     /// Cookie helper for httpOnly authentication
     pub cookie_helper: Arc<CookieHelper>,
+    /// Outcome of the startup dependency checks
+    pub preflight: Arc<PreflightReport>,
+    /// Rolling tenant log store (None if disabled)
+    pub log_store: Option<Arc<LogStore>>,
+    /// Open device agent connections of this instance
+    pub device_agents: Arc<AgentRegistry>,
+    /// Seals and reveals stored device webhook secrets
+    pub webhook_secrets: WebhookSecrets,
+    /// API usage counters awaiting the rollup job (None if not tracked)
+    pub api_usage: Option<Arc<ApiUsageRecorder>>,
 }
 
-pub fn create_app(config: Config, pool: PgPool) -> Router {
+impl AppState {
+    /// Device store of the storage backend.
+    pub fn devices(&self) -> impl DeviceStore {
+        self.pool.devices()
+    }
+
+    /// Location store of the storage backend.
+    pub fn locations(&self) -> impl LocationStore {
+        self.pool.locations()
+    }
+}
+
+/// Create the push notification service: FCM if enabled and configured,
+/// otherwise a mock that only logs.
+pub fn create_notification_service(config: &Config) -> Arc<dyn NotificationService> {
+    if config.fcm.enabled {
+        match FcmNotificationService::new(config.fcm.clone()) {
+            Ok(service) => {
+                tracing::info!(
+                    project_id = %config.fcm.project_id,
+                    high_priority = %config.fcm.high_priority,
+                    "FCM notification service initialized"
+                );
+                Arc::new(service)
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to create FCM notification service, falling back to mock");
+                Arc::new(MockNotificationService::new())
+            }
+        }
+    } else {
+        tracing::info!("Notification service initialized (mock mode - FCM disabled)");
+        Arc::new(MockNotificationService::new())
+    }
+}
+
+pub fn create_app(
+    config: Config,
+    pool: PgPool,
+    preflight: PreflightReport,
+    webhook_secrets: WebhookSecrets,
+    api_usage: Option<Arc<ApiUsageRecorder>>,
+    log_store: Option<Arc<LogStore>>,
+) -> Router {
     let config = Arc::new(config);
 
     // Create rate limiter if rate limiting is enabled (rate_limit_per_minute > 0)
@@ -75,6 +149,15 @@ pub fn create_app(config: Config, pool: PgPool) -> Router {
         None
     };
 
+    // Trip exports share the hourly export limit, counted per device
+    let trip_export_rate_limiter = if config.security.export_rate_limit_per_hour > 0 {
+        Some(Arc::new(ExportRateLimiterState::new(
+            config.security.export_rate_limit_per_hour,
+        )))
+    } else {
+        None
+    };
+
     // Create auth rate limiters for forgot-password and request-verification endpoints
     let forgot_password_rate_limiter = if config.security.forgot_password_rate_limit_per_hour > 0 {
         Some(Arc::new(AuthRateLimiterState::new(
@@ -110,26 +193,7 @@ pub fn create_app(config: Config, pool: PgPool) -> Router {
         None
     };
 
-    // Create notification service (FCM if enabled and configured, otherwise mock)
-    let notification_service: Arc<dyn NotificationService> = if config.fcm.enabled {
-        match FcmNotificationService::new(config.fcm.clone()) {
-            Ok(service) => {
-                tracing::info!(
-                    project_id = %config.fcm.project_id,
-                    high_priority = %config.fcm.high_priority,
-                    "FCM notification service initialized"
-                );
-                Arc::new(service)
-            }
-            Err(e) => {
-                tracing::error!(error = %e, "Failed to create FCM notification service, falling back to mock");
-                Arc::new(MockNotificationService::new())
-            }
-        }
-    } else {
-        tracing::info!("Notification service initialized (mock mode - FCM disabled)");
-        Arc::new(MockNotificationService::new())
-    };
+    let notification_service = create_notification_service(&config);
 
     // Create cookie helper for httpOnly authentication
     let cookie_helper = Arc::new(CookieHelper::new(
@@ -150,11 +214,18 @@ pub fn create_app(config: Config, pool: PgPool) -> Router {
         config: config.clone(),
         rate_limiter,
         export_rate_limiter,
+        trip_export_rate_limiter,
+        group_token_rate_limiter: Arc::new(GroupTokenRateLimiterState::new()),
         forgot_password_rate_limiter,
         request_verification_rate_limiter,
         map_matching_client,
         notification_service,
         cookie_helper,
+        preflight: Arc::new(preflight),
+        log_store,
+        device_agents: Arc::new(AgentRegistry::new()),
+        webhook_secrets,
+        api_usage,
     };
 
     // Build CORS layer based on configuration
@@ -184,11 +255,17 @@ pub fn create_app(config: Config, pool: PgPool) -> Router {
                 .iter()
                 .filter_map(|o| o.parse().ok())
                 .collect();
+            // Let cross-origin dashboards read the CSRF token issued on login
+            let exposed: Vec<_> =
+                axum::http::HeaderName::from_bytes(config.cookies.csrf_header_name.as_bytes())
+                    .into_iter()
+                    .collect();
             CorsLayer::new()
                 .allow_origin(AllowOrigin::list(origins))
                 .allow_methods(Any)
                 .allow_headers(Any)
                 .allow_credentials(true)
+                .expose_headers(exposed)
         }
     } else if config.security.cors_origins.is_empty()
         || config.security.cors_origins.iter().any(|o| o == "*")
@@ -221,7 +298,15 @@ pub fn create_app(config: Config, pool: PgPool) -> Router {
         .route("/api/v1/devices/:device_id", delete(devices::delete_device))
         // Location routes (v1)
         .route("/api/v1/locations", post(locations::upload_location))
-        .route("/api/v1/locations/batch", post(locations::upload_batch))
+        // Offline buffers can be large: accept gzip bodies and a higher decoded limit
+        .route(
+            "/api/v1/locations/batch",
+            post(locations::upload_batch).layer(
+                ServiceBuilder::new()
+                    .layer(DefaultBodyLimit::max(config.server.max_batch_body_size))
+                    .layer(RequestDecompressionLayer::new()),
+            ),
+        )
         // Device location history (v1)
         .route(
             "/api/v1/devices/:device_id/locations",
@@ -230,11 +315,30 @@ pub fn create_app(config: Config, pool: PgPool) -> Router {
         // Privacy routes (v1) - GDPR compliance
         .route(
             "/api/v1/devices/:device_id/data-export",
-            get(privacy::export_device_data),
+            get(privacy::export_device_data)
+                .layer(middleware::from_fn_with_state(
+                    RouteClass::Export,
+                    concurrency_limit,
+                ))
+                .layer(middleware::from_fn(export_statement_timeout)),
         )
         .route(
             "/api/v1/devices/:device_id/data",
             delete(privacy::delete_device_data),
+        )
+        // Battery and connectivity telemetry (v1)
+        .route(
+            "/api/v1/devices/:device_id/telemetry",
+            post(device_telemetry::upload_telemetry),
+        )
+        // Push token registration and rotation (v1)
+        .route(
+            "/api/v1/devices/:device_id/push-tokens",
+            get(device_push_tokens::list_push_tokens).post(device_push_tokens::register_push_token),
+        )
+        .route(
+            "/api/v1/devices/:device_id/push-tokens/:token_id",
+            delete(device_push_tokens::delete_push_token),
         );
 
     // Movement tracking routes (feature toggle: movement_tracking_enabled)
@@ -254,20 +358,62 @@ pub fn create_app(config: Config, pool: PgPool) -> Router {
         )
         // Trip routes (v1)
         .route("/api/v1/trips", post(trips::create_trip))
+        .route("/api/v1/trips/merge", post(trip_edits::merge_trips))
         .route("/api/v1/trips/:trip_id", patch(trips::update_trip_state))
         .route(
             "/api/v1/trips/:trip_id/movement-events",
             get(trips::get_trip_movement_events),
         )
         .route("/api/v1/trips/:trip_id/path", get(trips::get_trip_path))
+        .route(
+            "/api/v1/trips/:trip_id/export",
+            get(trips::export_trip)
+                .layer(middleware::from_fn_with_state(
+                    RouteClass::Export,
+                    concurrency_limit,
+                ))
+                .layer(middleware::from_fn(export_statement_timeout)),
+        )
+        .route(
+            "/api/v1/trips/:trip_id/replay",
+            get(trips::get_trip_replay).layer(middleware::from_fn_with_state(
+                RouteClass::Playback,
+                concurrency_limit,
+            )),
+        )
+        .route(
+            "/api/v1/trips/:trip_id/speed-violations",
+            get(trips::get_trip_speed_violations),
+        )
         .route(
             "/api/v1/trips/:trip_id/correct-path",
             post(trips::trigger_path_correction),
         )
+        .route("/api/v1/trips/:trip_id/split", post(trip_edits::split_trip))
+        .route(
+            "/api/v1/trips/:trip_id/purpose",
+            put(trip_purposes::set_trip_purpose),
+        )
+        .route(
+            "/api/v1/trips/:trip_id/edits",
+            get(trip_edits::list_trip_edits),
+        )
         .route(
             "/api/v1/devices/:device_id/trips",
             get(trips::get_device_trips),
         )
+        .route(
+            "/api/v1/devices/:device_id/commutes",
+            get(commutes::get_device_commutes),
+        )
+        .route(
+            "/api/v1/devices/:device_id/trip-rules",
+            get(trip_purposes::list_trip_rules).post(trip_purposes::create_trip_rule),
+        )
+        .route(
+            "/api/v1/devices/:device_id/trip-rules/:rule_id",
+            put(trip_purposes::update_trip_rule).delete(trip_purposes::delete_trip_rule),
+        )
         .route_layer(middleware::from_fn_with_state(
             state.clone(),
             require_movement_tracking,
@@ -276,6 +422,10 @@ pub fn create_app(config: Config, pool: PgPool) -> Router {
     // Geofence routes (feature toggle: geofences_enabled)
     let geofence_routes = Router::new()
         .route("/api/v1/geofences", post(geofences::create_geofence))
+        .route(
+            "/api/v1/geofences/bulk",
+            post(geofences::bulk_create_geofences),
+        )
         .route("/api/v1/geofences", get(geofences::list_geofences))
         .route(
             "/api/v1/geofences/:geofence_id",
@@ -334,6 +484,38 @@ pub fn create_app(config: Config, pool: PgPool) -> Router {
             "/api/v1/webhooks/:webhook_id",
             delete(webhooks::delete_webhook),
         )
+        .route(
+            "/api/v1/webhooks/:webhook_id/enable",
+            post(webhooks::enable_webhook),
+        )
+        .route(
+            "/api/v1/webhooks/:webhook_id/test",
+            post(webhooks::test_webhook),
+        )
+        .route(
+            "/api/v1/webhooks/:webhook_id/deliveries",
+            get(webhooks::list_deliveries),
+        )
+        .route(
+            "/api/v1/webhooks/:webhook_id/deliveries/:delivery_id/retry",
+            post(webhooks::retry_delivery),
+        )
+        .route(
+            "/api/v1/webhooks/:webhook_id/dead-letters",
+            get(webhooks::list_dead_letters).delete(webhooks::purge_dead_letters),
+        )
+        .route(
+            "/api/v1/webhooks/:webhook_id/dead-letters/:dead_letter_id/requeue",
+            post(webhooks::requeue_dead_letter),
+        )
+        .route(
+            "/api/v1/webhooks/:webhook_id/rotate-secret",
+            post(webhooks::rotate_secret),
+        )
+        .route(
+            "/api/v1/webhooks/:webhook_id/convert-to-user",
+            post(webhooks::convert_to_user_owned),
+        )
         .route_layer(middleware::from_fn_with_state(
             state.clone(),
             require_webhooks,
@@ -397,6 +579,11 @@ pub fn create_app(config: Config, pool: PgPool) -> Router {
         .route(
             "/api/admin/v1/migrations",
             get(admin_migrations::list_migrations),
+        )
+        // Password hash parameter report
+        .route(
+            "/api/admin/v1/password-hashes",
+            get(admin_password_hashes::get_password_hash_report),
         );
 
     // B2B/Organization admin routes (feature toggle: b2b_enabled)
@@ -416,6 +603,10 @@ pub fn create_app(config: Config, pool: PgPool) -> Router {
             "/api/admin/v1/organizations/:org_id/usage",
             get(organizations::get_organization_usage),
         )
+        .route(
+            "/api/admin/v1/organizations/:org_id/usage/simulate",
+            get(organizations::simulate_organization_plan),
+        )
         // Organization suspend/reactivate routes (Story AP-2.7)
         .route(
             "/api/admin/v1/organizations/:org_id/suspend",
@@ -474,6 +665,16 @@ pub fn create_app(config: Config, pool: PgPool) -> Router {
             "/api/admin/v1/organizations/:org_id/devices",
             fleet::router(),
         )
+        // Device command macros
+        .nest(
+            "/api/admin/v1/organizations/:org_id/command-macros",
+            device_command_macros::router(),
+        )
+        // Open device agent connections
+        .route(
+            "/api/admin/v1/organizations/:org_id/agent-connections",
+            get(device_agent::list_agent_connections),
+        )
         // Bulk import routes (Story 13.8)
         .nest(
             "/api/admin/v1/organizations/:org_id/devices/bulk",
@@ -484,6 +685,11 @@ pub fn create_app(config: Config, pool: PgPool) -> Router {
             "/api/admin/v1/organizations/:org_id/audit-logs",
             audit_logs::router(),
         )
+        // Organization data export routes
+        .nest(
+            "/api/admin/v1/organizations/:org_id/export",
+            organization_exports::router(),
+        )
         // Data subject request routes (AP-11.4-6)
         .nest(
             "/api/admin/v1/organizations/:org_id/data-requests",
@@ -497,7 +703,10 @@ pub fn create_app(config: Config, pool: PgPool) -> Router {
         // Dashboard metrics (Story 14.1)
         .route(
             "/api/admin/v1/organizations/:org_id/dashboard",
-            get(dashboard::get_dashboard_metrics),
+            get(dashboard::get_dashboard_metrics).layer(middleware::from_fn_with_state(
+                RouteClass::Analytics,
+                concurrency_limit,
+            )),
         )
         // Admin user management routes (Story 14.3)
         .nest(
@@ -544,6 +753,10 @@ pub fn create_app(config: Config, pool: PgPool) -> Router {
             "/api/admin/v1/organizations/:org_id/webhooks",
             post(org_webhooks::create_webhook).get(org_webhooks::list_webhooks),
         )
+        .route(
+            "/api/admin/v1/organizations/:org_id/webhooks/event-types",
+            get(org_webhooks::list_event_types),
+        )
         .route(
             "/api/admin/v1/organizations/:org_id/webhooks/:webhook_id",
             get(org_webhooks::get_webhook)
@@ -563,10 +776,23 @@ pub fn create_app(config: Config, pool: PgPool) -> Router {
             "/api/admin/v1/organizations/:org_id/webhooks/:webhook_id/deliveries/:delivery_id/retry",
             post(org_webhooks::retry_delivery),
         )
+        .route(
+            "/api/admin/v1/organizations/:org_id/webhooks/:webhook_id/dead-letters",
+            get(org_webhooks::list_dead_letters).delete(org_webhooks::purge_dead_letters),
+        )
+        .route(
+            "/api/admin/v1/organizations/:org_id/webhooks/:webhook_id/dead-letters/:dead_letter_id/requeue",
+            post(org_webhooks::requeue_dead_letter),
+        )
         .route(
             "/api/admin/v1/organizations/:org_id/webhooks/:webhook_id/stats",
             get(org_webhooks::get_webhook_stats),
         )
+        .route(
+            "/api/admin/v1/organizations/:org_id/webhooks/:webhook_id/client-certificate",
+            put(org_webhooks::set_client_certificate)
+                .delete(org_webhooks::delete_client_certificate),
+        )
         // Organization permissions routes (Story AP-1.1)
         .nest(
             "/api/admin/v1/organizations/:org_id/permissions",
@@ -579,6 +805,16 @@ pub fn create_app(config: Config, pool: PgPool) -> Router {
             "/api/admin/v1/organizations/:org_id/geofences",
             admin_geofences::router(),
         )
+        // Custom group roles assigned to members of the organization's groups
+        .nest(
+            "/api/admin/v1/organizations/:org_id/group-roles",
+            group_roles::router(),
+        )
+        // Geofence templates stamped onto devices and groups
+        .nest(
+            "/api/admin/v1/organizations/:org_id/geofence-templates",
+            geofence_templates::router(),
+        )
         // Admin location management routes (Story AP-6)
         .nest(
             "/api/admin/v1/organizations/:org_id/locations",
@@ -609,6 +845,11 @@ pub fn create_app(config: Config, pool: PgPool) -> Router {
             "/api/admin/v1/organizations/:org_id/devices/:device_id/app-usage",
             app_usage::device_router(),
         )
+        // Effective settings diff between devices or against a policy
+        .nest(
+            "/api/admin/v1/organizations/:org_id/devices/:device_id/settings-diff",
+            settings_diff::router(),
+        )
         // App usage routes - organization level analytics (Story AP-8.7)
         .nest(
             "/api/admin/v1/organizations/:org_id/app-usage",
@@ -622,8 +863,16 @@ pub fn create_app(config: Config, pool: PgPool) -> Router {
         // Report generation routes (Story AP-10.4, AP-10.5, AP-10.6, AP-10.7)
         .nest(
             "/api/admin/v1/organizations/:org_id/reports",
-            analytics::reports_router(),
+            analytics::reports_router().route_layer(middleware::from_fn_with_state(
+                state.clone(),
+                require_blob_storage,
+            )),
         )
+        // Observers get imprecise locations from every organization route
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            mask_observer_locations,
+        ))
         .route_layer(middleware::from_fn_with_state(state.clone(), require_b2b));
 
     // Admin routes (require admin API key)
@@ -643,6 +892,18 @@ pub fn create_app(config: Config, pool: PgPool) -> Router {
     let system_role_routes =
         Router::new().nest("/api/admin/v1/system-roles", system_roles::router());
 
+    // Effective access inspector (require JWT auth with super_admin or support role)
+    let effective_access_routes = Router::new().nest(
+        "/api/admin/v1/users/:user_id/effective-access",
+        effective_access::router(),
+    );
+
+    // Tenant log export (require JWT auth with super_admin or support role)
+    let tenant_log_routes = Router::new().nest(
+        "/api/admin/v1/organizations/:org_id/logs",
+        tenant_logs::router(),
+    );
+
     // System configuration routes (require JWT auth with super_admin role)
     // AP-9: System Configuration endpoints
     let system_config_routes = Router::new().nest("/api/admin/v1/system", system_config::router());
@@ -669,6 +930,7 @@ pub fn create_app(config: Config, pool: PgPool) -> Router {
         .route("/api/v1/auth/oauth", post(auth::oauth_login))
         .route("/api/v1/auth/refresh", post(auth::refresh))
         .route("/api/v1/auth/logout", post(auth::logout))
+        .route("/api/v1/auth/csrf", get(auth::issue_csrf_token))
         .route("/api/v1/auth/reset-password", post(auth::reset_password))
         .route("/api/v1/auth/verify-email", post(auth::verify_email));
 
@@ -714,6 +976,45 @@ pub fn create_app(config: Config, pool: PgPool) -> Router {
     let user_routes = Router::new()
         .route("/api/v1/users/me", get(users::get_current_user))
         .route("/api/v1/users/me", put(users::update_current_user))
+        // Multi-device journeys (feature toggle: movement_tracking_enabled)
+        .route(
+            "/api/v1/users/me/journeys",
+            get(trips::get_my_journeys).layer(middleware::from_fn_with_state(
+                state.clone(),
+                require_movement_tracking,
+            )),
+        )
+        // Trip share links, managed by the owner of the trip's device
+        .route(
+            "/api/v1/trips/:trip_id/share",
+            post(trip_shares::create_trip_share).layer(middleware::from_fn_with_state(
+                state.clone(),
+                require_movement_tracking,
+            )),
+        )
+        .route(
+            "/api/v1/trips/:trip_id/shares",
+            get(trip_shares::list_trip_shares).layer(middleware::from_fn_with_state(
+                state.clone(),
+                require_movement_tracking,
+            )),
+        )
+        .route(
+            "/api/v1/trips/:trip_id/shares/:share_id",
+            delete(trip_shares::revoke_trip_share).layer(middleware::from_fn_with_state(
+                state.clone(),
+                require_movement_tracking,
+            )),
+        )
+        // Privacy zones (location fuzzing for group sharing)
+        .route(
+            "/api/v1/privacy-zones",
+            get(privacy_zones::list_privacy_zones).post(privacy_zones::create_privacy_zone),
+        )
+        .route(
+            "/api/v1/privacy-zones/:zone_id",
+            put(privacy_zones::update_privacy_zone).delete(privacy_zones::delete_privacy_zone),
+        )
         // Registration group status endpoint (UGM-1.2)
         .route(
             "/api/v1/devices/me/registration-group",
@@ -721,6 +1022,26 @@ pub fn create_app(config: Config, pool: PgPool) -> Router {
         )
         // User's linked devices endpoint (UGM-1.3)
         .route("/api/v1/devices/me", get(devices::get_my_devices))
+        // Location history import (Google Takeout)
+        .route(
+            "/api/v1/devices/:device_id/location-imports",
+            post(location_imports::create_location_import)
+                .layer(
+                    ServiceBuilder::new()
+                        .layer(DefaultBodyLimit::max(
+                            config.location_imports.max_upload_size,
+                        ))
+                        .layer(RequestDecompressionLayer::new()),
+                )
+                .route_layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    require_blob_storage,
+                )),
+        )
+        .route(
+            "/api/v1/devices/:device_id/location-imports/:job_id",
+            get(location_imports::get_location_import),
+        )
         // Device binding endpoints
         .route(
             "/api/v1/users/:user_id/devices/:device_id/link",
@@ -807,6 +1128,24 @@ pub fn create_app(config: Config, pool: PgPool) -> Router {
             "/api/v1/groups/:group_id/members/:user_id/role",
             put(groups::update_member_role),
         )
+        // Custom group roles with granular permissions
+        .route(
+            "/api/v1/groups/:group_id/members/:user_id/custom-role",
+            put(group_roles::assign_member_custom_role),
+        )
+        .route(
+            "/api/v1/groups/:group_id/members/:user_id/permissions",
+            get(group_roles::get_member_permissions),
+        )
+        // Member and device nicknames
+        .route(
+            "/api/v1/groups/:group_id/members/:user_id/nickname",
+            put(groups::set_member_nickname),
+        )
+        .route(
+            "/api/v1/groups/:group_id/devices/:device_id/nickname",
+            put(groups::set_device_nickname),
+        )
         // Invite management (Story 11.4)
         .route(
             "/api/v1/groups/:group_id/invites",
@@ -820,6 +1159,17 @@ pub fn create_app(config: Config, pool: PgPool) -> Router {
             "/api/v1/groups/:group_id/invites/:invite_id",
             delete(invites::revoke_invite),
         )
+        // Invite QR codes; the segment holds the invite code (axum requires
+        // one parameter name per path position)
+        .route(
+            "/api/v1/groups/:group_id/invites/:invite_id/qr",
+            get(invites::get_invite_qr),
+        )
+        // Email invitations
+        .route(
+            "/api/v1/groups/:group_id/invites/email",
+            post(invites::create_email_invite),
+        )
         // Join group with invite code (Story 11.5)
         .route("/api/v1/groups/join", post(groups::join_group))
         // Migrate registration group to authenticated group (Story UGM-2.2)
@@ -838,21 +1188,131 @@ pub fn create_app(config: Config, pool: PgPool) -> Router {
             "/api/v1/groups/:group_id/devices/members",
             get(groups::list_group_devices),
         )
+        .route(
+            "/api/v1/groups/:group_id/devices/nearby",
+            get(groups::list_nearby_group_devices),
+        )
         .route(
             "/api/v1/groups/:group_id/devices/:device_id",
             delete(groups::remove_device_from_group),
         )
         // Ownership transfer (Story 11.6)
+        .route(
+            "/api/v1/groups/:group_id/events",
+            get(groups::list_group_events),
+        )
+        // Group announcements and check-ins
+        .route(
+            "/api/v1/groups/:group_id/messages",
+            get(group_messages::list_group_messages).post(group_messages::post_group_message),
+        )
+        // Per-group notification preferences
+        .route(
+            "/api/v1/groups/:group_id/notification-preferences",
+            get(group_notification_preferences::get_notification_preferences)
+                .put(group_notification_preferences::update_notification_preferences),
+        )
+        // Per-group location retention
+        .route(
+            "/api/v1/groups/:group_id/retention",
+            get(groups::get_group_retention).put(groups::update_group_retention),
+        )
+        // Typed group settings
+        .route(
+            "/api/v1/groups/:group_id/settings",
+            get(groups::get_group_settings).put(groups::update_group_settings),
+        )
+        // Time-boxed location sharing
+        .route(
+            "/api/v1/groups/:group_id/sharing-window",
+            get(location_sharing::get_sharing_window)
+                .post(location_sharing::start_sharing_window)
+                .patch(location_sharing::extend_sharing_window)
+                .delete(location_sharing::stop_sharing_window),
+        )
+        // Recurring location sharing schedules
+        .route(
+            "/api/v1/groups/:group_id/sharing-schedule",
+            get(location_sharing::get_sharing_schedule)
+                .put(location_sharing::set_sharing_schedule)
+                .delete(location_sharing::delete_sharing_schedule),
+        )
+        // Group data export
+        .route(
+            "/api/v1/groups/:group_id/export",
+            post(group_exports::create_group_export)
+                .layer(middleware::from_fn_with_state(
+                    RouteClass::Export,
+                    concurrency_limit,
+                ))
+                .layer(middleware::from_fn(export_statement_timeout)),
+        )
+        .route(
+            "/api/v1/groups/:group_id/export/:job_id",
+            get(group_exports::get_group_export),
+        )
+        .route(
+            "/api/v1/groups/:group_id/export/:job_id/download",
+            get(group_exports::download_group_export),
+        )
+        // Nested groups
+        .route(
+            "/api/v1/groups/:group_id/parent",
+            put(group_hierarchy::set_parent_group),
+        )
+        .route(
+            "/api/v1/groups/:group_id/subgroups",
+            get(group_hierarchy::list_subgroups),
+        )
         .route(
             "/api/v1/groups/:group_id/transfer",
             post(groups::transfer_ownership),
         )
+        // Merge another group into this one
+        .route("/api/v1/groups/:group_id/merge", post(groups::merge_group))
         // Unlock requests for group (Story 12.6)
         .route(
             "/api/v1/groups/:group_id/unlock-requests",
             get(device_settings::list_unlock_requests),
+        )
+        // Group API tokens for read-only integrations
+        .route(
+            "/api/v1/groups/:group_id/api-tokens",
+            get(group_api_tokens::list_group_api_tokens)
+                .post(group_api_tokens::create_group_api_token),
+        )
+        .route(
+            "/api/v1/groups/:group_id/api-tokens/:token_id",
+            delete(group_api_tokens::revoke_group_api_token),
+        )
+        // Authenticated with a group API token (X-Group-Token)
+        .route(
+            "/api/v1/integrations/group/devices",
+            get(group_api_tokens::get_group_integration_devices),
         );
 
+    // Self-service organization routes (require JWT authentication)
+    // Feature toggles: b2b_enabled and self_service_orgs_enabled
+    let self_service_org_routes = Router::new()
+        .route(
+            "/api/v1/organizations",
+            post(organizations::create_trial_organization),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_self_service_orgs,
+        ))
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_b2b));
+
+    // Organization ownership transfer routes (require JWT authentication)
+    // Feature toggle: b2b_enabled
+    let org_ownership_transfer_routes = Router::new()
+        .nest(
+            "/api/v1/organizations/:org_id/ownership-transfer",
+            org_ownership_transfer::router(),
+        )
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_b2b));
+
     // Public routes (no authentication required)
     let public_routes = Router::new()
         .route("/api/health", get(health::health_check))
@@ -876,11 +1336,24 @@ pub fn create_app(config: Config, pool: PgPool) -> Router {
     let b2b_public_routes = Router::new()
         // Device enrollment (Story 13.5) - token is the auth, requires B2B
         .route("/api/v1/devices/enroll", post(enrollment::enroll_device))
+        // Device agent channel - device token is the auth, requires B2B
+        .route("/api/v1/devices/agent", get(device_agent::connect_agent))
         .layer(axum::middleware::from_fn_with_state(
             state.clone(),
             require_b2b,
         ));
 
+    // Shared trip view (no auth, the share token is the credential)
+    let shared_trip_routes = Router::new()
+        .route(
+            "/api/v1/shared/trips/:token",
+            get(trip_shares::get_shared_trip),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_movement_tracking,
+        ));
+
     // OpenAPI documentation routes (public, no auth)
     let openapi_routes = Router::new()
         .route("/api/docs", get(openapi::swagger_ui_redirect))
@@ -892,16 +1365,31 @@ pub fn create_app(config: Config, pool: PgPool) -> Router {
     let mut app = Router::new()
         .merge(public_routes)
         .merge(b2b_public_routes)
+        .merge(shared_trip_routes)
         .merge(auth_routes)
         .merge(user_routes)
         .merge(group_routes)
+        .merge(self_service_org_routes)
+        .merge(org_ownership_transfer_routes)
         .merge(openapi_routes)
         .merge(protected_routes)
         .merge(admin_routes)
         .merge(system_role_routes)
+        .merge(effective_access_routes)
+        .merge(tenant_log_routes)
         .merge(system_config_routes)
         .merge(legacy_routes);
 
+    // Fault injection for resilience testing (only built with the
+    // `fault-injection` feature; require JWT auth with super_admin role)
+    #[cfg(feature = "fault-injection")]
+    {
+        app = app.nest(
+            "/api/admin/v1/fault-injection",
+            crate::routes::fault_injection::router(),
+        );
+    }
+
     // Add frontend serving as fallback if enabled
     // This must be added after API routes so they take precedence
     if config.frontend.enabled {
@@ -915,16 +1403,37 @@ pub fn create_app(config: Config, pool: PgPool) -> Router {
         app = app.fallback(frontend::serve_frontend);
     }
 
+    let statement_timeouts = StatementTimeouts {
+        interactive: Duration::from_millis(config.database.statement_timeout_ms),
+        export: Duration::from_millis(config.database.export_statement_timeout_ms),
+    };
+
     // Global middleware (order matters: bottom layers run first)
-    app.layer(middleware::from_fn(security_headers_middleware)) // Security headers
-        .layer(CompressionLayer::new())
-        .layer(TimeoutLayer::new(Duration::from_secs(
-            config.server.request_timeout_secs,
-        )))
-        .layer(middleware::from_fn(metrics_middleware)) // Prometheus metrics
-        .layer(TraceLayer::new_for_http())
-        .layer(middleware::from_fn(version_check)) // Client version compatibility check
-        .layer(middleware::from_fn(trace_id)) // Request ID and logging
-        .layer(cors)
-        .with_state(state)
+    let mut app = app.layer(middleware::from_fn_with_state(
+        statement_timeouts,
+        statement_timeout,
+    )); // Per-request DB statement timeout
+    if config.concurrency.enabled {
+        // Limits for the routes wrapped in the concurrency_limit middleware
+        app = app.layer(Extension(ConcurrencyLimits::new(&config.concurrency)));
+    }
+    app.layer(middleware::from_fn_with_state(
+        state.cookie_helper.clone(),
+        csrf_protection,
+    )) // CSRF check for cookie-authenticated requests
+    .layer(middleware::from_fn(security_headers_middleware)) // Security headers
+    .layer(CompressionLayer::new())
+    .layer(TimeoutLayer::new(Duration::from_secs(
+        config.server.request_timeout_secs,
+    )))
+    .layer(middleware::from_fn(metrics_middleware)) // Prometheus metrics
+    .layer(middleware::from_fn_with_state(
+        state.clone(),
+        api_usage_middleware,
+    )) // Persisted API usage rollups
+    .layer(TraceLayer::new_for_http())
+    .layer(middleware::from_fn(version_check)) // Client version compatibility check
+    .layer(middleware::from_fn(trace_id)) // Request ID and logging
+    .layer(cors)
+    .with_state(state)
 }
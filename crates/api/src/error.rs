@@ -41,6 +41,9 @@ pub enum ApiError {
 
     #[error("Service unavailable: {0}")]
     ServiceUnavailable(String),
+
+    #[error("Service unavailable: {message}")]
+    ServiceUnavailableWithRetry { message: String, retry_after: u64 },
 }
 
 #[derive(Debug, Serialize)]
@@ -109,6 +112,15 @@ impl IntoResponse for ApiError {
                 msg.clone(),
                 None,
             ),
+            ApiError::ServiceUnavailableWithRetry {
+                message,
+                retry_after,
+            } => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "service_unavailable",
+                message.clone(),
+                Some((header::RETRY_AFTER, retry_after.to_string())),
+            ),
         };
 
         let body = ErrorBody {
@@ -260,6 +272,17 @@ mod tests {
         assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
     }
 
+    #[test]
+    fn test_api_error_service_unavailable_with_retry() {
+        let error = ApiError::ServiceUnavailableWithRetry {
+            message: "busy".to_string(),
+            retry_after: 5,
+        };
+        let response = error.into_response();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers().get(header::RETRY_AFTER).unwrap(), "5");
+    }
+
     #[test]
     fn test_api_error_payload_too_large() {
         let error = ApiError::PayloadTooLarge("Request exceeds maximum size".to_string());
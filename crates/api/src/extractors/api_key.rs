@@ -4,6 +4,7 @@
 
 use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
 use sqlx::PgPool;
+use uuid::Uuid;
 
 use crate::app::AppState;
 use crate::error::ApiError;
@@ -23,6 +24,8 @@ pub struct ApiKeyAuth {
     pub key_prefix: String,
     /// Whether this is an admin API key.
     pub is_admin: bool,
+    /// Organization the key is bound to, if any.
+    pub organization_id: Option<Uuid>,
 }
 
 impl ApiKeyAuth {
@@ -77,6 +80,7 @@ impl ApiKeyAuth {
             api_key_id: key.id,
             key_prefix: key.key_prefix,
             is_admin: key.is_admin,
+            organization_id: key.organization_id,
         })
     }
 }
@@ -205,6 +209,7 @@ mod tests {
             api_key_id: 1,
             key_prefix: "pm_aBcDe".to_string(),
             is_admin: false,
+            organization_id: None,
         };
         assert_eq!(auth.api_key_id, 1);
         assert_eq!(auth.key_prefix, "pm_aBcDe");
@@ -217,6 +222,7 @@ mod tests {
             api_key_id: 42,
             key_prefix: "pm_Admin".to_string(),
             is_admin: true,
+            organization_id: None,
         };
         assert_eq!(auth.api_key_id, 42);
         assert!(auth.is_admin);
@@ -228,6 +234,7 @@ mod tests {
             api_key_id: 1,
             key_prefix: "pm_test1".to_string(),
             is_admin: false,
+            organization_id: None,
         };
         let cloned = auth.clone();
         assert_eq!(cloned.api_key_id, auth.api_key_id);
@@ -241,6 +248,7 @@ mod tests {
             api_key_id: 1,
             key_prefix: "pm_debug".to_string(),
             is_admin: false,
+            organization_id: None,
         };
         let debug_str = format!("{:?}", auth);
         assert!(debug_str.contains("api_key_id"));
@@ -255,6 +263,7 @@ mod tests {
             api_key_id: i64::MAX,
             key_prefix: "pm_maxid".to_string(),
             is_admin: true,
+            organization_id: None,
         };
         assert_eq!(auth.api_key_id, i64::MAX);
     }
@@ -265,6 +274,7 @@ mod tests {
             api_key_id: 0,
             key_prefix: "pm_zeroid".to_string(),
             is_admin: false,
+            organization_id: None,
         };
         assert_eq!(auth.api_key_id, 0);
     }
@@ -279,6 +289,7 @@ mod tests {
             api_key_id: 1,
             key_prefix: "pm_aBcDe".to_string(),
             is_admin: true,
+            organization_id: None,
         };
         let optional = OptionalApiKeyAuth(Some(auth));
         assert!(optional.0.is_some());
@@ -297,6 +308,7 @@ mod tests {
             api_key_id: 5,
             key_prefix: "pm_clone".to_string(),
             is_admin: false,
+            organization_id: None,
         };
         let optional = OptionalApiKeyAuth(Some(auth));
         let cloned = optional.clone();
@@ -325,6 +337,7 @@ mod tests {
             api_key_id: 1,
             key_prefix: "pm_test".to_string(),
             is_admin: false,
+            organization_id: None,
         };
         let optional = OptionalApiKeyAuth(Some(auth));
         let debug_str = format!("{:?}", optional);
@@ -352,6 +365,7 @@ mod tests {
                 api_key_id: 1,
                 key_prefix: prefix.to_string(),
                 is_admin: false,
+                organization_id: None,
             };
             assert!(auth.key_prefix.starts_with("pm_"));
         }
@@ -364,6 +378,7 @@ mod tests {
             api_key_id: 1,
             key_prefix: "pm_üñîcödé".to_string(),
             is_admin: false,
+            organization_id: None,
         };
         assert!(auth.key_prefix.starts_with("pm_"));
     }
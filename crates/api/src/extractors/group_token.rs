@@ -0,0 +1,97 @@
+//! Group API token authentication extractor.
+//!
+//! Group API tokens authenticate read-only integrations of a single group.
+//! They are sent in the `X-Group-Token` header and are subject to the
+//! token's own rate limit.
+
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+use chrono::Utc;
+use domain::models::GROUP_API_TOKEN_PREFIX;
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::error::ApiError;
+use persistence::repositories::GroupApiTokenRepository;
+use shared::crypto::sha256_hex;
+
+/// Header carrying a group API token.
+pub const GROUP_TOKEN_HEADER: &str = "X-Group-Token";
+
+/// Authenticated group API token.
+#[derive(Debug, Clone)]
+pub struct GroupTokenAuth {
+    /// ID of the authenticated token.
+    pub token_id: Uuid,
+    /// Group the token is bound to.
+    pub group_id: Uuid,
+    /// Token prefix for identification (e.g., "pm_grp_aBcD1234").
+    pub token_prefix: String,
+}
+
+impl GroupTokenAuth {
+    /// Validates a group API token and applies its rate limit.
+    pub async fn validate(state: &AppState, token: &str) -> Result<Self, ApiError> {
+        let invalid = || ApiError::Unauthorized("Invalid or missing group token".to_string());
+        if !token.starts_with(GROUP_API_TOKEN_PREFIX) {
+            return Err(invalid());
+        }
+
+        let repo = GroupApiTokenRepository::new(state.pool.clone());
+        let entity = repo
+            .find_by_token_hash(&sha256_hex(token))
+            .await
+            .map_err(|e| {
+                tracing::error!("Database error during group token lookup: {}", e);
+                ApiError::Internal("Authentication service unavailable".to_string())
+            })?
+            .ok_or_else(invalid)?;
+        if !entity.is_active_at(Utc::now()) {
+            return Err(invalid());
+        }
+
+        if let Err(retry_after) = state
+            .group_token_rate_limiter
+            .check(entity.id, entity.rate_limit_per_minute as u32)
+        {
+            return Err(ApiError::RateLimitedWithRetry {
+                message: format!(
+                    "Rate limit of {} requests/minute exceeded",
+                    entity.rate_limit_per_minute
+                ),
+                retry_after,
+            });
+        }
+
+        // Update last_used_at asynchronously (fire and forget)
+        let token_id = entity.id;
+        tokio::spawn(async move {
+            if let Err(e) = repo.update_last_used(token_id).await {
+                tracing::warn!("Failed to update group token last_used_at: {}", e);
+            }
+        });
+
+        Ok(GroupTokenAuth {
+            token_id: entity.id,
+            group_id: entity.group_id,
+            token_prefix: entity.token_prefix,
+        })
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for GroupTokenAuth {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let token = parts
+            .headers
+            .get(GROUP_TOKEN_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| ApiError::Unauthorized("Invalid or missing group token".to_string()))?;
+
+        Self::validate(state, token).await
+    }
+}
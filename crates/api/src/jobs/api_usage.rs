@@ -0,0 +1,103 @@
+//! API usage rollup background jobs.
+//!
+//! Flushes the in-memory request counters into the daily rollups and prunes
+//! rollups past their retention.
+
+use chrono::{Months, Utc};
+use persistence::repositories::AnalyticsRepository;
+use sqlx::PgPool;
+use std::sync::Arc;
+use tracing::{debug, info};
+
+use crate::services::api_usage::ApiUsageRecorder;
+
+use super::scheduler::{Job, JobFrequency};
+
+/// Background job to persist collected API usage.
+pub struct ApiUsageRollupJob {
+    pool: PgPool,
+    recorder: Arc<ApiUsageRecorder>,
+}
+
+impl ApiUsageRollupJob {
+    /// Create a new rollup job flushing `recorder`.
+    pub fn new(pool: PgPool, recorder: Arc<ApiUsageRecorder>) -> Self {
+        Self { pool, recorder }
+    }
+}
+
+#[async_trait::async_trait]
+impl Job for ApiUsageRollupJob {
+    fn name(&self) -> &'static str {
+        "api_usage_rollup"
+    }
+
+    fn frequency(&self) -> JobFrequency {
+        JobFrequency::Minutes(1)
+    }
+
+    async fn execute(&self) -> Result<(), String> {
+        let written = self
+            .recorder
+            .flush(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to flush API usage: {}", e))?;
+
+        if written > 0 {
+            debug!(rollups = written, "Flushed API usage rollups");
+        }
+
+        Ok(())
+    }
+}
+
+/// Background job to delete API usage rollups past retention.
+pub struct ApiUsageCleanupJob {
+    pool: PgPool,
+    retention_months: u32,
+}
+
+impl ApiUsageCleanupJob {
+    /// Create a new cleanup job.
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    /// * `retention_months` - Number of months to retain daily rollups
+    pub fn new(pool: PgPool, retention_months: u32) -> Self {
+        Self {
+            pool,
+            retention_months,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Job for ApiUsageCleanupJob {
+    fn name(&self) -> &'static str {
+        "api_usage_cleanup"
+    }
+
+    fn frequency(&self) -> JobFrequency {
+        JobFrequency::Daily
+    }
+
+    async fn execute(&self) -> Result<(), String> {
+        let today = Utc::now().date_naive();
+        let cutoff = today
+            .checked_sub_months(Months::new(self.retention_months))
+            .unwrap_or(today);
+
+        let deleted = AnalyticsRepository::new(self.pool.clone())
+            .delete_api_usage_before(cutoff)
+            .await
+            .map_err(|e| format!("Failed to clean up API usage rollups: {}", e))?;
+
+        info!(
+            deleted = deleted,
+            retention_months = self.retention_months,
+            "Cleaned up old API usage rollups"
+        );
+
+        Ok(())
+    }
+}
@@ -5,7 +5,11 @@ use tracing::info;
 
 use super::scheduler::{Job, JobFrequency};
 
-/// Background job to clean up old location records.
+/// Tables whose rows follow the location retention settings. Each has `id`,
+/// `device_id` and an indexed `created_at` column.
+const RETAINED_TABLES: [&str; 3] = ["locations", "device_telemetry", "location_quarantine"];
+
+/// Background job to clean up old location, telemetry and quarantine records.
 pub struct CleanupLocationsJob {
     pool: PgPool,
     retention_days: u32,
@@ -17,7 +21,8 @@ impl CleanupLocationsJob {
     ///
     /// # Arguments
     /// * `pool` - Database connection pool
-    /// * `retention_days` - Number of days to retain locations
+    /// * `retention_days` - Default number of days to retain locations;
+    ///   groups may override it via `groups.location_retention_days`
     pub fn new(pool: PgPool, retention_days: u32) -> Self {
         Self {
             pool,
@@ -26,23 +31,43 @@ impl CleanupLocationsJob {
         }
     }
 
-    /// Delete old locations in batches to avoid long locks.
-    async fn delete_old_locations(&self) -> Result<u64, sqlx::Error> {
+    /// Delete old rows of a retained table in batches to avoid long locks.
+    ///
+    /// A device's retention is the longest of its groups' overrides, falling
+    /// back to the global value for groups without an override and for
+    /// devices outside any group. Only rows older than the shortest
+    /// configured retention are considered, which keeps the scan on the
+    /// `created_at` index.
+    async fn delete_old_rows(&self, table: &str) -> Result<u64, sqlx::Error> {
         let mut total_deleted: u64 = 0;
 
         loop {
             // Delete in batches using a CTE with LIMIT
-            let result = sqlx::query(
+            let result = sqlx::query(&format!(
                 r#"
-                WITH to_delete AS (
-                    SELECT id FROM locations
-                    WHERE created_at < NOW() - ($1 || ' days')::INTERVAL
+                WITH device_retention AS (
+                    SELECT dgm.device_id,
+                           MAX(COALESCE(g.location_retention_days, $1)) AS retention_days
+                    FROM device_group_memberships dgm
+                    JOIN groups g ON g.id = dgm.group_id
+                    GROUP BY dgm.device_id
+                ),
+                min_retention AS (
+                    SELECT LEAST($1, COALESCE(MIN(location_retention_days), $1)) AS days
+                    FROM groups
+                ),
+                to_delete AS (
+                    SELECT l.id FROM {table} l
+                    LEFT JOIN device_retention r ON r.device_id = l.device_id
+                    WHERE l.created_at < NOW() - make_interval(days => (SELECT days FROM min_retention))
+                      AND l.created_at < NOW() - make_interval(days => COALESCE(r.retention_days, $1))
                     LIMIT $2
                 )
-                DELETE FROM locations
+                DELETE FROM {table}
                 WHERE id IN (SELECT id FROM to_delete)
                 "#,
-            )
+                table = table
+            ))
             .bind(self.retention_days as i32)
             .bind(self.batch_size)
             .execute(&self.pool)
@@ -89,17 +114,20 @@ impl Job for CleanupLocationsJob {
     }
 
     async fn execute(&self) -> Result<(), String> {
-        // Clean up old locations
-        let locations_deleted = self
-            .delete_old_locations()
-            .await
-            .map_err(|e| format!("Failed to delete old locations: {}", e))?;
+        // Clean up old locations, telemetry and quarantined locations
+        for table in RETAINED_TABLES {
+            let deleted = self
+                .delete_old_rows(table)
+                .await
+                .map_err(|e| format!("Failed to delete old rows from {}: {}", table, e))?;
 
-        info!(
-            deleted = locations_deleted,
-            retention_days = self.retention_days,
-            "Cleaned up old locations"
-        );
+            info!(
+                table = table,
+                deleted = deleted,
+                default_retention_days = self.retention_days,
+                "Cleaned up old rows"
+            );
+        }
 
         // Clean up expired idempotency keys
         let keys_deleted = self
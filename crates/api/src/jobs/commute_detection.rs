@@ -0,0 +1,109 @@
+//! Commute detection background job.
+//!
+//! Detects the commutes of every device with recent completed trips and
+//! replaces the stored ones. Commutes of devices without recent trips are
+//! deleted.
+
+use chrono::{DateTime, Duration, Utc};
+use domain::models::commute::COMMUTE_LOOKBACK_DAYS;
+use domain::services::{detect_commutes, CommuteDetectionConfig, DetectedCommute};
+use persistence::repositories::{CommuteInput, CommuteRepository, TripRepository};
+use sqlx::PgPool;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::scheduler::{Job, JobFrequency};
+
+/// Background job to detect recurring trips.
+pub struct CommuteDetectionJob {
+    pool: PgPool,
+}
+
+impl CommuteDetectionJob {
+    /// Create a new commute detection job.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn detect_for_device(&self, device_id: Uuid, since: i64) -> Result<usize, sqlx::Error> {
+        let trips: Vec<_> = TripRepository::new(self.pool.clone())
+            .get_completed_trips_since(device_id, since)
+            .await?
+            .into_iter()
+            .map(|t| t.into_domain())
+            .collect();
+
+        let commutes: Vec<CommuteInput> =
+            detect_commutes(&trips, &CommuteDetectionConfig::default())
+                .iter()
+                .filter_map(commute_input)
+                .collect();
+        CommuteRepository::new(self.pool.clone())
+            .replace_for_device(device_id, &commutes)
+            .await?;
+        Ok(commutes.len())
+    }
+}
+
+#[async_trait::async_trait]
+impl Job for CommuteDetectionJob {
+    fn name(&self) -> &'static str {
+        "commute_detection"
+    }
+
+    fn frequency(&self) -> JobFrequency {
+        JobFrequency::Daily
+    }
+
+    async fn execute(&self) -> Result<(), String> {
+        let started = Utc::now();
+        let since = (started - Duration::days(COMMUTE_LOOKBACK_DAYS)).timestamp_millis();
+        let device_ids = TripRepository::new(self.pool.clone())
+            .find_device_ids_with_completed_trips_since(since)
+            .await
+            .map_err(|e| format!("Failed to load devices with trips: {}", e))?;
+
+        let mut detected = 0;
+        for device_id in &device_ids {
+            match self.detect_for_device(*device_id, since).await {
+                Ok(count) => detected += count,
+                // One device's failure must not hold up the others.
+                Err(e) => warn!(device_id = %device_id, error = %e, "Commute detection failed"),
+            }
+        }
+
+        let stale = CommuteRepository::new(self.pool.clone())
+            .delete_detected_before(started)
+            .await
+            .map_err(|e| format!("Failed to delete stale commutes: {}", e))?;
+
+        info!(
+            devices = device_ids.len(),
+            commutes = detected,
+            stale = stale,
+            "Detected commutes"
+        );
+
+        Ok(())
+    }
+}
+
+fn commute_input(commute: &DetectedCommute) -> Option<CommuteInput> {
+    Some(CommuteInput {
+        origin_latitude: commute.origin_latitude,
+        origin_longitude: commute.origin_longitude,
+        destination_latitude: commute.destination_latitude,
+        destination_longitude: commute.destination_longitude,
+        trip_count: commute.trip_count as i32,
+        typical_departure_minute: commute.typical_departure_minute as i32,
+        departure_spread_minutes: commute.departure_spread_minutes as i32,
+        typical_duration_seconds: commute.typical_duration_seconds,
+        weekdays: commute
+            .weekdays
+            .iter()
+            .map(|d| d.number_from_monday() as i16)
+            .collect(),
+        first_departure_at: DateTime::from_timestamp_millis(commute.first_departure)?,
+        last_departure_at: DateTime::from_timestamp_millis(commute.last_departure)?,
+    })
+}
@@ -0,0 +1,69 @@
+//! Export cleanup background job.
+//!
+//! Deletes expired organization and group export jobs together with their
+//! archives.
+
+use domain::models::ExportJobKind;
+use persistence::repositories::ExportJobRepository;
+use sqlx::PgPool;
+use std::io;
+use std::path::PathBuf;
+use tracing::{info, warn};
+
+use super::scheduler::{Job, JobFrequency};
+
+/// Background job to remove expired export archives.
+pub struct ExportCleanupJob {
+    pool: PgPool,
+    reports_dir: PathBuf,
+}
+
+impl ExportCleanupJob {
+    /// Create a new export cleanup job.
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    /// * `reports_dir` - Directory where export archives are stored
+    pub fn new(pool: PgPool, reports_dir: PathBuf) -> Self {
+        Self { pool, reports_dir }
+    }
+}
+
+#[async_trait::async_trait]
+impl Job for ExportCleanupJob {
+    fn name(&self) -> &'static str {
+        "export_cleanup"
+    }
+
+    fn frequency(&self) -> JobFrequency {
+        JobFrequency::Hourly
+    }
+
+    async fn execute(&self) -> Result<(), String> {
+        let repo = ExportJobRepository::new(self.pool.clone());
+        for kind in [ExportJobKind::Organization, ExportJobKind::Group] {
+            let expired = repo
+                .delete_expired(kind)
+                .await
+                .map_err(|e| format!("Failed to cleanup {} exports: {}", kind, e))?;
+
+            let mut deleted = 0u32;
+            for file_path in expired.iter().filter_map(|job| job.file_path.as_ref()) {
+                match std::fs::remove_file(self.reports_dir.join(file_path)) {
+                    Ok(()) => deleted += 1,
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                    Err(e) => warn!(
+                        file_path = %file_path,
+                        error = %e,
+                        "Failed to delete expired export archive"
+                    ),
+                }
+            }
+            if deleted > 0 {
+                info!(kind = %kind, deleted = deleted, "Cleaned up expired exports");
+            }
+        }
+
+        Ok(())
+    }
+}
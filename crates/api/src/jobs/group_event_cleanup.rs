@@ -0,0 +1,57 @@
+//! Group event archive cleanup background job.
+//!
+//! Deletes archived group events older than the configured retention window.
+
+use persistence::repositories::GroupEventRepository;
+use sqlx::PgPool;
+use tracing::info;
+
+use super::scheduler::{Job, JobFrequency};
+
+/// Background job to prune the group event archive.
+pub struct GroupEventCleanupJob {
+    pool: PgPool,
+    retention_days: u32,
+}
+
+impl GroupEventCleanupJob {
+    /// Create a new group event cleanup job.
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    /// * `retention_days` - Number of days to keep archived events
+    pub fn new(pool: PgPool, retention_days: u32) -> Self {
+        Self {
+            pool,
+            retention_days,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Job for GroupEventCleanupJob {
+    fn name(&self) -> &'static str {
+        "group_event_cleanup"
+    }
+
+    fn frequency(&self) -> JobFrequency {
+        JobFrequency::Daily
+    }
+
+    async fn execute(&self) -> Result<(), String> {
+        let repo = GroupEventRepository::new(self.pool.clone());
+
+        let deleted = repo
+            .delete_older_than(self.retention_days)
+            .await
+            .map_err(|e| format!("Failed to cleanup group events: {}", e))?;
+
+        info!(
+            deleted = deleted,
+            retention_days = self.retention_days,
+            "Cleaned up archived group events"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+//! Group message cleanup background job.
+//!
+//! Deletes group announcements and check-ins older than the retention window
+//! and trims each group to its newest messages.
+
+use persistence::repositories::GroupMessageRepository;
+use sqlx::PgPool;
+use tracing::info;
+
+use super::scheduler::{Job, JobFrequency};
+
+/// Background job to enforce group message retention limits.
+pub struct GroupMessageCleanupJob {
+    pool: PgPool,
+    retention_days: u32,
+    max_per_group: u32,
+}
+
+impl GroupMessageCleanupJob {
+    /// Create a new group message cleanup job.
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    /// * `retention_days` - Number of days to keep messages
+    /// * `max_per_group` - Newest messages to keep per group
+    pub fn new(pool: PgPool, retention_days: u32, max_per_group: u32) -> Self {
+        Self {
+            pool,
+            retention_days,
+            max_per_group,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Job for GroupMessageCleanupJob {
+    fn name(&self) -> &'static str {
+        "group_message_cleanup"
+    }
+
+    fn frequency(&self) -> JobFrequency {
+        JobFrequency::Daily
+    }
+
+    async fn execute(&self) -> Result<(), String> {
+        let repo = GroupMessageRepository::new(self.pool.clone());
+
+        let expired = repo
+            .delete_older_than(self.retention_days)
+            .await
+            .map_err(|e| format!("Failed to cleanup group messages: {}", e))?;
+        let trimmed = repo
+            .trim_per_group(self.max_per_group)
+            .await
+            .map_err(|e| format!("Failed to trim group messages: {}", e))?;
+
+        info!(
+            expired = expired,
+            trimmed = trimmed,
+            retention_days = self.retention_days,
+            max_per_group = self.max_per_group,
+            "Cleaned up group messages"
+        );
+
+        Ok(())
+    }
+}
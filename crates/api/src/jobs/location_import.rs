@@ -0,0 +1,60 @@
+//! Location history import background job.
+//!
+//! Processes pending location import jobs created by uploads.
+
+use sqlx::PgPool;
+use std::path::PathBuf;
+use tracing::info;
+
+use crate::services::LocationImportService;
+
+use super::scheduler::{Job, JobFrequency};
+
+/// Background job to process location history imports.
+pub struct LocationImportJob {
+    pool: PgPool,
+    batch_size: i64,
+    imports_dir: PathBuf,
+}
+
+impl LocationImportJob {
+    /// Create a new location import job.
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    /// * `batch_size` - Number of imports to process per run
+    /// * `imports_dir` - Directory where uploads are stored
+    pub fn new(pool: PgPool, batch_size: i64, imports_dir: PathBuf) -> Self {
+        Self {
+            pool,
+            batch_size,
+            imports_dir,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Job for LocationImportJob {
+    fn name(&self) -> &'static str {
+        "location_import"
+    }
+
+    fn frequency(&self) -> JobFrequency {
+        JobFrequency::Seconds(30)
+    }
+
+    async fn execute(&self) -> Result<(), String> {
+        let service = LocationImportService::new(self.pool.clone(), self.imports_dir.clone());
+
+        let processed = service
+            .process_pending_jobs(self.batch_size)
+            .await
+            .map_err(|e| format!("Failed to process location imports: {}", e))?;
+
+        if processed > 0 {
+            info!(processed = processed, "Processed location import jobs");
+        }
+
+        Ok(())
+    }
+}
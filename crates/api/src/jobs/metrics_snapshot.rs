@@ -0,0 +1,72 @@
+//! Metrics snapshot background job.
+//!
+//! Writes the current metrics to the snapshot directory and deletes
+//! snapshots past their retention.
+
+use chrono::{Duration, Utc};
+use std::path::PathBuf;
+use tracing::{debug, info};
+
+use crate::middleware::metrics::render_metrics;
+use crate::services::metrics_snapshot::{prune_snapshots, write_snapshot};
+
+use super::scheduler::{Job, JobFrequency};
+
+/// Background job to snapshot metrics to files.
+pub struct MetricsSnapshotJob {
+    snapshots_dir: PathBuf,
+    interval_minutes: u64,
+    retention_days: u32,
+}
+
+impl MetricsSnapshotJob {
+    /// Create a new metrics snapshot job.
+    ///
+    /// # Arguments
+    /// * `snapshots_dir` - Directory where snapshots are written
+    /// * `interval_minutes` - Minutes between snapshots
+    /// * `retention_days` - Number of days to keep snapshots
+    pub fn new(snapshots_dir: PathBuf, interval_minutes: u64, retention_days: u32) -> Self {
+        Self {
+            snapshots_dir,
+            interval_minutes,
+            retention_days,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Job for MetricsSnapshotJob {
+    fn name(&self) -> &'static str {
+        "metrics_snapshot"
+    }
+
+    fn frequency(&self) -> JobFrequency {
+        JobFrequency::Minutes(self.interval_minutes.max(1))
+    }
+
+    async fn execute(&self) -> Result<(), String> {
+        let exposition =
+            render_metrics().ok_or_else(|| "Metrics recorder not initialized".to_string())?;
+
+        let now = Utc::now();
+        write_snapshot(&self.snapshots_dir, now, &exposition)
+            .await
+            .map_err(|e| format!("Failed to write metrics snapshot: {}", e))?;
+        debug!(dir = %self.snapshots_dir.display(), "Wrote metrics snapshot");
+
+        let cutoff = now - Duration::days(self.retention_days as i64);
+        let deleted = prune_snapshots(&self.snapshots_dir, cutoff)
+            .await
+            .map_err(|e| format!("Failed to prune metrics snapshots: {}", e))?;
+        if deleted > 0 {
+            info!(
+                deleted = deleted,
+                retention_days = self.retention_days,
+                "Deleted old metrics snapshots"
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -1,17 +1,43 @@
 //! Background job scheduler and job implementations.
 
+mod api_usage;
 mod cleanup_locations;
+mod commute_detection;
+mod export_cleanup;
+mod group_event_cleanup;
+mod group_message_cleanup;
+mod location_import;
+mod metrics_snapshot;
+mod outbox;
 mod pool_metrics;
+mod push_token_cleanup;
 mod refresh_views;
 mod report_generation;
 mod scheduler;
+mod trip_detection;
+mod trip_share_cleanup;
+mod webhook_auto_disable;
+mod webhook_batch;
 mod webhook_cleanup;
 mod webhook_retry;
 
+pub use api_usage::{ApiUsageCleanupJob, ApiUsageRollupJob};
 pub use cleanup_locations::CleanupLocationsJob;
+pub use commute_detection::CommuteDetectionJob;
+pub use export_cleanup::ExportCleanupJob;
+pub use group_event_cleanup::GroupEventCleanupJob;
+pub use group_message_cleanup::GroupMessageCleanupJob;
+pub use location_import::LocationImportJob;
+pub use metrics_snapshot::MetricsSnapshotJob;
+pub use outbox::{OutboxCleanupJob, OutboxDispatchJob};
 pub use pool_metrics::PoolMetricsJob;
+pub use push_token_cleanup::PushTokenCleanupJob;
 pub use refresh_views::RefreshViewsJob;
 pub use report_generation::{ReportCleanupJob, ReportGenerationJob};
 pub use scheduler::JobScheduler;
+pub use trip_detection::TripDetectionJob;
+pub use trip_share_cleanup::TripShareCleanupJob;
+pub use webhook_auto_disable::WebhookAutoDisableJob;
+pub use webhook_batch::WebhookBatchFlushJob;
 pub use webhook_cleanup::WebhookCleanupJob;
 pub use webhook_retry::WebhookRetryJob;
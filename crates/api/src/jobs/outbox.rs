@@ -0,0 +1,213 @@
+//! Transactional outbox background jobs.
+//!
+//! The dispatch job performs the side effects that domain changes queued in
+//! the outbox; the cleanup job deletes dispatched messages.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use domain::models::OutboxMessage;
+use domain::services::NotificationService;
+use persistence::entities::EventOutboxEntity;
+use persistence::repositories::EventOutboxRepository;
+use sqlx::PgPool;
+use tokio::task::JoinSet;
+use tracing::{error, info, warn};
+
+use crate::services::outbox::dispatch;
+use crate::services::webhook_secret::WebhookSecrets;
+
+use super::scheduler::{Job, JobFrequency};
+
+/// Messages claimed per run.
+const BATCH_SIZE: i64 = 50;
+
+/// Seconds a claimed message is reserved for this dispatcher.
+const LEASE_SECS: i64 = 300;
+
+/// Dispatch attempts before a message is given up.
+const MAX_ATTEMPTS: i32 = 10;
+
+/// Days dispatched messages are kept.
+const RETENTION_DAYS: i32 = 7;
+
+/// When to retry a message whose `attempts`-th dispatch failed, or None
+/// once its attempts are used up.
+fn next_attempt_at(attempts: i32, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    if attempts >= MAX_ATTEMPTS {
+        return None;
+    }
+    // 10s after the first attempt, doubling up to about 43 minutes
+    Some(now + Duration::seconds(5i64 << attempts.max(0)))
+}
+
+/// Background job to dispatch outbox messages.
+pub struct OutboxDispatchJob {
+    pool: PgPool,
+    notification_service: Arc<dyn NotificationService>,
+    secrets: WebhookSecrets,
+}
+
+impl OutboxDispatchJob {
+    /// Create a new outbox dispatch job.
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    /// * `notification_service` - Push notifications queued in the outbox
+    /// * `secrets` - Stored webhook secrets to sign deliveries with
+    pub fn new(
+        pool: PgPool,
+        notification_service: Arc<dyn NotificationService>,
+        secrets: WebhookSecrets,
+    ) -> Self {
+        Self {
+            pool,
+            notification_service,
+            secrets,
+        }
+    }
+}
+
+/// Dispatch one claimed message and record the outcome.
+async fn process(
+    pool: PgPool,
+    notification_service: Arc<dyn NotificationService>,
+    secrets: WebhookSecrets,
+    entity: EventOutboxEntity,
+) {
+    let result = match serde_json::from_value::<OutboxMessage>(entity.payload) {
+        Ok(message) => dispatch(&pool, &notification_service, &secrets, message).await,
+        Err(e) => Err(format!("Invalid outbox message: {}", e)),
+    };
+
+    let repo = EventOutboxRepository::new(pool);
+    let recorded = match result {
+        Ok(()) => {
+            metrics::counter!("outbox_messages_dispatched_total", "kind" => entity.kind.clone())
+                .increment(1);
+            repo.mark_processed(entity.id).await
+        }
+        Err(e) => {
+            let retry_at = next_attempt_at(entity.attempts, Utc::now());
+            if retry_at.is_none() {
+                metrics::counter!("outbox_messages_failed_total", "kind" => entity.kind.clone())
+                    .increment(1);
+                error!(
+                    outbox_id = entity.id,
+                    kind = %entity.kind,
+                    attempts = entity.attempts,
+                    error = %e,
+                    "Giving up on outbox message"
+                );
+            } else {
+                warn!(
+                    outbox_id = entity.id,
+                    kind = %entity.kind,
+                    attempts = entity.attempts,
+                    error = %e,
+                    "Outbox message dispatch failed"
+                );
+            }
+            repo.mark_failed(entity.id, &e, retry_at).await
+        }
+    };
+    if let Err(e) = recorded {
+        // The lease expires and the message is dispatched again
+        warn!(outbox_id = entity.id, error = %e, "Failed to record outbox dispatch");
+    }
+}
+
+#[async_trait::async_trait]
+impl Job for OutboxDispatchJob {
+    fn name(&self) -> &'static str {
+        "outbox_dispatch"
+    }
+
+    fn frequency(&self) -> JobFrequency {
+        JobFrequency::Seconds(5)
+    }
+
+    async fn execute(&self) -> Result<(), String> {
+        let repo = EventOutboxRepository::new(self.pool.clone());
+        let messages = repo
+            .claim_due(BATCH_SIZE, LEASE_SECS)
+            .await
+            .map_err(|e| format!("Failed to claim outbox messages: {}", e))?;
+
+        // Webhook fan-out waits on receivers; dispatch messages concurrently
+        let mut tasks = JoinSet::new();
+        for entity in messages {
+            tasks.spawn(process(
+                self.pool.clone(),
+                Arc::clone(&self.notification_service),
+                self.secrets.clone(),
+                entity,
+            ));
+        }
+        while tasks.join_next().await.is_some() {}
+
+        let pending = repo
+            .count_unprocessed()
+            .await
+            .map_err(|e| format!("Failed to count outbox messages: {}", e))?;
+        metrics::gauge!("outbox_pending_messages").set(pending as f64);
+
+        Ok(())
+    }
+}
+
+/// Background job to delete dispatched outbox messages.
+pub struct OutboxCleanupJob {
+    pool: PgPool,
+}
+
+impl OutboxCleanupJob {
+    /// Create a new outbox cleanup job.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl Job for OutboxCleanupJob {
+    fn name(&self) -> &'static str {
+        "outbox_cleanup"
+    }
+
+    fn frequency(&self) -> JobFrequency {
+        JobFrequency::Daily
+    }
+
+    async fn execute(&self) -> Result<(), String> {
+        let deleted = EventOutboxRepository::new(self.pool.clone())
+            .delete_processed(RETENTION_DAYS)
+            .await
+            .map_err(|e| format!("Failed to cleanup outbox messages: {}", e))?;
+
+        info!(
+            deleted = deleted,
+            retention_days = RETENTION_DAYS,
+            "Cleaned up dispatched outbox messages"
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_attempt_backs_off() {
+        let now = Utc::now();
+        assert_eq!(next_attempt_at(1, now), Some(now + Duration::seconds(10)));
+        assert_eq!(next_attempt_at(2, now), Some(now + Duration::seconds(20)));
+        assert_eq!(next_attempt_at(9, now), Some(now + Duration::seconds(2560)));
+    }
+
+    #[test]
+    fn test_next_attempt_gives_up() {
+        assert!(next_attempt_at(MAX_ATTEMPTS, Utc::now()).is_none());
+    }
+}
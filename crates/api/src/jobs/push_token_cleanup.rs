@@ -0,0 +1,43 @@
+//! Expired push token cleanup background job.
+//!
+//! Expired tokens are already skipped when sending; this job deletes them.
+
+use persistence::repositories::DevicePushTokenRepository;
+use sqlx::PgPool;
+use tracing::info;
+
+use super::scheduler::{Job, JobFrequency};
+
+/// Background job to delete expired device push tokens.
+pub struct PushTokenCleanupJob {
+    pool: PgPool,
+}
+
+impl PushTokenCleanupJob {
+    /// Create a new push token cleanup job.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl Job for PushTokenCleanupJob {
+    fn name(&self) -> &'static str {
+        "push_token_cleanup"
+    }
+
+    fn frequency(&self) -> JobFrequency {
+        JobFrequency::Daily
+    }
+
+    async fn execute(&self) -> Result<(), String> {
+        let deleted = DevicePushTokenRepository::new(self.pool.clone())
+            .delete_expired()
+            .await
+            .map_err(|e| format!("Failed to cleanup push tokens: {}", e))?;
+
+        info!(deleted = deleted, "Cleaned up expired push tokens");
+
+        Ok(())
+    }
+}
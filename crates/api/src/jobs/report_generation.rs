@@ -16,6 +16,7 @@ pub struct ReportGenerationJob {
     pool: PgPool,
     batch_size: i64,
     reports_dir: PathBuf,
+    timescale: bool,
 }
 
 impl ReportGenerationJob {
@@ -30,8 +31,15 @@ impl ReportGenerationJob {
             pool,
             batch_size,
             reports_dir,
+            timescale: false,
         }
     }
+
+    /// Read report metrics from TimescaleDB continuous aggregates.
+    pub fn with_timescale(mut self, enabled: bool) -> Self {
+        self.timescale = enabled;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -46,7 +54,8 @@ impl Job for ReportGenerationJob {
     }
 
     async fn execute(&self) -> Result<(), String> {
-        let service = ReportGenerationService::new(self.pool.clone(), self.reports_dir.clone());
+        let service = ReportGenerationService::new(self.pool.clone(), self.reports_dir.clone())
+            .with_timescale(self.timescale);
 
         let processed = service
             .process_pending_jobs(self.batch_size)
@@ -0,0 +1,63 @@
+//! Server-side trip detection background job.
+//!
+//! Segments the locations of devices with `server_trip_detection_enabled`
+//! into trips.
+
+use domain::services::TRIP_DETECTION_SETTING_KEY;
+use persistence::repositories::SettingRepository;
+use serde_json::json;
+use sqlx::PgPool;
+use tracing::{info, warn};
+
+use crate::services::trip_detection::detect_trips;
+
+use super::scheduler::{Job, JobFrequency};
+
+/// Background job to derive trips from the location stream.
+pub struct TripDetectionJob {
+    pool: PgPool,
+}
+
+impl TripDetectionJob {
+    /// Create a new trip detection job.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl Job for TripDetectionJob {
+    fn name(&self) -> &'static str {
+        "trip_detection"
+    }
+
+    fn frequency(&self) -> JobFrequency {
+        JobFrequency::Minutes(5)
+    }
+
+    async fn execute(&self) -> Result<(), String> {
+        let device_ids = SettingRepository::new(self.pool.clone())
+            .find_device_ids_with_value(TRIP_DETECTION_SETTING_KEY, &json!(true))
+            .await
+            .map_err(|e| format!("Failed to load trip detection devices: {}", e))?;
+
+        let mut completed = 0;
+        for device_id in &device_ids {
+            match detect_trips(&self.pool, *device_id).await {
+                Ok(count) => completed += count,
+                // One device's failure must not hold up the others.
+                Err(e) => warn!(device_id = %device_id, error = %e, "Trip detection failed"),
+            }
+        }
+
+        if completed > 0 {
+            info!(
+                devices = device_ids.len(),
+                trips = completed,
+                "Detected trips from location stream"
+            );
+        }
+
+        Ok(())
+    }
+}
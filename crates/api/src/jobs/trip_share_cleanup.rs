@@ -0,0 +1,49 @@
+//! Expired trip share link cleanup background job.
+//!
+//! Expired links stop working immediately; they are kept for a while so
+//! trip owners can still see them in the share list, then deleted here.
+
+use chrono::{Duration, Utc};
+use persistence::repositories::TripShareLinkRepository;
+use sqlx::PgPool;
+use tracing::info;
+
+use super::scheduler::{Job, JobFrequency};
+
+/// Days an expired share link is kept before it is deleted.
+const EXPIRED_LINK_RETENTION_DAYS: i64 = 30;
+
+/// Background job to delete long-expired trip share links.
+pub struct TripShareCleanupJob {
+    pool: PgPool,
+}
+
+impl TripShareCleanupJob {
+    /// Create a new trip share cleanup job.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl Job for TripShareCleanupJob {
+    fn name(&self) -> &'static str {
+        "trip_share_cleanup"
+    }
+
+    fn frequency(&self) -> JobFrequency {
+        JobFrequency::Daily
+    }
+
+    async fn execute(&self) -> Result<(), String> {
+        let cutoff = Utc::now() - Duration::days(EXPIRED_LINK_RETENTION_DAYS);
+        let deleted = TripShareLinkRepository::new(self.pool.clone())
+            .delete_expired_before(cutoff)
+            .await
+            .map_err(|e| format!("Failed to cleanup trip share links: {}", e))?;
+
+        info!(deleted = deleted, "Cleaned up expired trip share links");
+
+        Ok(())
+    }
+}
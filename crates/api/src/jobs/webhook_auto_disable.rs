@@ -0,0 +1,169 @@
+//! Webhook auto-disable background job.
+//!
+//! The circuit breaker only pauses a failing webhook; a dead endpoint would
+//! keep receiving retries forever. This job disables webhooks whose failure
+//! streak reached the configured threshold and tells their owner by push
+//! notification and email.
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use domain::models::{Webhook, WebhookOwner};
+use domain::services::{NotificationService, NotificationType, WebhookDisabledPayload};
+use persistence::repositories::{DeviceRepository, UserRepository, WebhookRepository};
+use sqlx::PgPool;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::services::push_tokens::{active_push_tokens, handle_send_result};
+use crate::services::EmailService;
+
+use super::scheduler::{Job, JobFrequency};
+
+/// Background job to disable persistently failing webhooks.
+pub struct WebhookAutoDisableJob {
+    pool: PgPool,
+    failure_threshold: u32,
+    notification_service: Arc<dyn NotificationService>,
+    email_service: EmailService,
+}
+
+impl WebhookAutoDisableJob {
+    /// Create a new webhook auto-disable job.
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    /// * `failure_threshold` - Failures in a row after which a webhook is disabled
+    /// * `notification_service` - Push notifications to the owner's devices
+    /// * `email_service` - Email to the owning user
+    pub fn new(
+        pool: PgPool,
+        failure_threshold: u32,
+        notification_service: Arc<dyn NotificationService>,
+        email_service: EmailService,
+    ) -> Self {
+        Self {
+            pool,
+            failure_threshold,
+            notification_service,
+            email_service,
+        }
+    }
+
+    /// Notify the owner of a disabled webhook. Failures are logged only.
+    async fn notify_owner(&self, webhook: &Webhook, failure_streak: i32) {
+        let device_repo = DeviceRepository::new(self.pool.clone());
+        let (user_id, device_ids): (Option<Uuid>, Vec<Uuid>) = match webhook.owner() {
+            WebhookOwner::Device(device_id) => {
+                let owner = match device_repo.find_by_device_id(device_id).await {
+                    Ok(device) => device.and_then(|d| d.owner_user_id),
+                    Err(e) => {
+                        warn!(device_id = %device_id, error = %e, "Failed to load webhook owner device");
+                        None
+                    }
+                };
+                (owner, vec![device_id])
+            }
+            WebhookOwner::User(user_id) => {
+                let devices = match device_repo.find_devices_by_user(user_id, false).await {
+                    Ok(devices) => devices.into_iter().map(|d| d.device_id).collect(),
+                    Err(e) => {
+                        warn!(user_id = %user_id, error = %e, "Failed to load webhook owner devices");
+                        Vec::new()
+                    }
+                };
+                (Some(user_id), devices)
+            }
+        };
+
+        let payload = WebhookDisabledPayload {
+            notification_type: NotificationType::WebhookDisabled,
+            webhook_id: webhook.webhook_id,
+            webhook_name: webhook.name.clone(),
+            failure_streak,
+            timestamp: Utc::now(),
+        };
+        for device_id in device_ids {
+            for token in active_push_tokens(&self.pool, device_id).await {
+                let result = self
+                    .notification_service
+                    .send_webhook_disabled(&token, payload.clone())
+                    .await;
+                handle_send_result(&self.pool, device_id, &token, result).await;
+            }
+        }
+
+        let Some(user_id) = user_id else {
+            return;
+        };
+        let user = match UserRepository::new(self.pool.clone())
+            .find_by_id(user_id)
+            .await
+        {
+            Ok(Some(user)) => user,
+            Ok(None) => return,
+            Err(e) => {
+                warn!(user_id = %user_id, error = %e, "Failed to load webhook owner");
+                return;
+            }
+        };
+        if let Err(e) = self
+            .email_service
+            .send_webhook_disabled_email(
+                &user.email,
+                user.display_name.as_deref(),
+                &webhook.name,
+                failure_streak,
+            )
+            .await
+        {
+            warn!(
+                webhook_id = %webhook.webhook_id,
+                error = %e,
+                "Failed to send webhook disabled email"
+            );
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Job for WebhookAutoDisableJob {
+    fn name(&self) -> &'static str {
+        "webhook_auto_disable"
+    }
+
+    fn frequency(&self) -> JobFrequency {
+        JobFrequency::Minutes(5)
+    }
+
+    async fn execute(&self) -> Result<(), String> {
+        let threshold = self.failure_threshold.min(i32::MAX as u32) as i32;
+        let disabled = WebhookRepository::new(self.pool.clone())
+            .disable_failing(threshold)
+            .await
+            .map_err(|e| format!("Failed to disable failing webhooks: {}", e))?;
+
+        let count = disabled.len();
+        for entity in disabled {
+            let failure_streak = entity.failure_streak;
+            let webhook = Webhook::from(entity);
+            warn!(
+                webhook_id = %webhook.webhook_id,
+                failure_streak = failure_streak,
+                "Disabled webhook after consecutive delivery failures"
+            );
+            metrics::counter!("webhooks_auto_disabled_total").increment(1);
+            self.notify_owner(&webhook, failure_streak).await;
+        }
+
+        if count > 0 {
+            info!(
+                disabled = count,
+                threshold = threshold,
+                "Disabled failing webhooks"
+            );
+        }
+
+        Ok(())
+    }
+}
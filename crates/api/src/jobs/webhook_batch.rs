@@ -0,0 +1,57 @@
+//! Webhook batch background job.
+//!
+//! Sends the collected events of batched webhooks once their batch window
+//! has passed, and records how many events are waiting.
+
+use persistence::repositories::WebhookBatchRepository;
+use sqlx::PgPool;
+use tracing::info;
+
+use crate::services::webhook_secret::WebhookSecrets;
+use crate::services::WebhookDeliveryService;
+
+use super::scheduler::{Job, JobFrequency};
+
+/// Background job to send due webhook batches.
+pub struct WebhookBatchFlushJob {
+    pool: PgPool,
+    secrets: WebhookSecrets,
+}
+
+impl WebhookBatchFlushJob {
+    /// Create a new webhook batch job.
+    pub fn new(pool: PgPool, secrets: WebhookSecrets) -> Self {
+        Self { pool, secrets }
+    }
+}
+
+#[async_trait::async_trait]
+impl Job for WebhookBatchFlushJob {
+    fn name(&self) -> &'static str {
+        "webhook_batch_flush"
+    }
+
+    fn frequency(&self) -> JobFrequency {
+        // Batch windows are at least 5 seconds
+        JobFrequency::Seconds(5)
+    }
+
+    async fn execute(&self) -> Result<(), String> {
+        let flushed = WebhookDeliveryService::new(self.pool.clone(), self.secrets.clone())
+            .flush_due_batches()
+            .await
+            .map_err(|e| format!("Failed to send webhook batches: {}", e))?;
+
+        if flushed > 0 {
+            info!(batches = flushed, "Sent webhook batches");
+        }
+
+        let pending = WebhookBatchRepository::new(self.pool.clone())
+            .count_pending()
+            .await
+            .map_err(|e| format!("Failed to count batched webhook events: {}", e))?;
+        metrics::gauge!("webhook_batched_events_pending").set(pending as f64);
+
+        Ok(())
+    }
+}
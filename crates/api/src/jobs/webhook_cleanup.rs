@@ -3,11 +3,10 @@
 //! Story 15.3: Webhook Delivery Logging and Retry
 //! Cleans up old webhook delivery records based on retention policy.
 
+use persistence::repositories::WebhookDeliveryRepository;
 use sqlx::PgPool;
 use tracing::info;
 
-use crate::services::WebhookDeliveryService;
-
 use super::scheduler::{Job, JobFrequency};
 
 /// Default retention period in days for webhook deliveries.
@@ -45,10 +44,8 @@ impl Job for WebhookCleanupJob {
     }
 
     async fn execute(&self) -> Result<(), String> {
-        let service = WebhookDeliveryService::new(self.pool.clone());
-
-        let deleted = service
-            .cleanup_old_deliveries(self.retention_days)
+        let deleted = WebhookDeliveryRepository::new(self.pool.clone())
+            .delete_old_deliveries(self.retention_days)
             .await
             .map_err(|e| format!("Failed to cleanup webhook deliveries: {}", e))?;
 
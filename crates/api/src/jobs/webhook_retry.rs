@@ -1,11 +1,14 @@
 //! Webhook retry background job.
 //!
 //! Story 15.3: Webhook Delivery Logging and Retry
-//! Processes failed webhook deliveries that are due for retry.
+//! Processes failed webhook deliveries that are due for retry and records
+//! the depth of the dead-letter queue.
 
+use persistence::repositories::WebhookDeliveryRepository;
 use sqlx::PgPool;
 use tracing::info;
 
+use crate::services::webhook_secret::WebhookSecrets;
 use crate::services::WebhookDeliveryService;
 
 use super::scheduler::{Job, JobFrequency};
@@ -13,6 +16,7 @@ use super::scheduler::{Job, JobFrequency};
 /// Background job to retry failed webhook deliveries.
 pub struct WebhookRetryJob {
     pool: PgPool,
+    secrets: WebhookSecrets,
     batch_size: i64,
 }
 
@@ -21,9 +25,14 @@ impl WebhookRetryJob {
     ///
     /// # Arguments
     /// * `pool` - Database connection pool
+    /// * `secrets` - Stored webhook secrets to sign deliveries with
     /// * `batch_size` - Number of deliveries to process per batch
-    pub fn new(pool: PgPool, batch_size: i64) -> Self {
-        Self { pool, batch_size }
+    pub fn new(pool: PgPool, secrets: WebhookSecrets, batch_size: i64) -> Self {
+        Self {
+            pool,
+            secrets,
+            batch_size,
+        }
     }
 }
 
@@ -39,7 +48,7 @@ impl Job for WebhookRetryJob {
     }
 
     async fn execute(&self) -> Result<(), String> {
-        let service = WebhookDeliveryService::new(self.pool.clone());
+        let service = WebhookDeliveryService::new(self.pool.clone(), self.secrets.clone());
 
         let processed = service
             .process_pending_retries(self.batch_size)
@@ -54,6 +63,12 @@ impl Job for WebhookRetryJob {
             );
         }
 
+        let depth = WebhookDeliveryRepository::new(self.pool.clone())
+            .dead_letter_queue_depth()
+            .await
+            .map_err(|e| format!("Failed to count webhook dead letters: {}", e))?;
+        metrics::gauge!("webhook_dead_letter_queue_depth").set(depth as f64);
+
         Ok(())
     }
 }
@@ -1,8 +1,11 @@
 pub mod app;
 pub mod config;
+pub mod config_check;
 pub mod error;
 pub mod extractors;
 pub mod jobs;
+pub mod log_store;
 pub mod middleware;
+pub mod preflight;
 pub mod routes;
 pub mod services;
@@ -0,0 +1,325 @@
+//! Rolling store of tenant application logs.
+//!
+//! A tracing layer attributes each log event to an organization and group,
+//! taken from the event's own `org_id`/`organization_id`/`group_id` fields or
+//! from the enclosing spans (the request span carries the tenant of the
+//! requested path). Events attributed to an organization are kept in a
+//! bounded in-memory buffer, oldest dropped first, so support can export the
+//! recent logs of one organization without those of other tenants.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use chrono::Utc;
+use domain::models::{ExportTenantLogsQuery, LogLevel, TenantLogEntry};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+use uuid::Uuid;
+
+/// Bounded buffer of the most recent tenant log entries.
+#[derive(Debug)]
+pub struct LogStore {
+    capacity: usize,
+    entries: Mutex<VecDeque<TenantLogEntry>>,
+}
+
+impl LogStore {
+    /// Create a store keeping up to `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(VecDeque::with_capacity(capacity.min(1024))),
+        }
+    }
+
+    /// Add an entry, dropping the oldest one when full.
+    pub fn push(&self, entry: TenantLogEntry) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        if entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    /// The most recent entries of an organization matching the query,
+    /// oldest first, and whether older matching entries were left out.
+    pub fn export(
+        &self,
+        organization_id: Uuid,
+        query: &ExportTenantLogsQuery,
+    ) -> (Vec<TenantLogEntry>, bool) {
+        let limit = query.limit();
+        let entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut matching: Vec<TenantLogEntry> = entries
+            .iter()
+            .rev()
+            .filter(|e| e.organization_id == organization_id && query.matches(e))
+            .take(limit + 1)
+            .cloned()
+            .collect();
+        drop(entries);
+
+        let truncated = matching.len() > limit;
+        matching.truncate(limit);
+        matching.reverse();
+        (matching, truncated)
+    }
+}
+
+/// Tracing layer feeding a [`LogStore`].
+#[derive(Debug, Clone)]
+pub struct LogStoreLayer {
+    store: Arc<LogStore>,
+}
+
+impl LogStoreLayer {
+    pub fn new(store: Arc<LogStore>) -> Self {
+        Self { store }
+    }
+}
+
+impl<S> Layer<S> for LogStoreLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut tenant = TenantContext::default();
+        attrs.record(&mut tenant);
+        if let (false, Some(span)) = (tenant.is_empty(), ctx.span(id)) {
+            span.extensions_mut().insert(tenant);
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut extensions = span.extensions_mut();
+        match extensions.get_mut::<TenantContext>() {
+            Some(tenant) => values.record(tenant),
+            None => {
+                let mut tenant = TenantContext::default();
+                values.record(&mut tenant);
+                extensions.insert(tenant);
+            }
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut visitor = EventVisitor::default();
+        event.record(&mut visitor);
+
+        let mut tenant = visitor.tenant;
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope {
+                if let Some(outer) = span.extensions().get::<TenantContext>() {
+                    tenant.inherit(outer);
+                }
+            }
+        }
+        let Some(organization_id) = tenant.org_id else {
+            return;
+        };
+
+        let metadata = event.metadata();
+        self.store.push(TenantLogEntry {
+            timestamp: Utc::now(),
+            level: log_level(metadata.level()),
+            target: metadata.target().to_string(),
+            message: visitor.message,
+            organization_id,
+            group_id: tenant.group_id,
+            request_id: tenant.request_id,
+            fields: visitor.fields,
+        });
+    }
+}
+
+fn log_level(level: &Level) -> LogLevel {
+    match *level {
+        Level::ERROR => LogLevel::Error,
+        Level::WARN => LogLevel::Warn,
+        Level::INFO => LogLevel::Info,
+        Level::DEBUG => LogLevel::Debug,
+        Level::TRACE => LogLevel::Trace,
+    }
+}
+
+/// Tenant fields of a span or event.
+#[derive(Debug, Clone, Default)]
+struct TenantContext {
+    org_id: Option<Uuid>,
+    group_id: Option<String>,
+    request_id: Option<String>,
+}
+
+impl TenantContext {
+    fn is_empty(&self) -> bool {
+        self.org_id.is_none() && self.group_id.is_none() && self.request_id.is_none()
+    }
+
+    /// Record a field if it is a tenant field; returns whether it was.
+    fn set(&mut self, name: &str, value: &str) -> bool {
+        match name {
+            "org_id" | "organization_id" => {
+                if let Ok(org_id) = value.parse() {
+                    self.org_id = Some(org_id);
+                }
+            }
+            "group_id" => self.group_id = Some(value.to_string()),
+            "request_id" => self.request_id = Some(value.to_string()),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Fill the fields not set yet from an enclosing span.
+    fn inherit(&mut self, outer: &TenantContext) {
+        if self.org_id.is_none() {
+            self.org_id = outer.org_id;
+        }
+        if self.group_id.is_none() {
+            self.group_id.clone_from(&outer.group_id);
+        }
+        if self.request_id.is_none() {
+            self.request_id.clone_from(&outer.request_id);
+        }
+    }
+}
+
+impl Visit for TenantContext {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.set(field.name(), value);
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.set(field.name(), &format!("{:?}", value));
+    }
+}
+
+/// Collects the message, tenant and other fields of an event.
+#[derive(Debug, Default)]
+struct EventVisitor {
+    message: String,
+    tenant: TenantContext,
+    fields: serde_json::Map<String, serde_json::Value>,
+}
+
+impl EventVisitor {
+    fn insert(&mut self, field: &Field, value: serde_json::Value) {
+        self.fields.insert(field.name().to_string(), value);
+    }
+}
+
+impl Visit for EventVisitor {
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else if !self.tenant.set(field.name(), value) {
+            self.insert(field, value.into());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.record_str(field, &format!("{:?}", value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    fn capture(store: &Arc<LogStore>, f: impl FnOnce()) {
+        let subscriber = tracing_subscriber::registry().with(LogStoreLayer::new(Arc::clone(store)));
+        tracing::subscriber::with_default(subscriber, f);
+    }
+
+    #[test]
+    fn test_events_attributed_from_fields_and_spans() {
+        let store = Arc::new(LogStore::new(10));
+        let org_a = Uuid::new_v4();
+        let org_b = Uuid::new_v4();
+
+        capture(&store, || {
+            tracing::info!("no tenant");
+            tracing::warn!(organization_id = %org_a, count = 3, "from field");
+            let span = tracing::info_span!(
+                "request",
+                request_id = "req-1",
+                org_id = tracing::field::Empty,
+                group_id = tracing::field::Empty,
+            );
+            span.record("org_id", tracing::field::display(org_b));
+            span.record("group_id", "g1");
+            span.in_scope(|| {
+                tracing::error!("from span");
+                tracing::info!(organization_id = %org_a, "event field wins");
+            });
+        });
+
+        let query = ExportTenantLogsQuery::default();
+        let (a, _) = store.export(org_a, &query);
+        assert_eq!(a.len(), 2);
+        assert_eq!(a[0].message, "from field");
+        assert_eq!(a[0].level, LogLevel::Warn);
+        assert_eq!(a[0].fields["count"], 3);
+        assert_eq!(a[1].message, "event field wins");
+        assert_eq!(a[1].group_id.as_deref(), Some("g1"));
+
+        let (b, _) = store.export(org_b, &query);
+        assert_eq!(b.len(), 1);
+        assert_eq!(b[0].message, "from span");
+        assert_eq!(b[0].request_id.as_deref(), Some("req-1"));
+        assert!(!b[0].fields.contains_key("organization_id"));
+    }
+
+    #[test]
+    fn test_store_rolls_over_and_truncates_export() {
+        let store = Arc::new(LogStore::new(3));
+        let org_id = Uuid::new_v4();
+
+        capture(&store, || {
+            for i in 0..5 {
+                tracing::info!(org_id = %org_id, "entry {}", i);
+            }
+        });
+
+        let (all, truncated) = store.export(org_id, &ExportTenantLogsQuery::default());
+        let messages: Vec<_> = all.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, ["entry 2", "entry 3", "entry 4"]);
+        assert!(!truncated);
+
+        let query = ExportTenantLogsQuery {
+            limit: Some(2),
+            ..Default::default()
+        };
+        let (recent, truncated) = store.export(org_id, &query);
+        assert_eq!(recent[0].message, "entry 3");
+        assert_eq!(recent.len(), 2);
+        assert!(truncated);
+    }
+}
@@ -1,25 +1,46 @@
 use anyhow::Result;
+use std::sync::Arc;
 use std::time::Duration;
 use tracing::{info, warn};
 
-use phone_manager_api::{app, config, jobs, middleware, services};
+use phone_manager_api::{app, config, config_check, jobs, middleware, preflight, services};
 
 #[tokio::main]
 async fn main() -> Result<()> {
     // Load .env file if present
     dotenvy::dotenv().ok();
 
+    // `--validate-config [--check-smtp]`: print a configuration report and exit
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--validate-config") {
+        let check_smtp = args.iter().any(|arg| arg == "--check-smtp");
+        let report = config_check::validate_config(check_smtp).await;
+        print!("{}", report);
+        std::process::exit(if report.has_errors() { 1 } else { 0 });
+    }
+
     // Load configuration
-    let config = config::Config::load()?;
+    let mut config = config::Config::load()?;
 
     // Initialize logging
-    middleware::logging::init_logging(&config.logging);
+    let log_store = middleware::logging::init_logging(&config.logging);
+
+    // Lite profile: provision JWT keys in the data directory on first boot
+    if config.profile == config::RuntimeProfile::Lite {
+        info!(data_dir = %config.lite.data_dir, "Running with lite profile");
+        services::jwt_keys::ensure_jwt_keys(
+            &mut config.jwt,
+            std::path::Path::new(&config.lite.data_dir),
+        )?;
+    }
 
     // Initialize Prometheus metrics
     middleware::metrics::init_metrics();
     info!("Prometheus metrics initialized");
 
     info!("Starting Phone Manager API v{}", env!("CARGO_PKG_VERSION"));
+    #[cfg(feature = "fault-injection")]
+    warn!("Fault injection is enabled in this build - for resilience testing only");
 
     // Validate production configuration
     // In development mode (detected by placeholder values), log warnings instead of failing
@@ -39,6 +60,20 @@ async fn main() -> Result<()> {
         }
     }
 
+    // Keys encrypting device webhook secrets and webhook client certificate
+    // keys at rest
+    let webhook_secrets =
+        services::webhook_secret::WebhookSecrets::from_key(&config.security.webhook_secret_key)
+            .and_then(|secrets| {
+                secrets.with_client_cert_key(&config.security.webhook_client_cert_key)
+            })
+            .map_err(|e| anyhow::anyhow!("Invalid configuration: {}", e))?;
+
+    // Probe external dependencies; failing ones degrade their features
+    let preflight = preflight::run_preflight(&config).await;
+    preflight::apply_degradations(&mut config, &preflight);
+    let storage_available = !preflight.is_degraded(preflight::Dependency::BlobStorage);
+
     // Create database pool
     let db_config = persistence::db::DatabaseConfig {
         url: config.database.url.clone(),
@@ -56,6 +91,25 @@ async fn main() -> Result<()> {
         .await?;
     info!("Migrations completed");
 
+    // Optional TimescaleDB hypertables and continuous aggregates
+    if config.database.timescale_enabled {
+        info!("Applying TimescaleDB setup...");
+        persistence::timescale::setup(&pool)
+            .await
+            .map_err(|e| anyhow::anyhow!("TimescaleDB setup failed: {}", e))?;
+        info!("TimescaleDB setup completed");
+    }
+
+    // Encrypt webhook secrets stored before the key was configured
+    match webhook_secrets.seal_stored_secrets(&pool).await {
+        Ok(0) => {}
+        Ok(sealed) => info!(sealed = sealed, "Encrypted stored webhook secrets"),
+        Err(e) => warn!(
+            "Encrypting stored webhook secrets failed: {}. Continuing startup...",
+            e
+        ),
+    }
+
     // Bootstrap admin user if configured
     if let Err(e) = services::admin_bootstrap::bootstrap_admin(&pool, &config.admin).await {
         warn!("Admin bootstrap failed: {}. Continuing startup...", e);
@@ -70,24 +124,111 @@ async fn main() -> Result<()> {
     scheduler.register(jobs::RefreshViewsJob::new(pool.clone()));
     scheduler.register(jobs::PoolMetricsJob::new(pool.clone()));
     // Webhook retry job - runs every minute to process failed deliveries
-    scheduler.register(jobs::WebhookRetryJob::new(pool.clone(), 10));
+    scheduler.register(jobs::WebhookRetryJob::new(
+        pool.clone(),
+        webhook_secrets.clone(),
+        10,
+    ));
+    // Webhook batch job - runs every 5 seconds to send due batches
+    scheduler.register(jobs::WebhookBatchFlushJob::new(
+        pool.clone(),
+        webhook_secrets.clone(),
+    ));
+    // Webhook auto-disable job - runs every 5 minutes to disable dead endpoints
+    if config.limits.webhook_auto_disable_failures > 0 {
+        scheduler.register(jobs::WebhookAutoDisableJob::new(
+            pool.clone(),
+            config.limits.webhook_auto_disable_failures,
+            app::create_notification_service(&config),
+            services::EmailService::new(config.email.clone()),
+        ));
+    }
+    // Outbox jobs - dispatch queued webhook fan-outs and push notifications
+    // every 5 seconds, delete dispatched messages daily
+    scheduler.register(jobs::OutboxDispatchJob::new(
+        pool.clone(),
+        app::create_notification_service(&config),
+        webhook_secrets.clone(),
+    ));
+    scheduler.register(jobs::OutboxCleanupJob::new(pool.clone()));
     // Webhook cleanup job - runs daily to clean up old delivery records
     scheduler.register(jobs::WebhookCleanupJob::new(pool.clone(), Some(7)));
-    // Report generation job - runs every 30 seconds to process pending report jobs
-    scheduler.register(jobs::ReportGenerationJob::new(
+    // Group event cleanup job - runs daily to prune the group event archive
+    scheduler.register(jobs::GroupEventCleanupJob::new(
         pool.clone(),
-        config.reports.batch_size,
-        std::path::PathBuf::from(&config.reports.reports_dir),
+        config.limits.group_event_retention_days,
     ));
-    // Report cleanup job - runs daily to clean up expired reports
-    scheduler.register(jobs::ReportCleanupJob::new(
+    // Group message cleanup job - runs daily to enforce message retention limits
+    scheduler.register(jobs::GroupMessageCleanupJob::new(
         pool.clone(),
-        std::path::PathBuf::from(&config.reports.reports_dir),
+        config.limits.group_message_retention_days,
+        config.limits.max_messages_per_group,
     ));
+    // Push token cleanup job - runs daily to delete expired push tokens
+    scheduler.register(jobs::PushTokenCleanupJob::new(pool.clone()));
+    // Trip share cleanup job - runs daily to delete long-expired share links
+    scheduler.register(jobs::TripShareCleanupJob::new(pool.clone()));
+    // Trip detection job - runs every 5 minutes for devices that opted in
+    scheduler.register(jobs::TripDetectionJob::new(pool.clone()));
+    // Commute detection job - runs daily to find recurring trips
+    scheduler.register(jobs::CommuteDetectionJob::new(pool.clone()));
+    // API usage rollup jobs - flush request counters every minute, prune daily
+    let api_usage = Arc::new(services::api_usage::ApiUsageRecorder::new());
+    scheduler.register(jobs::ApiUsageRollupJob::new(
+        pool.clone(),
+        api_usage.clone(),
+    ));
+    scheduler.register(jobs::ApiUsageCleanupJob::new(
+        pool.clone(),
+        config.limits.api_usage_retention_months,
+    ));
+    // Metrics snapshot job - writes metrics files for deployments without Prometheus
+    if config.metrics_snapshots.enabled {
+        scheduler.register(jobs::MetricsSnapshotJob::new(
+            std::path::PathBuf::from(&config.metrics_snapshots.snapshots_dir),
+            config.metrics_snapshots.interval_minutes,
+            config.metrics_snapshots.retention_days,
+        ));
+    }
+    // Report and import jobs need writable storage
+    if storage_available {
+        // Report generation job - runs every 30 seconds to process pending report jobs
+        scheduler.register(
+            jobs::ReportGenerationJob::new(
+                pool.clone(),
+                config.reports.batch_size,
+                std::path::PathBuf::from(&config.reports.reports_dir),
+            )
+            .with_timescale(config.database.timescale_enabled),
+        );
+        // Location import job - runs every 30 seconds to process uploaded history
+        scheduler.register(jobs::LocationImportJob::new(
+            pool.clone(),
+            config.location_imports.batch_size,
+            std::path::PathBuf::from(&config.location_imports.imports_dir),
+        ));
+        // Report cleanup job - runs daily to clean up expired reports
+        scheduler.register(jobs::ReportCleanupJob::new(
+            pool.clone(),
+            std::path::PathBuf::from(&config.reports.reports_dir),
+        ));
+        // Export cleanup job - runs hourly to remove expired group and organization archives
+        scheduler.register(jobs::ExportCleanupJob::new(
+            pool.clone(),
+            std::path::PathBuf::from(&config.reports.reports_dir),
+        ));
+    }
     scheduler.start();
 
     // Build application
-    let app = app::create_app(config.clone(), pool);
+    let app = app::create_app(
+        config.clone(),
+        pool.clone(),
+        preflight,
+        webhook_secrets,
+        Some(api_usage.clone()),
+        log_store,
+    );
 
     // Start server
     let addr = config.socket_addr();
@@ -111,6 +252,11 @@ async fn main() -> Result<()> {
     scheduler.shutdown();
     scheduler.wait_for_shutdown(Duration::from_secs(30)).await;
 
+    // Persist requests counted since the last rollup flush
+    if let Err(e) = api_usage.flush(&pool).await {
+        warn!("Failed to flush API usage on shutdown: {}", e);
+    }
+
     info!("Server shutdown complete");
     Ok(())
 }
@@ -0,0 +1,79 @@
+//! API usage tracking middleware.
+//!
+//! Feeds handled requests into the application's
+//! [`ApiUsageRecorder`](crate::services::api_usage::ApiUsageRecorder).
+//! Requests are attributed to the organization of the API key they were
+//! made with, or else to the organization in their path; requests without
+//! either, or that matched no route, are not counted.
+
+use axum::{
+    body::{Body, HttpBody},
+    extract::{MatchedPath, State},
+    http::{header, HeaderMap, Request},
+    middleware::Next,
+    response::Response,
+};
+use chrono::Utc;
+use std::time::Instant;
+
+use super::trace_id::path_tenant;
+use crate::app::AppState;
+use crate::extractors::api_key::ApiKeyAuth;
+use crate::services::api_usage::ApiRequestSample;
+
+/// Middleware to count requests per organization, API key and endpoint.
+pub async fn api_usage_middleware(
+    State(state): State<AppState>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let Some(recorder) = state.api_usage else {
+        return next.run(req).await;
+    };
+
+    let start = Instant::now();
+    let received_at = Utc::now();
+    let method = req.method().as_str().to_string();
+    let endpoint_path = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_string());
+    let (path_org_id, _) = path_tenant(req.uri().path());
+    let request_bytes = content_length(req.headers()).unwrap_or(0);
+
+    let response = next.run(req).await;
+
+    let Some(endpoint_path) = endpoint_path else {
+        return response;
+    };
+    let api_key = response.extensions().get::<ApiKeyAuth>();
+    let Some(organization_id) = api_key.and_then(|k| k.organization_id).or(path_org_id) else {
+        return response;
+    };
+
+    let response_bytes = content_length(response.headers())
+        .or_else(|| response.body().size_hint().exact().map(|n| n as i64))
+        .unwrap_or(0);
+
+    recorder.record(ApiRequestSample {
+        organization_id,
+        api_key_id: api_key.map(|k| k.api_key_id),
+        endpoint_path,
+        method,
+        status: response.status().as_u16(),
+        response_time_ms: start.elapsed().as_millis() as i64,
+        request_bytes,
+        response_bytes,
+        received_at,
+    });
+
+    response
+}
+
+/// Body size declared by the `Content-Length` header.
+fn content_length(headers: &HeaderMap) -> Option<i64> {
+    headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+}
@@ -22,7 +22,7 @@ use crate::extractors::api_key::ApiKeyAuth;
 /// request extensions for use by downstream handlers.
 pub async fn require_auth(
     State(state): State<AppState>,
-    mut req: Request<Body>,
+    req: Request<Body>,
     next: Next,
 ) -> Response {
     // Extract API key from header
@@ -37,11 +37,7 @@ pub async fn require_auth(
 
     // Validate the API key
     match ApiKeyAuth::validate(&state.pool, &api_key).await {
-        Ok(auth) => {
-            // Store authentication info in request extensions
-            req.extensions_mut().insert(auth);
-            next.run(req).await
-        }
+        Ok(auth) => run_authenticated(req, auth, next).await,
         Err(err) => err.into_response(),
     }
 }
@@ -54,14 +50,14 @@ pub async fn require_auth(
 #[allow(dead_code)] // Will be used in future stories
 pub async fn optional_auth(
     State(state): State<AppState>,
-    mut req: Request<Body>,
+    req: Request<Body>,
     next: Next,
 ) -> Response {
     // Try to extract API key from header
     if let Some(api_key) = req.headers().get("X-API-Key").and_then(|v| v.to_str().ok()) {
         // Validate the API key if present
         if let Ok(auth) = ApiKeyAuth::validate(&state.pool, api_key).await {
-            req.extensions_mut().insert(auth);
+            return run_authenticated(req, auth, next).await;
         }
     }
 
@@ -75,7 +71,7 @@ pub async fn optional_auth(
 #[allow(dead_code)] // Will be used in Story 4.7 (Admin Operations API)
 pub async fn require_admin(
     State(state): State<AppState>,
-    mut req: Request<Body>,
+    req: Request<Body>,
     next: Next,
 ) -> Response {
     // Extract API key from header
@@ -94,13 +90,23 @@ pub async fn require_admin(
             if !auth.is_admin {
                 return forbidden_response("Admin access required");
             }
-            req.extensions_mut().insert(auth);
-            next.run(req).await
+            run_authenticated(req, auth, next).await
         }
         Err(err) => err.into_response(),
     }
 }
 
+/// Runs the request with the authenticated key in its extensions.
+///
+/// The key is also attached to the response so that outer layers, such as
+/// API usage tracking, can attribute the request to it.
+async fn run_authenticated(mut req: Request<Body>, auth: ApiKeyAuth, next: Next) -> Response {
+    req.extensions_mut().insert(auth.clone());
+    let mut response = next.run(req).await;
+    response.extensions_mut().insert(auth);
+    response
+}
+
 /// Helper to create unauthorized response.
 fn unauthorized_response(message: &str) -> Response {
     (
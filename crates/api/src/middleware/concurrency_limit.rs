@@ -0,0 +1,214 @@
+//! Per-route-class concurrency limits for expensive endpoints.
+//!
+//! Exports, analytics and trip playback can hold database connections and
+//! CPU for seconds each. Such routes are wrapped in [`concurrency_limit`]
+//! with their [`RouteClass`], and every class gets its own semaphore; a
+//! request waits up to the queue timeout for a permit and is otherwise
+//! rejected with 503 and `Retry-After`, so a burst of heavy reporting cannot
+//! starve location ingest and other interactive routes. Cheap routes next to
+//! expensive ones, such as polling an export job, are not wrapped.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    body::Body, extract::State, http::Request, middleware::Next, response::IntoResponse,
+    response::Response,
+};
+use tokio::sync::Semaphore;
+use tracing::warn;
+
+use crate::config::ConcurrencyConfig;
+use crate::error::ApiError;
+
+/// Expensive route classes with their own concurrency limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteClass {
+    /// Data exports and report generation.
+    Export,
+    /// Analytics and dashboard aggregations.
+    Analytics,
+    /// Trip and event replay.
+    Playback,
+}
+
+impl RouteClass {
+    /// Metric label of the class.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RouteClass::Export => "export",
+            RouteClass::Analytics => "analytics",
+            RouteClass::Playback => "playback",
+        }
+    }
+}
+
+/// Semaphores and timeouts used by [`concurrency_limit`], installed as a
+/// request extension when concurrency limits are enabled.
+#[derive(Debug, Clone)]
+pub struct ConcurrencyLimits {
+    export: Arc<Semaphore>,
+    analytics: Arc<Semaphore>,
+    playback: Arc<Semaphore>,
+    queue_timeout: Duration,
+    retry_after_secs: u64,
+}
+
+impl ConcurrencyLimits {
+    /// Create the limits from configuration.
+    pub fn new(config: &ConcurrencyConfig) -> Self {
+        Self {
+            export: Arc::new(Semaphore::new(config.export_limit)),
+            analytics: Arc::new(Semaphore::new(config.analytics_limit)),
+            playback: Arc::new(Semaphore::new(config.playback_limit)),
+            queue_timeout: Duration::from_millis(config.queue_timeout_ms),
+            retry_after_secs: config.retry_after_secs,
+        }
+    }
+
+    fn semaphore(&self, class: RouteClass) -> &Arc<Semaphore> {
+        match class {
+            RouteClass::Export => &self.export,
+            RouteClass::Analytics => &self.analytics,
+            RouteClass::Playback => &self.playback,
+        }
+    }
+}
+
+/// Middleware that limits concurrent requests to a route of a class.
+///
+/// Attached per route with
+/// `middleware::from_fn_with_state(RouteClass::Export, concurrency_limit)`.
+/// Requests pass unlimited when no [`ConcurrencyLimits`] extension is
+/// installed. The permit is held until the response is produced; streamed
+/// bodies finish outside the limit.
+pub async fn concurrency_limit(
+    State(class): State<RouteClass>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let Some(limits) = req.extensions().get::<ConcurrencyLimits>().cloned() else {
+        return next.run(req).await;
+    };
+
+    let semaphore = limits.semaphore(class).clone();
+    let permit = match tokio::time::timeout(limits.queue_timeout, semaphore.acquire_owned()).await {
+        Ok(Ok(permit)) => permit,
+        // Timed out waiting, or the semaphore was closed
+        _ => {
+            metrics::counter!("concurrency_limit_rejections_total", "class" => class.as_str())
+                .increment(1);
+            warn!(
+                class = class.as_str(),
+                path = %req.uri().path(),
+                "Concurrency limit reached, rejecting request"
+            );
+            return ApiError::ServiceUnavailableWithRetry {
+                message: "Server is busy with other requests of this kind, try again later"
+                    .to_string(),
+                retry_after: limits.retry_after_secs,
+            }
+            .into_response();
+        }
+    };
+
+    let response = next.run(req).await;
+    drop(permit);
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{http::StatusCode, middleware, routing::get, Extension, Router};
+    use tower::ServiceExt;
+
+    fn config(limit: usize) -> ConcurrencyConfig {
+        ConcurrencyConfig {
+            enabled: true,
+            export_limit: limit,
+            analytics_limit: limit,
+            playback_limit: limit,
+            queue_timeout_ms: 50,
+            retry_after_secs: 7,
+        }
+    }
+
+    /// Router with a playback route, an export route and an unlimited
+    /// route, limited by `limits`.
+    fn app(limits: ConcurrencyLimits) -> Router {
+        Router::new()
+            .route(
+                "/replay",
+                get(|| async { "ok" }).layer(middleware::from_fn_with_state(
+                    RouteClass::Playback,
+                    concurrency_limit,
+                )),
+            )
+            .route(
+                "/export",
+                get(|| async { "ok" }).layer(middleware::from_fn_with_state(
+                    RouteClass::Export,
+                    concurrency_limit,
+                )),
+            )
+            .route("/export/:job_id", get(|| async { "ok" }))
+            .layer(Extension(limits))
+    }
+
+    async fn status(app: &Router, path: &str) -> StatusCode {
+        app.clone()
+            .oneshot(Request::get(path).body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn test_rejects_when_saturated() {
+        let limits = ConcurrencyLimits::new(&config(1));
+        let app = app(limits.clone());
+
+        // Hold the only playback and export permits
+        let _playback = limits.playback.clone().acquire_owned().await.unwrap();
+        let _export = limits.export.clone().acquire_owned().await.unwrap();
+
+        let response = app
+            .clone()
+            .oneshot(Request::get("/replay").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers().get("retry-after").unwrap(), "7");
+        assert_eq!(
+            status(&app, "/export").await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+
+        // Polling next to a limited route is not limited
+        assert_eq!(status(&app, "/export/job").await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_releases_permit_after_response() {
+        let limits = ConcurrencyLimits::new(&config(1));
+        let app = app(limits.clone());
+
+        for _ in 0..3 {
+            assert_eq!(status(&app, "/export").await, StatusCode::OK);
+        }
+        assert_eq!(limits.export.available_permits(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_unlimited_without_limits_extension() {
+        let app: Router = Router::new().route(
+            "/replay",
+            get(|| async { "ok" }).layer(middleware::from_fn_with_state(
+                RouteClass::Playback,
+                concurrency_limit,
+            )),
+        );
+        assert_eq!(status(&app, "/replay").await, StatusCode::OK);
+    }
+}
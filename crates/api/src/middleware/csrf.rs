@@ -0,0 +1,82 @@
+//! CSRF protection for cookie-authenticated requests.
+//!
+//! When cookie authentication is enabled, the browser attaches the token
+//! cookies to any request, including ones triggered by other sites. Mutating
+//! requests that carry an authentication cookie must therefore echo the CSRF
+//! cookie in the CSRF header (double submit). Requests authenticated only by
+//! an `Authorization` header or API key carry no cookie and are unaffected.
+
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{Method, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use tracing::debug;
+
+use crate::error::ApiError;
+use crate::services::CookieHelper;
+
+/// Endpoints that do not authenticate with cookies and may be called while
+/// stale cookies are still present.
+const CSRF_EXEMPT_PATHS: &[&str] = &[
+    "/api/v1/auth/login",
+    "/api/v1/auth/register",
+    "/api/v1/auth/oauth",
+    "/api/v1/auth/forgot-password",
+    "/api/v1/auth/reset-password",
+    "/api/v1/auth/verify-email",
+];
+
+/// Whether a request must present a valid CSRF token.
+fn requires_csrf_token(method: &Method, path: &str) -> bool {
+    !matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::OPTIONS | Method::TRACE
+    ) && !CSRF_EXEMPT_PATHS.contains(&path)
+}
+
+/// Middleware that rejects cookie-authenticated mutating requests without a
+/// matching CSRF token with 403 Forbidden.
+pub async fn csrf_protection(
+    State(cookies): State<Arc<CookieHelper>>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    if cookies.csrf_enabled()
+        && requires_csrf_token(req.method(), req.uri().path())
+        && cookies.has_auth_cookie(req.headers())
+        && !cookies.verify_csrf(req.headers())
+    {
+        debug!(
+            method = %req.method(),
+            path = %req.uri().path(),
+            "Rejected cookie-authenticated request without valid CSRF token"
+        );
+        return ApiError::Forbidden(format!(
+            "Missing or invalid CSRF token ({} header)",
+            cookies.csrf_header_name()
+        ))
+        .into_response();
+    }
+
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requires_csrf_token() {
+        assert!(requires_csrf_token(&Method::POST, "/api/v1/auth/logout"));
+        assert!(requires_csrf_token(&Method::DELETE, "/api/v1/devices/x"));
+        assert!(requires_csrf_token(&Method::PATCH, "/api/v1/users/me"));
+        assert!(!requires_csrf_token(&Method::GET, "/api/v1/users/me"));
+        assert!(!requires_csrf_token(&Method::OPTIONS, "/api/v1/users/me"));
+        assert!(!requires_csrf_token(&Method::POST, "/api/v1/auth/login"));
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! These middleware functions check configuration to determine if a feature
 //! is enabled. When disabled, they return 404 "Feature not available".
+//! Features whose dependency failed its startup preflight check return 503.
 
 use axum::{
     body::Body,
@@ -14,6 +15,7 @@ use axum::{
 use serde_json::json;
 
 use crate::app::AppState;
+use crate::preflight::Dependency;
 
 /// Helper to create a feature disabled response (404).
 fn feature_disabled_response(feature_name: &str) -> Response {
@@ -27,6 +29,18 @@ fn feature_disabled_response(feature_name: &str) -> Response {
         .into_response()
 }
 
+/// Helper to create a degraded dependency response (503).
+fn dependency_degraded_response(feature_name: &str) -> Response {
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(json!({
+            "error": "service_degraded",
+            "message": format!("{} are unavailable: storage is not writable", feature_name)
+        })),
+    )
+        .into_response()
+}
+
 /// Middleware that checks if geofences feature is enabled.
 ///
 /// When `features.geofences_enabled` is false, returns 404.
@@ -113,6 +127,34 @@ pub async fn require_geofence_events(
     next.run(req).await
 }
 
+/// Middleware that checks if self-service organization creation is enabled.
+///
+/// When `features.self_service_orgs_enabled` is false, returns 404.
+pub async fn require_self_service_orgs(
+    State(state): State<AppState>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    if !state.config.features.self_service_orgs_enabled {
+        return feature_disabled_response("Self-service organization");
+    }
+    next.run(req).await
+}
+
+/// Middleware that checks that report and import storage passed preflight.
+///
+/// When the `blob_storage` dependency is degraded, returns 503.
+pub async fn require_blob_storage(
+    State(state): State<AppState>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    if state.preflight.is_degraded(Dependency::BlobStorage) {
+        return dependency_degraded_response("Reports and location imports");
+    }
+    next.run(req).await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -122,4 +164,10 @@ mod tests {
         let response = feature_disabled_response("Test Feature");
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
+
+    #[test]
+    fn test_dependency_degraded_response() {
+        let response = dependency_degraded_response("Reports");
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
 }
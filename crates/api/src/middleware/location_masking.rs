@@ -0,0 +1,148 @@
+//! Observer location masking middleware.
+//!
+//! Organization observers only get imprecise device locations. Instead of
+//! every handler masking its own response, this middleware resolves the
+//! caller's role in the organization from the request path and masks the
+//! locations in successful JSON responses for roles that require it.
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::{FromRequestParts, State},
+    http::{header, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use domain::models::mask_json_locations;
+use persistence::repositories::OrgUserRepository;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::error::ApiError;
+use crate::extractors::UserAuth;
+
+/// Middleware that masks response locations for observers of the
+/// organization in the path.
+///
+/// Requests without an organization ID or a valid user token pass through;
+/// the handler rejects them if they need either.
+pub async fn mask_observer_locations(
+    State(state): State<AppState>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let Some(org_id) = extract_org_id_from_path(req.uri().path()) else {
+        return next.run(req).await;
+    };
+
+    let (mut parts, body) = req.into_parts();
+    let user = UserAuth::from_request_parts(&mut parts, &state).await.ok();
+    let response = next.run(Request::from_parts(parts, body)).await;
+
+    let Some(user) = user else {
+        return response;
+    };
+    if !response.status().is_success() || !is_json(&response) {
+        return response;
+    }
+
+    let org_user = match OrgUserRepository::new(state.pool.clone())
+        .find_by_org_and_user(org_id, user.user_id)
+        .await
+    {
+        Ok(org_user) => org_user,
+        Err(e) => {
+            error!(error = %e, %org_id, "Failed to resolve organization role for location masking");
+            return ApiError::Internal("Failed to resolve organization role".to_string())
+                .into_response();
+        }
+    };
+    match org_user {
+        Some(org_user) if org_user.role.masks_locations() => mask_response(response).await,
+        _ => response,
+    }
+}
+
+/// Mask the locations in a JSON response body.
+async fn mask_response(response: Response) -> Response {
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            error!(error = %e, "Failed to read response body for location masking");
+            return ApiError::Internal("Failed to read response".to_string()).into_response();
+        }
+    };
+    let mut value: serde_json::Value = match serde_json::from_slice(&bytes) {
+        Ok(value) => value,
+        Err(e) => {
+            error!(error = %e, "Failed to parse response body for location masking");
+            return ApiError::Internal("Failed to read response".to_string()).into_response();
+        }
+    };
+
+    mask_json_locations(&mut value);
+
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(value.to_string()))
+}
+
+/// Whether a response carries a JSON body.
+fn is_json(response: &Response) -> bool {
+    response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"))
+}
+
+/// Extract org_id from the request path.
+/// Expects paths like /api/admin/v1/organizations/:org_id/...
+fn extract_org_id_from_path(path: &str) -> Option<Uuid> {
+    let mut segments = path.split('/');
+    segments.find(|segment| *segment == "organizations")?;
+    segments.next().and_then(|id| Uuid::parse_str(id).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::Json;
+    use serde_json::json;
+
+    #[test]
+    fn test_extract_org_id_from_path() {
+        let org_id = Uuid::new_v4();
+        assert_eq!(
+            extract_org_id_from_path(&format!(
+                "/api/admin/v1/organizations/{org_id}/devices/7/location-history"
+            )),
+            Some(org_id)
+        );
+        assert_eq!(
+            extract_org_id_from_path("/api/admin/v1/organizations"),
+            None
+        );
+        assert_eq!(
+            extract_org_id_from_path("/api/admin/v1/organizations/not-a-uuid"),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mask_response() {
+        let response = Json(json!({
+            "location": { "latitude": 48.148_612, "longitude": 17.107_748, "speed": 3.0 }
+        }))
+        .into_response();
+        assert!(is_json(&response));
+
+        let masked = mask_response(response).await;
+        let body = to_bytes(masked.into_body(), usize::MAX).await.unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            value,
+            json!({ "location": { "latitude": 48.15, "longitude": 17.11 } })
+        );
+    }
+}
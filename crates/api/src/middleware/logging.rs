@@ -1,5 +1,7 @@
 //! Logging initialization and configuration.
 
+use std::sync::Arc;
+
 use tracing_subscriber::{
     fmt::{self, format::FmtSpan},
     layer::SubscriberExt,
@@ -8,13 +10,23 @@ use tracing_subscriber::{
 };
 
 use crate::config::LoggingConfig;
+use crate::log_store::{LogStore, LogStoreLayer};
 
 /// Initializes the logging subsystem based on configuration.
-pub fn init_logging(config: &LoggingConfig) {
+///
+/// Unless `store_capacity` is 0, log events attributed to an organization
+/// are also kept in a rolling tenant log store, which is returned for the
+/// application state.
+pub fn init_logging(config: &LoggingConfig) -> Option<Arc<LogStore>> {
     let env_filter =
         EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(&config.level));
 
-    let subscriber = tracing_subscriber::registry().with(env_filter);
+    let store = (config.store_capacity > 0).then(|| Arc::new(LogStore::new(config.store_capacity)));
+    let store_layer = store.clone().map(LogStoreLayer::new);
+
+    let subscriber = tracing_subscriber::registry()
+        .with(env_filter)
+        .with(store_layer);
 
     match config.format.as_str() {
         "json" => {
@@ -33,4 +45,5 @@ pub fn init_logging(config: &LoggingConfig) {
             subscriber.with(pretty_layer).init();
         }
     }
+    store
 }
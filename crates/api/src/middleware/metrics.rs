@@ -117,10 +117,7 @@ pub fn record_migration_failure(duration_secs: f64, reason: &str) {
 
 /// Handler for /metrics endpoint that returns Prometheus text format.
 pub async fn metrics_handler() -> impl IntoResponse {
-    // Get the handle from the global recorder
-    // This is set up during app initialization
-    if let Some(handle) = PROMETHEUS_HANDLE.get() {
-        let output = handle.render();
+    if let Some(output) = render_metrics() {
         (
             axum::http::StatusCode::OK,
             [(
@@ -138,6 +135,14 @@ pub async fn metrics_handler() -> impl IntoResponse {
     }
 }
 
+/// Render all metrics in the Prometheus text format.
+///
+/// Returns `None` until [`init_metrics`] has been called.
+pub fn render_metrics() -> Option<String> {
+    // The handle of the global recorder is set up during app initialization
+    PROMETHEUS_HANDLE.get().map(|handle| handle.render())
+}
+
 use std::sync::OnceLock;
 
 static PROMETHEUS_HANDLE: OnceLock<metrics_exporter_prometheus::PrometheusHandle> = OnceLock::new();
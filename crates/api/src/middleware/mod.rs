@@ -1,36 +1,52 @@
 //! HTTP middleware components.
 
+pub mod api_usage;
 pub mod auth;
+pub mod concurrency_limit;
+pub mod csrf;
 pub mod features;
+pub mod location_masking;
 pub mod logging;
 pub mod metrics;
 pub mod rate_limit;
 pub mod rbac;
 pub mod security_headers;
+pub mod statement_timeout;
 pub mod system_rbac;
 pub mod trace_id;
 pub mod user_auth;
 pub mod version_check;
 
+#[allow(unused_imports)] // Re-exports for downstream use
+pub use api_usage::api_usage_middleware;
 #[allow(unused_imports)] // Re-exports for downstream use
 pub use auth::{optional_auth, require_admin, require_auth};
 #[allow(unused_imports)] // Re-exports for downstream use
+pub use concurrency_limit::{concurrency_limit, ConcurrencyLimits, RouteClass};
+#[allow(unused_imports)] // Re-exports for downstream use
+pub use csrf::csrf_protection;
+#[allow(unused_imports)] // Re-exports for downstream use
 pub use features::{
-    require_b2b, require_geofence_events, require_geofences, require_movement_tracking,
-    require_proximity_alerts, require_webhooks,
+    require_b2b, require_blob_storage, require_geofence_events, require_geofences,
+    require_movement_tracking, require_proximity_alerts, require_self_service_orgs,
+    require_webhooks,
 };
 #[allow(unused_imports)] // Re-exports for downstream use
+pub use location_masking::mask_observer_locations;
+#[allow(unused_imports)] // Re-exports for downstream use
 pub use metrics::{init_metrics, metrics_handler, metrics_middleware};
 #[allow(unused_imports)] // Re-exports for downstream use
 pub use rate_limit::{
     auth_rate_limit_middleware, rate_limit_middleware, AuthRateLimiterState,
-    ExportRateLimiterState, RateLimiterState,
+    ExportRateLimiterState, GroupTokenRateLimiterState, RateLimiterState,
 };
 #[allow(unused_imports)] // Re-exports for downstream use
 pub use rbac::{require_group_admin, require_group_member, require_group_owner, GroupMembership};
 #[allow(unused_imports)] // Re-exports for downstream use
 pub use security_headers::security_headers_middleware;
 #[allow(unused_imports)] // Re-exports for downstream use
+pub use statement_timeout::{export_statement_timeout, statement_timeout, StatementTimeouts};
+#[allow(unused_imports)] // Re-exports for downstream use
 pub use system_rbac::{
     require_any_system_role, require_org_admin, require_org_manager, require_super_admin,
     require_support, require_viewer, SystemRoleAuth,
@@ -3,6 +3,7 @@
 //! Provides per-API-key rate limiting using a sliding window algorithm.
 //! Also provides per-organization export rate limiting for audit log exports.
 //! Also provides per-IP rate limiting for authentication endpoints.
+//! Also provides per-token rate limiting for group API tokens.
 
 use axum::{
     body::Body,
@@ -202,6 +203,78 @@ impl Clone for ExportRateLimiterState {
     }
 }
 
+/// Type alias for the rate limiter used per group API token.
+type GroupTokenRateLimiter = GovRateLimiter<NotKeyed, InMemoryState, DefaultClock>;
+
+/// Rate limiter state for group API tokens.
+/// Unlike API keys, every token carries its own limit per minute; a token's
+/// limiter is rebuilt if its limit differs from the one it was created with.
+#[derive(Default)]
+pub struct GroupTokenRateLimiterState {
+    limiters: RwLock<HashMap<Uuid, (u32, Arc<GroupTokenRateLimiter>)>>,
+}
+
+impl GroupTokenRateLimiterState {
+    /// Create an empty group token rate limiter state.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get or create the rate limiter for a token with the given limit.
+    fn get_or_create_limiter(
+        &self,
+        token_id: Uuid,
+        rate_limit_per_minute: u32,
+    ) -> Arc<GroupTokenRateLimiter> {
+        {
+            let limiters = self.limiters.read().unwrap();
+            if let Some((limit, limiter)) = limiters.get(&token_id) {
+                if *limit == rate_limit_per_minute {
+                    return limiter.clone();
+                }
+            }
+        }
+
+        let mut limiters = self.limiters.write().unwrap();
+        if let Some((limit, limiter)) = limiters.get(&token_id) {
+            if *limit == rate_limit_per_minute {
+                return limiter.clone();
+            }
+        }
+
+        let quota = Quota::per_minute(
+            NonZeroU32::new(rate_limit_per_minute).unwrap_or(NonZeroU32::new(1).unwrap()),
+        );
+        let limiter = Arc::new(GovRateLimiter::direct(quota));
+        limiters.insert(token_id, (rate_limit_per_minute, limiter.clone()));
+        limiter
+    }
+
+    /// Check if a request with the given token should be allowed.
+    /// Returns Ok(()) if allowed, or Err with retry_after seconds if rate limited.
+    pub fn check(&self, token_id: Uuid, rate_limit_per_minute: u32) -> Result<(), u64> {
+        let limiter = self.get_or_create_limiter(token_id, rate_limit_per_minute);
+
+        match limiter.check() {
+            Ok(_) => Ok(()),
+            Err(not_until) => {
+                let wait_time = not_until.wait_time_from(governor::clock::Clock::now(
+                    &governor::clock::DefaultClock::default(),
+                ));
+                Err(wait_time.as_secs().max(1))
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for GroupTokenRateLimiterState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GroupTokenRateLimiterState")
+            .field("active_limiters", &self.limiters.read().unwrap().len())
+            .finish()
+    }
+}
+
 /// Type alias for the rate limiter used per IP address for auth endpoints.
 type IpRateLimiter = GovRateLimiter<NotKeyed, InMemoryState, DefaultClock>;
 
@@ -956,4 +1029,34 @@ mod tests {
             assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
         }
     }
+
+    // ===========================================
+    // Group Token Rate Limiter Tests
+    // ===========================================
+
+    #[test]
+    fn test_group_token_rate_limiter_uses_token_limit() {
+        let state = GroupTokenRateLimiterState::new();
+        let small = Uuid::new_v4();
+        let large = Uuid::new_v4();
+
+        assert!(state.check(small, 1).is_ok());
+        assert!(state.check(small, 1).unwrap_err() >= 1);
+
+        for _ in 0..5 {
+            assert!(state.check(large, 5).is_ok());
+        }
+        assert!(state.check(large, 5).is_err());
+    }
+
+    #[test]
+    fn test_group_token_rate_limiter_rebuilt_on_limit_change() {
+        let state = GroupTokenRateLimiterState::new();
+        let token_id = Uuid::new_v4();
+
+        assert!(state.check(token_id, 1).is_ok());
+        assert!(state.check(token_id, 1).is_err());
+        // Raising the limit starts a fresh window
+        assert!(state.check(token_id, 10).is_ok());
+    }
 }
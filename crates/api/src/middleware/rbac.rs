@@ -13,11 +13,14 @@ use axum::{
     Json,
 };
 use domain::models::group::GroupRole;
-use persistence::repositories::GroupRepository;
+use domain::models::{GroupPermission, GroupPermissions};
+use persistence::repositories::{GroupCustomRoleRepository, GroupRepository};
 use serde_json::json;
+use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::app::AppState;
+use crate::error::ApiError;
 use crate::middleware::user_auth::UserAuth;
 
 /// Group membership information passed to handlers via request extensions.
@@ -27,6 +30,8 @@ pub struct GroupMembership {
     pub group_id: Uuid,
     /// The user's role in the group.
     pub role: GroupRole,
+    /// The user's effective permissions, including any custom role.
+    pub permissions: GroupPermissions,
     /// The membership ID.
     pub membership_id: Uuid,
 }
@@ -120,6 +125,20 @@ async fn require_group_role_impl(
     };
 
     let user_role: GroupRole = membership.role.into();
+    let permissions = match resolve_group_permissions(
+        &state.pool,
+        group_id,
+        user_auth.user_id,
+        user_role,
+    )
+    .await
+    {
+        Ok(permissions) => permissions,
+        Err(e) => {
+            tracing::error!("Database error resolving group permissions: {}", e);
+            return internal_error_response("Failed to verify group membership");
+        }
+    };
 
     // Check role requirement if specified
     if let Some(required_role) = min_role {
@@ -135,12 +154,53 @@ async fn require_group_role_impl(
     req.extensions_mut().insert(GroupMembership {
         group_id,
         role: user_role,
+        permissions,
         membership_id: membership.id,
     });
 
     next.run(req).await
 }
 
+/// Resolve a group member's effective permissions.
+///
+/// Members and viewers holding a custom role get the role's permissions
+/// instead of their built-in ones.
+pub async fn resolve_group_permissions(
+    pool: &PgPool,
+    group_id: Uuid,
+    user_id: Uuid,
+    role: GroupRole,
+) -> Result<GroupPermissions, sqlx::Error> {
+    if !matches!(role, GroupRole::Member | GroupRole::Viewer) {
+        return Ok(GroupPermissions::for_role(role));
+    }
+    let custom = GroupCustomRoleRepository::new(pool.clone())
+        .find_for_member(group_id, user_id)
+        .await?;
+    Ok(GroupPermissions::resolve(
+        role,
+        custom.map(|r| r.permissions()),
+    ))
+}
+
+/// Fail with 403 unless a group member holds `permission`.
+pub async fn require_group_permission(
+    pool: &PgPool,
+    group_id: Uuid,
+    user_id: Uuid,
+    role: GroupRole,
+    permission: GroupPermission,
+) -> Result<(), ApiError> {
+    let permissions = resolve_group_permissions(pool, group_id, user_id, role).await?;
+    if !permissions.contains(permission) {
+        return Err(ApiError::Forbidden(format!(
+            "Missing group permission: {}",
+            permission
+        )));
+    }
+    Ok(())
+}
+
 /// Checks if user_role is at least as privileged as required_role.
 fn has_sufficient_role(user_role: &GroupRole, required_role: &GroupRole) -> bool {
     match required_role {
@@ -327,6 +387,7 @@ mod tests {
         let membership = GroupMembership {
             group_id: Uuid::new_v4(),
             role: GroupRole::Admin,
+            permissions: GroupPermissions::all(),
             membership_id: Uuid::new_v4(),
         };
         assert_eq!(membership.role, GroupRole::Admin);
@@ -337,6 +398,7 @@ mod tests {
         let membership = GroupMembership {
             group_id: Uuid::new_v4(),
             role: GroupRole::Member,
+            permissions: GroupPermissions::for_role(GroupRole::Member),
             membership_id: Uuid::new_v4(),
         };
         let cloned = membership.clone();
@@ -0,0 +1,104 @@
+//! Per-request database statement timeout middleware.
+//!
+//! Scopes every request to a `statement_timeout` that the pool applies to
+//! each connection the handler checks out, so one pathological query cannot
+//! hold a connection for minutes and starve the pool. Export and report
+//! routes are wrapped in [`export_statement_timeout`] and get a looser limit
+//! than interactive ones.
+
+use axum::{body::Body, extract::State, http::Request, middleware::Next, response::Response};
+use std::time::Duration;
+
+/// Statement timeouts applied by [`statement_timeout`], installed as a
+/// request extension for [`export_statement_timeout`].
+#[derive(Debug, Clone, Copy)]
+pub struct StatementTimeouts {
+    pub interactive: Duration,
+    pub export: Duration,
+}
+
+/// Middleware that runs the request under the interactive statement timeout.
+pub async fn statement_timeout(
+    State(timeouts): State<StatementTimeouts>,
+    mut req: Request<Body>,
+    next: Next,
+) -> Response {
+    req.extensions_mut().insert(timeouts);
+    persistence::db::with_statement_timeout(timeouts.interactive, next.run(req)).await
+}
+
+/// Middleware that runs an export or report route under the export
+/// statement timeout.
+///
+/// Attached per route with `middleware::from_fn(export_statement_timeout)`.
+/// Requests keep the interactive timeout when no [`StatementTimeouts`]
+/// extension is installed.
+pub async fn export_statement_timeout(req: Request<Body>, next: Next) -> Response {
+    match req.extensions().get::<StatementTimeouts>() {
+        Some(timeouts) => {
+            persistence::db::with_statement_timeout(timeouts.export, next.run(req)).await
+        }
+        None => next.run(req).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::to_bytes, middleware, routing::get, Router};
+    use tower::ServiceExt;
+
+    /// Router with an interactive and an export route, each answering with
+    /// the statement timeout it ran under.
+    fn app() -> Router {
+        let timeout = || async {
+            persistence::db::current_statement_timeout()
+                .map(|t| t.as_secs().to_string())
+                .unwrap_or_default()
+        };
+        Router::new()
+            .route("/devices", get(timeout))
+            .route(
+                "/export",
+                get(timeout).layer(middleware::from_fn(export_statement_timeout)),
+            )
+            .layer(middleware::from_fn_with_state(
+                StatementTimeouts {
+                    interactive: Duration::from_secs(5),
+                    export: Duration::from_secs(30),
+                },
+                statement_timeout,
+            ))
+    }
+
+    async fn timeout_of(app: &Router, path: &str) -> String {
+        let response = app
+            .clone()
+            .oneshot(Request::get(path).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_route_timeouts() {
+        let app = app();
+        assert_eq!(timeout_of(&app, "/devices").await, "5");
+        assert_eq!(timeout_of(&app, "/export").await, "30");
+    }
+
+    #[tokio::test]
+    async fn test_export_timeout_without_extension() {
+        let app = Router::new().route(
+            "/export",
+            get(|| async {
+                persistence::db::current_statement_timeout()
+                    .is_none()
+                    .to_string()
+            })
+            .layer(middleware::from_fn(export_statement_timeout)),
+        );
+        assert_eq!(timeout_of(&app, "/export").await, "true");
+    }
+}
@@ -8,6 +8,7 @@ use axum::{
     middleware::Next,
     response::Response,
 };
+use tracing::Instrument;
 use uuid::Uuid;
 
 /// Header name for request ID.
@@ -38,30 +39,40 @@ pub async fn trace_id(mut req: Request<Body>, next: Next) -> Response {
     // Store in request extensions for handlers
     req.extensions_mut().insert(RequestId(request_id.clone()));
 
-    // Create a tracing span with the request ID
+    // Create a tracing span with the request ID and the requested tenant
     let span = tracing::info_span!(
         "request",
         request_id = %request_id,
         method = %req.method(),
         path = %req.uri().path(),
+        org_id = tracing::field::Empty,
+        group_id = tracing::field::Empty,
     );
+    let (org_id, group_id) = path_tenant(req.uri().path());
+    if let Some(org_id) = org_id {
+        span.record("org_id", tracing::field::display(org_id));
+    }
+    if let Some(group_id) = group_id {
+        span.record("group_id", tracing::field::display(group_id));
+    }
 
     // Execute the request within the span
-    let _guard = span.enter();
     let start = std::time::Instant::now();
 
-    let mut response = next.run(req).await;
+    let mut response = next.run(req).instrument(span.clone()).await;
 
     // Log request completion
     let duration_ms = start.elapsed().as_millis();
     let status = response.status().as_u16();
 
-    tracing::info!(
-        request_id = %request_id,
-        status = status,
-        duration_ms = duration_ms,
-        "Request completed"
-    );
+    span.in_scope(|| {
+        tracing::info!(
+            request_id = %request_id,
+            status = status,
+            duration_ms = duration_ms,
+            "Request completed"
+        )
+    });
 
     // Add request ID to response headers
     if let Ok(header_value) = HeaderValue::from_str(&request_id) {
@@ -73,6 +84,25 @@ pub async fn trace_id(mut req: Request<Body>, next: Next) -> Response {
     response
 }
 
+/// Extracts the organization and group a request path is scoped to, from
+/// `/organizations/{uuid}` and `/groups/{uuid}` segments.
+pub(crate) fn path_tenant(path: &str) -> (Option<Uuid>, Option<Uuid>) {
+    let mut org_id = None;
+    let mut group_id = None;
+    let mut segments = path.split('/');
+    while let Some(segment) = segments.next() {
+        let slot = match segment {
+            "organizations" => &mut org_id,
+            "groups" => &mut group_id,
+            _ => continue,
+        };
+        if let Some(id) = segments.next().and_then(|s| Uuid::parse_str(s).ok()) {
+            *slot = Some(id);
+        }
+    }
+    (org_id, group_id)
+}
+
 /// Extracts the request ID from request extensions.
 ///
 /// Returns the request ID if present, or a placeholder if not.
@@ -129,6 +159,25 @@ mod tests {
         assert_eq!(get_request_id(&extensions), uuid_str);
     }
 
+    #[test]
+    fn test_path_tenant() {
+        let org = "550e8400-e29b-41d4-a716-446655440000";
+        let group = "6ba7b810-9dad-11d1-80b4-00c04fd430c8";
+
+        let (org_id, group_id) = path_tenant(&format!(
+            "/api/admin/v1/organizations/{org}/groups/{group}/members"
+        ));
+        assert_eq!(org_id, Uuid::parse_str(org).ok());
+        assert_eq!(group_id, Uuid::parse_str(group).ok());
+
+        let (org_id, group_id) = path_tenant(&format!("/api/v1/groups/{group}"));
+        assert_eq!(org_id, None);
+        assert_eq!(group_id, Uuid::parse_str(group).ok());
+
+        assert_eq!(path_tenant("/api/v1/groups/join"), (None, None));
+        assert_eq!(path_tenant("/api/admin/v1/organizations"), (None, None));
+    }
+
     #[test]
     fn test_request_id_header_constant() {
         assert_eq!(REQUEST_ID_HEADER, "X-Request-ID");
@@ -5,6 +5,7 @@
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
+    middleware,
     response::IntoResponse,
     routing::{delete, get, post, put},
     Json, Router,
@@ -17,15 +18,17 @@ use validator::Validate;
 use crate::app::AppState;
 use crate::error::ApiError;
 use crate::extractors::UserAuth;
+use crate::middleware::{concurrency_limit, RouteClass};
 
 use chrono::{TimeZone, Utc};
 
+use domain::models::admin_geofence::normalize_geofence_tags;
 use domain::models::{
     AdminGeofenceEventInfo, AdminGeofenceEventsQuery, AdminGeofenceEventsResponse,
     AdminGeofenceInfo, AdminGeofenceListResponse, AdminGeofencePagination, AdminGeofenceQuery,
     AdminLocationAnalyticsResponse, CreateAdminGeofenceRequest, CreateAdminGeofenceResponse,
-    DeleteAdminGeofenceResponse, GeofenceVisitCount, LocationAnalyticsSummary, OrgUserRole,
-    UpdateAdminGeofenceRequest, UpdateAdminGeofenceResponse,
+    DeleteAdminGeofenceResponse, GeofenceCategoryCount, GeofenceVisitCount,
+    LocationAnalyticsSummary, OrgUserRole, UpdateAdminGeofenceRequest, UpdateAdminGeofenceResponse,
 };
 
 /// Create admin geofence management routes.
@@ -45,7 +48,13 @@ pub fn geofence_events_router() -> Router<AppState> {
 
 /// Create location analytics routes.
 pub fn location_analytics_router() -> Router<AppState> {
-    Router::new().route("/", get(get_location_analytics))
+    Router::new().route(
+        "/",
+        get(get_location_analytics).layer(middleware::from_fn_with_state(
+            RouteClass::Analytics,
+            concurrency_limit,
+        )),
+    )
 }
 
 /// List admin geofences in organization.
@@ -85,21 +94,13 @@ async fn list_geofences(
     let offset = (page - 1) * per_page;
 
     // Get total count
-    let total = geofence_repo
-        .count_geofences(org_id, query.active, query.search.as_deref())
-        .await?;
+    let total = geofence_repo.count_geofences(org_id, &query).await?;
 
     let total_pages = ((total as f64) / (per_page as f64)).ceil() as u32;
 
     // Get geofences
     let geofences = geofence_repo
-        .list_geofences(
-            org_id,
-            query.active,
-            query.search.as_deref(),
-            per_page,
-            offset,
-        )
+        .list_geofences(org_id, &query, per_page, offset)
         .await?;
 
     // Map to response
@@ -116,6 +117,8 @@ async fn list_geofences(
             event_types: g.event_types,
             active: g.active,
             color: g.color,
+            category: g.category,
+            tags: g.tags,
             metadata: g.metadata,
             created_by: g.created_by,
             creator_name: g.creator_name,
@@ -194,6 +197,9 @@ async fn create_geofence(
         ));
     }
 
+    let category = request.category.as_deref().map(str::trim);
+    let tags = normalize_geofence_tags(&request.tags);
+
     // Create geofence
     let geofence = geofence_repo
         .create_geofence(
@@ -205,6 +211,8 @@ async fn create_geofence(
             request.radius_meters,
             &request.event_types,
             request.color.as_deref(),
+            category,
+            &tags,
             request.metadata.as_ref(),
             user.user_id,
         )
@@ -229,6 +237,8 @@ async fn create_geofence(
             event_types: geofence.event_types,
             active: geofence.active,
             color: geofence.color,
+            category: geofence.category,
+            tags: geofence.tags,
             metadata: geofence.metadata,
             created_by: geofence.created_by,
             creator_name: None, // Not available from create
@@ -282,6 +292,8 @@ async fn get_geofence(
         event_types: geofence.event_types,
         active: geofence.active,
         color: geofence.color,
+        category: geofence.category,
+        tags: geofence.tags,
         metadata: geofence.metadata,
         created_by: geofence.created_by,
         creator_name: geofence.creator_name,
@@ -344,6 +356,9 @@ async fn update_geofence(
         ));
     }
 
+    let category = request.category.as_deref().map(str::trim);
+    let tags = request.tags.as_deref().map(normalize_geofence_tags);
+
     // Update geofence
     let geofence = geofence_repo
         .update_geofence(
@@ -357,6 +372,8 @@ async fn update_geofence(
             request.event_types.as_deref(),
             request.active,
             request.color.as_deref(),
+            category,
+            tags.as_deref(),
             request.metadata.as_ref(),
         )
         .await?
@@ -381,6 +398,8 @@ async fn update_geofence(
             event_types: geofence.event_types,
             active: geofence.active,
             color: geofence.color,
+            category: geofence.category,
+            tags: geofence.tags,
             metadata: geofence.metadata,
             created_by: geofence.created_by,
             creator_name: None, // Not available from update
@@ -579,6 +598,10 @@ async fn get_location_analytics(
     // Get most visited geofences (top 10)
     let most_visited = geofence_repo.get_most_visited_geofences(org_id, 10).await?;
 
+    let by_category = geofence_repo
+        .get_geofence_counts_by_category(org_id)
+        .await?;
+
     let most_visited_geofences: Vec<GeofenceVisitCount> = most_visited
         .into_iter()
         .map(|v| GeofenceVisitCount {
@@ -588,6 +611,15 @@ async fn get_location_analytics(
         })
         .collect();
 
+    let geofences_by_category: Vec<GeofenceCategoryCount> = by_category
+        .into_iter()
+        .map(|c| GeofenceCategoryCount {
+            category: c.category,
+            geofence_count: c.geofence_count,
+            visit_count: c.visit_count,
+        })
+        .collect();
+
     info!(
         org_id = %org_id,
         user_id = %user.user_id,
@@ -604,6 +636,7 @@ async fn get_location_analytics(
             total_geofences: analytics.total_geofences,
             total_geofence_events_today: analytics.total_geofence_events_today,
             most_visited_geofences,
+            geofences_by_category,
         },
     };
 
@@ -19,32 +19,35 @@ use validator::Validate;
 use crate::app::AppState;
 use crate::error::ApiError;
 use crate::extractors::UserAuth;
+use crate::services::content_filter::check_blocked_terms;
 
 use domain::models::invite::generate_invite_code;
 use domain::models::{
     AddGroupMemberRequest, AddGroupMemberResponse, AdminGroupDetailResponse,
     AdminGroupListResponse, AdminGroupPagination, AdminGroupQuery, AdminSortOrder,
-    CreateGroupInvitationRequest, CreateGroupInvitationResponse, DeactivateGroupResponse,
-    GroupInvitationInfo, GroupMembersPagination, ListGroupInvitationsResponse,
-    ListGroupMembersQuery, ListGroupMembersResponse, OrgUserRole, RemoveGroupMemberResponse,
-    UpdateAdminGroupRequest, UpdateAdminGroupResponse,
+    BulkGroupMemberAction, BulkGroupMemberOperation, BulkGroupMemberResult,
+    BulkGroupMembersRequest, BulkGroupMembersResponse, CreateGroupInvitationRequest,
+    CreateGroupInvitationResponse, DeactivateGroupResponse, GroupInvitationInfo,
+    GroupMembersPagination, ListGroupInvitationsResponse, ListGroupMembersQuery,
+    ListGroupMembersResponse, MoveGroupMemberResult, MoveGroupMembersRequest,
+    MoveGroupMembersResponse, OrgUserRole, RemoveGroupMemberResponse, UpdateAdminGroupRequest,
+    UpdateAdminGroupResponse,
 };
 
 /// Create admin group management routes.
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/", get(list_groups))
-        .route("/{group_id}", get(get_group_detail))
-        .route("/{group_id}", put(update_group))
-        .route("/{group_id}", delete(deactivate_group))
-        .route("/{group_id}/members", get(list_group_members))
-        .route("/{group_id}/members", post(add_group_member))
-        .route(
-            "/{group_id}/members/{member_id}",
-            delete(remove_group_member),
-        )
-        .route("/{group_id}/invitations", get(list_group_invitations))
-        .route("/{group_id}/invitations", post(create_group_invitation))
+        .route("/:group_id", get(get_group_detail))
+        .route("/:group_id", put(update_group))
+        .route("/:group_id", delete(deactivate_group))
+        .route("/:group_id/members", get(list_group_members))
+        .route("/:group_id/members", post(add_group_member))
+        .route("/:group_id/members/bulk", post(bulk_update_group_members))
+        .route("/:group_id/members/move", post(move_group_members))
+        .route("/:group_id/members/:member_id", delete(remove_group_member))
+        .route("/:group_id/invitations", get(list_group_invitations))
+        .route("/:group_id/invitations", post(create_group_invitation))
 }
 
 /// List groups in organization.
@@ -219,6 +222,16 @@ async fn update_group(
         ));
     }
 
+    check_blocked_terms(
+        &state.pool,
+        org_id,
+        &[
+            ("name", request.name.as_deref()),
+            ("description", request.description.as_deref()),
+        ],
+    )
+    .await?;
+
     // Update group
     let updated = admin_group_repo
         .update_group(
@@ -459,6 +472,314 @@ async fn add_group_member(
     Ok((StatusCode::CREATED, Json(response)))
 }
 
+/// Add, remove and change roles of several members in one call.
+///
+/// POST /api/admin/v1/organizations/{org_id}/groups/{group_id}/members/bulk
+///
+/// Operations are applied in order and independently; the response reports
+/// the outcome of each one.
+#[axum::debug_handler]
+async fn bulk_update_group_members(
+    State(state): State<AppState>,
+    Path((org_id, group_id)): Path<(Uuid, Uuid)>,
+    user: UserAuth,
+    Json(request): Json<BulkGroupMembersRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    // Validate request
+    request
+        .validate()
+        .map_err(|e| ApiError::Validation(e.to_string()))?;
+
+    let org_user_repo = OrgUserRepository::new(state.pool.clone());
+    let admin_group_repo = AdminGroupRepository::new(state.pool.clone());
+
+    // Verify user has access to organization
+    let org_user = org_user_repo
+        .find_by_org_and_user(org_id, user.user_id)
+        .await?
+        .ok_or_else(|| ApiError::Forbidden("User not in organization".to_string()))?;
+
+    // Check permission (admin or owner can manage members)
+    if org_user.role != OrgUserRole::Owner && org_user.role != OrgUserRole::Admin {
+        return Err(ApiError::Forbidden(
+            "Admin or owner access required".to_string(),
+        ));
+    }
+
+    // Verify group belongs to organization
+    if !admin_group_repo
+        .group_belongs_to_org(org_id, group_id)
+        .await?
+    {
+        return Err(ApiError::NotFound(
+            "Group not found in organization".to_string(),
+        ));
+    }
+
+    let is_org_owner = org_user.role == OrgUserRole::Owner;
+    let total = request.operations.len();
+    let mut results = Vec::with_capacity(total);
+    let mut successful = 0;
+
+    for operation in request.operations {
+        let outcome = apply_member_operation(
+            &admin_group_repo,
+            org_id,
+            group_id,
+            is_org_owner,
+            &operation,
+        )
+        .await;
+        let (role, error) = match outcome {
+            Ok(role) => {
+                successful += 1;
+                (role, None)
+            }
+            Err(error) => (None, Some(error)),
+        };
+        results.push(BulkGroupMemberResult {
+            user_id: operation.user_id,
+            action: operation.action,
+            success: error.is_none(),
+            role,
+            error,
+        });
+    }
+
+    info!(
+        org_id = %org_id,
+        group_id = %group_id,
+        admin_user_id = %user.user_id,
+        total = total,
+        successful = successful,
+        "Bulk group membership update"
+    );
+
+    Ok(Json(BulkGroupMembersResponse {
+        group_id,
+        total,
+        successful,
+        failed: total - successful,
+        results,
+    }))
+}
+
+/// Apply one operation of a bulk membership request. Returns the member's
+/// role afterwards (`None` once removed), or why the operation failed.
+async fn apply_member_operation(
+    repo: &AdminGroupRepository,
+    org_id: Uuid,
+    group_id: Uuid,
+    is_org_owner: bool,
+    operation: &BulkGroupMemberOperation,
+) -> Result<Option<String>, String> {
+    operation
+        .validate()
+        .map_err(|e| format!("Validation error: {}", e))?;
+    let user_id = operation.user_id;
+    let is_member = repo
+        .is_group_member(group_id, user_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    match operation.action {
+        BulkGroupMemberAction::Add => {
+            if !repo
+                .user_in_org(org_id, user_id)
+                .await
+                .map_err(|e| e.to_string())?
+            {
+                return Err("User must be a member of the organization".to_string());
+            }
+            if is_member {
+                return Err("User is already a member of this group".to_string());
+            }
+            let role = operation.role.as_deref().unwrap_or("member");
+            let member = repo
+                .add_group_member(group_id, user_id, role)
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(Some(member.role))
+        }
+        BulkGroupMemberAction::Remove => {
+            if !is_member {
+                return Err("Member not found in group".to_string());
+            }
+            if !is_org_owner
+                && repo
+                    .is_group_owner(group_id, user_id)
+                    .await
+                    .map_err(|e| e.to_string())?
+            {
+                return Err("Only organization owners can remove group owners".to_string());
+            }
+            repo.remove_group_member(group_id, user_id)
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(None)
+        }
+        BulkGroupMemberAction::UpdateRole => {
+            let role = operation
+                .role
+                .as_deref()
+                .ok_or_else(|| "Role is required to update a member's role".to_string())?;
+            if !is_member {
+                return Err("Member not found in group".to_string());
+            }
+            if !is_org_owner
+                && (role == "owner"
+                    || repo
+                        .is_group_owner(group_id, user_id)
+                        .await
+                        .map_err(|e| e.to_string())?)
+            {
+                return Err("Only organization owners can change group owner roles".to_string());
+            }
+            let member = repo
+                .update_group_member_role(group_id, user_id, role)
+                .await
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| "Member not found in group".to_string())?;
+            Ok(Some(member.role))
+        }
+    }
+}
+
+/// Move members from one group to another.
+///
+/// POST /api/admin/v1/organizations/{org_id}/groups/{group_id}/members/move
+///
+/// Each member is moved in its own transaction and keeps their role unless
+/// the request sets one. Group owners cannot be moved.
+#[axum::debug_handler]
+async fn move_group_members(
+    State(state): State<AppState>,
+    Path((org_id, group_id)): Path<(Uuid, Uuid)>,
+    user: UserAuth,
+    Json(request): Json<MoveGroupMembersRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    // Validate request
+    request
+        .validate()
+        .map_err(|e| ApiError::Validation(e.to_string()))?;
+    if request.target_group_id == group_id {
+        return Err(ApiError::Validation(
+            "Target group must differ from the source group".to_string(),
+        ));
+    }
+
+    let org_user_repo = OrgUserRepository::new(state.pool.clone());
+    let admin_group_repo = AdminGroupRepository::new(state.pool.clone());
+
+    // Verify user has access to organization
+    let org_user = org_user_repo
+        .find_by_org_and_user(org_id, user.user_id)
+        .await?
+        .ok_or_else(|| ApiError::Forbidden("User not in organization".to_string()))?;
+
+    // Check permission (admin or owner can manage members)
+    if org_user.role != OrgUserRole::Owner && org_user.role != OrgUserRole::Admin {
+        return Err(ApiError::Forbidden(
+            "Admin or owner access required".to_string(),
+        ));
+    }
+
+    // Verify both groups belong to organization
+    for id in [group_id, request.target_group_id] {
+        if !admin_group_repo.group_belongs_to_org(org_id, id).await? {
+            return Err(ApiError::NotFound(
+                "Group not found in organization".to_string(),
+            ));
+        }
+    }
+
+    let total = request.user_ids.len();
+    let mut results = Vec::with_capacity(total);
+    let mut successful = 0;
+
+    for user_id in request.user_ids {
+        let outcome = move_member(
+            &admin_group_repo,
+            group_id,
+            request.target_group_id,
+            user_id,
+            request.role.as_deref(),
+        )
+        .await;
+        let (role, error) = match outcome {
+            Ok(role) => {
+                successful += 1;
+                (Some(role), None)
+            }
+            Err(error) => (None, Some(error)),
+        };
+        results.push(MoveGroupMemberResult {
+            user_id,
+            success: error.is_none(),
+            role,
+            error,
+        });
+    }
+
+    info!(
+        org_id = %org_id,
+        source_group_id = %group_id,
+        target_group_id = %request.target_group_id,
+        admin_user_id = %user.user_id,
+        total = total,
+        successful = successful,
+        "Group members moved"
+    );
+
+    Ok(Json(MoveGroupMembersResponse {
+        source_group_id: group_id,
+        target_group_id: request.target_group_id,
+        total,
+        successful,
+        failed: total - successful,
+        results,
+    }))
+}
+
+/// Move one member. Returns their role in the target group, or why the
+/// move failed.
+async fn move_member(
+    repo: &AdminGroupRepository,
+    source_group_id: Uuid,
+    target_group_id: Uuid,
+    user_id: Uuid,
+    role: Option<&str>,
+) -> Result<String, String> {
+    if !repo
+        .is_group_member(source_group_id, user_id)
+        .await
+        .map_err(|e| e.to_string())?
+    {
+        return Err("Member not found in group".to_string());
+    }
+    if repo
+        .is_group_owner(source_group_id, user_id)
+        .await
+        .map_err(|e| e.to_string())?
+    {
+        return Err("Group owners cannot be moved. Transfer ownership first.".to_string());
+    }
+    if repo
+        .is_group_member(target_group_id, user_id)
+        .await
+        .map_err(|e| e.to_string())?
+    {
+        return Err("User is already a member of the target group".to_string());
+    }
+
+    let member = repo
+        .move_group_member(source_group_id, target_group_id, user_id, role)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Member not found in group".to_string())?;
+    Ok(member.role)
+}
+
 /// Remove a member from a group.
 ///
 /// DELETE /api/admin/v1/organizations/{org_id}/groups/{group_id}/members/{member_id}
@@ -679,6 +1000,7 @@ async fn create_group_invitation(
             max_uses,
             expires_at,
             user.user_id,
+            None,
         )
         .await?;
 
@@ -62,10 +62,10 @@ async fn get_device_location(
         .await?
         .ok_or_else(|| ApiError::Forbidden("User not in organization".to_string()))?;
 
-    // Check permission (admin or owner can view locations)
-    if org_user.role != OrgUserRole::Owner && org_user.role != OrgUserRole::Admin {
+    // Check permission (admin, owner or observer can view current locations)
+    if !org_user.role.can_view_org_data() {
         return Err(ApiError::Forbidden(
-            "Admin or owner access required".to_string(),
+            "Admin, owner or observer access required".to_string(),
         ));
     }
 
@@ -92,6 +92,7 @@ async fn get_device_location(
             to_timestamp: None,
             limit: 1,
             ascending: false, // Get most recent first
+            source: None,
         })
         .await?;
 
@@ -252,10 +253,10 @@ async fn get_all_device_locations(
         .await?
         .ok_or_else(|| ApiError::Forbidden("User not in organization".to_string()))?;
 
-    // Check permission (admin or owner can view locations)
-    if org_user.role != OrgUserRole::Owner && org_user.role != OrgUserRole::Admin {
+    // Check permission (admin, owner or observer can view current locations)
+    if !org_user.role.can_view_org_data() {
         return Err(ApiError::Forbidden(
-            "Admin or owner access required".to_string(),
+            "Admin, owner or observer access required".to_string(),
         ));
     }
 
@@ -275,6 +276,7 @@ async fn get_all_device_locations(
                 to_timestamp: None,
                 limit: 1,
                 ascending: false,
+                source: None,
             })
             .await?;
 
@@ -383,7 +385,7 @@ async fn get_org_location_history(
     }
 
     // Sort by timestamp descending (most recent first)
-    all_locations.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    all_locations.sort_by_key(|l| std::cmp::Reverse(l.timestamp));
 
     let total = all_locations.len() as i64;
 
@@ -0,0 +1,234 @@
+//! Admin report of password hash parameters.
+//!
+//! Lists how many accounts still use a password hash created with parameters
+//! other than the configured Argon2id set. Those hashes are upgraded the next
+//! time the user logs in with their password.
+
+use axum::{
+    extract::{Extension, Query, State},
+    response::IntoResponse,
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use shared::password::{inspect_hash, PasswordHashParams};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::error::ApiError;
+use crate::extractors::api_key::ApiKeyAuth;
+use persistence::repositories::{PasswordHashSchemeRow, UserRepository};
+
+/// Query parameters for the password hash report.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PasswordHashReportQuery {
+    /// Maximum number of outdated accounts to list (1-500)
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+}
+
+fn default_limit() -> i64 {
+    100
+}
+
+/// Parameter set accounts are currently hashed with.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PasswordHashParamsInfo {
+    pub algorithm: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_cost: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_cost: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parallelism: Option<u32>,
+}
+
+/// Number of accounts sharing one parameter set.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PasswordHashGroup {
+    pub params: PasswordHashParamsInfo,
+    pub is_current: bool,
+    pub account_count: i64,
+}
+
+/// Account whose password hash uses outdated parameters.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct OutdatedPasswordAccount {
+    pub user_id: Uuid,
+    pub email: String,
+    pub last_login_at: Option<DateTime<Utc>>,
+}
+
+/// Response for the password hash report.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PasswordHashReportResponse {
+    pub current_params: PasswordHashParamsInfo,
+    pub total_accounts: i64,
+    pub outdated_accounts: i64,
+    pub groups: Vec<PasswordHashGroup>,
+    /// Outdated accounts, least recently logged in first
+    pub accounts: Vec<OutdatedPasswordAccount>,
+}
+
+impl From<&PasswordHashParams> for PasswordHashParamsInfo {
+    fn from(params: &PasswordHashParams) -> Self {
+        Self {
+            algorithm: "argon2id".to_string(),
+            version: Some(params.version),
+            memory_cost: Some(params.memory_cost),
+            time_cost: Some(params.time_cost),
+            parallelism: Some(params.parallelism),
+        }
+    }
+}
+
+/// Classifies each hash scheme against the current parameter set.
+fn classify_schemes(
+    rows: Vec<PasswordHashSchemeRow>,
+    current: &PasswordHashParams,
+) -> Vec<(String, PasswordHashGroup)> {
+    rows.into_iter()
+        .map(|row| {
+            let (params, is_current) = match inspect_hash(&row.sample_hash) {
+                Ok(stored) => {
+                    let is_current = stored.matches(current);
+                    let params = PasswordHashParamsInfo {
+                        algorithm: stored.algorithm,
+                        version: stored.version,
+                        memory_cost: Some(stored.memory_cost),
+                        time_cost: Some(stored.time_cost),
+                        parallelism: Some(stored.parallelism),
+                    };
+                    (params, is_current)
+                }
+                Err(_) => {
+                    let params = PasswordHashParamsInfo {
+                        algorithm: "unknown".to_string(),
+                        version: None,
+                        memory_cost: None,
+                        time_cost: None,
+                        parallelism: None,
+                    };
+                    (params, false)
+                }
+            };
+            let group = PasswordHashGroup {
+                params,
+                is_current,
+                account_count: row.account_count,
+            };
+            (row.scheme, group)
+        })
+        .collect()
+}
+
+/// GET /api/admin/v1/password-hashes
+///
+/// Returns how many accounts use each password hash parameter set and lists
+/// accounts that have not been upgraded to the configured parameters yet.
+/// Requires admin API key authentication.
+pub async fn get_password_hash_report(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
+    Query(query): Query<PasswordHashReportQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let limit = query.limit.clamp(1, 500);
+    let current = state.config.security.password_hash_params();
+
+    let repo = UserRepository::new(state.pool.clone());
+    let groups = classify_schemes(repo.password_hash_schemes().await?, &current);
+
+    let outdated_schemes: Vec<String> = groups
+        .iter()
+        .filter(|(_, group)| !group.is_current)
+        .map(|(scheme, _)| scheme.clone())
+        .collect();
+    let accounts = if outdated_schemes.is_empty() {
+        Vec::new()
+    } else {
+        repo.list_users_by_password_scheme(&outdated_schemes, limit)
+            .await?
+    };
+
+    let total_accounts = groups.iter().map(|(_, g)| g.account_count).sum();
+    let outdated_accounts = groups
+        .iter()
+        .filter(|(_, g)| !g.is_current)
+        .map(|(_, g)| g.account_count)
+        .sum();
+
+    info!(
+        admin_key_id = auth.api_key_id,
+        total_accounts = total_accounts,
+        outdated_accounts = outdated_accounts,
+        "Admin queried password hash report"
+    );
+
+    Ok(Json(PasswordHashReportResponse {
+        current_params: PasswordHashParamsInfo::from(&current),
+        total_accounts,
+        outdated_accounts,
+        groups: groups.into_iter().map(|(_, group)| group).collect(),
+        accounts: accounts
+            .into_iter()
+            .map(|a| OutdatedPasswordAccount {
+                user_id: a.id,
+                email: a.email,
+                last_login_at: a.last_login_at,
+            })
+            .collect(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::password::{hash_password, hash_password_with};
+
+    fn row(scheme: &str, sample_hash: String, account_count: i64) -> PasswordHashSchemeRow {
+        PasswordHashSchemeRow {
+            scheme: scheme.to_string(),
+            account_count,
+            sample_hash,
+        }
+    }
+
+    #[test]
+    fn test_classify_schemes() {
+        let current = PasswordHashParams::default();
+        let weak = PasswordHashParams {
+            version: 0,
+            memory_cost: 4096,
+            time_cost: 1,
+            parallelism: 1,
+        };
+        let rows = vec![
+            row("current", hash_password("a").unwrap(), 10),
+            row("weak", hash_password_with("a", &weak).unwrap(), 3),
+            row("bcrypt", "$2b$12$abcdefghijklmnopqrstuv".to_string(), 1),
+        ];
+
+        let groups = classify_schemes(rows, &current);
+        assert_eq!(groups.len(), 3);
+        assert!(groups[0].1.is_current);
+        assert!(!groups[1].1.is_current);
+        assert_eq!(groups[1].1.params.memory_cost, Some(4096));
+        assert_eq!(groups[1].1.params.version, Some(0));
+        assert!(!groups[2].1.is_current);
+        assert_eq!(groups[2].1.params.algorithm, "unknown");
+    }
+
+    #[test]
+    fn test_report_query_defaults() {
+        let query: PasswordHashReportQuery = serde_json::from_str("{}").unwrap();
+        assert_eq!(query.limit, 100);
+    }
+}
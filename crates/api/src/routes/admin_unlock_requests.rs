@@ -255,6 +255,7 @@ async fn approve_unlock_request(
             UnlockRequestStatusDb::Approved,
             user.user_id,
             request.note.as_deref(),
+            None,
         )
         .await?
         .ok_or_else(|| {
@@ -326,6 +327,7 @@ async fn deny_unlock_request(
             UnlockRequestStatusDb::Denied,
             user.user_id,
             request.note.as_deref(),
+            None,
         )
         .await?
         .ok_or_else(|| {
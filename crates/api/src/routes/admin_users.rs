@@ -21,12 +21,14 @@ use crate::app::AppState;
 use crate::error::ApiError;
 use crate::extractors::UserAuth;
 use crate::services::auth::{AuthError, AuthService};
+use crate::services::org_webhook_delivery::emit_member_event;
 
 use domain::models::{
     validate_permissions, AddOrgUserRequest, AdminUserDetailResponse, AdminUserListResponse,
-    AdminUserPagination, AdminUserQuery, ForceMfaResponse, ListUserSessionsResponse, MfaMethod,
-    MfaStatusResponse, OrgUserRole, ReactivateOrgUserResponse, RemoveUserResponse,
-    ResetMfaResponse, RevokeAllSessionsResponse, RevokeSessionResponse, SuspendOrgUserRequest,
+    AdminUserPagination, AdminUserQuery, ForceMfaResponse, ListUserSessionsResponse,
+    MemberEventMember, MemberEventPayload, MemberEventType, MfaMethod, MfaStatusResponse,
+    OrgUserRole, ReactivateOrgUserResponse, RemoveUserResponse, ResetMfaResponse,
+    RevokeAllSessionsResponse, RevokeSessionResponse, SuspendOrgUserRequest,
     SuspendOrgUserResponse, TriggerPasswordResetResponse, UpdateAdminUserRequest,
     UpdateAdminUserResponse, UserSessionInfo,
 };
@@ -79,6 +81,7 @@ fn create_auth_service(state: &AppState) -> Result<AuthService, ApiError> {
         google_client_id,
         apple_client_id,
     )
+    .map(|service| service.with_password_params(state.config.security.password_hash_params()))
     .map_err(|e| {
         tracing::error!(error = %e, "Failed to initialize auth service");
         ApiError::Internal(format!("Failed to initialize auth service: {}", e))
@@ -165,6 +168,21 @@ async fn create_user(
                 invitation_id = %invite.id,
                 "Created invitation for non-existing user"
             );
+            emit_member_event(
+                &state.pool,
+                &state.webhook_secrets,
+                MemberEventPayload::new(
+                    MemberEventType::Invited,
+                    org_id,
+                    MemberEventMember {
+                        email: Some(request.email.clone()),
+                        role: Some(role.clone()),
+                        invitation_id: Some(invite.id),
+                        ..Default::default()
+                    },
+                    Some(user.user_id),
+                ),
+            );
 
             return Ok((
                 StatusCode::CREATED,
@@ -203,6 +221,21 @@ async fn create_user(
         role = %request.role,
         "Added user to organization via JWT auth"
     );
+    emit_member_event(
+        &state.pool,
+        &state.webhook_secrets,
+        MemberEventPayload::new(
+            MemberEventType::Joined,
+            org_id,
+            MemberEventMember {
+                user_id: Some(target_user.id),
+                email: Some(target_user.email.clone()),
+                role: Some(request.role.to_string()),
+                ..Default::default()
+            },
+            Some(user.user_id),
+        ),
+    );
 
     Ok((
         StatusCode::CREATED,
@@ -433,6 +466,25 @@ async fn update_user(
         .await?
         .ok_or_else(|| ApiError::NotFound("User not found in organization".to_string()))?;
 
+    if updated.role != target_org_user.role {
+        emit_member_event(
+            &state.pool,
+            &state.webhook_secrets,
+            MemberEventPayload::new(
+                MemberEventType::RoleChanged,
+                org_id,
+                MemberEventMember {
+                    user_id: Some(target_user_id),
+                    email: Some(updated.user.email.clone()),
+                    role: Some(updated.role.to_string()),
+                    previous_role: Some(target_org_user.role.to_string()),
+                    ..Default::default()
+                },
+                Some(user.user_id),
+            ),
+        );
+    }
+
     let response = UpdateAdminUserResponse {
         id: updated.user.id,
         email: updated.user.email,
@@ -515,6 +567,21 @@ async fn remove_user(
         ));
     }
 
+    emit_member_event(
+        &state.pool,
+        &state.webhook_secrets,
+        MemberEventPayload::new(
+            MemberEventType::Removed,
+            org_id,
+            MemberEventMember {
+                user_id: Some(target_user_id),
+                role: Some(target_org_user.role.to_string()),
+                ..Default::default()
+            },
+            Some(user.user_id),
+        ),
+    );
+
     let response = RemoveUserResponse {
         removed: true,
         user_id: target_user_id,
@@ -615,6 +682,21 @@ async fn suspend_user(
         reason = ?request.reason,
         "User suspended from organization"
     );
+    emit_member_event(
+        &state.pool,
+        &state.webhook_secrets,
+        MemberEventPayload::new(
+            MemberEventType::Suspended,
+            org_id,
+            MemberEventMember {
+                user_id: Some(target_user_id),
+                role: Some(suspended.role.to_string()),
+                reason: suspended.suspension_reason.clone(),
+                ..Default::default()
+            },
+            Some(user.user_id),
+        ),
+    );
 
     let response = SuspendOrgUserResponse {
         id: suspended.id,
@@ -1027,6 +1109,20 @@ async fn reset_mfa(
         reset_by = %user.user_id,
         "Admin reset MFA for user"
     );
+    emit_member_event(
+        &state.pool,
+        &state.webhook_secrets,
+        MemberEventPayload::new(
+            MemberEventType::MfaReset,
+            org_id,
+            MemberEventMember {
+                user_id: Some(target_user_id),
+                role: Some(target_org_user.role.to_string()),
+                ..Default::default()
+            },
+            Some(user.user_id),
+        ),
+    );
 
     let response = ResetMfaResponse {
         user_id: target_user_id,
@@ -5,7 +5,8 @@
 use axum::{
     body::Body,
     extract::{Path, Query, State},
-    http::header,
+    http::{header, StatusCode},
+    middleware,
     response::Response,
     routing::{get, post},
     Json, Router,
@@ -14,18 +15,28 @@ use chrono::{Datelike, Duration, NaiveDate, Utc};
 use tokio::fs::File;
 use tokio_util::io::ReaderStream;
 use uuid::Uuid;
+use validator::Validate;
 
 use crate::app::AppState;
 use crate::error::ApiError;
 use crate::extractors::UserAuth;
+use crate::middleware::{concurrency_limit, export_statement_timeout, RouteClass};
+use crate::services::report_generation::report_content_type;
+use domain::models::report_builder::validate_report_sections;
 use domain::models::{
     AnalyticsDeviceStatusBreakdown, AnalyticsGroupBy, AnalyticsPeriod, ApiUsageAnalyticsQuery,
-    ApiUsageAnalyticsResponse, ApiUsageSummary, ApiUsageTrend, DeviceActivityTrend,
-    DeviceAnalyticsQuery, DeviceAnalyticsResponse, DeviceAnalyticsSummary, EndpointUsage,
-    GenerateReportRequest, OrgUserRole, ReportJobResponse, ReportStatus, UserActivityTrend,
+    ApiUsageAnalyticsResponse, ApiUsageSummary, ApiUsageTrend, CreateReportTemplateRequest,
+    DeviceActivityTrend, DeviceAnalyticsQuery, DeviceAnalyticsResponse, DeviceAnalyticsSummary,
+    EndpointUsage, GenerateExtractRequest, GenerateMileageReportRequest, GenerateReportRequest,
+    ListReportTemplatesResponse, OrgUserRole, ReportBuilderDefinition, ReportJobResponse,
+    ReportStatus, ReportTemplate, UnitSystem, UpdateReportTemplateRequest, UserActivityTrend,
     UserAnalyticsQuery, UserAnalyticsResponse, UserAnalyticsSummary, UserRoleBreakdown,
+    MAX_REPORT_TEMPLATES_PER_ORG,
+};
+use persistence::repositories::{
+    AnalyticsRepository, DeviceRepository, OrgUserRepository, ReportTemplateRepository,
+    UserRepository,
 };
-use persistence::repositories::{AnalyticsRepository, OrgUserRepository};
 
 /// Build the analytics router.
 pub fn router() -> Router<AppState> {
@@ -34,6 +45,10 @@ pub fn router() -> Router<AppState> {
         .route("/users", get(get_user_analytics))
         .route("/devices", get(get_device_analytics))
         .route("/api", get(get_api_usage_analytics))
+        .route_layer(middleware::from_fn_with_state(
+            RouteClass::Analytics,
+            concurrency_limit,
+        ))
 }
 
 /// Build the reports router.
@@ -42,6 +57,25 @@ pub fn reports_router() -> Router<AppState> {
         // Report generation endpoints (FR-10.4, FR-10.5, FR-10.6, FR-10.7)
         .route("/users", post(generate_user_report))
         .route("/devices", post(generate_device_report))
+        .route("/mileage", post(generate_mileage_report))
+        .route("/custom", post(generate_custom_report))
+        .route("/extracts", post(generate_extract))
+        .route_layer(middleware::from_fn_with_state(
+            RouteClass::Export,
+            concurrency_limit,
+        ))
+        .route_layer(middleware::from_fn(export_statement_timeout))
+        // Templates and status polls are cheap and not limited
+        .route(
+            "/templates",
+            get(list_report_templates).post(create_report_template),
+        )
+        .route(
+            "/templates/:template_id",
+            get(get_report_template)
+                .put(update_report_template)
+                .delete(delete_report_template),
+        )
         .route("/:report_id/status", get(get_report_status))
         .route("/:report_id/download", get(download_report))
 }
@@ -66,6 +100,44 @@ async fn verify_org_admin(
     Ok(())
 }
 
+/// Helper function to verify read access to analytics.
+///
+/// Observers can view analytics but not generate reports or extracts.
+async fn verify_org_viewer(
+    pool: &sqlx::PgPool,
+    org_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), ApiError> {
+    let org_user_repo = OrgUserRepository::new(pool.clone());
+    let org_user = org_user_repo
+        .find_by_org_and_user(org_id, user_id)
+        .await?
+        .ok_or_else(|| ApiError::Forbidden("User not in organization".to_string()))?;
+
+    if !org_user.role.can_view_org_data() {
+        return Err(ApiError::Forbidden("Insufficient permissions".to_string()));
+    }
+
+    Ok(())
+}
+
+/// Resolve the unit system for analytics output: explicit override first,
+/// then the requesting user's stored preference.
+async fn resolve_user_units(
+    pool: &sqlx::PgPool,
+    user_id: Uuid,
+    requested: Option<UnitSystem>,
+) -> Result<UnitSystem, ApiError> {
+    if let Some(units) = requested {
+        return Ok(units);
+    }
+
+    let stored = UserRepository::new(pool.clone())
+        .get_unit_system(user_id)
+        .await?;
+    Ok(UnitSystem::resolve(None, stored.as_deref()))
+}
+
 /// Get user analytics for organization (FR-10.1).
 #[axum::debug_handler]
 async fn get_user_analytics(
@@ -75,7 +147,7 @@ async fn get_user_analytics(
     user: UserAuth,
 ) -> Result<Json<UserAnalyticsResponse>, ApiError> {
     // Verify user has admin access to organization
-    verify_org_admin(&state.pool, org_id, user.user_id).await?;
+    verify_org_viewer(&state.pool, org_id, user.user_id).await?;
 
     let repo = AnalyticsRepository::new(state.pool.clone());
 
@@ -162,7 +234,7 @@ async fn get_device_analytics(
     user: UserAuth,
 ) -> Result<Json<DeviceAnalyticsResponse>, ApiError> {
     // Verify user has admin access to organization
-    verify_org_admin(&state.pool, org_id, user.user_id).await?;
+    verify_org_viewer(&state.pool, org_id, user.user_id).await?;
 
     let repo = AnalyticsRepository::new(state.pool.clone());
 
@@ -180,6 +252,15 @@ async fn get_device_analytics(
     // Get device status breakdown
     let status_entities = repo.get_device_status_breakdown(org_id).await?;
 
+    // Completed trip distance, presented in the caller's preferred units
+    let units = resolve_user_units(&state.pool, user.user_id, query.units).await?;
+    let trip_distance_meters: f64 = repo
+        .get_trip_distance_by_day(org_id, from, to)
+        .await?
+        .iter()
+        .map(|d| d.distance_meters)
+        .sum();
+
     // Convert entities to domain models
     let summary = DeviceAnalyticsSummary {
         total_devices: summary_entity.total_devices,
@@ -189,6 +270,8 @@ async fn get_device_analytics(
         total_locations_reported: summary_entity.total_locations,
         total_geofence_events: summary_entity.total_geofence_events,
         total_commands_issued: summary_entity.total_commands,
+        total_trip_distance: (units.convert_distance(trip_distance_meters) * 100.0).round() / 100.0,
+        distance_unit: units.distance_unit(),
     };
 
     let trends: Vec<DeviceActivityTrend> = trends_entities
@@ -229,6 +312,7 @@ async fn get_device_analytics(
             start: from,
             end: to,
         },
+        unit_system: units,
         summary,
         trends,
         by_status,
@@ -246,7 +330,7 @@ async fn get_api_usage_analytics(
     user: UserAuth,
 ) -> Result<Json<ApiUsageAnalyticsResponse>, ApiError> {
     // Verify user has admin access to organization
-    verify_org_admin(&state.pool, org_id, user.user_id).await?;
+    verify_org_viewer(&state.pool, org_id, user.user_id).await?;
 
     let repo = AnalyticsRepository::new(state.pool.clone());
 
@@ -256,13 +340,19 @@ async fn get_api_usage_analytics(
     let to = query.to.unwrap_or(today);
 
     // Get API usage summary
-    let summary_entity = repo.get_api_usage_summary(org_id, from, to).await?;
+    let summary_entity = repo
+        .get_api_usage_summary(org_id, from, to, query.api_key_id)
+        .await?;
 
     // Get API usage trends
-    let trends_entities = repo.get_api_usage_trends(org_id, from, to).await?;
+    let trends_entities = repo
+        .get_api_usage_trends(org_id, from, to, query.api_key_id)
+        .await?;
 
     // Get top endpoints
-    let top_endpoints_entities = repo.get_top_endpoints(org_id, from, to, 10).await?;
+    let top_endpoints_entities = repo
+        .get_top_endpoints(org_id, from, to, query.api_key_id, 10)
+        .await?;
 
     // Convert entities to domain models
     let total_requests = summary_entity.total_requests;
@@ -293,19 +383,18 @@ async fn get_api_usage_analytics(
             error_count: 0,
             avg_response_time_ms: 0.0,
         });
+        // Weight each row's average by its request count
+        let weighted = entry.avg_response_time_ms * entry.total_requests as f64
+            + e.avg_response_time_ms.unwrap_or(0.0) * e.total_requests as f64;
         entry.total_requests += e.total_requests;
         entry.success_count += e.success_count;
         entry.error_count += e.error_count;
-        // Simple average of averages (not weighted, but good enough for trends)
-        if entry.avg_response_time_ms == 0.0 {
-            entry.avg_response_time_ms = e.avg_response_time_ms.unwrap_or(0.0);
-        } else {
-            entry.avg_response_time_ms =
-                (entry.avg_response_time_ms + e.avg_response_time_ms.unwrap_or(0.0)) / 2.0;
+        if entry.total_requests > 0 {
+            entry.avg_response_time_ms = weighted / entry.total_requests as f64;
         }
     }
     let mut trends: Vec<ApiUsageTrend> = trends_map.into_values().collect();
-    trends.sort_by(|a, b| a.date.cmp(&b.date));
+    trends.sort_by_key(|t| t.date);
 
     // Aggregate trends by group_by if specified
     let trends = aggregate_api_trends(trends, query.group_by);
@@ -362,10 +451,12 @@ async fn generate_user_report(
 
     let repo = AnalyticsRepository::new(state.pool.clone());
 
+    let units = resolve_user_units(&state.pool, user.user_id, request.units).await?;
     let parameters = serde_json::json!({
         "from": request.from,
         "to": request.to,
         "format": request.format,
+        "unit_system": units,
         "additional": request.parameters,
     });
 
@@ -407,10 +498,12 @@ async fn generate_device_report(
 
     let repo = AnalyticsRepository::new(state.pool.clone());
 
+    let units = resolve_user_units(&state.pool, user.user_id, request.units).await?;
     let parameters = serde_json::json!({
         "from": request.from,
         "to": request.to,
         "format": request.format,
+        "unit_system": units,
         "additional": request.parameters,
     });
 
@@ -439,6 +532,330 @@ async fn generate_device_report(
     Ok(Json(response))
 }
 
+/// Generate a mileage report.
+///
+/// Completed trip distance of the organization's devices, or of one device,
+/// per trip purpose and per day, processed by the report generation worker.
+#[axum::debug_handler]
+async fn generate_mileage_report(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    user: UserAuth,
+    Json(request): Json<GenerateMileageReportRequest>,
+) -> Result<Json<ReportJobResponse>, ApiError> {
+    // Verify user has admin access to organization
+    verify_org_admin(&state.pool, org_id, user.user_id).await?;
+
+    request.validate_range().map_err(ApiError::Validation)?;
+
+    if let Some(device_id) = request.device_id {
+        let in_org = DeviceRepository::new(state.pool.clone())
+            .find_by_device_id(device_id)
+            .await?
+            .is_some_and(|d| d.organization_id == Some(org_id));
+        if !in_org {
+            return Err(ApiError::NotFound("Device not found".to_string()));
+        }
+    }
+
+    let repo = AnalyticsRepository::new(state.pool.clone());
+
+    let units = resolve_user_units(&state.pool, user.user_id, request.units).await?;
+    let parameters = serde_json::json!({
+        "from": request.from,
+        "to": request.to,
+        "format": request.format,
+        "unit_system": units,
+        "device_id": request.device_id,
+    });
+
+    let job = repo
+        .create_report_job(org_id, "mileage", parameters, user.user_id)
+        .await?;
+
+    let response = ReportJobResponse {
+        id: job.id,
+        organization_id: job.organization_id,
+        report_type: job.report_type,
+        status: ReportStatus::from(job.status.as_str()),
+        parameters: job.parameters,
+        file_size_bytes: job.file_size_bytes,
+        error_message: job.error_message,
+        created_by: job.created_by,
+        started_at: job.started_at,
+        completed_at: job.completed_at,
+        expires_at: job.expires_at,
+        created_at: job.created_at,
+    };
+
+    Ok(Json(response))
+}
+
+/// Generate a raw data extract (locations or geofence events).
+///
+/// Extracts are processed by the report generation worker like other report
+/// jobs and default to Parquet output.
+#[axum::debug_handler]
+async fn generate_extract(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    user: UserAuth,
+    Json(request): Json<GenerateExtractRequest>,
+) -> Result<Json<ReportJobResponse>, ApiError> {
+    // Verify user has admin access to organization
+    verify_org_admin(&state.pool, org_id, user.user_id).await?;
+
+    request.validate_range().map_err(ApiError::Validation)?;
+
+    let repo = AnalyticsRepository::new(state.pool.clone());
+
+    let parameters = serde_json::json!({
+        "from": request.from,
+        "to": request.to,
+        "format": request.format,
+    });
+
+    let job = repo
+        .create_report_job(
+            org_id,
+            request.dataset.report_type(),
+            parameters,
+            user.user_id,
+        )
+        .await?;
+
+    let response = ReportJobResponse {
+        id: job.id,
+        organization_id: job.organization_id,
+        report_type: job.report_type,
+        status: ReportStatus::from(job.status.as_str()),
+        parameters: job.parameters,
+        file_size_bytes: job.file_size_bytes,
+        error_message: job.error_message,
+        created_by: job.created_by,
+        started_at: job.started_at,
+        completed_at: job.completed_at,
+        expires_at: job.expires_at,
+        created_at: job.created_at,
+    };
+
+    Ok(Json(response))
+}
+
+/// Generate a custom report from selected sections and grouping dimensions.
+///
+/// When `template_id` is given, sections, grouping and format come from the
+/// saved template; the date range and units always come from the request.
+#[axum::debug_handler]
+async fn generate_custom_report(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    user: UserAuth,
+    Json(request): Json<GenerateReportRequest>,
+) -> Result<Json<ReportJobResponse>, ApiError> {
+    // Verify user has admin access to organization
+    verify_org_admin(&state.pool, org_id, user.user_id).await?;
+
+    request.validate().map_err(validation_error)?;
+    if request.to < request.from {
+        return Err(ApiError::Validation(
+            "to: End date must not be before start date".to_string(),
+        ));
+    }
+
+    let definition = match request.template_id {
+        Some(template_id) => {
+            let template: ReportTemplate = ReportTemplateRepository::new(state.pool.clone())
+                .find_by_id(org_id, template_id)
+                .await?
+                .ok_or_else(|| ApiError::NotFound("Report template not found".to_string()))?
+                .into();
+            template.definition
+        }
+        None => ReportBuilderDefinition {
+            sections: request.sections.clone(),
+            group_by: request.group_by.clone(),
+            format: request.format,
+        },
+    };
+    validate_report_sections(&definition.sections).map_err(|e| {
+        ApiError::Validation(format!("sections: {}", e.message.unwrap_or_default()))
+    })?;
+
+    let repo = AnalyticsRepository::new(state.pool.clone());
+
+    let units = resolve_user_units(&state.pool, user.user_id, request.units).await?;
+    let parameters = serde_json::json!({
+        "from": request.from,
+        "to": request.to,
+        "format": definition.format,
+        "unit_system": units,
+        "sections": definition.sections,
+        "group_by": definition.group_by,
+        "template_id": request.template_id,
+        "additional": request.parameters,
+    });
+
+    let job = repo
+        .create_report_job(org_id, "custom", parameters, user.user_id)
+        .await?;
+
+    let response = ReportJobResponse {
+        id: job.id,
+        organization_id: job.organization_id,
+        report_type: job.report_type,
+        status: ReportStatus::from(job.status.as_str()),
+        parameters: job.parameters,
+        file_size_bytes: job.file_size_bytes,
+        error_message: job.error_message,
+        created_by: job.created_by,
+        started_at: job.started_at,
+        completed_at: job.completed_at,
+        expires_at: job.expires_at,
+        created_at: job.created_at,
+    };
+
+    Ok(Json(response))
+}
+
+/// List saved report templates.
+#[axum::debug_handler]
+async fn list_report_templates(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    user: UserAuth,
+) -> Result<Json<ListReportTemplatesResponse>, ApiError> {
+    verify_org_admin(&state.pool, org_id, user.user_id).await?;
+
+    let templates = ReportTemplateRepository::new(state.pool.clone())
+        .list_by_organization(org_id)
+        .await?;
+
+    Ok(Json(ListReportTemplatesResponse {
+        data: templates.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// Save a report template.
+#[axum::debug_handler]
+async fn create_report_template(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    user: UserAuth,
+    Json(request): Json<CreateReportTemplateRequest>,
+) -> Result<(StatusCode, Json<ReportTemplate>), ApiError> {
+    verify_org_admin(&state.pool, org_id, user.user_id).await?;
+
+    request.validate().map_err(validation_error)?;
+
+    let repo = ReportTemplateRepository::new(state.pool.clone());
+    if repo.count_by_organization(org_id).await? >= MAX_REPORT_TEMPLATES_PER_ORG {
+        return Err(ApiError::Conflict(format!(
+            "Organization already has the maximum of {} report templates",
+            MAX_REPORT_TEMPLATES_PER_ORG
+        )));
+    }
+
+    let definition = serde_json::to_value(request.definition())
+        .map_err(|e| ApiError::Internal(format!("Failed to serialize template: {}", e)))?;
+    let template = repo
+        .create(
+            org_id,
+            &request.name,
+            request.description.as_deref(),
+            &definition,
+            user.user_id,
+        )
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(template.into())))
+}
+
+/// Get a saved report template.
+#[axum::debug_handler]
+async fn get_report_template(
+    State(state): State<AppState>,
+    Path((org_id, template_id)): Path<(Uuid, Uuid)>,
+    user: UserAuth,
+) -> Result<Json<ReportTemplate>, ApiError> {
+    verify_org_admin(&state.pool, org_id, user.user_id).await?;
+
+    let template = ReportTemplateRepository::new(state.pool.clone())
+        .find_by_id(org_id, template_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Report template not found".to_string()))?;
+
+    Ok(Json(template.into()))
+}
+
+/// Update a saved report template.
+#[axum::debug_handler]
+async fn update_report_template(
+    State(state): State<AppState>,
+    Path((org_id, template_id)): Path<(Uuid, Uuid)>,
+    user: UserAuth,
+    Json(request): Json<UpdateReportTemplateRequest>,
+) -> Result<Json<ReportTemplate>, ApiError> {
+    verify_org_admin(&state.pool, org_id, user.user_id).await?;
+
+    request.validate().map_err(validation_error)?;
+
+    let repo = ReportTemplateRepository::new(state.pool.clone());
+    let existing: ReportTemplate = repo
+        .find_by_id(org_id, template_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Report template not found".to_string()))?
+        .into();
+
+    let definition = serde_json::to_value(request.apply_to(&existing.definition))
+        .map_err(|e| ApiError::Internal(format!("Failed to serialize template: {}", e)))?;
+    let template = repo
+        .update(
+            org_id,
+            template_id,
+            request.name.as_deref(),
+            request.description.as_deref(),
+            &definition,
+        )
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Report template not found".to_string()))?;
+
+    Ok(Json(template.into()))
+}
+
+/// Delete a saved report template.
+#[axum::debug_handler]
+async fn delete_report_template(
+    State(state): State<AppState>,
+    Path((org_id, template_id)): Path<(Uuid, Uuid)>,
+    user: UserAuth,
+) -> Result<StatusCode, ApiError> {
+    verify_org_admin(&state.pool, org_id, user.user_id).await?;
+
+    let deleted = ReportTemplateRepository::new(state.pool.clone())
+        .delete(org_id, template_id)
+        .await?;
+    if !deleted {
+        return Err(ApiError::NotFound("Report template not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Map validator errors to an API validation error.
+fn validation_error(e: validator::ValidationErrors) -> ApiError {
+    let errors: Vec<String> = e
+        .field_errors()
+        .iter()
+        .flat_map(|(field, errors)| {
+            errors.iter().map(move |err| {
+                format!("{}: {}", field, err.message.as_ref().unwrap_or(&"".into()))
+            })
+        })
+        .collect();
+    ApiError::Validation(errors.join(", "))
+}
+
 /// Get report status (FR-10.6).
 #[axum::debug_handler]
 async fn get_report_status(
@@ -527,24 +944,14 @@ async fn download_report(
     })?;
 
     // Determine content type based on file extension
-    let content_type = if file_name.ends_with(".csv") {
-        "text/csv"
-    } else if file_name.ends_with(".json") {
-        "application/json"
-    } else {
-        "application/octet-stream"
-    };
+    let content_type = report_content_type(&file_name);
 
     // Create a friendly download filename
     let download_filename = format!(
         "{}_{}.{}",
         job.report_type,
         job.created_at.format("%Y%m%d_%H%M%S"),
-        if file_name.ends_with(".csv") {
-            "csv"
-        } else {
-            "json"
-        }
+        file_name.rsplit('.').next().unwrap_or("json")
     );
 
     // Stream the file
@@ -610,7 +1017,7 @@ fn aggregate_user_trends(
     }
 
     let mut result: Vec<UserActivityTrend> = aggregated.into_values().collect();
-    result.sort_by(|a, b| a.date.cmp(&b.date));
+    result.sort_by_key(|t| t.date);
     result
 }
 
@@ -654,7 +1061,7 @@ fn aggregate_device_trends(
     }
 
     let mut result: Vec<DeviceActivityTrend> = aggregated.into_values().collect();
-    result.sort_by(|a, b| a.date.cmp(&b.date));
+    result.sort_by_key(|t| t.date);
     result
 }
 
@@ -708,6 +1115,6 @@ fn aggregate_api_trends(
             trend
         })
         .collect();
-    result.sort_by(|a, b| a.date.cmp(&b.date));
+    result.sort_by_key(|t| t.date);
     result
 }
@@ -5,6 +5,7 @@
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
+    middleware,
     response::IntoResponse,
     routing::get,
     Json, Router,
@@ -16,6 +17,7 @@ use uuid::Uuid;
 use crate::app::AppState;
 use crate::error::ApiError;
 use crate::extractors::UserAuth;
+use crate::middleware::{concurrency_limit, RouteClass};
 use domain::models::{
     AnalyticsSummary, AnalyticsTrendPoint, AppUsageAnalyticsQuery, AppUsageAnalyticsResponse,
     AppUsageHistoryEntry, AppUsageHistoryQuery, AppUsageHistoryResponse, AppUsageItem,
@@ -40,7 +42,13 @@ pub fn device_router() -> Router<AppState> {
 /// Routes:
 /// - GET /api/admin/v1/organizations/:org_id/app-usage/analytics - Get org-wide analytics
 pub fn org_router() -> Router<AppState> {
-    Router::new().route("/analytics", get(get_org_app_usage_analytics))
+    Router::new().route(
+        "/analytics",
+        get(get_org_app_usage_analytics).layer(middleware::from_fn_with_state(
+            RouteClass::Analytics,
+            concurrency_limit,
+        )),
+    )
 }
 
 /// Get app usage summary for a device.
@@ -6,8 +6,9 @@
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
+    middleware,
     response::IntoResponse,
-    routing::get,
+    routing::{delete, get},
     Json, Router,
 };
 use base64::{engine::general_purpose::STANDARD, Engine};
@@ -16,18 +17,43 @@ use uuid::Uuid;
 
 use crate::app::AppState;
 use crate::error::ApiError;
+use crate::middleware::{concurrency_limit, export_statement_timeout, RouteClass};
+use crate::services::xlsx::{XlsxWriter, XLSX_CONTENT_TYPE};
 use domain::models::{
-    AsyncExportResponse, AuditLog, AuditLogPagination, ExportAuditLogsQuery, ExportFormat,
-    ExportJobResponse, ExportJobStatus, ListAuditLogsQuery, ListAuditLogsResponse,
-    SyncExportResponse, MAX_EXPORT_RECORDS, MAX_SYNC_EXPORT_RECORDS,
+    validate_export_destination, AsyncExportResponse, AuditLog, AuditLogPagination,
+    ExportAuditLogsQuery, ExportFormat, ExportJobKind, ExportJobResponse, ExportJobStatus,
+    IncrementalExportQuery, IncrementalExportResponse, ListAuditExportCursorsResponse,
+    ListAuditLogsQuery, ListAuditLogsResponse, SyncExportResponse, MAX_EXPORT_RECORDS,
+    MAX_SYNC_EXPORT_RECORDS,
+};
+use persistence::repositories::{
+    AuditExportCursorRepository, AuditLogRepository, ExportJobRepository,
 };
-use persistence::repositories::{AuditExportJobRepository, AuditLogRepository};
 
 /// Create audit logs router.
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/", get(list_audit_logs))
-        .route("/export", get(export_audit_logs))
+        .route(
+            "/export",
+            get(export_audit_logs)
+                .layer(middleware::from_fn_with_state(
+                    RouteClass::Export,
+                    concurrency_limit,
+                ))
+                .layer(middleware::from_fn(export_statement_timeout)),
+        )
+        .route(
+            "/export/incremental",
+            get(export_audit_logs_incremental)
+                .layer(middleware::from_fn_with_state(
+                    RouteClass::Export,
+                    concurrency_limit,
+                ))
+                .layer(middleware::from_fn(export_statement_timeout)),
+        )
+        .route("/export/cursors", get(list_export_cursors))
+        .route("/export/cursors/:destination", delete(reset_export_cursor))
         .route("/export/:job_id", get(get_export_job_status))
         .route("/:log_id", get(get_audit_log))
 }
@@ -41,19 +67,20 @@ pub async fn list_audit_logs(
 ) -> Result<impl IntoResponse, ApiError> {
     let repo = AuditLogRepository::new(state.pool.clone());
 
-    let (logs, total) = repo.list(org_id, &query).await?;
+    let (logs, total, has_more) = repo.list(org_id, &query).await?;
 
     let page = query.page.unwrap_or(1).max(1);
     let per_page = query.per_page.unwrap_or(50).clamp(1, 100);
-    let total_pages = ((total as f64) / (per_page as f64)).ceil() as i32;
 
     let response = ListAuditLogsResponse {
         data: logs,
         pagination: AuditLogPagination {
             page,
             per_page,
-            total,
-            total_pages,
+            total: total.value(),
+            total_pages: total.total_pages(per_page as i64).map(|pages| pages as i32),
+            total_estimated: total.is_estimate(),
+            has_more,
         },
     };
 
@@ -86,27 +113,16 @@ pub async fn export_audit_logs(
     Path(org_id): Path<Uuid>,
     Query(query): Query<ExportAuditLogsQuery>,
 ) -> Result<impl IntoResponse, ApiError> {
-    // Check export rate limit (10/hour/org)
-    if let Some(ref export_limiter) = state.export_rate_limiter {
-        if let Err(retry_after) = export_limiter.check(org_id) {
-            return Err(ApiError::RateLimitedWithRetry {
-                message: format!(
-                    "Export rate limit of {} exports/hour exceeded for this organization",
-                    export_limiter.rate_limit_per_hour()
-                ),
-                retry_after,
-            });
-        }
-    }
+    check_export_rate_limit(&state, org_id)?;
 
     let log_repo = AuditLogRepository::new(state.pool.clone());
-    let job_repo = AuditExportJobRepository::new(state.pool.clone());
+    let job_repo = ExportJobRepository::new(state.pool.clone());
 
     let format = query.format.unwrap_or_default();
     let list_query = query.to_list_query();
 
     // First, count the records to determine sync vs async export
-    let (_, total) = log_repo.list(org_id, &list_query).await?;
+    let total = log_repo.count_matching(org_id, &list_query).await?;
 
     if total > MAX_EXPORT_RECORDS {
         return Err(ApiError::Validation(format!(
@@ -136,7 +152,9 @@ pub async fn export_audit_logs(
 
     // Async export: create job and process in background
     let filters = serde_json::to_value(&query).ok();
-    let job = job_repo.create(org_id, format, filters).await?;
+    let job = job_repo
+        .create(org_id, ExportJobKind::AuditLogs, format, filters)
+        .await?;
 
     // Spawn background task to process the export
     let pool = state.pool.clone();
@@ -158,15 +176,142 @@ pub async fn export_audit_logs(
     Ok((StatusCode::ACCEPTED, Json(ExportResponse::Async(response))))
 }
 
+/// Export the audit logs recorded since the destination's previous export.
+///
+/// Each destination (e.g. a nightly data warehouse pull) keeps its own cursor,
+/// so repeated runs never return the same row twice. At most
+/// `MAX_EXPORT_RECORDS` rows are returned per call, oldest first; `has_more`
+/// tells the caller to request again.
+#[axum::debug_handler]
+pub async fn export_audit_logs_incremental(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    Query(query): Query<IncrementalExportQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    validate_export_destination(&query.destination).map_err(ApiError::Validation)?;
+    check_export_rate_limit(&state, org_id)?;
+
+    let log_repo = AuditLogRepository::new(state.pool.clone());
+    let cursor_repo = AuditExportCursorRepository::new(state.pool.clone());
+
+    let format = query.format.unwrap_or_default();
+    let cursor = cursor_repo
+        .get_or_create(org_id, &query.destination)
+        .await?;
+
+    let mut rows = log_repo
+        .list_after_sequence(org_id, cursor.last_sequence, MAX_EXPORT_RECORDS + 1)
+        .await?;
+    let has_more = rows.len() as i64 > MAX_EXPORT_RECORDS;
+    rows.truncate(MAX_EXPORT_RECORDS as usize);
+
+    let to_sequence = rows
+        .last()
+        .map(|(seq, _)| *seq)
+        .unwrap_or(cursor.last_sequence);
+    let logs: Vec<AuditLog> = rows.into_iter().map(|(_, log)| log).collect();
+    let (data, content_type) = generate_export_data(&logs, format)?;
+
+    if !logs.is_empty() {
+        let advanced = cursor_repo
+            .advance(
+                org_id,
+                &query.destination,
+                cursor.last_sequence,
+                to_sequence,
+                logs.len() as i64,
+            )
+            .await?;
+        if !advanced {
+            return Err(ApiError::Conflict(
+                "Another export of this destination ran concurrently; retry".to_string(),
+            ));
+        }
+    }
+
+    tracing::info!(
+        org_id = %org_id,
+        destination = %query.destination,
+        record_count = logs.len(),
+        from_sequence = cursor.last_sequence,
+        to_sequence = to_sequence,
+        "Incremental audit log export"
+    );
+
+    let response = IncrementalExportResponse {
+        destination: query.destination,
+        format,
+        record_count: logs.len() as i64,
+        from_sequence: cursor.last_sequence,
+        to_sequence,
+        has_more,
+        download_url: format!("data:{};base64,{}", content_type, STANDARD.encode(&data)),
+    };
+
+    Ok((StatusCode::OK, Json(response)))
+}
+
+/// List the incremental export cursors of an organization.
+#[axum::debug_handler]
+pub async fn list_export_cursors(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let cursors = AuditExportCursorRepository::new(state.pool.clone())
+        .list_for_org(org_id)
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ListAuditExportCursorsResponse { cursors }),
+    ))
+}
+
+/// Reset an incremental export cursor; the next export of the destination
+/// starts from the beginning of the audit log.
+#[axum::debug_handler]
+pub async fn reset_export_cursor(
+    State(state): State<AppState>,
+    Path((org_id, destination)): Path<(Uuid, String)>,
+) -> Result<StatusCode, ApiError> {
+    let deleted = AuditExportCursorRepository::new(state.pool.clone())
+        .delete(org_id, &destination)
+        .await?;
+
+    if deleted {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(ApiError::NotFound("Export cursor not found".to_string()))
+    }
+}
+
+/// Enforce the per-organization export rate limit (10/hour/org).
+fn check_export_rate_limit(state: &AppState, org_id: Uuid) -> Result<(), ApiError> {
+    if let Some(ref export_limiter) = state.export_rate_limiter {
+        if let Err(retry_after) = export_limiter.check(org_id) {
+            return Err(ApiError::RateLimitedWithRetry {
+                message: format!(
+                    "Export rate limit of {} exports/hour exceeded for this organization",
+                    export_limiter.rate_limit_per_hour()
+                ),
+                retry_after,
+            });
+        }
+    }
+    Ok(())
+}
+
 /// Get export job status.
 #[axum::debug_handler]
 pub async fn get_export_job_status(
     State(state): State<AppState>,
     Path((org_id, job_id)): Path<(Uuid, String)>,
 ) -> Result<impl IntoResponse, ApiError> {
-    let job_repo = AuditExportJobRepository::new(state.pool.clone());
+    let job_repo = ExportJobRepository::new(state.pool.clone());
 
-    let job = job_repo.find_by_job_id(org_id, &job_id).await?;
+    let job = job_repo
+        .find_by_job_id(org_id, ExportJobKind::AuditLogs, &job_id)
+        .await?;
 
     match job {
         Some(job) => {
@@ -193,7 +338,7 @@ async fn process_export_job(
     format: ExportFormat,
 ) {
     let log_repo = AuditLogRepository::new(pool.clone());
-    let job_repo = AuditExportJobRepository::new(pool);
+    let job_repo = ExportJobRepository::new(pool);
 
     // Mark job as processing
     if let Err(e) = job_repo.mark_processing(&job_id).await {
@@ -250,9 +395,51 @@ fn generate_export_data(
             let csv = generate_csv(logs)?;
             Ok((csv.into_bytes(), "text/csv"))
         }
+        ExportFormat::Xlsx => {
+            let xlsx = generate_xlsx(logs).map_err(|e| ApiError::Internal(e.to_string()))?;
+            Ok((xlsx, XLSX_CONTENT_TYPE))
+        }
     }
 }
 
+/// Column headers shared by CSV and XLSX exports.
+const EXPORT_COLUMNS: [&str; 11] = [
+    "id",
+    "timestamp",
+    "actor_type",
+    "actor_id",
+    "actor_email",
+    "action",
+    "resource_type",
+    "resource_id",
+    "resource_name",
+    "ip_address",
+    "user_agent",
+];
+
+/// Generate an XLSX workbook from audit logs, with typed timestamp cells.
+fn generate_xlsx(logs: &[AuditLog]) -> std::io::Result<Vec<u8>> {
+    let mut writer = XlsxWriter::new();
+    writer.start_sheet("Audit log", &EXPORT_COLUMNS)?;
+    for log in logs {
+        let metadata = log.metadata.as_ref();
+        writer.write_row(&[
+            log.id.to_string().into(),
+            log.timestamp.into(),
+            log.actor.actor_type.to_string().into(),
+            log.actor.id.map(|u| u.to_string()).into(),
+            log.actor.email.clone().into(),
+            log.action.clone().into(),
+            log.resource.resource_type.clone().into(),
+            log.resource.id.clone().into(),
+            log.resource.name.clone().into(),
+            metadata.and_then(|m| m.ip_address.clone()).into(),
+            metadata.and_then(|m| m.user_agent.clone()).into(),
+        ])?;
+    }
+    writer.finish()
+}
+
 /// Generate CSV from audit logs.
 /// Includes UTF-8 BOM for Excel compatibility.
 fn generate_csv(logs: &[AuditLog]) -> Result<String, ApiError> {
@@ -262,7 +449,8 @@ fn generate_csv(logs: &[AuditLog]) -> Result<String, ApiError> {
     csv.push('\u{FEFF}');
 
     // Header
-    csv.push_str("id,timestamp,actor_type,actor_id,actor_email,action,resource_type,resource_id,resource_name,ip_address,user_agent\n");
+    csv.push_str(&EXPORT_COLUMNS.join(","));
+    csv.push('\n');
 
     for log in logs {
         csv.push_str(&format!(
@@ -320,6 +508,15 @@ mod tests {
         assert!(query.per_page.is_none());
     }
 
+    #[test]
+    fn test_generate_xlsx_has_header_sheet() {
+        use crate::services::xlsx::read_part;
+
+        let xlsx = generate_xlsx(&[]).unwrap();
+        assert!(read_part(&xlsx, "xl/workbook.xml").contains(r#"<sheet name="Audit log""#));
+        assert!(read_part(&xlsx, "xl/worksheets/sheet1.xml").contains("<t>actor_email</t>"));
+    }
+
     #[test]
     fn test_escape_csv_simple() {
         assert_eq!(escape_csv("hello"), "hello");
@@ -14,6 +14,7 @@ use validator::Validate;
 
 use crate::app::AppState;
 use crate::error::ApiError;
+use crate::routes::groups::join_with_invite;
 use crate::services::auth::{AuthError, AuthService};
 
 /// Attempt to link a device to a user after successful authentication.
@@ -100,6 +101,29 @@ async fn try_link_device_to_user(
     Ok(true)
 }
 
+/// Join the group of an invite code passed during authentication.
+///
+/// Best effort: authentication succeeds even if the invite can't be used.
+/// Returns the ID of the joined group.
+async fn try_accept_group_invite(
+    state: &AppState,
+    user_id: Uuid,
+    code: Option<&str>,
+) -> Option<Uuid> {
+    let code = code.filter(|code| !code.is_empty())?;
+    match join_with_invite(state, user_id, code).await {
+        Ok(joined) => Some(joined.group.id),
+        Err(e) => {
+            tracing::warn!(
+                user_id = %user_id,
+                error = %e,
+                "Could not accept group invite during authentication"
+            );
+            None
+        }
+    }
+}
+
 /// Helper to create AuthService with OAuth config from AppState.
 fn create_auth_service(state: &AppState) -> Result<AuthService, ApiError> {
     let google_client_id = if state.config.oauth.google_client_id.is_empty() {
@@ -119,6 +143,7 @@ fn create_auth_service(state: &AppState) -> Result<AuthService, ApiError> {
         google_client_id,
         apple_client_id,
     )
+    .map(|service| service.with_password_params(state.config.security.password_hash_params()))
     .map_err(|e| {
         tracing::error!(error = %e, "Failed to initialize auth service");
         ApiError::Internal(format!("Failed to initialize auth service: {}", e))
@@ -138,7 +163,8 @@ pub struct RegisterRequest {
     pub password: String,
 
     /// User's display name
-    #[validate(length(min = 1, max = 100, message = "Display name must be 1-100 characters"))]
+    #[serde(deserialize_with = "shared::text::deserialize_line")]
+    #[validate(custom(function = "shared::text::validate_display_name"))]
     pub display_name: String,
 
     /// Optional device ID to link after registration
@@ -151,6 +177,9 @@ pub struct RegisterRequest {
 
     /// Optional invite token (required when invite_only mode is enabled)
     pub invite_token: Option<String>,
+
+    /// Optional group invite code to join once registered
+    pub group_invite_code: Option<String>,
 }
 
 /// User information in response.
@@ -177,24 +206,50 @@ pub struct TokensResponse {
     pub expires_in: i64,
 }
 
+/// Token information for the response body.
+///
+/// Returns `None` when the client sent `X-Session-Mode: cookie`, so browser
+/// clients receive the tokens only as httpOnly cookies.
+fn response_tokens(
+    state: &AppState,
+    request_headers: &HeaderMap,
+    tokens: TokensResponse,
+) -> Option<TokensResponse> {
+    if state
+        .cookie_helper
+        .cookie_session_requested(request_headers)
+    {
+        None
+    } else {
+        Some(tokens)
+    }
+}
+
 /// Response body for successful registration.
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub struct RegisterResponse {
     pub user: UserResponse,
-    pub tokens: TokensResponse,
+    /// Omitted when the client requested cookie-only delivery
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tokens: Option<TokensResponse>,
     pub device_linked: bool,
     pub requires_email_verification: bool,
+    /// Group joined with `group_invite_code`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub joined_group_id: Option<Uuid>,
 }
 
 /// Register a new user with email and password.
 ///
 /// POST /api/v1/auth/register
 ///
-/// When cookie authentication is enabled, tokens are also set as httpOnly cookies.
-/// The response body still contains the tokens for backward compatibility.
+/// When cookie authentication is enabled, tokens are also set as httpOnly cookies
+/// together with a CSRF cookie. The response body still contains the tokens for
+/// backward compatibility unless the request sends `X-Session-Mode: cookie`.
 pub async fn register(
     State(state): State<AppState>,
+    request_headers: HeaderMap,
     Json(request): Json<RegisterRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
     // Check auth toggles
@@ -299,6 +354,9 @@ pub async fn register(
         }
     }
 
+    let joined_group_id =
+        try_accept_group_invite(&state, result.user_id, request.group_invite_code.as_deref()).await;
+
     // Build response
     let response = RegisterResponse {
         user: UserResponse {
@@ -311,14 +369,19 @@ pub async fn register(
             organization_id: None,
             created_at: chrono::Utc::now().to_rfc3339(),
         },
-        tokens: TokensResponse {
-            access_token: result.access_token.clone(),
-            refresh_token: result.refresh_token.clone(),
-            token_type: "Bearer".to_string(),
-            expires_in: result.access_token_expires_in,
-        },
+        tokens: response_tokens(
+            &state,
+            &request_headers,
+            TokensResponse {
+                access_token: result.access_token.clone(),
+                refresh_token: result.refresh_token.clone(),
+                token_type: "Bearer".to_string(),
+                expires_in: result.access_token_expires_in,
+            },
+        ),
         device_linked: false, // Device linking will be implemented later
         requires_email_verification: true,
+        joined_group_id,
     };
 
     // Set cookies if cookie authentication is enabled
@@ -349,6 +412,9 @@ pub struct LoginRequest {
 
     /// Device name (used when linking the device)
     pub device_name: Option<String>,
+
+    /// Optional group invite code to join after signing in
+    pub group_invite_code: Option<String>,
 }
 
 /// Request body for OAuth sign-in.
@@ -368,6 +434,9 @@ pub struct OAuthLoginRequest {
 
     /// Device name (used when linking the device)
     pub device_name: Option<String>,
+
+    /// Optional group invite code to join after signing in
+    pub group_invite_code: Option<String>,
 }
 
 /// Response body for successful login.
@@ -375,19 +444,26 @@ pub struct OAuthLoginRequest {
 #[serde(rename_all = "snake_case")]
 pub struct LoginResponse {
     pub user: UserResponse,
-    pub tokens: TokensResponse,
+    /// Omitted when the client requested cookie-only delivery
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tokens: Option<TokensResponse>,
     /// Whether a device was linked to the user during this authentication
     pub device_linked: bool,
+    /// Group joined with `group_invite_code`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub joined_group_id: Option<Uuid>,
 }
 
 /// Login with email and password.
 ///
 /// POST /api/v1/auth/login
 ///
-/// When cookie authentication is enabled, tokens are also set as httpOnly cookies.
-/// The response body still contains the tokens for backward compatibility.
+/// When cookie authentication is enabled, tokens are also set as httpOnly cookies
+/// together with a CSRF cookie. The response body still contains the tokens for
+/// backward compatibility unless the request sends `X-Session-Mode: cookie`.
 pub async fn login(
     State(state): State<AppState>,
+    request_headers: HeaderMap,
     Json(request): Json<LoginRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
     // Check auth toggles
@@ -433,6 +509,9 @@ pub async fn login(
     )
     .await?;
 
+    let joined_group_id =
+        try_accept_group_invite(&state, result.user_id, request.group_invite_code.as_deref()).await;
+
     // Build response
     let response = LoginResponse {
         user: UserResponse {
@@ -445,13 +524,18 @@ pub async fn login(
             organization_id: None,
             created_at: chrono::Utc::now().to_rfc3339(),
         },
-        tokens: TokensResponse {
-            access_token: result.access_token.clone(),
-            refresh_token: result.refresh_token.clone(),
-            token_type: "Bearer".to_string(),
-            expires_in: result.access_token_expires_in,
-        },
+        tokens: response_tokens(
+            &state,
+            &request_headers,
+            TokensResponse {
+                access_token: result.access_token.clone(),
+                refresh_token: result.refresh_token.clone(),
+                token_type: "Bearer".to_string(),
+                expires_in: result.access_token_expires_in,
+            },
+        ),
         device_linked,
+        joined_group_id,
     };
 
     // Set cookies if cookie authentication is enabled
@@ -469,10 +553,12 @@ pub async fn login(
 ///
 /// POST /api/v1/auth/oauth
 ///
-/// When cookie authentication is enabled, tokens are also set as httpOnly cookies.
-/// The response body still contains the tokens for backward compatibility.
+/// When cookie authentication is enabled, tokens are also set as httpOnly cookies
+/// together with a CSRF cookie. The response body still contains the tokens for
+/// backward compatibility unless the request sends `X-Session-Mode: cookie`.
 pub async fn oauth_login(
     State(state): State<AppState>,
+    request_headers: HeaderMap,
     Json(request): Json<OAuthLoginRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
     // Validate request
@@ -512,6 +598,9 @@ pub async fn oauth_login(
     )
     .await?;
 
+    let joined_group_id =
+        try_accept_group_invite(&state, result.user_id, request.group_invite_code.as_deref()).await;
+
     // Determine auth provider for response
     let auth_provider = request.provider.to_lowercase();
 
@@ -527,13 +616,18 @@ pub async fn oauth_login(
             organization_id: None,
             created_at: chrono::Utc::now().to_rfc3339(),
         },
-        tokens: TokensResponse {
-            access_token: result.access_token.clone(),
-            refresh_token: result.refresh_token.clone(),
-            token_type: "Bearer".to_string(),
-            expires_in: result.access_token_expires_in,
-        },
+        tokens: response_tokens(
+            &state,
+            &request_headers,
+            TokensResponse {
+                access_token: result.access_token.clone(),
+                refresh_token: result.refresh_token.clone(),
+                token_type: "Bearer".to_string(),
+                expires_in: result.access_token_expires_in,
+            },
+        ),
         device_linked,
+        joined_group_id,
     };
 
     // Set cookies if cookie authentication is enabled
@@ -562,7 +656,9 @@ pub struct RefreshRequest {
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub struct RefreshResponse {
-    pub tokens: TokensResponse,
+    /// Omitted when the client requested cookie-only delivery
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tokens: Option<TokensResponse>,
 }
 
 /// Refresh access token using a valid refresh token.
@@ -571,8 +667,10 @@ pub struct RefreshResponse {
 ///
 /// When cookie authentication is enabled:
 /// - The refresh token is read from the `refresh_token` cookie
-/// - New tokens are set as httpOnly cookies in the response
-/// - The response body still contains tokens for backward compatibility
+/// - New tokens are set as httpOnly cookies in the response, with a new CSRF cookie
+/// - The response body still contains tokens for backward compatibility,
+///   unless the request sends `X-Session-Mode: cookie`
+/// - A cookie-authenticated request must carry the CSRF header
 ///
 /// When cookie authentication is disabled:
 /// - The refresh token must be provided in the request body
@@ -624,12 +722,16 @@ pub async fn refresh(
 
     // Build response
     let response = RefreshResponse {
-        tokens: TokensResponse {
-            access_token: result.access_token.clone(),
-            refresh_token: result.refresh_token.clone(),
-            token_type: "Bearer".to_string(),
-            expires_in: result.expires_in,
-        },
+        tokens: response_tokens(
+            &state,
+            &headers,
+            TokensResponse {
+                access_token: result.access_token.clone(),
+                refresh_token: result.refresh_token.clone(),
+                token_type: "Bearer".to_string(),
+                expires_in: result.expires_in,
+            },
+        ),
     };
 
     // Set cookies if cookie authentication is enabled
@@ -643,6 +745,33 @@ pub async fn refresh(
     Ok((response_headers, Json(response)))
 }
 
+/// Response body for CSRF token issuance.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct CsrfTokenResponse {
+    pub csrf_token: String,
+}
+
+/// Issue a new CSRF token.
+///
+/// GET /api/v1/auth/csrf
+///
+/// Sets a fresh CSRF cookie and returns its value, for browser clients whose
+/// CSRF cookie was lost. Send it in the CSRF header of cookie-authenticated
+/// mutating requests.
+/// Returns 404 if cookie authentication or CSRF protection is disabled.
+pub async fn issue_csrf_token(
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, ApiError> {
+    let mut headers = HeaderMap::new();
+    let csrf_token = state
+        .cookie_helper
+        .add_csrf_cookie(&mut headers)
+        .ok_or_else(|| ApiError::NotFound("CSRF protection is not enabled".to_string()))?;
+
+    Ok((headers, Json(CsrfTokenResponse { csrf_token })))
+}
+
 /// Request body for logout.
 /// When cookie authentication is enabled, the refresh_token can be read from cookies
 /// and the body may only contain `all_devices`.
@@ -923,6 +1052,7 @@ mod tests {
             device_id: None,
             device_name: None,
             invite_token: None,
+            group_invite_code: None,
         };
 
         assert!(request.validate().is_ok());
@@ -937,6 +1067,7 @@ mod tests {
             device_id: None,
             device_name: None,
             invite_token: None,
+            group_invite_code: None,
         };
 
         assert!(request.validate().is_err());
@@ -951,6 +1082,7 @@ mod tests {
             device_id: None,
             device_name: None,
             invite_token: None,
+            group_invite_code: None,
         };
 
         assert!(request.validate().is_err());
@@ -965,6 +1097,7 @@ mod tests {
             device_id: None,
             device_name: None,
             invite_token: None,
+            group_invite_code: None,
         };
 
         assert!(request.validate().is_err());
@@ -979,6 +1112,7 @@ mod tests {
             device_id: None,
             device_name: None,
             invite_token: None,
+            group_invite_code: None,
         };
 
         assert!(request.validate().is_err());
@@ -991,6 +1125,7 @@ mod tests {
             password: "SecureP@ss1".to_string(),
             device_id: None,
             device_name: None,
+            group_invite_code: None,
         };
 
         assert!(request.validate().is_ok());
@@ -1003,6 +1138,7 @@ mod tests {
             password: "SecureP@ss1".to_string(),
             device_id: None,
             device_name: None,
+            group_invite_code: None,
         };
 
         assert!(request.validate().is_err());
@@ -1015,6 +1151,7 @@ mod tests {
             password: "".to_string(),
             device_id: None,
             device_name: None,
+            group_invite_code: None,
         };
 
         assert!(request.validate().is_err());
@@ -1212,6 +1349,7 @@ mod tests {
             id_token: "some.id.token".to_string(),
             device_id: None,
             device_name: None,
+            group_invite_code: None,
         };
 
         assert!(request.validate().is_ok());
@@ -1224,6 +1362,7 @@ mod tests {
             id_token: "some.id.token".to_string(),
             device_id: None,
             device_name: None,
+            group_invite_code: None,
         };
 
         assert!(request.validate().is_err());
@@ -1236,6 +1375,7 @@ mod tests {
             id_token: "".to_string(),
             device_id: None,
             device_name: None,
+            group_invite_code: None,
         };
 
         assert!(request.validate().is_err());
@@ -1248,6 +1388,7 @@ mod tests {
             id_token: "some.id.token".to_string(),
             device_id: Some("device-123".to_string()),
             device_name: Some("My iPhone".to_string()),
+            group_invite_code: None,
         };
 
         assert!(request.validate().is_ok());
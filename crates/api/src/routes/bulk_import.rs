@@ -12,6 +12,7 @@ use axum::{
 use persistence::repositories::{
     DevicePolicyRepository, DeviceRepository, GroupRepository, OrgUserRepository, UserRepository,
 };
+use persistence::retry::retry_on_conflict;
 use uuid::Uuid;
 use validator::Validate;
 
@@ -227,13 +228,14 @@ async fn bulk_import_devices(
         // Convert group_id to string for storage
         let group_id_str = device_item.group_id.as_ref().map(|g| g.to_string());
 
-        // Process based on existence and options
+        // Process based on existence and options; writes are retried when a
+        // concurrent import deadlocks on the same rows
         let result = match existing_device {
             Some(existing) => {
                 if request.options.update_existing {
                     // Update existing device
-                    match device_repo
-                        .update_bulk_device(
+                    match retry_on_conflict("update_bulk_device", || {
+                        device_repo.update_bulk_device(
                             existing.id,
                             &device_item.display_name,
                             group_id_str.as_deref(),
@@ -241,7 +243,8 @@ async fn bulk_import_devices(
                             assigned_user_id,
                             device_item.metadata.as_ref(),
                         )
-                        .await
+                    })
+                    .await
                     {
                         Ok(d) => BulkImportResult::Updated(d.id),
                         Err(e) => BulkImportResult::Error(e.to_string()),
@@ -252,8 +255,8 @@ async fn bulk_import_devices(
             }
             None => {
                 // Create new device
-                match device_repo
-                    .create_bulk_device(
+                match retry_on_conflict("create_bulk_device", || {
+                    device_repo.create_bulk_device(
                         org_id,
                         device_item.external_id.as_deref(),
                         &device_item.display_name,
@@ -262,7 +265,8 @@ async fn bulk_import_devices(
                         assigned_user_id,
                         device_item.metadata.as_ref(),
                     )
-                    .await
+                })
+                .await
                 {
                     Ok(d) => BulkImportResult::Created(d.id),
                     Err(e) => BulkImportResult::Error(e.to_string()),
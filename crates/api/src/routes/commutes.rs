@@ -0,0 +1,43 @@
+//! Commute pattern handlers.
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use domain::models::commute::{CommuteResponse, ListCommutesResponse};
+use persistence::repositories::{CommuteRepository, DeviceRepository};
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::error::ApiError;
+
+/// List the commutes of a device.
+///
+/// GET /api/v1/devices/:deviceId/commutes
+///
+/// Commutes are recurring trips between two places, detected nightly from
+/// the device's trips of the last 8 weeks; most frequent first.
+/// Returns 404 if device not found or inactive.
+pub async fn get_device_commutes(
+    State(state): State<AppState>,
+    Path(device_id): Path<Uuid>,
+) -> Result<Json<ListCommutesResponse>, ApiError> {
+    let device = DeviceRepository::new(state.pool.clone())
+        .find_by_device_id(device_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Device not found".to_string()))?;
+    if !device.active {
+        return Err(ApiError::NotFound("Device not found".to_string()));
+    }
+
+    let entities = CommuteRepository::new(state.pool.clone())
+        .list_by_device(device_id)
+        .await?;
+    let detected_at = entities.iter().map(|c| c.detected_at).max();
+
+    Ok(Json(ListCommutesResponse {
+        device_id,
+        commutes: entities.into_iter().map(CommuteResponse::from).collect(),
+        detected_at,
+    }))
+}
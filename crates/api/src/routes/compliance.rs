@@ -5,6 +5,7 @@
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
+    middleware,
     response::IntoResponse,
     routing::get,
     Json, Router,
@@ -15,15 +16,17 @@ use uuid::Uuid;
 
 use crate::app::AppState;
 use crate::error::ApiError;
+use crate::middleware::{concurrency_limit, export_statement_timeout, RouteClass};
 use domain::models::{
     AuditActivitySummary, AuditLogStats, ComplianceAssessment, ComplianceDashboardResponse,
     ComplianceFinding, ComplianceReportFormat, ComplianceReportQuery, ComplianceReportResponse,
     ComplianceStatus, DataRetentionStatus, DataSubjectRequestReportSummary,
-    DataSubjectRequestStats, FindingSeverity, OrganizationReportSummary, RequestStatusCounts,
-    RequestTypeCount,
+    DataSubjectRequestStats, DeviceConfigurationSummary, FindingSeverity,
+    OrganizationReportSummary, PolicyDeviceCount, RequestStatusCounts, RequestTypeCount,
 };
 use persistence::repositories::{
     AuditLogRepository, DashboardRepository, DataSubjectRequestRepository,
+    DeviceConfigHistoryRepository,
 };
 
 /// Create compliance router.
@@ -34,7 +37,15 @@ use persistence::repositories::{
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/", get(get_compliance_dashboard))
-        .route("/report", get(generate_compliance_report))
+        .route(
+            "/report",
+            get(generate_compliance_report)
+                .layer(middleware::from_fn_with_state(
+                    RouteClass::Export,
+                    concurrency_limit,
+                ))
+                .layer(middleware::from_fn(export_statement_timeout)),
+        )
 }
 
 /// Get compliance dashboard.
@@ -64,24 +75,24 @@ async fn get_compliance_dashboard(
     let last_7d = now - Duration::days(7);
     let last_30d = now - Duration::days(30);
 
-    // For audit log time-based counts, we'll use the list method with date filters
+    // Time-based audit counts use the list filters with date ranges
     let query_24h = domain::models::ListAuditLogsQuery {
         from: Some(last_24h),
         ..Default::default()
     };
-    let (_, count_24h) = audit_repo.list(org_id, &query_24h).await?;
+    let count_24h = audit_repo.count_matching(org_id, &query_24h).await?;
 
     let query_7d = domain::models::ListAuditLogsQuery {
         from: Some(last_7d),
         ..Default::default()
     };
-    let (_, count_7d) = audit_repo.list(org_id, &query_7d).await?;
+    let count_7d = audit_repo.count_matching(org_id, &query_7d).await?;
 
     let query_30d = domain::models::ListAuditLogsQuery {
         from: Some(last_30d),
         ..Default::default()
     };
-    let (_, count_30d) = audit_repo.list(org_id, &query_30d).await?;
+    let count_30d = audit_repo.count_matching(org_id, &query_30d).await?;
 
     // Calculate compliance score
     let (score, status, _findings) =
@@ -167,7 +178,24 @@ async fn generate_compliance_report(
         to: Some(period_end),
         ..Default::default()
     };
-    let (_, total_audit_entries) = audit_repo.list(org_id, &audit_query).await?;
+    let total_audit_entries = audit_repo.count_matching(org_id, &audit_query).await?;
+
+    // Device configuration as it was at the end of the period
+    let config_as_of = period_end.min(now);
+    let history_repo = DeviceConfigHistoryRepository::new(state.pool.clone());
+    let (total_devices, devices_with_locked_settings) = history_repo
+        .count_devices_as_of(org_id, config_as_of)
+        .await?;
+    let by_policy: Vec<PolicyDeviceCount> = history_repo
+        .policy_assignment_counts_as_of(org_id, config_as_of)
+        .await?
+        .into_iter()
+        .map(|p| PolicyDeviceCount {
+            policy_id: p.policy_id,
+            policy_name: p.policy_name,
+            device_count: p.device_count,
+        })
+        .collect();
 
     // Calculate compliance assessment
     let (score, status, findings) =
@@ -207,6 +235,13 @@ async fn generate_compliance_report(
             total_entries: total_audit_entries,
             top_actions: vec![], // Would need additional query for top actions
         },
+        device_configuration: DeviceConfigurationSummary {
+            as_of: config_as_of,
+            total_devices,
+            devices_with_policy: by_policy.iter().map(|p| p.device_count).sum(),
+            devices_with_locked_settings,
+            by_policy,
+        },
         compliance_assessment: ComplianceAssessment {
             score,
             status,
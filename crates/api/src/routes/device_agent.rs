@@ -0,0 +1,396 @@
+//! Device agent channel route handlers.
+//!
+//! Enrolled devices open a WebSocket at `/api/v1/devices/agent`, using their
+//! device token as a Bearer token. The server sends the device's pending
+//! commands on connect and pushes new commands and settings changes as they
+//! are issued; the device sends heartbeats and reports command outcomes.
+//! Connections without traffic for [`AGENT_MISSED_HEARTBEATS`] heartbeat
+//! intervals are closed.
+
+use std::collections::HashSet;
+
+use axum::{
+    extract::{
+        ws::{
+            close_code, rejection::WebSocketUpgradeRejection, CloseFrame, Message, WebSocket,
+            WebSocketUpgrade,
+        },
+        Path, State,
+    },
+    http::{header, HeaderMap},
+    response::Response,
+    Json,
+};
+use chrono::Utc;
+use domain::models::{
+    AgentClientMessage, AgentServerMessage, ApiEndpointClass, ListAgentConnectionsResponse,
+    OrgUserRole, AGENT_HEARTBEAT_INTERVAL_SECS, AGENT_MISSED_HEARTBEATS,
+    MAX_AGENT_FAILURE_REASON_LENGTH,
+};
+use persistence::repositories::{
+    DeviceCommandRepository, DeviceRepository, DeviceTokenRepository, OrgUserRepository,
+};
+use tokio::sync::mpsc;
+use tokio::time::{sleep_until, Duration, Instant};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::error::ApiError;
+use crate::extractors::UserAuth;
+use crate::services::device_agent::AgentPush;
+use crate::services::device_usage::track_device_request;
+
+/// Largest message accepted from an agent, in bytes.
+const MAX_AGENT_MESSAGE_SIZE: usize = 64 * 1024;
+
+/// Device authenticated for an agent connection.
+#[derive(Debug, Clone, Copy)]
+struct AgentDevice {
+    device_id: Uuid,
+    device_pk: i64,
+    organization_id: Uuid,
+}
+
+/// Open the agent WebSocket of a device.
+///
+/// GET /api/v1/devices/agent
+///
+/// Authenticated with `Authorization: Bearer <device token>`.
+pub async fn connect_agent(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    upgrade: Result<WebSocketUpgrade, WebSocketUpgradeRejection>,
+) -> Result<Response, ApiError> {
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .ok_or_else(|| ApiError::Unauthorized("Device token required".to_string()))?;
+
+    let token_repo = DeviceTokenRepository::new(state.pool.clone());
+    let device_token = token_repo
+        .find_valid_token(token)
+        .await?
+        .ok_or_else(|| ApiError::Unauthorized("Invalid or expired device token".to_string()))?;
+
+    let device = DeviceRepository::new(state.pool.clone())
+        .find_by_id(device_token.device_id)
+        .await?
+        .filter(|d| d.active)
+        .ok_or_else(|| ApiError::NotFound("Device not found or inactive".to_string()))?;
+
+    let upgrade = upgrade.map_err(|e| ApiError::Validation(e.body_text()))?;
+
+    token_repo.update_last_used(device_token.id).await?;
+
+    let agent = AgentDevice {
+        device_id: device.device_id,
+        device_pk: device.id,
+        organization_id: device_token.organization_id,
+    };
+    Ok(upgrade
+        .max_message_size(MAX_AGENT_MESSAGE_SIZE)
+        .max_frame_size(MAX_AGENT_MESSAGE_SIZE)
+        .on_failed_upgrade(move |e| {
+            warn!(
+                device_id = %agent.device_id,
+                error = %e,
+                "Agent connection upgrade failed"
+            )
+        })
+        .on_upgrade(move |socket| run_connection(state, agent, socket)))
+}
+
+/// List the open agent connections of an organization's devices.
+///
+/// GET /api/admin/v1/organizations/:org_id/agent-connections
+///
+/// Only connections to the instance serving the request are listed.
+pub async fn list_agent_connections(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    user: UserAuth,
+) -> Result<Json<ListAgentConnectionsResponse>, ApiError> {
+    let org_user = OrgUserRepository::new(state.pool.clone())
+        .find_by_org_and_user(org_id, user.user_id)
+        .await?
+        .ok_or_else(|| ApiError::Forbidden("User not in organization".to_string()))?;
+
+    if org_user.role != OrgUserRole::Owner && org_user.role != OrgUserRole::Admin {
+        return Err(ApiError::Forbidden(
+            "Admin or owner access required".to_string(),
+        ));
+    }
+
+    let connections = state.device_agents.connections_for_org(org_id);
+    Ok(Json(ListAgentConnectionsResponse {
+        total: connections.len(),
+        connections,
+    }))
+}
+
+/// Serve an upgraded agent connection until it closes.
+async fn run_connection(state: AppState, agent: AgentDevice, socket: WebSocket) {
+    let (connection_id, mut outbox) =
+        state
+            .device_agents
+            .register(agent.device_id, agent.device_pk, agent.organization_id);
+    info!(
+        device_id = %agent.device_id,
+        organization_id = %agent.organization_id,
+        connection_id = %connection_id,
+        "Agent connected"
+    );
+
+    let mut session = AgentSession {
+        state: state.clone(),
+        agent,
+        connection_id,
+        socket,
+        sent_commands: HashSet::new(),
+    };
+    match session.run(&mut outbox).await {
+        Ok(Some(code)) => {
+            let close = Message::Close(Some(CloseFrame {
+                code,
+                reason: "".into(),
+            }));
+            let _ = session.socket.send(close).await;
+        }
+        Ok(None) => {}
+        Err(e) => warn!(device_id = %agent.device_id, error = %e, "Agent connection error"),
+    }
+
+    state
+        .device_agents
+        .unregister(agent.device_id, connection_id);
+    info!(
+        device_id = %agent.device_id,
+        connection_id = %connection_id,
+        "Agent disconnected"
+    );
+}
+
+/// State of one agent connection.
+struct AgentSession {
+    state: AppState,
+    agent: AgentDevice,
+    connection_id: Uuid,
+    socket: WebSocket,
+    /// Commands already sent on this connection.
+    sent_commands: HashSet<Uuid>,
+}
+
+impl AgentSession {
+    /// Exchange messages until the connection ends; returns the close code
+    /// to send, if any.
+    async fn run(
+        &mut self,
+        outbox: &mut mpsc::Receiver<AgentPush>,
+    ) -> Result<Option<u16>, axum::Error> {
+        self.send(&AgentServerMessage::Welcome {
+            connection_id: self.connection_id,
+            heartbeat_interval_secs: AGENT_HEARTBEAT_INTERVAL_SECS,
+        })
+        .await?;
+        // Connecting fetches the pending commands, like a command poll
+        track_device_request(
+            &self.state.pool,
+            self.agent.device_id,
+            ApiEndpointClass::Commands,
+        );
+        self.sync_commands().await?;
+
+        let idle_timeout =
+            Duration::from_secs(AGENT_HEARTBEAT_INTERVAL_SECS * AGENT_MISSED_HEARTBEATS);
+        let mut deadline = Instant::now() + idle_timeout;
+        loop {
+            tokio::select! {
+                push = outbox.recv() => match push {
+                    Some(AgentPush::Message(message)) => self.send(&message).await?,
+                    Some(AgentPush::SyncCommands) => self.sync_commands().await?,
+                    // Replaced by a newer connection of the device.
+                    None => return Ok(Some(close_code::AWAY)),
+                },
+                received = self.socket.recv() => {
+                    let message = match received {
+                        Some(message) => message?,
+                        None => return Ok(None),
+                    };
+                    deadline = Instant::now() + idle_timeout;
+                    match message {
+                        Message::Text(text) => self.handle_text(&text).await?,
+                        Message::Binary(_) => {
+                            self.send_error("Binary messages are not supported").await?
+                        }
+                        // Pings are answered by the WebSocket implementation.
+                        Message::Ping(_) | Message::Pong(_) => {}
+                        Message::Close(_) => return Ok(None),
+                    }
+                },
+                _ = sleep_until(deadline) => {
+                    info!(device_id = %self.agent.device_id, "Agent connection timed out");
+                    return Ok(Some(close_code::AWAY));
+                }
+            }
+        }
+    }
+
+    async fn send(&mut self, message: &AgentServerMessage) -> Result<(), axum::Error> {
+        let text = serde_json::to_string(message).map_err(axum::Error::new)?;
+        self.socket.send(Message::Text(text)).await?;
+        metrics::counter!("device_agent_messages_sent_total", "type" => message.kind())
+            .increment(1);
+        Ok(())
+    }
+
+    async fn send_error(&mut self, message: &str) -> Result<(), axum::Error> {
+        self.send(&AgentServerMessage::Error {
+            message: message.to_string(),
+        })
+        .await
+    }
+
+    /// Send the pending commands of the device not sent yet.
+    async fn sync_commands(&mut self) -> Result<(), axum::Error> {
+        let commands = match DeviceCommandRepository::new(self.state.pool.clone())
+            .get_pending_for_device(self.agent.device_pk)
+            .await
+        {
+            Ok(commands) => commands,
+            Err(e) => {
+                warn!(
+                    device_id = %self.agent.device_id,
+                    error = %e,
+                    "Failed to load pending commands for agent"
+                );
+                return Ok(());
+            }
+        };
+
+        for command in commands {
+            if !self.sent_commands.insert(command.id) {
+                continue;
+            }
+            self.send(&AgentServerMessage::Command {
+                command_id: command.id,
+                command_type: command.command_type,
+                payload: command.payload,
+                issued_at: command.issued_at,
+                expires_at: command.expires_at,
+            })
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn handle_text(&mut self, text: &str) -> Result<(), axum::Error> {
+        let message: AgentClientMessage = match serde_json::from_str(text) {
+            Ok(message) => message,
+            Err(e) => return self.send_error(&format!("Invalid message: {}", e)).await,
+        };
+        metrics::counter!("device_agent_messages_received_total", "type" => message.kind())
+            .increment(1);
+
+        match message {
+            AgentClientMessage::Heartbeat => {
+                let now = Utc::now();
+                self.state
+                    .device_agents
+                    .heartbeat(self.agent.device_id, self.connection_id);
+                if let Err(e) = DeviceRepository::new(self.state.pool.clone())
+                    .update_last_seen_at(self.agent.device_id, now)
+                    .await
+                {
+                    warn!(
+                        device_id = %self.agent.device_id,
+                        error = %e,
+                        "Failed to update last seen time"
+                    );
+                }
+                self.send(&AgentServerMessage::HeartbeatAck { server_time: now })
+                    .await
+            }
+            AgentClientMessage::CommandAck { command_id } => {
+                self.update_command(command_id, None).await
+            }
+            AgentClientMessage::CommandResult {
+                command_id,
+                success,
+                error,
+            } => {
+                let outcome = if success {
+                    Ok(())
+                } else {
+                    let reason = error.unwrap_or_else(|| "Command failed".to_string());
+                    Err(reason
+                        .chars()
+                        .take(MAX_AGENT_FAILURE_REASON_LENGTH)
+                        .collect::<String>())
+                };
+                self.update_command(command_id, Some(outcome)).await
+            }
+        }
+    }
+
+    /// Acknowledge a command of the device, or record its outcome.
+    async fn update_command(
+        &mut self,
+        command_id: Uuid,
+        outcome: Option<Result<(), String>>,
+    ) -> Result<(), axum::Error> {
+        track_device_request(
+            &self.state.pool,
+            self.agent.device_id,
+            ApiEndpointClass::Commands,
+        );
+        let repo = DeviceCommandRepository::new(self.state.pool.clone());
+        let result = match repo.get_by_id(command_id).await {
+            Ok(Some(command)) if command.device_id == self.agent.device_pk => match &outcome {
+                None => repo.acknowledge(command_id).await,
+                Some(Ok(())) => repo.complete(command_id).await,
+                Some(Err(reason)) => repo.fail(command_id, reason).await,
+            },
+            Ok(_) => return self.send_error("Command not found").await,
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(true) => {
+                info!(
+                    device_id = %self.agent.device_id,
+                    command_id = %command_id,
+                    "Agent updated command"
+                );
+                Ok(())
+            }
+            Ok(false) => self.send_error("Command is no longer pending").await,
+            Err(e) => {
+                warn!(
+                    device_id = %self.agent.device_id,
+                    command_id = %command_id,
+                    error = %e,
+                    "Failed to update command from agent"
+                );
+                self.send_error("Failed to update command").await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Router};
+
+    #[test]
+    fn test_routes_creation() {
+        let _router: Router<AppState> = Router::new()
+            .route("/api/v1/devices/agent", get(connect_agent))
+            .route(
+                "/api/admin/v1/organizations/:org_id/agent-connections",
+                get(list_agent_connections),
+            );
+    }
+}
@@ -0,0 +1,366 @@
+//! Device command macro route handlers.
+//!
+//! Org admins define named sequences of fleet commands and run them against
+//! a device or a fleet filter. Each run is tracked as a parent operation
+//! with the status of every command it queued.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use chrono::{Duration, Utc};
+use persistence::repositories::{
+    DeviceCommandMacroRepository, DeviceRepository, MacroRunStep, OrgUserRepository,
+};
+use tracing::info;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::app::AppState;
+use crate::error::ApiError;
+use crate::extractors::UserAuth;
+use crate::services::device_agent::deliver_commands;
+
+use domain::models::{
+    CommandMacroRun, CommandMacroRunCommand, CommandMacroRunResponse, DeviceCommandMacro,
+    ListCommandMacrosResponse, OrgUserRole, RunCommandMacroRequest, SaveCommandMacroRequest,
+    MAX_MACRO_RUN_DEVICES,
+};
+
+/// Create device command macro routes.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_macros).post(create_macro))
+        .route(
+            "/:macro_id",
+            get(get_macro).put(update_macro).delete(delete_macro),
+        )
+        .route("/:macro_id/runs", post(run_macro))
+        .route("/runs/:run_id", get(get_macro_run))
+}
+
+/// Check that the user is an admin or owner of the organization.
+async fn require_org_admin(state: &AppState, org_id: Uuid, user_id: Uuid) -> Result<(), ApiError> {
+    let org_user = OrgUserRepository::new(state.pool.clone())
+        .find_by_org_and_user(org_id, user_id)
+        .await?
+        .ok_or_else(|| ApiError::Forbidden("User not in organization".to_string()))?;
+
+    if org_user.role != OrgUserRole::Owner && org_user.role != OrgUserRole::Admin {
+        return Err(ApiError::Forbidden(
+            "Admin or owner access required".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Find a macro of the organization.
+async fn find_macro(
+    repo: &DeviceCommandMacroRepository,
+    org_id: Uuid,
+    macro_id: Uuid,
+) -> Result<DeviceCommandMacro, ApiError> {
+    repo.find_by_id(org_id, macro_id)
+        .await?
+        .map(Into::into)
+        .ok_or_else(|| ApiError::NotFound("Command macro not found".to_string()))
+}
+
+/// Fail with a conflict if another macro of the organization has the name.
+async fn check_name_available(
+    repo: &DeviceCommandMacroRepository,
+    org_id: Uuid,
+    name: &str,
+    exclude_id: Option<Uuid>,
+) -> Result<(), ApiError> {
+    let taken = repo
+        .find_by_name(org_id, name)
+        .await?
+        .is_some_and(|m| Some(m.id) != exclude_id);
+    if taken {
+        return Err(ApiError::Conflict(format!(
+            "Command macro with name '{}' already exists",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// List command macros.
+///
+/// GET /api/admin/v1/organizations/:org_id/command-macros
+async fn list_macros(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    user: UserAuth,
+) -> Result<Json<ListCommandMacrosResponse>, ApiError> {
+    require_org_admin(&state, org_id, user.user_id).await?;
+
+    let data = DeviceCommandMacroRepository::new(state.pool.clone())
+        .list(org_id)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(Json(ListCommandMacrosResponse { data }))
+}
+
+/// Create a command macro.
+///
+/// POST /api/admin/v1/organizations/:org_id/command-macros
+async fn create_macro(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    user: UserAuth,
+    Json(request): Json<SaveCommandMacroRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    request
+        .validate()
+        .map_err(|e| ApiError::Validation(e.to_string()))?;
+    require_org_admin(&state, org_id, user.user_id).await?;
+
+    let repo = DeviceCommandMacroRepository::new(state.pool.clone());
+    check_name_available(&repo, org_id, &request.name, None).await?;
+
+    let steps =
+        serde_json::to_value(&request.steps).map_err(|e| ApiError::Internal(e.to_string()))?;
+    let command_macro: DeviceCommandMacro = repo
+        .create(
+            org_id,
+            &request.name,
+            request.description.as_deref(),
+            &steps,
+            user.user_id,
+        )
+        .await?
+        .into();
+
+    info!(
+        organization_id = %org_id,
+        macro_id = %command_macro.id,
+        steps = command_macro.steps.len(),
+        "Command macro created"
+    );
+
+    Ok((StatusCode::CREATED, Json(command_macro)))
+}
+
+/// Get a command macro.
+///
+/// GET /api/admin/v1/organizations/:org_id/command-macros/:macro_id
+async fn get_macro(
+    State(state): State<AppState>,
+    Path((org_id, macro_id)): Path<(Uuid, Uuid)>,
+    user: UserAuth,
+) -> Result<Json<DeviceCommandMacro>, ApiError> {
+    require_org_admin(&state, org_id, user.user_id).await?;
+
+    let repo = DeviceCommandMacroRepository::new(state.pool.clone());
+    Ok(Json(find_macro(&repo, org_id, macro_id).await?))
+}
+
+/// Replace a command macro.
+///
+/// PUT /api/admin/v1/organizations/:org_id/command-macros/:macro_id
+async fn update_macro(
+    State(state): State<AppState>,
+    Path((org_id, macro_id)): Path<(Uuid, Uuid)>,
+    user: UserAuth,
+    Json(request): Json<SaveCommandMacroRequest>,
+) -> Result<Json<DeviceCommandMacro>, ApiError> {
+    request
+        .validate()
+        .map_err(|e| ApiError::Validation(e.to_string()))?;
+    require_org_admin(&state, org_id, user.user_id).await?;
+
+    let repo = DeviceCommandMacroRepository::new(state.pool.clone());
+    check_name_available(&repo, org_id, &request.name, Some(macro_id)).await?;
+
+    let steps =
+        serde_json::to_value(&request.steps).map_err(|e| ApiError::Internal(e.to_string()))?;
+    let command_macro = repo
+        .update(
+            org_id,
+            macro_id,
+            &request.name,
+            request.description.as_deref(),
+            &steps,
+        )
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Command macro not found".to_string()))?;
+
+    Ok(Json(command_macro.into()))
+}
+
+/// Delete a command macro. Past runs remain available.
+///
+/// DELETE /api/admin/v1/organizations/:org_id/command-macros/:macro_id
+async fn delete_macro(
+    State(state): State<AppState>,
+    Path((org_id, macro_id)): Path<(Uuid, Uuid)>,
+    user: UserAuth,
+) -> Result<StatusCode, ApiError> {
+    require_org_admin(&state, org_id, user.user_id).await?;
+
+    let deleted = DeviceCommandMacroRepository::new(state.pool.clone())
+        .delete(org_id, macro_id)
+        .await?;
+    if !deleted {
+        return Err(ApiError::NotFound("Command macro not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Run a command macro against a device or the devices matching a filter.
+///
+/// POST /api/admin/v1/organizations/:org_id/command-macros/:macro_id/runs
+///
+/// Queues every step for each device and returns 201 with the run.
+async fn run_macro(
+    State(state): State<AppState>,
+    Path((org_id, macro_id)): Path<(Uuid, Uuid)>,
+    user: UserAuth,
+    Json(request): Json<RunCommandMacroRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    request
+        .validate()
+        .map_err(|e| ApiError::Validation(e.to_string()))?;
+    require_org_admin(&state, org_id, user.user_id).await?;
+
+    let repo = DeviceCommandMacroRepository::new(state.pool.clone());
+    let command_macro = find_macro(&repo, org_id, macro_id).await?;
+
+    let steps = command_macro
+        .steps
+        .iter()
+        .map(|step| {
+            Ok(MacroRunStep {
+                command_type: step.command_type.as_str().to_string(),
+                payload: step.render_payload(&request.parameters)?,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()
+        .map_err(ApiError::Validation)?;
+
+    let device_repo = DeviceRepository::new(state.pool.clone());
+    let device_ids = match (request.device_id, &request.filter) {
+        (Some(device_id), None) => {
+            if !device_repo.device_exists_in_org(device_id, org_id).await? {
+                return Err(ApiError::NotFound(
+                    "Device not found in organization".to_string(),
+                ));
+            }
+            vec![device_id]
+        }
+        (None, Some(filter)) => {
+            let ids = device_repo
+                .list_fleet_device_ids(
+                    org_id,
+                    filter.status.as_ref().map(|s| s.as_str()),
+                    filter.group_id.as_deref(),
+                    filter.policy_id,
+                    filter.assigned,
+                    MAX_MACRO_RUN_DEVICES + 1,
+                )
+                .await?;
+            if ids.is_empty() {
+                return Err(ApiError::Validation(
+                    "No devices match the filter".to_string(),
+                ));
+            }
+            if ids.len() as i64 > MAX_MACRO_RUN_DEVICES {
+                return Err(ApiError::Validation(format!(
+                    "Filter matches more than {} devices",
+                    MAX_MACRO_RUN_DEVICES
+                )));
+            }
+            ids
+        }
+        _ => {
+            return Err(ApiError::Validation(
+                "Specify either device_id or filter".to_string(),
+            ))
+        }
+    };
+
+    let parameters =
+        serde_json::to_value(&request.parameters).map_err(|e| ApiError::Internal(e.to_string()))?;
+    let expires_at = Utc::now() + Duration::hours(request.expires_in_hours.unwrap_or(24) as i64);
+
+    let run: CommandMacroRun = repo
+        .create_run(
+            org_id,
+            command_macro.id,
+            &command_macro.name,
+            &parameters,
+            &device_ids,
+            &steps,
+            user.user_id,
+            expires_at,
+        )
+        .await?
+        .into();
+
+    info!(
+        organization_id = %org_id,
+        macro_id = %command_macro.id,
+        run_id = %run.id,
+        devices = device_ids.len(),
+        steps = steps.len(),
+        "Command macro run queued"
+    );
+    deliver_commands(&state, device_ids);
+
+    let commands = repo
+        .list_run_commands(run.id)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect::<Vec<CommandMacroRunCommand>>();
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CommandMacroRunResponse::new(run, commands)),
+    ))
+}
+
+/// Get a macro run with the status of its commands.
+///
+/// GET /api/admin/v1/organizations/:org_id/command-macros/runs/:run_id
+async fn get_macro_run(
+    State(state): State<AppState>,
+    Path((org_id, run_id)): Path<(Uuid, Uuid)>,
+    user: UserAuth,
+) -> Result<Json<CommandMacroRunResponse>, ApiError> {
+    require_org_admin(&state, org_id, user.user_id).await?;
+
+    let repo = DeviceCommandMacroRepository::new(state.pool.clone());
+    let run = repo
+        .find_run(org_id, run_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Command macro run not found".to_string()))?;
+
+    let commands = repo
+        .list_run_commands(run.id)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(Json(CommandMacroRunResponse::new(run.into(), commands)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_router_creation() {
+        let _router: Router<AppState> = router();
+    }
+}
@@ -0,0 +1,131 @@
+//! Device push token endpoint handlers.
+//!
+//! Devices register, rotate, and remove their push tokens here instead of
+//! through the single `fcm_token` field of device registration.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::Utc;
+use domain::models::{
+    ApiEndpointClass, DevicePushToken, ListPushTokensResponse, RegisterPushTokenRequest,
+    MAX_PUSH_TOKENS_PER_DEVICE,
+};
+use persistence::repositories::{DevicePushTokenRepository, DeviceRepository};
+use tracing::info;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::app::AppState;
+use crate::error::ApiError;
+use crate::services::device_usage::track_device_request;
+
+/// Fail with 404 unless the device exists and is active.
+async fn require_active_device(state: &AppState, device_id: Uuid) -> Result<(), ApiError> {
+    let device = DeviceRepository::new(state.pool.clone())
+        .find_by_device_id(device_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Device not found".to_string()))?;
+
+    if !device.active {
+        return Err(ApiError::NotFound("Device not found".to_string()));
+    }
+    Ok(())
+}
+
+/// List a device's push tokens.
+///
+/// GET /api/v1/devices/:device_id/push-tokens
+pub async fn list_push_tokens(
+    State(state): State<AppState>,
+    Path(device_id): Path<Uuid>,
+) -> Result<Json<ListPushTokensResponse>, ApiError> {
+    require_active_device(&state, device_id).await?;
+
+    let data = DevicePushTokenRepository::new(state.pool.clone())
+        .list_for_device(device_id)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(Json(ListPushTokensResponse { data }))
+}
+
+/// Register or rotate a push token.
+///
+/// POST /api/v1/devices/:device_id/push-tokens
+///
+/// Re-registering a known token refreshes its platform, app version, and
+/// expiry. `replaces_token` removes the token being rotated out.
+pub async fn register_push_token(
+    State(state): State<AppState>,
+    Path(device_id): Path<Uuid>,
+    Json(request): Json<RegisterPushTokenRequest>,
+) -> Result<(StatusCode, Json<DevicePushToken>), ApiError> {
+    request.validate().map_err(|e| {
+        let errors: Vec<String> = e
+            .field_errors()
+            .iter()
+            .flat_map(|(field, errors)| {
+                errors.iter().map(move |err| {
+                    format!("{}: {}", field, err.message.as_ref().unwrap_or(&"".into()))
+                })
+            })
+            .collect();
+        ApiError::Validation(errors.join(", "))
+    })?;
+
+    if request.expires_at.is_some_and(|at| at <= Utc::now()) {
+        return Err(ApiError::Validation(
+            "expires_at must be in the future".to_string(),
+        ));
+    }
+
+    require_active_device(&state, device_id).await?;
+    track_device_request(&state.pool, device_id, ApiEndpointClass::Sync);
+
+    let token: DevicePushToken = DevicePushTokenRepository::new(state.pool.clone())
+        .register(
+            device_id,
+            &request.token,
+            request.platform.as_str(),
+            request.app_version.as_deref(),
+            request.expires_at,
+            request.replaces_token.as_deref(),
+            MAX_PUSH_TOKENS_PER_DEVICE,
+        )
+        .await?
+        .into();
+
+    info!(
+        device_id = %device_id,
+        token_id = %token.id,
+        platform = token.platform.as_str(),
+        rotated = request.replaces_token.is_some(),
+        "Push token registered"
+    );
+
+    Ok((StatusCode::CREATED, Json(token)))
+}
+
+/// Remove a push token.
+///
+/// DELETE /api/v1/devices/:device_id/push-tokens/:token_id
+pub async fn delete_push_token(
+    State(state): State<AppState>,
+    Path((device_id, token_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, ApiError> {
+    let deleted = DevicePushTokenRepository::new(state.pool.clone())
+        .delete(device_id, token_id)
+        .await?;
+    if !deleted {
+        return Err(ApiError::NotFound("Push token not found".to_string()));
+    }
+
+    info!(device_id = %device_id, token_id = %token_id, "Push token removed");
+
+    Ok(StatusCode::NO_CONTENT)
+}
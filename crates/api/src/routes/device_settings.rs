@@ -6,7 +6,7 @@ use axum::{
     extract::{Path, Query, State},
     Json,
 };
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use domain::models::setting::{
     BulkUpdateLocksRequest, BulkUpdateLocksResponse, GetSettingsResponse, ListLocksResponse,
     LockInfo, LockSettingRequest, LockSettingResponse, LockUpdateResult, LockerInfo,
@@ -20,14 +20,16 @@ use domain::models::unlock_request::{
     ListUnlockRequestsResponse, Pagination, RespondToUnlockRequestRequest,
     RespondToUnlockRequestResponse, UnlockRequestItem, UnlockRequestStatus, UserInfo,
 };
+use domain::models::{AgentServerMessage, ApiEndpointClass, WeeklySchedule};
 use domain::services::{
     NotificationType, SettingChangeAction, SettingChangeNotification, SettingsChangedPayload,
-    UnlockRequestResponsePayload,
+    TRACKING_SCHEDULE_SETTING_KEY,
 };
 use persistence::entities::{SettingChangeTypeDb, UnlockRequestStatusDb};
 use persistence::repositories::{
-    CreateSettingChangeInput, DeviceRepository, GroupRepository, OrgUserRepository,
-    SettingChangeRepository, SettingRepository, UnlockRequestRepository, UserRepository,
+    CreateSettingChangeInput, DeviceConfigHistoryRepository, DeviceRepository, GroupRepository,
+    OrgUserRepository, SettingChangeRepository, SettingRepository, UnlockRequestRepository,
+    UserRepository,
 };
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -37,6 +39,8 @@ use uuid::Uuid;
 use crate::app::AppState;
 use crate::error::ApiError;
 use crate::extractors::UserAuth;
+use crate::services::device_usage::track_device_request;
+use crate::services::push_tokens::{active_push_tokens, handle_send_result};
 
 /// Query parameters for get settings endpoint.
 #[derive(Debug, Deserialize)]
@@ -45,6 +49,9 @@ pub struct GetSettingsQuery {
     /// Include setting definitions in response.
     #[serde(default)]
     pub include_definitions: bool,
+    /// Show the settings the device had at this past time.
+    #[serde(default)]
+    pub as_of: Option<DateTime<Utc>>,
 }
 
 /// Query parameters for update settings endpoints.
@@ -62,6 +69,8 @@ pub struct UpdateSettingsQuery {
 ///
 /// Requires JWT authentication.
 /// Device owner, group admin, or org admin can access.
+/// With `as_of`, returns the values and locks the device had at that time;
+/// lock details and `updated_by` are not part of the history.
 pub async fn get_device_settings(
     State(state): State<AppState>,
     user_auth: UserAuth,
@@ -95,12 +104,17 @@ pub async fn get_device_settings(
         ));
     }
 
+    if let Some(as_of) = query.as_of {
+        if as_of > Utc::now() {
+            return Err(ApiError::Validation(
+                "as_of must not be in the future".to_string(),
+            ));
+        }
+    }
+
     // Get all setting definitions
     let definitions = setting_repo.get_all_definitions().await?;
 
-    // Get device-specific settings
-    let device_settings = setting_repo.get_device_settings(device_id).await?;
-
     // Build settings map, merging device values with defaults
     let mut settings: HashMap<String, SettingValue> = HashMap::new();
 
@@ -122,20 +136,41 @@ pub async fn get_device_settings(
     }
 
     // Override with device-specific values
-    for ds in device_settings {
-        settings.insert(
-            ds.setting_key.clone(),
-            SettingValue {
-                value: ds.value,
-                is_locked: ds.is_locked,
-                locked_by: ds.locked_by,
-                locked_at: ds.locked_at,
-                lock_reason: ds.lock_reason,
-                updated_at: ds.updated_at,
-                updated_by: ds.updated_by,
-                error: None,
-            },
-        );
+    if let Some(as_of) = query.as_of {
+        let versions = DeviceConfigHistoryRepository::new(state.pool.clone())
+            .settings_as_of(device_id, as_of)
+            .await?;
+        for version in versions {
+            settings.insert(
+                version.setting_key,
+                SettingValue {
+                    value: version.value,
+                    is_locked: version.is_locked,
+                    locked_by: None,
+                    locked_at: None,
+                    lock_reason: None,
+                    updated_at: version.valid_from,
+                    updated_by: None,
+                    error: None,
+                },
+            );
+        }
+    } else {
+        for ds in setting_repo.get_device_settings(device_id).await? {
+            settings.insert(
+                ds.setting_key.clone(),
+                SettingValue {
+                    value: ds.value,
+                    is_locked: ds.is_locked,
+                    locked_by: ds.locked_by,
+                    locked_at: ds.locked_at,
+                    lock_reason: ds.lock_reason,
+                    updated_at: ds.updated_at,
+                    updated_by: ds.updated_by,
+                    error: None,
+                },
+            );
+        }
     }
 
     // Optionally include definitions
@@ -170,7 +205,8 @@ pub async fn get_device_settings(
     Ok(Json(GetSettingsResponse {
         device_id,
         settings,
-        last_synced_at: Some(Utc::now()),
+        last_synced_at: query.as_of.is_none().then(Utc::now),
+        as_of: query.as_of,
         definitions: definitions_response,
     }))
 }
@@ -285,6 +321,25 @@ pub async fn update_device_settings(
             continue;
         }
 
+        // Validate structured values
+        if let Err(message) = validate_setting_content(&key, &value) {
+            invalid.push(key.clone());
+            settings.insert(
+                key.clone(),
+                SettingValue {
+                    value,
+                    is_locked: false,
+                    locked_by: None,
+                    locked_at: None,
+                    lock_reason: None,
+                    updated_at: Utc::now(),
+                    updated_by: None,
+                    error: Some(message),
+                },
+            );
+            continue;
+        }
+
         // Get current value from prefetched map (for change logging)
         let old_setting = current_settings_map.get(&key);
         let old_value = old_setting.map(|s| s.value.clone());
@@ -434,6 +489,7 @@ pub async fn update_device_setting(
             db_data_type_to_domain(def.data_type)
         )));
     }
+    validate_setting_content(&key, &request.value).map_err(ApiError::Validation)?;
 
     // Get current setting value before update (for change logging)
     let old_setting = setting_repo.get_device_setting(device_id, &key).await?;
@@ -641,6 +697,7 @@ pub async fn lock_setting(
                 db_data_type_to_domain(def.data_type)
             )));
         }
+        validate_setting_content(&key, value).map_err(ApiError::Validation)?;
     }
 
     // Get current setting value before lock (for history fidelity)
@@ -696,14 +753,7 @@ pub async fn lock_setting(
             new_value: request.value,
         }];
 
-        send_settings_changed_notification(
-            &state,
-            device_id,
-            device.fcm_token.as_deref(),
-            changes,
-            user_name,
-        )
-        .await;
+        send_settings_changed_notification(&state, device_id, changes, user_name).await;
     }
 
     Ok(Json(LockSettingResponse {
@@ -968,14 +1018,7 @@ pub async fn bulk_update_locks(
             })
             .collect();
 
-        send_settings_changed_notification(
-            &state,
-            device_id,
-            device.fcm_token.as_deref(),
-            changes,
-            user_name,
-        )
-        .await;
+        send_settings_changed_notification(&state, device_id, changes, user_name).await;
         true
     } else {
         false
@@ -1037,6 +1080,14 @@ fn validate_value_type(value: &serde_json::Value, expected: &SettingDataType) ->
     }
 }
 
+/// Validate the content of settings with structured values.
+fn validate_setting_content(key: &str, value: &serde_json::Value) -> Result<(), String> {
+    if key == TRACKING_SCHEDULE_SETTING_KEY {
+        WeeklySchedule::from_value(value)?;
+    }
+    Ok(())
+}
+
 /// Check if user is authorized to access device settings.
 async fn check_settings_authorization(
     _device_repo: &DeviceRepository,
@@ -1123,21 +1174,34 @@ fn db_status_to_domain(status: UnlockRequestStatusDb) -> UnlockRequestStatus {
     }
 }
 
-/// Helper to send settings changed notification (fire-and-forget).
+/// Helper to push settings changes over the device's agent connection, or
+/// else send a notification to every push token of the device
+/// (fire-and-forget).
 async fn send_settings_changed_notification(
     state: &AppState,
     device_id: Uuid,
-    fcm_token: Option<&str>,
     changes: Vec<SettingChangeNotification>,
     changed_by: String,
 ) {
-    let Some(token) = fcm_token else {
+    let message = AgentServerMessage::SettingsChanged {
+        changes: changes.clone(),
+        changed_by: changed_by.clone(),
+        timestamp: Utc::now(),
+    };
+    if state.device_agents.push(device_id, message) {
+        return;
+    }
+    metrics::counter!("device_agent_push_fallback_total", "type" => "settings_changed")
+        .increment(1);
+
+    let tokens = active_push_tokens(&state.pool, device_id).await;
+    if tokens.is_empty() {
         info!(
             device_id = %device_id,
-            "Skipping notification - device has no FCM token"
+            "Skipping notification - device has no push token"
         );
         return;
-    };
+    }
 
     let payload = SettingsChangedPayload {
         notification_type: NotificationType::SettingsChanged,
@@ -1147,64 +1211,12 @@ async fn send_settings_changed_notification(
         timestamp: Utc::now(),
     };
 
-    // Fire and forget - don't await or block on notification result
-    let result = state
-        .notification_service
-        .send_settings_changed(token, payload)
-        .await;
-
-    match result {
-        domain::services::NotificationResult::Sent => {
-            info!(device_id = %device_id, "Settings changed notification sent");
-        }
-        domain::services::NotificationResult::Failed(err) => {
-            warn!(device_id = %device_id, error = %err, "Failed to send notification");
-        }
-        _ => {}
-    }
-}
-
-/// Helper to send unlock request response notification (fire-and-forget).
-async fn send_unlock_request_response_notification(
-    state: &AppState,
-    fcm_token: Option<&str>,
-    request_id: Uuid,
-    setting_key: String,
-    status: String,
-    note: Option<String>,
-    decided_by: String,
-) {
-    let Some(token) = fcm_token else {
-        info!(
-            request_id = %request_id,
-            "Skipping notification - device has no FCM token"
-        );
-        return;
-    };
-
-    let payload = UnlockRequestResponsePayload {
-        notification_type: NotificationType::UnlockRequestResponse,
-        request_id,
-        setting_key,
-        status,
-        note,
-        decided_by,
-        timestamp: Utc::now(),
-    };
-
-    let result = state
-        .notification_service
-        .send_unlock_request_response(token, payload)
-        .await;
-
-    match result {
-        domain::services::NotificationResult::Sent => {
-            info!(request_id = %request_id, "Unlock request response notification sent");
-        }
-        domain::services::NotificationResult::Failed(err) => {
-            warn!(request_id = %request_id, error = %err, "Failed to send notification");
-        }
-        _ => {}
+    for token in tokens {
+        let result = state
+            .notification_service
+            .send_settings_changed(&token, payload.clone())
+            .await;
+        handle_send_result(&state.pool, device_id, &token, result).await;
     }
 }
 
@@ -1456,7 +1468,15 @@ pub async fn respond_to_unlock_request(
         ));
     }
 
-    // Update the unlock request
+    // Name shown to the requester's device in the response notification
+    let user_repo = UserRepository::new(state.pool.clone());
+    let decided_by = user_repo
+        .find_by_id(user_auth.user_id)
+        .await?
+        .map(|u| u.display_name.unwrap_or_else(|| "Admin".to_string()))
+        .unwrap_or_else(|| "Admin".to_string());
+
+    // Update the unlock request and queue the notification to the device
     let db_status = domain_status_to_db(request.status);
     let updated = unlock_repo
         .respond(
@@ -1464,6 +1484,7 @@ pub async fn respond_to_unlock_request(
             db_status,
             user_auth.user_id,
             request.note.as_deref(),
+            Some(&decided_by),
         )
         .await?
         .ok_or_else(|| {
@@ -1496,25 +1517,6 @@ pub async fn respond_to_unlock_request(
         "Responded to unlock request"
     );
 
-    // Send notification to requester's device
-    let user_repo = UserRepository::new(state.pool.clone());
-    let decided_by = user_repo
-        .find_by_id(user_auth.user_id)
-        .await?
-        .map(|u| u.display_name.unwrap_or_else(|| "Admin".to_string()))
-        .unwrap_or_else(|| "Admin".to_string());
-
-    send_unlock_request_response_notification(
-        &state,
-        device.fcm_token.as_deref(),
-        request_id,
-        unlock_request.setting_key.clone(),
-        request.status.to_string(),
-        request.note.clone(),
-        decided_by,
-    )
-    .await;
-
     Ok(Json(RespondToUnlockRequestResponse {
         id: updated.id,
         status: db_status_to_domain(updated.status),
@@ -1565,6 +1567,8 @@ pub async fn sync_settings(
         ));
     }
 
+    track_device_request(&state.pool, device_id, ApiEndpointClass::Sync);
+
     let synced_at = Utc::now();
 
     // Get all setting definitions for defaults
@@ -1898,4 +1902,28 @@ mod tests {
             &SettingDataType::Json
         ));
     }
+
+    #[test]
+    fn test_validate_setting_content_tracking_schedule() {
+        assert!(
+            validate_setting_content(TRACKING_SCHEDULE_SETTING_KEY, &serde_json::json!(null))
+                .is_ok()
+        );
+        assert!(validate_setting_content(
+            TRACKING_SCHEDULE_SETTING_KEY,
+            &serde_json::json!({
+                "windows": [{"days": ["mon"], "start": "07:00", "end": "19:00"}]
+            })
+        )
+        .is_ok());
+        assert!(validate_setting_content(
+            TRACKING_SCHEDULE_SETTING_KEY,
+            &serde_json::json!({"windows": "weekdays"})
+        )
+        .is_err());
+        // Other JSON settings are not inspected
+        assert!(
+            validate_setting_content("custom", &serde_json::json!({"windows": "weekdays"})).is_ok()
+        );
+    }
 }
@@ -0,0 +1,91 @@
+//! Device telemetry endpoint handlers.
+//!
+//! Telemetry also arrives embedded in location uploads; this endpoint lets a
+//! device report battery and connectivity changes without a location fix.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{TimeZone, Utc};
+use domain::models::{ApiEndpointClass, DeviceTelemetry, UploadTelemetryRequest};
+use persistence::repositories::{DeviceRepository, DeviceTelemetryRepository};
+use tracing::info;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::app::AppState;
+use crate::error::ApiError;
+use crate::services::device_usage::track_device_request;
+use crate::services::group_notifications::notify_low_battery_if_dropped;
+
+/// Record a battery and connectivity sample for a device.
+///
+/// POST /api/v1/devices/:device_id/telemetry
+pub async fn upload_telemetry(
+    State(state): State<AppState>,
+    Path(device_id): Path<Uuid>,
+    Json(request): Json<UploadTelemetryRequest>,
+) -> Result<(StatusCode, Json<DeviceTelemetry>), ApiError> {
+    request.validate().map_err(|e| {
+        let errors: Vec<String> = e
+            .field_errors()
+            .iter()
+            .flat_map(|(field, errors)| {
+                errors.iter().map(move |err| {
+                    format!("{}: {}", field, err.message.as_ref().unwrap_or(&"".into()))
+                })
+            })
+            .collect();
+        ApiError::Validation(errors.join(", "))
+    })?;
+
+    let recorded_at = match request.timestamp {
+        Some(ts) => Utc
+            .timestamp_millis_opt(ts)
+            .single()
+            .ok_or_else(|| ApiError::Validation("Invalid timestamp".to_string()))?,
+        None => Utc::now(),
+    };
+
+    let telemetry = DeviceTelemetry::from_readings(
+        request.battery_level,
+        request.charging_state,
+        request.network_type,
+        request.signal_strength,
+        recorded_at,
+    )
+    .ok_or_else(|| {
+        ApiError::Validation("At least one telemetry reading is required".to_string())
+    })?;
+
+    // Verify device exists and is active
+    let device = DeviceRepository::new(state.pool.clone())
+        .find_by_device_id(device_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Device not found".to_string()))?;
+
+    if !device.active {
+        return Err(ApiError::NotFound("Device not found".to_string()));
+    }
+
+    track_device_request(&state.pool, device_id, ApiEndpointClass::Ingest);
+
+    let telemetry: DeviceTelemetry = DeviceTelemetryRepository::new(state.pool.clone())
+        .insert(device_id, telemetry.into())
+        .await?
+        .into();
+
+    if telemetry.battery_level.is_some() {
+        notify_low_battery_if_dropped(
+            state.pool.clone(),
+            state.notification_service.clone(),
+            device_id,
+        );
+    }
+
+    info!(device_id = %device_id, "Telemetry uploaded");
+
+    Ok((StatusCode::CREATED, Json(telemetry)))
+}
@@ -6,7 +6,8 @@ use axum::{
     Json,
 };
 use chrono::{DateTime, Utc};
-use domain::models::{check_usage_warning, ResponseWithWarnings};
+use domain::models::{check_usage_warning, ApiEndpointClass, ResponseWithWarnings};
+use persistence::backend::DeviceStore;
 use persistence::repositories::DeviceRepository;
 use serde::{Deserialize, Serialize};
 use tracing::info;
@@ -16,6 +17,9 @@ use validator::Validate;
 use crate::app::AppState;
 use crate::error::ApiError;
 use crate::extractors::{OptionalUserAuth, UserAuth};
+use crate::routes::location_sharing::load_slug_location_filter;
+use crate::services::device_usage::track_device_request;
+use crate::services::push_tokens::register_legacy_push_token;
 use domain::models::device::{
     DeviceLastLocation, DeviceSummary, RegisterDeviceRequest, RegisterDeviceResponse,
 };
@@ -59,10 +63,10 @@ pub async fn register_device(
         ApiError::Validation(errors.join(", "))
     })?;
 
-    let repo = DeviceRepository::new(state.pool.clone());
+    let store = state.devices();
 
     // Check if this is a new device or an update
-    let existing_device = repo.find_by_device_id(request.device_id).await?;
+    let existing_device = store.find_by_device_id(request.device_id).await?;
     let is_new_device = existing_device.is_none();
     let is_changing_group = existing_device
         .as_ref()
@@ -86,7 +90,7 @@ pub async fn register_device(
 
     // Check group capacity if this is a new device or changing groups
     if is_new_device || is_changing_group {
-        let group_count = repo
+        let group_count = store
             .count_active_devices_in_group(&request.group_id)
             .await?;
 
@@ -102,7 +106,7 @@ pub async fn register_device(
     }
 
     // Perform upsert
-    let device = repo
+    let device = store
         .upsert_device(
             request.device_id,
             &request.display_name,
@@ -111,11 +115,21 @@ pub async fn register_device(
             request.fcm_token.as_deref(),
         )
         .await?;
+    register_legacy_push_token(
+        &state.pool,
+        request.device_id,
+        request.fcm_token.as_deref(),
+        &request.platform,
+    )
+    .await?;
+
+    track_device_request(&state.pool, request.device_id, ApiEndpointClass::Sync);
 
     // If user is authenticated and device doesn't have an owner, link it
     let final_device = if let Some(user_auth) = optional_user.0 {
         if device.owner_user_id.is_none() {
             // Link device to user (first device becomes primary)
+            let repo = DeviceRepository::new(state.pool.clone());
             let user_has_other_devices = !repo
                 .find_devices_by_user(user_auth.user_id, false)
                 .await?
@@ -184,6 +198,16 @@ pub async fn get_group_devices(
     let repo = DeviceRepository::new(state.pool.clone());
     let devices = repo.find_devices_with_last_location(&group_id).await?;
 
+    // API key callers are not identified as a user, so owners' privacy zones
+    // and sharing windows apply to every device
+    let privacy_zones = load_slug_location_filter(
+        &state.pool,
+        &group_id,
+        None,
+        devices.iter().map(|d| d.owner_user_id),
+    )
+    .await?;
+
     let summaries: Vec<DeviceSummary> = devices
         .into_iter()
         .map(|d| {
@@ -194,12 +218,15 @@ pub async fn get_group_devices(
                 d.last_location_time,
                 d.last_accuracy,
             ) {
-                (Some(lat), Some(lon), Some(time), Some(acc)) => Some(DeviceLastLocation {
-                    latitude: lat,
-                    longitude: lon,
-                    timestamp: time,
-                    accuracy: acc as f64,
-                }),
+                (Some(lat), Some(lon), Some(time), Some(acc)) => privacy_zones
+                    .share(d.owner_user_id, lat, lon)
+                    .apply(lat, lon, acc as f64)
+                    .map(|(latitude, longitude, accuracy)| DeviceLastLocation {
+                        latitude,
+                        longitude,
+                        timestamp: time,
+                        accuracy,
+                    }),
                 _ => None,
             };
 
@@ -208,6 +235,7 @@ pub async fn get_group_devices(
                 display_name: d.display_name,
                 last_location,
                 last_seen_at: d.last_seen_at,
+                sharing_paused: privacy_zones.is_paused(d.owner_user_id),
             }
         })
         .collect();
@@ -222,8 +250,7 @@ pub async fn delete_device(
     State(state): State<AppState>,
     Path(device_id): Path<Uuid>,
 ) -> Result<StatusCode, ApiError> {
-    let repo = DeviceRepository::new(state.pool.clone());
-    let rows_affected = repo.deactivate_device(device_id).await?;
+    let rows_affected = state.devices().deactivate_device(device_id).await?;
 
     if rows_affected == 0 {
         return Err(ApiError::NotFound("Device not found".to_string()));
@@ -399,12 +426,14 @@ mod tests {
                 accuracy: 10.0,
             }),
             last_seen_at: Some(Utc::now()),
+            sharing_paused: false,
         };
         let device2 = DeviceSummary {
             device_id: Uuid::new_v4(),
             display_name: "Phone 2".to_string(),
             last_location: None,
             last_seen_at: None,
+            sharing_paused: false,
         };
         let response = GetDevicesResponse {
             devices: vec![device1, device2],
@@ -430,6 +459,7 @@ mod tests {
             display_name: "Test Device".to_string(),
             last_location: None,
             last_seen_at: None,
+            sharing_paused: false,
         };
         let response = GetDevicesResponse {
             devices: vec![device],
@@ -452,6 +482,7 @@ mod tests {
                 accuracy: 10.0,
             }),
             last_seen_at: Some(Utc::now()),
+            sharing_paused: false,
         };
         let response = GetDevicesResponse {
             devices: vec![device],
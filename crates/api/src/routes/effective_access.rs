@@ -0,0 +1,177 @@
+//! Effective access inspector route handlers.
+//!
+//! Computes everything a user can currently see and do, for support and
+//! security reviews.
+
+use std::collections::HashSet;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
+use chrono::Utc;
+use domain::models::{
+    group_capabilities, DeviceAccess, DeviceAccessSource, EffectiveAccessResponse,
+    EffectiveAccessUser, GroupAccess, GroupPermission, GroupRole, OrganizationAccess, SystemAccess,
+    SystemRole,
+};
+use persistence::repositories::{
+    DeviceRepository, GroupRepository, OrgUserRepository, OrganizationRepository,
+    SystemRoleRepository, UserRepository,
+};
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::error::ApiError;
+use crate::middleware::rbac::resolve_group_permissions;
+use crate::middleware::system_rbac::SystemRoleAuth;
+
+/// Create effective access routes.
+///
+/// Mounted at /api/admin/v1/users/:user_id/effective-access.
+pub fn router() -> Router<AppState> {
+    Router::new().route("/", get(get_effective_access))
+}
+
+/// Get a user's effective access.
+///
+/// GET /api/admin/v1/users/{user_id}/effective-access
+///
+/// Returns the user's system roles, organization and group memberships with
+/// resolved permissions, and the devices reachable through them.
+/// Requires super_admin or support role.
+#[axum::debug_handler(state = AppState)]
+async fn get_effective_access(
+    State(state): State<AppState>,
+    Path(target_user_id): Path<Uuid>,
+    system_auth: SystemRoleAuth,
+) -> Result<impl IntoResponse, ApiError> {
+    if !system_auth.is_super_admin() && !system_auth.has_role(SystemRole::Support) {
+        return Err(ApiError::Forbidden(
+            "Super admin or support access required".to_string(),
+        ));
+    }
+
+    let user = UserRepository::new(state.pool.clone())
+        .find_by_id(target_user_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("User not found".to_string()))?;
+
+    // System roles
+    let system_role_repo = SystemRoleRepository::new(state.pool.clone());
+    let roles = system_role_repo
+        .get_user_roles(target_user_id)
+        .await?
+        .into_iter()
+        .map(|r| r.role)
+        .collect();
+    let assigned_org_ids = system_role_repo
+        .get_assigned_org_ids(target_user_id)
+        .await?;
+    let system_access = SystemAccess::resolve(roles, assigned_org_ids);
+
+    // Organization memberships
+    let org_repo = OrganizationRepository::new(state.pool.clone());
+    let memberships = OrgUserRepository::new(state.pool.clone())
+        .list_for_user(target_user_id)
+        .await?;
+    let mut organizations = Vec::with_capacity(memberships.len());
+    for membership in &memberships {
+        let name = org_repo
+            .find_by_id(membership.organization_id)
+            .await?
+            .map(|org| org.name)
+            .unwrap_or_default();
+        organizations.push(OrganizationAccess::resolve(membership, name));
+    }
+
+    // Group memberships, with permissions resolved through custom roles
+    let mut groups = Vec::new();
+    let mut location_groups = Vec::new();
+    for g in GroupRepository::new(state.pool.clone())
+        .find_user_groups(target_user_id, None, None)
+        .await?
+    {
+        let role: GroupRole = g.role.into();
+        let permissions =
+            resolve_group_permissions(&state.pool, g.id, target_user_id, role).await?;
+        if permissions.contains(GroupPermission::ViewLocations) {
+            location_groups.push((g.id, g.slug.clone()));
+        }
+        groups.push(GroupAccess {
+            group_id: g.id,
+            name: g.name,
+            slug: g.slug,
+            role,
+            capabilities: group_capabilities(role, permissions),
+            device_count: g.device_count,
+        });
+    }
+
+    // Devices: owned ones first, then those visible through groups
+    let device_repo = DeviceRepository::new(state.pool.clone());
+    let mut seen = HashSet::new();
+    let mut devices = Vec::new();
+    for device in device_repo
+        .find_devices_by_user(target_user_id, false)
+        .await?
+    {
+        seen.insert(device.device_id);
+        devices.push(DeviceAccess {
+            device_id: device.device_id,
+            display_name: device.display_name,
+            source: DeviceAccessSource::Owner,
+            group_id: None,
+            last_seen_at: device.last_seen_at,
+        });
+    }
+    for (group_id, slug) in &location_groups {
+        for device in device_repo.find_active_devices_by_group(slug).await? {
+            if !seen.insert(device.device_id) {
+                continue;
+            }
+            devices.push(DeviceAccess {
+                device_id: device.device_id,
+                display_name: device.display_name,
+                source: DeviceAccessSource::Group,
+                group_id: Some(*group_id),
+                last_seen_at: device.last_seen_at,
+            });
+        }
+    }
+
+    tracing::info!(
+        inspector_id = %system_auth.user_id,
+        user_id = %target_user_id,
+        "Computed effective access"
+    );
+
+    let response = EffectiveAccessResponse {
+        user: EffectiveAccessUser {
+            id: user.id,
+            email: user.email,
+            display_name: user.display_name,
+            is_active: user.is_active,
+        },
+        system_access,
+        organizations,
+        groups,
+        devices,
+        computed_at: Utc::now(),
+    };
+
+    Ok((StatusCode::OK, Json(response)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_router_creation() {
+        let _router: Router<AppState> = router();
+    }
+}
@@ -12,6 +12,7 @@ use validator::Validate;
 
 use crate::app::AppState;
 use crate::error::ApiError;
+use crate::services::push_tokens::register_legacy_push_token;
 use domain::models::{
     calculate_device_token_expiry, extract_device_token_prefix, generate_device_token,
     DevicePolicy, EnrollDeviceRequest, EnrollDeviceResponse, EnrolledDevice, EnrollmentGroupInfo,
@@ -112,6 +113,14 @@ pub async fn enroll_device(
             .await?
     };
 
+    register_legacy_push_token(
+        &state.pool,
+        device.device_id,
+        request.fcm_token.as_deref(),
+        &request.platform,
+    )
+    .await?;
+
     // Increment enrollment token usage
     enrollment_token_repo
         .increment_usage(enrollment_token.id)
@@ -0,0 +1,157 @@
+//! Fault injection route handlers.
+//!
+//! Only built with the `fault-injection` feature. Lets the team configure
+//! latency and error rates on the database and external services of a
+//! running test server, to verify retries, circuit breakers and degraded
+//! modes. Faults are kept in memory and cleared on restart.
+
+use axum::{
+    extract::Path,
+    http::StatusCode,
+    routing::{delete, put},
+    Json, Router,
+};
+use persistence::faults::{self, FaultPoint, FaultSpec};
+use serde::Serialize;
+use tracing::warn;
+
+use crate::app::AppState;
+use crate::error::ApiError;
+use crate::middleware::system_rbac::SystemRoleAuth;
+
+/// Faults configured at a point.
+#[derive(Debug, Clone, Serialize)]
+struct ActiveFault {
+    point: FaultPoint,
+    #[serde(flatten)]
+    spec: FaultSpec,
+}
+
+/// Response listing the configured faults.
+#[derive(Debug, Clone, Serialize)]
+struct ListFaultsResponse {
+    faults: Vec<ActiveFault>,
+}
+
+/// Create fault injection routes.
+///
+/// Mounted at /api/admin/v1/fault-injection. Requires super_admin role.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", delete(clear_faults).get(list_faults))
+        .route("/:point", put(set_fault).delete(remove_fault))
+}
+
+fn require_super_admin(system_auth: &SystemRoleAuth) -> Result<(), ApiError> {
+    if !system_auth.is_super_admin() {
+        return Err(ApiError::Forbidden(
+            "Super admin access required".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn parse_point(point: &str) -> Result<FaultPoint, ApiError> {
+    FaultPoint::parse(point)
+        .ok_or_else(|| ApiError::NotFound(format!("Unknown fault point '{}'", point)))
+}
+
+/// List the configured faults.
+///
+/// GET /api/admin/v1/fault-injection
+async fn list_faults(system_auth: SystemRoleAuth) -> Result<Json<ListFaultsResponse>, ApiError> {
+    require_super_admin(&system_auth)?;
+
+    let faults = faults::active()
+        .into_iter()
+        .map(|(point, spec)| ActiveFault { point, spec })
+        .collect();
+    Ok(Json(ListFaultsResponse { faults }))
+}
+
+/// Configure the faults injected at a point.
+///
+/// PUT /api/admin/v1/fault-injection/:point
+async fn set_fault(
+    Path(point): Path<String>,
+    system_auth: SystemRoleAuth,
+    Json(spec): Json<FaultSpec>,
+) -> Result<Json<ActiveFault>, ApiError> {
+    require_super_admin(&system_auth)?;
+    let point = parse_point(&point)?;
+    spec.validate().map_err(ApiError::Validation)?;
+
+    faults::set(point, spec);
+    warn!(
+        user_id = %system_auth.user_id,
+        point = point.as_str(),
+        latency_ms = spec.latency_ms,
+        error_rate = spec.error_rate,
+        "Fault injection configured"
+    );
+
+    Ok(Json(ActiveFault { point, spec }))
+}
+
+/// Stop injecting faults at a point.
+///
+/// DELETE /api/admin/v1/fault-injection/:point
+async fn remove_fault(
+    Path(point): Path<String>,
+    system_auth: SystemRoleAuth,
+) -> Result<StatusCode, ApiError> {
+    require_super_admin(&system_auth)?;
+    let point = parse_point(&point)?;
+
+    if !faults::remove(point) {
+        return Err(ApiError::NotFound(format!(
+            "No faults configured at '{}'",
+            point.as_str()
+        )));
+    }
+    warn!(
+        user_id = %system_auth.user_id,
+        point = point.as_str(),
+        "Fault injection removed"
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Stop injecting faults everywhere.
+///
+/// DELETE /api/admin/v1/fault-injection
+async fn clear_faults(system_auth: SystemRoleAuth) -> Result<StatusCode, ApiError> {
+    require_super_admin(&system_auth)?;
+
+    faults::clear();
+    warn!(user_id = %system_auth.user_id, "Fault injection cleared");
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_router_creation() {
+        let _router: Router<AppState> = router();
+    }
+
+    #[test]
+    fn test_active_fault_serialization() {
+        let fault = ActiveFault {
+            point: FaultPoint::MapMatching,
+            spec: FaultSpec {
+                latency_ms: 250,
+                error_rate: 0.5,
+            },
+        };
+        let json = serde_json::to_value(&fault).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "point": "map_matching", "latency_ms": 250, "error_rate": 0.5 })
+        );
+    }
+}
@@ -3,28 +3,39 @@
 //! Story 13.7: Fleet Management Endpoints
 
 use axum::{
+    body::Body,
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::IntoResponse,
+    http::{header, StatusCode},
+    middleware,
+    response::{IntoResponse, Response},
     routing::{get, post},
     Json, Router,
 };
 use chrono::Utc;
 use persistence::repositories::{
-    DeviceCommandRepository, DeviceRepository, OrgUserRepository, UserRepository,
+    DeviceApiUsageRepository, DeviceCommandRepository, DeviceRepository, OrgUserRepository,
+    UserRepository,
 };
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio_util::io::ReaderStream;
 use uuid::Uuid;
 use validator::Validate;
 
 use crate::app::AppState;
 use crate::error::ApiError;
 use crate::extractors::UserAuth;
+use crate::middleware::{concurrency_limit, export_statement_timeout, RouteClass};
+use crate::services::device_agent::deliver_commands;
+use crate::services::report_rendering::{csv_row, ReportCell};
+use crate::services::xlsx::{XlsxWriter, XLSX_CONTENT_TYPE};
 
 use domain::models::{
-    AssignDeviceRequest, AssignDeviceResponse, AssignedUserInfo, BulkDeviceUpdateResult,
-    BulkUpdateDevicesRequest, BulkUpdateDevicesResponse, DeviceCommandHistoryItem,
+    ApiEndpointClass, AssignDeviceRequest, AssignDeviceResponse, AssignedUserInfo,
+    BulkDeviceUpdateResult, BulkUpdateDevicesRequest, BulkUpdateDevicesResponse,
+    DeviceApiUsageItem, DeviceApiUsageResponse, DeviceCommandHistoryItem,
     DeviceCommandHistoryPagination, DeviceCommandHistoryQuery, DeviceCommandHistoryResponse,
     DeviceCommandStatus, DeviceCommandType, DeviceStatusChangeResponse, EnrollmentStatus,
+    ExportFormat, FleetDeviceDetailResponse, FleetDeviceExportQuery, FleetDeviceItem,
     FleetDeviceListResponse, FleetDeviceQuery, FleetPagination, FleetSummary, IssueCommandRequest,
     IssueCommandResponse, OrgUserRole, UnassignDeviceResponse,
 };
@@ -33,6 +44,15 @@ use domain::models::{
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/", get(list_fleet_devices))
+        .route(
+            "/export",
+            get(export_fleet_devices)
+                .layer(middleware::from_fn_with_state(
+                    RouteClass::Export,
+                    concurrency_limit,
+                ))
+                .layer(middleware::from_fn(export_statement_timeout)),
+        )
         .route("/bulk-update", post(bulk_update_devices))
         .route("/{device_id}/assign", post(assign_device))
         .route("/{device_id}/unassign", post(unassign_device))
@@ -40,6 +60,8 @@ pub fn router() -> Router<AppState> {
         .route("/{device_id}/retire", post(retire_device))
         .route("/{device_id}/wipe", post(wipe_device))
         .route("/{device_id}/commands", get(get_device_command_history))
+        .route("/:device_id", get(get_fleet_device))
+        .route("/:device_id/usage", get(get_device_api_usage))
 }
 
 /// List all devices in organization fleet.
@@ -66,10 +88,10 @@ async fn list_fleet_devices(
         .await?
         .ok_or_else(|| ApiError::Forbidden("User not in organization".to_string()))?;
 
-    // Check permission (admin or owner can view fleet)
-    if org_user.role != OrgUserRole::Owner && org_user.role != OrgUserRole::Admin {
+    // Check permission (admin, owner or observer can view fleet)
+    if !org_user.role.can_view_org_data() {
         return Err(ApiError::Forbidden(
-            "Admin or owner access required".to_string(),
+            "Admin, owner or observer access required".to_string(),
         ));
     }
 
@@ -140,6 +162,220 @@ async fn list_fleet_devices(
     Ok((StatusCode::OK, Json(response)))
 }
 
+/// Devices fetched per page while exporting.
+const FLEET_EXPORT_PAGE_SIZE: u32 = 500;
+
+/// Column headers of fleet device exports.
+const FLEET_EXPORT_COLUMNS: [&str; 15] = [
+    "device_id",
+    "display_name",
+    "platform",
+    "enrollment_status",
+    "is_managed",
+    "assigned_user_email",
+    "group_id",
+    "group_name",
+    "policy_name",
+    "last_seen_at",
+    "last_latitude",
+    "last_longitude",
+    "last_location_at",
+    "enrolled_at",
+    "created_at",
+];
+
+/// Export all devices in the organization fleet.
+///
+/// GET /api/admin/v1/organizations/{org_id}/devices/export?format=csv|json|xlsx
+///
+/// Devices are read page by page and streamed to the client, so memory use
+/// does not grow with fleet size. XLSX rows are spooled to disk and the
+/// workbook is sent once complete, with a second sheet holding the fleet
+/// summary.
+#[axum::debug_handler]
+async fn export_fleet_devices(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    Query(query): Query<FleetDeviceExportQuery>,
+    user: UserAuth,
+) -> Result<Response, ApiError> {
+    query
+        .validate()
+        .map_err(|e| ApiError::Validation(e.to_string()))?;
+
+    let org_user_repo = OrgUserRepository::new(state.pool.clone());
+
+    let org_user = org_user_repo
+        .find_by_org_and_user(org_id, user.user_id)
+        .await?
+        .ok_or_else(|| ApiError::Forbidden("User not in organization".to_string()))?;
+
+    if org_user.role != OrgUserRole::Owner && org_user.role != OrgUserRole::Admin {
+        return Err(ApiError::Forbidden(
+            "Admin or owner access required".to_string(),
+        ));
+    }
+
+    let format = query.format.unwrap_or_default();
+    let (content_type, extension) = match format {
+        ExportFormat::Json => ("application/json", "json"),
+        ExportFormat::Csv => ("text/csv", "csv"),
+        ExportFormat::Xlsx => (XLSX_CONTENT_TYPE, "xlsx"),
+    };
+
+    let device_repo = DeviceRepository::new(state.pool.clone());
+    let (reader, mut writer) = tokio::io::duplex(64 * 1024);
+    tokio::spawn(async move {
+        if let Err(e) = write_fleet_export(&device_repo, org_id, &query, format, &mut writer).await
+        {
+            tracing::warn!(
+                org_id = %org_id,
+                error = %e,
+                "Fleet device export aborted"
+            );
+        }
+    });
+
+    let filename = format!(
+        "fleet_devices_{}.{}",
+        Utc::now().format("%Y%m%d%H%M%S"),
+        extension
+    );
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, content_type)
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", filename),
+        )
+        .body(Body::from_stream(ReaderStream::new(reader)))
+        .map_err(|e| ApiError::Internal(format!("Failed to build response: {}", e)))
+}
+
+/// Write fleet devices page by page in the requested format.
+async fn write_fleet_export<W: AsyncWrite + Unpin>(
+    device_repo: &DeviceRepository,
+    org_id: Uuid,
+    query: &FleetDeviceExportQuery,
+    format: ExportFormat,
+    out: &mut W,
+) -> Result<(), String> {
+    let status = query.status.as_ref().map(|s| s.as_str());
+    let sort_field = query.sort.unwrap_or_default();
+    let sort_order = query.order.unwrap_or_default();
+    let mut xlsx = XlsxWriter::new();
+
+    match format {
+        ExportFormat::Json => out.write_all(b"[").await,
+        ExportFormat::Csv => out.write_all(csv_header().as_bytes()).await,
+        ExportFormat::Xlsx => {
+            xlsx.start_sheet("Devices", &FLEET_EXPORT_COLUMNS)
+                .map_err(|e| e.to_string())?;
+            Ok(())
+        }
+    }
+    .map_err(|e| e.to_string())?;
+
+    let mut offset = 0;
+    loop {
+        let devices = device_repo
+            .list_fleet_devices(
+                org_id,
+                status,
+                query.group_id.as_deref(),
+                query.policy_id,
+                query.assigned,
+                query.search.as_deref(),
+                sort_field,
+                sort_order,
+                FLEET_EXPORT_PAGE_SIZE,
+                offset,
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let mut chunk = Vec::new();
+        for (i, device) in devices.iter().enumerate() {
+            match format {
+                ExportFormat::Json => {
+                    if offset > 0 || i > 0 {
+                        chunk.push(b',');
+                    }
+                    serde_json::to_writer(&mut chunk, device).map_err(|e| e.to_string())?;
+                }
+                ExportFormat::Csv => {
+                    chunk.extend_from_slice(csv_row(&fleet_export_row(device)).as_bytes())
+                }
+                ExportFormat::Xlsx => xlsx
+                    .write_row(&fleet_export_row(device))
+                    .map_err(|e| e.to_string())?,
+            }
+        }
+        out.write_all(&chunk).await.map_err(|e| e.to_string())?;
+
+        if devices.len() < FLEET_EXPORT_PAGE_SIZE as usize {
+            break;
+        }
+        offset += FLEET_EXPORT_PAGE_SIZE;
+    }
+
+    let tail = match format {
+        ExportFormat::Json => b"]".to_vec(),
+        ExportFormat::Csv => Vec::new(),
+        ExportFormat::Xlsx => {
+            let summary = device_repo
+                .get_fleet_summary(org_id)
+                .await
+                .map_err(|e| e.to_string())?;
+            xlsx.start_sheet("Summary", &["status", "devices"])
+                .map_err(|e| e.to_string())?;
+            for (label, count) in [
+                ("enrolled", summary.enrolled),
+                ("pending", summary.pending),
+                ("suspended", summary.suspended),
+                ("retired", summary.retired),
+                ("assigned", summary.assigned),
+                ("unassigned", summary.unassigned),
+            ] {
+                xlsx.write_row(&[label.into(), count.into()])
+                    .map_err(|e| e.to_string())?;
+            }
+            xlsx.finish().map_err(|e| e.to_string())?
+        }
+    };
+    out.write_all(&tail).await.map_err(|e| e.to_string())?;
+    out.shutdown().await.map_err(|e| e.to_string())
+}
+
+fn csv_header() -> String {
+    format!("{}\n", FLEET_EXPORT_COLUMNS.join(","))
+}
+
+/// Typed export cells for a fleet device, in [`FLEET_EXPORT_COLUMNS`] order.
+fn fleet_export_row(device: &FleetDeviceItem) -> Vec<ReportCell> {
+    vec![
+        device.device_uuid.to_string().into(),
+        device.display_name.as_str().into(),
+        device.platform.as_str().into(),
+        device.enrollment_status.map(|s| s.as_str()).into(),
+        device.is_managed.into(),
+        device
+            .assigned_user
+            .as_ref()
+            .map(|u| u.email.as_str())
+            .into(),
+        device.group.as_ref().map(|g| g.id.as_str()).into(),
+        device.group.as_ref().and_then(|g| g.name.as_deref()).into(),
+        device.policy.as_ref().map(|p| p.name.as_str()).into(),
+        device.last_seen_at.into(),
+        device.last_location.as_ref().map(|l| l.latitude).into(),
+        device.last_location.as_ref().map(|l| l.longitude).into(),
+        device.last_location.as_ref().map(|l| l.timestamp).into(),
+        device.enrolled_at.into(),
+        device.created_at.into(),
+    ]
+}
+
 /// Assign a user to a device.
 ///
 /// POST /api/admin/v1/organizations/{org_id}/devices/{device_id}/assign
@@ -376,6 +612,7 @@ async fn wipe_device(
             expires_in_hours,
         )
         .await?;
+    deliver_commands(&state, vec![device_id]);
 
     let expires_at = Utc::now() + chrono::Duration::hours(expires_in_hours as i64);
 
@@ -621,6 +858,106 @@ async fn get_device_command_history(
     Ok((StatusCode::OK, Json(response)))
 }
 
+/// Get a device of the organization fleet.
+///
+/// GET /api/admin/v1/organizations/{org_id}/devices/{device_id}
+///
+/// Admins and owners also get the device's API usage counters.
+#[axum::debug_handler]
+async fn get_fleet_device(
+    State(state): State<AppState>,
+    Path((org_id, device_id)): Path<(Uuid, i64)>,
+    user: UserAuth,
+) -> Result<impl IntoResponse, ApiError> {
+    let org_user = OrgUserRepository::new(state.pool.clone())
+        .find_by_org_and_user(org_id, user.user_id)
+        .await?
+        .ok_or_else(|| ApiError::Forbidden("User not in organization".to_string()))?;
+
+    // Check permission (admin, owner or observer can view fleet)
+    if !org_user.role.can_view_org_data() {
+        return Err(ApiError::Forbidden(
+            "Admin, owner or observer access required".to_string(),
+        ));
+    }
+
+    let device = DeviceRepository::new(state.pool.clone())
+        .find_fleet_device_item(org_id, device_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Device not found in organization".to_string()))?;
+
+    let api_usage = if org_user.role == OrgUserRole::Owner || org_user.role == OrgUserRole::Admin {
+        Some(load_device_api_usage(&state, device_id).await?)
+    } else {
+        None
+    };
+
+    let response = FleetDeviceDetailResponse { device, api_usage };
+    Ok((StatusCode::OK, Json(response)))
+}
+
+/// API usage counters of a device, per endpoint class.
+async fn load_device_api_usage(
+    state: &AppState,
+    device_id: i64,
+) -> Result<DeviceApiUsageResponse, ApiError> {
+    let usage = DeviceApiUsageRepository::new(state.pool.clone())
+        .find_by_device(device_id)
+        .await?
+        .into_iter()
+        .filter_map(|u| {
+            let endpoint_class: ApiEndpointClass = u.endpoint_class.parse().ok()?;
+            Some(DeviceApiUsageItem {
+                endpoint_class,
+                request_count: u.request_count,
+                current_hour_count: u.current_hour_count,
+                last_request_at: u.last_request_at,
+            })
+        })
+        .collect();
+    Ok(DeviceApiUsageResponse::new(device_id, usage))
+}
+
+/// Get API usage counters of a device.
+///
+/// GET /api/admin/v1/organizations/{org_id}/devices/{device_id}/usage
+///
+/// Returns request counts and last activity per endpoint class
+/// (ingest, sync, commands) to help spot clients hammering the API.
+#[axum::debug_handler]
+async fn get_device_api_usage(
+    State(state): State<AppState>,
+    Path((org_id, device_id)): Path<(Uuid, i64)>,
+    user: UserAuth,
+) -> Result<impl IntoResponse, ApiError> {
+    let org_user_repo = OrgUserRepository::new(state.pool.clone());
+    let device_repo = DeviceRepository::new(state.pool.clone());
+
+    // Verify user has access to organization
+    let org_user = org_user_repo
+        .find_by_org_and_user(org_id, user.user_id)
+        .await?
+        .ok_or_else(|| ApiError::Forbidden("User not in organization".to_string()))?;
+
+    // Check permission (admin or owner can view device usage)
+    if org_user.role != OrgUserRole::Owner && org_user.role != OrgUserRole::Admin {
+        return Err(ApiError::Forbidden(
+            "Admin or owner access required".to_string(),
+        ));
+    }
+
+    // Check if device exists in organization
+    let exists = device_repo.device_exists_in_org(device_id, org_id).await?;
+    if !exists {
+        return Err(ApiError::NotFound(
+            "Device not found in organization".to_string(),
+        ));
+    }
+
+    let usage = load_device_api_usage(&state, device_id).await?;
+    Ok((StatusCode::OK, Json(usage)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -629,4 +966,30 @@ mod tests {
     fn test_router_creation() {
         let _router: Router<AppState> = router();
     }
+
+    #[test]
+    fn test_fleet_export_row_matches_columns() {
+        let device = FleetDeviceItem {
+            id: 1,
+            device_uuid: Uuid::nil(),
+            display_name: "Pixel, 7".to_string(),
+            platform: "android".to_string(),
+            enrollment_status: None,
+            is_managed: true,
+            assigned_user: None,
+            group: None,
+            policy: None,
+            last_seen_at: None,
+            last_location: None,
+            telemetry: None,
+            enrolled_at: None,
+            created_at: Utc::now(),
+        };
+        let row = fleet_export_row(&device);
+        assert_eq!(row.len(), FLEET_EXPORT_COLUMNS.len());
+        assert_eq!(row[4], ReportCell::Boolean(true));
+        assert!(matches!(row[14], ReportCell::DateTime(_)));
+        assert!(csv_row(&row).contains("\"Pixel, 7\""));
+        assert!(csv_header().starts_with("device_id,display_name,"));
+    }
 }
@@ -7,8 +7,13 @@
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
+    response::{IntoResponse, Response},
     Json,
 };
+use chrono::{DateTime, Utc};
+use domain::models::{ApiEndpointClass, Geofence, GeofenceEventSource};
+use domain::services::resolve_overlap;
+use persistence::entities::GeofenceEventEntity;
 use persistence::repositories::{DeviceRepository, GeofenceEventRepository, GeofenceRepository};
 use tracing::info;
 use uuid::Uuid;
@@ -16,24 +21,37 @@ use validator::Validate;
 
 use crate::app::AppState;
 use crate::error::ApiError;
-use crate::services::webhook_delivery::WebhookDeliveryService;
+use crate::services::device_usage::track_device_request;
 use domain::models::geofence_event::{
-    CreateGeofenceEventRequest, GeofenceEvent, GeofenceEventResponse, ListGeofenceEventsQuery,
-    ListGeofenceEventsResponse,
+    CreateGeofenceEventRequest, GeofenceEvent, GeofenceEventResponse, GeofenceTransitionType,
+    ListGeofenceEventsQuery, ListGeofenceEventsResponse,
 };
 
 /// Maximum events per query.
 const MAX_EVENTS_LIMIT: i64 = 100;
 
+/// How close a client-reported event must be to a server-derived event of
+/// the same type to be treated as the same crossing.
+const SERVER_EVENT_DEDUP_WINDOW_MS: i64 = 10 * 60 * 1000;
+
 /// Create a new geofence event.
 ///
 /// POST /api/v1/geofence-events
 ///
 /// AC 15.2.2: Creates geofence event and triggers webhook delivery
+///
+/// Events of a scheduled geofence that fall outside its schedule are
+/// dropped without webhooks and answered with 204 No Content, as are enter
+/// and exit events within the geofence's cool-down of its previous enter or
+/// exit. An event
+/// matching a server-derived event of the same crossing is not stored
+/// again; the existing event is returned with 200 OK. An event at a location
+/// inside a higher-priority geofence of the device is stored, resolved to
+/// that geofence, but not delivered.
 pub async fn create_geofence_event(
     State(state): State<AppState>,
     Json(request): Json<CreateGeofenceEventRequest>,
-) -> Result<(StatusCode, Json<GeofenceEventResponse>), ApiError> {
+) -> Result<Response, ApiError> {
     // Validate request
     request.validate().map_err(|e| {
         let errors: Vec<String> = e
@@ -62,6 +80,8 @@ pub async fn create_geofence_event(
         return Err(ApiError::NotFound("Device not found".to_string()));
     }
 
+    track_device_request(&state.pool, request.device_id, ApiEndpointClass::Ingest);
+
     // Verify geofence exists and belongs to device
     let geofence_repo = GeofenceRepository::new(state.pool.clone());
     let geofence = geofence_repo
@@ -73,80 +93,130 @@ pub async fn create_geofence_event(
         return Err(ApiError::NotFound("Geofence not found".to_string()));
     }
 
-    // Create the event
+    let geofence: Geofence = geofence.into();
+    let occurred_at = DateTime::<Utc>::from_timestamp_millis(timestamp)
+        .ok_or_else(|| ApiError::Validation("Timestamp out of range".to_string()))?;
+    if !geofence.is_scheduled_at(occurred_at) {
+        metrics::counter!("geofence_events_outside_schedule_total").increment(1);
+        info!(
+            device_id = %request.device_id,
+            geofence_id = %request.geofence_id,
+            event_type = %request.event_type,
+            "Geofence event outside schedule suppressed"
+        );
+        return Ok(StatusCode::NO_CONTENT.into_response());
+    }
+
     let event_repo = GeofenceEventRepository::new(state.pool.clone());
+
+    let latest = event_repo
+        .find_latest_transition(request.device_id, request.geofence_id)
+        .await?;
+
+    // Skip crossings the server already derived from the location stream
+    if let Some(existing) = latest
+        .clone()
+        .filter(|e| is_server_duplicate(e, request.event_type.as_str(), timestamp))
+    {
+        metrics::counter!("geofence_events_deduplicated_total").increment(1);
+        info!(
+            event_id = %existing.event_id,
+            device_id = %request.device_id,
+            geofence_id = %request.geofence_id,
+            event_type = %request.event_type,
+            "Geofence event matches server-derived event"
+        );
+        let response = to_response(existing, geofence.name);
+        return Ok((StatusCode::OK, Json(response)).into_response());
+    }
+
+    // Drop enter/exit events bouncing on the boundary within the cool-down
+    let is_transition = matches!(
+        request.event_type,
+        GeofenceTransitionType::Enter | GeofenceTransitionType::Exit
+    );
+    if is_transition && latest.is_some_and(|e| geofence.is_in_cooldown(e.timestamp, timestamp)) {
+        metrics::counter!("geofence_events_throttled_total").increment(1);
+        info!(
+            device_id = %request.device_id,
+            geofence_id = %request.geofence_id,
+            event_type = %request.event_type,
+            cooldown_seconds = geofence.cooldown_seconds,
+            "Geofence event within cool-down suppressed"
+        );
+        return Ok(StatusCode::NO_CONTENT.into_response());
+    }
+
+    // Resolve overlapping geofences by priority
+    let device_geofences: Vec<Geofence> = geofence_repo
+        .find_by_device_id(request.device_id, false)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+    let resolved_geofence_id = resolve_overlap(
+        &geofence,
+        &device_geofences,
+        request.latitude,
+        request.longitude,
+        occurred_at,
+    )
+    .geofence_id;
+
+    // Create the event
     let entity = event_repo
         .create(
             request.device_id,
             request.geofence_id,
+            &geofence.name,
             request.event_type.as_str(),
             timestamp,
             request.latitude,
             request.longitude,
+            GeofenceEventSource::Client.as_str(),
+            resolved_geofence_id,
         )
         .await?;
 
-    let event_id = entity.event_id;
+    let response = to_response(entity, geofence.name);
 
-    // Convert to domain model
-    let event = GeofenceEvent::from_raw(
+    info!(
+        event_id = %response.event_id,
+        device_id = %response.device_id,
+        geofence_id = %response.geofence_id,
+        event_type = %request.event_type,
+        "Geofence event created"
+    );
+
+    Ok((StatusCode::CREATED, Json(response)).into_response())
+}
+
+/// Whether a client-reported event repeats the latest transition, derived
+/// by the server shortly before or after it.
+fn is_server_duplicate(latest: &GeofenceEventEntity, event_type: &str, timestamp: i64) -> bool {
+    GeofenceEventSource::parse(&latest.source) == GeofenceEventSource::Server
+        && latest.event_type == event_type
+        && (latest.timestamp - timestamp).abs() <= SERVER_EVENT_DEDUP_WINDOW_MS
+}
+
+fn to_response(entity: GeofenceEventEntity, geofence_name: String) -> GeofenceEventResponse {
+    GeofenceEvent::from_raw(
         entity.id,
         entity.event_id,
         entity.device_id,
         entity.geofence_id,
-        Some(geofence.name.clone()),
+        Some(geofence_name),
         &entity.event_type,
         entity.timestamp,
         entity.latitude,
         entity.longitude,
         entity.webhook_delivered,
         entity.webhook_response_code,
+        &entity.source,
+        entity.resolved_geofence_id,
         entity.created_at,
-    );
-
-    let response: GeofenceEventResponse = event.into();
-
-    info!(
-        event_id = %response.event_id,
-        device_id = %response.device_id,
-        geofence_id = %response.geofence_id,
-        event_type = %request.event_type,
-        "Geofence event created"
-    );
-
-    // Trigger async webhook delivery (AC 15.2.5, 15.2.6)
-    let pool = state.pool.clone();
-    let geofence_name = geofence.name;
-    let event_type = request.event_type;
-    let device_id = request.device_id;
-    let geofence_id = request.geofence_id;
-    let latitude = request.latitude;
-    let longitude = request.longitude;
-
-    tokio::spawn(async move {
-        let delivery_service = WebhookDeliveryService::new(pool.clone());
-        if let Err(e) = delivery_service
-            .deliver_geofence_event(
-                event_id,
-                device_id,
-                geofence_id,
-                &geofence_name,
-                event_type,
-                timestamp,
-                latitude,
-                longitude,
-            )
-            .await
-        {
-            tracing::error!(
-                event_id = %event_id,
-                error = %e,
-                "Failed to deliver geofence event webhooks"
-            );
-        }
-    });
-
-    Ok((StatusCode::CREATED, Json(response)))
+    )
+    .into()
 }
 
 /// List geofence events for a device.
@@ -166,7 +236,12 @@ pub async fn list_geofence_events(
         .await?;
 
     let total = event_repo
-        .count_by_device_id(query.device_id, query.geofence_id)
+        .total_by_device_id(
+            query.device_id,
+            query.geofence_id,
+            query.count,
+            entities.len() as i64,
+        )
         .await?;
 
     let events: Vec<GeofenceEventResponse> = entities
@@ -184,13 +259,19 @@ pub async fn list_geofence_events(
                 e.longitude,
                 e.webhook_delivered,
                 e.webhook_response_code,
+                &e.source,
+                e.resolved_geofence_id,
                 e.created_at,
             );
             event.into()
         })
         .collect();
 
-    Ok(Json(ListGeofenceEventsResponse { events, total }))
+    Ok(Json(ListGeofenceEventsResponse {
+        events,
+        total: total.value(),
+        total_estimated: total.is_estimate(),
+    }))
 }
 
 /// Get a single geofence event by ID.
@@ -220,6 +301,8 @@ pub async fn get_geofence_event(
         entity.longitude,
         entity.webhook_delivered,
         entity.webhook_response_code,
+        &entity.source,
+        entity.resolved_geofence_id,
         entity.created_at,
     );
     Ok(Json(event.into()))
@@ -283,6 +366,8 @@ mod tests {
             longitude: -122.4194,
             webhook_delivered: true,
             webhook_response_code: Some(200),
+            source: GeofenceEventSource::Client,
+            resolved_geofence_id: None,
         };
 
         let json = serde_json::to_string(&response).unwrap();
@@ -0,0 +1,351 @@
+//! Geofence template route handlers.
+//!
+//! Org admins define geofence templates and stamp them onto devices or
+//! groups at once. Stamped geofences keep a link to their template, so a
+//! template edit can be propagated to every device carrying it.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use domain::models::geofence::GeofenceResponse;
+use domain::models::{
+    ApplyGeofenceTemplateRequest, ApplyGeofenceTemplateResponse, DeleteGeofenceTemplateQuery,
+    Geofence, GeofenceTemplate, ListGeofenceTemplatesResponse, ListTemplateGeofencesResponse,
+    OrgUserRole, SaveGeofenceTemplateRequest, UpdateGeofenceTemplateResponse,
+    MAX_TEMPLATE_APPLY_DEVICES,
+};
+use persistence::repositories::{
+    GeofenceTemplateFields, GeofenceTemplateRepository, OrgUserRepository,
+};
+use tracing::info;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::app::AppState;
+use crate::error::ApiError;
+use crate::extractors::UserAuth;
+use crate::routes::geofences::{schedule_value, MAX_GEOFENCES_PER_DEVICE};
+
+/// Create geofence template routes.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_templates).post(create_template))
+        .route(
+            "/:template_id",
+            get(get_template)
+                .put(update_template)
+                .delete(delete_template),
+        )
+        .route("/:template_id/apply", post(apply_template))
+        .route("/:template_id/geofences", get(list_template_geofences))
+}
+
+/// Check that the user is an admin or owner of the organization.
+async fn require_org_admin(state: &AppState, org_id: Uuid, user_id: Uuid) -> Result<(), ApiError> {
+    let org_user = OrgUserRepository::new(state.pool.clone())
+        .find_by_org_and_user(org_id, user_id)
+        .await?
+        .ok_or_else(|| ApiError::Forbidden("User not in organization".to_string()))?;
+
+    if org_user.role != OrgUserRole::Owner && org_user.role != OrgUserRole::Admin {
+        return Err(ApiError::Forbidden(
+            "Admin or owner access required".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Find a template of the organization.
+async fn find_template(
+    repo: &GeofenceTemplateRepository,
+    org_id: Uuid,
+    template_id: Uuid,
+) -> Result<GeofenceTemplate, ApiError> {
+    repo.find_by_id(org_id, template_id)
+        .await?
+        .map(Into::into)
+        .ok_or_else(|| ApiError::NotFound("Geofence template not found".to_string()))
+}
+
+/// Fail with a conflict if another template of the organization has the name.
+async fn check_name_available(
+    repo: &GeofenceTemplateRepository,
+    org_id: Uuid,
+    name: &str,
+    exclude_id: Option<Uuid>,
+) -> Result<(), ApiError> {
+    let taken = repo
+        .find_by_name(org_id, name)
+        .await?
+        .is_some_and(|t| Some(t.id) != exclude_id);
+    if taken {
+        return Err(ApiError::Conflict(format!(
+            "Geofence template with name '{}' already exists",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Validate a save request and convert it for storage.
+fn template_fields<'a>(
+    request: &'a SaveGeofenceTemplateRequest,
+    event_types: &'a [String],
+) -> Result<GeofenceTemplateFields<'a>, ApiError> {
+    request
+        .validate()
+        .map_err(|e| ApiError::Validation(e.to_string()))?;
+
+    Ok(GeofenceTemplateFields {
+        name: &request.name,
+        description: request.description.as_deref(),
+        latitude: request.latitude,
+        longitude: request.longitude,
+        radius_meters: request.radius_meters,
+        event_types,
+        schedule: schedule_value(request.schedule.as_ref())?,
+        metadata: request.metadata.clone(),
+    })
+}
+
+fn event_type_strings(request: &SaveGeofenceTemplateRequest) -> Vec<String> {
+    request
+        .event_types
+        .iter()
+        .map(|t| t.as_str().to_string())
+        .collect()
+}
+
+/// List geofence templates.
+///
+/// GET /api/admin/v1/organizations/:org_id/geofence-templates
+async fn list_templates(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    user: UserAuth,
+) -> Result<Json<ListGeofenceTemplatesResponse>, ApiError> {
+    require_org_admin(&state, org_id, user.user_id).await?;
+
+    let data = GeofenceTemplateRepository::new(state.pool.clone())
+        .list(org_id)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(Json(ListGeofenceTemplatesResponse { data }))
+}
+
+/// Create a geofence template.
+///
+/// POST /api/admin/v1/organizations/:org_id/geofence-templates
+async fn create_template(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    user: UserAuth,
+    Json(request): Json<SaveGeofenceTemplateRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let event_types = event_type_strings(&request);
+    let fields = template_fields(&request, &event_types)?;
+    require_org_admin(&state, org_id, user.user_id).await?;
+
+    let repo = GeofenceTemplateRepository::new(state.pool.clone());
+    check_name_available(&repo, org_id, &request.name, None).await?;
+
+    let template: GeofenceTemplate = repo.create(org_id, &fields, user.user_id).await?.into();
+
+    info!(
+        organization_id = %org_id,
+        template_id = %template.id,
+        "Geofence template created"
+    );
+
+    Ok((StatusCode::CREATED, Json(template)))
+}
+
+/// Get a geofence template.
+///
+/// GET /api/admin/v1/organizations/:org_id/geofence-templates/:template_id
+async fn get_template(
+    State(state): State<AppState>,
+    Path((org_id, template_id)): Path<(Uuid, Uuid)>,
+    user: UserAuth,
+) -> Result<Json<GeofenceTemplate>, ApiError> {
+    require_org_admin(&state, org_id, user.user_id).await?;
+
+    let repo = GeofenceTemplateRepository::new(state.pool.clone());
+    Ok(Json(find_template(&repo, org_id, template_id).await?))
+}
+
+/// Replace a geofence template.
+///
+/// PUT /api/admin/v1/organizations/:org_id/geofence-templates/:template_id
+///
+/// Unless `propagate` is false, the geofences stamped from the template are
+/// updated to match it.
+async fn update_template(
+    State(state): State<AppState>,
+    Path((org_id, template_id)): Path<(Uuid, Uuid)>,
+    user: UserAuth,
+    Json(request): Json<SaveGeofenceTemplateRequest>,
+) -> Result<Json<UpdateGeofenceTemplateResponse>, ApiError> {
+    let event_types = event_type_strings(&request);
+    let fields = template_fields(&request, &event_types)?;
+    require_org_admin(&state, org_id, user.user_id).await?;
+
+    let repo = GeofenceTemplateRepository::new(state.pool.clone());
+    check_name_available(&repo, org_id, &request.name, Some(template_id)).await?;
+
+    let (template, propagated) = repo
+        .update(org_id, template_id, &fields, request.propagate)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Geofence template not found".to_string()))?;
+
+    info!(
+        organization_id = %org_id,
+        template_id = %template_id,
+        propagated,
+        "Geofence template updated"
+    );
+
+    Ok(Json(UpdateGeofenceTemplateResponse {
+        template: template.into(),
+        propagated,
+    }))
+}
+
+/// Delete a geofence template.
+///
+/// DELETE /api/admin/v1/organizations/:org_id/geofence-templates/:template_id
+///
+/// Stamped geofences are kept as standalone geofences unless
+/// `delete_geofences=true` is given.
+async fn delete_template(
+    State(state): State<AppState>,
+    Path((org_id, template_id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<DeleteGeofenceTemplateQuery>,
+    user: UserAuth,
+) -> Result<StatusCode, ApiError> {
+    require_org_admin(&state, org_id, user.user_id).await?;
+
+    let deleted = GeofenceTemplateRepository::new(state.pool.clone())
+        .delete(org_id, template_id, query.delete_geofences)
+        .await?;
+    if !deleted {
+        return Err(ApiError::NotFound(
+            "Geofence template not found".to_string(),
+        ));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Stamp a geofence template onto devices and groups.
+///
+/// POST /api/admin/v1/organizations/:org_id/geofence-templates/:template_id/apply
+///
+/// Devices that already carry the template or have reached the per-device
+/// geofence limit are skipped and reported.
+async fn apply_template(
+    State(state): State<AppState>,
+    Path((org_id, template_id)): Path<(Uuid, Uuid)>,
+    user: UserAuth,
+    Json(request): Json<ApplyGeofenceTemplateRequest>,
+) -> Result<Json<ApplyGeofenceTemplateResponse>, ApiError> {
+    if request.is_empty() {
+        return Err(ApiError::Validation(
+            "Specify device_ids or group_ids".to_string(),
+        ));
+    }
+    require_org_admin(&state, org_id, user.user_id).await?;
+
+    let repo = GeofenceTemplateRepository::new(state.pool.clone());
+    let template = find_template(&repo, org_id, template_id).await?;
+
+    let device_ids = repo
+        .resolve_target_devices(
+            org_id,
+            &request.device_ids,
+            &request.group_ids,
+            MAX_TEMPLATE_APPLY_DEVICES + 1,
+        )
+        .await?;
+    if device_ids.len() as i64 > MAX_TEMPLATE_APPLY_DEVICES {
+        return Err(ApiError::Validation(format!(
+            "Targets more than {} devices",
+            MAX_TEMPLATE_APPLY_DEVICES
+        )));
+    }
+    if let Some(missing) = request
+        .device_ids
+        .iter()
+        .find(|id| !device_ids.contains(id))
+    {
+        return Err(ApiError::NotFound(format!(
+            "Device {} not found in organization",
+            missing
+        )));
+    }
+    if device_ids.is_empty() {
+        return Err(ApiError::Validation(
+            "No devices match the groups".to_string(),
+        ));
+    }
+
+    let outcome = repo
+        .apply(template.id, &device_ids, MAX_GEOFENCES_PER_DEVICE)
+        .await?;
+
+    info!(
+        organization_id = %org_id,
+        template_id = %template.id,
+        created = outcome.created.len(),
+        already_applied = outcome.already_applied.len(),
+        limit_reached = outcome.limit_reached.len(),
+        "Geofence template applied"
+    );
+
+    Ok(Json(ApplyGeofenceTemplateResponse {
+        template_id: template.id,
+        created: outcome.created,
+        already_applied: outcome.already_applied,
+        limit_reached: outcome.limit_reached,
+    }))
+}
+
+/// List the geofences stamped from a template.
+///
+/// GET /api/admin/v1/organizations/:org_id/geofence-templates/:template_id/geofences
+async fn list_template_geofences(
+    State(state): State<AppState>,
+    Path((org_id, template_id)): Path<(Uuid, Uuid)>,
+    user: UserAuth,
+) -> Result<Json<ListTemplateGeofencesResponse>, ApiError> {
+    require_org_admin(&state, org_id, user.user_id).await?;
+
+    let repo = GeofenceTemplateRepository::new(state.pool.clone());
+    find_template(&repo, org_id, template_id).await?;
+
+    let data = repo
+        .list_geofences(org_id, template_id)
+        .await?
+        .into_iter()
+        .map(|entity| GeofenceResponse::from(Geofence::from(entity)))
+        .collect();
+
+    Ok(Json(ListTemplateGeofencesResponse { data }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_router_creation() {
+        let _router: Router<AppState> = router();
+    }
+}
@@ -5,8 +5,9 @@ use axum::{
     http::StatusCode,
     Json,
 };
-use domain::models::{check_usage_warning, ResponseWithWarnings};
+use domain::models::{check_usage_warning, ResponseWithWarnings, WeeklySchedule};
 use persistence::repositories::{DeviceRepository, GeofenceRepository};
+use std::collections::HashMap;
 use tracing::info;
 use uuid::Uuid;
 use validator::Validate;
@@ -14,12 +15,40 @@ use validator::Validate;
 use crate::app::AppState;
 use crate::error::ApiError;
 use domain::models::geofence::{
-    CreateGeofenceRequest, GeofenceResponse, ListGeofencesQuery, ListGeofencesResponse,
-    UpdateGeofenceRequest,
+    BulkCreateGeofencesRequest, BulkCreateGeofencesResponse, BulkCreatedGeofence,
+    BulkGeofenceError, CreateGeofenceRequest, GeofenceResponse, ListGeofencesQuery,
+    ListGeofencesResponse, UpdateGeofenceRequest,
 };
 
 /// Maximum number of geofences allowed per device.
-const MAX_GEOFENCES_PER_DEVICE: i64 = 50;
+pub(crate) const MAX_GEOFENCES_PER_DEVICE: i64 = 50;
+
+/// Join field validation errors into a single message.
+fn validation_message(errors: &validator::ValidationErrors) -> String {
+    errors
+        .field_errors()
+        .iter()
+        .flat_map(|(field, errors)| {
+            errors.iter().map(move |err| {
+                format!("{}: {}", field, err.message.as_ref().unwrap_or(&"".into()))
+            })
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Validate a requested schedule and convert it for storage.
+pub(crate) fn schedule_value(
+    schedule: Option<&WeeklySchedule>,
+) -> Result<Option<serde_json::Value>, ApiError> {
+    let Some(schedule) = schedule else {
+        return Ok(None);
+    };
+    schedule.validate().map_err(ApiError::Validation)?;
+    serde_json::to_value(schedule)
+        .map(Some)
+        .map_err(|e| ApiError::Internal(format!("Failed to serialize schedule: {}", e)))
+}
 
 /// Create a new geofence.
 ///
@@ -30,18 +59,9 @@ pub async fn create_geofence(
     Json(request): Json<CreateGeofenceRequest>,
 ) -> Result<(StatusCode, Json<ResponseWithWarnings<GeofenceResponse>>), ApiError> {
     // Validate request
-    request.validate().map_err(|e| {
-        let errors: Vec<String> = e
-            .field_errors()
-            .iter()
-            .flat_map(|(field, errors)| {
-                errors.iter().map(move |err| {
-                    format!("{}: {}", field, err.message.as_ref().unwrap_or(&"".into()))
-                })
-            })
-            .collect();
-        ApiError::Validation(errors.join(", "))
-    })?;
+    request
+        .validate()
+        .map_err(|e| ApiError::Validation(validation_message(&e)))?;
 
     // Validate event_types is not empty
     if request.event_types.is_empty() {
@@ -50,6 +70,8 @@ pub async fn create_geofence(
         ));
     }
 
+    let schedule = schedule_value(request.schedule.as_ref())?;
+
     // Verify device exists and is active
     let device_repo = DeviceRepository::new(state.pool.clone());
     let device = device_repo
@@ -93,6 +115,10 @@ pub async fn create_geofence(
             &event_types,
             request.active,
             request.metadata,
+            schedule,
+            request.cooldown_seconds,
+            request.arrival_alert_minutes,
+            request.priority,
         )
         .await?;
 
@@ -121,6 +147,126 @@ pub async fn create_geofence(
     Ok((StatusCode::CREATED, Json(response_with_warnings)))
 }
 
+/// Create geofences in bulk, for fleet onboarding.
+///
+/// POST /api/v1/geofences/bulk
+///
+/// Each item is validated and created independently; failures are reported
+/// per row and do not roll back the others.
+pub async fn bulk_create_geofences(
+    State(state): State<AppState>,
+    Json(request): Json<BulkCreateGeofencesRequest>,
+) -> Result<Json<BulkCreateGeofencesResponse>, ApiError> {
+    request
+        .validate()
+        .map_err(|e| ApiError::Validation(validation_message(&e)))?;
+
+    let device_repo = DeviceRepository::new(state.pool.clone());
+    let geofence_repo = GeofenceRepository::new(state.pool.clone());
+    // Geofence counts per device, including those created by this request
+    let mut counts: HashMap<Uuid, i64> = HashMap::new();
+    let mut response = BulkCreateGeofencesResponse::default();
+
+    for (idx, item) in request.geofences.into_iter().enumerate() {
+        let row = idx + 1;
+        let device_id = item.device_id;
+        response.processed += 1;
+
+        match create_bulk_item(&device_repo, &geofence_repo, &mut counts, item).await {
+            Ok(geofence) => {
+                response.created += 1;
+                response
+                    .geofences
+                    .push(BulkCreatedGeofence { row, geofence });
+            }
+            Err(error) => response.errors.push(BulkGeofenceError {
+                row,
+                device_id,
+                error,
+            }),
+        }
+    }
+
+    info!(
+        processed = response.processed,
+        created = response.created,
+        failed = response.errors.len(),
+        "Bulk geofence creation completed"
+    );
+
+    Ok(Json(response))
+}
+
+/// Validate and create one geofence of a bulk request.
+async fn create_bulk_item(
+    device_repo: &DeviceRepository,
+    geofence_repo: &GeofenceRepository,
+    counts: &mut HashMap<Uuid, i64>,
+    item: CreateGeofenceRequest,
+) -> Result<GeofenceResponse, String> {
+    item.validate().map_err(|e| validation_message(&e))?;
+    if item.event_types.is_empty() {
+        return Err("At least one event type is required".to_string());
+    }
+    let schedule = match &item.schedule {
+        Some(schedule) => {
+            schedule.validate()?;
+            Some(serde_json::to_value(schedule).map_err(|e| e.to_string())?)
+        }
+        None => None,
+    };
+
+    let device = device_repo
+        .find_by_device_id(item.device_id)
+        .await
+        .map_err(|e| format!("Error checking device: {}", e))?;
+    if !device.is_some_and(|d| d.active) {
+        return Err("Device not found".to_string());
+    }
+
+    let count = match counts.get(&item.device_id) {
+        Some(count) => *count,
+        None => geofence_repo
+            .count_by_device_id(item.device_id)
+            .await
+            .map_err(|e| format!("Error counting geofences: {}", e))?,
+    };
+    if count >= MAX_GEOFENCES_PER_DEVICE {
+        return Err(format!(
+            "Device has reached maximum geofence limit ({})",
+            MAX_GEOFENCES_PER_DEVICE
+        ));
+    }
+
+    let event_types: Vec<String> = item
+        .event_types
+        .iter()
+        .map(|e| e.as_str().to_string())
+        .collect();
+
+    let entity = geofence_repo
+        .create(
+            item.device_id,
+            &item.name,
+            item.latitude,
+            item.longitude,
+            item.radius_meters,
+            &event_types,
+            item.active,
+            item.metadata,
+            schedule,
+            item.cooldown_seconds,
+            item.arrival_alert_minutes,
+            item.priority,
+        )
+        .await
+        .map_err(|e| format!("Error creating geofence: {}", e))?;
+    counts.insert(item.device_id, count + 1);
+
+    let geofence: domain::models::Geofence = entity.into();
+    Ok(geofence.into())
+}
+
 /// List geofences for a device.
 ///
 /// GET /api/v1/geofences?deviceId=<uuid>
@@ -172,18 +318,9 @@ pub async fn update_geofence(
     Json(request): Json<UpdateGeofenceRequest>,
 ) -> Result<Json<GeofenceResponse>, ApiError> {
     // Validate request
-    request.validate().map_err(|e| {
-        let errors: Vec<String> = e
-            .field_errors()
-            .iter()
-            .flat_map(|(field, errors)| {
-                errors.iter().map(move |err| {
-                    format!("{}: {}", field, err.message.as_ref().unwrap_or(&"".into()))
-                })
-            })
-            .collect();
-        ApiError::Validation(errors.join(", "))
-    })?;
+    request
+        .validate()
+        .map_err(|e| ApiError::Validation(validation_message(&e)))?;
 
     // Validate event_types if provided
     if let Some(ref event_types) = request.event_types {
@@ -194,6 +331,17 @@ pub async fn update_geofence(
         }
     }
 
+    if request.clear_schedule && request.schedule.is_some() {
+        return Err(ApiError::Validation(
+            "schedule and clear_schedule cannot be combined".to_string(),
+        ));
+    }
+    let schedule = if request.clear_schedule {
+        Some(None)
+    } else {
+        schedule_value(request.schedule.as_ref())?.map(Some)
+    };
+
     let geofence_repo = GeofenceRepository::new(state.pool.clone());
 
     // Convert event types if provided
@@ -212,6 +360,10 @@ pub async fn update_geofence(
             event_types.as_deref(),
             request.active,
             request.metadata.clone(),
+            schedule,
+            request.cooldown_seconds,
+            request.arrival_alert_minutes,
+            request.priority,
         )
         .await?
         .ok_or_else(|| ApiError::NotFound("Geofence not found".to_string()))?;
@@ -288,6 +440,11 @@ mod tests {
             event_types: vec![GeofenceEventType::Enter, GeofenceEventType::Exit],
             active: true,
             metadata: None,
+            schedule: None,
+            template_id: None,
+            cooldown_seconds: 0,
+            arrival_alert_minutes: 0,
+            priority: 0,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         };
@@ -308,4 +465,21 @@ mod tests {
         let query: ListGeofencesQuery = serde_json::from_str(json_with_inactive).unwrap();
         assert!(query.include_inactive);
     }
+
+    #[test]
+    fn test_schedule_value() {
+        assert!(schedule_value(None).unwrap().is_none());
+
+        let schedule: WeeklySchedule = serde_json::from_value(serde_json::json!({
+            "windows": [{ "days": ["mon"], "start": "08:00", "end": "15:00" }]
+        }))
+        .unwrap();
+        assert!(schedule_value(Some(&schedule)).unwrap().is_some());
+
+        let invalid = WeeklySchedule {
+            utc_offset_minutes: 900,
+            ..schedule
+        };
+        assert!(schedule_value(Some(&invalid)).is_err());
+    }
 }
@@ -0,0 +1,267 @@
+//! Group API token routes.
+//!
+//! Group owners mint, list and revoke tokens for read-only integrations.
+//! Integrations authenticate with the `X-Group-Token` header and can only
+//! read their group's devices that are not in secret mode.
+
+use std::collections::HashSet;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{Duration, Utc};
+use domain::models::group::GroupRole;
+use domain::models::{
+    CreateGroupApiTokenRequest, GroupApiTokenResponse, GroupIntegrationDevicesResponse,
+    ListGroupApiTokensResponse, GROUP_API_TOKEN_PREFIX, MAX_GROUP_API_TOKENS_PER_GROUP,
+};
+use persistence::entities::GroupApiTokenEntity;
+use persistence::repositories::{
+    CreateGroupApiTokenInput, DeviceRepository, GroupApiTokenRepository, GroupRepository,
+    SettingRepository,
+};
+use rand::Rng;
+use serde_json::json;
+use shared::crypto::sha256_hex;
+use tracing::info;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::app::AppState;
+use crate::error::ApiError;
+use crate::extractors::{GroupTokenAuth, UserAuth};
+use crate::routes::groups::device_summaries;
+
+/// Device setting that hides a device's location from the rest of its group.
+const SECRET_MODE_SETTING_KEY: &str = "secret_mode_enabled";
+
+/// Number of random bytes in a token.
+const TOKEN_RANDOM_BYTES: usize = 32;
+
+/// Characters of the random part kept as the token prefix.
+const TOKEN_PREFIX_CHARS: usize = 8;
+
+/// Generate a new group API token.
+fn generate_group_token() -> String {
+    let bytes: [u8; TOKEN_RANDOM_BYTES] = rand::thread_rng().gen();
+    format!(
+        "{}{}",
+        GROUP_API_TOKEN_PREFIX,
+        URL_SAFE_NO_PAD.encode(bytes)
+    )
+}
+
+/// Displayable prefix of a token (e.g., "pm_grp_aBcD1234").
+fn token_prefix(token: &str) -> String {
+    token
+        .chars()
+        .take(GROUP_API_TOKEN_PREFIX.len() + TOKEN_PREFIX_CHARS)
+        .collect()
+}
+
+fn token_response(entity: GroupApiTokenEntity) -> GroupApiTokenResponse {
+    GroupApiTokenResponse {
+        id: entity.id,
+        group_id: entity.group_id,
+        name: entity.name,
+        token_prefix: entity.token_prefix,
+        token: None,
+        rate_limit_per_minute: entity.rate_limit_per_minute as u32,
+        last_used_at: entity.last_used_at,
+        expires_at: entity.expires_at,
+        revoked_at: entity.revoked_at,
+        created_at: entity.created_at,
+    }
+}
+
+/// Ensure the user owns the group.
+async fn require_group_owner(
+    state: &AppState,
+    group_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), ApiError> {
+    let membership = GroupRepository::new(state.pool.clone())
+        .get_membership(group_id, user_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Group not found or you are not a member".to_string()))?;
+
+    let role: GroupRole = membership.role.into();
+    if !role.can_manage_api_tokens() {
+        return Err(ApiError::Forbidden(
+            "Only the group owner can manage API tokens".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Create a group API token.
+///
+/// POST /api/v1/groups/:group_id/api-tokens
+///
+/// Requires JWT authentication. Only the group owner can create tokens.
+/// The full token is returned only once - store it securely.
+pub async fn create_group_api_token(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(group_id): Path<Uuid>,
+    Json(request): Json<CreateGroupApiTokenRequest>,
+) -> Result<(StatusCode, Json<GroupApiTokenResponse>), ApiError> {
+    request
+        .validate()
+        .map_err(|e| ApiError::Validation(e.to_string()))?;
+    require_group_owner(&state, group_id, user_auth.user_id).await?;
+
+    let repo = GroupApiTokenRepository::new(state.pool.clone());
+    if repo.count_unrevoked(group_id).await? >= MAX_GROUP_API_TOKENS_PER_GROUP {
+        return Err(ApiError::Conflict(format!(
+            "A group can have at most {} API tokens; revoke one first",
+            MAX_GROUP_API_TOKENS_PER_GROUP
+        )));
+    }
+
+    let token = generate_group_token();
+    let entity = repo
+        .create(CreateGroupApiTokenInput {
+            group_id,
+            name: request.name.clone(),
+            token_prefix: token_prefix(&token),
+            token_hash: sha256_hex(&token),
+            rate_limit_per_minute: request.rate_limit_per_minute() as i32,
+            created_by: user_auth.user_id,
+            expires_at: request
+                .expires_in_days
+                .map(|days| Utc::now() + Duration::days(days as i64)),
+        })
+        .await?;
+
+    info!(
+        group_id = %group_id,
+        user_id = %user_auth.user_id,
+        token_id = %entity.id,
+        token_prefix = %entity.token_prefix,
+        "Group API token created"
+    );
+
+    let mut response = token_response(entity);
+    response.token = Some(token);
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+/// List a group's API tokens.
+///
+/// GET /api/v1/groups/:group_id/api-tokens
+///
+/// Requires JWT authentication. Only the group owner can list tokens.
+/// Revoked tokens are included so owners can see the revocation list.
+pub async fn list_group_api_tokens(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(group_id): Path<Uuid>,
+) -> Result<Json<ListGroupApiTokensResponse>, ApiError> {
+    require_group_owner(&state, group_id, user_auth.user_id).await?;
+
+    let tokens = GroupApiTokenRepository::new(state.pool.clone())
+        .list_by_group(group_id)
+        .await?
+        .into_iter()
+        .map(token_response)
+        .collect();
+
+    Ok(Json(ListGroupApiTokensResponse { tokens }))
+}
+
+/// Revoke a group API token.
+///
+/// DELETE /api/v1/groups/:group_id/api-tokens/:token_id
+///
+/// Requires JWT authentication. Only the group owner can revoke tokens.
+/// The token stops working immediately.
+pub async fn revoke_group_api_token(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path((group_id, token_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<GroupApiTokenResponse>, ApiError> {
+    require_group_owner(&state, group_id, user_auth.user_id).await?;
+
+    let entity = GroupApiTokenRepository::new(state.pool.clone())
+        .revoke(group_id, token_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("API token not found".to_string()))?;
+
+    info!(
+        group_id = %group_id,
+        user_id = %user_auth.user_id,
+        token_id = %token_id,
+        "Group API token revoked"
+    );
+
+    Ok(Json(token_response(entity)))
+}
+
+/// List the devices visible to a group API token.
+///
+/// GET /api/v1/integrations/group/devices
+///
+/// Requires a group API token in the `X-Group-Token` header. Returns the
+/// token's group devices with their last location, excluding devices in
+/// secret mode. Locations inside privacy zones are hidden or blurred as for
+/// any other viewer.
+pub async fn get_group_integration_devices(
+    State(state): State<AppState>,
+    auth: GroupTokenAuth,
+) -> Result<Json<GroupIntegrationDevicesResponse>, ApiError> {
+    let group = GroupRepository::new(state.pool.clone())
+        .find_by_id(auth.group_id)
+        .await?
+        .filter(|g| g.is_active)
+        .ok_or_else(|| ApiError::NotFound("Group not found".to_string()))?;
+
+    let secret_devices: HashSet<Uuid> = SettingRepository::new(state.pool.clone())
+        .find_device_ids_with_value(SECRET_MODE_SETTING_KEY, &json!(true))
+        .await?
+        .into_iter()
+        .collect();
+    let devices = DeviceRepository::new(state.pool.clone())
+        .find_devices_with_last_location(&group.slug)
+        .await?
+        .into_iter()
+        .filter(|d| !secret_devices.contains(&d.device_id))
+        .collect();
+    let devices = device_summaries(&state, group.id, None, devices).await?;
+
+    info!(
+        group_id = %group.id,
+        token_prefix = %auth.token_prefix,
+        device_count = devices.len(),
+        "Group integration listed devices"
+    );
+
+    Ok(Json(GroupIntegrationDevicesResponse {
+        group_id: group.id,
+        group_name: group.name,
+        devices,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_group_token() {
+        let token = generate_group_token();
+        assert!(token.starts_with(GROUP_API_TOKEN_PREFIX));
+        // 32 bytes encode to 43 base64 characters without padding
+        assert_eq!(token.len(), GROUP_API_TOKEN_PREFIX.len() + 43);
+        assert_ne!(token, generate_group_token());
+    }
+
+    #[test]
+    fn test_token_prefix() {
+        let prefix = token_prefix("pm_grp_aBcD1234eFgH5678");
+        assert_eq!(prefix, "pm_grp_aBcD1234");
+    }
+}
@@ -0,0 +1,196 @@
+//! Group data export endpoint handlers.
+//!
+//! Group owners export all of a group's data as a ZIP archive. The archive
+//! is built in the background; clients poll the job and download the archive
+//! once it completes.
+
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::Response,
+    Json,
+};
+use chrono::Utc;
+use domain::models::group::GroupRole;
+use domain::models::{ExportJobResponse, ExportJobStatus};
+use persistence::repositories::{
+    DeviceGroupMembershipRepository, ExportJob, ExportJobRepository, GroupRepository,
+};
+use std::path::PathBuf;
+use tokio::fs::File;
+use tokio_util::io::ReaderStream;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::error::ApiError;
+use crate::extractors::UserAuth;
+use crate::routes::location_sharing::load_group_location_filter;
+use crate::services::group_export::{GroupExportService, GROUP_EXPORT_CONTENT_TYPE};
+
+/// Require the user to own the group.
+async fn require_owner(state: &AppState, group_id: Uuid, user_id: Uuid) -> Result<(), ApiError> {
+    let membership = GroupRepository::new(state.pool.clone())
+        .get_membership(group_id, user_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Group not found or you are not a member".to_string()))?;
+    let role: GroupRole = membership.role.into();
+    if !role.can_export_data() {
+        return Err(ApiError::Forbidden(
+            "Only the group owner can export the group's data".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn job_response(group_id: Uuid, job: ExportJob) -> ExportJobResponse {
+    let status = job.status_at(Utc::now());
+    let download_url = (status == ExportJobStatus::Completed)
+        .then(|| format!("/api/v1/groups/{}/export/{}/download", group_id, job.job_id));
+    ExportJobResponse {
+        job_id: job.job_id,
+        status,
+        record_count: job.record_count,
+        download_url,
+        expires_at: Some(job.expires_at),
+        error: job.error_message,
+    }
+}
+
+/// Start an export of all of a group's data.
+///
+/// POST /api/v1/groups/:group_id/export
+///
+/// Requires JWT authentication. Only the owner can export. The archive holds
+/// the members, devices, location history, geofences and trips; poll the
+/// returned job until it completes. Returns 409 while another export of the
+/// group is running.
+pub async fn create_group_export(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(group_id): Path<Uuid>,
+) -> Result<(StatusCode, Json<ExportJobResponse>), ApiError> {
+    require_owner(&state, group_id, user_auth.user_id).await?;
+
+    let repo = ExportJobRepository::new(state.pool.clone());
+    if repo.has_running_group_job(group_id).await? {
+        return Err(ApiError::Conflict(
+            "An export of this group is already running".to_string(),
+        ));
+    }
+
+    // Exported locations are filtered the way the owner sees them in the group
+    let devices = DeviceGroupMembershipRepository::new(state.pool.clone())
+        .list_devices_in_group(group_id, i64::MAX, 0)
+        .await?;
+    let locations = load_group_location_filter(
+        &state.pool,
+        group_id,
+        Some(user_auth.user_id),
+        devices.iter().map(|d| d.owner_user_id),
+    )
+    .await?;
+
+    let job = repo.create_for_group(group_id, user_auth.user_id).await?;
+
+    info!(
+        group_id = %group_id,
+        user_id = %user_auth.user_id,
+        job_id = %job.job_id,
+        "Group export started"
+    );
+
+    let service = GroupExportService::new(
+        state.pool.clone(),
+        PathBuf::from(&state.config.reports.reports_dir),
+    );
+    let job_id = job.job_id.clone();
+    tokio::spawn(async move {
+        service.process_job(&job_id, group_id, locations).await;
+    });
+
+    Ok((StatusCode::ACCEPTED, Json(job_response(group_id, job))))
+}
+
+/// Get the status of a group export.
+///
+/// GET /api/v1/groups/:group_id/export/:job_id
+///
+/// Requires JWT authentication. Only the owner can see exports.
+pub async fn get_group_export(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path((group_id, job_id)): Path<(Uuid, String)>,
+) -> Result<Json<ExportJobResponse>, ApiError> {
+    require_owner(&state, group_id, user_auth.user_id).await?;
+
+    let job = ExportJobRepository::new(state.pool.clone())
+        .find_group_job(group_id, &job_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Export job not found".to_string()))?;
+
+    Ok(Json(job_response(group_id, job)))
+}
+
+/// Download a completed group export.
+///
+/// GET /api/v1/groups/:group_id/export/:job_id/download
+///
+/// Requires JWT authentication. Only the owner can download exports.
+pub async fn download_group_export(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path((group_id, job_id)): Path<(Uuid, String)>,
+) -> Result<Response, ApiError> {
+    require_owner(&state, group_id, user_auth.user_id).await?;
+
+    let job = ExportJobRepository::new(state.pool.clone())
+        .find_group_job(group_id, &job_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Export job not found".to_string()))?;
+
+    let status = job.status_at(Utc::now());
+    if status != ExportJobStatus::Completed {
+        return Err(ApiError::Validation(format!(
+            "Export is not ready for download. Status: {}",
+            status
+        )));
+    }
+    let file_name = job
+        .file_path
+        .as_deref()
+        .ok_or_else(|| ApiError::NotFound("Export file not found".to_string()))?;
+
+    let full_path = PathBuf::from(&state.config.reports.reports_dir).join(file_name);
+    let file = File::open(&full_path).await.map_err(|e| {
+        tracing::error!(
+            error = %e,
+            path = %full_path.display(),
+            "Failed to open group export file"
+        );
+        ApiError::NotFound("Export file not found on disk".to_string())
+    })?;
+    let metadata = file.metadata().await.map_err(|e| {
+        tracing::error!(error = %e, "Failed to get file metadata");
+        ApiError::Internal("Failed to read export file".to_string())
+    })?;
+
+    let download_filename = format!(
+        "group_export_{}.zip",
+        job.created_at.format("%Y%m%d_%H%M%S")
+    );
+
+    Response::builder()
+        .header(header::CONTENT_TYPE, GROUP_EXPORT_CONTENT_TYPE)
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", download_filename),
+        )
+        .header(header::CONTENT_LENGTH, metadata.len())
+        .body(Body::from_stream(ReaderStream::new(file)))
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to build response");
+            ApiError::Internal("Failed to build response".to_string())
+        })
+}
@@ -0,0 +1,136 @@
+//! Nested group endpoint handlers.
+//!
+//! A group can be moved under a parent group. Owners and admins of the
+//! parent then administer the sub-group, and geofence events of the
+//! sub-group's devices show up in the parent's event archive. The
+//! inheritance rules themselves live in `GroupRepository`.
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use domain::models::group::{
+    fits_group_hierarchy, GroupRole, ListSubgroupsResponse, SetParentGroupRequest, SubgroupSummary,
+    MAX_GROUP_DEPTH,
+};
+use domain::models::Group;
+use persistence::repositories::{GroupRepository, SetParentOutcome};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::error::ApiError;
+use crate::extractors::UserAuth;
+
+/// Move a group under a parent group, or make it top-level again.
+///
+/// PUT /api/v1/groups/:group_id/parent
+///
+/// Requires JWT authentication.
+/// - Only the group's owner can change its parent
+/// - The caller must be an owner or admin of the new parent
+/// - Returns 409 if the new parent is the group itself or one of its
+///   sub-groups, or if its parent chain is too deep to check
+pub async fn set_parent_group(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(group_id): Path<Uuid>,
+    Json(request): Json<SetParentGroupRequest>,
+) -> Result<Json<Group>, ApiError> {
+    let repo = GroupRepository::new(state.pool.clone());
+
+    let membership = repo
+        .get_membership(group_id, user_auth.user_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Group not found or you are not a member".to_string()))?;
+    let role: GroupRole = membership.role.into();
+    if role != GroupRole::Owner {
+        return Err(ApiError::Forbidden(
+            "Only the group owner can change its parent group".to_string(),
+        ));
+    }
+
+    if let Some(parent_id) = request.parent_group_id {
+        if parent_id == group_id {
+            return Err(ApiError::Conflict(
+                "A group cannot be its own parent".to_string(),
+            ));
+        }
+
+        let parent_membership = repo
+            .get_membership(parent_id, user_auth.user_id)
+            .await?
+            .ok_or_else(|| {
+                ApiError::NotFound("Parent group not found or you are not a member".to_string())
+            })?;
+        let parent_role: GroupRole = parent_membership.role.into();
+        if !parent_role.can_manage_group() {
+            return Err(ApiError::Forbidden(
+                "Only owners and admins of the parent group can add sub-groups".to_string(),
+            ));
+        }
+
+        let parent_depth = repo.ancestor_ids(parent_id).await?.len() + 1;
+        let subtree_height = repo.subtree_height(group_id).await?.max(1) as usize;
+        if !fits_group_hierarchy(parent_depth, subtree_height) {
+            return Err(ApiError::Validation(format!(
+                "Groups can be nested at most {} levels deep",
+                MAX_GROUP_DEPTH
+            )));
+        }
+    }
+
+    let group: Group = match repo.set_parent(group_id, request.parent_group_id).await? {
+        SetParentOutcome::Updated(group) => (*group).into(),
+        SetParentOutcome::NotFound => {
+            return Err(ApiError::NotFound("Group not found".to_string()));
+        }
+        SetParentOutcome::Cycle => {
+            return Err(ApiError::Conflict(
+                "A group cannot be nested inside one of its sub-groups".to_string(),
+            ));
+        }
+        SetParentOutcome::ChainTooDeep => {
+            return Err(ApiError::Conflict(
+                "The parent group's hierarchy is too deep to verify".to_string(),
+            ));
+        }
+    };
+
+    info!(
+        group_id = %group_id,
+        parent_group_id = ?group.parent_group_id,
+        user_id = %user_auth.user_id,
+        "Group parent changed"
+    );
+
+    Ok(Json(group))
+}
+
+/// List a group's direct sub-groups.
+///
+/// GET /api/v1/groups/:group_id/subgroups
+///
+/// Requires JWT authentication. User must be a member of the group.
+pub async fn list_subgroups(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(group_id): Path<Uuid>,
+) -> Result<Json<ListSubgroupsResponse>, ApiError> {
+    let repo = GroupRepository::new(state.pool.clone());
+
+    let _membership = repo
+        .get_membership(group_id, user_auth.user_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Group not found or you are not a member".to_string()))?;
+
+    let data: Vec<SubgroupSummary> = repo
+        .list_subgroups(group_id)
+        .await?
+        .into_iter()
+        .map(SubgroupSummary::from)
+        .collect();
+    let count = data.len();
+
+    Ok(Json(ListSubgroupsResponse { data, count }))
+}
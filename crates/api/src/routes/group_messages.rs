@@ -0,0 +1,223 @@
+//! Group message endpoint handlers.
+//!
+//! Announcements (owners and admins) and location check-ins (any member),
+//! pushed to the other members' devices when posted.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::Utc;
+use domain::models::group::GroupRole;
+use domain::models::location::PaginationInfo;
+use domain::models::{
+    message_preview, GroupMessage, GroupMessageKind, ListGroupMessagesQuery,
+    ListGroupMessagesResponse, PostGroupMessageRequest,
+};
+use domain::services::{GroupMessagePayload, NotificationService, NotificationType};
+use persistence::repositories::{GroupMessageRepository, GroupRepository, NewGroupMessage};
+use sqlx::PgPool;
+use tracing::{info, warn};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::app::AppState;
+use crate::error::ApiError;
+use crate::extractors::UserAuth;
+use crate::services::push_tokens::{active_push_tokens, handle_send_result};
+
+fn validation_error(e: validator::ValidationErrors) -> ApiError {
+    let errors: Vec<String> = e
+        .field_errors()
+        .iter()
+        .flat_map(|(field, errors)| {
+            errors.iter().map(move |err| {
+                format!("{}: {}", field, err.message.as_ref().unwrap_or(&"".into()))
+            })
+        })
+        .collect();
+    ApiError::Validation(errors.join(", "))
+}
+
+/// Post a message to a group.
+///
+/// POST /api/v1/groups/:group_id/messages
+///
+/// Requires JWT authentication.
+/// - Any member can post a check-in
+/// - Only owners and admins can post announcements
+/// - The other members' devices are notified in the background
+pub async fn post_group_message(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(group_id): Path<Uuid>,
+    Json(request): Json<PostGroupMessageRequest>,
+) -> Result<(StatusCode, Json<GroupMessage>), ApiError> {
+    request.validate().map_err(validation_error)?;
+
+    let group_repo = GroupRepository::new(state.pool.clone());
+    let membership = group_repo
+        .get_membership(group_id, user_auth.user_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Group not found or you are not a member".to_string()))?;
+
+    let role: GroupRole = membership.role.into();
+    if request.kind == GroupMessageKind::Announcement && !role.can_manage_group() {
+        return Err(ApiError::Forbidden(
+            "Only owners and admins can post announcements".to_string(),
+        ));
+    }
+
+    let group = group_repo
+        .find_by_id(group_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Group not found".to_string()))?;
+
+    let message: GroupMessage = GroupMessageRepository::new(state.pool.clone())
+        .create(NewGroupMessage {
+            group_id,
+            sender_user_id: user_auth.user_id,
+            kind: request.kind.as_str(),
+            body: request.body.as_deref(),
+            latitude: request.latitude,
+            longitude: request.longitude,
+            place_name: request.place_name.as_deref(),
+        })
+        .await?
+        .into();
+
+    info!(
+        group_id = %group_id,
+        user_id = %user_auth.user_id,
+        message_id = %message.message_id,
+        kind = %message.kind,
+        "Group message posted"
+    );
+
+    notify_members(
+        state.pool.clone(),
+        state.notification_service.clone(),
+        group.name,
+        user_auth.user_id,
+        &message,
+    );
+
+    Ok((StatusCode::CREATED, Json(message)))
+}
+
+/// List a group's messages, newest first.
+///
+/// GET /api/v1/groups/:group_id/messages
+///
+/// Requires JWT authentication.
+/// - User must be a member of the group
+/// - Follow `pagination.next_cursor` to page back in time
+/// - Only messages within the retention limits are available
+pub async fn list_group_messages(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(group_id): Path<Uuid>,
+    Query(query): Query<ListGroupMessagesQuery>,
+) -> Result<Json<ListGroupMessagesResponse>, ApiError> {
+    query.validate().map_err(validation_error)?;
+
+    let cursor = query
+        .cursor
+        .as_deref()
+        .map(shared::pagination::decode_cursor)
+        .transpose()
+        .map_err(|_| ApiError::Validation("Invalid cursor format".to_string()))?;
+
+    let _membership = GroupRepository::new(state.pool.clone())
+        .get_membership(group_id, user_auth.user_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Group not found or you are not a member".to_string()))?;
+
+    let (entities, has_more) = GroupMessageRepository::new(state.pool.clone())
+        .list_by_group(
+            group_id,
+            query.kind.map(|k| k.as_str()),
+            cursor,
+            query.limit,
+        )
+        .await?;
+
+    let next_cursor = if has_more {
+        entities
+            .last()
+            .map(|m| shared::pagination::encode_cursor(m.created_at, m.id))
+    } else {
+        None
+    };
+
+    let messages: Vec<GroupMessage> = entities.into_iter().map(GroupMessage::from).collect();
+
+    Ok(Json(ListGroupMessagesResponse {
+        messages,
+        pagination: PaginationInfo {
+            next_cursor,
+            has_more,
+        },
+    }))
+}
+
+/// Push a new message to the devices of the other group members in the
+/// background. Failures are logged only.
+fn notify_members(
+    pool: PgPool,
+    notification_service: Arc<dyn NotificationService>,
+    group_name: String,
+    sender: Uuid,
+    message: &GroupMessage,
+) {
+    let preview = match message.kind {
+        GroupMessageKind::Announcement => message.body.as_deref().map(message_preview),
+        GroupMessageKind::CheckIn => message
+            .location
+            .as_ref()
+            .and_then(|l| l.place_name.clone())
+            .or_else(|| message.body.as_deref().map(message_preview)),
+    };
+    let payload = GroupMessagePayload {
+        notification_type: NotificationType::GroupMessage,
+        group_id: message.group_id,
+        group_name,
+        message_id: message.message_id,
+        kind: message.kind.to_string(),
+        sender_name: message
+            .sender_display_name
+            .clone()
+            .unwrap_or_else(|| "A member".to_string()),
+        preview,
+        timestamp: Utc::now(),
+    };
+
+    tokio::spawn(async move {
+        let recipients = match GroupMessageRepository::new(pool.clone())
+            .list_recipient_device_ids(payload.group_id, sender)
+            .await
+        {
+            Ok(devices) => devices,
+            Err(e) => {
+                warn!(
+                    group_id = %payload.group_id,
+                    error = %e,
+                    "Failed to load group message recipients"
+                );
+                return;
+            }
+        };
+
+        for device_id in recipients {
+            for token in active_push_tokens(&pool, device_id).await {
+                let result = notification_service
+                    .send_group_message(&token, payload.clone())
+                    .await;
+                handle_send_result(&pool, device_id, &token, result).await;
+            }
+        }
+    });
+}
@@ -0,0 +1,96 @@
+//! Group notification preference endpoint handlers.
+//!
+//! Members choose, per group, which events push notifications to their own
+//! devices. Only direct members have preferences; admins inheriting access
+//! from a parent group are not notified about the group.
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use domain::models::{
+    GroupNotificationPreferences, GroupNotificationPreferencesResponse,
+    UpdateGroupNotificationPreferencesRequest,
+};
+use persistence::repositories::{GroupNotificationPreferencesRepository, GroupRepository};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::error::ApiError;
+use crate::extractors::UserAuth;
+
+async fn require_direct_membership(
+    state: &AppState,
+    group_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), ApiError> {
+    GroupRepository::new(state.pool.clone())
+        .get_direct_membership(group_id, user_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Group not found or you are not a member".to_string()))?;
+    Ok(())
+}
+
+/// Get the current user's notification preferences for a group.
+///
+/// GET /api/v1/groups/:group_id/notification-preferences
+///
+/// Members who never changed them get every category enabled.
+pub async fn get_notification_preferences(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(group_id): Path<Uuid>,
+) -> Result<Json<GroupNotificationPreferencesResponse>, ApiError> {
+    require_direct_membership(&state, group_id, user_auth.user_id).await?;
+
+    let stored = GroupNotificationPreferencesRepository::new(state.pool.clone())
+        .find(group_id, user_auth.user_id)
+        .await?;
+
+    Ok(Json(GroupNotificationPreferencesResponse {
+        group_id,
+        preferences: stored
+            .as_ref()
+            .map(GroupNotificationPreferences::from)
+            .unwrap_or_default(),
+        updated_at: stored.map(|p| p.updated_at),
+    }))
+}
+
+/// Update the current user's notification preferences for a group.
+///
+/// PUT /api/v1/groups/:group_id/notification-preferences
+///
+/// Omitted categories keep their current value.
+pub async fn update_notification_preferences(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(group_id): Path<Uuid>,
+    Json(request): Json<UpdateGroupNotificationPreferencesRequest>,
+) -> Result<Json<GroupNotificationPreferencesResponse>, ApiError> {
+    require_direct_membership(&state, group_id, user_auth.user_id).await?;
+
+    let repo = GroupNotificationPreferencesRepository::new(state.pool.clone());
+    let current = repo
+        .find(group_id, user_auth.user_id)
+        .await?
+        .as_ref()
+        .map(GroupNotificationPreferences::from)
+        .unwrap_or_default();
+    let stored = repo
+        .upsert(group_id, user_auth.user_id, &request.apply(current))
+        .await?;
+
+    info!(
+        group_id = %group_id,
+        user_id = %user_auth.user_id,
+        "Group notification preferences updated"
+    );
+
+    Ok(Json(GroupNotificationPreferencesResponse {
+        group_id,
+        preferences: GroupNotificationPreferences::from(&stored),
+        updated_at: Some(stored.updated_at),
+    }))
+}
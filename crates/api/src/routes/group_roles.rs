@@ -0,0 +1,333 @@
+//! Custom group role route handlers.
+//!
+//! Org admins define custom group roles with granular permissions; group
+//! owners and admins assign them to members of groups belonging to the
+//! organization.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
+use domain::models::group::GroupRole;
+use domain::models::{
+    AssignGroupCustomRoleRequest, GroupCustomRole, GroupPermissions, ListGroupCustomRolesResponse,
+    MemberPermissionsResponse, OrgUserRole, SaveGroupCustomRoleRequest,
+};
+use persistence::repositories::{
+    GroupCustomRoleFields, GroupCustomRoleRepository, GroupRepository, OrgUserRepository,
+};
+use tracing::info;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::app::AppState;
+use crate::error::ApiError;
+use crate::extractors::UserAuth;
+use crate::services::content_filter::check_blocked_terms;
+
+/// Create custom group role routes.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_roles).post(create_role))
+        .route(
+            "/:role_id",
+            get(get_role).put(update_role).delete(delete_role),
+        )
+}
+
+/// Check that the user is an admin or owner of the organization.
+async fn require_org_admin(state: &AppState, org_id: Uuid, user_id: Uuid) -> Result<(), ApiError> {
+    let org_user = OrgUserRepository::new(state.pool.clone())
+        .find_by_org_and_user(org_id, user_id)
+        .await?
+        .ok_or_else(|| ApiError::Forbidden("User not in organization".to_string()))?;
+
+    if org_user.role != OrgUserRole::Owner && org_user.role != OrgUserRole::Admin {
+        return Err(ApiError::Forbidden(
+            "Admin or owner access required".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Fail with a conflict if another role of the organization has the name.
+async fn check_name_available(
+    repo: &GroupCustomRoleRepository,
+    org_id: Uuid,
+    name: &str,
+    exclude_id: Option<Uuid>,
+) -> Result<(), ApiError> {
+    let taken = repo
+        .find_by_name(org_id, name)
+        .await?
+        .is_some_and(|r| Some(r.id) != exclude_id);
+    if taken {
+        return Err(ApiError::Conflict(format!(
+            "Group role with name '{}' already exists",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Reject role names and descriptions containing a blocked term.
+async fn check_role_terms(
+    state: &AppState,
+    org_id: Uuid,
+    request: &SaveGroupCustomRoleRequest,
+) -> Result<(), ApiError> {
+    check_blocked_terms(
+        &state.pool,
+        org_id,
+        &[
+            ("name", Some(&request.name)),
+            ("description", request.description.as_deref()),
+        ],
+    )
+    .await
+}
+
+/// Validate a save request and convert it for storage.
+fn role_fields(
+    request: &SaveGroupCustomRoleRequest,
+) -> Result<GroupCustomRoleFields<'_>, ApiError> {
+    request
+        .validate()
+        .map_err(|e| ApiError::Validation(e.to_string()))?;
+
+    let permissions: GroupPermissions = request.permissions.iter().copied().collect();
+    Ok(GroupCustomRoleFields {
+        name: &request.name,
+        description: request.description.as_deref(),
+        permissions: permissions.bits(),
+    })
+}
+
+/// List custom group roles.
+///
+/// GET /api/admin/v1/organizations/:org_id/group-roles
+async fn list_roles(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    user: UserAuth,
+) -> Result<Json<ListGroupCustomRolesResponse>, ApiError> {
+    require_org_admin(&state, org_id, user.user_id).await?;
+
+    let data = GroupCustomRoleRepository::new(state.pool.clone())
+        .list(org_id)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(Json(ListGroupCustomRolesResponse { data }))
+}
+
+/// Create a custom group role.
+///
+/// POST /api/admin/v1/organizations/:org_id/group-roles
+async fn create_role(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    user: UserAuth,
+    Json(request): Json<SaveGroupCustomRoleRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let fields = role_fields(&request)?;
+    require_org_admin(&state, org_id, user.user_id).await?;
+    check_role_terms(&state, org_id, &request).await?;
+
+    let repo = GroupCustomRoleRepository::new(state.pool.clone());
+    check_name_available(&repo, org_id, &request.name, None).await?;
+
+    let role: GroupCustomRole = repo.create(org_id, fields, user.user_id).await?.into();
+
+    info!(
+        organization_id = %org_id,
+        role_id = %role.id,
+        "Custom group role created"
+    );
+
+    Ok((StatusCode::CREATED, Json(role)))
+}
+
+/// Get a custom group role.
+///
+/// GET /api/admin/v1/organizations/:org_id/group-roles/:role_id
+async fn get_role(
+    State(state): State<AppState>,
+    Path((org_id, role_id)): Path<(Uuid, Uuid)>,
+    user: UserAuth,
+) -> Result<Json<GroupCustomRole>, ApiError> {
+    require_org_admin(&state, org_id, user.user_id).await?;
+
+    let role = GroupCustomRoleRepository::new(state.pool.clone())
+        .find_by_id(org_id, role_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Group role not found".to_string()))?;
+
+    Ok(Json(role.into()))
+}
+
+/// Replace a custom group role.
+///
+/// PUT /api/admin/v1/organizations/:org_id/group-roles/:role_id
+///
+/// Members holding the role get the new permissions right away.
+async fn update_role(
+    State(state): State<AppState>,
+    Path((org_id, role_id)): Path<(Uuid, Uuid)>,
+    user: UserAuth,
+    Json(request): Json<SaveGroupCustomRoleRequest>,
+) -> Result<Json<GroupCustomRole>, ApiError> {
+    let fields = role_fields(&request)?;
+    require_org_admin(&state, org_id, user.user_id).await?;
+    check_role_terms(&state, org_id, &request).await?;
+
+    let repo = GroupCustomRoleRepository::new(state.pool.clone());
+    check_name_available(&repo, org_id, &request.name, Some(role_id)).await?;
+
+    let role = repo
+        .update(org_id, role_id, fields)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Group role not found".to_string()))?;
+
+    info!(
+        organization_id = %org_id,
+        role_id = %role_id,
+        "Custom group role updated"
+    );
+
+    Ok(Json(role.into()))
+}
+
+/// Delete a custom group role.
+///
+/// DELETE /api/admin/v1/organizations/:org_id/group-roles/:role_id
+///
+/// Members holding the role fall back to their built-in role.
+async fn delete_role(
+    State(state): State<AppState>,
+    Path((org_id, role_id)): Path<(Uuid, Uuid)>,
+    user: UserAuth,
+) -> Result<StatusCode, ApiError> {
+    require_org_admin(&state, org_id, user.user_id).await?;
+
+    let deleted = GroupCustomRoleRepository::new(state.pool.clone())
+        .delete(org_id, role_id)
+        .await?;
+    if !deleted {
+        return Err(ApiError::NotFound("Group role not found".to_string()));
+    }
+
+    info!(
+        organization_id = %org_id,
+        role_id = %role_id,
+        "Custom group role deleted"
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Effective permissions of a group member with `role`.
+async fn member_permissions(
+    state: &AppState,
+    group_id: Uuid,
+    user_id: Uuid,
+    role: GroupRole,
+) -> Result<MemberPermissionsResponse, ApiError> {
+    let custom = GroupCustomRoleRepository::new(state.pool.clone())
+        .find_for_member(group_id, user_id)
+        .await?;
+    let permissions = GroupPermissions::resolve(role, custom.as_ref().map(|r| r.permissions()));
+
+    Ok(MemberPermissionsResponse {
+        group_id,
+        user_id,
+        role,
+        custom_role_id: custom.map(|r| r.id),
+        permissions: permissions.to_vec(),
+    })
+}
+
+/// Get a group member's effective permissions.
+///
+/// GET /api/v1/groups/:group_id/members/:user_id/permissions
+///
+/// Requires JWT authentication. User must be a member of the group.
+pub async fn get_member_permissions(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path((group_id, user_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<MemberPermissionsResponse>, ApiError> {
+    let group_repo = GroupRepository::new(state.pool.clone());
+    group_repo
+        .get_membership(group_id, user_auth.user_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Group not found or you are not a member".to_string()))?;
+
+    let target = group_repo
+        .get_membership(group_id, user_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Member not found".to_string()))?;
+
+    Ok(Json(
+        member_permissions(&state, group_id, user_id, target.role.into()).await?,
+    ))
+}
+
+/// Assign a custom role to a group member, or remove it.
+///
+/// PUT /api/v1/groups/:group_id/members/:user_id/custom-role
+///
+/// Requires JWT authentication.
+/// - Only owners and admins can assign custom roles
+/// - The role must be defined by an organization a group member belongs to
+/// - Custom roles only change the permissions of members and viewers
+pub async fn assign_member_custom_role(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path((group_id, user_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<AssignGroupCustomRoleRequest>,
+) -> Result<Json<MemberPermissionsResponse>, ApiError> {
+    let group_repo = GroupRepository::new(state.pool.clone());
+    let membership = group_repo
+        .get_membership(group_id, user_auth.user_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Group not found or you are not a member".to_string()))?;
+
+    let actor_role: GroupRole = membership.role.into();
+    if !actor_role.can_manage_members() {
+        return Err(ApiError::Forbidden(
+            "Only admins and owners can assign custom roles".to_string(),
+        ));
+    }
+
+    let target = group_repo
+        .get_direct_membership(group_id, user_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Member not found".to_string()))?;
+
+    let repo = GroupCustomRoleRepository::new(state.pool.clone());
+    if let Some(role_id) = request.custom_role_id {
+        repo.find_assignable(role_id, group_id)
+            .await?
+            .ok_or_else(|| ApiError::NotFound("Group role not found".to_string()))?;
+    }
+    repo.assign(group_id, user_id, request.custom_role_id)
+        .await?;
+
+    info!(
+        group_id = %group_id,
+        user_id = %user_id,
+        actor_id = %user_auth.user_id,
+        custom_role_id = ?request.custom_role_id,
+        "Group member custom role updated"
+    );
+
+    Ok(Json(
+        member_permissions(&state, group_id, user_id, target.role.into()).await?,
+    ))
+}
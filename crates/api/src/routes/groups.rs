@@ -10,21 +10,34 @@ use axum::{
 use chrono::{DateTime, Utc};
 use domain::models::device::{DeviceLastLocation, DeviceSummary};
 use domain::models::group::{
-    generate_slug, CreateGroupRequest, CreateGroupResponse, GroupDetail, GroupRole, GroupSummary,
-    LastLocationInfo, ListGroupsQuery, ListGroupsResponse, ListMembersQuery, ListMembersResponse,
-    MemberDeviceInfo, MemberResponse, MembershipInfo, Pagination, TransferOwnershipRequest,
-    TransferOwnershipResponse, UpdateGroupRequest, UpdateRoleRequest, UpdateRoleResponse,
-    UserPublic,
+    generate_slug, CreateGroupRequest, CreateGroupResponse, DeviceNicknameResponse, GroupDetail,
+    GroupRetentionPolicy, GroupRole, GroupSummary, LastLocationInfo, ListGroupsQuery,
+    ListGroupsResponse, ListMembersQuery, ListMembersResponse, MemberDeviceInfo,
+    MemberNicknameResponse, MemberResponse, MembershipInfo, Pagination, SetNicknameRequest,
+    TransferOwnershipRequest, TransferOwnershipResponse, UpdateGroupRequest,
+    UpdateGroupRetentionRequest, UpdateRoleRequest, UpdateRoleResponse, UserPublic,
+};
+use domain::models::group_settings::{
+    GroupSettings, GroupSettingsResponse, UpdateGroupSettingsRequest,
 };
 use domain::models::invite::{
-    JoinGroupInfo, JoinGroupRequest, JoinGroupResponse, JoinMembershipInfo,
+    can_accept_invite, JoinGroupInfo, JoinGroupRequest, JoinGroupResponse, JoinMembershipInfo,
+};
+use domain::models::location::PaginationInfo;
+use domain::models::{
+    distance_meters, GroupEvent, GroupEventType, GroupPermission, ListGroupEventsQuery,
+    ListGroupEventsResponse, PrivacyZoneSet, SharedLocation,
+};
+use persistence::entities::{
+    DeviceWithLastLocationEntity, MemberDeviceEntity, NearbyDeviceInGroupEntity,
 };
-use persistence::entities::MemberDeviceEntity;
 use persistence::repositories::{
-    DeviceGroupMembershipRepository, DeviceRepository, GroupRepository, InviteRepository,
-    MigrationAuditRepository,
+    DeviceGroupMembershipRepository, DeviceRepository, GroupEventRepository, GroupRepository,
+    InviteRepository, MigrationAuditRepository, UserRepository,
 };
+use persistence::retry::{retry_on_conflict, Retryable};
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use tracing::{error, info, warn};
 use uuid::Uuid;
 use validator::Validate;
@@ -32,13 +45,26 @@ use validator::Validate;
 use crate::app::AppState;
 use crate::error::ApiError;
 use crate::extractors::UserAuth;
+use crate::middleware::rbac::{require_group_permission, resolve_group_permissions};
+use crate::routes::location_sharing::load_group_location_filter;
+use crate::services::group_notifications::notify_member_joined;
+use crate::services::GroupEventRecorder;
 
 /// Threshold in minutes for considering a device as online.
 /// A device is considered online if it was last seen within this duration.
 const DEVICE_ONLINE_THRESHOLD_MINUTES: i64 = 5;
 
 /// Convert a database device entity to the API response format.
-fn to_member_device_info(device: MemberDeviceEntity, now: DateTime<Utc>) -> MemberDeviceInfo {
+///
+/// The last location is shared through `locations`, the group's location
+/// filter for the viewer, and omitted when the viewer lacks the
+/// `view_locations` group permission.
+fn to_member_device_info(
+    device: MemberDeviceEntity,
+    locations: &PrivacyZoneSet,
+    can_view_locations: bool,
+    now: DateTime<Utc>,
+) -> MemberDeviceInfo {
     let online_threshold = chrono::Duration::minutes(DEVICE_ONLINE_THRESHOLD_MINUTES);
     let is_online = device
         .last_seen_at
@@ -54,16 +80,69 @@ fn to_member_device_info(device: MemberDeviceEntity, now: DateTime<Utc>) -> Memb
             device.last_longitude,
             device.last_location_time,
         ) {
-            (Some(lat), Some(lon), Some(time)) => Some(LastLocationInfo {
-                latitude: lat,
-                longitude: lon,
-                timestamp: time,
-            }),
+            (Some(lat), Some(lon), Some(time)) if can_view_locations => locations
+                .share(Some(device.owner_user_id), lat, lon)
+                .apply(lat, lon, 0.0)
+                .map(|(latitude, longitude, _)| LastLocationInfo {
+                    latitude,
+                    longitude,
+                    timestamp: time,
+                }),
             _ => None,
         },
+        sharing_paused: locations.is_paused(Some(device.owner_user_id)),
+    }
+}
+
+/// Apply the location filter to the position in an archived event's payload.
+///
+/// Positions the viewer may not see are removed; positions in a snapping
+/// privacy zone are replaced by the zone center.
+fn filter_event_location(
+    payload: &mut serde_json::Value,
+    owner: Option<Uuid>,
+    locations: &PrivacyZoneSet,
+    can_view_locations: bool,
+) {
+    let (Some(latitude), Some(longitude)) =
+        (payload["latitude"].as_f64(), payload["longitude"].as_f64())
+    else {
+        return;
+    };
+    let Some(fields) = payload.as_object_mut() else {
+        return;
+    };
+
+    let shared = if can_view_locations && !locations.is_paused(owner) {
+        locations
+            .share(owner, latitude, longitude)
+            .apply(latitude, longitude, 0.0)
+    } else {
+        None
+    };
+    match shared {
+        Some((latitude, longitude, _)) => {
+            fields.insert("latitude".to_string(), latitude.into());
+            fields.insert("longitude".to_string(), longitude.into());
+        }
+        None => {
+            fields.remove("latitude");
+            fields.remove("longitude");
+        }
     }
 }
 
+/// Whether a group member may see other members' locations.
+async fn can_view_group_locations(
+    pool: &sqlx::PgPool,
+    group_id: Uuid,
+    user_id: Uuid,
+    role: GroupRole,
+) -> Result<bool, ApiError> {
+    let permissions = resolve_group_permissions(pool, group_id, user_id, role).await?;
+    Ok(permissions.contains(GroupPermission::ViewLocations))
+}
+
 /// Create a new group.
 ///
 /// POST /api/v1/groups
@@ -168,6 +247,7 @@ pub async fn list_groups(
             device_count: g.device_count,
             your_role: g.role.into(),
             joined_at: g.joined_at,
+            parent_group_id: g.parent_group_id,
             has_current_device: g.has_current_device,
         })
         .collect();
@@ -229,6 +309,7 @@ pub async fn get_group(
         created_by: group.created_by,
         created_at: group.created_at,
         updated_at: group.updated_at,
+        parent_group_id: group.parent_group_id,
         your_role: role,
         your_membership: MembershipInfo {
             id: group.membership_id,
@@ -327,6 +408,7 @@ pub async fn update_group(
         created_by: updated_group.created_by,
         created_at: updated_group.created_at,
         updated_at: updated_group.updated_at,
+        parent_group_id: updated_group.parent_group_id,
         your_role: role,
         your_membership: MembershipInfo {
             id: group.membership_id,
@@ -399,10 +481,17 @@ pub async fn list_members(
     let membership_repo = DeviceGroupMembershipRepository::new(state.pool.clone());
 
     // Check user is a member of the group
-    let _membership = repo
+    let membership = repo
         .get_membership(group_id, user_auth.user_id)
         .await?
         .ok_or_else(|| ApiError::NotFound("Group not found or you are not a member".to_string()))?;
+    let can_view_locations = can_view_group_locations(
+        &state.pool,
+        group_id,
+        user_auth.user_id,
+        membership.role.into(),
+    )
+    .await?;
 
     // Pagination defaults
     let page = query.page.unwrap_or(1).max(1);
@@ -421,12 +510,20 @@ pub async fn list_members(
     let user_ids: Vec<Uuid> = members.iter().map(|m| m.user_id).collect();
     let all_devices = device_repo.find_devices_by_users(&user_ids).await?;
 
+    let locations = load_group_location_filter(
+        &state.pool,
+        group_id,
+        Some(user_auth.user_id),
+        user_ids.iter().copied().map(Some),
+    )
+    .await?;
+
     // Group devices by user_id
     let now = Utc::now();
     let mut devices_by_user: HashMap<Uuid, Vec<MemberDeviceInfo>> = HashMap::new();
     for device in all_devices {
         let owner_id = device.owner_user_id;
-        let device_info = to_member_device_info(device, now);
+        let device_info = to_member_device_info(device, &locations, can_view_locations, now);
         devices_by_user
             .entry(owner_id)
             .or_default()
@@ -455,6 +552,7 @@ pub async fn list_members(
                 role: m.role.into(),
                 joined_at: m.joined_at,
                 invited_by: m.invited_by,
+                nickname: m.nickname,
                 devices: user_devices,
                 device_count,
             }
@@ -498,10 +596,17 @@ pub async fn get_member(
     let membership_repo = DeviceGroupMembershipRepository::new(state.pool.clone());
 
     // Check user is a member of the group
-    let _membership = repo
+    let membership = repo
         .get_membership(group_id, user_auth.user_id)
         .await?
         .ok_or_else(|| ApiError::NotFound("Group not found or you are not a member".to_string()))?;
+    let can_view_locations = can_view_group_locations(
+        &state.pool,
+        group_id,
+        user_auth.user_id,
+        membership.role.into(),
+    )
+    .await?;
 
     // Get target member
     let member = repo
@@ -511,10 +616,17 @@ pub async fn get_member(
 
     // Fetch devices for the member
     let member_devices = device_repo.find_devices_by_users(&[target_user_id]).await?;
+    let locations = load_group_location_filter(
+        &state.pool,
+        group_id,
+        Some(user_auth.user_id),
+        [Some(target_user_id)],
+    )
+    .await?;
     let now = Utc::now();
     let devices: Vec<MemberDeviceInfo> = member_devices
         .into_iter()
-        .map(|d| to_member_device_info(d, now))
+        .map(|d| to_member_device_info(d, &locations, can_view_locations, now))
         .collect();
 
     // Get device count for this user in this group (Story UGM-3.6)
@@ -544,6 +656,7 @@ pub async fn get_member(
         role: member.role.into(),
         joined_at: member.joined_at,
         invited_by: member.invited_by,
+        nickname: member.nickname,
         devices: if devices.is_empty() {
             None
         } else {
@@ -595,7 +708,7 @@ pub async fn remove_member(
 
         // Check target member exists and their role
         let target_membership = repo
-            .get_membership(group_id, target_user_id)
+            .get_direct_membership(group_id, target_user_id)
             .await?
             .ok_or_else(|| ApiError::NotFound("Member not found".to_string()))?;
 
@@ -631,6 +744,16 @@ pub async fn remove_member(
         "Member removed from group"
     );
 
+    GroupEventRecorder::new(state.pool.clone())
+        .record(
+            group_id,
+            GroupEventType::MemberRemoved,
+            None,
+            Some(user_auth.user_id),
+            json!({ "user_id": target_user_id, "self_removal": is_self_removal }),
+        )
+        .await;
+
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -679,7 +802,7 @@ pub async fn update_member_role(
 
     // Get target member
     let target_membership = repo
-        .get_membership(group_id, target_user_id)
+        .get_direct_membership(group_id, target_user_id)
         .await?
         .ok_or_else(|| ApiError::NotFound("Member not found".to_string()))?;
 
@@ -719,6 +842,16 @@ pub async fn update_member_role(
         "Member role updated"
     );
 
+    GroupEventRecorder::new(state.pool.clone())
+        .record(
+            group_id,
+            GroupEventType::MemberRoleChanged,
+            None,
+            Some(user_auth.user_id),
+            json!({ "user_id": target_user_id, "role": request.role }),
+        )
+        .await;
+
     Ok(Json(UpdateRoleResponse {
         id: updated.id,
         user_id: updated.user_id,
@@ -728,6 +861,125 @@ pub async fn update_member_role(
     }))
 }
 
+// =============================================================================
+// Nicknames
+// =============================================================================
+
+/// Check that the actor may set nicknames in a group.
+async fn require_nickname_manager(
+    repo: &GroupRepository,
+    group_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), ApiError> {
+    let membership = repo
+        .get_membership(group_id, user_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Group not found or you are not a member".to_string()))?;
+    let role: GroupRole = membership.role.into();
+    if !role.can_manage_members() {
+        return Err(ApiError::Forbidden(
+            "Only admins and owners can set nicknames".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Set or clear a member's nickname within the group.
+///
+/// PUT /api/v1/groups/:group_id/members/:user_id/nickname
+///
+/// Requires admin or owner role.
+pub async fn set_member_nickname(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path((group_id, target_user_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<SetNicknameRequest>,
+) -> Result<Json<MemberNicknameResponse>, ApiError> {
+    request.validate().map_err(|e| {
+        let errors: Vec<String> = e
+            .field_errors()
+            .iter()
+            .flat_map(|(field, errors)| {
+                errors.iter().map(move |err| {
+                    format!("{}: {}", field, err.message.as_ref().unwrap_or(&"".into()))
+                })
+            })
+            .collect();
+        ApiError::Validation(errors.join(", "))
+    })?;
+
+    let repo = GroupRepository::new(state.pool.clone());
+    require_nickname_manager(&repo, group_id, user_auth.user_id).await?;
+
+    if !repo
+        .set_member_nickname(group_id, target_user_id, request.nickname.as_deref())
+        .await?
+    {
+        return Err(ApiError::NotFound("Member not found".to_string()));
+    }
+
+    info!(
+        group_id = %group_id,
+        actor_user_id = %user_auth.user_id,
+        target_user_id = %target_user_id,
+        "Member nickname updated"
+    );
+
+    Ok(Json(MemberNicknameResponse {
+        group_id,
+        user_id: target_user_id,
+        nickname: request.nickname,
+    }))
+}
+
+/// Set or clear a device's nickname within the group.
+///
+/// PUT /api/v1/groups/:group_id/devices/:device_id/nickname
+///
+/// Requires admin or owner role.
+pub async fn set_device_nickname(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path((group_id, device_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<SetNicknameRequest>,
+) -> Result<Json<DeviceNicknameResponse>, ApiError> {
+    request.validate().map_err(|e| {
+        let errors: Vec<String> = e
+            .field_errors()
+            .iter()
+            .flat_map(|(field, errors)| {
+                errors.iter().map(move |err| {
+                    format!("{}: {}", field, err.message.as_ref().unwrap_or(&"".into()))
+                })
+            })
+            .collect();
+        ApiError::Validation(errors.join(", "))
+    })?;
+
+    let repo = GroupRepository::new(state.pool.clone());
+    require_nickname_manager(&repo, group_id, user_auth.user_id).await?;
+
+    if !DeviceGroupMembershipRepository::new(state.pool.clone())
+        .set_device_nickname(device_id, group_id, request.nickname.as_deref())
+        .await?
+    {
+        return Err(ApiError::NotFound("Device not found in group".to_string()));
+    }
+
+    info!(
+        group_id = %group_id,
+        actor_user_id = %user_auth.user_id,
+        device_id = %device_id,
+        "Device nickname updated"
+    );
+
+    Ok(Json(DeviceNicknameResponse {
+        group_id,
+        device_id,
+        nickname: request.nickname,
+    }))
+}
+
 // =============================================================================
 // Join Group with Invite Code (Story 11.5)
 // =============================================================================
@@ -739,6 +991,7 @@ pub async fn update_member_role(
 /// Requires JWT authentication.
 /// Returns 400 for invalid code format.
 /// Returns 404 if invite not found.
+/// Returns 403 if the invite was sent to a different email address.
 /// Returns 409 if already a member.
 /// Returns 410 if invite expired or fully used.
 pub async fn join_group(
@@ -760,12 +1013,26 @@ pub async fn join_group(
         ApiError::Validation(errors.join(", "))
     })?;
 
+    Ok(Json(
+        join_with_invite(&state, user_auth.user_id, &request.code).await?,
+    ))
+}
+
+/// Add a user to the group of an invite code.
+///
+/// Shared by joining directly and by passing an invite code when registering
+/// or logging in.
+pub(crate) async fn join_with_invite(
+    state: &AppState,
+    user_id: Uuid,
+    code: &str,
+) -> Result<JoinGroupResponse, ApiError> {
     let group_repo = GroupRepository::new(state.pool.clone());
     let invite_repo = InviteRepository::new(state.pool.clone());
 
     // Find the invite by code
     let invite = invite_repo
-        .find_by_code_with_group(&request.code)
+        .find_by_code_with_group(code)
         .await?
         .ok_or_else(|| ApiError::NotFound("Invite not found".to_string()))?;
 
@@ -783,12 +1050,21 @@ pub async fn join_group(
         ));
     }
 
+    // Email invites can only be accepted by their recipient
+    if invite.recipient_email.is_some() {
+        let user = UserRepository::new(state.pool.clone())
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| ApiError::NotFound("User not found".to_string()))?;
+        if !can_accept_invite(invite.recipient_email.as_deref(), &user.email) {
+            return Err(ApiError::Forbidden(
+                "This invite was sent to a different email address".to_string(),
+            ));
+        }
+    }
+
     // Check if user is already a member
-    if group_repo
-        .get_membership(invite.group_id, user_auth.user_id)
-        .await?
-        .is_some()
-    {
+    if group_repo.is_member(invite.group_id, user_id).await? {
         return Err(ApiError::Conflict(
             "You are already a member of this group".to_string(),
         ));
@@ -799,21 +1075,38 @@ pub async fn join_group(
 
     // Add user as member with preset role
     let membership = group_repo
-        .add_member(invite.group_id, user_auth.user_id, preset_role, None)
+        .add_member(invite.group_id, user_id, preset_role, None)
         .await?;
 
     // Increment invite use count
-    invite_repo.increment_use_count(invite.id).await?;
+    invite_repo.increment_use_count(invite.id, user_id).await?;
 
     info!(
         group_id = %invite.group_id,
-        user_id = %user_auth.user_id,
-        invite_code = %request.code,
+        user_id = %user_id,
+        invite_code = %code,
         role = %preset_role,
         "User joined group via invite"
     );
 
-    Ok(Json(JoinGroupResponse {
+    GroupEventRecorder::new(state.pool.clone())
+        .record(
+            invite.group_id,
+            GroupEventType::MemberJoined,
+            None,
+            Some(user_id),
+            json!({ "user_id": user_id, "role": preset_role }),
+        )
+        .await;
+    notify_member_joined(
+        state.pool.clone(),
+        state.notification_service.clone(),
+        invite.group_id,
+        invite.group_name.clone(),
+        user_id,
+    );
+
+    Ok(JoinGroupResponse {
         group: JoinGroupInfo {
             id: invite.group_id,
             name: invite.group_name,
@@ -824,7 +1117,7 @@ pub async fn join_group(
             role: membership.role.into(),
             joined_at: membership.joined_at,
         },
-    }))
+    })
 }
 
 // =============================================================================
@@ -870,7 +1163,7 @@ pub async fn transfer_ownership(
 
     // Check target user is a member of the group
     let _target_membership = repo
-        .get_membership(group_id, request.new_owner_id)
+        .get_direct_membership(group_id, request.new_owner_id)
         .await?
         .ok_or_else(|| {
             ApiError::Validation("Target user is not a member of this group".to_string())
@@ -889,6 +1182,19 @@ pub async fn transfer_ownership(
         "Group ownership transferred"
     );
 
+    GroupEventRecorder::new(state.pool.clone())
+        .record(
+            group_id,
+            GroupEventType::OwnershipTransferred,
+            None,
+            Some(user_auth.user_id),
+            json!({
+                "previous_owner_id": user_auth.user_id,
+                "new_owner_id": request.new_owner_id,
+            }),
+        )
+        .await;
+
     Ok(Json(TransferOwnershipResponse {
         group_id,
         previous_owner_id: user_auth.user_id,
@@ -1063,35 +1369,120 @@ pub async fn migrate_registration_group(
     let device_ids: Vec<Uuid> = devices.iter().map(|d| d.device_id).collect();
     let devices_count = device_ids.len() as i32;
 
-    // Start transaction for atomic migration
-    let mut tx = state.pool.begin().await.map_err(|e| {
-        error!(error = %e, "Failed to start transaction for migration");
-        record_migration_failure(
-            start_time.elapsed().as_secs_f64(),
-            "transaction_start_error",
-        );
-        ApiError::Internal("Failed to start migration transaction".to_string())
+    // Run the migration atomically, retrying if it loses a serialization
+    // or deadlock race with a concurrent writer
+    let migration = retry_on_conflict("migrate_registration_group", || {
+        run_migration_transaction(
+            &state.pool,
+            user_auth.user_id,
+            &request.registration_group_id,
+            &group_name,
+            &slug,
+            &device_ids,
+        )
+    })
+    .await;
+
+    let (new_group, audit_log) = migration.map_err(|e| {
+        error!(error = %e.source, step = e.step, "Registration group migration failed");
+        record_migration_failure(start_time.elapsed().as_secs_f64(), e.step);
+        ApiError::Internal(e.message.to_string())
     })?;
 
+    // Record successful migration metrics
+    record_migration_success(start_time.elapsed().as_secs_f64(), devices_count);
+
+    info!(
+        migration_id = %audit_log.id,
+        user_id = %user_auth.user_id,
+        registration_group_id = %request.registration_group_id,
+        authenticated_group_id = %new_group.id,
+        devices_migrated = devices_count,
+        "Registration group migrated successfully"
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(MigrateGroupResponse {
+            migration_id: audit_log.id,
+            authenticated_group_id: new_group.id,
+            name: new_group.name,
+            devices_migrated: devices_count,
+            device_ids,
+        }),
+    ))
+}
+
+/// A failed step of the registration group migration transaction.
+struct MigrationStepError {
+    /// Failure label recorded in migration metrics.
+    step: &'static str,
+    /// Message returned to the client.
+    message: &'static str,
+    source: sqlx::Error,
+}
+
+impl MigrationStepError {
+    fn at(step: &'static str, message: &'static str) -> impl FnOnce(sqlx::Error) -> Self {
+        move |source| Self {
+            step,
+            message,
+            source,
+        }
+    }
+}
+
+impl std::fmt::Display for MigrationStepError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.step, self.source)
+    }
+}
+
+impl Retryable for MigrationStepError {
+    fn is_retryable(&self) -> bool {
+        self.source.is_retryable()
+    }
+}
+
+/// Creates the authenticated group, moves the devices and records the
+/// migration audit log in one transaction.
+async fn run_migration_transaction(
+    pool: &sqlx::PgPool,
+    user_id: Uuid,
+    registration_group_id: &str,
+    group_name: &str,
+    slug: &str,
+    device_ids: &[Uuid],
+) -> Result<
+    (
+        persistence::entities::GroupEntity,
+        persistence::entities::MigrationAuditLogEntity,
+    ),
+    MigrationStepError,
+> {
+    let mut tx = pool.begin().await.map_err(MigrationStepError::at(
+        "transaction_start_error",
+        "Failed to start migration transaction",
+    ))?;
+
     // Create the new authenticated group (user becomes owner)
     let new_group = sqlx::query_as::<_, persistence::entities::GroupEntity>(
         r#"
         INSERT INTO groups (name, slug, max_devices, created_by, is_active)
         VALUES ($1, $2, $3, $4, true)
-        RETURNING id, name, slug, description, icon_emoji, max_devices, is_active, settings, created_by, created_at, updated_at
+        RETURNING id, name, slug, description, icon_emoji, max_devices, is_active, settings, created_by, created_at, updated_at, parent_group_id
         "#,
     )
-    .bind(&group_name)
-    .bind(&slug)
+    .bind(group_name)
+    .bind(slug)
     .bind(20) // Default max devices
-    .bind(user_auth.user_id)
+    .bind(user_id)
     .fetch_one(&mut *tx)
     .await
-    .map_err(|e| {
-        error!(error = %e, "Failed to create group during migration");
-        record_migration_failure(start_time.elapsed().as_secs_f64(), "create_group_error");
-        ApiError::Internal("Failed to create authenticated group".to_string())
-    })?;
+    .map_err(MigrationStepError::at(
+            "create_group_error",
+            "Failed to create authenticated group",
+        ))?;
 
     // Add the user as owner of the group
     sqlx::query(
@@ -1100,18 +1491,14 @@ pub async fn migrate_registration_group(
         VALUES ($1, $2, 'owner', NULL, NOW())
         "#,
     )
-    .bind(user_auth.user_id)
+    .bind(user_id)
     .bind(new_group.id)
     .execute(&mut *tx)
     .await
-    .map_err(|e| {
-        error!(error = %e, "Failed to add owner membership during migration");
-        record_migration_failure(
-            start_time.elapsed().as_secs_f64(),
-            "create_membership_error",
-        );
-        ApiError::Internal("Failed to create group membership".to_string())
-    })?;
+    .map_err(MigrationStepError::at(
+        "create_membership_error",
+        "Failed to create group membership",
+    ))?;
 
     // Update all devices to point to the new authenticated group (using slug)
     // Note: devices.group_id is a VARCHAR containing the registration group ID or slug
@@ -1122,15 +1509,14 @@ pub async fn migrate_registration_group(
         WHERE group_id = $2 AND is_active = true
         "#,
     )
-    .bind(&slug)
-    .bind(&request.registration_group_id)
+    .bind(slug)
+    .bind(registration_group_id)
     .execute(&mut *tx)
     .await
-    .map_err(|e| {
-        error!(error = %e, "Failed to update devices during migration");
-        record_migration_failure(start_time.elapsed().as_secs_f64(), "update_devices_error");
-        ApiError::Internal("Failed to migrate devices".to_string())
-    })?;
+    .map_err(MigrationStepError::at(
+        "update_devices_error",
+        "Failed to migrate devices",
+    ))?;
 
     // Create migration audit log
     let audit_log = sqlx::query_as::<_, persistence::entities::MigrationAuditLogEntity>(
@@ -1148,55 +1534,144 @@ pub async fn migrate_registration_group(
         RETURNING id, user_id, registration_group_id, authenticated_group_id, devices_migrated, device_ids, status, error_message, created_at
         "#,
     )
-    .bind(user_auth.user_id)
-    .bind(&request.registration_group_id)
+    .bind(user_id)
+    .bind(registration_group_id)
     .bind(new_group.id)
-    .bind(devices_count)
-    .bind(&device_ids)
+    .bind(device_ids.len() as i32)
+    .bind(device_ids)
     .fetch_one(&mut *tx)
     .await
-    .map_err(|e| {
-        error!(error = %e, "Failed to create migration audit log");
-        record_migration_failure(start_time.elapsed().as_secs_f64(), "audit_log_error");
-        ApiError::Internal("Failed to create migration audit log".to_string())
-    })?;
+    .map_err(MigrationStepError::at(
+            "audit_log_error",
+            "Failed to create migration audit log",
+        ))?;
 
-    // Commit the transaction
-    tx.commit().await.map_err(|e| {
-        error!(error = %e, "Failed to commit migration transaction");
-        record_migration_failure(start_time.elapsed().as_secs_f64(), "commit_error");
-        ApiError::Internal("Failed to complete migration".to_string())
-    })?;
+    tx.commit().await.map_err(MigrationStepError::at(
+        "commit_error",
+        "Failed to complete migration",
+    ))?;
 
-    // Record successful migration metrics
-    record_migration_success(start_time.elapsed().as_secs_f64(), devices_count);
+    Ok((new_group, audit_log))
+}
+
+// =============================================================================
+// Group Merge
+// =============================================================================
+
+/// Request to merge another group into this one.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MergeGroupRequest {
+    /// The group to merge and delete.
+    pub source_group_id: Uuid,
+}
+
+/// Response from a successful group merge.
+#[derive(Debug, Clone, Serialize)]
+pub struct MergeGroupResponse {
+    /// The migration audit log ID recording the merge.
+    pub migration_id: Uuid,
+
+    /// The group that received the source group's data.
+    pub group_id: Uuid,
+
+    /// The merged and deleted group.
+    pub source_group_id: Uuid,
+
+    /// Number of members added to the group.
+    pub members_moved: i64,
+
+    /// Number of devices moved.
+    pub devices_moved: i32,
+
+    /// Number of active geofences on the moved devices.
+    pub geofences_moved: i64,
+
+    /// Number of pending invites moved.
+    pub invites_moved: i64,
+
+    /// IDs of the devices that were moved.
+    pub device_ids: Vec<Uuid>,
+}
+
+/// Merge another group into a group.
+///
+/// POST /api/v1/groups/:group_id/merge
+///
+/// Requires JWT authentication. The user must own both groups. Members,
+/// devices (with their geofences) and pending invites move from the source
+/// group atomically, the source group is deleted and the merge is recorded
+/// in the migration audit log. Members already in the group keep their role.
+pub async fn merge_group(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(group_id): Path<Uuid>,
+    Json(request): Json<MergeGroupRequest>,
+) -> Result<Json<MergeGroupResponse>, ApiError> {
+    if request.source_group_id == group_id {
+        return Err(ApiError::Validation(
+            "Cannot merge a group into itself".to_string(),
+        ));
+    }
+
+    let repo = GroupRepository::new(state.pool.clone());
+
+    for id in [group_id, request.source_group_id] {
+        let membership = repo
+            .get_membership(id, user_auth.user_id)
+            .await?
+            .ok_or_else(|| {
+                ApiError::NotFound("Group not found or you are not a member".to_string())
+            })?;
+        let role: GroupRole = membership.role.into();
+        if !role.can_delete_group() {
+            return Err(ApiError::Forbidden(
+                "Only the owner of both groups can merge them".to_string(),
+            ));
+        }
+    }
+
+    let target = repo
+        .find_by_id(group_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Group not found".to_string()))?;
+    let source = repo
+        .find_by_id(request.source_group_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Group not found".to_string()))?;
+
+    let outcome = repo
+        .merge_group(&source, &target, user_auth.user_id)
+        .await?;
 
     info!(
-        migration_id = %audit_log.id,
+        migration_id = %outcome.audit_log.id,
         user_id = %user_auth.user_id,
-        registration_group_id = %request.registration_group_id,
-        authenticated_group_id = %new_group.id,
-        devices_migrated = devices_count,
-        "Registration group migrated successfully"
+        group_id = %group_id,
+        source_group_id = %source.id,
+        members_moved = outcome.members_moved,
+        devices_moved = outcome.device_ids.len(),
+        invites_moved = outcome.invites_moved,
+        "Group merged"
     );
 
-    Ok((
-        StatusCode::CREATED,
-        Json(MigrateGroupResponse {
-            migration_id: audit_log.id,
-            authenticated_group_id: new_group.id,
-            name: new_group.name,
-            devices_migrated: devices_count,
-            device_ids,
-        }),
-    ))
+    Ok(Json(MergeGroupResponse {
+        migration_id: outcome.audit_log.id,
+        group_id,
+        source_group_id: source.id,
+        members_moved: outcome.members_moved,
+        devices_moved: outcome.audit_log.devices_migrated,
+        geofences_moved: outcome.geofences_moved,
+        invites_moved: outcome.invites_moved,
+        device_ids: outcome.device_ids,
+    }))
 }
 
 /// Get devices in a group.
 ///
 /// GET /api/v1/groups/:group_id/devices
 ///
-/// Requires JWT authentication. User must be a member of the group.
+/// Requires JWT authentication. User must be a member of the group with
+/// the `view_locations` permission.
 /// Returns devices with their last location information.
 pub async fn get_group_devices(
     State(state): State<AppState>,
@@ -1211,28 +1686,71 @@ pub async fn get_group_devices(
         .find_group_with_membership(group_id, user_auth.user_id, None)
         .await?
         .ok_or_else(|| ApiError::NotFound("Group not found or you are not a member".to_string()))?;
+    require_group_permission(
+        &state.pool,
+        group_id,
+        user_auth.user_id,
+        group.role.into(),
+        GroupPermission::ViewLocations,
+    )
+    .await?;
 
     // Fetch devices using the group's slug (group_id column in devices table is the slug)
     let devices = device_repo
         .find_devices_with_last_location(&group.slug)
         .await?;
 
-    // Transform to DeviceSummary
-    let summaries: Vec<DeviceSummary> = devices
-        .into_iter()
-        .map(|d| {
+    // Other members' devices are subject to their owners' privacy zones
+    let summaries = device_summaries(&state, group.id, Some(user_auth.user_id), devices).await?;
+
+    info!(
+        group_id = %group_id,
+        user_id = %user_auth.user_id,
+        device_count = summaries.len(),
+        "Listed group devices"
+    );
+
+    Ok(Json(GroupDevicesResponse { devices: summaries }))
+}
+
+/// Convert group devices to summaries as seen by `viewer`.
+///
+/// Last locations inside a privacy zone of the device owner are hidden or
+/// blurred unless the viewer owns the device; owners outside their sharing
+/// schedule, and in on-demand groups owners without an open sharing window,
+/// have theirs hidden. `None` views every device as someone else's.
+pub(crate) async fn device_summaries(
+    state: &AppState,
+    group_id: Uuid,
+    viewer: Option<Uuid>,
+    devices: Vec<DeviceWithLastLocationEntity>,
+) -> Result<Vec<DeviceSummary>, ApiError> {
+    let privacy_zones = load_group_location_filter(
+        &state.pool,
+        group_id,
+        viewer,
+        devices.iter().map(|d| d.owner_user_id),
+    )
+    .await?;
+
+    Ok(devices
+        .into_iter()
+        .map(|d| {
             let last_location = match (
                 d.last_latitude,
                 d.last_longitude,
                 d.last_location_time,
                 d.last_accuracy,
             ) {
-                (Some(lat), Some(lon), Some(time), Some(acc)) => Some(DeviceLastLocation {
-                    latitude: lat,
-                    longitude: lon,
-                    timestamp: time,
-                    accuracy: acc as f64,
-                }),
+                (Some(lat), Some(lon), Some(time), Some(acc)) => privacy_zones
+                    .share(d.owner_user_id, lat, lon)
+                    .apply(lat, lon, acc as f64)
+                    .map(|(latitude, longitude, accuracy)| DeviceLastLocation {
+                        latitude,
+                        longitude,
+                        timestamp: time,
+                        accuracy,
+                    }),
                 _ => None,
             };
 
@@ -1241,18 +1759,10 @@ pub async fn get_group_devices(
                 display_name: d.display_name,
                 last_location,
                 last_seen_at: d.last_seen_at,
+                sharing_paused: privacy_zones.is_paused(d.owner_user_id),
             }
         })
-        .collect();
-
-    info!(
-        group_id = %group_id,
-        user_id = %user_auth.user_id,
-        device_count = summaries.len(),
-        "Listed group devices"
-    );
-
-    Ok(Json(GroupDevicesResponse { devices: summaries }))
+        .collect())
 }
 
 // =============================================================================
@@ -1334,6 +1844,16 @@ pub async fn add_device_to_group(
         "Device added to group"
     );
 
+    GroupEventRecorder::new(state.pool.clone())
+        .record(
+            group_id,
+            GroupEventType::DeviceAdded,
+            Some(request.device_id),
+            Some(user_auth.user_id),
+            json!({ "display_name": device.display_name }),
+        )
+        .await;
+
     Ok((
         StatusCode::CREATED,
         Json(AddDeviceToGroupResponse {
@@ -1377,8 +1897,14 @@ pub struct GroupDeviceInfo {
     pub owner_display_name: Option<String>,
     pub added_at: DateTime<Utc>,
     pub last_seen_at: Option<DateTime<Utc>>,
+    /// Alias for the device set by a group admin, visible only in this group.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nickname: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_location: Option<DeviceLocationInfo>,
+    /// The owner's sharing schedule is outside its windows.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub sharing_paused: bool,
 }
 
 /// Last location information for a device.
@@ -1403,7 +1929,8 @@ pub struct ListGroupDevicesResponse {
 ///
 /// Requires JWT authentication.
 /// - User must be a member of the group
-/// - Optionally include last location with `include_location=true`
+/// - Optionally include last location with `include_location=true`, which
+///   requires the `view_locations` permission
 /// - Supports pagination
 ///
 /// Story UGM-3.4: List Group Devices
@@ -1417,10 +1944,20 @@ pub async fn list_group_devices(
     let membership_repo = DeviceGroupMembershipRepository::new(state.pool.clone());
 
     // Check user is a member of the group
-    let _membership = group_repo
+    let membership = group_repo
         .get_membership(group_id, user_auth.user_id)
         .await?
         .ok_or_else(|| ApiError::NotFound("Group not found or you are not a member".to_string()))?;
+    if query.include_location {
+        require_group_permission(
+            &state.pool,
+            group_id,
+            user_auth.user_id,
+            membership.role.into(),
+            GroupPermission::ViewLocations,
+        )
+        .await?;
+    }
 
     // Pagination
     let page = query.page.max(1);
@@ -1435,6 +1972,13 @@ pub async fn list_group_devices(
         let device_entities = membership_repo
             .list_devices_in_group_with_location(group_id, per_page, offset)
             .await?;
+        let privacy_zones = load_group_location_filter(
+            &state.pool,
+            group_id,
+            Some(user_auth.user_id),
+            device_entities.iter().map(|d| d.owner_user_id),
+        )
+        .await?;
 
         device_entities
             .into_iter()
@@ -1445,15 +1989,20 @@ pub async fn list_group_devices(
                 owner_display_name: d.owner_display_name,
                 added_at: d.added_at,
                 last_seen_at: d.last_seen_at,
+                nickname: d.nickname,
                 last_location: match (d.latitude, d.longitude, d.accuracy, d.location_timestamp) {
-                    (Some(lat), Some(lon), Some(acc), Some(ts)) => Some(DeviceLocationInfo {
-                        latitude: lat,
-                        longitude: lon,
-                        accuracy: acc,
-                        timestamp: ts,
-                    }),
+                    (Some(lat), Some(lon), Some(acc), Some(ts)) => privacy_zones
+                        .share(d.owner_user_id, lat, lon)
+                        .apply(lat, lon, acc as f64)
+                        .map(|(latitude, longitude, accuracy)| DeviceLocationInfo {
+                            latitude,
+                            longitude,
+                            accuracy: accuracy as f32,
+                            timestamp: ts,
+                        }),
                     _ => None,
                 },
+                sharing_paused: privacy_zones.is_paused(d.owner_user_id),
             })
             .collect()
     } else {
@@ -1470,7 +2019,9 @@ pub async fn list_group_devices(
                 owner_display_name: d.owner_display_name,
                 added_at: d.added_at,
                 last_seen_at: d.last_seen_at,
+                nickname: d.nickname,
                 last_location: None,
+                sharing_paused: false,
             })
             .collect()
     };
@@ -1497,13 +2048,187 @@ pub async fn list_group_devices(
     }))
 }
 
+/// Default search radius for nearby devices (meters).
+const DEFAULT_NEARBY_RADIUS_METERS: f64 = 1_000.0;
+
+/// Maximum search radius for nearby devices (meters).
+const MAX_NEARBY_RADIUS_METERS: f64 = 100_000.0;
+
+fn default_nearby_radius() -> f64 {
+    DEFAULT_NEARBY_RADIUS_METERS
+}
+
+fn default_nearby_limit() -> i64 {
+    50
+}
+
+/// Query parameters for finding nearby group devices.
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct NearbyDevicesQuery {
+    /// Latitude of the search point
+    #[validate(range(min = -90.0, max = 90.0, message = "Latitude must be between -90 and 90"))]
+    pub lat: f64,
+
+    /// Longitude of the search point
+    #[validate(range(
+        min = -180.0,
+        max = 180.0,
+        message = "Longitude must be between -180 and 180"
+    ))]
+    pub lon: f64,
+
+    /// Search radius in meters
+    #[serde(default = "default_nearby_radius")]
+    #[validate(range(
+        min = 1.0,
+        max = MAX_NEARBY_RADIUS_METERS,
+        message = "Radius must be between 1 and 100000 meters"
+    ))]
+    pub radius: f64,
+
+    /// Maximum number of devices to return (1-100)
+    #[serde(default = "default_nearby_limit")]
+    #[validate(range(min = 1, max = 100, message = "Limit must be between 1 and 100"))]
+    pub limit: i64,
+}
+
+/// A group device near the search point.
+#[derive(Debug, Clone, Serialize)]
+pub struct NearbyDeviceInfo {
+    pub device_id: Uuid,
+    pub display_name: String,
+    pub owner_user_id: Option<Uuid>,
+    pub owner_display_name: Option<String>,
+    pub last_seen_at: Option<DateTime<Utc>>,
+    pub distance_meters: f64,
+    pub last_location: DeviceLocationInfo,
+}
+
+impl From<NearbyDeviceInGroupEntity> for NearbyDeviceInfo {
+    fn from(d: NearbyDeviceInGroupEntity) -> Self {
+        Self {
+            device_id: d.device_id,
+            display_name: d.display_name,
+            owner_user_id: d.owner_user_id,
+            owner_display_name: d.owner_display_name,
+            last_seen_at: d.last_seen_at,
+            distance_meters: (d.distance_meters * 10.0).round() / 10.0,
+            last_location: DeviceLocationInfo {
+                latitude: d.latitude,
+                longitude: d.longitude,
+                accuracy: d.accuracy,
+                timestamp: d.location_timestamp,
+            },
+        }
+    }
+}
+
+/// Response for nearby group devices.
+#[derive(Debug, Clone, Serialize)]
+pub struct NearbyDevicesResponse {
+    pub data: Vec<NearbyDeviceInfo>,
+    pub radius_meters: f64,
+}
+
+/// Find group devices near a point.
+///
+/// GET /api/v1/groups/:group_id/devices/nearby?lat=&lon=&radius=
+///
+/// Requires JWT authentication.
+/// - User must be a member of the group with the `view_locations` permission
+/// - Uses each device's most recent location
+/// - Results are ordered by distance, nearest first
+pub async fn list_nearby_group_devices(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(group_id): Path<Uuid>,
+    Query(query): Query<NearbyDevicesQuery>,
+) -> Result<Json<NearbyDevicesResponse>, ApiError> {
+    query.validate().map_err(|e| {
+        let errors: Vec<String> = e
+            .field_errors()
+            .iter()
+            .flat_map(|(field, errors)| {
+                errors.iter().map(move |err| {
+                    format!("{}: {}", field, err.message.as_ref().unwrap_or(&"".into()))
+                })
+            })
+            .collect();
+        ApiError::Validation(errors.join(", "))
+    })?;
+
+    let group_repo = GroupRepository::new(state.pool.clone());
+    let membership_repo = DeviceGroupMembershipRepository::new(state.pool.clone());
+
+    // Check user is a member of the group
+    let membership = group_repo
+        .get_membership(group_id, user_auth.user_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Group not found or you are not a member".to_string()))?;
+    require_group_permission(
+        &state.pool,
+        group_id,
+        user_auth.user_id,
+        membership.role.into(),
+        GroupPermission::ViewLocations,
+    )
+    .await?;
+
+    let nearby = membership_repo
+        .find_nearby_devices_in_group(group_id, query.lat, query.lon, query.radius, query.limit)
+        .await?;
+    let privacy_zones = load_group_location_filter(
+        &state.pool,
+        group_id,
+        Some(user_auth.user_id),
+        nearby.iter().map(|d| d.owner_user_id),
+    )
+    .await?;
+
+    // Devices inside a privacy zone are dropped (suppress) or re-measured from
+    // the zone center (snap), so distances never reveal the exact position.
+    let mut devices: Vec<NearbyDeviceInfo> = nearby
+        .into_iter()
+        .filter_map(|d| {
+            let shared = privacy_zones.share(d.owner_user_id, d.latitude, d.longitude);
+            let (latitude, longitude, accuracy) =
+                shared.apply(d.latitude, d.longitude, d.accuracy as f64)?;
+            let mut info = NearbyDeviceInfo::from(d);
+            if shared != SharedLocation::Exact {
+                info.distance_meters =
+                    (distance_meters(query.lat, query.lon, latitude, longitude) * 10.0).round()
+                        / 10.0;
+                info.last_location.latitude = latitude;
+                info.last_location.longitude = longitude;
+                info.last_location.accuracy = accuracy as f32;
+            }
+            (info.distance_meters <= query.radius).then_some(info)
+        })
+        .collect();
+    devices.sort_by(|a, b| a.distance_meters.total_cmp(&b.distance_meters));
+
+    info!(
+        group_id = %group_id,
+        user_id = %user_auth.user_id,
+        radius_meters = query.radius,
+        device_count = devices.len(),
+        "Listed nearby group devices"
+    );
+
+    Ok(Json(NearbyDevicesResponse {
+        data: devices,
+        radius_meters: query.radius,
+    }))
+}
+
 /// Remove a device from an authenticated group.
 ///
 /// DELETE /api/v1/groups/:group_id/devices/:device_id
 ///
 /// Requires JWT authentication.
 /// - Device owner can remove their own device
-/// - Group admin/owner can remove any device
+/// - Members with the `manage_devices` permission (owners and admins always
+///   have it) can remove any device
 ///
 /// Story UGM-3.3: Remove Device from Group
 pub async fn remove_device_from_group(
@@ -1539,14 +2264,17 @@ pub async fn remove_device_from_group(
         ));
     }
 
-    // Authorization: device owner or group admin/owner
+    // Authorization: device owner or a member with the manage_devices permission
     let is_device_owner = device.owner_user_id == Some(user_auth.user_id);
-    let is_group_admin = user_role.can_manage_members();
-
-    if !is_device_owner && !is_group_admin {
-        return Err(ApiError::Forbidden(
-            "You can only remove your own devices or must be a group admin".to_string(),
-        ));
+    if !is_device_owner {
+        require_group_permission(
+            &state.pool,
+            group_id,
+            user_auth.user_id,
+            user_role,
+            GroupPermission::ManageDevices,
+        )
+        .await?;
     }
 
     // Remove the device from the group
@@ -1565,10 +2293,19 @@ pub async fn remove_device_from_group(
         device_id = %device_id,
         user_id = %user_auth.user_id,
         is_device_owner = is_device_owner,
-        is_group_admin = is_group_admin,
         "Device removed from group"
     );
 
+    GroupEventRecorder::new(state.pool.clone())
+        .record(
+            group_id,
+            GroupEventType::DeviceRemoved,
+            Some(device_id),
+            Some(user_auth.user_id),
+            json!({}),
+        )
+        .await;
+
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -1677,6 +2414,319 @@ pub async fn list_device_groups(
     }))
 }
 
+// =============================================================================
+// Group Event Archive
+// =============================================================================
+
+/// Replay a group's archived events.
+///
+/// GET /api/v1/groups/:group_id/events?since=&cursor=&limit=
+///
+/// Requires JWT authentication.
+/// - User must be a member of the group
+/// - Events are returned oldest first; follow `pagination.next_cursor`
+///   to page forward
+/// - Only events within the configured retention window are available
+pub async fn list_group_events(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(group_id): Path<Uuid>,
+    Query(query): Query<ListGroupEventsQuery>,
+) -> Result<Json<ListGroupEventsResponse>, ApiError> {
+    query.validate().map_err(|e| {
+        let errors: Vec<String> = e
+            .field_errors()
+            .iter()
+            .flat_map(|(field, errors)| {
+                errors.iter().map(move |err| {
+                    format!("{}: {}", field, err.message.as_ref().unwrap_or(&"".into()))
+                })
+            })
+            .collect();
+        ApiError::Validation(errors.join(", "))
+    })?;
+
+    let cursor = query
+        .cursor
+        .as_deref()
+        .map(shared::pagination::decode_cursor)
+        .transpose()
+        .map_err(|_| ApiError::Validation("Invalid cursor format".to_string()))?;
+
+    let group_repo = GroupRepository::new(state.pool.clone());
+    let event_repo = GroupEventRepository::new(state.pool.clone());
+
+    // Check user is a member of the group
+    let membership = group_repo
+        .get_membership(group_id, user_auth.user_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Group not found or you are not a member".to_string()))?;
+    let can_view_locations = can_view_group_locations(
+        &state.pool,
+        group_id,
+        user_auth.user_id,
+        membership.role.into(),
+    )
+    .await?;
+
+    let (entities, has_more) = event_repo
+        .list_by_group(group_id, query.since, cursor, query.limit)
+        .await?;
+
+    let next_cursor = if has_more {
+        entities
+            .last()
+            .map(|e| shared::pagination::encode_cursor(e.occurred_at, e.id))
+    } else {
+        None
+    };
+
+    // Archived geofence events carry the device's position at the time
+    let locations = load_group_location_filter(
+        &state.pool,
+        group_id,
+        Some(user_auth.user_id),
+        entities.iter().map(|e| e.device_owner_id),
+    )
+    .await?;
+    let events: Vec<GroupEvent> = entities
+        .into_iter()
+        .map(|entity| {
+            let owner = entity.device_owner_id;
+            let mut event = GroupEvent::from(entity);
+            filter_event_location(&mut event.payload, owner, &locations, can_view_locations);
+            event
+        })
+        .collect();
+
+    info!(
+        group_id = %group_id,
+        user_id = %user_auth.user_id,
+        count = events.len(),
+        has_more = has_more,
+        "Listed group events"
+    );
+
+    Ok(Json(ListGroupEventsResponse {
+        events,
+        pagination: PaginationInfo {
+            next_cursor,
+            has_more,
+        },
+    }))
+}
+
+// =============================================================================
+// Location Retention
+// =============================================================================
+
+/// Get a group's location retention policy.
+///
+/// GET /api/v1/groups/:group_id/retention
+///
+/// Requires JWT authentication.
+/// - User must be a member of the group
+pub async fn get_group_retention(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(group_id): Path<Uuid>,
+) -> Result<Json<GroupRetentionPolicy>, ApiError> {
+    let repo = GroupRepository::new(state.pool.clone());
+
+    let _membership = repo
+        .get_membership(group_id, user_auth.user_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Group not found or you are not a member".to_string()))?;
+
+    let retention_days = repo
+        .get_location_retention_days(group_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Group not found".to_string()))?;
+
+    Ok(Json(GroupRetentionPolicy::new(
+        group_id,
+        retention_days.map(|d| d as u32),
+        state.config.limits.location_retention_days,
+        state.config.limits.max_group_location_retention_days,
+    )))
+}
+
+/// Set or clear a group's location retention override.
+///
+/// PUT /api/v1/groups/:group_id/retention
+///
+/// Requires JWT authentication.
+/// - Only the group owner can change retention
+/// - Value must not exceed `limits.max_group_location_retention_days`
+/// - `null` restores the system default
+pub async fn update_group_retention(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(group_id): Path<Uuid>,
+    Json(request): Json<UpdateGroupRetentionRequest>,
+) -> Result<Json<GroupRetentionPolicy>, ApiError> {
+    request.validate().map_err(|e| {
+        let errors: Vec<String> = e
+            .field_errors()
+            .iter()
+            .flat_map(|(field, errors)| {
+                errors.iter().map(move |err| {
+                    format!("{}: {}", field, err.message.as_ref().unwrap_or(&"".into()))
+                })
+            })
+            .collect();
+        ApiError::Validation(errors.join(", "))
+    })?;
+
+    let max_days = state.config.limits.max_group_location_retention_days;
+    if let Some(days) = request.location_retention_days {
+        if days > max_days {
+            return Err(ApiError::Validation(format!(
+                "location_retention_days: Location retention must be at most {} days",
+                max_days
+            )));
+        }
+    }
+
+    let repo = GroupRepository::new(state.pool.clone());
+
+    let membership = repo
+        .get_membership(group_id, user_auth.user_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Group not found or you are not a member".to_string()))?;
+
+    let membership_role: GroupRole = membership.role.into();
+    if !membership_role.can_manage_retention() {
+        return Err(ApiError::Forbidden(
+            "Only the group owner can change location retention".to_string(),
+        ));
+    }
+
+    let updated = repo
+        .set_location_retention_days(group_id, request.location_retention_days.map(|d| d as i32))
+        .await?;
+    if !updated {
+        return Err(ApiError::NotFound("Group not found".to_string()));
+    }
+
+    info!(
+        group_id = %group_id,
+        user_id = %user_auth.user_id,
+        location_retention_days = ?request.location_retention_days,
+        "Group location retention updated"
+    );
+
+    Ok(Json(GroupRetentionPolicy::new(
+        group_id,
+        request.location_retention_days,
+        state.config.limits.location_retention_days,
+        max_days,
+    )))
+}
+
+/// Get a group's settings.
+///
+/// GET /api/v1/groups/:group_id/settings
+///
+/// Requires JWT authentication.
+/// - User must be a member of the group
+pub async fn get_group_settings(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(group_id): Path<Uuid>,
+) -> Result<Json<GroupSettingsResponse>, ApiError> {
+    let repo = GroupRepository::new(state.pool.clone());
+
+    let _membership = repo
+        .get_membership(group_id, user_auth.user_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Group not found or you are not a member".to_string()))?;
+
+    let stored = repo
+        .get_settings(group_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Group not found".to_string()))?;
+
+    Ok(Json(GroupSettingsResponse {
+        group_id,
+        settings: GroupSettings::from_stored(&stored),
+    }))
+}
+
+/// Update a group's settings.
+///
+/// PUT /api/v1/groups/:group_id/settings
+///
+/// Requires JWT authentication.
+/// - Only admins and owners can update settings; members with the
+///   `manage_geofences` permission can update `geofence_notifications`
+/// - Omitted fields keep their current value
+pub async fn update_group_settings(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(group_id): Path<Uuid>,
+    Json(request): Json<UpdateGroupSettingsRequest>,
+) -> Result<Json<GroupSettingsResponse>, ApiError> {
+    request.validate().map_err(|e| {
+        let errors: Vec<String> = e
+            .field_errors()
+            .iter()
+            .flat_map(|(field, errors)| {
+                errors.iter().map(move |err| {
+                    format!("{}: {}", field, err.message.as_ref().unwrap_or(&"".into()))
+                })
+            })
+            .collect();
+        ApiError::Validation(errors.join(", "))
+    })?;
+
+    let repo = GroupRepository::new(state.pool.clone());
+
+    let membership = repo
+        .get_membership(group_id, user_auth.user_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Group not found or you are not a member".to_string()))?;
+
+    let membership_role: GroupRole = membership.role.into();
+    if !membership_role.can_manage_group() {
+        if !request.changes_only_geofence_notifications() {
+            return Err(ApiError::Forbidden(
+                "Only group admins and owners can update group settings".to_string(),
+            ));
+        }
+        require_group_permission(
+            &state.pool,
+            group_id,
+            user_auth.user_id,
+            membership_role,
+            GroupPermission::ManageGeofences,
+        )
+        .await?;
+    }
+
+    let stored = repo
+        .get_settings(group_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Group not found".to_string()))?;
+    let settings = request.apply(GroupSettings::from_stored(&stored));
+
+    let stored = repo
+        .merge_settings(group_id, &settings.to_stored())
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Group not found".to_string()))?;
+
+    info!(
+        group_id = %group_id,
+        user_id = %user_auth.user_id,
+        "Group settings updated"
+    );
+
+    Ok(Json(GroupSettingsResponse {
+        group_id,
+        settings: GroupSettings::from_stored(&stored),
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1709,4 +2759,74 @@ mod tests {
         assert!(!GroupRole::Viewer.can_manage_group());
         assert!(!GroupRole::Viewer.can_manage_members());
     }
+
+    #[test]
+    fn test_filter_event_location() {
+        let viewer = Uuid::new_v4();
+        let owner = Uuid::new_v4();
+        let paused_owner = Uuid::new_v4();
+        let mut locations = PrivacyZoneSet::new(Some(viewer), Vec::new());
+        locations.pause_owners([paused_owner]);
+        let event = json!({ "geofence_name": "Home", "latitude": 48.1486, "longitude": 17.1077 });
+
+        let mut visible = event.clone();
+        filter_event_location(&mut visible, Some(owner), &locations, true);
+        assert_eq!(visible, event);
+
+        let mut paused = event.clone();
+        filter_event_location(&mut paused, Some(paused_owner), &locations, true);
+        assert_eq!(paused, json!({ "geofence_name": "Home" }));
+
+        let mut without_permission = event.clone();
+        filter_event_location(&mut without_permission, Some(owner), &locations, false);
+        assert_eq!(without_permission, json!({ "geofence_name": "Home" }));
+    }
+
+    #[test]
+    fn test_nearby_devices_query_defaults() {
+        let query: NearbyDevicesQuery =
+            serde_json::from_str(r#"{"lat": 48.1486, "lon": 17.1077}"#).unwrap();
+        assert_eq!(query.radius, DEFAULT_NEARBY_RADIUS_METERS);
+        assert_eq!(query.limit, 50);
+        assert!(query.validate().is_ok());
+    }
+
+    #[test]
+    fn test_nearby_devices_query_validation() {
+        let query = NearbyDevicesQuery {
+            lat: 91.0,
+            lon: 0.0,
+            radius: 500.0,
+            limit: 10,
+        };
+        assert!(query.validate().is_err());
+
+        let query = NearbyDevicesQuery {
+            lat: 0.0,
+            lon: 0.0,
+            radius: MAX_NEARBY_RADIUS_METERS + 1.0,
+            limit: 10,
+        };
+        assert!(query.validate().is_err());
+    }
+
+    #[test]
+    fn test_nearby_device_info_from_entity() {
+        let entity = NearbyDeviceInGroupEntity {
+            device_id: Uuid::new_v4(),
+            display_name: "Phone".to_string(),
+            last_seen_at: None,
+            owner_user_id: None,
+            owner_display_name: None,
+            latitude: 48.1486,
+            longitude: 17.1077,
+            accuracy: 5.0,
+            location_timestamp: Utc::now(),
+            distance_meters: 123.456,
+        };
+
+        let info = NearbyDeviceInfo::from(entity);
+        assert_eq!(info.distance_meters, 123.5);
+        assert_eq!(info.last_location.latitude, 48.1486);
+    }
 }
@@ -5,6 +5,7 @@ use serde::Serialize;
 
 use crate::app::AppState;
 use crate::middleware::version_check::MIN_COMPATIBLE_VERSION;
+use crate::preflight::DependencyCheck;
 
 /// Health check response.
 #[derive(Debug, Serialize)]
@@ -17,6 +18,9 @@ pub struct HealthResponse {
     pub database: DatabaseHealth,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub external_services: Option<ExternalServicesHealth>,
+    /// Startup preflight results; degraded dependencies have features disabled.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<DependencyCheck>,
 }
 
 /// Database health status.
@@ -55,7 +59,8 @@ pub struct StatusResponse {
 /// Full health check endpoint.
 ///
 /// Returns detailed health information including database connectivity
-/// and external service status.
+/// and external service status. Reports `degraded` when a dependency failed
+/// its startup preflight check.
 pub async fn health_check(
     State(state): State<AppState>,
 ) -> Result<Json<HealthResponse>, StatusCode> {
@@ -66,7 +71,7 @@ pub async fn health_check(
     // Check map-matching service status using the shared client
     let map_matching_config = &state.config.map_matching;
     let map_matching_enabled = map_matching_config.enabled;
-    let map_matching_configured = !map_matching_config.url.is_empty();
+    let map_matching_configured = map_matching_config.is_configured();
 
     // Get actual circuit breaker state from the shared client
     let (map_matching_available, circuit_state) =
@@ -101,8 +106,16 @@ pub async fn health_check(
         },
     });
 
+    let status = if !db_connected {
+        "unhealthy"
+    } else if state.preflight.is_any_degraded() {
+        "degraded"
+    } else {
+        "healthy"
+    };
+
     let response = HealthResponse {
-        status: if db_connected { "healthy" } else { "unhealthy" }.to_string(),
+        status: status.to_string(),
         version: env!("CARGO_PKG_VERSION").to_string(),
         min_client_version: MIN_COMPATIBLE_VERSION.to_string(),
         database: DatabaseHealth {
@@ -110,6 +123,7 @@ pub async fn health_check(
             latency_ms: if db_connected { Some(latency_ms) } else { None },
         },
         external_services,
+        dependencies: state.preflight.checks.clone(),
     };
 
     if db_connected {
@@ -158,6 +172,7 @@ mod tests {
                 latency_ms: Some(5),
             },
             external_services: None,
+            dependencies: vec![],
         };
         assert_eq!(response.status, "healthy");
         assert_eq!(response.version, "0.9.0");
@@ -177,6 +192,7 @@ mod tests {
                 latency_ms: None,
             },
             external_services: None,
+            dependencies: vec![],
         };
         assert_eq!(response.status, "unhealthy");
         assert!(!response.database.connected);
@@ -200,6 +216,7 @@ mod tests {
                     circuit_state: "closed".to_string(),
                 },
             }),
+            dependencies: vec![],
         };
         assert!(response.external_services.is_some());
         let services = response.external_services.unwrap();
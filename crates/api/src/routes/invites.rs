@@ -1,31 +1,39 @@
 //! Invite routes for managing group invitations.
 
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
 use chrono::{Duration, Utc};
 use domain::models::group::GroupRole;
 use domain::models::invite::{
-    generate_invite_code, CreateInviteRequest, CreateInviteResponse, CreatorInfo, InviteSummary,
-    ListInvitesResponse, PublicGroupInfo, PublicInviteInfo,
+    generate_invite_code, normalize_invite_email, CreateEmailInviteRequest, CreateInviteRequest,
+    CreateInviteResponse, CreatorInfo, GroupInviteStatus, InviteQrQuery, InviteSummary,
+    ListInvitesResponse, PublicGroupInfo, PublicInviteInfo, DEFAULT_EMAIL_INVITE_EXPIRY_HOURS,
+    DEFAULT_INVITE_QR_SIZE,
 };
+use domain::models::GroupPermission;
 use persistence::entities::GroupRoleDb;
-use persistence::repositories::{GroupRepository, InviteRepository};
-use tracing::info;
+use persistence::repositories::{GroupRepository, InviteRepository, UserRepository};
+use tracing::{info, warn};
 use uuid::Uuid;
 use validator::Validate;
 
 use crate::app::AppState;
 use crate::error::ApiError;
 use crate::extractors::UserAuth;
+use crate::middleware::rbac::require_group_permission;
+use crate::services::qr_code::render_qr_code;
+use crate::services::EmailService;
 
 /// Create a new invite for a group.
 ///
 /// POST /api/v1/groups/:group_id/invites
 ///
-/// Requires JWT authentication. Only admins and owners can create invites.
+/// Requires JWT authentication and the `invite_members` permission, which
+/// owners and admins always hold. Only owners and admins can invite admins.
 pub async fn create_invite(
     State(state): State<AppState>,
     user_auth: UserAuth,
@@ -56,11 +64,14 @@ pub async fn create_invite(
         .ok_or_else(|| ApiError::NotFound("Group not found or you are not a member".to_string()))?;
 
     let role: GroupRole = membership.role.into();
-    if !role.can_manage_members() {
-        return Err(ApiError::Forbidden(
-            "Only admins and owners can create invites".to_string(),
-        ));
-    }
+    require_group_permission(
+        &state.pool,
+        group_id,
+        user_auth.user_id,
+        role,
+        GroupPermission::InviteMembers,
+    )
+    .await?;
 
     // Check preset role is not owner
     let preset_role = request.preset_role.unwrap_or(GroupRole::Member);
@@ -69,6 +80,11 @@ pub async fn create_invite(
             "Cannot create invite with owner role".to_string(),
         ));
     }
+    if preset_role == GroupRole::Admin && !role.can_manage_members() {
+        return Err(ApiError::Forbidden(
+            "Only admins and owners can invite admins".to_string(),
+        ));
+    }
 
     // Generate unique code
     let code = invite_repo
@@ -91,6 +107,7 @@ pub async fn create_invite(
             max_uses,
             expires_at,
             user_auth.user_id,
+            None,
         )
         .await?;
 
@@ -118,6 +135,167 @@ pub async fn create_invite(
             created_by: invite.created_by,
             created_at: invite.created_at,
             invite_url,
+            recipient_email: None,
+            email_sent: None,
+        }),
+    ))
+}
+
+/// Invite someone to a group by email.
+///
+/// POST /api/v1/groups/:group_id/invites/email
+///
+/// Requires JWT authentication and the `invite_members` permission, which
+/// owners and admins always hold. Only owners and admins can invite admins.
+/// The invite is single-use and can only be accepted by a user with the
+/// invited email address, either by joining with its code or by passing the
+/// code when registering or logging in.
+pub async fn create_email_invite(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(group_id): Path<Uuid>,
+    Json(request): Json<CreateEmailInviteRequest>,
+) -> Result<(StatusCode, Json<CreateInviteResponse>), ApiError> {
+    request.validate().map_err(|e| {
+        let errors: Vec<String> = e
+            .field_errors()
+            .iter()
+            .flat_map(|(field, errors)| {
+                errors.iter().map(move |err| {
+                    format!("{}: {}", field, err.message.as_ref().unwrap_or(&"".into()))
+                })
+            })
+            .collect();
+        ApiError::Validation(errors.join(", "))
+    })?;
+
+    let group_repo = GroupRepository::new(state.pool.clone());
+    let invite_repo = InviteRepository::new(state.pool.clone());
+    let user_repo = UserRepository::new(state.pool.clone());
+
+    let membership = group_repo
+        .get_membership(group_id, user_auth.user_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Group not found or you are not a member".to_string()))?;
+
+    let role: GroupRole = membership.role.into();
+    require_group_permission(
+        &state.pool,
+        group_id,
+        user_auth.user_id,
+        role,
+        GroupPermission::InviteMembers,
+    )
+    .await?;
+
+    let preset_role = request.preset_role.unwrap_or(GroupRole::Member);
+    if preset_role == GroupRole::Owner {
+        return Err(ApiError::Validation(
+            "Cannot create invite with owner role".to_string(),
+        ));
+    }
+    if preset_role == GroupRole::Admin && !role.can_manage_members() {
+        return Err(ApiError::Forbidden(
+            "Only admins and owners can invite admins".to_string(),
+        ));
+    }
+
+    let email = normalize_invite_email(&request.email);
+    if let Some(user) = user_repo.find_by_email(&email).await? {
+        if group_repo.is_member(group_id, user.id).await? {
+            return Err(ApiError::Conflict(
+                "User is already a member of this group".to_string(),
+            ));
+        }
+    }
+    if invite_repo
+        .has_pending_email_invite(group_id, &email)
+        .await?
+    {
+        return Err(ApiError::Conflict(
+            "A pending invitation already exists for this email".to_string(),
+        ));
+    }
+
+    let group = group_repo
+        .find_by_id(group_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Group not found".to_string()))?;
+
+    let code = invite_repo
+        .generate_unique_code(generate_invite_code)
+        .await?;
+    let expires_in_hours = request
+        .expires_in_hours
+        .unwrap_or(DEFAULT_EMAIL_INVITE_EXPIRY_HOURS);
+    let expires_at = Utc::now() + Duration::hours(expires_in_hours as i64);
+    let preset_role_db: GroupRoleDb = preset_role.into();
+
+    let invite = invite_repo
+        .create_invite(
+            group_id,
+            &code,
+            preset_role_db,
+            1,
+            expires_at,
+            user_auth.user_id,
+            Some(&email),
+        )
+        .await?;
+
+    let invite_url = format!("{}/join/{}", state.config.server.app_base_url, code);
+
+    // The invite stays valid if sending fails; the inviter can share the link
+    let inviter_name = user_repo
+        .find_by_id(user_auth.user_id)
+        .await?
+        .and_then(|user| user.display_name);
+    let email_service = EmailService::new(state.config.email.clone());
+    let email_sent = match email_service
+        .send_group_invite_email(
+            &email,
+            inviter_name.as_deref(),
+            &group.name,
+            &invite_url,
+            expires_in_hours,
+        )
+        .await
+    {
+        Ok(()) => email_service.is_enabled(),
+        Err(e) => {
+            warn!(
+                group_id = %group_id,
+                invite_id = %invite.id,
+                error = %e,
+                "Failed to send group invite email"
+            );
+            false
+        }
+    };
+
+    info!(
+        group_id = %group_id,
+        invite_id = %invite.id,
+        user_id = %user_auth.user_id,
+        email_sent,
+        "Email invite created"
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreateInviteResponse {
+            id: invite.id,
+            group_id: invite.group_id,
+            code: invite.code,
+            preset_role: invite.preset_role.into(),
+            max_uses: invite.max_uses,
+            current_uses: invite.current_uses,
+            expires_at: invite.expires_at,
+            created_by: invite.created_by,
+            created_at: invite.created_at,
+            invite_url,
+            recipient_email: invite.recipient_email,
+            email_sent: Some(email_sent),
         }),
     ))
 }
@@ -165,6 +343,8 @@ pub async fn list_invites(
                 display_name: i.creator_display_name,
             },
             created_at: i.created_at,
+            status: GroupInviteStatus::from_stored(&i.status, i.expires_at),
+            recipient_email: i.recipient_email,
         })
         .collect();
 
@@ -233,6 +413,69 @@ pub async fn revoke_invite(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Get a QR code for an invite.
+///
+/// GET /api/v1/groups/:group_id/invites/:code/qr?format=png|svg&size=256
+///
+/// Requires JWT authentication and the `invite_members` permission. The QR
+/// code encodes the invite's join deep link. Only pending invites can be
+/// rendered.
+pub async fn get_invite_qr(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path((group_id, code)): Path<(Uuid, String)>,
+    Query(query): Query<InviteQrQuery>,
+) -> Result<Response, ApiError> {
+    query
+        .validate()
+        .map_err(|e| ApiError::Validation(e.to_string()))?;
+
+    let group_repo = GroupRepository::new(state.pool.clone());
+    let membership = group_repo
+        .get_membership(group_id, user_auth.user_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Group not found or you are not a member".to_string()))?;
+    require_group_permission(
+        &state.pool,
+        group_id,
+        user_auth.user_id,
+        membership.role.into(),
+        GroupPermission::InviteMembers,
+    )
+    .await?;
+
+    let invite = InviteRepository::new(state.pool.clone())
+        .find_by_code(&code)
+        .await?
+        .filter(|i| i.group_id == group_id)
+        .ok_or_else(|| ApiError::NotFound("Invite not found".to_string()))?;
+
+    if GroupInviteStatus::from_stored(&invite.status, invite.expires_at)
+        != GroupInviteStatus::Pending
+    {
+        return Err(ApiError::Conflict(
+            "Cannot generate QR code for an invite that is no longer pending".to_string(),
+        ));
+    }
+
+    let invite_url = format!("{}/join/{}", state.config.server.app_base_url, invite.code);
+    let image = render_qr_code(
+        &invite_url,
+        query.format,
+        query.size.unwrap_or(DEFAULT_INVITE_QR_SIZE),
+    )
+    .map_err(|e| ApiError::Internal(format!("Failed to generate QR code: {}", e)))?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, query.format.content_type()),
+            (header::CACHE_CONTROL, "no-store"),
+        ],
+        image,
+    )
+        .into_response())
+}
+
 /// Get invite info by code (public, no auth required).
 ///
 /// GET /api/v1/invites/:code
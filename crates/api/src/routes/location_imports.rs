@@ -0,0 +1,119 @@
+//! Location history import endpoint handlers.
+//!
+//! Device owners upload a Google Takeout `Records.json` export; a background
+//! job backfills the device's location history from it.
+
+use std::path::PathBuf;
+
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use domain::models::{LocationImportJob, GOOGLE_TAKEOUT_SOURCE};
+use persistence::repositories::{DeviceRepository, LocationImportJobRepository};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::error::ApiError;
+use crate::extractors::UserAuth;
+use crate::services::location_import::remove_upload;
+use crate::services::LocationImportService;
+
+/// Check that the authenticated user owns the device.
+async fn require_device_owner(
+    state: &AppState,
+    device_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), ApiError> {
+    let device = DeviceRepository::new(state.pool.clone())
+        .find_by_device_id(device_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Device not found".to_string()))?;
+
+    if device.owner_user_id != Some(user_id) {
+        return Err(ApiError::Forbidden(
+            "Only the device owner can import location history".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Upload a Google Takeout export for a device.
+///
+/// POST /api/v1/devices/:device_id/location-imports
+///
+/// The body is the `Records.json` file. Returns 202 with the pending job.
+pub async fn create_location_import(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(device_id): Path<Uuid>,
+    body: Bytes,
+) -> Result<(StatusCode, Json<LocationImportJob>), ApiError> {
+    require_device_owner(&state, device_id, user_auth.user_id).await?;
+
+    // Reject malformed uploads now rather than in the background job
+    let upload = body.clone();
+    tokio::task::spawn_blocking(move || {
+        serde_json::from_slice::<serde::de::IgnoredAny>(&upload).map(|_| ())
+    })
+    .await
+    .map_err(|e| ApiError::Internal(e.to_string()))?
+    .map_err(|e| ApiError::Validation(format!("Upload is not valid JSON: {}", e)))?;
+
+    let service = LocationImportService::new(
+        state.pool.clone(),
+        PathBuf::from(&state.config.location_imports.imports_dir),
+    );
+    let job_id = Uuid::new_v4();
+    let path = service.store_upload(job_id, &body).await.map_err(|e| {
+        ApiError::Internal(format!("Failed to store location import upload: {}", e))
+    })?;
+
+    let job = match LocationImportJobRepository::new(state.pool.clone())
+        .create(
+            job_id,
+            device_id,
+            user_auth.user_id,
+            GOOGLE_TAKEOUT_SOURCE,
+            &path.to_string_lossy(),
+        )
+        .await
+    {
+        Ok(job) => job,
+        Err(e) => {
+            remove_upload(&path).await;
+            return Err(e.into());
+        }
+    };
+
+    info!(
+        job_id = %job.id,
+        device_id = %device_id,
+        user_id = %user_auth.user_id,
+        size_bytes = body.len(),
+        "Location import queued"
+    );
+
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
+
+/// Get the status of a location import.
+///
+/// GET /api/v1/devices/:device_id/location-imports/:job_id
+pub async fn get_location_import(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path((device_id, job_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<LocationImportJob>, ApiError> {
+    require_device_owner(&state, device_id, user_auth.user_id).await?;
+
+    let job = LocationImportJobRepository::new(state.pool.clone())
+        .find_for_device(device_id, job_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Location import not found".to_string()))?;
+
+    Ok(Json(job))
+}
@@ -0,0 +1,342 @@
+//! Time-boxed and scheduled location sharing endpoint handlers.
+//!
+//! In groups whose default sharing mode is `on_demand`, members' locations
+//! are visible to other members only while the member's sharing window is
+//! open. Windows expire on their own; members can extend or stop them early.
+//!
+//! In any group, members can also set a recurring sharing schedule. Outside
+//! the schedule their sharing is paused and other members see none of their
+//! locations.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{Duration, Utc};
+use domain::models::group_settings::{GroupSettings, GroupSharingMode};
+use domain::models::{
+    extended_expiry, ExtendSharingWindowRequest, LocationSharingSchedule, LocationSharingWindow,
+    PrivacyZoneSet, SharingScheduleResponse, SharingWindowResponse, StartSharingWindowRequest,
+    WeeklySchedule, DEFAULT_SHARING_WINDOW_MINUTES,
+};
+use persistence::repositories::{
+    GroupRepository, LocationSharingScheduleRepository, LocationSharingWindowRepository,
+};
+use sqlx::PgPool;
+use tracing::info;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::app::AppState;
+use crate::error::ApiError;
+use crate::extractors::UserAuth;
+use crate::routes::privacy_zones::load_privacy_zones;
+
+fn validation_error(e: validator::ValidationErrors) -> ApiError {
+    let errors: Vec<String> = e
+        .field_errors()
+        .iter()
+        .flat_map(|(field, errors)| {
+            errors.iter().map(move |err| {
+                format!("{}: {}", field, err.message.as_ref().unwrap_or(&"".into()))
+            })
+        })
+        .collect();
+    ApiError::Validation(errors.join(", "))
+}
+
+/// Whether locations in the group are shared only during sharing windows.
+async fn is_on_demand(pool: &PgPool, group_id: Uuid) -> Result<bool, ApiError> {
+    let stored = GroupRepository::new(pool.clone())
+        .get_settings(group_id)
+        .await?
+        .unwrap_or_default();
+    Ok(GroupSettings::from_stored(&stored).default_sharing_mode == GroupSharingMode::OnDemand)
+}
+
+/// Load the location filter for a group's devices, as seen by `viewer`.
+///
+/// Applies the owners' privacy zones, pauses owners outside their sharing
+/// schedule and, in `on_demand` groups, hides the locations of owners
+/// without an open sharing window.
+pub(crate) async fn load_group_location_filter(
+    pool: &PgPool,
+    group_id: Uuid,
+    viewer: Option<Uuid>,
+    owners: impl IntoIterator<Item = Option<Uuid>>,
+) -> Result<PrivacyZoneSet, ApiError> {
+    let mut owner_ids: Vec<Uuid> = owners.into_iter().flatten().collect();
+    owner_ids.sort_unstable();
+    owner_ids.dedup();
+
+    let mut filter = load_privacy_zones(pool, viewer, owner_ids.iter().copied().map(Some)).await?;
+    if is_on_demand(pool, group_id).await? {
+        let sharing = LocationSharingWindowRepository::new(pool.clone())
+            .active_users(group_id, &owner_ids)
+            .await?;
+        filter.hide_owners(owner_ids.iter().copied().filter(|id| !sharing.contains(id)));
+    }
+
+    let now = Utc::now();
+    let schedules = LocationSharingScheduleRepository::new(pool.clone())
+        .list_for_users(group_id, &owner_ids)
+        .await?;
+    filter.pause_owners(
+        schedules
+            .into_iter()
+            .map(LocationSharingSchedule::from)
+            .filter(|s| !s.is_sharing_at(now))
+            .map(|s| s.user_id),
+    );
+    Ok(filter)
+}
+
+/// Like [`load_group_location_filter`], for devices registered with a group
+/// slug. Unknown slugs only get privacy zones applied.
+pub(crate) async fn load_slug_location_filter(
+    pool: &PgPool,
+    group_slug: &str,
+    viewer: Option<Uuid>,
+    owners: impl IntoIterator<Item = Option<Uuid>>,
+) -> Result<PrivacyZoneSet, ApiError> {
+    match GroupRepository::new(pool.clone())
+        .find_by_slug(group_slug)
+        .await?
+    {
+        Some(group) => load_group_location_filter(pool, group.id, viewer, owners).await,
+        None => load_privacy_zones(pool, viewer, owners).await,
+    }
+}
+
+async fn require_membership(
+    state: &AppState,
+    group_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), ApiError> {
+    GroupRepository::new(state.pool.clone())
+        .get_membership(group_id, user_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Group not found or you are not a member".to_string()))?;
+    Ok(())
+}
+
+/// Get the current user's sharing window in a group.
+///
+/// GET /api/v1/groups/:group_id/sharing-window
+///
+/// Returns 404 if the user never opened one.
+pub async fn get_sharing_window(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(group_id): Path<Uuid>,
+) -> Result<Json<SharingWindowResponse>, ApiError> {
+    require_membership(&state, group_id, user_auth.user_id).await?;
+
+    let window: LocationSharingWindow = LocationSharingWindowRepository::new(state.pool.clone())
+        .find(user_auth.user_id, group_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("No sharing window in this group".to_string()))?
+        .into();
+
+    Ok(Json(SharingWindowResponse::new(window, Utc::now())))
+}
+
+/// Start sharing the current user's locations with a group.
+///
+/// POST /api/v1/groups/:group_id/sharing-window
+///
+/// The body is optional (`{"duration_minutes": 120}`). Replaces any open
+/// window. Returns 409 if the group doesn't use on-demand sharing.
+pub async fn start_sharing_window(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(group_id): Path<Uuid>,
+    request: Option<Json<StartSharingWindowRequest>>,
+) -> Result<Json<SharingWindowResponse>, ApiError> {
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+    request.validate().map_err(validation_error)?;
+
+    require_membership(&state, group_id, user_auth.user_id).await?;
+    if !is_on_demand(&state.pool, group_id).await? {
+        return Err(ApiError::Conflict(
+            "Locations in this group are shared continuously".to_string(),
+        ));
+    }
+
+    let minutes = request
+        .duration_minutes
+        .unwrap_or(DEFAULT_SHARING_WINDOW_MINUTES);
+    let now = Utc::now();
+    let window: LocationSharingWindow = LocationSharingWindowRepository::new(state.pool.clone())
+        .start(
+            user_auth.user_id,
+            group_id,
+            now + Duration::minutes(minutes),
+        )
+        .await?
+        .into();
+
+    info!(
+        group_id = %group_id,
+        user_id = %user_auth.user_id,
+        expires_at = %window.expires_at,
+        "Location sharing window started"
+    );
+
+    Ok(Json(SharingWindowResponse::new(window, now)))
+}
+
+/// Extend the current user's open sharing window.
+///
+/// PATCH /api/v1/groups/:group_id/sharing-window
+///
+/// The window never ends more than 24 hours from now. Returns 404 if no
+/// window is open.
+pub async fn extend_sharing_window(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(group_id): Path<Uuid>,
+    Json(request): Json<ExtendSharingWindowRequest>,
+) -> Result<Json<SharingWindowResponse>, ApiError> {
+    request.validate().map_err(validation_error)?;
+    require_membership(&state, group_id, user_auth.user_id).await?;
+
+    let repo = LocationSharingWindowRepository::new(state.pool.clone());
+    let now = Utc::now();
+    let current: LocationSharingWindow = repo
+        .find(user_auth.user_id, group_id)
+        .await?
+        .map(LocationSharingWindow::from)
+        .filter(|w| w.is_active(now))
+        .ok_or_else(|| ApiError::NotFound("No open sharing window in this group".to_string()))?;
+
+    let expires_at = extended_expiry(current.expires_at, request.extend_minutes, now);
+    let window: LocationSharingWindow = repo
+        .extend(user_auth.user_id, group_id, expires_at)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("No open sharing window in this group".to_string()))?
+        .into();
+
+    info!(
+        group_id = %group_id,
+        user_id = %user_auth.user_id,
+        expires_at = %window.expires_at,
+        "Location sharing window extended"
+    );
+
+    Ok(Json(SharingWindowResponse::new(window, now)))
+}
+
+/// Stop sharing the current user's locations with a group early.
+///
+/// DELETE /api/v1/groups/:group_id/sharing-window
+///
+/// Returns 404 if no window is open.
+pub async fn stop_sharing_window(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(group_id): Path<Uuid>,
+) -> Result<Json<SharingWindowResponse>, ApiError> {
+    require_membership(&state, group_id, user_auth.user_id).await?;
+
+    let window: LocationSharingWindow = LocationSharingWindowRepository::new(state.pool.clone())
+        .stop(user_auth.user_id, group_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("No open sharing window in this group".to_string()))?
+        .into();
+
+    info!(
+        group_id = %group_id,
+        user_id = %user_auth.user_id,
+        "Location sharing window stopped"
+    );
+
+    Ok(Json(SharingWindowResponse::new(window, Utc::now())))
+}
+
+/// Get the current user's sharing schedule in a group.
+///
+/// GET /api/v1/groups/:group_id/sharing-schedule
+///
+/// Returns 404 if the user shares without a schedule.
+pub async fn get_sharing_schedule(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(group_id): Path<Uuid>,
+) -> Result<Json<SharingScheduleResponse>, ApiError> {
+    require_membership(&state, group_id, user_auth.user_id).await?;
+
+    let schedule: LocationSharingSchedule =
+        LocationSharingScheduleRepository::new(state.pool.clone())
+            .find(user_auth.user_id, group_id)
+            .await?
+            .ok_or_else(|| ApiError::NotFound("No sharing schedule in this group".to_string()))?
+            .into();
+
+    Ok(Json(SharingScheduleResponse::new(schedule, Utc::now())))
+}
+
+/// Set the current user's sharing schedule in a group.
+///
+/// PUT /api/v1/groups/:group_id/sharing-schedule
+///
+/// Replaces any previous schedule. Outside its windows the user's sharing
+/// with the group is paused.
+pub async fn set_sharing_schedule(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(group_id): Path<Uuid>,
+    Json(request): Json<WeeklySchedule>,
+) -> Result<Json<SharingScheduleResponse>, ApiError> {
+    request.validate().map_err(ApiError::Validation)?;
+    require_membership(&state, group_id, user_auth.user_id).await?;
+
+    let value = serde_json::to_value(&request)
+        .map_err(|e| ApiError::Internal(format!("Failed to serialize schedule: {}", e)))?;
+    let schedule: LocationSharingSchedule =
+        LocationSharingScheduleRepository::new(state.pool.clone())
+            .upsert(user_auth.user_id, group_id, &value)
+            .await?
+            .into();
+
+    info!(
+        group_id = %group_id,
+        user_id = %user_auth.user_id,
+        windows = schedule.schedule.windows.len(),
+        "Location sharing schedule set"
+    );
+
+    Ok(Json(SharingScheduleResponse::new(schedule, Utc::now())))
+}
+
+/// Remove the current user's sharing schedule in a group.
+///
+/// DELETE /api/v1/groups/:group_id/sharing-schedule
+///
+/// The user shares again as the group's sharing mode allows. Returns 404 if
+/// no schedule is set.
+pub async fn delete_sharing_schedule(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(group_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    require_membership(&state, group_id, user_auth.user_id).await?;
+
+    let deleted = LocationSharingScheduleRepository::new(state.pool.clone())
+        .delete(user_auth.user_id, group_id)
+        .await?;
+    if !deleted {
+        return Err(ApiError::NotFound(
+            "No sharing schedule in this group".to_string(),
+        ));
+    }
+
+    info!(
+        group_id = %group_id,
+        user_id = %user_auth.user_id,
+        "Location sharing schedule removed"
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -5,10 +5,11 @@ use axum::{
     Json,
 };
 use chrono::{DateTime, TimeZone, Utc};
-use geo::{LineString, Simplify};
+use geo::{LineString, SimplifyIdx};
+use persistence::backend::{DeviceStore, LocationStore};
 use persistence::repositories::{
-    DeviceRepository, IdempotencyKeyRepository, LocationHistoryQuery, LocationInput,
-    LocationRepository, TripRepository,
+    DeviceTelemetryRepository, IdempotencyKeyRepository, LocationHistoryQuery, LocationInput,
+    LocationRepository, TelemetryInput, TripRepository,
 };
 use std::collections::HashSet;
 use tracing::info;
@@ -18,10 +19,21 @@ use validator::Validate;
 use crate::app::AppState;
 use crate::error::ApiError;
 use crate::extractors::idempotency_key::OptionalIdempotencyKey;
+use crate::routes::location_sharing::load_slug_location_filter;
+use crate::services::arrival_forecast::forecast_arrivals_if_enabled;
+use crate::services::device_usage::track_device_request;
+use crate::services::geofence_evaluation::evaluate_geofences_if_enabled;
+use crate::services::group_notifications::notify_low_battery_if_dropped;
+use crate::services::location_filter::{load_filter_config, quarantine_invalid_locations};
+use crate::services::location_smoothing::{is_smoothing_enabled, smooth_locations};
+use crate::services::movement_detection::detect_movement_if_enabled;
+use crate::services::tracking_schedule::{drop_outside_schedule, load_tracking_schedule};
 use domain::models::location::{
-    BatchUploadRequest, GetLocationHistoryQuery, LocationHistoryItem, LocationHistoryResponse,
-    PaginationInfo, SimplificationInfo, SortOrder, UploadLocationRequest, UploadLocationResponse,
+    trust_score, BatchUploadRequest, GetLocationHistoryQuery, LocationHistoryItem,
+    LocationHistoryResponse, LocationSource, PaginationInfo, SimplificationInfo, SortOrder,
+    UploadLocationRequest, UploadLocationResponse,
 };
+use domain::models::{ApiEndpointClass, DeviceTelemetry, PrivacyZoneSet, SharedLocation};
 
 /// Upload a single location.
 ///
@@ -63,8 +75,8 @@ pub async fn upload_location(
     })?;
 
     // Verify device exists
-    let device_repo = DeviceRepository::new(state.pool.clone());
-    let device = device_repo
+    let device = state
+        .devices()
         .find_by_device_id(request.device_id)
         .await?
         .ok_or_else(|| {
@@ -78,6 +90,8 @@ pub async fn upload_location(
         ));
     }
 
+    track_device_request(&state.pool, request.device_id, ApiEndpointClass::Ingest);
+
     // Convert millisecond timestamp to DateTime
     let captured_at = Utc
         .timestamp_millis_opt(request.timestamp)
@@ -98,8 +112,17 @@ pub async fn upload_location(
         }
     }
 
+    // Battery and connectivity readings go to the telemetry table as well
+    let telemetry = DeviceTelemetry::from_readings(
+        request.battery_level,
+        request.charging_state,
+        request.network_type.clone(),
+        request.signal_strength,
+        captured_at,
+    );
+
     // Insert location
-    let location_repo = LocationRepository::new(state.pool.clone());
+    let source = LocationSource::resolve(request.source, request.provider.as_deref());
     let input = LocationInput {
         device_id: request.device_id,
         latitude: request.latitude,
@@ -115,22 +138,78 @@ pub async fn upload_location(
         transportation_mode: request.transportation_mode.map(|m| m.as_str().to_string()),
         detection_source: request.detection_source.map(|s| s.as_str().to_string()),
         trip_id: request.trip_id,
+        sequence_number: request.sequence_number,
+        source: source.map(|s| s.as_str().to_string()),
+        trust_score: Some(trust_score(source, request.accuracy) as i16),
+    };
+
+    // Points captured outside the tracking schedule are ignored
+    let mut accepted = vec![input];
+    let mut outside_schedule_count = 0;
+    if let Some(schedule) = load_tracking_schedule(&state.pool, request.device_id).await {
+        (accepted, outside_schedule_count) =
+            drop_outside_schedule(&schedule, request.device_id, accepted);
+    }
+
+    // Points breaking the accuracy or speed limits are quarantined
+    let filter_config =
+        load_filter_config(&state.pool, request.device_id, device.organization_id).await;
+    if !accepted.is_empty() && filter_config.is_enabled() {
+        accepted =
+            quarantine_invalid_locations(&state.pool, request.device_id, filter_config, accepted)
+                .await?;
+    }
+
+    // Optional smoothing may adjust the point or drop it as an outlier
+    if !accepted.is_empty() && is_smoothing_enabled(&state.pool, request.device_id).await {
+        accepted = smooth_locations(&state.pool, request.device_id, accepted).await?;
+    }
+
+    // A replayed point whose sequence number is already stored is skipped
+    let (processed_count, duplicate_count) = match accepted.into_iter().next() {
+        Some(input) => match state.locations().insert_location(input).await? {
+            Some(_) => (1, 0),
+            None => (0, 1),
+        },
+        None => (0, 0),
     };
-    location_repo.insert_location(input).await?;
+
+    // Recorded even if the point was quarantined or dropped as a GPS outlier
+    if let Some(telemetry) = telemetry {
+        let has_battery_level = telemetry.battery_level.is_some();
+        DeviceTelemetryRepository::new(state.pool.clone())
+            .insert(request.device_id, telemetry.into())
+            .await?;
+        if has_battery_level {
+            notify_low_battery_if_dropped(
+                state.pool.clone(),
+                state.notification_service.clone(),
+                request.device_id,
+            );
+        }
+    }
+
+    if processed_count > 0 {
+        detect_movement_if_enabled(&state.pool, request.device_id, captured_at).await;
+        evaluate_geofences_if_enabled(&state.pool, request.device_id, captured_at).await;
+        forecast_arrivals_if_enabled(&state.pool, &state.notification_service, request.device_id)
+            .await;
+    }
 
     // Update device last_seen_at (fire-and-forget)
-    let pool_clone = state.pool.clone();
+    let devices = state.devices();
     let device_id = request.device_id;
     tokio::spawn(async move {
-        let repo = DeviceRepository::new(pool_clone);
-        if let Err(e) = repo.update_last_seen_at(device_id, Utc::now()).await {
+        if let Err(e) = devices.update_last_seen_at(device_id, Utc::now()).await {
             tracing::warn!("Failed to update device last_seen_at: {}", e);
         }
     });
 
     let response = UploadLocationResponse {
         success: true,
-        processed_count: 1,
+        processed_count,
+        duplicate_count,
+        outside_schedule_count,
     };
 
     // Store idempotency key with response if present
@@ -151,6 +230,10 @@ pub async fn upload_location(
 /// Upload multiple locations in a batch.
 ///
 /// POST /api/v1/locations/batch
+///
+/// The body may be sent with `Content-Encoding: gzip`; it is decompressed by
+/// the route's middleware and limited to `server.max_batch_body_size` decoded
+/// bytes.
 pub async fn upload_batch(
     State(state): State<AppState>,
     OptionalIdempotencyKey(idempotency_key): OptionalIdempotencyKey,
@@ -209,8 +292,8 @@ pub async fn upload_batch(
     }
 
     // Verify device exists
-    let device_repo = DeviceRepository::new(state.pool.clone());
-    let device = device_repo
+    let device = state
+        .devices()
         .find_by_device_id(request.device_id)
         .await?
         .ok_or_else(|| {
@@ -224,6 +307,8 @@ pub async fn upload_batch(
         ));
     }
 
+    track_device_request(&state.pool, request.device_id, ApiEndpointClass::Ingest);
+
     // Collect unique trip IDs from the batch for validation
     let unique_trip_ids: std::collections::HashSet<Uuid> = request
         .locations
@@ -249,12 +334,24 @@ pub async fn upload_batch(
 
     // Convert locations to repository format
     let mut locations_data = Vec::with_capacity(request.locations.len());
+    let mut telemetry_data: Vec<TelemetryInput> = Vec::new();
     for loc in &request.locations {
         let captured_at = Utc
             .timestamp_millis_opt(loc.timestamp)
             .single()
             .ok_or_else(|| ApiError::Validation("Invalid timestamp".to_string()))?;
 
+        if let Some(telemetry) = DeviceTelemetry::from_readings(
+            loc.battery_level,
+            loc.charging_state,
+            loc.network_type.clone(),
+            loc.signal_strength,
+            captured_at,
+        ) {
+            telemetry_data.push(telemetry.into());
+        }
+
+        let source = LocationSource::resolve(loc.source, loc.provider.as_deref());
         locations_data.push(LocationInput {
             device_id: request.device_id,
             latitude: loc.latitude,
@@ -270,21 +367,67 @@ pub async fn upload_batch(
             transportation_mode: loc.transportation_mode.map(|m| m.as_str().to_string()),
             detection_source: loc.detection_source.map(|s| s.as_str().to_string()),
             trip_id: loc.trip_id,
+            sequence_number: loc.sequence_number,
+            source: source.map(|s| s.as_str().to_string()),
+            trust_score: Some(trust_score(source, loc.accuracy) as i16),
         });
     }
 
-    // Insert all locations in a transaction
-    let location_repo = LocationRepository::new(state.pool.clone());
-    let processed_count = location_repo
+    let mut outside_schedule_count = 0;
+    if let Some(schedule) = load_tracking_schedule(&state.pool, request.device_id).await {
+        (locations_data, outside_schedule_count) =
+            drop_outside_schedule(&schedule, request.device_id, locations_data);
+    }
+
+    let filter_config =
+        load_filter_config(&state.pool, request.device_id, device.organization_id).await;
+    if filter_config.is_enabled() {
+        locations_data = quarantine_invalid_locations(
+            &state.pool,
+            request.device_id,
+            filter_config,
+            locations_data,
+        )
+        .await?;
+    }
+
+    if is_smoothing_enabled(&state.pool, request.device_id).await {
+        locations_data = smooth_locations(&state.pool, request.device_id, locations_data).await?;
+    }
+
+    let earliest_captured_at = locations_data.iter().map(|loc| loc.captured_at).min();
+
+    // Insert all locations in a transaction, skipping replayed points
+    let accepted_count = locations_data.len();
+    let processed_count = state
+        .locations()
         .insert_locations_batch(request.device_id, locations_data)
         .await?;
+    let duplicate_count = accepted_count - processed_count;
+
+    DeviceTelemetryRepository::new(state.pool.clone())
+        .insert_batch(request.device_id, &telemetry_data)
+        .await?;
+    if telemetry_data.iter().any(|t| t.battery_level.is_some()) {
+        notify_low_battery_if_dropped(
+            state.pool.clone(),
+            state.notification_service.clone(),
+            request.device_id,
+        );
+    }
+
+    if let Some(since) = earliest_captured_at {
+        detect_movement_if_enabled(&state.pool, request.device_id, since).await;
+        evaluate_geofences_if_enabled(&state.pool, request.device_id, since).await;
+        forecast_arrivals_if_enabled(&state.pool, &state.notification_service, request.device_id)
+            .await;
+    }
 
     // Update device last_seen_at (fire-and-forget)
-    let pool_clone = state.pool.clone();
+    let devices = state.devices();
     let device_id = request.device_id;
     tokio::spawn(async move {
-        let repo = DeviceRepository::new(pool_clone);
-        if let Err(e) = repo.update_last_seen_at(device_id, Utc::now()).await {
+        if let Err(e) = devices.update_last_seen_at(device_id, Utc::now()).await {
             tracing::warn!("Failed to update device last_seen_at: {}", e);
         }
     });
@@ -292,6 +435,8 @@ pub async fn upload_batch(
     let response = UploadLocationResponse {
         success: true,
         processed_count,
+        duplicate_count,
+        outside_schedule_count,
     };
 
     // Store idempotency key with response if present
@@ -302,6 +447,8 @@ pub async fn upload_batch(
     info!(
         device_id = %request.device_id,
         count = processed_count,
+        duplicates = duplicate_count,
+        outside_schedule = outside_schedule_count,
         "Batch locations uploaded"
     );
 
@@ -325,17 +472,21 @@ async fn store_idempotency_key(
 ///
 /// GET /api/v1/devices/:device_id/locations
 ///
-/// Supports optional simplification via the `tolerance` parameter (in meters).
-/// When tolerance > 0, applies Ramer-Douglas-Peucker line simplification
-/// and pagination is disabled.
+/// Supports optional simplification via the `simplify_tolerance` parameter
+/// (in meters; `tolerance` is accepted too). When it is > 0, applies
+/// Ramer-Douglas-Peucker line simplification, pagination is disabled and up
+/// to `MAX_SIMPLIFIED_LIMIT` points are returned.
+///
+/// The caller is not identified as a user, so the device owner's privacy
+/// zones always apply.
 pub async fn get_location_history(
     State(state): State<AppState>,
     Path(device_id): Path<Uuid>,
     Query(query): Query<GetLocationHistoryQuery>,
 ) -> Result<Json<LocationHistoryResponse>, ApiError> {
     // Verify device exists and is active
-    let device_repo = DeviceRepository::new(state.pool.clone());
-    let device = device_repo
+    let device = state
+        .devices()
         .find_by_device_id(device_id)
         .await?
         .ok_or_else(|| ApiError::NotFound("Device not found".to_string()))?;
@@ -382,19 +533,26 @@ pub async fn get_location_history(
     };
 
     let location_repo = LocationRepository::new(state.pool.clone());
+    let privacy_zones =
+        load_slug_location_filter(&state.pool, &device.group_id, None, [device.owner_user_id])
+            .await?;
 
     // Check if simplification is requested
     if let Some(tolerance) = query.effective_tolerance() {
-        return get_simplified_locations(
+        let Json(mut response) = get_simplified_locations(
             &location_repo,
             device_id,
             from_timestamp,
             to_timestamp,
             query.order == SortOrder::Asc,
+            query.source,
             tolerance,
-            query.effective_limit(),
+            query.effective_simplified_limit(),
         )
-        .await;
+        .await?;
+        response.locations =
+            share_history(&privacy_zones, device.owner_user_id, response.locations);
+        return Ok(Json(response));
     }
 
     // Standard pagination path (no simplification)
@@ -420,6 +578,7 @@ pub async fn get_location_history(
         to_timestamp,
         limit,
         ascending: query.order == SortOrder::Asc,
+        source: query.source.map(|s| s.as_str().to_string()),
     };
 
     // Execute query
@@ -442,6 +601,7 @@ pub async fn get_location_history(
             loc.into()
         })
         .collect();
+    let locations = share_history(&privacy_zones, device.owner_user_id, locations);
 
     info!(
         device_id = %device_id,
@@ -460,16 +620,45 @@ pub async fn get_location_history(
     }))
 }
 
+/// Apply the owner's privacy zones to history items: suppressed points are
+/// dropped, snapped points are moved to the zone center and lose the motion
+/// fields that could reveal the exact position.
+fn share_history(
+    zones: &PrivacyZoneSet,
+    owner: Option<Uuid>,
+    items: Vec<LocationHistoryItem>,
+) -> Vec<LocationHistoryItem> {
+    items
+        .into_iter()
+        .filter_map(|mut item| {
+            let shared = zones.share(owner, item.latitude, item.longitude);
+            let (latitude, longitude, accuracy) =
+                shared.apply(item.latitude, item.longitude, item.accuracy)?;
+            if shared != SharedLocation::Exact {
+                item.latitude = latitude;
+                item.longitude = longitude;
+                item.accuracy = accuracy;
+                item.altitude = None;
+                item.bearing = None;
+                item.speed = None;
+            }
+            Some(item)
+        })
+        .collect()
+}
+
 /// Fetch all locations in time range and apply RDP line simplification.
 ///
 /// When simplification is active, pagination is disabled and all matching
 /// locations are processed together to ensure correct trajectory simplification.
+#[allow(clippy::too_many_arguments)]
 async fn get_simplified_locations(
     location_repo: &LocationRepository,
     device_id: Uuid,
     from_timestamp: Option<DateTime<Utc>>,
     to_timestamp: Option<DateTime<Utc>>,
     ascending: bool,
+    source: Option<LocationSource>,
     tolerance: f64,
     limit: i32,
 ) -> Result<Json<LocationHistoryResponse>, ApiError> {
@@ -477,6 +666,9 @@ async fn get_simplified_locations(
     let mut entities = location_repo
         .get_all_locations_in_range(device_id, from_timestamp, to_timestamp)
         .await?;
+    if let Some(source) = source {
+        entities.retain(|e| e.source.as_deref() == Some(source.as_str()));
+    }
 
     let original_count = entities.len();
 
@@ -519,31 +711,14 @@ async fn get_simplified_locations(
         }));
     }
 
-    // Build LineString from coordinates (geo uses (x, y) = (lon, lat))
-    let coords: Vec<geo::Coord<f64>> = entities
-        .iter()
-        .map(|e| geo::coord! { x: e.longitude, y: e.latitude })
-        .collect();
-
-    let line: LineString<f64> = coords.into();
-
-    // Convert tolerance from meters to degrees (approximate: 1 degree ≈ 111km)
-    let tolerance_degrees = tolerance / 111_000.0;
-
     // Apply Ramer-Douglas-Peucker simplification
-    let simplified_line = line.simplify(&tolerance_degrees);
-
-    // Build set of simplified coordinates for matching
-    // Use bit representation for exact floating point comparison
-    let simplified_coords: HashSet<(u64, u64)> = simplified_line
-        .coords()
-        .map(|c| (c.x.to_bits(), c.y.to_bits()))
-        .collect();
-
-    // Filter entities to only those whose coordinates appear in simplified line
+    let points: Vec<(f64, f64)> = entities.iter().map(|e| (e.latitude, e.longitude)).collect();
+    let kept: HashSet<usize> = simplify_path(&points, tolerance).into_iter().collect();
     let kept_entities: Vec<_> = entities
         .into_iter()
-        .filter(|e| simplified_coords.contains(&(e.longitude.to_bits(), e.latitude.to_bits())))
+        .enumerate()
+        .filter(|(i, _)| kept.contains(i))
+        .map(|(_, e)| e)
         .collect();
 
     // If descending order requested, reverse the result
@@ -586,12 +761,129 @@ async fn get_simplified_locations(
     }))
 }
 
+/// Approximate meters per degree of latitude.
+const METERS_PER_DEGREE: f64 = 111_320.0;
+
+/// Indices of the points kept by Ramer-Douglas-Peucker simplification,
+/// in input order. Points are `(latitude, longitude)`.
+///
+/// Points are projected to local meters (equirectangular around the mean
+/// latitude) so that the tolerance means the same distance east-west as
+/// north-south.
+fn simplify_path(points: &[(f64, f64)], tolerance_meters: f64) -> Vec<usize> {
+    if points.len() < 3 {
+        return (0..points.len()).collect();
+    }
+
+    let mean_latitude = points.iter().map(|(lat, _)| lat).sum::<f64>() / points.len() as f64;
+    let longitude_scale = mean_latitude.to_radians().cos() * METERS_PER_DEGREE;
+
+    // geo uses (x, y) = (lon, lat)
+    let line: LineString<f64> = points
+        .iter()
+        .map(|&(lat, lon)| geo::coord! { x: lon * longitude_scale, y: lat * METERS_PER_DEGREE })
+        .collect::<Vec<_>>()
+        .into();
+    line.simplify_idx(&tolerance_meters)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use domain::models::location::LocationData;
     use uuid::Uuid;
 
+    fn history_item(id: i64, latitude: f64, longitude: f64) -> LocationHistoryItem {
+        LocationHistoryItem {
+            id,
+            latitude,
+            longitude,
+            accuracy: 10.0,
+            altitude: Some(150.0),
+            bearing: Some(90.0),
+            speed: Some(1.5),
+            provider: None,
+            battery_level: None,
+            network_type: None,
+            captured_at: Utc::now(),
+            created_at: Utc::now(),
+            transportation_mode: None,
+            detection_source: None,
+            trip_id: None,
+            source: None,
+            trust_score: None,
+        }
+    }
+
+    #[test]
+    fn test_simplify_path_drops_collinear_points() {
+        // Straight line north, then a sharp turn east
+        let mut points: Vec<(f64, f64)> =
+            (0..50).map(|i| (48.0 + i as f64 * 0.001, 17.0)).collect();
+        points.extend((1..50).map(|i| (48.049, 17.0 + i as f64 * 0.001)));
+
+        let kept = simplify_path(&points, 10.0);
+        assert_eq!(kept, vec![0, 49, points.len() - 1]);
+    }
+
+    #[test]
+    fn test_simplify_path_tolerance_is_in_meters() {
+        // ~37 m east-west bump at 48°N (0.0005° of longitude)
+        let points = [(48.0, 17.0), (48.001, 17.0005), (48.002, 17.0)];
+        assert_eq!(simplify_path(&points, 30.0), vec![0, 1, 2]);
+        assert_eq!(simplify_path(&points, 45.0), vec![0, 2]);
+    }
+
+    #[test]
+    fn test_simplify_path_too_few_points() {
+        assert!(simplify_path(&[], 10.0).is_empty());
+        assert_eq!(
+            simplify_path(&[(48.0, 17.0), (48.1, 17.1)], 10.0),
+            vec![0, 1]
+        );
+    }
+
+    #[test]
+    fn test_share_history_applies_owner_zones() {
+        use domain::models::{PrivacyZone, PrivacyZoneMode};
+
+        let owner = Uuid::new_v4();
+        let zone = |latitude: f64, mode: PrivacyZoneMode| PrivacyZone {
+            id: Uuid::new_v4(),
+            user_id: owner,
+            name: "Zone".to_string(),
+            latitude,
+            longitude: 17.0,
+            radius_meters: 100.0,
+            mode,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let zones = PrivacyZoneSet::new(
+            None,
+            vec![
+                zone(48.0, PrivacyZoneMode::Snap),
+                zone(49.0, PrivacyZoneMode::Suppress),
+            ],
+        );
+        let items = vec![
+            history_item(1, 48.0003, 17.0003),
+            history_item(2, 49.0001, 17.0),
+            history_item(3, 50.0, 17.0),
+        ];
+
+        let shared = share_history(&zones, Some(owner), items.clone());
+        assert_eq!(shared.len(), 2);
+        assert_eq!((shared[0].latitude, shared[0].longitude), (48.0, 17.0));
+        assert_eq!(shared[0].accuracy, 100.0);
+        assert!(shared[0].speed.is_none() && shared[0].altitude.is_none());
+        assert_eq!(shared[1].id, 3);
+        assert_eq!(shared[1].speed, Some(1.5));
+
+        // Devices without an owner have no zones
+        assert_eq!(share_history(&zones, None, items).len(), 3);
+    }
+
     #[test]
     fn test_upload_location_request_serialization() {
         let json = r#"{
@@ -665,6 +957,8 @@ mod tests {
         let response = UploadLocationResponse {
             success: true,
             processed_count: 5,
+            duplicate_count: 0,
+            outside_schedule_count: 0,
         };
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("\"success\":true"));
@@ -676,6 +970,8 @@ mod tests {
         let response = UploadLocationResponse {
             success: false,
             processed_count: 0,
+            duplicate_count: 0,
+            outside_schedule_count: 0,
         };
         assert!(!response.success);
         assert_eq!(response.processed_count, 0);
@@ -694,9 +990,13 @@ mod tests {
             provider: Some("fused".to_string()),
             battery_level: Some(75),
             network_type: Some("5g".to_string()),
+            charging_state: None,
+            signal_strength: None,
             transportation_mode: None,
             detection_source: None,
             trip_id: None,
+            sequence_number: None,
+            source: None,
         };
         assert_eq!(data.latitude, 40.7128);
         assert_eq!(data.provider, Some("fused".to_string()));
@@ -715,9 +1015,13 @@ mod tests {
             provider: None,
             battery_level: None,
             network_type: None,
+            charging_state: None,
+            signal_strength: None,
             transportation_mode: None,
             detection_source: None,
             trip_id: None,
+            sequence_number: None,
+            source: None,
         };
         let json = serde_json::to_string(&data).unwrap();
         assert!(json.contains("\"latitude\":45"));
@@ -6,6 +6,7 @@ pub mod admin_groups;
 pub mod admin_locations;
 pub mod admin_managed_users;
 pub mod admin_migrations;
+pub mod admin_password_hashes;
 pub mod admin_unlock_requests;
 pub mod admin_users;
 pub mod analytics;
@@ -14,35 +15,60 @@ pub mod app_usage;
 pub mod audit_logs;
 pub mod auth;
 pub mod bulk_import;
+pub mod commutes;
 pub mod compliance;
 pub mod dashboard;
 pub mod data_subject_requests;
+pub mod device_agent;
+pub mod device_command_macros;
 pub mod device_policies;
+pub mod device_push_tokens;
 pub mod device_settings;
+pub mod device_telemetry;
 pub mod devices;
+pub mod effective_access;
 pub mod enrollment;
 pub mod enrollment_tokens;
+#[cfg(feature = "fault-injection")]
+pub mod fault_injection;
 pub mod fleet;
 pub mod frontend;
 pub mod geofence_events;
+pub mod geofence_templates;
 pub mod geofences;
+pub mod group_api_tokens;
+pub mod group_exports;
+pub mod group_hierarchy;
+pub mod group_messages;
+pub mod group_notification_preferences;
+pub mod group_roles;
 pub mod groups;
 pub mod health;
 pub mod invites;
+pub mod location_imports;
+pub mod location_sharing;
 pub mod locations;
 pub mod movement_events;
 pub mod openapi;
 pub mod org_invitations;
+pub mod org_ownership_transfer;
 pub mod org_webhooks;
+pub mod organization_exports;
 pub mod organization_settings;
 pub mod organizations;
 pub mod permissions;
 pub mod privacy;
+pub mod privacy_zones;
 pub mod proximity_alerts;
 pub mod public_config;
 pub mod roles;
+pub mod settings_diff;
 pub mod system_config;
 pub mod system_roles;
+pub mod tenant_logs;
+pub mod trip_edits;
+pub mod trip_purposes;
+pub mod trip_shares;
 pub mod trips;
 pub mod users;
 pub mod versioning;
@@ -16,11 +16,13 @@ use validator::Validate;
 
 use crate::app::AppState;
 use crate::error::ApiError;
+use crate::services::device_usage::track_device_request;
 use domain::models::movement_event::{
     BatchMovementEventRequest, BatchMovementEventResponse, CreateMovementEventRequest,
     CreateMovementEventResponse, DetectionSource, GetMovementEventsResponse,
     MovementEventPagination, MovementEventResponse, TransportationMode,
 };
+use domain::models::ApiEndpointClass;
 
 /// Query parameters for GET /api/v1/devices/:deviceId/movement-events
 #[derive(Debug, Clone, Deserialize)]
@@ -84,6 +86,8 @@ pub async fn create_movement_event(
         ));
     }
 
+    track_device_request(&state.pool, request.device_id, ApiEndpointClass::Ingest);
+
     // Validate trip_id if provided - must exist and belong to this device
     if let Some(trip_id) = request.trip_id {
         let trip_repo = TripRepository::new(state.pool.clone());
@@ -113,6 +117,7 @@ pub async fn create_movement_event(
         transportation_mode: request.transportation_mode.as_str().to_string(),
         confidence: request.confidence,
         detection_source: request.detection_source.as_str().to_string(),
+        movement_state: None,
     };
 
     // Insert movement event
@@ -172,6 +177,8 @@ pub async fn create_movement_events_batch(
         ));
     }
 
+    track_device_request(&state.pool, request.device_id, ApiEndpointClass::Ingest);
+
     // Validate all trip_ids in the batch - collect unique ones for efficiency
     let unique_trip_ids: std::collections::HashSet<Uuid> =
         request.events.iter().filter_map(|e| e.trip_id).collect();
@@ -210,6 +217,7 @@ pub async fn create_movement_events_batch(
             transportation_mode: event.transportation_mode.as_str().to_string(),
             confidence: event.confidence,
             detection_source: event.detection_source.as_str().to_string(),
+            movement_state: None,
         })
         .collect();
 
@@ -312,6 +320,7 @@ pub async fn get_device_movement_events(
                 .detection_source
                 .parse::<DetectionSource>()
                 .unwrap_or(DetectionSource::None),
+            movement_state: e.movement_state.as_deref().and_then(|s| s.parse().ok()),
             created_at: e.created_at,
         })
         .collect();
@@ -13,7 +13,8 @@ use chrono::Utc;
 use domain::models::{
     CreateInvitationRequest, CreateInvitationResponse, InvitationPagination, InvitationResponse,
     InvitationStatus, InvitationSummary, InvitedByInfo, ListInvitationsQuery,
-    ListInvitationsResponse, MAX_INVITATIONS_PER_ORG,
+    ListInvitationsResponse, MemberEventMember, MemberEventPayload, MemberEventType,
+    MAX_INVITATIONS_PER_ORG,
 };
 use persistence::entities::OrgMemberInviteEntity;
 use persistence::repositories::{
@@ -27,6 +28,8 @@ use validator::Validate;
 use crate::app::AppState;
 use crate::error::ApiError;
 use crate::extractors::api_key::ApiKeyAuth;
+use crate::services::content_filter::check_blocked_terms;
+use crate::services::org_webhook_delivery::emit_member_event;
 
 /// POST /api/admin/v1/organizations/:org_id/invitations
 ///
@@ -50,6 +53,8 @@ pub async fn create_invitation(
         .await?
         .ok_or_else(|| ApiError::NotFound("Organization not found".to_string()))?;
 
+    check_blocked_terms(&state.pool, org_id, &[("note", request.note.as_deref())]).await?;
+
     let invite_repo = OrgMemberInviteRepository::new(state.pool.clone());
     let org_user_repo = OrgUserRepository::new(state.pool.clone());
 
@@ -123,6 +128,21 @@ pub async fn create_invitation(
         role = %role,
         "Created organization member invitation"
     );
+    emit_member_event(
+        &state.pool,
+        &state.webhook_secrets,
+        MemberEventPayload::new(
+            MemberEventType::Invited,
+            org_id,
+            MemberEventMember {
+                email: Some(entity.email.clone()),
+                role: Some(entity.role.clone()),
+                invitation_id: Some(entity.id),
+                ..Default::default()
+            },
+            None,
+        ),
+    );
 
     // Build invite URL
     let invite_url = format!("{}/invite/{}", state.config.server.app_base_url, token);
@@ -0,0 +1,288 @@
+//! Organization ownership transfer route handlers.
+//!
+//! The owner initiates a transfer to an admin of the organization; roles are
+//! only swapped once that admin accepts. Both sides confirm with their
+//! password.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use chrono::Utc;
+use persistence::repositories::{
+    AuditLogRepository, OrgOwnershipTransferRepository, OrgUserRepository, UserRepository,
+};
+use tracing::info;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::app::AppState;
+use crate::error::ApiError;
+use crate::extractors::UserAuth;
+
+use domain::models::{
+    AcceptOrgTransferRequest, AuditAction, CreateAuditLogInput, InitiateOrgTransferRequest,
+    OrgOwnershipTransfer, OrgUser, OrgUserRole,
+};
+
+/// Create organization ownership transfer routes.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/",
+            get(get_transfer)
+                .post(initiate_transfer)
+                .delete(cancel_transfer),
+        )
+        .route("/accept", post(accept_transfer))
+}
+
+/// Look up the caller's membership in the organization.
+async fn require_org_user(
+    state: &AppState,
+    org_id: Uuid,
+    user_id: Uuid,
+) -> Result<OrgUser, ApiError> {
+    OrgUserRepository::new(state.pool.clone())
+        .find_by_org_and_user(org_id, user_id)
+        .await?
+        .ok_or_else(|| ApiError::Forbidden("User not in organization".to_string()))
+}
+
+/// Re-authenticate the caller with their password.
+async fn confirm_password(state: &AppState, user_id: Uuid, password: &str) -> Result<(), ApiError> {
+    let user = UserRepository::new(state.pool.clone())
+        .find_by_id(user_id)
+        .await?
+        .ok_or_else(|| ApiError::Unauthorized("User not found".to_string()))?;
+
+    let hash = user.password_hash.ok_or_else(|| {
+        ApiError::Forbidden("Set a password on your account to confirm this action".to_string())
+    })?;
+
+    if !shared::password::verify_password(password, &hash).unwrap_or(false) {
+        return Err(ApiError::Unauthorized("Invalid password".to_string()));
+    }
+    Ok(())
+}
+
+/// Get the pending ownership transfer.
+///
+/// GET /api/v1/organizations/:org_id/ownership-transfer
+///
+/// Visible to the owner and to the admin the transfer is addressed to.
+async fn get_transfer(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    user: UserAuth,
+) -> Result<Json<OrgOwnershipTransfer>, ApiError> {
+    let org_user = require_org_user(&state, org_id, user.user_id).await?;
+
+    let transfer = OrgOwnershipTransferRepository::new(state.pool.clone())
+        .find_pending(org_id)
+        .await?
+        .filter(|t| org_user.role == OrgUserRole::Owner || t.to_user_id == user.user_id)
+        .ok_or_else(|| ApiError::NotFound("No pending ownership transfer".to_string()))?;
+
+    Ok(Json(transfer))
+}
+
+/// Initiate an ownership transfer.
+///
+/// POST /api/v1/organizations/:org_id/ownership-transfer
+///
+/// Replaces any pending transfer. Returns 201 with the pending transfer.
+async fn initiate_transfer(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    user: UserAuth,
+    Json(request): Json<InitiateOrgTransferRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    request
+        .validate()
+        .map_err(|e| ApiError::Validation(e.to_string()))?;
+
+    let org_user = require_org_user(&state, org_id, user.user_id).await?;
+    if org_user.role != OrgUserRole::Owner {
+        return Err(ApiError::Forbidden(
+            "Only the organization owner can transfer ownership".to_string(),
+        ));
+    }
+
+    if request.new_owner_id == user.user_id {
+        return Err(ApiError::Validation(
+            "Cannot transfer ownership to yourself".to_string(),
+        ));
+    }
+
+    let target = OrgUserRepository::new(state.pool.clone())
+        .find_by_org_and_user(org_id, request.new_owner_id)
+        .await?
+        .ok_or_else(|| {
+            ApiError::Validation("Target user is not a member of this organization".to_string())
+        })?;
+    if target.role != OrgUserRole::Admin || target.suspended_at.is_some() {
+        return Err(ApiError::Validation(
+            "Ownership can only be transferred to an active admin".to_string(),
+        ));
+    }
+
+    confirm_password(&state, user.user_id, &request.password).await?;
+
+    let transfer = OrgOwnershipTransferRepository::new(state.pool.clone())
+        .create(
+            org_id,
+            user.user_id,
+            request.new_owner_id,
+            OrgOwnershipTransfer::expiry_from(Utc::now()),
+        )
+        .await?;
+
+    info!(
+        organization_id = %org_id,
+        transfer_id = %transfer.id,
+        from_user_id = %user.user_id,
+        to_user_id = %request.new_owner_id,
+        "Organization ownership transfer initiated"
+    );
+
+    let audit_input = CreateAuditLogInput::new(
+        org_id,
+        AuditAction::OrgOwnershipTransferInitiate,
+        "ownership_transfer",
+    )
+    .with_user_actor(user.user_id, None)
+    .with_resource_id(transfer.id.to_string())
+    .add_change(
+        "to_user_id",
+        None,
+        Some(serde_json::json!(transfer.to_user_id)),
+    );
+    AuditLogRepository::new(state.pool.clone()).insert_async(audit_input);
+
+    Ok((StatusCode::CREATED, Json(transfer)))
+}
+
+/// Cancel the pending ownership transfer.
+///
+/// DELETE /api/v1/organizations/:org_id/ownership-transfer
+async fn cancel_transfer(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    user: UserAuth,
+) -> Result<StatusCode, ApiError> {
+    let org_user = require_org_user(&state, org_id, user.user_id).await?;
+    if org_user.role != OrgUserRole::Owner {
+        return Err(ApiError::Forbidden(
+            "Only the organization owner can cancel an ownership transfer".to_string(),
+        ));
+    }
+
+    let repo = OrgOwnershipTransferRepository::new(state.pool.clone());
+    let transfer = repo
+        .find_pending(org_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("No pending ownership transfer".to_string()))?;
+
+    if !repo.cancel(transfer.id).await? {
+        return Err(ApiError::Conflict(
+            "Ownership transfer is no longer pending".to_string(),
+        ));
+    }
+
+    info!(
+        organization_id = %org_id,
+        transfer_id = %transfer.id,
+        "Organization ownership transfer cancelled"
+    );
+
+    let audit_input = CreateAuditLogInput::new(
+        org_id,
+        AuditAction::OrgOwnershipTransferCancel,
+        "ownership_transfer",
+    )
+    .with_user_actor(user.user_id, None)
+    .with_resource_id(transfer.id.to_string());
+    AuditLogRepository::new(state.pool.clone()).insert_async(audit_input);
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Accept the pending ownership transfer.
+///
+/// POST /api/v1/organizations/:org_id/ownership-transfer/accept
+///
+/// The accepting admin becomes the owner and the previous owner becomes an
+/// admin. Returns 409 if the transfer expired or either role changed since
+/// it was initiated.
+async fn accept_transfer(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    user: UserAuth,
+    Json(request): Json<AcceptOrgTransferRequest>,
+) -> Result<Json<OrgOwnershipTransfer>, ApiError> {
+    request
+        .validate()
+        .map_err(|e| ApiError::Validation(e.to_string()))?;
+
+    require_org_user(&state, org_id, user.user_id).await?;
+
+    let repo = OrgOwnershipTransferRepository::new(state.pool.clone());
+    let pending = repo
+        .find_pending(org_id)
+        .await?
+        .filter(|t| t.to_user_id == user.user_id)
+        .ok_or_else(|| ApiError::NotFound("No pending ownership transfer".to_string()))?;
+
+    if !pending.is_open(Utc::now()) {
+        return Err(ApiError::Conflict(
+            "Ownership transfer has expired".to_string(),
+        ));
+    }
+
+    confirm_password(&state, user.user_id, &request.password).await?;
+
+    let transfer = repo
+        .accept(pending.id, user.user_id)
+        .await?
+        .ok_or_else(|| {
+            ApiError::Conflict("Ownership transfer can no longer be accepted".to_string())
+        })?;
+
+    info!(
+        organization_id = %org_id,
+        transfer_id = %transfer.id,
+        previous_owner_id = %transfer.from_user_id,
+        new_owner_id = %transfer.to_user_id,
+        "Organization ownership transferred"
+    );
+
+    let audit_input = CreateAuditLogInput::new(
+        org_id,
+        AuditAction::OrgOwnershipTransferAccept,
+        "ownership_transfer",
+    )
+    .with_user_actor(user.user_id, None)
+    .with_resource_id(transfer.id.to_string())
+    .add_change(
+        "owner_id",
+        Some(serde_json::json!(transfer.from_user_id)),
+        Some(serde_json::json!(transfer.to_user_id)),
+    );
+    AuditLogRepository::new(state.pool.clone()).insert_async(audit_input);
+
+    Ok(Json(transfer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_router_creation() {
+        let _router: Router<AppState> = router();
+    }
+}
@@ -9,14 +9,19 @@ use axum::{
     Json,
 };
 use chrono::Utc;
+use domain::models::speed_violation::{SpeedViolationEventPayload, SPEED_VIOLATION_EVENT_TYPE};
 use domain::models::{
-    CreateOrgWebhookRequest, ListOrgWebhooksResponse, ListWebhookDeliveriesQuery,
-    ListWebhookDeliveriesResponse, OrgWebhookResponse, RetryDeliveryResponse,
-    TestOrgWebhookRequest, TestOrgWebhookResponse, UpdateOrgWebhookRequest,
-    WebhookDeliveryResponse, WebhookPagination, WebhookStatsResponse, MAX_WEBHOOKS_PER_ORG,
+    event_type_description, payload_content_type, CreateOrgWebhookRequest,
+    ListOrgWebhookEventTypesResponse, ListOrgWebhooksResponse, ListWebhookDeadLettersQuery,
+    ListWebhookDeadLettersResponse, ListWebhookDeliveriesQuery, ListWebhookDeliveriesResponse,
+    MemberEventPayload, MemberEventType, OrgWebhookEventTypeInfo, OrgWebhookResponse,
+    PurgeDeadLettersResponse, RequeueDeadLetterResponse, RetryDeliveryResponse,
+    SetOrgWebhookClientCertificateRequest, TestOrgWebhookRequest, TestOrgWebhookResponse,
+    UpdateOrgWebhookRequest, WebhookDeadLetterResponse, WebhookDeliveryResponse, WebhookPagination,
+    WebhookRetryPolicy, WebhookStatsResponse, MAX_WEBHOOKS_PER_ORG, SUPPORTED_EVENT_TYPES,
 };
 use hmac::{Hmac, Mac};
-use persistence::entities::{OrgWebhookEntity, WebhookDeliveryEntity};
+use persistence::entities::OrgWebhookEntity;
 use persistence::repositories::{
     OrgWebhookRepository, OrganizationRepository, WebhookDeliveryRepository,
 };
@@ -31,6 +36,11 @@ use validator::Validate;
 use crate::app::AppState;
 use crate::error::ApiError;
 use crate::extractors::api_key::ApiKeyAuth;
+use crate::services::content_filter::check_blocked_terms;
+use crate::services::webhook_client_cert::{client_identity, mtls_client};
+use crate::services::webhook_delivery::{
+    sign_timestamped_payload, DeliveryResponse, SIGNATURE_HEADER,
+};
 
 /// POST /api/admin/v1/organizations/:org_id/webhooks
 ///
@@ -54,12 +64,21 @@ pub async fn create_webhook(
         .validate_event_types()
         .map_err(ApiError::Validation)?;
 
+    let retry_policy = request
+        .retry_policy
+        .as_ref()
+        .map(WebhookRetryPolicy::to_value)
+        .transpose()
+        .map_err(ApiError::Validation)?;
+
     // Verify organization exists
     let org_repo = OrganizationRepository::new(state.pool.clone());
     if org_repo.find_by_id(org_id).await?.is_none() {
         return Err(ApiError::NotFound("Organization not found".to_string()));
     }
 
+    check_blocked_terms(&state.pool, org_id, &[("name", Some(&request.name))]).await?;
+
     let webhook_repo = OrgWebhookRepository::new(state.pool.clone());
 
     // Check webhook limit
@@ -86,6 +105,8 @@ pub async fn create_webhook(
             &request.target_url,
             &request.secret,
             &request.event_types,
+            retry_policy,
+            request.format,
         )
         .await?;
 
@@ -131,6 +152,40 @@ pub async fn list_webhooks(
     Ok(Json(ListOrgWebhooksResponse { webhooks }))
 }
 
+/// GET /api/admin/v1/organizations/:org_id/webhooks/event-types
+///
+/// List the event types webhooks can subscribe to, with an example payload
+/// of each delivered event type.
+pub async fn list_event_types(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+) -> Result<Json<ListOrgWebhookEventTypesResponse>, ApiError> {
+    let org_repo = OrganizationRepository::new(state.pool.clone());
+    if org_repo.find_by_id(org_id).await?.is_none() {
+        return Err(ApiError::NotFound("Organization not found".to_string()));
+    }
+
+    Ok(Json(ListOrgWebhookEventTypesResponse {
+        event_types: SUPPORTED_EVENT_TYPES
+            .iter()
+            .map(|event_type| OrgWebhookEventTypeInfo {
+                event_type: event_type.to_string(),
+                description: event_type_description(event_type).to_string(),
+                example_payload: example_payload(event_type, org_id),
+            })
+            .collect(),
+    }))
+}
+
+/// Example payload of a delivered event type.
+fn example_payload(event_type: &str, org_id: Uuid) -> Option<serde_json::Value> {
+    if event_type == SPEED_VIOLATION_EVENT_TYPE {
+        return serde_json::to_value(SpeedViolationEventPayload::example(org_id)).ok();
+    }
+    let event_type: MemberEventType = event_type.parse().ok()?;
+    serde_json::to_value(MemberEventPayload::example(event_type, org_id)).ok()
+}
+
 /// GET /api/admin/v1/organizations/:org_id/webhooks/:webhook_id
 ///
 /// Get details for a specific webhook.
@@ -189,12 +244,21 @@ pub async fn update_webhook(
         .validate_event_types()
         .map_err(ApiError::Validation)?;
 
+    let retry_policy = request
+        .retry_policy
+        .as_ref()
+        .map(WebhookRetryPolicy::to_value)
+        .transpose()
+        .map_err(ApiError::Validation)?;
+
     // Verify organization exists
     let org_repo = OrganizationRepository::new(state.pool.clone());
     if org_repo.find_by_id(org_id).await?.is_none() {
         return Err(ApiError::NotFound("Organization not found".to_string()));
     }
 
+    check_blocked_terms(&state.pool, org_id, &[("name", request.name.as_deref())]).await?;
+
     let webhook_repo = OrgWebhookRepository::new(state.pool.clone());
 
     // Check if webhook exists
@@ -225,6 +289,8 @@ pub async fn update_webhook(
             request.secret.as_deref(),
             request.enabled,
             request.event_types.as_deref(),
+            retry_policy,
+            request.format,
         )
         .await?
         .ok_or_else(|| ApiError::NotFound("Webhook not found".to_string()))?;
@@ -277,6 +343,87 @@ pub async fn delete_webhook(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// PUT /api/admin/v1/organizations/:org_id/webhooks/:webhook_id/client-certificate
+///
+/// Set the client certificate the webhook presents for mutual TLS. The
+/// private key is encrypted at rest and never returned.
+pub async fn set_client_certificate(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
+    Path((org_id, webhook_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<SetOrgWebhookClientCertificateRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    request
+        .validate()
+        .map_err(|e| ApiError::Validation(format!("Validation error: {}", e)))?;
+
+    if !state.webhook_secrets.has_client_cert_key() {
+        return Err(ApiError::ServiceUnavailable(
+            "Client certificates require PM__SECURITY__WEBHOOK_CLIENT_CERT_KEY".to_string(),
+        ));
+    }
+
+    // Reject certificates reqwest cannot present before storing them
+    client_identity(&request.certificate_pem, request.private_key_pem.as_bytes())
+        .map_err(ApiError::Validation)?;
+    let encrypted_key = state
+        .webhook_secrets
+        .seal_client_key(request.private_key_pem.as_bytes())
+        .map_err(ApiError::Internal)?;
+
+    let org_repo = OrganizationRepository::new(state.pool.clone());
+    if org_repo.find_by_id(org_id).await?.is_none() {
+        return Err(ApiError::NotFound("Organization not found".to_string()));
+    }
+
+    let entity = OrgWebhookRepository::new(state.pool.clone())
+        .set_client_certificate(
+            webhook_id,
+            org_id,
+            Some(&request.certificate_pem),
+            Some(&encrypted_key),
+        )
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Webhook not found".to_string()))?;
+
+    info!(
+        admin_key_id = auth.api_key_id,
+        organization_id = %org_id,
+        webhook_id = %webhook_id,
+        "Set organization webhook client certificate"
+    );
+
+    Ok(Json(entity_to_response(entity)))
+}
+
+/// DELETE /api/admin/v1/organizations/:org_id/webhooks/:webhook_id/client-certificate
+///
+/// Stop presenting a client certificate.
+pub async fn delete_client_certificate(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
+    Path((org_id, webhook_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, ApiError> {
+    let org_repo = OrganizationRepository::new(state.pool.clone());
+    if org_repo.find_by_id(org_id).await?.is_none() {
+        return Err(ApiError::NotFound("Organization not found".to_string()));
+    }
+
+    let entity = OrgWebhookRepository::new(state.pool.clone())
+        .set_client_certificate(webhook_id, org_id, None, None)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Webhook not found".to_string()))?;
+
+    info!(
+        admin_key_id = auth.api_key_id,
+        organization_id = %org_id,
+        webhook_id = %webhook_id,
+        "Removed organization webhook client certificate"
+    );
+
+    Ok(Json(entity_to_response(entity)))
+}
+
 /// Webhook delivery timeout in seconds for test delivery.
 const TEST_WEBHOOK_TIMEOUT_SECS: u64 = 10;
 
@@ -309,16 +456,27 @@ pub async fn test_webhook(
         .await?
         .ok_or_else(|| ApiError::NotFound("Webhook not found".to_string()))?;
 
-    // Create test payload
+    // Create test payload; delivered event types get their example payload
     let event_type = request.get_event_type();
-    let test_payload = json!({
-        "event_type": event_type,
-        "test": true,
-        "timestamp": Utc::now().timestamp_millis(),
-        "organization_id": org_id.to_string(),
-        "message": "This is a test webhook delivery"
+    let test_payload = example_payload(event_type, org_id).unwrap_or_else(|| {
+        json!({
+            "event_type": event_type,
+            "test": true,
+            "timestamp": Utc::now().timestamp_millis(),
+            "organization_id": org_id.to_string(),
+            "message": "This is a test webhook delivery"
+        })
     });
 
+    let test_payload = webhook.payload_format().wrap(
+        event_type,
+        &format!("/organizations/{}", org_id),
+        None,
+        Utc::now(),
+        test_payload,
+    );
+    let policy = webhook.delivery_policy();
+
     // Create delivery record
     let delivery = delivery_repo
         .create(webhook_id, None, event_type, &test_payload)
@@ -329,19 +487,28 @@ pub async fn test_webhook(
         .map_err(|e| ApiError::Internal(format!("Failed to serialize payload: {}", e)))?;
 
     let signature = sign_payload(&payload_json, &webhook.secret)?;
-
-    // Create HTTP client with timeout
-    let client = Client::builder()
-        .timeout(Duration::from_secs(TEST_WEBHOOK_TIMEOUT_SECS))
-        .build()
-        .map_err(|e| ApiError::Internal(format!("Failed to create HTTP client: {}", e)))?;
+    let timestamped_signature =
+        sign_timestamped_payload(&payload_json, &webhook.secret, Utc::now().timestamp())
+            .map_err(|e| ApiError::Internal(e.to_string()))?;
+
+    // Create HTTP client with timeout, presenting the client certificate (if any)
+    let timeout = Duration::from_secs(TEST_WEBHOOK_TIMEOUT_SECS);
+    let client = match (&webhook.client_cert_pem, &webhook.client_key_encrypted) {
+        (Some(cert), Some(key)) => mtls_client(&state.webhook_secrets, cert, key, Some(timeout))
+            .map_err(ApiError::Internal)?,
+        _ => Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| ApiError::Internal(format!("Failed to create HTTP client: {}", e)))?,
+    };
 
     // Deliver the test webhook
     let start_time = Instant::now();
     let result = client
         .post(&webhook.target_url)
-        .header("Content-Type", "application/json")
+        .header("Content-Type", payload_content_type(&test_payload))
         .header("X-Webhook-Signature", &signature)
+        .header(SIGNATURE_HEADER, &timestamped_signature)
         .header("X-Webhook-Test", "true")
         .body(payload_json.clone())
         .send()
@@ -352,17 +519,34 @@ pub async fn test_webhook(
     // Update delivery record with result
     let (success, response_code, error) = match result {
         Ok(response) => {
-            let status = response.status().as_u16() as i32;
-            let is_success = (200..300).contains(&status);
+            let response = DeliveryResponse::read(response, start_time).await;
+            let status = response.status_code as i32;
+            let is_success = response.is_success();
             delivery_repo
-                .update_attempt(delivery.delivery_id, is_success, Some(status), None)
+                .update_attempt(
+                    delivery.delivery_id,
+                    is_success,
+                    Some(status),
+                    None,
+                    Some(response.latency_ms),
+                    Some(&response.body),
+                    &policy,
+                )
                 .await?;
             (is_success, Some(status), None)
         }
         Err(e) => {
             let error_msg = e.to_string();
             delivery_repo
-                .update_attempt(delivery.delivery_id, false, None, Some(&error_msg))
+                .update_attempt(
+                    delivery.delivery_id,
+                    false,
+                    None,
+                    Some(&error_msg),
+                    None,
+                    None,
+                    &policy,
+                )
                 .await?;
             (false, None, Some(error_msg))
         }
@@ -443,7 +627,10 @@ pub async fn list_deliveries(
     );
 
     let response = ListWebhookDeliveriesResponse {
-        deliveries: deliveries.into_iter().map(delivery_to_response).collect(),
+        deliveries: deliveries
+            .into_iter()
+            .map(WebhookDeliveryResponse::from)
+            .collect(),
         pagination: WebhookPagination {
             page,
             per_page,
@@ -520,6 +707,148 @@ pub async fn retry_delivery(
     Ok(Json(response))
 }
 
+/// GET /api/admin/v1/organizations/:org_id/webhooks/:webhook_id/dead-letters
+///
+/// List deliveries of a webhook that failed after their final retry.
+pub async fn list_dead_letters(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
+    Path((org_id, webhook_id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<ListWebhookDeadLettersQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    // Validate query
+    query
+        .validate()
+        .map_err(|e| ApiError::Validation(e.to_string()))?;
+
+    // Verify organization exists
+    let org_repo = OrganizationRepository::new(state.pool.clone());
+    if org_repo.find_by_id(org_id).await?.is_none() {
+        return Err(ApiError::NotFound("Organization not found".to_string()));
+    }
+
+    let webhook_repo = OrgWebhookRepository::new(state.pool.clone());
+    let delivery_repo = WebhookDeliveryRepository::new(state.pool.clone());
+
+    // Verify webhook exists and belongs to organization
+    if webhook_repo.find_by_id(webhook_id, org_id).await?.is_none() {
+        return Err(ApiError::NotFound("Webhook not found".to_string()));
+    }
+
+    let page = query.page.unwrap_or(1);
+    let per_page = query.per_page.unwrap_or(20);
+    let offset = ((page - 1) * per_page) as i64;
+
+    let dead_letters = delivery_repo
+        .list_dead_letters(webhook_id, per_page as i64, offset)
+        .await?;
+    let total = delivery_repo.count_dead_letters(webhook_id).await?;
+    let total_pages = ((total as f64) / (per_page as f64)).ceil() as u32;
+
+    info!(
+        admin_key_id = auth.api_key_id,
+        organization_id = %org_id,
+        webhook_id = %webhook_id,
+        dead_letter_count = dead_letters.len(),
+        "Listed webhook dead letters"
+    );
+
+    let response = ListWebhookDeadLettersResponse {
+        dead_letters: dead_letters
+            .into_iter()
+            .map(WebhookDeadLetterResponse::from)
+            .collect(),
+        pagination: WebhookPagination {
+            page,
+            per_page,
+            total,
+            total_pages,
+        },
+    };
+
+    Ok(Json(response))
+}
+
+/// POST /api/admin/v1/organizations/:org_id/webhooks/:webhook_id/dead-letters/:dead_letter_id/requeue
+///
+/// Requeue a dead-lettered delivery as a new pending delivery.
+pub async fn requeue_dead_letter(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
+    Path((org_id, webhook_id, dead_letter_id)): Path<(Uuid, Uuid, Uuid)>,
+) -> Result<impl IntoResponse, ApiError> {
+    // Verify organization exists
+    let org_repo = OrganizationRepository::new(state.pool.clone());
+    if org_repo.find_by_id(org_id).await?.is_none() {
+        return Err(ApiError::NotFound("Organization not found".to_string()));
+    }
+
+    let webhook_repo = OrgWebhookRepository::new(state.pool.clone());
+    let delivery_repo = WebhookDeliveryRepository::new(state.pool.clone());
+
+    // Verify webhook exists and belongs to organization
+    if webhook_repo.find_by_id(webhook_id, org_id).await?.is_none() {
+        return Err(ApiError::NotFound("Webhook not found".to_string()));
+    }
+
+    let delivery = delivery_repo
+        .requeue_dead_letter(webhook_id, dead_letter_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Dead letter not found".to_string()))?;
+
+    info!(
+        admin_key_id = auth.api_key_id,
+        organization_id = %org_id,
+        webhook_id = %webhook_id,
+        dead_letter_id = %dead_letter_id,
+        delivery_id = %delivery.delivery_id,
+        "Requeued dead-lettered webhook delivery"
+    );
+
+    let response = RequeueDeadLetterResponse {
+        success: true,
+        delivery_id: delivery.delivery_id,
+        message: "Dead letter has been requeued for delivery".to_string(),
+    };
+
+    Ok(Json(response))
+}
+
+/// DELETE /api/admin/v1/organizations/:org_id/webhooks/:webhook_id/dead-letters
+///
+/// Delete all dead letters of a webhook.
+pub async fn purge_dead_letters(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
+    Path((org_id, webhook_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, ApiError> {
+    // Verify organization exists
+    let org_repo = OrganizationRepository::new(state.pool.clone());
+    if org_repo.find_by_id(org_id).await?.is_none() {
+        return Err(ApiError::NotFound("Organization not found".to_string()));
+    }
+
+    let webhook_repo = OrgWebhookRepository::new(state.pool.clone());
+    let delivery_repo = WebhookDeliveryRepository::new(state.pool.clone());
+
+    // Verify webhook exists and belongs to organization
+    if webhook_repo.find_by_id(webhook_id, org_id).await?.is_none() {
+        return Err(ApiError::NotFound("Webhook not found".to_string()));
+    }
+
+    let purged = delivery_repo.purge_dead_letters(webhook_id).await?;
+
+    info!(
+        admin_key_id = auth.api_key_id,
+        organization_id = %org_id,
+        webhook_id = %webhook_id,
+        purged = purged,
+        "Purged webhook dead letters"
+    );
+
+    Ok(Json(PurgeDeadLettersResponse { purged }))
+}
+
 /// GET /api/admin/v1/organizations/:org_id/webhooks/:webhook_id/stats
 ///
 /// Get delivery statistics for a webhook.
@@ -602,24 +931,12 @@ fn sign_payload(payload: &str, secret: &str) -> Result<String, ApiError> {
     Ok(format!("sha256={}", signature))
 }
 
-/// Convert delivery entity to response.
-fn delivery_to_response(entity: WebhookDeliveryEntity) -> WebhookDeliveryResponse {
-    WebhookDeliveryResponse {
-        id: entity.delivery_id,
-        event_id: entity.event_id,
-        event_type: entity.event_type,
-        status: entity.status,
-        attempts: entity.attempts,
-        last_attempt_at: entity.last_attempt_at,
-        next_retry_at: entity.next_retry_at,
-        response_code: entity.response_code,
-        error_message: entity.error_message,
-        created_at: entity.created_at,
-    }
-}
-
 /// Convert entity to response (excludes secret for security).
 fn entity_to_response(entity: OrgWebhookEntity) -> OrgWebhookResponse {
+    let retry_policy = entity.delivery_policy();
+    let retry_policy_overridden = entity.retry_policy_overridden();
+    let has_client_certificate = entity.has_client_certificate();
+    let format = entity.payload_format();
     OrgWebhookResponse {
         id: entity.webhook_id,
         name: entity.name,
@@ -628,6 +945,10 @@ fn entity_to_response(entity: OrgWebhookEntity) -> OrgWebhookResponse {
         event_types: entity.event_types,
         consecutive_failures: entity.consecutive_failures,
         circuit_open_until: entity.circuit_open_until,
+        retry_policy,
+        retry_policy_overridden,
+        format,
+        has_client_certificate,
         created_at: entity.created_at,
         updated_at: entity.updated_at,
     }
@@ -645,6 +966,8 @@ mod tests {
             target_url: "https://api.example.com/webhooks".to_string(),
             secret: "whsec_testsecretkey123456".to_string(),
             event_types: vec!["device.enrolled".to_string()],
+            retry_policy: None,
+            format: Default::default(),
         };
         assert!(valid.validate().is_ok());
         assert!(valid.validate_https().is_ok());
@@ -659,6 +982,8 @@ mod tests {
             secret: None,
             enabled: Some(false),
             event_types: Some(vec!["member.joined".to_string()]),
+            retry_policy: None,
+            format: None,
         };
         assert!(valid.validate().is_ok());
         assert!(valid.validate_https().is_ok());
@@ -0,0 +1,204 @@
+//! Organization data export routes.
+//!
+//! Organization admins export all of an organization's data as a ZIP
+//! archive. The archive is built in the background; clients poll the job
+//! and download the archive once it completes.
+
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{header, StatusCode},
+    middleware,
+    response::Response,
+    routing::{get, post},
+    Json, Router,
+};
+use chrono::Utc;
+use domain::models::{ExportFormat, ExportJobKind, ExportJobResponse, ExportJobStatus};
+use persistence::repositories::{
+    DeviceRepository, ExportJob, ExportJobRepository, OrganizationRepository,
+};
+use std::path::PathBuf;
+use tokio::fs::File;
+use tokio_util::io::ReaderStream;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::error::ApiError;
+use crate::middleware::{concurrency_limit, export_statement_timeout, RouteClass};
+use crate::routes::privacy_zones::load_privacy_zones;
+use crate::services::organization_export::{
+    OrganizationExportService, ORGANIZATION_EXPORT_CONTENT_TYPE,
+};
+
+/// Create organization export router.
+///
+/// Routes:
+/// - POST /api/admin/v1/organizations/:org_id/export - Start an export
+/// - GET /api/admin/v1/organizations/:org_id/export/:job_id - Export job status
+/// - GET /api/admin/v1/organizations/:org_id/export/:job_id/download - Download the archive
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/",
+            post(create_organization_export)
+                .layer(middleware::from_fn_with_state(
+                    RouteClass::Export,
+                    concurrency_limit,
+                ))
+                .layer(middleware::from_fn(export_statement_timeout)),
+        )
+        .route("/:job_id", get(get_organization_export))
+        .route("/:job_id/download", get(download_organization_export))
+}
+
+fn job_response(org_id: Uuid, job: ExportJob) -> ExportJobResponse {
+    let status = job.status_at(Utc::now());
+    let download_url = (status == ExportJobStatus::Completed).then(|| {
+        format!(
+            "/api/admin/v1/organizations/{}/export/{}/download",
+            org_id, job.job_id
+        )
+    });
+    ExportJobResponse {
+        job_id: job.job_id,
+        status,
+        record_count: job.record_count,
+        download_url,
+        expires_at: Some(job.expires_at),
+        error: job.error_message,
+    }
+}
+
+/// Find an organization export job.
+async fn find_job(state: &AppState, org_id: Uuid, job_id: &str) -> Result<ExportJob, ApiError> {
+    ExportJobRepository::new(state.pool.clone())
+        .find_by_job_id(org_id, ExportJobKind::Organization, job_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Export job not found".to_string()))
+}
+
+/// Start an export of all of an organization's data.
+///
+/// POST /api/admin/v1/organizations/:org_id/export
+///
+/// The archive holds the users, managed devices, device policies, location
+/// history and audit logs; poll the returned job until it completes.
+/// Returns 409 while another export of the organization is running.
+#[axum::debug_handler(state = AppState)]
+async fn create_organization_export(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+) -> Result<(StatusCode, Json<ExportJobResponse>), ApiError> {
+    OrganizationRepository::new(state.pool.clone())
+        .find_by_id(org_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Organization not found".to_string()))?;
+
+    let repo = ExportJobRepository::new(state.pool.clone());
+    if repo
+        .has_running_job(org_id, ExportJobKind::Organization)
+        .await?
+    {
+        return Err(ApiError::Conflict(
+            "An export of this organization is already running".to_string(),
+        ));
+    }
+
+    // Admins are not the devices' owners, so every owner's privacy zones apply
+    let devices = DeviceRepository::new(state.pool.clone())
+        .list_org_managed_devices(org_id)
+        .await?;
+    let locations =
+        load_privacy_zones(&state.pool, None, devices.iter().map(|d| d.owner_user_id)).await?;
+
+    let job = repo
+        .create(
+            org_id,
+            ExportJobKind::Organization,
+            ExportFormat::Json,
+            None,
+        )
+        .await?;
+
+    info!(org_id = %org_id, job_id = %job.job_id, "Organization export started");
+
+    let service = OrganizationExportService::new(
+        state.pool.clone(),
+        PathBuf::from(&state.config.reports.reports_dir),
+    );
+    let job_id = job.job_id.clone();
+    tokio::spawn(async move {
+        service.process_job(&job_id, org_id, locations).await;
+    });
+
+    Ok((StatusCode::ACCEPTED, Json(job_response(org_id, job))))
+}
+
+/// Get the status of an organization export.
+///
+/// GET /api/admin/v1/organizations/:org_id/export/:job_id
+#[axum::debug_handler(state = AppState)]
+async fn get_organization_export(
+    State(state): State<AppState>,
+    Path((org_id, job_id)): Path<(Uuid, String)>,
+) -> Result<Json<ExportJobResponse>, ApiError> {
+    let job = find_job(&state, org_id, &job_id).await?;
+    Ok(Json(job_response(org_id, job)))
+}
+
+/// Download a completed organization export.
+///
+/// GET /api/admin/v1/organizations/:org_id/export/:job_id/download
+#[axum::debug_handler(state = AppState)]
+async fn download_organization_export(
+    State(state): State<AppState>,
+    Path((org_id, job_id)): Path<(Uuid, String)>,
+) -> Result<Response, ApiError> {
+    let job = find_job(&state, org_id, &job_id).await?;
+
+    let status = job.status_at(Utc::now());
+    if status != ExportJobStatus::Completed {
+        return Err(ApiError::Validation(format!(
+            "Export is not ready for download. Status: {}",
+            status
+        )));
+    }
+    let file_name = job
+        .file_path
+        .as_deref()
+        .ok_or_else(|| ApiError::NotFound("Export file not found".to_string()))?;
+
+    let full_path = PathBuf::from(&state.config.reports.reports_dir).join(file_name);
+    let file = File::open(&full_path).await.map_err(|e| {
+        tracing::error!(
+            error = %e,
+            path = %full_path.display(),
+            "Failed to open organization export file"
+        );
+        ApiError::NotFound("Export file not found on disk".to_string())
+    })?;
+    let metadata = file.metadata().await.map_err(|e| {
+        tracing::error!(error = %e, "Failed to get file metadata");
+        ApiError::Internal("Failed to read export file".to_string())
+    })?;
+
+    let download_filename = format!(
+        "organization_export_{}.zip",
+        job.created_at.format("%Y%m%d_%H%M%S")
+    );
+
+    Response::builder()
+        .header(header::CONTENT_TYPE, ORGANIZATION_EXPORT_CONTENT_TYPE)
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", download_filename),
+        )
+        .header(header::CONTENT_LENGTH, metadata.len())
+        .body(Body::from_stream(ReaderStream::new(file)))
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to build response");
+            ApiError::Internal("Failed to build response".to_string())
+        })
+}
@@ -8,10 +8,12 @@ use axum::{
     response::IntoResponse,
     Json,
 };
+use domain::models::organization_settings::normalize_blocked_terms;
 use domain::models::{
     OrganizationSettings, OrganizationSettingsResponse, UpdateOrganizationSettingsRequest,
     VerifyPinRequest, VerifyPinResponse,
 };
+use domain::services::filter_limit;
 use tracing::{info, warn};
 use uuid::Uuid;
 use validator::Validate;
@@ -42,17 +44,7 @@ pub async fn get_organization_settings(
     let entity = settings_repo.get_or_create(org_id).await?;
 
     // Convert to domain model
-    let settings = OrganizationSettings {
-        id: entity.id,
-        organization_id: entity.organization_id,
-        has_unlock_pin: entity.unlock_pin_hash.is_some(),
-        unlock_pin_hash: entity.unlock_pin_hash,
-        default_daily_limit_minutes: entity.default_daily_limit_minutes,
-        notifications_enabled: entity.notifications_enabled,
-        auto_approve_unlock_requests: entity.auto_approve_unlock_requests,
-        created_at: entity.created_at,
-        updated_at: entity.updated_at,
-    };
+    let settings: OrganizationSettings = entity.into();
 
     info!(
         admin_key_id = auth.api_key_id,
@@ -113,6 +105,26 @@ pub async fn update_organization_settings(
     let auto_approve_unlock_requests = request
         .auto_approve_unlock_requests
         .unwrap_or(current.auto_approve_unlock_requests);
+    // Zero clears a location filter limit
+    let max_location_accuracy_meters = match request.max_location_accuracy_meters {
+        Some(value) => filter_limit(Some(value)),
+        None => current.max_location_accuracy_meters.map(f64::from),
+    };
+    let max_location_speed_mps = match request.max_location_speed_mps {
+        Some(value) => filter_limit(Some(value)),
+        None => current.max_location_speed_mps.map(f64::from),
+    };
+    let blocked_terms = match request.blocked_terms {
+        Some(ref terms) => normalize_blocked_terms(terms),
+        None => current.blocked_terms.clone(),
+    };
+    let webhook_retry_policy = if request.clear_webhook_retry_policy {
+        None
+    } else if let Some(ref policy) = request.webhook_retry_policy {
+        Some(policy.to_value().map_err(ApiError::Validation)?)
+    } else {
+        current.webhook_retry_policy.clone()
+    };
 
     // Update settings
     let entity = settings_repo
@@ -122,21 +134,15 @@ pub async fn update_organization_settings(
             default_daily_limit_minutes,
             notifications_enabled,
             auto_approve_unlock_requests,
+            max_location_accuracy_meters,
+            max_location_speed_mps,
+            &blocked_terms,
+            webhook_retry_policy.as_ref(),
         )
         .await?;
 
     // Convert to domain model
-    let settings = OrganizationSettings {
-        id: entity.id,
-        organization_id: entity.organization_id,
-        has_unlock_pin: entity.unlock_pin_hash.is_some(),
-        unlock_pin_hash: entity.unlock_pin_hash,
-        default_daily_limit_minutes: entity.default_daily_limit_minutes,
-        notifications_enabled: entity.notifications_enabled,
-        auto_approve_unlock_requests: entity.auto_approve_unlock_requests,
-        created_at: entity.created_at,
-        updated_at: entity.updated_at,
-    };
+    let settings: OrganizationSettings = entity.into();
 
     info!(
         admin_key_id = auth.api_key_id,
@@ -245,6 +251,10 @@ mod tests {
             default_daily_limit_minutes: 120,
             notifications_enabled: true,
             auto_approve_unlock_requests: false,
+            max_location_accuracy_meters: None,
+            max_location_speed_mps: None,
+            blocked_terms: vec![],
+            webhook_retry_policy: None,
         };
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("\"has_unlock_pin\":true"));
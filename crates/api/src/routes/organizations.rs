@@ -1,7 +1,8 @@
 //! Organization admin API routes.
 //!
 //! Provides administrative endpoints for B2B organization management.
-//! These routes require platform admin authentication.
+//! These routes require platform admin authentication, except for
+//! self-service trial creation which any authenticated user can use.
 
 use axum::{
     extract::{Extension, Path, Query, State},
@@ -11,9 +12,11 @@ use axum::{
 };
 use domain::models::{
     validate_permissions, AddOrgUserRequest, CreateOrganizationRequest, CreateOrganizationResponse,
-    ListOrgUsersQuery, ListOrgUsersResponse, ListOrganizationsQuery, ListOrganizationsResponse,
-    OrgUserPagination, OrgUserResponse, OrgUserRole, OrganizationPagination, PlanType,
-    SuspendOrganizationRequest, UpdateOrgUserRequest, UpdateOrganizationRequest,
+    CreateTrialOrganizationRequest, ListOrgUsersQuery, ListOrgUsersResponse,
+    ListOrganizationsQuery, ListOrganizationsResponse, MemberEventMember, MemberEventPayload,
+    MemberEventType, OrgUserPagination, OrgUserResponse, OrgUserRole, OrganizationPagination,
+    PlanSimulationQuery, PlanSimulationResponse, PlanType, SuspendOrganizationRequest,
+    UpdateOrgUserRequest, UpdateOrganizationRequest,
 };
 use tracing::{info, warn};
 use uuid::Uuid;
@@ -22,9 +25,11 @@ use validator::Validate;
 use crate::app::AppState;
 use crate::error::ApiError;
 use crate::extractors::api_key::ApiKeyAuth;
+use crate::extractors::UserAuth;
+use crate::services::org_webhook_delivery::emit_member_event;
 use persistence::repositories::{
     default_invite_expiration, generate_org_member_invite_token, OrgMemberInviteRepository,
-    OrgUserRepository, OrganizationRepository, UserRepository,
+    OrgUserRepository, OrganizationRepository, OrganizationRoleRepository, UserRepository,
 };
 
 /// POST /api/admin/v1/organizations
@@ -83,6 +88,108 @@ pub async fn create_organization(
     ))
 }
 
+/// POST /api/v1/organizations
+///
+/// Create a trial organization owned by the authenticated user.
+/// Provisions the trial plan quotas, the default organization roles and the
+/// creator's owner membership. Each user can own one active trial.
+pub async fn create_trial_organization(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Json(request): Json<CreateTrialOrganizationRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    request
+        .validate()
+        .map_err(|e| ApiError::Validation(format!("Validation error: {}", e)))?;
+
+    let user = UserRepository::new(state.pool.clone())
+        .find_by_id(user_auth.user_id)
+        .await?
+        .ok_or_else(|| ApiError::Unauthorized("User not found".to_string()))?;
+
+    let repo = OrganizationRepository::new(state.pool.clone());
+
+    if repo.user_owns_plan(user.id, PlanType::Trial).await? {
+        return Err(ApiError::Conflict(
+            "You already own a trial organization".to_string(),
+        ));
+    }
+
+    if repo.slug_exists(&request.slug).await? {
+        return Err(ApiError::Conflict(format!(
+            "Organization with slug '{}' already exists",
+            request.slug
+        )));
+    }
+
+    let plan_type = PlanType::Trial;
+    let (max_users, max_devices, max_groups) = plan_type.default_limits();
+    let billing_email = request.billing_email.unwrap_or_else(|| user.email.clone());
+
+    let organization = repo
+        .create(
+            &request.name,
+            &request.slug,
+            &billing_email,
+            plan_type,
+            max_users,
+            max_devices,
+            max_groups,
+            &serde_json::json!({}),
+        )
+        .await?;
+
+    // Without an owner or roles the organization is unusable, so roll it
+    // back if provisioning fails.
+    if let Err(e) = provision_trial_organization(&state, organization.id, user.id).await {
+        warn!(
+            organization_id = %organization.id,
+            user_id = %user.id,
+            error = %e,
+            "Failed to provision trial organization, deactivating it"
+        );
+        repo.soft_delete(organization.id).await?;
+        return Err(e);
+    }
+
+    info!(
+        user_id = %user.id,
+        organization_id = %organization.id,
+        slug = %organization.slug,
+        "Created self-service trial organization"
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreateOrganizationResponse { organization }),
+    ))
+}
+
+/// Add the creator as owner and set up the default organization roles.
+async fn provision_trial_organization(
+    state: &AppState,
+    organization_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), ApiError> {
+    let role = OrgUserRole::Owner;
+    OrgUserRepository::new(state.pool.clone())
+        .create(
+            organization_id,
+            user_id,
+            role,
+            &role.default_permissions(),
+            None,
+        )
+        .await?;
+
+    OrganizationRoleRepository::new(state.pool.clone())
+        .init_system_roles(organization_id, Some(user_id))
+        .await
+        .map_err(|e| ApiError::Internal(e.to_string()))?;
+
+    Ok(())
+}
+
 /// GET /api/admin/v1/organizations
 ///
 /// List organizations with pagination and filtering.
@@ -262,6 +369,39 @@ pub async fn get_organization_usage(
     }
 }
 
+/// GET /api/admin/v1/organizations/:org_id/usage/simulate?plan_type=starter
+///
+/// Simulate the organization's current usage against another plan's quotas.
+pub async fn simulate_organization_plan(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
+    Path(org_id): Path<Uuid>,
+    Query(query): Query<PlanSimulationQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let repo = OrganizationRepository::new(state.pool.clone());
+
+    let org = repo
+        .find_by_id(org_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Organization not found".to_string()))?;
+    let usage = repo
+        .get_usage(org_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Organization not found".to_string()))?;
+
+    let simulation = PlanSimulationResponse::new(org.plan_type, query.plan_type, &usage);
+
+    info!(
+        admin_key_id = auth.api_key_id,
+        organization_id = %org_id,
+        simulated_plan = %query.plan_type,
+        fits = simulation.fits,
+        "Simulated organization plan change"
+    );
+
+    Ok(Json(simulation))
+}
+
 /// POST /api/admin/v1/organizations/:org_id/suspend
 ///
 /// Suspend an organization.
@@ -393,7 +533,15 @@ pub async fn add_org_user(
 
             // Create the invitation (no user_id for API key authenticated requests)
             let invite = invite_repo
-                .create(org_id, &token, &request.email, &role, None, expires_at, None)
+                .create(
+                    org_id,
+                    &token,
+                    &request.email,
+                    &role,
+                    None,
+                    expires_at,
+                    None,
+                )
                 .await?;
 
             info!(
@@ -404,6 +552,21 @@ pub async fn add_org_user(
                 invitation_id = %invite.id,
                 "Created invitation for non-existing user"
             );
+            emit_member_event(
+                &state.pool,
+                &state.webhook_secrets,
+                MemberEventPayload::new(
+                    MemberEventType::Invited,
+                    org_id,
+                    MemberEventMember {
+                        email: Some(request.email.clone()),
+                        role: Some(role.clone()),
+                        invitation_id: Some(invite.id),
+                        ..Default::default()
+                    },
+                    None,
+                ),
+            );
 
             return Ok((
                 StatusCode::CREATED,
@@ -442,6 +605,21 @@ pub async fn add_org_user(
         role = %request.role,
         "Added user to organization"
     );
+    emit_member_event(
+        &state.pool,
+        &state.webhook_secrets,
+        MemberEventPayload::new(
+            MemberEventType::Joined,
+            org_id,
+            MemberEventMember {
+                user_id: Some(user.id),
+                email: Some(user.email.clone()),
+                role: Some(request.role.to_string()),
+                ..Default::default()
+            },
+            None,
+        ),
+    );
 
     Ok((
         StatusCode::CREATED,
@@ -564,6 +742,24 @@ pub async fn update_org_user(
                 user_id = %user_id,
                 "Updated organization user"
             );
+            if user.role != existing.role {
+                emit_member_event(
+                    &state.pool,
+                    &state.webhook_secrets,
+                    MemberEventPayload::new(
+                        MemberEventType::RoleChanged,
+                        org_id,
+                        MemberEventMember {
+                            user_id: Some(user_id),
+                            email: Some(user.user.email.clone()),
+                            role: Some(user.role.to_string()),
+                            previous_role: Some(existing.role.to_string()),
+                            ..Default::default()
+                        },
+                        None,
+                    ),
+                );
+            }
             Ok(Json(OrgUserResponse { org_user: user }))
         }
         None => Err(ApiError::NotFound(
@@ -618,6 +814,20 @@ pub async fn remove_org_user(
             user_id = %user_id,
             "Removed user from organization"
         );
+        emit_member_event(
+            &state.pool,
+            &state.webhook_secrets,
+            MemberEventPayload::new(
+                MemberEventType::Removed,
+                org_id,
+                MemberEventMember {
+                    user_id: Some(user_id),
+                    role: Some(existing.role.to_string()),
+                    ..Default::default()
+                },
+                None,
+            ),
+        );
         Ok(StatusCode::NO_CONTENT)
     } else {
         Err(ApiError::NotFound(
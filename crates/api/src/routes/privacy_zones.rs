@@ -0,0 +1,172 @@
+//! Privacy zone endpoint handlers.
+//!
+//! Users manage their own privacy zones; the zones are applied on location
+//! read paths that share a device's position with other group members.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use domain::models::{
+    CreatePrivacyZoneRequest, ListPrivacyZonesResponse, PrivacyZone, PrivacyZoneSet,
+    UpdatePrivacyZoneRequest, MAX_PRIVACY_ZONES_PER_USER,
+};
+use persistence::repositories::PrivacyZoneRepository;
+use sqlx::PgPool;
+use tracing::info;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::app::AppState;
+use crate::error::ApiError;
+use crate::extractors::UserAuth;
+
+fn validation_error(e: validator::ValidationErrors) -> ApiError {
+    let errors: Vec<String> = e
+        .field_errors()
+        .iter()
+        .flat_map(|(field, errors)| {
+            errors.iter().map(move |err| {
+                format!("{}: {}", field, err.message.as_ref().unwrap_or(&"".into()))
+            })
+        })
+        .collect();
+    ApiError::Validation(errors.join(", "))
+}
+
+/// Load the privacy zones of the given device owners, to be applied on
+/// behalf of `viewer` (None when the caller has no user identity).
+pub async fn load_privacy_zones(
+    pool: &PgPool,
+    viewer: Option<Uuid>,
+    owners: impl IntoIterator<Item = Option<Uuid>>,
+) -> Result<PrivacyZoneSet, ApiError> {
+    let mut owner_ids: Vec<Uuid> = owners
+        .into_iter()
+        .flatten()
+        .filter(|owner| Some(*owner) != viewer)
+        .collect();
+    owner_ids.sort_unstable();
+    owner_ids.dedup();
+
+    let zones = PrivacyZoneRepository::new(pool.clone())
+        .list_by_users(&owner_ids)
+        .await?
+        .into_iter()
+        .map(PrivacyZone::from)
+        .collect();
+    Ok(PrivacyZoneSet::new(viewer, zones))
+}
+
+/// List the current user's privacy zones.
+///
+/// GET /api/v1/privacy-zones
+pub async fn list_privacy_zones(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+) -> Result<Json<ListPrivacyZonesResponse>, ApiError> {
+    let zones: Vec<PrivacyZone> = PrivacyZoneRepository::new(state.pool.clone())
+        .list_by_user(user_auth.user_id)
+        .await?
+        .into_iter()
+        .map(PrivacyZone::from)
+        .collect();
+
+    Ok(Json(ListPrivacyZonesResponse {
+        total: zones.len(),
+        zones,
+    }))
+}
+
+/// Create a privacy zone for the current user.
+///
+/// POST /api/v1/privacy-zones
+pub async fn create_privacy_zone(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Json(request): Json<CreatePrivacyZoneRequest>,
+) -> Result<(StatusCode, Json<PrivacyZone>), ApiError> {
+    request.validate().map_err(validation_error)?;
+
+    let repo = PrivacyZoneRepository::new(state.pool.clone());
+
+    let count = repo.count_by_user(user_auth.user_id).await?;
+    if count >= MAX_PRIVACY_ZONES_PER_USER {
+        return Err(ApiError::Conflict(format!(
+            "Maximum number of privacy zones reached ({})",
+            MAX_PRIVACY_ZONES_PER_USER
+        )));
+    }
+
+    let zone: PrivacyZone = repo
+        .create(
+            user_auth.user_id,
+            request.name.trim(),
+            request.latitude,
+            request.longitude,
+            request.radius_meters,
+            request.mode.as_str(),
+        )
+        .await?
+        .into();
+
+    info!(
+        user_id = %user_auth.user_id,
+        zone_id = %zone.id,
+        mode = zone.mode.as_str(),
+        "Privacy zone created"
+    );
+
+    Ok((StatusCode::CREATED, Json(zone)))
+}
+
+/// Update one of the current user's privacy zones.
+///
+/// PUT /api/v1/privacy-zones/:zone_id
+pub async fn update_privacy_zone(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(zone_id): Path<Uuid>,
+    Json(request): Json<UpdatePrivacyZoneRequest>,
+) -> Result<Json<PrivacyZone>, ApiError> {
+    request.validate().map_err(validation_error)?;
+
+    let zone: PrivacyZone = PrivacyZoneRepository::new(state.pool.clone())
+        .update(
+            user_auth.user_id,
+            zone_id,
+            request.name.as_deref().map(str::trim),
+            request.latitude,
+            request.longitude,
+            request.radius_meters,
+            request.mode.map(|m| m.as_str()),
+        )
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Privacy zone not found".to_string()))?
+        .into();
+
+    info!(user_id = %user_auth.user_id, zone_id = %zone_id, "Privacy zone updated");
+
+    Ok(Json(zone))
+}
+
+/// Delete one of the current user's privacy zones.
+///
+/// DELETE /api/v1/privacy-zones/:zone_id
+pub async fn delete_privacy_zone(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(zone_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let deleted = PrivacyZoneRepository::new(state.pool.clone())
+        .delete(user_auth.user_id, zone_id)
+        .await?;
+    if !deleted {
+        return Err(ApiError::NotFound("Privacy zone not found".to_string()));
+    }
+
+    info!(user_id = %user_auth.user_id, zone_id = %zone_id, "Privacy zone deleted");
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -52,6 +52,8 @@ pub struct FeaturesConfig {
     pub b2b: bool,
     /// Whether geofence events feature is enabled
     pub geofence_events: bool,
+    /// Whether users can create trial organizations themselves
+    pub self_service_orgs: bool,
 }
 
 /// GET /api/v1/config/public
@@ -77,6 +79,7 @@ pub async fn get_public_config(State(state): State<AppState>) -> Json<PublicConf
             movement_tracking: config.features.movement_tracking_enabled,
             b2b: config.features.b2b_enabled,
             geofence_events: config.features.geofence_events_enabled,
+            self_service_orgs: config.features.self_service_orgs_enabled,
         },
     };
 
@@ -104,6 +107,7 @@ mod tests {
                 movement_tracking: true,
                 b2b: false,
                 geofence_events: true,
+                self_service_orgs: false,
             },
         };
 
@@ -139,11 +143,13 @@ mod tests {
             movement_tracking: false,
             b2b: true,
             geofence_events: false,
+            self_service_orgs: true,
         };
 
         let json = serde_json::to_string(&config).unwrap();
         assert!(json.contains("\"geofences\":true"));
         assert!(json.contains("\"proximity_alerts\":false"));
         assert!(json.contains("\"movement_tracking\":false"));
+        assert!(json.contains("\"self_service_orgs\":true"));
     }
 }
@@ -0,0 +1,265 @@
+//! Device settings diff route handlers.
+//!
+//! Explains why two devices behave differently by diffing their effective
+//! settings, or shows how a device has drifted from a policy template.
+
+use std::collections::{HashMap, HashSet};
+
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use chrono::{DateTime, Utc};
+use domain::models::{DevicePolicy, OrgUserRole};
+use domain::services::{
+    diff_resolved_settings, resolve_effective_settings, PolicyResolutionInput, PolicySettings,
+    ResolvedSettings, SettingsDiffResponse,
+};
+use persistence::entities::FleetDeviceEntity;
+use persistence::repositories::{
+    DeviceConfigHistoryRepository, DevicePolicyRepository, DeviceRepository, OrgUserRepository,
+    SettingRepository,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::error::ApiError;
+use crate::extractors::UserAuth;
+
+/// Create settings diff routes.
+///
+/// Mounted at /api/admin/v1/organizations/:org_id/devices/:device_id/settings-diff.
+pub fn router() -> Router<AppState> {
+    Router::new().route("/", get(get_settings_diff))
+}
+
+/// Query parameters for the settings diff endpoint.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SettingsDiffQuery {
+    /// Device to compare against.
+    pub other_device_id: Option<Uuid>,
+    /// Policy template to compare against.
+    pub policy_id: Option<Uuid>,
+    /// Compare the devices' settings and policy assignments as they were at
+    /// this past time.
+    pub as_of: Option<DateTime<Utc>>,
+}
+
+/// Check that the user is an admin or owner of the organization.
+async fn require_org_admin(state: &AppState, org_id: Uuid, user_id: Uuid) -> Result<(), ApiError> {
+    let org_user = OrgUserRepository::new(state.pool.clone())
+        .find_by_org_and_user(org_id, user_id)
+        .await?
+        .ok_or_else(|| ApiError::Forbidden("User not in organization".to_string()))?;
+
+    if org_user.role != OrgUserRole::Owner && org_user.role != OrgUserRole::Admin {
+        return Err(ApiError::Forbidden(
+            "Admin or owner access required".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Find a device of the organization.
+async fn find_org_device(
+    repo: &DeviceRepository,
+    org_id: Uuid,
+    device_id: Uuid,
+) -> Result<FleetDeviceEntity, ApiError> {
+    repo.find_fleet_device(org_id, device_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Device not found in organization".to_string()))
+}
+
+fn policy_settings(policy: DevicePolicy) -> PolicySettings {
+    PolicySettings {
+        settings: policy.settings,
+        locked_keys: policy.locked_settings,
+    }
+}
+
+/// Resolves effective settings for devices and policy templates of one
+/// organization, sharing the lookups both sides need.
+struct SettingsResolver {
+    setting_defaults: HashMap<String, serde_json::Value>,
+    organization_defaults: Option<HashMap<String, serde_json::Value>>,
+    policy_repo: DevicePolicyRepository,
+    setting_repo: SettingRepository,
+    history_repo: DeviceConfigHistoryRepository,
+    /// Resolve device settings and policy assignments as of this time.
+    /// Policy contents and organization defaults are always current.
+    as_of: Option<DateTime<Utc>>,
+}
+
+impl SettingsResolver {
+    async fn new(
+        state: &AppState,
+        org_id: Uuid,
+        as_of: Option<DateTime<Utc>>,
+    ) -> Result<Self, ApiError> {
+        let setting_repo = SettingRepository::new(state.pool.clone());
+        let policy_repo = DevicePolicyRepository::new(state.pool.clone());
+
+        let setting_defaults = setting_repo
+            .get_all_definitions()
+            .await?
+            .into_iter()
+            .map(|d| (d.key, d.default_value))
+            .collect();
+        let organization_defaults = policy_repo.find_default(org_id).await?.map(|p| p.settings);
+
+        Ok(Self {
+            setting_defaults,
+            organization_defaults,
+            policy_repo,
+            setting_repo,
+            history_repo: DeviceConfigHistoryRepository::new(state.pool.clone()),
+            as_of,
+        })
+    }
+
+    /// Effective settings of a device, including its own setting locks.
+    async fn resolve_device(
+        &self,
+        device: &FleetDeviceEntity,
+    ) -> Result<ResolvedSettings, ApiError> {
+        let policy_id = match self.as_of {
+            Some(as_of) => {
+                self.history_repo
+                    .policy_id_as_of(device.device_id, as_of)
+                    .await?
+            }
+            None => device.policy_id,
+        };
+        let device_policy = match policy_id {
+            Some(policy_id) => self
+                .policy_repo
+                .find_by_id(policy_id)
+                .await?
+                .map(policy_settings),
+            None => None,
+        };
+
+        let custom: Vec<(String, serde_json::Value, bool)> = match self.as_of {
+            Some(as_of) => self
+                .history_repo
+                .settings_as_of(device.device_id, as_of)
+                .await?
+                .into_iter()
+                .map(|s| (s.setting_key, s.value, s.is_locked))
+                .collect(),
+            None => self
+                .setting_repo
+                .get_device_settings(device.device_id)
+                .await?
+                .into_iter()
+                .map(|s| (s.setting_key, s.value, s.is_locked))
+                .collect(),
+        };
+        let device_locks: Vec<String> = custom
+            .iter()
+            .filter(|(_, _, is_locked)| *is_locked)
+            .map(|(key, _, _)| key.clone())
+            .collect();
+
+        let mut resolved = resolve_effective_settings(PolicyResolutionInput {
+            organization_defaults: self.organization_defaults.clone(),
+            group_policy: None,
+            device_policy,
+            device_settings: custom
+                .into_iter()
+                .map(|(key, value, _)| (key, value))
+                .collect(),
+            setting_defaults: self.setting_defaults.clone(),
+        });
+        resolved.locked_keys.extend(device_locks);
+        Ok(resolved)
+    }
+
+    /// Effective settings a device would have with only the policy applied.
+    fn resolve_policy(&self, policy: DevicePolicy) -> ResolvedSettings {
+        resolve_effective_settings(PolicyResolutionInput {
+            organization_defaults: self.organization_defaults.clone(),
+            group_policy: None,
+            device_policy: Some(policy_settings(policy)),
+            device_settings: HashMap::new(),
+            setting_defaults: self.setting_defaults.clone(),
+        })
+    }
+}
+
+/// Diff the effective settings of a device against another device or a
+/// policy template.
+///
+/// GET /api/admin/v1/organizations/:org_id/devices/:device_id/settings-diff
+///
+/// Exactly one of `other_device_id` or `policy_id` must be given. With
+/// `as_of`, device settings and policy assignments are taken as they were
+/// at that time.
+async fn get_settings_diff(
+    State(state): State<AppState>,
+    Path((org_id, device_id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<SettingsDiffQuery>,
+    user: UserAuth,
+) -> Result<Json<SettingsDiffResponse>, ApiError> {
+    require_org_admin(&state, org_id, user.user_id).await?;
+
+    let device_repo = DeviceRepository::new(state.pool.clone());
+    let device = find_org_device(&device_repo, org_id, device_id).await?;
+    if query.as_of.is_some_and(|as_of| as_of > Utc::now()) {
+        return Err(ApiError::Validation(
+            "as_of must not be in the future".to_string(),
+        ));
+    }
+    let resolver = SettingsResolver::new(&state, org_id, query.as_of).await?;
+
+    let right = match (query.other_device_id, query.policy_id) {
+        (Some(other_device_id), None) => {
+            let other = find_org_device(&device_repo, org_id, other_device_id).await?;
+            resolver.resolve_device(&other).await?
+        }
+        (None, Some(policy_id)) => {
+            let policy = resolver
+                .policy_repo
+                .find_by_id(policy_id)
+                .await?
+                .filter(|p| p.organization_id == org_id)
+                .ok_or_else(|| ApiError::NotFound("Policy not found".to_string()))?;
+            resolver.resolve_policy(policy)
+        }
+        _ => {
+            return Err(ApiError::Validation(
+                "Specify either other_device_id or policy_id".to_string(),
+            ))
+        }
+    };
+    let left = resolver.resolve_device(&device).await?;
+
+    let compared_keys = left
+        .settings
+        .keys()
+        .chain(right.settings.keys())
+        .collect::<HashSet<_>>()
+        .len();
+
+    Ok(Json(SettingsDiffResponse {
+        device_id,
+        other_device_id: query.other_device_id,
+        policy_id: query.policy_id,
+        compared_keys,
+        differences: diff_resolved_settings(&left, &right),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_router_creation() {
+        let _router: Router<AppState> = router();
+    }
+}
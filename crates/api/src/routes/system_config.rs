@@ -3,9 +3,10 @@
 //! AP-9: System Configuration endpoints
 
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    response::IntoResponse,
+    body::Body,
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
     routing::{get, put},
     Json, Router,
 };
@@ -17,12 +18,13 @@ use validator::Validate;
 use crate::app::AppState;
 use crate::error::ApiError;
 use crate::middleware::system_rbac::SystemRoleAuth;
+use crate::services::metrics_snapshot::latest_snapshot;
 
 use domain::models::{
     AuthTogglesInfo, DatabaseSettingsInfo, EmailSettingsInfo, EmailTemplate,
     EmailTemplatesResponse, FcmSettingsInfo, FeatureFlagResponse, FeatureFlagsInfo,
     FeatureFlagsResponse, FrontendSettingsInfo, LimitsSettingsInfo, LoggingSettingsInfo,
-    MaintenanceModeResponse, MapMatchingSettingsInfo, NotificationTemplate,
+    MaintenanceModeResponse, MapMatchingSettingsInfo, MetricsSnapshotQuery, NotificationTemplate,
     NotificationTemplatesResponse, RateLimitConfigItem, RateLimitsResponse, SecuritySettingsInfo,
     ServerSettingsInfo, SystemSettingsResponse, ToggleMaintenanceModeRequest,
     UpdateEmailTemplateRequest, UpdateFeatureFlagRequest, UpdateNotificationTemplateRequest,
@@ -64,6 +66,10 @@ pub fn router() -> Router<AppState> {
         )
         .route("/email-templates", get(list_email_templates))
         .route("/email-templates/{template_id}", put(update_email_template))
+        .route(
+            "/metrics-snapshots/latest",
+            get(download_latest_metrics_snapshot),
+        )
 }
 
 /// Get system settings.
@@ -99,6 +105,7 @@ async fn get_system_settings(
             min_connections: config.database.min_connections,
             connect_timeout_secs: config.database.connect_timeout_secs,
             idle_timeout_secs: config.database.idle_timeout_secs,
+            timescale_enabled: config.database.timescale_enabled,
         },
         logging: LoggingSettingsInfo {
             level: config.logging.level.clone(),
@@ -128,7 +135,7 @@ async fn get_system_settings(
             provider: config.map_matching.provider.clone(),
             enabled: config.map_matching.enabled,
             timeout_ms: config.map_matching.timeout_ms,
-            rate_limit_per_minute: config.map_matching.rate_limit_per_minute,
+            rate_limit_per_minute: config.map_matching.provider_rate_limit_per_minute(),
             circuit_breaker_failures: config.map_matching.circuit_breaker_failures,
             circuit_breaker_reset_secs: config.map_matching.circuit_breaker_reset_secs,
         },
@@ -301,6 +308,7 @@ async fn update_system_settings(
             min_connections: config.database.min_connections,
             connect_timeout_secs: config.database.connect_timeout_secs,
             idle_timeout_secs: config.database.idle_timeout_secs,
+            timescale_enabled: config.database.timescale_enabled,
         },
         logging: LoggingSettingsInfo {
             level: request
@@ -346,7 +354,7 @@ async fn update_system_settings(
             provider: config.map_matching.provider.clone(),
             enabled: config.map_matching.enabled,
             timeout_ms: config.map_matching.timeout_ms,
-            rate_limit_per_minute: config.map_matching.rate_limit_per_minute,
+            rate_limit_per_minute: config.map_matching.provider_rate_limit_per_minute(),
             circuit_breaker_failures: config.map_matching.circuit_breaker_failures,
             circuit_breaker_reset_secs: config.map_matching.circuit_breaker_reset_secs,
         },
@@ -411,6 +419,7 @@ async fn get_feature_flags(
             movement_tracking_enabled: config.features.movement_tracking_enabled,
             b2b_enabled: config.features.b2b_enabled,
             geofence_events_enabled: config.features.geofence_events_enabled,
+            self_service_orgs_enabled: config.features.self_service_orgs_enabled,
         },
         auth: AuthTogglesInfo {
             registration_enabled: config.auth_toggles.registration_enabled,
@@ -456,7 +465,7 @@ async fn get_rate_limits(
         request_verification_rate_limit_per_hour: config
             .security
             .request_verification_rate_limit_per_hour,
-        map_matching_rate_limit_per_minute: config.map_matching.rate_limit_per_minute,
+        map_matching_rate_limit_per_minute: config.map_matching.provider_rate_limit_per_minute(),
     };
 
     info!(
@@ -932,6 +941,58 @@ async fn update_email_template(
     Ok((StatusCode::OK, Json(response)))
 }
 
+/// Download the latest metrics snapshot.
+///
+/// GET /api/admin/v1/system/metrics-snapshots/latest?format=openmetrics|json
+///
+/// Returns the newest snapshot written by the metrics snapshot job, for
+/// deployments that cannot scrape `/metrics` with Prometheus.
+/// Returns 404 if snapshots are disabled or none has been written yet.
+/// Requires super_admin role.
+#[axum::debug_handler(state = AppState)]
+async fn download_latest_metrics_snapshot(
+    State(state): State<AppState>,
+    Query(query): Query<MetricsSnapshotQuery>,
+    system_auth: SystemRoleAuth,
+) -> Result<Response, ApiError> {
+    // Only super_admin can download metrics snapshots
+    if !system_auth.is_super_admin() {
+        return Err(ApiError::Forbidden(
+            "Super admin access required".to_string(),
+        ));
+    }
+
+    let config = &state.config.metrics_snapshots;
+    if !config.enabled {
+        return Err(ApiError::NotFound(
+            "Metrics snapshots are disabled".to_string(),
+        ));
+    }
+
+    let path = latest_snapshot(std::path::Path::new(&config.snapshots_dir), query.format)
+        .await
+        .map_err(|e| ApiError::Internal(format!("Failed to list metrics snapshots: {}", e)))?
+        .ok_or_else(|| ApiError::NotFound("No metrics snapshot available yet".to_string()))?;
+    let content = tokio::fs::read(&path)
+        .await
+        .map_err(|e| ApiError::Internal(format!("Failed to read metrics snapshot: {}", e)))?;
+    let filename = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default()
+        .to_string();
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, query.format.content_type())
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", filename),
+        )
+        .body(Body::from(content))
+        .map_err(|e| ApiError::Internal(format!("Failed to build response: {}", e)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,86 @@
+//! Tenant log export route handlers.
+//!
+//! Lets support export the recent application logs of one organization,
+//! e.g. to share them with an enterprise customer, from the rolling tenant
+//! log store.
+
+use axum::{
+    extract::{Path, Query, State},
+    middleware,
+    routing::get,
+    Json, Router,
+};
+use domain::models::{ExportTenantLogsQuery, ExportTenantLogsResponse, SystemRole};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::error::ApiError;
+use crate::middleware::system_rbac::SystemRoleAuth;
+use crate::middleware::{concurrency_limit, export_statement_timeout, RouteClass};
+
+/// Create tenant log routes.
+///
+/// Mounted at /api/admin/v1/organizations/:org_id/logs.
+pub fn router() -> Router<AppState> {
+    Router::new().route(
+        "/export",
+        get(export_logs)
+            .layer(middleware::from_fn_with_state(
+                RouteClass::Export,
+                concurrency_limit,
+            ))
+            .layer(middleware::from_fn(export_statement_timeout)),
+    )
+}
+
+/// Export an organization's recent application logs.
+///
+/// GET /api/admin/v1/organizations/:org_id/logs/export
+///
+/// Only entries attributed to the organization are returned, optionally
+/// narrowed by group, minimum level and time. Requires super_admin, or
+/// support with access to the organization.
+async fn export_logs(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    Query(query): Query<ExportTenantLogsQuery>,
+    system_auth: SystemRoleAuth,
+) -> Result<Json<ExportTenantLogsResponse>, ApiError> {
+    if !system_auth.is_super_admin()
+        && !(system_auth.has_role(SystemRole::Support) && system_auth.can_access_org(org_id))
+    {
+        return Err(ApiError::Forbidden(
+            "Super admin or support access required".to_string(),
+        ));
+    }
+
+    let store = state.log_store.as_ref().ok_or_else(|| {
+        ApiError::ServiceUnavailable("Tenant log storage is disabled".to_string())
+    })?;
+    let (logs, truncated) = store.export(org_id, &query);
+
+    info!(
+        user_id = %system_auth.user_id,
+        organization_id = %org_id,
+        record_count = logs.len(),
+        "Tenant logs exported"
+    );
+
+    Ok(Json(ExportTenantLogsResponse {
+        organization_id: org_id,
+        record_count: logs.len(),
+        truncated,
+        logs,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_router_creation() {
+        let _router: Router<AppState> = router();
+    }
+}
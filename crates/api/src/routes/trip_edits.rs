@@ -0,0 +1,261 @@
+//! Manual trip edit handlers: merging and splitting trips.
+//!
+//! Edits rewrite trip boundaries, move movement events and locations to the
+//! resulting trips, and re-run statistics and path correction in the
+//! background. Every edit is recorded in the trip edit history.
+
+use axum::{
+    extract::{Path, State},
+    Extension, Json,
+};
+use domain::models::trip::TripResponse;
+use domain::models::trip_edit::{
+    plan_merge, validate_split, ListTripEditsResponse, MergeTripsRequest, MergeTripsResponse,
+    SplitTripRequest, SplitTripResponse, TripEditResponse, TripEditType,
+};
+use persistence::entities::{TripEditEntity, TripEntity};
+use persistence::repositories::{
+    TripEditRepository, TripMergeInput, TripRepository, TripSplitInput,
+};
+use tracing::{info, warn};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::app::AppState;
+use crate::error::ApiError;
+use crate::extractors::ApiKeyAuth;
+use crate::routes::trips::{calculate_trip_statistics, correct_trip_path, load_raw_export_points};
+use crate::services::trip_classification::classify_completed_trip;
+use crate::services::trip_export::TripExportPoint;
+
+/// Merge trips into one.
+///
+/// POST /api/v1/trips/merge
+///
+/// The earliest trip is kept and extended to the latest end; the other
+/// trips' movement events and locations move to it and they are deleted.
+/// Returns 400 if the trips are not all completed trips of one device.
+/// Returns 404 if a trip is not found.
+/// Returns 409 if a trip changed while merging.
+pub async fn merge_trips(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
+    Json(request): Json<MergeTripsRequest>,
+) -> Result<Json<MergeTripsResponse>, ApiError> {
+    request
+        .validate()
+        .map_err(|e| ApiError::Validation(e.to_string()))?;
+
+    let trip_repo = TripRepository::new(state.pool.clone());
+    let mut trips = Vec::with_capacity(request.trip_ids.len());
+    for trip_id in &request.trip_ids {
+        let trip = trip_repo
+            .find_by_id(*trip_id)
+            .await?
+            .ok_or_else(|| ApiError::NotFound(format!("Trip {} not found", trip_id)))?;
+        trips.push(trip.into_domain());
+    }
+    let plan = plan_merge(&trips).map_err(ApiError::Validation)?;
+
+    let (trip, edit) = TripEditRepository::new(state.pool.clone())
+        .merge(&TripMergeInput {
+            device_id: trips[0].device_id,
+            kept_trip_id: plan.kept_trip_id,
+            merged_trip_ids: plan.merged_trip_ids.clone(),
+            end_timestamp: plan.end_timestamp,
+            end_latitude: plan.end_latitude,
+            end_longitude: plan.end_longitude,
+            api_key_id: Some(auth.api_key_id),
+        })
+        .await?
+        .ok_or_else(|| ApiError::Conflict("Trips changed during the merge".to_string()))?;
+
+    info!(
+        trip_id = %trip.id,
+        merged_trip_ids = ?plan.merged_trip_ids,
+        edit_id = %edit.id,
+        "Trips merged"
+    );
+
+    recompute_trip(&state, &trip);
+    Ok(Json(MergeTripsResponse {
+        trip: TripResponse::from(trip.into_domain()),
+        merged_trip_ids: plan.merged_trip_ids,
+        edit_id: edit.id,
+    }))
+}
+
+/// Split a trip in two.
+///
+/// POST /api/v1/trips/:tripId/split
+///
+/// The trip ends at its last location before `split_at`; a new trip starts
+/// at the first location from `split_at` on and takes the rest of the trip.
+/// Returns 400 if the trip is not completed, `split_at` is outside the trip,
+/// or there are no locations on both sides of it.
+/// Returns 404 if trip not found.
+pub async fn split_trip(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
+    Path(trip_id): Path<Uuid>,
+    Json(request): Json<SplitTripRequest>,
+) -> Result<Json<SplitTripResponse>, ApiError> {
+    let trip = TripRepository::new(state.pool.clone())
+        .find_by_id(trip_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Trip not found".to_string()))?;
+    validate_split(&trip.clone().into_domain(), request.split_at).map_err(ApiError::Validation)?;
+
+    let points = load_raw_export_points(&state, &trip).await?;
+    let (before, after) = split_boundary(&points, request.split_at).ok_or_else(|| {
+        ApiError::Validation(
+            "split_at must fall between two recorded locations of the trip".to_string(),
+        )
+    })?;
+
+    let (first, second, edit) = TripEditRepository::new(state.pool.clone())
+        .split(&TripSplitInput {
+            trip_id,
+            split_at: request.split_at,
+            first_end_timestamp: before.0,
+            first_end_latitude: before.1,
+            first_end_longitude: before.2,
+            second_start_timestamp: after.0,
+            second_start_latitude: after.1,
+            second_start_longitude: after.2,
+            second_local_trip_id: format!("split-{}-{}", trip_id.simple(), request.split_at),
+            api_key_id: Some(auth.api_key_id),
+        })
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Trip not found".to_string()))?;
+
+    info!(
+        trip_id = %trip_id,
+        new_trip_id = %second.id,
+        split_at = request.split_at,
+        edit_id = %edit.id,
+        "Trip split"
+    );
+
+    recompute_trip(&state, &first);
+    recompute_trip(&state, &second);
+    Ok(Json(SplitTripResponse {
+        trips: vec![
+            TripResponse::from(first.into_domain()),
+            TripResponse::from(second.into_domain()),
+        ],
+        edit_id: edit.id,
+    }))
+}
+
+/// List the edits a trip took part in.
+///
+/// GET /api/v1/trips/:tripId/edits
+///
+/// Also works for trips that were merged away.
+pub async fn list_trip_edits(
+    State(state): State<AppState>,
+    Path(trip_id): Path<Uuid>,
+) -> Result<Json<ListTripEditsResponse>, ApiError> {
+    let edits = TripEditRepository::new(state.pool.clone())
+        .list_for_trip(trip_id)
+        .await?
+        .into_iter()
+        .map(edit_response)
+        .collect();
+
+    Ok(Json(ListTripEditsResponse { edits }))
+}
+
+/// Recalculate statistics and path correction of an edited trip in the
+/// background.
+fn recompute_trip(state: &AppState, trip: &TripEntity) {
+    let pool = state.pool.clone();
+    let map_matching_client = state.map_matching_client.clone();
+    let webhook_secrets = state.webhook_secrets.clone();
+    let (trip_id, start_ts, end_ts) = (trip.id, trip.start_timestamp, trip.end_timestamp);
+    tokio::spawn(async move {
+        calculate_trip_statistics(pool.clone(), trip_id, start_ts, end_ts).await;
+        correct_trip_path(pool.clone(), map_matching_client, webhook_secrets, trip_id).await;
+        if let Err(e) = classify_completed_trip(&pool, trip_id).await {
+            warn!(trip_id = %trip_id, error = %e, "Failed to classify trip");
+        }
+    });
+}
+
+/// A timed trip point: (timestamp ms, latitude, longitude).
+type BoundaryPoint = (i64, f64, f64);
+
+/// Last point before `split_at` and first point from `split_at` on.
+/// Points without a time are ignored.
+fn split_boundary(
+    points: &[TripExportPoint],
+    split_at: i64,
+) -> Option<(BoundaryPoint, BoundaryPoint)> {
+    let timed = points.iter().filter_map(|p| {
+        p.captured_at
+            .map(|t| (t.timestamp_millis(), p.latitude, p.longitude))
+    });
+    let before = timed
+        .clone()
+        .filter(|p| p.0 < split_at)
+        .max_by_key(|p| p.0)?;
+    let after = timed.filter(|p| p.0 >= split_at).min_by_key(|p| p.0)?;
+    Some((before, after))
+}
+
+fn edit_response(edit: TripEditEntity) -> TripEditResponse {
+    TripEditResponse {
+        id: edit.id,
+        device_id: edit.device_id,
+        edit_type: edit.edit_type.parse().unwrap_or(TripEditType::Merge),
+        source_trip_ids: edit.source_trip_ids,
+        result_trip_ids: edit.result_trip_ids,
+        split_at: edit.split_at,
+        api_key_id: edit.api_key_id,
+        created_at: edit.created_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::DateTime;
+
+    fn point(ms: Option<i64>, latitude: f64) -> TripExportPoint {
+        TripExportPoint {
+            latitude,
+            longitude: 17.0,
+            captured_at: ms.and_then(DateTime::from_timestamp_millis),
+            altitude: None,
+            speed: None,
+        }
+    }
+
+    #[test]
+    fn test_split_boundary() {
+        let points = [
+            point(Some(1_000), 48.0),
+            point(Some(2_000), 48.1),
+            point(None, 48.15),
+            point(Some(3_000), 48.2),
+            point(Some(4_000), 48.3),
+        ];
+
+        let (before, after) = split_boundary(&points, 2_500).unwrap();
+        assert_eq!(before, (2_000, 48.1, 17.0));
+        assert_eq!(after, (3_000, 48.2, 17.0));
+
+        let (before, after) = split_boundary(&points, 3_000).unwrap();
+        assert_eq!(before.0, 2_000);
+        assert_eq!(after.0, 3_000);
+    }
+
+    #[test]
+    fn test_split_boundary_needs_points_on_both_sides() {
+        let points = [point(Some(1_000), 48.0), point(Some(2_000), 48.1)];
+        assert!(split_boundary(&points, 500).is_none());
+        assert!(split_boundary(&points, 2_500).is_none());
+        assert!(split_boundary(&[], 1_500).is_none());
+    }
+}
@@ -0,0 +1,232 @@
+//! Trip purpose and classification rule handlers.
+//!
+//! Trips carry an optional purpose label (business, personal or a custom
+//! label). The device owner sets it directly, or defines rules that label
+//! trips automatically when they complete.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use domain::models::trip::TripResponse;
+use domain::models::trip_purpose::{
+    normalize_purpose, parse_minute_of_day, ListTripRulesResponse, SetTripPurposeRequest,
+    TripPurposeSource, TripRuleRequest, TripRuleResponse, MAX_TRIP_RULES_PER_DEVICE,
+};
+use persistence::repositories::{
+    DeviceRepository, GeofenceRepository, TripRepository, TripRuleInput, TripRuleRepository,
+};
+use tracing::info;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::app::AppState;
+use crate::error::ApiError;
+
+/// Set or clear the purpose of a trip.
+///
+/// PUT /api/v1/trips/:tripId/purpose
+///
+/// A purpose set here is never replaced by classification rules; clearing
+/// it (`{"purpose": null}`) lets the rules label the trip again the next
+/// time it is edited.
+/// Returns 404 if trip not found.
+pub async fn set_trip_purpose(
+    State(state): State<AppState>,
+    Path(trip_id): Path<Uuid>,
+    Json(request): Json<SetTripPurposeRequest>,
+) -> Result<Json<TripResponse>, ApiError> {
+    request
+        .validate()
+        .map_err(|e| ApiError::Validation(e.to_string()))?;
+    let purpose = request
+        .purpose
+        .as_deref()
+        .map(normalize_purpose)
+        .transpose()
+        .map_err(ApiError::Validation)?;
+
+    let trip_repo = TripRepository::new(state.pool.clone());
+    let source = purpose.as_ref().map(|_| TripPurposeSource::Manual.as_str());
+    if !trip_repo
+        .set_purpose(trip_id, purpose.as_deref(), source)
+        .await?
+    {
+        return Err(ApiError::NotFound("Trip not found".to_string()));
+    }
+    let trip = trip_repo
+        .find_by_id(trip_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Trip not found".to_string()))?;
+
+    info!(trip_id = %trip_id, purpose = ?purpose, "Trip purpose set");
+
+    Ok(Json(TripResponse::from(trip.into_domain())))
+}
+
+/// List the classification rules of a device.
+///
+/// GET /api/v1/devices/:deviceId/trip-rules
+///
+/// Rules are listed in evaluation order: highest priority first.
+/// Returns 404 if device not found or inactive.
+pub async fn list_trip_rules(
+    State(state): State<AppState>,
+    Path(device_id): Path<Uuid>,
+) -> Result<Json<ListTripRulesResponse>, ApiError> {
+    ensure_device_active(&state, device_id).await?;
+
+    let rules = TripRuleRepository::new(state.pool.clone())
+        .list_by_device(device_id)
+        .await?
+        .into_iter()
+        .map(TripRuleResponse::from)
+        .collect();
+
+    Ok(Json(ListTripRulesResponse { rules }))
+}
+
+/// Create a classification rule for a device.
+///
+/// POST /api/v1/devices/:deviceId/trip-rules
+///
+/// Returns 400 if the rule is invalid, references a geofence of another
+/// device, or the device already has the maximum number of rules.
+/// Returns 404 if device not found or inactive.
+pub async fn create_trip_rule(
+    State(state): State<AppState>,
+    Path(device_id): Path<Uuid>,
+    Json(request): Json<TripRuleRequest>,
+) -> Result<(StatusCode, Json<TripRuleResponse>), ApiError> {
+    ensure_device_active(&state, device_id).await?;
+    let input = rule_input(&state, device_id, request).await?;
+
+    let rule_repo = TripRuleRepository::new(state.pool.clone());
+    if rule_repo.count_by_device(device_id).await? >= MAX_TRIP_RULES_PER_DEVICE {
+        return Err(ApiError::Validation(format!(
+            "A device can have at most {} trip rules",
+            MAX_TRIP_RULES_PER_DEVICE
+        )));
+    }
+    let rule = rule_repo.create(device_id, &input).await?;
+
+    info!(device_id = %device_id, rule_id = %rule.id, purpose = %rule.purpose, "Trip rule created");
+
+    Ok((StatusCode::CREATED, Json(TripRuleResponse::from(rule))))
+}
+
+/// Replace a classification rule of a device.
+///
+/// PUT /api/v1/devices/:deviceId/trip-rules/:ruleId
+///
+/// Only trips completed or edited afterwards are classified by the new
+/// version of the rule.
+/// Returns 400 if the rule is invalid or references a geofence of another
+/// device.
+/// Returns 404 if device or rule not found.
+pub async fn update_trip_rule(
+    State(state): State<AppState>,
+    Path((device_id, rule_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<TripRuleRequest>,
+) -> Result<Json<TripRuleResponse>, ApiError> {
+    ensure_device_active(&state, device_id).await?;
+    let input = rule_input(&state, device_id, request).await?;
+
+    let rule = TripRuleRepository::new(state.pool.clone())
+        .update(device_id, rule_id, &input)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Trip rule not found".to_string()))?;
+
+    info!(device_id = %device_id, rule_id = %rule_id, "Trip rule updated");
+
+    Ok(Json(TripRuleResponse::from(rule)))
+}
+
+/// Delete a classification rule of a device.
+///
+/// DELETE /api/v1/devices/:deviceId/trip-rules/:ruleId
+///
+/// Purposes the rule already set are kept.
+/// Returns 404 if rule not found.
+pub async fn delete_trip_rule(
+    State(state): State<AppState>,
+    Path((device_id, rule_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, ApiError> {
+    if !TripRuleRepository::new(state.pool.clone())
+        .delete(device_id, rule_id)
+        .await?
+    {
+        return Err(ApiError::NotFound("Trip rule not found".to_string()));
+    }
+
+    info!(device_id = %device_id, rule_id = %rule_id, "Trip rule deleted");
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn ensure_device_active(state: &AppState, device_id: Uuid) -> Result<(), ApiError> {
+    let device = DeviceRepository::new(state.pool.clone())
+        .find_by_device_id(device_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Device not found".to_string()))?;
+    if !device.active {
+        return Err(ApiError::NotFound("Device not found".to_string()));
+    }
+    Ok(())
+}
+
+/// Validate a rule request and convert it to repository input.
+async fn rule_input(
+    state: &AppState,
+    device_id: Uuid,
+    request: TripRuleRequest,
+) -> Result<TripRuleInput, ApiError> {
+    request
+        .validate()
+        .map_err(|e| ApiError::Validation(e.to_string()))?;
+    let purpose = normalize_purpose(&request.purpose).map_err(ApiError::Validation)?;
+
+    let geofence_repo = GeofenceRepository::new(state.pool.clone());
+    for geofence_id in [request.start_geofence_id, request.end_geofence_id]
+        .into_iter()
+        .flatten()
+    {
+        let belongs_to_device = geofence_repo
+            .find_by_geofence_id(geofence_id)
+            .await?
+            .is_some_and(|g| g.device_id == device_id);
+        if !belongs_to_device {
+            return Err(ApiError::Validation(format!(
+                "Geofence {} not found for this device",
+                geofence_id
+            )));
+        }
+    }
+
+    let mut weekdays: Vec<i16> = request
+        .weekdays
+        .iter()
+        .map(|d| d.number_from_monday() as i16)
+        .collect();
+    weekdays.sort_unstable();
+    weekdays.dedup();
+
+    Ok(TripRuleInput {
+        name: request.name.trim().to_string(),
+        purpose,
+        priority: request.priority,
+        start_geofence_id: request.start_geofence_id,
+        end_geofence_id: request.end_geofence_id,
+        weekdays,
+        departure_after_minute: request
+            .departure_after
+            .as_deref()
+            .and_then(parse_minute_of_day),
+        departure_before_minute: request
+            .departure_before
+            .as_deref()
+            .and_then(parse_minute_of_day),
+        enabled: request.enabled,
+    })
+}
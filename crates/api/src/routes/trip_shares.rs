@@ -0,0 +1,275 @@
+//! Trip share link handlers.
+//!
+//! The owner of a trip's device creates, lists and revokes its share links;
+//! anyone holding a link's token can view the trip read-only until the link
+//! expires or is revoked.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{Duration, Utc};
+use persistence::entities::TripShareLinkEntity;
+use persistence::repositories::{DeviceRepository, TripRepository, TripShareLinkRepository};
+use shared::crypto::sha256_hex;
+use tracing::info;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::app::AppState;
+use crate::error::ApiError;
+use crate::extractors::UserAuth;
+use crate::routes::trips::{load_corrected_export_points, load_raw_export_points};
+use domain::models::movement_event::TransportationMode;
+use domain::models::trip::TripState;
+use domain::models::trip_share::{
+    CreateTripShareRequest, ListTripSharesResponse, SharedTripFormat, SharedTripPoint,
+    SharedTripQuery, SharedTripResponse, TripShareResponse,
+};
+
+/// Path of the public shared trip view, relative to the server.
+const SHARED_TRIP_PATH: &str = "/api/v1/shared/trips";
+
+/// Check that the trip exists and the authenticated user owns its device.
+async fn require_trip_owner(
+    state: &AppState,
+    trip_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), ApiError> {
+    let trip = TripRepository::new(state.pool.clone())
+        .find_by_id(trip_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Trip not found".to_string()))?;
+
+    let device = DeviceRepository::new(state.pool.clone())
+        .find_by_device_id(trip.device_id)
+        .await?
+        .filter(|device| device.active)
+        .ok_or_else(|| ApiError::NotFound("Trip not found".to_string()))?;
+
+    if device.owner_user_id != Some(user_id) {
+        return Err(ApiError::Forbidden(
+            "Only the device owner can manage share links of its trips".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Create a share link for a trip.
+///
+/// POST /api/v1/trips/:tripId/share
+///
+/// The body is optional (`{"expires_in_hours": 48}`). The link's token and
+/// URL are only returned here; the server keeps just a hash of the token.
+/// Requires JWT authentication as the owner of the trip's device.
+/// Returns 403 if the caller doesn't own the device.
+/// Returns 404 if trip not found.
+pub async fn create_trip_share(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(trip_id): Path<Uuid>,
+    request: Option<Json<CreateTripShareRequest>>,
+) -> Result<(StatusCode, Json<TripShareResponse>), ApiError> {
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+    request
+        .validate()
+        .map_err(|e| ApiError::Validation(e.to_string()))?;
+
+    require_trip_owner(&state, trip_id, user_auth.user_id).await?;
+
+    let token = generate_share_token();
+    let expires_at = Utc::now() + Duration::hours(request.expires_in_hours() as i64);
+    let link = TripShareLinkRepository::new(state.pool.clone())
+        .create(trip_id, &sha256_hex(&token), expires_at)
+        .await?;
+
+    info!(
+        trip_id = %trip_id,
+        share_id = %link.id,
+        user_id = %user_auth.user_id,
+        expires_at = %expires_at,
+        "Trip share link created"
+    );
+
+    let url = format!(
+        "{}{}/{}",
+        state.config.server.app_base_url.trim_end_matches('/'),
+        SHARED_TRIP_PATH,
+        token
+    );
+    let mut response = share_response(link);
+    response.url = Some(url);
+    response.token = Some(token);
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+/// List the share links of a trip.
+///
+/// GET /api/v1/trips/:tripId/shares
+///
+/// Includes expired and revoked links; tokens are not returned.
+/// Requires JWT authentication as the owner of the trip's device.
+/// Returns 403 if the caller doesn't own the device.
+/// Returns 404 if trip not found.
+pub async fn list_trip_shares(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(trip_id): Path<Uuid>,
+) -> Result<Json<ListTripSharesResponse>, ApiError> {
+    require_trip_owner(&state, trip_id, user_auth.user_id).await?;
+
+    let shares = TripShareLinkRepository::new(state.pool.clone())
+        .list_by_trip_id(trip_id)
+        .await?
+        .into_iter()
+        .map(share_response)
+        .collect();
+
+    Ok(Json(ListTripSharesResponse { shares }))
+}
+
+/// Revoke a share link.
+///
+/// DELETE /api/v1/trips/:tripId/shares/:shareId
+///
+/// The link stops working immediately.
+/// Requires JWT authentication as the owner of the trip's device.
+/// Returns 403 if the caller doesn't own the device.
+/// Returns 404 if the trip has no such link.
+pub async fn revoke_trip_share(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path((trip_id, share_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<TripShareResponse>, ApiError> {
+    require_trip_owner(&state, trip_id, user_auth.user_id).await?;
+
+    let link = TripShareLinkRepository::new(state.pool.clone())
+        .revoke(trip_id, share_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Share link not found".to_string()))?;
+
+    info!(
+        trip_id = %trip_id,
+        share_id = %share_id,
+        user_id = %user_auth.user_id,
+        "Trip share link revoked"
+    );
+
+    Ok(Json(share_response(link)))
+}
+
+/// View a shared trip.
+///
+/// GET /api/v1/shared/trips/:token?format=json|geojson
+///
+/// Unauthenticated; the token is the credential. Serves the map-matched
+/// path when path correction completed, otherwise the recorded locations.
+/// Returns 404 if the link does not exist, expired or was revoked.
+pub async fn get_shared_trip(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+    Query(query): Query<SharedTripQuery>,
+) -> Result<Response, ApiError> {
+    let not_found = || ApiError::NotFound("Shared trip not found".to_string());
+
+    let link = TripShareLinkRepository::new(state.pool.clone())
+        .find_active_by_token_hash(&sha256_hex(&token))
+        .await?
+        .ok_or_else(not_found)?;
+    let trip = TripRepository::new(state.pool.clone())
+        .find_by_id(link.trip_id)
+        .await?
+        .ok_or_else(not_found)?;
+
+    let (points, path_kind) = match load_corrected_export_points(&state, trip.id).await? {
+        Some(points) => (points, "corrected"),
+        None => (load_raw_export_points(&state, &trip).await?, "raw"),
+    };
+
+    let response = SharedTripResponse {
+        trip_id: trip.id,
+        state: trip.state.parse().unwrap_or(TripState::Active),
+        transportation_mode: trip
+            .transportation_mode
+            .parse()
+            .unwrap_or(TransportationMode::Unknown),
+        start_timestamp: trip.start_timestamp,
+        end_timestamp: trip.end_timestamp,
+        distance_meters: trip.distance_meters,
+        duration_seconds: trip.duration_seconds,
+        path_kind: path_kind.to_string(),
+        path: points
+            .into_iter()
+            .map(|p| SharedTripPoint {
+                latitude: p.latitude,
+                longitude: p.longitude,
+                captured_at: p.captured_at,
+            })
+            .collect(),
+        expires_at: link.expires_at,
+    };
+
+    Ok(match query.format {
+        SharedTripFormat::Json => Json(response).into_response(),
+        SharedTripFormat::Geojson => (
+            [(header::CONTENT_TYPE, "application/geo+json")],
+            Json(response.to_geojson()),
+        )
+            .into_response(),
+    })
+}
+
+/// Random 256-bit link token, hex encoded.
+fn generate_share_token() -> String {
+    use rand::Rng;
+    let bytes: [u8; 32] = rand::thread_rng().gen();
+    hex::encode(bytes)
+}
+
+fn share_response(link: TripShareLinkEntity) -> TripShareResponse {
+    TripShareResponse {
+        share_id: link.id,
+        trip_id: link.trip_id,
+        url: None,
+        token: None,
+        active: link.is_active_at(Utc::now()),
+        expires_at: link.expires_at,
+        revoked_at: link.revoked_at,
+        created_at: link.created_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_share_token() {
+        let token = generate_share_token();
+        assert_eq!(token.len(), 64);
+        assert!(token.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(token, generate_share_token());
+    }
+
+    #[test]
+    fn test_share_response_hides_token() {
+        let now = Utc::now();
+        let link = TripShareLinkEntity {
+            id: Uuid::new_v4(),
+            trip_id: Uuid::new_v4(),
+            token_hash: sha256_hex("token"),
+            expires_at: now + Duration::hours(1),
+            revoked_at: Some(now),
+            created_at: now,
+        };
+
+        let response = share_response(link);
+        assert!(!response.active);
+        let json = serde_json::to_value(&response).unwrap();
+        assert!(json.get("token").is_none());
+        assert!(json.get("url").is_none());
+        assert!(json.get("token_hash").is_none());
+    }
+}
@@ -1,33 +1,48 @@
 //! Trip endpoint handlers.
 
 use axum::{
+    body::Body,
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{header, StatusCode},
+    response::Response,
     Json,
 };
 use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::DateTime;
+use persistence::entities::TripEntity;
 use persistence::repositories::{
-    DeviceRepository, MovementEventRepository, TripInput, TripPathCorrectionRepository, TripQuery,
-    TripRepository, TripUpdateInput,
+    DeviceRepository, LocationRepository, MovementEventRepository, TripInput,
+    TripPathCorrectionRepository, TripQuery, TripRepository, TripSpeedViolationRepository,
+    TripUpdateInput, UserRepository,
 };
+use tokio_util::io::ReaderStream;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 use validator::Validate;
 
 use crate::app::AppState;
 use crate::error::ApiError;
+use crate::extractors::UserAuth;
+use crate::services::trip_classification::classify_completed_trip;
+use crate::services::trip_export::{write_trip_export, TripExportPoint};
 use crate::services::PathCorrectionService;
 use domain::models::movement_event::{
     DetectionSource, GetTripMovementEventsQuery, GetTripMovementEventsResponse,
     MovementEventResponse, TransportationMode,
 };
+use domain::models::speed_violation::{ListSpeedViolationsResponse, SpeedViolationResponse};
 use domain::models::trip::{
-    CreateTripRequest, CreateTripResponse, GetTripsQuery, GetTripsResponse, TripPagination,
-    TripResponse, TripState, UpdateTripRequest,
+    CreateTripRequest, CreateTripResponse, GetJourneysQuery, GetJourneysResponse, GetTripsQuery,
+    GetTripsResponse, JourneyResponse, TripExportQuery, TripPagination, TripReplayQuery,
+    TripReplayResponse, TripResponse, TripState, UpdateTripRequest, DEFAULT_JOURNEY_RANGE_MS,
+    MAX_JOURNEY_RANGE_MS, MAX_REPLAY_FRAMES,
 };
 use domain::models::trip_path_correction::{
     CorrectPathResponse, CorrectionStatus, TripPathResponse,
 };
+use domain::models::trip_purpose::normalize_purpose;
+use domain::models::unit_system::UnitSystem;
+use domain::services::{resample_track, stitch_journeys, JourneyStitchingConfig, ReplayPoint};
 
 /// Create a new trip with idempotency support.
 ///
@@ -238,8 +253,20 @@ pub async fn update_trip_state(
             .unwrap_or(DetectionSource::None),
         distance_meters: updated.distance_meters,
         duration_seconds: updated.duration_seconds,
+        purpose: updated.purpose,
+        purpose_source: updated
+            .purpose_source
+            .as_deref()
+            .and_then(|s| s.parse().ok()),
+        stats: None,
         created_at: updated.created_at,
     };
+    let response = if response.distance_meters.is_some() {
+        let units = resolve_trip_units(&state.pool, updated.device_id, None).await?;
+        response.with_units(units)
+    } else {
+        response
+    };
 
     info!(
         trip_id = %trip_id,
@@ -252,11 +279,15 @@ pub async fn update_trip_state(
     if request.state == TripState::Completed {
         let pool = state.pool.clone();
         let map_matching_client = state.map_matching_client.clone();
+        let webhook_secrets = state.webhook_secrets.clone();
         let start_ts = updated.start_timestamp;
         let end_ts = updated.end_timestamp;
         tokio::spawn(async move {
             calculate_trip_statistics(pool.clone(), trip_id, start_ts, end_ts).await;
-            correct_trip_path(pool, map_matching_client, trip_id).await;
+            correct_trip_path(pool.clone(), map_matching_client, webhook_secrets, trip_id).await;
+            if let Err(e) = classify_completed_trip(&pool, trip_id).await {
+                warn!(trip_id = %trip_id, error = %e, "Failed to classify trip");
+            }
         });
     }
 
@@ -305,6 +336,13 @@ pub async fn get_device_trips(
         }
     }
 
+    let purpose_filter = query
+        .purpose
+        .as_deref()
+        .map(normalize_purpose)
+        .transpose()
+        .map_err(ApiError::Validation)?;
+
     // Build query
     let trip_query = TripQuery {
         device_id,
@@ -313,6 +351,7 @@ pub async fn get_device_trips(
         from_timestamp: query.from,
         to_timestamp: query.to,
         state_filter: query.state.clone(),
+        purpose_filter,
         limit,
     };
 
@@ -328,33 +367,44 @@ pub async fn get_device_trips(
         None
     };
 
+    let units = resolve_trip_units(&state.pool, device_id, query.units).await?;
+
     // Convert to response format
     let trip_responses: Vec<TripResponse> = trips
         .into_iter()
-        .map(|entity| TripResponse {
-            id: entity.id,
-            local_trip_id: entity.local_trip_id,
-            state: entity
-                .state
-                .parse::<TripState>()
-                .unwrap_or(TripState::Active),
-            start_timestamp: entity.start_timestamp,
-            end_timestamp: entity.end_timestamp,
-            start_latitude: entity.start_latitude,
-            start_longitude: entity.start_longitude,
-            end_latitude: entity.end_latitude,
-            end_longitude: entity.end_longitude,
-            transportation_mode: entity
-                .transportation_mode
-                .parse::<TransportationMode>()
-                .unwrap_or(TransportationMode::Unknown),
-            detection_source: entity
-                .detection_source
-                .parse::<DetectionSource>()
-                .unwrap_or(DetectionSource::None),
-            distance_meters: entity.distance_meters,
-            duration_seconds: entity.duration_seconds,
-            created_at: entity.created_at,
+        .map(|entity| {
+            TripResponse {
+                id: entity.id,
+                local_trip_id: entity.local_trip_id,
+                state: entity
+                    .state
+                    .parse::<TripState>()
+                    .unwrap_or(TripState::Active),
+                start_timestamp: entity.start_timestamp,
+                end_timestamp: entity.end_timestamp,
+                start_latitude: entity.start_latitude,
+                start_longitude: entity.start_longitude,
+                end_latitude: entity.end_latitude,
+                end_longitude: entity.end_longitude,
+                transportation_mode: entity
+                    .transportation_mode
+                    .parse::<TransportationMode>()
+                    .unwrap_or(TransportationMode::Unknown),
+                detection_source: entity
+                    .detection_source
+                    .parse::<DetectionSource>()
+                    .unwrap_or(DetectionSource::None),
+                distance_meters: entity.distance_meters,
+                duration_seconds: entity.duration_seconds,
+                purpose: entity.purpose,
+                purpose_source: entity
+                    .purpose_source
+                    .as_deref()
+                    .and_then(|s| s.parse().ok()),
+                stats: None,
+                created_at: entity.created_at,
+            }
+            .with_units(units)
         })
         .collect();
 
@@ -374,6 +424,69 @@ pub async fn get_device_trips(
     }))
 }
 
+/// Get the current user's journeys.
+///
+/// GET /api/v1/users/me/journeys
+///
+/// Trips of the user's devices are stitched into journeys, so a journey
+/// continued on another phone is returned once. Defaults to the last 7 days;
+/// ranges are limited to 31 days. Journeys are returned newest first.
+pub async fn get_my_journeys(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Query(query): Query<GetJourneysQuery>,
+) -> Result<Json<GetJourneysResponse>, ApiError> {
+    let to = query
+        .to
+        .unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+    let from = query.from.unwrap_or(to - DEFAULT_JOURNEY_RANGE_MS);
+    if from > to {
+        return Err(ApiError::Validation(
+            "from must not be after to".to_string(),
+        ));
+    }
+    if to - from > MAX_JOURNEY_RANGE_MS {
+        return Err(ApiError::Validation(
+            "Journey range must not exceed 31 days".to_string(),
+        ));
+    }
+
+    let units = match query.units {
+        Some(units) => units,
+        None => {
+            let stored = UserRepository::new(state.pool.clone())
+                .get_unit_system(user_auth.user_id)
+                .await?;
+            UnitSystem::resolve(None, stored.as_deref())
+        }
+    };
+
+    let trips = TripRepository::new(state.pool.clone())
+        .get_trips_by_user(user_auth.user_id, from, to)
+        .await?
+        .into_iter()
+        .map(|entity| entity.into_domain())
+        .collect();
+
+    let mut journeys: Vec<JourneyResponse> =
+        stitch_journeys(trips, &JourneyStitchingConfig::default())
+            .into_iter()
+            .filter_map(|trips| JourneyResponse::from_trips(trips, units))
+            .collect();
+    journeys.reverse();
+
+    info!(
+        user_id = %user_auth.user_id,
+        count = journeys.len(),
+        "Retrieved user journeys"
+    );
+
+    Ok(Json(GetJourneysResponse {
+        count: journeys.len(),
+        journeys,
+    }))
+}
+
 /// Get all movement events for a specific trip.
 ///
 /// GET /api/v1/trips/:tripId/movement-events
@@ -434,6 +547,7 @@ pub async fn get_trip_movement_events(
                 .detection_source
                 .parse::<DetectionSource>()
                 .unwrap_or(DetectionSource::None),
+            movement_state: e.movement_state.as_deref().and_then(|s| s.parse().ok()),
             created_at: e.created_at,
         })
         .collect();
@@ -512,6 +626,241 @@ pub async fn get_trip_path(
     }))
 }
 
+/// Get a trip resampled for playback.
+///
+/// GET /api/v1/trips/:tripId/replay?interval_secs=5
+///
+/// Returns positions interpolated from the recorded locations at a fixed
+/// interval, each with the bearing and speed of travel. The last frame is
+/// at the last recorded location.
+/// Returns 400 if the interval would produce more than 10,000 frames.
+/// Returns 404 if trip not found.
+pub async fn get_trip_replay(
+    State(state): State<AppState>,
+    Path(trip_id): Path<Uuid>,
+    Query(query): Query<TripReplayQuery>,
+) -> Result<Json<TripReplayResponse>, ApiError> {
+    query.validate()?;
+
+    let trip_repo = TripRepository::new(state.pool.clone());
+    let trip = trip_repo
+        .find_by_id(trip_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Trip not found".to_string()))?;
+
+    let points: Vec<ReplayPoint> = load_raw_export_points(&state, &trip)
+        .await?
+        .into_iter()
+        .filter_map(|p| {
+            Some(ReplayPoint {
+                captured_at: p.captured_at?,
+                latitude: p.latitude,
+                longitude: p.longitude,
+            })
+        })
+        .collect();
+
+    let first = points.iter().map(|p| p.captured_at).min();
+    let last = points.iter().map(|p| p.captured_at).max();
+    if let (Some(first), Some(last)) = (first, last) {
+        let frames = (last - first).num_seconds() / i64::from(query.interval_secs) + 2;
+        if frames > MAX_REPLAY_FRAMES {
+            return Err(ApiError::Validation(format!(
+                "interval_secs too small for this trip: replay would exceed {} frames",
+                MAX_REPLAY_FRAMES
+            )));
+        }
+    }
+
+    let frames = resample_track(
+        &points,
+        chrono::Duration::seconds(i64::from(query.interval_secs)),
+    );
+
+    debug!(
+        trip_id = %trip_id,
+        points = points.len(),
+        frames = frames.len(),
+        "Resampled trip for replay"
+    );
+
+    Ok(Json(TripReplayResponse {
+        trip_id,
+        interval_secs: query.interval_secs,
+        frame_count: frames.len(),
+        frames,
+    }))
+}
+
+/// Get the speed-limit violations of a trip.
+///
+/// GET /api/v1/trips/:tripId/speed-violations
+///
+/// Violations are detected during path correction when the map-matching
+/// provider annotates the matched path with speed limits; the list is empty
+/// otherwise.
+/// Returns 404 if trip not found.
+pub async fn get_trip_speed_violations(
+    State(state): State<AppState>,
+    Path(trip_id): Path<Uuid>,
+) -> Result<Json<ListSpeedViolationsResponse>, ApiError> {
+    let trip_repo = TripRepository::new(state.pool.clone());
+    trip_repo
+        .find_by_id(trip_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Trip not found".to_string()))?;
+
+    let violations: Vec<SpeedViolationResponse> =
+        TripSpeedViolationRepository::new(state.pool.clone())
+            .list_for_trip(trip_id)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+    Ok(Json(ListSpeedViolationsResponse {
+        trip_id,
+        count: violations.len(),
+        violations,
+    }))
+}
+
+/// Export a trip path as GPX or CSV.
+///
+/// GET /api/v1/trips/:tripId/export?format=gpx|csv
+///
+/// Streams the map-matched path when path correction completed, otherwise
+/// the raw locations recorded during the trip. The `X-Trip-Path` header tells
+/// which one was exported (`corrected` or `raw`).
+/// Returns 404 if trip not found.
+/// Returns 429 if the device exceeded its hourly export limit.
+pub async fn export_trip(
+    State(state): State<AppState>,
+    Path(trip_id): Path<Uuid>,
+    Query(query): Query<TripExportQuery>,
+) -> Result<Response, ApiError> {
+    let trip_repo = TripRepository::new(state.pool.clone());
+    let trip = trip_repo
+        .find_by_id(trip_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Trip not found".to_string()))?;
+
+    if let Some(ref limiter) = state.trip_export_rate_limiter {
+        if let Err(retry_after) = limiter.check(trip.device_id) {
+            return Err(ApiError::RateLimitedWithRetry {
+                message: format!(
+                    "Export rate limit of {} exports/hour exceeded for this device",
+                    limiter.rate_limit_per_hour()
+                ),
+                retry_after,
+            });
+        }
+    }
+
+    let (points, path_kind) = match load_corrected_export_points(&state, trip_id).await? {
+        Some(points) => (points, "corrected"),
+        None => (load_raw_export_points(&state, &trip).await?, "raw"),
+    };
+
+    debug!(
+        trip_id = %trip_id,
+        format = ?query.format,
+        path = path_kind,
+        points = points.len(),
+        "Exporting trip"
+    );
+
+    let format = query.format;
+    let name = format!("Trip {}", trip.local_trip_id);
+    let (reader, mut writer) = tokio::io::duplex(64 * 1024);
+    tokio::spawn(async move {
+        if let Err(e) = write_trip_export(&mut writer, format, &name, &points).await {
+            warn!(trip_id = %trip_id, error = %e, "Trip export aborted");
+        }
+    });
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, format.content_type())
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"trip_{}.{}\"",
+                trip_id,
+                format.extension()
+            ),
+        )
+        .header("X-Trip-Path", path_kind)
+        .body(Body::from_stream(ReaderStream::new(reader)))
+        .map_err(|e| ApiError::Internal(format!("Failed to build response: {}", e)))
+}
+
+/// Points of the map-matched path, if path correction completed.
+pub(crate) async fn load_corrected_export_points(
+    state: &AppState,
+    trip_id: Uuid,
+) -> Result<Option<Vec<TripExportPoint>>, ApiError> {
+    let correction = TripPathCorrectionRepository::new(state.pool.clone())
+        .find_by_trip_id(trip_id)
+        .await?;
+    let corrected_path = match correction {
+        Some(c) if c.correction_status == CorrectionStatus::Completed.as_str() => c.corrected_path,
+        _ => None,
+    };
+    let Some(corrected_path) = corrected_path else {
+        return Ok(None);
+    };
+
+    let coords = parse_geojson_linestring(&corrected_path).map_err(|e| {
+        error!(trip_id = %trip_id, error = %e, "Failed to parse corrected path GeoJSON");
+        ApiError::Internal("Failed to parse path data".to_string())
+    })?;
+    Ok(Some(
+        coords
+            .into_iter()
+            .map(|[latitude, longitude]| TripExportPoint {
+                latitude,
+                longitude,
+                captured_at: None,
+                altitude: None,
+                speed: None,
+            })
+            .collect(),
+    ))
+}
+
+/// Points recorded by the device during the trip.
+///
+/// Falls back to the trip's time window for locations uploaded without a
+/// trip reference.
+pub(crate) async fn load_raw_export_points(
+    state: &AppState,
+    trip: &TripEntity,
+) -> Result<Vec<TripExportPoint>, ApiError> {
+    let location_repo = LocationRepository::new(state.pool.clone());
+    let mut locations = location_repo.get_locations_for_trip(trip.id).await?;
+    if locations.is_empty() {
+        locations = location_repo
+            .get_all_locations_in_range(
+                trip.device_id,
+                DateTime::from_timestamp_millis(trip.start_timestamp),
+                trip.end_timestamp.and_then(DateTime::from_timestamp_millis),
+            )
+            .await?;
+    }
+
+    Ok(locations
+        .into_iter()
+        .map(|l| TripExportPoint {
+            latitude: l.latitude,
+            longitude: l.longitude,
+            captured_at: Some(l.captured_at),
+            altitude: l.altitude,
+            speed: l.speed.map(f64::from),
+        })
+        .collect())
+}
+
 /// Trigger on-demand path correction for a trip.
 ///
 /// POST /api/v1/trips/:tripId/correct-path
@@ -603,10 +952,11 @@ pub async fn trigger_path_correction(
     // Clone data needed for background task
     let pool = state.pool.clone();
     let map_matching_client = state.map_matching_client.clone();
+    let webhook_secrets = state.webhook_secrets.clone();
 
     // Spawn background task for path correction
     tokio::spawn(async move {
-        let service = PathCorrectionService::new(pool, map_matching_client);
+        let service = PathCorrectionService::new(pool, map_matching_client, webhook_secrets);
 
         match service.correct_trip_path(trip_id).await {
             Ok(result) => {
@@ -705,12 +1055,31 @@ fn encode_cursor(timestamp: i64, id: Uuid) -> String {
     URL_SAFE_NO_PAD.encode(format!("{}:{}", timestamp, id))
 }
 
+/// Resolve the unit system for trip stats.
+///
+/// A `units` query override wins, then the device owner's preference,
+/// falling back to metric for unlinked devices.
+async fn resolve_trip_units(
+    pool: &sqlx::PgPool,
+    device_id: Uuid,
+    requested: Option<UnitSystem>,
+) -> Result<UnitSystem, ApiError> {
+    if let Some(units) = requested {
+        return Ok(units);
+    }
+
+    let stored = UserRepository::new(pool.clone())
+        .get_unit_system_for_device(device_id)
+        .await?;
+    Ok(UnitSystem::resolve(None, stored.as_deref()))
+}
+
 /// Calculate and store trip statistics asynchronously.
 ///
 /// Calculates distance using PostGIS ST_Distance on movement events
 /// and duration from timestamps. Updates the trips table with results.
 /// Errors are logged but don't affect the trip state.
-async fn calculate_trip_statistics(
+pub(crate) async fn calculate_trip_statistics(
     pool: sqlx::PgPool,
     trip_id: Uuid,
     start_timestamp: i64,
@@ -768,14 +1137,15 @@ async fn calculate_trip_statistics(
 /// Extracts trip locations, calls map-matching service, and stores
 /// the corrected path. Errors are logged but don't affect the trip state.
 /// If map-matching is disabled, the correction is marked as SKIPPED.
-async fn correct_trip_path(
+pub(crate) async fn correct_trip_path(
     pool: sqlx::PgPool,
     map_matching_client: Option<std::sync::Arc<crate::services::map_matching::MapMatchingClient>>,
+    webhook_secrets: crate::services::webhook_secret::WebhookSecrets,
     trip_id: Uuid,
 ) {
     info!(trip_id = %trip_id, "Starting path correction for trip");
 
-    let service = PathCorrectionService::new(pool, map_matching_client);
+    let service = PathCorrectionService::new(pool, map_matching_client, webhook_secrets);
 
     match service.correct_trip_path(trip_id).await {
         Ok(result) => {
@@ -924,13 +1294,19 @@ mod tests {
             detection_source: DetectionSource::ActivityRecognition,
             distance_meters: Some(1500.0),
             duration_seconds: Some(9000),
+            purpose: None,
+            purpose_source: None,
+            stats: None,
             created_at: chrono::Utc::now(),
-        };
+        }
+        .with_units(UnitSystem::Imperial);
 
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("COMPLETED"));
         assert!(json.contains("distance_meters"));
         assert!(json.contains("duration_seconds"));
+        assert!(json.contains("\"distance_unit\":\"mi\""));
+        assert!(json.contains("\"speed_unit\":\"mph\""));
     }
 
     #[test]
@@ -949,6 +1325,9 @@ mod tests {
             detection_source: DetectionSource::BluetoothCar,
             distance_meters: None,
             duration_seconds: None,
+            purpose: None,
+            purpose_source: None,
+            stats: None,
             created_at: chrono::Utc::now(),
         };
 
@@ -957,6 +1336,7 @@ mod tests {
         // Optional fields should not appear when None (skip_serializing_if)
         assert!(!json.contains("end_timestamp"));
         assert!(!json.contains("distance_meters"));
+        assert!(!json.contains("stats"));
     }
 
     #[test]
@@ -1041,6 +1421,9 @@ mod tests {
                 detection_source: DetectionSource::ActivityRecognition,
                 distance_meters: Some(1500.0),
                 duration_seconds: Some(9000),
+                purpose: None,
+                purpose_source: None,
+                stats: None,
                 created_at: chrono::Utc::now(),
             }],
             pagination: TripPagination {
@@ -1184,6 +1567,7 @@ mod tests {
             transportation_mode: TransportationMode::Walking,
             confidence: 0.95,
             detection_source: DetectionSource::ActivityRecognition,
+            movement_state: None,
             created_at: chrono::Utc::now(),
         };
 
@@ -15,6 +15,7 @@ use validator::{Validate, ValidationError};
 use crate::app::AppState;
 use crate::error::ApiError;
 use crate::extractors::UserAuth;
+use domain::models::UnitSystem;
 
 /// User profile response.
 #[derive(Debug, Clone, Serialize)]
@@ -25,6 +26,7 @@ pub struct ProfileResponse {
     pub display_name: String,
     pub avatar_url: Option<String>,
     pub email_verified: bool,
+    pub unit_system: UnitSystem,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -37,10 +39,26 @@ struct UserProfileRow {
     display_name: String,
     avatar_url: Option<String>,
     email_verified: bool,
+    unit_system: String,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
 }
 
+impl From<UserProfileRow> for ProfileResponse {
+    fn from(user: UserProfileRow) -> Self {
+        Self {
+            id: user.id.to_string(),
+            email: user.email,
+            display_name: user.display_name,
+            avatar_url: user.avatar_url,
+            email_verified: user.email_verified,
+            unit_system: UnitSystem::resolve(None, Some(&user.unit_system)),
+            created_at: user.created_at.to_rfc3339(),
+            updated_at: user.updated_at.to_rfc3339(),
+        }
+    }
+}
+
 /// Get current user profile.
 ///
 /// GET /api/v1/users/me
@@ -53,7 +71,7 @@ pub async fn get_current_user(
     // Fetch user from database
     let user: Option<UserProfileRow> = sqlx::query_as(
         r#"
-        SELECT id, email, COALESCE(display_name, '') as display_name, avatar_url, email_verified, created_at, updated_at
+        SELECT id, email, COALESCE(display_name, '') as display_name, avatar_url, email_verified, unit_system, created_at, updated_at
         FROM users
         WHERE id = $1 AND is_active = true
         "#,
@@ -65,15 +83,7 @@ pub async fn get_current_user(
 
     let user = user.ok_or_else(|| ApiError::NotFound("User not found".to_string()))?;
 
-    Ok(Json(ProfileResponse {
-        id: user.id.to_string(),
-        email: user.email,
-        display_name: user.display_name,
-        avatar_url: user.avatar_url,
-        email_verified: user.email_verified,
-        created_at: user.created_at.to_rfc3339(),
-        updated_at: user.updated_at.to_rfc3339(),
-    }))
+    Ok(Json(ProfileResponse::from(user)))
 }
 
 /// Request body for updating user profile.
@@ -81,11 +91,15 @@ pub async fn get_current_user(
 #[serde(rename_all = "snake_case")]
 pub struct UpdateProfileRequest {
     /// User's display name (1-100 characters)
+    #[serde(default, deserialize_with = "shared::text::deserialize_optional_line")]
     pub display_name: Option<String>,
 
     /// User's avatar URL (optional). If provided as `null`, clears the avatar URL.
     #[serde(default)]
     pub avatar_url: Option<Option<String>>,
+
+    /// Preferred unit system for distances and speeds (metric or imperial).
+    pub unit_system: Option<UnitSystem>,
 }
 
 impl UpdateProfileRequest {
@@ -113,16 +127,7 @@ fn validate_display_name(display_name: &Option<String>) -> Result<(), Validation
         return Ok(());
     };
 
-    let trimmed = display_name.trim();
-    if trimmed.is_empty() {
-        return Err(ValidationError::new("display_name_blank")
-            .with_message("Display name must be 1-100 characters".into()));
-    }
-    if trimmed.len() > 100 {
-        return Err(ValidationError::new("display_name_too_long")
-            .with_message("Display name must be 1-100 characters".into()));
-    }
-    Ok(())
+    shared::text::validate_display_name(display_name)
 }
 
 fn validate_avatar_url_update(avatar_url: &Option<Option<String>>) -> Result<(), ValidationError> {
@@ -190,13 +195,14 @@ pub async fn update_current_user(
     // Build dynamic update query based on provided fields
     let now = Utc::now();
 
-    if request.display_name.is_none() && request.avatar_url.is_none() {
+    if request.display_name.is_none()
+        && request.avatar_url.is_none()
+        && request.unit_system.is_none()
+    {
         // No fields to update, just return current profile
         return get_current_user(State(state), user_auth).await;
     }
 
-    let normalized_display_name: Option<String> =
-        request.display_name.as_ref().map(|s| s.trim().to_string());
     let normalized_avatar_url: Option<Option<String>> = request.avatar_url.as_ref().map(|inner| {
         inner.as_ref().map(|s| s.trim().to_string())
         // explicit null stays None (clears)
@@ -207,9 +213,10 @@ pub async fn update_current_user(
     qb.push("updated_at = ");
     qb.push_bind(now);
 
-    if request.display_name.is_some() {
+    // Display names are already normalized on deserialization
+    if let Some(display_name) = &request.display_name {
         qb.push(", display_name = ");
-        qb.push_bind(normalized_display_name);
+        qb.push_bind(display_name.clone());
     }
 
     if request.avatar_url.is_some() {
@@ -218,10 +225,15 @@ pub async fn update_current_user(
         qb.push_bind(normalized_avatar_url.unwrap_or(None));
     }
 
+    if let Some(unit_system) = request.unit_system {
+        qb.push(", unit_system = ");
+        qb.push_bind(unit_system.as_str());
+    }
+
     qb.push(" WHERE id = ");
     qb.push_bind(user_auth.user_id);
     qb.push(
-        " RETURNING id, email, COALESCE(display_name, '') as display_name, avatar_url, email_verified, created_at, updated_at",
+        " RETURNING id, email, COALESCE(display_name, '') as display_name, avatar_url, email_verified, unit_system, created_at, updated_at",
     );
 
     let user: UserProfileRow = qb
@@ -230,15 +242,7 @@ pub async fn update_current_user(
         .await
         .map_err(ApiError::from)?;
 
-    Ok(Json(ProfileResponse {
-        id: user.id.to_string(),
-        email: user.email,
-        display_name: user.display_name,
-        avatar_url: user.avatar_url,
-        email_verified: user.email_verified,
-        created_at: user.created_at.to_rfc3339(),
-        updated_at: user.updated_at.to_rfc3339(),
-    }))
+    Ok(Json(ProfileResponse::from(user)))
 }
 
 // ============================================================================
@@ -644,6 +648,7 @@ mod tests {
         let request = UpdateProfileRequest {
             display_name: Some("Test User".to_string()),
             avatar_url: None,
+            unit_system: None,
         };
 
         assert!(request.validate().is_ok());
@@ -654,6 +659,7 @@ mod tests {
         let request = UpdateProfileRequest {
             display_name: Some("A".repeat(101)),
             avatar_url: None,
+            unit_system: None,
         };
 
         assert!(request.validate().is_err());
@@ -664,6 +670,7 @@ mod tests {
         let request = UpdateProfileRequest {
             display_name: Some("".to_string()),
             avatar_url: None,
+            unit_system: None,
         };
 
         assert!(request.validate().is_err());
@@ -674,6 +681,7 @@ mod tests {
         let request = UpdateProfileRequest {
             display_name: Some("   ".to_string()),
             avatar_url: None,
+            unit_system: None,
         };
 
         assert!(request.validate().is_err());
@@ -684,6 +692,7 @@ mod tests {
         let request = UpdateProfileRequest {
             display_name: None,
             avatar_url: Some(Some("https://example.com/avatar.png".to_string())),
+            unit_system: None,
         };
 
         assert!(request.validate().is_ok());
@@ -694,6 +703,7 @@ mod tests {
         let request = UpdateProfileRequest {
             display_name: None,
             avatar_url: Some(Some("not-a-url".to_string())),
+            unit_system: None,
         };
 
         assert!(request.validate().is_err());
@@ -704,6 +714,7 @@ mod tests {
         let request = UpdateProfileRequest {
             display_name: None,
             avatar_url: Some(Some("ftp://example.com/avatar.png".to_string())),
+            unit_system: None,
         };
 
         assert!(request.validate().is_err());
@@ -714,6 +725,7 @@ mod tests {
         let request = UpdateProfileRequest {
             display_name: None,
             avatar_url: Some(None),
+            unit_system: None,
         };
 
         assert!(request.validate().is_ok());
@@ -724,12 +736,25 @@ mod tests {
         let request = UpdateProfileRequest {
             display_name: None,
             avatar_url: None,
+            unit_system: None,
         };
 
         // Empty request should be valid (no-op)
         assert!(request.validate().is_ok());
     }
 
+    #[test]
+    fn test_update_profile_request_unit_system() {
+        let request: UpdateProfileRequest =
+            serde_json::from_str(r#"{"unit_system": "imperial"}"#).unwrap();
+        assert_eq!(request.unit_system, Some(UnitSystem::Imperial));
+        assert!(request.validate().is_ok());
+
+        let invalid: Result<UpdateProfileRequest, _> =
+            serde_json::from_str(r#"{"unit_system": "nautical"}"#);
+        assert!(invalid.is_err());
+    }
+
     #[test]
     fn test_profile_response_serialization() {
         let response = ProfileResponse {
@@ -738,6 +763,7 @@ mod tests {
             display_name: "Test User".to_string(),
             avatar_url: Some("https://example.com/avatar.png".to_string()),
             email_verified: true,
+            unit_system: UnitSystem::Imperial,
             created_at: "2024-01-01T00:00:00Z".to_string(),
             updated_at: "2024-01-02T00:00:00Z".to_string(),
         };
@@ -748,6 +774,7 @@ mod tests {
         assert!(json.contains("email_verified"));
         assert!(json.contains("created_at"));
         assert!(json.contains("updated_at"));
+        assert!(json.contains("\"unit_system\":\"imperial\""));
     }
 
     #[test]
@@ -758,6 +785,7 @@ mod tests {
             display_name: "Test User".to_string(),
             avatar_url: None,
             email_verified: false,
+            unit_system: UnitSystem::Metric,
             created_at: "2024-01-01T00:00:00Z".to_string(),
             updated_at: "2024-01-02T00:00:00Z".to_string(),
         };
@@ -9,31 +9,100 @@ use axum::{
     http::StatusCode,
     Json,
 };
-use domain::models::{check_usage_warning, ResponseWithWarnings};
-use persistence::repositories::{DeviceRepository, WebhookRepository};
+use domain::models::{check_usage_warning, payload_content_type, ResponseWithWarnings};
+use persistence::repositories::{
+    DeviceRepository, UserRepository, WebhookDeliveryRepository, WebhookRepository,
+};
+use reqwest::Client;
+use std::time::{Duration, Instant};
 use tracing::info;
 use uuid::Uuid;
 use validator::Validate;
 
 use crate::app::AppState;
 use crate::error::ApiError;
+use crate::extractors::{OptionalUserAuth, UserAuth};
+use crate::services::webhook_delivery::{
+    sample_payload, sign_timestamped_payload, sign_webhook_payload, webhook_payload,
+    DeliveryResponse, SIGNATURE_HEADER,
+};
+use crate::services::webhook_secret;
 use domain::models::webhook::{
-    CreateWebhookRequest, ListWebhooksQuery, ListWebhooksResponse, UpdateWebhookRequest,
-    WebhookResponse,
+    CreateWebhookRequest, ListWebhooksQuery, ListWebhooksResponse, TestWebhookQuery,
+    TestWebhookResponse, UpdateWebhookRequest, WebhookResponse,
+};
+use domain::models::{
+    ListWebhookDeadLettersQuery, ListWebhookDeadLettersResponse, ListWebhookDeliveriesQuery,
+    ListWebhookDeliveriesResponse, PurgeDeadLettersResponse, RequeueDeadLetterResponse,
+    RetryDeliveryResponse, Webhook, WebhookDeadLetterResponse, WebhookDeliveryResponse,
+    WebhookOwner, WebhookPagination, WebhookRetryPolicy,
 };
 
 /// Maximum number of webhooks allowed per device.
 /// Configurable via PM__LIMITS__MAX_WEBHOOKS_PER_DEVICE
 const DEFAULT_MAX_WEBHOOKS_PER_DEVICE: i64 = 10;
 
+/// Timeout for test webhook deliveries (seconds).
+const TEST_WEBHOOK_TIMEOUT_SECS: u64 = 10;
+
+/// Check that `device_ids` are active devices of `user_id`.
+async fn validate_user_devices(
+    state: &AppState,
+    user_id: Uuid,
+    device_ids: &[Uuid],
+) -> Result<(), ApiError> {
+    let devices = DeviceRepository::new(state.pool.clone())
+        .find_devices_by_user(user_id, false)
+        .await?;
+    let unknown = device_ids
+        .iter()
+        .any(|id| !devices.iter().any(|d| d.device_id == *id));
+    if unknown {
+        return Err(ApiError::Validation(
+            "device_ids must be active devices of the webhook owner".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Check that the caller is signed in as `user_id`.
+///
+/// API keys are not tied to a user, so webhooks can only be attached to the
+/// user behind the request's access token.
+fn require_webhook_user(user: Option<&UserAuth>, user_id: Uuid) -> Result<(), ApiError> {
+    let user = user.ok_or_else(|| {
+        ApiError::Unauthorized("User-owned webhooks require user authentication".to_string())
+    })?;
+    if user.user_id != user_id {
+        return Err(ApiError::Forbidden(
+            "Webhooks can only be owned by the authenticated user".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Name of an owner kind in messages.
+fn owner_kind(owner: WebhookOwner) -> &'static str {
+    match owner {
+        WebhookOwner::Device(_) => "device",
+        WebhookOwner::User(_) => "user",
+    }
+}
+
 /// Create a new webhook.
 ///
 /// POST /api/v1/webhooks
 ///
+/// Webhooks are owned by a device (`owner_device_id`) or by a user
+/// (`owner_user_id`). User-owned webhooks receive events of all of the
+/// user's devices, or of those listed in `device_ids`, and require the
+/// owner's access token.
+///
 /// AC 15.1.2: Creates webhook with validation
 /// Returns usage warning when webhook count approaches configured limit.
 pub async fn create_webhook(
     State(state): State<AppState>,
+    OptionalUserAuth(user_auth): OptionalUserAuth,
     Json(request): Json<CreateWebhookRequest>,
 ) -> Result<(StatusCode, Json<ResponseWithWarnings<WebhookResponse>>), ApiError> {
     // Validate request
@@ -49,63 +118,105 @@ pub async fn create_webhook(
             .collect();
         ApiError::Validation(errors.join(", "))
     })?;
-
-    // Verify device exists and is active
-    let device_repo = DeviceRepository::new(state.pool.clone());
-    let device = device_repo
-        .find_by_device_id(request.owner_device_id)
-        .await?
-        .ok_or_else(|| ApiError::NotFound("Device not found".to_string()))?;
-
-    if !device.active {
-        return Err(ApiError::NotFound("Device not found".to_string()));
+    let retry_policy = request
+        .retry_policy
+        .as_ref()
+        .map(WebhookRetryPolicy::to_value)
+        .transpose()
+        .map_err(ApiError::Validation)?;
+    request
+        .validate_payload_template()
+        .map_err(ApiError::Validation)?;
+    request.validate_batching().map_err(ApiError::Validation)?;
+    let owner = request.owner().map_err(ApiError::Validation)?;
+
+    match owner {
+        WebhookOwner::Device(device_id) => {
+            // Verify device exists and is active
+            let device_repo = DeviceRepository::new(state.pool.clone());
+            let device = device_repo
+                .find_by_device_id(device_id)
+                .await?
+                .ok_or_else(|| ApiError::NotFound("Device not found".to_string()))?;
+
+            if !device.active {
+                return Err(ApiError::NotFound("Device not found".to_string()));
+            }
+        }
+        WebhookOwner::User(user_id) => {
+            require_webhook_user(user_auth.as_ref(), user_id)?;
+            let user_repo = UserRepository::new(state.pool.clone());
+            if user_repo.find_by_id(user_id).await?.is_none() {
+                return Err(ApiError::NotFound("User not found".to_string()));
+            }
+            if let Some(ref device_ids) = request.device_ids {
+                validate_user_devices(&state, user_id, device_ids).await?;
+            }
+        }
     }
 
     let webhook_repo = WebhookRepository::new(state.pool.clone());
 
-    // Check webhook limit per device (AC 15.1.2.7)
+    // Check webhook limit per owner (AC 15.1.2.7)
     let max_webhooks = state
         .config
         .limits
         .max_webhooks_per_device
         .unwrap_or(DEFAULT_MAX_WEBHOOKS_PER_DEVICE as u32) as i64;
-    let count = webhook_repo
-        .count_by_owner_device_id(request.owner_device_id)
-        .await?;
+    let count = webhook_repo.count_by_owner(owner).await?;
     if count >= max_webhooks {
+        let owner_label = match owner {
+            WebhookOwner::Device(_) => "Device",
+            WebhookOwner::User(_) => "User",
+        };
         return Err(ApiError::Conflict(format!(
-            "Device has reached maximum webhook limit ({})",
-            max_webhooks
+            "{} has reached maximum webhook limit ({})",
+            owner_label, max_webhooks
         )));
     }
 
     // Check name uniqueness (AC 15.1.2.6)
     let existing = webhook_repo
-        .find_by_device_and_name(request.owner_device_id, &request.name)
+        .find_by_owner_and_name(owner, &request.name)
         .await?;
     if existing.is_some() {
-        return Err(ApiError::Conflict(
-            "A webhook with this name already exists for this device".to_string(),
-        ));
+        return Err(ApiError::Conflict(format!(
+            "A webhook with this name already exists for this {}",
+            owner_kind(owner)
+        )));
     }
 
     // Create webhook
+    let secret = state
+        .webhook_secrets
+        .seal(&request.secret)
+        .map_err(ApiError::Internal)?;
     let entity = webhook_repo
         .create(
-            request.owner_device_id,
+            owner,
+            request.device_ids.as_deref(),
             &request.name,
             &request.target_url,
-            &request.secret,
+            &secret,
             request.enabled,
+            retry_policy,
+            request.payload_template.as_ref(),
+            request.format,
+            request.max_deliveries_per_minute,
+            request.batch_window_secs,
+            request.batch_max_events,
         )
         .await?;
 
     let webhook: domain::models::Webhook = entity.into();
-    let response: WebhookResponse = webhook.into();
+    let mut response: WebhookResponse = webhook.into();
+    // The secret is only returned here and on rotation
+    response.secret = Some(request.secret.clone());
 
     info!(
         webhook_id = %response.webhook_id,
-        owner_device_id = %response.owner_device_id,
+        owner_device_id = ?response.owner_device_id,
+        owner_user_id = ?response.owner_user_id,
         name = %response.name,
         "Webhook created"
     );
@@ -120,19 +231,21 @@ pub async fn create_webhook(
     Ok((StatusCode::CREATED, Json(response_with_warnings)))
 }
 
-/// List webhooks for a device.
+/// List webhooks of a device or user.
 ///
 /// GET /api/v1/webhooks?ownerDeviceId=<uuid>
+/// GET /api/v1/webhooks?ownerUserId=<uuid>
+/// GET /api/v1/webhooks?deviceId=<uuid>
 ///
-/// AC 15.1.3: Returns webhooks for device
+/// AC 15.1.3: Returns webhooks for device. `deviceId` returns every webhook
+/// receiving the device's events, including user-owned ones.
 pub async fn list_webhooks(
     State(state): State<AppState>,
     Query(query): Query<ListWebhooksQuery>,
 ) -> Result<Json<ListWebhooksResponse>, ApiError> {
+    let filter = query.filter().map_err(ApiError::Validation)?;
     let webhook_repo = WebhookRepository::new(state.pool.clone());
-    let entities = webhook_repo
-        .find_by_owner_device_id(query.owner_device_id)
-        .await?;
+    let entities = webhook_repo.list(filter).await?;
 
     let webhooks: Vec<WebhookResponse> = entities
         .into_iter()
@@ -192,36 +305,83 @@ pub async fn update_webhook(
 
     // Validate HTTPS if target_url is provided
     request.validate_https().map_err(ApiError::Validation)?;
+    request.validate_devices().map_err(ApiError::Validation)?;
+    request
+        .validate_payload_template()
+        .map_err(ApiError::Validation)?;
+    request.validate_batching().map_err(ApiError::Validation)?;
+    let retry_policy = request
+        .retry_policy
+        .as_ref()
+        .map(WebhookRetryPolicy::to_value)
+        .transpose()
+        .map_err(ApiError::Validation)?;
 
     let webhook_repo = WebhookRepository::new(state.pool.clone());
 
-    // Check if webhook exists first (to get device ID for name uniqueness check)
-    let existing = webhook_repo
+    // Check if webhook exists first (to get its owner for name uniqueness check)
+    let existing: Webhook = webhook_repo
         .find_by_webhook_id(webhook_id)
         .await?
-        .ok_or_else(|| ApiError::NotFound("Webhook not found".to_string()))?;
+        .ok_or_else(|| ApiError::NotFound("Webhook not found".to_string()))?
+        .into();
+    let owner = existing.owner();
+
+    if request.batch_max_events.is_some()
+        && existing.batch_window_secs.is_none()
+        && request.batch_window_secs.is_none()
+    {
+        return Err(ApiError::Validation(
+            "batch_max_events requires batch_window_secs".to_string(),
+        ));
+    }
+
+    if request.changes_devices() {
+        let WebhookOwner::User(user_id) = owner else {
+            return Err(ApiError::Validation(
+                "device_ids and all_devices only apply to user-owned webhooks".to_string(),
+            ));
+        };
+        if let Some(ref device_ids) = request.device_ids {
+            validate_user_devices(&state, user_id, device_ids).await?;
+        }
+    }
 
     // Check name uniqueness if name is being changed (AC 15.1.5.6)
     if let Some(ref new_name) = request.name {
         if new_name != &existing.name {
-            let conflict = webhook_repo
-                .find_by_device_and_name(existing.owner_device_id, new_name)
-                .await?;
+            let conflict = webhook_repo.find_by_owner_and_name(owner, new_name).await?;
             if conflict.is_some() {
-                return Err(ApiError::Conflict(
-                    "A webhook with this name already exists for this device".to_string(),
-                ));
+                return Err(ApiError::Conflict(format!(
+                    "A webhook with this name already exists for this {}",
+                    owner_kind(owner)
+                )));
             }
         }
     }
 
+    let secret = request
+        .secret
+        .as_deref()
+        .map(|secret| state.webhook_secrets.seal(secret))
+        .transpose()
+        .map_err(ApiError::Internal)?;
     let entity = webhook_repo
         .update(
             webhook_id,
             request.name.as_deref(),
             request.target_url.as_deref(),
-            request.secret.as_deref(),
+            secret.as_deref(),
             request.enabled,
+            retry_policy,
+            request.device_ids.as_deref(),
+            request.all_devices,
+            request.payload_template.as_ref(),
+            request.default_payload,
+            request.format,
+            request.max_deliveries_per_minute,
+            request.batch_window_secs,
+            request.batch_max_events,
         )
         .await?
         .ok_or_else(|| ApiError::NotFound("Webhook not found".to_string()))?;
@@ -254,6 +414,467 @@ pub async fn delete_webhook(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Re-enable a webhook, typically one that was disabled for failing.
+///
+/// POST /api/v1/webhooks/:webhook_id/enable
+///
+/// Clears the failure counters, circuit breaker and auto-disable time so
+/// the webhook starts over.
+pub async fn enable_webhook(
+    State(state): State<AppState>,
+    Path(webhook_id): Path<Uuid>,
+) -> Result<Json<WebhookResponse>, ApiError> {
+    let entity = WebhookRepository::new(state.pool.clone())
+        .reenable(webhook_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Webhook not found".to_string()))?;
+
+    info!(webhook_id = %webhook_id, "Webhook re-enabled");
+
+    let webhook: Webhook = entity.into();
+    Ok(Json(webhook.into()))
+}
+
+/// Replace a webhook's secret with a new random one.
+///
+/// POST /api/v1/webhooks/:webhook_id/rotate-secret
+///
+/// The new secret is only returned in this response; deliveries are signed
+/// with it from now on, including retries of earlier deliveries.
+pub async fn rotate_secret(
+    State(state): State<AppState>,
+    Path(webhook_id): Path<Uuid>,
+) -> Result<Json<WebhookResponse>, ApiError> {
+    let secret = webhook_secret::generate_secret();
+    let stored = state
+        .webhook_secrets
+        .seal(&secret)
+        .map_err(ApiError::Internal)?;
+    let entity = WebhookRepository::new(state.pool.clone())
+        .set_secret(webhook_id, &stored)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Webhook not found".to_string()))?;
+
+    info!(webhook_id = %webhook_id, "Webhook secret rotated");
+
+    let webhook: Webhook = entity.into();
+    let mut response: WebhookResponse = webhook.into();
+    response.secret = Some(secret);
+    Ok(Json(response))
+}
+
+/// Convert a device-owned webhook into a webhook owned by the device's user.
+///
+/// POST /api/v1/webhooks/:webhook_id/convert-to-user
+///
+/// The converted webhook keeps its ID, secret, settings and delivery log
+/// and receives events of all of the user's devices, so it survives the
+/// user replacing the phone it was created for. Only the device's owner can
+/// convert it.
+pub async fn convert_to_user_owned(
+    State(state): State<AppState>,
+    user_auth: UserAuth,
+    Path(webhook_id): Path<Uuid>,
+) -> Result<Json<WebhookResponse>, ApiError> {
+    let webhook_repo = WebhookRepository::new(state.pool.clone());
+    let webhook: Webhook = webhook_repo
+        .find_by_webhook_id(webhook_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Webhook not found".to_string()))?
+        .into();
+
+    let WebhookOwner::Device(device_id) = webhook.owner() else {
+        return Err(ApiError::Conflict(
+            "Webhook is already owned by a user".to_string(),
+        ));
+    };
+
+    let device = DeviceRepository::new(state.pool.clone())
+        .find_by_device_id(device_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Device not found".to_string()))?;
+    let user_id = device.owner_user_id.ok_or_else(|| {
+        ApiError::Conflict("The webhook's device is not linked to a user".to_string())
+    })?;
+    require_webhook_user(Some(&user_auth), user_id)?;
+
+    let owner = WebhookOwner::User(user_id);
+    if webhook_repo
+        .find_by_owner_and_name(owner, &webhook.name)
+        .await?
+        .is_some()
+    {
+        return Err(ApiError::Conflict(
+            "A webhook with this name already exists for this user".to_string(),
+        ));
+    }
+
+    let entity = webhook_repo
+        .convert_to_user_owned(webhook_id, user_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Webhook not found".to_string()))?;
+
+    let webhook: Webhook = entity.into();
+    let response: WebhookResponse = webhook.into();
+
+    info!(
+        webhook_id = %webhook_id,
+        device_id = %device_id,
+        owner_user_id = %user_id,
+        "Webhook converted to user-owned"
+    );
+
+    Ok(Json(response))
+}
+
+/// Send a sample payload to a webhook.
+///
+/// POST /api/v1/webhooks/:webhook_id/test?event_type=
+///
+/// Delivers a realistic, signed sample event so users can debug their
+/// receivers, rendered with the webhook's payload template (if any). The
+/// delivery is logged like a regular one and flagged with the
+/// `X-Webhook-Test: true` header. The response reports the target's status,
+/// latency and the start of its response body synchronously.
+pub async fn test_webhook(
+    State(state): State<AppState>,
+    Path(webhook_id): Path<Uuid>,
+    Query(query): Query<TestWebhookQuery>,
+) -> Result<Json<TestWebhookResponse>, ApiError> {
+    query.validate_event_type().map_err(ApiError::Validation)?;
+
+    let webhook_repo = WebhookRepository::new(state.pool.clone());
+    let delivery_repo = WebhookDeliveryRepository::new(state.pool.clone());
+
+    let webhook: Webhook = webhook_repo
+        .find_by_webhook_id(webhook_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Webhook not found".to_string()))?
+        .into();
+
+    // User-owned webhooks get a sample from one of the devices they receive events of
+    let sample_device_id = match webhook.owner() {
+        WebhookOwner::Device(device_id) => device_id,
+        WebhookOwner::User(user_id) => {
+            match webhook.device_ids.as_ref().and_then(|ids| ids.first()) {
+                Some(device_id) => *device_id,
+                None => DeviceRepository::new(state.pool.clone())
+                    .find_devices_by_user(user_id, false)
+                    .await?
+                    .first()
+                    .map(|d| d.device_id)
+                    .unwrap_or_else(Uuid::new_v4),
+            }
+        }
+    };
+
+    let event_type = query.get_event_type();
+    let sample = sample_payload(event_type, sample_device_id);
+    let device_name = match webhook.payload_template {
+        Some(_) => DeviceRepository::new(state.pool.clone())
+            .find_by_device_id(sample_device_id)
+            .await?
+            .map(|d| d.display_name),
+        None => None,
+    };
+    let payload = webhook_payload(&webhook, &sample, device_name)
+        .map_err(|e| ApiError::Internal(format!("Failed to serialize payload: {}", e)))?;
+
+    let delivery = delivery_repo
+        .create(webhook_id, None, event_type, &payload)
+        .await?;
+
+    let payload_json = payload.to_string();
+    let secret = state
+        .webhook_secrets
+        .reveal(&webhook.secret)
+        .map_err(ApiError::Internal)?;
+    let signature = sign_webhook_payload(&payload_json, &secret)
+        .map_err(|e| ApiError::Internal(e.to_string()))?;
+    let timestamped_signature =
+        sign_timestamped_payload(&payload_json, &secret, chrono::Utc::now().timestamp())
+            .map_err(|e| ApiError::Internal(e.to_string()))?;
+
+    let client = Client::builder()
+        .timeout(Duration::from_secs(TEST_WEBHOOK_TIMEOUT_SECS))
+        .build()
+        .map_err(|e| ApiError::Internal(format!("Failed to create HTTP client: {}", e)))?;
+
+    let start_time = Instant::now();
+    let result = client
+        .post(&webhook.target_url)
+        .header("Content-Type", payload_content_type(&payload))
+        .header("X-Webhook-Signature", &signature)
+        .header(SIGNATURE_HEADER, &timestamped_signature)
+        .header("X-Webhook-Test", "true")
+        .body(payload_json)
+        .send()
+        .await;
+
+    let (success, response_code, response_body_excerpt, latency_ms, error) = match result {
+        Ok(response) => {
+            let response = DeliveryResponse::read(response, start_time).await;
+            let status = response.status_code as i32;
+            let is_success = response.is_success();
+            delivery_repo
+                .update_attempt(
+                    delivery.delivery_id,
+                    is_success,
+                    Some(status),
+                    None,
+                    Some(response.latency_ms),
+                    Some(&response.body),
+                    &webhook.retry_policy,
+                )
+                .await?;
+            (
+                is_success,
+                Some(status),
+                Some(response.body),
+                Some(response.latency_ms),
+                None,
+            )
+        }
+        Err(e) => {
+            let error_msg = e.to_string();
+            delivery_repo
+                .update_attempt(
+                    delivery.delivery_id,
+                    false,
+                    None,
+                    Some(&error_msg),
+                    None,
+                    None,
+                    &webhook.retry_policy,
+                )
+                .await?;
+            (false, None, None, None, Some(error_msg))
+        }
+    };
+    let duration_ms = start_time.elapsed().as_millis() as i64;
+
+    info!(
+        webhook_id = %webhook_id,
+        delivery_id = %delivery.delivery_id,
+        event_type = event_type,
+        success = success,
+        duration_ms = duration_ms,
+        "Webhook test delivery sent"
+    );
+
+    Ok(Json(TestWebhookResponse {
+        success,
+        delivery_id: delivery.delivery_id,
+        event_type: event_type.to_string(),
+        payload,
+        response_code,
+        response_body_excerpt,
+        latency_ms,
+        error,
+        duration_ms,
+    }))
+}
+
+/// List delivery attempts of a webhook, newest first.
+///
+/// GET /api/v1/webhooks/:webhook_id/deliveries?status=&page=&per_page=
+///
+/// Shows the status, response code, latency and the start of the response
+/// body of each delivery so users can debug their receivers.
+pub async fn list_deliveries(
+    State(state): State<AppState>,
+    Path(webhook_id): Path<Uuid>,
+    Query(query): Query<ListWebhookDeliveriesQuery>,
+) -> Result<Json<ListWebhookDeliveriesResponse>, ApiError> {
+    query
+        .validate()
+        .map_err(|e| ApiError::Validation(e.to_string()))?;
+
+    let webhook_repo = WebhookRepository::new(state.pool.clone());
+    if webhook_repo.find_by_webhook_id(webhook_id).await?.is_none() {
+        return Err(ApiError::NotFound("Webhook not found".to_string()));
+    }
+
+    let page = query.page.unwrap_or(1);
+    let per_page = query.per_page.unwrap_or(20);
+    let offset = ((page - 1) * per_page) as i64;
+
+    let delivery_repo = WebhookDeliveryRepository::new(state.pool.clone());
+    let deliveries = delivery_repo
+        .list_by_webhook_id(webhook_id, query.status.as_deref(), per_page as i64, offset)
+        .await?;
+    let total = delivery_repo
+        .count_by_webhook_id(webhook_id, query.status.as_deref())
+        .await?;
+    let total_pages = ((total as f64) / (per_page as f64)).ceil() as u32;
+
+    Ok(Json(ListWebhookDeliveriesResponse {
+        deliveries: deliveries
+            .into_iter()
+            .map(WebhookDeliveryResponse::from)
+            .collect(),
+        pagination: WebhookPagination {
+            page,
+            per_page,
+            total,
+            total_pages,
+        },
+    }))
+}
+
+/// Retry a failed webhook delivery.
+///
+/// POST /api/v1/webhooks/:webhook_id/deliveries/:delivery_id/retry
+///
+/// Resets the delivery to pending with no backoff so the retry worker
+/// picks it up on its next run.
+pub async fn retry_delivery(
+    State(state): State<AppState>,
+    Path((webhook_id, delivery_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<RetryDeliveryResponse>, ApiError> {
+    let webhook_repo = WebhookRepository::new(state.pool.clone());
+    if webhook_repo.find_by_webhook_id(webhook_id).await?.is_none() {
+        return Err(ApiError::NotFound("Webhook not found".to_string()));
+    }
+
+    let delivery_repo = WebhookDeliveryRepository::new(state.pool.clone());
+    let delivery = delivery_repo
+        .find_by_delivery_id(delivery_id)
+        .await?
+        .filter(|d| d.webhook_id == webhook_id)
+        .ok_or_else(|| ApiError::NotFound("Delivery not found".to_string()))?;
+
+    if delivery.status != "failed" {
+        return Err(ApiError::Conflict(format!(
+            "Only failed deliveries can be retried. Current status: {}",
+            delivery.status
+        )));
+    }
+
+    // A concurrent retry may have reset the delivery since it was read
+    let updated = delivery_repo
+        .reset_for_retry(delivery_id)
+        .await?
+        .ok_or_else(|| ApiError::Conflict("Delivery is no longer in failed status".to_string()))?;
+
+    info!(
+        webhook_id = %webhook_id,
+        delivery_id = %delivery_id,
+        "Queued webhook delivery for retry"
+    );
+
+    Ok(Json(RetryDeliveryResponse {
+        success: true,
+        delivery_id: updated.delivery_id,
+        status: updated.status,
+        message: "Delivery has been queued for retry".to_string(),
+    }))
+}
+
+/// List deliveries of a webhook that failed after their final retry.
+///
+/// GET /api/v1/webhooks/:webhook_id/dead-letters?page=&per_page=
+///
+/// Dead letters keep the full payload and outlive the delivery log
+/// retention until they are requeued or purged.
+pub async fn list_dead_letters(
+    State(state): State<AppState>,
+    Path(webhook_id): Path<Uuid>,
+    Query(query): Query<ListWebhookDeadLettersQuery>,
+) -> Result<Json<ListWebhookDeadLettersResponse>, ApiError> {
+    query
+        .validate()
+        .map_err(|e| ApiError::Validation(e.to_string()))?;
+
+    let webhook_repo = WebhookRepository::new(state.pool.clone());
+    if webhook_repo.find_by_webhook_id(webhook_id).await?.is_none() {
+        return Err(ApiError::NotFound("Webhook not found".to_string()));
+    }
+
+    let page = query.page.unwrap_or(1);
+    let per_page = query.per_page.unwrap_or(20);
+    let offset = ((page - 1) * per_page) as i64;
+
+    let delivery_repo = WebhookDeliveryRepository::new(state.pool.clone());
+    let dead_letters = delivery_repo
+        .list_dead_letters(webhook_id, per_page as i64, offset)
+        .await?;
+    let total = delivery_repo.count_dead_letters(webhook_id).await?;
+    let total_pages = ((total as f64) / (per_page as f64)).ceil() as u32;
+
+    Ok(Json(ListWebhookDeadLettersResponse {
+        dead_letters: dead_letters
+            .into_iter()
+            .map(WebhookDeadLetterResponse::from)
+            .collect(),
+        pagination: WebhookPagination {
+            page,
+            per_page,
+            total,
+            total_pages,
+        },
+    }))
+}
+
+/// Requeue a dead-lettered delivery.
+///
+/// POST /api/v1/webhooks/:webhook_id/dead-letters/:dead_letter_id/requeue
+///
+/// Removes the dead letter and queues its payload as a new delivery, which
+/// the retry worker picks up on its next run.
+pub async fn requeue_dead_letter(
+    State(state): State<AppState>,
+    Path((webhook_id, dead_letter_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<RequeueDeadLetterResponse>, ApiError> {
+    let webhook_repo = WebhookRepository::new(state.pool.clone());
+    if webhook_repo.find_by_webhook_id(webhook_id).await?.is_none() {
+        return Err(ApiError::NotFound("Webhook not found".to_string()));
+    }
+
+    let delivery = WebhookDeliveryRepository::new(state.pool.clone())
+        .requeue_dead_letter(webhook_id, dead_letter_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Dead letter not found".to_string()))?;
+
+    info!(
+        webhook_id = %webhook_id,
+        dead_letter_id = %dead_letter_id,
+        delivery_id = %delivery.delivery_id,
+        "Requeued dead-lettered webhook delivery"
+    );
+
+    Ok(Json(RequeueDeadLetterResponse {
+        success: true,
+        delivery_id: delivery.delivery_id,
+        message: "Dead letter has been requeued for delivery".to_string(),
+    }))
+}
+
+/// Delete all dead letters of a webhook.
+///
+/// DELETE /api/v1/webhooks/:webhook_id/dead-letters
+pub async fn purge_dead_letters(
+    State(state): State<AppState>,
+    Path(webhook_id): Path<Uuid>,
+) -> Result<Json<PurgeDeadLettersResponse>, ApiError> {
+    let webhook_repo = WebhookRepository::new(state.pool.clone());
+    if webhook_repo.find_by_webhook_id(webhook_id).await?.is_none() {
+        return Err(ApiError::NotFound("Webhook not found".to_string()));
+    }
+
+    let purged = WebhookDeliveryRepository::new(state.pool.clone())
+        .purge_dead_letters(webhook_id)
+        .await?;
+
+    info!(
+        webhook_id = %webhook_id,
+        purged = purged,
+        "Purged webhook dead letters"
+    );
+
+    Ok(Json(PurgeDeadLettersResponse { purged }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -290,18 +911,28 @@ mod tests {
     fn test_webhook_response_serialization() {
         let response = WebhookResponse {
             webhook_id: Uuid::new_v4(),
-            owner_device_id: Uuid::new_v4(),
+            owner_device_id: Some(Uuid::new_v4()),
+            owner_user_id: None,
+            device_ids: None,
             name: "Test".to_string(),
             target_url: "https://example.com/webhook".to_string(),
-            secret: "test-secret".to_string(),
+            secret: None,
             enabled: true,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            retry_policy: WebhookRetryPolicy::default(),
+            payload_template: None,
+            format: domain::models::WebhookPayloadFormat::Default,
+            max_deliveries_per_minute: None,
+            batch_window_secs: None,
+            batch_max_events: None,
+            auto_disabled_at: None,
         };
 
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("\"name\":\"Test\""));
         assert!(json.contains("\"enabled\":true"));
+        assert!(!json.contains("auto_disabled_at"));
     }
 
     #[test]
@@ -310,7 +941,7 @@ mod tests {
         let json = r#"{"ownerDeviceId": "550e8400-e29b-41d4-a716-446655440000"}"#;
         let query: ListWebhooksQuery = serde_json::from_str(json).unwrap();
         assert_eq!(
-            query.owner_device_id.to_string(),
+            query.owner_device_id.unwrap().to_string(),
             "550e8400-e29b-41d4-a716-446655440000"
         );
     }
@@ -0,0 +1,181 @@
+//! Per-endpoint, per-key API usage collection.
+//!
+//! Each instance counts requests per organization, API key, endpoint and day
+//! in memory. [`ApiUsageRecorder::flush`] merges the counts into the
+//! persisted `api_usage_daily` rollups that back the API usage analytics.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use domain::services::{latency_bucket, LATENCY_BUCKET_COUNT};
+use persistence::repositories::{AnalyticsRepository, ApiUsageRollupInput};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Rollup key: organization, API key, day, endpoint and method.
+type RollupKey = (Uuid, i64, NaiveDate, String, String);
+
+/// A single handled request.
+#[derive(Debug, Clone)]
+pub struct ApiRequestSample {
+    pub organization_id: Uuid,
+    /// API key the request was made with, `None` for user sessions.
+    pub api_key_id: Option<i64>,
+    /// Route template, e.g. `/api/v1/devices/:device_id`.
+    pub endpoint_path: String,
+    pub method: String,
+    pub status: u16,
+    pub response_time_ms: i64,
+    pub request_bytes: i64,
+    pub response_bytes: i64,
+    pub received_at: DateTime<Utc>,
+}
+
+/// Request counters not yet flushed to the database.
+#[derive(Debug, Default)]
+pub struct ApiUsageRecorder {
+    pending: Mutex<HashMap<RollupKey, ApiUsageRollupInput>>,
+}
+
+impl ApiUsageRecorder {
+    /// Create an empty recorder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count a handled request.
+    pub fn record(&self, sample: ApiRequestSample) {
+        let mut latency_histogram = vec![0; LATENCY_BUCKET_COUNT];
+        latency_histogram[latency_bucket(sample.response_time_ms)] = 1;
+        let success = sample.status < 400;
+
+        self.merge(ApiUsageRollupInput {
+            organization_id: sample.organization_id,
+            api_key_id: sample.api_key_id.unwrap_or(0),
+            usage_date: sample.received_at.date_naive(),
+            endpoint_path: sample.endpoint_path,
+            method: sample.method,
+            total_requests: 1,
+            success_count: i64::from(success),
+            error_count: i64::from(!success),
+            total_response_time_ms: sample.response_time_ms,
+            max_response_time_ms: sample.response_time_ms.clamp(0, i32::MAX as i64) as i32,
+            latency_histogram,
+            total_request_bytes: sample.request_bytes,
+            total_response_bytes: sample.response_bytes,
+        });
+    }
+
+    /// Remove and return all pending rollups.
+    pub fn take(&self) -> Vec<ApiUsageRollupInput> {
+        let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+        pending.drain().map(|(_, rollup)| rollup).collect()
+    }
+
+    /// Merge all pending rollups into the database.
+    ///
+    /// Rollups that could not be written are kept for the next flush.
+    /// Returns the number of rollups written.
+    pub async fn flush(&self, pool: &PgPool) -> Result<usize, sqlx::Error> {
+        let repo = AnalyticsRepository::new(pool.clone());
+        let mut rollups = self.take().into_iter();
+        let mut written = 0;
+        while let Some(rollup) = rollups.next() {
+            if let Err(e) = repo.merge_api_usage(&rollup).await {
+                self.merge(rollup);
+                rollups.for_each(|rest| self.merge(rest));
+                return Err(e);
+            }
+            written += 1;
+        }
+        Ok(written)
+    }
+
+    fn merge(&self, rollup: ApiUsageRollupInput) {
+        let key = (
+            rollup.organization_id,
+            rollup.api_key_id,
+            rollup.usage_date,
+            rollup.endpoint_path.clone(),
+            rollup.method.clone(),
+        );
+        let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+        match pending.get_mut(&key) {
+            Some(existing) => {
+                existing.total_requests += rollup.total_requests;
+                existing.success_count += rollup.success_count;
+                existing.error_count += rollup.error_count;
+                existing.total_response_time_ms += rollup.total_response_time_ms;
+                existing.max_response_time_ms = existing
+                    .max_response_time_ms
+                    .max(rollup.max_response_time_ms);
+                for (count, added) in existing
+                    .latency_histogram
+                    .iter_mut()
+                    .zip(&rollup.latency_histogram)
+                {
+                    *count += added;
+                }
+                existing.total_request_bytes += rollup.total_request_bytes;
+                existing.total_response_bytes += rollup.total_response_bytes;
+            }
+            None => {
+                pending.insert(key, rollup);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn sample(api_key_id: Option<i64>, status: u16, response_time_ms: i64) -> ApiRequestSample {
+        ApiRequestSample {
+            organization_id: Uuid::nil(),
+            api_key_id,
+            endpoint_path: "/api/v1/devices/:device_id".to_string(),
+            method: "GET".to_string(),
+            status,
+            response_time_ms,
+            request_bytes: 0,
+            response_bytes: 512,
+            received_at: Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_requests_are_rolled_up_per_key() {
+        let recorder = ApiUsageRecorder::new();
+        recorder.record(sample(Some(7), 200, 8));
+        recorder.record(sample(Some(7), 500, 400));
+        recorder.record(sample(None, 200, 20));
+
+        let mut rollups = recorder.take();
+        rollups.sort_by_key(|r| r.api_key_id);
+        assert_eq!(rollups.len(), 2);
+        assert!(recorder.take().is_empty());
+
+        let session = &rollups[0];
+        assert_eq!(session.api_key_id, 0);
+        assert_eq!(session.total_requests, 1);
+
+        let keyed = &rollups[1];
+        assert_eq!(keyed.api_key_id, 7);
+        assert_eq!(keyed.total_requests, 2);
+        assert_eq!(keyed.success_count, 1);
+        assert_eq!(keyed.error_count, 1);
+        assert_eq!(keyed.total_response_time_ms, 408);
+        assert_eq!(keyed.max_response_time_ms, 400);
+        assert_eq!(keyed.total_response_bytes, 1024);
+        assert_eq!(keyed.latency_histogram[latency_bucket(8)], 1);
+        assert_eq!(keyed.latency_histogram[latency_bucket(400)], 1);
+        assert_eq!(keyed.latency_histogram.iter().sum::<i64>(), 2);
+        assert_eq!(
+            keyed.usage_date,
+            NaiveDate::from_ymd_opt(2026, 3, 1).unwrap()
+        );
+    }
+}
@@ -0,0 +1,309 @@
+//! Geofence arrival forecasts on location ingestion.
+//!
+//! Geofences with `arrival_alert_minutes` set announce a device that is
+//! heading toward them, once its estimated time of arrival drops below the
+//! threshold. Devices opt out through the
+//! `geofence_arrival_forecasts_enabled` setting. The announcement goes to
+//! the group activity feed and to the other devices of the device's groups.
+//!
+//! Each approach is announced once: a geofence is not announced again until
+//! the device enters and leaves it, or until
+//! [`ARRIVAL_FORECAST_SUPPRESSION_SECS`] after an approach that did not end
+//! in an enter event.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use domain::models::{Geofence, GeofenceTransitionType, GroupEventType, GroupNotificationCategory};
+use domain::services::{
+    forecast_arrival, ArrivalForecast, GeofenceArrivingPayload, GeofenceRegion, MotionFix,
+    NotificationService, NotificationType, ARRIVAL_FORECAST_SETTING_KEY,
+    ARRIVAL_FORECAST_SUPPRESSION_SECS, MAX_FORECAST_FIX_AGE_SECS,
+};
+use persistence::repositories::{
+    DeviceRepository, GeofenceArrivalForecastRepository, GeofenceEventRepository,
+    GeofenceRepository, GroupNotificationPreferencesRepository, LocationRepository,
+    SettingRepository,
+};
+use serde_json::json;
+use sqlx::PgPool;
+use std::sync::Arc;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::services::geofence_evaluation::{is_trusted, load_min_trust_score};
+use crate::services::push_tokens::{active_push_tokens, handle_send_result};
+use crate::services::GroupEventRecorder;
+
+/// Forecast arrivals of a device at its geofences from its latest location.
+///
+/// Failures are logged so that ingestion never fails because of forecasts.
+pub async fn forecast_arrivals_if_enabled(
+    pool: &PgPool,
+    notification_service: &Arc<dyn NotificationService>,
+    device_id: Uuid,
+) {
+    let enabled = match SettingRepository::new(pool.clone())
+        .get_device_setting(device_id, ARRIVAL_FORECAST_SETTING_KEY)
+        .await
+    {
+        Ok(setting) => setting.and_then(|s| s.value.as_bool()).unwrap_or(true),
+        Err(e) => {
+            warn!(
+                device_id = %device_id,
+                error = %e,
+                "Failed to load arrival forecast setting"
+            );
+            false
+        }
+    };
+    if !enabled {
+        return;
+    }
+
+    let min_trust_score = load_min_trust_score(pool, device_id).await;
+    match forecast_arrivals(pool, notification_service, device_id, min_trust_score).await {
+        Ok(0) => {}
+        Ok(count) => debug!(device_id = %device_id, count, "Announced geofence arrivals"),
+        Err(e) => warn!(
+            device_id = %device_id,
+            error = %e,
+            "Geofence arrival forecast failed"
+        ),
+    }
+}
+
+/// Announce arrivals due at the device's geofences, returning how many were
+/// announced. Nothing is forecast from a location below `min_trust_score`.
+pub async fn forecast_arrivals(
+    pool: &PgPool,
+    notification_service: &Arc<dyn NotificationService>,
+    device_id: Uuid,
+    min_trust_score: i16,
+) -> Result<usize, sqlx::Error> {
+    let now = Utc::now();
+    let geofences: Vec<Geofence> = GeofenceRepository::new(pool.clone())
+        .find_by_device_id(device_id, false)
+        .await?
+        .into_iter()
+        .map(Geofence::from)
+        .filter(|g| g.arrival_alert_minutes > 0 && g.is_scheduled_at(now))
+        .collect();
+    if geofences.is_empty() {
+        return Ok(0);
+    }
+
+    let Some(location) = LocationRepository::new(pool.clone())
+        .get_latest_location(device_id)
+        .await?
+    else {
+        return Ok(0);
+    };
+    if !is_trusted(&location, min_trust_score) {
+        return Ok(0);
+    }
+    let (Some(bearing), Some(speed)) = (location.bearing, location.speed) else {
+        return Ok(0);
+    };
+    if now - location.captured_at > Duration::seconds(MAX_FORECAST_FIX_AGE_SECS) {
+        return Ok(0);
+    }
+    let fix = MotionFix {
+        latitude: location.latitude,
+        longitude: location.longitude,
+        bearing: f64::from(bearing),
+        speed_mps: f64::from(speed),
+    };
+
+    let last_events: HashMap<Uuid, (GeofenceTransitionType, i64)> =
+        GeofenceEventRepository::new(pool.clone())
+            .latest_transitions(device_id)
+            .await?
+            .into_iter()
+            .filter_map(|e| {
+                GeofenceTransitionType::parse(&e.event_type)
+                    .map(|t| (e.geofence_id, (t, e.timestamp)))
+            })
+            .collect();
+    let forecast_repo = GeofenceArrivalForecastRepository::new(pool.clone());
+    let last_forecasts: HashMap<Uuid, DateTime<Utc>> = forecast_repo
+        .latest_by_device(device_id)
+        .await?
+        .into_iter()
+        .map(|f| (f.geofence_id, f.forecast_at))
+        .collect();
+
+    let mut announced = 0;
+    for geofence in geofences {
+        let last_event = last_events.get(&geofence.geofence_id).copied();
+        if is_suppressed(
+            last_event,
+            last_forecasts.get(&geofence.geofence_id).copied(),
+            location.captured_at,
+        ) {
+            continue;
+        }
+
+        let region = GeofenceRegion {
+            latitude: geofence.latitude,
+            longitude: geofence.longitude,
+            radius_meters: geofence.radius_meters as f64,
+        };
+        let Some(forecast) = forecast_arrival(&region, &fix) else {
+            continue;
+        };
+        if !geofence.should_announce_arrival(forecast.eta_seconds) {
+            continue;
+        }
+
+        forecast_repo
+            .create(
+                device_id,
+                geofence.geofence_id,
+                forecast.eta_seconds.round() as i32,
+                forecast.distance_meters as f32,
+                fix.latitude,
+                fix.longitude,
+                location.captured_at,
+            )
+            .await?;
+        metrics::counter!("geofence_arrival_forecasts_total").increment(1);
+        dispatch_arrival(
+            pool.clone(),
+            Arc::clone(notification_service),
+            device_id,
+            &geofence,
+            forecast,
+        );
+        announced += 1;
+    }
+
+    Ok(announced)
+}
+
+/// Whether an approach was already announced, or the device is inside.
+///
+/// `last_event` is the geofence's latest enter or exit event and
+/// `last_forecast` the capture time of its latest announced arrival.
+fn is_suppressed(
+    last_event: Option<(GeofenceTransitionType, i64)>,
+    last_forecast: Option<DateTime<Utc>>,
+    at: DateTime<Utc>,
+) -> bool {
+    if let Some((GeofenceTransitionType::Enter, _)) = last_event {
+        return true;
+    }
+    let Some(last_forecast) = last_forecast else {
+        return false;
+    };
+    let announced_since_event =
+        last_event.is_none_or(|(_, timestamp_ms)| last_forecast.timestamp_millis() > timestamp_ms);
+    announced_since_event
+        && at - last_forecast < Duration::seconds(ARRIVAL_FORECAST_SUPPRESSION_SECS)
+}
+
+/// Record the arrival in the group feed and notify the other devices of the
+/// device's groups in the background, skipping members who muted geofence
+/// events for every shared group.
+fn dispatch_arrival(
+    pool: PgPool,
+    notification_service: Arc<dyn NotificationService>,
+    device_id: Uuid,
+    geofence: &Geofence,
+    forecast: ArrivalForecast,
+) {
+    let geofence_id = geofence.geofence_id;
+    let geofence_name = geofence.name.clone();
+    let eta_seconds = forecast.eta_seconds.round() as i32;
+
+    tokio::spawn(async move {
+        GroupEventRecorder::new(pool.clone())
+            .record_for_device(
+                device_id,
+                GroupEventType::GeofenceArriving,
+                json!({
+                    "geofence_id": geofence_id,
+                    "geofence_name": geofence_name,
+                    "eta_seconds": eta_seconds,
+                    "distance_meters": forecast.distance_meters.round(),
+                }),
+            )
+            .await;
+
+        let device_name = match DeviceRepository::new(pool.clone())
+            .find_by_device_id(device_id)
+            .await
+        {
+            Ok(Some(device)) => device.display_name,
+            Ok(None) => return,
+            Err(e) => {
+                warn!(device_id = %device_id, error = %e, "Failed to load device for arrival");
+                return;
+            }
+        };
+        let peers = match GroupNotificationPreferencesRepository::new(pool.clone())
+            .list_peer_recipient_device_ids(device_id, GroupNotificationCategory::GeofenceEvents)
+            .await
+        {
+            Ok(peers) => peers,
+            Err(e) => {
+                warn!(device_id = %device_id, error = %e, "Failed to load group devices");
+                return;
+            }
+        };
+
+        let payload = GeofenceArrivingPayload {
+            notification_type: NotificationType::GeofenceArriving,
+            device_id,
+            device_name,
+            geofence_id,
+            geofence_name,
+            eta_seconds,
+            timestamp: Utc::now(),
+        };
+        for peer_id in peers {
+            for token in active_push_tokens(&pool, peer_id).await {
+                let result = notification_service
+                    .send_geofence_arriving(&token, payload.clone())
+                    .await;
+                handle_send_result(&pool, peer_id, &token, result).await;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_suppressed() {
+        let at = Utc::now();
+        let ms = |t: DateTime<Utc>| t.timestamp_millis();
+        let exit = |t: DateTime<Utc>| Some((GeofenceTransitionType::Exit, ms(t)));
+
+        // Nothing announced yet
+        assert!(!is_suppressed(None, None, at));
+        assert!(!is_suppressed(exit(at - Duration::hours(2)), None, at));
+
+        // Inside the geofence
+        let entered = Some((GeofenceTransitionType::Enter, ms(at - Duration::hours(3))));
+        assert!(is_suppressed(entered, None, at));
+
+        // Approach already announced
+        let announced = Some(at - Duration::minutes(5));
+        assert!(is_suppressed(None, announced, at));
+        assert!(is_suppressed(exit(at - Duration::hours(1)), announced, at));
+
+        // Left the geofence since the last announcement
+        assert!(!is_suppressed(
+            exit(at - Duration::minutes(1)),
+            announced,
+            at
+        ));
+
+        // Announced approach that never ended in an enter event
+        let stale = Some(at - Duration::seconds(ARRIVAL_FORECAST_SUPPRESSION_SECS));
+        assert!(!is_suppressed(None, stale, at));
+    }
+}
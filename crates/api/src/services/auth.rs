@@ -4,7 +4,9 @@ use chrono::Utc;
 use domain::models::user::OAuthProvider;
 use shared::crypto::sha256_hex;
 use shared::jwt::{JwtConfig, JwtError};
-use shared::password::{hash_password, verify_password, PasswordError};
+use shared::password::{
+    hash_password_with, needs_rehash, verify_password, PasswordError, PasswordHashParams,
+};
 use sqlx::PgPool;
 use std::str::FromStr;
 use thiserror::Error;
@@ -133,6 +135,8 @@ pub struct AuthService {
     google_client_id: Option<String>,
     /// Apple auth client for proper JWT verification
     apple_auth_client: AppleAuthClient,
+    /// Argon2id parameters for new password hashes
+    password_params: PasswordHashParams,
 }
 
 impl AuthService {
@@ -171,12 +175,21 @@ impl AuthService {
             access_token_expiry: jwt_config.access_token_expiry_secs,
             google_client_id,
             apple_auth_client,
+            password_params: PasswordHashParams::default(),
         })
     }
 
+    /// Sets the Argon2id parameters used for new password hashes.
+    ///
+    /// Stored hashes with other parameters are upgraded on login.
+    pub fn with_password_params(mut self, params: PasswordHashParams) -> Self {
+        self.password_params = params;
+        self
+    }
+
     /// Normalize PEM key by converting various newline representations to actual newlines.
     /// Handles: literal "\n" string, escaped "\\n", and already-correct newlines.
-    fn normalize_pem_key(key: &str) -> String {
+    pub(crate) fn normalize_pem_key(key: &str) -> String {
         tracing::debug!("Raw key first 80 chars: {:?}", &key[..80.min(key.len())]);
 
         // Strip surrounding quotes if present (some env file parsers include them)
@@ -219,7 +232,7 @@ impl AuthService {
         self.validate_password(password)?;
 
         // Hash the password
-        let password_hash = hash_password(password)?;
+        let password_hash = hash_password_with(password, &self.password_params)?;
 
         // Check if email already exists
         let existing: Option<(Uuid,)> = sqlx::query_as("SELECT id FROM users WHERE email = $1")
@@ -306,6 +319,11 @@ impl AuthService {
             return Err(AuthError::InvalidCredentials);
         }
 
+        if needs_rehash(&password_hash, &self.password_params) {
+            self.upgrade_password_hash(user.id, &password_hash, password)
+                .await;
+        }
+
         // Update last_login_at
         let now = Utc::now();
         sqlx::query("UPDATE users SET last_login_at = $1 WHERE id = $2")
@@ -331,6 +349,41 @@ impl AuthService {
         })
     }
 
+    /// Replaces a password hash created with outdated parameters.
+    ///
+    /// Only called after the password was verified. The update is skipped if
+    /// the hash changed concurrently; failures are logged and do not affect
+    /// the login.
+    async fn upgrade_password_hash(&self, user_id: Uuid, old_hash: &str, password: &str) {
+        let new_hash = match hash_password_with(password, &self.password_params) {
+            Ok(hash) => hash,
+            Err(e) => {
+                tracing::warn!(user_id = %user_id, error = %e, "Failed to rehash password");
+                return;
+            }
+        };
+
+        let result = sqlx::query(
+            "UPDATE users SET password_hash = $1, updated_at = NOW() WHERE id = $2 AND password_hash = $3",
+        )
+        .bind(&new_hash)
+        .bind(user_id)
+        .bind(old_hash)
+        .execute(&self.pool)
+        .await;
+
+        match result {
+            Ok(_) => tracing::info!(
+                user_id = %user_id,
+                params_version = self.password_params.version,
+                "Upgraded password hash parameters"
+            ),
+            Err(e) => {
+                tracing::warn!(user_id = %user_id, error = %e, "Failed to store rehashed password")
+            }
+        }
+    }
+
     /// Authenticate using an OAuth provider (Google or Apple).
     ///
     /// This method validates the ID token from the OAuth provider, then either:
@@ -988,7 +1041,7 @@ impl AuthService {
         }
 
         // Hash the new password
-        let password_hash = hash_password(new_password)?;
+        let password_hash = hash_password_with(new_password, &self.password_params)?;
 
         // Update password and clear reset token
         let now = Utc::now();
@@ -0,0 +1,53 @@
+//! Organization word filter for names and notes entered by members.
+//!
+//! Organizations can list blocked terms in their settings. The terms are
+//! per organization, so they apply to the text fields entered in the scope
+//! of one:
+//! - organization webhook names
+//! - invitation notes
+//! - organization group names and descriptions
+//! - custom group role names and descriptions
+//!
+//! Text outside an organization, such as user display names or device
+//! names, is normalized by `shared::text` but not filtered. Text is matched
+//! after the normalization applied on deserialization, so invisible
+//! characters or odd spacing do not get around the filter.
+
+use persistence::repositories::OrganizationSettingsRepository;
+use shared::text::find_blocked_term;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::ApiError;
+
+/// Reject text containing one of the organization's blocked terms.
+///
+/// `fields` pairs each field name with its value. Returns a validation
+/// error naming the field; the matched term is not echoed back.
+pub async fn check_blocked_terms(
+    pool: &PgPool,
+    organization_id: Uuid,
+    fields: &[(&str, Option<&str>)],
+) -> Result<(), ApiError> {
+    let Some(settings) = OrganizationSettingsRepository::new(pool.clone())
+        .get_by_organization_id(organization_id)
+        .await?
+    else {
+        return Ok(());
+    };
+    if settings.blocked_terms.is_empty() {
+        return Ok(());
+    }
+
+    for (field, value) in fields {
+        if let Some(value) = value {
+            if find_blocked_term(value, &settings.blocked_terms).is_some() {
+                return Err(ApiError::Validation(format!(
+                    "{} contains a term blocked by the organization",
+                    field
+                )));
+            }
+        }
+    }
+    Ok(())
+}
@@ -2,11 +2,19 @@
 //!
 //! Provides utilities for setting, reading, and clearing authentication cookies
 //! for the admin-portal's browser-based authentication.
+//!
+//! Cookie-authenticated requests are protected against CSRF with the double
+//! submit pattern: every token cookie is accompanied by a CSRF cookie readable
+//! by the page, whose value must be echoed in the CSRF header on mutating
+//! requests (see [`crate::middleware::csrf`]).
 
-use axum::http::{header::SET_COOKIE, HeaderMap, HeaderValue};
+use axum::http::{header::SET_COOKIE, HeaderMap, HeaderName, HeaderValue};
 
 use crate::config::CookieConfig;
 
+/// Request header a browser client sends to receive tokens only as cookies.
+pub const SESSION_MODE_HEADER: &str = "x-session-mode";
+
 /// Cookie helper for managing httpOnly authentication cookies.
 #[derive(Debug, Clone)]
 pub struct CookieHelper {
@@ -72,7 +80,94 @@ impl CookieHelper {
         )
     }
 
+    /// Check if CSRF protection applies to cookie-authenticated requests.
+    pub fn csrf_enabled(&self) -> bool {
+        self.config.enabled && self.config.csrf_enabled
+    }
+
+    /// Name of the request header carrying the CSRF token.
+    pub fn csrf_header_name(&self) -> &str {
+        &self.config.csrf_header_name
+    }
+
+    /// Generate a new random CSRF token (32 bytes, hex encoded).
+    pub fn generate_csrf_token(&self) -> String {
+        use rand::Rng;
+        let bytes: [u8; 32] = rand::thread_rng().gen();
+        hex::encode(bytes)
+    }
+
+    /// Build a Set-Cookie header value for the CSRF token.
+    ///
+    /// Unlike the token cookies it is not HttpOnly, so the page can read it
+    /// and echo it in the CSRF header. It lives as long as the refresh token.
+    pub fn build_csrf_cookie(&self, token: &str) -> String {
+        let cookie = self.build_cookie(
+            &self.config.csrf_cookie_name,
+            token,
+            "/",
+            self.refresh_token_expiry_secs,
+        );
+        cookie.replacen("; HttpOnly", "", 1)
+    }
+
+    /// Add a fresh CSRF cookie to a HeaderMap and return the token.
+    ///
+    /// The token is also returned in the CSRF response header.
+    pub fn add_csrf_cookie(&self, headers: &mut HeaderMap) -> Option<String> {
+        if !self.csrf_enabled() {
+            return None;
+        }
+
+        let token = self.generate_csrf_token();
+        if let Ok(value) = HeaderValue::from_str(&self.build_csrf_cookie(&token)) {
+            headers.append(SET_COOKIE, value);
+        }
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(self.config.csrf_header_name.as_bytes()),
+            HeaderValue::from_str(&token),
+        ) {
+            headers.insert(name, value);
+        }
+        Some(token)
+    }
+
+    /// Check whether the request carries an authentication cookie.
+    pub fn has_auth_cookie(&self, headers: &HeaderMap) -> bool {
+        self.extract_access_token(headers).is_some()
+            || self.extract_refresh_token(headers).is_some()
+    }
+
+    /// Check that the CSRF header matches the CSRF cookie.
+    pub fn verify_csrf(&self, headers: &HeaderMap) -> bool {
+        let cookie = self.extract_cookie(headers, &self.config.csrf_cookie_name);
+        let header = headers
+            .get(self.config.csrf_header_name.as_str())
+            .and_then(|v| v.to_str().ok());
+
+        match (cookie, header) {
+            (Some(cookie), Some(header)) if !cookie.is_empty() => {
+                constant_time_eq(cookie.as_bytes(), header.as_bytes())
+            }
+            _ => false,
+        }
+    }
+
+    /// Check if the client asked for tokens to be delivered only as cookies.
+    ///
+    /// Browser clients send `X-Session-Mode: cookie` so JWTs never reach
+    /// JavaScript-accessible storage. Ignored when cookie auth is disabled.
+    pub fn cookie_session_requested(&self, headers: &HeaderMap) -> bool {
+        self.config.enabled
+            && headers
+                .get(SESSION_MODE_HEADER)
+                .and_then(|v| v.to_str().ok())
+                .is_some_and(|v| v.eq_ignore_ascii_case("cookie"))
+    }
+
     /// Add token cookies to a HeaderMap.
+    ///
+    /// A fresh CSRF cookie is issued alongside the tokens.
     pub fn add_token_cookies(
         &self,
         headers: &mut HeaderMap,
@@ -92,6 +187,7 @@ impl CookieHelper {
         if let Ok(value) = HeaderValue::from_str(&refresh_cookie) {
             headers.append(SET_COOKIE, value);
         }
+        self.add_csrf_cookie(headers);
     }
 
     /// Add clear cookies to a HeaderMap (for logout).
@@ -109,6 +205,15 @@ impl CookieHelper {
         if let Ok(value) = HeaderValue::from_str(&clear_refresh) {
             headers.append(SET_COOKIE, value);
         }
+
+        if self.config.csrf_enabled {
+            let clear_csrf = self
+                .build_clear_cookie(&self.config.csrf_cookie_name, "/")
+                .replacen("; HttpOnly", "", 1);
+            if let Ok(value) = HeaderValue::from_str(&clear_csrf) {
+                headers.append(SET_COOKIE, value);
+            }
+        }
     }
 
     /// Extract a cookie value from request headers by name.
@@ -203,6 +308,11 @@ impl CookieHelper {
     }
 }
 
+/// Compare two byte strings in time independent of where they differ.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -217,6 +327,9 @@ mod tests {
             refresh_token_path: "/api/v1/auth".to_string(),
             access_token_name: "access_token".to_string(),
             refresh_token_name: "refresh_token".to_string(),
+            csrf_enabled: true,
+            csrf_cookie_name: "csrf_token".to_string(),
+            csrf_header_name: "x-csrf-token".to_string(),
         }
     }
 
@@ -312,5 +425,78 @@ mod tests {
         let mut headers = HeaderMap::new();
         helper.add_token_cookies(&mut headers, "access", "refresh");
         assert!(headers.get(SET_COOKIE).is_none());
+        assert!(!helper.csrf_enabled());
+    }
+
+    #[test]
+    fn test_token_cookies_include_readable_csrf_cookie() {
+        let helper = CookieHelper::new(test_config(), 3600, 2592000);
+        let mut headers = HeaderMap::new();
+        helper.add_token_cookies(&mut headers, "access", "refresh");
+
+        let cookies: Vec<&str> = headers
+            .get_all(SET_COOKIE)
+            .iter()
+            .map(|v| v.to_str().unwrap())
+            .collect();
+        assert_eq!(cookies.len(), 3);
+        let csrf = cookies[2];
+        assert!(csrf.starts_with("csrf_token="));
+        assert!(csrf.contains("Path=/;"));
+        assert!(csrf.contains("SameSite=Strict"));
+        assert!(!csrf.contains("HttpOnly"));
+
+        let token = headers.get("x-csrf-token").unwrap().to_str().unwrap();
+        assert_eq!(token.len(), 64);
+        assert!(csrf.contains(token));
+    }
+
+    #[test]
+    fn test_verify_csrf() {
+        let helper = CookieHelper::new(test_config(), 3600, 2592000);
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            axum::http::header::COOKIE,
+            HeaderValue::from_static("access_token=abc; csrf_token=t0ken"),
+        );
+        assert!(helper.has_auth_cookie(&headers));
+        assert!(!helper.verify_csrf(&headers));
+
+        headers.insert("x-csrf-token", HeaderValue::from_static("other"));
+        assert!(!helper.verify_csrf(&headers));
+
+        headers.insert("x-csrf-token", HeaderValue::from_static("t0ken"));
+        assert!(helper.verify_csrf(&headers));
+    }
+
+    #[test]
+    fn test_clear_cookies_clear_csrf_cookie() {
+        let helper = CookieHelper::new(test_config(), 3600, 2592000);
+        let mut headers = HeaderMap::new();
+        helper.add_clear_cookies(&mut headers);
+
+        let cookies: Vec<&str> = headers
+            .get_all(SET_COOKIE)
+            .iter()
+            .map(|v| v.to_str().unwrap())
+            .collect();
+        assert_eq!(cookies.len(), 3);
+        assert!(cookies[2].starts_with("csrf_token=;"));
+        assert!(cookies[2].contains("Max-Age=0"));
+    }
+
+    #[test]
+    fn test_cookie_session_requested() {
+        let helper = CookieHelper::new(test_config(), 3600, 2592000);
+        let mut headers = HeaderMap::new();
+        assert!(!helper.cookie_session_requested(&headers));
+
+        headers.insert(SESSION_MODE_HEADER, HeaderValue::from_static("Cookie"));
+        assert!(helper.cookie_session_requested(&headers));
+
+        let mut config = test_config();
+        config.enabled = false;
+        let helper = CookieHelper::new(config, 3600, 2592000);
+        assert!(!helper.cookie_session_requested(&headers));
     }
 }
@@ -0,0 +1,298 @@
+//! Raw data extracts for analytical consumers.
+//!
+//! Extracts stream every row of a dataset (locations, geofence events) for an
+//! organization and date range into a single file. Unlike reports, values are
+//! written at full precision so the output can be loaded directly into data
+//! tooling; Parquet is the default format.
+
+use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
+use domain::models::{ExtractDataset, ExtractFormat};
+use persistence::entities::{GeofenceEventExtractEntity, LocationExtractEntity};
+use serde_json::{Map, Value};
+use std::io::{self, Write};
+
+use super::parquet::{ParquetColumn, ParquetType, ParquetWriter};
+use super::report_rendering::{csv_escape, ReportCell};
+
+/// Columns of a locations extract.
+pub const LOCATION_EXTRACT_COLUMNS: &[ParquetColumn] = &[
+    ParquetColumn::new("id", ParquetType::Int64),
+    ParquetColumn::new("device_id", ParquetType::Utf8),
+    ParquetColumn::new("device_name", ParquetType::Utf8),
+    ParquetColumn::new("captured_at", ParquetType::TimestampMillis),
+    ParquetColumn::new("latitude", ParquetType::Double),
+    ParquetColumn::new("longitude", ParquetType::Double),
+    ParquetColumn::new("accuracy", ParquetType::Double),
+    ParquetColumn::new("altitude", ParquetType::Double),
+    ParquetColumn::new("speed", ParquetType::Double),
+    ParquetColumn::new("bearing", ParquetType::Double),
+    ParquetColumn::new("provider", ParquetType::Utf8),
+    ParquetColumn::new("battery_level", ParquetType::Int64),
+    ParquetColumn::new("network_type", ParquetType::Utf8),
+    ParquetColumn::new("transportation_mode", ParquetType::Utf8),
+    ParquetColumn::new("trip_id", ParquetType::Utf8),
+];
+
+/// Columns of a geofence events extract.
+pub const GEOFENCE_EVENT_EXTRACT_COLUMNS: &[ParquetColumn] = &[
+    ParquetColumn::new("id", ParquetType::Int64),
+    ParquetColumn::new("event_id", ParquetType::Utf8),
+    ParquetColumn::new("device_id", ParquetType::Utf8),
+    ParquetColumn::new("device_name", ParquetType::Utf8),
+    ParquetColumn::new("geofence_id", ParquetType::Utf8),
+    ParquetColumn::new("geofence_name", ParquetType::Utf8),
+    ParquetColumn::new("event_type", ParquetType::Utf8),
+    ParquetColumn::new("timestamp", ParquetType::TimestampMillis),
+    ParquetColumn::new("latitude", ParquetType::Double),
+    ParquetColumn::new("longitude", ParquetType::Double),
+    ParquetColumn::new("webhook_delivered", ParquetType::Boolean),
+    ParquetColumn::new("created_at", ParquetType::TimestampMillis),
+];
+
+/// Columns of an extract dataset.
+pub fn extract_columns(dataset: ExtractDataset) -> &'static [ParquetColumn] {
+    match dataset {
+        ExtractDataset::Locations => LOCATION_EXTRACT_COLUMNS,
+        ExtractDataset::GeofenceEvents => GEOFENCE_EVENT_EXTRACT_COLUMNS,
+    }
+}
+
+/// File extension of an extract format.
+pub fn extract_extension(format: ExtractFormat) -> &'static str {
+    match format {
+        ExtractFormat::Csv => "csv",
+        ExtractFormat::Jsonl => "jsonl",
+        ExtractFormat::Parquet => "parquet",
+    }
+}
+
+/// Extract row for a location point.
+pub fn location_extract_row(row: &LocationExtractEntity) -> Vec<ReportCell> {
+    vec![
+        row.id.into(),
+        row.device_id.to_string().into(),
+        row.device_name.clone().into(),
+        row.captured_at.into(),
+        row.latitude.into(),
+        row.longitude.into(),
+        f64::from(row.accuracy).into(),
+        row.altitude.into(),
+        row.speed.map(f64::from).into(),
+        row.bearing.map(f64::from).into(),
+        row.provider.clone().into(),
+        row.battery_level.map(i64::from).into(),
+        row.network_type.clone().into(),
+        row.transportation_mode.clone().into(),
+        row.trip_id.map(|id| id.to_string()).into(),
+    ]
+}
+
+/// Extract row for a geofence event.
+pub fn geofence_event_extract_row(row: &GeofenceEventExtractEntity) -> Vec<ReportCell> {
+    vec![
+        row.id.into(),
+        row.event_id.to_string().into(),
+        row.device_id.to_string().into(),
+        row.device_name.clone().into(),
+        row.geofence_id.to_string().into(),
+        row.geofence_name.clone().into(),
+        row.event_type.clone().into(),
+        Utc.timestamp_millis_opt(row.timestamp).single().into(),
+        row.latitude.into(),
+        row.longitude.into(),
+        row.webhook_delivered.into(),
+        row.created_at.into(),
+    ]
+}
+
+/// Writes extract rows in the requested format.
+pub enum ExtractWriter<W: Write + Send> {
+    Csv(W),
+    Jsonl(W, &'static [ParquetColumn]),
+    Parquet(Box<ParquetWriter<W>>),
+}
+
+impl<W: Write + Send> ExtractWriter<W> {
+    /// Create a writer and emit any header the format requires.
+    pub fn new(
+        mut out: W,
+        format: ExtractFormat,
+        columns: &'static [ParquetColumn],
+    ) -> io::Result<Self> {
+        Ok(match format {
+            ExtractFormat::Csv => {
+                let header: Vec<&str> = columns.iter().map(|c| c.name).collect();
+                writeln!(out, "{}", header.join(","))?;
+                ExtractWriter::Csv(out)
+            }
+            ExtractFormat::Jsonl => ExtractWriter::Jsonl(out, columns),
+            ExtractFormat::Parquet => {
+                ExtractWriter::Parquet(Box::new(ParquetWriter::new(out, columns)?))
+            }
+        })
+    }
+
+    /// Append a row.
+    pub fn write_row(&mut self, cells: &[ReportCell]) -> io::Result<()> {
+        match self {
+            ExtractWriter::Csv(out) => {
+                let fields: Vec<String> = cells.iter().map(extract_text).collect();
+                writeln!(out, "{}", fields.join(","))
+            }
+            ExtractWriter::Jsonl(out, columns) => {
+                let object: Map<String, Value> = columns
+                    .iter()
+                    .zip(cells)
+                    .map(|(column, cell)| (column.name.to_string(), extract_json(cell)))
+                    .collect();
+                serde_json::to_writer(&mut *out, &object)?;
+                out.write_all(b"\n")
+            }
+            ExtractWriter::Parquet(writer) => writer.write_row(cells),
+        }
+    }
+
+    /// Flush remaining data and return the underlying writer.
+    pub fn finish(self) -> io::Result<W> {
+        match self {
+            ExtractWriter::Csv(mut out) | ExtractWriter::Jsonl(mut out, _) => {
+                out.flush()?;
+                Ok(out)
+            }
+            ExtractWriter::Parquet(writer) => writer.finish(),
+        }
+    }
+}
+
+fn extract_timestamp(ts: &DateTime<Utc>) -> String {
+    ts.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// CSV field for an extract cell, keeping full numeric precision.
+fn extract_text(cell: &ReportCell) -> String {
+    match cell {
+        ReportCell::Text(s) => csv_escape(s),
+        ReportCell::Integer(i) => i.to_string(),
+        ReportCell::Number(n) => n.to_string(),
+        ReportCell::Boolean(b) => b.to_string(),
+        ReportCell::DateTime(ts) => extract_timestamp(ts),
+        ReportCell::Empty => String::new(),
+    }
+}
+
+/// JSON value for an extract cell.
+fn extract_json(cell: &ReportCell) -> Value {
+    match cell {
+        ReportCell::Text(s) => Value::String(s.clone()),
+        ReportCell::Integer(i) => Value::from(*i),
+        ReportCell::Number(n) => Value::from(*n),
+        ReportCell::Boolean(b) => Value::Bool(*b),
+        ReportCell::DateTime(ts) => Value::String(extract_timestamp(ts)),
+        ReportCell::Empty => Value::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn sample_location() -> LocationExtractEntity {
+        LocationExtractEntity {
+            id: 42,
+            device_id: Uuid::nil(),
+            device_name: Some("Pixel, \"work\"".to_string()),
+            captured_at: Utc.timestamp_millis_opt(1_700_000_000_123).unwrap(),
+            latitude: 48.1234567,
+            longitude: 17.7654321,
+            accuracy: 5.5,
+            altitude: None,
+            speed: Some(1.25),
+            bearing: None,
+            provider: Some("gps".to_string()),
+            battery_level: Some(80),
+            network_type: None,
+            transportation_mode: None,
+            trip_id: None,
+        }
+    }
+
+    #[test]
+    fn test_extract_rows_match_columns() {
+        assert_eq!(
+            location_extract_row(&sample_location()).len(),
+            LOCATION_EXTRACT_COLUMNS.len()
+        );
+        let event = GeofenceEventExtractEntity {
+            id: 1,
+            event_id: Uuid::nil(),
+            device_id: Uuid::nil(),
+            device_name: None,
+            geofence_id: Uuid::nil(),
+            geofence_name: Some("Home".to_string()),
+            event_type: "enter".to_string(),
+            timestamp: 1_700_000_000_000,
+            latitude: 0.0,
+            longitude: 0.0,
+            webhook_delivered: true,
+            created_at: Utc::now(),
+        };
+        let row = geofence_event_extract_row(&event);
+        assert_eq!(row.len(), GEOFENCE_EVENT_EXTRACT_COLUMNS.len());
+        assert!(matches!(row[7], ReportCell::DateTime(_)));
+    }
+
+    #[test]
+    fn test_csv_extract_keeps_precision() {
+        let mut writer =
+            ExtractWriter::new(Vec::new(), ExtractFormat::Csv, LOCATION_EXTRACT_COLUMNS).unwrap();
+        writer
+            .write_row(&location_extract_row(&sample_location()))
+            .unwrap();
+        let csv = String::from_utf8(writer.finish().unwrap()).unwrap();
+        let mut lines = csv.lines();
+        assert!(lines
+            .next()
+            .unwrap()
+            .starts_with("id,device_id,device_name,"));
+        let row = lines.next().unwrap();
+        assert!(row.contains("\"Pixel, \"\"work\"\"\""));
+        assert!(row.contains("2023-11-14T22:13:20.123Z,48.1234567,17.7654321,5.5,,1.25,"));
+    }
+
+    #[test]
+    fn test_jsonl_extract_writes_one_object_per_line() {
+        let mut writer =
+            ExtractWriter::new(Vec::new(), ExtractFormat::Jsonl, LOCATION_EXTRACT_COLUMNS).unwrap();
+        let row = location_extract_row(&sample_location());
+        writer.write_row(&row).unwrap();
+        writer.write_row(&row).unwrap();
+        let output = String::from_utf8(writer.finish().unwrap()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let value: Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(value["id"], 42);
+        assert_eq!(value["latitude"], 48.1234567);
+        assert_eq!(value["altitude"], Value::Null);
+    }
+
+    #[test]
+    fn test_parquet_extract_is_framed() {
+        let mut writer =
+            ExtractWriter::new(Vec::new(), ExtractFormat::Parquet, LOCATION_EXTRACT_COLUMNS)
+                .unwrap();
+        writer
+            .write_row(&location_extract_row(&sample_location()))
+            .unwrap();
+        let bytes = writer.finish().unwrap();
+        assert_eq!(&bytes[..4], b"PAR1");
+        assert_eq!(&bytes[bytes.len() - 4..], b"PAR1");
+    }
+
+    #[test]
+    fn test_extract_extension() {
+        assert_eq!(extract_extension(ExtractFormat::Parquet), "parquet");
+        assert_eq!(extract_extension(ExtractFormat::Jsonl), "jsonl");
+        assert_eq!(extract_extension(ExtractFormat::Csv), "csv");
+    }
+}
@@ -0,0 +1,293 @@
+//! Registry of open device agent connections and command delivery.
+//!
+//! Each API instance tracks the agent connections it accepted. Commands and
+//! settings changes for a device connected to this instance are pushed over
+//! its connection. Other devices, including those connected to another
+//! instance, are woken with a `commands_pending` push notification and fetch
+//! their queued commands when they reconnect.
+
+use std::collections::HashMap;
+use std::sync::{PoisonError, RwLock};
+
+use chrono::{DateTime, Utc};
+use domain::models::{AgentConnectionInfo, AgentServerMessage};
+use domain::services::{CommandsPendingPayload, NotificationType};
+use persistence::repositories::DeviceRepository;
+use tokio::sync::mpsc;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::services::push_tokens::{active_push_tokens, handle_send_result};
+
+/// Messages queued for a connection before pushes are refused.
+const OUTBOX_CAPACITY: usize = 64;
+
+/// Work queued for an agent connection.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AgentPush {
+    /// Send a message as is.
+    Message(AgentServerMessage),
+    /// Send the device's pending commands not sent yet.
+    SyncCommands,
+}
+
+#[derive(Debug)]
+struct AgentConnection {
+    connection_id: Uuid,
+    device_pk: i64,
+    organization_id: Uuid,
+    connected_at: DateTime<Utc>,
+    last_heartbeat_at: Option<DateTime<Utc>>,
+    sender: mpsc::Sender<AgentPush>,
+}
+
+#[derive(Debug, Default)]
+struct Connections {
+    /// Connections by public device ID.
+    by_device: HashMap<Uuid, AgentConnection>,
+    /// Public device IDs by internal device ID.
+    device_ids: HashMap<i64, Uuid>,
+}
+
+/// Open agent connections of this instance, at most one per device.
+#[derive(Debug, Default)]
+pub struct AgentRegistry {
+    connections: RwLock<Connections>,
+}
+
+impl AgentRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a new connection for a device, replacing any previous one.
+    ///
+    /// Returns the connection ID and the receiver of pushes for it. The
+    /// receiver of a replaced connection is closed.
+    pub fn register(
+        &self,
+        device_id: Uuid,
+        device_pk: i64,
+        organization_id: Uuid,
+    ) -> (Uuid, mpsc::Receiver<AgentPush>) {
+        let (sender, receiver) = mpsc::channel(OUTBOX_CAPACITY);
+        let connection_id = Uuid::new_v4();
+        let mut connections = self.write();
+        connections.device_ids.insert(device_pk, device_id);
+        connections.by_device.insert(
+            device_id,
+            AgentConnection {
+                connection_id,
+                device_pk,
+                organization_id,
+                connected_at: Utc::now(),
+                last_heartbeat_at: None,
+                sender,
+            },
+        );
+        record_connections(connections.by_device.len());
+        (connection_id, receiver)
+    }
+
+    /// Remove a connection, unless it was already replaced by a newer one.
+    pub fn unregister(&self, device_id: Uuid, connection_id: Uuid) {
+        let mut connections = self.write();
+        let Some(connection) = connections
+            .by_device
+            .get(&device_id)
+            .filter(|c| c.connection_id == connection_id)
+        else {
+            return;
+        };
+        let device_pk = connection.device_pk;
+        connections.by_device.remove(&device_id);
+        connections.device_ids.remove(&device_pk);
+        record_connections(connections.by_device.len());
+    }
+
+    /// Queue a message for a connected device; returns whether it was queued.
+    pub fn push(&self, device_id: Uuid, message: AgentServerMessage) -> bool {
+        self.send(&device_id, AgentPush::Message(message))
+    }
+
+    /// Ask a connected device's connection to send its pending commands;
+    /// returns whether the device is connected and the request was queued.
+    pub fn notify_commands(&self, device_pk: i64) -> bool {
+        let device_id = self.read().device_ids.get(&device_pk).copied();
+        device_id.is_some_and(|id| self.send(&id, AgentPush::SyncCommands))
+    }
+
+    /// Record a heartbeat on a connection.
+    pub fn heartbeat(&self, device_id: Uuid, connection_id: Uuid) {
+        if let Some(connection) = self
+            .write()
+            .by_device
+            .get_mut(&device_id)
+            .filter(|c| c.connection_id == connection_id)
+        {
+            connection.last_heartbeat_at = Some(Utc::now());
+        }
+    }
+
+    /// Open connections of an organization's devices, oldest first.
+    pub fn connections_for_org(&self, organization_id: Uuid) -> Vec<AgentConnectionInfo> {
+        let connections = self.read();
+        let mut infos: Vec<AgentConnectionInfo> = connections
+            .by_device
+            .iter()
+            .filter(|(_, c)| c.organization_id == organization_id)
+            .map(|(device_id, c)| AgentConnectionInfo {
+                connection_id: c.connection_id,
+                device_id: *device_id,
+                connected_at: c.connected_at,
+                last_heartbeat_at: c.last_heartbeat_at,
+            })
+            .collect();
+        infos.sort_by_key(|c| c.connected_at);
+        infos
+    }
+
+    /// Number of open connections.
+    pub fn len(&self) -> usize {
+        self.read().by_device.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn send(&self, device_id: &Uuid, push: AgentPush) -> bool {
+        let connections = self.read();
+        let Some(connection) = connections.by_device.get(device_id) else {
+            return false;
+        };
+        match connection.sender.try_send(push) {
+            Ok(()) => true,
+            Err(e) => {
+                warn!(
+                    device_id = %device_id,
+                    error = %e,
+                    "Failed to queue agent push"
+                );
+                false
+            }
+        }
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, Connections> {
+        self.connections
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, Connections> {
+        self.connections
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+fn record_connections(count: usize) {
+    metrics::gauge!("device_agent_connections").set(count as f64);
+}
+
+/// Deliver newly queued commands to devices (fire-and-forget).
+///
+/// Devices connected to this instance get the commands over their agent
+/// connection; the others are sent a `commands_pending` push notification.
+pub fn deliver_commands(state: &AppState, device_pks: Vec<i64>) {
+    let state = state.clone();
+    tokio::spawn(async move {
+        let device_repo = DeviceRepository::new(state.pool.clone());
+        for device_pk in device_pks {
+            if state.device_agents.notify_commands(device_pk) {
+                continue;
+            }
+            metrics::counter!("device_agent_push_fallback_total", "type" => "command").increment(1);
+
+            let device = match device_repo.find_by_id(device_pk).await {
+                Ok(Some(device)) if device.active => device,
+                Ok(_) => continue,
+                Err(e) => {
+                    warn!(device_pk, error = %e, "Failed to load device for command delivery");
+                    continue;
+                }
+            };
+
+            let payload = CommandsPendingPayload {
+                notification_type: NotificationType::CommandsPending,
+                device_id: device.device_id,
+                timestamp: Utc::now(),
+            };
+            for token in active_push_tokens(&state.pool, device.device_id).await {
+                let result = state
+                    .notification_service
+                    .send_commands_pending(&token, payload.clone())
+                    .await;
+                handle_send_result(&state.pool, device.device_id, &token, result).await;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn heartbeat_ack() -> AgentServerMessage {
+        AgentServerMessage::HeartbeatAck {
+            server_time: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_push_to_connected_device() {
+        let registry = AgentRegistry::new();
+        let device_id = Uuid::new_v4();
+        assert!(!registry.push(device_id, heartbeat_ack()));
+        assert!(!registry.notify_commands(7));
+
+        let (_, mut receiver) = registry.register(device_id, 7, Uuid::new_v4());
+        let message = heartbeat_ack();
+        assert!(registry.push(device_id, message.clone()));
+        assert!(registry.notify_commands(7));
+        assert_eq!(receiver.recv().await, Some(AgentPush::Message(message)));
+        assert_eq!(receiver.recv().await, Some(AgentPush::SyncCommands));
+    }
+
+    #[tokio::test]
+    async fn test_register_replaces_previous_connection() {
+        let registry = AgentRegistry::new();
+        let device_id = Uuid::new_v4();
+        let org_id = Uuid::new_v4();
+
+        let (first_id, mut first) = registry.register(device_id, 1, org_id);
+        let (second_id, _second) = registry.register(device_id, 1, org_id);
+        assert_eq!(first.recv().await, None);
+        assert_eq!(registry.len(), 1);
+
+        // The replaced connection must not remove its successor.
+        registry.unregister(device_id, first_id);
+        assert_eq!(registry.len(), 1);
+        registry.unregister(device_id, second_id);
+        assert!(registry.is_empty());
+        assert!(!registry.notify_commands(1));
+    }
+
+    #[test]
+    fn test_connections_for_org() {
+        let registry = AgentRegistry::new();
+        let org_id = Uuid::new_v4();
+        let device_id = Uuid::new_v4();
+        let (connection_id, _receiver) = registry.register(device_id, 1, org_id);
+        let (_, _other) = registry.register(Uuid::new_v4(), 2, Uuid::new_v4());
+
+        registry.heartbeat(device_id, connection_id);
+        let connections = registry.connections_for_org(org_id);
+        assert_eq!(connections.len(), 1);
+        assert_eq!(connections[0].device_id, device_id);
+        assert_eq!(connections[0].connection_id, connection_id);
+        assert!(connections[0].last_heartbeat_at.is_some());
+    }
+}
@@ -0,0 +1,28 @@
+//! Per-device API usage tracking.
+//!
+//! Counters are updated in the background so tracking never adds latency
+//! to, or fails, the device request being counted.
+
+use domain::models::ApiEndpointClass;
+use persistence::repositories::DeviceApiUsageRepository;
+use sqlx::PgPool;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Count a request from a device for the given endpoint class (fire-and-forget).
+pub fn track_device_request(pool: &PgPool, device_id: Uuid, endpoint_class: ApiEndpointClass) {
+    let repo = DeviceApiUsageRepository::new(pool.clone());
+    tokio::spawn(async move {
+        if let Err(e) = repo
+            .record_request(device_id, endpoint_class.as_str())
+            .await
+        {
+            warn!(
+                device_id = %device_id,
+                endpoint_class = %endpoint_class,
+                error = %e,
+                "Failed to record device API usage"
+            );
+        }
+    });
+}
@@ -242,6 +242,153 @@ The Phone Manager Team"#,
         self.send(message).await
     }
 
+    /// Send a notice that a webhook was disabled after failing repeatedly.
+    pub async fn send_webhook_disabled_email(
+        &self,
+        to_email: &str,
+        to_name: Option<&str>,
+        webhook_name: &str,
+        failure_streak: i32,
+    ) -> Result<(), EmailError> {
+        let subject = format!("Webhook \"{}\" was disabled - Phone Manager", webhook_name);
+
+        let body_text = format!(
+            r#"Hi{name},
+
+Your webhook "{webhook}" was disabled after {failures} failed delivery attempts in a row.
+
+No further events will be sent to it. Once the receiving endpoint is working again, re-enable the webhook in the app or through the API.
+
+Best regards,
+The Phone Manager Team"#,
+            name = to_name.map(|n| format!(" {}", n)).unwrap_or_default(),
+            webhook = webhook_name,
+            failures = failure_streak
+        );
+
+        let body_html = if self.config.template_style == "html" {
+            Some(format!(
+                r#"<!DOCTYPE html>
+<html>
+<head>
+    <meta charset="utf-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>Webhook disabled</title>
+</head>
+<body style="font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, Helvetica, Arial, sans-serif; line-height: 1.6; color: #333; max-width: 600px; margin: 0 auto; padding: 20px;">
+    <div style="background: linear-gradient(135deg, #667eea 0%, #764ba2 100%); padding: 30px; border-radius: 10px 10px 0 0;">
+        <h1 style="color: white; margin: 0; font-size: 24px;">Phone Manager</h1>
+    </div>
+    <div style="background: #f9f9f9; padding: 30px; border-radius: 0 0 10px 10px;">
+        <h2 style="color: #333; margin-top: 0;">Webhook disabled</h2>
+        <p>Hi{name},</p>
+        <p>Your webhook <strong>{webhook}</strong> was disabled after {failures} failed delivery attempts in a row.</p>
+        <p>No further events will be sent to it. Once the receiving endpoint is working again, re-enable the webhook in the app or through the API.</p>
+    </div>
+</body>
+</html>"#,
+                name = to_name.map(|n| format!(" {}", n)).unwrap_or_default(),
+                webhook = webhook_name,
+                failures = failure_streak
+            ))
+        } else {
+            None
+        };
+
+        let message = EmailMessage {
+            to: to_email.to_string(),
+            to_name: to_name.map(|s| s.to_string()),
+            subject,
+            body_text,
+            body_html,
+        };
+
+        self.send(message).await
+    }
+
+    /// Send an invitation to join a group.
+    pub async fn send_group_invite_email(
+        &self,
+        to_email: &str,
+        inviter_name: Option<&str>,
+        group_name: &str,
+        invite_url: &str,
+        expires_in_hours: i32,
+    ) -> Result<(), EmailError> {
+        let inviter = inviter_name.unwrap_or("Someone");
+        let subject = format!(
+            "{} invited you to join \"{}\" - Phone Manager",
+            inviter, group_name
+        );
+
+        let body_text = format!(
+            r#"Hi,
+
+{inviter} invited you to join the group "{group}" on Phone Manager.
+
+Open the link below on your phone to accept. If you don't have an account yet, sign up with this email address and you'll join the group right away:
+
+{url}
+
+This invitation will expire in {hours} hours and can only be accepted with this email address.
+
+If you weren't expecting this invitation, you can safely ignore this email.
+
+Best regards,
+The Phone Manager Team"#,
+            inviter = inviter,
+            group = group_name,
+            url = invite_url,
+            hours = expires_in_hours
+        );
+
+        let body_html = if self.config.template_style == "html" {
+            Some(format!(
+                r#"<!DOCTYPE html>
+<html>
+<head>
+    <meta charset="utf-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>Group invitation</title>
+</head>
+<body style="font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, Helvetica, Arial, sans-serif; line-height: 1.6; color: #333; max-width: 600px; margin: 0 auto; padding: 20px;">
+    <div style="background: linear-gradient(135deg, #667eea 0%, #764ba2 100%); padding: 30px; border-radius: 10px 10px 0 0;">
+        <h1 style="color: white; margin: 0; font-size: 24px;">Phone Manager</h1>
+    </div>
+    <div style="background: #f9f9f9; padding: 30px; border-radius: 0 0 10px 10px;">
+        <h2 style="color: #333; margin-top: 0;">You're invited to {group}</h2>
+        <p>Hi,</p>
+        <p>{inviter} invited you to join the group <strong>{group}</strong> on Phone Manager. Open the invitation on your phone to accept. If you don't have an account yet, sign up with this email address and you'll join the group right away.</p>
+        <div style="text-align: center; margin: 30px 0;">
+            <a href="{url}" style="background: linear-gradient(135deg, #667eea 0%, #764ba2 100%); color: white; padding: 14px 28px; text-decoration: none; border-radius: 6px; font-weight: bold; display: inline-block;">Accept Invitation</a>
+        </div>
+        <p style="color: #666; font-size: 14px;">This invitation will expire in {hours} hours and can only be accepted with this email address.</p>
+        <p style="color: #666; font-size: 14px;">If you weren't expecting this invitation, you can safely ignore this email.</p>
+        <hr style="border: none; border-top: 1px solid #ddd; margin: 30px 0;">
+        <p style="color: #999; font-size: 12px;">Or copy and paste this link into your browser:<br><a href="{url}" style="color: #667eea;">{url}</a></p>
+    </div>
+</body>
+</html>"#,
+                inviter = inviter,
+                group = group_name,
+                url = invite_url,
+                hours = expires_in_hours
+            ))
+        } else {
+            None
+        };
+
+        let message = EmailMessage {
+            to: to_email.to_string(),
+            to_name: None,
+            subject,
+            body_text,
+            body_html,
+        };
+
+        self.send(message).await
+    }
+
     /// Console provider - logs email to console (for development).
     async fn send_console(&self, message: EmailMessage) -> Result<(), EmailError> {
         info!(
@@ -490,6 +637,36 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_send_webhook_disabled_email() {
+        let config = test_config();
+        let service = EmailService::new(config);
+
+        let result = service
+            .send_webhook_disabled_email("user@example.com", None, "Home Assistant", 50)
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_send_group_invite_email() {
+        let config = test_config();
+        let service = EmailService::new(config);
+
+        let result = service
+            .send_group_invite_email(
+                "friend@example.com",
+                Some("Alex"),
+                "Family",
+                "https://app.example.com/join/ABC-DEF-GHJ",
+                72,
+            )
+            .await;
+
+        assert!(result.is_ok());
+    }
+
     #[test]
     fn test_email_message_creation() {
         let message = EmailMessage {
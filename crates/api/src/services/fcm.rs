@@ -8,8 +8,11 @@ use std::time::{Duration, Instant};
 
 use chrono::Utc;
 use domain::services::{
-    NotificationResult, NotificationService, SettingsChangedPayload, UnlockRequestResponsePayload,
+    CommandsPendingPayload, GeofenceArrivingPayload, GroupMessagePayload, LowBatteryPayload,
+    MemberJoinedPayload, NotificationResult, NotificationService, SettingsChangedPayload,
+    UnlockRequestResponsePayload, WebhookDisabledPayload,
 };
+use persistence::faults::{self, FaultPoint};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
@@ -177,6 +180,20 @@ impl FcmNotificationService {
         }
     }
 
+    /// Check that credentials load and contain a usable private key, without
+    /// contacting Google.
+    pub fn verify_credentials(credentials_source: &str) -> Result<(), FcmError> {
+        let credentials = Self::load_credentials(credentials_source)?;
+        jsonwebtoken::EncodingKey::from_rsa_pem(credentials.private_key.as_bytes())
+            .map(|_| ())
+            .map_err(|e| FcmError::CredentialsError(format!("Invalid private key: {}", e)))
+    }
+
+    /// Check that Google issues an access token for the credentials.
+    pub async fn probe(&self) -> Result<(), FcmError> {
+        self.fetch_access_token().await.map(|_| ())
+    }
+
     /// Get a valid OAuth2 access token, refreshing if necessary.
     async fn get_access_token(&self) -> Result<String, FcmError> {
         // Check cache first
@@ -292,6 +309,11 @@ impl FcmNotificationService {
                 tokio::time::sleep(Duration::from_millis(100 * (1 << (attempt - 1)))).await;
             }
 
+            if let Err(e) = faults::inject(FaultPoint::Fcm).await {
+                last_error = Some(FcmError::ApiError(e.to_string()));
+                continue;
+            }
+
             let response = self
                 .client
                 .post(&url)
@@ -375,9 +397,9 @@ impl NotificationService for FcmNotificationService {
                 tracing::warn!(
                     fcm_token = %fcm_token,
                     device_id = %payload.device_id,
-                    "Invalid FCM token - device should re-register"
+                    "FCM rejected token"
                 );
-                NotificationResult::NoToken
+                NotificationResult::InvalidToken
             }
             Err(e) => {
                 tracing::error!(
@@ -418,9 +440,9 @@ impl NotificationService for FcmNotificationService {
                 tracing::warn!(
                     fcm_token = %fcm_token,
                     request_id = %payload.request_id,
-                    "Invalid FCM token - device should re-register"
+                    "FCM rejected token"
                 );
-                NotificationResult::NoToken
+                NotificationResult::InvalidToken
             }
             Err(e) => {
                 tracing::error!(
@@ -433,6 +455,258 @@ impl NotificationService for FcmNotificationService {
             }
         }
     }
+    async fn send_commands_pending(
+        &self,
+        fcm_token: &str,
+        payload: CommandsPendingPayload,
+    ) -> NotificationResult {
+        let data = match serde_json::to_value(&payload) {
+            Ok(v) => v,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to serialize notification payload");
+                return NotificationResult::Failed(format!("Serialization error: {}", e));
+            }
+        };
+
+        match self.send_message(fcm_token, data).await {
+            Ok(()) => {
+                tracing::info!(
+                    fcm_token = %fcm_token,
+                    device_id = %payload.device_id,
+                    "Commands pending notification sent"
+                );
+                NotificationResult::Sent
+            }
+            Err(FcmError::InvalidToken) => {
+                tracing::warn!(
+                    fcm_token = %fcm_token,
+                    device_id = %payload.device_id,
+                    "FCM rejected token"
+                );
+                NotificationResult::InvalidToken
+            }
+            Err(e) => {
+                tracing::error!(
+                    error = %e,
+                    fcm_token = %fcm_token,
+                    device_id = %payload.device_id,
+                    "Failed to send commands pending notification"
+                );
+                NotificationResult::Failed(e.to_string())
+            }
+        }
+    }
+
+    async fn send_geofence_arriving(
+        &self,
+        fcm_token: &str,
+        payload: GeofenceArrivingPayload,
+    ) -> NotificationResult {
+        let data = match serde_json::to_value(&payload) {
+            Ok(v) => v,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to serialize notification payload");
+                return NotificationResult::Failed(format!("Serialization error: {}", e));
+            }
+        };
+
+        match self.send_message(fcm_token, data).await {
+            Ok(()) => {
+                tracing::info!(
+                    fcm_token = %fcm_token,
+                    device_id = %payload.device_id,
+                    geofence_id = %payload.geofence_id,
+                    "Geofence arriving notification sent"
+                );
+                NotificationResult::Sent
+            }
+            Err(FcmError::InvalidToken) => {
+                tracing::warn!(
+                    fcm_token = %fcm_token,
+                    geofence_id = %payload.geofence_id,
+                    "FCM rejected token"
+                );
+                NotificationResult::InvalidToken
+            }
+            Err(e) => {
+                tracing::error!(
+                    error = %e,
+                    fcm_token = %fcm_token,
+                    geofence_id = %payload.geofence_id,
+                    "Failed to send geofence arriving notification"
+                );
+                NotificationResult::Failed(e.to_string())
+            }
+        }
+    }
+
+    async fn send_webhook_disabled(
+        &self,
+        fcm_token: &str,
+        payload: WebhookDisabledPayload,
+    ) -> NotificationResult {
+        let data = match serde_json::to_value(&payload) {
+            Ok(v) => v,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to serialize notification payload");
+                return NotificationResult::Failed(format!("Serialization error: {}", e));
+            }
+        };
+
+        match self.send_message(fcm_token, data).await {
+            Ok(()) => {
+                tracing::info!(
+                    fcm_token = %fcm_token,
+                    webhook_id = %payload.webhook_id,
+                    "Webhook disabled notification sent"
+                );
+                NotificationResult::Sent
+            }
+            Err(FcmError::InvalidToken) => {
+                tracing::warn!(
+                    fcm_token = %fcm_token,
+                    webhook_id = %payload.webhook_id,
+                    "FCM rejected token"
+                );
+                NotificationResult::InvalidToken
+            }
+            Err(e) => {
+                tracing::error!(
+                    error = %e,
+                    fcm_token = %fcm_token,
+                    webhook_id = %payload.webhook_id,
+                    "Failed to send webhook disabled notification"
+                );
+                NotificationResult::Failed(e.to_string())
+            }
+        }
+    }
+
+    async fn send_group_message(
+        &self,
+        fcm_token: &str,
+        payload: GroupMessagePayload,
+    ) -> NotificationResult {
+        let data = match serde_json::to_value(&payload) {
+            Ok(v) => v,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to serialize notification payload");
+                return NotificationResult::Failed(format!("Serialization error: {}", e));
+            }
+        };
+
+        match self.send_message(fcm_token, data).await {
+            Ok(()) => {
+                tracing::info!(
+                    fcm_token = %fcm_token,
+                    message_id = %payload.message_id,
+                    "Group message notification sent"
+                );
+                NotificationResult::Sent
+            }
+            Err(FcmError::InvalidToken) => {
+                tracing::warn!(
+                    fcm_token = %fcm_token,
+                    message_id = %payload.message_id,
+                    "FCM rejected token"
+                );
+                NotificationResult::InvalidToken
+            }
+            Err(e) => {
+                tracing::error!(
+                    error = %e,
+                    fcm_token = %fcm_token,
+                    message_id = %payload.message_id,
+                    "Failed to send group message notification"
+                );
+                NotificationResult::Failed(e.to_string())
+            }
+        }
+    }
+
+    async fn send_low_battery(
+        &self,
+        fcm_token: &str,
+        payload: LowBatteryPayload,
+    ) -> NotificationResult {
+        let data = match serde_json::to_value(&payload) {
+            Ok(v) => v,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to serialize notification payload");
+                return NotificationResult::Failed(format!("Serialization error: {}", e));
+            }
+        };
+
+        match self.send_message(fcm_token, data).await {
+            Ok(()) => {
+                tracing::info!(
+                    fcm_token = %fcm_token,
+                    device_id = %payload.device_id,
+                    "Low battery notification sent"
+                );
+                NotificationResult::Sent
+            }
+            Err(FcmError::InvalidToken) => {
+                tracing::warn!(
+                    fcm_token = %fcm_token,
+                    device_id = %payload.device_id,
+                    "FCM rejected token"
+                );
+                NotificationResult::InvalidToken
+            }
+            Err(e) => {
+                tracing::error!(
+                    error = %e,
+                    fcm_token = %fcm_token,
+                    device_id = %payload.device_id,
+                    "Failed to send low battery notification"
+                );
+                NotificationResult::Failed(e.to_string())
+            }
+        }
+    }
+
+    async fn send_member_joined(
+        &self,
+        fcm_token: &str,
+        payload: MemberJoinedPayload,
+    ) -> NotificationResult {
+        let data = match serde_json::to_value(&payload) {
+            Ok(v) => v,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to serialize notification payload");
+                return NotificationResult::Failed(format!("Serialization error: {}", e));
+            }
+        };
+
+        match self.send_message(fcm_token, data).await {
+            Ok(()) => {
+                tracing::info!(
+                    fcm_token = %fcm_token,
+                    group_id = %payload.group_id,
+                    "Member joined notification sent"
+                );
+                NotificationResult::Sent
+            }
+            Err(FcmError::InvalidToken) => {
+                tracing::warn!(
+                    fcm_token = %fcm_token,
+                    group_id = %payload.group_id,
+                    "FCM rejected token"
+                );
+                NotificationResult::InvalidToken
+            }
+            Err(e) => {
+                tracing::error!(
+                    error = %e,
+                    fcm_token = %fcm_token,
+                    group_id = %payload.group_id,
+                    "Failed to send member joined notification"
+                );
+                NotificationResult::Failed(e.to_string())
+            }
+        }
+    }
 }
 
 #[cfg(test)]
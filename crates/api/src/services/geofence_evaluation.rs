@@ -0,0 +1,228 @@
+//! Server-side geofence evaluation on location ingestion.
+//!
+//! Enabled per device through the `server_geofence_evaluation_enabled`
+//! setting, for devices whose clients do not report geofence events. After
+//! locations are stored, each active geofence of the device is resumed from
+//! its last enter or exit event, client-reported or derived, and the new
+//! locations are replayed through it. Crossings are stored as `server`
+//! events and delivered like client-reported ones, resolved against
+//! overlapping geofences by priority. Locations below the device's
+//! `geofence_min_trust_score` are skipped.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use domain::models::location::MAX_TRUST_SCORE;
+use domain::models::{Geofence, GeofenceEventSource, GeofenceTransitionType};
+use domain::services::{
+    resolve_overlap, GeofenceEvaluator, GeofenceFix, GeofenceRegion,
+    GEOFENCE_EVALUATION_SETTING_KEY, GEOFENCE_MIN_TRUST_SETTING_KEY,
+};
+use persistence::entities::LocationEntity;
+use persistence::repositories::{
+    GeofenceEventRepository, GeofenceRepository, LocationRepository, SettingRepository,
+};
+use sqlx::PgPool;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+/// Run geofence evaluation for a device if it is enabled.
+///
+/// `since` is the earliest capture time of the locations just stored.
+/// Failures are logged so that ingestion never fails because of evaluation.
+pub async fn evaluate_geofences_if_enabled(pool: &PgPool, device_id: Uuid, since: DateTime<Utc>) {
+    let enabled = match SettingRepository::new(pool.clone())
+        .get_device_setting(device_id, GEOFENCE_EVALUATION_SETTING_KEY)
+        .await
+    {
+        Ok(setting) => setting.and_then(|s| s.value.as_bool()).unwrap_or(false),
+        Err(e) => {
+            warn!(
+                device_id = %device_id,
+                error = %e,
+                "Failed to load geofence evaluation setting"
+            );
+            false
+        }
+    };
+    if !enabled {
+        return;
+    }
+
+    let min_trust_score = load_min_trust_score(pool, device_id).await;
+    match evaluate_geofences(pool, device_id, since, min_trust_score).await {
+        Ok(0) => {}
+        Ok(count) => debug!(
+            device_id = %device_id,
+            count,
+            "Recorded server-derived geofence events"
+        ),
+        Err(e) => warn!(
+            device_id = %device_id,
+            error = %e,
+            "Geofence evaluation failed"
+        ),
+    }
+}
+
+/// The device's minimum trust score for geofence evaluation and arrival
+/// forecasts.
+///
+/// Lookup failures are logged and fall back to 0, accepting all locations.
+pub async fn load_min_trust_score(pool: &PgPool, device_id: Uuid) -> i16 {
+    match SettingRepository::new(pool.clone())
+        .get_device_setting(device_id, GEOFENCE_MIN_TRUST_SETTING_KEY)
+        .await
+    {
+        Ok(setting) => setting
+            .and_then(|s| s.value.as_i64())
+            .map_or(0, |v| v.clamp(0, i64::from(MAX_TRUST_SCORE)) as i16),
+        Err(e) => {
+            warn!(
+                device_id = %device_id,
+                error = %e,
+                "Failed to load geofence minimum trust score"
+            );
+            0
+        }
+    }
+}
+
+/// Whether a location is trusted enough for geofence evaluation. Locations
+/// stored before trust scoring are always trusted.
+pub fn is_trusted(location: &LocationEntity, min_trust_score: i16) -> bool {
+    location
+        .trust_score
+        .is_none_or(|score| score >= min_trust_score)
+}
+
+/// Derive and store geofence events, returning how many were recorded.
+///
+/// The device's last location before `since` is replayed first without
+/// producing events: it places a geofence without previous events, so no
+/// event is derived for where the device already was, and catches up on
+/// crossings that were not stored. Crossings of event types the geofence
+/// does not subscribe to, or outside its schedule, are not stored.
+/// Crossings within the geofence's cool-down of its previous enter or exit
+/// are held back until a fix after the cool-down confirms them.
+pub async fn evaluate_geofences(
+    pool: &PgPool,
+    device_id: Uuid,
+    since: DateTime<Utc>,
+    min_trust_score: i16,
+) -> Result<usize, sqlx::Error> {
+    let geofences: Vec<Geofence> = GeofenceRepository::new(pool.clone())
+        .find_by_device_id(device_id, false)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+    if geofences.is_empty() {
+        return Ok(0);
+    }
+
+    let location_repo = LocationRepository::new(pool.clone());
+    let mut locations = location_repo
+        .get_all_locations_in_range(device_id, Some(since), None)
+        .await?;
+    locations.retain(|l| is_trusted(l, min_trust_score));
+    if locations.is_empty() {
+        return Ok(0);
+    }
+    let baseline = location_repo
+        .get_latest_location_before(device_id, since)
+        .await?
+        .filter(|l| is_trusted(l, min_trust_score));
+
+    let event_repo = GeofenceEventRepository::new(pool.clone());
+    let last_events: HashMap<Uuid, (GeofenceTransitionType, i64)> = event_repo
+        .latest_transitions(device_id)
+        .await?
+        .into_iter()
+        .filter_map(|e| {
+            GeofenceTransitionType::parse(&e.event_type).map(|t| (e.geofence_id, (t, e.timestamp)))
+        })
+        .collect();
+
+    let mut recorded = 0;
+    for geofence in &geofences {
+        let region = GeofenceRegion {
+            latitude: geofence.latitude,
+            longitude: geofence.longitude,
+            radius_meters: geofence.radius_meters as f64,
+        };
+        let mut evaluator = match last_events.get(&geofence.geofence_id) {
+            Some(&(last, timestamp_ms)) => GeofenceEvaluator::resume(region, last, timestamp_ms),
+            None => GeofenceEvaluator::new(region),
+        };
+        // Catch up on crossings that were not stored since the last event.
+        if let Some(baseline) = &baseline {
+            evaluator.process(to_fix(baseline));
+        }
+
+        let mut last_recorded_ms = last_events
+            .get(&geofence.geofence_id)
+            .map(|&(_, timestamp_ms)| timestamp_ms);
+
+        for location in &locations {
+            let Some(crossing) = evaluator.process(to_fix(location)) else {
+                continue;
+            };
+            if last_recorded_ms
+                .is_some_and(|last| geofence.is_in_cooldown(last, crossing.timestamp_ms))
+            {
+                // Keep the previous side so the crossing is derived again
+                // from a later fix if the device stays on the new side.
+                evaluator.revert(&crossing);
+                metrics::counter!("geofence_events_throttled_total").increment(1);
+                continue;
+            }
+
+            let subscribed = geofence
+                .event_types
+                .iter()
+                .any(|t| t.as_str() == crossing.event_type.as_str());
+            let Some(at) = DateTime::<Utc>::from_timestamp_millis(crossing.timestamp_ms) else {
+                continue;
+            };
+            if !subscribed || !geofence.is_scheduled_at(at) {
+                continue;
+            }
+            let resolved = resolve_overlap(
+                geofence,
+                &geofences,
+                crossing.latitude,
+                crossing.longitude,
+                at,
+            );
+
+            event_repo
+                .create(
+                    device_id,
+                    geofence.geofence_id,
+                    &geofence.name,
+                    crossing.event_type.as_str(),
+                    crossing.timestamp_ms,
+                    crossing.latitude,
+                    crossing.longitude,
+                    GeofenceEventSource::Server.as_str(),
+                    resolved.geofence_id,
+                )
+                .await?;
+            last_recorded_ms = Some(crossing.timestamp_ms);
+            metrics::counter!("geofence_events_derived_total").increment(1);
+            recorded += 1;
+        }
+    }
+
+    Ok(recorded)
+}
+
+fn to_fix(location: &LocationEntity) -> GeofenceFix {
+    GeofenceFix {
+        latitude: location.latitude,
+        longitude: location.longitude,
+        accuracy: location.accuracy as f64,
+        timestamp_ms: location.captured_at.timestamp_millis(),
+    }
+}
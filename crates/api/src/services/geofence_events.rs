@@ -0,0 +1,77 @@
+//! Fan-out of newly stored geofence events.
+//!
+//! Shared by client-reported and server-derived events so both reach the
+//! group activity feed and webhooks the same way. Events are queued in the
+//! outbox when they are stored and fanned out by the outbox dispatcher.
+//! Events shadowed by a higher-priority overlapping geofence are not fanned
+//! out.
+
+use domain::models::{GeofenceTransitionType, GroupEventType};
+use persistence::entities::GeofenceEventEntity;
+use serde_json::json;
+use sqlx::PgPool;
+
+use crate::services::webhook_delivery::WebhookDeliveryService;
+use crate::services::webhook_secret::WebhookSecrets;
+use crate::services::GroupEventRecorder;
+
+/// Record a group event and deliver webhooks for a stored geofence event
+/// (AC 15.2.5, 15.2.6).
+///
+/// Nothing is delivered for an event that resolved to another geofence.
+/// Errors are returned so the outbox dispatcher retries the event.
+pub async fn dispatch_geofence_event(
+    pool: &PgPool,
+    secrets: &WebhookSecrets,
+    event: &GeofenceEventEntity,
+    geofence_name: &str,
+) -> Result<(), String> {
+    let Some(event_type) = GeofenceTransitionType::parse(&event.event_type) else {
+        return Ok(());
+    };
+    let event_id = event.event_id;
+    let geofence_id = event.geofence_id;
+    let resolved_geofence_id = event.resolved_geofence_id.unwrap_or(geofence_id);
+    if resolved_geofence_id != geofence_id {
+        metrics::counter!("geofence_events_shadowed_total").increment(1);
+        tracing::info!(
+            event_id = %event_id,
+            geofence_id = %geofence_id,
+            resolved_geofence_id = %resolved_geofence_id,
+            "Geofence event shadowed by higher-priority geofence"
+        );
+        return Ok(());
+    }
+
+    GroupEventRecorder::new(pool.clone())
+        .record_for_device(
+            event.device_id,
+            GroupEventType::from(event_type),
+            json!({
+                "event_id": event_id,
+                "geofence_id": geofence_id,
+                "geofence_name": geofence_name,
+                "resolved_geofence_id": resolved_geofence_id,
+                "timestamp": event.timestamp,
+                "latitude": event.latitude,
+                "longitude": event.longitude,
+                "source": event.source,
+            }),
+        )
+        .await;
+
+    WebhookDeliveryService::new(pool.clone(), secrets.clone())
+        .deliver_geofence_event(
+            event_id,
+            event.device_id,
+            geofence_id,
+            geofence_name,
+            resolved_geofence_id,
+            event_type,
+            event.timestamp,
+            event.latitude,
+            event.longitude,
+        )
+        .await
+        .map_err(|e| format!("Failed to deliver geofence event webhooks: {}", e))
+}
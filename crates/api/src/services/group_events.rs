@@ -0,0 +1,82 @@
+//! Group event archive recording.
+//!
+//! Recording is best-effort: failures are logged and never fail the
+//! request that produced the event.
+
+use domain::models::GroupEventType;
+use persistence::repositories::{GroupEventRepository, GroupRepository};
+use sqlx::PgPool;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Records events into the per-group event archive.
+pub struct GroupEventRecorder {
+    repo: GroupEventRepository,
+    groups: GroupRepository,
+}
+
+impl GroupEventRecorder {
+    /// Create a new recorder.
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            repo: GroupEventRepository::new(pool.clone()),
+            groups: GroupRepository::new(pool),
+        }
+    }
+
+    /// Record an event in a single group's archive.
+    pub async fn record(
+        &self,
+        group_id: Uuid,
+        event_type: GroupEventType,
+        device_id: Option<Uuid>,
+        actor_user_id: Option<Uuid>,
+        payload: serde_json::Value,
+    ) {
+        if let Err(e) = self
+            .repo
+            .create(
+                group_id,
+                event_type.as_str(),
+                device_id,
+                actor_user_id,
+                &payload,
+            )
+            .await
+        {
+            warn!(
+                group_id = %group_id,
+                event_type = %event_type,
+                error = %e,
+                "Failed to archive group event"
+            );
+        }
+    }
+
+    /// Record a device's geofence event in the archive of every group the
+    /// device belongs to and of their ancestor groups.
+    pub async fn record_for_device(
+        &self,
+        device_id: Uuid,
+        event_type: GroupEventType,
+        payload: serde_json::Value,
+    ) {
+        let result = match self.groups.geofence_event_group_ids(device_id).await {
+            Ok(group_ids) if group_ids.is_empty() => Ok(0),
+            Ok(group_ids) => {
+                self.repo
+                    .create_for_groups(&group_ids, device_id, event_type.as_str(), &payload)
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            warn!(
+                device_id = %device_id,
+                event_type = %event_type,
+                error = %e,
+                "Failed to archive device group event"
+            );
+        }
+    }
+}
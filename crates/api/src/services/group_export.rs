@@ -0,0 +1,417 @@
+//! Group data export.
+//!
+//! Builds the ZIP archive of a group export job: a manifest, the members,
+//! devices, geofences and trips as JSON, and the devices' location history as
+//! CSV. Location history is filtered the way the exporting user sees it:
+//! owners' privacy zones, sharing windows and schedules apply. Archives are
+//! written to the reports directory and removed when the job expires.
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Seek, Write};
+use std::path::PathBuf;
+
+use chrono::{SecondsFormat, Utc};
+use domain::models::{
+    Geofence, GroupExportDevice, GroupExportManifest, GroupExportMember, PrivacyZoneSet, Trip,
+};
+use persistence::entities::LocationEntity;
+use persistence::repositories::{
+    DeviceGroupMembershipRepository, ExportJobRepository, GeofenceRepository, GroupRepository,
+    LocationHistoryQuery, LocationRepository, TripQuery, TripRepository,
+};
+use serde::Serialize;
+use sqlx::PgPool;
+use thiserror::Error;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use super::report_rendering::csv_escape;
+
+/// MIME type of group export archives.
+pub const GROUP_EXPORT_CONTENT_TYPE: &str = "application/zip";
+
+/// Header row of `locations.csv`.
+pub const LOCATIONS_CSV_HEADER: &str =
+    "device_id,captured_at,latitude,longitude,accuracy,altitude,\
+    speed,bearing,provider,battery_level,network_type,transportation_mode,trip_id\n";
+
+/// Trips fetched per page while exporting.
+const TRIP_PAGE_SIZE: i32 = 500;
+
+/// Locations fetched per page while exporting.
+const LOCATION_PAGE_SIZE: i32 = 1000;
+
+/// Group export errors.
+#[derive(Error, Debug)]
+pub enum GroupExportError {
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+
+    #[error("Serialization error: {0}")]
+    SerializationError(#[from] serde_json::Error),
+
+    #[error("IO error: {0}")]
+    IoError(#[from] io::Error),
+
+    #[error("Archive error: {0}")]
+    ArchiveError(#[from] zip::result::ZipError),
+
+    #[error("Group not found")]
+    GroupNotFound,
+}
+
+/// Builds group export archives.
+pub struct GroupExportService {
+    pool: PgPool,
+    reports_dir: PathBuf,
+}
+
+impl GroupExportService {
+    pub fn new(pool: PgPool, reports_dir: PathBuf) -> Self {
+        Self { pool, reports_dir }
+    }
+
+    /// Archive file name of a job, relative to the reports directory.
+    pub fn archive_file_name(job_id: &str) -> String {
+        format!("{}.zip", job_id)
+    }
+
+    /// Build the archive of a pending job and record the outcome on the job.
+    ///
+    /// `locations` is the group's location filter as seen by the exporting
+    /// user; it is applied to every exported location.
+    pub async fn process_job(&self, job_id: &str, group_id: Uuid, locations: PrivacyZoneSet) {
+        let repo = ExportJobRepository::new(self.pool.clone());
+        match repo.mark_processing(job_id).await {
+            Ok(true) => {}
+            Ok(false) => return,
+            Err(e) => {
+                error!(job_id = %job_id, error = %e, "Failed to mark group export as processing");
+                return;
+            }
+        }
+
+        let file_name = Self::archive_file_name(job_id);
+        let result = match self.write_archive(group_id, &file_name, &locations).await {
+            Ok((manifest, file_size)) => {
+                info!(
+                    job_id = %job_id,
+                    group_id = %group_id,
+                    records = manifest.record_count(),
+                    file_size = file_size,
+                    "Group export completed"
+                );
+                repo.mark_completed_with_file(
+                    job_id,
+                    manifest.record_count(),
+                    &file_name,
+                    file_size as i64,
+                )
+                .await
+            }
+            Err(e) => {
+                warn!(job_id = %job_id, group_id = %group_id, error = %e, "Group export failed");
+                let _ = fs::remove_file(self.reports_dir.join(&file_name));
+                repo.mark_failed(job_id, &e.to_string()).await
+            }
+        };
+        if let Err(e) = result {
+            error!(job_id = %job_id, error = %e, "Failed to record group export outcome");
+        }
+    }
+
+    /// Write the archive, returning its manifest and size in bytes.
+    async fn write_archive(
+        &self,
+        group_id: Uuid,
+        file_name: &str,
+        locations: &PrivacyZoneSet,
+    ) -> Result<(GroupExportManifest, u64), GroupExportError> {
+        let group_repo = GroupRepository::new(self.pool.clone());
+        let group = group_repo
+            .find_by_id(group_id)
+            .await?
+            .ok_or(GroupExportError::GroupNotFound)?;
+
+        fs::create_dir_all(&self.reports_dir)?;
+        let path = self.reports_dir.join(file_name);
+        let mut zip = ZipWriter::new(BufWriter::new(File::create(&path)?));
+
+        let members: Vec<GroupExportMember> = group_repo
+            .list_members(group_id, None, i64::MAX, 0)
+            .await?
+            .into_iter()
+            .map(|m| GroupExportMember {
+                user_id: m.user_id,
+                display_name: m.display_name,
+                role: m.role.into(),
+                joined_at: m.joined_at,
+            })
+            .collect();
+        add_json_entry(&mut zip, "members.json", &members)?;
+
+        let devices: Vec<GroupExportDevice> =
+            DeviceGroupMembershipRepository::new(self.pool.clone())
+                .list_devices_in_group(group_id, i64::MAX, 0)
+                .await?
+                .into_iter()
+                .map(|d| GroupExportDevice {
+                    device_id: d.device_id,
+                    display_name: d.display_name,
+                    owner_user_id: d.owner_user_id,
+                    owner_display_name: d.owner_display_name,
+                    added_at: d.added_at,
+                    last_seen_at: d.last_seen_at,
+                })
+                .collect();
+        add_json_entry(&mut zip, "devices.json", &devices)?;
+
+        let geofence_repo = GeofenceRepository::new(self.pool.clone());
+        let mut geofences: Vec<Geofence> = Vec::new();
+        for device in &devices {
+            geofences.extend(
+                geofence_repo
+                    .find_by_device_id(device.device_id, true)
+                    .await?
+                    .into_iter()
+                    .map(Geofence::from),
+            );
+        }
+        add_json_entry(&mut zip, "geofences.json", &geofences)?;
+
+        let trip_repo = TripRepository::new(self.pool.clone());
+        let mut trips: Vec<Trip> = Vec::new();
+        for device in &devices {
+            let mut cursor = None;
+            loop {
+                let (page, has_more) = trip_repo
+                    .get_trips_by_device(TripQuery {
+                        device_id: device.device_id,
+                        cursor_timestamp: cursor.map(|(ts, _)| ts),
+                        cursor_id: cursor.map(|(_, id)| id),
+                        from_timestamp: None,
+                        to_timestamp: None,
+                        state_filter: None,
+                        purpose_filter: None,
+                        limit: TRIP_PAGE_SIZE,
+                    })
+                    .await?;
+                cursor = page.last().map(|t| (t.start_timestamp, t.id));
+                trips.extend(page.into_iter().map(Trip::from));
+                if !has_more {
+                    break;
+                }
+            }
+        }
+        add_json_entry(&mut zip, "trips.json", &trips)?;
+
+        let location_repo = LocationRepository::new(self.pool.clone());
+        let mut location_count = 0i64;
+        zip.start_file("locations.csv", archive_entry_options(true))?;
+        zip.write_all(LOCATIONS_CSV_HEADER.as_bytes())?;
+        for device in &devices {
+            location_count += write_device_locations::<_, GroupExportError>(
+                &mut zip,
+                &location_repo,
+                device.device_id,
+                device.owner_user_id,
+                locations,
+            )
+            .await?;
+        }
+
+        let manifest = GroupExportManifest {
+            group_id,
+            group_name: group.name,
+            group_slug: group.slug,
+            exported_at: Utc::now(),
+            member_count: members.len() as i64,
+            device_count: devices.len() as i64,
+            location_count,
+            geofence_count: geofences.len() as i64,
+            trip_count: trips.len() as i64,
+        };
+        add_json_entry(&mut zip, "manifest.json", &manifest)?;
+
+        zip.finish()?.flush()?;
+        let file_size = fs::metadata(&path)?.len();
+        Ok((manifest, file_size))
+    }
+}
+
+/// Options of an export archive entry.
+///
+/// Entries are deflated. `large` entries, streamed without knowing their
+/// size up front, get ZIP64 extensions so they may exceed 4 GiB.
+pub fn archive_entry_options(large: bool) -> SimpleFileOptions {
+    SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .large_file(large)
+}
+
+/// Add a value as a pretty-printed JSON entry of an export archive.
+pub fn add_json_entry<W: Write + Seek, T: Serialize>(
+    zip: &mut ZipWriter<W>,
+    name: &str,
+    value: &T,
+) -> io::Result<()> {
+    let data = serde_json::to_vec_pretty(value)?;
+    let large = data.len() as u64 >= u64::from(u32::MAX);
+    zip.start_file(name, archive_entry_options(large))?;
+    zip.write_all(&data)
+}
+
+/// Stream a device's location history into the current archive entry,
+/// newest first, a page at a time.
+///
+/// Each location is passed through `filter` on behalf of `owner`: suppressed
+/// locations are skipped and snapped ones exported at the zone center.
+/// Returns the number of rows written.
+pub async fn write_device_locations<W, E>(
+    zip: &mut ZipWriter<W>,
+    location_repo: &LocationRepository,
+    device_id: Uuid,
+    owner: Option<Uuid>,
+    filter: &PrivacyZoneSet,
+) -> Result<i64, E>
+where
+    W: Write + Seek,
+    E: From<sqlx::Error> + From<io::Error>,
+{
+    let mut count = 0i64;
+    let mut cursor = None;
+    loop {
+        let (page, has_more) = location_repo
+            .get_location_history(LocationHistoryQuery {
+                device_id,
+                cursor_timestamp: cursor.map(|(ts, _)| ts),
+                cursor_id: cursor.map(|(_, id)| id),
+                from_timestamp: None,
+                to_timestamp: None,
+                limit: LOCATION_PAGE_SIZE,
+                ascending: false,
+                source: None,
+            })
+            .await?;
+        cursor = page.last().map(|l| (l.captured_at, l.id));
+        for mut location in page {
+            let Some((latitude, longitude, accuracy)) = filter
+                .share(owner, location.latitude, location.longitude)
+                .apply(
+                    location.latitude,
+                    location.longitude,
+                    f64::from(location.accuracy),
+                )
+            else {
+                continue;
+            };
+            location.latitude = latitude;
+            location.longitude = longitude;
+            location.accuracy = accuracy as f32;
+            zip.write_all(location_csv_row(&location).as_bytes())?;
+            count += 1;
+        }
+        if !has_more {
+            break;
+        }
+    }
+    Ok(count)
+}
+
+/// One row of `locations.csv`.
+pub fn location_csv_row(location: &LocationEntity) -> String {
+    fn opt<T: ToString>(value: Option<T>) -> String {
+        value.map(|v| v.to_string()).unwrap_or_default()
+    }
+    fn text(value: &Option<String>) -> String {
+        value.as_deref().map(csv_escape).unwrap_or_default()
+    }
+
+    format!(
+        "{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
+        location.device_id,
+        location
+            .captured_at
+            .to_rfc3339_opts(SecondsFormat::Millis, true),
+        location.latitude,
+        location.longitude,
+        location.accuracy,
+        opt(location.altitude),
+        opt(location.speed),
+        opt(location.bearing),
+        text(&location.provider),
+        opt(location.battery_level),
+        text(&location.network_type),
+        text(&location.transportation_mode),
+        opt(location.trip_id),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::io::{Cursor, Read};
+
+    #[test]
+    fn test_location_csv_row() {
+        let captured_at = Utc.with_ymd_and_hms(2024, 5, 1, 8, 30, 0).unwrap();
+        let location = LocationEntity {
+            id: 1,
+            device_id: Uuid::nil(),
+            latitude: 48.1486,
+            longitude: 17.1077,
+            accuracy: 12.5,
+            altitude: None,
+            bearing: Some(90.0),
+            speed: None,
+            provider: Some("fused, network".to_string()),
+            battery_level: Some(80),
+            network_type: None,
+            captured_at,
+            created_at: captured_at,
+            transportation_mode: None,
+            detection_source: None,
+            trip_id: None,
+            source: None,
+            trust_score: None,
+        };
+        let row = location_csv_row(&location);
+        assert_eq!(
+            row,
+            "00000000-0000-0000-0000-000000000000,2024-05-01T08:30:00.000Z,48.1486,17.1077,\
+             12.5,,,90,\"fused, network\",80,,,\n"
+        );
+    }
+
+    #[test]
+    fn test_archive_entries_are_deflated() {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        let members = vec!["member"; 1000];
+        add_json_entry(&mut zip, "members.json", &members).unwrap();
+        zip.start_file("locations.csv", archive_entry_options(true))
+            .unwrap();
+        zip.write_all(LOCATIONS_CSV_HEADER.as_bytes()).unwrap();
+        let bytes = zip.finish().unwrap().into_inner();
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+        let mut entry = archive.by_name("members.json").unwrap();
+        assert_eq!(entry.compression(), CompressionMethod::Deflated);
+        assert!(entry.compressed_size() < entry.size());
+        let mut json = String::new();
+        entry.read_to_string(&mut json).unwrap();
+        drop(entry);
+        let parsed: Vec<String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.len(), 1000);
+
+        let mut csv = String::new();
+        archive
+            .by_name("locations.csv")
+            .unwrap()
+            .read_to_string(&mut csv)
+            .unwrap();
+        assert_eq!(csv, LOCATIONS_CSV_HEADER);
+    }
+}
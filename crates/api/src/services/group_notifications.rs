@@ -0,0 +1,143 @@
+//! Group event push notifications.
+//!
+//! Fans low battery and member joined notifications out to the devices of
+//! the group's members, honoring each member's per-group notification
+//! preferences. Sending happens in the background so that the triggering
+//! request never waits for, or fails because of, push delivery.
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use domain::models::{is_low_battery_drop, GroupNotificationCategory};
+use domain::services::{
+    LowBatteryPayload, MemberJoinedPayload, NotificationService, NotificationType,
+};
+use persistence::repositories::{
+    DeviceRepository, DeviceTelemetryRepository, GroupNotificationPreferencesRepository,
+    UserRepository,
+};
+use sqlx::PgPool;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::services::push_tokens::{active_push_tokens, handle_send_result};
+
+/// Alert the device's group members if its latest battery reading dropped to
+/// the low battery threshold.
+///
+/// Call after storing telemetry that includes a battery level.
+pub fn notify_low_battery_if_dropped(
+    pool: PgPool,
+    notification_service: Arc<dyn NotificationService>,
+    device_id: Uuid,
+) {
+    tokio::spawn(async move {
+        let levels = match DeviceTelemetryRepository::new(pool.clone())
+            .latest_battery_levels(device_id)
+            .await
+        {
+            Ok(levels) => levels,
+            Err(e) => {
+                warn!(device_id = %device_id, error = %e, "Failed to load battery levels");
+                return;
+            }
+        };
+        let Some(&battery_level) = levels.first() else {
+            return;
+        };
+        if !is_low_battery_drop(levels.get(1).copied(), battery_level) {
+            return;
+        }
+
+        let device_name = match DeviceRepository::new(pool.clone())
+            .find_by_device_id(device_id)
+            .await
+        {
+            Ok(Some(device)) => device.display_name,
+            Ok(None) => return,
+            Err(e) => {
+                warn!(device_id = %device_id, error = %e, "Failed to load device for low battery");
+                return;
+            }
+        };
+        let recipients = match GroupNotificationPreferencesRepository::new(pool.clone())
+            .list_peer_recipient_device_ids(device_id, GroupNotificationCategory::LowBattery)
+            .await
+        {
+            Ok(recipients) => recipients,
+            Err(e) => {
+                warn!(device_id = %device_id, error = %e, "Failed to load group devices");
+                return;
+            }
+        };
+
+        let payload = LowBatteryPayload {
+            notification_type: NotificationType::LowBattery,
+            device_id,
+            device_name,
+            battery_level,
+            timestamp: Utc::now(),
+        };
+        for peer_id in recipients {
+            for token in active_push_tokens(&pool, peer_id).await {
+                let result = notification_service
+                    .send_low_battery(&token, payload.clone())
+                    .await;
+                handle_send_result(&pool, peer_id, &token, result).await;
+            }
+        }
+    });
+}
+
+/// Tell the other members of a group that `user_id` joined it.
+pub fn notify_member_joined(
+    pool: PgPool,
+    notification_service: Arc<dyn NotificationService>,
+    group_id: Uuid,
+    group_name: String,
+    user_id: Uuid,
+) {
+    tokio::spawn(async move {
+        let member_name = match UserRepository::new(pool.clone()).find_by_id(user_id).await {
+            Ok(Some(user)) => user
+                .display_name
+                .unwrap_or_else(|| "A new member".to_string()),
+            Ok(None) => return,
+            Err(e) => {
+                warn!(user_id = %user_id, error = %e, "Failed to load joined member");
+                return;
+            }
+        };
+        let recipients = match GroupNotificationPreferencesRepository::new(pool.clone())
+            .list_member_recipient_device_ids(
+                group_id,
+                user_id,
+                GroupNotificationCategory::MemberJoins,
+            )
+            .await
+        {
+            Ok(recipients) => recipients,
+            Err(e) => {
+                warn!(group_id = %group_id, error = %e, "Failed to load group members");
+                return;
+            }
+        };
+
+        let payload = MemberJoinedPayload {
+            notification_type: NotificationType::MemberJoined,
+            group_id,
+            group_name,
+            user_id,
+            member_name,
+            timestamp: Utc::now(),
+        };
+        for device_id in recipients {
+            for token in active_push_tokens(&pool, device_id).await {
+                let result = notification_service
+                    .send_member_joined(&token, payload.clone())
+                    .await;
+                handle_send_result(&pool, device_id, &token, result).await;
+            }
+        }
+    });
+}
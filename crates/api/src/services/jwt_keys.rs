@@ -0,0 +1,184 @@
+//! JWT signing keys for the lite profile.
+//!
+//! Self-hosted installs should not need to run openssl before first boot.
+//! When no keys are configured, a key pair is generated on first startup
+//! and persisted to the data directory so issued tokens survive restarts.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use rsa::pkcs1::{EncodeRsaPrivateKey, LineEnding};
+use rsa::pkcs8::EncodePublicKey;
+use rsa::RsaPrivateKey;
+use tracing::info;
+
+use crate::config::JwtAuthConfig;
+
+/// RSA modulus size for generated keys.
+const JWT_KEY_BITS: usize = 2048;
+
+/// File names of the persisted key pair inside the data directory.
+pub(crate) const PRIVATE_KEY_FILE: &str = "jwt_private.pem";
+pub(crate) const PUBLIC_KEY_FILE: &str = "jwt_public.pem";
+
+/// Error types for JWT key provisioning.
+#[derive(Debug, thiserror::Error)]
+pub enum JwtKeyError {
+    #[error("Key file error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("Key generation error: {0}")]
+    Generation(String),
+
+    #[error("Configuration error: {0}")]
+    Config(String),
+}
+
+/// Fill in missing JWT keys from `data_dir`, generating them on first boot.
+///
+/// Keys set through configuration take precedence and are left untouched.
+pub fn ensure_jwt_keys(config: &mut JwtAuthConfig, data_dir: &Path) -> Result<(), JwtKeyError> {
+    ensure_jwt_keys_with_bits(config, data_dir, JWT_KEY_BITS)
+}
+
+fn ensure_jwt_keys_with_bits(
+    config: &mut JwtAuthConfig,
+    data_dir: &Path,
+    bits: usize,
+) -> Result<(), JwtKeyError> {
+    match (config.private_key.is_empty(), config.public_key.is_empty()) {
+        (false, false) => return Ok(()),
+        (true, true) => {}
+        _ => {
+            return Err(JwtKeyError::Config(
+                "PM__JWT__PRIVATE_KEY and PM__JWT__PUBLIC_KEY must be set together".to_string(),
+            ))
+        }
+    }
+
+    let private_path = data_dir.join(PRIVATE_KEY_FILE);
+    let public_path = data_dir.join(PUBLIC_KEY_FILE);
+
+    if private_path.exists() && public_path.exists() {
+        config.private_key = fs::read_to_string(&private_path)?;
+        config.public_key = fs::read_to_string(&public_path)?;
+        info!(path = %private_path.display(), "Loaded JWT keys from data directory");
+        return Ok(());
+    }
+
+    let (private_pem, public_pem) = generate_key_pair(bits)?;
+    fs::create_dir_all(data_dir)?;
+    write_private(&private_path, &private_pem)?;
+    fs::write(&public_path, &public_pem)?;
+    info!(path = %private_path.display(), "Generated JWT keys");
+
+    config.private_key = private_pem;
+    config.public_key = public_pem;
+    Ok(())
+}
+
+/// Generate an RSA key pair as (PKCS#1 private PEM, SPKI public PEM).
+pub(crate) fn generate_key_pair(bits: usize) -> Result<(String, String), JwtKeyError> {
+    let private_key = RsaPrivateKey::new(&mut rand::rngs::OsRng, bits)
+        .map_err(|e| JwtKeyError::Generation(e.to_string()))?;
+    let private_pem = private_key
+        .to_pkcs1_pem(LineEnding::LF)
+        .map_err(|e| JwtKeyError::Generation(e.to_string()))?;
+    let public_pem = private_key
+        .to_public_key()
+        .to_public_key_pem(LineEnding::LF)
+        .map_err(|e| JwtKeyError::Generation(e.to_string()))?;
+    Ok((private_pem.to_string(), public_pem))
+}
+
+/// Write the private key readable by the owner only.
+fn write_private(path: &Path, pem: &str) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::io::Write;
+        use std::os::unix::fs::OpenOptionsExt;
+
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(path)?;
+        file.write_all(pem.as_bytes())
+    }
+    #[cfg(not(unix))]
+    {
+        fs::write(path, pem)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::jwt::JwtConfig;
+    use std::path::PathBuf;
+
+    fn empty_config() -> JwtAuthConfig {
+        JwtAuthConfig {
+            private_key: String::new(),
+            public_key: String::new(),
+            access_token_expiry_secs: 3600,
+            refresh_token_expiry_secs: 2592000,
+            leeway_secs: 30,
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("pm-jwt-keys-{}-{}", name, uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_generates_then_reloads_keys() {
+        let dir = temp_dir("generate");
+
+        let mut first = empty_config();
+        ensure_jwt_keys_with_bits(&mut first, &dir, 1024).unwrap();
+        assert!(first.private_key.contains("BEGIN RSA PRIVATE KEY"));
+        assert!(first.public_key.contains("BEGIN PUBLIC KEY"));
+        assert!(JwtConfig::new(&first.private_key, &first.public_key, 3600, 2592000).is_ok());
+
+        let mut second = empty_config();
+        ensure_jwt_keys_with_bits(&mut second, &dir, 1024).unwrap();
+        assert_eq!(second.private_key, first.private_key);
+        assert_eq!(second.public_key, first.public_key);
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_configured_keys_are_kept() {
+        let dir = temp_dir("configured");
+        let mut config = empty_config();
+        config.private_key = "configured-private".to_string();
+        config.public_key = "configured-public".to_string();
+
+        ensure_jwt_keys_with_bits(&mut config, &dir, 1024).unwrap();
+        assert_eq!(config.private_key, "configured-private");
+        assert!(!dir.join(PRIVATE_KEY_FILE).exists());
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_partial_configuration_rejected() {
+        let dir = temp_dir("partial");
+        let mut config = empty_config();
+        config.private_key = "configured-private".to_string();
+
+        assert!(matches!(
+            ensure_jwt_keys_with_bits(&mut config, &dir, 1024),
+            Err(JwtKeyError::Config(_))
+        ));
+
+        fs::remove_dir_all(dir).ok();
+    }
+}
@@ -0,0 +1,138 @@
+//! Accuracy and speed filtering of uploaded locations.
+//!
+//! Limits come from the device's `max_location_accuracy_meters` and
+//! `max_location_speed_mps` settings, falling back to its organization's
+//! settings. Locations breaking a limit are moved to the quarantine table
+//! instead of the location history.
+
+use domain::services::{
+    filter_limit, LocationFilter, LocationFilterConfig, LocationSample, MAX_ACCURACY_SETTING_KEY,
+    MAX_SPEED_SETTING_KEY,
+};
+use persistence::repositories::{
+    LocationInput, LocationQuarantineRepository, LocationRepository,
+    OrganizationSettingsRepository, QuarantinedLocation, SettingRepository,
+};
+use sqlx::PgPool;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+/// Load the filter limits for a device.
+///
+/// Lookup failures are logged and leave the affected limits disabled so
+/// that ingestion never fails because of the filtering stage.
+pub async fn load_filter_config(
+    pool: &PgPool,
+    device_id: Uuid,
+    organization_id: Option<Uuid>,
+) -> LocationFilterConfig {
+    let setting_repo = SettingRepository::new(pool.clone());
+    let device_config = LocationFilterConfig {
+        max_accuracy_meters: device_limit(&setting_repo, device_id, MAX_ACCURACY_SETTING_KEY).await,
+        max_speed_mps: device_limit(&setting_repo, device_id, MAX_SPEED_SETTING_KEY).await,
+    };
+
+    let Some(organization_id) = organization_id else {
+        return device_config;
+    };
+    let org_config = match OrganizationSettingsRepository::new(pool.clone())
+        .get_by_organization_id(organization_id)
+        .await
+    {
+        Ok(settings) => settings
+            .map(|s| LocationFilterConfig {
+                max_accuracy_meters: filter_limit(s.max_location_accuracy_meters.map(f64::from)),
+                max_speed_mps: filter_limit(s.max_location_speed_mps.map(f64::from)),
+            })
+            .unwrap_or_default(),
+        Err(e) => {
+            warn!(
+                organization_id = %organization_id,
+                error = %e,
+                "Failed to load organization location filter settings"
+            );
+            LocationFilterConfig::default()
+        }
+    };
+
+    device_config.or(org_config)
+}
+
+/// A device's limit for a filter setting, if set to a positive value.
+async fn device_limit(repo: &SettingRepository, device_id: Uuid, key: &str) -> Option<f64> {
+    match repo.get_device_setting(device_id, key).await {
+        Ok(setting) => filter_limit(setting.and_then(|s| s.value.as_f64())),
+        Err(e) => {
+            warn!(
+                device_id = %device_id,
+                setting = key,
+                error = %e,
+                "Failed to load location filter setting"
+            );
+            None
+        }
+    }
+}
+
+/// Quarantine locations that break the configured limits.
+///
+/// Returns the accepted locations sorted by `captured_at`. The filter is
+/// seeded with the device's latest stored location so that speed is
+/// checked across uploads.
+pub async fn quarantine_invalid_locations(
+    pool: &PgPool,
+    device_id: Uuid,
+    config: LocationFilterConfig,
+    mut locations: Vec<LocationInput>,
+) -> Result<Vec<LocationInput>, sqlx::Error> {
+    let last_known = if config.max_speed_mps.is_some() {
+        LocationRepository::new(pool.clone())
+            .get_latest_location(device_id)
+            .await?
+            .map(|last| LocationSample {
+                latitude: last.latitude,
+                longitude: last.longitude,
+                accuracy: last.accuracy as f64,
+                timestamp_ms: last.captured_at.timestamp_millis(),
+            })
+    } else {
+        None
+    };
+    let mut filter = LocationFilter::new(config, last_known);
+
+    locations.sort_by_key(|loc| loc.captured_at);
+
+    let mut accepted = Vec::with_capacity(locations.len());
+    let mut quarantined = Vec::new();
+    for loc in locations {
+        let sample = LocationSample {
+            latitude: loc.latitude,
+            longitude: loc.longitude,
+            accuracy: loc.accuracy,
+            timestamp_ms: loc.captured_at.timestamp_millis(),
+        };
+        match filter.check(sample) {
+            Ok(()) => accepted.push(loc),
+            Err(reason) => {
+                debug!(
+                    device_id = %device_id,
+                    captured_at = %loc.captured_at,
+                    reason = %reason,
+                    "Quarantined location"
+                );
+                metrics::counter!("locations_quarantined_total", "reason" => reason.as_str())
+                    .increment(1);
+                quarantined.push(QuarantinedLocation {
+                    location: loc,
+                    reason,
+                });
+            }
+        }
+    }
+
+    LocationQuarantineRepository::new(pool.clone())
+        .insert_batch(device_id, &quarantined)
+        .await?;
+
+    Ok(accepted)
+}
@@ -0,0 +1,217 @@
+//! Location history import service.
+//!
+//! Processes pending import jobs: parses the uploaded Google Takeout export,
+//! backfills the device's location history in chunks while reporting
+//! progress, and removes the upload once the job finishes.
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use domain::models::location::trust_score;
+use domain::models::GOOGLE_TAKEOUT_SOURCE;
+use domain::services::{prepare_takeout_records, ImportedLocation, TakeoutRecords};
+use persistence::entities::LocationImportJobEntity;
+use persistence::repositories::{
+    LocationImportJobRepository, LocationImportProgress, LocationInput, LocationRepository,
+};
+use sqlx::PgPool;
+use thiserror::Error;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Locations written per transaction; progress is reported after each chunk.
+const IMPORT_CHUNK_SIZE: usize = 500;
+
+/// Location import errors.
+#[derive(Error, Debug)]
+pub enum LocationImportError {
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+
+    #[error("Invalid export file: {0}")]
+    InvalidFile(#[from] serde_json::Error),
+
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Import task failed: {0}")]
+    TaskFailed(String),
+}
+
+/// Service processing location import jobs.
+pub struct LocationImportService {
+    pool: PgPool,
+    imports_dir: PathBuf,
+}
+
+impl LocationImportService {
+    /// Create a new location import service.
+    pub fn new(pool: PgPool, imports_dir: PathBuf) -> Self {
+        Self { pool, imports_dir }
+    }
+
+    /// Path where the upload of a job is stored.
+    pub fn upload_path(&self, job_id: Uuid) -> PathBuf {
+        self.imports_dir.join(format!("{}.json", job_id))
+    }
+
+    /// Store an uploaded export for a job.
+    pub async fn store_upload(&self, job_id: Uuid, body: &[u8]) -> std::io::Result<PathBuf> {
+        tokio::fs::create_dir_all(&self.imports_dir).await?;
+        let path = self.upload_path(job_id);
+        tokio::fs::write(&path, body).await?;
+        Ok(path)
+    }
+
+    /// Process pending import jobs. Returns the number of completed jobs.
+    pub async fn process_pending_jobs(&self, batch_size: i64) -> Result<u32, LocationImportError> {
+        let job_repo = LocationImportJobRepository::new(self.pool.clone());
+        let pending = job_repo.find_pending(batch_size).await?;
+        let mut processed = 0u32;
+
+        for job in pending {
+            // Another instance may have picked the job up already
+            if !job_repo.mark_processing(job.id).await? {
+                continue;
+            }
+
+            match self.process_job(&job_repo, &job).await {
+                Ok(progress) => {
+                    job_repo.mark_completed(job.id, progress).await?;
+                    processed += 1;
+                    info!(
+                        job_id = %job.id,
+                        device_id = %job.device_id,
+                        imported = progress.imported,
+                        duplicates = progress.duplicates,
+                        invalid = progress.invalid,
+                        "Location import completed"
+                    );
+                }
+                Err(e) => {
+                    error!(
+                        job_id = %job.id,
+                        device_id = %job.device_id,
+                        error = %e,
+                        "Location import failed"
+                    );
+                    if let Err(update_err) = job_repo.mark_failed(job.id, &e.to_string()).await {
+                        error!(
+                            job_id = %job.id,
+                            error = %update_err,
+                            "Failed to mark location import as failed"
+                        );
+                    }
+                }
+            }
+
+            remove_upload(Path::new(&job.file_path)).await;
+        }
+
+        Ok(processed)
+    }
+
+    /// Import the records of a single job.
+    async fn process_job(
+        &self,
+        job_repo: &LocationImportJobRepository,
+        job: &LocationImportJobEntity,
+    ) -> Result<LocationImportProgress, LocationImportError> {
+        let path = PathBuf::from(&job.file_path);
+        let records = tokio::task::spawn_blocking(move || {
+            let reader = BufReader::new(File::open(path)?);
+            Ok::<_, LocationImportError>(serde_json::from_reader::<_, TakeoutRecords>(reader)?)
+        })
+        .await
+        .map_err(|e| LocationImportError::TaskFailed(e.to_string()))??;
+
+        let prepared = prepare_takeout_records(&records);
+        let mut progress = LocationImportProgress {
+            total: records.locations.len() as i32,
+            imported: 0,
+            duplicates: prepared.duplicates as i32,
+            invalid: prepared.invalid as i32,
+        };
+        drop(records);
+        job_repo.update_progress(job.id, progress).await?;
+
+        let location_repo = LocationRepository::new(self.pool.clone());
+        for chunk in prepared.locations.chunks(IMPORT_CHUNK_SIZE) {
+            let inputs: Vec<LocationInput> = chunk
+                .iter()
+                .map(|loc| location_input(job.device_id, loc))
+                .collect();
+            let inserted = location_repo
+                .insert_locations_if_absent(job.device_id, &inputs)
+                .await?;
+
+            progress.imported += inserted as i32;
+            progress.duplicates += (chunk.len() - inserted) as i32;
+            job_repo.update_progress(job.id, progress).await?;
+            metrics::counter!("locations_imported_total").increment(inserted as u64);
+        }
+
+        Ok(progress)
+    }
+}
+
+/// Convert an imported record into a location row.
+fn location_input(device_id: Uuid, loc: &ImportedLocation) -> LocationInput {
+    LocationInput {
+        device_id,
+        latitude: loc.latitude,
+        longitude: loc.longitude,
+        accuracy: loc.accuracy,
+        altitude: loc.altitude,
+        bearing: loc.bearing,
+        speed: loc.speed,
+        provider: Some(GOOGLE_TAKEOUT_SOURCE.to_string()),
+        battery_level: None,
+        network_type: None,
+        captured_at: loc.captured_at,
+        transportation_mode: None,
+        detection_source: None,
+        trip_id: None,
+        sequence_number: None,
+        source: None,
+        trust_score: Some(trust_score(None, loc.accuracy) as i16),
+    }
+}
+
+/// Delete a processed upload, logging failures.
+pub async fn remove_upload(path: &Path) {
+    if let Err(e) = tokio::fs::remove_file(path).await {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            warn!(path = %path.display(), error = %e, "Failed to remove location import upload");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_location_input_from_imported() {
+        let captured_at = Utc.timestamp_millis_opt(1_500_000_000_000).unwrap();
+        let device_id = Uuid::new_v4();
+        let input = location_input(
+            device_id,
+            &ImportedLocation {
+                latitude: 48.1486,
+                longitude: 17.1077,
+                accuracy: 12.0,
+                altitude: Some(140.0),
+                speed: None,
+                bearing: Some(90.0),
+                captured_at,
+            },
+        );
+        assert_eq!(input.device_id, device_id);
+        assert_eq!(input.captured_at, captured_at);
+        assert_eq!(input.provider.as_deref(), Some("google_takeout"));
+        assert_eq!(input.bearing, Some(90.0));
+    }
+}
@@ -0,0 +1,114 @@
+//! Optional server-side smoothing of uploaded locations.
+//!
+//! Enabled per device through the `location_smoothing_enabled` setting.
+//! Smoothing is seeded with the device's latest stored location so that
+//! single-point uploads benefit as well as batches. Dropped outliers are
+//! quarantined, and those captured since the seed count towards restarting
+//! the filter.
+
+use domain::services::{
+    LocationSample, LocationSmoother, QuarantineReason, SmoothingConfig, SmoothingOutcome,
+    LOCATION_SMOOTHING_SETTING_KEY,
+};
+use persistence::repositories::{
+    LocationInput, LocationQuarantineRepository, LocationRepository, QuarantinedLocation,
+    SettingRepository,
+};
+use sqlx::PgPool;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+/// Whether smoothing is enabled for a device.
+///
+/// Lookup failures are logged and treated as disabled so that ingestion
+/// never fails because of the smoothing stage.
+pub async fn is_smoothing_enabled(pool: &PgPool, device_id: Uuid) -> bool {
+    let repo = SettingRepository::new(pool.clone());
+    match repo
+        .get_device_setting(device_id, LOCATION_SMOOTHING_SETTING_KEY)
+        .await
+    {
+        Ok(setting) => setting.and_then(|s| s.value.as_bool()).unwrap_or(false),
+        Err(e) => {
+            warn!(
+                device_id = %device_id,
+                error = %e,
+                "Failed to load location smoothing setting"
+            );
+            false
+        }
+    }
+}
+
+/// Smooth locations before persistence, dropping outliers.
+///
+/// Locations are processed in capture order; the returned list is sorted
+/// by `captured_at`.
+pub async fn smooth_locations(
+    pool: &PgPool,
+    device_id: Uuid,
+    mut locations: Vec<LocationInput>,
+) -> Result<Vec<LocationInput>, sqlx::Error> {
+    let config = SmoothingConfig::default();
+    let location_repo = LocationRepository::new(pool.clone());
+    let quarantine_repo = LocationQuarantineRepository::new(pool.clone());
+    let mut smoother = match location_repo.get_latest_location(device_id).await? {
+        Some(last) => {
+            let prior_rejections = quarantine_repo
+                .count_captured_after(
+                    device_id,
+                    QuarantineReason::SmoothingOutlier,
+                    last.captured_at,
+                )
+                .await?;
+            LocationSmoother::with_last_known(
+                config,
+                LocationSample {
+                    latitude: last.latitude,
+                    longitude: last.longitude,
+                    accuracy: last.accuracy as f64,
+                    timestamp_ms: last.captured_at.timestamp_millis(),
+                },
+            )
+            .with_prior_rejections(u32::try_from(prior_rejections).unwrap_or(u32::MAX))
+        }
+        None => LocationSmoother::new(config),
+    };
+
+    locations.sort_by_key(|loc| loc.captured_at);
+
+    let mut smoothed = Vec::with_capacity(locations.len());
+    let mut outliers = Vec::new();
+    for mut loc in locations {
+        let sample = LocationSample {
+            latitude: loc.latitude,
+            longitude: loc.longitude,
+            accuracy: loc.accuracy,
+            timestamp_ms: loc.captured_at.timestamp_millis(),
+        };
+        match smoother.process(sample) {
+            SmoothingOutcome::Accepted(result) => {
+                loc.latitude = result.latitude;
+                loc.longitude = result.longitude;
+                loc.accuracy = result.accuracy;
+                smoothed.push(loc);
+            }
+            SmoothingOutcome::Rejected(reason) => {
+                debug!(
+                    device_id = %device_id,
+                    captured_at = %loc.captured_at,
+                    reason = ?reason,
+                    "Dropped outlier location"
+                );
+                outliers.push(QuarantinedLocation {
+                    location: loc,
+                    reason: QuarantineReason::SmoothingOutlier,
+                });
+            }
+        }
+    }
+
+    quarantine_repo.insert_batch(device_id, &outliers).await?;
+
+    Ok(smoothed)
+}
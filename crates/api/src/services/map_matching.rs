@@ -1,11 +1,15 @@
 //! Map-matching service integration for GPS trace correction.
 //!
-//! Supports OSRM Match API for snapping GPS coordinates to road networks.
+//! Supports the OSRM Match API and the Mapbox Map Matching API for snapping
+//! GPS coordinates to road networks. Mapbox requests are counted and priced
+//! in the `map_matching_billable_requests_total` and
+//! `map_matching_estimated_cost_usd` metrics.
 
 use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 
-use reqwest::Client;
+use persistence::faults::{self, FaultPoint};
+use reqwest::{Client, StatusCode};
 use serde::Deserialize;
 use thiserror::Error;
 use tokio::sync::RwLock;
@@ -66,9 +70,20 @@ pub struct MapMatchingResult {
 
     /// Duration of the match operation in milliseconds.
     pub duration_ms: u64,
+
+    /// Posted speed limit in km/h of each segment between consecutive
+    /// matched coordinates (`None` where unknown).
+    ///
+    /// Only Mapbox returns speed limits; empty for other providers.
+    pub speed_limits: Vec<Option<f64>>,
 }
 
+/// Maximum number of coordinates per Mapbox Map Matching request.
+const MAPBOX_MAX_COORDINATES: usize = 100;
+
 /// OSRM Match API response structure.
+///
+/// The Mapbox Map Matching API responds with the same structure.
 #[derive(Debug, Deserialize)]
 struct OsrmMatchResponse {
     code: String,
@@ -81,6 +96,44 @@ struct OsrmMatchResponse {
 struct OsrmMatching {
     confidence: f64,
     geometry: OsrmGeometry,
+    #[serde(default)]
+    legs: Vec<OsrmLeg>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsrmLeg {
+    #[serde(default)]
+    annotation: Option<OsrmAnnotation>,
+}
+
+/// Per-segment annotations (requested with `annotations=maxspeed`).
+#[derive(Debug, Deserialize)]
+struct OsrmAnnotation {
+    #[serde(default)]
+    maxspeed: Vec<MaxSpeed>,
+}
+
+/// A Mapbox `maxspeed` annotation entry.
+///
+/// Either `{"speed": 50, "unit": "km/h"}`, `{"unknown": true}` or
+/// `{"none": true}` (no posted limit).
+#[derive(Debug, Deserialize)]
+struct MaxSpeed {
+    #[serde(default)]
+    speed: Option<f64>,
+    #[serde(default)]
+    unit: Option<String>,
+}
+
+impl MaxSpeed {
+    /// The limit in km/h, if one is posted.
+    fn kmh(&self) -> Option<f64> {
+        let speed = self.speed?;
+        match self.unit.as_deref() {
+            Some("mph") => Some(speed * 1.609_344),
+            _ => Some(speed),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -274,7 +327,7 @@ impl MapMatchingClient {
             .build()
             .map_err(MapMatchingError::Http)?;
 
-        let rate_limiter = RateLimiter::new(config.rate_limit_per_minute);
+        let rate_limiter = RateLimiter::new(config.provider_rate_limit_per_minute());
         let circuit_breaker = CircuitBreaker::new(
             config.circuit_breaker_failures,
             config.circuit_breaker_reset_secs,
@@ -291,7 +344,7 @@ impl MapMatchingClient {
     /// Check if map-matching is enabled and configured.
     #[allow(dead_code)] // Public API for monitoring
     pub fn is_available(&self) -> bool {
-        self.config.enabled && !self.config.url.is_empty()
+        self.config.enabled && self.config.is_configured()
     }
 
     /// Get current circuit breaker state.
@@ -300,7 +353,7 @@ impl MapMatchingClient {
         self.circuit_breaker.state().await
     }
 
-    /// Match coordinates to road network using OSRM.
+    /// Match coordinates to road network using the configured provider.
     ///
     /// Returns snapped coordinates and confidence score.
     pub async fn match_coordinates(
@@ -313,7 +366,7 @@ impl MapMatchingClient {
         }
 
         // Check if configured
-        if self.config.url.is_empty() {
+        if !self.config.is_configured() {
             return Err(MapMatchingError::NotConfigured);
         }
 
@@ -334,10 +387,19 @@ impl MapMatchingClient {
 
         let start = Instant::now();
 
-        // Build OSRM URL with coordinates
-        let result = self.call_osrm_match(coordinates).await;
+        let result = if self.config.is_mapbox() {
+            self.call_mapbox_match(coordinates).await
+        } else {
+            self.call_osrm_match(coordinates).await
+        };
 
         let duration_ms = start.elapsed().as_millis() as u64;
+        metrics::counter!(
+            "map_matching_requests_total",
+            "provider" => self.config.provider.clone(),
+            "outcome" => if result.is_ok() { "success" } else { "error" }
+        )
+        .increment(1);
 
         match result {
             Ok(mut res) => {
@@ -369,6 +431,10 @@ impl MapMatchingClient {
         &self,
         coordinates: &[Coordinate],
     ) -> Result<MapMatchingResult, MapMatchingError> {
+        faults::inject(FaultPoint::MapMatching)
+            .await
+            .map_err(|e| MapMatchingError::ServiceError(e.to_string()))?;
+
         // Build coordinate string: lon,lat;lon,lat;...
         let coord_str: String = coordinates
             .iter()
@@ -385,15 +451,53 @@ impl MapMatchingClient {
 
         debug!(url = %url, "Calling OSRM Match API");
 
-        let response = self.client.get(&url).send().await.map_err(|e| {
-            if e.is_timeout() {
-                MapMatchingError::Timeout(self.config.timeout_ms)
-            } else {
-                MapMatchingError::Http(e)
-            }
-        })?;
+        let response = self.send(&url).await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(MapMatchingError::ServiceError(format!(
+                "HTTP {}: {}",
+                status, body
+            )));
+        }
+
+        Self::parse_match_response(response).await
+    }
+
+    /// Call the Mapbox Map Matching API.
+    ///
+    /// Longer traces are downsampled to the 100 coordinates a request may
+    /// hold; the full road geometry is still returned.
+    async fn call_mapbox_match(
+        &self,
+        coordinates: &[Coordinate],
+    ) -> Result<MapMatchingResult, MapMatchingError> {
+        faults::inject(FaultPoint::MapMatching)
+            .await
+            .map_err(|e| MapMatchingError::ServiceError(e.to_string()))?;
 
+        let coord_str: String = downsample(coordinates, MAPBOX_MAX_COORDINATES)
+            .iter()
+            .map(|[lon, lat]| format!("{},{}", lon, lat))
+            .collect::<Vec<_>>()
+            .join(";");
+
+        let url = format!(
+            "{}/matching/v5/mapbox/driving/{}?overview=full&geometries=geojson&annotations=maxspeed",
+            self.config.base_url().trim_end_matches('/'),
+            coord_str
+        );
+
+        // The access token is appended after logging to keep it out of logs
+        debug!(url = %url, "Calling Mapbox Map Matching API");
+
+        let response = self
+            .send(&format!("{}&access_token={}", url, self.config.api_key))
+            .await?;
         let status = response.status();
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            return Err(MapMatchingError::RateLimited);
+        }
         if !status.is_success() {
             let body = response.text().await.unwrap_or_default();
             return Err(MapMatchingError::ServiceError(format!(
@@ -402,6 +506,34 @@ impl MapMatchingClient {
             )));
         }
 
+        self.record_billable_request();
+        Self::parse_match_response(response).await
+    }
+
+    /// Count a billed request and its estimated cost.
+    fn record_billable_request(&self) {
+        let provider = self.config.provider.clone();
+        metrics::counter!("map_matching_billable_requests_total", "provider" => provider.clone())
+            .increment(1);
+        metrics::gauge!("map_matching_estimated_cost_usd", "provider" => provider)
+            .increment(self.config.mapbox_cost_per_1000_requests / 1000.0);
+    }
+
+    /// Send a GET request, mapping timeouts.
+    async fn send(&self, url: &str) -> Result<reqwest::Response, MapMatchingError> {
+        self.client.get(url).send().await.map_err(|e| {
+            if e.is_timeout() {
+                MapMatchingError::Timeout(self.config.timeout_ms)
+            } else {
+                MapMatchingError::Http(e)
+            }
+        })
+    }
+
+    /// Parse an OSRM-style match response.
+    async fn parse_match_response(
+        response: reqwest::Response,
+    ) -> Result<MapMatchingResult, MapMatchingError> {
         let osrm_response: OsrmMatchResponse = response
             .json()
             .await
@@ -448,14 +580,47 @@ impl MapMatchingClient {
             ));
         }
 
+        let speed_limits = segment_speed_limits(matching, matched_coordinates.len());
+
         Ok(MapMatchingResult {
             matched_coordinates,
             confidence: matching.confidence as f32,
             duration_ms: 0, // Will be set by caller
+            speed_limits,
         })
     }
 }
 
+/// Flatten the per-leg `maxspeed` annotations into one limit per segment.
+///
+/// Returns an empty list when the response carries no annotations or they
+/// do not line up with the matched geometry.
+fn segment_speed_limits(matching: &OsrmMatching, coordinate_count: usize) -> Vec<Option<f64>> {
+    let limits: Vec<Option<f64>> = matching
+        .legs
+        .iter()
+        .filter_map(|leg| leg.annotation.as_ref())
+        .flat_map(|annotation| annotation.maxspeed.iter().map(MaxSpeed::kmh))
+        .collect();
+
+    if limits.len() + 1 != coordinate_count {
+        return Vec::new();
+    }
+    limits
+}
+
+/// Pick at most `max` evenly spaced coordinates, keeping the first and last.
+fn downsample(coordinates: &[Coordinate], max: usize) -> Vec<Coordinate> {
+    if coordinates.len() <= max || max < 2 {
+        return coordinates.to_vec();
+    }
+
+    let step = (coordinates.len() - 1) as f64 / (max - 1) as f64;
+    (0..max)
+        .map(|i| coordinates[(i as f64 * step).round() as usize])
+        .collect()
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -472,8 +637,11 @@ mod tests {
             } else {
                 "".to_string()
             },
+            api_key: "".to_string(),
             timeout_ms: 30000,
             rate_limit_per_minute: 30,
+            mapbox_rate_limit_per_minute: 300,
+            mapbox_cost_per_1000_requests: 2.0,
             circuit_breaker_failures: 5,
             circuit_breaker_reset_secs: 60,
             enabled,
@@ -517,6 +685,54 @@ mod tests {
         assert!(matches!(result, Err(MapMatchingError::TooFewCoordinates)));
     }
 
+    #[test]
+    fn test_mapbox_requires_api_key() {
+        let mut config = create_test_config(false);
+        config.enabled = true;
+        config.provider = "mapbox".to_string();
+        assert!(!MapMatchingClient::new(config.clone())
+            .unwrap()
+            .is_available());
+
+        config.api_key = "pk.test".to_string();
+        assert_eq!(config.base_url(), "https://api.mapbox.com");
+        assert_eq!(config.provider_rate_limit_per_minute(), 300);
+        assert!(MapMatchingClient::new(config).unwrap().is_available());
+    }
+
+    #[test]
+    fn test_downsample_keeps_endpoints() {
+        let coords: Vec<Coordinate> = (0..250).map(|i| [i as f64, 0.0]).collect();
+        let sampled = downsample(&coords, MAPBOX_MAX_COORDINATES);
+
+        assert_eq!(sampled.len(), MAPBOX_MAX_COORDINATES);
+        assert_eq!(sampled[0], coords[0]);
+        assert_eq!(sampled[99], coords[249]);
+        assert_eq!(downsample(&coords[..10], MAPBOX_MAX_COORDINATES).len(), 10);
+    }
+
+    #[test]
+    fn test_segment_speed_limits() {
+        let matching: OsrmMatching = serde_json::from_value(serde_json::json!({
+            "confidence": 0.9,
+            "geometry": {"coordinates": [[17.0, 48.0], [17.1, 48.0], [17.2, 48.0], [17.3, 48.0]]},
+            "legs": [
+                {"annotation": {"maxspeed": [{"speed": 50, "unit": "km/h"}, {"unknown": true}]}},
+                {"annotation": {"maxspeed": [{"speed": 30, "unit": "mph"}]}}
+            ]
+        }))
+        .unwrap();
+
+        let limits = segment_speed_limits(&matching, 4);
+        assert_eq!(limits.len(), 3);
+        assert_eq!(limits[0], Some(50.0));
+        assert_eq!(limits[1], None);
+        assert!((limits[2].unwrap() - 48.28).abs() < 0.01);
+
+        // Misaligned annotations are dropped
+        assert!(segment_speed_limits(&matching, 3).is_empty());
+    }
+
     #[test]
     fn test_rate_limiter() {
         let limiter = RateLimiter::new(3);
@@ -574,6 +790,7 @@ mod tests {
             matched_coordinates: vec![[-120.0, 45.0], [-120.1, 45.1]],
             confidence: 0.95,
             duration_ms: 150,
+            speed_limits: vec![Some(50.0)],
         };
 
         let debug_str = format!("{:?}", result);
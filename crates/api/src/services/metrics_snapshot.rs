@@ -0,0 +1,272 @@
+//! Metrics snapshots for air-gapped monitoring.
+//!
+//! Deployments that cannot run a Prometheus server get the metrics of the
+//! `/metrics` endpoint written periodically to a directory, as OpenMetrics
+//! text and as JSON, to be collected offline or downloaded through the admin
+//! API. Snapshot files are named `metrics-<UTC timestamp>.<ext>`, so the
+//! newest snapshot sorts last.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use domain::models::MetricsSnapshotFormat;
+use serde::Serialize;
+
+const SNAPSHOT_PREFIX: &str = "metrics-";
+const TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+const ALL_FORMATS: [MetricsSnapshotFormat; 2] = [
+    MetricsSnapshotFormat::OpenMetrics,
+    MetricsSnapshotFormat::Json,
+];
+
+/// A single metric sample.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetricSample {
+    pub name: String,
+    pub labels: BTreeMap<String, String>,
+    /// `null` in JSON for NaN and infinite values.
+    pub value: f64,
+}
+
+/// JSON form of a snapshot.
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricsSnapshot {
+    pub taken_at: DateTime<Utc>,
+    pub samples: Vec<MetricSample>,
+}
+
+/// Parse samples from the Prometheus text exposition format.
+///
+/// Comment lines and malformed lines are skipped.
+pub fn parse_exposition(text: &str) -> Vec<MetricSample> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(parse_sample)
+        .collect()
+}
+
+fn parse_sample(line: &str) -> Option<MetricSample> {
+    let name_end = line.find(|c: char| c == '{' || c.is_whitespace())?;
+    let name = &line[..name_end];
+    let (labels, rest) = if line[name_end..].starts_with('{') {
+        let (labels, consumed) = parse_labels(&line[name_end + 1..])?;
+        (labels, &line[name_end + 1 + consumed..])
+    } else {
+        (BTreeMap::new(), &line[name_end..])
+    };
+    // A timestamp may follow the value
+    let value = rest.split_whitespace().next()?.parse().ok()?;
+    Some(MetricSample {
+        name: name.to_string(),
+        labels,
+        value,
+    })
+}
+
+/// Parse `key="value",...}` and return the labels and the bytes consumed,
+/// including the closing brace.
+fn parse_labels(input: &str) -> Option<(BTreeMap<String, String>, usize)> {
+    let mut labels = BTreeMap::new();
+    let mut chars = input.char_indices().peekable();
+    loop {
+        while chars
+            .next_if(|(_, c)| *c == ',' || c.is_whitespace())
+            .is_some()
+        {}
+        let (start, c) = chars.next()?;
+        if c == '}' {
+            return Some((labels, start + 1));
+        }
+
+        let mut key = c.to_string();
+        for (_, c) in chars.by_ref() {
+            if c == '=' {
+                break;
+            }
+            key.push(c);
+        }
+        if chars.next()?.1 != '"' {
+            return None;
+        }
+
+        let mut value = String::new();
+        loop {
+            match chars.next()?.1 {
+                '"' => break,
+                '\\' => match chars.next()?.1 {
+                    'n' => value.push('\n'),
+                    other => value.push(other),
+                },
+                other => value.push(other),
+            }
+        }
+        labels.insert(key.trim().to_string(), value);
+    }
+}
+
+/// File name of the snapshot taken at `taken_at`.
+pub fn snapshot_file_name(taken_at: DateTime<Utc>, format: MetricsSnapshotFormat) -> String {
+    format!(
+        "{}{}.{}",
+        SNAPSHOT_PREFIX,
+        taken_at.format(TIMESTAMP_FORMAT),
+        format.extension()
+    )
+}
+
+/// Time a snapshot file was taken, from its name.
+fn snapshot_time(file_name: &str) -> Option<DateTime<Utc>> {
+    let stamp = file_name.strip_prefix(SNAPSHOT_PREFIX)?.split('.').next()?;
+    NaiveDateTime::parse_from_str(stamp, TIMESTAMP_FORMAT)
+        .ok()
+        .map(|t| t.and_utc())
+}
+
+/// Write a snapshot of `exposition` in every format.
+///
+/// Files are written under a temporary name and renamed, so readers never
+/// see a partial snapshot.
+pub async fn write_snapshot(
+    dir: &Path,
+    taken_at: DateTime<Utc>,
+    exposition: &str,
+) -> io::Result<()> {
+    tokio::fs::create_dir_all(dir).await?;
+
+    for format in ALL_FORMATS {
+        let content = match format {
+            MetricsSnapshotFormat::OpenMetrics => {
+                let mut text = exposition.trim_end().to_string();
+                text.push_str("\n# EOF\n");
+                text.into_bytes()
+            }
+            MetricsSnapshotFormat::Json => serde_json::to_vec_pretty(&MetricsSnapshot {
+                taken_at,
+                samples: parse_exposition(exposition),
+            })?,
+        };
+
+        let path = dir.join(snapshot_file_name(taken_at, format));
+        let tmp_path = path.with_extension("tmp");
+        tokio::fs::write(&tmp_path, content).await?;
+        tokio::fs::rename(&tmp_path, &path).await?;
+    }
+    Ok(())
+}
+
+/// Path of the newest snapshot in `format`, if any.
+pub async fn latest_snapshot(
+    dir: &Path,
+    format: MetricsSnapshotFormat,
+) -> io::Result<Option<PathBuf>> {
+    let suffix = format!(".{}", format.extension());
+    let mut latest: Option<String> = None;
+    for name in snapshot_names(dir).await? {
+        if name.ends_with(&suffix) && latest.as_ref().is_none_or(|l| name > *l) {
+            latest = Some(name);
+        }
+    }
+    Ok(latest.map(|name| dir.join(name)))
+}
+
+/// Delete snapshots taken before `cutoff`. Returns the number of files
+/// deleted.
+pub async fn prune_snapshots(dir: &Path, cutoff: DateTime<Utc>) -> io::Result<usize> {
+    let mut deleted = 0;
+    for name in snapshot_names(dir).await? {
+        if snapshot_time(&name).is_some_and(|taken_at| taken_at < cutoff) {
+            tokio::fs::remove_file(dir.join(&name)).await?;
+            deleted += 1;
+        }
+    }
+    Ok(deleted)
+}
+
+/// Names of the snapshot files in `dir`; none if it does not exist yet.
+async fn snapshot_names(dir: &Path) -> io::Result<Vec<String>> {
+    let mut entries = match tokio::fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    let mut names = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        if let Some(name) = entry.file_name().to_str() {
+            if snapshot_time(name).is_some() && !name.ends_with(".tmp") {
+                names.push(name.to_string());
+            }
+        }
+    }
+    Ok(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    const EXPOSITION: &str = "\
+# TYPE http_requests_total counter
+http_requests_total{method=\"GET\",path=\"/api/v1/devices\",status=\"200\"} 42
+db_pool_idle 3 1700000000000
+weird{msg=\"a \\\"quoted\\\", value\"} +Inf
+";
+
+    #[test]
+    fn test_parse_exposition() {
+        let samples = parse_exposition(EXPOSITION);
+        assert_eq!(samples.len(), 3);
+
+        assert_eq!(samples[0].name, "http_requests_total");
+        assert_eq!(samples[0].labels["path"], "/api/v1/devices");
+        assert_eq!(samples[0].labels["status"], "200");
+        assert_eq!(samples[0].value, 42.0);
+
+        assert_eq!(samples[1].name, "db_pool_idle");
+        assert!(samples[1].labels.is_empty());
+        assert_eq!(samples[1].value, 3.0);
+
+        assert_eq!(samples[2].labels["msg"], "a \"quoted\", value");
+        assert!(samples[2].value.is_infinite());
+    }
+
+    #[test]
+    fn test_snapshot_file_name() {
+        let taken_at = Utc.with_ymd_and_hms(2026, 3, 1, 8, 30, 0).unwrap();
+        let name = snapshot_file_name(taken_at, MetricsSnapshotFormat::Json);
+        assert_eq!(name, "metrics-20260301T083000Z.json");
+        assert_eq!(snapshot_time(&name), Some(taken_at));
+        assert_eq!(snapshot_time("report.json"), None);
+    }
+
+    #[tokio::test]
+    async fn test_write_latest_and_prune() {
+        let dir = std::env::temp_dir().join(format!("metrics-snapshots-{}", uuid::Uuid::new_v4()));
+        let old = Utc.with_ymd_and_hms(2026, 3, 1, 8, 0, 0).unwrap();
+        let new = old + Duration::minutes(15);
+
+        write_snapshot(&dir, old, EXPOSITION).await.unwrap();
+        write_snapshot(&dir, new, EXPOSITION).await.unwrap();
+
+        let latest = latest_snapshot(&dir, MetricsSnapshotFormat::OpenMetrics)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(latest.ends_with(snapshot_file_name(new, MetricsSnapshotFormat::OpenMetrics)));
+        let text = tokio::fs::read_to_string(&latest).await.unwrap();
+        assert!(text.ends_with("+Inf\n# EOF\n"));
+
+        assert_eq!(prune_snapshots(&dir, new).await.unwrap(), 2);
+        assert_eq!(snapshot_names(&dir).await.unwrap().len(), 2);
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+        assert!(latest_snapshot(&dir, MetricsSnapshotFormat::Json)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
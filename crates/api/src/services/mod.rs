@@ -1,15 +1,46 @@
 //! External service integrations.
 
 pub mod admin_bootstrap;
+pub mod api_usage;
 pub mod apple_auth;
+pub mod arrival_forecast;
 pub mod auth;
+pub mod content_filter;
 pub mod cookies;
+pub mod data_extract;
+pub mod device_agent;
+pub mod device_usage;
 pub mod email;
 pub mod fcm;
+pub mod geofence_evaluation;
+pub mod geofence_events;
+pub mod group_events;
+pub mod group_export;
+pub mod group_notifications;
+pub mod jwt_keys;
+pub mod location_filter;
+pub mod location_import;
+pub mod location_smoothing;
 pub mod map_matching;
+pub mod metrics_snapshot;
+pub mod movement_detection;
+pub mod org_webhook_delivery;
+pub mod organization_export;
+pub mod outbox;
+pub mod parquet;
 pub mod path_correction;
+pub mod push_tokens;
+pub mod qr_code;
 pub mod report_generation;
+pub mod report_rendering;
+pub mod tracking_schedule;
+pub mod trip_classification;
+pub mod trip_detection;
+pub mod trip_export;
+pub mod webhook_client_cert;
 pub mod webhook_delivery;
+pub mod webhook_secret;
+pub mod xlsx;
 
 #[allow(unused_imports)] // Used in routes
 pub use apple_auth::AppleAuthClient;
@@ -21,6 +52,8 @@ pub use cookies::CookieHelper;
 pub use email::{EmailError, EmailMessage, EmailService};
 #[allow(unused_imports)] // Used when FCM is enabled
 pub use fcm::{FcmError, FcmNotificationService};
+pub use group_events::GroupEventRecorder;
+pub use location_import::LocationImportService;
 #[allow(unused_imports)] // Public API for external use
 pub use map_matching::{MapMatchingClient, MapMatchingResult};
 pub use path_correction::PathCorrectionService;
@@ -0,0 +1,142 @@
+//! Server-side movement state detection on location ingestion.
+//!
+//! Enabled per device through the `server_movement_detection_enabled`
+//! setting. After locations are stored, the detector is resumed from the
+//! device's last derived transition, replays recent locations and records
+//! new arrivals and departures as `LOCATION_STREAM` movement events.
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use domain::models::movement_event::{DetectionSource, MovementState};
+use domain::services::{
+    MovementDetectionConfig, MovementDetector, MovementFix, MovementTransition,
+    MOVEMENT_DETECTION_SETTING_KEY,
+};
+use persistence::entities::MovementEventEntity;
+use persistence::repositories::{
+    LocationRepository, MovementEventInput, MovementEventRepository, SettingRepository,
+};
+use sqlx::PgPool;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+/// Confidence recorded on derived events; lower than sensor-based detection.
+const DERIVED_CONFIDENCE: f64 = 0.8;
+
+/// Run movement detection for a device if it is enabled.
+///
+/// `since` is the earliest capture time of the locations just stored.
+/// Failures are logged so that ingestion never fails because of detection.
+pub async fn detect_movement_if_enabled(pool: &PgPool, device_id: Uuid, since: DateTime<Utc>) {
+    let enabled = match SettingRepository::new(pool.clone())
+        .get_device_setting(device_id, MOVEMENT_DETECTION_SETTING_KEY)
+        .await
+    {
+        Ok(setting) => setting.and_then(|s| s.value.as_bool()).unwrap_or(false),
+        Err(e) => {
+            warn!(
+                device_id = %device_id,
+                error = %e,
+                "Failed to load movement detection setting"
+            );
+            false
+        }
+    };
+    if !enabled {
+        return;
+    }
+
+    match detect_movement(pool, device_id, since).await {
+        Ok(0) => {}
+        Ok(count) => debug!(
+            device_id = %device_id,
+            count,
+            "Recorded derived movement transitions"
+        ),
+        Err(e) => warn!(
+            device_id = %device_id,
+            error = %e,
+            "Movement detection failed"
+        ),
+    }
+}
+
+/// Derive and store movement transitions, returning how many were recorded.
+pub async fn detect_movement(
+    pool: &PgPool,
+    device_id: Uuid,
+    since: DateTime<Utc>,
+) -> Result<usize, sqlx::Error> {
+    let config = MovementDetectionConfig::default();
+    let movement_repo = MovementEventRepository::new(pool.clone());
+    let location_repo = LocationRepository::new(pool.clone());
+
+    let last = movement_repo
+        .get_latest_transition(device_id)
+        .await?
+        .and_then(to_transition);
+
+    // Replay one dwell period before the new locations so a stationary
+    // period that started in an earlier upload can complete, but never
+    // from before the last recorded transition.
+    let mut from = since - Duration::milliseconds(config.dwell_ms);
+    let mut detector = match last {
+        Some(last) => {
+            if let Some(last_at) = Utc.timestamp_millis_opt(last.timestamp_ms).single() {
+                from = from.max(last_at);
+            }
+            MovementDetector::resume(config, last)
+        }
+        None => MovementDetector::new(config),
+    };
+
+    let locations = location_repo
+        .get_all_locations_in_range(device_id, Some(from), None)
+        .await?;
+
+    let events: Vec<MovementEventInput> = locations
+        .into_iter()
+        .filter_map(|loc| {
+            detector.process(MovementFix {
+                latitude: loc.latitude,
+                longitude: loc.longitude,
+                accuracy: loc.accuracy as f64,
+                speed: loc.speed.map(|s| s as f64),
+                timestamp_ms: loc.captured_at.timestamp_millis(),
+            })
+        })
+        .filter(|t| last.is_none_or(|last| t.timestamp_ms > last.timestamp_ms))
+        .map(|t| MovementEventInput {
+            device_id,
+            trip_id: None,
+            timestamp: t.timestamp_ms,
+            latitude: t.latitude,
+            longitude: t.longitude,
+            accuracy: t.accuracy,
+            speed: t.speed,
+            bearing: None,
+            altitude: None,
+            transportation_mode: t.transportation_mode.as_str().to_string(),
+            confidence: DERIVED_CONFIDENCE,
+            detection_source: DetectionSource::LocationStream.as_str().to_string(),
+            movement_state: Some(t.state.as_str().to_string()),
+        })
+        .collect();
+
+    if events.is_empty() {
+        return Ok(0);
+    }
+    movement_repo.insert_events_batch(events).await
+}
+
+fn to_transition(entity: MovementEventEntity) -> Option<MovementTransition> {
+    let state: MovementState = entity.movement_state.as_deref()?.parse().ok()?;
+    Some(MovementTransition {
+        state,
+        latitude: entity.latitude,
+        longitude: entity.longitude,
+        accuracy: entity.accuracy as f64,
+        speed: entity.speed.map(|s| s as f64),
+        timestamp_ms: entity.timestamp,
+        transportation_mode: entity.transportation_mode.parse().ok()?,
+    })
+}
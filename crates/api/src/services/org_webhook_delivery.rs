@@ -0,0 +1,306 @@
+//! Organization webhook delivery service.
+//!
+//! Delivers organization events, such as member lifecycle changes and trip
+//! speed violations, to the organization's webhooks subscribed to them.
+//! Deliveries are logged like device webhook deliveries and retried by the
+//! webhook retry job following the webhook's retry policy; the same circuit
+//! breaker protects failing targets. Webhooks with a client certificate are
+//! delivered over mutual TLS.
+
+use std::time::{Duration, Instant};
+
+use chrono::{Duration as ChronoDuration, Utc};
+use domain::models::speed_violation::SpeedViolationEventPayload;
+use domain::models::{payload_content_type, MemberEventPayload, WebhookRetryPolicy};
+use persistence::entities::{OrgWebhookEntity, WebhookDeliveryEntity};
+use persistence::faults::{self, FaultPoint};
+use persistence::repositories::{OrgWebhookRepository, WebhookDeliveryRepository};
+use reqwest::Client;
+use sqlx::PgPool;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+use super::webhook_client_cert::mtls_client;
+use super::webhook_delivery::{
+    sign_timestamped_payload, sign_webhook_payload, DeliveryResponse, WebhookDeliveryError,
+    CIRCUIT_BREAKER_COOLDOWN_SECS, CIRCUIT_BREAKER_THRESHOLD, SIGNATURE_HEADER,
+};
+use super::webhook_secret::WebhookSecrets;
+
+/// Service for delivering organization webhooks.
+pub struct OrgWebhookDeliveryService {
+    pool: PgPool,
+    secrets: WebhookSecrets,
+    client: Client,
+}
+
+impl OrgWebhookDeliveryService {
+    /// Create a new organization webhook delivery service.
+    pub fn new(pool: PgPool, secrets: WebhookSecrets) -> Self {
+        Self {
+            pool,
+            secrets,
+            client: Client::new(),
+        }
+    }
+
+    /// Deliver an event to the enabled webhooks of an organization that
+    /// subscribe to `event_type`. Returns the number of webhooks it was
+    /// delivered to successfully.
+    pub async fn deliver_event(
+        &self,
+        organization_id: Uuid,
+        event_type: &str,
+        payload: &serde_json::Value,
+    ) -> Result<usize, WebhookDeliveryError> {
+        let webhooks = OrgWebhookRepository::new(self.pool.clone())
+            .find_enabled_for_event(organization_id, event_type)
+            .await?;
+        if webhooks.is_empty() {
+            debug!(
+                organization_id = %organization_id,
+                event_type = event_type,
+                "No org webhooks subscribed to event"
+            );
+            return Ok(0);
+        }
+
+        let delivery_repo = WebhookDeliveryRepository::new(self.pool.clone());
+        let source = format!("/organizations/{}", organization_id);
+        let mut delivered = 0;
+        for webhook in &webhooks {
+            let payload = webhook.payload_format().wrap(
+                event_type,
+                &source,
+                None,
+                Utc::now(),
+                payload.clone(),
+            );
+            let delivery = delivery_repo
+                .create(webhook.webhook_id, None, event_type, &payload)
+                .await?;
+            if self
+                .attempt(webhook, &delivery.delivery_id, &payload)
+                .await?
+            {
+                delivered += 1;
+            }
+        }
+        Ok(delivered)
+    }
+
+    /// Retry a pending delivery to an organization webhook.
+    pub async fn retry_delivery(
+        &self,
+        delivery: &WebhookDeliveryEntity,
+        webhook: &OrgWebhookEntity,
+    ) -> Result<(), WebhookDeliveryError> {
+        let delivery_repo = WebhookDeliveryRepository::new(self.pool.clone());
+
+        if !webhook.enabled {
+            delivery_repo
+                .update_attempt(
+                    delivery.delivery_id,
+                    false,
+                    None,
+                    Some("Webhook disabled"),
+                    None,
+                    None,
+                    &webhook.delivery_policy(),
+                )
+                .await?;
+            return Ok(());
+        }
+
+        if let Some(open_until) = webhook.circuit_open_until {
+            if open_until > Utc::now() {
+                delivery_repo
+                    .postpone_retry(
+                        delivery.delivery_id,
+                        open_until + ChronoDuration::seconds(60),
+                    )
+                    .await?;
+                return Ok(());
+            }
+        }
+
+        self.attempt(webhook, &delivery.delivery_id, &delivery.payload)
+            .await?;
+        Ok(())
+    }
+
+    /// Send a payload to a webhook and record the attempt. Returns whether
+    /// the target accepted it.
+    async fn attempt(
+        &self,
+        webhook: &OrgWebhookEntity,
+        delivery_id: &Uuid,
+        payload: &serde_json::Value,
+    ) -> Result<bool, WebhookDeliveryError> {
+        let policy = webhook.delivery_policy();
+        let payload_json = serde_json::to_string(payload)?;
+        let signature = sign_webhook_payload(&payload_json, &webhook.secret)?;
+        let content_type = payload_content_type(payload);
+
+        let (success, status, error, response) = match self
+            .send(webhook, &payload_json, content_type, &signature, &policy)
+            .await
+        {
+            Ok(response) => (
+                response.is_success(),
+                Some(response.status_code as i32),
+                None,
+                Some(response),
+            ),
+            Err(e) => (false, None, Some(e.to_string()), None),
+        };
+
+        WebhookDeliveryRepository::new(self.pool.clone())
+            .update_attempt(
+                *delivery_id,
+                success,
+                status,
+                error.as_deref(),
+                response.as_ref().map(|r| r.latency_ms),
+                response.as_ref().map(|r| r.body.as_str()),
+                &policy,
+            )
+            .await?;
+
+        let webhook_repo = OrgWebhookRepository::new(self.pool.clone());
+        if success {
+            info!(
+                webhook_id = %webhook.webhook_id,
+                delivery_id = %delivery_id,
+                "Org webhook delivered successfully"
+            );
+            if webhook.consecutive_failures > 0 {
+                if let Err(e) = webhook_repo.reset_failures(webhook.webhook_id).await {
+                    warn!(webhook_id = %webhook.webhook_id, error = %e, "Failed to reset consecutive failures");
+                }
+            }
+        } else {
+            warn!(
+                webhook_id = %webhook.webhook_id,
+                delivery_id = %delivery_id,
+                status_code = ?status,
+                error = ?error,
+                "Org webhook delivery failed"
+            );
+            handle_delivery_failure(&webhook_repo, webhook.webhook_id).await;
+        }
+        Ok(success)
+    }
+
+    async fn send(
+        &self,
+        webhook: &OrgWebhookEntity,
+        payload: &str,
+        content_type: &str,
+        signature: &str,
+        policy: &WebhookRetryPolicy,
+    ) -> Result<DeliveryResponse, WebhookDeliveryError> {
+        faults::inject(FaultPoint::Webhooks).await?;
+
+        let timestamped_signature =
+            sign_timestamped_payload(payload, &webhook.secret, Utc::now().timestamp())?;
+        let client = match (&webhook.client_cert_pem, &webhook.client_key_encrypted) {
+            (Some(cert), Some(key)) => mtls_client(&self.secrets, cert, key, None)
+                .map_err(WebhookDeliveryError::ClientCertificateError)?,
+            _ => self.client.clone(),
+        };
+        let started = Instant::now();
+        let response = client
+            .post(&webhook.target_url)
+            .timeout(Duration::from_secs(policy.timeout_secs))
+            .header("Content-Type", content_type)
+            .header("X-Webhook-Signature", signature)
+            .header(SIGNATURE_HEADER, timestamped_signature)
+            .body(payload.to_string())
+            .send()
+            .await?;
+        Ok(DeliveryResponse::read(response, started).await)
+    }
+}
+
+/// Count a failure and open the circuit breaker at the threshold.
+async fn handle_delivery_failure(webhook_repo: &OrgWebhookRepository, webhook_id: Uuid) {
+    match webhook_repo.increment_failures(webhook_id).await {
+        Ok(failures) if failures >= CIRCUIT_BREAKER_THRESHOLD => {
+            let open_until = Utc::now() + ChronoDuration::seconds(CIRCUIT_BREAKER_COOLDOWN_SECS);
+            if let Err(e) = webhook_repo.open_circuit(webhook_id, open_until).await {
+                error!(webhook_id = %webhook_id, error = %e, "Failed to open circuit breaker");
+            } else {
+                warn!(
+                    webhook_id = %webhook_id,
+                    open_until = %open_until,
+                    "Org webhook circuit breaker opened due to consecutive failures"
+                );
+            }
+        }
+        Ok(_) => {}
+        Err(e) => {
+            warn!(webhook_id = %webhook_id, error = %e, "Failed to increment consecutive failures")
+        }
+    }
+}
+
+/// Deliver a member lifecycle event in the background.
+///
+/// Failures are logged; the change that caused the event never fails
+/// because of webhooks.
+pub fn emit_member_event(pool: &PgPool, secrets: &WebhookSecrets, payload: MemberEventPayload) {
+    let service = OrgWebhookDeliveryService::new(pool.clone(), secrets.clone());
+    tokio::spawn(async move {
+        let event_type = payload.event_type.as_str();
+        let value = match serde_json::to_value(&payload) {
+            Ok(value) => value,
+            Err(e) => {
+                error!(error = %e, "Failed to serialize member event");
+                return;
+            }
+        };
+        if let Err(e) = service
+            .deliver_event(payload.organization_id, event_type, &value)
+            .await
+        {
+            error!(
+                organization_id = %payload.organization_id,
+                event_type = event_type,
+                error = %e,
+                "Failed to deliver member event"
+            );
+        }
+    });
+}
+
+/// Deliver a trip speed violation event in the background.
+///
+/// Failures are logged; path correction never fails because of webhooks.
+pub fn emit_speed_violation_event(
+    pool: &PgPool,
+    secrets: &WebhookSecrets,
+    payload: SpeedViolationEventPayload,
+) {
+    let service = OrgWebhookDeliveryService::new(pool.clone(), secrets.clone());
+    tokio::spawn(async move {
+        let value = match serde_json::to_value(&payload) {
+            Ok(value) => value,
+            Err(e) => {
+                error!(error = %e, "Failed to serialize speed violation event");
+                return;
+            }
+        };
+        if let Err(e) = service
+            .deliver_event(payload.organization_id, &payload.event_type, &value)
+            .await
+        {
+            error!(
+                organization_id = %payload.organization_id,
+                trip_id = %payload.trip_id,
+                error = %e,
+                "Failed to deliver speed violation event"
+            );
+        }
+    });
+}
@@ -0,0 +1,247 @@
+//! Organization data export.
+//!
+//! Builds the ZIP archive of an organization export job: a manifest, the
+//! users, managed devices and device policies as JSON, the devices' location
+//! history as CSV and the audit log as JSON Lines. Device owners' privacy
+//! zones apply to the exported location history. Archives are written to the
+//! reports directory and removed when the job expires.
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+
+use chrono::Utc;
+use domain::models::{
+    ListDevicePoliciesQuery, ListOrgUsersQuery, OrganizationExportDevice,
+    OrganizationExportManifest, PrivacyZoneSet,
+};
+use persistence::repositories::{
+    AuditLogRepository, DevicePolicyRepository, DeviceRepository, ExportJobRepository,
+    LocationRepository, OrgUserRepository, OrganizationRepository,
+};
+use sqlx::PgPool;
+use thiserror::Error;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+use zip::ZipWriter;
+
+use super::group_export::{
+    add_json_entry, archive_entry_options, write_device_locations, LOCATIONS_CSV_HEADER,
+};
+
+/// MIME type of organization export archives.
+pub const ORGANIZATION_EXPORT_CONTENT_TYPE: &str = "application/zip";
+
+/// Users and policies fetched per page while exporting.
+const LIST_PAGE_SIZE: i32 = 100;
+
+/// Audit logs fetched per page while exporting.
+const AUDIT_LOG_PAGE_SIZE: i64 = 1000;
+
+/// Organization export errors.
+#[derive(Error, Debug)]
+pub enum OrganizationExportError {
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+
+    #[error("Serialization error: {0}")]
+    SerializationError(#[from] serde_json::Error),
+
+    #[error("IO error: {0}")]
+    IoError(#[from] io::Error),
+
+    #[error("Archive error: {0}")]
+    ArchiveError(#[from] zip::result::ZipError),
+
+    #[error("Organization not found")]
+    OrganizationNotFound,
+}
+
+/// Builds organization export archives.
+pub struct OrganizationExportService {
+    pool: PgPool,
+    reports_dir: PathBuf,
+}
+
+impl OrganizationExportService {
+    pub fn new(pool: PgPool, reports_dir: PathBuf) -> Self {
+        Self { pool, reports_dir }
+    }
+
+    /// Archive file name of a job, relative to the reports directory.
+    pub fn archive_file_name(job_id: &str) -> String {
+        format!("{}.zip", job_id)
+    }
+
+    /// Build the archive of a pending job and record the outcome on the job.
+    ///
+    /// `locations` holds the device owners' privacy zones; it is applied to
+    /// every exported location.
+    pub async fn process_job(&self, job_id: &str, org_id: Uuid, locations: PrivacyZoneSet) {
+        let repo = ExportJobRepository::new(self.pool.clone());
+        match repo.mark_processing(job_id).await {
+            Ok(true) => {}
+            Ok(false) => return,
+            Err(e) => {
+                error!(job_id = %job_id, error = %e, "Failed to mark organization export as processing");
+                return;
+            }
+        }
+
+        let file_name = Self::archive_file_name(job_id);
+        let result = match self.write_archive(org_id, &file_name, &locations).await {
+            Ok((manifest, file_size)) => {
+                info!(
+                    job_id = %job_id,
+                    org_id = %org_id,
+                    records = manifest.record_count(),
+                    file_size = file_size,
+                    "Organization export completed"
+                );
+                repo.mark_completed_with_file(
+                    job_id,
+                    manifest.record_count(),
+                    &file_name,
+                    file_size as i64,
+                )
+                .await
+            }
+            Err(e) => {
+                warn!(job_id = %job_id, org_id = %org_id, error = %e, "Organization export failed");
+                let _ = fs::remove_file(self.reports_dir.join(&file_name));
+                repo.mark_failed(job_id, &e.to_string()).await
+            }
+        };
+        if let Err(e) = result {
+            error!(job_id = %job_id, error = %e, "Failed to record organization export outcome");
+        }
+    }
+
+    /// Write the archive, returning its manifest and size in bytes.
+    async fn write_archive(
+        &self,
+        org_id: Uuid,
+        file_name: &str,
+        locations: &PrivacyZoneSet,
+    ) -> Result<(OrganizationExportManifest, u64), OrganizationExportError> {
+        let organization = OrganizationRepository::new(self.pool.clone())
+            .find_by_id(org_id)
+            .await?
+            .ok_or(OrganizationExportError::OrganizationNotFound)?;
+
+        fs::create_dir_all(&self.reports_dir)?;
+        let path = self.reports_dir.join(file_name);
+        let mut zip = ZipWriter::new(BufWriter::new(File::create(&path)?));
+
+        let user_repo = OrgUserRepository::new(self.pool.clone());
+        let mut users = Vec::new();
+        for page in 1.. {
+            let (batch, _) = user_repo
+                .list(
+                    org_id,
+                    &ListOrgUsersQuery {
+                        page: Some(page),
+                        per_page: Some(LIST_PAGE_SIZE),
+                        role: None,
+                    },
+                )
+                .await?;
+            let done = batch.len() < LIST_PAGE_SIZE as usize;
+            users.extend(batch);
+            if done {
+                break;
+            }
+        }
+        add_json_entry(&mut zip, "users.json", &users)?;
+
+        let devices: Vec<OrganizationExportDevice> = DeviceRepository::new(self.pool.clone())
+            .list_org_managed_devices(org_id)
+            .await?
+            .into_iter()
+            .map(|d| OrganizationExportDevice {
+                device_id: d.device_id,
+                display_name: d.display_name,
+                platform: d.platform,
+                active: d.active,
+                owner_user_id: d.owner_user_id,
+                created_at: d.created_at,
+                last_seen_at: d.last_seen_at,
+            })
+            .collect();
+        add_json_entry(&mut zip, "devices.json", &devices)?;
+
+        let policy_repo = DevicePolicyRepository::new(self.pool.clone());
+        let mut policies = Vec::new();
+        for page in 1.. {
+            let (batch, _) = policy_repo
+                .list(
+                    org_id,
+                    &ListDevicePoliciesQuery {
+                        page: Some(page),
+                        per_page: Some(LIST_PAGE_SIZE as u32),
+                        is_default: None,
+                    },
+                )
+                .await?;
+            let done = batch.len() < LIST_PAGE_SIZE as usize;
+            policies.extend(batch);
+            if done {
+                break;
+            }
+        }
+        add_json_entry(&mut zip, "policies.json", &policies)?;
+
+        let location_repo = LocationRepository::new(self.pool.clone());
+        let mut location_count = 0i64;
+        zip.start_file("locations.csv", archive_entry_options(true))?;
+        zip.write_all(LOCATIONS_CSV_HEADER.as_bytes())?;
+        for device in &devices {
+            location_count += write_device_locations::<_, OrganizationExportError>(
+                &mut zip,
+                &location_repo,
+                device.device_id,
+                device.owner_user_id,
+                locations,
+            )
+            .await?;
+        }
+
+        // Audit logs can be numerous, so they are streamed one per line
+        let audit_repo = AuditLogRepository::new(self.pool.clone());
+        let mut audit_log_count = 0i64;
+        let mut last_seq = 0i64;
+        zip.start_file("audit_logs.jsonl", archive_entry_options(true))?;
+        loop {
+            let page = audit_repo
+                .list_after_sequence(org_id, last_seq, AUDIT_LOG_PAGE_SIZE)
+                .await?;
+            for (seq, log) in &page {
+                let mut line = serde_json::to_vec(log)?;
+                line.push(b'\n');
+                zip.write_all(&line)?;
+                last_seq = *seq;
+            }
+            audit_log_count += page.len() as i64;
+            if (page.len() as i64) < AUDIT_LOG_PAGE_SIZE {
+                break;
+            }
+        }
+
+        let manifest = OrganizationExportManifest {
+            organization_id: org_id,
+            organization_name: organization.name,
+            organization_slug: organization.slug,
+            exported_at: Utc::now(),
+            user_count: users.len() as i64,
+            device_count: devices.len() as i64,
+            policy_count: policies.len() as i64,
+            location_count,
+            audit_log_count,
+        };
+        add_json_entry(&mut zip, "manifest.json", &manifest)?;
+
+        zip.finish()?.flush()?;
+        let file_size = fs::metadata(&path)?.len();
+        Ok((manifest, file_size))
+    }
+}
@@ -0,0 +1,85 @@
+//! Dispatch of transactional outbox messages.
+//!
+//! Messages are dispatched at least once: a crash after the side effect but
+//! before the message is marked processed repeats it. Webhook receivers can
+//! deduplicate deliveries by their delivery ID.
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use domain::models::OutboxMessage;
+use domain::services::{NotificationService, NotificationType, UnlockRequestResponsePayload};
+use persistence::entities::GeofenceEventEntity;
+use persistence::repositories::GeofenceEventRepository;
+use sqlx::PgPool;
+use tracing::info;
+
+use crate::services::geofence_events::dispatch_geofence_event;
+use crate::services::push_tokens::{active_push_tokens, handle_send_result};
+use crate::services::webhook_secret::WebhookSecrets;
+
+/// Perform the side effect of an outbox message.
+pub async fn dispatch(
+    pool: &PgPool,
+    notification_service: &Arc<dyn NotificationService>,
+    secrets: &WebhookSecrets,
+    message: OutboxMessage,
+) -> Result<(), String> {
+    match message {
+        OutboxMessage::GeofenceEvent {
+            event_id,
+            geofence_name,
+        } => {
+            let event = GeofenceEventRepository::new(pool.clone())
+                .find_by_event_id(event_id)
+                .await
+                .map_err(|e| format!("Failed to load geofence event: {}", e))?;
+            // The event may have been deleted with its device since
+            let Some(event) = event else {
+                return Ok(());
+            };
+            dispatch_geofence_event(
+                pool,
+                secrets,
+                &GeofenceEventEntity::from(event),
+                &geofence_name,
+            )
+            .await
+        }
+        OutboxMessage::UnlockRequestResponse {
+            device_id,
+            request_id,
+            setting_key,
+            status,
+            note,
+            decided_by,
+        } => {
+            let tokens = active_push_tokens(pool, device_id).await;
+            if tokens.is_empty() {
+                info!(
+                    request_id = %request_id,
+                    "Skipping notification - device has no push token"
+                );
+                return Ok(());
+            }
+
+            let payload = UnlockRequestResponsePayload {
+                notification_type: NotificationType::UnlockRequestResponse,
+                request_id,
+                setting_key,
+                status,
+                note,
+                decided_by,
+                timestamp: Utc::now(),
+            };
+
+            for token in tokens {
+                let result = notification_service
+                    .send_unlock_request_response(&token, payload.clone())
+                    .await;
+                handle_send_result(pool, device_id, &token, result).await;
+            }
+            Ok(())
+        }
+    }
+}
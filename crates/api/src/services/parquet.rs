@@ -0,0 +1,436 @@
+//! Parquet writer for analytical extracts.
+//!
+//! Adapts extract rows of `ReportCell`s to the `parquet` crate's column
+//! writers. Schemas are flat and every column is optional. Rows are buffered
+//! per row group and flushed once the group is full, so memory is bounded by
+//! the row group size regardless of extract length.
+
+use std::io::{self, Write};
+use std::sync::Arc;
+
+use parquet::basic::{
+    Compression, GzipLevel, LogicalType, Repetition, TimeUnit, Type as PhysicalType,
+};
+use parquet::data_type::{BoolType, ByteArray, ByteArrayType, DoubleType, Int64Type};
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::SerializedFileWriter;
+use parquet::format::MilliSeconds;
+use parquet::schema::types::Type;
+
+use super::report_rendering::ReportCell;
+
+/// MIME type of Parquet files.
+pub const PARQUET_CONTENT_TYPE: &str = "application/vnd.apache.parquet";
+
+/// Rows buffered before a row group is written.
+pub const DEFAULT_ROW_GROUP_SIZE: usize = 50_000;
+
+const CREATED_BY: &str = "phone-manager-backend";
+
+/// Column value type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParquetType {
+    Boolean,
+    Int64,
+    Double,
+    /// UTF-8 string.
+    Utf8,
+    /// Milliseconds since the Unix epoch, UTC.
+    TimestampMillis,
+}
+
+impl ParquetType {
+    fn physical(&self) -> PhysicalType {
+        match self {
+            ParquetType::Boolean => PhysicalType::BOOLEAN,
+            ParquetType::Int64 | ParquetType::TimestampMillis => PhysicalType::INT64,
+            ParquetType::Double => PhysicalType::DOUBLE,
+            ParquetType::Utf8 => PhysicalType::BYTE_ARRAY,
+        }
+    }
+
+    fn logical(&self) -> Option<LogicalType> {
+        match self {
+            ParquetType::Utf8 => Some(LogicalType::String),
+            ParquetType::TimestampMillis => Some(LogicalType::Timestamp {
+                is_adjusted_to_u_t_c: true,
+                unit: TimeUnit::MILLIS(MilliSeconds {}),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// A named, typed column. All columns are nullable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParquetColumn {
+    pub name: &'static str,
+    pub column_type: ParquetType,
+}
+
+impl ParquetColumn {
+    pub const fn new(name: &'static str, column_type: ParquetType) -> Self {
+        Self { name, column_type }
+    }
+}
+
+/// Column chunk compression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParquetCompression {
+    None,
+    #[default]
+    Gzip,
+}
+
+impl ParquetCompression {
+    fn codec(&self) -> Compression {
+        match self {
+            ParquetCompression::None => Compression::UNCOMPRESSED,
+            ParquetCompression::Gzip => Compression::GZIP(GzipLevel::default()),
+        }
+    }
+}
+
+/// Non-null values of one column within the current row group.
+enum ColumnValues {
+    Boolean(Vec<bool>),
+    Int64(Vec<i64>),
+    Double(Vec<f64>),
+    ByteArray(Vec<ByteArray>),
+}
+
+impl ColumnValues {
+    fn for_type(column_type: ParquetType) -> Self {
+        match column_type {
+            ParquetType::Boolean => ColumnValues::Boolean(Vec::new()),
+            ParquetType::Int64 | ParquetType::TimestampMillis => ColumnValues::Int64(Vec::new()),
+            ParquetType::Double => ColumnValues::Double(Vec::new()),
+            ParquetType::Utf8 => ColumnValues::ByteArray(Vec::new()),
+        }
+    }
+}
+
+/// Values of one column within the current row group.
+struct ColumnBuffer {
+    /// 1 for a value, 0 for null.
+    definition_levels: Vec<i16>,
+    values: ColumnValues,
+}
+
+impl ColumnBuffer {
+    fn new(column_type: ParquetType) -> Self {
+        Self {
+            definition_levels: Vec::new(),
+            values: ColumnValues::for_type(column_type),
+        }
+    }
+
+    fn push(&mut self, cell: &ReportCell) {
+        match (&mut self.values, cell) {
+            (_, ReportCell::Empty) => {}
+            (ColumnValues::Boolean(values), ReportCell::Boolean(b)) => values.push(*b),
+            (ColumnValues::Int64(values), ReportCell::Integer(v)) => values.push(*v),
+            (ColumnValues::Int64(values), ReportCell::DateTime(ts)) => {
+                values.push(ts.timestamp_millis())
+            }
+            (ColumnValues::Double(values), ReportCell::Number(v)) => values.push(*v),
+            (ColumnValues::Double(values), ReportCell::Integer(v)) => values.push(*v as f64),
+            (ColumnValues::ByteArray(values), ReportCell::Text(text)) => {
+                values.push(ByteArray::from(text.as_str()))
+            }
+            _ => unreachable!("cell type checked by accepts()"),
+        }
+        self.definition_levels
+            .push(i16::from(!matches!(cell, ReportCell::Empty)));
+    }
+}
+
+/// Streaming Parquet file writer.
+pub struct ParquetWriter<W: Write + Send> {
+    writer: SerializedFileWriter<W>,
+    columns: Vec<ParquetColumn>,
+    buffers: Vec<ColumnBuffer>,
+    buffered_rows: usize,
+    row_group_size: usize,
+    row_groups: usize,
+}
+
+impl<W: Write + Send> ParquetWriter<W> {
+    /// Create a writer with the default compression and emit the file header.
+    pub fn new(out: W, columns: &[ParquetColumn]) -> io::Result<Self> {
+        Self::with_compression(out, columns, ParquetCompression::default())
+    }
+
+    /// Create a writer compressing column chunks with `compression`.
+    pub fn with_compression(
+        out: W,
+        columns: &[ParquetColumn],
+        compression: ParquetCompression,
+    ) -> io::Result<Self> {
+        let fields = columns
+            .iter()
+            .map(|column| {
+                Type::primitive_type_builder(column.name, column.column_type.physical())
+                    .with_repetition(Repetition::OPTIONAL)
+                    .with_logical_type(column.column_type.logical())
+                    .build()
+                    .map(Arc::new)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let schema = Type::group_type_builder("schema")
+            .with_fields(fields)
+            .build()?;
+        let properties = WriterProperties::builder()
+            .set_compression(compression.codec())
+            .set_created_by(CREATED_BY.to_string())
+            .build();
+
+        Ok(Self {
+            writer: SerializedFileWriter::new(out, Arc::new(schema), Arc::new(properties))?,
+            columns: columns.to_vec(),
+            buffers: columns
+                .iter()
+                .map(|c| ColumnBuffer::new(c.column_type))
+                .collect(),
+            buffered_rows: 0,
+            row_group_size: DEFAULT_ROW_GROUP_SIZE,
+            row_groups: 0,
+        })
+    }
+
+    /// Override the number of rows per row group.
+    pub fn with_row_group_size(mut self, rows: usize) -> Self {
+        self.row_group_size = rows.max(1);
+        self
+    }
+
+    /// Append a row. Cells must follow the column order; missing trailing
+    /// cells are null.
+    pub fn write_row(&mut self, cells: &[ReportCell]) -> io::Result<()> {
+        if cells.len() > self.columns.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "row has {} cells but schema has {} columns",
+                    cells.len(),
+                    self.columns.len()
+                ),
+            ));
+        }
+
+        // Check the whole row first so a bad cell leaves no partial row
+        for (i, column) in self.columns.iter().enumerate() {
+            let cell = cells.get(i).unwrap_or(&ReportCell::Empty);
+            if !accepts(column.column_type, cell) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "column {} expects {:?}, got {:?}",
+                        column.name, column.column_type, cell
+                    ),
+                ));
+            }
+        }
+
+        for (i, buffer) in self.buffers.iter_mut().enumerate() {
+            buffer.push(cells.get(i).unwrap_or(&ReportCell::Empty));
+        }
+
+        self.buffered_rows += 1;
+        if self.buffered_rows >= self.row_group_size {
+            self.flush_row_group()?;
+        }
+        Ok(())
+    }
+
+    /// Flush buffered rows and write the footer, returning the writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        if self.buffered_rows > 0 {
+            self.flush_row_group()?;
+        }
+        let mut out = self.writer.into_inner()?;
+        out.flush()?;
+        Ok(out)
+    }
+
+    fn flush_row_group(&mut self) -> io::Result<()> {
+        let mut row_group = self.writer.next_row_group()?;
+        for (i, column) in self.columns.iter().enumerate() {
+            let buffer =
+                std::mem::replace(&mut self.buffers[i], ColumnBuffer::new(column.column_type));
+            let mut writer = row_group
+                .next_column()?
+                .ok_or_else(|| io::Error::other("Parquet schema has fewer columns than rows"))?;
+            let levels = Some(buffer.definition_levels.as_slice());
+            match &buffer.values {
+                ColumnValues::Boolean(values) => writer
+                    .typed::<BoolType>()
+                    .write_batch(values, levels, None)?,
+                ColumnValues::Int64(values) => writer
+                    .typed::<Int64Type>()
+                    .write_batch(values, levels, None)?,
+                ColumnValues::Double(values) => writer
+                    .typed::<DoubleType>()
+                    .write_batch(values, levels, None)?,
+                ColumnValues::ByteArray(values) => writer
+                    .typed::<ByteArrayType>()
+                    .write_batch(values, levels, None)?,
+            };
+            writer.close()?;
+        }
+        row_group.close()?;
+
+        self.row_groups += 1;
+        self.buffered_rows = 0;
+        Ok(())
+    }
+}
+
+/// Whether a cell can be stored in a column of the given type.
+fn accepts(column_type: ParquetType, cell: &ReportCell) -> bool {
+    matches!(
+        (column_type, cell),
+        (_, ReportCell::Empty)
+            | (ParquetType::Boolean, ReportCell::Boolean(_))
+            | (ParquetType::Int64, ReportCell::Integer(_))
+            | (
+                ParquetType::Double,
+                ReportCell::Number(_) | ReportCell::Integer(_)
+            )
+            | (ParquetType::Utf8, ReportCell::Text(_))
+            | (
+                ParquetType::TimestampMillis,
+                ReportCell::DateTime(_) | ReportCell::Integer(_)
+            )
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Bytes;
+    use chrono::{TimeZone, Utc};
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use parquet::record::Field;
+
+    const COLUMNS: [ParquetColumn; 5] = [
+        ParquetColumn::new("id", ParquetType::Int64),
+        ParquetColumn::new("name", ParquetType::Utf8),
+        ParquetColumn::new("score", ParquetType::Double),
+        ParquetColumn::new("active", ParquetType::Boolean),
+        ParquetColumn::new("seen_at", ParquetType::TimestampMillis),
+    ];
+
+    fn read_back(bytes: Vec<u8>) -> SerializedFileReader<Bytes> {
+        SerializedFileReader::new(Bytes::from(bytes)).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut writer = ParquetWriter::new(Vec::new(), &COLUMNS).unwrap();
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        writer
+            .write_row(&[
+                1i64.into(),
+                "a".into(),
+                1.5f64.into(),
+                true.into(),
+                ts.into(),
+            ])
+            .unwrap();
+        writer.write_row(&[2i64.into()]).unwrap();
+        let bytes = writer.finish().unwrap();
+        assert_eq!(&bytes[..4], b"PAR1");
+
+        let reader = read_back(bytes);
+        let metadata = reader.metadata();
+        assert_eq!(metadata.file_metadata().num_rows(), 2);
+        let fields: Vec<&str> = metadata
+            .file_metadata()
+            .schema_descr()
+            .columns()
+            .iter()
+            .map(|c| c.name())
+            .collect();
+        assert_eq!(fields, vec!["id", "name", "score", "active", "seen_at"]);
+        let chunk = metadata.row_group(0).column(0);
+        assert_eq!(chunk.compression(), Compression::GZIP(GzipLevel::default()));
+
+        let rows: Vec<Vec<Field>> = reader
+            .get_row_iter(None)
+            .unwrap()
+            .map(|row| {
+                row.unwrap()
+                    .get_column_iter()
+                    .map(|(_, field)| field.clone())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            rows[0],
+            vec![
+                Field::Long(1),
+                Field::Str("a".to_string()),
+                Field::Double(1.5),
+                Field::Bool(true),
+                Field::TimestampMillis(ts.timestamp_millis()),
+            ]
+        );
+        assert_eq!(
+            rows[1],
+            vec![
+                Field::Long(2),
+                Field::Null,
+                Field::Null,
+                Field::Null,
+                Field::Null
+            ]
+        );
+    }
+
+    #[test]
+    fn test_uncompressed() {
+        let mut writer =
+            ParquetWriter::with_compression(Vec::new(), &COLUMNS[..1], ParquetCompression::None)
+                .unwrap();
+        writer.write_row(&[7i64.into()]).unwrap();
+        let reader = read_back(writer.finish().unwrap());
+        assert_eq!(
+            reader.metadata().row_group(0).column(0).compression(),
+            Compression::UNCOMPRESSED
+        );
+    }
+
+    #[test]
+    fn test_row_groups_flush_at_size() {
+        let mut writer = ParquetWriter::new(Vec::new(), &COLUMNS[..1])
+            .unwrap()
+            .with_row_group_size(2);
+        for i in 0..5 {
+            writer.write_row(&[(i as i64).into()]).unwrap();
+        }
+        assert_eq!(writer.row_groups, 2);
+        assert_eq!(writer.buffered_rows, 1);
+
+        let reader = read_back(writer.finish().unwrap());
+        assert_eq!(reader.metadata().num_row_groups(), 3);
+        assert_eq!(reader.metadata().file_metadata().num_rows(), 5);
+    }
+
+    #[test]
+    fn test_type_mismatch_is_rejected() {
+        let mut writer = ParquetWriter::new(Vec::new(), &COLUMNS).unwrap();
+        assert!(writer
+            .write_row(&[1i64.into(), "ok".into(), "not a number".into()])
+            .is_err());
+        assert!(writer
+            .buffers
+            .iter()
+            .all(|b| b.definition_levels.is_empty()));
+        assert!(writer
+            .write_row(&[ReportCell::Empty, ReportCell::Empty, 3i64.into()])
+            .is_ok());
+        let too_long = vec![ReportCell::Empty; COLUMNS.len() + 1];
+        assert!(writer.write_row(&too_long).is_err());
+    }
+}
@@ -4,17 +4,26 @@
 //! 1. Extract trip locations
 //! 2. Call map-matching service
 //! 3. Store corrected path
+//! 4. Detect and store speed-limit violations when the provider annotates
+//!    the matched path with speed limits
 
+use domain::models::speed_violation::{SpeedViolationEventPayload, SpeedViolationResponse};
+use domain::services::{
+    detect_speed_violations, SpeedLimitSegment, SpeedSample, SpeedViolationConfig,
+};
+use persistence::entities::LocationEntity;
 use persistence::repositories::{
-    LocationRepository, TripPathCorrectionInput, TripPathCorrectionRepository,
-    TripPathCorrectionUpdateInput,
+    DeviceRepository, LocationRepository, TripPathCorrectionInput, TripPathCorrectionRepository,
+    TripPathCorrectionUpdateInput, TripSpeedViolationRepository,
 };
 use sqlx::PgPool;
 use thiserror::Error;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
-use crate::services::map_matching::{MapMatchingClient, MapMatchingError};
+use crate::services::map_matching::{MapMatchingClient, MapMatchingError, MapMatchingResult};
+use crate::services::org_webhook_delivery::emit_speed_violation_event;
+use crate::services::webhook_secret::WebhookSecrets;
 
 // ============================================================================
 // Error Types
@@ -61,6 +70,8 @@ pub struct PathCorrectionResult {
 pub struct PathCorrectionService {
     pool: PgPool,
     map_matching_client: Option<std::sync::Arc<MapMatchingClient>>,
+    /// Used to deliver speed violation events to organization webhooks.
+    webhook_secrets: WebhookSecrets,
 }
 
 impl PathCorrectionService {
@@ -70,10 +81,12 @@ impl PathCorrectionService {
     pub fn new(
         pool: PgPool,
         map_matching_client: Option<std::sync::Arc<MapMatchingClient>>,
+        webhook_secrets: WebhookSecrets,
     ) -> Self {
         Self {
             pool,
             map_matching_client,
+            webhook_secrets,
         }
     }
 
@@ -201,6 +214,19 @@ impl PathCorrectionService {
 
                 let corrected_points = result.matched_coordinates.len();
 
+                // Violations are a by-product; failing to store them must not
+                // fail the correction
+                if let Err(e) = self
+                    .record_speed_violations(trip_id, &locations, &result)
+                    .await
+                {
+                    warn!(
+                        trip_id = %trip_id,
+                        error = %e,
+                        "Failed to record speed violations"
+                    );
+                }
+
                 // Update with corrected path
                 let update = TripPathCorrectionUpdateInput {
                     corrected_path_coords: Some(result.matched_coordinates),
@@ -264,6 +290,88 @@ impl PathCorrectionService {
             }
         }
     }
+
+    /// Detect and store the speed violations of a map-matched trip.
+    ///
+    /// Does nothing when the provider returned no speed limits. Emits a
+    /// `trip.speed_violation` org webhook event when violations were found
+    /// and the device belongs to an organization.
+    async fn record_speed_violations(
+        &self,
+        trip_id: Uuid,
+        locations: &[LocationEntity],
+        result: &MapMatchingResult,
+    ) -> Result<(), sqlx::Error> {
+        let segments = speed_limit_segments(result);
+        let Some(device_id) = locations.first().map(|loc| loc.device_id) else {
+            return Ok(());
+        };
+        if segments.is_empty() {
+            return Ok(());
+        }
+
+        let samples: Vec<SpeedSample> = locations
+            .iter()
+            .filter_map(|loc| {
+                Some(SpeedSample {
+                    captured_at: loc.captured_at,
+                    latitude: loc.latitude,
+                    longitude: loc.longitude,
+                    speed_kmh: f64::from(loc.speed?) * MPS_TO_KMH,
+                })
+            })
+            .collect();
+        let detected =
+            detect_speed_violations(&samples, &segments, &SpeedViolationConfig::default());
+
+        let stored = TripSpeedViolationRepository::new(self.pool.clone())
+            .replace_for_trip(trip_id, device_id, &detected)
+            .await?;
+        if stored.is_empty() {
+            return Ok(());
+        }
+
+        info!(
+            trip_id = %trip_id,
+            violations = stored.len(),
+            "Detected speed violations"
+        );
+
+        let device = DeviceRepository::new(self.pool.clone())
+            .find_by_device_id(device_id)
+            .await?;
+        if let Some(organization_id) = device.and_then(|d| d.organization_id) {
+            let violations: Vec<SpeedViolationResponse> =
+                stored.into_iter().map(Into::into).collect();
+            emit_speed_violation_event(
+                &self.pool,
+                &self.webhook_secrets,
+                SpeedViolationEventPayload::new(organization_id, device_id, trip_id, violations),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Conversion from the m/s speeds reported by devices to km/h.
+const MPS_TO_KMH: f64 = 3.6;
+
+/// Matched road segments with a known speed limit.
+fn speed_limit_segments(result: &MapMatchingResult) -> Vec<SpeedLimitSegment> {
+    result
+        .matched_coordinates
+        .windows(2)
+        .zip(&result.speed_limits)
+        .filter_map(|(pair, limit)| {
+            Some(SpeedLimitSegment {
+                start_latitude: pair[0][1],
+                start_longitude: pair[0][0],
+                end_latitude: pair[1][1],
+                end_longitude: pair[1][0],
+                limit_kmh: (*limit)?,
+            })
+        })
+        .collect()
 }
 
 // ============================================================================
@@ -309,4 +417,20 @@ mod tests {
         assert_eq!(cloned.status, result.status);
         assert_eq!(cloned.quality, result.quality);
     }
+
+    #[test]
+    fn test_speed_limit_segments_skip_unknown_limits() {
+        let result = MapMatchingResult {
+            matched_coordinates: vec![[17.0, 48.0], [17.1, 48.0], [17.2, 48.1]],
+            confidence: 0.9,
+            duration_ms: 0,
+            speed_limits: vec![None, Some(90.0)],
+        };
+
+        let segments = speed_limit_segments(&result);
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].start_longitude, 17.1);
+        assert_eq!(segments[0].end_latitude, 48.1);
+        assert_eq!(segments[0].limit_kmh, 90.0);
+    }
 }
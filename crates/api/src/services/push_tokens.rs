@@ -0,0 +1,90 @@
+//! Push token registration, lookup, and cleanup for notification delivery.
+//!
+//! Notifications go to every unexpired token of a device. Tokens the push
+//! provider rejects are deleted so they are not tried again.
+
+use domain::models::{PushPlatform, MAX_PUSH_TOKENS_PER_DEVICE};
+use domain::services::NotificationResult;
+use persistence::repositories::DevicePushTokenRepository;
+use sqlx::PgPool;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Unexpired push tokens of a device.
+///
+/// Lookup failures are logged and treated as no tokens so that the
+/// triggering request never fails because of notifications.
+pub async fn active_push_tokens(pool: &PgPool, device_id: Uuid) -> Vec<String> {
+    match DevicePushTokenRepository::new(pool.clone())
+        .list_active_tokens(device_id)
+        .await
+    {
+        Ok(tokens) => tokens,
+        Err(e) => {
+            warn!(
+                device_id = %device_id,
+                error = %e,
+                "Failed to load push tokens"
+            );
+            Vec::new()
+        }
+    }
+}
+
+/// Register the token sent in the legacy `fcm_token` field of device
+/// registration and enrollment requests.
+pub async fn register_legacy_push_token(
+    pool: &PgPool,
+    device_id: Uuid,
+    fcm_token: Option<&str>,
+    platform: &str,
+) -> Result<(), sqlx::Error> {
+    let Some(token) = fcm_token.filter(|t| !t.is_empty()) else {
+        return Ok(());
+    };
+    let platform = platform.parse().unwrap_or(PushPlatform::Android);
+    DevicePushTokenRepository::new(pool.clone())
+        .register(
+            device_id,
+            token,
+            platform.as_str(),
+            None,
+            None,
+            None,
+            MAX_PUSH_TOKENS_PER_DEVICE,
+        )
+        .await?;
+    Ok(())
+}
+
+/// Log a send result, deleting the token if the provider rejected it.
+pub async fn handle_send_result(
+    pool: &PgPool,
+    device_id: Uuid,
+    token: &str,
+    result: NotificationResult,
+) {
+    match result {
+        NotificationResult::Sent => {
+            info!(device_id = %device_id, "Push notification sent");
+        }
+        NotificationResult::InvalidToken => {
+            metrics::counter!("push_tokens_rejected_total").increment(1);
+            match DevicePushTokenRepository::new(pool.clone())
+                .delete_token(token)
+                .await
+            {
+                Ok(_) => info!(device_id = %device_id, "Removed rejected push token"),
+                Err(e) => warn!(
+                    device_id = %device_id,
+                    error = %e,
+                    "Failed to remove rejected push token"
+                ),
+            }
+        }
+        NotificationResult::Failed(err) => {
+            warn!(device_id = %device_id, error = %err, "Failed to send notification");
+        }
+        NotificationResult::NoToken | NotificationResult::Skipped => {}
+    }
+}
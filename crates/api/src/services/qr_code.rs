@@ -0,0 +1,129 @@
+//! QR code rendering.
+//!
+//! Encodes text (typically a deep link) as a QR code and renders it as a
+//! PNG or SVG image. PNGs are 8-bit grayscale, encoded with the `png` crate.
+
+use domain::models::invite::QrImageFormat;
+use png::{BitDepth, ColorType, Encoder};
+use qrcode::render::svg;
+use qrcode::{Color, EcLevel, QrCode};
+use thiserror::Error;
+
+/// Light modules around the code, as required by the QR specification.
+const QUIET_ZONE_MODULES: usize = 4;
+
+/// Errors from QR code rendering.
+#[derive(Debug, Error)]
+pub enum QrCodeError {
+    #[error("Data too long for a QR code: {0}")]
+    Encode(#[from] qrcode::types::QrError),
+    #[error("Failed to encode image: {0}")]
+    Png(#[from] png::EncodingError),
+}
+
+/// Render `data` as a QR code image of roughly `size` pixels square.
+///
+/// The image is never smaller than one pixel per module, so very small
+/// sizes yield a slightly larger image.
+pub fn render_qr_code(
+    data: &str,
+    format: QrImageFormat,
+    size: u32,
+) -> Result<Vec<u8>, QrCodeError> {
+    let code = QrCode::with_error_correction_level(data, EcLevel::M)?;
+    match format {
+        QrImageFormat::Png => render_png(&code, size),
+        QrImageFormat::Svg => Ok(code
+            .render::<svg::Color>()
+            .quiet_zone(true)
+            .min_dimensions(size, size)
+            .build()
+            .into_bytes()),
+    }
+}
+
+/// Render a QR code as a grayscale PNG.
+fn render_png(code: &QrCode, size: u32) -> Result<Vec<u8>, QrCodeError> {
+    let modules = code.width();
+    let colors = code.to_colors();
+    let total_modules = modules + 2 * QUIET_ZONE_MODULES;
+    let scale = (size as usize / total_modules).max(1);
+    let dimension = total_modules * scale;
+
+    let mut pixels = Vec::with_capacity(dimension * dimension);
+    for y in 0..dimension {
+        let my = (y / scale).checked_sub(QUIET_ZONE_MODULES);
+        for x in 0..dimension {
+            let mx = (x / scale).checked_sub(QUIET_ZONE_MODULES);
+            let dark = match (mx, my) {
+                (Some(mx), Some(my)) if mx < modules && my < modules => {
+                    colors[my * modules + mx] == Color::Dark
+                }
+                _ => false,
+            };
+            pixels.push(if dark { 0x00 } else { 0xFF });
+        }
+    }
+
+    let mut png = Vec::new();
+    let mut encoder = Encoder::new(&mut png, dimension as u32, dimension as u32);
+    encoder.set_color(ColorType::Grayscale);
+    encoder.set_depth(BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&pixels)?;
+    writer.finish()?;
+    Ok(png)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINK: &str = "https://app.example.com/join/ABC-DEF-GHJ";
+
+    /// Decode a rendered PNG into its width and grayscale pixels.
+    fn decode(png: &[u8]) -> (usize, Vec<u8>) {
+        let mut reader = png::Decoder::new(png).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!(info.color_type, ColorType::Grayscale);
+        assert_eq!(info.width, info.height);
+        pixels.truncate(info.buffer_size());
+        (info.width as usize, pixels)
+    }
+
+    #[test]
+    fn test_png_pixels() {
+        let (width, pixels) = decode(&render_qr_code(LINK, QrImageFormat::Png, 256).unwrap());
+        assert!(width <= 256 && width > 128);
+        assert_eq!(pixels.len(), width * width);
+
+        // The quiet zone is white and the finder pattern corner is black
+        let scale = width / (QrCode::new(LINK).unwrap().width() + 8);
+        assert_eq!(pixels[0], 0xFF);
+        assert_eq!(pixels[4 * scale * width + 4 * scale], 0x00);
+    }
+
+    #[test]
+    fn test_png_minimum_scale() {
+        let (width, _) = decode(&render_qr_code(LINK, QrImageFormat::Png, 1).unwrap());
+        assert_eq!(width, QrCode::new(LINK).unwrap().width() + 8);
+    }
+
+    #[test]
+    fn test_svg_output() {
+        let svg = render_qr_code(LINK, QrImageFormat::Svg, 200).unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+        assert!(svg.contains("<svg"));
+        assert!(svg.ends_with("</svg>"));
+    }
+
+    #[test]
+    fn test_data_too_long() {
+        let data = "x".repeat(5000);
+        assert!(matches!(
+            render_qr_code(&data, QrImageFormat::Png, 256),
+            Err(QrCodeError::Encode(_))
+        ));
+    }
+}
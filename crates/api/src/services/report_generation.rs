@@ -1,21 +1,35 @@
 //! Report generation service.
 //!
 //! FR-10.5-10.9: Async Report Generation System
-//! Handles background generation of user and device analytics reports
-//! with CSV and JSON export formats.
+//! Handles background generation of user and device analytics reports,
+//! mileage reports and custom report builder reports in CSV, JSON, XLSX
+//! and PDF formats.
 
 use chrono::NaiveDate;
-use persistence::entities::ReportJobEntity;
+use domain::models::report_builder::{report_groups_by_device, report_time_bucket};
+use domain::models::{ExtractDataset, ExtractFormat, ReportDimension, ReportSection, UnitSystem};
+use persistence::entities::{ReportJobEntity, TripMileageEntity};
 use persistence::repositories::AnalyticsRepository;
 use serde::Serialize;
 use sqlx::PgPool;
+use std::collections::BTreeMap;
 use std::fs;
-use std::io::Write;
+use std::io::{BufWriter, Write};
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
+use super::data_extract::{
+    extract_columns, extract_extension, geofence_event_extract_row, location_extract_row,
+    ExtractWriter,
+};
+use super::parquet::PARQUET_CONTENT_TYPE;
+use super::report_rendering::{
+    render_csv, render_json, render_pdf, render_xlsx, ReportCell, ReportTable,
+};
+use super::xlsx::XLSX_CONTENT_TYPE;
+
 /// Report generation errors.
 #[derive(Error, Debug)]
 pub enum ReportGenerationError {
@@ -40,12 +54,16 @@ pub enum ReportGenerationError {
 pub enum ReportFormat {
     Csv,
     Json,
+    Xlsx,
+    Pdf,
 }
 
 impl ReportFormat {
     fn from_str(s: &str) -> Self {
         match s.to_lowercase().as_str() {
             "csv" => ReportFormat::Csv,
+            "xlsx" => ReportFormat::Xlsx,
+            "pdf" => ReportFormat::Pdf,
             _ => ReportFormat::Json, // Default to JSON
         }
     }
@@ -54,10 +72,28 @@ impl ReportFormat {
         match self {
             ReportFormat::Csv => "csv",
             ReportFormat::Json => "json",
+            ReportFormat::Xlsx => "xlsx",
+            ReportFormat::Pdf => "pdf",
         }
     }
 }
 
+/// Content type for a generated report file, based on its extension.
+pub fn report_content_type(file_name: &str) -> &'static str {
+    match file_name.rsplit('.').next() {
+        Some("csv") => "text/csv",
+        Some("json") => "application/json",
+        Some("xlsx") => XLSX_CONTENT_TYPE,
+        Some("pdf") => "application/pdf",
+        Some("parquet") => PARQUET_CONTENT_TYPE,
+        Some("jsonl") => "application/x-ndjson",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Rows fetched per query while writing a data extract.
+const EXTRACT_PAGE_SIZE: i64 = 10_000;
+
 /// User analytics report row for export.
 #[derive(Debug, Serialize)]
 pub struct UserReportRow {
@@ -79,12 +115,15 @@ pub struct DeviceReportRow {
     pub total_locations_reported: i64,
     pub total_geofence_events: i64,
     pub total_commands_issued: i64,
+    pub trip_distance: f64,
+    pub distance_unit: &'static str,
 }
 
 /// Service for generating reports.
 pub struct ReportGenerationService {
     pool: PgPool,
     reports_dir: PathBuf,
+    timescale: bool,
 }
 
 impl ReportGenerationService {
@@ -94,7 +133,17 @@ impl ReportGenerationService {
     /// * `pool` - Database connection pool
     /// * `reports_dir` - Directory to store generated reports
     pub fn new(pool: PgPool, reports_dir: PathBuf) -> Self {
-        Self { pool, reports_dir }
+        Self {
+            pool,
+            reports_dir,
+            timescale: false,
+        }
+    }
+
+    /// Read report metrics from TimescaleDB continuous aggregates.
+    pub fn with_timescale(mut self, enabled: bool) -> Self {
+        self.timescale = enabled;
+        self
     }
 
     /// Process pending report jobs.
@@ -104,7 +153,7 @@ impl ReportGenerationService {
         &self,
         batch_size: i64,
     ) -> Result<u32, ReportGenerationError> {
-        let repo = AnalyticsRepository::new(self.pool.clone());
+        let repo = AnalyticsRepository::new(self.pool.clone()).with_timescale(self.timescale);
 
         let pending = repo.find_pending_report_jobs(batch_size).await?;
         let mut processed = 0u32;
@@ -159,6 +208,10 @@ impl ReportGenerationService {
             .and_then(|v| v.as_str())
             .map(ReportFormat::from_str)
             .unwrap_or(ReportFormat::Json);
+        let units = UnitSystem::resolve(
+            None,
+            job.parameters.get("unit_system").and_then(|v| v.as_str()),
+        );
 
         // Generate report based on type
         let (file_path, file_size) = match job.report_type.as_str() {
@@ -167,14 +220,81 @@ impl ReportGenerationService {
                     .await?
             }
             "device_analytics" => {
-                self.generate_device_report(repo, job.id, job.organization_id, from, to, format)
+                self.generate_device_report(
+                    repo,
+                    job.id,
+                    job.organization_id,
+                    from,
+                    to,
+                    format,
+                    units,
+                )
+                .await?
+            }
+            "mileage" => {
+                let device_id = job
+                    .parameters
+                    .get("device_id")
+                    .cloned()
+                    .map(serde_json::from_value::<Option<Uuid>>)
+                    .transpose()?
+                    .flatten();
+                self.generate_mileage_report(repo, job, device_id, from, to, format, units)
                     .await?
             }
-            _ => {
-                return Err(ReportGenerationError::UnsupportedReportType(
-                    job.report_type.clone(),
-                ))
+            "custom" => {
+                let sections: Vec<ReportSection> = job
+                    .parameters
+                    .get("sections")
+                    .cloned()
+                    .map(serde_json::from_value)
+                    .transpose()?
+                    .unwrap_or_default();
+                let group_by: Vec<ReportDimension> = job
+                    .parameters
+                    .get("group_by")
+                    .cloned()
+                    .map(serde_json::from_value)
+                    .transpose()?
+                    .unwrap_or_default();
+                if sections.is_empty() {
+                    return Err(ReportGenerationError::InvalidParameters(
+                        "Custom report requires at least one section".to_string(),
+                    ));
+                }
+
+                self.generate_custom_report(
+                    repo,
+                    job,
+                    CustomReportSpec {
+                        from,
+                        to,
+                        sections: &sections,
+                        group_by: &group_by,
+                        format,
+                        units,
+                    },
+                )
+                .await?
             }
+            other => match ExtractDataset::from_report_type(other) {
+                Some(dataset) => {
+                    let format: ExtractFormat = job
+                        .parameters
+                        .get("format")
+                        .cloned()
+                        .map(serde_json::from_value)
+                        .transpose()?
+                        .unwrap_or_default();
+                    self.generate_extract(repo, job, dataset, from, to, format)
+                        .await?
+                }
+                None => {
+                    return Err(ReportGenerationError::UnsupportedReportType(
+                        job.report_type.clone(),
+                    ))
+                }
+            },
         };
 
         // Mark job as completed
@@ -220,8 +340,13 @@ impl ReportGenerationService {
         let file_path = self.reports_dir.join(&filename);
 
         let content = match format {
-            ReportFormat::Json => self.to_json(&rows)?,
-            ReportFormat::Csv => self.to_csv_user(&rows)?,
+            ReportFormat::Json => self.to_json(&rows)?.into_bytes(),
+            ReportFormat::Csv => self.to_csv_user(&rows)?.into_bytes(),
+            ReportFormat::Xlsx => render_xlsx(&[user_report_table(&rows)])?,
+            ReportFormat::Pdf => render_pdf(
+                &format!("User analytics report {} to {}", from, to),
+                &[user_report_table(&rows)],
+            ),
         };
 
         self.write_file(&file_path, &content)?;
@@ -231,6 +356,7 @@ impl ReportGenerationService {
     }
 
     /// Generate device analytics report.
+    #[allow(clippy::too_many_arguments)]
     async fn generate_device_report(
         &self,
         repo: &AnalyticsRepository,
@@ -239,10 +365,19 @@ impl ReportGenerationService {
         from: NaiveDate,
         to: NaiveDate,
         format: ReportFormat,
+        units: UnitSystem,
     ) -> Result<(String, i64), ReportGenerationError> {
         // Fetch device activity trends
         let trends = repo.get_device_activity_trends(org_id, from, to).await?;
 
+        // Fetch completed trip distance per day (meters)
+        let distances: std::collections::HashMap<NaiveDate, f64> = repo
+            .get_trip_distance_by_day(org_id, from, to)
+            .await?
+            .into_iter()
+            .map(|d| (d.activity_date, d.distance_meters))
+            .collect();
+
         // Convert to report rows
         let rows: Vec<DeviceReportRow> = trends
             .into_iter()
@@ -254,6 +389,9 @@ impl ReportGenerationService {
                 total_locations_reported: t.total_locations_reported,
                 total_geofence_events: t.total_geofence_events,
                 total_commands_issued: t.total_commands_issued,
+                trip_distance: units
+                    .convert_distance(distances.get(&t.activity_date).copied().unwrap_or(0.0)),
+                distance_unit: units.distance_unit(),
             })
             .collect();
 
@@ -267,8 +405,52 @@ impl ReportGenerationService {
         let file_path = self.reports_dir.join(&filename);
 
         let content = match format {
-            ReportFormat::Json => self.to_json(&rows)?,
-            ReportFormat::Csv => self.to_csv_device(&rows)?,
+            ReportFormat::Json => self.to_json(&rows)?.into_bytes(),
+            ReportFormat::Csv => self.to_csv_device(&rows)?.into_bytes(),
+            ReportFormat::Xlsx => render_xlsx(&[device_report_table(&rows)])?,
+            ReportFormat::Pdf => render_pdf(
+                &format!("Device analytics report {} to {}", from, to),
+                &[device_report_table(&rows)],
+            ),
+        };
+
+        self.write_file(&file_path, &content)?;
+
+        let file_size = fs::metadata(&file_path)?.len() as i64;
+        Ok((filename, file_size))
+    }
+
+    /// Generate a mileage report: completed trip distance per purpose and
+    /// per day, device and purpose.
+    #[allow(clippy::too_many_arguments)]
+    async fn generate_mileage_report(
+        &self,
+        repo: &AnalyticsRepository,
+        job: &ReportJobEntity,
+        device_id: Option<Uuid>,
+        from: NaiveDate,
+        to: NaiveDate,
+        format: ReportFormat,
+        units: UnitSystem,
+    ) -> Result<(String, i64), ReportGenerationError> {
+        let rows = repo
+            .get_trip_mileage(job.organization_id, device_id, from, to)
+            .await?;
+        let tables = mileage_report_tables(&rows, units);
+
+        let filename = format!(
+            "mileage_report_{}_{}.{}",
+            job.id,
+            chrono::Utc::now().format("%Y%m%d%H%M%S"),
+            format.extension()
+        );
+        let file_path = self.reports_dir.join(&filename);
+
+        let content = match format {
+            ReportFormat::Json => render_json(&tables)?.into_bytes(),
+            ReportFormat::Csv => render_csv(&tables).into_bytes(),
+            ReportFormat::Xlsx => render_xlsx(&tables)?,
+            ReportFormat::Pdf => render_pdf(&format!("Mileage report {} to {}", from, to), &tables),
         };
 
         self.write_file(&file_path, &content)?;
@@ -277,6 +459,216 @@ impl ReportGenerationService {
         Ok((filename, file_size))
     }
 
+    /// Generate a custom report builder report.
+    async fn generate_custom_report(
+        &self,
+        repo: &AnalyticsRepository,
+        job: &ReportJobEntity,
+        spec: CustomReportSpec<'_>,
+    ) -> Result<(String, i64), ReportGenerationError> {
+        let time_bucket = report_time_bucket(spec.group_by);
+        let by_device = report_groups_by_device(spec.group_by);
+        let org_id = job.organization_id;
+        let (from, to) = (spec.from, spec.to);
+
+        let mut tables = Vec::with_capacity(spec.sections.len());
+        for section in spec.sections {
+            let mut table = ReportTable::new(
+                section.as_str(),
+                &dimension_columns(time_bucket.is_some(), by_device),
+            );
+            match section {
+                ReportSection::Devices => {
+                    table
+                        .columns
+                        .extend(["active_devices", "locations_reported"].map(String::from));
+                    for row in repo
+                        .get_device_report_metrics(org_id, from, to, time_bucket, by_device)
+                        .await?
+                    {
+                        let mut cells = dimension_cells(
+                            (time_bucket.is_some(), by_device),
+                            row.bucket,
+                            row.device_id,
+                            row.device_name,
+                        );
+                        cells.extend([row.active_devices.into(), row.locations_reported.into()]);
+                        table.push_row(cells);
+                    }
+                }
+                ReportSection::Trips => {
+                    table.columns.extend(
+                        ["trips", "distance", "distance_unit", "duration_seconds"]
+                            .map(String::from),
+                    );
+                    for row in repo
+                        .get_trip_report_metrics(org_id, from, to, time_bucket, by_device)
+                        .await?
+                    {
+                        let mut cells = dimension_cells(
+                            (time_bucket.is_some(), by_device),
+                            row.bucket,
+                            row.device_id,
+                            row.device_name,
+                        );
+                        cells.extend([
+                            row.trip_count.into(),
+                            spec.units.convert_distance(row.distance_meters).into(),
+                            spec.units.distance_unit().into(),
+                            row.duration_seconds.into(),
+                        ]);
+                        table.push_row(cells);
+                    }
+                }
+                ReportSection::GeofenceCompliance => {
+                    table
+                        .columns
+                        .extend(["enter_events", "exit_events", "dwell_events"].map(String::from));
+                    for row in repo
+                        .get_geofence_report_metrics(org_id, from, to, time_bucket, by_device)
+                        .await?
+                    {
+                        let mut cells = dimension_cells(
+                            (time_bucket.is_some(), by_device),
+                            row.bucket,
+                            row.device_id,
+                            row.device_name,
+                        );
+                        cells.extend([
+                            row.enter_count.into(),
+                            row.exit_count.into(),
+                            row.dwell_count.into(),
+                        ]);
+                        table.push_row(cells);
+                    }
+                }
+                ReportSection::AppUsage => {
+                    table.columns.extend(
+                        ["foreground_time_ms", "launches", "unique_apps"].map(String::from),
+                    );
+                    for row in repo
+                        .get_app_usage_report_metrics(org_id, from, to, time_bucket, by_device)
+                        .await?
+                    {
+                        let mut cells = dimension_cells(
+                            (time_bucket.is_some(), by_device),
+                            row.bucket,
+                            row.device_id,
+                            row.device_name,
+                        );
+                        cells.extend([
+                            row.foreground_time_ms.into(),
+                            row.launch_count.into(),
+                            row.unique_apps.into(),
+                        ]);
+                        table.push_row(cells);
+                    }
+                }
+            }
+            tables.push(table);
+        }
+
+        let filename = format!(
+            "custom_report_{}_{}.{}",
+            job.id,
+            chrono::Utc::now().format("%Y%m%d%H%M%S"),
+            spec.format.extension()
+        );
+        let file_path = self.reports_dir.join(&filename);
+
+        let content = match spec.format {
+            ReportFormat::Json => render_json(&tables)?.into_bytes(),
+            ReportFormat::Csv => render_csv(&tables).into_bytes(),
+            ReportFormat::Xlsx => render_xlsx(&tables)?,
+            ReportFormat::Pdf => render_pdf(&format!("Custom report {} to {}", from, to), &tables),
+        };
+
+        self.write_file(&file_path, &content)?;
+
+        let file_size = fs::metadata(&file_path)?.len() as i64;
+        Ok((filename, file_size))
+    }
+
+    /// Generate a raw data extract, paging through the dataset so only one
+    /// page (plus the current Parquet row group) is held in memory.
+    async fn generate_extract(
+        &self,
+        repo: &AnalyticsRepository,
+        job: &ReportJobEntity,
+        dataset: ExtractDataset,
+        from: NaiveDate,
+        to: NaiveDate,
+        format: ExtractFormat,
+    ) -> Result<(String, i64), ReportGenerationError> {
+        let filename = format!(
+            "{}_{}_{}.{}",
+            dataset.report_type(),
+            job.id,
+            chrono::Utc::now().format("%Y%m%d%H%M%S"),
+            extract_extension(format)
+        );
+        let file_path = self.reports_dir.join(&filename);
+        fs::create_dir_all(&self.reports_dir)?;
+
+        let file = BufWriter::new(fs::File::create(&file_path)?);
+        let mut writer = ExtractWriter::new(file, format, extract_columns(dataset))?;
+        let mut after_id = 0i64;
+        let mut total_rows = 0usize;
+        loop {
+            let fetched = match dataset {
+                ExtractDataset::Locations => {
+                    let page = repo
+                        .list_location_extract(
+                            job.organization_id,
+                            from,
+                            to,
+                            after_id,
+                            EXTRACT_PAGE_SIZE,
+                        )
+                        .await?;
+                    for row in &page {
+                        writer.write_row(&location_extract_row(row))?;
+                    }
+                    page.last().map(|row| (row.id, page.len()))
+                }
+                ExtractDataset::GeofenceEvents => {
+                    let page = repo
+                        .list_geofence_event_extract(
+                            job.organization_id,
+                            from,
+                            to,
+                            after_id,
+                            EXTRACT_PAGE_SIZE,
+                        )
+                        .await?;
+                    for row in &page {
+                        writer.write_row(&geofence_event_extract_row(row))?;
+                    }
+                    page.last().map(|row| (row.id, page.len()))
+                }
+            };
+            let Some((last_id, count)) = fetched else {
+                break;
+            };
+            after_id = last_id;
+            total_rows += count;
+            if (count as i64) < EXTRACT_PAGE_SIZE {
+                break;
+            }
+        }
+        writer.finish()?.flush()?;
+
+        info!(
+            job_id = %job.id,
+            dataset = dataset.report_type(),
+            rows = total_rows,
+            "Data extract written"
+        );
+
+        let file_size = fs::metadata(&file_path)?.len() as i64;
+        Ok((filename, file_size))
+    }
+
     /// Clean up expired reports and their files.
     pub async fn cleanup_expired_reports(&self) -> Result<u32, ReportGenerationError> {
         let repo = AnalyticsRepository::new(self.pool.clone());
@@ -360,18 +752,20 @@ impl ReportGenerationService {
     /// Convert device report rows to CSV.
     fn to_csv_device(&self, rows: &[DeviceReportRow]) -> Result<String, ReportGenerationError> {
         let mut csv = String::new();
-        csv.push_str("date,active_devices,new_enrollments,unenrollments,total_locations_reported,total_geofence_events,total_commands_issued\n");
+        csv.push_str("date,active_devices,new_enrollments,unenrollments,total_locations_reported,total_geofence_events,total_commands_issued,trip_distance,distance_unit\n");
 
         for row in rows {
             csv.push_str(&format!(
-                "{},{},{},{},{},{},{}\n",
+                "{},{},{},{},{},{},{},{:.2},{}\n",
                 row.date,
                 row.active_devices,
                 row.new_enrollments,
                 row.unenrollments,
                 row.total_locations_reported,
                 row.total_geofence_events,
-                row.total_commands_issued
+                row.total_commands_issued,
+                row.trip_distance,
+                row.distance_unit
             ));
         }
 
@@ -379,14 +773,14 @@ impl ReportGenerationService {
     }
 
     /// Write content to a file.
-    fn write_file(&self, path: &Path, content: &str) -> Result<(), ReportGenerationError> {
+    fn write_file(&self, path: &Path, content: &[u8]) -> Result<(), ReportGenerationError> {
         // Ensure reports directory exists
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)?;
         }
 
         let mut file = fs::File::create(path)?;
-        file.write_all(content.as_bytes())?;
+        file.write_all(content)?;
         Ok(())
     }
 
@@ -396,6 +790,155 @@ impl ReportGenerationService {
     }
 }
 
+/// Parameters of a custom report builder job.
+struct CustomReportSpec<'a> {
+    from: NaiveDate,
+    to: NaiveDate,
+    sections: &'a [ReportSection],
+    group_by: &'a [ReportDimension],
+    format: ReportFormat,
+    units: UnitSystem,
+}
+
+/// Leading dimension columns of a custom report section.
+fn dimension_columns(by_time: bool, by_device: bool) -> Vec<&'static str> {
+    let mut columns = Vec::new();
+    if by_time {
+        columns.push("period");
+    }
+    if by_device {
+        columns.extend(["device_id", "device_name"]);
+    }
+    columns
+}
+
+/// Leading dimension cells matching [`dimension_columns`].
+fn dimension_cells(
+    (by_time, by_device): (bool, bool),
+    bucket: Option<NaiveDate>,
+    device_id: Option<Uuid>,
+    device_name: Option<String>,
+) -> Vec<ReportCell> {
+    let mut cells = Vec::new();
+    if by_time {
+        cells.push(bucket.map(|d| d.to_string()).into());
+    }
+    if by_device {
+        cells.push(device_id.map(|id| id.to_string()).into());
+        cells.push(device_name.into());
+    }
+    cells
+}
+
+/// Mileage rows as a per-purpose summary table followed by the daily
+/// breakdown. Trips without a purpose are reported as `unlabeled`.
+fn mileage_report_tables(rows: &[TripMileageEntity], units: UnitSystem) -> Vec<ReportTable> {
+    const UNLABELED: &str = "unlabeled";
+
+    let mut totals: BTreeMap<&str, (i64, f64)> = BTreeMap::new();
+    let mut daily = ReportTable::new(
+        "mileage_by_day",
+        &[
+            "date",
+            "device_id",
+            "device_name",
+            "purpose",
+            "trip_count",
+            "distance",
+            "distance_unit",
+        ],
+    );
+    for row in rows {
+        let purpose = row.purpose.as_deref().unwrap_or(UNLABELED);
+        let total = totals.entry(purpose).or_default();
+        total.0 += row.trip_count;
+        total.1 += row.distance_meters;
+
+        daily.push_row(vec![
+            row.activity_date.to_string().into(),
+            row.device_id.to_string().into(),
+            row.device_name.as_str().into(),
+            purpose.into(),
+            row.trip_count.into(),
+            units.convert_distance(row.distance_meters).into(),
+            units.distance_unit().into(),
+        ]);
+    }
+
+    let mut summary = ReportTable::new(
+        "mileage_by_purpose",
+        &["purpose", "trip_count", "distance", "distance_unit"],
+    );
+    for (purpose, (trip_count, distance_meters)) in totals {
+        summary.push_row(vec![
+            purpose.into(),
+            trip_count.into(),
+            units.convert_distance(distance_meters).into(),
+            units.distance_unit().into(),
+        ]);
+    }
+
+    vec![summary, daily]
+}
+
+/// User analytics rows as a table (XLSX/PDF output).
+fn user_report_table(rows: &[UserReportRow]) -> ReportTable {
+    let mut table = ReportTable::new(
+        "user_analytics",
+        &[
+            "date",
+            "active_users",
+            "new_users",
+            "returning_users",
+            "total_sessions",
+            "avg_session_duration_seconds",
+        ],
+    );
+    for row in rows {
+        table.push_row(vec![
+            row.date.as_str().into(),
+            row.active_users.into(),
+            row.new_users.into(),
+            row.returning_users.into(),
+            row.total_sessions.into(),
+            row.avg_session_duration_seconds.into(),
+        ]);
+    }
+    table
+}
+
+/// Device analytics rows as a table (XLSX/PDF output).
+fn device_report_table(rows: &[DeviceReportRow]) -> ReportTable {
+    let mut table = ReportTable::new(
+        "device_analytics",
+        &[
+            "date",
+            "active_devices",
+            "new_enrollments",
+            "unenrollments",
+            "total_locations_reported",
+            "total_geofence_events",
+            "total_commands_issued",
+            "trip_distance",
+            "distance_unit",
+        ],
+    );
+    for row in rows {
+        table.push_row(vec![
+            row.date.as_str().into(),
+            row.active_devices.into(),
+            row.new_enrollments.into(),
+            row.unenrollments.into(),
+            row.total_locations_reported.into(),
+            row.total_geofence_events.into(),
+            row.total_commands_issued.into(),
+            row.trip_distance.into(),
+            row.distance_unit.into(),
+        ]);
+    }
+    table
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -407,12 +950,86 @@ mod tests {
         assert_eq!(ReportFormat::from_str("json"), ReportFormat::Json);
         assert_eq!(ReportFormat::from_str("JSON"), ReportFormat::Json);
         assert_eq!(ReportFormat::from_str("unknown"), ReportFormat::Json);
+        assert_eq!(ReportFormat::from_str("XLSX"), ReportFormat::Xlsx);
+        assert_eq!(ReportFormat::from_str("pdf"), ReportFormat::Pdf);
     }
 
     #[test]
     fn test_report_format_extension() {
         assert_eq!(ReportFormat::Csv.extension(), "csv");
         assert_eq!(ReportFormat::Json.extension(), "json");
+        assert_eq!(ReportFormat::Xlsx.extension(), "xlsx");
+        assert_eq!(ReportFormat::Pdf.extension(), "pdf");
+    }
+
+    #[test]
+    fn test_report_content_type() {
+        assert_eq!(report_content_type("a.csv"), "text/csv");
+        assert_eq!(report_content_type("a.pdf"), "application/pdf");
+        assert_eq!(
+            report_content_type("a.xlsx"),
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+        );
+        assert_eq!(report_content_type("a.parquet"), PARQUET_CONTENT_TYPE);
+        assert_eq!(report_content_type("a.jsonl"), "application/x-ndjson");
+        assert_eq!(report_content_type("a"), "application/octet-stream");
+    }
+
+    #[test]
+    fn test_dimension_columns_and_cells_align() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let device_id = Uuid::nil();
+
+        for (by_time, by_device) in [(false, false), (true, false), (false, true), (true, true)] {
+            let columns = dimension_columns(by_time, by_device);
+            let cells = dimension_cells(
+                (by_time, by_device),
+                Some(date),
+                Some(device_id),
+                Some("Phone".to_string()),
+            );
+            assert_eq!(columns.len(), cells.len());
+        }
+
+        let cells = dimension_cells((true, true), Some(date), None, None);
+        assert_eq!(cells[0], ReportCell::Text("2024-01-01".to_string()));
+        assert_eq!(cells[1], ReportCell::Empty);
+    }
+
+    #[test]
+    fn test_mileage_report_tables() {
+        let row = |day: u32, purpose: Option<&str>, trips: i64, meters: f64| TripMileageEntity {
+            activity_date: NaiveDate::from_ymd_opt(2024, 1, day).unwrap(),
+            device_id: Uuid::nil(),
+            device_name: "Van 1".to_string(),
+            purpose: purpose.map(str::to_string),
+            trip_count: trips,
+            distance_meters: meters,
+        };
+        let rows = [
+            row(1, Some("business"), 2, 10_000.0),
+            row(1, None, 1, 2_000.0),
+            row(2, Some("business"), 1, 5_000.0),
+        ];
+
+        let tables = mileage_report_tables(&rows, UnitSystem::Metric);
+        assert_eq!(tables.len(), 2);
+
+        let summary = &tables[0];
+        assert_eq!(summary.title, "mileage_by_purpose");
+        assert_eq!(summary.rows.len(), 2);
+        assert_eq!(summary.rows[0][0], ReportCell::Text("business".to_string()));
+        assert_eq!(summary.rows[0][1], ReportCell::Integer(3));
+        assert_eq!(summary.rows[0][2], ReportCell::Number(15.0));
+        assert_eq!(
+            summary.rows[1][0],
+            ReportCell::Text("unlabeled".to_string())
+        );
+
+        let daily = &tables[1];
+        assert_eq!(daily.rows.len(), 3);
+        assert_eq!(daily.rows[1][3], ReportCell::Text("unlabeled".to_string()));
+        assert_eq!(daily.rows[0][6], ReportCell::Text("km".to_string()));
     }
 
     #[test]
@@ -441,10 +1058,13 @@ mod tests {
             total_locations_reported: 1000,
             total_geofence_events: 100,
             total_commands_issued: 25,
+            trip_distance: 12.5,
+            distance_unit: "mi",
         };
 
         let json = serde_json::to_string(&row).unwrap();
         assert!(json.contains("\"active_devices\":50"));
         assert!(json.contains("\"total_locations_reported\":1000"));
+        assert!(json.contains("\"distance_unit\":\"mi\""));
     }
 }
@@ -0,0 +1,569 @@
+//! Tabular report rendering.
+//!
+//! Reports are assembled as a list of [`ReportTable`]s (one per section) and
+//! rendered into the requested output format. XLSX is written with
+//! [`XlsxWriter`], PDF as plain monospaced text pages.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::{Map, Value};
+
+use super::xlsx::XlsxWriter;
+
+/// A single cell value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReportCell {
+    Text(String),
+    Integer(i64),
+    Number(f64),
+    Boolean(bool),
+    DateTime(DateTime<Utc>),
+    Empty,
+}
+
+impl ReportCell {
+    /// Display form used by CSV and PDF output.
+    fn display(&self) -> String {
+        match self {
+            ReportCell::Text(s) => s.clone(),
+            ReportCell::Integer(i) => i.to_string(),
+            ReportCell::Number(n) => format!("{:.2}", n),
+            ReportCell::Boolean(b) => b.to_string(),
+            ReportCell::DateTime(ts) => ts.to_rfc3339_opts(SecondsFormat::Secs, true),
+            ReportCell::Empty => String::new(),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        match self {
+            ReportCell::Text(s) => Value::String(s.clone()),
+            ReportCell::Integer(i) => Value::from(*i),
+            ReportCell::Number(n) => Value::from(*n),
+            ReportCell::Boolean(b) => Value::Bool(*b),
+            ReportCell::DateTime(_) => Value::String(self.display()),
+            ReportCell::Empty => Value::Null,
+        }
+    }
+}
+
+impl From<String> for ReportCell {
+    fn from(value: String) -> Self {
+        ReportCell::Text(value)
+    }
+}
+
+impl From<&str> for ReportCell {
+    fn from(value: &str) -> Self {
+        ReportCell::Text(value.to_string())
+    }
+}
+
+impl From<i64> for ReportCell {
+    fn from(value: i64) -> Self {
+        ReportCell::Integer(value)
+    }
+}
+
+impl From<f64> for ReportCell {
+    fn from(value: f64) -> Self {
+        ReportCell::Number(value)
+    }
+}
+
+impl From<bool> for ReportCell {
+    fn from(value: bool) -> Self {
+        ReportCell::Boolean(value)
+    }
+}
+
+impl From<DateTime<Utc>> for ReportCell {
+    fn from(value: DateTime<Utc>) -> Self {
+        ReportCell::DateTime(value)
+    }
+}
+
+impl<T: Into<ReportCell>> From<Option<T>> for ReportCell {
+    fn from(value: Option<T>) -> Self {
+        value.map(Into::into).unwrap_or(ReportCell::Empty)
+    }
+}
+
+/// A titled table of rows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportTable {
+    pub title: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<ReportCell>>,
+}
+
+impl ReportTable {
+    /// Create an empty table with the given column headers.
+    pub fn new(title: impl Into<String>, columns: &[&str]) -> Self {
+        Self {
+            title: title.into(),
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Append a row. Missing trailing cells are rendered empty.
+    pub fn push_row(&mut self, row: Vec<ReportCell>) {
+        self.rows.push(row);
+    }
+}
+
+// ============================================================================
+// CSV / JSON
+// ============================================================================
+
+/// Render tables as CSV. Multiple tables are separated by a blank line and
+/// introduced with a `# title` line.
+pub fn render_csv(tables: &[ReportTable]) -> String {
+    let multiple = tables.len() > 1;
+    let mut out = String::new();
+
+    for (i, table) in tables.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        if multiple {
+            out.push_str(&format!("# {}\n", table.title));
+        }
+        let header: Vec<String> = table.columns.iter().map(|c| csv_escape(c)).collect();
+        out.push_str(&header.join(","));
+        out.push('\n');
+        for row in &table.rows {
+            out.push_str(&csv_row(row));
+        }
+    }
+
+    out
+}
+
+/// Format a single CSV line, including the trailing newline.
+pub fn csv_row(cells: &[ReportCell]) -> String {
+    let cells: Vec<String> = cells.iter().map(|c| csv_escape(&c.display())).collect();
+    format!("{}\n", cells.join(","))
+}
+
+/// Escape a value for CSV output.
+pub fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Render tables as a JSON object keyed by table title, each holding an
+/// array of row objects.
+pub fn render_json(tables: &[ReportTable]) -> Result<String, serde_json::Error> {
+    let mut root = Map::new();
+    for table in tables {
+        let rows: Vec<Value> = table
+            .rows
+            .iter()
+            .map(|row| {
+                let mut obj = Map::new();
+                for (i, column) in table.columns.iter().enumerate() {
+                    let value = row.get(i).map(ReportCell::to_json).unwrap_or(Value::Null);
+                    obj.insert(column.clone(), value);
+                }
+                Value::Object(obj)
+            })
+            .collect();
+        root.insert(table.title.clone(), Value::Array(rows));
+    }
+    serde_json::to_string_pretty(&Value::Object(root))
+}
+
+// ============================================================================
+// XLSX
+// ============================================================================
+
+/// Render tables as an XLSX workbook with one worksheet per table.
+pub fn render_xlsx(tables: &[ReportTable]) -> std::io::Result<Vec<u8>> {
+    let mut writer = XlsxWriter::new();
+    for table in tables {
+        let columns: Vec<&str> = table.columns.iter().map(String::as_str).collect();
+        writer.start_sheet(&table.title, &columns)?;
+        for row in &table.rows {
+            writer.write_row(row)?;
+        }
+    }
+    writer.finish()
+}
+
+// ============================================================================
+// PDF
+// ============================================================================
+
+/// A4 landscape page size in points.
+const PDF_PAGE_WIDTH: u32 = 842;
+const PDF_PAGE_HEIGHT: u32 = 595;
+const PDF_MARGIN: u32 = 36;
+const PDF_FONT_SIZE: u32 = 8;
+const PDF_LINE_HEIGHT: u32 = 11;
+/// Widest a single column may grow, in characters.
+const PDF_MAX_COLUMN_CHARS: usize = 32;
+/// Characters per line; Courier glyphs are 0.6 em wide.
+const PDF_LINE_CHARS: usize =
+    ((PDF_PAGE_WIDTH - 2 * PDF_MARGIN) * 10 / (PDF_FONT_SIZE * 6)) as usize;
+
+/// Render tables as a PDF document of monospaced text pages.
+pub fn render_pdf(title: &str, tables: &[ReportTable]) -> Vec<u8> {
+    let mut lines: Vec<String> = wrap_line(title);
+    lines.push(String::new());
+    for table in tables {
+        lines.extend(table_text_lines(table));
+        lines.push(String::new());
+    }
+
+    let lines_per_page = ((PDF_PAGE_HEIGHT - 2 * PDF_MARGIN) / PDF_LINE_HEIGHT) as usize;
+    let pages: Vec<&[String]> = lines.chunks(lines_per_page.max(1)).collect();
+
+    // Object layout: 1 catalog, 2 pages, 3 font, then (page, content) pairs.
+    let page_ids: Vec<usize> = (0..pages.len()).map(|i| 4 + i * 2).collect();
+    let mut objects: Vec<Vec<u8>> = Vec::new();
+
+    objects.push(b"<< /Type /Catalog /Pages 2 0 R >>".to_vec());
+    let kids: Vec<String> = page_ids.iter().map(|id| format!("{} 0 R", id)).collect();
+    objects.push(
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            kids.join(" "),
+            pages.len()
+        )
+        .into_bytes(),
+    );
+    objects.push(
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Courier /Encoding /WinAnsiEncoding >>".to_vec(),
+    );
+
+    for (i, page_lines) in pages.iter().enumerate() {
+        let content_id = page_ids[i] + 1;
+        objects.push(
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+                PDF_PAGE_WIDTH, PDF_PAGE_HEIGHT, content_id
+            )
+            .into_bytes(),
+        );
+
+        let mut stream = format!(
+            "BT\n/F1 {} Tf\n{} TL\n{} {} Td\n",
+            PDF_FONT_SIZE,
+            PDF_LINE_HEIGHT,
+            PDF_MARGIN,
+            PDF_PAGE_HEIGHT - PDF_MARGIN
+        )
+        .into_bytes();
+        for line in page_lines.iter() {
+            stream.push(b'(');
+            stream.extend_from_slice(&pdf_escape(line));
+            stream.extend_from_slice(b") Tj T*\n");
+        }
+        stream.extend_from_slice(b"ET");
+        let mut content = format!("<< /Length {} >>\nstream\n", stream.len()).into_bytes();
+        content.extend_from_slice(&stream);
+        content.extend_from_slice(b"\nendstream");
+        objects.push(content);
+    }
+
+    // The binary comment marks the file as containing 8-bit text
+    let mut pdf: Vec<u8> = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n", i + 1).as_bytes());
+        pdf.extend_from_slice(object);
+        pdf.extend_from_slice(b"\nendobj\n");
+    }
+
+    let xref_offset = pdf.len();
+    pdf.extend_from_slice(format!("xref\n0 {}\n", objects.len() + 1).as_bytes());
+    pdf.extend_from_slice(b"0000000000 65535 f \n");
+    for offset in offsets {
+        pdf.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+    }
+    pdf.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref_offset
+        )
+        .as_bytes(),
+    );
+
+    pdf
+}
+
+/// Format a table as aligned text lines.
+///
+/// Tables wider than the page are split into column groups printed one
+/// after the other, each with its own header.
+fn table_text_lines(table: &ReportTable) -> Vec<String> {
+    let cells: Vec<Vec<String>> = table
+        .rows
+        .iter()
+        .map(|row| row.iter().map(ReportCell::display).collect())
+        .collect();
+
+    let widths: Vec<usize> = table
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            cells
+                .iter()
+                .filter_map(|row| row.get(i))
+                .map(|c| c.chars().count())
+                .chain(std::iter::once(column.chars().count()))
+                .max()
+                .unwrap_or(0)
+                .min(PDF_MAX_COLUMN_CHARS)
+        })
+        .collect();
+
+    let groups = column_groups(&widths);
+    let mut lines = Vec::new();
+    for (n, group) in groups.iter().enumerate() {
+        let title = if groups.len() > 1 {
+            format!("{} ({}/{})", table.title, n + 1, groups.len())
+        } else {
+            table.title.clone()
+        };
+        lines.extend(wrap_line(&title));
+
+        let format_row = |row: &[String]| -> String {
+            group
+                .clone()
+                .map(|i| {
+                    let value: String = row
+                        .get(i)
+                        .map(|v| v.chars().take(widths[i]).collect())
+                        .unwrap_or_default();
+                    format!("{:<width$}", value, width = widths[i])
+                })
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        };
+
+        lines.push(format_row(&table.columns));
+        lines.push(
+            group
+                .clone()
+                .map(|i| "-".repeat(widths[i]))
+                .collect::<Vec<_>>()
+                .join("  "),
+        );
+        if cells.is_empty() {
+            lines.push("(no data)".to_string());
+        }
+        for row in &cells {
+            lines.push(format_row(row));
+        }
+        if n + 1 < groups.len() {
+            lines.push(String::new());
+        }
+    }
+    lines
+}
+
+/// Split columns into consecutive ranges that each fit on a line.
+fn column_groups(widths: &[usize]) -> Vec<std::ops::Range<usize>> {
+    let mut groups = Vec::new();
+    let mut start = 0;
+    let mut line = 0;
+    for (i, width) in widths.iter().enumerate() {
+        let needed = if i == start { *width } else { line + 2 + width };
+        if i > start && needed > PDF_LINE_CHARS {
+            groups.push(start..i);
+            start = i;
+            line = *width;
+        } else {
+            line = needed;
+        }
+    }
+    groups.push(start..widths.len());
+    groups
+}
+
+/// Break a line into pieces of at most `PDF_LINE_CHARS` characters.
+fn wrap_line(line: &str) -> Vec<String> {
+    let chars: Vec<char> = line.chars().collect();
+    if chars.is_empty() {
+        return vec![String::new()];
+    }
+    chars
+        .chunks(PDF_LINE_CHARS)
+        .map(|chunk| chunk.iter().collect())
+        .collect()
+}
+
+/// Encode a string for a PDF literal in WinAnsiEncoding, the encoding of
+/// the standard font. Characters it lacks are replaced with `?`.
+fn pdf_escape(value: &str) -> Vec<u8> {
+    let mut out = Vec::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                out.push(b'\\');
+                out.push(c as u8);
+            }
+            c => out.push(win_ansi_byte(c).unwrap_or(b'?')),
+        }
+    }
+    out
+}
+
+/// WinAnsiEncoding (Windows-1252) code of a printable character.
+fn win_ansi_byte(c: char) -> Option<u8> {
+    let byte = match c {
+        ' '..='~' | '\u{A0}'..='\u{FF}' => c as u8,
+        '€' => 0x80,
+        '‚' => 0x82,
+        'ƒ' => 0x83,
+        '„' => 0x84,
+        '…' => 0x85,
+        '†' => 0x86,
+        '‡' => 0x87,
+        'ˆ' => 0x88,
+        '‰' => 0x89,
+        'Š' => 0x8A,
+        '‹' => 0x8B,
+        'Œ' => 0x8C,
+        'Ž' => 0x8E,
+        '‘' => 0x91,
+        '’' => 0x92,
+        '“' => 0x93,
+        '”' => 0x94,
+        '•' => 0x95,
+        '–' => 0x96,
+        '—' => 0x97,
+        '˜' => 0x98,
+        '™' => 0x99,
+        'š' => 0x9A,
+        '›' => 0x9B,
+        'œ' => 0x9C,
+        'ž' => 0x9E,
+        'Ÿ' => 0x9F,
+        _ => return None,
+    };
+    Some(byte)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_table() -> ReportTable {
+        let mut table = ReportTable::new("devices", &["period", "device", "locations"]);
+        table.push_row(vec!["2024-01-01".into(), "Pixel, 7".into(), 42i64.into()]);
+        table.push_row(vec!["2024-01-02".into(), ReportCell::Empty, 3.5f64.into()]);
+        table
+    }
+
+    #[test]
+    fn test_render_csv_single_table() {
+        let csv = render_csv(&[sample_table()]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "period,device,locations");
+        assert_eq!(lines[1], "2024-01-01,\"Pixel, 7\",42");
+        assert_eq!(lines[2], "2024-01-02,,3.50");
+    }
+
+    #[test]
+    fn test_render_csv_multiple_tables_have_titles() {
+        let mut other = ReportTable::new("trips", &["trips"]);
+        other.push_row(vec![1i64.into()]);
+        let csv = render_csv(&[sample_table(), other]);
+        assert!(csv.starts_with("# devices\n"));
+        assert!(csv.contains("\n\n# trips\ntrips\n1\n"));
+    }
+
+    #[test]
+    fn test_render_json() {
+        let json = render_json(&[sample_table()]).unwrap();
+        let value: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["devices"][0]["locations"], 42);
+        assert_eq!(value["devices"][1]["device"], Value::Null);
+    }
+
+    #[test]
+    fn test_typed_cell_display() {
+        let ts = DateTime::parse_from_rfc3339("2024-03-01T08:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(ReportCell::from(ts).display(), "2024-03-01T08:30:00Z");
+        assert_eq!(ReportCell::from(true).display(), "true");
+        assert_eq!(ReportCell::from(false).to_json(), Value::Bool(false));
+        assert_eq!(
+            ReportCell::from(ts).to_json(),
+            Value::String("2024-03-01T08:30:00Z".into())
+        );
+    }
+
+    #[test]
+    fn test_render_xlsx_has_a_sheet_per_table() {
+        use crate::services::xlsx::read_part;
+
+        let bytes = render_xlsx(&[sample_table(), ReportTable::new("trips", &["trips"])]).unwrap();
+        let workbook = read_part(&bytes, "xl/workbook.xml");
+        assert!(workbook.contains(r#"<sheet name="devices""#));
+        assert!(workbook.contains(r#"<sheet name="trips""#));
+        assert!(read_part(&bytes, "xl/worksheets/sheet1.xml").contains("<t>Pixel, 7</t>"));
+    }
+
+    #[test]
+    fn test_render_pdf_structure() {
+        let pdf = render_pdf("Custom report", &[sample_table()]);
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.starts_with("%PDF-1.4"));
+        assert!(text.trim_end().ends_with("%%EOF"));
+        assert!(text.contains("/Count 1"));
+        assert!(text.contains("(Custom report) Tj"));
+        assert!(text.contains("Pixel, 7"));
+    }
+
+    #[test]
+    fn test_render_pdf_paginates() {
+        let mut table = ReportTable::new("big", &["n"]);
+        for i in 0..200 {
+            table.push_row(vec![(i as i64).into()]);
+        }
+        let pdf = render_pdf("Big", &[table]);
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains("/Count 5"));
+    }
+
+    #[test]
+    fn test_render_pdf_splits_wide_tables() {
+        let columns: Vec<String> = (0..10).map(|i| format!("column_{}", i)).collect();
+        let column_refs: Vec<&str> = columns.iter().map(String::as_str).collect();
+        let mut table = ReportTable::new("wide", &column_refs);
+        table.push_row((0..10).map(|_| "x".repeat(40).into()).collect());
+
+        let lines = table_text_lines(&table);
+        assert!(lines.iter().all(|l| l.chars().count() <= PDF_LINE_CHARS));
+        // 32-character columns, four to a line
+        assert_eq!(lines[0], "wide (1/3)");
+        assert!(lines.contains(&"wide (3/3)".to_string()));
+        assert!(lines.iter().any(|l| l.starts_with("column_4")));
+    }
+
+    #[test]
+    fn test_wrap_line() {
+        let long = "a".repeat(PDF_LINE_CHARS + 1);
+        assert_eq!(wrap_line(&long).len(), 2);
+        assert_eq!(wrap_line(""), vec![String::new()]);
+    }
+
+    #[test]
+    fn test_pdf_escape() {
+        assert_eq!(pdf_escape("a(b)\\c"), b"a\\(b\\)\\\\c");
+        assert_eq!(pdf_escape("café €5 – ok"), b"caf\xE9 \x805 \x96 ok");
+        assert_eq!(pdf_escape("日本"), b"??");
+    }
+}
@@ -0,0 +1,72 @@
+//! Enforcement of per-device tracking schedules on upload.
+//!
+//! The schedule comes from the device's `tracking_schedule` setting.
+//! Locations captured outside the schedule are dropped before filtering,
+//! smoothing, or persistence.
+
+use domain::models::WeeklySchedule;
+use domain::services::TRACKING_SCHEDULE_SETTING_KEY;
+use persistence::repositories::{LocationInput, SettingRepository};
+use sqlx::PgPool;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+/// Load the tracking schedule for a device, if one is set.
+///
+/// Lookup failures and unparsable values are logged and treated as no
+/// schedule so that ingestion never fails because of the schedule stage.
+pub async fn load_tracking_schedule(pool: &PgPool, device_id: Uuid) -> Option<WeeklySchedule> {
+    let repo = SettingRepository::new(pool.clone());
+    let setting = match repo
+        .get_device_setting(device_id, TRACKING_SCHEDULE_SETTING_KEY)
+        .await
+    {
+        Ok(setting) => setting?,
+        Err(e) => {
+            warn!(
+                device_id = %device_id,
+                error = %e,
+                "Failed to load tracking schedule setting"
+            );
+            return None;
+        }
+    };
+
+    match WeeklySchedule::from_value(&setting.value) {
+        Ok(schedule) => schedule,
+        Err(e) => {
+            warn!(
+                device_id = %device_id,
+                error = %e,
+                "Ignoring invalid tracking schedule setting"
+            );
+            None
+        }
+    }
+}
+
+/// Drop locations captured outside the schedule.
+///
+/// Returns the locations inside the schedule and the number dropped.
+pub fn drop_outside_schedule(
+    schedule: &WeeklySchedule,
+    device_id: Uuid,
+    locations: Vec<LocationInput>,
+) -> (Vec<LocationInput>, usize) {
+    let total = locations.len();
+    let inside: Vec<LocationInput> = locations
+        .into_iter()
+        .filter(|loc| schedule.is_active_at(loc.captured_at))
+        .collect();
+
+    let dropped = total - inside.len();
+    if dropped > 0 {
+        debug!(
+            device_id = %device_id,
+            dropped,
+            "Ignored locations outside tracking schedule"
+        );
+        metrics::counter!("locations_outside_schedule_total").increment(dropped as u64);
+    }
+    (inside, dropped)
+}
@@ -0,0 +1,144 @@
+//! Trip classification by the device's rules.
+//!
+//! Run when a trip completes or is edited. The purpose of the first
+//! matching enabled rule is stored with source `rule`; when no rule matches
+//! any earlier rule purpose is cleared. Purposes set by the device owner are
+//! never replaced.
+
+use chrono::Weekday;
+use domain::models::trip::TripState;
+use domain::models::trip_purpose::TripPurposeSource;
+use domain::services::{classify_trip, CircleArea, ClassificationRule};
+use persistence::entities::{GeofenceEntity, TripRuleEntity};
+use persistence::repositories::{GeofenceRepository, TripRepository, TripRuleRepository};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Classify a completed trip, returning the purpose it ends up with.
+pub async fn classify_completed_trip(
+    pool: &PgPool,
+    trip_id: Uuid,
+) -> Result<Option<String>, sqlx::Error> {
+    let trip_repo = TripRepository::new(pool.clone());
+    let Some(trip) = trip_repo.find_by_id(trip_id).await? else {
+        return Ok(None);
+    };
+    if trip.state != TripState::Completed.as_str()
+        || trip.purpose_source.as_deref() == Some(TripPurposeSource::Manual.as_str())
+    {
+        return Ok(trip.purpose);
+    }
+
+    let rules = TripRuleRepository::new(pool.clone())
+        .list_by_device(trip.device_id)
+        .await?;
+    let rules: Vec<TripRuleEntity> = rules.into_iter().filter(|r| r.enabled).collect();
+    let purpose = if rules.is_empty() {
+        None
+    } else {
+        let geofences: HashMap<Uuid, GeofenceEntity> = GeofenceRepository::new(pool.clone())
+            .find_by_device_id(trip.device_id, true)
+            .await?
+            .into_iter()
+            .map(|g| (g.geofence_id, g))
+            .collect();
+        let rules: Vec<ClassificationRule> = rules
+            .iter()
+            .filter_map(|rule| to_classification_rule(rule, &geofences))
+            .collect();
+        classify_trip(&trip.into_domain(), &rules).map(str::to_string)
+    };
+
+    trip_repo
+        .set_rule_purpose(trip_id, purpose.as_deref())
+        .await?;
+    Ok(purpose)
+}
+
+/// Resolve the geofences of a rule. Rules referencing a geofence of
+/// another device are skipped.
+fn to_classification_rule(
+    rule: &TripRuleEntity,
+    geofences: &HashMap<Uuid, GeofenceEntity>,
+) -> Option<ClassificationRule> {
+    let area = |id: Option<Uuid>| -> Option<Option<CircleArea>> {
+        match id {
+            None => Some(None),
+            Some(id) => geofences.get(&id).map(|g| {
+                Some(CircleArea {
+                    latitude: g.latitude,
+                    longitude: g.longitude,
+                    radius_meters: f64::from(g.radius_meters),
+                })
+            }),
+        }
+    };
+    let departure_window = rule
+        .departure_after_minute
+        .zip(rule.departure_before_minute)
+        .map(|(after, before)| (after.max(0) as u32, before.max(0) as u32));
+
+    Some(ClassificationRule {
+        purpose: rule.purpose.clone(),
+        start_area: area(rule.start_geofence_id)?,
+        end_area: area(rule.end_geofence_id)?,
+        weekdays: rule
+            .weekdays
+            .iter()
+            .filter_map(|&d| weekday_from_number(d))
+            .collect(),
+        departure_window,
+    })
+}
+
+fn weekday_from_number(number: i16) -> Option<Weekday> {
+    Weekday::try_from(u8::try_from(number).ok()?.checked_sub(1)?).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn rule_entity() -> TripRuleEntity {
+        TripRuleEntity {
+            id: Uuid::new_v4(),
+            device_id: Uuid::new_v4(),
+            name: "Office".to_string(),
+            purpose: "business".to_string(),
+            priority: 0,
+            start_geofence_id: None,
+            end_geofence_id: None,
+            weekdays: vec![1, 7],
+            departure_after_minute: Some(420),
+            departure_before_minute: Some(600),
+            enabled: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_weekday_from_number() {
+        assert_eq!(weekday_from_number(1), Some(Weekday::Mon));
+        assert_eq!(weekday_from_number(7), Some(Weekday::Sun));
+        assert_eq!(weekday_from_number(0), None);
+        assert_eq!(weekday_from_number(8), None);
+    }
+
+    #[test]
+    fn test_rule_without_geofences() {
+        let rule = to_classification_rule(&rule_entity(), &HashMap::new()).unwrap();
+        assert_eq!(rule.weekdays, vec![Weekday::Mon, Weekday::Sun]);
+        assert_eq!(rule.departure_window, Some((420, 600)));
+        assert!(rule.start_area.is_none());
+    }
+
+    #[test]
+    fn test_rule_with_unknown_geofence_is_skipped() {
+        let mut entity = rule_entity();
+        entity.end_geofence_id = Some(Uuid::new_v4());
+        assert!(to_classification_rule(&entity, &HashMap::new()).is_none());
+    }
+}
@@ -0,0 +1,166 @@
+//! Server-side trip detection for a device.
+//!
+//! Run by the trip detection job for devices with the
+//! `server_trip_detection_enabled` setting. The segmenter is resumed from
+//! the device's last derived trip: an active trip is rebuilt from its start,
+//! otherwise detection continues from where the last trip ended. Trips are
+//! stored with detection source `LOCATION_STREAM`, created as active while
+//! the device is still moving and completed on arrival.
+
+use chrono::{DateTime, Duration, Utc};
+use domain::models::movement_event::{DetectionSource, TransportationMode};
+use domain::models::trip::TripState;
+use domain::services::{DetectedTrip, MovementFix, TripDetectionConfig, TripSegmenter};
+use persistence::entities::{LocationEntity, TripEntity};
+use persistence::repositories::{LocationRepository, TripInput, TripRepository, TripUpdateInput};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::trip_classification::classify_completed_trip;
+
+/// How far back locations are replayed when resuming after a completed
+/// trip, or for a device without derived trips.
+const LOOKBACK_HOURS: i64 = 24;
+
+/// Derive and store trips of a device, returning how many were completed.
+///
+/// Devices with an active trip from another source detect trips
+/// themselves and are skipped.
+pub async fn detect_trips(pool: &PgPool, device_id: Uuid) -> Result<usize, sqlx::Error> {
+    let config = TripDetectionConfig::default();
+    let source = DetectionSource::LocationStream.as_str();
+    let trip_repo = TripRepository::new(pool.clone());
+
+    if trip_repo
+        .find_active_for_device(device_id)
+        .await?
+        .is_some_and(|t| t.detection_source != source)
+    {
+        return Ok(0);
+    }
+
+    let lookback_ms = (Utc::now() - Duration::hours(LOOKBACK_HOURS)).timestamp_millis();
+    let last = trip_repo.find_latest_by_source(device_id, source).await?;
+    let (mut segmenter, since_ms, mut active) = match last {
+        Some(trip) if trip.state == TripState::Active.as_str() => {
+            let segmenter = TripSegmenter::resume_trip(config, &to_detected(&trip));
+            (segmenter, trip.start_timestamp, Some(trip))
+        }
+        Some(trip) => {
+            let detected = to_detected(&trip);
+            let segmenter = TripSegmenter::resume_after(config, &detected);
+            (segmenter, detected.end_timestamp_ms.max(lookback_ms), None)
+        }
+        None => (TripSegmenter::new(config), lookback_ms, None),
+    };
+
+    let since = DateTime::<Utc>::from_timestamp_millis(since_ms);
+    let locations = LocationRepository::new(pool.clone())
+        .get_all_locations_in_range(device_id, since, None)
+        .await?;
+
+    let mut completed = 0;
+    for location in &locations {
+        if let Some(trip) = segmenter.process(to_fix(location)) {
+            let trip_id = save_trip(&trip_repo, device_id, &trip, active.take(), true).await?;
+            classify_completed_trip(pool, trip_id).await?;
+            metrics::counter!("trips_detected_total").increment(1);
+            completed += 1;
+        }
+    }
+    match (segmenter.ongoing(), active) {
+        (Some(trip), active) => {
+            save_trip(&trip_repo, device_id, trip, active, false).await?;
+        }
+        // The replayed locations no longer make a trip, e.g. after some
+        // were deleted.
+        (None, Some(stale)) => {
+            trip_repo
+                .update_state(
+                    stale.id,
+                    TripUpdateInput {
+                        state: TripState::Cancelled.as_str().to_string(),
+                        end_timestamp: None,
+                        end_latitude: None,
+                        end_longitude: None,
+                    },
+                )
+                .await?;
+        }
+        (None, None) => {}
+    }
+
+    Ok(completed)
+}
+
+/// Create or update the stored trip of a detected trip, returning its id.
+async fn save_trip(
+    trip_repo: &TripRepository,
+    device_id: Uuid,
+    trip: &DetectedTrip,
+    existing: Option<TripEntity>,
+    completed: bool,
+) -> Result<Uuid, sqlx::Error> {
+    let entity = match existing {
+        Some(entity) => entity,
+        None => {
+            let input = TripInput {
+                device_id,
+                local_trip_id: trip.local_trip_id(),
+                start_timestamp: trip.start_timestamp_ms,
+                start_latitude: trip.start_latitude,
+                start_longitude: trip.start_longitude,
+                transportation_mode: trip.transportation_mode.as_str().to_string(),
+                detection_source: DetectionSource::LocationStream.as_str().to_string(),
+            };
+            trip_repo.create_trip(input).await?.0
+        }
+    };
+
+    if completed {
+        trip_repo
+            .update_state(
+                entity.id,
+                TripUpdateInput {
+                    state: TripState::Completed.as_str().to_string(),
+                    end_timestamp: Some(trip.end_timestamp_ms),
+                    end_latitude: Some(trip.end_latitude),
+                    end_longitude: Some(trip.end_longitude),
+                },
+            )
+            .await?;
+        trip_repo
+            .update_transportation_mode(entity.id, trip.transportation_mode.as_str())
+            .await?;
+    }
+    trip_repo
+        .update_statistics(entity.id, trip.distance_meters, trip.duration_seconds())
+        .await?;
+    Ok(entity.id)
+}
+
+fn to_detected(trip: &TripEntity) -> DetectedTrip {
+    DetectedTrip {
+        start_latitude: trip.start_latitude,
+        start_longitude: trip.start_longitude,
+        start_timestamp_ms: trip.start_timestamp,
+        end_latitude: trip.end_latitude.unwrap_or(trip.start_latitude),
+        end_longitude: trip.end_longitude.unwrap_or(trip.start_longitude),
+        end_timestamp_ms: trip.end_timestamp.unwrap_or(trip.start_timestamp),
+        distance_meters: trip.distance_meters.unwrap_or(0.0),
+        transportation_mode: trip
+            .transportation_mode
+            .parse()
+            .unwrap_or(TransportationMode::Unknown),
+    }
+}
+
+fn to_fix(location: &LocationEntity) -> MovementFix {
+    MovementFix {
+        latitude: location.latitude,
+        longitude: location.longitude,
+        accuracy: location.accuracy as f64,
+        speed: location.speed.map(f64::from),
+        timestamp_ms: location.captured_at.timestamp_millis(),
+    }
+}
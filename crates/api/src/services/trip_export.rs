@@ -0,0 +1,156 @@
+//! Trip export rendering.
+//!
+//! Writes a trip path as a GPX 1.1 track or as CSV. Map-matched points carry
+//! no capture time, altitude or speed, so those fields are left empty.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use domain::models::trip::TripExportFormat;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use super::report_rendering::csv_escape;
+use super::xlsx::xml_escape;
+
+/// Header row of the CSV export.
+const CSV_HEADER: &str = "latitude,longitude,timestamp,altitude,speed\n";
+
+/// Closing tags of the GPX export.
+const GPX_FOOTER: &str = "</trkseg></trk></gpx>\n";
+
+/// A single point of an exported trip path.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TripExportPoint {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub captured_at: Option<DateTime<Utc>>,
+    pub altitude: Option<f64>,
+    /// Speed in meters per second.
+    pub speed: Option<f64>,
+}
+
+/// Write the trip path in the requested format and close the writer.
+pub async fn write_trip_export<W: AsyncWrite + Unpin>(
+    out: &mut W,
+    format: TripExportFormat,
+    name: &str,
+    points: &[TripExportPoint],
+) -> std::io::Result<()> {
+    match format {
+        TripExportFormat::Gpx => {
+            out.write_all(gpx_header(name).as_bytes()).await?;
+            for point in points {
+                out.write_all(gpx_point(point).as_bytes()).await?;
+            }
+            out.write_all(GPX_FOOTER.as_bytes()).await?;
+        }
+        TripExportFormat::Csv => {
+            out.write_all(CSV_HEADER.as_bytes()).await?;
+            for point in points {
+                out.write_all(csv_point(point).as_bytes()).await?;
+            }
+        }
+    }
+    out.shutdown().await
+}
+
+fn gpx_header(name: &str) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <gpx version=\"1.1\" creator=\"phone-manager\" xmlns=\"http://www.topografix.com/GPX/1/1\">\n\
+         <trk><name>{}</name><trkseg>\n",
+        xml_escape(name)
+    )
+}
+
+fn gpx_point(point: &TripExportPoint) -> String {
+    let mut out = format!(
+        "<trkpt lat=\"{}\" lon=\"{}\">",
+        point.latitude, point.longitude
+    );
+    // GPX requires <ele> before <time>; speed is not part of GPX 1.1.
+    if let Some(altitude) = point.altitude {
+        out.push_str(&format!("<ele>{}</ele>", altitude));
+    }
+    if let Some(captured_at) = point.captured_at {
+        out.push_str(&format!("<time>{}</time>", format_time(captured_at)));
+    }
+    out.push_str("</trkpt>\n");
+    out
+}
+
+fn csv_point(point: &TripExportPoint) -> String {
+    let optional = |value: Option<f64>| value.map(|v| v.to_string()).unwrap_or_default();
+    let timestamp = point.captured_at.map(format_time).unwrap_or_default();
+    format!(
+        "{},{},{},{},{}\n",
+        point.latitude,
+        point.longitude,
+        csv_escape(&timestamp),
+        optional(point.altitude),
+        optional(point.speed)
+    )
+}
+
+fn format_time(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn points() -> Vec<TripExportPoint> {
+        vec![
+            TripExportPoint {
+                latitude: 48.1486,
+                longitude: 17.1077,
+                captured_at: Some(Utc.with_ymd_and_hms(2026, 3, 1, 8, 30, 0).unwrap()),
+                altitude: Some(140.5),
+                speed: Some(12.0),
+            },
+            TripExportPoint {
+                latitude: 48.15,
+                longitude: 17.11,
+                captured_at: None,
+                altitude: None,
+                speed: None,
+            },
+        ]
+    }
+
+    async fn render(format: TripExportFormat) -> String {
+        let mut out = Vec::new();
+        write_trip_export(&mut out, format, "Home & work", &points())
+            .await
+            .unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_gpx_export() {
+        let gpx = render(TripExportFormat::Gpx).await;
+
+        assert!(gpx.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<gpx version=\"1.1\""));
+        assert!(gpx.contains("<name>Home &amp; work</name>"));
+        assert!(gpx.contains(
+            "<trkpt lat=\"48.1486\" lon=\"17.1077\"><ele>140.5</ele><time>2026-03-01T08:30:00.000Z</time></trkpt>"
+        ));
+        assert!(gpx.contains("<trkpt lat=\"48.15\" lon=\"17.11\"></trkpt>"));
+        assert!(gpx.ends_with(GPX_FOOTER));
+    }
+
+    #[tokio::test]
+    async fn test_csv_export() {
+        let csv = render(TripExportFormat::Csv).await;
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(
+            lines,
+            vec![
+                "latitude,longitude,timestamp,altitude,speed",
+                "48.1486,17.1077,2026-03-01T08:30:00.000Z,140.5,12",
+                "48.15,17.11,,,",
+            ]
+        );
+    }
+}
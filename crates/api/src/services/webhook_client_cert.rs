@@ -0,0 +1,55 @@
+//! Client certificates for mutual TLS webhook deliveries.
+//!
+//! Organization webhooks may carry a PEM client certificate and private key.
+//! The private key is stored encrypted with AES-256-GCM under
+//! `security.webhook_client_cert_key`, see [`WebhookSecrets::seal_client_key`],
+//! and decrypted whenever a delivery needs it.
+
+use std::time::Duration;
+
+use reqwest::{Client, Identity};
+
+use super::webhook_secret::WebhookSecrets;
+
+/// Parse a PEM certificate (chain) and private key into a TLS client identity.
+pub fn client_identity(certificate_pem: &str, private_key_pem: &[u8]) -> Result<Identity, String> {
+    let mut pem = certificate_pem.trim_end().as_bytes().to_vec();
+    pem.push(b'\n');
+    pem.extend_from_slice(private_key_pem);
+    Identity::from_pem(&pem).map_err(|e| format!("Invalid client certificate or key: {}", e))
+}
+
+/// Build an HTTP client presenting a webhook's client certificate.
+pub fn mtls_client(
+    secrets: &WebhookSecrets,
+    certificate_pem: &str,
+    encrypted_key: &[u8],
+    timeout: Option<Duration>,
+) -> Result<Client, String> {
+    let key = secrets.open_client_key(encrypted_key)?;
+    let identity = client_identity(certificate_pem, &key)?;
+
+    let mut builder = Client::builder().identity(identity);
+    if let Some(timeout) = timeout {
+        builder = builder.timeout(timeout);
+    }
+    builder
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_identity_rejects_invalid_pem() {
+        assert!(client_identity("not a certificate", b"not a key").is_err());
+    }
+
+    #[test]
+    fn test_mtls_client_requires_client_cert_key() {
+        let err = mtls_client(&WebhookSecrets::default(), "cert", b"key", None).unwrap_err();
+        assert!(err.contains("webhook_client_cert_key"));
+    }
+}
@@ -6,24 +6,34 @@
 //! Handles asynchronous delivery of webhook notifications to external systems
 //! with full delivery logging, retry support, and circuit breaker protection.
 
-use chrono::{Duration as ChronoDuration, Utc};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
 use hmac::{Hmac, Mac};
 use persistence::entities::WebhookDeliveryEntity;
+use persistence::faults::{self, FaultPoint, InjectedFault};
 use persistence::repositories::{
-    GeofenceEventRepository, WebhookDeliveryRepository, WebhookRepository,
+    DeviceRepository, GeofenceEventRepository, OrgWebhookRepository, WebhookBatchRepository,
+    WebhookDeliveryRepository, WebhookRepository,
 };
 use reqwest::Client;
 use serde::Serialize;
 use sha2::Sha256;
 use sqlx::PgPool;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
-use domain::models::GeofenceTransitionType;
+use domain::models::{
+    payload_content_type, render_payload_template, GeofenceTransitionType, Webhook,
+    WebhookPayloadFormat, WebhookRetryPolicy, WebhookTemplateValues,
+    DEFAULT_WEBHOOK_BATCH_MAX_EVENTS, WEBHOOK_RATE_LIMIT_WINDOW_SECS,
+};
+
+use super::org_webhook_delivery::OrgWebhookDeliveryService;
+use super::webhook_secret::WebhookSecrets;
 
-/// Webhook delivery timeout in seconds.
+/// Webhook delivery timeout in seconds, used unless a webhook's retry
+/// policy sets its own.
 const WEBHOOK_TIMEOUT_SECS: u64 = 5;
 
 /// Number of consecutive failures before circuit breaker opens.
@@ -34,6 +44,18 @@ pub const CIRCUIT_BREAKER_THRESHOLD: i32 = 5;
 /// The webhook will be unavailable for this duration after the circuit opens.
 pub const CIRCUIT_BREAKER_COOLDOWN_SECS: i64 = 300; // 5 minutes
 
+/// Header carrying the timestamped signature, `t=<unix seconds>,v1=<hex>`.
+pub const SIGNATURE_HEADER: &str = "X-Signature";
+
+/// Maximum age in seconds of a timestamped signature that receivers should
+/// accept. Older (or further in the future) deliveries should be rejected
+/// as possible replays.
+pub const SIGNATURE_TOLERANCE_SECS: i64 = 300; // 5 minutes
+
+/// Maximum number of characters of a target's response body kept in the
+/// delivery log.
+pub const RESPONSE_BODY_EXCERPT_CHARS: usize = 1024;
+
 /// Errors that can occur during webhook delivery.
 #[derive(Error, Debug)]
 pub enum WebhookDeliveryError {
@@ -48,6 +70,12 @@ pub enum WebhookDeliveryError {
 
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
+
+    #[error("Client certificate error: {0}")]
+    ClientCertificateError(String),
+
+    #[error("{0}")]
+    InjectedFault(#[from] InjectedFault),
 }
 
 /// Webhook payload for geofence events.
@@ -57,10 +85,89 @@ pub struct GeofenceWebhookPayload {
     pub device_id: Uuid,
     pub geofence_id: Uuid,
     pub geofence_name: String,
+    /// Geofence the event resolved to among overlapping geofences; only
+    /// events resolving to their own geofence are delivered.
+    pub resolved_geofence_id: Uuid,
     pub timestamp: i64,
     pub location: WebhookLocation,
 }
 
+impl GeofenceWebhookPayload {
+    /// Values of this event for rendering payload templates.
+    pub fn template_values(&self, device_name: Option<String>) -> WebhookTemplateValues {
+        WebhookTemplateValues {
+            event_type: self.event_type.clone(),
+            device_id: self.device_id,
+            device_name,
+            geofence_id: self.geofence_id,
+            geofence_name: self.geofence_name.clone(),
+            latitude: self.location.latitude,
+            longitude: self.location.longitude,
+            timestamp: self.timestamp,
+        }
+    }
+}
+
+/// Build what a webhook receives for a geofence event: its payload template
+/// rendered with the event (or the payload itself), in the webhook's format.
+pub fn webhook_payload(
+    webhook: &Webhook,
+    payload: &GeofenceWebhookPayload,
+    device_name: Option<String>,
+) -> Result<serde_json::Value, serde_json::Error> {
+    let data = match webhook.payload_template {
+        Some(ref template) => {
+            render_payload_template(template, &payload.template_values(device_name))
+        }
+        None => serde_json::to_value(payload)?,
+    };
+    let time = DateTime::from_timestamp_millis(payload.timestamp).unwrap_or_else(Utc::now);
+    Ok(webhook.payload_format.wrap(
+        &payload.event_type,
+        &format!("/devices/{}", payload.device_id),
+        Some(&format!("geofences/{}", payload.geofence_id)),
+        time,
+        data,
+    ))
+}
+
+/// Response of a webhook target to a delivery attempt.
+#[derive(Debug, Clone)]
+pub struct DeliveryResponse {
+    pub status_code: u16,
+    /// Time until the response headers arrived.
+    pub latency_ms: i32,
+    /// Response body, truncated with [`response_body_excerpt`].
+    pub body: String,
+}
+
+impl DeliveryResponse {
+    /// Read the status and body of a response whose request started at `started`.
+    pub async fn read(response: reqwest::Response, started: Instant) -> Self {
+        let latency_ms = started.elapsed().as_millis().min(i32::MAX as u128) as i32;
+        let status_code = response.status().as_u16();
+        let body = response.text().await.unwrap_or_default();
+        Self {
+            status_code,
+            latency_ms,
+            body: response_body_excerpt(&body),
+        }
+    }
+
+    /// Whether the target accepted the delivery.
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status_code)
+    }
+}
+
+/// Truncate a response body to at most `RESPONSE_BODY_EXCERPT_CHARS` characters.
+pub fn response_body_excerpt(body: &str) -> String {
+    match body.char_indices().nth(RESPONSE_BODY_EXCERPT_CHARS) {
+        Some((idx, _)) => format!("{}...", &body[..idx]),
+        None => body.to_string(),
+    }
+}
+
 /// Location data in webhook payload.
 #[derive(Debug, Clone, Serialize)]
 pub struct WebhookLocation {
@@ -68,28 +175,141 @@ pub struct WebhookLocation {
     pub longitude: f64,
 }
 
+/// Build a realistic sample payload for a webhook test delivery.
+///
+/// The payload has the same shape as a real geofence event so receivers
+/// (Home Assistant, n8n, ...) can be configured against it.
+pub fn sample_payload(event_type: &str, device_id: Uuid) -> GeofenceWebhookPayload {
+    let (geofence_name, latitude, longitude) = match event_type {
+        "geofence_exit" => ("Office", 37.7897, -122.3972),
+        "geofence_dwell" => ("Gym", 37.7793, -122.4193),
+        _ => ("Home", 37.7749, -122.4194),
+    };
+
+    let geofence_id = Uuid::new_v4();
+    GeofenceWebhookPayload {
+        event_type: event_type.to_string(),
+        device_id,
+        geofence_id,
+        geofence_name: geofence_name.to_string(),
+        resolved_geofence_id: geofence_id,
+        timestamp: Utc::now().timestamp_millis(),
+        location: WebhookLocation {
+            latitude,
+            longitude,
+        },
+    }
+}
+
+/// Sign a webhook payload with HMAC-SHA256, formatted as `sha256=<hex>`.
+pub fn sign_webhook_payload(payload: &str, secret: &str) -> Result<String, WebhookDeliveryError> {
+    type HmacSha256 = Hmac<Sha256>;
+
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
+        .map_err(|e| WebhookDeliveryError::SigningError(e.to_string()))?;
+
+    mac.update(payload.as_bytes());
+    let result = mac.finalize();
+    let signature = hex::encode(result.into_bytes());
+
+    Ok(format!("sha256={}", signature))
+}
+
+/// Sign a webhook payload together with the delivery time, formatted as
+/// `t=<unix seconds>,v1=<hex>`.
+///
+/// The HMAC-SHA256 covers `<timestamp>.<payload>`, so a captured delivery
+/// cannot be replayed outside the verification window without the secret.
+pub fn sign_timestamped_payload(
+    payload: &str,
+    secret: &str,
+    timestamp: i64,
+) -> Result<String, WebhookDeliveryError> {
+    let mac = timestamped_mac(payload, secret, timestamp)?;
+    let signature = hex::encode(mac.finalize().into_bytes());
+
+    Ok(format!("t={},v1={}", timestamp, signature))
+}
+
+/// Verify an `X-Signature` header the way receivers are expected to.
+///
+/// Accepts the header when one of its `v1` signatures matches and its
+/// timestamp is within `tolerance_secs` of `now`.
+pub fn verify_timestamped_signature(
+    header: &str,
+    payload: &str,
+    secret: &str,
+    now: i64,
+    tolerance_secs: i64,
+) -> bool {
+    let mut timestamp = None;
+    let mut signatures = Vec::new();
+    for part in header.split(',') {
+        match part.trim().split_once('=') {
+            Some(("t", value)) => timestamp = value.parse::<i64>().ok(),
+            Some(("v1", value)) => signatures.push(value),
+            _ => {}
+        }
+    }
+
+    let Some(timestamp) = timestamp else {
+        return false;
+    };
+    if (now - timestamp).abs() > tolerance_secs {
+        return false;
+    }
+
+    signatures.into_iter().any(|signature| {
+        let Ok(expected) = hex::decode(signature) else {
+            return false;
+        };
+        timestamped_mac(payload, secret, timestamp)
+            .map(|mac| mac.verify_slice(&expected).is_ok())
+            .unwrap_or(false)
+    })
+}
+
+fn timestamped_mac(
+    payload: &str,
+    secret: &str,
+    timestamp: i64,
+) -> Result<Hmac<Sha256>, WebhookDeliveryError> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .map_err(|e| WebhookDeliveryError::SigningError(e.to_string()))?;
+
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(payload.as_bytes());
+    Ok(mac)
+}
+
 /// Service for delivering webhooks.
 pub struct WebhookDeliveryService {
     pool: PgPool,
+    secrets: WebhookSecrets,
     client: Client,
 }
 
 impl WebhookDeliveryService {
     /// Create a new webhook delivery service.
-    pub fn new(pool: PgPool) -> Self {
+    pub fn new(pool: PgPool, secrets: WebhookSecrets) -> Self {
         let client = Client::builder()
             .timeout(Duration::from_secs(WEBHOOK_TIMEOUT_SECS))
             .build()
             .expect("Failed to create HTTP client");
 
-        Self { pool, client }
+        Self {
+            pool,
+            secrets,
+            client,
+        }
     }
 
     /// Deliver geofence event to all enabled webhooks for the device.
     ///
     /// This method:
     /// 1. Finds all enabled webhooks for the device
-    /// 2. Creates the webhook payload
+    /// 2. Creates the webhook payload, rendering each webhook's template (if any)
     /// 3. Logs delivery record for each webhook
     /// 4. Signs the payload with HMAC-SHA256
     /// 5. Delivers to each webhook URL
@@ -102,6 +322,7 @@ impl WebhookDeliveryService {
         device_id: Uuid,
         geofence_id: Uuid,
         geofence_name: &str,
+        resolved_geofence_id: Uuid,
         event_type: GeofenceTransitionType,
         timestamp: i64,
         latitude: f64,
@@ -109,9 +330,12 @@ impl WebhookDeliveryService {
     ) -> Result<(), WebhookDeliveryError> {
         // Find all enabled webhooks for this device
         let webhook_repo = WebhookRepository::new(self.pool.clone());
-        let webhooks = webhook_repo
-            .find_enabled_by_owner_device_id(device_id)
-            .await?;
+        let webhooks: Vec<Webhook> = webhook_repo
+            .find_enabled_for_device(device_id)
+            .await?
+            .into_iter()
+            .map(Webhook::from)
+            .collect();
 
         if webhooks.is_empty() {
             info!(device_id = %device_id, "No enabled webhooks found for device");
@@ -124,6 +348,7 @@ impl WebhookDeliveryService {
             device_id,
             geofence_id,
             geofence_name: geofence_name.to_string(),
+            resolved_geofence_id,
             timestamp,
             location: WebhookLocation {
                 latitude,
@@ -131,19 +356,74 @@ impl WebhookDeliveryService {
             },
         };
 
-        let payload_json = serde_json::to_string(&payload)?;
-        let payload_value: serde_json::Value = serde_json::from_str(&payload_json)?;
+        let default_json = serde_json::to_string(&payload)?;
+        let default_value: serde_json::Value = serde_json::from_str(&default_json)?;
+
+        // The device name is only needed by payload templates
+        let device_name = if webhooks.iter().any(|w| w.payload_template.is_some()) {
+            DeviceRepository::new(self.pool.clone())
+                .find_by_device_id(device_id)
+                .await?
+                .map(|d| d.display_name)
+        } else {
+            None
+        };
 
         // Track overall delivery status
         let mut any_success = false;
         let mut last_response_code: Option<i32> = None;
 
         let delivery_repo = WebhookDeliveryRepository::new(self.pool.clone());
+        let batch_repo = WebhookBatchRepository::new(self.pool.clone());
 
         // Deliver to each webhook
         for webhook in &webhooks {
-            // Create delivery record
+            let (payload_value, payload_json) = if webhook.payload_template.is_none()
+                && webhook.payload_format == WebhookPayloadFormat::Default
+            {
+                (default_value.clone(), default_json.clone())
+            } else {
+                let value = webhook_payload(webhook, &payload, device_name.clone())?;
+                let json = value.to_string();
+                (value, json)
+            };
+
             let event_type_str = event_type.to_webhook_event_type();
+
+            // Batched webhooks get the event with their next batch
+            if let Some(batching) = webhook.batching() {
+                let size = batch_repo
+                    .add(webhook.webhook_id, event_id, &payload_value)
+                    .await?;
+                if size >= i64::from(batching.max_events) {
+                    self.flush_batch(&batch_repo, webhook.webhook_id, batching.max_events)
+                        .await?;
+                }
+                continue;
+            }
+
+            // Hold back deliveries over the webhook's rate limit
+            if let Some(send_at) = self.throttled_until(webhook, &delivery_repo).await? {
+                let coalesced = delivery_repo
+                    .hold(
+                        webhook.webhook_id,
+                        device_id,
+                        event_id,
+                        event_type_str,
+                        &payload_value,
+                        send_at,
+                    )
+                    .await?;
+                debug!(
+                    webhook_id = %webhook.webhook_id,
+                    send_at = %send_at,
+                    coalesced = coalesced,
+                    "Webhook rate limit reached, holding delivery"
+                );
+                continue;
+            }
+
+            // Create delivery record; retries resend the rendered payload
             let delivery = delivery_repo
                 .create(
                     webhook.webhook_id,
@@ -153,14 +433,13 @@ impl WebhookDeliveryService {
                 )
                 .await?;
 
-            let signature = self.sign_payload(&payload_json, &webhook.secret)?;
-
             match self
-                .deliver_to_webhook(&webhook.target_url, &payload_json, &signature)
+                .deliver_to_webhook(webhook, &payload_json, payload_content_type(&payload_value))
                 .await
             {
-                Ok(status_code) => {
-                    let success = (200..300).contains(&(status_code as i32));
+                Ok(response) => {
+                    let success = response.is_success();
+                    let status_code = response.status_code;
 
                     // Update delivery record
                     delivery_repo
@@ -169,6 +448,9 @@ impl WebhookDeliveryService {
                             success,
                             Some(status_code as i32),
                             None,
+                            Some(response.latency_ms),
+                            Some(&response.body),
+                            &webhook.retry_policy,
                         )
                         .await?;
 
@@ -211,7 +493,15 @@ impl WebhookDeliveryService {
                 Err(e) => {
                     // Update delivery record with error
                     delivery_repo
-                        .update_attempt(delivery.delivery_id, false, None, Some(&e.to_string()))
+                        .update_attempt(
+                            delivery.delivery_id,
+                            false,
+                            None,
+                            Some(&e.to_string()),
+                            None,
+                            None,
+                            &webhook.retry_policy,
+                        )
                         .await?;
 
                     warn!(
@@ -281,6 +571,57 @@ impl WebhookDeliveryService {
         Ok(processed)
     }
 
+    /// Deliver the due batches of batched webhooks.
+    ///
+    /// Returns the number of batches sent.
+    pub async fn flush_due_batches(&self) -> Result<u32, WebhookDeliveryError> {
+        let batch_repo = WebhookBatchRepository::new(self.pool.clone());
+        let due = batch_repo
+            .due_webhooks(DEFAULT_WEBHOOK_BATCH_MAX_EVENTS)
+            .await?;
+
+        let mut flushed = 0u32;
+        for (webhook_id, max_events) in due {
+            match self.flush_batch(&batch_repo, webhook_id, max_events).await {
+                Ok(true) => flushed += 1,
+                Ok(false) => {}
+                Err(e) => {
+                    error!(
+                        webhook_id = %webhook_id,
+                        error = %e,
+                        "Failed to deliver webhook batch"
+                    );
+                }
+            }
+        }
+        Ok(flushed)
+    }
+
+    /// Move up to `max_events` batched events of a webhook into one delivery
+    /// and send it. Returns false if there was nothing to send.
+    async fn flush_batch(
+        &self,
+        batch_repo: &WebhookBatchRepository,
+        webhook_id: Uuid,
+        max_events: i32,
+    ) -> Result<bool, WebhookDeliveryError> {
+        let Some(delivery) = batch_repo
+            .take_into_delivery(webhook_id, max_events)
+            .await?
+        else {
+            return Ok(false);
+        };
+        metrics::counter!("webhook_batches_sent_total").increment(1);
+
+        // The first attempt of a batch goes through the retry path, which
+        // checks the webhook's state, circuit breaker and rate limit
+        let webhook_repo = WebhookRepository::new(self.pool.clone());
+        let delivery_repo = WebhookDeliveryRepository::new(self.pool.clone());
+        self.retry_delivery(&delivery, &webhook_repo, &delivery_repo)
+            .await?;
+        Ok(true)
+    }
+
     /// Retry a single delivery.
     async fn retry_delivery(
         &self,
@@ -290,11 +631,29 @@ impl WebhookDeliveryService {
     ) -> Result<(), WebhookDeliveryError> {
         // Find the webhook
         let webhook = match webhook_repo.find_by_webhook_id(delivery.webhook_id).await? {
-            Some(w) => w,
+            Some(w) => Webhook::from(w),
             None => {
+                // Organization webhook deliveries share the delivery log
+                let org_webhook = OrgWebhookRepository::new(self.pool.clone())
+                    .find_by_webhook_id(delivery.webhook_id)
+                    .await?;
+                if let Some(org_webhook) = org_webhook {
+                    return OrgWebhookDeliveryService::new(self.pool.clone(), self.secrets.clone())
+                        .retry_delivery(delivery, &org_webhook)
+                        .await;
+                }
+
                 // Webhook was deleted, mark delivery as failed
                 delivery_repo
-                    .update_attempt(delivery.delivery_id, false, None, Some("Webhook not found"))
+                    .update_attempt(
+                        delivery.delivery_id,
+                        false,
+                        None,
+                        Some("Webhook not found"),
+                        None,
+                        None,
+                        &WebhookRetryPolicy::default(),
+                    )
                     .await?;
                 return Ok(());
             }
@@ -303,7 +662,15 @@ impl WebhookDeliveryService {
         // Check if webhook is still enabled
         if !webhook.enabled {
             delivery_repo
-                .update_attempt(delivery.delivery_id, false, None, Some("Webhook disabled"))
+                .update_attempt(
+                    delivery.delivery_id,
+                    false,
+                    None,
+                    Some("Webhook disabled"),
+                    None,
+                    None,
+                    &webhook.retry_policy,
+                )
                 .await?;
             return Ok(());
         }
@@ -327,21 +694,42 @@ impl WebhookDeliveryService {
             }
         }
 
+        // Keep held deliveries back until the rate limit allows them
+        if let Some(send_at) = self.throttled_until(&webhook, delivery_repo).await? {
+            delivery_repo
+                .postpone_retry(delivery.delivery_id, send_at)
+                .await?;
+            debug!(
+                delivery_id = %delivery.delivery_id,
+                webhook_id = %webhook.webhook_id,
+                send_at = %send_at,
+                "Skipping retry - webhook rate limit reached, postponing delivery"
+            );
+            return Ok(());
+        }
+
         let payload_json = serde_json::to_string(&delivery.payload)?;
-        let signature = self.sign_payload(&payload_json, &webhook.secret)?;
 
         match self
-            .deliver_to_webhook(&webhook.target_url, &payload_json, &signature)
+            .deliver_to_webhook(
+                &webhook,
+                &payload_json,
+                payload_content_type(&delivery.payload),
+            )
             .await
         {
-            Ok(status_code) => {
-                let success = (200..300).contains(&(status_code as i32));
+            Ok(response) => {
+                let success = response.is_success();
+                let status_code = response.status_code;
                 delivery_repo
                     .update_attempt(
                         delivery.delivery_id,
                         success,
                         Some(status_code as i32),
                         None,
+                        Some(response.latency_ms),
+                        Some(&response.body),
+                        &webhook.retry_policy,
                     )
                     .await?;
 
@@ -394,7 +782,15 @@ impl WebhookDeliveryService {
             }
             Err(e) => {
                 delivery_repo
-                    .update_attempt(delivery.delivery_id, false, None, Some(&e.to_string()))
+                    .update_attempt(
+                        delivery.delivery_id,
+                        false,
+                        None,
+                        Some(&e.to_string()),
+                        None,
+                        None,
+                        &webhook.retry_policy,
+                    )
                     .await?;
 
                 warn!(
@@ -413,23 +809,21 @@ impl WebhookDeliveryService {
         Ok(())
     }
 
-    /// Clean up old delivery records.
-    pub async fn cleanup_old_deliveries(
+    /// When the next delivery to a webhook may be sent, if its rate limit is
+    /// exhausted.
+    async fn throttled_until(
         &self,
-        retention_days: i32,
-    ) -> Result<u64, WebhookDeliveryError> {
-        let delivery_repo = WebhookDeliveryRepository::new(self.pool.clone());
-        let deleted = delivery_repo.delete_old_deliveries(retention_days).await?;
-
-        if deleted > 0 {
-            info!(
-                deleted = deleted,
-                retention_days = retention_days,
-                "Cleaned up old webhook deliveries"
-            );
+        webhook: &Webhook,
+        delivery_repo: &WebhookDeliveryRepository,
+    ) -> Result<Option<DateTime<Utc>>, WebhookDeliveryError> {
+        if webhook.max_deliveries_per_minute.is_none() {
+            return Ok(None);
         }
-
-        Ok(deleted)
+        let since = Utc::now() - ChronoDuration::seconds(WEBHOOK_RATE_LIMIT_WINDOW_SECS);
+        let (attempts, oldest) = delivery_repo
+            .recent_attempts(webhook.webhook_id, since)
+            .await?;
+        Ok(webhook.throttled_until(attempts, oldest))
     }
 
     /// Handle a delivery failure by updating the circuit breaker state.
@@ -473,38 +867,35 @@ impl WebhookDeliveryService {
         }
     }
 
-    /// Sign the payload with HMAC-SHA256.
-    fn sign_payload(&self, payload: &str, secret: &str) -> Result<String, WebhookDeliveryError> {
-        type HmacSha256 = Hmac<Sha256>;
-
-        let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
-            .map_err(|e| WebhookDeliveryError::SigningError(e.to_string()))?;
-
-        mac.update(payload.as_bytes());
-        let result = mac.finalize();
-        let signature = hex::encode(result.into_bytes());
-
-        Ok(format!("sha256={}", signature))
-    }
-
-    /// Deliver payload to a single webhook URL.
+    /// Sign and deliver payload to a single webhook's URL.
     async fn deliver_to_webhook(
         &self,
-        url: &str,
+        webhook: &Webhook,
         payload: &str,
-        signature: &str,
-    ) -> Result<u16, WebhookDeliveryError> {
+        content_type: &str,
+    ) -> Result<DeliveryResponse, WebhookDeliveryError> {
+        faults::inject(FaultPoint::Webhooks).await?;
+
+        let secret = self
+            .secrets
+            .reveal(&webhook.secret)
+            .map_err(WebhookDeliveryError::SigningError)?;
+        let signature = sign_webhook_payload(payload, &secret)?;
+        let timestamped_signature =
+            sign_timestamped_payload(payload, &secret, Utc::now().timestamp())?;
+        let started = Instant::now();
         let response = self
             .client
-            .post(url)
-            .header("Content-Type", "application/json")
+            .post(&webhook.target_url)
+            .timeout(Duration::from_secs(webhook.retry_policy.timeout_secs))
+            .header("Content-Type", content_type)
             .header("X-Webhook-Signature", signature)
+            .header(SIGNATURE_HEADER, timestamped_signature)
             .body(payload.to_string())
             .send()
             .await?;
 
-        let status = response.status().as_u16();
-        Ok(status)
+        Ok(DeliveryResponse::read(response, started).await)
     }
 }
 
@@ -529,6 +920,168 @@ mod tests {
         assert_eq!(signature.len(), 64); // SHA256 produces 32 bytes = 64 hex chars
     }
 
+    #[test]
+    fn test_sign_webhook_payload_format() {
+        let signature = sign_webhook_payload("{}", "my-secret-key").unwrap();
+        assert!(signature.starts_with("sha256="));
+        assert_eq!(signature.len(), "sha256=".len() + 64);
+    }
+
+    #[test]
+    fn test_sign_timestamped_payload_format() {
+        let header = sign_timestamped_payload("{}", "my-secret-key", 1_700_000_000).unwrap();
+        let (timestamp, signature) = header.split_once(',').unwrap();
+        assert_eq!(timestamp, "t=1700000000");
+        assert!(signature.starts_with("v1="));
+        assert_eq!(signature.len(), "v1=".len() + 64);
+    }
+
+    #[test]
+    fn test_verify_timestamped_signature() {
+        let now = 1_700_000_000;
+        let header = sign_timestamped_payload("{\"a\":1}", "secret", now).unwrap();
+
+        assert!(verify_timestamped_signature(
+            &header,
+            "{\"a\":1}",
+            "secret",
+            now + 10,
+            SIGNATURE_TOLERANCE_SECS
+        ));
+        // Tampered body, wrong secret
+        assert!(!verify_timestamped_signature(
+            &header,
+            "{\"a\":2}",
+            "secret",
+            now,
+            SIGNATURE_TOLERANCE_SECS
+        ));
+        assert!(!verify_timestamped_signature(
+            &header,
+            "{\"a\":1}",
+            "other",
+            now,
+            SIGNATURE_TOLERANCE_SECS
+        ));
+        // Replayed outside the window
+        assert!(!verify_timestamped_signature(
+            &header,
+            "{\"a\":1}",
+            "secret",
+            now + SIGNATURE_TOLERANCE_SECS + 1,
+            SIGNATURE_TOLERANCE_SECS
+        ));
+        // Timestamp changed without re-signing
+        let forged = header.replacen("t=1700000000", "t=1700000100", 1);
+        assert!(!verify_timestamped_signature(
+            &forged,
+            "{\"a\":1}",
+            "secret",
+            now + 100,
+            SIGNATURE_TOLERANCE_SECS
+        ));
+        assert!(!verify_timestamped_signature(
+            "v1=abc",
+            "{}",
+            "secret",
+            now,
+            SIGNATURE_TOLERANCE_SECS
+        ));
+    }
+
+    #[test]
+    fn test_response_body_excerpt_short_body_unchanged() {
+        assert_eq!(response_body_excerpt("ok"), "ok");
+        assert_eq!(response_body_excerpt(""), "");
+    }
+
+    #[test]
+    fn test_response_body_excerpt_truncates_on_char_boundary() {
+        let body = "é".repeat(RESPONSE_BODY_EXCERPT_CHARS + 10);
+        let excerpt = response_body_excerpt(&body);
+        assert!(excerpt.ends_with("..."));
+        assert_eq!(
+            excerpt.trim_end_matches("...").chars().count(),
+            RESPONSE_BODY_EXCERPT_CHARS
+        );
+    }
+
+    #[test]
+    fn test_sample_payload_for_each_event_type() {
+        let device_id = Uuid::new_v4();
+        for event_type in domain::models::SUPPORTED_WEBHOOK_EVENT_TYPES {
+            let payload = sample_payload(event_type, device_id);
+            assert_eq!(payload.event_type, *event_type);
+            assert_eq!(payload.device_id, device_id);
+            assert!(!payload.geofence_name.is_empty());
+            assert!(payload.timestamp > 0);
+        }
+        assert_eq!(
+            sample_payload("geofence_exit", device_id).geofence_name,
+            "Office"
+        );
+    }
+
+    #[test]
+    fn test_render_template_with_sample_payload() {
+        let payload = sample_payload("geofence_exit", Uuid::nil());
+        let template = serde_json::json!({
+            "state": "{{event_type}}",
+            "gps": ["{{lat}}", "{{lon}}"],
+            "message": "{{device_name}} left {{geofence_name}}"
+        });
+        let rendered = render_payload_template(
+            &template,
+            &payload.template_values(Some("Pixel".to_string())),
+        );
+        assert_eq!(rendered["state"], "geofence_exit");
+        assert_eq!(rendered["gps"][0], 37.7897);
+        assert_eq!(rendered["gps"][1], -122.3972);
+        assert_eq!(rendered["message"], "Pixel left Office");
+    }
+
+    #[test]
+    fn test_webhook_payload_cloudevents_format() {
+        let payload = sample_payload("geofence_enter", Uuid::nil());
+        let mut webhook = Webhook {
+            id: 1,
+            webhook_id: Uuid::new_v4(),
+            owner_device_id: Some(Uuid::nil()),
+            owner_user_id: None,
+            device_ids: None,
+            name: "Knative".to_string(),
+            target_url: "https://example.com/events".to_string(),
+            secret: "test-secret-key".to_string(),
+            enabled: true,
+            consecutive_failures: 0,
+            circuit_open_until: None,
+            retry_policy: WebhookRetryPolicy::default(),
+            payload_template: None,
+            payload_format: WebhookPayloadFormat::Default,
+            max_deliveries_per_minute: None,
+            batch_window_secs: None,
+            batch_max_events: None,
+            auto_disabled_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let plain = webhook_payload(&webhook, &payload, None).unwrap();
+        assert_eq!(plain["event_type"], "geofence_enter");
+        assert_eq!(payload_content_type(&plain), "application/json");
+
+        webhook.payload_format = WebhookPayloadFormat::Cloudevents;
+        webhook.payload_template = Some(serde_json::json!({"place": "{{geofence_name}}"}));
+        let event = webhook_payload(&webhook, &payload, None).unwrap();
+        assert_eq!(event["specversion"], "1.0");
+        assert_eq!(event["type"], "com.phonemanager.geofence_enter");
+        assert_eq!(event["source"], format!("/devices/{}", Uuid::nil()));
+        assert_eq!(event["data"], serde_json::json!({"place": "Home"}));
+        assert_eq!(
+            payload_content_type(&event),
+            domain::models::CLOUD_EVENTS_CONTENT_TYPE
+        );
+    }
+
     #[test]
     fn test_geofence_webhook_payload_serialization() {
         let payload = GeofenceWebhookPayload {
@@ -536,6 +1089,7 @@ mod tests {
             device_id: Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap(),
             geofence_id: Uuid::parse_str("660e8400-e29b-41d4-a716-446655440001").unwrap(),
             geofence_name: "Home".to_string(),
+            resolved_geofence_id: Uuid::parse_str("660e8400-e29b-41d4-a716-446655440001").unwrap(),
             timestamp: 1701878400000,
             location: WebhookLocation {
                 latitude: 37.7749,
@@ -546,6 +1100,7 @@ mod tests {
         let json = serde_json::to_string(&payload).unwrap();
         assert!(json.contains("\"event_type\":\"geofence_enter\""));
         assert!(json.contains("\"geofence_name\":\"Home\""));
+        assert!(json.contains("\"resolved_geofence_id\":\"660e8400-e29b-41d4-a716-446655440001\""));
         assert!(json.contains("\"latitude\":37.7749"));
     }
 
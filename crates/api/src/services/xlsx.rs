@@ -0,0 +1,318 @@
+//! XLSX workbook writer.
+//!
+//! Builds workbooks with `rust_xlsxwriter` in constant memory mode: each
+//! worksheet's rows are flushed to a temporary file as they are written, so
+//! exports of any length stay bounded until the compressed package is
+//! assembled by [`XlsxWriter::finish`].
+//!
+//! Cells are typed: numbers, booleans and timestamps are stored as native
+//! spreadsheet values with a matching number format. Each sheet has a bold,
+//! frozen header row.
+
+use std::io;
+
+use chrono::{DateTime, Utc};
+use rust_xlsxwriter::{Format, Workbook, Worksheet, XlsxError};
+
+use super::report_rendering::ReportCell;
+
+/// MIME type of XLSX workbooks.
+pub const XLSX_CONTENT_TYPE: &str =
+    "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+
+/// Maximum sheet name length allowed by Excel.
+const MAX_SHEET_NAME_LEN: usize = 31;
+
+/// Maximum characters in a cell allowed by Excel.
+const MAX_CELL_CHARS: usize = 32_767;
+
+/// Column width bounds, in characters.
+const MIN_COLUMN_WIDTH: usize = 10;
+const MAX_COLUMN_WIDTH: usize = 60;
+
+/// Cell formats shared by all sheets.
+struct CellFormats {
+    header: Format,
+    integer: Format,
+    number: Format,
+    datetime: Format,
+}
+
+impl Default for CellFormats {
+    fn default() -> Self {
+        Self {
+            header: Format::new().set_bold(),
+            integer: Format::new().set_num_format("0"),
+            number: Format::new().set_num_format("0.00"),
+            datetime: Format::new().set_num_format("yyyy-mm-dd hh:mm:ss"),
+        }
+    }
+}
+
+/// XLSX workbook writer.
+///
+/// Call [`start_sheet`](Self::start_sheet) for each worksheet, followed by
+/// [`write_row`](Self::write_row) for its rows, then [`finish`](Self::finish).
+pub struct XlsxWriter {
+    workbook: Workbook,
+    formats: CellFormats,
+    sheet_names: Vec<String>,
+    /// Next row of the current sheet.
+    next_row: Option<u32>,
+}
+
+impl Default for XlsxWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl XlsxWriter {
+    pub fn new() -> Self {
+        Self {
+            workbook: Workbook::new(),
+            formats: CellFormats::default(),
+            sheet_names: Vec::new(),
+            next_row: None,
+        }
+    }
+
+    /// Start a new worksheet with the given header row. Names are sanitized
+    /// and made unique.
+    pub fn start_sheet(&mut self, name: &str, headers: &[&str]) -> io::Result<()> {
+        let name = unique_sheet_name(name, self.sheet_names.len(), &self.sheet_names);
+        let worksheet = self.workbook.add_worksheet_with_constant_memory();
+        worksheet.set_name(&name).map_err(xlsx_error)?;
+        self.sheet_names.push(name);
+
+        if !headers.is_empty() {
+            worksheet.set_freeze_panes(1, 0).map_err(xlsx_error)?;
+            for (col, header) in headers.iter().enumerate() {
+                let col = column_index(col)?;
+                let width = (header.chars().count() + 2).clamp(MIN_COLUMN_WIDTH, MAX_COLUMN_WIDTH);
+                worksheet
+                    .set_column_width(col, width as f64)
+                    .map_err(xlsx_error)?;
+                worksheet
+                    .write_string_with_format(0, col, *header, &self.formats.header)
+                    .map_err(xlsx_error)?;
+            }
+        }
+        self.next_row = Some(u32::from(!headers.is_empty()));
+        Ok(())
+    }
+
+    /// Append a row to the current sheet.
+    pub fn write_row(&mut self, cells: &[ReportCell]) -> io::Result<()> {
+        let Some(row) = self.next_row else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "write_row called before start_sheet",
+            ));
+        };
+        let index = self.sheet_names.len() - 1;
+        let worksheet = self
+            .workbook
+            .worksheet_from_index(index)
+            .map_err(xlsx_error)?;
+        for (col, cell) in cells.iter().enumerate() {
+            write_cell(worksheet, &self.formats, row, column_index(col)?, cell)
+                .map_err(xlsx_error)?;
+        }
+        self.next_row = Some(row + 1);
+        Ok(())
+    }
+
+    /// Assemble the workbook package.
+    pub fn finish(mut self) -> io::Result<Vec<u8>> {
+        if self.sheet_names.is_empty() {
+            self.start_sheet("Sheet1", &[])?;
+        }
+        self.workbook.save_to_buffer().map_err(xlsx_error)
+    }
+}
+
+fn write_cell(
+    worksheet: &mut Worksheet,
+    formats: &CellFormats,
+    row: u32,
+    col: u16,
+    cell: &ReportCell,
+) -> Result<(), XlsxError> {
+    match cell {
+        ReportCell::Text(text) if text.chars().count() > MAX_CELL_CHARS => {
+            let text: String = text.chars().take(MAX_CELL_CHARS).collect();
+            worksheet.write_string(row, col, text)?;
+        }
+        ReportCell::Text(text) => {
+            worksheet.write_string(row, col, text)?;
+        }
+        ReportCell::Integer(i) => {
+            worksheet.write_number_with_format(row, col, *i as f64, &formats.integer)?;
+        }
+        ReportCell::Number(n) if n.is_finite() => {
+            worksheet.write_number_with_format(row, col, *n, &formats.number)?;
+        }
+        ReportCell::Boolean(b) => {
+            worksheet.write_boolean(row, col, *b)?;
+        }
+        ReportCell::DateTime(ts) => {
+            worksheet.write_number_with_format(
+                row,
+                col,
+                excel_serial_date(ts),
+                &formats.datetime,
+            )?;
+        }
+        ReportCell::Number(_) | ReportCell::Empty => {}
+    }
+    Ok(())
+}
+
+fn xlsx_error(e: XlsxError) -> io::Error {
+    io::Error::other(e)
+}
+
+fn column_index(col: usize) -> io::Result<u16> {
+    u16::try_from(col).map_err(|_| io::Error::other("Too many XLSX columns"))
+}
+
+/// Spreadsheet serial date: days since 1899-12-30, with fractional time.
+fn excel_serial_date(ts: &DateTime<Utc>) -> f64 {
+    const UNIX_EPOCH_SERIAL: f64 = 25569.0;
+    UNIX_EPOCH_SERIAL + ts.timestamp_millis() as f64 / 86_400_000.0
+}
+
+/// Excel-safe sheet name derived from a title, unique among `existing`.
+fn unique_sheet_name(title: &str, index: usize, existing: &[String]) -> String {
+    let mut name: String = title
+        .chars()
+        .map(|c| match c {
+            '[' | ']' | ':' | '*' | '?' | '/' | '\\' => '_',
+            c => c,
+        })
+        .take(MAX_SHEET_NAME_LEN)
+        .collect();
+    if name.is_empty() || existing.contains(&name) {
+        let suffix = format!("_{}", index + 1);
+        name = name
+            .chars()
+            .take(MAX_SHEET_NAME_LEN - suffix.len())
+            .collect::<String>()
+            + &suffix;
+    }
+    name
+}
+
+/// Escape text for XML element content and attribute values.
+pub fn xml_escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            // Control characters are not allowed in XML 1.0
+            c if (c as u32) < 0x20 && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Read a part of an XLSX package, for tests.
+#[cfg(test)]
+pub fn read_part(bytes: &[u8], name: &str) -> String {
+    use std::io::Read;
+
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes)).unwrap();
+    let mut part = String::new();
+    archive
+        .by_name(name)
+        .unwrap()
+        .read_to_string(&mut part)
+        .unwrap();
+    part
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_unique_sheet_name() {
+        let mut names = Vec::new();
+        for (i, title) in ["a/b", "a/b", &"x".repeat(40), ""].iter().enumerate() {
+            let name = unique_sheet_name(title, i, &names);
+            names.push(name);
+        }
+        assert_eq!(names[0], "a_b");
+        assert_eq!(names[1], "a_b_2");
+        assert_eq!(names[2].len(), MAX_SHEET_NAME_LEN);
+        assert_eq!(names[3], "_4");
+    }
+
+    #[test]
+    fn test_xml_escape() {
+        assert_eq!(xml_escape("a<b & \"c\""), "a&lt;b &amp; &quot;c&quot;");
+        assert_eq!(xml_escape("bell\u{7}"), "bell");
+    }
+
+    #[test]
+    fn test_excel_serial_date() {
+        let ts = Utc.with_ymd_and_hms(1970, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(excel_serial_date(&ts), 25569.0);
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+        assert_eq!(excel_serial_date(&ts), 45292.5);
+    }
+
+    #[test]
+    fn test_workbook_with_multiple_sheets() {
+        let ts = Utc.with_ymd_and_hms(1970, 1, 2, 0, 0, 0).unwrap();
+        let mut writer = XlsxWriter::new();
+        writer.start_sheet("Devices", &["name", "count"]).unwrap();
+        writer
+            .write_row(&[
+                ReportCell::Text("Pixel".into()),
+                ReportCell::Integer(3),
+                ReportCell::Number(1.5),
+                ReportCell::Boolean(true),
+                ReportCell::DateTime(ts),
+                ReportCell::Empty,
+            ])
+            .unwrap();
+        writer.start_sheet("Devices", &["total"]).unwrap();
+        writer.write_row(&[ReportCell::Integer(3)]).unwrap();
+        let bytes = writer.finish().unwrap();
+
+        let workbook = read_part(&bytes, "xl/workbook.xml");
+        assert!(workbook.contains(r#"<sheet name="Devices" sheetId="1""#));
+        assert!(workbook.contains(r#"<sheet name="Devices_2" sheetId="2""#));
+
+        let sheet = read_part(&bytes, "xl/worksheets/sheet1.xml");
+        assert!(sheet.contains(r#"state="frozen""#));
+        assert!(sheet.contains("<t>name</t>"));
+        assert!(sheet.contains("<t>Pixel</t>"));
+        assert!(sheet.contains("<v>3</v>"));
+        assert!(sheet.contains("<v>1.5</v>"));
+        assert!(sheet.contains(r#"t="b"><v>1</v>"#));
+        assert!(sheet.contains("<v>25570</v>"));
+        assert!(!sheet.contains(r#"r="F2""#));
+    }
+
+    #[test]
+    fn test_empty_workbook_has_a_sheet() {
+        let bytes = XlsxWriter::new().finish().unwrap();
+        let workbook = read_part(&bytes, "xl/workbook.xml");
+        assert!(workbook.contains(r#"<sheet name="Sheet1""#));
+    }
+
+    #[test]
+    fn test_write_row_requires_sheet() {
+        let mut writer = XlsxWriter::new();
+        assert!(writer.write_row(&[ReportCell::Empty]).is_err());
+    }
+}
@@ -8,7 +8,10 @@
 #![allow(dead_code)]
 
 use axum::Router;
-use phone_manager_api::{app::create_app, config::Config};
+use phone_manager_api::{
+    app::create_app, config::Config, preflight::PreflightReport,
+    services::webhook_secret::WebhookSecrets,
+};
 use sqlx::{postgres::PgPoolOptions, PgPool};
 use std::time::Duration;
 
@@ -105,11 +108,13 @@ YQIDAQAB
 -----END PUBLIC KEY-----"#;
 
     Config {
+        profile: phone_manager_api::config::RuntimeProfile::Standard,
         server: phone_manager_api::config::ServerConfig {
             host: "127.0.0.1".to_string(),
             port: 0, // Use random port
             request_timeout_secs: 30,
             max_body_size: 1048576,
+            max_batch_body_size: 10485760,
             app_base_url: "http://localhost:3000".to_string(),
         },
         database: phone_manager_api::config::DatabaseConfig {
@@ -121,10 +126,14 @@ YQIDAQAB
             min_connections: 1,
             connect_timeout_secs: 10,
             idle_timeout_secs: 600,
+            timescale_enabled: false,
+            statement_timeout_ms: 5_000,
+            export_statement_timeout_ms: 30_000,
         },
         logging: phone_manager_api::config::LoggingConfig {
             level: "debug".to_string(),
             format: "pretty".to_string(),
+            store_capacity: 0,
         },
         security: phone_manager_api::config::SecurityConfig {
             cors_origins: vec![],
@@ -132,6 +141,12 @@ YQIDAQAB
             export_rate_limit_per_hour: 0, // Disable export rate limiting for tests
             forgot_password_rate_limit_per_hour: 0, // Disable auth rate limiting for tests
             request_verification_rate_limit_per_hour: 0, // Disable auth rate limiting for tests
+            password_hash_version: 1,
+            password_hash_memory_kib: 19456,
+            password_hash_iterations: 2,
+            password_hash_parallelism: 1,
+            webhook_client_cert_key: String::new(),
+            webhook_secret_key: String::new(),
         },
         limits: phone_manager_api::config::LimitsConfig {
             max_devices_per_group: 20,
@@ -141,12 +156,22 @@ YQIDAQAB
             max_group_id_length: 50,
             max_webhooks_per_device: Some(10),
             warning_threshold_percent: 80,
+            max_geofences_per_user: 50,
+            group_event_retention_days: 30,
+            group_message_retention_days: 90,
+            max_messages_per_group: 1000,
+            api_usage_retention_months: 13,
+            max_group_location_retention_days: 365,
+            webhook_auto_disable_failures: 50,
         },
         map_matching: phone_manager_api::config::MapMatchingConfig {
             provider: "osrm".to_string(),
             url: "".to_string(),
+            api_key: "".to_string(),
             timeout_ms: 30000,
             rate_limit_per_minute: 30,
+            mapbox_rate_limit_per_minute: 300,
+            mapbox_cost_per_1000_requests: 2.0,
             circuit_breaker_failures: 5,
             circuit_breaker_reset_secs: 60,
             enabled: false,
@@ -199,6 +224,7 @@ YQIDAQAB
             movement_tracking_enabled: true,
             b2b_enabled: true,
             geofence_events_enabled: true,
+            self_service_orgs_enabled: false,
         },
         admin: phone_manager_api::config::AdminBootstrapConfig {
             bootstrap_email: String::new(),
@@ -209,6 +235,11 @@ YQIDAQAB
             batch_size: 5,
             expiration_days: 7,
         },
+        location_imports: phone_manager_api::config::LocationImportsConfig {
+            imports_dir: "./test_imports".to_string(),
+            max_upload_size: 10 * 1024 * 1024,
+            batch_size: 2,
+        },
         cookies: phone_manager_api::config::CookieConfig {
             enabled: false,
             secure: false, // Allow non-HTTPS in tests
@@ -218,13 +249,26 @@ YQIDAQAB
             refresh_token_path: "/api/v1/auth".to_string(),
             access_token_name: "access_token".to_string(),
             refresh_token_name: "refresh_token".to_string(),
+            csrf_enabled: true,
+            csrf_cookie_name: "csrf_token".to_string(),
+            csrf_header_name: "X-CSRF-Token".to_string(),
         },
+        metrics_snapshots: phone_manager_api::config::MetricsSnapshotsConfig::default(),
+        lite: phone_manager_api::config::LiteConfig::default(),
+        concurrency: phone_manager_api::config::ConcurrencyConfig::default(),
     }
 }
 
 /// Create a test application router.
 pub fn create_test_app(config: Config, pool: PgPool) -> Router {
-    create_app(config, pool)
+    create_app(
+        config,
+        pool,
+        PreflightReport::default(),
+        WebhookSecrets::default(),
+        None,
+        None,
+    )
 }
 
 /// Generate a unique email for testing.
@@ -263,7 +307,7 @@ pub async fn cleanup_all_test_data(pool: &PgPool) {
     // Truncate all tables in reverse dependency order
     let tables = [
         // Audit and export
-        "audit_export_jobs",
+        "export_jobs",
         "audit_logs",
         // Fleet and bulk operations
         "bulk_import_jobs",
@@ -304,6 +348,7 @@ pub async fn cleanup_all_test_data(pool: &PgPool) {
         "webhooks",
         "geofences",
         "locations",
+        "device_telemetry",
         // Core
         "idempotency_keys",
         "api_keys",
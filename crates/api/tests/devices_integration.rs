@@ -478,3 +478,78 @@ async fn test_delete_device_invalid_uuid() {
 
     cleanup_all_test_data(&pool).await;
 }
+
+// ============================================================================
+// Push Token Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_push_token_register_rotate_and_delete() {
+    let pool = create_test_pool().await;
+    run_migrations(&pool).await;
+    cleanup_all_test_data(&pool).await;
+
+    let config = test_config();
+    let app = create_test_app(config.clone(), pool.clone());
+
+    let user = TestUser::new();
+    let auth = create_authenticated_user(&app, &user).await;
+    let device = TestDevice::new();
+    register_test_device(&app, &pool, &auth, &device).await;
+
+    let api_key = create_test_api_key(&pool, "test_push_tokens").await;
+    let path = format!("/api/v1/devices/{}/push-tokens", device.device_id);
+
+    // Two tokens, e.g. two app builds on the same device
+    for (token, platform) in [("token-build-a", "android"), ("token-build-b", "android")] {
+        let app = create_test_app(config.clone(), pool.clone());
+        let request = json_request_with_api_key_and_jwt(
+            Method::POST,
+            &path,
+            json!({ "token": token, "platform": platform, "app_version": "1.0.0" }),
+            &api_key,
+            &auth.access_token,
+        );
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    // Rotate the first token
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_api_key_and_jwt(
+        Method::POST,
+        &path,
+        json!({
+            "token": "token-build-a-rotated",
+            "platform": "android",
+            "app_version": "1.1.0",
+            "replaces_token": "token-build-a"
+        }),
+        &api_key,
+        &auth.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let rotated = parse_response_body(response).await;
+    assert_eq!(rotated["token_hint"], "...otated");
+    assert!(rotated.get("token").is_none());
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = get_request_with_api_key_and_jwt(&path, &api_key, &auth.access_token);
+    let body = parse_response_body(app.oneshot(request).await.unwrap()).await;
+    let tokens = body["data"].as_array().unwrap();
+    assert_eq!(tokens.len(), 2);
+    assert_eq!(tokens[0]["app_version"], "1.1.0");
+
+    // Remove the rotated token
+    let app = create_test_app(config, pool.clone());
+    let request = delete_request_with_api_key_and_jwt(
+        &format!("{}/{}", path, rotated["id"].as_str().unwrap()),
+        &api_key,
+        &auth.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    cleanup_all_test_data(&pool).await;
+}
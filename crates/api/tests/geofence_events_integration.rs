@@ -383,6 +383,166 @@ async fn test_create_geofence_event_geofence_not_found() {
     cleanup_all_test_data(&pool).await;
 }
 
+#[tokio::test]
+async fn test_create_geofence_event_outside_schedule_suppressed() {
+    let pool = create_test_pool().await;
+    run_migrations(&pool).await;
+    cleanup_all_test_data(&pool).await;
+
+    let config = test_config();
+    let app = create_test_app(config.clone(), pool.clone());
+
+    let user = TestUser::new();
+    let auth = create_authenticated_user(&app, &user).await;
+    let api_key = create_test_api_key(&pool, "test_scheduled_event").await;
+    let device = TestDevice::new();
+    let app = create_test_app(config.clone(), pool.clone());
+    let device_response = register_test_device(&app, &pool, &auth, &device).await;
+    let device_id = device_response["device_id"].as_str().unwrap();
+
+    // School fence active on weekdays 08:00-15:00 UTC
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_api_key_and_jwt(
+        Method::POST,
+        "/api/v1/geofences",
+        json!({
+            "device_id": device_id,
+            "name": "School",
+            "latitude": 37.7749,
+            "longitude": -122.4194,
+            "radius_meters": 100.0,
+            "schedule": {
+                "windows": [{
+                    "days": ["mon", "tue", "wed", "thu", "fri"],
+                    "start": "08:00",
+                    "end": "15:00"
+                }]
+            }
+        }),
+        &api_key,
+        &auth.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = parse_response_body(response).await;
+    let geofence_id = body["geofence_id"].as_str().unwrap().to_string();
+
+    // 2024-01-06 is a Saturday
+    let saturday = chrono::DateTime::parse_from_rfc3339("2024-01-06T09:00:00Z").unwrap();
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_api_key_and_jwt(
+        Method::POST,
+        "/api/v1/geofence-events",
+        json!({
+            "device_id": device_id,
+            "geofence_id": geofence_id,
+            "event_type": "enter",
+            "timestamp": saturday.timestamp_millis().to_string(),
+            "latitude": 37.7749,
+            "longitude": -122.4194
+        }),
+        &api_key,
+        &auth.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    // 2024-01-08 is a Monday
+    let monday = chrono::DateTime::parse_from_rfc3339("2024-01-08T09:00:00Z").unwrap();
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_api_key_and_jwt(
+        Method::POST,
+        "/api/v1/geofence-events",
+        json!({
+            "device_id": device_id,
+            "geofence_id": geofence_id,
+            "event_type": "enter",
+            "timestamp": monday.timestamp_millis().to_string(),
+            "latitude": 37.7749,
+            "longitude": -122.4194
+        }),
+        &api_key,
+        &auth.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    // Only the event inside the schedule was stored
+    let app = create_test_app(config, pool.clone());
+    let request = get_request_with_api_key_and_jwt(
+        &format!("/api/v1/geofence-events?device_id={}", device_id),
+        &api_key,
+        &auth.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = parse_response_body(response).await;
+    assert_eq!(body["events"].as_array().unwrap().len(), 1);
+
+    cleanup_all_test_data(&pool).await;
+}
+
+#[tokio::test]
+async fn test_create_geofence_event_matching_server_event_deduplicated() {
+    let pool = create_test_pool().await;
+    run_migrations(&pool).await;
+    cleanup_all_test_data(&pool).await;
+
+    let config = test_config();
+    let app = create_test_app(config.clone(), pool.clone());
+
+    let user = TestUser::new();
+    let auth = create_authenticated_user(&app, &user).await;
+    let api_key = create_test_api_key(&pool, "test_server_dedup").await;
+    let device = TestDevice::new();
+    let app = create_test_app(config.clone(), pool.clone());
+    let device_response = register_test_device(&app, &pool, &auth, &device).await;
+    let device_id = device_response["device_id"].as_str().unwrap();
+    let geofence_id =
+        create_test_geofence(&pool, &config, &api_key, &auth, device_id, "Home").await;
+
+    // The server derived the enter from the location stream first
+    let timestamp = chrono::Utc::now().timestamp_millis();
+    let derived = persistence::repositories::GeofenceEventRepository::new(pool.clone())
+        .create(
+            device_id.parse().unwrap(),
+            geofence_id.parse().unwrap(),
+            "Home",
+            "enter",
+            timestamp,
+            37.7749,
+            -122.4194,
+            "server",
+            geofence_id.parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    // The client reports the same crossing a minute later
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_api_key_and_jwt(
+        Method::POST,
+        "/api/v1/geofence-events",
+        json!({
+            "device_id": device_id,
+            "geofence_id": geofence_id,
+            "event_type": "enter",
+            "timestamp": (timestamp + 60_000).to_string(),
+            "latitude": 37.7749,
+            "longitude": -122.4194
+        }),
+        &api_key,
+        &auth.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = parse_response_body(response).await;
+    assert_eq!(body["event_id"], derived.event_id.to_string());
+    assert_eq!(body["source"], "server");
+
+    cleanup_all_test_data(&pool).await;
+}
+
 // ============================================================================
 // Geofence Event Listing Tests (AC 15.2.3)
 // ============================================================================
@@ -9,9 +9,12 @@ mod common;
 
 use axum::http::{Method, StatusCode};
 use common::{
-    cleanup_all_test_data, create_authenticated_user, create_test_app, create_test_group,
-    create_test_pool, delete_request_with_auth, get_request_with_auth, json_request_with_auth,
-    parse_response_body, run_migrations, test_config, TestGroup, TestUser,
+    add_user_to_organization, cleanup_all_test_data, create_authenticated_user,
+    create_test_admin_api_key, create_test_app, create_test_group, create_test_organization,
+    create_test_pool, delete_admin_request_with_jwt, delete_request_with_auth,
+    get_request_with_auth, json_request_with_auth, parse_response_body,
+    post_admin_request_with_jwt, run_migrations, test_config, TestGroup, TestOrganization,
+    TestUser,
 };
 use serde_json::json;
 use tower::ServiceExt;
@@ -773,7 +776,8 @@ async fn test_add_device_to_group_not_member() {
     let non_member_auth = create_authenticated_user(&app, &non_member).await;
     let device = common::TestDevice::new();
     let app = create_test_app(config.clone(), pool.clone());
-    let _device_response = common::register_test_device(&app, &pool, &non_member_auth, &device).await;
+    let _device_response =
+        common::register_test_device(&app, &pool, &non_member_auth, &device).await;
 
     // Non-member tries to add their device to the group
     // Returns 404 to avoid leaking group existence to non-members
@@ -1271,3 +1275,1039 @@ async fn test_has_current_device_with_device_id_filter() {
 
     cleanup_all_test_data(&pool).await;
 }
+
+// ============================================================================
+// Temporary Location Sharing Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_sharing_window_lifecycle() {
+    let pool = create_test_pool().await;
+    run_migrations(&pool).await;
+    cleanup_all_test_data(&pool).await;
+
+    let config = test_config();
+    let app = create_test_app(config.clone(), pool.clone());
+
+    let user = TestUser::new();
+    let auth = create_authenticated_user(&app, &user).await;
+    let created = create_test_group(&app, &auth, &TestGroup::new()).await;
+    let uri = format!("/api/v1/groups/{}/sharing-window", created.id);
+
+    // Groups share continuously by default
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(Method::POST, &uri, json!({}), &auth.access_token);
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::PUT,
+        &format!("/api/v1/groups/{}/settings", created.id),
+        json!({ "default_sharing_mode": "on_demand" }),
+        &auth.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Start a 2 hour window
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::POST,
+        &uri,
+        json!({ "duration_minutes": 120 }),
+        &auth.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = parse_response_body(response).await;
+    assert_eq!(body["is_active"], true);
+    let remaining = body["remaining_seconds"].as_i64().unwrap();
+    assert!(remaining > 7000 && remaining <= 7200);
+
+    // Extend by an hour
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::PATCH,
+        &uri,
+        json!({ "extend_minutes": 60 }),
+        &auth.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = parse_response_body(response).await;
+    assert!(body["remaining_seconds"].as_i64().unwrap() > 10000);
+
+    // Stop early
+    let app = create_test_app(config.clone(), pool.clone());
+    let response = app
+        .oneshot(delete_request_with_auth(&uri, &auth.access_token))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = parse_response_body(response).await;
+    assert_eq!(body["is_active"], false);
+    assert!(body["stopped_at"].is_string());
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let response = app
+        .oneshot(delete_request_with_auth(&uri, &auth.access_token))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    cleanup_all_test_data(&pool).await;
+}
+
+#[tokio::test]
+async fn test_sharing_schedule_pauses_locations() {
+    let pool = create_test_pool().await;
+    run_migrations(&pool).await;
+    cleanup_all_test_data(&pool).await;
+
+    let config = test_config();
+    let app = create_test_app(config.clone(), pool.clone());
+    let owner = create_authenticated_user(&app, &TestUser::new()).await;
+    let member = create_authenticated_user(&app, &TestUser::new()).await;
+    let created = create_test_group(&app, &owner, &TestGroup::new()).await;
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::POST,
+        "/api/v1/groups/join",
+        json!({ "code": created.invite_code }),
+        &member.access_token,
+    );
+    app.oneshot(request).await.unwrap();
+
+    // The owner's device has a location in the group
+    let device = common::TestDevice::new();
+    let app = create_test_app(config.clone(), pool.clone());
+    common::register_test_device(&app, &pool, &owner, &device).await;
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::POST,
+        &format!("/api/v1/groups/{}/devices", created.id),
+        json!({ "device_id": device.device_id }),
+        &owner.access_token,
+    );
+    app.oneshot(request).await.unwrap();
+    common::insert_device_location(&pool, &device.device_id, 48.8566, 2.3522, 10.0).await;
+
+    let uri = format!("/api/v1/groups/{}/sharing-schedule", created.id);
+    let devices_uri = format!(
+        "/api/v1/groups/{}/devices/members?include_location=true",
+        created.id
+    );
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let response = app
+        .oneshot(get_request_with_auth(&uri, &owner.access_token))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // Invalid schedules are rejected
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::PUT,
+        &uri,
+        json!({ "windows": [{ "days": [], "start": "09:00", "end": "17:00" }] }),
+        &owner.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // A schedule without windows never shares
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::PUT,
+        &uri,
+        json!({ "windows": [] }),
+        &owner.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = parse_response_body(response).await;
+    assert_eq!(body["is_sharing"], false);
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let response = app
+        .oneshot(get_request_with_auth(&devices_uri, &member.access_token))
+        .await
+        .unwrap();
+    let body = parse_response_body(response).await;
+    let devices = body["data"].as_array().unwrap();
+    assert_eq!(devices[0]["sharing_paused"], true);
+    assert!(devices[0].get("last_location").is_none());
+
+    // The owner still sees their own location
+    let app = create_test_app(config.clone(), pool.clone());
+    let response = app
+        .oneshot(get_request_with_auth(&devices_uri, &owner.access_token))
+        .await
+        .unwrap();
+    let body = parse_response_body(response).await;
+    assert!(body["data"][0]["last_location"].is_object());
+    assert!(body["data"][0].get("sharing_paused").is_none());
+
+    // Removing the schedule resumes sharing
+    let app = create_test_app(config.clone(), pool.clone());
+    let response = app
+        .oneshot(delete_request_with_auth(&uri, &owner.access_token))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let response = app
+        .oneshot(get_request_with_auth(&devices_uri, &member.access_token))
+        .await
+        .unwrap();
+    let body = parse_response_body(response).await;
+    assert!(body["data"][0]["last_location"].is_object());
+
+    cleanup_all_test_data(&pool).await;
+}
+
+#[tokio::test]
+async fn test_group_nicknames() {
+    let pool = create_test_pool().await;
+    run_migrations(&pool).await;
+    cleanup_all_test_data(&pool).await;
+
+    let config = test_config();
+    let app = create_test_app(config.clone(), pool.clone());
+    let owner = create_authenticated_user(&app, &TestUser::new()).await;
+    let member = create_authenticated_user(&app, &TestUser::new()).await;
+    let created = create_test_group(&app, &owner, &TestGroup::new()).await;
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::POST,
+        "/api/v1/groups/join",
+        json!({ "code": created.invite_code }),
+        &member.access_token,
+    );
+    app.oneshot(request).await.unwrap();
+
+    let device = common::TestDevice::new();
+    let app = create_test_app(config.clone(), pool.clone());
+    common::register_test_device(&app, &pool, &member, &device).await;
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::POST,
+        &format!("/api/v1/groups/{}/devices", created.id),
+        json!({ "device_id": device.device_id }),
+        &member.access_token,
+    );
+    app.oneshot(request).await.unwrap();
+
+    let member_uri = format!(
+        "/api/v1/groups/{}/members/{}/nickname",
+        created.id, member.user_id
+    );
+    let device_uri = format!(
+        "/api/v1/groups/{}/devices/{}/nickname",
+        created.id, device.device_id
+    );
+
+    // Plain members cannot set nicknames
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::PUT,
+        &member_uri,
+        json!({ "nickname": "Me" }),
+        &member.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // Blank nicknames are rejected
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::PUT,
+        &member_uri,
+        json!({ "nickname": "  " }),
+        &owner.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::PUT,
+        &member_uri,
+        json!({ "nickname": "Mom" }),
+        &owner.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = parse_response_body(response).await;
+    assert_eq!(body["nickname"], "Mom");
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::PUT,
+        &device_uri,
+        json!({ "nickname": "Mom's phone" }),
+        &owner.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Nicknames show up in member and device listings
+    let app = create_test_app(config.clone(), pool.clone());
+    let response = app
+        .oneshot(get_request_with_auth(
+            &format!("/api/v1/groups/{}/members/{}", created.id, member.user_id),
+            &owner.access_token,
+        ))
+        .await
+        .unwrap();
+    let body = parse_response_body(response).await;
+    assert_eq!(body["nickname"], "Mom");
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let response = app
+        .oneshot(get_request_with_auth(
+            &format!("/api/v1/groups/{}/devices/members", created.id),
+            &owner.access_token,
+        ))
+        .await
+        .unwrap();
+    let body = parse_response_body(response).await;
+    assert_eq!(body["data"][0]["nickname"], "Mom's phone");
+
+    // Clearing a nickname removes it from listings
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::PUT,
+        &device_uri,
+        json!({ "nickname": null }),
+        &owner.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let response = app
+        .oneshot(get_request_with_auth(
+            &format!("/api/v1/groups/{}/devices/members", created.id),
+            &owner.access_token,
+        ))
+        .await
+        .unwrap();
+    let body = parse_response_body(response).await;
+    assert!(body["data"][0].get("nickname").is_none());
+
+    // Unknown members are not found
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::PUT,
+        &format!(
+            "/api/v1/groups/{}/members/{}/nickname",
+            created.id,
+            uuid::Uuid::new_v4()
+        ),
+        json!({ "nickname": "Nobody" }),
+        &owner.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    cleanup_all_test_data(&pool).await;
+}
+
+// ============================================================================
+// Group Message Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_post_and_list_group_messages() {
+    let pool = create_test_pool().await;
+    run_migrations(&pool).await;
+    cleanup_all_test_data(&pool).await;
+
+    let config = test_config();
+    let app = create_test_app(config.clone(), pool.clone());
+
+    let user = TestUser::new();
+    let auth = create_authenticated_user(&app, &user).await;
+    let created = create_test_group(&app, &auth, &TestGroup::new()).await;
+    let uri = format!("/api/v1/groups/{}/messages", created.id);
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::POST,
+        &uri,
+        json!({ "kind": "announcement", "body": "Dinner at 7" }),
+        &auth.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::POST,
+        &uri,
+        json!({
+            "kind": "check_in",
+            "latitude": 48.1486,
+            "longitude": 17.1077,
+            "place_name": "School"
+        }),
+        &auth.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = parse_response_body(response).await;
+    assert_eq!(body["kind"], "check_in");
+    assert_eq!(body["location"]["place_name"], "School");
+
+    // Check-ins need a location
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::POST,
+        &uri,
+        json!({ "kind": "check_in", "body": "Arrived" }),
+        &auth.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // Newest first, one per page
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = get_request_with_auth(&format!("{}?limit=1", uri), &auth.access_token);
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = parse_response_body(response).await;
+    assert_eq!(body["messages"][0]["kind"], "check_in");
+    assert_eq!(body["pagination"]["has_more"], true);
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = get_request_with_auth(
+        &format!(
+            "{}?limit=1&cursor={}",
+            uri,
+            body["pagination"]["next_cursor"].as_str().unwrap()
+        ),
+        &auth.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    let body = parse_response_body(response).await;
+    assert_eq!(body["messages"][0]["body"], "Dinner at 7");
+    assert_eq!(body["pagination"]["has_more"], false);
+
+    cleanup_all_test_data(&pool).await;
+}
+
+// ============================================================================
+// Nested Group Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_nested_groups() {
+    let pool = create_test_pool().await;
+    run_migrations(&pool).await;
+    cleanup_all_test_data(&pool).await;
+
+    let config = test_config();
+    let app = create_test_app(config.clone(), pool.clone());
+
+    // The parent owner and the owner of the team that joins the parent
+    let org_owner = create_authenticated_user(&app, &TestUser::new()).await;
+    let team_owner = create_authenticated_user(&app, &TestUser::new()).await;
+    let org = create_test_group(&app, &org_owner, &TestGroup::new()).await;
+    let app = create_test_app(config.clone(), pool.clone());
+    let team = create_test_group(&app, &team_owner, &TestGroup::new()).await;
+
+    // Only owners and admins of the parent can add sub-groups
+    let parent_uri = format!("/api/v1/groups/{}/parent", team.id);
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::PUT,
+        &parent_uri,
+        json!({ "parent_group_id": org.id }),
+        &team_owner.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::POST,
+        "/api/v1/groups/join",
+        json!({ "code": org.invite_code }),
+        &team_owner.access_token,
+    );
+    app.oneshot(request).await.unwrap();
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::PUT,
+        &format!(
+            "/api/v1/groups/{}/members/{}/role",
+            org.id, team_owner.user_id
+        ),
+        json!({ "role": "admin" }),
+        &org_owner.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::PUT,
+        &parent_uri,
+        json!({ "parent_group_id": org.id }),
+        &team_owner.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = parse_response_body(response).await;
+    assert_eq!(body["parent_group_id"], org.id);
+
+    // The parent owner administers the sub-group without joining it
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = get_request_with_auth(
+        &format!("/api/v1/groups/{}", team.id),
+        &org_owner.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = parse_response_body(response).await;
+    assert_eq!(body["your_role"], "admin");
+    assert_eq!(body["parent_group_id"], org.id);
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = get_request_with_auth(
+        &format!("/api/v1/groups/{}/subgroups", org.id),
+        &org_owner.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    let body = parse_response_body(response).await;
+    assert_eq!(body["count"], 1);
+    assert_eq!(body["data"][0]["id"], team.id);
+
+    // Nesting the parent inside its sub-group would create a cycle
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::PUT,
+        &format!("/api/v1/groups/{}/parent", org.id),
+        json!({ "parent_group_id": team.id }),
+        &org_owner.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    // Detaching makes the team top-level again
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::PUT,
+        &parent_uri,
+        json!({ "parent_group_id": null }),
+        &team_owner.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = parse_response_body(response).await;
+    assert!(body["parent_group_id"].is_null());
+
+    cleanup_all_test_data(&pool).await;
+}
+
+// ============================================================================
+// Group Data Export Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_group_export_requires_owner() {
+    let pool = create_test_pool().await;
+    run_migrations(&pool).await;
+    cleanup_all_test_data(&pool).await;
+
+    let config = test_config();
+    let app = create_test_app(config.clone(), pool.clone());
+
+    let owner = create_authenticated_user(&app, &TestUser::new()).await;
+    let member = create_authenticated_user(&app, &TestUser::new()).await;
+    let app = create_test_app(config.clone(), pool.clone());
+    let group = create_test_group(&app, &owner, &TestGroup::new()).await;
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::POST,
+        "/api/v1/groups/join",
+        json!({ "code": group.invite_code }),
+        &member.access_token,
+    );
+    app.oneshot(request).await.unwrap();
+
+    let export_uri = format!("/api/v1/groups/{}/export", group.id);
+
+    // Members cannot export the group's data
+    let app = create_test_app(config.clone(), pool.clone());
+    let request =
+        json_request_with_auth(Method::POST, &export_uri, json!({}), &member.access_token);
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // The owner starts an export job
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(Method::POST, &export_uri, json!({}), &owner.access_token);
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let body = parse_response_body(response).await;
+    let job_id = body["job_id"].as_str().unwrap().to_string();
+    assert!(job_id.starts_with("group_export_"));
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = get_request_with_auth(&format!("{}/{}", export_uri, job_id), &owner.access_token);
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = parse_response_body(response).await;
+    assert_eq!(body["job_id"], job_id);
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let request =
+        get_request_with_auth(&format!("{}/{}", export_uri, job_id), &member.access_token);
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    cleanup_all_test_data(&pool).await;
+}
+
+// ============================================================================
+// Custom Group Role Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_custom_group_role_grants_permissions() {
+    let pool = create_test_pool().await;
+    run_migrations(&pool).await;
+    cleanup_all_test_data(&pool).await;
+
+    let config = test_config();
+    let admin_api_key = create_test_admin_api_key(&pool, "test-admin-key").await;
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let org = create_test_organization(&app, &admin_api_key, &TestOrganization::new()).await;
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let owner = create_authenticated_user(&app, &TestUser::new()).await;
+    let member = create_authenticated_user(&app, &TestUser::new()).await;
+    add_user_to_organization(&pool, &owner.user_id, &org.id, "admin").await;
+    add_user_to_organization(&pool, &member.user_id, &org.id, "member").await;
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let group = create_test_group(&app, &owner, &TestGroup::new()).await;
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::POST,
+        "/api/v1/groups/join",
+        json!({ "code": group.invite_code }),
+        &member.access_token,
+    );
+    app.oneshot(request).await.unwrap();
+
+    let invites_uri = format!("/api/v1/groups/{}/invites", group.id);
+
+    // Plain members cannot invite
+    let app = create_test_app(config.clone(), pool.clone());
+    let request =
+        json_request_with_auth(Method::POST, &invites_uri, json!({}), &member.access_token);
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // The org admin defines a role that can invite but not view locations
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = post_admin_request_with_jwt(
+        &format!("/api/admin/v1/organizations/{}/group-roles", org.id),
+        json!({ "name": "Recruiter", "permissions": ["invite_members"] }),
+        &admin_api_key,
+        &owner.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = parse_response_body(response).await;
+    let role_id = body["id"].as_str().unwrap().to_string();
+    assert_eq!(body["permissions"], json!(["invite_members"]));
+
+    // The group owner assigns it to the member
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::PUT,
+        &format!(
+            "/api/v1/groups/{}/members/{}/custom-role",
+            group.id, member.user_id
+        ),
+        json!({ "custom_role_id": role_id }),
+        &owner.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = parse_response_body(response).await;
+    assert_eq!(body["custom_role_id"], role_id);
+    assert_eq!(body["permissions"], json!(["invite_members"]));
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let request =
+        json_request_with_auth(Method::POST, &invites_uri, json!({}), &member.access_token);
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    // Inviting admins still requires a built-in admin role
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::POST,
+        &invites_uri,
+        json!({ "preset_role": "admin" }),
+        &member.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // The role replaces the member's view_locations permission
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = get_request_with_auth(
+        &format!("/api/v1/groups/{}/devices", group.id),
+        &member.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // Deleting the role restores the built-in permissions
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = delete_admin_request_with_jwt(
+        &format!(
+            "/api/admin/v1/organizations/{}/group-roles/{}",
+            org.id, role_id
+        ),
+        &admin_api_key,
+        &owner.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = get_request_with_auth(
+        &format!(
+            "/api/v1/groups/{}/members/{}/permissions",
+            group.id, member.user_id
+        ),
+        &member.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = parse_response_body(response).await;
+    assert!(body["custom_role_id"].is_null());
+    assert_eq!(body["permissions"], json!(["view_locations"]));
+
+    cleanup_all_test_data(&pool).await;
+}
+
+#[tokio::test]
+async fn test_custom_group_role_hides_member_locations() {
+    let pool = create_test_pool().await;
+    run_migrations(&pool).await;
+    cleanup_all_test_data(&pool).await;
+
+    let config = test_config();
+    let admin_api_key = create_test_admin_api_key(&pool, "test-admin-key").await;
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let org = create_test_organization(&app, &admin_api_key, &TestOrganization::new()).await;
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let owner = create_authenticated_user(&app, &TestUser::new()).await;
+    let member = create_authenticated_user(&app, &TestUser::new()).await;
+    add_user_to_organization(&pool, &owner.user_id, &org.id, "admin").await;
+    add_user_to_organization(&pool, &member.user_id, &org.id, "member").await;
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let group = create_test_group(&app, &owner, &TestGroup::new()).await;
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::POST,
+        "/api/v1/groups/join",
+        json!({ "code": group.invite_code }),
+        &member.access_token,
+    );
+    app.oneshot(request).await.unwrap();
+
+    // The owner's device has a last location
+    let device = common::TestDevice::new();
+    let app = create_test_app(config.clone(), pool.clone());
+    common::register_test_device(&app, &pool, &owner, &device).await;
+    common::insert_device_location(&pool, &device.device_id, 48.8566, 2.3522, 10.0).await;
+
+    let members_uri = format!("/api/v1/groups/{}/members", group.id);
+    let member_uri = format!("/api/v1/groups/{}/members/{}", group.id, owner.user_id);
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let response = app
+        .oneshot(get_request_with_auth(&member_uri, &member.access_token))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = parse_response_body(response).await;
+    assert!(body["devices"][0]["last_location"].is_object());
+
+    // A custom role without view_locations hides member locations
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = post_admin_request_with_jwt(
+        &format!("/api/admin/v1/organizations/{}/group-roles", org.id),
+        json!({ "name": "Recruiter", "permissions": ["invite_members"] }),
+        &admin_api_key,
+        &owner.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = parse_response_body(response).await;
+    let role_id = body["id"].as_str().unwrap().to_string();
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::PUT,
+        &format!(
+            "/api/v1/groups/{}/members/{}/custom-role",
+            group.id, member.user_id
+        ),
+        json!({ "custom_role_id": role_id }),
+        &owner.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let response = app
+        .oneshot(get_request_with_auth(&members_uri, &member.access_token))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = parse_response_body(response).await;
+    let owner_entry = body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|m| m["user"]["id"] == owner.user_id.as_str())
+        .unwrap();
+    assert_eq!(owner_entry["devices"].as_array().unwrap().len(), 1);
+    assert!(owner_entry["devices"][0]["last_location"].is_null());
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let response = app
+        .oneshot(get_request_with_auth(&member_uri, &member.access_token))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = parse_response_body(response).await;
+    assert!(body["devices"][0]["last_location"].is_null());
+
+    // The owner keeps seeing locations
+    let app = create_test_app(config.clone(), pool.clone());
+    let response = app
+        .oneshot(get_request_with_auth(&member_uri, &owner.access_token))
+        .await
+        .unwrap();
+    let body = parse_response_body(response).await;
+    assert!(body["devices"][0]["last_location"].is_object());
+
+    cleanup_all_test_data(&pool).await;
+}
+
+// ============================================================================
+// Group Merge Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_merge_group_moves_members_and_invites() {
+    let pool = create_test_pool().await;
+    run_migrations(&pool).await;
+    cleanup_all_test_data(&pool).await;
+
+    let config = test_config();
+    let app = create_test_app(config.clone(), pool.clone());
+
+    let owner = create_authenticated_user(&app, &TestUser::new()).await;
+    let member = create_authenticated_user(&app, &TestUser::new()).await;
+    let app = create_test_app(config.clone(), pool.clone());
+    let target = create_test_group(&app, &owner, &TestGroup::new()).await;
+    let app = create_test_app(config.clone(), pool.clone());
+    let source = create_test_group(&app, &owner, &TestGroup::new()).await;
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::POST,
+        "/api/v1/groups/join",
+        json!({ "code": source.invite_code }),
+        &member.access_token,
+    );
+    app.oneshot(request).await.unwrap();
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::POST,
+        &format!("/api/v1/groups/{}/invites", source.id),
+        json!({}),
+        &owner.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let merge_uri = format!("/api/v1/groups/{}/merge", target.id);
+
+    // A group cannot be merged into itself
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::POST,
+        &merge_uri,
+        json!({ "source_group_id": target.id }),
+        &owner.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // Members of the source group cannot merge it
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::POST,
+        &format!("/api/v1/groups/{}/merge", source.id),
+        json!({ "source_group_id": target.id }),
+        &member.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::POST,
+        &merge_uri,
+        json!({ "source_group_id": source.id }),
+        &owner.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = parse_response_body(response).await;
+    assert_eq!(body["source_group_id"], source.id);
+    assert_eq!(body["members_moved"], 1);
+    assert_eq!(body["invites_moved"], 1);
+    assert!(body["migration_id"].is_string());
+
+    // The member now belongs to the target group
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = get_request_with_auth(
+        &format!("/api/v1/groups/{}/members/{}", target.id, member.user_id),
+        &member.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = get_request_with_auth(
+        &format!("/api/v1/groups/{}/invites", target.id),
+        &owner.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // The source group is gone
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = get_request_with_auth(
+        &format!("/api/v1/groups/{}", source.id),
+        &owner.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    cleanup_all_test_data(&pool).await;
+}
+
+// ============================================================================
+// Notification Preference Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_group_notification_preferences() {
+    let pool = create_test_pool().await;
+    run_migrations(&pool).await;
+    cleanup_all_test_data(&pool).await;
+
+    let config = test_config();
+    let app = create_test_app(config.clone(), pool.clone());
+    let owner = create_authenticated_user(&app, &TestUser::new()).await;
+    let member = create_authenticated_user(&app, &TestUser::new()).await;
+    let outsider = create_authenticated_user(&app, &TestUser::new()).await;
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let group = create_test_group(&app, &owner, &TestGroup::new()).await;
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::POST,
+        "/api/v1/groups/join",
+        json!({ "code": group.invite_code }),
+        &member.access_token,
+    );
+    app.oneshot(request).await.unwrap();
+
+    let uri = format!("/api/v1/groups/{}/notification-preferences", group.id);
+
+    // Everything is enabled by default
+    let app = create_test_app(config.clone(), pool.clone());
+    let response = app
+        .oneshot(get_request_with_auth(&uri, &member.access_token))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = parse_response_body(response).await;
+    assert_eq!(body["geofence_events"], true);
+    assert_eq!(body["low_battery"], true);
+    assert!(body.get("updated_at").is_none());
+
+    // Omitted categories keep their value across updates
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::PUT,
+        &uri,
+        json!({ "low_battery": false, "member_joins": false }),
+        &member.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_auth(
+        Method::PUT,
+        &uri,
+        json!({ "member_joins": true }),
+        &member.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    let body = parse_response_body(response).await;
+    assert_eq!(body["low_battery"], false);
+    assert_eq!(body["member_joins"], true);
+    assert_eq!(body["sos"], true);
+    assert!(body["updated_at"].is_string());
+
+    // Preferences are per member
+    let app = create_test_app(config.clone(), pool.clone());
+    let response = app
+        .oneshot(get_request_with_auth(&uri, &owner.access_token))
+        .await
+        .unwrap();
+    let body = parse_response_body(response).await;
+    assert_eq!(body["low_battery"], true);
+
+    // Non-members have no preferences in the group
+    let app = create_test_app(config.clone(), pool.clone());
+    let response = app
+        .oneshot(get_request_with_auth(&uri, &outsider.access_token))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    cleanup_all_test_data(&pool).await;
+}
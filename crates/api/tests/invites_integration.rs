@@ -100,8 +100,8 @@ async fn create_test_invite(pool: &PgPool, group_id: Uuid, created_by: Uuid) ->
     let invite_id = Uuid::new_v4();
     let code = format!(
         "TST-{}-{}",
-        &Uuid::new_v4().to_string()[..3].to_uppercase(),
-        &Uuid::new_v4().to_string()[..3].to_uppercase()
+        Uuid::new_v4().to_string()[..3].to_uppercase(),
+        Uuid::new_v4().to_string()[..3].to_uppercase()
     );
 
     sqlx::query(
@@ -126,8 +126,8 @@ async fn create_expired_invite(pool: &PgPool, group_id: Uuid, created_by: Uuid)
     let invite_id = Uuid::new_v4();
     let code = format!(
         "EXP-{}-{}",
-        &Uuid::new_v4().to_string()[..3].to_uppercase(),
-        &Uuid::new_v4().to_string()[..3].to_uppercase()
+        Uuid::new_v4().to_string()[..3].to_uppercase(),
+        Uuid::new_v4().to_string()[..3].to_uppercase()
     );
 
     sqlx::query(
@@ -569,3 +569,158 @@ async fn test_get_expired_invite_info() {
 
     cleanup_all_test_data(&pool).await;
 }
+
+// ============================================================================
+// Email Invite Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_email_invite_joins_recipient_on_registration() {
+    let pool = create_test_pool().await;
+    run_migrations(&pool).await;
+    cleanup_all_test_data(&pool).await;
+
+    let config = test_config();
+    let app = create_test_app(config.clone(), pool.clone());
+
+    let owner = TestUser::new();
+    let owner_auth = create_authenticated_user(&app, &owner).await;
+    let owner_id = Uuid::parse_str(&owner_auth.user_id).unwrap();
+    let group_id = create_test_group_with_owner(&pool, owner_id).await;
+
+    // Invite a user who has no account yet
+    let recipient = TestUser::new();
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_jwt(
+        Method::POST,
+        &format!("/api/v1/groups/{}/invites/email", group_id),
+        json!({ "email": recipient.email.to_uppercase() }),
+        &owner_auth.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let body = parse_response_body(response).await;
+    assert_eq!(body["recipient_email"], recipient.email.to_lowercase());
+    assert_eq!(body["max_uses"], 1);
+    assert!(body["email_sent"].is_boolean());
+    let code = body["code"].as_str().unwrap().to_string();
+
+    // A second invite to the same address is rejected while the first is pending
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_jwt(
+        Method::POST,
+        &format!("/api/v1/groups/{}/invites/email", group_id),
+        json!({ "email": recipient.email }),
+        &owner_auth.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    // Someone else can't use the invite
+    let app = create_test_app(config.clone(), pool.clone());
+    let other_auth = create_authenticated_user(&app, &TestUser::new()).await;
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_jwt(
+        Method::POST,
+        "/api/v1/groups/join",
+        json!({ "code": code }),
+        &other_auth.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // The recipient joins by registering with the code from the email
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = axum::http::Request::builder()
+        .method(Method::POST)
+        .uri("/api/v1/auth/register")
+        .header(axum::http::header::CONTENT_TYPE, "application/json")
+        .body(axum::body::Body::from(
+            json!({
+                "email": recipient.email,
+                "password": recipient.password,
+                "display_name": recipient.display_name,
+                "group_invite_code": code
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let body = parse_response_body(response).await;
+    assert_eq!(body["joined_group_id"], group_id.to_string());
+
+    let status: String = sqlx::query_scalar("SELECT status FROM group_invites WHERE code = $1")
+        .bind(&code)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(status, "accepted");
+
+    cleanup_all_test_data(&pool).await;
+}
+
+// ============================================================================
+// Invite QR Code Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_get_invite_qr_code() {
+    let pool = create_test_pool().await;
+    run_migrations(&pool).await;
+    cleanup_all_test_data(&pool).await;
+
+    let config = test_config();
+    let app = create_test_app(config.clone(), pool.clone());
+
+    let owner = create_authenticated_user(&app, &TestUser::new()).await;
+    let owner_id = Uuid::parse_str(&owner.user_id).unwrap();
+    let group_id = create_test_group_with_owner(&pool, owner_id).await;
+    let (_invite_id, code) = create_test_invite(&pool, group_id, owner_id).await;
+    let (_expired_id, expired_code) = create_expired_invite(&pool, group_id, owner_id).await;
+
+    let qr_uri = |code: &str, query: &str| {
+        format!("/api/v1/groups/{}/invites/{}/qr{}", group_id, code, query)
+    };
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = get_request_with_jwt(&qr_uri(&code, ""), &owner.access_token);
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "image/png");
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert!(body.starts_with(b"\x89PNG"));
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = get_request_with_jwt(&qr_uri(&code, "?format=svg"), &owner.access_token);
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "image/svg+xml");
+
+    // Expired invites cannot be rendered
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = get_request_with_jwt(&qr_uri(&expired_code, ""), &owner.access_token);
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    // Plain members cannot get invite QR codes
+    let app = create_test_app(config.clone(), pool.clone());
+    let member = create_authenticated_user(&app, &TestUser::new()).await;
+    let member_group =
+        create_test_group_with_member(&pool, owner_id, Uuid::parse_str(&member.user_id).unwrap())
+            .await;
+    let (_id, member_code) = create_test_invite(&pool, member_group, owner_id).await;
+    let app = create_test_app(config, pool.clone());
+    let request = get_request_with_jwt(
+        &format!("/api/v1/groups/{}/invites/{}/qr", member_group, member_code),
+        &member.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    cleanup_all_test_data(&pool).await;
+}
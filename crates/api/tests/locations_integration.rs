@@ -257,6 +257,232 @@ async fn test_upload_batch_success() {
     cleanup_all_test_data(&pool).await;
 }
 
+#[tokio::test]
+async fn test_upload_batch_replay_skips_stored_sequence_numbers() {
+    let pool = create_test_pool().await;
+    run_migrations(&pool).await;
+    cleanup_all_test_data(&pool).await;
+
+    let config = test_config();
+    let app = create_test_app(config.clone(), pool.clone());
+
+    // Create authenticated user and register device
+    let user = TestUser::new();
+    let auth = create_authenticated_user(&app, &user).await;
+    let device = TestDevice::new();
+    let app = create_test_app(config.clone(), pool.clone());
+    let _device_response = register_test_device(&app, &pool, &auth, &device).await;
+
+    // Create API key
+    let api_key = create_test_api_key(
+        &pool,
+        "test_upload_batch_replay_skips_stored_sequence_numbers",
+    )
+    .await;
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let batch = json!({
+        "device_id": device.device_id,
+        "locations": [
+            {
+                "latitude": 37.7749,
+                "longitude": -122.4194,
+                "accuracy": 10.5,
+                "timestamp": now - 1000,
+                "sequence_number": 1
+            },
+            {
+                "latitude": 37.7750,
+                "longitude": -122.4195,
+                "accuracy": 8.0,
+                "timestamp": now,
+                "sequence_number": 2
+            }
+        ]
+    });
+
+    // First upload stores both points
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_api_key_and_jwt(
+        Method::POST,
+        "/api/v1/locations/batch",
+        batch.clone(),
+        &api_key,
+        &auth.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = parse_response_body(response).await;
+    assert_eq!(body["processed_count"].as_i64().unwrap(), 2);
+    assert_eq!(body["duplicate_count"].as_i64().unwrap(), 0);
+
+    // Replaying the same batch stores nothing
+    let app = create_test_app(config, pool.clone());
+    let request = json_request_with_api_key_and_jwt(
+        Method::POST,
+        "/api/v1/locations/batch",
+        batch,
+        &api_key,
+        &auth.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = parse_response_body(response).await;
+    assert_eq!(body["processed_count"].as_i64().unwrap(), 0);
+    assert_eq!(body["duplicate_count"].as_i64().unwrap(), 2);
+
+    cleanup_all_test_data(&pool).await;
+}
+
+#[tokio::test]
+async fn test_upload_batch_gzip_encoded() {
+    use axum::{
+        body::Body,
+        http::{header, Request},
+    };
+    use flate2::{write::GzEncoder, Compression};
+    use std::io::Write;
+
+    let pool = create_test_pool().await;
+    run_migrations(&pool).await;
+    cleanup_all_test_data(&pool).await;
+
+    let config = test_config();
+    let app = create_test_app(config.clone(), pool.clone());
+
+    // Create authenticated user and register device
+    let user = TestUser::new();
+    let auth = create_authenticated_user(&app, &user).await;
+    let device = TestDevice::new();
+    let app = create_test_app(config.clone(), pool.clone());
+    let _device_response = register_test_device(&app, &pool, &auth, &device).await;
+
+    // Create API key
+    let api_key = create_test_api_key(&pool, "test_upload_batch_gzip_encoded").await;
+
+    // Upload a gzip-compressed batch
+    let now = chrono::Utc::now().timestamp_millis();
+    let body = json!({
+        "device_id": device.device_id,
+        "locations": [
+            {
+                "latitude": 37.7749,
+                "longitude": -122.4194,
+                "accuracy": 10.5,
+                "timestamp": now - 1000
+            },
+            {
+                "latitude": 37.7750,
+                "longitude": -122.4195,
+                "accuracy": 8.0,
+                "timestamp": now
+            }
+        ]
+    });
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(serde_json::to_string(&body).unwrap().as_bytes())
+        .unwrap();
+    let compressed = encoder.finish().unwrap();
+
+    let app = create_test_app(config, pool.clone());
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri("/api/v1/locations/batch")
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::CONTENT_ENCODING, "gzip")
+        .header("X-API-Key", &api_key)
+        .header(
+            header::AUTHORIZATION,
+            format!("Bearer {}", auth.access_token),
+        )
+        .body(Body::from(compressed))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = parse_response_body(response).await;
+    assert_eq!(body["processed_count"].as_i64().unwrap(), 2);
+
+    cleanup_all_test_data(&pool).await;
+}
+
+#[tokio::test]
+async fn test_latest_location_fast_path_ignores_older_uploads() {
+    let pool = create_test_pool().await;
+    run_migrations(&pool).await;
+    cleanup_all_test_data(&pool).await;
+
+    let config = test_config();
+    let app = create_test_app(config.clone(), pool.clone());
+
+    // Create authenticated user and register device
+    let user = TestUser::new();
+    let auth = create_authenticated_user(&app, &user).await;
+    let device = TestDevice::new();
+    let app = create_test_app(config.clone(), pool.clone());
+    let _device_response = register_test_device(&app, &pool, &auth, &device).await;
+
+    // Create API key
+    let api_key = create_test_api_key(&pool, "test_latest_location_fast_path_ignores_older").await;
+
+    // Upload a fresh point, then a batch of older buffered points
+    let now = chrono::Utc::now().timestamp_millis();
+    for body in [
+        json!({
+            "device_id": device.device_id,
+            "locations": [
+                { "latitude": 48.1486, "longitude": 17.1077, "accuracy": 5.0, "timestamp": now }
+            ]
+        }),
+        json!({
+            "device_id": device.device_id,
+            "locations": [
+                { "latitude": 10.0, "longitude": 10.0, "accuracy": 5.0, "timestamp": now - 60_000 },
+                { "latitude": 11.0, "longitude": 11.0, "accuracy": 5.0, "timestamp": now - 30_000 }
+            ]
+        }),
+    ] {
+        let app = create_test_app(config.clone(), pool.clone());
+        let request = json_request_with_api_key_and_jwt(
+            Method::POST,
+            "/api/v1/locations/batch",
+            body,
+            &api_key,
+            &auth.access_token,
+        );
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    let device_id: uuid::Uuid = device.device_id.parse().unwrap();
+    let (latitude, longitude): (f64, f64) = sqlx::query_as(
+        "SELECT latitude, longitude FROM device_latest_locations WHERE device_id = $1",
+    )
+    .bind(device_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!((latitude, longitude), (48.1486, 17.1077));
+
+    // Deleting the cached point falls back to the newest remaining one
+    sqlx::query("DELETE FROM locations WHERE device_id = $1 AND latitude = 48.1486")
+        .bind(device_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let latitude: f64 =
+        sqlx::query_scalar("SELECT latitude FROM device_latest_locations WHERE device_id = $1")
+            .bind(device_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(latitude, 11.0);
+
+    cleanup_all_test_data(&pool).await;
+}
+
 #[tokio::test]
 async fn test_upload_batch_exceeds_limit() {
     let pool = create_test_pool().await;
@@ -711,3 +937,96 @@ async fn test_get_location_history_device_not_found() {
 
     cleanup_all_test_data(&pool).await;
 }
+
+// ============================================================================
+// Telemetry Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_telemetry_from_locations_and_standalone_endpoint() {
+    let pool = create_test_pool().await;
+    run_migrations(&pool).await;
+    cleanup_all_test_data(&pool).await;
+
+    let config = test_config();
+    let app = create_test_app(config.clone(), pool.clone());
+
+    // Create authenticated user and register device
+    let user = TestUser::new();
+    let auth = create_authenticated_user(&app, &user).await;
+    let device = TestDevice::new();
+    let app = create_test_app(config.clone(), pool.clone());
+    let _device_response = register_test_device(&app, &pool, &auth, &device).await;
+
+    // Create API key
+    let api_key = create_test_api_key(&pool, "test_telemetry_from_locations").await;
+
+    // Readings sent with a location are stored as telemetry
+    let now = chrono::Utc::now().timestamp_millis();
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_api_key_and_jwt(
+        Method::POST,
+        "/api/v1/locations",
+        json!({
+            "device_id": device.device_id,
+            "latitude": 37.7749,
+            "longitude": -122.4194,
+            "accuracy": 10.5,
+            "timestamp": now - 60_000,
+            "battery_level": 71,
+            "charging_state": "discharging",
+            "network_type": "lte",
+            "signal_strength": -97
+        }),
+        &api_key,
+        &auth.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Standalone sample without a location fix
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_api_key_and_jwt(
+        Method::POST,
+        &format!("/api/v1/devices/{}/telemetry", device.device_id),
+        json!({ "battery_level": 70, "charging_state": "charging" }),
+        &api_key,
+        &auth.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = parse_response_body(response).await;
+    assert_eq!(body["battery_level"], 70);
+    assert_eq!(body["charging_state"], "charging");
+    assert!(body.get("signal_strength").is_none());
+
+    // A sample needs at least one reading
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_api_key_and_jwt(
+        Method::POST,
+        &format!("/api/v1/devices/{}/telemetry", device.device_id),
+        json!({ "timestamp": now }),
+        &api_key,
+        &auth.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let device_id: uuid::Uuid = device.device_id.parse().unwrap();
+    let samples: Vec<(Option<i16>, Option<String>, Option<i16>)> = sqlx::query_as(
+        "SELECT battery_level, charging_state, signal_strength FROM device_telemetry WHERE device_id = $1 ORDER BY recorded_at",
+    )
+    .bind(device_id)
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        samples,
+        vec![
+            (Some(71), Some("discharging".to_string()), Some(-97)),
+            (Some(70), Some("charging".to_string()), None),
+        ]
+    );
+
+    cleanup_all_test_data(&pool).await;
+}
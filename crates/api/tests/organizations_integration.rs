@@ -709,3 +709,65 @@ async fn test_remove_org_user_not_member() {
 
     cleanup_all_test_data(&pool).await;
 }
+
+// ============================================================================
+// Organization Data Export Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_organization_export() {
+    let pool = create_test_pool().await;
+    run_migrations(&pool).await;
+    cleanup_all_test_data(&pool).await;
+
+    let config = test_config();
+    let api_key = create_test_admin_api_key(&pool, "test-admin-key").await;
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let created_org = create_test_organization(&app, &api_key, &TestOrganization::new()).await;
+    let export_uri = format!("/api/admin/v1/organizations/{}/export", created_org.id);
+
+    // Start an export job
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_api_key(Method::POST, &export_uri, json!({}), &api_key);
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let body = parse_response_body(response).await;
+    let job_id = body["job_id"].as_str().unwrap().to_string();
+    assert!(job_id.starts_with("org_export_"));
+
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = get_request_with_api_key(&format!("{}/{}", export_uri, job_id), &api_key);
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = parse_response_body(response).await;
+    assert_eq!(body["job_id"], job_id);
+
+    // Jobs of other export kinds are not found
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = get_request_with_api_key(
+        &format!(
+            "/api/admin/v1/organizations/{}/audit-logs/export/{}",
+            created_org.id, job_id
+        ),
+        &api_key,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // Unknown organizations cannot be exported
+    let app = create_test_app(config, pool.clone());
+    let request = json_request_with_api_key(
+        Method::POST,
+        &format!(
+            "/api/admin/v1/organizations/{}/export",
+            uuid::Uuid::new_v4()
+        ),
+        json!({}),
+        &api_key,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    cleanup_all_test_data(&pool).await;
+}
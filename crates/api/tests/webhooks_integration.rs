@@ -13,8 +13,8 @@ use axum::http::{Method, StatusCode};
 use common::{
     cleanup_all_test_data, create_authenticated_user, create_test_api_key, create_test_app,
     create_test_pool, delete_request_with_api_key_and_jwt, get_request_with_api_key_and_jwt,
-    json_request_with_api_key_and_jwt, parse_response_body, register_test_device, run_migrations,
-    test_config, TestDevice, TestUser,
+    json_request_with_api_key, json_request_with_api_key_and_jwt, parse_response_body,
+    register_test_device, run_migrations, test_config, TestDevice, TestUser,
 };
 use serde_json::json;
 use tower::ServiceExt;
@@ -72,6 +72,60 @@ async fn test_create_webhook_success() {
     cleanup_all_test_data(&pool).await;
 }
 
+#[tokio::test]
+async fn test_create_user_owned_webhook_requires_owner_token() {
+    let pool = create_test_pool().await;
+    run_migrations(&pool).await;
+    cleanup_all_test_data(&pool).await;
+
+    let config = test_config();
+    let app = create_test_app(config.clone(), pool.clone());
+
+    let auth = create_authenticated_user(&app, &TestUser::new()).await;
+    let other = create_authenticated_user(&app, &TestUser::new()).await;
+    let api_key = create_test_api_key(&pool, "test_user_owned_webhook").await;
+    let body = json!({
+        "owner_user_id": auth.user_id,
+        "name": "Home Assistant",
+        "target_url": "https://homeassistant.local/api/webhook/test",
+        "secret": "my-secret-key-12345"
+    });
+
+    // An API key alone cannot attach a webhook to a user
+    let app = create_test_app(config.clone(), pool.clone());
+    let request =
+        json_request_with_api_key(Method::POST, "/api/v1/webhooks", body.clone(), &api_key);
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // Nor can another user's token
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_api_key_and_jwt(
+        Method::POST,
+        "/api/v1/webhooks",
+        body.clone(),
+        &api_key,
+        &other.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let app = create_test_app(config, pool.clone());
+    let request = json_request_with_api_key_and_jwt(
+        Method::POST,
+        "/api/v1/webhooks",
+        body,
+        &api_key,
+        &auth.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = parse_response_body(response).await;
+    assert_eq!(body["owner_user_id"], auth.user_id.as_str());
+
+    cleanup_all_test_data(&pool).await;
+}
+
 #[tokio::test]
 async fn test_create_webhook_invalid_name_empty() {
     let pool = create_test_pool().await;
@@ -1032,7 +1086,85 @@ async fn test_update_webhook_only_secret() {
 
     let body = parse_response_body(response).await;
     assert_eq!(body["name"], "Home Assistant"); // Unchanged
-    assert_eq!(body["secret"], "new-rotated-secret-key"); // Changed
+    assert!(body.get("secret").is_none()); // Only returned on creation
+
+    cleanup_all_test_data(&pool).await;
+}
+
+#[tokio::test]
+async fn test_rotate_webhook_secret() {
+    let pool = create_test_pool().await;
+    run_migrations(&pool).await;
+    cleanup_all_test_data(&pool).await;
+
+    let config = test_config();
+    let app = create_test_app(config.clone(), pool.clone());
+
+    // Create authenticated user and register device
+    let user = TestUser::new();
+    let auth = create_authenticated_user(&app, &user).await;
+    let api_key = create_test_api_key(&pool, "test_rotate_secret").await;
+    let device = TestDevice::new();
+    let app = create_test_app(config.clone(), pool.clone());
+    let device_response = register_test_device(&app, &pool, &auth, &device).await;
+    let device_id = device_response["device_id"].as_str().unwrap();
+
+    // Create a webhook; the secret is returned once
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_api_key_and_jwt(
+        Method::POST,
+        "/api/v1/webhooks",
+        json!({
+            "owner_device_id": device_id,
+            "name": "Home Assistant",
+            "target_url": "https://homeassistant.local/webhook",
+            "secret": "old-secret-key-12345"
+        }),
+        &api_key,
+        &auth.access_token,
+    );
+    let create_response = app.oneshot(request).await.unwrap();
+    let create_body = parse_response_body(create_response).await;
+    assert_eq!(create_body["secret"], "old-secret-key-12345");
+    let webhook_id = create_body["webhook_id"].as_str().unwrap();
+
+    // Reads never return it
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = get_request_with_api_key_and_jwt(
+        &format!("/api/v1/webhooks/{}", webhook_id),
+        &api_key,
+        &auth.access_token,
+    );
+    let body = parse_response_body(app.oneshot(request).await.unwrap()).await;
+    assert!(body.get("secret").is_none());
+
+    // Rotate
+    let app = create_test_app(config.clone(), pool.clone());
+    let request = json_request_with_api_key_and_jwt(
+        Method::POST,
+        &format!("/api/v1/webhooks/{}/rotate-secret", webhook_id),
+        json!({}),
+        &api_key,
+        &auth.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = parse_response_body(response).await;
+    let secret = body["secret"].as_str().unwrap();
+    assert_ne!(secret, "old-secret-key-12345");
+    assert_eq!(secret.len(), 43);
+
+    // Unknown webhook
+    let app = create_test_app(config, pool.clone());
+    let request = json_request_with_api_key_and_jwt(
+        Method::POST,
+        &format!("/api/v1/webhooks/{}/rotate-secret", uuid::Uuid::new_v4()),
+        json!({}),
+        &api_key,
+        &auth.access_token,
+    );
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
 
     cleanup_all_test_data(&pool).await;
 }
@@ -1466,7 +1598,7 @@ async fn test_create_webhook_without_api_key() {
     // Create authenticated user and register device
     let user = TestUser::new();
     let auth = create_authenticated_user(&app, &user).await;
-    let api_key = create_test_api_key(&pool, "test_no_api_key").await;
+    let _api_key = create_test_api_key(&pool, "test_no_api_key").await;
     let device = TestDevice::new();
     let app = create_test_app(config.clone(), pool.clone());
     let device_response = register_test_device(&app, &pool, &auth, &device).await;
@@ -1520,7 +1652,7 @@ async fn test_create_webhook_with_invalid_api_key() {
     // Create authenticated user and register device
     let user = TestUser::new();
     let auth = create_authenticated_user(&app, &user).await;
-    let api_key = create_test_api_key(&pool, "test_invalid_api_key").await;
+    let _api_key = create_test_api_key(&pool, "test_invalid_api_key").await;
     let device = TestDevice::new();
     let app = create_test_app(config.clone(), pool.clone());
     let device_response = register_test_device(&app, &pool, &auth, &device).await;
@@ -2004,7 +2136,7 @@ async fn test_update_webhook_all_fields_at_once() {
     let body = parse_response_body(response).await;
     assert_eq!(body["name"], "Updated Name");
     assert_eq!(body["target_url"], "https://updated.example.com/webhook");
-    assert_eq!(body["secret"], "updated-secret-key");
+    assert!(body.get("secret").is_none());
     assert_eq!(body["enabled"], false);
 
     cleanup_all_test_data(&pool).await;
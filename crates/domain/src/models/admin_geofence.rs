@@ -5,6 +5,12 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::Validate;
 
+/// Maximum tags on one admin geofence.
+pub const MAX_GEOFENCE_TAGS: usize = 20;
+
+/// Maximum length of a geofence tag.
+pub const MAX_GEOFENCE_TAG_LENGTH: usize = 50;
+
 /// Query parameters for listing admin geofences.
 #[derive(Debug, Clone, Deserialize, Validate, Default)]
 #[serde(rename_all = "snake_case")]
@@ -15,6 +21,12 @@ pub struct AdminGeofenceQuery {
     pub per_page: Option<u32>,
     pub active: Option<bool>,
     pub search: Option<String>,
+    /// Only geofences of this category (case-insensitive).
+    pub category: Option<String>,
+    /// Only geofences carrying this tag.
+    pub tag: Option<String>,
+    /// Only geofences of this color (case-insensitive).
+    pub color: Option<String>,
 }
 
 /// Pagination for admin geofence list.
@@ -41,6 +53,8 @@ pub struct AdminGeofenceInfo {
     pub event_types: Vec<String>,
     pub active: bool,
     pub color: Option<String>,
+    pub category: Option<String>,
+    pub tags: Vec<String>,
     pub metadata: Option<serde_json::Value>,
     pub created_by: Option<Uuid>,
     pub creator_name: Option<String>,
@@ -76,6 +90,11 @@ pub struct CreateAdminGeofenceRequest {
     #[validate(length(min = 1, message = "At least one event type required"))]
     pub event_types: Vec<String>,
     pub color: Option<String>,
+    #[validate(length(min = 1, max = 50, message = "Category must be 1-50 characters"))]
+    pub category: Option<String>,
+    #[serde(default)]
+    #[validate(custom(function = "validate_tags"))]
+    pub tags: Vec<String>,
     pub metadata: Option<serde_json::Value>,
 }
 
@@ -106,6 +125,11 @@ pub struct UpdateAdminGeofenceRequest {
     pub event_types: Option<Vec<String>>,
     pub active: Option<bool>,
     pub color: Option<String>,
+    #[validate(length(min = 1, max = 50, message = "Category must be 1-50 characters"))]
+    pub category: Option<String>,
+    /// Replaces all tags when provided.
+    #[validate(custom(function = "validate_tags"))]
+    pub tags: Option<Vec<String>>,
     pub metadata: Option<serde_json::Value>,
 }
 
@@ -225,6 +249,7 @@ pub struct LocationAnalyticsSummary {
     pub total_geofences: i64,
     pub total_geofence_events_today: i64,
     pub most_visited_geofences: Vec<GeofenceVisitCount>,
+    pub geofences_by_category: Vec<GeofenceCategoryCount>,
 }
 
 /// Geofence visit count for analytics.
@@ -236,6 +261,16 @@ pub struct GeofenceVisitCount {
     pub visit_count: i64,
 }
 
+/// Active geofences and visits of one category for analytics.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GeofenceCategoryCount {
+    /// `None` for geofences without a category.
+    pub category: Option<String>,
+    pub geofence_count: i64,
+    pub visit_count: i64,
+}
+
 /// Response for location analytics.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -243,6 +278,39 @@ pub struct AdminLocationAnalyticsResponse {
     pub summary: LocationAnalyticsSummary,
 }
 
+/// Trim and lowercase tags, dropping empty and repeated ones.
+pub fn normalize_geofence_tags(tags: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if !tag.is_empty() && !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    normalized
+}
+
+fn validate_tags(tags: &[String]) -> Result<(), validator::ValidationError> {
+    if tags.len() > MAX_GEOFENCE_TAGS {
+        return Err(
+            validator::ValidationError::new("too_many_tags").with_message(
+                std::borrow::Cow::Borrowed("A geofence can have at most 20 tags"),
+            ),
+        );
+    }
+    if tags
+        .iter()
+        .any(|tag| tag.trim().len() > MAX_GEOFENCE_TAG_LENGTH)
+    {
+        return Err(
+            validator::ValidationError::new("tag_too_long").with_message(
+                std::borrow::Cow::Borrowed("Tags must be at most 50 characters"),
+            ),
+        );
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -254,6 +322,9 @@ mod tests {
             per_page: Some(50),
             active: Some(true),
             search: None,
+            category: Some("depot".to_string()),
+            tag: Some("north".to_string()),
+            color: None,
         };
         assert!(query.validate().is_ok());
     }
@@ -268,6 +339,8 @@ mod tests {
             radius_meters: 500.0,
             event_types: vec!["enter".to_string(), "exit".to_string()],
             color: Some("#FF5733".to_string()),
+            category: Some("Depot".to_string()),
+            tags: vec!["north".to_string(), "24h".to_string()],
             metadata: None,
         };
         assert!(request.validate().is_ok());
@@ -283,6 +356,8 @@ mod tests {
             radius_meters: 500.0,
             event_types: vec!["enter".to_string()],
             color: None,
+            category: None,
+            tags: vec![],
             metadata: None,
         };
         assert!(request.validate().is_err());
@@ -298,8 +373,47 @@ mod tests {
             radius_meters: 10.0, // Too small
             event_types: vec!["enter".to_string()],
             color: None,
+            category: None,
+            tags: vec![],
             metadata: None,
         };
         assert!(request.validate().is_err());
     }
+
+    #[test]
+    fn test_admin_geofence_tags_validation() {
+        let mut request: CreateAdminGeofenceRequest = serde_json::from_value(serde_json::json!({
+            "name": "Depot",
+            "latitude": 48.1486,
+            "longitude": 17.1077,
+            "radius_meters": 200.0,
+            "event_types": ["enter"]
+        }))
+        .unwrap();
+        assert!(request.tags.is_empty());
+        assert!(request.validate().is_ok());
+
+        request.tags = (0..=MAX_GEOFENCE_TAGS)
+            .map(|i| format!("tag{}", i))
+            .collect();
+        assert!(request.validate().is_err());
+
+        request.tags = vec!["x".repeat(MAX_GEOFENCE_TAG_LENGTH + 1)];
+        assert!(request.validate().is_err());
+
+        request.tags = vec![];
+        request.category = Some(String::new());
+        assert!(request.validate().is_err());
+    }
+
+    #[test]
+    fn test_normalize_geofence_tags() {
+        let tags = vec![
+            " North ".to_string(),
+            "north".to_string(),
+            "".to_string(),
+            "24h".to_string(),
+        ];
+        assert_eq!(normalize_geofence_tags(&tags), vec!["north", "24h"]);
+    }
 }
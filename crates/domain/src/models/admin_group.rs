@@ -151,7 +151,8 @@ pub struct AdminGroupDetailResponse {
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 #[serde(rename_all = "snake_case")]
 pub struct UpdateAdminGroupRequest {
-    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    #[serde(default, deserialize_with = "shared::text::deserialize_optional_line")]
+    #[validate(custom(function = "shared::text::validate_name"))]
     pub name: Option<String>,
     #[validate(length(max = 500, message = "Description must be at most 500 characters"))]
     pub description: Option<String>,
@@ -319,6 +320,104 @@ pub struct ListGroupInvitationsResponse {
     pub data: Vec<GroupInvitationInfo>,
 }
 
+/// Maximum number of members in a bulk membership or move request.
+pub const MAX_BULK_GROUP_MEMBERS: usize = 100;
+
+/// Change made to a member in a bulk membership request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkGroupMemberAction {
+    /// Add an organization user to the group (role defaults to member).
+    Add,
+    /// Remove a member from the group.
+    Remove,
+    /// Change a member's role.
+    UpdateRole,
+}
+
+/// A single change in a bulk membership request.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct BulkGroupMemberOperation {
+    pub action: BulkGroupMemberAction,
+    pub user_id: Uuid,
+    /// Role for `add` and `update_role`.
+    #[validate(custom(function = "validate_group_role"))]
+    pub role: Option<String>,
+}
+
+/// Request for adding, removing and changing roles of several members.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct BulkGroupMembersRequest {
+    /// Changes, applied in order (max 100).
+    #[validate(length(min = 1, max = 100))]
+    pub operations: Vec<BulkGroupMemberOperation>,
+}
+
+/// Result of a single change in a bulk membership request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct BulkGroupMemberResult {
+    pub user_id: Uuid,
+    pub action: BulkGroupMemberAction,
+    pub success: bool,
+    /// Member's role after the change; omitted for removals and failures.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Response for a bulk membership request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct BulkGroupMembersResponse {
+    pub group_id: Uuid,
+    pub total: usize,
+    pub successful: usize,
+    pub failed: usize,
+    pub results: Vec<BulkGroupMemberResult>,
+}
+
+/// Request for moving members from one group to another.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct MoveGroupMembersRequest {
+    pub target_group_id: Uuid,
+    /// Members to move (max 100).
+    #[validate(length(min = 1, max = 100))]
+    pub user_ids: Vec<Uuid>,
+    /// Role in the target group; members keep their role if omitted.
+    #[validate(custom(function = "validate_group_role"))]
+    pub role: Option<String>,
+}
+
+/// Result of moving a single member.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct MoveGroupMemberResult {
+    pub user_id: Uuid,
+    pub success: bool,
+    /// Role in the target group; omitted for failures.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Response for moving members between groups.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct MoveGroupMembersResponse {
+    pub source_group_id: Uuid,
+    pub target_group_id: Uuid,
+    pub total: usize,
+    pub successful: usize,
+    pub failed: usize,
+    pub results: Vec<MoveGroupMemberResult>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -378,4 +477,44 @@ mod tests {
         };
         assert!(request.validate().is_err());
     }
+
+    #[test]
+    fn test_bulk_group_members_request_validation() {
+        let request: BulkGroupMembersRequest = serde_json::from_value(serde_json::json!({
+            "operations": [
+                {"action": "add", "user_id": Uuid::new_v4(), "role": "admin"},
+                {"action": "remove", "user_id": Uuid::new_v4()},
+                {"action": "update_role", "user_id": Uuid::new_v4(), "role": "member"}
+            ]
+        }))
+        .unwrap();
+        assert!(request.validate().is_ok());
+        assert_eq!(
+            request.operations[2].action,
+            BulkGroupMemberAction::UpdateRole
+        );
+
+        let empty = BulkGroupMembersRequest { operations: vec![] };
+        assert!(empty.validate().is_err());
+
+        let operation = BulkGroupMemberOperation {
+            action: BulkGroupMemberAction::Add,
+            user_id: Uuid::new_v4(),
+            role: Some("superuser".to_string()),
+        };
+        assert!(operation.validate().is_err());
+    }
+
+    #[test]
+    fn test_move_group_members_request_validation() {
+        let mut request = MoveGroupMembersRequest {
+            target_group_id: Uuid::new_v4(),
+            user_ids: vec![Uuid::new_v4()],
+            role: None,
+        };
+        assert!(request.validate().is_ok());
+
+        request.user_ids = vec![Uuid::new_v4(); MAX_BULK_GROUP_MEMBERS + 1];
+        assert!(request.validate().is_err());
+    }
 }
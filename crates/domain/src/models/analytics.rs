@@ -7,6 +7,9 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::Validate;
 
+use super::report_builder::{validate_report_dimensions, ReportDimension, ReportSection};
+use super::unit_system::UnitSystem;
+
 // Re-export AnalyticsGroupBy from app_usage to avoid duplication
 pub use super::app_usage::AnalyticsGroupBy;
 
@@ -94,6 +97,9 @@ pub struct DeviceAnalyticsQuery {
     /// Group by: day, week, or month
     #[serde(default)]
     pub group_by: Option<AnalyticsGroupBy>,
+    /// Unit system override (defaults to the requesting user's preference)
+    #[serde(default)]
+    pub units: Option<UnitSystem>,
 }
 
 /// Device analytics response.
@@ -102,6 +108,7 @@ pub struct DeviceAnalyticsQuery {
 pub struct DeviceAnalyticsResponse {
     pub organization_id: Uuid,
     pub period: AnalyticsPeriod,
+    pub unit_system: UnitSystem,
     pub summary: DeviceAnalyticsSummary,
     pub trends: Vec<DeviceActivityTrend>,
     pub by_status: DeviceStatusBreakdown,
@@ -118,6 +125,9 @@ pub struct DeviceAnalyticsSummary {
     pub total_locations_reported: i64,
     pub total_geofence_events: i64,
     pub total_commands_issued: i64,
+    /// Completed trip distance in `distance_unit`
+    pub total_trip_distance: f64,
+    pub distance_unit: &'static str,
 }
 
 /// Device activity trend point.
@@ -158,6 +168,9 @@ pub struct ApiUsageAnalyticsQuery {
     /// Group by: day, week, or month
     #[serde(default)]
     pub group_by: Option<AnalyticsGroupBy>,
+    /// Only count requests made with this API key (0 for user sessions)
+    #[serde(default)]
+    pub api_key_id: Option<i64>,
 }
 
 /// API usage analytics response.
@@ -225,6 +238,19 @@ pub struct GenerateReportRequest {
     /// Additional parameters (depends on report type)
     #[serde(default)]
     pub parameters: Option<serde_json::Value>,
+    /// Unit system override (defaults to the requesting user's preference)
+    #[serde(default)]
+    pub units: Option<UnitSystem>,
+    /// Sections to include (custom reports only)
+    #[serde(default)]
+    pub sections: Vec<ReportSection>,
+    /// Grouping dimensions (custom reports only)
+    #[serde(default)]
+    #[validate(custom(function = "validate_report_dimensions"))]
+    pub group_by: Vec<ReportDimension>,
+    /// Saved template providing sections, grouping and format (custom reports only)
+    #[serde(default)]
+    pub template_id: Option<Uuid>,
 }
 
 /// Report format options.
@@ -238,6 +264,117 @@ pub enum ReportFormat {
     Pdf,
 }
 
+/// Longest date range a single data extract may cover.
+pub const MAX_EXTRACT_RANGE_DAYS: i64 = 366;
+
+/// Raw dataset exported by a data extract.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExtractDataset {
+    /// Individual location points.
+    Locations,
+    /// Geofence enter/exit/dwell events.
+    GeofenceEvents,
+}
+
+impl ExtractDataset {
+    /// Report job type used for extracts of this dataset.
+    pub fn report_type(&self) -> &'static str {
+        match self {
+            ExtractDataset::Locations => "locations_extract",
+            ExtractDataset::GeofenceEvents => "geofence_events_extract",
+        }
+    }
+
+    /// Dataset of an extract report job type.
+    pub fn from_report_type(report_type: &str) -> Option<Self> {
+        match report_type {
+            "locations_extract" => Some(ExtractDataset::Locations),
+            "geofence_events_extract" => Some(ExtractDataset::GeofenceEvents),
+            _ => None,
+        }
+    }
+}
+
+/// Data extract output format.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExtractFormat {
+    Csv,
+    /// Newline-delimited JSON, one object per row.
+    Jsonl,
+    /// Columnar Parquet with compressed column chunks.
+    #[default]
+    Parquet,
+}
+
+/// Request to generate a raw data extract.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GenerateExtractRequest {
+    pub dataset: ExtractDataset,
+    /// First day included (UTC)
+    pub from: NaiveDate,
+    /// Last day included (UTC)
+    pub to: NaiveDate,
+    #[serde(default)]
+    pub format: ExtractFormat,
+}
+
+impl GenerateExtractRequest {
+    /// Validate the date range.
+    pub fn validate_range(&self) -> Result<(), String> {
+        if self.to < self.from {
+            return Err("to: End date must not be before start date".to_string());
+        }
+        if (self.to - self.from).num_days() >= MAX_EXTRACT_RANGE_DAYS {
+            return Err(format!(
+                "to: Extracts can cover at most {} days",
+                MAX_EXTRACT_RANGE_DAYS
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Longest date range a single mileage report may cover.
+pub const MAX_MILEAGE_REPORT_RANGE_DAYS: i64 = 366;
+
+/// Request to generate a mileage report: completed trip distance per day,
+/// device and trip purpose.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GenerateMileageReportRequest {
+    /// First day included (UTC)
+    pub from: NaiveDate,
+    /// Last day included (UTC)
+    pub to: NaiveDate,
+    /// Limit the report to one device of the organization
+    #[serde(default)]
+    pub device_id: Option<Uuid>,
+    #[serde(default)]
+    pub format: ReportFormat,
+    /// Unit system override (defaults to the requesting user's preference)
+    #[serde(default)]
+    pub units: Option<UnitSystem>,
+}
+
+impl GenerateMileageReportRequest {
+    /// Validate the date range.
+    pub fn validate_range(&self) -> Result<(), String> {
+        if self.to < self.from {
+            return Err("to: End date must not be before start date".to_string());
+        }
+        if (self.to - self.from).num_days() >= MAX_MILEAGE_REPORT_RANGE_DAYS {
+            return Err(format!(
+                "to: Mileage reports can cover at most {} days",
+                MAX_MILEAGE_REPORT_RANGE_DAYS
+            ));
+        }
+        Ok(())
+    }
+}
+
 /// Report job response.
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -306,3 +443,64 @@ pub struct ReportDownloadResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub url_expires_at: Option<DateTime<Utc>>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_dataset_report_type_round_trip() {
+        for dataset in [ExtractDataset::Locations, ExtractDataset::GeofenceEvents] {
+            assert_eq!(
+                ExtractDataset::from_report_type(dataset.report_type()),
+                Some(dataset)
+            );
+        }
+        assert_eq!(ExtractDataset::from_report_type("user_analytics"), None);
+    }
+
+    #[test]
+    fn test_generate_extract_request_defaults_to_parquet() {
+        let request: GenerateExtractRequest = serde_json::from_str(
+            r#"{"dataset": "geofence_events", "from": "2024-01-01", "to": "2024-01-31"}"#,
+        )
+        .unwrap();
+        assert_eq!(request.dataset, ExtractDataset::GeofenceEvents);
+        assert_eq!(request.format, ExtractFormat::Parquet);
+        assert!(request.validate_range().is_ok());
+    }
+
+    #[test]
+    fn test_generate_extract_request_range_validation() {
+        let request = |from: &str, to: &str| GenerateExtractRequest {
+            dataset: ExtractDataset::Locations,
+            from: from.parse().unwrap(),
+            to: to.parse().unwrap(),
+            format: ExtractFormat::Jsonl,
+        };
+        assert!(request("2024-02-01", "2024-01-01")
+            .validate_range()
+            .is_err());
+        assert!(request("2024-01-01", "2024-12-31").validate_range().is_ok());
+        assert!(request("2024-01-01", "2025-01-01")
+            .validate_range()
+            .is_err());
+    }
+
+    #[test]
+    fn test_generate_mileage_report_request() {
+        let request: GenerateMileageReportRequest =
+            serde_json::from_str(r#"{"from": "2024-01-01", "to": "2024-01-31", "format": "pdf"}"#)
+                .unwrap();
+        assert_eq!(request.format, ReportFormat::Pdf);
+        assert!(request.device_id.is_none());
+        assert!(request.validate_range().is_ok());
+
+        let reversed = GenerateMileageReportRequest {
+            from: request.to,
+            to: request.from,
+            ..request
+        };
+        assert!(reversed.validate_range().is_err());
+    }
+}
@@ -5,6 +5,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
+use shared::pagination::CountMode;
 use std::collections::HashMap;
 use std::net::IpAddr;
 use std::str::FromStr;
@@ -107,6 +108,9 @@ pub enum AuditAction {
     OrgUpdate,
     OrgDelete,
     OrgSettingsChange,
+    OrgOwnershipTransferInitiate,
+    OrgOwnershipTransferAccept,
+    OrgOwnershipTransferCancel,
 
     // User actions
     UserCreate,
@@ -170,6 +174,9 @@ impl FromStr for AuditAction {
             "org.update" => Ok(AuditAction::OrgUpdate),
             "org.delete" => Ok(AuditAction::OrgDelete),
             "org.settings_change" => Ok(AuditAction::OrgSettingsChange),
+            "org.ownership_transfer_initiate" => Ok(AuditAction::OrgOwnershipTransferInitiate),
+            "org.ownership_transfer_accept" => Ok(AuditAction::OrgOwnershipTransferAccept),
+            "org.ownership_transfer_cancel" => Ok(AuditAction::OrgOwnershipTransferCancel),
             "user.create" => Ok(AuditAction::UserCreate),
             "user.update" => Ok(AuditAction::UserUpdate),
             "user.delete" => Ok(AuditAction::UserDelete),
@@ -218,6 +225,9 @@ impl std::fmt::Display for AuditAction {
             AuditAction::OrgUpdate => "org.update",
             AuditAction::OrgDelete => "org.delete",
             AuditAction::OrgSettingsChange => "org.settings_change",
+            AuditAction::OrgOwnershipTransferInitiate => "org.ownership_transfer_initiate",
+            AuditAction::OrgOwnershipTransferAccept => "org.ownership_transfer_accept",
+            AuditAction::OrgOwnershipTransferCancel => "org.ownership_transfer_cancel",
             AuditAction::UserCreate => "user.create",
             AuditAction::UserUpdate => "user.update",
             AuditAction::UserDelete => "user.delete",
@@ -504,16 +514,26 @@ pub struct ListAuditLogsQuery {
     pub resource_id: Option<String>,
     pub from: Option<DateTime<Utc>>,
     pub to: Option<DateTime<Utc>>,
+    /// How to compute `total` (default exact).
+    pub count: Option<CountMode>,
 }
 
 /// Pagination info for audit log list.
+///
+/// `total` and `total_pages` are omitted for `count=none`; `has_more` is
+/// always present.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct AuditLogPagination {
     pub page: i32,
     pub per_page: i32,
-    pub total: i64,
-    pub total_pages: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_pages: Option<i32>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub total_estimated: bool,
+    pub has_more: bool,
 }
 
 /// Response for audit log list.
@@ -531,6 +551,7 @@ pub enum ExportFormat {
     #[default]
     Json,
     Csv,
+    Xlsx,
 }
 
 impl FromStr for ExportFormat {
@@ -540,6 +561,7 @@ impl FromStr for ExportFormat {
         match s.to_lowercase().as_str() {
             "json" => Ok(ExportFormat::Json),
             "csv" => Ok(ExportFormat::Csv),
+            "xlsx" => Ok(ExportFormat::Xlsx),
             _ => Err(format!("Unknown export format: {}", s)),
         }
     }
@@ -570,6 +592,7 @@ impl ExportAuditLogsQuery {
             resource_id: self.resource_id.clone(),
             from: self.from,
             to: self.to,
+            count: None,
         }
     }
 }
@@ -612,6 +635,57 @@ impl FromStr for ExportJobStatus {
     }
 }
 
+/// What an export job exports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportJobKind {
+    /// Audit logs matching a filter, as JSON, CSV or XLSX.
+    AuditLogs,
+    /// All of an organization's data as a ZIP archive.
+    Organization,
+    /// All of a group's data as a ZIP archive.
+    Group,
+}
+
+impl ExportJobKind {
+    /// Value of the kind in `export_jobs.kind`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExportJobKind::AuditLogs => "audit_logs",
+            ExportJobKind::Organization => "organization",
+            ExportJobKind::Group => "group",
+        }
+    }
+
+    /// Prefix of the user-facing job IDs of this kind.
+    pub fn job_id_prefix(&self) -> &'static str {
+        match self {
+            ExportJobKind::AuditLogs => "export",
+            ExportJobKind::Organization => "org_export",
+            ExportJobKind::Group => "group_export",
+        }
+    }
+}
+
+impl std::fmt::Display for ExportJobKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ExportJobKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "audit_logs" => Ok(ExportJobKind::AuditLogs),
+            "organization" => Ok(ExportJobKind::Organization),
+            "group" => Ok(ExportJobKind::Group),
+            _ => Err(format!("Unknown export job kind: {}", s)),
+        }
+    }
+}
+
 /// Sync export response (for small datasets).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -643,6 +717,76 @@ pub struct ExportJobResponse {
     pub error: Option<String>,
 }
 
+/// Query parameters for an incremental ("since last export") export.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct IncrementalExportQuery {
+    /// Destination name; each destination keeps its own cursor.
+    pub destination: String,
+    pub format: Option<ExportFormat>,
+}
+
+/// Validate an incremental export destination name.
+pub fn validate_export_destination(destination: &str) -> Result<(), String> {
+    if destination.is_empty() || destination.len() > MAX_EXPORT_DESTINATION_LENGTH {
+        return Err(format!(
+            "destination must be 1-{} characters",
+            MAX_EXPORT_DESTINATION_LENGTH
+        ));
+    }
+    if !destination
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        return Err("destination may only contain letters, digits, '-', '_' and '.'".to_string());
+    }
+    Ok(())
+}
+
+/// Incremental export response.
+///
+/// Contains the audit logs recorded after the destination's previous export,
+/// oldest first. The cursor is advanced to `to_sequence` before responding;
+/// when `has_more` is true the caller should request again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct IncrementalExportResponse {
+    pub destination: String,
+    pub format: ExportFormat,
+    pub record_count: i64,
+    /// Cursor position before this export (exclusive).
+    pub from_sequence: i64,
+    /// Cursor position after this export (inclusive).
+    pub to_sequence: i64,
+    pub has_more: bool,
+    pub download_url: String,
+}
+
+/// Stored position of an incremental export destination.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct AuditExportCursor {
+    pub destination: String,
+    pub last_sequence: i64,
+    pub last_record_count: Option<i64>,
+    pub last_exported_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Response for listing incremental export cursors.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ListAuditExportCursorsResponse {
+    pub cursors: Vec<AuditExportCursor>,
+}
+
+/// Maximum length of an incremental export destination name.
+pub const MAX_EXPORT_DESTINATION_LENGTH: usize = 100;
+
+/// Seconds an audit log must have existed before an incremental export picks
+/// it up, so rows from transactions still in flight are not skipped.
+pub const INCREMENTAL_EXPORT_SETTLE_SECONDS: i64 = 5;
+
 /// Maximum records for sync export.
 pub const MAX_SYNC_EXPORT_RECORDS: i64 = 1000;
 
@@ -767,4 +911,44 @@ mod tests {
         assert_eq!(change.old, Some(serde_json::json!("old_value")));
         assert_eq!(change.new, Some(serde_json::json!("new_value")));
     }
+
+    #[test]
+    fn test_export_format_from_str() {
+        assert_eq!(ExportFormat::from_str("CSV").unwrap(), ExportFormat::Csv);
+        assert_eq!(ExportFormat::from_str("xlsx").unwrap(), ExportFormat::Xlsx);
+        assert!(ExportFormat::from_str("pdf").is_err());
+        let parsed: ExportFormat = serde_json::from_str("\"xlsx\"").unwrap();
+        assert_eq!(parsed, ExportFormat::Xlsx);
+    }
+
+    #[test]
+    fn test_export_job_kind_round_trip() {
+        for kind in [
+            ExportJobKind::AuditLogs,
+            ExportJobKind::Organization,
+            ExportJobKind::Group,
+        ] {
+            assert_eq!(kind.as_str().parse::<ExportJobKind>().unwrap(), kind);
+        }
+        assert_eq!(ExportJobKind::Organization.job_id_prefix(), "org_export");
+        assert_eq!(ExportJobKind::Group.job_id_prefix(), "group_export");
+        assert!("reports".parse::<ExportJobKind>().is_err());
+    }
+
+    #[test]
+    fn test_validate_export_destination() {
+        assert!(validate_export_destination("warehouse").is_ok());
+        assert!(validate_export_destination("bq.prod-eu_1").is_ok());
+        assert!(validate_export_destination("").is_err());
+        assert!(validate_export_destination("s3://bucket").is_err());
+        assert!(validate_export_destination(&"a".repeat(101)).is_err());
+    }
+
+    #[test]
+    fn test_incremental_export_query_deserialization() {
+        let query: IncrementalExportQuery =
+            serde_json::from_str(r#"{"destination": "warehouse", "format": "csv"}"#).unwrap();
+        assert_eq!(query.destination, "warehouse");
+        assert_eq!(query.format, Some(ExportFormat::Csv));
+    }
 }
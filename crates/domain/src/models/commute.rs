@@ -0,0 +1,96 @@
+//! Commute pattern models.
+//!
+//! Commutes are recurring trips of a device between the same two places,
+//! detected nightly from its recent trips. Useful for family ETAs and fleet
+//! planning. Times of day and weekdays are in UTC.
+
+use chrono::{DateTime, Utc, Weekday};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Days of trips commute detection looks at.
+pub const COMMUTE_LOOKBACK_DAYS: i64 = 56;
+
+/// An end of a commute.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct CommutePlace {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+/// A recurring trip between two places.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct CommuteResponse {
+    pub id: Uuid,
+    pub origin: CommutePlace,
+    pub destination: CommutePlace,
+    /// Trips that make up the commute, at most one per day.
+    pub trip_count: i32,
+    /// Typical departure time, `HH:MM` in UTC.
+    pub typical_departure_time: String,
+    /// Median deviation of departures from the typical time.
+    pub departure_spread_minutes: i32,
+    pub typical_duration_seconds: i64,
+    /// Weekdays the commute was made on (UTC), Monday first.
+    pub weekdays: Vec<Weekday>,
+    pub first_departure_at: DateTime<Utc>,
+    pub last_departure_at: DateTime<Utc>,
+}
+
+/// Response for GET /api/v1/devices/:deviceId/commutes
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ListCommutesResponse {
+    pub device_id: Uuid,
+    pub commutes: Vec<CommuteResponse>,
+    /// When the commutes were detected; `None` if the device has none.
+    pub detected_at: Option<DateTime<Utc>>,
+}
+
+/// Format minutes after midnight as `HH:MM`.
+pub fn format_minute_of_day(minute: i32) -> String {
+    let minute = minute.rem_euclid(24 * 60);
+    format!("{:02}:{:02}", minute / 60, minute % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_minute_of_day() {
+        assert_eq!(format_minute_of_day(0), "00:00");
+        assert_eq!(format_minute_of_day(7 * 60 + 5), "07:05");
+        assert_eq!(format_minute_of_day(23 * 60 + 59), "23:59");
+    }
+
+    #[test]
+    fn test_commute_serialization() {
+        let now = Utc::now();
+        let commute = CommuteResponse {
+            id: Uuid::nil(),
+            origin: CommutePlace {
+                latitude: 48.1486,
+                longitude: 17.1077,
+            },
+            destination: CommutePlace {
+                latitude: 48.17,
+                longitude: 17.06,
+            },
+            trip_count: 5,
+            typical_departure_time: format_minute_of_day(465),
+            departure_spread_minutes: 5,
+            typical_duration_seconds: 1500,
+            weekdays: vec![Weekday::Mon, Weekday::Fri],
+            first_departure_at: now,
+            last_departure_at: now,
+        };
+
+        let json = serde_json::to_value(&commute).unwrap();
+        assert_eq!(json["typical_departure_time"], "07:45");
+        assert_eq!(json["weekdays"], serde_json::json!(["Mon", "Fri"]));
+        assert_eq!(json["origin"]["latitude"], 48.1486);
+    }
+}
@@ -4,6 +4,7 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 /// Compliance dashboard response.
 #[derive(Debug, Clone, Serialize)]
@@ -123,6 +124,8 @@ pub struct ComplianceReportResponse {
     pub data_subject_requests: DataSubjectRequestReportSummary,
     /// Audit activity summary.
     pub audit_activity: AuditActivitySummary,
+    /// Device configuration at the end of the period.
+    pub device_configuration: DeviceConfigurationSummary,
     /// Compliance assessment.
     pub compliance_assessment: ComplianceAssessment,
 }
@@ -139,6 +142,33 @@ pub struct OrganizationReportSummary {
     pub total_groups: i64,
 }
 
+/// Device configuration of the organization at a point in time.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DeviceConfigurationSummary {
+    /// Time the configuration is reported for.
+    pub as_of: DateTime<Utc>,
+    /// Devices registered at that time.
+    pub total_devices: i64,
+    /// Devices with a policy assigned.
+    pub devices_with_policy: i64,
+    /// Devices with at least one locked setting.
+    pub devices_with_locked_settings: i64,
+    /// Devices per assigned policy.
+    pub by_policy: Vec<PolicyDeviceCount>,
+}
+
+/// Number of devices assigned to a policy.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PolicyDeviceCount {
+    pub policy_id: Uuid,
+    /// Policy name; absent if the policy has been deleted since.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub policy_name: Option<String>,
+    pub device_count: i64,
+}
+
 /// Data Subject Request summary for report.
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -251,6 +281,18 @@ mod tests {
         assert_eq!(format, ComplianceReportFormat::Json);
     }
 
+    #[test]
+    fn test_policy_device_count_omits_deleted_policy_name() {
+        let count = PolicyDeviceCount {
+            policy_id: Uuid::nil(),
+            policy_name: None,
+            device_count: 3,
+        };
+        let json = serde_json::to_value(&count).unwrap();
+        assert!(json.get("policy_name").is_none());
+        assert_eq!(json["device_count"], 3);
+    }
+
     #[test]
     fn test_finding_severity_serialization() {
         let severity = FindingSeverity::Warning;
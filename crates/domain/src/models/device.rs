@@ -32,11 +32,8 @@ pub struct Device {
 pub struct RegisterDeviceRequest {
     pub device_id: Uuid,
 
-    #[validate(length(
-        min = 2,
-        max = 50,
-        message = "Display name must be between 2 and 50 characters"
-    ))]
+    #[serde(deserialize_with = "shared::text::deserialize_line")]
+    #[validate(custom(function = "shared::text::validate_device_name"))]
     pub display_name: String,
 
     #[validate(length(
@@ -91,6 +88,9 @@ pub struct DeviceSummary {
     pub display_name: String,
     pub last_location: Option<DeviceLastLocation>,
     pub last_seen_at: Option<DateTime<Utc>>,
+    /// The owner's sharing schedule is outside its windows.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub sharing_paused: bool,
 }
 
 fn default_platform() -> String {
@@ -138,6 +138,7 @@ impl From<Device> for DeviceSummary {
             display_name: device.display_name,
             last_location: None, // Location not available from basic Device
             last_seen_at: device.last_seen_at,
+            sharing_paused: false,
         }
     }
 }
@@ -313,6 +314,7 @@ mod tests {
             display_name: "Test Phone".to_string(),
             last_location: Some(location),
             last_seen_at: Some(Utc::now()),
+            sharing_paused: false,
         };
         assert!(summary.last_location.is_some());
         let loc = summary.last_location.unwrap();
@@ -333,6 +335,7 @@ mod tests {
             display_name: "Test Device".to_string(),
             last_location: Some(location),
             last_seen_at: None,
+            sharing_paused: false,
         };
         let json = serde_json::to_string(&summary).unwrap();
         assert!(json.contains("\"last_location\""));
@@ -348,6 +351,7 @@ mod tests {
             display_name: "Test Device".to_string(),
             last_location: None,
             last_seen_at: None,
+            sharing_paused: false,
         };
         let json = serde_json::to_string(&summary).unwrap();
         assert!(json.contains("\"last_location\":null"));
@@ -0,0 +1,154 @@
+//! Device agent protocol domain models.
+//!
+//! Managed devices with persistent connectivity (kiosks, company phones on
+//! Wi-Fi) keep a WebSocket open to the server. The server pushes queued
+//! commands and settings changes over it as soon as they are issued, and
+//! the device reports heartbeats and command outcomes. Every message is a
+//! JSON text frame tagged by `type`.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::services::notification::SettingChangeNotification;
+
+/// Interval at which agents are expected to send heartbeats, in seconds.
+pub const AGENT_HEARTBEAT_INTERVAL_SECS: u64 = 30;
+
+/// Heartbeats an agent may miss before the server closes the connection.
+pub const AGENT_MISSED_HEARTBEATS: u64 = 3;
+
+/// Maximum length of a command failure reason reported by an agent.
+pub const MAX_AGENT_FAILURE_REASON_LENGTH: usize = 1000;
+
+/// Message pushed from the server to a device agent.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AgentServerMessage {
+    /// First message after the connection is accepted.
+    Welcome {
+        connection_id: Uuid,
+        heartbeat_interval_secs: u64,
+    },
+    /// A queued device command to execute.
+    Command {
+        command_id: Uuid,
+        command_type: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        payload: Option<serde_json::Value>,
+        issued_at: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    },
+    /// Device settings changed by an admin.
+    SettingsChanged {
+        changes: Vec<SettingChangeNotification>,
+        changed_by: String,
+        timestamp: DateTime<Utc>,
+    },
+    /// Reply to a heartbeat.
+    HeartbeatAck { server_time: DateTime<Utc> },
+    /// A client message could not be processed.
+    Error { message: String },
+}
+
+impl AgentServerMessage {
+    /// Message type, as sent in the `type` field.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AgentServerMessage::Welcome { .. } => "welcome",
+            AgentServerMessage::Command { .. } => "command",
+            AgentServerMessage::SettingsChanged { .. } => "settings_changed",
+            AgentServerMessage::HeartbeatAck { .. } => "heartbeat_ack",
+            AgentServerMessage::Error { .. } => "error",
+        }
+    }
+}
+
+/// Message sent from a device agent to the server.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AgentClientMessage {
+    /// Keeps the connection alive.
+    Heartbeat,
+    /// The device received a command and will execute it.
+    CommandAck { command_id: Uuid },
+    /// The device finished executing a command.
+    CommandResult {
+        command_id: Uuid,
+        success: bool,
+        #[serde(default)]
+        error: Option<String>,
+    },
+}
+
+impl AgentClientMessage {
+    /// Message type, as sent in the `type` field.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AgentClientMessage::Heartbeat => "heartbeat",
+            AgentClientMessage::CommandAck { .. } => "command_ack",
+            AgentClientMessage::CommandResult { .. } => "command_result",
+        }
+    }
+}
+
+/// An open agent connection, for admin listings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct AgentConnectionInfo {
+    pub connection_id: Uuid,
+    pub device_id: Uuid,
+    pub connected_at: DateTime<Utc>,
+    pub last_heartbeat_at: Option<DateTime<Utc>>,
+}
+
+/// Response listing the open agent connections of an organization.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ListAgentConnectionsResponse {
+    pub connections: Vec<AgentConnectionInfo>,
+    pub total: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_server_message_serialization() {
+        let message = AgentServerMessage::Command {
+            command_id: Uuid::nil(),
+            command_type: "lock".to_string(),
+            payload: None,
+            issued_at: Utc::now(),
+            expires_at: Utc::now(),
+        };
+        let json = serde_json::to_value(&message).unwrap();
+        assert_eq!(json["type"], "command");
+        assert_eq!(json["command_type"], "lock");
+        assert!(json.get("payload").is_none());
+        assert_eq!(message.kind(), "command");
+    }
+
+    #[test]
+    fn test_client_message_deserialization() {
+        let heartbeat: AgentClientMessage =
+            serde_json::from_str(r#"{"type":"heartbeat","battery":80}"#).unwrap();
+        assert_eq!(heartbeat, AgentClientMessage::Heartbeat);
+
+        let result: AgentClientMessage = serde_json::from_str(
+            r#"{"type":"command_result","command_id":"00000000-0000-0000-0000-000000000000","success":false,"error":"busy"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            AgentClientMessage::CommandResult {
+                command_id: Uuid::nil(),
+                success: false,
+                error: Some("busy".to_string()),
+            }
+        );
+
+        assert!(serde_json::from_str::<AgentClientMessage>(r#"{"type":"reboot"}"#).is_err());
+    }
+}
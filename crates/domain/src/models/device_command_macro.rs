@@ -0,0 +1,393 @@
+//! Device command macro domain models.
+//!
+//! A macro is a named sequence of fleet commands defined by org admins, e.g.
+//! "prepare for return": lock, then wipe. Running a macro against a device
+//! or a fleet filter queues every step for each matching device, grouped
+//! under one run whose status is derived from its child commands.
+
+use std::collections::{BTreeSet, HashMap};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+use validator::Validate;
+
+use super::device_token::EnrollmentStatus;
+use super::fleet::{DeviceCommandStatus, DeviceCommandType};
+
+/// Maximum number of steps in a macro.
+pub const MAX_MACRO_STEPS: usize = 20;
+
+/// Maximum number of devices a single macro run can target.
+pub const MAX_MACRO_RUN_DEVICES: i64 = 500;
+
+/// A single command of a macro.
+///
+/// String values of the payload written as `{{name}}` are placeholders,
+/// filled in from the parameters given when the macro is run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct CommandMacroStep {
+    pub command_type: DeviceCommandType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<Value>,
+}
+
+impl CommandMacroStep {
+    /// The payload with its placeholders replaced by run parameters.
+    pub fn render_payload(
+        &self,
+        parameters: &HashMap<String, Value>,
+    ) -> Result<Option<Value>, String> {
+        self.payload
+            .as_ref()
+            .map(|payload| render_value(payload, parameters))
+            .transpose()
+    }
+}
+
+/// Placeholder name of a `{{name}}` string value.
+fn placeholder(value: &str) -> Option<&str> {
+    value
+        .strip_prefix("{{")
+        .and_then(|v| v.strip_suffix("}}"))
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+}
+
+fn render_value(value: &Value, parameters: &HashMap<String, Value>) -> Result<Value, String> {
+    match value {
+        Value::String(s) => match placeholder(s) {
+            Some(name) => parameters
+                .get(name)
+                .cloned()
+                .ok_or_else(|| format!("Missing macro parameter: {}", name)),
+            None => Ok(value.clone()),
+        },
+        Value::Array(items) => items
+            .iter()
+            .map(|item| render_value(item, parameters))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array),
+        Value::Object(fields) => fields
+            .iter()
+            .map(|(key, item)| Ok((key.clone(), render_value(item, parameters)?)))
+            .collect::<Result<serde_json::Map<_, _>, String>>()
+            .map(Value::Object),
+        _ => Ok(value.clone()),
+    }
+}
+
+fn collect_placeholders(value: &Value, names: &mut BTreeSet<String>) {
+    match value {
+        Value::String(s) => {
+            if let Some(name) = placeholder(s) {
+                names.insert(name.to_string());
+            }
+        }
+        Value::Array(items) => items.iter().for_each(|v| collect_placeholders(v, names)),
+        Value::Object(fields) => fields.values().for_each(|v| collect_placeholders(v, names)),
+        _ => {}
+    }
+}
+
+/// Names of the parameters used by the steps, sorted.
+pub fn macro_parameters(steps: &[CommandMacroStep]) -> Vec<String> {
+    let mut names = BTreeSet::new();
+    for payload in steps.iter().filter_map(|s| s.payload.as_ref()) {
+        collect_placeholders(payload, &mut names);
+    }
+    names.into_iter().collect()
+}
+
+/// A device command macro.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DeviceCommandMacro {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub steps: Vec<CommandMacroStep>,
+    /// Parameters that must be supplied when running the macro.
+    pub parameters: Vec<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request to create or replace a command macro.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct SaveCommandMacroRequest {
+    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    pub name: String,
+    #[validate(length(max = 500, message = "Description must be at most 500 characters"))]
+    pub description: Option<String>,
+    #[validate(length(min = 1, max = 20, message = "A macro must have 1-20 steps"))]
+    pub steps: Vec<CommandMacroStep>,
+}
+
+/// Response for listing command macros.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ListCommandMacrosResponse {
+    pub data: Vec<DeviceCommandMacro>,
+}
+
+/// Fleet filter selecting the devices a macro runs against.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct CommandMacroDeviceFilter {
+    pub status: Option<EnrollmentStatus>,
+    pub group_id: Option<String>,
+    pub policy_id: Option<Uuid>,
+    pub assigned: Option<bool>,
+}
+
+/// Request to run a command macro.
+///
+/// Exactly one of `device_id` and `filter` must be given.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct RunCommandMacroRequest {
+    pub device_id: Option<i64>,
+    pub filter: Option<CommandMacroDeviceFilter>,
+    /// Values for the macro's `{{name}}` placeholders.
+    #[serde(default)]
+    pub parameters: HashMap<String, Value>,
+    /// Expiry of the queued commands (defaults to 24 hours).
+    #[validate(range(min = 1, max = 168))]
+    pub expires_in_hours: Option<u32>,
+}
+
+/// Overall status of a macro run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandMacroRunStatus {
+    /// No device has picked up a command yet.
+    Pending,
+    /// Some commands are still outstanding.
+    InProgress,
+    /// Every command completed.
+    Completed,
+    /// Every command finished, some failed or expired.
+    PartiallyFailed,
+    /// No command completed.
+    Failed,
+}
+
+/// Child command counts of a macro run, by status.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct CommandMacroRunCounts {
+    pub total: i64,
+    pub pending: i64,
+    pub acknowledged: i64,
+    pub completed: i64,
+    pub failed: i64,
+    pub expired: i64,
+}
+
+impl CommandMacroRunCounts {
+    /// Count child command statuses.
+    pub fn from_statuses(statuses: impl IntoIterator<Item = DeviceCommandStatus>) -> Self {
+        let mut counts = Self::default();
+        for status in statuses {
+            counts.total += 1;
+            match status {
+                DeviceCommandStatus::Pending => counts.pending += 1,
+                DeviceCommandStatus::Acknowledged => counts.acknowledged += 1,
+                DeviceCommandStatus::Completed => counts.completed += 1,
+                DeviceCommandStatus::Failed => counts.failed += 1,
+                DeviceCommandStatus::Expired => counts.expired += 1,
+            }
+        }
+        counts
+    }
+
+    /// Overall run status.
+    pub fn status(&self) -> CommandMacroRunStatus {
+        if self.pending == self.total {
+            CommandMacroRunStatus::Pending
+        } else if self.pending + self.acknowledged > 0 {
+            CommandMacroRunStatus::InProgress
+        } else if self.completed == self.total {
+            CommandMacroRunStatus::Completed
+        } else if self.completed == 0 {
+            CommandMacroRunStatus::Failed
+        } else {
+            CommandMacroRunStatus::PartiallyFailed
+        }
+    }
+}
+
+/// A child command of a macro run.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct CommandMacroRunCommand {
+    pub command_id: Uuid,
+    pub device_id: i64,
+    pub step_index: i32,
+    pub command_type: DeviceCommandType,
+    pub status: DeviceCommandStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure_reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub acknowledged_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failed_at: Option<DateTime<Utc>>,
+}
+
+/// A macro run: the parent operation of the commands it queued.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct CommandMacroRun {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    /// The macro, unless it has since been deleted.
+    pub macro_id: Option<Uuid>,
+    pub macro_name: String,
+    pub parameters: Value,
+    pub device_count: i32,
+    pub issued_by: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Response for a macro run with its child command statuses.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct CommandMacroRunResponse {
+    #[serde(flatten)]
+    pub run: CommandMacroRun,
+    pub status: CommandMacroRunStatus,
+    pub counts: CommandMacroRunCounts,
+    pub commands: Vec<CommandMacroRunCommand>,
+}
+
+impl CommandMacroRunResponse {
+    /// Build the response, deriving the status from the child commands.
+    pub fn new(run: CommandMacroRun, commands: Vec<CommandMacroRunCommand>) -> Self {
+        let counts = CommandMacroRunCounts::from_statuses(commands.iter().map(|c| c.status));
+        Self {
+            run,
+            status: counts.status(),
+            counts,
+            commands,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn step(command_type: DeviceCommandType, payload: Option<Value>) -> CommandMacroStep {
+        CommandMacroStep {
+            command_type,
+            payload,
+        }
+    }
+
+    #[test]
+    fn test_render_payload_fills_placeholders() {
+        let step = step(
+            DeviceCommandType::Lock,
+            Some(json!({"message": "{{message}}", "options": ["{{ pin }}", "fixed"], "level": 2})),
+        );
+        let parameters = HashMap::from([
+            ("message".to_string(), json!("Return to IT")),
+            ("pin".to_string(), json!(1234)),
+        ]);
+
+        assert_eq!(
+            step.render_payload(&parameters).unwrap(),
+            Some(json!({"message": "Return to IT", "options": [1234, "fixed"], "level": 2}))
+        );
+    }
+
+    #[test]
+    fn test_render_payload_missing_parameter() {
+        let step = step(
+            DeviceCommandType::Lock,
+            Some(json!({"message": "{{message}}"})),
+        );
+        assert_eq!(
+            step.render_payload(&HashMap::new()).unwrap_err(),
+            "Missing macro parameter: message"
+        );
+
+        let no_payload = CommandMacroStep {
+            command_type: DeviceCommandType::Wipe,
+            payload: None,
+        };
+        assert_eq!(no_payload.render_payload(&HashMap::new()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_macro_parameters() {
+        let steps = vec![
+            step(
+                DeviceCommandType::Lock,
+                Some(json!({"message": "{{message}}"})),
+            ),
+            step(DeviceCommandType::Wipe, None),
+            step(
+                DeviceCommandType::UpdatePolicy,
+                Some(json!({"policy": "{{policy}}", "note": "{{message}}", "raw": "{{}}"})),
+            ),
+        ];
+        assert_eq!(macro_parameters(&steps), vec!["message", "policy"]);
+    }
+
+    #[test]
+    fn test_run_status_from_counts() {
+        use DeviceCommandStatus::*;
+
+        let status = |statuses: &[DeviceCommandStatus]| {
+            CommandMacroRunCounts::from_statuses(statuses.iter().copied()).status()
+        };
+
+        assert_eq!(status(&[Pending, Pending]), CommandMacroRunStatus::Pending);
+        assert_eq!(
+            status(&[Acknowledged, Pending]),
+            CommandMacroRunStatus::InProgress
+        );
+        assert_eq!(
+            status(&[Completed, Pending]),
+            CommandMacroRunStatus::InProgress
+        );
+        assert_eq!(
+            status(&[Completed, Completed]),
+            CommandMacroRunStatus::Completed
+        );
+        assert_eq!(
+            status(&[Completed, Expired]),
+            CommandMacroRunStatus::PartiallyFailed
+        );
+        assert_eq!(status(&[Failed, Expired]), CommandMacroRunStatus::Failed);
+    }
+
+    #[test]
+    fn test_save_request_validation() {
+        let request: SaveCommandMacroRequest = serde_json::from_value(json!({
+            "name": "Prepare for return",
+            "steps": []
+        }))
+        .unwrap();
+        assert!(request.validate().is_err());
+
+        let request: SaveCommandMacroRequest = serde_json::from_value(json!({
+            "name": "Prepare for return",
+            "steps": [{"command_type": "lock"}, {"command_type": "wipe"}]
+        }))
+        .unwrap();
+        assert!(request.validate().is_ok());
+        assert_eq!(request.steps.len(), 2);
+    }
+}
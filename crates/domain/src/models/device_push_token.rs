@@ -0,0 +1,135 @@
+//! Device push token models.
+//!
+//! A device can register several push tokens, e.g. after an app reinstall
+//! or for a second app build. Tokens rejected by the push provider are
+//! removed automatically and expired tokens are no longer used.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Maximum number of push tokens kept per device; the least recently
+/// registered tokens are dropped beyond this.
+pub const MAX_PUSH_TOKENS_PER_DEVICE: i64 = 10;
+
+/// Platform a push token was issued for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PushPlatform {
+    Android,
+    Ios,
+}
+
+impl PushPlatform {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Android => "android",
+            Self::Ios => "ios",
+        }
+    }
+}
+
+impl std::str::FromStr for PushPlatform {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "android" => Ok(Self::Android),
+            "ios" => Ok(Self::Ios),
+            _ => Err(format!("Invalid push platform: {}", s)),
+        }
+    }
+}
+
+/// A registered push token. The token itself is only shown as a hint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DevicePushToken {
+    pub id: Uuid,
+    pub device_id: Uuid,
+    pub platform: PushPlatform,
+    /// Last characters of the token, to tell tokens apart.
+    pub token_hint: String,
+    pub app_version: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Hint shown in place of a token: its last six characters.
+pub fn push_token_hint(token: &str) -> String {
+    let chars: Vec<char> = token.chars().collect();
+    let tail: String = chars[chars.len().saturating_sub(6)..].iter().collect();
+    format!("...{}", tail)
+}
+
+/// Request payload for `POST /api/v1/devices/:device_id/push-tokens`.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct RegisterPushTokenRequest {
+    #[validate(length(min = 1, max = 4096, message = "Token must be 1-4096 characters"))]
+    pub token: String,
+
+    pub platform: PushPlatform,
+
+    #[validate(length(max = 50, message = "App version must be at most 50 characters"))]
+    pub app_version: Option<String>,
+
+    /// When the token should stop being used.
+    pub expires_at: Option<DateTime<Utc>>,
+
+    /// A previous token of the device that this one rotates out.
+    #[validate(length(min = 1, max = 4096, message = "Token must be 1-4096 characters"))]
+    pub replaces_token: Option<String>,
+}
+
+/// Response for listing a device's push tokens.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ListPushTokensResponse {
+    pub data: Vec<DevicePushToken>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_platform_round_trip() {
+        for platform in [PushPlatform::Android, PushPlatform::Ios] {
+            assert_eq!(platform.as_str().parse::<PushPlatform>(), Ok(platform));
+        }
+        assert_eq!("iOS".parse::<PushPlatform>(), Ok(PushPlatform::Ios));
+        assert!("web".parse::<PushPlatform>().is_err());
+    }
+
+    #[test]
+    fn test_push_token_hint() {
+        assert_eq!(push_token_hint("abcdefghijkl"), "...ghijkl");
+        assert_eq!(push_token_hint("abc"), "...abc");
+    }
+
+    #[test]
+    fn test_register_request_validation() {
+        let request: RegisterPushTokenRequest = serde_json::from_value(serde_json::json!({
+            "token": "fcm-token",
+            "platform": "android",
+            "app_version": "2.4.0"
+        }))
+        .unwrap();
+        assert!(request.validate().is_ok());
+
+        let empty = RegisterPushTokenRequest {
+            token: String::new(),
+            ..request.clone()
+        };
+        assert!(empty.validate().is_err());
+
+        let empty_replaces = RegisterPushTokenRequest {
+            replaces_token: Some(String::new()),
+            ..request
+        };
+        assert!(empty_replaces.validate().is_err());
+    }
+}
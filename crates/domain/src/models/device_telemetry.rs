@@ -0,0 +1,199 @@
+//! Device telemetry models.
+//!
+//! Battery and connectivity samples, reported either with a location upload
+//! or through the standalone telemetry endpoint.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Battery level, in percent, at or below which group members are alerted.
+pub const LOW_BATTERY_THRESHOLD_PERCENT: i32 = 15;
+
+/// Whether a battery reading drops to the low battery threshold.
+///
+/// `previous` is the device's reading before `current`; a device first
+/// reporting a low level counts as dropping.
+pub fn is_low_battery_drop(previous: Option<i32>, current: i32) -> bool {
+    current <= LOW_BATTERY_THRESHOLD_PERCENT
+        && previous.is_none_or(|p| p > LOW_BATTERY_THRESHOLD_PERCENT)
+}
+
+/// Battery charging state as reported by the device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChargingState {
+    Charging,
+    Discharging,
+    Full,
+    /// Plugged in but not charging (e.g. battery protection).
+    NotCharging,
+}
+
+impl ChargingState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Charging => "charging",
+            Self::Discharging => "discharging",
+            Self::Full => "full",
+            Self::NotCharging => "not_charging",
+        }
+    }
+}
+
+impl std::str::FromStr for ChargingState {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "charging" => Ok(Self::Charging),
+            "discharging" => Ok(Self::Discharging),
+            "full" => Ok(Self::Full),
+            "not_charging" => Ok(Self::NotCharging),
+            _ => Err(format!("Invalid charging state: {}", s)),
+        }
+    }
+}
+
+/// A telemetry sample.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct DeviceTelemetry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub battery_level: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub charging_state: Option<ChargingState>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network_type: Option<String>,
+    /// Signal strength in dBm
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signal_strength: Option<i32>,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl DeviceTelemetry {
+    /// Build a sample from optional readings; None if nothing was reported.
+    pub fn from_readings(
+        battery_level: Option<i32>,
+        charging_state: Option<ChargingState>,
+        network_type: Option<String>,
+        signal_strength: Option<i32>,
+        recorded_at: DateTime<Utc>,
+    ) -> Option<Self> {
+        if battery_level.is_none()
+            && charging_state.is_none()
+            && network_type.is_none()
+            && signal_strength.is_none()
+        {
+            return None;
+        }
+        Some(Self {
+            battery_level,
+            charging_state,
+            network_type,
+            signal_strength,
+            recorded_at,
+        })
+    }
+}
+
+/// Request payload for `POST /api/v1/devices/:device_id/telemetry`.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct UploadTelemetryRequest {
+    /// Sample time in milliseconds since epoch; defaults to the server time
+    #[validate(custom(function = "shared::validation::validate_timestamp"))]
+    pub timestamp: Option<i64>,
+
+    #[validate(custom(function = "shared::validation::validate_battery_level"))]
+    pub battery_level: Option<i32>,
+
+    pub charging_state: Option<ChargingState>,
+
+    #[validate(length(max = 50, message = "Network type must be at most 50 characters"))]
+    pub network_type: Option<String>,
+
+    /// Signal strength in dBm
+    #[validate(custom(function = "shared::validation::validate_signal_strength"))]
+    pub signal_strength: Option<i32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_charging_state_round_trip() {
+        for state in [
+            ChargingState::Charging,
+            ChargingState::Discharging,
+            ChargingState::Full,
+            ChargingState::NotCharging,
+        ] {
+            assert_eq!(state.as_str().parse::<ChargingState>(), Ok(state));
+            assert_eq!(
+                serde_json::to_value(state).unwrap(),
+                serde_json::json!(state.as_str())
+            );
+        }
+        assert!("plugged".parse::<ChargingState>().is_err());
+    }
+
+    #[test]
+    fn test_is_low_battery_drop() {
+        assert!(is_low_battery_drop(Some(16), 15));
+        assert!(is_low_battery_drop(Some(40), 3));
+        assert!(is_low_battery_drop(None, 10));
+        assert!(!is_low_battery_drop(Some(15), 14));
+        assert!(!is_low_battery_drop(Some(20), 16));
+    }
+
+    #[test]
+    fn test_from_readings_requires_a_value() {
+        let now = Utc::now();
+        assert!(DeviceTelemetry::from_readings(None, None, None, None, now).is_none());
+
+        let sample =
+            DeviceTelemetry::from_readings(None, Some(ChargingState::Full), None, None, now)
+                .unwrap();
+        assert_eq!(sample.charging_state, Some(ChargingState::Full));
+        assert_eq!(sample.recorded_at, now);
+    }
+
+    #[test]
+    fn test_upload_request_validation() {
+        let request: UploadTelemetryRequest = serde_json::from_str(
+            r#"{"battery_level": 42, "charging_state": "charging", "network_type": "lte", "signal_strength": -95}"#,
+        )
+        .unwrap();
+        assert!(request.validate().is_ok());
+        assert!(request.timestamp.is_none());
+
+        let bad_signal = UploadTelemetryRequest {
+            signal_strength: Some(10),
+            ..request.clone()
+        };
+        assert!(bad_signal.validate().is_err());
+
+        let bad_battery = UploadTelemetryRequest {
+            battery_level: Some(120),
+            ..request
+        };
+        assert!(bad_battery.validate().is_err());
+    }
+
+    #[test]
+    fn test_telemetry_serialization_skips_missing_readings() {
+        let sample = DeviceTelemetry {
+            battery_level: Some(80),
+            charging_state: None,
+            network_type: None,
+            signal_strength: None,
+            recorded_at: Utc::now(),
+        };
+        let json = serde_json::to_value(&sample).unwrap();
+        assert_eq!(json["battery_level"], 80);
+        assert!(json.get("charging_state").is_none());
+        assert!(json.get("signal_strength").is_none());
+    }
+}
@@ -0,0 +1,220 @@
+//! Effective access domain models for the admin access inspector.
+//!
+//! Describes everything a user can currently see and do: system roles,
+//! organization memberships, group memberships and the devices reachable
+//! through them, with permissions resolved the same way request handling
+//! resolves them.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::group::GroupRole;
+use super::group_custom_role::GroupPermissions;
+use super::org_user::{OrgUser, OrgUserRole};
+use super::system_role::{SystemRole, SYSTEM_PERMISSIONS};
+
+/// Response for GET /api/admin/v1/users/:user_id/effective-access
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct EffectiveAccessResponse {
+    pub user: EffectiveAccessUser,
+    pub system_access: SystemAccess,
+    pub organizations: Vec<OrganizationAccess>,
+    pub groups: Vec<GroupAccess>,
+    pub devices: Vec<DeviceAccess>,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// The user being inspected.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct EffectiveAccessUser {
+    pub id: Uuid,
+    pub email: String,
+    pub display_name: Option<String>,
+    pub is_active: bool,
+}
+
+/// System-level roles and the permissions they grant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SystemAccess {
+    pub roles: Vec<SystemRole>,
+    pub permissions: Vec<String>,
+    /// Organizations assigned for org_admin/org_manager roles.
+    pub assigned_org_ids: Vec<Uuid>,
+    /// Whether any role grants access to all organizations.
+    pub has_global_access: bool,
+}
+
+impl SystemAccess {
+    /// Resolve system access from a user's roles and org assignments.
+    pub fn resolve(roles: Vec<SystemRole>, assigned_org_ids: Vec<Uuid>) -> Self {
+        // Keep the canonical permission order instead of role order
+        let permissions = SYSTEM_PERMISSIONS
+            .iter()
+            .filter(|p| roles.iter().any(|r| r.default_permissions().contains(p)))
+            .map(|p| p.to_string())
+            .collect();
+        let has_global_access = roles.iter().any(|r| r.has_global_access());
+
+        Self {
+            roles,
+            permissions,
+            assigned_org_ids,
+            has_global_access,
+        }
+    }
+}
+
+/// Membership in an organization and the permissions it grants.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct OrganizationAccess {
+    pub organization_id: Uuid,
+    pub name: String,
+    pub role: OrgUserRole,
+    pub suspended: bool,
+    pub permissions: Vec<String>,
+}
+
+impl OrganizationAccess {
+    /// Resolve access for an organization membership.
+    pub fn resolve(org_user: &OrgUser, name: String) -> Self {
+        Self {
+            organization_id: org_user.organization_id,
+            name,
+            role: org_user.role,
+            suspended: org_user.is_suspended(),
+            permissions: org_user.effective_permissions(),
+        }
+    }
+}
+
+/// Membership in a group and what that role allows.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GroupAccess {
+    pub group_id: Uuid,
+    pub name: String,
+    pub slug: String,
+    pub role: GroupRole,
+    pub capabilities: Vec<String>,
+    pub device_count: i64,
+}
+
+/// Capabilities of a group member: the member's resolved permissions,
+/// including those of a custom role, followed by those of the built-in role.
+pub fn group_capabilities(role: GroupRole, permissions: GroupPermissions) -> Vec<String> {
+    let granted = permissions.to_vec().into_iter().map(|p| p.as_str());
+    let administrative = [
+        ("manage_group", role.can_manage_group()),
+        ("manage_members", role.can_manage_members()),
+        ("manage_retention", role.can_manage_retention()),
+        ("transfer_ownership", role.can_transfer_ownership()),
+        ("delete_group", role.can_delete_group()),
+    ]
+    .into_iter()
+    .filter(|(_, allowed)| *allowed)
+    .map(|(name, _)| name);
+    granted.chain(administrative).map(str::to_string).collect()
+}
+
+/// How a user reaches a device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeviceAccessSource {
+    /// The user owns the device.
+    Owner,
+    /// The device is in a group the user belongs to.
+    Group,
+}
+
+/// A device the user can see.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DeviceAccess {
+    pub device_id: Uuid,
+    pub display_name: String,
+    pub source: DeviceAccessSource,
+    /// Group through which the device is reachable, for group access.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<Uuid>,
+    pub last_seen_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::group_custom_role::GroupPermission;
+
+    #[test]
+    fn test_system_access_merges_role_permissions() {
+        let access =
+            SystemAccess::resolve(vec![SystemRole::Viewer, SystemRole::OrgManager], vec![]);
+        assert_eq!(
+            access.permissions,
+            vec![
+                "system:read",
+                "org:read",
+                "user:read_all",
+                "user:manage_all"
+            ]
+        );
+        assert!(access.has_global_access);
+    }
+
+    #[test]
+    fn test_system_access_without_roles() {
+        let access = SystemAccess::resolve(vec![], vec![]);
+        assert!(access.permissions.is_empty());
+        assert!(!access.has_global_access);
+    }
+
+    #[test]
+    fn test_group_capabilities() {
+        let capabilities = |role| group_capabilities(role, GroupPermissions::for_role(role));
+        assert_eq!(capabilities(GroupRole::Viewer), vec!["view_locations"]);
+        assert_eq!(
+            capabilities(GroupRole::Admin),
+            vec![
+                "view_locations",
+                "manage_geofences",
+                "manage_devices",
+                "invite_members",
+                "manage_group",
+                "manage_members"
+            ]
+        );
+        assert_eq!(capabilities(GroupRole::Owner).len(), 9);
+    }
+
+    #[test]
+    fn test_group_capabilities_with_custom_role() {
+        let custom: GroupPermissions = [GroupPermission::InviteMembers].into_iter().collect();
+        assert_eq!(
+            group_capabilities(GroupRole::Member, custom),
+            vec!["invite_members"]
+        );
+    }
+
+    #[test]
+    fn test_organization_access_for_suspended_member() {
+        let org_user = OrgUser {
+            id: Uuid::nil(),
+            organization_id: Uuid::nil(),
+            user_id: Uuid::nil(),
+            role: OrgUserRole::Admin,
+            permissions: vec!["device:read".to_string()],
+            granted_at: Utc::now(),
+            granted_by: None,
+            suspended_at: Some(Utc::now()),
+            suspended_by: None,
+            suspension_reason: None,
+        };
+        let access = OrganizationAccess::resolve(&org_user, "Acme".to_string());
+        assert!(access.suspended);
+        assert!(access.permissions.is_empty());
+    }
+}
@@ -7,6 +7,8 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::Validate;
 
+use super::audit_log::ExportFormat;
+use super::device_telemetry::DeviceTelemetry;
 use super::device_token::EnrollmentStatus;
 
 /// Device command types.
@@ -146,6 +148,32 @@ pub struct FleetDeviceQuery {
     pub order: Option<SortOrder>,
 }
 
+/// Fleet device export query parameters.
+///
+/// Accepts the same filters and sorting as [`FleetDeviceQuery`]; all
+/// matching devices are exported.
+#[derive(Debug, Clone, Default, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct FleetDeviceExportQuery {
+    /// Output format (json, csv or xlsx)
+    pub format: Option<ExportFormat>,
+    /// Filter by enrollment status
+    pub status: Option<EnrollmentStatus>,
+    /// Filter by group ID
+    pub group_id: Option<String>,
+    /// Filter by policy ID
+    pub policy_id: Option<Uuid>,
+    /// Filter by assignment status
+    pub assigned: Option<bool>,
+    /// Search by name or UUID
+    #[validate(length(max = 100))]
+    pub search: Option<String>,
+    /// Sort field
+    pub sort: Option<FleetSortField>,
+    /// Sort order
+    pub order: Option<SortOrder>,
+}
+
 /// Sort fields for fleet device listing.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -239,10 +267,24 @@ pub struct FleetDeviceItem {
     pub last_seen_at: Option<DateTime<Utc>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_location: Option<FleetLastLocation>,
+    /// Latest battery and connectivity sample
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub telemetry: Option<DeviceTelemetry>,
     pub enrolled_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
 }
 
+/// Fleet device detail response.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct FleetDeviceDetailResponse {
+    #[serde(flatten)]
+    pub device: FleetDeviceItem,
+    /// API usage counters; shown to admins and owners only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_usage: Option<DeviceApiUsageResponse>,
+}
+
 /// Pagination info in fleet response.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -447,6 +489,84 @@ pub struct DeviceCommandHistoryResponse {
     pub pagination: DeviceCommandHistoryPagination,
 }
 
+/// Class of device-facing API endpoints used for usage tracking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiEndpointClass {
+    /// Location, movement and geofence event uploads.
+    Ingest,
+    /// Device registration and settings synchronization.
+    Sync,
+    /// Device command polling and acknowledgement.
+    Commands,
+}
+
+impl ApiEndpointClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ingest => "ingest",
+            Self::Sync => "sync",
+            Self::Commands => "commands",
+        }
+    }
+}
+
+impl std::fmt::Display for ApiEndpointClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for ApiEndpointClass {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ingest" => Ok(Self::Ingest),
+            "sync" => Ok(Self::Sync),
+            "commands" => Ok(Self::Commands),
+            _ => Err(format!("Invalid endpoint class: {}", s)),
+        }
+    }
+}
+
+/// API usage of a device for one endpoint class.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DeviceApiUsageItem {
+    pub endpoint_class: ApiEndpointClass,
+    /// Total requests since tracking started.
+    pub request_count: i64,
+    /// Requests in the current clock hour.
+    pub current_hour_count: i64,
+    pub last_request_at: DateTime<Utc>,
+}
+
+/// Response for a fleet device's API usage.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DeviceApiUsageResponse {
+    pub device_id: i64,
+    pub total_requests: i64,
+    pub current_hour_requests: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_activity_at: Option<DateTime<Utc>>,
+    pub by_endpoint_class: Vec<DeviceApiUsageItem>,
+}
+
+impl DeviceApiUsageResponse {
+    /// Build the response from per-class usage, computing the totals.
+    pub fn new(device_id: i64, by_endpoint_class: Vec<DeviceApiUsageItem>) -> Self {
+        Self {
+            device_id,
+            total_requests: by_endpoint_class.iter().map(|u| u.request_count).sum(),
+            current_hour_requests: by_endpoint_class.iter().map(|u| u.current_hour_count).sum(),
+            last_activity_at: by_endpoint_class.iter().map(|u| u.last_request_at).max(),
+            by_endpoint_class,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -477,6 +597,21 @@ mod tests {
         assert!("invalid".parse::<DeviceCommandStatus>().is_err());
     }
 
+    #[test]
+    fn test_fleet_device_export_query_deserialization() {
+        let query: FleetDeviceExportQuery =
+            serde_json::from_str(r#"{"format": "xlsx", "assigned": true}"#).unwrap();
+        assert_eq!(query.format, Some(ExportFormat::Xlsx));
+        assert_eq!(query.assigned, Some(true));
+        assert!(query.validate().is_ok());
+
+        let query = FleetDeviceExportQuery {
+            search: Some("x".repeat(101)),
+            ..Default::default()
+        };
+        assert!(query.validate().is_err());
+    }
+
     #[test]
     fn test_fleet_device_query_validation() {
         let query = FleetDeviceQuery {
@@ -534,4 +669,53 @@ mod tests {
         assert_eq!(summary.assigned, 0);
         assert_eq!(summary.unassigned, 0);
     }
+
+    #[test]
+    fn test_api_endpoint_class_roundtrip() {
+        for class in [
+            ApiEndpointClass::Ingest,
+            ApiEndpointClass::Sync,
+            ApiEndpointClass::Commands,
+        ] {
+            assert_eq!(class.as_str().parse::<ApiEndpointClass>().unwrap(), class);
+        }
+        assert!("admin".parse::<ApiEndpointClass>().is_err());
+    }
+
+    #[test]
+    fn test_device_api_usage_response_totals() {
+        let earlier = Utc::now() - chrono::Duration::hours(2);
+        let later = Utc::now();
+        let response = DeviceApiUsageResponse::new(
+            7,
+            vec![
+                DeviceApiUsageItem {
+                    endpoint_class: ApiEndpointClass::Ingest,
+                    request_count: 120,
+                    current_hour_count: 30,
+                    last_request_at: later,
+                },
+                DeviceApiUsageItem {
+                    endpoint_class: ApiEndpointClass::Sync,
+                    request_count: 5,
+                    current_hour_count: 0,
+                    last_request_at: earlier,
+                },
+            ],
+        );
+
+        assert_eq!(response.total_requests, 125);
+        assert_eq!(response.current_hour_requests, 30);
+        assert_eq!(response.last_activity_at, Some(later));
+    }
+
+    #[test]
+    fn test_device_api_usage_response_empty() {
+        let response = DeviceApiUsageResponse::new(1, vec![]);
+        assert_eq!(response.total_requests, 0);
+        assert!(response.last_activity_at.is_none());
+
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(!json.contains("last_activity_at"));
+    }
 }
@@ -5,6 +5,20 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::Validate;
 
+use super::weekly_schedule::WeeklySchedule;
+
+/// Maximum geofences per bulk create request.
+pub const MAX_BULK_GEOFENCES: usize = 100;
+
+/// Maximum geofence cool-down, in seconds (one day).
+pub const MAX_GEOFENCE_COOLDOWN_SECONDS: i32 = 86_400;
+
+/// Maximum arrival alert lead time, in minutes.
+pub const MAX_ARRIVAL_ALERT_MINUTES: i32 = 120;
+
+/// Maximum geofence priority.
+pub const MAX_GEOFENCE_PRIORITY: i32 = 100;
+
 /// Represents a geofence in the system.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -19,10 +33,43 @@ pub struct Geofence {
     pub event_types: Vec<GeofenceEventType>,
     pub active: bool,
     pub metadata: Option<serde_json::Value>,
+    /// When set, the geofence only produces events inside these windows.
+    pub schedule: Option<WeeklySchedule>,
+    /// Organization template the geofence was stamped from, if any.
+    pub template_id: Option<Uuid>,
+    /// Minimum time between recorded enter/exit events; 0 disables it.
+    pub cooldown_seconds: i32,
+    /// Announce arrivals expected within this many minutes; 0 disables it.
+    pub arrival_alert_minutes: i32,
+    /// Precedence among overlapping geofences of the device; higher wins.
+    pub priority: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+impl Geofence {
+    /// Whether the geofence's schedule, if any, covers the given instant.
+    pub fn is_scheduled_at(&self, at: DateTime<Utc>) -> bool {
+        self.schedule
+            .as_ref()
+            .is_none_or(|schedule| schedule.is_active_at(at))
+    }
+
+    /// Whether an enter or exit at `timestamp_ms` falls within the cool-down
+    /// of the geofence's previous enter or exit at `previous_ms`.
+    pub fn is_in_cooldown(&self, previous_ms: i64, timestamp_ms: i64) -> bool {
+        self.cooldown_seconds > 0
+            && (timestamp_ms - previous_ms).abs() < i64::from(self.cooldown_seconds) * 1000
+    }
+
+    /// Whether arrivals expected within `eta_seconds` should be announced.
+    pub fn should_announce_arrival(&self, eta_seconds: f64) -> bool {
+        self.arrival_alert_minutes > 0
+            && self.event_types.contains(&GeofenceEventType::Enter)
+            && eta_seconds <= f64::from(self.arrival_alert_minutes) * 60.0
+    }
+}
+
 /// Supported geofence event types.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -54,7 +101,7 @@ impl GeofenceEventType {
 }
 
 /// Default event types for new geofences.
-fn default_event_types() -> Vec<GeofenceEventType> {
+pub(super) fn default_event_types() -> Vec<GeofenceEventType> {
     vec![GeofenceEventType::Enter, GeofenceEventType::Exit]
 }
 
@@ -64,7 +111,7 @@ fn default_active() -> bool {
 }
 
 /// Request payload for creating a geofence.
-#[derive(Debug, Clone, Deserialize, Validate)]
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 #[serde(rename_all = "snake_case")]
 pub struct CreateGeofenceRequest {
     pub device_id: Uuid,
@@ -92,6 +139,36 @@ pub struct CreateGeofenceRequest {
     pub active: bool,
 
     pub metadata: Option<serde_json::Value>,
+
+    /// Weekly windows during which the geofence is active; always if omitted.
+    pub schedule: Option<WeeklySchedule>,
+
+    /// Enter/exit events within this many seconds of the previous one are
+    /// dropped, so a device bouncing on the boundary does not spam
+    /// notifications.
+    #[serde(default)]
+    #[validate(range(
+        min = 0,
+        max = 86400,
+        message = "Cooldown must be between 0 and 86400 seconds"
+    ))]
+    pub cooldown_seconds: i32,
+
+    /// Group members are told the device is arriving when its estimated
+    /// time of arrival drops below this many minutes; 0 disables it.
+    #[serde(default)]
+    #[validate(range(
+        min = 0,
+        max = 120,
+        message = "Arrival alert must be between 0 and 120 minutes"
+    ))]
+    pub arrival_alert_minutes: i32,
+
+    /// Where geofences of the device overlap, only events of the one with
+    /// the highest priority are delivered.
+    #[serde(default)]
+    #[validate(range(min = 0, max = 100, message = "Priority must be between 0 and 100"))]
+    pub priority: i32,
 }
 
 /// Request payload for updating a geofence (partial update).
@@ -119,6 +196,29 @@ pub struct UpdateGeofenceRequest {
     pub active: Option<bool>,
 
     pub metadata: Option<serde_json::Value>,
+
+    pub schedule: Option<WeeklySchedule>,
+
+    /// Remove the schedule so the geofence is always active.
+    #[serde(default)]
+    pub clear_schedule: bool,
+
+    #[validate(range(
+        min = 0,
+        max = 86400,
+        message = "Cooldown must be between 0 and 86400 seconds"
+    ))]
+    pub cooldown_seconds: Option<i32>,
+
+    #[validate(range(
+        min = 0,
+        max = 120,
+        message = "Arrival alert must be between 0 and 120 minutes"
+    ))]
+    pub arrival_alert_minutes: Option<i32>,
+
+    #[validate(range(min = 0, max = 100, message = "Priority must be between 0 and 100"))]
+    pub priority: Option<i32>,
 }
 
 /// Response payload for geofence operations.
@@ -135,6 +235,13 @@ pub struct GeofenceResponse {
     pub active: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<WeeklySchedule>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template_id: Option<Uuid>,
+    pub cooldown_seconds: i32,
+    pub arrival_alert_minutes: i32,
+    pub priority: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -151,6 +258,11 @@ impl From<Geofence> for GeofenceResponse {
             event_types: g.event_types,
             active: g.active,
             metadata: g.metadata,
+            schedule: g.schedule,
+            template_id: g.template_id,
+            cooldown_seconds: g.cooldown_seconds,
+            arrival_alert_minutes: g.arrival_alert_minutes,
+            priority: g.priority,
             created_at: g.created_at,
             updated_at: g.updated_at,
         }
@@ -165,6 +277,59 @@ pub struct ListGeofencesResponse {
     pub total: usize,
 }
 
+/// Request payload for creating geofences in bulk.
+///
+/// Items are validated and created independently; an invalid item is
+/// reported in the response without failing the others.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct BulkCreateGeofencesRequest {
+    #[validate(length(min = 1, max = 100, message = "geofences must contain 1-100 items"))]
+    pub geofences: Vec<CreateGeofenceRequest>,
+}
+
+/// Response from bulk geofence creation.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct BulkCreateGeofencesResponse {
+    /// Total number of geofences processed.
+    pub processed: u32,
+
+    /// Number of geofences created.
+    pub created: u32,
+
+    /// Created geofences.
+    pub geofences: Vec<BulkCreatedGeofence>,
+
+    /// List of errors encountered.
+    pub errors: Vec<BulkGeofenceError>,
+}
+
+/// Geofence created by a bulk request.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct BulkCreatedGeofence {
+    /// Row number (1-indexed) of the item.
+    pub row: usize,
+
+    #[serde(flatten)]
+    pub geofence: GeofenceResponse,
+}
+
+/// Error encountered during bulk geofence creation.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct BulkGeofenceError {
+    /// Row number (1-indexed) where error occurred.
+    pub row: usize,
+
+    /// Device the geofence was for.
+    pub device_id: Uuid,
+
+    /// Error message.
+    pub error: String,
+}
+
 /// Query parameters for listing geofences.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -289,6 +454,11 @@ mod tests {
             event_types: vec![GeofenceEventType::Enter],
             active: true,
             metadata: None,
+            schedule: None,
+            template_id: None,
+            cooldown_seconds: 0,
+            arrival_alert_minutes: 0,
+            priority: 0,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -301,10 +471,140 @@ mod tests {
         assert!(!json.contains("\"metadata\":null"));
     }
 
+    #[test]
+    fn test_bulk_create_request_validation() {
+        let item = serde_json::json!({
+            "device_id": "550e8400-e29b-41d4-a716-446655440000",
+            "name": "Depot",
+            "latitude": 48.15,
+            "longitude": 17.11,
+            "radius_meters": 200.0
+        });
+
+        let request: BulkCreateGeofencesRequest =
+            serde_json::from_value(serde_json::json!({ "geofences": [] })).unwrap();
+        assert!(request.validate().is_err());
+
+        let request: BulkCreateGeofencesRequest = serde_json::from_value(
+            serde_json::json!({ "geofences": vec![item.clone(); MAX_BULK_GEOFENCES + 1] }),
+        )
+        .unwrap();
+        assert!(request.validate().is_err());
+
+        // Items are validated one by one when processed.
+        let mut invalid = item.clone();
+        invalid["radius_meters"] = serde_json::json!(5.0);
+        let request: BulkCreateGeofencesRequest =
+            serde_json::from_value(serde_json::json!({ "geofences": [item, invalid] })).unwrap();
+        assert!(request.validate().is_ok());
+        assert!(request.geofences[1].validate().is_err());
+    }
+
     #[test]
     fn test_list_geofences_query_defaults() {
         let json = r#"{"device_id": "550e8400-e29b-41d4-a716-446655440000"}"#;
         let query: ListGeofencesQuery = serde_json::from_str(json).unwrap();
         assert!(!query.include_inactive);
     }
+
+    #[test]
+    fn test_geofence_is_scheduled_at() {
+        use chrono::TimeZone;
+
+        let mut geofence = Geofence {
+            id: 1,
+            geofence_id: Uuid::new_v4(),
+            device_id: Uuid::new_v4(),
+            name: "School".to_string(),
+            latitude: 48.15,
+            longitude: 17.11,
+            radius_meters: 150.0,
+            event_types: default_event_types(),
+            active: true,
+            metadata: None,
+            schedule: None,
+            template_id: None,
+            cooldown_seconds: 0,
+            arrival_alert_minutes: 0,
+            priority: 0,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        // 2024-01-01 is a Monday
+        let monday_morning = Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap();
+        let saturday_morning = Utc.with_ymd_and_hms(2024, 1, 6, 9, 0, 0).unwrap();
+        assert!(geofence.is_scheduled_at(saturday_morning));
+
+        geofence.schedule = WeeklySchedule::from_value(&serde_json::json!({
+            "windows": [{
+                "days": ["mon", "tue", "wed", "thu", "fri"],
+                "start": "08:00",
+                "end": "15:00"
+            }]
+        }))
+        .unwrap();
+        assert!(geofence.is_scheduled_at(monday_morning));
+        assert!(!geofence.is_scheduled_at(saturday_morning));
+    }
+
+    #[test]
+    fn test_geofence_is_in_cooldown() {
+        let mut geofence = Geofence {
+            id: 1,
+            geofence_id: Uuid::new_v4(),
+            device_id: Uuid::new_v4(),
+            name: "School".to_string(),
+            latitude: 48.15,
+            longitude: 17.11,
+            radius_meters: 150.0,
+            event_types: default_event_types(),
+            active: true,
+            metadata: None,
+            schedule: None,
+            template_id: None,
+            cooldown_seconds: 0,
+            arrival_alert_minutes: 0,
+            priority: 0,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        assert!(!geofence.is_in_cooldown(0, 1_000));
+
+        geofence.cooldown_seconds = 60;
+        assert!(geofence.is_in_cooldown(0, 59_999));
+        assert!(geofence.is_in_cooldown(59_999, 0));
+        assert!(!geofence.is_in_cooldown(0, 60_000));
+    }
+
+    #[test]
+    fn test_geofence_should_announce_arrival() {
+        let mut geofence = Geofence {
+            id: 1,
+            geofence_id: Uuid::new_v4(),
+            device_id: Uuid::new_v4(),
+            name: "Home".to_string(),
+            latitude: 48.15,
+            longitude: 17.11,
+            radius_meters: 150.0,
+            event_types: default_event_types(),
+            active: true,
+            metadata: None,
+            schedule: None,
+            template_id: None,
+            cooldown_seconds: 0,
+            arrival_alert_minutes: 0,
+            priority: 0,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        assert!(!geofence.should_announce_arrival(0.0));
+
+        geofence.arrival_alert_minutes = 5;
+        assert!(geofence.should_announce_arrival(300.0));
+        assert!(!geofence.should_announce_arrival(300.5));
+
+        // Only geofences subscribed to enter events announce arrivals.
+        geofence.event_types = vec![GeofenceEventType::Exit];
+        assert!(!geofence.should_announce_arrival(60.0));
+    }
 }
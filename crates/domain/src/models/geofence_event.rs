@@ -5,6 +5,7 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use shared::pagination::CountMode;
 use uuid::Uuid;
 use validator::Validate;
 
@@ -53,6 +54,35 @@ impl std::fmt::Display for GeofenceTransitionType {
     }
 }
 
+/// Who detected a geofence event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GeofenceEventSource {
+    /// Reported by the device's client.
+    #[default]
+    Client,
+    /// Derived by the server from the device's location stream.
+    Server,
+}
+
+impl GeofenceEventSource {
+    /// Convert to string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Client => "client",
+            Self::Server => "server",
+        }
+    }
+
+    /// Parse from string; unknown values are treated as client-reported.
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "server" => Self::Server,
+            _ => Self::Client,
+        }
+    }
+}
+
 /// Domain model for a geofence event.
 #[derive(Debug, Clone)]
 pub struct GeofenceEvent {
@@ -67,6 +97,9 @@ pub struct GeofenceEvent {
     pub longitude: f64,
     pub webhook_delivered: bool,
     pub webhook_response_code: Option<i32>,
+    pub source: GeofenceEventSource,
+    /// Geofence the event resolved to among overlapping geofences.
+    pub resolved_geofence_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -122,6 +155,9 @@ pub struct ListGeofenceEventsQuery {
     pub geofence_id: Option<Uuid>,
     #[serde(default = "default_limit")]
     pub limit: i64,
+    /// How to compute `total`: none, estimated or exact (default).
+    #[serde(default)]
+    pub count: CountMode,
 }
 
 fn default_limit() -> i64 {
@@ -144,6 +180,11 @@ pub struct GeofenceEventResponse {
     pub webhook_delivered: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub webhook_response_code: Option<i32>,
+    pub source: GeofenceEventSource,
+    /// Differs from `geofence_id` when a higher-priority overlapping
+    /// geofence won and the event was not delivered.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_geofence_id: Option<Uuid>,
 }
 
 impl From<GeofenceEvent> for GeofenceEventResponse {
@@ -161,6 +202,8 @@ impl From<GeofenceEvent> for GeofenceEventResponse {
             longitude: event.longitude,
             webhook_delivered: event.webhook_delivered,
             webhook_response_code: event.webhook_response_code,
+            source: event.source,
+            resolved_geofence_id: event.resolved_geofence_id,
         }
     }
 }
@@ -170,7 +213,11 @@ impl From<GeofenceEvent> for GeofenceEventResponse {
 #[serde(rename_all = "snake_case")]
 pub struct ListGeofenceEventsResponse {
     pub events: Vec<GeofenceEventResponse>,
-    pub total: i64,
+    /// Omitted for `count=none`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<i64>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub total_estimated: bool,
 }
 
 impl GeofenceEvent {
@@ -189,6 +236,8 @@ impl GeofenceEvent {
         longitude: f64,
         webhook_delivered: bool,
         webhook_response_code: Option<i32>,
+        source: &str,
+        resolved_geofence_id: Option<Uuid>,
         created_at: DateTime<Utc>,
     ) -> Self {
         Self {
@@ -204,6 +253,8 @@ impl GeofenceEvent {
             longitude,
             webhook_delivered,
             webhook_response_code,
+            source: GeofenceEventSource::parse(source),
+            resolved_geofence_id,
             created_at,
         }
     }
@@ -303,6 +354,17 @@ mod tests {
         assert!(query.geofence_id.is_none());
     }
 
+    #[test]
+    fn test_list_query_count_mode() {
+        let json = r#"{"deviceId": "550e8400-e29b-41d4-a716-446655440000"}"#;
+        let query: ListGeofenceEventsQuery = serde_json::from_str(json).unwrap();
+        assert_eq!(query.count, CountMode::Exact);
+
+        let json = r#"{"deviceId": "550e8400-e29b-41d4-a716-446655440000", "count": "none"}"#;
+        let query: ListGeofenceEventsQuery = serde_json::from_str(json).unwrap();
+        assert_eq!(query.count, CountMode::None);
+    }
+
     #[test]
     fn test_response_serialization() {
         let response = GeofenceEventResponse {
@@ -316,10 +378,14 @@ mod tests {
             longitude: -122.4194,
             webhook_delivered: true,
             webhook_response_code: Some(200),
+            source: GeofenceEventSource::Server,
+            resolved_geofence_id: Some(Uuid::nil()),
         };
 
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("\"event_type\":\"enter\""));
+        assert!(json.contains("\"source\":\"server\""));
+        assert!(json.contains("\"resolved_geofence_id\":\"00000000-0000-0000-0000-000000000000\""));
         assert!(json.contains("\"webhook_delivered\":true"));
     }
 }
@@ -0,0 +1,205 @@
+//! Geofence template domain models.
+//!
+//! A template is an organization-level geofence definition, e.g. "Office"
+//! or "Warehouse", that org admins stamp onto many devices at once. Each
+//! stamped geofence keeps a link to its template, so later template edits
+//! can be propagated to every device carrying it.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use super::geofence::{default_event_types, GeofenceEventType, GeofenceResponse};
+use super::weekly_schedule::WeeklySchedule;
+
+/// Maximum number of devices a single template application can target.
+pub const MAX_TEMPLATE_APPLY_DEVICES: i64 = 500;
+
+/// A geofence template.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GeofenceTemplate {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub radius_meters: f32,
+    pub event_types: Vec<GeofenceEventType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<WeeklySchedule>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Value>,
+    /// Number of device geofences stamped from the template.
+    pub geofence_count: i64,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+fn default_propagate() -> bool {
+    true
+}
+
+/// Request to create or replace a geofence template.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct SaveGeofenceTemplateRequest {
+    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    pub name: String,
+
+    #[validate(length(max = 500, message = "Description must be at most 500 characters"))]
+    pub description: Option<String>,
+
+    #[validate(custom(function = "shared::validation::validate_latitude"))]
+    pub latitude: f64,
+
+    #[validate(custom(function = "shared::validation::validate_longitude"))]
+    pub longitude: f64,
+
+    #[validate(range(
+        min = 20.0,
+        max = 50000.0,
+        message = "Radius must be between 20 and 50000 meters"
+    ))]
+    pub radius_meters: f32,
+
+    #[serde(default = "default_event_types")]
+    #[validate(length(min = 1, message = "At least one event type required"))]
+    pub event_types: Vec<GeofenceEventType>,
+
+    /// Weekly windows during which stamped geofences are active; always if omitted.
+    pub schedule: Option<WeeklySchedule>,
+
+    pub metadata: Option<serde_json::Value>,
+
+    /// On replace, also update the geofences stamped from the template.
+    #[serde(default = "default_propagate")]
+    pub propagate: bool,
+}
+
+/// Response for listing geofence templates.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ListGeofenceTemplatesResponse {
+    pub data: Vec<GeofenceTemplate>,
+}
+
+/// Response for replacing a geofence template.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct UpdateGeofenceTemplateResponse {
+    #[serde(flatten)]
+    pub template: GeofenceTemplate,
+    /// Number of stamped geofences updated from the template.
+    pub propagated: u64,
+}
+
+/// Request to stamp a template onto devices.
+///
+/// Targets the listed devices plus every active device of the listed
+/// groups; at least one device or group must be given.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ApplyGeofenceTemplateRequest {
+    #[serde(default)]
+    pub device_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub group_ids: Vec<String>,
+}
+
+impl ApplyGeofenceTemplateRequest {
+    /// Whether the request targets nothing.
+    pub fn is_empty(&self) -> bool {
+        self.device_ids.is_empty() && self.group_ids.is_empty()
+    }
+}
+
+/// Outcome of stamping a template onto devices.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ApplyGeofenceTemplateResponse {
+    pub template_id: Uuid,
+    /// Devices that received a geofence from the template.
+    pub created: Vec<Uuid>,
+    /// Devices that already had a geofence from the template.
+    pub already_applied: Vec<Uuid>,
+    /// Devices skipped because they reached the per-device geofence limit.
+    pub limit_reached: Vec<Uuid>,
+}
+
+/// Query parameters for deleting a geofence template.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DeleteGeofenceTemplateQuery {
+    /// Also delete the stamped geofences instead of keeping them standalone.
+    #[serde(default)]
+    pub delete_geofences: bool,
+}
+
+/// Response for listing the geofences stamped from a template.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ListTemplateGeofencesResponse {
+    pub data: Vec<GeofenceResponse>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_save_request_defaults() {
+        let request: SaveGeofenceTemplateRequest = serde_json::from_value(json!({
+            "name": "Office",
+            "latitude": 48.15,
+            "longitude": 17.11,
+            "radius_meters": 150.0
+        }))
+        .unwrap();
+
+        assert!(request.validate().is_ok());
+        assert!(request.propagate);
+        assert_eq!(
+            request.event_types,
+            vec![GeofenceEventType::Enter, GeofenceEventType::Exit]
+        );
+    }
+
+    #[test]
+    fn test_save_request_validation() {
+        let request: SaveGeofenceTemplateRequest = serde_json::from_value(json!({
+            "name": "Warehouse",
+            "latitude": 48.15,
+            "longitude": 17.11,
+            "radius_meters": 10.0
+        }))
+        .unwrap();
+        assert!(request.validate().is_err());
+
+        let request: SaveGeofenceTemplateRequest = serde_json::from_value(json!({
+            "name": "Warehouse",
+            "latitude": 48.15,
+            "longitude": 17.11,
+            "radius_meters": 200.0,
+            "event_types": []
+        }))
+        .unwrap();
+        assert!(request.validate().is_err());
+    }
+
+    #[test]
+    fn test_apply_request_targets() {
+        let request: ApplyGeofenceTemplateRequest = serde_json::from_value(json!({})).unwrap();
+        assert!(request.is_empty());
+
+        let request: ApplyGeofenceTemplateRequest =
+            serde_json::from_value(json!({"group_ids": ["warehouse-staff"]})).unwrap();
+        assert!(!request.is_empty());
+        assert!(request.device_ids.is_empty());
+    }
+}
@@ -52,6 +52,21 @@ impl GroupRole {
     pub fn can_transfer_ownership(&self) -> bool {
         matches!(self, GroupRole::Owner)
     }
+
+    /// Returns true if this role can change the group's data retention
+    pub fn can_manage_retention(&self) -> bool {
+        matches!(self, GroupRole::Owner)
+    }
+
+    /// Returns true if this role can mint and revoke group API tokens
+    pub fn can_manage_api_tokens(&self) -> bool {
+        matches!(self, GroupRole::Owner)
+    }
+
+    /// Returns true if this role can export all of the group's data
+    pub fn can_export_data(&self) -> bool {
+        matches!(self, GroupRole::Owner)
+    }
 }
 
 impl FromStr for GroupRole {
@@ -89,6 +104,8 @@ pub struct Group {
     pub created_by: Uuid,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Parent group; None for top-level groups.
+    pub parent_group_id: Option<Uuid>,
 }
 
 /// Represents a user's membership in a group.
@@ -108,11 +125,8 @@ pub struct GroupMembership {
 #[derive(Debug, Clone, Deserialize, Validate)]
 #[serde(rename_all = "snake_case")]
 pub struct CreateGroupRequest {
-    #[validate(length(
-        min = 1,
-        max = 100,
-        message = "Name must be between 1 and 100 characters"
-    ))]
+    #[serde(deserialize_with = "shared::text::deserialize_line")]
+    #[validate(custom(function = "shared::text::validate_name"))]
     pub name: String,
 
     #[validate(length(max = 500, message = "Description must be at most 500 characters"))]
@@ -129,11 +143,8 @@ pub struct CreateGroupRequest {
 #[derive(Debug, Clone, Deserialize, Validate)]
 #[serde(rename_all = "snake_case")]
 pub struct UpdateGroupRequest {
-    #[validate(length(
-        min = 1,
-        max = 100,
-        message = "Name must be between 1 and 100 characters"
-    ))]
+    #[serde(default, deserialize_with = "shared::text::deserialize_optional_line")]
+    #[validate(custom(function = "shared::text::validate_name"))]
     pub name: Option<String>,
 
     #[validate(length(max = 500, message = "Description must be at most 500 characters"))]
@@ -158,6 +169,8 @@ pub struct GroupSummary {
     pub device_count: i64,
     pub your_role: GroupRole,
     pub joined_at: DateTime<Utc>,
+    /// Parent group; None for top-level groups.
+    pub parent_group_id: Option<Uuid>,
     /// Whether the requesting user's device is assigned to this group.
     /// True if any of the user's devices (or specific device_id if provided) is in this group.
     pub has_current_device: bool,
@@ -180,6 +193,8 @@ pub struct GroupDetail {
     pub created_by: Uuid,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Parent group; None for top-level groups.
+    pub parent_group_id: Option<Uuid>,
     pub your_role: GroupRole,
     pub your_membership: MembershipInfo,
 }
@@ -230,6 +245,48 @@ pub struct ListGroupsResponse {
     pub count: usize,
 }
 
+// ============================================================================
+// Group hierarchy
+// ============================================================================
+
+/// Deepest allowed group hierarchy, in levels, counting the top-level group.
+pub const MAX_GROUP_DEPTH: usize = 4;
+
+/// Whether a group whose subtree is `subtree_height` levels tall (1 for a
+/// group without sub-groups) can be nested under a parent that sits
+/// `parent_depth` levels deep (1 for a top-level parent).
+pub fn fits_group_hierarchy(parent_depth: usize, subtree_height: usize) -> bool {
+    parent_depth + subtree_height <= MAX_GROUP_DEPTH
+}
+
+/// Request payload for moving a group under a parent group.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SetParentGroupRequest {
+    /// New parent group; null makes the group top-level.
+    #[serde(default)]
+    pub parent_group_id: Option<Uuid>,
+}
+
+/// A direct sub-group of a group.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SubgroupSummary {
+    pub id: Uuid,
+    pub name: String,
+    pub slug: String,
+    pub icon_emoji: Option<String>,
+    pub member_count: i64,
+}
+
+/// Response for listing a group's sub-groups.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ListSubgroupsResponse {
+    pub data: Vec<SubgroupSummary>,
+    pub count: usize,
+}
+
 // ============================================================================
 // Membership DTOs (Story 11.2)
 // ============================================================================
@@ -275,6 +332,9 @@ pub struct MemberDeviceInfo {
     pub is_online: bool,
     /// Last known location
     pub last_location: Option<LastLocationInfo>,
+    /// Whether the owner's sharing schedule is outside its windows
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub sharing_paused: bool,
 }
 
 /// Last location info for device.
@@ -295,6 +355,9 @@ pub struct MemberResponse {
     pub role: GroupRole,
     pub joined_at: DateTime<Utc>,
     pub invited_by: Option<Uuid>,
+    /// Alias for the member set by a group admin, visible only in this group.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nickname: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub devices: Option<Vec<MemberDeviceInfo>>,
     /// Number of devices this member has in the group (Story UGM-3.6)
@@ -340,6 +403,39 @@ pub struct UpdateRoleResponse {
     pub updated_at: DateTime<Utc>,
 }
 
+// ============================================================================
+// Nickname DTOs
+// ============================================================================
+
+/// Request to set a member's or device's nickname within a group.
+///
+/// A null nickname clears it.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct SetNicknameRequest {
+    #[serde(deserialize_with = "shared::text::deserialize_optional_line")]
+    #[validate(custom(function = "shared::text::validate_nickname"))]
+    pub nickname: Option<String>,
+}
+
+/// Response after setting a member's nickname.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct MemberNicknameResponse {
+    pub group_id: Uuid,
+    pub user_id: Uuid,
+    pub nickname: Option<String>,
+}
+
+/// Response after setting a device's nickname.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DeviceNicknameResponse {
+    pub group_id: Uuid,
+    pub device_id: Uuid,
+    pub nickname: Option<String>,
+}
+
 // ============================================================================
 // Ownership Transfer DTOs (Story 11.6)
 // ============================================================================
@@ -362,6 +458,45 @@ pub struct TransferOwnershipResponse {
     pub transferred_at: DateTime<Utc>,
 }
 
+/// Location retention policy of a group.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GroupRetentionPolicy {
+    pub group_id: Uuid,
+    /// Group-specific override; `None` means the system default applies.
+    pub location_retention_days: Option<u32>,
+    /// Retention currently applied to the group's locations.
+    pub effective_location_retention_days: u32,
+    /// Largest value a group owner may configure.
+    pub max_location_retention_days: u32,
+}
+
+impl GroupRetentionPolicy {
+    pub fn new(
+        group_id: Uuid,
+        location_retention_days: Option<u32>,
+        default_days: u32,
+        max_days: u32,
+    ) -> Self {
+        Self {
+            group_id,
+            location_retention_days,
+            effective_location_retention_days: location_retention_days.unwrap_or(default_days),
+            max_location_retention_days: max_days,
+        }
+    }
+}
+
+/// Request payload for setting a group's location retention.
+///
+/// A `null` value clears the override and restores the system default.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct UpdateGroupRetentionRequest {
+    #[validate(range(min = 1, message = "Location retention must be at least 1 day"))]
+    pub location_retention_days: Option<u32>,
+}
+
 /// Helper function to generate URL-safe slug from name.
 pub fn generate_slug(name: &str) -> String {
     name.to_lowercase()
@@ -418,6 +553,8 @@ mod tests {
         assert!(GroupRole::Owner.can_view_locations());
         assert!(GroupRole::Owner.can_delete_group());
         assert!(GroupRole::Owner.can_transfer_ownership());
+        assert!(GroupRole::Owner.can_manage_api_tokens());
+        assert!(GroupRole::Owner.can_export_data());
 
         // Admin can manage but not delete/transfer
         assert!(GroupRole::Admin.can_manage_group());
@@ -425,6 +562,8 @@ mod tests {
         assert!(GroupRole::Admin.can_view_locations());
         assert!(!GroupRole::Admin.can_delete_group());
         assert!(!GroupRole::Admin.can_transfer_ownership());
+        assert!(!GroupRole::Admin.can_manage_api_tokens());
+        assert!(!GroupRole::Admin.can_export_data());
 
         // Member can only view
         assert!(!GroupRole::Member.can_manage_group());
@@ -432,6 +571,8 @@ mod tests {
         assert!(GroupRole::Member.can_view_locations());
         assert!(!GroupRole::Member.can_delete_group());
         assert!(!GroupRole::Member.can_transfer_ownership());
+        assert!(!GroupRole::Member.can_manage_api_tokens());
+        assert!(!GroupRole::Member.can_export_data());
 
         // Viewer can only view
         assert!(!GroupRole::Viewer.can_manage_group());
@@ -439,6 +580,8 @@ mod tests {
         assert!(GroupRole::Viewer.can_view_locations());
         assert!(!GroupRole::Viewer.can_delete_group());
         assert!(!GroupRole::Viewer.can_transfer_ownership());
+        assert!(!GroupRole::Viewer.can_manage_api_tokens());
+        assert!(!GroupRole::Viewer.can_export_data());
     }
 
     #[test]
@@ -477,4 +620,74 @@ mod tests {
         };
         assert!(too_many_devices.validate().is_err());
     }
+
+    #[test]
+    fn test_fits_group_hierarchy() {
+        // A leaf group under a top-level parent
+        assert!(fits_group_hierarchy(1, 1));
+        // A leaf group at the deepest allowed level
+        assert!(fits_group_hierarchy(MAX_GROUP_DEPTH - 1, 1));
+        assert!(!fits_group_hierarchy(MAX_GROUP_DEPTH, 1));
+        // A group with sub-groups takes them along
+        assert!(!fits_group_hierarchy(2, MAX_GROUP_DEPTH - 1));
+    }
+
+    #[test]
+    fn test_set_parent_group_request() {
+        let request: SetParentGroupRequest =
+            serde_json::from_str(r#"{"parent_group_id": "00000000-0000-0000-0000-000000000001"}"#)
+                .unwrap();
+        assert!(request.parent_group_id.is_some());
+
+        let request: SetParentGroupRequest =
+            serde_json::from_str(r#"{"parent_group_id": null}"#).unwrap();
+        assert!(request.parent_group_id.is_none());
+    }
+
+    #[test]
+    fn test_set_nickname_request_validation() {
+        let request: SetNicknameRequest =
+            serde_json::from_str(r#"{"nickname": "  Mom's   phone "}"#).unwrap();
+        assert_eq!(request.nickname.as_deref(), Some("Mom's phone"));
+        assert!(request.validate().is_ok());
+
+        let request: SetNicknameRequest = serde_json::from_str(r#"{"nickname": null}"#).unwrap();
+        assert!(request.nickname.is_none());
+        assert!(request.validate().is_ok());
+
+        let request: SetNicknameRequest = serde_json::from_str(r#"{"nickname": "   "}"#).unwrap();
+        assert!(request.validate().is_err());
+
+        let request = SetNicknameRequest {
+            nickname: Some("x".repeat(51)),
+        };
+        assert!(request.validate().is_err());
+    }
+
+    #[test]
+    fn test_group_retention_policy_effective_days() {
+        let policy = GroupRetentionPolicy::new(Uuid::nil(), None, 30, 365);
+        assert_eq!(policy.effective_location_retention_days, 30);
+
+        let policy = GroupRetentionPolicy::new(Uuid::nil(), Some(7), 30, 365);
+        assert_eq!(policy.location_retention_days, Some(7));
+        assert_eq!(policy.effective_location_retention_days, 7);
+        assert_eq!(policy.max_location_retention_days, 365);
+    }
+
+    #[test]
+    fn test_update_group_retention_request_validation() {
+        let request: UpdateGroupRetentionRequest =
+            serde_json::from_str(r#"{"location_retention_days": 90}"#).unwrap();
+        assert!(request.validate().is_ok());
+
+        let request: UpdateGroupRetentionRequest =
+            serde_json::from_str(r#"{"location_retention_days": null}"#).unwrap();
+        assert!(request.location_retention_days.is_none());
+        assert!(request.validate().is_ok());
+
+        let request: UpdateGroupRetentionRequest =
+            serde_json::from_str(r#"{"location_retention_days": 0}"#).unwrap();
+        assert!(request.validate().is_err());
+    }
 }
@@ -0,0 +1,146 @@
+//! Group API token domain models.
+//!
+//! Group owners mint tokens for read-only integrations, such as a
+//! wall-mounted dashboard showing family positions. A token only sees its own
+//! group's devices, minus devices in secret mode.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::models::device::DeviceSummary;
+
+/// Prefix of every group API token.
+pub const GROUP_API_TOKEN_PREFIX: &str = "pm_grp_";
+
+/// Maximum tokens a group can have that are not revoked.
+pub const MAX_GROUP_API_TOKENS_PER_GROUP: i64 = 10;
+
+/// Requests per minute allowed when a token is created without a limit.
+pub const DEFAULT_GROUP_API_TOKEN_RATE_LIMIT: u32 = 30;
+
+/// Highest per-token rate limit.
+pub const MAX_GROUP_API_TOKEN_RATE_LIMIT: u32 = 600;
+
+/// Request to create a group API token.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct CreateGroupApiTokenRequest {
+    /// Human-readable token name (1-100 chars)
+    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    pub name: String,
+
+    /// Requests per minute (1-600, default 30)
+    #[validate(range(
+        min = 1,
+        max = 600,
+        message = "Rate limit must be 1-600 requests/minute"
+    ))]
+    pub rate_limit_per_minute: Option<u32>,
+
+    /// Days until expiration (1-365, null = never expires)
+    #[validate(range(min = 1, max = 365, message = "Expiration must be 1-365 days"))]
+    pub expires_in_days: Option<i32>,
+}
+
+impl CreateGroupApiTokenRequest {
+    /// Requested rate limit, or the default.
+    pub fn rate_limit_per_minute(&self) -> u32 {
+        self.rate_limit_per_minute
+            .unwrap_or(DEFAULT_GROUP_API_TOKEN_RATE_LIMIT)
+    }
+}
+
+/// A group API token. The token itself is only included when created.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GroupApiTokenResponse {
+    pub id: Uuid,
+    pub group_id: Uuid,
+    pub name: String,
+    /// Token prefix for identification (e.g., "pm_grp_aBcD1234")
+    pub token_prefix: String,
+    /// The full token (shown ONCE, store securely)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    pub rate_limit_per_minute: u32,
+    pub last_used_at: Option<DateTime<Utc>>,
+    /// When the token expires (null = never)
+    pub expires_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Response for listing a group's API tokens.
+#[derive(Debug, Clone, Serialize)]
+pub struct ListGroupApiTokensResponse {
+    pub tokens: Vec<GroupApiTokenResponse>,
+}
+
+/// Devices visible to a group API token.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GroupIntegrationDevicesResponse {
+    pub group_id: Uuid,
+    pub group_name: String,
+    pub devices: Vec<DeviceSummary>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(
+        rate_limit_per_minute: Option<u32>,
+        expires_in_days: Option<i32>,
+    ) -> CreateGroupApiTokenRequest {
+        CreateGroupApiTokenRequest {
+            name: "Kitchen dashboard".to_string(),
+            rate_limit_per_minute,
+            expires_in_days,
+        }
+    }
+
+    #[test]
+    fn test_create_request_validation() {
+        assert!(request(None, None).validate().is_ok());
+        assert!(request(Some(600), Some(365)).validate().is_ok());
+        assert!(request(Some(0), None).validate().is_err());
+        assert!(request(Some(601), None).validate().is_err());
+        assert!(request(None, Some(0)).validate().is_err());
+
+        let mut unnamed = request(None, None);
+        unnamed.name = String::new();
+        assert!(unnamed.validate().is_err());
+    }
+
+    #[test]
+    fn test_default_rate_limit() {
+        assert_eq!(
+            request(None, None).rate_limit_per_minute(),
+            DEFAULT_GROUP_API_TOKEN_RATE_LIMIT
+        );
+        assert_eq!(request(Some(5), None).rate_limit_per_minute(), 5);
+    }
+
+    #[test]
+    fn test_token_omitted_when_absent() {
+        let now = Utc::now();
+        let response = GroupApiTokenResponse {
+            id: Uuid::new_v4(),
+            group_id: Uuid::new_v4(),
+            name: "Dashboard".to_string(),
+            token_prefix: "pm_grp_aBcD1234".to_string(),
+            token: None,
+            rate_limit_per_minute: 30,
+            last_used_at: None,
+            expires_at: None,
+            revoked_at: None,
+            created_at: now,
+        };
+        let json = serde_json::to_value(&response).unwrap();
+        assert!(json.get("token").is_none());
+        assert_eq!(json["token_prefix"], "pm_grp_aBcD1234");
+    }
+}
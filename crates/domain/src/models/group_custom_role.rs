@@ -0,0 +1,274 @@
+//! Custom group role domain models.
+//!
+//! Organizations define custom roles for the groups of their users, each
+//! granting a set of granular permissions. A custom role assigned to a
+//! member or viewer replaces the permissions of their built-in role; owners
+//! and admins always hold every permission.
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use super::group::GroupRole;
+
+/// A granular permission within a group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupPermission {
+    /// See the locations of the group's devices.
+    ViewLocations,
+    /// Change the group's geofence notification defaults.
+    ManageGeofences,
+    /// Remove other members' devices from the group.
+    ManageDevices,
+    /// Invite new members to the group.
+    InviteMembers,
+}
+
+impl GroupPermission {
+    /// All permissions, in bit order.
+    pub const ALL: [GroupPermission; 4] = [
+        Self::ViewLocations,
+        Self::ManageGeofences,
+        Self::ManageDevices,
+        Self::InviteMembers,
+    ];
+
+    /// Bit of the permission in a [`GroupPermissions`] set.
+    pub fn bit(&self) -> i32 {
+        match self {
+            Self::ViewLocations => 1,
+            Self::ManageGeofences => 1 << 1,
+            Self::ManageDevices => 1 << 2,
+            Self::InviteMembers => 1 << 3,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ViewLocations => "view_locations",
+            Self::ManageGeofences => "manage_geofences",
+            Self::ManageDevices => "manage_devices",
+            Self::InviteMembers => "invite_members",
+        }
+    }
+}
+
+impl fmt::Display for GroupPermission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Set of group permissions, stored as a bitset.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GroupPermissions(i32);
+
+impl GroupPermissions {
+    /// Permissions from stored bits; unknown bits are dropped.
+    pub fn from_bits(bits: i32) -> Self {
+        let known = GroupPermission::ALL.iter().fold(0, |acc, p| acc | p.bit());
+        Self(bits & known)
+    }
+
+    /// Every permission.
+    pub fn all() -> Self {
+        GroupPermission::ALL.into_iter().collect()
+    }
+
+    /// Permissions of a built-in role.
+    pub fn for_role(role: GroupRole) -> Self {
+        match role {
+            GroupRole::Owner | GroupRole::Admin => Self::all(),
+            GroupRole::Member | GroupRole::Viewer => {
+                [GroupPermission::ViewLocations].into_iter().collect()
+            }
+        }
+    }
+
+    /// Effective permissions of a member with `role` and an optional
+    /// custom role's permissions.
+    pub fn resolve(role: GroupRole, custom: Option<GroupPermissions>) -> Self {
+        match (role, custom) {
+            (GroupRole::Member | GroupRole::Viewer, Some(custom)) => custom,
+            _ => Self::for_role(role),
+        }
+    }
+
+    pub fn bits(&self) -> i32 {
+        self.0
+    }
+
+    pub fn contains(&self, permission: GroupPermission) -> bool {
+        self.0 & permission.bit() != 0
+    }
+
+    /// The permissions in the set, in bit order.
+    pub fn to_vec(&self) -> Vec<GroupPermission> {
+        GroupPermission::ALL
+            .into_iter()
+            .filter(|p| self.contains(*p))
+            .collect()
+    }
+}
+
+impl FromIterator<GroupPermission> for GroupPermissions {
+    fn from_iter<I: IntoIterator<Item = GroupPermission>>(iter: I) -> Self {
+        Self(iter.into_iter().fold(0, |acc, p| acc | p.bit()))
+    }
+}
+
+/// A custom group role defined by an organization.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GroupCustomRole {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub permissions: Vec<GroupPermission>,
+    /// Number of group members holding the role.
+    pub member_count: i64,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request to create or replace a custom group role.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct SaveGroupCustomRoleRequest {
+    #[serde(deserialize_with = "shared::text::deserialize_line")]
+    #[validate(custom(function = "shared::text::validate_role_name"))]
+    pub name: String,
+
+    #[validate(length(max = 500, message = "Description must be at most 500 characters"))]
+    pub description: Option<String>,
+
+    /// Permissions granted by the role; may be empty.
+    pub permissions: Vec<GroupPermission>,
+}
+
+/// Response for listing an organization's custom group roles.
+#[derive(Debug, Clone, Serialize)]
+pub struct ListGroupCustomRolesResponse {
+    pub data: Vec<GroupCustomRole>,
+}
+
+/// Request to assign a custom role to a group member.
+///
+/// `null` removes the member's custom role.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct AssignGroupCustomRoleRequest {
+    pub custom_role_id: Option<Uuid>,
+}
+
+/// A group member's effective permissions.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct MemberPermissionsResponse {
+    pub group_id: Uuid,
+    pub user_id: Uuid,
+    pub role: GroupRole,
+    pub custom_role_id: Option<Uuid>,
+    pub permissions: Vec<GroupPermission>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_permission_bits_roundtrip() {
+        let set: GroupPermissions = [
+            GroupPermission::ManageGeofences,
+            GroupPermission::InviteMembers,
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(set.bits(), 0b1010);
+        assert_eq!(GroupPermissions::from_bits(set.bits()), set);
+        assert_eq!(
+            set.to_vec(),
+            vec![
+                GroupPermission::ManageGeofences,
+                GroupPermission::InviteMembers
+            ]
+        );
+        assert_eq!(GroupPermissions::from_bits(0b1_0001).bits(), 1);
+    }
+
+    #[test]
+    fn test_role_defaults() {
+        let all = GroupPermissions::all();
+        assert_eq!(GroupPermissions::for_role(GroupRole::Owner), all);
+        assert_eq!(GroupPermissions::for_role(GroupRole::Admin), all);
+
+        let member = GroupPermissions::for_role(GroupRole::Member);
+        assert!(member.contains(GroupPermission::ViewLocations));
+        assert!(!member.contains(GroupPermission::InviteMembers));
+        assert_eq!(GroupPermissions::for_role(GroupRole::Viewer), member);
+    }
+
+    #[test]
+    fn test_resolve_custom_role() {
+        let custom: GroupPermissions = [GroupPermission::InviteMembers].into_iter().collect();
+
+        // A custom role replaces the permissions of members and viewers
+        let resolved = GroupPermissions::resolve(GroupRole::Member, Some(custom));
+        assert!(resolved.contains(GroupPermission::InviteMembers));
+        assert!(!resolved.contains(GroupPermission::ViewLocations));
+        assert_eq!(
+            GroupPermissions::resolve(GroupRole::Viewer, Some(custom)),
+            custom
+        );
+
+        // Owners and admins keep every permission
+        assert_eq!(
+            GroupPermissions::resolve(GroupRole::Admin, Some(custom)),
+            GroupPermissions::all()
+        );
+        assert_eq!(
+            GroupPermissions::resolve(GroupRole::Member, None),
+            GroupPermissions::for_role(GroupRole::Member)
+        );
+    }
+
+    #[test]
+    fn test_save_request() {
+        let request: SaveGroupCustomRoleRequest = serde_json::from_value(json!({
+            "name": "Dispatcher",
+            "permissions": ["view_locations", "manage_devices"]
+        }))
+        .unwrap();
+        assert!(request.validate().is_ok());
+        assert_eq!(request.permissions.len(), 2);
+
+        assert!(serde_json::from_value::<SaveGroupCustomRoleRequest>(json!({
+            "name": "Dispatcher",
+            "permissions": ["fly"]
+        }))
+        .is_err());
+
+        let request: SaveGroupCustomRoleRequest =
+            serde_json::from_value(json!({"name": "", "permissions": []})).unwrap();
+        assert!(request.validate().is_err());
+
+        // Names are normalized, so whitespace alone is empty
+        let request: SaveGroupCustomRoleRequest = serde_json::from_value(json!({
+            "name": "  Night \u{200B}shift  ",
+            "permissions": []
+        }))
+        .unwrap();
+        assert_eq!(request.name, "Night shift");
+        let request: SaveGroupCustomRoleRequest =
+            serde_json::from_value(json!({"name": " \t ", "permissions": []})).unwrap();
+        assert!(request.validate().is_err());
+    }
+}
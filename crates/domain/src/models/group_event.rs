@@ -0,0 +1,167 @@
+//! Group event archive domain models.
+//!
+//! Events that happen within a group are archived for a configurable
+//! retention window so that automations and webhooks added later can
+//! backfill recent history.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use super::geofence_event::GeofenceTransitionType;
+use super::location::PaginationInfo;
+
+/// Type of an archived group event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupEventType {
+    MemberJoined,
+    MemberRemoved,
+    MemberRoleChanged,
+    OwnershipTransferred,
+    DeviceAdded,
+    DeviceRemoved,
+    GeofenceEnter,
+    GeofenceExit,
+    GeofenceDwell,
+    GeofenceArriving,
+}
+
+impl GroupEventType {
+    /// Returns the string representation for database storage.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MemberJoined => "member_joined",
+            Self::MemberRemoved => "member_removed",
+            Self::MemberRoleChanged => "member_role_changed",
+            Self::OwnershipTransferred => "ownership_transferred",
+            Self::DeviceAdded => "device_added",
+            Self::DeviceRemoved => "device_removed",
+            Self::GeofenceEnter => "geofence_enter",
+            Self::GeofenceExit => "geofence_exit",
+            Self::GeofenceDwell => "geofence_dwell",
+            Self::GeofenceArriving => "geofence_arriving",
+        }
+    }
+}
+
+impl From<GeofenceTransitionType> for GroupEventType {
+    fn from(transition: GeofenceTransitionType) -> Self {
+        match transition {
+            GeofenceTransitionType::Enter => Self::GeofenceEnter,
+            GeofenceTransitionType::Exit => Self::GeofenceExit,
+            GeofenceTransitionType::Dwell => Self::GeofenceDwell,
+        }
+    }
+}
+
+impl std::fmt::Display for GroupEventType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// An archived group event.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GroupEvent {
+    pub event_id: Uuid,
+    pub group_id: Uuid,
+    /// Event type (kept as a string so older archived events stay readable).
+    pub event_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor_user_id: Option<Uuid>,
+    pub payload: serde_json::Value,
+    pub occurred_at: DateTime<Utc>,
+}
+
+fn default_limit() -> i64 {
+    100
+}
+
+/// Query parameters for replaying a group's event archive.
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct ListGroupEventsQuery {
+    /// Only return events that occurred at or after this time.
+    pub since: Option<DateTime<Utc>>,
+
+    /// Cursor returned by the previous page.
+    pub cursor: Option<String>,
+
+    /// Page size (1-500, default 100).
+    #[serde(default = "default_limit")]
+    #[validate(range(min = 1, max = 500, message = "Limit must be between 1 and 500"))]
+    pub limit: i64,
+}
+
+/// Response for replaying a group's event archive.
+#[derive(Debug, Clone, Serialize)]
+pub struct ListGroupEventsResponse {
+    pub events: Vec<GroupEvent>,
+    pub pagination: PaginationInfo,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_event_type_serialization() {
+        let json = serde_json::to_string(&GroupEventType::MemberRoleChanged).unwrap();
+        assert_eq!(json, "\"member_role_changed\"");
+        assert_eq!(GroupEventType::DeviceAdded.as_str(), "device_added");
+        assert_eq!(GroupEventType::GeofenceDwell.to_string(), "geofence_dwell");
+    }
+
+    #[test]
+    fn test_group_event_type_from_geofence_transition() {
+        assert_eq!(
+            GroupEventType::from(GeofenceTransitionType::Enter).as_str(),
+            GeofenceTransitionType::Enter.to_webhook_event_type()
+        );
+        assert_eq!(
+            GroupEventType::from(GeofenceTransitionType::Exit),
+            GroupEventType::GeofenceExit
+        );
+    }
+
+    #[test]
+    fn test_list_group_events_query_defaults() {
+        let query: ListGroupEventsQuery = serde_json::from_str("{}").unwrap();
+        assert!(query.since.is_none());
+        assert!(query.cursor.is_none());
+        assert_eq!(query.limit, 100);
+        assert!(query.validate().is_ok());
+    }
+
+    #[test]
+    fn test_list_group_events_query_limit_validation() {
+        let query = ListGroupEventsQuery {
+            since: None,
+            cursor: None,
+            limit: 501,
+        };
+        assert!(query.validate().is_err());
+    }
+
+    #[test]
+    fn test_group_event_skips_missing_ids() {
+        let event = GroupEvent {
+            event_id: Uuid::new_v4(),
+            group_id: Uuid::new_v4(),
+            event_type: "member_joined".to_string(),
+            device_id: None,
+            actor_user_id: Some(Uuid::new_v4()),
+            payload: serde_json::json!({"role": "member"}),
+            occurred_at: Utc::now(),
+        };
+
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains("\"event_type\":\"member_joined\""));
+        assert!(json.contains("\"actor_user_id\""));
+        assert!(!json.contains("\"device_id\""));
+    }
+}
@@ -0,0 +1,84 @@
+//! Group data export models.
+//!
+//! Group owners can export everything a group holds (members, devices,
+//! location history, geofences and trips) as a ZIP archive. The archive is
+//! built by a background export job and downloaded once the job completes.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::group::GroupRole;
+
+/// Contents of `manifest.json` in a group export.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GroupExportManifest {
+    pub group_id: Uuid,
+    pub group_name: String,
+    pub group_slug: String,
+    pub exported_at: DateTime<Utc>,
+    pub member_count: i64,
+    pub device_count: i64,
+    pub location_count: i64,
+    pub geofence_count: i64,
+    pub trip_count: i64,
+}
+
+impl GroupExportManifest {
+    /// Total number of exported records.
+    pub fn record_count(&self) -> i64 {
+        self.member_count
+            + self.device_count
+            + self.location_count
+            + self.geofence_count
+            + self.trip_count
+    }
+}
+
+/// A member in `members.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GroupExportMember {
+    pub user_id: Uuid,
+    pub display_name: Option<String>,
+    pub role: GroupRole,
+    pub joined_at: DateTime<Utc>,
+}
+
+/// A device in `devices.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GroupExportDevice {
+    pub device_id: Uuid,
+    pub display_name: String,
+    pub owner_user_id: Option<Uuid>,
+    pub owner_display_name: Option<String>,
+    pub added_at: DateTime<Utc>,
+    pub last_seen_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_record_count() {
+        let manifest = GroupExportManifest {
+            group_id: Uuid::new_v4(),
+            group_name: "Family".to_string(),
+            group_slug: "family".to_string(),
+            exported_at: Utc::now(),
+            member_count: 3,
+            device_count: 4,
+            location_count: 1000,
+            geofence_count: 2,
+            trip_count: 10,
+        };
+        assert_eq!(manifest.record_count(), 1019);
+
+        let json = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(json["group_slug"], "family");
+        assert_eq!(json["location_count"], 1000);
+    }
+}
@@ -0,0 +1,255 @@
+//! Group message domain models.
+//!
+//! Groups have a lightweight message feed: announcements posted by group
+//! admins and "I arrived" check-ins that members post with their location.
+//! Messages are pushed to the other members and pruned after a retention
+//! window.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use super::location::PaginationInfo;
+
+/// Kind of a group message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupMessageKind {
+    /// Announcement from a group owner or admin.
+    Announcement,
+    /// A member telling the group where they arrived.
+    CheckIn,
+}
+
+impl GroupMessageKind {
+    /// Returns the string representation for database storage.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Announcement => "announcement",
+            Self::CheckIn => "check_in",
+        }
+    }
+}
+
+impl fmt::Display for GroupMessageKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for GroupMessageKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "announcement" => Ok(Self::Announcement),
+            "check_in" => Ok(Self::CheckIn),
+            other => Err(format!("Unknown group message kind: {}", other)),
+        }
+    }
+}
+
+/// Where a check-in was posted from.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct CheckInLocation {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Name of the place, e.g. "School".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub place_name: Option<String>,
+}
+
+/// A message in a group's feed.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GroupMessage {
+    pub message_id: Uuid,
+    pub group_id: Uuid,
+    /// Sender; None once the sender's account is deleted.
+    pub sender_user_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sender_display_name: Option<String>,
+    pub kind: GroupMessageKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<CheckInLocation>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Request to post a message to a group.
+///
+/// Announcements need a body; check-ins need `latitude` and `longitude` and
+/// may carry a place name and a short note.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+#[validate(schema(function = "validate_message_content"))]
+pub struct PostGroupMessageRequest {
+    pub kind: GroupMessageKind,
+
+    #[serde(
+        default,
+        deserialize_with = "shared::text::deserialize_optional_multiline"
+    )]
+    #[validate(custom(function = "shared::text::validate_message"))]
+    pub body: Option<String>,
+
+    #[validate(custom(function = "shared::validation::validate_latitude"))]
+    pub latitude: Option<f64>,
+
+    #[validate(custom(function = "shared::validation::validate_longitude"))]
+    pub longitude: Option<f64>,
+
+    #[serde(default, deserialize_with = "shared::text::deserialize_optional_line")]
+    #[validate(custom(function = "shared::text::validate_place_name"))]
+    pub place_name: Option<String>,
+}
+
+fn validate_message_content(request: &PostGroupMessageRequest) -> Result<(), ValidationError> {
+    let has_location = request.latitude.is_some() || request.longitude.is_some();
+    let message = match request.kind {
+        GroupMessageKind::Announcement if request.body.is_none() => "Announcements need a body",
+        GroupMessageKind::Announcement if has_location || request.place_name.is_some() => {
+            "Announcements cannot have a location"
+        }
+        GroupMessageKind::CheckIn if request.latitude.is_none() || request.longitude.is_none() => {
+            "Check-ins need a latitude and longitude"
+        }
+        _ => return Ok(()),
+    };
+    let mut err = ValidationError::new("invalid_message");
+    err.message = Some(message.into());
+    Err(err)
+}
+
+fn default_limit() -> i64 {
+    50
+}
+
+/// Query parameters for listing a group's messages.
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct ListGroupMessagesQuery {
+    /// Only return messages of this kind.
+    pub kind: Option<GroupMessageKind>,
+
+    /// Cursor returned by the previous page.
+    pub cursor: Option<String>,
+
+    /// Page size (1-100, default 50).
+    #[serde(default = "default_limit")]
+    #[validate(range(min = 1, max = 100, message = "Limit must be between 1 and 100"))]
+    pub limit: i64,
+}
+
+/// Response for listing a group's messages, newest first.
+#[derive(Debug, Clone, Serialize)]
+pub struct ListGroupMessagesResponse {
+    pub messages: Vec<GroupMessage>,
+    pub pagination: PaginationInfo,
+}
+
+/// Push notification preview of a message body.
+pub fn message_preview(body: &str) -> String {
+    const PREVIEW_CHARS: usize = 100;
+    match body.char_indices().nth(PREVIEW_CHARS) {
+        Some((end, _)) => format!("{}…", body[..end].trim_end()),
+        None => body.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn request(value: serde_json::Value) -> PostGroupMessageRequest {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_kind_roundtrip() {
+        for kind in [GroupMessageKind::Announcement, GroupMessageKind::CheckIn] {
+            assert_eq!(kind.as_str().parse::<GroupMessageKind>(), Ok(kind));
+        }
+        assert_eq!(
+            serde_json::to_string(&GroupMessageKind::CheckIn).unwrap(),
+            "\"check_in\""
+        );
+        assert!("chat".parse::<GroupMessageKind>().is_err());
+    }
+
+    #[test]
+    fn test_announcement_validation() {
+        assert!(
+            request(json!({"kind": "announcement", "body": "Dinner at 7"}))
+                .validate()
+                .is_ok()
+        );
+        assert!(request(json!({"kind": "announcement"})).validate().is_err());
+        assert!(request(json!({"kind": "announcement", "body": ""}))
+            .validate()
+            .is_err());
+        // Bodies are normalized, so whitespace alone is empty
+        assert!(
+            request(json!({"kind": "announcement", "body": " \n\u{200B} "}))
+                .validate()
+                .is_err()
+        );
+        assert_eq!(
+            request(json!({"kind": "announcement", "body": "  Dinner\u{202E} at 7 "})).body,
+            Some("Dinner at 7".to_string())
+        );
+        // Emoji count as one character each
+        assert!(request(
+            json!({"kind": "announcement", "body": "\u{1F1F8}\u{1F1F0}".repeat(1000)})
+        )
+        .validate()
+        .is_ok());
+        assert!(request(json!({
+            "kind": "announcement",
+            "body": "Here",
+            "latitude": 48.1,
+            "longitude": 17.1
+        }))
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn test_check_in_validation() {
+        assert!(request(json!({
+            "kind": "check_in",
+            "latitude": 48.1,
+            "longitude": 17.1,
+            "place_name": "School"
+        }))
+        .validate()
+        .is_ok());
+        assert!(
+            request(json!({"kind": "check_in", "body": "Arrived", "latitude": 48.1}))
+                .validate()
+                .is_err()
+        );
+        assert!(request(json!({
+            "kind": "check_in",
+            "latitude": 95.0,
+            "longitude": 17.1
+        }))
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn test_message_preview() {
+        assert_eq!(message_preview("Short"), "Short");
+        let long = "a".repeat(150);
+        let preview = message_preview(&long);
+        assert_eq!(preview.chars().count(), 101);
+        assert!(preview.ends_with('…'));
+    }
+}
@@ -0,0 +1,153 @@
+//! Per-group notification preferences.
+//!
+//! Each member chooses, per group, which events push notifications to their
+//! own devices. Members who never changed their preferences get every
+//! notification.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Event category a member can mute for a group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupNotificationCategory {
+    /// Geofence events of the group's devices, such as upcoming arrivals.
+    GeofenceEvents,
+    /// SOS alerts raised by the group's devices.
+    Sos,
+    /// A group device's battery dropping below the low battery threshold.
+    LowBattery,
+    /// New members joining the group.
+    MemberJoins,
+}
+
+impl GroupNotificationCategory {
+    /// Column of the category in `group_notification_preferences`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::GeofenceEvents => "geofence_events",
+            Self::Sos => "sos",
+            Self::LowBattery => "low_battery",
+            Self::MemberJoins => "member_joins",
+        }
+    }
+}
+
+/// Notification categories a member receives for a group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GroupNotificationPreferences {
+    pub geofence_events: bool,
+    pub sos: bool,
+    pub low_battery: bool,
+    pub member_joins: bool,
+}
+
+impl Default for GroupNotificationPreferences {
+    fn default() -> Self {
+        Self {
+            geofence_events: true,
+            sos: true,
+            low_battery: true,
+            member_joins: true,
+        }
+    }
+}
+
+impl GroupNotificationPreferences {
+    /// Whether notifications of `category` are received.
+    pub fn allows(&self, category: GroupNotificationCategory) -> bool {
+        match category {
+            GroupNotificationCategory::GeofenceEvents => self.geofence_events,
+            GroupNotificationCategory::Sos => self.sos,
+            GroupNotificationCategory::LowBattery => self.low_battery,
+            GroupNotificationCategory::MemberJoins => self.member_joins,
+        }
+    }
+}
+
+/// Request to update notification preferences for a group.
+///
+/// Omitted categories keep their current value.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct UpdateGroupNotificationPreferencesRequest {
+    pub geofence_events: Option<bool>,
+    pub sos: Option<bool>,
+    pub low_battery: Option<bool>,
+    pub member_joins: Option<bool>,
+}
+
+impl UpdateGroupNotificationPreferencesRequest {
+    /// Preferences after applying the update to `current`.
+    pub fn apply(&self, current: GroupNotificationPreferences) -> GroupNotificationPreferences {
+        GroupNotificationPreferences {
+            geofence_events: self.geofence_events.unwrap_or(current.geofence_events),
+            sos: self.sos.unwrap_or(current.sos),
+            low_battery: self.low_battery.unwrap_or(current.low_battery),
+            member_joins: self.member_joins.unwrap_or(current.member_joins),
+        }
+    }
+}
+
+/// Notification preferences response.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GroupNotificationPreferencesResponse {
+    pub group_id: Uuid,
+    #[serde(flatten)]
+    pub preferences: GroupNotificationPreferences,
+    /// When the preferences were last changed; absent for the defaults.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_allow_everything() {
+        let preferences = GroupNotificationPreferences::default();
+        for category in [
+            GroupNotificationCategory::GeofenceEvents,
+            GroupNotificationCategory::Sos,
+            GroupNotificationCategory::LowBattery,
+            GroupNotificationCategory::MemberJoins,
+        ] {
+            assert!(preferences.allows(category));
+        }
+    }
+
+    #[test]
+    fn test_update_keeps_omitted_categories() {
+        let request: UpdateGroupNotificationPreferencesRequest =
+            serde_json::from_str(r#"{"low_battery": false}"#).unwrap();
+        let current = GroupNotificationPreferences {
+            member_joins: false,
+            ..Default::default()
+        };
+        let updated = request.apply(current);
+        assert!(!updated.allows(GroupNotificationCategory::LowBattery));
+        assert!(!updated.allows(GroupNotificationCategory::MemberJoins));
+        assert!(updated.allows(GroupNotificationCategory::GeofenceEvents));
+        assert!(updated.allows(GroupNotificationCategory::Sos));
+    }
+
+    #[test]
+    fn test_response_serialization() {
+        let response = GroupNotificationPreferencesResponse {
+            group_id: Uuid::nil(),
+            preferences: GroupNotificationPreferences {
+                sos: false,
+                ..Default::default()
+            },
+            updated_at: None,
+        };
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["sos"], false);
+        assert_eq!(json["geofence_events"], true);
+        assert!(json.get("updated_at").is_none());
+    }
+}
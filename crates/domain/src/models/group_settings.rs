@@ -0,0 +1,264 @@
+//! Group settings domain models.
+//!
+//! Typed view of the `groups.settings` JSON column. Stored settings are read
+//! leniently: missing or unreadable keys fall back to their defaults, and keys
+//! this version doesn't know are kept when settings are updated.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+use validator::Validate;
+
+use super::geofence::GeofenceEventType;
+
+/// How members' devices share their location by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupSharingMode {
+    /// Locations are shared all the time.
+    #[default]
+    Continuous,
+    /// Locations are shared only within the device's sharing schedule.
+    Scheduled,
+    /// Locations are shared only during a member's temporary sharing window.
+    OnDemand,
+}
+
+/// Precision of member locations shown to other members.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupLocationPrecision {
+    /// Exact coordinates.
+    #[default]
+    Precise,
+    /// Coordinates rounded to about 1 km.
+    Approximate,
+}
+
+/// Notification defaults for geofences created in the group.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GroupGeofenceNotificationDefaults {
+    /// Events new geofences notify about.
+    pub event_types: Vec<GeofenceEventType>,
+    /// Whether all members are notified, rather than only the device owner.
+    pub notify_all_members: bool,
+}
+
+impl Default for GroupGeofenceNotificationDefaults {
+    fn default() -> Self {
+        Self {
+            event_types: vec![GeofenceEventType::Enter, GeofenceEventType::Exit],
+            notify_all_members: true,
+        }
+    }
+}
+
+/// Typed settings of a group.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GroupSettings {
+    pub default_sharing_mode: GroupSharingMode,
+    pub member_location_precision: GroupLocationPrecision,
+    pub geofence_notifications: GroupGeofenceNotificationDefaults,
+}
+
+/// Read a stored setting, or its default if missing or unreadable.
+fn stored<T: serde::de::DeserializeOwned + Default>(value: &Value, key: &str) -> T {
+    value
+        .get(key)
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+impl GroupSettings {
+    /// Settings from the stored JSON column.
+    pub fn from_stored(value: &Value) -> Self {
+        let defaults = GroupGeofenceNotificationDefaults::default();
+        let geofence_notifications = value.get("geofence_notifications");
+        let geofence_notifications = GroupGeofenceNotificationDefaults {
+            event_types: geofence_notifications
+                .and_then(|v| v.get("event_types"))
+                .and_then(|v| serde_json::from_value(v.clone()).ok())
+                .filter(|types: &Vec<GeofenceEventType>| !types.is_empty())
+                .unwrap_or(defaults.event_types),
+            notify_all_members: geofence_notifications
+                .and_then(|v| v.get("notify_all_members"))
+                .and_then(Value::as_bool)
+                .unwrap_or(defaults.notify_all_members),
+        };
+
+        Self {
+            default_sharing_mode: stored(value, "default_sharing_mode"),
+            member_location_precision: stored(value, "member_location_precision"),
+            geofence_notifications,
+        }
+    }
+
+    /// JSON to merge into the stored column.
+    pub fn to_stored(&self) -> Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+}
+
+/// Group settings response.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GroupSettingsResponse {
+    pub group_id: Uuid,
+    #[serde(flatten)]
+    pub settings: GroupSettings,
+}
+
+/// Changes to a group's geofence notification defaults.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct UpdateGroupGeofenceNotificationDefaults {
+    pub event_types: Option<Vec<GeofenceEventType>>,
+    pub notify_all_members: Option<bool>,
+}
+
+/// Request payload for updating group settings.
+///
+/// Omitted fields keep their current value.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct UpdateGroupSettingsRequest {
+    pub default_sharing_mode: Option<GroupSharingMode>,
+    pub member_location_precision: Option<GroupLocationPrecision>,
+    #[validate(custom(function = "validate_geofence_notifications"))]
+    pub geofence_notifications: Option<UpdateGroupGeofenceNotificationDefaults>,
+}
+
+fn validate_geofence_notifications(
+    update: &UpdateGroupGeofenceNotificationDefaults,
+) -> Result<(), validator::ValidationError> {
+    let Some(types) = &update.event_types else {
+        return Ok(());
+    };
+    let unique = types
+        .iter()
+        .enumerate()
+        .all(|(i, t)| !types[..i].contains(t));
+    if types.is_empty() || !unique {
+        let mut err = validator::ValidationError::new("event_types");
+        err.message = Some("Event types must be a non-empty list without duplicates".into());
+        return Err(err);
+    }
+    Ok(())
+}
+
+impl UpdateGroupSettingsRequest {
+    /// Whether the request only changes the geofence notification defaults.
+    pub fn changes_only_geofence_notifications(&self) -> bool {
+        self.default_sharing_mode.is_none()
+            && self.member_location_precision.is_none()
+            && self.geofence_notifications.is_some()
+    }
+
+    /// Apply the request to the current settings.
+    pub fn apply(&self, mut settings: GroupSettings) -> GroupSettings {
+        if let Some(mode) = self.default_sharing_mode {
+            settings.default_sharing_mode = mode;
+        }
+        if let Some(precision) = self.member_location_precision {
+            settings.member_location_precision = precision;
+        }
+        if let Some(notifications) = &self.geofence_notifications {
+            if let Some(event_types) = &notifications.event_types {
+                settings.geofence_notifications.event_types = event_types.clone();
+            }
+            if let Some(notify_all_members) = notifications.notify_all_members {
+                settings.geofence_notifications.notify_all_members = notify_all_members;
+            }
+        }
+        settings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_from_stored_defaults() {
+        let settings = GroupSettings::from_stored(&json!({}));
+        assert_eq!(settings, GroupSettings::default());
+        assert_eq!(settings.default_sharing_mode, GroupSharingMode::Continuous);
+        assert_eq!(
+            settings.member_location_precision,
+            GroupLocationPrecision::Precise
+        );
+        assert_eq!(
+            settings.geofence_notifications.event_types,
+            vec![GeofenceEventType::Enter, GeofenceEventType::Exit]
+        );
+        assert!(settings.geofence_notifications.notify_all_members);
+    }
+
+    #[test]
+    fn test_from_stored_is_lenient() {
+        let settings = GroupSettings::from_stored(&json!({
+            "default_sharing_mode": "on_demand",
+            "member_location_precision": "blurry",
+            "geofence_notifications": {"event_types": [], "notify_all_members": false},
+            "legacy_key": 1
+        }));
+        assert_eq!(settings.default_sharing_mode, GroupSharingMode::OnDemand);
+        assert_eq!(
+            settings.member_location_precision,
+            GroupLocationPrecision::Precise
+        );
+        assert_eq!(
+            settings.geofence_notifications.event_types,
+            vec![GeofenceEventType::Enter, GeofenceEventType::Exit]
+        );
+        assert!(!settings.geofence_notifications.notify_all_members);
+    }
+
+    #[test]
+    fn test_update_request_validation() {
+        let request: UpdateGroupSettingsRequest = serde_json::from_value(json!({
+            "geofence_notifications": {"event_types": ["enter", "dwell"]}
+        }))
+        .unwrap();
+        assert!(request.validate().is_ok());
+        assert!(request.changes_only_geofence_notifications());
+
+        for event_types in [json!([]), json!(["exit", "exit"])] {
+            let request: UpdateGroupSettingsRequest = serde_json::from_value(json!({
+                "geofence_notifications": {"event_types": event_types}
+            }))
+            .unwrap();
+            assert!(request.validate().is_err());
+        }
+
+        assert!(serde_json::from_value::<UpdateGroupSettingsRequest>(
+            json!({"default_sharing_mode": "sometimes"})
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_update_request_apply() {
+        let request: UpdateGroupSettingsRequest = serde_json::from_value(json!({
+            "member_location_precision": "approximate",
+            "geofence_notifications": {"notify_all_members": false}
+        }))
+        .unwrap();
+        assert!(!request.changes_only_geofence_notifications());
+        let settings = request.apply(GroupSettings::default());
+        assert_eq!(settings.default_sharing_mode, GroupSharingMode::Continuous);
+        assert_eq!(
+            settings.member_location_precision,
+            GroupLocationPrecision::Approximate
+        );
+        assert_eq!(settings.geofence_notifications.event_types.len(), 2);
+        assert!(!settings.geofence_notifications.notify_all_members);
+
+        let stored = settings.to_stored();
+        assert_eq!(stored["member_location_precision"], "approximate");
+        assert_eq!(GroupSettings::from_stored(&stored), settings);
+    }
+}
@@ -43,6 +43,64 @@ pub struct CreateInviteRequest {
     pub expires_in_hours: Option<i32>,
 }
 
+/// Request to invite someone to a group by email.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct CreateEmailInviteRequest {
+    /// Email address of the invitee; only this user can accept the invite.
+    #[validate(email(message = "Invalid email address"))]
+    #[validate(length(max = 255, message = "Email must be at most 255 characters"))]
+    pub email: String,
+
+    /// Role to assign when joining (default: member). Cannot be owner.
+    pub preset_role: Option<GroupRole>,
+
+    /// Hours until expiry (1-168, default: 72)
+    #[validate(range(
+        min = 1,
+        max = 168,
+        message = "expires_in_hours must be between 1 and 168"
+    ))]
+    pub expires_in_hours: Option<i32>,
+}
+
+/// Default lifetime of email invites in hours.
+pub const DEFAULT_EMAIL_INVITE_EXPIRY_HOURS: i32 = 72;
+
+/// Normalize an email address for binding and comparing invites.
+pub fn normalize_invite_email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+/// Whether a user may accept an invite: anyone may use a shareable code,
+/// only the recipient an email invite.
+pub fn can_accept_invite(recipient_email: Option<&str>, user_email: &str) -> bool {
+    recipient_email.is_none_or(|recipient| recipient == normalize_invite_email(user_email))
+}
+
+/// Status of an invite.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupInviteStatus {
+    Pending,
+    /// All uses of the invite were taken.
+    Accepted,
+    Revoked,
+    Expired,
+}
+
+impl GroupInviteStatus {
+    /// Status from the stored status and the expiry, which is not stored.
+    pub fn from_stored(status: &str, expires_at: DateTime<Utc>) -> Self {
+        match status {
+            "accepted" => Self::Accepted,
+            "revoked" => Self::Revoked,
+            _ if expires_at <= Utc::now() => Self::Expired,
+            _ => Self::Pending,
+        }
+    }
+}
+
 /// Response after creating an invite.
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -57,6 +115,12 @@ pub struct CreateInviteResponse {
     pub created_by: Uuid,
     pub created_at: DateTime<Utc>,
     pub invite_url: String,
+    /// Email the invite was sent to (email invites only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recipient_email: Option<String>,
+    /// Whether the invite email was sent (email invites only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_sent: Option<bool>,
 }
 
 /// Summary of an invite for listing.
@@ -71,6 +135,9 @@ pub struct InviteSummary {
     pub expires_at: DateTime<Utc>,
     pub created_by: CreatorInfo,
     pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recipient_email: Option<String>,
+    pub status: GroupInviteStatus,
 }
 
 /// Creator info for invite listing.
@@ -88,6 +155,40 @@ pub struct ListInvitesResponse {
     pub data: Vec<InviteSummary>,
 }
 
+/// Image format of an invite QR code.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QrImageFormat {
+    #[default]
+    Png,
+    Svg,
+}
+
+impl QrImageFormat {
+    /// MIME type of the rendered image.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Png => "image/png",
+            Self::Svg => "image/svg+xml",
+        }
+    }
+}
+
+/// Default edge length of invite QR codes in pixels.
+pub const DEFAULT_INVITE_QR_SIZE: u32 = 256;
+
+/// Query parameters for an invite QR code.
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct InviteQrQuery {
+    /// Image format (png or svg, default: png)
+    #[serde(default)]
+    pub format: QrImageFormat,
+
+    /// Approximate edge length in pixels (64-1024, default: 256)
+    #[validate(range(min = 64, max = 1024, message = "size must be between 64 and 1024"))]
+    pub size: Option<u32>,
+}
+
 /// Public invite info (for GET /invites/:code without auth).
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -178,6 +279,19 @@ pub fn generate_invite_code() -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_invite_qr_query() {
+        let query: InviteQrQuery = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert_eq!(query.format, QrImageFormat::Png);
+        assert_eq!(query.format.content_type(), "image/png");
+        assert!(query.validate().is_ok());
+
+        let query: InviteQrQuery =
+            serde_json::from_value(serde_json::json!({"format": "svg", "size": 32})).unwrap();
+        assert_eq!(query.format.content_type(), "image/svg+xml");
+        assert!(query.validate().is_err());
+    }
+
     #[test]
     fn test_generate_invite_code_format() {
         let code = generate_invite_code();
@@ -232,4 +346,61 @@ mod tests {
         };
         assert!(too_long_expiry.validate().is_err());
     }
+
+    #[test]
+    fn test_create_email_invite_request_validation() {
+        let valid = CreateEmailInviteRequest {
+            email: "friend@example.com".to_string(),
+            preset_role: None,
+            expires_in_hours: Some(48),
+        };
+        assert!(valid.validate().is_ok());
+
+        let invalid_email = CreateEmailInviteRequest {
+            email: "not-an-email".to_string(),
+            ..valid.clone()
+        };
+        assert!(invalid_email.validate().is_err());
+
+        let too_long_expiry = CreateEmailInviteRequest {
+            expires_in_hours: Some(500),
+            ..valid
+        };
+        assert!(too_long_expiry.validate().is_err());
+    }
+
+    #[test]
+    fn test_can_accept_invite() {
+        assert!(can_accept_invite(None, "anyone@example.com"));
+        assert!(can_accept_invite(
+            Some("friend@example.com"),
+            " Friend@Example.com"
+        ));
+        assert!(!can_accept_invite(
+            Some("friend@example.com"),
+            "other@example.com"
+        ));
+    }
+
+    #[test]
+    fn test_invite_status_from_stored() {
+        let later = Utc::now() + chrono::Duration::hours(1);
+        let earlier = Utc::now() - chrono::Duration::hours(1);
+        assert_eq!(
+            GroupInviteStatus::from_stored("pending", later),
+            GroupInviteStatus::Pending
+        );
+        assert_eq!(
+            GroupInviteStatus::from_stored("pending", earlier),
+            GroupInviteStatus::Expired
+        );
+        assert_eq!(
+            GroupInviteStatus::from_stored("accepted", earlier),
+            GroupInviteStatus::Accepted
+        );
+        assert_eq!(
+            GroupInviteStatus::from_stored("revoked", later),
+            GroupInviteStatus::Revoked
+        );
+    }
 }
@@ -2,9 +2,93 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
 use uuid::Uuid;
 use validator::Validate;
 
+/// Highest trust score of a location.
+pub const MAX_TRUST_SCORE: i32 = 100;
+
+/// Where a location fix came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LocationSource {
+    Gps,
+    Network,
+    Beacon,
+    ManualCheckIn,
+    Osmand,
+}
+
+impl LocationSource {
+    /// Returns the string representation for database storage.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LocationSource::Gps => "gps",
+            LocationSource::Network => "network",
+            LocationSource::Beacon => "beacon",
+            LocationSource::ManualCheckIn => "manual_check_in",
+            LocationSource::Osmand => "osmand",
+        }
+    }
+
+    /// Source of a fix: the one the client reported, else inferred from
+    /// its provider. Fused and unknown providers have no source.
+    pub fn resolve(reported: Option<Self>, provider: Option<&str>) -> Option<Self> {
+        reported.or_else(|| match provider?.to_ascii_lowercase().as_str() {
+            "gps" => Some(LocationSource::Gps),
+            "network" | "wifi" | "cell" => Some(LocationSource::Network),
+            _ => None,
+        })
+    }
+
+    /// Trust score of a perfectly accurate fix from this source.
+    fn base_trust_score(&self) -> i32 {
+        match self {
+            LocationSource::Gps | LocationSource::Osmand => 90,
+            LocationSource::Beacon => 80,
+            LocationSource::Network => 60,
+            // Entered by hand, so the easiest to get wrong or fake.
+            LocationSource::ManualCheckIn => 40,
+        }
+    }
+}
+
+impl fmt::Display for LocationSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for LocationSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "gps" => Ok(LocationSource::Gps),
+            "network" => Ok(LocationSource::Network),
+            "beacon" => Ok(LocationSource::Beacon),
+            "manual_check_in" => Ok(LocationSource::ManualCheckIn),
+            "osmand" => Ok(LocationSource::Osmand),
+            _ => Err(format!(
+                "Invalid location source: {}. Must be one of: gps, network, beacon, manual_check_in, osmand",
+                s
+            )),
+        }
+    }
+}
+
+/// Trust score (0-100) of a fix.
+///
+/// Starts from the source's base score, 50 for an unknown source, and loses
+/// a point per 10 m of reported accuracy, at most 50.
+pub fn trust_score(source: Option<LocationSource>, accuracy: f64) -> i32 {
+    let base = source.map_or(50, |s| s.base_trust_score());
+    let penalty = (accuracy.max(0.0) / 10.0).min(50.0) as i32;
+    (base - penalty).clamp(0, MAX_TRUST_SCORE)
+}
+
 /// Represents a location record in the system.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -29,6 +113,10 @@ pub struct Location {
     pub detection_source: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub trip_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trust_score: Option<i32>,
 }
 
 /// Request payload for single location upload.
@@ -65,6 +153,12 @@ pub struct UploadLocationRequest {
 
     pub network_type: Option<String>,
 
+    pub charging_state: Option<super::device_telemetry::ChargingState>,
+
+    /// Signal strength in dBm
+    #[validate(custom(function = "shared::validation::validate_signal_strength"))]
+    pub signal_strength: Option<i32>,
+
     // Context fields (Epic 7)
     /// Transportation mode when location was captured (e.g., WALKING, IN_VEHICLE)
     pub transportation_mode: Option<super::movement_event::TransportationMode>,
@@ -74,6 +168,14 @@ pub struct UploadLocationRequest {
 
     /// Optional link to the active trip when this location was recorded
     pub trip_id: Option<Uuid>,
+
+    /// Per-device increasing sequence number; a point re-sent with a
+    /// number already stored for the device is skipped
+    #[validate(range(min = 0))]
+    pub sequence_number: Option<i64>,
+
+    /// Where the fix came from; inferred from `provider` when absent
+    pub source: Option<LocationSource>,
 }
 
 /// Request payload for batch location upload.
@@ -117,6 +219,14 @@ pub struct LocationData {
 
     pub network_type: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub charging_state: Option<super::device_telemetry::ChargingState>,
+
+    /// Signal strength in dBm
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(custom(function = "shared::validation::validate_signal_strength"))]
+    pub signal_strength: Option<i32>,
+
     // Context fields (Epic 7)
     /// Transportation mode when location was captured (e.g., WALKING, IN_VEHICLE)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -129,6 +239,16 @@ pub struct LocationData {
     /// Optional link to the active trip when this location was recorded
     #[serde(skip_serializing_if = "Option::is_none")]
     pub trip_id: Option<Uuid>,
+
+    /// Per-device increasing sequence number; a point re-sent with a
+    /// number already stored for the device is skipped
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 0))]
+    pub sequence_number: Option<i64>,
+
+    /// Where the fix came from; inferred from `provider` when absent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<LocationSource>,
 }
 
 /// Response payload for location upload.
@@ -137,6 +257,13 @@ pub struct LocationData {
 pub struct UploadLocationResponse {
     pub success: bool,
     pub processed_count: usize,
+    /// Points skipped because their sequence number was already stored.
+    #[serde(default)]
+    pub duplicate_count: usize,
+    /// Points ignored because they were captured outside the device's
+    /// tracking schedule.
+    #[serde(default)]
+    pub outside_schedule_count: usize,
 }
 
 /// Last known location for a device.
@@ -195,10 +322,15 @@ pub struct GetLocationHistoryQuery {
     #[serde(default)]
     pub order: SortOrder,
 
-    /// Simplification tolerance in meters (0-10000).
+    /// Simplification tolerance in meters (0-10000), sent as
+    /// `simplify_tolerance` (or the older `tolerance`).
     /// When > 0, applies Ramer-Douglas-Peucker line simplification.
     /// Pagination is disabled when simplification is active.
+    #[serde(alias = "simplify_tolerance")]
     pub tolerance: Option<f64>,
+
+    /// Only return locations from this source.
+    pub source: Option<LocationSource>,
 }
 
 impl GetLocationHistoryQuery {
@@ -210,6 +342,8 @@ impl GetLocationHistoryQuery {
     pub const MIN_LIMIT: i32 = 1;
     /// Maximum tolerance for simplification (meters).
     pub const MAX_TOLERANCE: f64 = 10000.0;
+    /// Maximum (and default) number of points in a simplified response.
+    pub const MAX_SIMPLIFIED_LIMIT: i32 = 1000;
 
     /// Returns the effective limit, clamped to valid range.
     pub fn effective_limit(&self) -> i32 {
@@ -218,6 +352,16 @@ impl GetLocationHistoryQuery {
             .clamp(Self::MIN_LIMIT, Self::MAX_LIMIT)
     }
 
+    /// Returns the effective limit for simplified responses.
+    ///
+    /// A simplified path is returned in one response, so the limit defaults
+    /// to and is clamped at `MAX_SIMPLIFIED_LIMIT` rather than the page size.
+    pub fn effective_simplified_limit(&self) -> i32 {
+        self.limit
+            .unwrap_or(Self::MAX_SIMPLIFIED_LIMIT)
+            .clamp(Self::MIN_LIMIT, Self::MAX_SIMPLIFIED_LIMIT)
+    }
+
     /// Returns the effective tolerance if valid and > 0.
     /// Returns None if tolerance is not set, zero, or negative.
     /// Clamps to MAX_TOLERANCE if exceeds the limit.
@@ -263,6 +407,14 @@ pub struct LocationHistoryItem {
     /// Optional link to the active trip when this location was recorded
     #[serde(skip_serializing_if = "Option::is_none")]
     pub trip_id: Option<Uuid>,
+
+    /// Where the fix came from (e.g., gps, network)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
+    /// Trust score (0-100) from the source and accuracy
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trust_score: Option<i32>,
 }
 
 impl From<Location> for LocationHistoryItem {
@@ -283,6 +435,8 @@ impl From<Location> for LocationHistoryItem {
             transportation_mode: loc.transportation_mode,
             detection_source: loc.detection_source,
             trip_id: loc.trip_id,
+            source: loc.source,
+            trust_score: loc.trust_score,
         }
     }
 }
@@ -353,6 +507,46 @@ mod tests {
         Utc::now().timestamp_millis()
     }
 
+    #[test]
+    fn test_location_source_resolve() {
+        assert_eq!(
+            LocationSource::resolve(Some(LocationSource::Beacon), Some("gps")),
+            Some(LocationSource::Beacon)
+        );
+        assert_eq!(
+            LocationSource::resolve(None, Some("GPS")),
+            Some(LocationSource::Gps)
+        );
+        assert_eq!(
+            LocationSource::resolve(None, Some("network")),
+            Some(LocationSource::Network)
+        );
+        assert_eq!(LocationSource::resolve(None, Some("fused")), None);
+        assert_eq!(LocationSource::resolve(None, None), None);
+    }
+
+    #[test]
+    fn test_location_source_serde() {
+        let json = serde_json::to_string(&LocationSource::ManualCheckIn).unwrap();
+        assert_eq!(json, "\"manual_check_in\"");
+        assert_eq!(
+            "osmand".parse::<LocationSource>().unwrap(),
+            LocationSource::Osmand
+        );
+        assert!("GPS".parse::<LocationSource>().is_err());
+    }
+
+    #[test]
+    fn test_trust_score() {
+        assert_eq!(trust_score(Some(LocationSource::Gps), 5.0), 90);
+        assert_eq!(trust_score(Some(LocationSource::Gps), 25.0), 88);
+        assert_eq!(trust_score(Some(LocationSource::Network), 300.0), 30);
+        assert_eq!(trust_score(None, 10.0), 49);
+        // The accuracy penalty is capped
+        assert_eq!(trust_score(Some(LocationSource::ManualCheckIn), 5000.0), 0);
+        assert_eq!(trust_score(Some(LocationSource::Gps), 5000.0), 40);
+    }
+
     fn create_test_location() -> Location {
         Location {
             id: 1,
@@ -371,6 +565,8 @@ mod tests {
             transportation_mode: None,
             detection_source: None,
             trip_id: None,
+            source: None,
+            trust_score: None,
         }
     }
 
@@ -408,6 +604,8 @@ mod tests {
         let response = UploadLocationResponse {
             success: true,
             processed_count: 5,
+            duplicate_count: 0,
+            outside_schedule_count: 0,
         };
         assert!(response.success);
         assert_eq!(response.processed_count, 5);
@@ -438,9 +636,13 @@ mod tests {
             provider: None,
             battery_level: Some(50),
             network_type: None,
+            charging_state: None,
+            signal_strength: None,
             transportation_mode: None,
             detection_source: None,
             trip_id: None,
+            sequence_number: None,
+            source: None,
         };
         assert!(data.validate().is_ok());
     }
@@ -458,9 +660,13 @@ mod tests {
             provider: None,
             battery_level: None,
             network_type: None,
+            charging_state: None,
+            signal_strength: None,
             transportation_mode: None,
             detection_source: None,
             trip_id: None,
+            sequence_number: None,
+            source: None,
         };
         assert!(data.validate().is_err());
     }
@@ -478,9 +684,13 @@ mod tests {
             provider: None,
             battery_level: None,
             network_type: None,
+            charging_state: None,
+            signal_strength: None,
             transportation_mode: None,
             detection_source: None,
             trip_id: None,
+            sequence_number: None,
+            source: None,
         };
         assert!(data.validate().is_err());
     }
@@ -498,9 +708,13 @@ mod tests {
             provider: None,
             battery_level: None,
             network_type: None,
+            charging_state: None,
+            signal_strength: None,
             transportation_mode: None,
             detection_source: None,
             trip_id: None,
+            sequence_number: None,
+            source: None,
         };
         assert!(data.validate().is_err());
     }
@@ -518,9 +732,13 @@ mod tests {
             provider: None,
             battery_level: None,
             network_type: None,
+            charging_state: None,
+            signal_strength: None,
             transportation_mode: None,
             detection_source: None,
             trip_id: None,
+            sequence_number: None,
+            source: None,
         };
         assert!(data.validate().is_err());
     }
@@ -538,9 +756,13 @@ mod tests {
             provider: None,
             battery_level: None,
             network_type: None,
+            charging_state: None,
+            signal_strength: None,
             transportation_mode: None,
             detection_source: None,
             trip_id: None,
+            sequence_number: None,
+            source: None,
         };
         assert!(data.validate().is_err());
     }
@@ -558,13 +780,63 @@ mod tests {
             provider: None,
             battery_level: Some(150), // Invalid: > 100
             network_type: None,
+            charging_state: None,
+            signal_strength: None,
             transportation_mode: None,
             detection_source: None,
             trip_id: None,
+            sequence_number: None,
+            source: None,
         };
         assert!(data.validate().is_err());
     }
 
+    #[test]
+    fn test_location_data_telemetry_fields() {
+        let data: LocationData = serde_json::from_value(serde_json::json!({
+            "timestamp": current_timestamp_millis(),
+            "latitude": 45.0,
+            "longitude": -120.0,
+            "accuracy": 10.0,
+            "battery_level": 64,
+            "charging_state": "not_charging",
+            "network_type": "wifi",
+            "signal_strength": -58
+        }))
+        .unwrap();
+        assert!(data.validate().is_ok());
+        assert_eq!(
+            data.charging_state,
+            Some(crate::models::ChargingState::NotCharging)
+        );
+
+        let invalid = LocationData {
+            signal_strength: Some(-200),
+            ..data
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_location_data_sequence_number() {
+        let data: LocationData = serde_json::from_value(serde_json::json!({
+            "timestamp": current_timestamp_millis(),
+            "latitude": 45.0,
+            "longitude": -120.0,
+            "accuracy": 10.0,
+            "sequence_number": 42
+        }))
+        .unwrap();
+        assert!(data.validate().is_ok());
+        assert_eq!(data.sequence_number, Some(42));
+
+        let invalid = LocationData {
+            sequence_number: Some(-1),
+            ..data
+        };
+        assert!(invalid.validate().is_err());
+    }
+
     #[test]
     fn test_batch_upload_request_valid() {
         let request = BatchUploadRequest {
@@ -580,9 +852,13 @@ mod tests {
                 provider: None,
                 battery_level: None,
                 network_type: None,
+                charging_state: None,
+                signal_strength: None,
                 transportation_mode: None,
                 detection_source: None,
                 trip_id: None,
+                sequence_number: None,
+                source: None,
             }],
         };
         assert!(request.validate().is_ok());
@@ -612,9 +888,13 @@ mod tests {
                 provider: None,
                 battery_level: None,
                 network_type: None,
+                charging_state: None,
+                signal_strength: None,
                 transportation_mode: None,
                 detection_source: None,
                 trip_id: None,
+                sequence_number: None,
+                source: None,
             })
             .collect();
 
@@ -639,9 +919,13 @@ mod tests {
             provider: None,
             battery_level: Some(0), // Min valid
             network_type: None,
+            charging_state: None,
+            signal_strength: None,
             transportation_mode: None,
             detection_source: None,
             trip_id: None,
+            sequence_number: None,
+            source: None,
         };
         assert!(data.validate().is_ok());
     }
@@ -659,9 +943,13 @@ mod tests {
             provider: None,
             battery_level: Some(100), // Max valid
             network_type: None,
+            charging_state: None,
+            signal_strength: None,
             transportation_mode: None,
             detection_source: None,
             trip_id: None,
+            sequence_number: None,
+            source: None,
         };
         assert!(data.validate().is_ok());
     }
@@ -681,9 +969,13 @@ mod tests {
             provider: None,
             battery_level: None,
             network_type: None,
+            charging_state: None,
+            signal_strength: None,
             transportation_mode: None,
             detection_source: None,
             trip_id: None,
+            sequence_number: None,
+            source: None,
         };
         assert!(data.validate().is_err());
     }
@@ -703,9 +995,13 @@ mod tests {
             provider: None,
             battery_level: None,
             network_type: None,
+            charging_state: None,
+            signal_strength: None,
             transportation_mode: None,
             detection_source: None,
             trip_id: None,
+            sequence_number: None,
+            source: None,
         };
         assert!(data.validate().is_err());
     }
@@ -760,6 +1056,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_get_location_history_query_simplify_tolerance() {
+        let json = r#"{"simplify_tolerance": 25}"#;
+        let query: GetLocationHistoryQuery = serde_json::from_str(json).unwrap();
+        assert_eq!(query.effective_tolerance(), Some(25.0));
+    }
+
+    #[test]
+    fn test_get_location_history_query_simplified_limit() {
+        let query: GetLocationHistoryQuery = serde_json::from_str("{}").unwrap();
+        assert_eq!(
+            query.effective_simplified_limit(),
+            GetLocationHistoryQuery::MAX_SIMPLIFIED_LIMIT
+        );
+
+        let query: GetLocationHistoryQuery = serde_json::from_str(r#"{"limit": 300}"#).unwrap();
+        assert_eq!(query.effective_simplified_limit(), 300);
+
+        let query: GetLocationHistoryQuery = serde_json::from_str(r#"{"limit": 50000}"#).unwrap();
+        assert_eq!(
+            query.effective_simplified_limit(),
+            GetLocationHistoryQuery::MAX_SIMPLIFIED_LIMIT
+        );
+    }
+
     #[test]
     fn test_get_location_history_query_tolerance_float() {
         let json = r#"{"tolerance": 50.5}"#;
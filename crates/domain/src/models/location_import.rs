@@ -0,0 +1,88 @@
+//! Location history import job models.
+//!
+//! Uploaded exports (currently Google Takeout `Records.json`) are stored and
+//! processed in the background; clients poll the job for progress.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Source of a location import.
+pub const GOOGLE_TAKEOUT_SOURCE: &str = "google_takeout";
+
+/// Status of a location import job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LocationImportStatus {
+    Pending,
+    Processing,
+    Completed,
+    Failed,
+}
+
+impl From<&str> for LocationImportStatus {
+    fn from(s: &str) -> Self {
+        match s {
+            "pending" => LocationImportStatus::Pending,
+            "processing" => LocationImportStatus::Processing,
+            "completed" => LocationImportStatus::Completed,
+            "failed" => LocationImportStatus::Failed,
+            _ => LocationImportStatus::Pending,
+        }
+    }
+}
+
+impl LocationImportStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LocationImportStatus::Pending => "pending",
+            LocationImportStatus::Processing => "processing",
+            LocationImportStatus::Completed => "completed",
+            LocationImportStatus::Failed => "failed",
+        }
+    }
+}
+
+/// A location import job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct LocationImportJob {
+    pub id: Uuid,
+    pub device_id: Uuid,
+    pub source: String,
+    pub status: LocationImportStatus,
+    /// Records found in the export, known once processing starts.
+    pub total_records: i32,
+    /// Records written to the location history so far.
+    pub imported_records: i32,
+    /// Records already present in the history or repeated in the export.
+    pub duplicate_records: i32,
+    /// Records that were incomplete or out of range.
+    pub invalid_records: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_message: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_round_trip() {
+        for status in [
+            LocationImportStatus::Pending,
+            LocationImportStatus::Processing,
+            LocationImportStatus::Completed,
+            LocationImportStatus::Failed,
+        ] {
+            assert_eq!(LocationImportStatus::from(status.as_str()), status);
+        }
+        assert_eq!(
+            serde_json::to_string(&LocationImportStatus::Processing).unwrap(),
+            "\"processing\""
+        );
+    }
+}
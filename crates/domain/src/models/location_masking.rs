@@ -0,0 +1,97 @@
+//! Location masking for observer organization users.
+//!
+//! Observers see where devices are, but not precisely: coordinates are
+//! rounded to [`MASKED_COORDINATE_DECIMALS`] decimals (about 1 km) and
+//! altitude, speed, bearing and accuracy are dropped. Masking works on the
+//! serialized response, so every organization endpoint gets it from one
+//! place instead of each handler opting in.
+
+use serde_json::Value;
+
+/// Decimals coordinates are rounded to for observers.
+pub const MASKED_COORDINATE_DECIMALS: i32 = 2;
+
+/// Location details observers do not get.
+const PRECISE_LOCATION_FIELDS: [&str; 4] = ["accuracy", "altitude", "speed", "bearing"];
+
+/// Round a coordinate to [`MASKED_COORDINATE_DECIMALS`] decimals.
+pub fn mask_coordinate(value: f64) -> f64 {
+    let factor = 10f64.powi(MASKED_COORDINATE_DECIMALS);
+    (value * factor).round() / factor
+}
+
+/// Mask every location in a JSON response.
+///
+/// A location is any object with numeric `latitude` and `longitude`
+/// fields: its coordinates are rounded and its precise details removed.
+pub fn mask_json_locations(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            let is_location = ["latitude", "longitude"]
+                .iter()
+                .all(|key| object.get(*key).is_some_and(Value::is_number));
+            if is_location {
+                for key in ["latitude", "longitude"] {
+                    if let Some(coordinate) = object.get(key).and_then(Value::as_f64) {
+                        object.insert(key.to_string(), mask_coordinate(coordinate).into());
+                    }
+                }
+                for key in PRECISE_LOCATION_FIELDS {
+                    object.remove(key);
+                }
+            }
+            object.values_mut().for_each(mask_json_locations);
+        }
+        Value::Array(items) => items.iter_mut().for_each(mask_json_locations),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_mask_coordinate() {
+        assert_eq!(mask_coordinate(48.148_612), 48.15);
+        assert_eq!(mask_coordinate(-17.104_9), -17.1);
+        assert_eq!(mask_coordinate(0.004), 0.0);
+    }
+
+    #[test]
+    fn test_mask_json_locations() {
+        let mut response = json!({
+            "devices": [{
+                "device_name": "Van 7",
+                "latitude": 48.148_612,
+                "longitude": 17.107_748,
+                "accuracy": 4.5,
+                "altitude": 140.0,
+                "speed": 12.0,
+                "bearing": 90.0
+            }],
+            "data": [{
+                "display_name": "Van 8",
+                "last_location": { "latitude": 48.1, "longitude": 17.104_9 }
+            }],
+            "total": 1
+        });
+
+        mask_json_locations(&mut response);
+
+        assert_eq!(
+            response["devices"][0],
+            json!({ "device_name": "Van 7", "latitude": 48.15, "longitude": 17.11 })
+        );
+        assert_eq!(response["data"][0]["last_location"]["longitude"], 17.1);
+        assert_eq!(response["total"], 1);
+    }
+
+    #[test]
+    fn test_mask_json_locations_ignores_other_objects() {
+        let mut settings = json!({ "accuracy": "high", "latitude": "n/a" });
+        mask_json_locations(&mut settings);
+        assert_eq!(settings, json!({ "accuracy": "high", "latitude": "n/a" }));
+    }
+}
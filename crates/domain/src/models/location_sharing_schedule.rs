@@ -0,0 +1,79 @@
+//! Recurring location sharing schedules.
+//!
+//! A member can limit sharing with a group to a weekly schedule, e.g. only
+//! on weekdays during work hours. Outside the schedule other members see the
+//! member's sharing as paused and get none of their locations.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::weekly_schedule::WeeklySchedule;
+
+/// A member's recurring sharing schedule for a group.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct LocationSharingSchedule {
+    pub user_id: Uuid,
+    pub group_id: Uuid,
+    pub schedule: WeeklySchedule,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl LocationSharingSchedule {
+    /// Whether locations are shared at `now`.
+    pub fn is_sharing_at(&self, now: DateTime<Utc>) -> bool {
+        self.schedule.is_active_at(now)
+    }
+}
+
+/// Sharing schedule response.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SharingScheduleResponse {
+    #[serde(flatten)]
+    pub schedule: LocationSharingSchedule,
+    /// Whether the schedule currently shares locations.
+    pub is_sharing: bool,
+}
+
+impl SharingScheduleResponse {
+    pub fn new(schedule: LocationSharingSchedule, now: DateTime<Utc>) -> Self {
+        let is_sharing = schedule.is_sharing_at(now);
+        Self {
+            schedule,
+            is_sharing,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    #[test]
+    fn test_response_reports_sharing_state() {
+        let schedule = WeeklySchedule::from_value(&json!({
+            "windows": [{ "days": ["mon", "tue", "wed", "thu", "fri"], "start": "09:00", "end": "17:00" }]
+        }))
+        .unwrap()
+        .unwrap();
+        let schedule = LocationSharingSchedule {
+            user_id: Uuid::nil(),
+            group_id: Uuid::nil(),
+            schedule,
+            updated_at: Utc::now(),
+        };
+
+        // 2024-01-01 is a Monday
+        let working = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let weekend = Utc.with_ymd_and_hms(2024, 1, 6, 10, 0, 0).unwrap();
+        assert!(SharingScheduleResponse::new(schedule.clone(), working).is_sharing);
+
+        let json = serde_json::to_value(SharingScheduleResponse::new(schedule, weekend)).unwrap();
+        assert_eq!(json["is_sharing"], false);
+        assert_eq!(json["schedule"]["windows"][0]["start"], "09:00:00");
+    }
+}
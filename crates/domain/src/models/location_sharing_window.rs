@@ -0,0 +1,182 @@
+//! Time-boxed location sharing.
+//!
+//! In groups whose default sharing mode is `on_demand`, members share their
+//! locations by opening a sharing window ("share my location with this
+//! group for 2 hours"). The window expires on its own and can be extended or
+//! stopped early.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Longest sharing window, in minutes, counted from now.
+pub const MAX_SHARING_WINDOW_MINUTES: i64 = 24 * 60;
+
+/// Sharing window length when none is given, in minutes.
+pub const DEFAULT_SHARING_WINDOW_MINUTES: i64 = 120;
+
+/// A member's time-boxed sharing with a group.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct LocationSharingWindow {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub group_id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stopped_at: Option<DateTime<Utc>>,
+}
+
+impl LocationSharingWindow {
+    /// Whether locations are shared at `now`.
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.stopped_at.is_none() && self.expires_at > now
+    }
+}
+
+/// Expiry of a window extended by `minutes` at `now`.
+///
+/// Extends from the current expiry, capped at [`MAX_SHARING_WINDOW_MINUTES`]
+/// from now.
+pub fn extended_expiry(
+    expires_at: DateTime<Utc>,
+    minutes: i64,
+    now: DateTime<Utc>,
+) -> DateTime<Utc> {
+    let extended = expires_at.max(now) + Duration::minutes(minutes);
+    extended.min(now + Duration::minutes(MAX_SHARING_WINDOW_MINUTES))
+}
+
+/// Sharing window response.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SharingWindowResponse {
+    #[serde(flatten)]
+    pub window: LocationSharingWindow,
+    pub is_active: bool,
+    /// Seconds until the window expires; 0 once it is over.
+    pub remaining_seconds: i64,
+}
+
+impl SharingWindowResponse {
+    pub fn new(window: LocationSharingWindow, now: DateTime<Utc>) -> Self {
+        let is_active = window.is_active(now);
+        let remaining_seconds = if is_active {
+            (window.expires_at - now).num_seconds()
+        } else {
+            0
+        };
+        Self {
+            window,
+            is_active,
+            remaining_seconds,
+        }
+    }
+}
+
+/// Request to start sharing locations with a group.
+#[derive(Debug, Clone, Default, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct StartSharingWindowRequest {
+    /// Window length in minutes (5-1440, default 120)
+    #[validate(range(
+        min = 5,
+        max = 1440,
+        message = "duration_minutes must be between 5 and 1440"
+    ))]
+    pub duration_minutes: Option<i64>,
+}
+
+/// Request to extend an open sharing window.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct ExtendSharingWindowRequest {
+    /// Minutes to add (5-1440); the window never ends more than 24 hours
+    /// from now.
+    #[validate(range(
+        min = 5,
+        max = 1440,
+        message = "extend_minutes must be between 5 and 1440"
+    ))]
+    pub extend_minutes: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(expires_in_minutes: i64, stopped: bool) -> LocationSharingWindow {
+        let now = Utc::now();
+        LocationSharingWindow {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            group_id: Uuid::new_v4(),
+            started_at: now - Duration::minutes(10),
+            expires_at: now + Duration::minutes(expires_in_minutes),
+            stopped_at: stopped.then_some(now),
+        }
+    }
+
+    #[test]
+    fn test_is_active() {
+        let now = Utc::now();
+        assert!(window(30, false).is_active(now));
+        assert!(!window(-1, false).is_active(now));
+        assert!(!window(30, true).is_active(now));
+    }
+
+    #[test]
+    fn test_extended_expiry() {
+        let now = Utc::now();
+
+        // Extends from the current expiry
+        let expiry = extended_expiry(now + Duration::minutes(30), 60, now);
+        assert_eq!(expiry, now + Duration::minutes(90));
+
+        // An expired window extends from now
+        let expiry = extended_expiry(now - Duration::minutes(30), 60, now);
+        assert_eq!(expiry, now + Duration::minutes(60));
+
+        // Capped at 24 hours from now
+        let expiry = extended_expiry(now + Duration::minutes(1400), 120, now);
+        assert_eq!(expiry, now + Duration::minutes(MAX_SHARING_WINDOW_MINUTES));
+    }
+
+    #[test]
+    fn test_response_remaining_seconds() {
+        let now = Utc::now();
+        let active = window(30, false);
+        let response = SharingWindowResponse::new(active.clone(), now);
+        assert!(response.is_active);
+        assert_eq!(
+            response.remaining_seconds,
+            (active.expires_at - now).num_seconds()
+        );
+
+        let response = SharingWindowResponse::new(window(30, true), now);
+        assert!(!response.is_active);
+        assert_eq!(response.remaining_seconds, 0);
+    }
+
+    #[test]
+    fn test_request_validation() {
+        assert!(StartSharingWindowRequest::default().validate().is_ok());
+        assert!(StartSharingWindowRequest {
+            duration_minutes: Some(120)
+        }
+        .validate()
+        .is_ok());
+        assert!(StartSharingWindowRequest {
+            duration_minutes: Some(1)
+        }
+        .validate()
+        .is_err());
+        assert!(ExtendSharingWindowRequest {
+            extend_minutes: 2000
+        }
+        .validate()
+        .is_err());
+    }
+}
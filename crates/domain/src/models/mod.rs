@@ -8,41 +8,74 @@ pub mod api_key;
 pub mod app_usage;
 pub mod audit_log;
 pub mod bulk_import;
+pub mod commute;
 pub mod compliance;
 pub mod dashboard;
 pub mod data_subject_request;
 pub mod device;
+pub mod device_agent;
+pub mod device_command_macro;
 pub mod device_policy;
+pub mod device_push_token;
+pub mod device_telemetry;
 pub mod device_token;
+pub mod effective_access;
 pub mod enrollment;
 pub mod enrollment_token;
 pub mod fleet;
 pub mod geofence;
 pub mod geofence_event;
+pub mod geofence_template;
 pub mod group;
+pub mod group_api_token;
+pub mod group_custom_role;
+pub mod group_event;
+pub mod group_export;
+pub mod group_message;
+pub mod group_notification_preferences;
+pub mod group_settings;
 pub mod invite;
 pub mod location;
+pub mod location_import;
+pub mod location_masking;
+pub mod location_sharing_schedule;
+pub mod location_sharing_window;
 pub mod managed_user;
 pub mod movement_event;
 pub mod org_member_invite;
+pub mod org_ownership_transfer;
 pub mod org_user;
 pub mod org_webhook;
 pub mod organization;
+pub mod organization_export;
 pub mod organization_role;
 pub mod organization_settings;
+pub mod outbox;
 pub mod permission;
+pub mod privacy_zone;
 pub mod proximity_alert;
+pub mod report_builder;
 pub mod setting;
 pub mod setting_change;
+pub mod speed_violation;
 pub mod system_config;
 pub mod system_role;
+pub mod tenant_log;
 pub mod trip;
+pub mod trip_edit;
 pub mod trip_path_correction;
+pub mod trip_purpose;
+pub mod trip_share;
+pub mod unit_system;
 pub mod unlock_request;
 pub mod usage_warning;
 pub mod user;
 pub mod user_geofence;
 pub mod webhook;
+pub mod webhook_payload_format;
+pub mod webhook_payload_template;
+pub mod webhook_retry_policy;
+pub mod weekly_schedule;
 
 pub use admin_geofence::{
     AdminAllDeviceLocationsResponse, AdminDeviceLocation, AdminDeviceLocationResponse,
@@ -50,17 +83,20 @@ pub use admin_geofence::{
     AdminGeofenceInfo, AdminGeofenceListResponse, AdminGeofencePagination, AdminGeofenceQuery,
     AdminLocationAnalyticsResponse, AdminLocationHistoryQuery, AdminLocationHistoryResponse,
     CreateAdminGeofenceRequest, CreateAdminGeofenceResponse, DeleteAdminGeofenceResponse,
-    GeofenceVisitCount, LocationAnalyticsSummary, UpdateAdminGeofenceRequest,
-    UpdateAdminGeofenceResponse,
+    GeofenceCategoryCount, GeofenceVisitCount, LocationAnalyticsSummary,
+    UpdateAdminGeofenceRequest, UpdateAdminGeofenceResponse,
 };
 pub use admin_group::{
     AddGroupMemberRequest, AddGroupMemberResponse, AdminGroupDetailResponse, AdminGroupItem,
     AdminGroupListResponse, AdminGroupPagination, AdminGroupProfile, AdminGroupQuery,
-    AdminGroupSortField, AdminGroupSummary, CreateGroupInvitationRequest,
-    CreateGroupInvitationResponse, DeactivateGroupResponse, GroupDeviceInfo, GroupInvitationInfo,
-    GroupMemberInfo, GroupMembersPagination, GroupOwnerInfo, ListGroupInvitationsResponse,
-    ListGroupMembersQuery, ListGroupMembersResponse, RemoveGroupMemberResponse,
-    UpdateAdminGroupRequest, UpdateAdminGroupResponse,
+    AdminGroupSortField, AdminGroupSummary, BulkGroupMemberAction, BulkGroupMemberOperation,
+    BulkGroupMemberResult, BulkGroupMembersRequest, BulkGroupMembersResponse,
+    CreateGroupInvitationRequest, CreateGroupInvitationResponse, DeactivateGroupResponse,
+    GroupDeviceInfo, GroupInvitationInfo, GroupMemberInfo, GroupMembersPagination, GroupOwnerInfo,
+    ListGroupInvitationsResponse, ListGroupMembersQuery, ListGroupMembersResponse,
+    MoveGroupMemberResult, MoveGroupMembersRequest, MoveGroupMembersResponse,
+    RemoveGroupMemberResponse, UpdateAdminGroupRequest, UpdateAdminGroupResponse,
+    MAX_BULK_GROUP_MEMBERS,
 };
 pub use admin_user::{
     AdminUserDetailResponse, AdminUserItem, AdminUserListResponse, AdminUserPagination,
@@ -72,9 +108,11 @@ pub use analytics::{
     AnalyticsGroupBy, AnalyticsPeriod, ApiUsageAnalyticsQuery, ApiUsageAnalyticsResponse,
     ApiUsageSummary, ApiUsageTrend, DeviceActivityTrend, DeviceAnalyticsQuery,
     DeviceAnalyticsResponse, DeviceAnalyticsSummary,
-    DeviceStatusBreakdown as AnalyticsDeviceStatusBreakdown, EndpointUsage, GenerateReportRequest,
+    DeviceStatusBreakdown as AnalyticsDeviceStatusBreakdown, EndpointUsage, ExtractDataset,
+    ExtractFormat, GenerateExtractRequest, GenerateMileageReportRequest, GenerateReportRequest,
     ReportDownloadResponse, ReportFormat, ReportJobResponse, ReportStatus, UserActivityTrend,
     UserAnalyticsQuery, UserAnalyticsResponse, UserAnalyticsSummary, UserRoleBreakdown,
+    MAX_EXTRACT_RANGE_DAYS, MAX_MILEAGE_REPORT_RANGE_DAYS,
 };
 pub use api_key::{
     ApiKeyPagination, ApiKeyResponse, CreateApiKeyRequest, CreateApiKeyResponse, ListApiKeysQuery,
@@ -87,11 +125,13 @@ pub use app_usage::{
     AppUsageSummaryResponse, CategoryUsageItem, TopAppItem,
 };
 pub use audit_log::{
-    ActorType, AsyncExportResponse, AuditAction, AuditActor, AuditLog, AuditLogPagination,
-    AuditMetadata, AuditResource, CreateAuditLogInput, ExportAuditLogsQuery, ExportFormat,
-    ExportJobResponse, ExportJobStatus, FieldChange, ListAuditLogsQuery, ListAuditLogsResponse,
-    ResourceType, SyncExportResponse, EXPORT_JOB_EXPIRY_HOURS, MAX_EXPORT_RECORDS,
-    MAX_SYNC_EXPORT_RECORDS,
+    validate_export_destination, ActorType, AsyncExportResponse, AuditAction, AuditActor,
+    AuditExportCursor, AuditLog, AuditLogPagination, AuditMetadata, AuditResource,
+    CreateAuditLogInput, ExportAuditLogsQuery, ExportFormat, ExportJobKind, ExportJobResponse,
+    ExportJobStatus, FieldChange, IncrementalExportQuery, IncrementalExportResponse,
+    ListAuditExportCursorsResponse, ListAuditLogsQuery, ListAuditLogsResponse, ResourceType,
+    SyncExportResponse, EXPORT_JOB_EXPIRY_HOURS, INCREMENTAL_EXPORT_SETTLE_SECONDS,
+    MAX_EXPORT_DESTINATION_LENGTH, MAX_EXPORT_RECORDS, MAX_SYNC_EXPORT_RECORDS,
 };
 pub use bulk_import::{
     BulkDeviceImportRequest, BulkDeviceImportResponse, BulkDeviceInput, BulkDeviceItem,
@@ -102,8 +142,9 @@ pub use compliance::{
     ActionCount, AuditActivitySummary, AuditLogStats, ComplianceAssessment,
     ComplianceDashboardResponse, ComplianceFinding, ComplianceReportFormat, ComplianceReportQuery,
     ComplianceReportResponse, ComplianceStatus, DataRetentionStatus,
-    DataSubjectRequestReportSummary, DataSubjectRequestStats, FindingSeverity,
-    OrganizationReportSummary, RequestStatusCounts, RequestTypeCount,
+    DataSubjectRequestReportSummary, DataSubjectRequestStats, DeviceConfigurationSummary,
+    FindingSeverity, OrganizationReportSummary, PolicyDeviceCount, RequestStatusCounts,
+    RequestTypeCount,
 };
 pub use dashboard::{
     ActivityPeriod, ActivitySummary, DashboardMetrics, DeviceMetrics, DeviceStatusBreakdown,
@@ -116,16 +157,38 @@ pub use data_subject_request::{
     ProcessDataSubjectRequestRequest, ProcessorInfo,
 };
 pub use device::Device;
+pub use device_agent::{
+    AgentClientMessage, AgentConnectionInfo, AgentServerMessage, ListAgentConnectionsResponse,
+    AGENT_HEARTBEAT_INTERVAL_SECS, AGENT_MISSED_HEARTBEATS, MAX_AGENT_FAILURE_REASON_LENGTH,
+};
+pub use device_command_macro::{
+    macro_parameters, CommandMacroDeviceFilter, CommandMacroRun, CommandMacroRunCommand,
+    CommandMacroRunCounts, CommandMacroRunResponse, CommandMacroRunStatus, CommandMacroStep,
+    DeviceCommandMacro, ListCommandMacrosResponse, RunCommandMacroRequest, SaveCommandMacroRequest,
+    MAX_MACRO_RUN_DEVICES, MAX_MACRO_STEPS,
+};
 pub use device_policy::{
     AppliedToCount, ApplyPolicyRequest, ApplyPolicyResponse, CreateDevicePolicyRequest,
     DevicePolicy, DevicePolicyPagination, DevicePolicyResponse, ListDevicePoliciesQuery,
     ListDevicePoliciesResponse, PolicyTarget, PolicyTargetType, UnapplyPolicyRequest,
     UnapplyPolicyResponse, UpdateDevicePolicyRequest,
 };
+pub use device_push_token::{
+    push_token_hint, DevicePushToken, ListPushTokensResponse, PushPlatform,
+    RegisterPushTokenRequest, MAX_PUSH_TOKENS_PER_DEVICE,
+};
+pub use device_telemetry::{
+    is_low_battery_drop, ChargingState, DeviceTelemetry, UploadTelemetryRequest,
+    LOW_BATTERY_THRESHOLD_PERCENT,
+};
 pub use device_token::{
     calculate_device_token_expiry, extract_device_token_prefix, generate_device_token, DeviceToken,
     EnrollmentStatus, DEFAULT_TOKEN_EXPIRY_DAYS, DEVICE_TOKEN_PREFIX,
 };
+pub use effective_access::{
+    group_capabilities, DeviceAccess, DeviceAccessSource, EffectiveAccessResponse,
+    EffectiveAccessUser, GroupAccess, OrganizationAccess, SystemAccess,
+};
 pub use enrollment::{
     DeviceInfo, EnrollDeviceRequest, EnrollDeviceResponse, EnrolledDevice, EnrollmentGroupInfo,
     EnrollmentPolicyInfo,
@@ -136,23 +199,55 @@ pub use enrollment_token::{
     ListEnrollmentTokensResponse, QrCodeResponse,
 };
 pub use fleet::{
-    AssignDeviceRequest, AssignDeviceResponse, AssignedUserInfo, BulkDeviceUpdate,
-    BulkDeviceUpdateResult, BulkUpdateDevicesRequest, BulkUpdateDevicesResponse, DeviceCommand,
-    DeviceCommandHistoryItem, DeviceCommandHistoryPagination, DeviceCommandHistoryQuery,
-    DeviceCommandHistoryResponse, DeviceCommandStatus, DeviceCommandType,
-    DeviceStatusChangeResponse, FleetDeviceItem, FleetDeviceListResponse, FleetDeviceQuery,
+    ApiEndpointClass, AssignDeviceRequest, AssignDeviceResponse, AssignedUserInfo,
+    BulkDeviceUpdate, BulkDeviceUpdateResult, BulkUpdateDevicesRequest, BulkUpdateDevicesResponse,
+    DeviceApiUsageItem, DeviceApiUsageResponse, DeviceCommand, DeviceCommandHistoryItem,
+    DeviceCommandHistoryPagination, DeviceCommandHistoryQuery, DeviceCommandHistoryResponse,
+    DeviceCommandStatus, DeviceCommandType, DeviceStatusChangeResponse, FleetDeviceDetailResponse,
+    FleetDeviceExportQuery, FleetDeviceItem, FleetDeviceListResponse, FleetDeviceQuery,
     FleetGroupInfo, FleetLastLocation, FleetPagination, FleetPolicyInfo, FleetSortField,
     FleetSummary, IssueCommandRequest, IssueCommandResponse, SortOrder, UnassignDeviceResponse,
     MAX_BULK_UPDATE_DEVICES,
 };
 pub use geofence::Geofence;
 pub use geofence_event::{
-    CreateGeofenceEventRequest, GeofenceEvent, GeofenceEventResponse, GeofenceTransitionType,
-    ListGeofenceEventsQuery, ListGeofenceEventsResponse,
+    CreateGeofenceEventRequest, GeofenceEvent, GeofenceEventResponse, GeofenceEventSource,
+    GeofenceTransitionType, ListGeofenceEventsQuery, ListGeofenceEventsResponse,
+};
+pub use geofence_template::{
+    ApplyGeofenceTemplateRequest, ApplyGeofenceTemplateResponse, DeleteGeofenceTemplateQuery,
+    GeofenceTemplate, ListGeofenceTemplatesResponse, ListTemplateGeofencesResponse,
+    SaveGeofenceTemplateRequest, UpdateGeofenceTemplateResponse, MAX_TEMPLATE_APPLY_DEVICES,
 };
 pub use group::{Group, GroupMembership, GroupRole};
+pub use group_api_token::{
+    CreateGroupApiTokenRequest, GroupApiTokenResponse, GroupIntegrationDevicesResponse,
+    ListGroupApiTokensResponse, DEFAULT_GROUP_API_TOKEN_RATE_LIMIT, GROUP_API_TOKEN_PREFIX,
+    MAX_GROUP_API_TOKENS_PER_GROUP, MAX_GROUP_API_TOKEN_RATE_LIMIT,
+};
+pub use group_custom_role::{
+    AssignGroupCustomRoleRequest, GroupCustomRole, GroupPermission, GroupPermissions,
+    ListGroupCustomRolesResponse, MemberPermissionsResponse, SaveGroupCustomRoleRequest,
+};
+pub use group_event::{GroupEvent, GroupEventType, ListGroupEventsQuery, ListGroupEventsResponse};
+pub use group_export::{GroupExportDevice, GroupExportManifest, GroupExportMember};
+pub use group_message::{
+    message_preview, CheckInLocation, GroupMessage, GroupMessageKind, ListGroupMessagesQuery,
+    ListGroupMessagesResponse, PostGroupMessageRequest,
+};
+pub use group_notification_preferences::{
+    GroupNotificationCategory, GroupNotificationPreferences, GroupNotificationPreferencesResponse,
+    UpdateGroupNotificationPreferencesRequest,
+};
 pub use invite::GroupInvite;
-pub use location::Location;
+pub use location::{Location, LocationSource};
+pub use location_import::{LocationImportJob, LocationImportStatus, GOOGLE_TAKEOUT_SOURCE};
+pub use location_masking::{mask_coordinate, mask_json_locations};
+pub use location_sharing_schedule::{LocationSharingSchedule, SharingScheduleResponse};
+pub use location_sharing_window::{
+    extended_expiry, ExtendSharingWindowRequest, LocationSharingWindow, SharingWindowResponse,
+    StartSharingWindowRequest, DEFAULT_SHARING_WINDOW_MINUTES,
+};
 pub use managed_user::{
     ListManagedUsersQuery, ListManagedUsersResponse, ManagedUser, ManagedUserPagination,
     RemoveManagedUserResponse, UpdateTrackingRequest, UpdateTrackingResponse, UserLastLocation,
@@ -165,6 +260,10 @@ pub use org_member_invite::{
     ListInvitationsResponse, DEFAULT_EXPIRATION_DAYS, MAX_EXPIRATION_DAYS, MAX_INVITATIONS_PER_ORG,
     MIN_EXPIRATION_DAYS,
 };
+pub use org_ownership_transfer::{
+    AcceptOrgTransferRequest, InitiateOrgTransferRequest, OrgOwnershipTransfer, OrgTransferStatus,
+    ORG_TRANSFER_EXPIRY_HOURS,
+};
 pub use org_user::{
     validate_permissions, AddOrgUserRequest, ForceMfaResponse, ListOrgUsersQuery,
     ListOrgUsersResponse, ListUserSessionsResponse, MfaMethod, MfaStatusResponse, OrgUser,
@@ -174,19 +273,24 @@ pub use org_user::{
     UpdateOrgUserRequest, UserSessionInfo, PERMISSIONS,
 };
 pub use org_webhook::{
-    CreateOrgWebhookRequest, ListOrgWebhooksResponse, ListWebhookDeliveriesQuery,
-    ListWebhookDeliveriesResponse, OrgWebhookResponse, RetryDeliveryResponse,
-    TestOrgWebhookRequest, TestOrgWebhookResponse, UpdateOrgWebhookRequest,
-    WebhookDeliveryResponse, WebhookPagination, WebhookStatsResponse, MAX_WEBHOOKS_PER_ORG,
-    SUPPORTED_EVENT_TYPES,
+    event_type_description, CreateOrgWebhookRequest, ListOrgWebhookEventTypesResponse,
+    ListOrgWebhooksResponse, ListWebhookDeadLettersQuery, ListWebhookDeadLettersResponse,
+    ListWebhookDeliveriesQuery, ListWebhookDeliveriesResponse, MemberEventMember,
+    MemberEventPayload, MemberEventType, OrgWebhookEventTypeInfo, OrgWebhookResponse,
+    PurgeDeadLettersResponse, RequeueDeadLetterResponse, RetryDeliveryResponse,
+    SetOrgWebhookClientCertificateRequest, TestOrgWebhookRequest, TestOrgWebhookResponse,
+    UpdateOrgWebhookRequest, WebhookDeadLetterResponse, WebhookDeliveryResponse, WebhookPagination,
+    WebhookStatsResponse, MAX_CLIENT_CERT_PEM_LENGTH, MAX_WEBHOOKS_PER_ORG, SUPPORTED_EVENT_TYPES,
 };
 pub use organization::{
-    CreateOrganizationRequest, CreateOrganizationResponse, DeviceStatusCounts, DeviceUsageMetric,
-    ListOrganizationsQuery, ListOrganizationsResponse, Organization, OrganizationPagination,
-    OrganizationUsageResponse, OrganizationWithUsage, PlanType, ReactivateOrganizationResponse,
-    SuspendOrganizationRequest, SuspendOrganizationResponse, UpdateOrganizationRequest,
-    UsageMetric, SLUG_REGEX,
+    CreateOrganizationRequest, CreateOrganizationResponse, CreateTrialOrganizationRequest,
+    DeviceStatusCounts, DeviceUsageMetric, ListOrganizationsQuery, ListOrganizationsResponse,
+    Organization, OrganizationPagination, OrganizationUsageResponse, OrganizationWithUsage,
+    PlanSimulationQuery, PlanSimulationResponse, PlanType, QuotaSimulation,
+    ReactivateOrganizationResponse, SuspendOrganizationRequest, SuspendOrganizationResponse,
+    UpdateOrganizationRequest, UsageMetric, SLUG_REGEX,
 };
+pub use organization_export::{OrganizationExportDevice, OrganizationExportManifest};
 pub use organization_role::{
     is_system_role_name, CreateOrganizationRoleRequest, DeleteOrganizationRoleResponse,
     ListOrganizationRolesQuery, ListOrganizationRolesResponse, OrganizationRole,
@@ -196,12 +300,23 @@ pub use organization_settings::{
     OrganizationSettings, OrganizationSettingsResponse, UpdateOrganizationSettingsRequest,
     VerifyPinRequest, VerifyPinResponse,
 };
+pub use outbox::OutboxMessage;
 pub use permission::{
     get_all_permissions, get_permissions_by_category, get_permissions_by_category_filter,
     ListPermissionsQuery, ListPermissionsResponse, Permission, PermissionCategory,
     PermissionsByCategory,
 };
+pub use privacy_zone::{
+    distance_meters, share_location, CreatePrivacyZoneRequest, ListPrivacyZonesResponse,
+    PrivacyZone, PrivacyZoneMode, PrivacyZoneSet, SharedLocation, UpdatePrivacyZoneRequest,
+    MAX_PRIVACY_ZONES_PER_USER,
+};
 pub use proximity_alert::ProximityAlert;
+pub use report_builder::{
+    CreateReportTemplateRequest, ListReportTemplatesResponse, ReportBuilderDefinition,
+    ReportDimension, ReportSection, ReportTemplate, UpdateReportTemplateRequest,
+    MAX_REPORT_TEMPLATES_PER_ORG,
+};
 pub use setting::{
     DeviceSetting, GetSettingsResponse, SettingCategory, SettingDataType, SettingDefinition,
     SettingValue,
@@ -211,11 +326,12 @@ pub use system_config::{
     AuthTogglesInfo, DatabaseSettingsInfo, EmailSettingsInfo, EmailTemplate,
     EmailTemplatesResponse, FcmSettingsInfo, FeatureFlagResponse, FeatureFlagsInfo,
     FeatureFlagsResponse, FrontendSettingsInfo, LimitsSettingsInfo, LoggingSettingsInfo,
-    MaintenanceModeResponse, MapMatchingSettingsInfo, NotificationTemplate,
-    NotificationTemplatesResponse, RateLimitConfigItem, RateLimitsResponse, SecuritySettingsInfo,
-    ServerSettingsInfo, SystemSettingItem, SystemSettingsResponse, ToggleMaintenanceModeRequest,
-    UpdateEmailTemplateRequest, UpdateFeatureFlagRequest, UpdateNotificationTemplateRequest,
-    UpdateRateLimitsRequest, UpdateSystemSettingsRequest, UpdateSystemSettingsResponse,
+    MaintenanceModeResponse, MapMatchingSettingsInfo, MetricsSnapshotFormat, MetricsSnapshotQuery,
+    NotificationTemplate, NotificationTemplatesResponse, RateLimitConfigItem, RateLimitsResponse,
+    SecuritySettingsInfo, ServerSettingsInfo, SystemSettingItem, SystemSettingsResponse,
+    ToggleMaintenanceModeRequest, UpdateEmailTemplateRequest, UpdateFeatureFlagRequest,
+    UpdateNotificationTemplateRequest, UpdateRateLimitsRequest, UpdateSystemSettingsRequest,
+    UpdateSystemSettingsResponse,
 };
 pub use system_role::{
     AddSystemRoleRequest, AddSystemRoleResponse, AdminOrgAssignment, AssignOrgRequest,
@@ -223,8 +339,10 @@ pub use system_role::{
     RemoveSystemRoleResponse, SystemRole, SystemRoleInfo, UserOrgAssignmentsResponse,
     UserSystemRole, UserSystemRoleDetail, UserSystemRolesResponse, SYSTEM_PERMISSIONS,
 };
+pub use tenant_log::{ExportTenantLogsQuery, ExportTenantLogsResponse, LogLevel, TenantLogEntry};
 pub use trip::Trip;
 pub use trip_path_correction::TripPathCorrection;
+pub use unit_system::UnitSystem;
 pub use unlock_request::{
     AdminListUnlockRequestsQuery, AdminListUnlockRequestsResponse, AdminUnlockPagination,
     AdminUnlockRequestActionResponse, AdminUnlockRequestItem, AdminUserBrief,
@@ -240,6 +358,21 @@ pub use user_geofence::{
     ListUserGeofencesResponse, UpdateUserGeofenceRequest, UpdateUserGeofenceResponse, UserGeofence,
 };
 pub use webhook::{
-    CreateWebhookRequest, ListWebhooksQuery, ListWebhooksResponse, UpdateWebhookRequest, Webhook,
-    WebhookResponse,
+    CreateWebhookRequest, ListWebhooksQuery, ListWebhooksResponse, TestWebhookQuery,
+    TestWebhookResponse, UpdateWebhookRequest, Webhook, WebhookBatching, WebhookListFilter,
+    WebhookOwner, WebhookResponse, DEFAULT_WEBHOOK_BATCH_MAX_EVENTS,
+    MAX_WEBHOOK_DELIVERIES_PER_MINUTE, SUPPORTED_WEBHOOK_EVENT_TYPES,
+    WEBHOOK_RATE_LIMIT_WINDOW_SECS,
+};
+pub use webhook_payload_format::{
+    payload_content_type, WebhookPayloadFormat, CLOUD_EVENTS_CONTENT_TYPE, CLOUD_EVENT_TYPE_PREFIX,
+};
+pub use webhook_payload_template::{
+    render_payload_template, validate_payload_template, WebhookTemplateValues,
+    MAX_PAYLOAD_TEMPLATE_BYTES, WEBHOOK_TEMPLATE_PLACEHOLDERS,
+};
+pub use webhook_retry_policy::{
+    WebhookBackoffStrategy, WebhookRetryPolicy, DEFAULT_RETRY_BACKOFF_SECONDS,
+    MAX_WEBHOOK_ATTEMPTS, MAX_WEBHOOK_BACKOFF_SECS, MAX_WEBHOOK_TIMEOUT_SECS,
 };
+pub use weekly_schedule::{ScheduleWindow, WeeklySchedule, MAX_SCHEDULE_WINDOWS};
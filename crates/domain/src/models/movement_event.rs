@@ -70,6 +70,8 @@ pub enum DetectionSource {
     AndroidAuto,
     Multiple,
     None,
+    /// Derived on the server from the device's location stream.
+    LocationStream,
 }
 
 impl DetectionSource {
@@ -81,6 +83,7 @@ impl DetectionSource {
             DetectionSource::AndroidAuto => "ANDROID_AUTO",
             DetectionSource::Multiple => "MULTIPLE",
             DetectionSource::None => "NONE",
+            DetectionSource::LocationStream => "LOCATION_STREAM",
         }
     }
 }
@@ -101,8 +104,51 @@ impl std::str::FromStr for DetectionSource {
             "ANDROID_AUTO" => Ok(DetectionSource::AndroidAuto),
             "MULTIPLE" => Ok(DetectionSource::Multiple),
             "NONE" => Ok(DetectionSource::None),
+            "LOCATION_STREAM" => Ok(DetectionSource::LocationStream),
             _ => Err(format!(
-                "Invalid detection source: {}. Must be one of: ACTIVITY_RECOGNITION, BLUETOOTH_CAR, ANDROID_AUTO, MULTIPLE, NONE",
+                "Invalid detection source: {}. Must be one of: ACTIVITY_RECOGNITION, BLUETOOTH_CAR, ANDROID_AUTO, MULTIPLE, NONE, LOCATION_STREAM",
+                s
+            )),
+        }
+    }
+}
+
+/// Movement state transition derived on the server from the location stream.
+///
+/// An `Arrived` event marks the start of a stationary period ("stationary
+/// since" its timestamp); a `Departed` event marks the start of movement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MovementState {
+    Arrived,
+    Departed,
+}
+
+impl MovementState {
+    /// Returns the string representation for database storage.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MovementState::Arrived => "ARRIVED",
+            MovementState::Departed => "DEPARTED",
+        }
+    }
+}
+
+impl fmt::Display for MovementState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for MovementState {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ARRIVED" => Ok(MovementState::Arrived),
+            "DEPARTED" => Ok(MovementState::Departed),
+            _ => Err(format!(
+                "Invalid movement state: {}. Must be one of: ARRIVED, DEPARTED",
                 s
             )),
         }
@@ -134,6 +180,9 @@ pub struct MovementEvent {
     pub transportation_mode: TransportationMode,
     pub confidence: f64,
     pub detection_source: DetectionSource,
+    /// Set on events derived from the location stream.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub movement_state: Option<MovementState>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -294,6 +343,8 @@ pub struct MovementEventResponse {
     pub transportation_mode: TransportationMode,
     pub confidence: f64,
     pub detection_source: DetectionSource,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub movement_state: Option<MovementState>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -312,6 +363,7 @@ impl From<MovementEvent> for MovementEventResponse {
             transportation_mode: event.transportation_mode,
             confidence: event.confidence,
             detection_source: event.detection_source,
+            movement_state: event.movement_state,
             created_at: event.created_at,
         }
     }
@@ -432,6 +484,7 @@ mod tests {
         assert_eq!(DetectionSource::AndroidAuto.as_str(), "ANDROID_AUTO");
         assert_eq!(DetectionSource::Multiple.as_str(), "MULTIPLE");
         assert_eq!(DetectionSource::None.as_str(), "NONE");
+        assert_eq!(DetectionSource::LocationStream.as_str(), "LOCATION_STREAM");
     }
 
     #[test]
@@ -463,6 +516,20 @@ mod tests {
         assert!("invalid".parse::<DetectionSource>().is_err());
     }
 
+    // =========================================================================
+    // MovementState Tests
+    // =========================================================================
+
+    #[test]
+    fn test_movement_state_round_trip() {
+        for state in [MovementState::Arrived, MovementState::Departed] {
+            assert_eq!(state.as_str().parse::<MovementState>().unwrap(), state);
+            let json = serde_json::to_string(&state).unwrap();
+            assert_eq!(json, format!("\"{}\"", state.as_str()));
+        }
+        assert!("MOVING".parse::<MovementState>().is_err());
+    }
+
     #[test]
     fn test_detection_source_serde() {
         let source = DetectionSource::ActivityRecognition;
@@ -681,6 +748,7 @@ mod tests {
             transportation_mode: TransportationMode::Walking,
             confidence: 0.95,
             detection_source: DetectionSource::ActivityRecognition,
+            movement_state: None,
             created_at: Utc::now(),
         };
 
@@ -768,6 +836,7 @@ mod tests {
             transportation_mode: TransportationMode::Walking,
             confidence: 0.95,
             detection_source: DetectionSource::ActivityRecognition,
+            movement_state: None,
             created_at: Utc::now(),
         };
 
@@ -798,6 +867,7 @@ mod tests {
                 transportation_mode: TransportationMode::Walking,
                 confidence: 0.95,
                 detection_source: DetectionSource::ActivityRecognition,
+                movement_state: None,
                 created_at: Utc::now(),
             })
             .collect();
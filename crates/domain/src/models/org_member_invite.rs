@@ -33,7 +33,11 @@ pub struct CreateInvitationRequest {
     pub role: Option<String>,
 
     /// Optional note for admin tracking.
-    #[validate(length(max = 255, message = "Note must be at most 255 characters"))]
+    #[serde(
+        default,
+        deserialize_with = "shared::text::deserialize_optional_multiline"
+    )]
+    #[validate(custom(function = "shared::text::validate_note"))]
     pub note: Option<String>,
 
     /// Days until expiration (1-30, default: 7).
@@ -184,11 +188,8 @@ pub struct AcceptInvitationRequest {
     pub password: String,
 
     /// Display name for the new user.
-    #[validate(length(
-        min = 1,
-        max = 100,
-        message = "Display name must be between 1 and 100 characters"
-    ))]
+    #[serde(deserialize_with = "shared::text::deserialize_line")]
+    #[validate(custom(function = "shared::text::validate_display_name"))]
     pub display_name: String,
 }
 
@@ -0,0 +1,139 @@
+//! Organization ownership transfer models.
+//!
+//! The current owner initiates a transfer to an admin of the organization;
+//! the transfer takes effect only once that admin accepts it. Both steps
+//! require the acting user to confirm their password.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Hours a pending transfer can be accepted.
+pub const ORG_TRANSFER_EXPIRY_HOURS: i64 = 72;
+
+/// Status of an ownership transfer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrgTransferStatus {
+    Pending,
+    Accepted,
+    Cancelled,
+}
+
+impl From<&str> for OrgTransferStatus {
+    fn from(s: &str) -> Self {
+        match s {
+            "accepted" => OrgTransferStatus::Accepted,
+            "cancelled" => OrgTransferStatus::Cancelled,
+            _ => OrgTransferStatus::Pending,
+        }
+    }
+}
+
+impl OrgTransferStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrgTransferStatus::Pending => "pending",
+            OrgTransferStatus::Accepted => "accepted",
+            OrgTransferStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
+/// An ownership transfer of an organization.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct OrgOwnershipTransfer {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub from_user_id: Uuid,
+    pub to_user_id: Uuid,
+    pub status: OrgTransferStatus,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl OrgOwnershipTransfer {
+    /// Expiry time for a transfer initiated at `now`.
+    pub fn expiry_from(now: DateTime<Utc>) -> DateTime<Utc> {
+        now + Duration::hours(ORG_TRANSFER_EXPIRY_HOURS)
+    }
+
+    /// Whether the transfer can still be accepted.
+    pub fn is_open(&self, now: DateTime<Utc>) -> bool {
+        self.status == OrgTransferStatus::Pending && self.expires_at > now
+    }
+}
+
+/// Request to initiate an ownership transfer.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct InitiateOrgTransferRequest {
+    /// The user ID of the new owner (must be an admin of the organization).
+    pub new_owner_id: Uuid,
+    /// The current owner's password, confirming the action.
+    #[validate(length(min = 1, message = "Password is required"))]
+    pub password: String,
+}
+
+/// Request to accept an ownership transfer.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct AcceptOrgTransferRequest {
+    /// The accepting admin's password, confirming the action.
+    #[validate(length(min = 1, message = "Password is required"))]
+    pub password: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transfer(status: OrgTransferStatus, expires_at: DateTime<Utc>) -> OrgOwnershipTransfer {
+        OrgOwnershipTransfer {
+            id: Uuid::nil(),
+            organization_id: Uuid::nil(),
+            from_user_id: Uuid::nil(),
+            to_user_id: Uuid::nil(),
+            status,
+            expires_at,
+            created_at: Utc::now(),
+            resolved_at: None,
+        }
+    }
+
+    #[test]
+    fn test_is_open() {
+        let now = Utc::now();
+        let expires_at = OrgOwnershipTransfer::expiry_from(now);
+        assert_eq!(expires_at - now, Duration::hours(72));
+
+        assert!(transfer(OrgTransferStatus::Pending, expires_at).is_open(now));
+        assert!(!transfer(OrgTransferStatus::Pending, expires_at).is_open(expires_at));
+        assert!(!transfer(OrgTransferStatus::Accepted, expires_at).is_open(now));
+        assert!(!transfer(OrgTransferStatus::Cancelled, expires_at).is_open(now));
+    }
+
+    #[test]
+    fn test_status_round_trip() {
+        for status in [
+            OrgTransferStatus::Pending,
+            OrgTransferStatus::Accepted,
+            OrgTransferStatus::Cancelled,
+        ] {
+            assert_eq!(OrgTransferStatus::from(status.as_str()), status);
+        }
+    }
+
+    #[test]
+    fn test_initiate_request_requires_password() {
+        let request: InitiateOrgTransferRequest = serde_json::from_value(serde_json::json!({
+            "new_owner_id": Uuid::nil(),
+            "password": ""
+        }))
+        .unwrap();
+        assert!(request.validate().is_err());
+    }
+}
@@ -24,6 +24,9 @@ pub enum OrgUserRole {
     Owner,
     Admin,
     Member,
+    /// Read-only access to dashboards and device lists with masked
+    /// locations, e.g. for external consultants.
+    Observer,
 }
 
 impl OrgUserRole {
@@ -31,14 +34,29 @@ impl OrgUserRole {
     pub fn has_at_least(&self, required: OrgUserRole) -> bool {
         match (self, required) {
             (OrgUserRole::Owner, _) => true,
-            (OrgUserRole::Admin, OrgUserRole::Member) => true,
-            (OrgUserRole::Admin, OrgUserRole::Admin) => true,
             (OrgUserRole::Admin, OrgUserRole::Owner) => false,
-            (OrgUserRole::Member, OrgUserRole::Member) => true,
+            (OrgUserRole::Admin, _) => true,
+            (OrgUserRole::Member, OrgUserRole::Member | OrgUserRole::Observer) => true,
             (OrgUserRole::Member, _) => false,
+            (OrgUserRole::Observer, OrgUserRole::Observer) => true,
+            (OrgUserRole::Observer, _) => false,
         }
     }
 
+    /// Whether this role can view organization analytics, device lists and
+    /// current device locations.
+    pub fn can_view_org_data(&self) -> bool {
+        matches!(
+            self,
+            OrgUserRole::Owner | OrgUserRole::Admin | OrgUserRole::Observer
+        )
+    }
+
+    /// Whether this role only sees imprecise device locations.
+    pub fn masks_locations(&self) -> bool {
+        *self == OrgUserRole::Observer
+    }
+
     /// Get default permissions for this role.
     pub fn default_permissions(&self) -> Vec<String> {
         match self {
@@ -51,6 +69,7 @@ impl OrgUserRole {
                 "policy:read".to_string(),
             ],
             OrgUserRole::Member => vec!["device:read".to_string(), "user:read".to_string()],
+            OrgUserRole::Observer => vec!["device:read".to_string()],
         }
     }
 }
@@ -63,6 +82,7 @@ impl FromStr for OrgUserRole {
             "owner" => Ok(OrgUserRole::Owner),
             "admin" => Ok(OrgUserRole::Admin),
             "member" => Ok(OrgUserRole::Member),
+            "observer" => Ok(OrgUserRole::Observer),
             _ => Err(format!("Unknown role: {}", s)),
         }
     }
@@ -74,6 +94,7 @@ impl std::fmt::Display for OrgUserRole {
             OrgUserRole::Owner => write!(f, "owner"),
             OrgUserRole::Admin => write!(f, "admin"),
             OrgUserRole::Member => write!(f, "member"),
+            OrgUserRole::Observer => write!(f, "observer"),
         }
     }
 }
@@ -112,8 +133,10 @@ impl OrgUser {
     pub fn can_manage_role(&self, target_role: OrgUserRole) -> bool {
         match self.role {
             OrgUserRole::Owner => true,
-            OrgUserRole::Admin => target_role == OrgUserRole::Member,
-            OrgUserRole::Member => false,
+            OrgUserRole::Admin => {
+                matches!(target_role, OrgUserRole::Member | OrgUserRole::Observer)
+            }
+            OrgUserRole::Member | OrgUserRole::Observer => false,
         }
     }
 
@@ -121,6 +144,20 @@ impl OrgUser {
     pub fn is_suspended(&self) -> bool {
         self.suspended_at.is_some()
     }
+
+    /// Permissions this user currently holds in the organization.
+    ///
+    /// Suspended users hold none; owners hold every permission.
+    pub fn effective_permissions(&self) -> Vec<String> {
+        if self.is_suspended() {
+            return Vec::new();
+        }
+        PERMISSIONS
+            .iter()
+            .filter(|p| self.has_permission(p))
+            .map(|p| p.to_string())
+            .collect()
+    }
 }
 
 /// User info for organization user responses.
@@ -425,6 +462,25 @@ mod tests {
         assert!(OrgUserRole::Admin.has_at_least(OrgUserRole::Member));
         assert!(!OrgUserRole::Admin.has_at_least(OrgUserRole::Owner));
         assert!(!OrgUserRole::Member.has_at_least(OrgUserRole::Admin));
+        assert!(OrgUserRole::Member.has_at_least(OrgUserRole::Observer));
+        assert!(!OrgUserRole::Observer.has_at_least(OrgUserRole::Member));
+    }
+
+    #[test]
+    fn test_observer_role() {
+        assert_eq!(
+            OrgUserRole::from_str("observer").unwrap(),
+            OrgUserRole::Observer
+        );
+        assert_eq!(OrgUserRole::Observer.to_string(), "observer");
+        assert!(OrgUserRole::Observer.can_view_org_data());
+        assert!(OrgUserRole::Observer.masks_locations());
+        assert!(!OrgUserRole::Member.can_view_org_data());
+        assert!(!OrgUserRole::Admin.masks_locations());
+        assert_eq!(
+            OrgUserRole::Observer.default_permissions(),
+            vec!["device:read".to_string()]
+        );
     }
 
     #[test]
@@ -474,6 +530,30 @@ mod tests {
         assert!(!admin.has_permission("audit:read"));
     }
 
+    #[test]
+    fn test_effective_permissions() {
+        let mut member = OrgUser {
+            id: Uuid::nil(),
+            organization_id: Uuid::nil(),
+            user_id: Uuid::nil(),
+            role: OrgUserRole::Member,
+            permissions: vec!["device:read".to_string(), "unknown:perm".to_string()],
+            granted_at: Utc::now(),
+            granted_by: None,
+            suspended_at: None,
+            suspended_by: None,
+            suspension_reason: None,
+        };
+        // Unknown permissions are not reported
+        assert_eq!(member.effective_permissions(), vec!["device:read"]);
+
+        member.role = OrgUserRole::Owner;
+        assert_eq!(member.effective_permissions().len(), PERMISSIONS.len());
+
+        member.suspended_at = Some(Utc::now());
+        assert!(member.effective_permissions().is_empty());
+    }
+
     #[test]
     fn test_can_manage_role() {
         let owner = OrgUser {
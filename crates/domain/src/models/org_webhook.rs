@@ -7,6 +7,9 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::Validate;
 
+use super::webhook_payload_format::WebhookPayloadFormat;
+use super::webhook_retry_policy::WebhookRetryPolicy;
+
 /// Maximum webhooks per organization.
 pub const MAX_WEBHOOKS_PER_ORG: i64 = 50;
 
@@ -16,23 +19,32 @@ pub const MIN_SECRET_LENGTH: usize = 16;
 /// Maximum secret length.
 pub const MAX_SECRET_LENGTH: usize = 256;
 
+/// Maximum length of a PEM client certificate chain or private key.
+pub const MAX_CLIENT_CERT_PEM_LENGTH: u64 = 16384;
+
 /// Supported organization webhook event types.
 pub const SUPPORTED_EVENT_TYPES: &[&str] = &[
     "device.enrolled",
     "device.unenrolled",
     "device.assigned",
     "device.unassigned",
+    "member.invited",
     "member.joined",
     "member.removed",
+    "member.suspended",
+    "member.mfa_reset",
+    "member.role_changed",
     "policy.applied",
     "policy.updated",
+    "trip.speed_violation",
 ];
 
 /// Request to create an organization webhook.
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 pub struct CreateOrgWebhookRequest {
     /// Webhook name (1-100 characters).
-    #[validate(length(min = 1, max = 100))]
+    #[serde(deserialize_with = "shared::text::deserialize_line")]
+    #[validate(custom(function = "shared::text::validate_name"))]
     pub name: String,
 
     /// Target URL (must be HTTPS).
@@ -46,6 +58,13 @@ pub struct CreateOrgWebhookRequest {
     /// Event types to subscribe to.
     #[validate(length(min = 1, message = "At least one event type is required"))]
     pub event_types: Vec<String>,
+
+    /// Delivery retry policy; the default policy if omitted.
+    pub retry_policy: Option<WebhookRetryPolicy>,
+
+    /// Payload format; `default` delivers the payload as is.
+    #[serde(default)]
+    pub format: WebhookPayloadFormat,
 }
 
 impl CreateOrgWebhookRequest {
@@ -72,7 +91,8 @@ impl CreateOrgWebhookRequest {
 #[derive(Debug, Clone, Serialize, Deserialize, Default, Validate)]
 pub struct UpdateOrgWebhookRequest {
     /// New webhook name.
-    #[validate(length(min = 1, max = 100))]
+    #[serde(default, deserialize_with = "shared::text::deserialize_optional_line")]
+    #[validate(custom(function = "shared::text::validate_name"))]
     pub name: Option<String>,
 
     /// New target URL.
@@ -88,6 +108,12 @@ pub struct UpdateOrgWebhookRequest {
 
     /// New event types.
     pub event_types: Option<Vec<String>>,
+
+    /// Replaces the delivery retry policy.
+    pub retry_policy: Option<WebhookRetryPolicy>,
+
+    /// Replaces the payload format.
+    pub format: Option<WebhookPayloadFormat>,
 }
 
 impl UpdateOrgWebhookRequest {
@@ -98,6 +124,8 @@ impl UpdateOrgWebhookRequest {
             || self.secret.is_some()
             || self.enabled.is_some()
             || self.event_types.is_some()
+            || self.retry_policy.is_some()
+            || self.format.is_some()
     }
 
     /// Validates that the target URL uses HTTPS (if provided).
@@ -151,6 +179,19 @@ pub struct OrgWebhookResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub circuit_open_until: Option<DateTime<Utc>>,
 
+    /// Delivery retry policy in effect.
+    pub retry_policy: WebhookRetryPolicy,
+
+    /// Whether the organization's webhook retry policy replaces the
+    /// webhook's own.
+    pub retry_policy_overridden: bool,
+
+    /// Payload format (`default` or `cloudevents`).
+    pub format: WebhookPayloadFormat,
+
+    /// Whether deliveries present a client certificate (mutual TLS).
+    pub has_client_certificate: bool,
+
     /// When the webhook was created.
     pub created_at: DateTime<Utc>,
 
@@ -158,6 +199,19 @@ pub struct OrgWebhookResponse {
     pub updated_at: DateTime<Utc>,
 }
 
+/// Request to set the client certificate an organization webhook presents
+/// for mutual TLS.
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct SetOrgWebhookClientCertificateRequest {
+    /// PEM certificate, optionally followed by intermediate certificates.
+    #[validate(length(min = 1, max = MAX_CLIENT_CERT_PEM_LENGTH))]
+    pub certificate_pem: String,
+
+    /// PEM private key (PKCS#8, PKCS#1 or SEC1); stored encrypted.
+    #[validate(length(min = 1, max = MAX_CLIENT_CERT_PEM_LENGTH))]
+    pub private_key_pem: String,
+}
+
 /// Response for listing organization webhooks.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ListOrgWebhooksResponse {
@@ -261,6 +315,14 @@ pub struct WebhookDeliveryResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error_message: Option<String>,
 
+    /// Duration of the last attempt in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<i32>,
+
+    /// Response body of the last attempt, truncated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_body: Option<String>,
+
     /// When the delivery was created.
     pub created_at: DateTime<Utc>,
 }
@@ -307,6 +369,85 @@ pub struct RetryDeliveryResponse {
     pub message: String,
 }
 
+/// Query parameters for listing dead-lettered webhook deliveries.
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct ListWebhookDeadLettersQuery {
+    /// Page number (1-based).
+    #[validate(range(min = 1))]
+    pub page: Option<u32>,
+
+    /// Items per page.
+    #[validate(range(min = 1, max = 100))]
+    pub per_page: Option<u32>,
+}
+
+/// Webhook delivery that failed after its final retry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookDeadLetterResponse {
+    /// Dead letter UUID.
+    pub id: Uuid,
+
+    /// UUID of the failed delivery.
+    pub delivery_id: Uuid,
+
+    /// Event ID the delivery was for (if applicable).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<Uuid>,
+
+    /// Event type.
+    pub event_type: String,
+
+    /// Payload exactly as it was delivered.
+    pub payload: serde_json::Value,
+
+    /// Number of delivery attempts made.
+    pub attempts: i32,
+
+    /// HTTP response code from the last attempt.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_code: Option<i32>,
+
+    /// Error message from the last attempt.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_message: Option<String>,
+
+    /// When the original delivery was created.
+    pub delivery_created_at: DateTime<Utc>,
+
+    /// When the delivery was dead-lettered.
+    pub dead_lettered_at: DateTime<Utc>,
+}
+
+/// Response for listing dead-lettered webhook deliveries.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListWebhookDeadLettersResponse {
+    /// Dead letters, newest first.
+    pub dead_letters: Vec<WebhookDeadLetterResponse>,
+
+    /// Pagination info.
+    pub pagination: WebhookPagination,
+}
+
+/// Response for requeueing a dead-lettered delivery.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequeueDeadLetterResponse {
+    /// Whether the delivery was requeued.
+    pub success: bool,
+
+    /// UUID of the new pending delivery.
+    pub delivery_id: Uuid,
+
+    /// Message about the requeue.
+    pub message: String,
+}
+
+/// Response for purging a webhook's dead letters.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PurgeDeadLettersResponse {
+    /// Number of dead letters deleted.
+    pub purged: u64,
+}
+
 /// Webhook delivery statistics.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WebhookStatsResponse {
@@ -342,6 +483,184 @@ pub struct WebhookStatsResponse {
     pub time_period: String,
 }
 
+/// Organization member lifecycle events delivered to org webhooks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MemberEventType {
+    #[serde(rename = "member.invited")]
+    Invited,
+    #[serde(rename = "member.joined")]
+    Joined,
+    #[serde(rename = "member.removed")]
+    Removed,
+    #[serde(rename = "member.suspended")]
+    Suspended,
+    #[serde(rename = "member.mfa_reset")]
+    MfaReset,
+    #[serde(rename = "member.role_changed")]
+    RoleChanged,
+}
+
+impl MemberEventType {
+    /// All member event types.
+    pub const ALL: [MemberEventType; 6] = [
+        MemberEventType::Invited,
+        MemberEventType::Joined,
+        MemberEventType::Removed,
+        MemberEventType::Suspended,
+        MemberEventType::MfaReset,
+        MemberEventType::RoleChanged,
+    ];
+
+    /// Event type name used in subscriptions and payloads.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MemberEventType::Invited => "member.invited",
+            MemberEventType::Joined => "member.joined",
+            MemberEventType::Removed => "member.removed",
+            MemberEventType::Suspended => "member.suspended",
+            MemberEventType::MfaReset => "member.mfa_reset",
+            MemberEventType::RoleChanged => "member.role_changed",
+        }
+    }
+}
+
+impl std::str::FromStr for MemberEventType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|t| t.as_str() == s)
+            .ok_or_else(|| format!("Not a member event type: {}", s))
+    }
+}
+
+impl std::fmt::Display for MemberEventType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Describe a supported event type for the event type catalog.
+pub fn event_type_description(event_type: &str) -> &'static str {
+    match event_type {
+        "device.enrolled" => "A device was enrolled in the organization",
+        "device.unenrolled" => "A device was unenrolled from the organization",
+        "device.assigned" => "A device was assigned to a user",
+        "device.unassigned" => "A device was unassigned from its user",
+        "member.invited" => "Someone was invited to join the organization",
+        "member.joined" => "A user became a member of the organization",
+        "member.removed" => "A member was removed from the organization",
+        "member.suspended" => "A member was suspended",
+        "member.mfa_reset" => "An admin reset a member's multi-factor authentication",
+        "member.role_changed" => "A member's role changed",
+        "policy.applied" => "A device policy was applied",
+        "policy.updated" => "A device policy was updated",
+        "trip.speed_violation" => "A trip was driven above the posted speed limit",
+        _ => "",
+    }
+}
+
+/// The member a lifecycle event is about.
+///
+/// Fields that do not apply to an event are omitted.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MemberEventMember {
+    /// Member's user ID; omitted for invitations of people without an account.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// Role in the organization, after the change.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    /// Role before the change (`member.role_changed`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_role: Option<String>,
+    /// Invitation ID (`member.invited`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invitation_id: Option<Uuid>,
+    /// Suspension reason (`member.suspended`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Webhook payload of a member lifecycle event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemberEventPayload {
+    /// Unique event ID, identical across deliveries and retries.
+    pub event_id: Uuid,
+    pub event_type: MemberEventType,
+    pub organization_id: Uuid,
+    /// Milliseconds since epoch.
+    pub timestamp: i64,
+    pub member: MemberEventMember,
+    /// User who made the change; omitted for API key requests.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor_user_id: Option<Uuid>,
+}
+
+impl MemberEventPayload {
+    /// Create the payload of an event happening now.
+    pub fn new(
+        event_type: MemberEventType,
+        organization_id: Uuid,
+        member: MemberEventMember,
+        actor_user_id: Option<Uuid>,
+    ) -> Self {
+        Self {
+            event_id: Uuid::new_v4(),
+            event_type,
+            organization_id,
+            timestamp: Utc::now().timestamp_millis(),
+            member,
+            actor_user_id,
+        }
+    }
+
+    /// Example payload of an event type, for the event type catalog and
+    /// test deliveries.
+    pub fn example(event_type: MemberEventType, organization_id: Uuid) -> Self {
+        let mut member = MemberEventMember {
+            user_id: Some(Uuid::nil()),
+            email: Some("jane.doe@example.com".to_string()),
+            role: Some("member".to_string()),
+            ..Default::default()
+        };
+        match event_type {
+            MemberEventType::Invited => {
+                member.user_id = None;
+                member.invitation_id = Some(Uuid::nil());
+            }
+            MemberEventType::Suspended => {
+                member.reason = Some("Left the company".to_string());
+            }
+            MemberEventType::RoleChanged => {
+                member.role = Some("admin".to_string());
+                member.previous_role = Some("member".to_string());
+            }
+            MemberEventType::Joined | MemberEventType::Removed | MemberEventType::MfaReset => {}
+        }
+        Self::new(event_type, organization_id, member, Some(Uuid::nil()))
+    }
+}
+
+/// An entry of the org webhook event type catalog.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrgWebhookEventTypeInfo {
+    pub event_type: String,
+    pub description: String,
+    /// Example payload; present for event types that are delivered.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example_payload: Option<serde_json::Value>,
+}
+
+/// Response for listing org webhook event types.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListOrgWebhookEventTypesResponse {
+    pub event_types: Vec<OrgWebhookEventTypeInfo>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -353,12 +672,31 @@ mod tests {
             target_url: "https://api.example.com/webhooks".to_string(),
             secret: "whsec_testsecretkey123456".to_string(),
             event_types: vec!["device.enrolled".to_string()],
+            retry_policy: None,
+            format: WebhookPayloadFormat::Default,
         };
         assert!(valid.validate().is_ok());
         assert!(valid.validate_https().is_ok());
         assert!(valid.validate_event_types().is_ok());
     }
 
+    #[test]
+    fn test_create_request_retry_policy_deserialization() {
+        let request: CreateOrgWebhookRequest = serde_json::from_str(
+            r#"{
+                "name": "Production Events",
+                "target_url": "https://api.example.com/webhooks",
+                "secret": "whsec_testsecretkey123456",
+                "event_types": ["member.joined"],
+                "retry_policy": {"max_attempts": 8, "backoff_strategy": "exponential"}
+            }"#,
+        )
+        .unwrap();
+        let policy = request.retry_policy.unwrap();
+        assert_eq!(policy.max_attempts, 8);
+        assert_eq!(policy.timeout_secs, 5);
+    }
+
     #[test]
     fn test_create_request_invalid_url() {
         let invalid = CreateOrgWebhookRequest {
@@ -366,6 +704,8 @@ mod tests {
             target_url: "http://insecure.example.com".to_string(),
             secret: "whsec_testsecretkey123456".to_string(),
             event_types: vec!["device.enrolled".to_string()],
+            retry_policy: None,
+            format: WebhookPayloadFormat::Default,
         };
         assert!(invalid.validate_https().is_err());
     }
@@ -377,6 +717,8 @@ mod tests {
             target_url: "https://api.example.com/webhooks".to_string(),
             secret: "whsec_testsecretkey123456".to_string(),
             event_types: vec!["invalid.event.type".to_string()],
+            retry_policy: None,
+            format: WebhookPayloadFormat::Default,
         };
         assert!(invalid.validate_event_types().is_err());
     }
@@ -388,6 +730,8 @@ mod tests {
             target_url: "https://api.example.com/webhooks".to_string(),
             secret: "short".to_string(),
             event_types: vec!["device.enrolled".to_string()],
+            retry_policy: None,
+            format: WebhookPayloadFormat::Default,
         };
         assert!(invalid.validate().is_err());
     }
@@ -408,6 +752,12 @@ mod tests {
             ..Default::default()
         };
         assert!(with_enabled.has_updates());
+
+        let with_retry_policy = UpdateOrgWebhookRequest {
+            retry_policy: Some(WebhookRetryPolicy::default()),
+            ..Default::default()
+        };
+        assert!(with_retry_policy.has_updates());
     }
 
     #[test]
@@ -458,5 +808,35 @@ mod tests {
         assert!(SUPPORTED_EVENT_TYPES.contains(&"member.joined"));
         assert!(SUPPORTED_EVENT_TYPES.contains(&"policy.updated"));
         assert!(!SUPPORTED_EVENT_TYPES.contains(&"invalid.event"));
+        for event_type in SUPPORTED_EVENT_TYPES {
+            assert!(!event_type_description(event_type).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_member_event_type_round_trip() {
+        for event_type in MemberEventType::ALL {
+            assert!(SUPPORTED_EVENT_TYPES.contains(&event_type.as_str()));
+            assert_eq!(event_type.as_str().parse(), Ok(event_type));
+        }
+        assert!("device.enrolled".parse::<MemberEventType>().is_err());
+    }
+
+    #[test]
+    fn test_member_event_payload_serialization() {
+        let org_id = Uuid::new_v4();
+        let payload = MemberEventPayload::example(MemberEventType::RoleChanged, org_id);
+        let json = serde_json::to_value(&payload).unwrap();
+        assert_eq!(json["event_type"], "member.role_changed");
+        assert_eq!(json["organization_id"], org_id.to_string());
+        assert_eq!(json["member"]["role"], "admin");
+        assert_eq!(json["member"]["previous_role"], "member");
+        assert!(json["member"].get("invitation_id").is_none());
+        assert!(json["member"].get("reason").is_none());
+
+        let invited = MemberEventPayload::example(MemberEventType::Invited, org_id);
+        let json = serde_json::to_value(&invited).unwrap();
+        assert!(json["member"].get("user_id").is_none());
+        assert!(json["member"].get("invitation_id").is_some());
     }
 }
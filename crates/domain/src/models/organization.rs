@@ -15,6 +15,8 @@ pub enum PlanType {
     Starter,
     Business,
     Enterprise,
+    /// Self-service trial, created by users without an admin API key.
+    Trial,
 }
 
 impl PlanType {
@@ -26,6 +28,7 @@ impl PlanType {
             PlanType::Starter => (25, 100, 20),
             PlanType::Business => (100, 500, 50),
             PlanType::Enterprise => (i32::MAX, i32::MAX, i32::MAX), // Unlimited
+            PlanType::Trial => PlanType::Starter.default_limits(),
         }
     }
 }
@@ -39,6 +42,7 @@ impl FromStr for PlanType {
             "starter" => Ok(PlanType::Starter),
             "business" => Ok(PlanType::Business),
             "enterprise" => Ok(PlanType::Enterprise),
+            "trial" => Ok(PlanType::Trial),
             _ => Err(format!("Unknown plan type: {}", s)),
         }
     }
@@ -51,6 +55,7 @@ impl std::fmt::Display for PlanType {
             PlanType::Starter => write!(f, "starter"),
             PlanType::Business => write!(f, "business"),
             PlanType::Enterprise => write!(f, "enterprise"),
+            PlanType::Trial => write!(f, "trial"),
         }
     }
 }
@@ -138,6 +143,89 @@ pub struct DeviceStatusCounts {
     pub retired: i64,
 }
 
+/// Query parameters for simulating usage against another plan.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PlanSimulationQuery {
+    pub plan_type: PlanType,
+}
+
+/// A single quota compared between the current and the simulated plan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct QuotaSimulation {
+    pub resource: String,
+    pub current: i64,
+    pub current_max: i32,
+    pub simulated_max: i32,
+    pub exceeded: bool,
+    /// How far current usage is over the simulated limit (0 when within).
+    pub overage: i64,
+}
+
+impl QuotaSimulation {
+    fn new(resource: &str, current: i64, current_max: i32, simulated_max: i32) -> Self {
+        let overage = (current - simulated_max as i64).max(0);
+        Self {
+            resource: resource.to_string(),
+            current,
+            current_max,
+            simulated_max,
+            exceeded: overage > 0,
+            overage,
+        }
+    }
+}
+
+/// Result of simulating an organization's current usage on another plan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PlanSimulationResponse {
+    pub organization_id: Uuid,
+    pub current_plan: PlanType,
+    pub simulated_plan: PlanType,
+    /// True when current usage fits within all simulated limits.
+    pub fits: bool,
+    /// Resources whose simulated limit would be exceeded.
+    pub exceeded: Vec<String>,
+    pub quotas: Vec<QuotaSimulation>,
+}
+
+impl PlanSimulationResponse {
+    /// Compare current usage against the default limits of `simulated_plan`.
+    pub fn new(
+        current_plan: PlanType,
+        simulated_plan: PlanType,
+        usage: &OrganizationUsageResponse,
+    ) -> Self {
+        let (max_users, max_devices, max_groups) = simulated_plan.default_limits();
+        let quotas = vec![
+            QuotaSimulation::new("users", usage.users.current, usage.users.max, max_users),
+            QuotaSimulation::new(
+                "devices",
+                usage.devices.current,
+                usage.devices.max,
+                max_devices,
+            ),
+            QuotaSimulation::new("groups", usage.groups.current, usage.groups.max, max_groups),
+        ];
+        let exceeded: Vec<String> = quotas
+            .iter()
+            .filter(|q| q.exceeded)
+            .map(|q| q.resource.clone())
+            .collect();
+
+        Self {
+            organization_id: usage.organization_id,
+            current_plan,
+            simulated_plan,
+            fits: exceeded.is_empty(),
+            exceeded,
+            quotas,
+        }
+    }
+}
+
 /// Request to create a new organization.
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 #[serde(rename_all = "snake_case")]
@@ -154,6 +242,21 @@ pub struct CreateOrganizationRequest {
     pub settings: Option<JsonValue>,
 }
 
+/// Request to create a trial organization (self-service).
+///
+/// The plan is always `trial`; the billing email defaults to the creator's.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct CreateTrialOrganizationRequest {
+    #[validate(length(min = 2, max = 255, message = "Name must be 2-255 characters"))]
+    pub name: String,
+    #[validate(length(min = 3, max = 50, message = "Slug must be 3-50 characters"))]
+    #[validate(custom(function = "validate_slug"))]
+    pub slug: String,
+    #[validate(email(message = "Invalid billing email format"))]
+    pub billing_email: Option<String>,
+}
+
 /// Validate slug format: lowercase alphanumeric with hyphens, no leading/trailing hyphens.
 fn validate_slug(slug: &str) -> Result<(), validator::ValidationError> {
     if SLUG_REGEX.is_match(slug) {
@@ -289,6 +392,86 @@ mod tests {
         assert_eq!(users, 100);
         assert_eq!(devices, 500);
         assert_eq!(groups, 50);
+
+        assert_eq!(
+            PlanType::Trial.default_limits(),
+            PlanType::Starter.default_limits()
+        );
+    }
+
+    #[test]
+    fn test_create_trial_organization_request_validation() {
+        let request: CreateTrialOrganizationRequest =
+            serde_json::from_value(json!({"name": "Acme", "slug": "acme-trial"})).unwrap();
+        assert!(request.validate().is_ok());
+        assert!(request.billing_email.is_none());
+
+        let invalid = CreateTrialOrganizationRequest {
+            slug: "-acme".to_string(),
+            billing_email: Some("not-an-email".to_string()),
+            ..request
+        };
+        let errors = invalid.validate().unwrap_err();
+        assert!(errors.field_errors().contains_key("slug"));
+        assert!(errors.field_errors().contains_key("billing_email"));
+    }
+
+    fn sample_usage(users: i64, devices: i64, groups: i64) -> OrganizationUsageResponse {
+        OrganizationUsageResponse {
+            organization_id: Uuid::nil(),
+            users: UsageMetric {
+                current: users,
+                max: 100,
+                percentage: 0.0,
+            },
+            devices: DeviceUsageMetric {
+                current: devices,
+                max: 500,
+                percentage: 0.0,
+                by_status: DeviceStatusCounts::default(),
+            },
+            groups: UsageMetric {
+                current: groups,
+                max: 50,
+                percentage: 0.0,
+            },
+            period: "2026-10".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_plan_simulation_downgrade_exceeds_limits() {
+        let usage = sample_usage(30, 8, 2);
+        let sim = PlanSimulationResponse::new(PlanType::Business, PlanType::Free, &usage);
+
+        assert!(!sim.fits);
+        assert_eq!(sim.exceeded, vec!["users".to_string()]);
+        let users = &sim.quotas[0];
+        assert_eq!(users.simulated_max, 5);
+        assert_eq!(users.current_max, 100);
+        assert_eq!(users.overage, 25);
+        assert!(!sim.quotas[1].exceeded);
+        assert_eq!(sim.quotas[1].overage, 0);
+    }
+
+    #[test]
+    fn test_plan_simulation_fits_at_exact_limit() {
+        let usage = sample_usage(25, 100, 20);
+        let sim = PlanSimulationResponse::new(PlanType::Business, PlanType::Starter, &usage);
+
+        assert!(sim.fits);
+        assert!(sim.exceeded.is_empty());
+    }
+
+    #[test]
+    fn test_plan_simulation_enterprise_is_unlimited() {
+        let usage = sample_usage(10_000, 1_000_000, 500);
+        let sim = PlanSimulationResponse::new(PlanType::Business, PlanType::Enterprise, &usage);
+
+        assert!(sim.fits);
+        let json = serde_json::to_string(&sim).unwrap();
+        assert!(json.contains("\"simulated_plan\":\"enterprise\""));
+        assert!(json.contains("\"current_plan\":\"business\""));
     }
 
     #[test]
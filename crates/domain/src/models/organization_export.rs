@@ -0,0 +1,74 @@
+//! Organization data export models.
+//!
+//! Organization admins can export all of an organization's data (users,
+//! managed devices, device policies, location history and audit logs) as a
+//! ZIP archive. The archive is built by a background export job and
+//! downloaded once the job completes.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Contents of `manifest.json` in an organization export.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct OrganizationExportManifest {
+    pub organization_id: Uuid,
+    pub organization_name: String,
+    pub organization_slug: String,
+    pub exported_at: DateTime<Utc>,
+    pub user_count: i64,
+    pub device_count: i64,
+    pub policy_count: i64,
+    pub location_count: i64,
+    pub audit_log_count: i64,
+}
+
+impl OrganizationExportManifest {
+    /// Total number of exported records.
+    pub fn record_count(&self) -> i64 {
+        self.user_count
+            + self.device_count
+            + self.policy_count
+            + self.location_count
+            + self.audit_log_count
+    }
+}
+
+/// A managed device in `devices.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct OrganizationExportDevice {
+    pub device_id: Uuid,
+    pub display_name: String,
+    pub platform: String,
+    pub active: bool,
+    pub owner_user_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_record_count() {
+        let manifest = OrganizationExportManifest {
+            organization_id: Uuid::new_v4(),
+            organization_name: "Acme".to_string(),
+            organization_slug: "acme".to_string(),
+            exported_at: Utc::now(),
+            user_count: 5,
+            device_count: 10,
+            policy_count: 2,
+            location_count: 1000,
+            audit_log_count: 40,
+        };
+        assert_eq!(manifest.record_count(), 1057);
+
+        let json = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(json["organization_slug"], "acme");
+        assert_eq!(json["audit_log_count"], 40);
+    }
+}
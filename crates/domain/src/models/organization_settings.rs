@@ -7,6 +7,8 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::Validate;
 
+use super::WebhookRetryPolicy;
+
 /// Internal representation of organization settings.
 #[derive(Debug, Clone)]
 pub struct OrganizationSettings {
@@ -22,6 +24,14 @@ pub struct OrganizationSettings {
     pub notifications_enabled: bool,
     /// Automatically approve device unlock requests
     pub auto_approve_unlock_requests: bool,
+    /// Reject uploaded locations with a worse accuracy in meters (None = no limit)
+    pub max_location_accuracy_meters: Option<f64>,
+    /// Reject uploaded locations implying a higher speed in m/s (None = no limit)
+    pub max_location_speed_mps: Option<f64>,
+    /// Terms rejected in names and notes entered by members (empty = no filter)
+    pub blocked_terms: Vec<String>,
+    /// Retry policy replacing those of the organization's webhooks (None = no override)
+    pub webhook_retry_policy: Option<WebhookRetryPolicy>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -38,6 +48,14 @@ pub struct OrganizationSettingsResponse {
     pub notifications_enabled: bool,
     /// Automatically approve device unlock requests
     pub auto_approve_unlock_requests: bool,
+    /// Reject uploaded locations with a worse accuracy in meters (null = no limit)
+    pub max_location_accuracy_meters: Option<f64>,
+    /// Reject uploaded locations implying a higher speed in m/s (null = no limit)
+    pub max_location_speed_mps: Option<f64>,
+    /// Terms rejected in names and notes entered by members (empty = no filter)
+    pub blocked_terms: Vec<String>,
+    /// Retry policy replacing those of the organization's webhooks (null = no override)
+    pub webhook_retry_policy: Option<WebhookRetryPolicy>,
 }
 
 impl From<OrganizationSettings> for OrganizationSettingsResponse {
@@ -47,6 +65,10 @@ impl From<OrganizationSettings> for OrganizationSettingsResponse {
             default_daily_limit_minutes: settings.default_daily_limit_minutes,
             notifications_enabled: settings.notifications_enabled,
             auto_approve_unlock_requests: settings.auto_approve_unlock_requests,
+            max_location_accuracy_meters: settings.max_location_accuracy_meters,
+            max_location_speed_mps: settings.max_location_speed_mps,
+            blocked_terms: settings.blocked_terms,
+            webhook_retry_policy: settings.webhook_retry_policy,
         }
     }
 }
@@ -69,6 +91,67 @@ pub struct UpdateOrganizationSettingsRequest {
     pub notifications_enabled: Option<bool>,
     /// Automatically approve device unlock requests
     pub auto_approve_unlock_requests: Option<bool>,
+    /// Maximum accepted location accuracy in meters (0 = no limit)
+    #[validate(range(min = 0.0, message = "Maximum accuracy must not be negative"))]
+    pub max_location_accuracy_meters: Option<f64>,
+    /// Maximum plausible speed in m/s (0 = no limit)
+    #[validate(range(min = 0.0, message = "Maximum speed must not be negative"))]
+    pub max_location_speed_mps: Option<f64>,
+    /// Replaces the blocked terms; an empty list turns the filter off
+    #[validate(custom(function = "validate_blocked_terms"))]
+    pub blocked_terms: Option<Vec<String>>,
+    /// Retry policy for all of the organization's webhooks, overriding their own
+    #[validate(custom(function = "validate_webhook_retry_policy"))]
+    pub webhook_retry_policy: Option<WebhookRetryPolicy>,
+    /// Remove the webhook retry policy override (if true, ignores webhook_retry_policy)
+    #[serde(default)]
+    pub clear_webhook_retry_policy: bool,
+}
+
+fn validate_webhook_retry_policy(
+    policy: &WebhookRetryPolicy,
+) -> Result<(), validator::ValidationError> {
+    policy.validate().map_err(|message| {
+        let mut err = validator::ValidationError::new("webhook_retry_policy");
+        err.message = Some(message.into());
+        err
+    })
+}
+
+/// Maximum number of blocked terms per organization.
+pub const MAX_BLOCKED_TERMS: usize = 500;
+
+/// Maximum length of a blocked term in characters.
+pub const MAX_BLOCKED_TERM_LENGTH: usize = 50;
+
+fn validate_blocked_terms(terms: &[String]) -> Result<(), validator::ValidationError> {
+    if terms.len() > MAX_BLOCKED_TERMS {
+        let mut err = validator::ValidationError::new("blocked_terms_count");
+        err.message =
+            Some(format!("At most {} blocked terms are allowed", MAX_BLOCKED_TERMS).into());
+        return Err(err);
+    }
+    for term in terms {
+        shared::text::check_length(
+            &shared::text::normalize_line(term),
+            1,
+            MAX_BLOCKED_TERM_LENGTH,
+            "Blocked terms must be between 1 and 50 characters",
+        )?;
+    }
+    Ok(())
+}
+
+/// Normalize blocked terms for storage: lowercased, deduplicated and sorted.
+pub fn normalize_blocked_terms(terms: &[String]) -> Vec<String> {
+    let mut terms: Vec<String> = terms
+        .iter()
+        .map(|term| shared::text::normalize_line(term).to_lowercase())
+        .filter(|term| !term.is_empty())
+        .collect();
+    terms.sort();
+    terms.dedup();
+    terms
 }
 
 /// POST request to verify unlock PIN.
@@ -104,10 +187,33 @@ mod tests {
             default_daily_limit_minutes: 120,
             notifications_enabled: true,
             auto_approve_unlock_requests: false,
+            max_location_accuracy_meters: Some(100.0),
+            max_location_speed_mps: None,
+            blocked_terms: vec![],
+            webhook_retry_policy: None,
         };
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("\"has_unlock_pin\":true"));
         assert!(json.contains("\"default_daily_limit_minutes\":120"));
+        assert!(json.contains("\"max_location_accuracy_meters\":100.0"));
+        assert!(json.contains("\"max_location_speed_mps\":null"));
+    }
+
+    #[test]
+    fn test_blocked_terms_validation_and_normalization() {
+        let request: UpdateOrganizationSettingsRequest =
+            serde_json::from_str(r#"{"blocked_terms": ["Darn", " darn ", "Silly  Goose"]}"#)
+                .unwrap();
+        assert!(request.validate().is_ok());
+        assert_eq!(
+            normalize_blocked_terms(&request.blocked_terms.unwrap()),
+            vec!["darn".to_string(), "silly goose".to_string()]
+        );
+
+        let too_long: UpdateOrganizationSettingsRequest =
+            serde_json::from_value(serde_json::json!({ "blocked_terms": ["x".repeat(51)] }))
+                .unwrap();
+        assert!(too_long.validate().is_err());
     }
 
     #[test]
@@ -126,6 +232,11 @@ mod tests {
             default_daily_limit_minutes: Some(120),
             notifications_enabled: None,
             auto_approve_unlock_requests: None,
+            max_location_accuracy_meters: None,
+            max_location_speed_mps: None,
+            blocked_terms: None,
+            webhook_retry_policy: None,
+            clear_webhook_retry_policy: false,
         };
         assert!(request.validate().is_err());
 
@@ -135,8 +246,28 @@ mod tests {
             default_daily_limit_minutes: Some(120),
             notifications_enabled: Some(true),
             auto_approve_unlock_requests: Some(false),
+            max_location_accuracy_meters: Some(0.0),
+            max_location_speed_mps: Some(70.0),
+            blocked_terms: Some(vec!["casino".to_string()]),
+            webhook_retry_policy: Some(WebhookRetryPolicy::default()),
+            clear_webhook_retry_policy: false,
         };
         assert!(valid_request.validate().is_ok());
+
+        let negative_limit = UpdateOrganizationSettingsRequest {
+            max_location_speed_mps: Some(-1.0),
+            ..valid_request.clone()
+        };
+        assert!(negative_limit.validate().is_err());
+
+        let invalid_policy = UpdateOrganizationSettingsRequest {
+            webhook_retry_policy: Some(WebhookRetryPolicy {
+                max_attempts: 11,
+                ..Default::default()
+            }),
+            ..valid_request
+        };
+        assert!(invalid_policy.validate().is_err());
     }
 
     #[test]
@@ -0,0 +1,71 @@
+//! Transactional outbox messages.
+//!
+//! Side effects of a domain change, such as webhook deliveries and push
+//! notifications, are stored as outbox messages in the same transaction as
+//! the change and dispatched by a background job. A crash between commit and
+//! dispatch delays them instead of losing them.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A side effect waiting in the outbox.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum OutboxMessage {
+    /// Fan out a stored geofence event to the group activity feed and
+    /// webhooks.
+    GeofenceEvent {
+        event_id: Uuid,
+        geofence_name: String,
+    },
+    /// Notify the device of an unlock request that an admin answered.
+    UnlockRequestResponse {
+        device_id: Uuid,
+        request_id: Uuid,
+        setting_key: String,
+        status: String,
+        note: Option<String>,
+        decided_by: String,
+    },
+}
+
+impl OutboxMessage {
+    /// Name of the message kind, stored next to the payload.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::GeofenceEvent { .. } => "geofence_event",
+            Self::UnlockRequestResponse { .. } => "unlock_request_response",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_roundtrip() {
+        let message = OutboxMessage::GeofenceEvent {
+            event_id: Uuid::nil(),
+            geofence_name: "Home".to_string(),
+        };
+        let value = serde_json::to_value(&message).unwrap();
+        assert_eq!(value["kind"], message.kind());
+        assert_eq!(value["geofence_name"], "Home");
+        assert_eq!(
+            serde_json::from_value::<OutboxMessage>(value).unwrap(),
+            message
+        );
+
+        let message = OutboxMessage::UnlockRequestResponse {
+            device_id: Uuid::nil(),
+            request_id: Uuid::nil(),
+            setting_key: "tracking_enabled".to_string(),
+            status: "approved".to_string(),
+            note: None,
+            decided_by: "Admin".to_string(),
+        };
+        let value = serde_json::to_value(&message).unwrap();
+        assert_eq!(value["kind"], "unlock_request_response");
+    }
+}
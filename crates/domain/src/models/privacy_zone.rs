@@ -0,0 +1,416 @@
+//! Privacy zone models.
+//!
+//! Users define privacy zones (e.g. home, work) around places they do not
+//! want other group members to see precisely. Locations of the user's devices
+//! inside a zone are suppressed or snapped to the zone center before being
+//! shared; the owner always sees exact data.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Maximum number of privacy zones per user.
+pub const MAX_PRIVACY_ZONES_PER_USER: i64 = 10;
+
+/// Earth radius in meters used for zone distance checks.
+const EARTH_RADIUS_METERS: f64 = 6_371_000.0;
+
+/// How locations inside a privacy zone are shared.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PrivacyZoneMode {
+    /// Points inside the zone are not shared at all.
+    Suppress,
+    /// Points inside the zone are reported at the zone center.
+    #[default]
+    Snap,
+}
+
+impl PrivacyZoneMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PrivacyZoneMode::Suppress => "suppress",
+            PrivacyZoneMode::Snap => "snap",
+        }
+    }
+}
+
+impl std::str::FromStr for PrivacyZoneMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "suppress" => Ok(PrivacyZoneMode::Suppress),
+            "snap" => Ok(PrivacyZoneMode::Snap),
+            _ => Err(format!("Invalid privacy zone mode: {}", s)),
+        }
+    }
+}
+
+/// A user's privacy zone.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct PrivacyZone {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub radius_meters: f32,
+    pub mode: PrivacyZoneMode,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PrivacyZone {
+    /// Whether a point lies inside the zone.
+    pub fn contains(&self, latitude: f64, longitude: f64) -> bool {
+        distance_meters(self.latitude, self.longitude, latitude, longitude)
+            <= f64::from(self.radius_meters)
+    }
+}
+
+/// Shared form of a location after applying privacy zones.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SharedLocation {
+    /// Outside every zone (or viewed by the owner): shared unchanged.
+    Exact,
+    /// Inside a snapping zone: report the zone center with the zone radius
+    /// as accuracy.
+    Snapped {
+        latitude: f64,
+        longitude: f64,
+        accuracy: f64,
+    },
+    /// Inside a suppressing zone: not shared.
+    Suppressed,
+}
+
+impl SharedLocation {
+    /// Position to share for an exact `(latitude, longitude, accuracy)`, or
+    /// None if the point must not be shared.
+    pub fn apply(self, latitude: f64, longitude: f64, accuracy: f64) -> Option<(f64, f64, f64)> {
+        match self {
+            SharedLocation::Exact => Some((latitude, longitude, accuracy)),
+            SharedLocation::Snapped {
+                latitude,
+                longitude,
+                accuracy: zone_accuracy,
+            } => Some((latitude, longitude, zone_accuracy.max(accuracy))),
+            SharedLocation::Suppressed => None,
+        }
+    }
+}
+
+/// Apply a user's zones to a point. Suppression wins over snapping when
+/// zones overlap; among snapping zones the first match is used.
+pub fn share_location(zones: &[PrivacyZone], latitude: f64, longitude: f64) -> SharedLocation {
+    let mut shared = SharedLocation::Exact;
+    for zone in zones.iter().filter(|z| z.contains(latitude, longitude)) {
+        match zone.mode {
+            PrivacyZoneMode::Suppress => return SharedLocation::Suppressed,
+            PrivacyZoneMode::Snap if shared == SharedLocation::Exact => {
+                shared = SharedLocation::Snapped {
+                    latitude: zone.latitude,
+                    longitude: zone.longitude,
+                    accuracy: f64::from(zone.radius_meters),
+                };
+            }
+            PrivacyZoneMode::Snap => {}
+        }
+    }
+    shared
+}
+
+/// Privacy zones of several device owners, applied on a viewer's behalf.
+#[derive(Debug, Clone, Default)]
+pub struct PrivacyZoneSet {
+    viewer: Option<Uuid>,
+    zones: HashMap<Uuid, Vec<PrivacyZone>>,
+    /// Owners whose locations are not shared at all.
+    hidden: HashSet<Uuid>,
+    /// Hidden owners whose sharing schedule is currently paused.
+    paused: HashSet<Uuid>,
+}
+
+impl PrivacyZoneSet {
+    /// Group zones by owner. `viewer` is the requesting user, if known; a
+    /// viewer always sees their own devices exactly.
+    pub fn new(viewer: Option<Uuid>, zones: Vec<PrivacyZone>) -> Self {
+        let mut by_owner: HashMap<Uuid, Vec<PrivacyZone>> = HashMap::new();
+        for zone in zones {
+            by_owner.entry(zone.user_id).or_default().push(zone);
+        }
+        Self {
+            viewer,
+            zones: by_owner,
+            hidden: HashSet::new(),
+            paused: HashSet::new(),
+        }
+    }
+
+    /// Withhold every location of `owners` from the viewer, such as members
+    /// who are not currently sharing with the group.
+    pub fn hide_owners(&mut self, owners: impl IntoIterator<Item = Uuid>) {
+        self.hidden.extend(owners);
+    }
+
+    /// Withhold every location of `owners` whose sharing schedule is outside
+    /// its windows, reporting their sharing as paused.
+    pub fn pause_owners(&mut self, owners: impl IntoIterator<Item = Uuid>) {
+        for owner in owners {
+            self.hidden.insert(owner);
+            self.paused.insert(owner);
+        }
+    }
+
+    /// Whether the viewer sees the sharing of `owner` as paused.
+    pub fn is_paused(&self, owner: Option<Uuid>) -> bool {
+        owner.is_some_and(|owner| Some(owner) != self.viewer && self.paused.contains(&owner))
+    }
+
+    /// Shared form of a point reported by a device owned by `owner`.
+    pub fn share(&self, owner: Option<Uuid>, latitude: f64, longitude: f64) -> SharedLocation {
+        match owner {
+            Some(owner) if Some(owner) != self.viewer && self.hidden.contains(&owner) => {
+                SharedLocation::Suppressed
+            }
+            Some(owner) if Some(owner) != self.viewer => self
+                .zones
+                .get(&owner)
+                .map(|zones| share_location(zones, latitude, longitude))
+                .unwrap_or(SharedLocation::Exact),
+            _ => SharedLocation::Exact,
+        }
+    }
+}
+
+/// Great-circle distance between two points in meters.
+pub fn distance_meters(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let d_lat = (lat2 - lat1).to_radians();
+    let d_lon = (lon2 - lon1).to_radians();
+    let a = (d_lat / 2.0).sin().powi(2)
+        + lat1.to_radians().cos() * lat2.to_radians().cos() * (d_lon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_METERS * a.sqrt().asin()
+}
+
+/// Request to create a privacy zone.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct CreatePrivacyZoneRequest {
+    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    pub name: String,
+
+    #[validate(custom(function = "shared::validation::validate_latitude"))]
+    pub latitude: f64,
+
+    #[validate(custom(function = "shared::validation::validate_longitude"))]
+    pub longitude: f64,
+
+    #[validate(range(
+        min = 50.0,
+        max = 5000.0,
+        message = "Radius must be between 50 and 5000 meters"
+    ))]
+    pub radius_meters: f32,
+
+    #[serde(default)]
+    pub mode: PrivacyZoneMode,
+}
+
+/// Request to update a privacy zone. Omitted fields are unchanged.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct UpdatePrivacyZoneRequest {
+    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    pub name: Option<String>,
+
+    #[validate(custom(function = "shared::validation::validate_latitude"))]
+    pub latitude: Option<f64>,
+
+    #[validate(custom(function = "shared::validation::validate_longitude"))]
+    pub longitude: Option<f64>,
+
+    #[validate(range(
+        min = 50.0,
+        max = 5000.0,
+        message = "Radius must be between 50 and 5000 meters"
+    ))]
+    pub radius_meters: Option<f32>,
+
+    pub mode: Option<PrivacyZoneMode>,
+}
+
+/// Response for listing the current user's privacy zones.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ListPrivacyZonesResponse {
+    pub zones: Vec<PrivacyZone>,
+    pub total: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zone(user_id: Uuid, mode: PrivacyZoneMode) -> PrivacyZone {
+        PrivacyZone {
+            id: Uuid::new_v4(),
+            user_id,
+            name: "Home".to_string(),
+            latitude: 48.1486,
+            longitude: 17.1077,
+            radius_meters: 200.0,
+            mode,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_zone_contains() {
+        let z = zone(Uuid::new_v4(), PrivacyZoneMode::Snap);
+        assert!(z.contains(48.1486, 17.1077));
+        // ~111 m north
+        assert!(z.contains(48.1496, 17.1077));
+        // ~1.1 km north
+        assert!(!z.contains(48.1586, 17.1077));
+    }
+
+    #[test]
+    fn test_share_location_snaps_to_center() {
+        let zones = vec![zone(Uuid::new_v4(), PrivacyZoneMode::Snap)];
+        assert_eq!(
+            share_location(&zones, 48.1490, 17.1080),
+            SharedLocation::Snapped {
+                latitude: 48.1486,
+                longitude: 17.1077,
+                accuracy: 200.0,
+            }
+        );
+        assert_eq!(share_location(&zones, 48.2, 17.2), SharedLocation::Exact);
+    }
+
+    #[test]
+    fn test_shared_location_apply() {
+        assert_eq!(
+            SharedLocation::Exact.apply(1.0, 2.0, 5.0),
+            Some((1.0, 2.0, 5.0))
+        );
+        let snapped = SharedLocation::Snapped {
+            latitude: 3.0,
+            longitude: 4.0,
+            accuracy: 200.0,
+        };
+        assert_eq!(snapped.apply(1.0, 2.0, 5.0), Some((3.0, 4.0, 200.0)));
+        assert_eq!(snapped.apply(1.0, 2.0, 500.0), Some((3.0, 4.0, 500.0)));
+        assert_eq!(SharedLocation::Suppressed.apply(1.0, 2.0, 5.0), None);
+    }
+
+    #[test]
+    fn test_suppress_wins_over_snap() {
+        let user = Uuid::new_v4();
+        let zones = vec![
+            zone(user, PrivacyZoneMode::Snap),
+            zone(user, PrivacyZoneMode::Suppress),
+        ];
+        assert_eq!(
+            share_location(&zones, 48.1486, 17.1077),
+            SharedLocation::Suppressed
+        );
+    }
+
+    #[test]
+    fn test_zone_set_exempts_owner() {
+        let owner = Uuid::new_v4();
+        let viewer = Uuid::new_v4();
+        let zones = vec![zone(owner, PrivacyZoneMode::Suppress)];
+
+        let as_member = PrivacyZoneSet::new(Some(viewer), zones.clone());
+        assert_eq!(
+            as_member.share(Some(owner), 48.1486, 17.1077),
+            SharedLocation::Suppressed
+        );
+        assert_eq!(
+            as_member.share(None, 48.1486, 17.1077),
+            SharedLocation::Exact
+        );
+
+        let as_owner = PrivacyZoneSet::new(Some(owner), zones.clone());
+        assert_eq!(
+            as_owner.share(Some(owner), 48.1486, 17.1077),
+            SharedLocation::Exact
+        );
+
+        let anonymous = PrivacyZoneSet::new(None, zones);
+        assert_eq!(
+            anonymous.share(Some(owner), 48.1486, 17.1077),
+            SharedLocation::Suppressed
+        );
+    }
+
+    #[test]
+    fn test_zone_set_hidden_owners() {
+        let owner = Uuid::new_v4();
+        let viewer = Uuid::new_v4();
+
+        let mut as_member = PrivacyZoneSet::new(Some(viewer), Vec::new());
+        as_member.hide_owners([owner, viewer]);
+        assert_eq!(
+            as_member.share(Some(owner), 48.1486, 17.1077),
+            SharedLocation::Suppressed
+        );
+        // The viewer's own devices stay visible to them
+        assert_eq!(
+            as_member.share(Some(viewer), 48.1486, 17.1077),
+            SharedLocation::Exact
+        );
+    }
+
+    #[test]
+    fn test_zone_set_paused_owners() {
+        let owner = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let viewer = Uuid::new_v4();
+
+        let mut as_member = PrivacyZoneSet::new(Some(viewer), Vec::new());
+        as_member.hide_owners([other]);
+        as_member.pause_owners([owner, viewer]);
+        assert!(as_member.is_paused(Some(owner)));
+        assert_eq!(
+            as_member.share(Some(owner), 48.1486, 17.1077),
+            SharedLocation::Suppressed
+        );
+        // Hidden without a schedule is not paused
+        assert!(!as_member.is_paused(Some(other)));
+        // The viewer's own sharing never looks paused to them
+        assert!(!as_member.is_paused(Some(viewer)));
+        assert!(!as_member.is_paused(None));
+    }
+
+    #[test]
+    fn test_create_request_validation() {
+        let request: CreatePrivacyZoneRequest = serde_json::from_str(
+            r#"{"name": "Work", "latitude": 48.0, "longitude": 17.0, "radius_meters": 300}"#,
+        )
+        .unwrap();
+        assert_eq!(request.mode, PrivacyZoneMode::Snap);
+        assert!(request.validate().is_ok());
+
+        let too_small = CreatePrivacyZoneRequest {
+            radius_meters: 10.0,
+            ..request
+        };
+        assert!(too_small.validate().is_err());
+    }
+
+    #[test]
+    fn test_mode_round_trip() {
+        for mode in [PrivacyZoneMode::Suppress, PrivacyZoneMode::Snap] {
+            assert_eq!(mode.as_str().parse::<PrivacyZoneMode>(), Ok(mode));
+        }
+        assert!("blur".parse::<PrivacyZoneMode>().is_err());
+    }
+}
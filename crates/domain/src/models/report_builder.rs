@@ -0,0 +1,320 @@
+//! Report builder domain models.
+//!
+//! Custom reports combine selectable sections with grouping dimensions and a
+//! date range. Definitions can be saved as organization report templates.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use super::analytics::ReportFormat;
+
+/// Maximum number of templates an organization can save.
+pub const MAX_REPORT_TEMPLATES_PER_ORG: i64 = 50;
+
+/// A data section that can be included in a custom report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportSection {
+    /// Active devices and reported locations.
+    Devices,
+    /// Completed trips, distance and duration.
+    Trips,
+    /// Geofence enter/exit/dwell event counts.
+    GeofenceCompliance,
+    /// App foreground time and launches.
+    AppUsage,
+}
+
+impl ReportSection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportSection::Devices => "devices",
+            ReportSection::Trips => "trips",
+            ReportSection::GeofenceCompliance => "geofence_compliance",
+            ReportSection::AppUsage => "app_usage",
+        }
+    }
+}
+
+/// A dimension report rows can be grouped by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportDimension {
+    Day,
+    Week,
+    Month,
+    Device,
+}
+
+impl ReportDimension {
+    /// Postgres `date_trunc` field for time dimensions.
+    pub fn date_trunc_field(&self) -> Option<&'static str> {
+        match self {
+            ReportDimension::Day => Some("day"),
+            ReportDimension::Week => Some("week"),
+            ReportDimension::Month => Some("month"),
+            ReportDimension::Device => None,
+        }
+    }
+}
+
+/// Time bucket for grouped rows, if any time dimension is selected.
+pub fn report_time_bucket(dimensions: &[ReportDimension]) -> Option<&'static str> {
+    dimensions
+        .iter()
+        .find_map(ReportDimension::date_trunc_field)
+}
+
+/// Whether rows should be broken down per device.
+pub fn report_groups_by_device(dimensions: &[ReportDimension]) -> bool {
+    dimensions.contains(&ReportDimension::Device)
+}
+
+/// Sections must be non-empty and unique.
+pub fn validate_report_sections(
+    sections: &[ReportSection],
+) -> Result<(), validator::ValidationError> {
+    if sections.is_empty() {
+        let mut err = validator::ValidationError::new("sections_required");
+        err.message = Some("At least one report section is required".into());
+        return Err(err);
+    }
+    for (i, section) in sections.iter().enumerate() {
+        if sections[..i].contains(section) {
+            let mut err = validator::ValidationError::new("duplicate_section");
+            err.message = Some(format!("Duplicate report section: {}", section.as_str()).into());
+            return Err(err);
+        }
+    }
+    Ok(())
+}
+
+/// At most one time dimension can be selected, and no duplicates.
+pub fn validate_report_dimensions(
+    dimensions: &[ReportDimension],
+) -> Result<(), validator::ValidationError> {
+    let time_dimensions = dimensions
+        .iter()
+        .filter(|d| d.date_trunc_field().is_some())
+        .count();
+    if time_dimensions > 1 {
+        let mut err = validator::ValidationError::new("multiple_time_dimensions");
+        err.message = Some("Only one of day, week or month can be used for grouping".into());
+        return Err(err);
+    }
+    let device_dimensions = dimensions
+        .iter()
+        .filter(|d| **d == ReportDimension::Device)
+        .count();
+    if device_dimensions > 1 {
+        let mut err = validator::ValidationError::new("duplicate_dimension");
+        err.message = Some("Duplicate grouping dimension: device".into());
+        return Err(err);
+    }
+    Ok(())
+}
+
+/// Sections, grouping and output format of a custom report.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ReportBuilderDefinition {
+    pub sections: Vec<ReportSection>,
+    #[serde(default)]
+    pub group_by: Vec<ReportDimension>,
+    #[serde(default)]
+    pub format: ReportFormat,
+}
+
+/// Saved report template.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ReportTemplate {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(flatten)]
+    pub definition: ReportBuilderDefinition,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request to create a report template.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct CreateReportTemplateRequest {
+    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    pub name: String,
+    #[validate(length(max = 500, message = "Description must be at most 500 characters"))]
+    pub description: Option<String>,
+    #[validate(custom(function = "validate_report_sections"))]
+    pub sections: Vec<ReportSection>,
+    #[serde(default)]
+    #[validate(custom(function = "validate_report_dimensions"))]
+    pub group_by: Vec<ReportDimension>,
+    #[serde(default)]
+    pub format: ReportFormat,
+}
+
+impl CreateReportTemplateRequest {
+    pub fn definition(&self) -> ReportBuilderDefinition {
+        ReportBuilderDefinition {
+            sections: self.sections.clone(),
+            group_by: self.group_by.clone(),
+            format: self.format,
+        }
+    }
+}
+
+/// Request to update a report template. Omitted fields are unchanged.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct UpdateReportTemplateRequest {
+    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    pub name: Option<String>,
+    #[validate(length(max = 500, message = "Description must be at most 500 characters"))]
+    pub description: Option<String>,
+    #[validate(custom(function = "validate_report_sections"))]
+    pub sections: Option<Vec<ReportSection>>,
+    #[validate(custom(function = "validate_report_dimensions"))]
+    pub group_by: Option<Vec<ReportDimension>>,
+    pub format: Option<ReportFormat>,
+}
+
+impl UpdateReportTemplateRequest {
+    /// Apply the update on top of an existing definition.
+    pub fn apply_to(&self, definition: &ReportBuilderDefinition) -> ReportBuilderDefinition {
+        ReportBuilderDefinition {
+            sections: self
+                .sections
+                .clone()
+                .unwrap_or_else(|| definition.sections.clone()),
+            group_by: self
+                .group_by
+                .clone()
+                .unwrap_or_else(|| definition.group_by.clone()),
+            format: self.format.unwrap_or(definition.format),
+        }
+    }
+}
+
+/// Response for listing report templates.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ListReportTemplatesResponse {
+    pub data: Vec<ReportTemplate>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_section_serde() {
+        let json = serde_json::to_string(&ReportSection::GeofenceCompliance).unwrap();
+        assert_eq!(json, "\"geofence_compliance\"");
+        let parsed: ReportSection = serde_json::from_str("\"app_usage\"").unwrap();
+        assert_eq!(parsed, ReportSection::AppUsage);
+        assert_eq!(ReportSection::Trips.as_str(), "trips");
+    }
+
+    #[test]
+    fn test_report_time_bucket() {
+        assert_eq!(report_time_bucket(&[]), None);
+        assert_eq!(report_time_bucket(&[ReportDimension::Device]), None);
+        assert_eq!(
+            report_time_bucket(&[ReportDimension::Device, ReportDimension::Week]),
+            Some("week")
+        );
+        assert!(report_groups_by_device(&[ReportDimension::Device]));
+        assert!(!report_groups_by_device(&[ReportDimension::Month]));
+    }
+
+    #[test]
+    fn test_validate_report_sections() {
+        assert!(validate_report_sections(&[]).is_err());
+        assert!(
+            validate_report_sections(&[ReportSection::Devices, ReportSection::Devices]).is_err()
+        );
+        assert!(validate_report_sections(&[ReportSection::Devices, ReportSection::Trips]).is_ok());
+    }
+
+    #[test]
+    fn test_validate_report_dimensions() {
+        assert!(validate_report_dimensions(&[]).is_ok());
+        assert!(
+            validate_report_dimensions(&[ReportDimension::Day, ReportDimension::Device]).is_ok()
+        );
+        assert!(
+            validate_report_dimensions(&[ReportDimension::Day, ReportDimension::Month]).is_err()
+        );
+        assert!(
+            validate_report_dimensions(&[ReportDimension::Device, ReportDimension::Device])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_create_template_request_validation() {
+        let request: CreateReportTemplateRequest = serde_json::from_value(serde_json::json!({
+            "name": "Weekly fleet",
+            "sections": ["devices", "trips"],
+            "group_by": ["week", "device"],
+            "format": "xlsx"
+        }))
+        .unwrap();
+        assert!(request.validate().is_ok());
+        assert_eq!(request.definition().format, ReportFormat::Xlsx);
+
+        let invalid: CreateReportTemplateRequest = serde_json::from_value(serde_json::json!({
+            "name": "",
+            "sections": []
+        }))
+        .unwrap();
+        let errors = invalid.validate().unwrap_err();
+        assert!(errors.field_errors().contains_key("name"));
+        assert!(errors.field_errors().contains_key("sections"));
+    }
+
+    #[test]
+    fn test_update_template_request_apply() {
+        let existing = ReportBuilderDefinition {
+            sections: vec![ReportSection::Devices],
+            group_by: vec![ReportDimension::Day],
+            format: ReportFormat::Csv,
+        };
+        let update: UpdateReportTemplateRequest = serde_json::from_value(serde_json::json!({
+            "format": "pdf"
+        }))
+        .unwrap();
+        let updated = update.apply_to(&existing);
+        assert_eq!(updated.sections, existing.sections);
+        assert_eq!(updated.group_by, existing.group_by);
+        assert_eq!(updated.format, ReportFormat::Pdf);
+    }
+
+    #[test]
+    fn test_report_template_serializes_definition_flat() {
+        let template = ReportTemplate {
+            id: Uuid::nil(),
+            organization_id: Uuid::nil(),
+            name: "Monthly".to_string(),
+            description: None,
+            definition: ReportBuilderDefinition {
+                sections: vec![ReportSection::AppUsage],
+                group_by: vec![ReportDimension::Month],
+                format: ReportFormat::Json,
+            },
+            created_by: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let json = serde_json::to_value(&template).unwrap();
+        assert_eq!(json["sections"][0], "app_usage");
+        assert_eq!(json["group_by"][0], "month");
+        assert_eq!(json["format"], "json");
+    }
+}
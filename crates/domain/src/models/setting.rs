@@ -114,6 +114,9 @@ pub struct GetSettingsResponse {
     pub settings: std::collections::HashMap<String, SettingValue>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_synced_at: Option<DateTime<Utc>>,
+    /// Point in time the settings are shown for, if not current.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub as_of: Option<DateTime<Utc>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub definitions: Option<Vec<SettingDefinition>>,
 }
@@ -0,0 +1,107 @@
+//! Trip speed violation domain models.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Org webhook event type emitted when a trip has speed violations.
+pub const SPEED_VIOLATION_EVENT_TYPE: &str = "trip.speed_violation";
+
+/// A stretch of a trip driven above the posted speed limit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SpeedViolationResponse {
+    pub id: Uuid,
+    pub trip_id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    pub start_latitude: f64,
+    pub start_longitude: f64,
+    pub end_latitude: f64,
+    pub end_longitude: f64,
+    /// Posted limit in km/h.
+    pub speed_limit_kmh: f64,
+    /// Highest recorded speed in km/h.
+    pub max_speed_kmh: f64,
+    pub avg_speed_kmh: f64,
+    /// Number of recorded locations above the limit.
+    pub sample_count: i32,
+}
+
+/// Response for GET /api/v1/trips/:tripId/speed-violations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ListSpeedViolationsResponse {
+    pub trip_id: Uuid,
+    pub violations: Vec<SpeedViolationResponse>,
+    pub count: usize,
+}
+
+/// Webhook payload of a `trip.speed_violation` event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpeedViolationEventPayload {
+    /// Unique event ID, identical across deliveries and retries.
+    pub event_id: Uuid,
+    pub event_type: String,
+    pub organization_id: Uuid,
+    /// Milliseconds since epoch.
+    pub timestamp: i64,
+    pub device_id: Uuid,
+    pub trip_id: Uuid,
+    pub violations: Vec<SpeedViolationResponse>,
+}
+
+impl SpeedViolationEventPayload {
+    /// Create the payload of an event happening now.
+    pub fn new(
+        organization_id: Uuid,
+        device_id: Uuid,
+        trip_id: Uuid,
+        violations: Vec<SpeedViolationResponse>,
+    ) -> Self {
+        Self {
+            event_id: Uuid::new_v4(),
+            event_type: SPEED_VIOLATION_EVENT_TYPE.to_string(),
+            organization_id,
+            timestamp: Utc::now().timestamp_millis(),
+            device_id,
+            trip_id,
+            violations,
+        }
+    }
+
+    /// Example payload, for the event type catalog and test deliveries.
+    pub fn example(organization_id: Uuid) -> Self {
+        let started_at = DateTime::from_timestamp(1_700_000_000, 0).unwrap_or_default();
+        let violation = SpeedViolationResponse {
+            id: Uuid::nil(),
+            trip_id: Uuid::nil(),
+            started_at,
+            ended_at: started_at + chrono::Duration::seconds(40),
+            start_latitude: 48.1486,
+            start_longitude: 17.1077,
+            end_latitude: 48.1512,
+            end_longitude: 17.1154,
+            speed_limit_kmh: 50.0,
+            max_speed_kmh: 78.5,
+            avg_speed_kmh: 67.2,
+            sample_count: 9,
+        };
+        Self::new(organization_id, Uuid::nil(), Uuid::nil(), vec![violation])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_payload_serialization() {
+        let org_id = Uuid::new_v4();
+        let json = serde_json::to_value(SpeedViolationEventPayload::example(org_id)).unwrap();
+        assert_eq!(json["event_type"], "trip.speed_violation");
+        assert_eq!(json["organization_id"], org_id.to_string());
+        assert_eq!(json["violations"][0]["speed_limit_kmh"], 50.0);
+        assert_eq!(json["violations"][0]["sample_count"], 9);
+    }
+}
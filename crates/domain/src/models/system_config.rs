@@ -50,6 +50,7 @@ pub struct DatabaseSettingsInfo {
     pub min_connections: u32,
     pub connect_timeout_secs: u64,
     pub idle_timeout_secs: u64,
+    pub timescale_enabled: bool,
 }
 
 /// Logging settings info.
@@ -149,6 +150,7 @@ pub struct FeatureFlagsInfo {
     pub movement_tracking_enabled: bool,
     pub b2b_enabled: bool,
     pub geofence_events_enabled: bool,
+    pub self_service_orgs_enabled: bool,
 }
 
 /// Auth toggles info.
@@ -441,6 +443,50 @@ pub struct UpdateSystemSettingsResponse {
     pub settings: SystemSettingsResponse,
 }
 
+// ============================================================================
+// Metrics Snapshots
+// ============================================================================
+
+/// File format of a metrics snapshot.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MetricsSnapshotFormat {
+    /// OpenMetrics text exposition format.
+    #[default]
+    #[serde(rename = "openmetrics")]
+    OpenMetrics,
+    /// JSON list of samples.
+    #[serde(rename = "json")]
+    Json,
+}
+
+impl MetricsSnapshotFormat {
+    /// MIME type of the snapshot file.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            MetricsSnapshotFormat::OpenMetrics => {
+                "application/openmetrics-text; version=1.0.0; charset=utf-8"
+            }
+            MetricsSnapshotFormat::Json => "application/json",
+        }
+    }
+
+    /// File extension of the snapshot file.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            MetricsSnapshotFormat::OpenMetrics => "txt",
+            MetricsSnapshotFormat::Json => "json",
+        }
+    }
+}
+
+/// Query parameters for downloading the latest metrics snapshot.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MetricsSnapshotQuery {
+    /// Snapshot format (defaults to OpenMetrics).
+    #[serde(default)]
+    pub format: MetricsSnapshotFormat,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -455,6 +501,7 @@ mod tests {
                 movement_tracking_enabled: true,
                 b2b_enabled: true,
                 geofence_events_enabled: true,
+                self_service_orgs_enabled: false,
             },
             auth: AuthTogglesInfo {
                 registration_enabled: true,
@@ -507,4 +554,14 @@ mod tests {
         assert!(request.enabled);
         assert_eq!(request.message, Some("Scheduled maintenance".to_string()));
     }
+
+    #[test]
+    fn test_metrics_snapshot_query_deserialization() {
+        let query: MetricsSnapshotQuery = serde_json::from_str("{}").unwrap();
+        assert_eq!(query.format, MetricsSnapshotFormat::OpenMetrics);
+
+        let query: MetricsSnapshotQuery = serde_json::from_str(r#"{"format":"json"}"#).unwrap();
+        assert_eq!(query.format, MetricsSnapshotFormat::Json);
+        assert_eq!(query.format.extension(), "json");
+    }
 }
@@ -0,0 +1,154 @@
+//! Tenant application log domain models.
+//!
+//! Application logs are tagged with the organization and group they concern.
+//! Recent tagged entries are kept in a rolling store so support can export
+//! the logs of one organization without including those of other tenants.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Number of entries exported when no limit is given.
+pub const DEFAULT_LOG_EXPORT_LIMIT: usize = 1000;
+
+/// Maximum number of entries a single export returns.
+pub const MAX_LOG_EXPORT_LIMIT: usize = 10_000;
+
+/// Severity of a log entry, most severe first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    /// Whether the level is at least as severe as `min`.
+    pub fn is_at_least(&self, min: LogLevel) -> bool {
+        *self <= min
+    }
+}
+
+/// A log entry attributed to an organization.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct TenantLogEntry {
+    pub timestamp: DateTime<Utc>,
+    pub level: LogLevel,
+    /// Module that emitted the entry.
+    pub target: String,
+    pub message: String,
+    pub organization_id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    /// Remaining structured fields of the entry.
+    #[serde(skip_serializing_if = "serde_json::Map::is_empty")]
+    pub fields: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Query parameters for exporting an organization's logs.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ExportTenantLogsQuery {
+    /// Only entries of this group.
+    pub group_id: Option<String>,
+    /// Only entries at least this severe.
+    pub level: Option<LogLevel>,
+    /// Only entries logged at or after this instant.
+    pub since: Option<DateTime<Utc>>,
+    /// Maximum number of entries, most recent kept.
+    pub limit: Option<usize>,
+}
+
+impl ExportTenantLogsQuery {
+    /// Whether an entry matches the group, level and time filters.
+    pub fn matches(&self, entry: &TenantLogEntry) -> bool {
+        self.group_id
+            .as_ref()
+            .is_none_or(|group_id| entry.group_id.as_ref() == Some(group_id))
+            && self.level.is_none_or(|min| entry.level.is_at_least(min))
+            && self.since.is_none_or(|since| entry.timestamp >= since)
+    }
+
+    /// Requested limit, clamped to `MAX_LOG_EXPORT_LIMIT`.
+    pub fn limit(&self) -> usize {
+        self.limit
+            .unwrap_or(DEFAULT_LOG_EXPORT_LIMIT)
+            .clamp(1, MAX_LOG_EXPORT_LIMIT)
+    }
+}
+
+/// Response for a tenant log export.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ExportTenantLogsResponse {
+    pub organization_id: Uuid,
+    pub record_count: usize,
+    /// Whether older matching entries were left out because of the limit.
+    pub truncated: bool,
+    /// Matching entries, oldest first.
+    pub logs: Vec<TenantLogEntry>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn entry(level: LogLevel, group_id: Option<&str>) -> TenantLogEntry {
+        TenantLogEntry {
+            timestamp: Utc::now(),
+            level,
+            target: "api::routes".to_string(),
+            message: "test".to_string(),
+            organization_id: Uuid::new_v4(),
+            group_id: group_id.map(str::to_string),
+            request_id: None,
+            fields: serde_json::Map::new(),
+        }
+    }
+
+    #[test]
+    fn test_log_level_ordering() {
+        assert!(LogLevel::Error.is_at_least(LogLevel::Warn));
+        assert!(LogLevel::Warn.is_at_least(LogLevel::Warn));
+        assert!(!LogLevel::Info.is_at_least(LogLevel::Warn));
+        let level: LogLevel = serde_json::from_str("\"debug\"").unwrap();
+        assert_eq!(level, LogLevel::Debug);
+    }
+
+    #[test]
+    fn test_query_matches() {
+        let query = ExportTenantLogsQuery {
+            group_id: Some("g1".to_string()),
+            level: Some(LogLevel::Warn),
+            since: Some(Utc::now() - Duration::minutes(5)),
+            limit: None,
+        };
+        assert!(query.matches(&entry(LogLevel::Error, Some("g1"))));
+        assert!(!query.matches(&entry(LogLevel::Error, Some("g2"))));
+        assert!(!query.matches(&entry(LogLevel::Error, None)));
+        assert!(!query.matches(&entry(LogLevel::Info, Some("g1"))));
+
+        let mut old = entry(LogLevel::Error, Some("g1"));
+        old.timestamp = Utc::now() - Duration::hours(1);
+        assert!(!query.matches(&old));
+
+        assert!(ExportTenantLogsQuery::default().matches(&entry(LogLevel::Trace, None)));
+    }
+
+    #[test]
+    fn test_query_limit_clamped() {
+        let mut query = ExportTenantLogsQuery::default();
+        assert_eq!(query.limit(), DEFAULT_LOG_EXPORT_LIMIT);
+        query.limit = Some(0);
+        assert_eq!(query.limit(), 1);
+        query.limit = Some(MAX_LOG_EXPORT_LIMIT + 1);
+        assert_eq!(query.limit(), MAX_LOG_EXPORT_LIMIT);
+    }
+}
@@ -7,6 +7,8 @@ use uuid::Uuid;
 use validator::Validate;
 
 use super::movement_event::{DetectionSource, TransportationMode};
+use super::trip_purpose::TripPurposeSource;
+use super::unit_system::UnitSystem;
 
 // ============================================================================
 // Trip State Enum
@@ -92,6 +94,11 @@ pub struct Trip {
     pub distance_meters: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration_seconds: Option<i64>,
+    /// Purpose label such as `business` or `personal`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purpose_source: Option<TripPurposeSource>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -191,9 +198,66 @@ pub struct TripResponse {
     pub distance_meters: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration_seconds: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purpose_source: Option<TripPurposeSource>,
+    /// Distance and average speed in the caller's preferred units.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<TripStats>,
     pub created_at: DateTime<Utc>,
 }
 
+/// Trip statistics converted to a unit system for display.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct TripStats {
+    pub unit_system: UnitSystem,
+    pub distance: f64,
+    pub distance_unit: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub average_speed: Option<f64>,
+    pub speed_unit: &'static str,
+}
+
+impl TripStats {
+    /// Build display statistics from raw SI measurements.
+    ///
+    /// Returns `None` until the trip distance has been calculated. Values are
+    /// rounded to two decimal places.
+    pub fn from_measurements(
+        distance_meters: Option<f64>,
+        duration_seconds: Option<i64>,
+        units: UnitSystem,
+    ) -> Option<Self> {
+        let distance_meters = distance_meters?;
+        let average_speed = duration_seconds
+            .filter(|secs| *secs > 0)
+            .map(|secs| round2(units.convert_speed(distance_meters / secs as f64)));
+
+        Some(Self {
+            unit_system: units,
+            distance: round2(units.convert_distance(distance_meters)),
+            distance_unit: units.distance_unit(),
+            average_speed,
+            speed_unit: units.speed_unit(),
+        })
+    }
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+impl TripResponse {
+    /// Attach statistics expressed in the given unit system.
+    pub fn with_units(mut self, units: UnitSystem) -> Self {
+        self.stats =
+            TripStats::from_measurements(self.distance_meters, self.duration_seconds, units);
+        self
+    }
+}
+
 impl From<Trip> for TripResponse {
     fn from(trip: Trip) -> Self {
         Self {
@@ -210,6 +274,13 @@ impl From<Trip> for TripResponse {
             detection_source: trip.detection_source,
             distance_meters: trip.distance_meters,
             duration_seconds: trip.duration_seconds,
+            purpose: trip.purpose,
+            purpose_source: trip.purpose_source,
+            stats: TripStats::from_measurements(
+                trip.distance_meters,
+                trip.duration_seconds,
+                UnitSystem::default(),
+            ),
             created_at: trip.created_at,
         }
     }
@@ -245,11 +316,208 @@ pub struct GetTripsQuery {
     /// Filter by trip state (ACTIVE, COMPLETED, CANCELLED).
     pub state: Option<String>,
 
+    /// Filter by purpose label (e.g. business, personal).
+    pub purpose: Option<String>,
+
     /// Start timestamp filter (milliseconds since epoch).
     pub from: Option<i64>,
 
     /// End timestamp filter (milliseconds since epoch).
     pub to: Option<i64>,
+
+    /// Unit system override for trip stats (defaults to the device owner's preference).
+    pub units: Option<UnitSystem>,
+}
+
+// ============================================================================
+// Journey DTOs
+// ============================================================================
+
+/// Maximum span of a journeys query in milliseconds (31 days).
+pub const MAX_JOURNEY_RANGE_MS: i64 = 31 * 24 * 60 * 60 * 1000;
+
+/// Default span of a journeys query in milliseconds (7 days).
+pub const DEFAULT_JOURNEY_RANGE_MS: i64 = 7 * 24 * 60 * 60 * 1000;
+
+/// Query parameters for GET /api/v1/users/me/journeys
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GetJourneysQuery {
+    /// Start timestamp filter (milliseconds since epoch, default 7 days before `to`).
+    pub from: Option<i64>,
+
+    /// End timestamp filter (milliseconds since epoch, default now).
+    pub to: Option<i64>,
+
+    /// Unit system override for journey stats (defaults to the user's preference).
+    pub units: Option<UnitSystem>,
+}
+
+/// A trip within a journey, with the device that recorded it.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct JourneyTrip {
+    pub device_id: Uuid,
+    #[serde(flatten)]
+    pub trip: TripResponse,
+}
+
+/// Trips of a user's devices merged into one logical journey.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct JourneyResponse {
+    pub start_timestamp: i64,
+    /// Absent while the last trip is still active.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_timestamp: Option<i64>,
+    pub start_latitude: f64,
+    pub start_longitude: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_latitude: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_longitude: Option<f64>,
+    /// Sum of the calculated trip distances.
+    pub distance_meters: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_seconds: Option<i64>,
+    pub device_ids: Vec<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<TripStats>,
+    pub trips: Vec<JourneyTrip>,
+}
+
+impl JourneyResponse {
+    /// Build a journey from its trips, ordered by start time.
+    ///
+    /// Returns `None` for an empty trip list.
+    pub fn from_trips(trips: Vec<Trip>, units: UnitSystem) -> Option<Self> {
+        let first = trips.first()?;
+        let last = trips.last()?;
+
+        let end_timestamp = last.end_timestamp;
+        let duration_seconds = end_timestamp.map(|end| (end - first.start_timestamp) / 1000);
+        let distance_meters: f64 = trips.iter().filter_map(|t| t.distance_meters).sum();
+        let mut device_ids: Vec<Uuid> = Vec::new();
+        for trip in &trips {
+            if !device_ids.contains(&trip.device_id) {
+                device_ids.push(trip.device_id);
+            }
+        }
+
+        Some(Self {
+            start_timestamp: first.start_timestamp,
+            end_timestamp,
+            start_latitude: first.start_latitude,
+            start_longitude: first.start_longitude,
+            end_latitude: last.end_latitude,
+            end_longitude: last.end_longitude,
+            distance_meters,
+            duration_seconds,
+            device_ids,
+            stats: TripStats::from_measurements(Some(distance_meters), duration_seconds, units),
+            trips: trips
+                .into_iter()
+                .map(|trip| JourneyTrip {
+                    device_id: trip.device_id,
+                    trip: TripResponse::from(trip).with_units(units),
+                })
+                .collect(),
+        })
+    }
+}
+
+/// Response for GET /api/v1/users/me/journeys
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GetJourneysResponse {
+    /// Newest first.
+    pub journeys: Vec<JourneyResponse>,
+    pub count: usize,
+}
+
+// ============================================================================
+// Export DTOs
+// ============================================================================
+
+/// File format of a trip export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TripExportFormat {
+    #[default]
+    Gpx,
+    Csv,
+}
+
+impl TripExportFormat {
+    /// MIME type of the exported file.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            TripExportFormat::Gpx => "application/gpx+xml",
+            TripExportFormat::Csv => "text/csv",
+        }
+    }
+
+    /// File extension of the exported file.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            TripExportFormat::Gpx => "gpx",
+            TripExportFormat::Csv => "csv",
+        }
+    }
+}
+
+/// Query parameters for GET /api/v1/trips/:tripId/export
+#[derive(Debug, Clone, Deserialize)]
+pub struct TripExportQuery {
+    /// Export format (defaults to GPX).
+    #[serde(default)]
+    pub format: TripExportFormat,
+}
+
+// ============================================================================
+// Replay DTOs
+// ============================================================================
+
+/// Default spacing of replay frames in seconds.
+pub const DEFAULT_REPLAY_INTERVAL_SECS: u32 = 5;
+
+/// Maximum number of frames returned by a single replay request.
+pub const MAX_REPLAY_FRAMES: i64 = 10_000;
+
+fn default_replay_interval() -> u32 {
+    DEFAULT_REPLAY_INTERVAL_SECS
+}
+
+/// Query parameters for GET /api/v1/trips/:tripId/replay
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct TripReplayQuery {
+    /// Seconds between frames.
+    #[serde(default = "default_replay_interval")]
+    #[validate(range(min = 1, max = 300, message = "interval_secs must be 1-300"))]
+    pub interval_secs: u32,
+}
+
+/// Interpolated position of a trip at one point in time.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ReplayFrame {
+    pub timestamp: DateTime<Utc>,
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Direction of travel in degrees (0-360), unknown until the device moved.
+    pub bearing: Option<f64>,
+    /// Speed in meters per second along the current segment.
+    pub speed: Option<f64>,
+}
+
+/// Response for GET /api/v1/trips/:tripId/replay
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct TripReplayResponse {
+    pub trip_id: Uuid,
+    pub interval_secs: u32,
+    pub frames: Vec<ReplayFrame>,
+    pub frame_count: usize,
 }
 
 // ============================================================================
@@ -444,6 +712,8 @@ mod tests {
             detection_source: DetectionSource::ActivityRecognition,
             distance_meters: None,
             duration_seconds: None,
+            purpose: None,
+            purpose_source: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -452,5 +722,118 @@ mod tests {
         assert_eq!(response.id, trip.id);
         assert_eq!(response.local_trip_id, trip.local_trip_id);
         assert_eq!(response.state, trip.state);
+        assert!(response.stats.is_none());
+    }
+
+    // =========================================================================
+    // TripStats Tests
+    // =========================================================================
+
+    #[test]
+    fn test_trip_stats_metric() {
+        let stats =
+            TripStats::from_measurements(Some(12_000.0), Some(1_200), UnitSystem::Metric).unwrap();
+        assert_eq!(stats.distance, 12.0);
+        assert_eq!(stats.distance_unit, "km");
+        assert_eq!(stats.average_speed, Some(36.0));
+        assert_eq!(stats.speed_unit, "km/h");
+    }
+
+    #[test]
+    fn test_trip_stats_imperial() {
+        let stats =
+            TripStats::from_measurements(Some(16_093.44), Some(600), UnitSystem::Imperial).unwrap();
+        assert_eq!(stats.distance, 10.0);
+        assert_eq!(stats.distance_unit, "mi");
+        assert_eq!(stats.average_speed, Some(60.0));
+        assert_eq!(stats.speed_unit, "mph");
+    }
+
+    #[test]
+    fn test_trip_stats_without_distance() {
+        assert!(TripStats::from_measurements(None, Some(600), UnitSystem::Metric).is_none());
+    }
+
+    #[test]
+    fn test_trip_stats_zero_duration_has_no_speed() {
+        let stats = TripStats::from_measurements(Some(500.0), Some(0), UnitSystem::Metric).unwrap();
+        assert_eq!(stats.distance, 0.5);
+        assert!(stats.average_speed.is_none());
+    }
+
+    // =========================================================================
+    // JourneyResponse Tests
+    // =========================================================================
+
+    #[test]
+    fn test_journey_from_trips() {
+        let (old, new) = (Uuid::new_v4(), Uuid::new_v4());
+        let leg = |device_id, start: i64, end: i64, distance| Trip {
+            id: Uuid::new_v4(),
+            device_id,
+            local_trip_id: format!("trip-{}", start),
+            state: TripState::Completed,
+            start_timestamp: start,
+            end_timestamp: Some(end),
+            start_latitude: 45.0,
+            start_longitude: -120.0,
+            end_latitude: Some(45.1),
+            end_longitude: Some(-120.1),
+            transportation_mode: TransportationMode::InVehicle,
+            detection_source: DetectionSource::ActivityRecognition,
+            distance_meters: distance,
+            duration_seconds: Some((end - start) / 1000),
+            purpose: None,
+            purpose_source: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        let journey = JourneyResponse::from_trips(
+            vec![
+                leg(old, 0, 600_000, Some(8_000.0)),
+                leg(new, 660_000, 1_200_000, Some(4_000.0)),
+            ],
+            UnitSystem::Metric,
+        )
+        .unwrap();
+
+        assert_eq!(journey.duration_seconds, Some(1_200));
+        assert_eq!(journey.distance_meters, 12_000.0);
+        assert_eq!(journey.device_ids, vec![old, new]);
+        assert_eq!(journey.stats.unwrap().average_speed, Some(36.0));
+
+        let json = serde_json::to_value(&journey.trips[1]).unwrap();
+        assert_eq!(json["device_id"], new.to_string());
+        assert_eq!(json["local_trip_id"], "trip-660000");
+    }
+
+    #[test]
+    fn test_journey_from_no_trips() {
+        assert!(JourneyResponse::from_trips(vec![], UnitSystem::Metric).is_none());
+    }
+
+    #[test]
+    fn test_trip_export_query_format() {
+        let query: TripExportQuery = serde_json::from_str("{}").unwrap();
+        assert_eq!(query.format, TripExportFormat::Gpx);
+
+        let query: TripExportQuery = serde_json::from_str(r#"{"format":"csv"}"#).unwrap();
+        assert_eq!(query.format, TripExportFormat::Csv);
+        assert_eq!(query.format.content_type(), "text/csv");
+
+        assert!(serde_json::from_str::<TripExportQuery>(r#"{"format":"kml"}"#).is_err());
+    }
+
+    #[test]
+    fn test_replay_query_interval() {
+        let query: TripReplayQuery = serde_json::from_str("{}").unwrap();
+        assert_eq!(query.interval_secs, DEFAULT_REPLAY_INTERVAL_SECS);
+        assert!(query.validate().is_ok());
+
+        let query: TripReplayQuery = serde_json::from_str(r#"{"interval_secs":0}"#).unwrap();
+        assert!(query.validate().is_err());
+        let query: TripReplayQuery = serde_json::from_str(r#"{"interval_secs":301}"#).unwrap();
+        assert!(query.validate().is_err());
     }
 }
@@ -0,0 +1,287 @@
+//! Manual trip edits: merging and splitting trips.
+//!
+//! GPS gaps often end a journey early and start a new trip when the signal
+//! returns. Merging joins such trips back together; splitting cuts a trip
+//! that covers two journeys in two. Every edit is recorded in the trip's
+//! edit history.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::models::trip::{Trip, TripResponse, TripState};
+
+/// Maximum number of trips merged in one request.
+pub const MAX_TRIPS_PER_MERGE: usize = 20;
+
+/// Kind of manual trip edit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TripEditType {
+    Merge,
+    Split,
+}
+
+impl TripEditType {
+    /// Returns the string representation for database storage.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TripEditType::Merge => "MERGE",
+            TripEditType::Split => "SPLIT",
+        }
+    }
+}
+
+impl fmt::Display for TripEditType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for TripEditType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "MERGE" => Ok(TripEditType::Merge),
+            "SPLIT" => Ok(TripEditType::Split),
+            _ => Err(format!("Unknown trip edit type: {}", s)),
+        }
+    }
+}
+
+/// Request to merge trips into one.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct MergeTripsRequest {
+    /// Trips to merge (2-20). The earliest one is kept and absorbs the others.
+    #[validate(length(min = 2, max = 20, message = "Between 2 and 20 trips can be merged"))]
+    pub trip_ids: Vec<Uuid>,
+}
+
+/// Request to split a trip in two.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SplitTripRequest {
+    /// Time to split at, in milliseconds since epoch. Locations and movement
+    /// events from this time on move to the new trip.
+    pub split_at: i64,
+}
+
+/// Response for a merge: the kept trip and the trips merged into it.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct MergeTripsResponse {
+    pub trip: TripResponse,
+    /// Trips absorbed by `trip`; they no longer exist.
+    pub merged_trip_ids: Vec<Uuid>,
+    pub edit_id: Uuid,
+}
+
+/// Response for a split: the shortened trip and the new trip.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SplitTripResponse {
+    pub trips: Vec<TripResponse>,
+    pub edit_id: Uuid,
+}
+
+/// A recorded manual trip edit.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct TripEditResponse {
+    pub id: Uuid,
+    pub device_id: Uuid,
+    pub edit_type: TripEditType,
+    /// Trips before the edit.
+    pub source_trip_ids: Vec<Uuid>,
+    /// Trips after the edit.
+    pub result_trip_ids: Vec<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub split_at: Option<i64>,
+    /// API key the edit was made with.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Response for listing a trip's edits.
+#[derive(Debug, Clone, Serialize)]
+pub struct ListTripEditsResponse {
+    pub edits: Vec<TripEditResponse>,
+}
+
+/// Boundaries of a merged trip.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergePlan {
+    /// Trip that is kept, the one that started first.
+    pub kept_trip_id: Uuid,
+    /// Trips merged into the kept trip.
+    pub merged_trip_ids: Vec<Uuid>,
+    pub end_timestamp: i64,
+    pub end_latitude: f64,
+    pub end_longitude: f64,
+}
+
+/// Check that `trips` can be merged and compute the merged trip's bounds.
+///
+/// All trips must belong to the same device, be completed, and be distinct.
+/// The merged trip starts where the earliest trip started and ends where the
+/// latest-ending trip ended.
+pub fn plan_merge(trips: &[Trip]) -> Result<MergePlan, String> {
+    if trips.len() < 2 {
+        return Err("At least 2 trips are required for a merge".to_string());
+    }
+    let mut seen = HashSet::new();
+    if let Some(duplicate) = trips.iter().find(|t| !seen.insert(t.id)) {
+        return Err(format!("Trip {} is listed more than once", duplicate.id));
+    }
+    if trips.iter().any(|t| t.device_id != trips[0].device_id) {
+        return Err("Only trips of the same device can be merged".to_string());
+    }
+
+    let mut ends = Vec::with_capacity(trips.len());
+    for trip in trips {
+        match (
+            trip.state,
+            trip.end_timestamp,
+            trip.end_latitude,
+            trip.end_longitude,
+        ) {
+            (TripState::Completed, Some(ts), Some(lat), Some(lon)) => ends.push((ts, lat, lon)),
+            _ => return Err(format!("Trip {} is not completed", trip.id)),
+        }
+    }
+
+    let first = trips
+        .iter()
+        .min_by_key(|t| (t.start_timestamp, t.id))
+        .expect("at least two trips");
+    let (end_timestamp, end_latitude, end_longitude) = ends
+        .into_iter()
+        .max_by_key(|(ts, _, _)| *ts)
+        .expect("at least two trips");
+
+    let mut merged_trip_ids: Vec<Uuid> = trips
+        .iter()
+        .filter(|t| t.id != first.id)
+        .map(|t| t.id)
+        .collect();
+    merged_trip_ids.sort();
+
+    Ok(MergePlan {
+        kept_trip_id: first.id,
+        merged_trip_ids,
+        end_timestamp,
+        end_latitude,
+        end_longitude,
+    })
+}
+
+/// Check that `trip` can be split at `split_at`.
+///
+/// Only completed trips can be split, strictly between their start and end.
+pub fn validate_split(trip: &Trip, split_at: i64) -> Result<(), String> {
+    if trip.state != TripState::Completed {
+        return Err("Only completed trips can be split".to_string());
+    }
+    let end = trip.end_timestamp.unwrap_or(trip.start_timestamp);
+    if split_at <= trip.start_timestamp || split_at >= end {
+        return Err(format!(
+            "split_at must be between the trip's start ({}) and end ({})",
+            trip.start_timestamp, end
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::movement_event::{DetectionSource, TransportationMode};
+
+    fn trip(device_id: Uuid, start: i64, end: Option<i64>) -> Trip {
+        Trip {
+            id: Uuid::new_v4(),
+            device_id,
+            local_trip_id: format!("trip-{}", start),
+            state: if end.is_some() {
+                TripState::Completed
+            } else {
+                TripState::Active
+            },
+            start_timestamp: start,
+            end_timestamp: end,
+            start_latitude: 48.0,
+            start_longitude: 17.0,
+            end_latitude: end.map(|e| 48.0 + e as f64 / 1e6),
+            end_longitude: end.map(|_| 17.1),
+            transportation_mode: TransportationMode::InVehicle,
+            detection_source: DetectionSource::ActivityRecognition,
+            distance_meters: None,
+            duration_seconds: None,
+            purpose: None,
+            purpose_source: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_plan_merge_keeps_earliest_trip() {
+        let device = Uuid::new_v4();
+        let later = trip(device, 5_000, Some(9_000));
+        let earlier = trip(device, 1_000, Some(4_000));
+
+        let plan = plan_merge(&[later.clone(), earlier.clone()]).unwrap();
+        assert_eq!(plan.kept_trip_id, earlier.id);
+        assert_eq!(plan.merged_trip_ids, vec![later.id]);
+        assert_eq!(plan.end_timestamp, 9_000);
+        assert_eq!(plan.end_latitude, later.end_latitude.unwrap());
+    }
+
+    #[test]
+    fn test_plan_merge_rejects_invalid_sets() {
+        let device = Uuid::new_v4();
+        let a = trip(device, 1_000, Some(2_000));
+
+        assert!(plan_merge(std::slice::from_ref(&a)).is_err());
+        assert!(plan_merge(&[a.clone(), a.clone()]).is_err());
+        assert!(plan_merge(&[a.clone(), trip(Uuid::new_v4(), 3_000, Some(4_000))]).is_err());
+        assert!(plan_merge(&[a, trip(device, 3_000, None)]).is_err());
+    }
+
+    #[test]
+    fn test_validate_split() {
+        let t = trip(Uuid::new_v4(), 1_000, Some(5_000));
+        assert!(validate_split(&t, 3_000).is_ok());
+        assert!(validate_split(&t, 1_000).is_err());
+        assert!(validate_split(&t, 5_000).is_err());
+        assert!(validate_split(&trip(Uuid::new_v4(), 1_000, None), 3_000).is_err());
+    }
+
+    #[test]
+    fn test_edit_type_round_trip() {
+        for edit_type in [TripEditType::Merge, TripEditType::Split] {
+            assert_eq!(edit_type.as_str().parse::<TripEditType>(), Ok(edit_type));
+        }
+        assert!("JOIN".parse::<TripEditType>().is_err());
+    }
+
+    #[test]
+    fn test_merge_request_validation() {
+        let request = MergeTripsRequest {
+            trip_ids: vec![Uuid::new_v4()],
+        };
+        assert!(request.validate().is_err());
+
+        let request = MergeTripsRequest {
+            trip_ids: (0..MAX_TRIPS_PER_MERGE).map(|_| Uuid::new_v4()).collect(),
+        };
+        assert!(request.validate().is_ok());
+    }
+}
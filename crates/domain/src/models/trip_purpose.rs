@@ -0,0 +1,260 @@
+//! Trip purpose labels and classification rules.
+//!
+//! A trip's purpose is `business`, `personal` or a custom label, e.g. for
+//! mileage reimbursement. The device owner sets it manually, or rules of
+//! the device label trips when they complete: a rule matches trips that
+//! start or end in a geofence and depart on given weekdays or within a
+//! time window (UTC). Manual labels are never overwritten by rules.
+
+use chrono::{DateTime, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::models::commute::format_minute_of_day;
+
+pub const TRIP_PURPOSE_BUSINESS: &str = "business";
+pub const TRIP_PURPOSE_PERSONAL: &str = "personal";
+
+/// Longest purpose label.
+pub const MAX_TRIP_PURPOSE_LENGTH: usize = 50;
+
+/// Maximum classification rules per device.
+pub const MAX_TRIP_RULES_PER_DEVICE: i64 = 20;
+
+/// How a trip got its purpose.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TripPurposeSource {
+    /// Set by the device owner.
+    Manual,
+    /// Set by a classification rule.
+    Rule,
+}
+
+impl TripPurposeSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TripPurposeSource::Manual => "manual",
+            TripPurposeSource::Rule => "rule",
+        }
+    }
+}
+
+impl std::str::FromStr for TripPurposeSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "manual" => Ok(TripPurposeSource::Manual),
+            "rule" => Ok(TripPurposeSource::Rule),
+            _ => Err(format!("Invalid trip purpose source: {}", s)),
+        }
+    }
+}
+
+/// Normalize a purpose label: trimmed and lowercase, 1-50 letters, digits,
+/// spaces, dashes or underscores.
+pub fn normalize_purpose(purpose: &str) -> Result<String, String> {
+    let purpose = purpose.trim().to_lowercase();
+    if purpose.is_empty() || purpose.chars().count() > MAX_TRIP_PURPOSE_LENGTH {
+        return Err(format!(
+            "purpose must be 1-{} characters",
+            MAX_TRIP_PURPOSE_LENGTH
+        ));
+    }
+    if !purpose
+        .chars()
+        .all(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_'))
+    {
+        return Err(
+            "purpose may only contain letters, digits, spaces, dashes and underscores".to_string(),
+        );
+    }
+    Ok(purpose)
+}
+
+fn validate_purpose(purpose: &str) -> Result<(), ValidationError> {
+    normalize_purpose(purpose).map(|_| ()).map_err(|message| {
+        let mut err = ValidationError::new("invalid_purpose");
+        err.message = Some(message.into());
+        err
+    })
+}
+
+/// Parse a `HH:MM` time of day into minutes after midnight.
+pub fn parse_minute_of_day(time: &str) -> Option<i32> {
+    let (hours, minutes) = time.split_once(':')?;
+    if hours.len() != 2 || minutes.len() != 2 {
+        return None;
+    }
+    let (hours, minutes): (i32, i32) = (hours.parse().ok()?, minutes.parse().ok()?);
+    ((0..24).contains(&hours) && (0..60).contains(&minutes)).then_some(hours * 60 + minutes)
+}
+
+fn validate_time_of_day(time: &str) -> Result<(), ValidationError> {
+    match parse_minute_of_day(time) {
+        Some(_) => Ok(()),
+        None => {
+            let mut err = ValidationError::new("invalid_time");
+            err.message = Some("Time must be HH:MM".into());
+            Err(err)
+        }
+    }
+}
+
+/// Request for PUT /api/v1/trips/:tripId/purpose
+///
+/// `null` clears the purpose.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct SetTripPurposeRequest {
+    #[validate(custom(function = "validate_purpose"))]
+    pub purpose: Option<String>,
+}
+
+/// Request to create or replace a trip classification rule.
+#[derive(Debug, Clone, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+#[validate(schema(function = "validate_rule_conditions"))]
+pub struct TripRuleRequest {
+    #[validate(length(min = 1, max = 100, message = "name must be 1-100 characters"))]
+    pub name: String,
+    #[validate(custom(function = "validate_purpose"))]
+    pub purpose: String,
+    /// Rules are evaluated by descending priority; the first match wins.
+    #[serde(default)]
+    pub priority: i32,
+    /// Geofence of the device the trip must start in.
+    pub start_geofence_id: Option<Uuid>,
+    /// Geofence of the device the trip must end in.
+    pub end_geofence_id: Option<Uuid>,
+    /// Weekdays (UTC) the trip must depart on; empty means any day.
+    #[serde(default)]
+    pub weekdays: Vec<Weekday>,
+    /// Departure window, `HH:MM` UTC. Wraps past midnight if `departure_after`
+    /// is later than `departure_before`.
+    #[validate(custom(function = "validate_time_of_day"))]
+    pub departure_after: Option<String>,
+    #[validate(custom(function = "validate_time_of_day"))]
+    pub departure_before: Option<String>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn validate_rule_conditions(request: &TripRuleRequest) -> Result<(), ValidationError> {
+    let message = if request.departure_after.is_some() != request.departure_before.is_some() {
+        "departure_after and departure_before must be set together"
+    } else if request.start_geofence_id.is_none()
+        && request.end_geofence_id.is_none()
+        && request.weekdays.is_empty()
+        && request.departure_after.is_none()
+    {
+        "A rule needs a geofence, weekday or departure window condition"
+    } else {
+        return Ok(());
+    };
+    let mut err = ValidationError::new("invalid_rule");
+    err.message = Some(message.into());
+    Err(err)
+}
+
+/// A trip classification rule.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct TripRuleResponse {
+    pub id: Uuid,
+    pub device_id: Uuid,
+    pub name: String,
+    pub purpose: String,
+    pub priority: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_geofence_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_geofence_id: Option<Uuid>,
+    pub weekdays: Vec<Weekday>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub departure_after: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub departure_before: Option<String>,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Response for GET /api/v1/devices/:deviceId/trip-rules
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ListTripRulesResponse {
+    pub rules: Vec<TripRuleResponse>,
+}
+
+/// Format an optional minute of day as `HH:MM`.
+pub fn format_optional_minute(minute: Option<i32>) -> Option<String> {
+    minute.map(format_minute_of_day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(json: serde_json::Value) -> TripRuleRequest {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_normalize_purpose() {
+        assert_eq!(normalize_purpose(" Business ").unwrap(), "business");
+        assert_eq!(
+            normalize_purpose("client-visit_2").unwrap(),
+            "client-visit_2"
+        );
+        assert!(normalize_purpose("").is_err());
+        assert!(normalize_purpose("a;b").is_err());
+        assert!(normalize_purpose(&"x".repeat(51)).is_err());
+    }
+
+    #[test]
+    fn test_parse_minute_of_day() {
+        assert_eq!(parse_minute_of_day("00:00"), Some(0));
+        assert_eq!(parse_minute_of_day("07:45"), Some(465));
+        assert_eq!(parse_minute_of_day("23:59"), Some(1439));
+        assert_eq!(parse_minute_of_day("24:00"), None);
+        assert_eq!(parse_minute_of_day("7:45"), None);
+        assert_eq!(parse_minute_of_day("07-45"), None);
+    }
+
+    #[test]
+    fn test_rule_request_validation() {
+        let valid = rule(serde_json::json!({
+            "name": "Work days",
+            "purpose": "business",
+            "weekdays": ["Mon", "Tue"],
+            "departure_after": "06:00",
+            "departure_before": "10:00"
+        }));
+        assert!(valid.validate().is_ok());
+        assert!(valid.enabled);
+
+        let no_condition = rule(serde_json::json!({"name": "All", "purpose": "personal"}));
+        assert!(no_condition.validate().is_err());
+
+        let half_window = rule(serde_json::json!({
+            "name": "Mornings",
+            "purpose": "business",
+            "departure_after": "06:00"
+        }));
+        assert!(half_window.validate().is_err());
+
+        let bad_purpose = rule(serde_json::json!({
+            "name": "Office",
+            "purpose": "<script>",
+            "end_geofence_id": Uuid::new_v4()
+        }));
+        assert!(bad_purpose.validate().is_err());
+    }
+}
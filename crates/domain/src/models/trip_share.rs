@@ -0,0 +1,197 @@
+//! Public share links for trips.
+//!
+//! A share link gives anyone holding its token read-only access to a trip's
+//! path until the link expires or is revoked, without authentication.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use super::movement_event::TransportationMode;
+use super::trip::TripState;
+
+/// Lifetime of a share link when none is requested (1 day).
+pub const DEFAULT_TRIP_SHARE_HOURS: u32 = 24;
+
+/// Longest lifetime of a share link (30 days).
+pub const MAX_TRIP_SHARE_HOURS: u32 = 720;
+
+/// Request payload for POST /api/v1/trips/:tripId/share
+#[derive(Debug, Clone, Default, Deserialize, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct CreateTripShareRequest {
+    /// Hours until the link expires (defaults to 24).
+    #[validate(range(min = 1, max = 720, message = "expires_in_hours must be 1-720"))]
+    pub expires_in_hours: Option<u32>,
+}
+
+impl CreateTripShareRequest {
+    /// Requested lifetime in hours.
+    pub fn expires_in_hours(&self) -> u32 {
+        self.expires_in_hours.unwrap_or(DEFAULT_TRIP_SHARE_HOURS)
+    }
+}
+
+/// A trip share link.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct TripShareResponse {
+    pub share_id: Uuid,
+    pub trip_id: Uuid,
+    /// Public URL of the shared trip; only returned when the link is created.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Link token; only returned when the link is created.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<DateTime<Utc>>,
+    /// Whether the link can still be used.
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Response for GET /api/v1/trips/:tripId/shares
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ListTripSharesResponse {
+    pub shares: Vec<TripShareResponse>,
+}
+
+/// Format of a shared trip view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SharedTripFormat {
+    #[default]
+    Json,
+    Geojson,
+}
+
+/// Query parameters for GET /api/v1/shared/trips/:token
+#[derive(Debug, Clone, Deserialize)]
+pub struct SharedTripQuery {
+    /// View format (defaults to JSON).
+    #[serde(default)]
+    pub format: SharedTripFormat,
+}
+
+/// Read-only view of a shared trip.
+///
+/// Leaves out device and local identifiers.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SharedTripResponse {
+    pub trip_id: Uuid,
+    pub state: TripState,
+    pub transportation_mode: TransportationMode,
+    pub start_timestamp: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_timestamp: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distance_meters: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_seconds: Option<i64>,
+    /// Whether `path` is the map-matched (`corrected`) or recorded (`raw`) path.
+    pub path_kind: String,
+    pub path: Vec<SharedTripPoint>,
+    /// When the share link expires.
+    pub expires_at: DateTime<Utc>,
+}
+
+/// A point of a shared trip's path.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SharedTripPoint {
+    pub latitude: f64,
+    pub longitude: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub captured_at: Option<DateTime<Utc>>,
+}
+
+impl SharedTripResponse {
+    /// GeoJSON Feature with the path as a LineString and the trip summary
+    /// as properties.
+    pub fn to_geojson(&self) -> serde_json::Value {
+        let coordinates: Vec<[f64; 2]> = self
+            .path
+            .iter()
+            .map(|p| [p.longitude, p.latitude])
+            .collect();
+        serde_json::json!({
+            "type": "Feature",
+            "geometry": {
+                "type": "LineString",
+                "coordinates": coordinates,
+            },
+            "properties": {
+                "trip_id": self.trip_id,
+                "state": self.state,
+                "transportation_mode": self.transportation_mode,
+                "start_timestamp": self.start_timestamp,
+                "end_timestamp": self.end_timestamp,
+                "distance_meters": self.distance_meters,
+                "duration_seconds": self.duration_seconds,
+                "path_kind": self.path_kind,
+                "expires_at": self.expires_at,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_trip_share_request_defaults() {
+        let request: CreateTripShareRequest = serde_json::from_str("{}").unwrap();
+        assert!(request.validate().is_ok());
+        assert_eq!(request.expires_in_hours(), DEFAULT_TRIP_SHARE_HOURS);
+
+        let request = CreateTripShareRequest {
+            expires_in_hours: Some(MAX_TRIP_SHARE_HOURS + 1),
+        };
+        assert!(request.validate().is_err());
+    }
+
+    #[test]
+    fn test_shared_trip_geojson() {
+        let response = SharedTripResponse {
+            trip_id: Uuid::nil(),
+            state: TripState::Completed,
+            transportation_mode: TransportationMode::Walking,
+            start_timestamp: 1_700_000_000_000,
+            end_timestamp: Some(1_700_000_600_000),
+            distance_meters: Some(850.0),
+            duration_seconds: Some(600),
+            path_kind: "raw".to_string(),
+            path: vec![
+                SharedTripPoint {
+                    latitude: 48.1,
+                    longitude: 17.1,
+                    captured_at: None,
+                },
+                SharedTripPoint {
+                    latitude: 48.2,
+                    longitude: 17.2,
+                    captured_at: None,
+                },
+            ],
+            expires_at: Utc::now(),
+        };
+
+        let geojson = response.to_geojson();
+        assert_eq!(geojson["type"], "Feature");
+        assert_eq!(geojson["geometry"]["coordinates"][0][0], 17.1);
+        assert_eq!(geojson["geometry"]["coordinates"][0][1], 48.1);
+        assert_eq!(geojson["properties"]["state"], "COMPLETED");
+    }
+
+    #[test]
+    fn test_shared_trip_query_default_format() {
+        let query: SharedTripQuery = serde_json::from_str("{}").unwrap();
+        assert_eq!(query.format, SharedTripFormat::Json);
+    }
+}
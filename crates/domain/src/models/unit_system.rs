@@ -0,0 +1,158 @@
+//! Measurement unit preferences for distances and speeds.
+//!
+//! Values are always stored in SI units (meters, meters/second). The unit
+//! system only affects how they are presented in API responses and reports.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+const METERS_PER_KILOMETER: f64 = 1_000.0;
+const METERS_PER_MILE: f64 = 1_609.344;
+const MPS_TO_KMH: f64 = 3.6;
+const MPS_TO_MPH: f64 = 2.236_936_292_054_402;
+
+/// Preferred unit system for displaying distances and speeds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnitSystem {
+    #[default]
+    Metric,
+    Imperial,
+}
+
+impl UnitSystem {
+    /// Returns the string representation for database storage.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UnitSystem::Metric => "metric",
+            UnitSystem::Imperial => "imperial",
+        }
+    }
+
+    /// Unit label for distances (`km` or `mi`).
+    pub fn distance_unit(&self) -> &'static str {
+        match self {
+            UnitSystem::Metric => "km",
+            UnitSystem::Imperial => "mi",
+        }
+    }
+
+    /// Unit label for speeds (`km/h` or `mph`).
+    pub fn speed_unit(&self) -> &'static str {
+        match self {
+            UnitSystem::Metric => "km/h",
+            UnitSystem::Imperial => "mph",
+        }
+    }
+
+    /// Converts a distance in meters to this unit system's distance unit.
+    pub fn convert_distance(&self, meters: f64) -> f64 {
+        match self {
+            UnitSystem::Metric => meters / METERS_PER_KILOMETER,
+            UnitSystem::Imperial => meters / METERS_PER_MILE,
+        }
+    }
+
+    /// Converts a speed in meters/second to this unit system's speed unit.
+    pub fn convert_speed(&self, meters_per_second: f64) -> f64 {
+        match self {
+            UnitSystem::Metric => meters_per_second * MPS_TO_KMH,
+            UnitSystem::Imperial => meters_per_second * MPS_TO_MPH,
+        }
+    }
+
+    /// Resolves the effective unit system for a request.
+    ///
+    /// An explicit per-request override wins over the stored user preference.
+    /// Unknown or missing stored values fall back to metric.
+    pub fn resolve(requested: Option<UnitSystem>, stored: Option<&str>) -> UnitSystem {
+        requested
+            .or_else(|| stored.and_then(|s| s.parse().ok()))
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Display for UnitSystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for UnitSystem {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "metric" => Ok(UnitSystem::Metric),
+            "imperial" => Ok(UnitSystem::Imperial),
+            _ => Err(format!(
+                "Invalid unit system: {}. Must be one of: metric, imperial",
+                s
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unit_system_default_is_metric() {
+        assert_eq!(UnitSystem::default(), UnitSystem::Metric);
+    }
+
+    #[test]
+    fn test_unit_system_from_str() {
+        assert_eq!("metric".parse::<UnitSystem>().unwrap(), UnitSystem::Metric);
+        assert_eq!(
+            "IMPERIAL".parse::<UnitSystem>().unwrap(),
+            UnitSystem::Imperial
+        );
+        assert!("nautical".parse::<UnitSystem>().is_err());
+    }
+
+    #[test]
+    fn test_unit_system_serde() {
+        let json = serde_json::to_string(&UnitSystem::Imperial).unwrap();
+        assert_eq!(json, "\"imperial\"");
+
+        let parsed: UnitSystem = serde_json::from_str("\"metric\"").unwrap();
+        assert_eq!(parsed, UnitSystem::Metric);
+    }
+
+    #[test]
+    fn test_convert_distance() {
+        assert!((UnitSystem::Metric.convert_distance(1500.0) - 1.5).abs() < 1e-9);
+        assert!((UnitSystem::Imperial.convert_distance(1609.344) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_convert_speed() {
+        assert!((UnitSystem::Metric.convert_speed(10.0) - 36.0).abs() < 1e-9);
+        assert!((UnitSystem::Imperial.convert_speed(10.0) - 22.369_362_920_544).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_unit_labels() {
+        assert_eq!(UnitSystem::Metric.distance_unit(), "km");
+        assert_eq!(UnitSystem::Metric.speed_unit(), "km/h");
+        assert_eq!(UnitSystem::Imperial.distance_unit(), "mi");
+        assert_eq!(UnitSystem::Imperial.speed_unit(), "mph");
+    }
+
+    #[test]
+    fn test_resolve_prefers_request_override() {
+        assert_eq!(
+            UnitSystem::resolve(Some(UnitSystem::Metric), Some("imperial")),
+            UnitSystem::Metric
+        );
+        assert_eq!(
+            UnitSystem::resolve(None, Some("imperial")),
+            UnitSystem::Imperial
+        );
+        assert_eq!(UnitSystem::resolve(None, Some("bogus")), UnitSystem::Metric);
+        assert_eq!(UnitSystem::resolve(None, None), UnitSystem::Metric);
+    }
+}
@@ -1,30 +1,88 @@
 //! Webhook domain model.
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::Validate;
 
+use super::webhook_payload_format::WebhookPayloadFormat;
+use super::webhook_payload_template::validate_payload_template;
+use super::webhook_retry_policy::WebhookRetryPolicy;
+
+/// Event types that device webhooks can receive.
+pub const SUPPORTED_WEBHOOK_EVENT_TYPES: &[&str] =
+    &["geofence_enter", "geofence_exit", "geofence_dwell"];
+
+/// Highest configurable delivery rate limit.
+pub const MAX_WEBHOOK_DELIVERIES_PER_MINUTE: i32 = 600;
+
+/// Window of the delivery rate limit.
+pub const WEBHOOK_RATE_LIMIT_WINDOW_SECS: i64 = 60;
+
+/// Events per batch delivery of batched webhooks without a batch size.
+pub const DEFAULT_WEBHOOK_BATCH_MAX_EVENTS: i32 = 50;
+
+/// Owner of a webhook.
+///
+/// Device-owned webhooks receive events of their device only and are
+/// deleted with it. User-owned webhooks receive events of all of the
+/// user's devices, or of a chosen subset, so they survive phone changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookOwner {
+    Device(Uuid),
+    User(Uuid),
+}
+
 /// Represents a webhook in the system.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct Webhook {
     pub id: i64,
     pub webhook_id: Uuid,
-    pub owner_device_id: Uuid,
+    /// Owning device (device-owned webhooks)
+    pub owner_device_id: Option<Uuid>,
+    /// Owning user (user-owned webhooks)
+    pub owner_user_id: Option<Uuid>,
+    /// Devices a user-owned webhook is limited to; all of the user's devices if None
+    pub device_ids: Option<Vec<Uuid>>,
     pub name: String,
     pub target_url: String,
+    /// Stored signing secret; encrypted if a webhook secret key is configured
     pub secret: String,
     pub enabled: bool,
     /// Number of consecutive delivery failures since last success
     pub consecutive_failures: i32,
     /// When circuit breaker is open, this is when it will auto-close
     pub circuit_open_until: Option<DateTime<Utc>>,
+    pub retry_policy: WebhookRetryPolicy,
+    /// Custom delivery payload; the default geofence event payload if None
+    pub payload_template: Option<serde_json::Value>,
+    pub payload_format: WebhookPayloadFormat,
+    /// Delivery attempts allowed per minute; unlimited if None
+    pub max_deliveries_per_minute: Option<i32>,
+    /// Seconds events are collected per batch delivery; not batched if None
+    pub batch_window_secs: Option<i32>,
+    /// Events per batch delivery; the default if None
+    pub batch_max_events: Option<i32>,
+    /// When the webhook was disabled for failing repeatedly
+    pub auto_disabled_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
 impl Webhook {
+    /// Owner of the webhook.
+    ///
+    /// The database guarantees exactly one owner; a row without one is
+    /// reported as owned by the nil device.
+    pub fn owner(&self) -> WebhookOwner {
+        match (self.owner_device_id, self.owner_user_id) {
+            (Some(device_id), _) => WebhookOwner::Device(device_id),
+            (None, Some(user_id)) => WebhookOwner::User(user_id),
+            (None, None) => WebhookOwner::Device(Uuid::nil()),
+        }
+    }
+
     /// Check if the circuit breaker is currently open.
     pub fn is_circuit_open(&self) -> bool {
         if let Some(open_until) = self.circuit_open_until {
@@ -38,6 +96,42 @@ impl Webhook {
     pub fn is_available(&self) -> bool {
         self.enabled && !self.is_circuit_open()
     }
+
+    /// Batching settings, if the webhook receives its events in batches.
+    pub fn batching(&self) -> Option<WebhookBatching> {
+        Some(WebhookBatching {
+            window_secs: self.batch_window_secs?,
+            max_events: self
+                .batch_max_events
+                .unwrap_or(DEFAULT_WEBHOOK_BATCH_MAX_EVENTS),
+        })
+    }
+
+    /// When the next delivery may be sent, if the rate limit is exhausted.
+    ///
+    /// `recent_attempts` is the number of delivery attempts within the last
+    /// rate limit window and `oldest_attempt_at` the time of the oldest one.
+    pub fn throttled_until(
+        &self,
+        recent_attempts: i64,
+        oldest_attempt_at: Option<DateTime<Utc>>,
+    ) -> Option<DateTime<Utc>> {
+        let limit = self.max_deliveries_per_minute?;
+        if recent_attempts < i64::from(limit) {
+            return None;
+        }
+        oldest_attempt_at.map(|at| at + Duration::seconds(WEBHOOK_RATE_LIMIT_WINDOW_SECS))
+    }
+}
+
+/// Batched delivery settings of a webhook.
+///
+/// Events are collected and delivered as one JSON array once the oldest
+/// is `window_secs` old or `max_events` have been collected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WebhookBatching {
+    pub window_secs: i32,
+    pub max_events: i32,
 }
 
 /// Default enabled status for new webhooks.
@@ -49,9 +143,22 @@ fn default_enabled() -> bool {
 #[derive(Debug, Clone, Deserialize, Validate)]
 #[serde(rename_all = "snake_case")]
 pub struct CreateWebhookRequest {
-    pub owner_device_id: Uuid,
-
-    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    /// Owning device; exactly one of `owner_device_id` and `owner_user_id` is required.
+    pub owner_device_id: Option<Uuid>,
+
+    /// Owning user; exactly one of `owner_device_id` and `owner_user_id` is required.
+    pub owner_user_id: Option<Uuid>,
+
+    /// Limit a user-owned webhook to these devices of the user.
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "device_ids must contain 1-50 devices; omit it for all devices"
+    ))]
+    pub device_ids: Option<Vec<Uuid>>,
+
+    #[serde(deserialize_with = "shared::text::deserialize_line")]
+    #[validate(custom(function = "shared::text::validate_name"))]
     pub name: String,
 
     #[validate(
@@ -66,6 +173,72 @@ pub struct CreateWebhookRequest {
 
     #[serde(default = "default_enabled")]
     pub enabled: bool,
+
+    /// Delivery retry policy; the default policy if omitted.
+    pub retry_policy: Option<WebhookRetryPolicy>,
+
+    /// Custom payload template; the default payload if omitted.
+    pub payload_template: Option<serde_json::Value>,
+
+    /// Payload format; `default` delivers the payload as is.
+    #[serde(default)]
+    pub format: WebhookPayloadFormat,
+
+    /// Delivery rate limit; unlimited if omitted.
+    #[validate(range(
+        min = 1,
+        max = 600,
+        message = "max_deliveries_per_minute must be between 1 and 600"
+    ))]
+    pub max_deliveries_per_minute: Option<i32>,
+
+    /// Deliver events in batches collected for this many seconds.
+    #[validate(range(
+        min = 5,
+        max = 300,
+        message = "batch_window_secs must be between 5 and 300"
+    ))]
+    pub batch_window_secs: Option<i32>,
+
+    /// Events per batch delivery (requires `batch_window_secs`).
+    #[validate(range(
+        min = 2,
+        max = 100,
+        message = "batch_max_events must be between 2 and 100"
+    ))]
+    pub batch_max_events: Option<i32>,
+}
+
+impl CreateWebhookRequest {
+    /// Validate the payload template (if provided).
+    pub fn validate_payload_template(&self) -> Result<(), String> {
+        match self.payload_template {
+            Some(ref template) => validate_payload_template(template),
+            None => Ok(()),
+        }
+    }
+
+    /// Validate that a batch size comes with a batch window.
+    pub fn validate_batching(&self) -> Result<(), String> {
+        if self.batch_max_events.is_some() && self.batch_window_secs.is_none() {
+            return Err("batch_max_events requires batch_window_secs".to_string());
+        }
+        Ok(())
+    }
+
+    /// Owner of the webhook to create.
+    pub fn owner(&self) -> Result<WebhookOwner, String> {
+        match (self.owner_device_id, self.owner_user_id) {
+            (Some(device_id), None) => {
+                if self.device_ids.is_some() {
+                    return Err("device_ids is only supported for user-owned webhooks".to_string());
+                }
+                Ok(WebhookOwner::Device(device_id))
+            }
+            (None, Some(user_id)) => Ok(WebhookOwner::User(user_id)),
+            _ => Err("Exactly one of owner_device_id and owner_user_id is required".to_string()),
+        }
+    }
 }
 
 /// Custom validator for HTTPS URLs.
@@ -83,7 +256,8 @@ fn validate_https_url(url: &str) -> Result<(), validator::ValidationError> {
 #[derive(Debug, Clone, Deserialize, Validate)]
 #[serde(rename_all = "snake_case")]
 pub struct UpdateWebhookRequest {
-    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    #[serde(default, deserialize_with = "shared::text::deserialize_optional_line")]
+    #[validate(custom(function = "shared::text::validate_name"))]
     pub name: Option<String>,
 
     #[validate(
@@ -96,9 +270,98 @@ pub struct UpdateWebhookRequest {
     pub secret: Option<String>,
 
     pub enabled: Option<bool>,
+
+    /// Replaces the delivery retry policy.
+    pub retry_policy: Option<WebhookRetryPolicy>,
+
+    /// Limit a user-owned webhook to these devices of the user.
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "device_ids must contain 1-50 devices; use all_devices to remove the limit"
+    ))]
+    pub device_ids: Option<Vec<Uuid>>,
+
+    /// Deliver events of all of the user's devices again (user-owned webhooks).
+    #[serde(default)]
+    pub all_devices: bool,
+
+    /// Replaces the custom payload template.
+    pub payload_template: Option<serde_json::Value>,
+
+    /// Deliver the default payload again instead of the template.
+    #[serde(default)]
+    pub default_payload: bool,
+
+    /// Replaces the payload format.
+    pub format: Option<WebhookPayloadFormat>,
+
+    /// Replaces the delivery rate limit; 0 removes it.
+    #[validate(range(
+        min = 0,
+        max = 600,
+        message = "max_deliveries_per_minute must be between 0 (unlimited) and 600"
+    ))]
+    pub max_deliveries_per_minute: Option<i32>,
+
+    /// Replaces the batch window; 0 turns batching off.
+    #[validate(custom(function = "validate_batch_window_update"))]
+    pub batch_window_secs: Option<i32>,
+
+    /// Replaces the batch size.
+    #[validate(range(
+        min = 2,
+        max = 100,
+        message = "batch_max_events must be between 2 and 100"
+    ))]
+    pub batch_max_events: Option<i32>,
+}
+
+/// Batch windows accepted by updates: 5-300 seconds, or 0 to turn batching off.
+fn validate_batch_window_update(secs: i32) -> Result<(), validator::ValidationError> {
+    if secs == 0 || (5..=300).contains(&secs) {
+        Ok(())
+    } else {
+        let mut err = validator::ValidationError::new("range");
+        err.message =
+            Some("batch_window_secs must be between 5 and 300, or 0 to turn batching off".into());
+        Err(err)
+    }
 }
 
 impl UpdateWebhookRequest {
+    /// Validate that device selection changes are consistent.
+    pub fn validate_devices(&self) -> Result<(), String> {
+        if self.all_devices && self.device_ids.is_some() {
+            return Err("device_ids and all_devices cannot be combined".to_string());
+        }
+        Ok(())
+    }
+
+    /// Validate the payload template and that it is not combined with `default_payload`.
+    pub fn validate_payload_template(&self) -> Result<(), String> {
+        match self.payload_template {
+            Some(_) if self.default_payload => {
+                Err("payload_template and default_payload cannot be combined".to_string())
+            }
+            Some(ref template) => validate_payload_template(template),
+            None => Ok(()),
+        }
+    }
+
+    /// Validate that a batch size is not combined with turning batching off.
+    pub fn validate_batching(&self) -> Result<(), String> {
+        if self.batch_max_events.is_some() && self.batch_window_secs == Some(0) {
+            return Err("batch_max_events cannot be set when turning batching off".to_string());
+        }
+        Ok(())
+    }
+
+    /// Whether the request changes which devices the webhook receives events from.
+    pub fn changes_devices(&self) -> bool {
+        self.all_devices || self.device_ids.is_some()
+    }
+
     /// Validate that target_url is HTTPS if provided.
     pub fn validate_https(&self) -> Result<(), String> {
         if let Some(ref url) = self.target_url {
@@ -116,11 +379,37 @@ impl UpdateWebhookRequest {
 #[serde(rename_all = "snake_case")]
 pub struct WebhookResponse {
     pub webhook_id: Uuid,
-    pub owner_device_id: Uuid,
+    /// Owning device; null for user-owned webhooks.
+    pub owner_device_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner_user_id: Option<Uuid>,
+    /// Devices a user-owned webhook is limited to; omitted for all devices.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device_ids: Option<Vec<Uuid>>,
     pub name: String,
     pub target_url: String,
-    pub secret: String,
+    /// Signing secret; only returned when it is set (creation and rotation).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
     pub enabled: bool,
+    pub retry_policy: WebhookRetryPolicy,
+    /// Custom payload template; omitted for the default payload.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload_template: Option<serde_json::Value>,
+    /// Payload format (`default` or `cloudevents`).
+    pub format: WebhookPayloadFormat,
+    /// Delivery rate limit; omitted if unlimited.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_deliveries_per_minute: Option<i32>,
+    /// Batch window; omitted if events are delivered one by one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch_window_secs: Option<i32>,
+    /// Events per batch delivery; omitted for the default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch_max_events: Option<i32>,
+    /// When the webhook was disabled for failing repeatedly; omitted otherwise.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_disabled_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -130,10 +419,19 @@ impl From<Webhook> for WebhookResponse {
         Self {
             webhook_id: w.webhook_id,
             owner_device_id: w.owner_device_id,
+            owner_user_id: w.owner_user_id,
+            device_ids: w.device_ids,
             name: w.name,
             target_url: w.target_url,
-            secret: w.secret,
+            secret: None,
             enabled: w.enabled,
+            retry_policy: w.retry_policy,
+            payload_template: w.payload_template,
+            format: w.payload_format,
+            max_deliveries_per_minute: w.max_deliveries_per_minute,
+            batch_window_secs: w.batch_window_secs,
+            batch_max_events: w.batch_max_events,
+            auto_disabled_at: w.auto_disabled_at,
             created_at: w.created_at,
             updated_at: w.updated_at,
         }
@@ -151,10 +449,91 @@ pub struct ListWebhooksResponse {
 
 /// Query parameters for listing webhooks.
 /// Uses ownerDeviceId to match frontend expectations (camelCase query param).
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ListWebhooksQuery {
-    pub owner_device_id: Uuid,
+    /// Webhooks owned by this device.
+    pub owner_device_id: Option<Uuid>,
+    /// Webhooks owned by this user.
+    pub owner_user_id: Option<Uuid>,
+    /// Webhooks receiving events of this device, device- or user-owned.
+    pub device_id: Option<Uuid>,
+}
+
+/// Filter selected by [`ListWebhooksQuery`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookListFilter {
+    OwnerDevice(Uuid),
+    OwnerUser(Uuid),
+    Device(Uuid),
+}
+
+impl ListWebhooksQuery {
+    /// The filter to apply; exactly one parameter is required.
+    pub fn filter(&self) -> Result<WebhookListFilter, String> {
+        match (self.owner_device_id, self.owner_user_id, self.device_id) {
+            (Some(id), None, None) => Ok(WebhookListFilter::OwnerDevice(id)),
+            (None, Some(id), None) => Ok(WebhookListFilter::OwnerUser(id)),
+            (None, None, Some(id)) => Ok(WebhookListFilter::Device(id)),
+            _ => Err(
+                "Exactly one of ownerDeviceId, ownerUserId and deviceId is required".to_string(),
+            ),
+        }
+    }
+}
+
+/// Query parameters for sending a test delivery to a webhook.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct TestWebhookQuery {
+    /// Event type of the sample payload (defaults to "geofence_enter").
+    pub event_type: Option<String>,
+}
+
+impl TestWebhookQuery {
+    /// Validates that the event type is supported (if provided).
+    pub fn validate_event_type(&self) -> Result<(), String> {
+        if let Some(ref event_type) = self.event_type {
+            if !SUPPORTED_WEBHOOK_EVENT_TYPES.contains(&event_type.as_str()) {
+                return Err(format!(
+                    "Unsupported event type: {}. Must be one of: {}",
+                    event_type,
+                    SUPPORTED_WEBHOOK_EVENT_TYPES.join(", ")
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the event type to use for testing.
+    pub fn get_event_type(&self) -> &str {
+        self.event_type.as_deref().unwrap_or("geofence_enter")
+    }
+}
+
+/// Response for a test webhook delivery.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct TestWebhookResponse {
+    /// Whether the target answered with a 2xx status.
+    pub success: bool,
+    pub delivery_id: Uuid,
+    pub event_type: String,
+    /// Sample payload that was sent to the target.
+    pub payload: serde_json::Value,
+    /// HTTP status returned by the target (if it responded).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_code: Option<i32>,
+    /// Leading part of the target's response body (if it responded).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_body_excerpt: Option<String>,
+    /// Time until the target's response headers arrived (if it responded).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<i32>,
+    /// Error message (if the request could not be completed).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub duration_ms: i64,
 }
 
 #[cfg(test)]
@@ -165,11 +544,20 @@ mod tests {
     fn test_webhook_response_serialization() {
         let response = WebhookResponse {
             webhook_id: Uuid::new_v4(),
-            owner_device_id: Uuid::new_v4(),
+            owner_device_id: Some(Uuid::new_v4()),
+            owner_user_id: None,
+            device_ids: None,
             name: "Test Webhook".to_string(),
             target_url: "https://example.com/webhook".to_string(),
-            secret: "test-secret-key".to_string(),
+            secret: None,
             enabled: true,
+            retry_policy: WebhookRetryPolicy::default(),
+            payload_template: None,
+            format: WebhookPayloadFormat::Default,
+            max_deliveries_per_minute: None,
+            batch_window_secs: None,
+            batch_max_events: None,
+            auto_disabled_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -180,6 +568,10 @@ mod tests {
         assert!(json.contains("\"name\":\"Test Webhook\""));
         assert!(json.contains("\"target_url\":\"https://example.com/webhook\""));
         assert!(json.contains("\"enabled\":true"));
+        assert!(!json.contains("secret"));
+        assert!(json.contains("\"backoff_strategy\":\"stepped\""));
+        assert!(json.contains("\"format\":\"default\""));
+        assert!(!json.contains("max_deliveries_per_minute"));
     }
 
     #[test]
@@ -196,6 +588,81 @@ mod tests {
         assert!(request.target_url.starts_with("https://"));
         // Default should be applied
         assert!(request.enabled);
+        assert!(request.retry_policy.is_none());
+        assert_eq!(request.format, WebhookPayloadFormat::Default);
+        assert!(matches!(request.owner(), Ok(WebhookOwner::Device(_))));
+    }
+
+    #[test]
+    fn test_create_webhook_request_owner() {
+        let user_id = Uuid::new_v4();
+        let json = format!(
+            r#"{{
+                "owner_user_id": "{}",
+                "device_ids": ["550e8400-e29b-41d4-a716-446655440000"],
+                "name": "Home Assistant",
+                "target_url": "https://example.com/webhook",
+                "secret": "my-secret-key-12345678"
+            }}"#,
+            user_id
+        );
+        let request: CreateWebhookRequest = serde_json::from_str(&json).unwrap();
+        assert!(request.validate().is_ok());
+        assert_eq!(request.owner(), Ok(WebhookOwner::User(user_id)));
+
+        // Both or neither owner
+        let both = CreateWebhookRequest {
+            owner_device_id: Some(Uuid::new_v4()),
+            ..request.clone()
+        };
+        assert!(both.owner().is_err());
+        let neither = CreateWebhookRequest {
+            owner_user_id: None,
+            ..request.clone()
+        };
+        assert!(neither.owner().is_err());
+
+        // Device subsets only apply to user-owned webhooks
+        let device_owned = CreateWebhookRequest {
+            owner_device_id: Some(Uuid::new_v4()),
+            owner_user_id: None,
+            ..request.clone()
+        };
+        assert!(device_owned.owner().is_err());
+
+        let empty_subset = CreateWebhookRequest {
+            device_ids: Some(vec![]),
+            ..request
+        };
+        assert!(empty_subset.validate().is_err());
+    }
+
+    #[test]
+    fn test_max_deliveries_per_minute_validation() {
+        let json = r#"{
+            "owner_device_id": "550e8400-e29b-41d4-a716-446655440000",
+            "name": "Home Assistant",
+            "target_url": "https://example.com/webhook",
+            "secret": "my-secret-key-12345678",
+            "max_deliveries_per_minute": 30
+        }"#;
+        let request: CreateWebhookRequest = serde_json::from_str(json).unwrap();
+        assert!(request.validate().is_ok());
+        for invalid in [0, MAX_WEBHOOK_DELIVERIES_PER_MINUTE + 1] {
+            let request = CreateWebhookRequest {
+                max_deliveries_per_minute: Some(invalid),
+                ..request.clone()
+            };
+            assert!(request.validate().is_err());
+        }
+
+        // Updates accept 0 to remove the limit
+        let update: UpdateWebhookRequest =
+            serde_json::from_str(r#"{"max_deliveries_per_minute": 0}"#).unwrap();
+        assert!(update.validate().is_ok());
+        let update: UpdateWebhookRequest =
+            serde_json::from_str(r#"{"max_deliveries_per_minute": -1}"#).unwrap();
+        assert!(update.validate().is_err());
     }
 
     #[test]
@@ -232,6 +699,15 @@ mod tests {
             target_url: Some("http://example.com".to_string()),
             secret: None,
             enabled: None,
+            retry_policy: None,
+            device_ids: None,
+            all_devices: false,
+            payload_template: None,
+            default_payload: false,
+            format: None,
+            max_deliveries_per_minute: None,
+            batch_window_secs: None,
+            batch_max_events: None,
         };
 
         let result = request.validate_https();
@@ -246,21 +722,75 @@ mod tests {
             target_url: Some("https://example.com".to_string()),
             secret: None,
             enabled: None,
+            retry_policy: None,
+            device_ids: None,
+            all_devices: false,
+            payload_template: None,
+            default_payload: false,
+            format: None,
+            max_deliveries_per_minute: None,
+            batch_window_secs: None,
+            batch_max_events: None,
         };
 
         let result = request.validate_https();
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_update_webhook_request_validate_payload_template() {
+        let json = r#"{"payload_template": {"state": "{{event_type}}"}}"#;
+        let request: UpdateWebhookRequest = serde_json::from_str(json).unwrap();
+        assert!(request.validate_payload_template().is_ok());
+
+        let both = UpdateWebhookRequest {
+            default_payload: true,
+            ..request.clone()
+        };
+        assert_eq!(
+            both.validate_payload_template().unwrap_err(),
+            "payload_template and default_payload cannot be combined"
+        );
+
+        let unknown = UpdateWebhookRequest {
+            payload_template: Some(serde_json::json!({"x": "{{speed}}"})),
+            ..request
+        };
+        assert!(unknown.validate_payload_template().is_err());
+    }
+
     #[test]
     fn test_list_webhooks_query_deserialization() {
         // Frontend sends camelCase
         let json = r#"{"ownerDeviceId": "550e8400-e29b-41d4-a716-446655440000"}"#;
         let query: ListWebhooksQuery = serde_json::from_str(json).unwrap();
         assert_eq!(
-            query.owner_device_id.to_string(),
+            query.owner_device_id.unwrap().to_string(),
             "550e8400-e29b-41d4-a716-446655440000"
         );
+        assert!(matches!(
+            query.filter(),
+            Ok(WebhookListFilter::OwnerDevice(_))
+        ));
+    }
+
+    #[test]
+    fn test_list_webhooks_query_requires_one_filter() {
+        assert!(ListWebhooksQuery::default().filter().is_err());
+
+        let id = Uuid::new_v4();
+        let query = ListWebhooksQuery {
+            device_id: Some(id),
+            ..Default::default()
+        };
+        assert_eq!(query.filter(), Ok(WebhookListFilter::Device(id)));
+
+        let query = ListWebhooksQuery {
+            owner_user_id: Some(id),
+            device_id: Some(id),
+            ..Default::default()
+        };
+        assert!(query.filter().is_err());
     }
 
     #[test]
@@ -292,13 +822,22 @@ mod tests {
         Webhook {
             id: 1,
             webhook_id: Uuid::new_v4(),
-            owner_device_id: Uuid::new_v4(),
+            owner_device_id: Some(Uuid::new_v4()),
+            owner_user_id: None,
+            device_ids: None,
             name: "Test Webhook".to_string(),
             target_url: "https://example.com/webhook".to_string(),
             secret: "test-secret-key".to_string(),
             enabled,
             consecutive_failures: 0,
             circuit_open_until,
+            retry_policy: WebhookRetryPolicy::default(),
+            payload_template: None,
+            payload_format: WebhookPayloadFormat::Default,
+            max_deliveries_per_minute: None,
+            batch_window_secs: None,
+            batch_max_events: None,
+            auto_disabled_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         }
@@ -353,4 +892,119 @@ mod tests {
         let webhook = create_test_webhook(false, Some(future));
         assert!(!webhook.is_available());
     }
+
+    #[test]
+    fn test_webhook_batching() {
+        let mut webhook = create_test_webhook(true, None);
+        assert_eq!(webhook.batching(), None);
+
+        webhook.batch_window_secs = Some(30);
+        assert_eq!(
+            webhook.batching(),
+            Some(WebhookBatching {
+                window_secs: 30,
+                max_events: DEFAULT_WEBHOOK_BATCH_MAX_EVENTS
+            })
+        );
+        webhook.batch_max_events = Some(10);
+        assert_eq!(webhook.batching().unwrap().max_events, 10);
+    }
+
+    #[test]
+    fn test_batching_validation() {
+        let json = r#"{
+            "owner_device_id": "550e8400-e29b-41d4-a716-446655440000",
+            "name": "Home Assistant",
+            "target_url": "https://example.com/webhook",
+            "secret": "my-secret-key-12345678",
+            "batch_max_events": 10
+        }"#;
+        let request: CreateWebhookRequest = serde_json::from_str(json).unwrap();
+        assert!(request.validate().is_ok());
+        assert!(request.validate_batching().is_err());
+        let request = CreateWebhookRequest {
+            batch_window_secs: Some(2),
+            ..request
+        };
+        assert!(request.validate().is_err());
+
+        let update: UpdateWebhookRequest =
+            serde_json::from_str(r#"{"batch_window_secs": 0}"#).unwrap();
+        assert!(update.validate().is_ok());
+        assert!(update.validate_batching().is_ok());
+        let update = UpdateWebhookRequest {
+            batch_max_events: Some(10),
+            ..update
+        };
+        assert!(update.validate_batching().is_err());
+        let update = UpdateWebhookRequest {
+            batch_window_secs: Some(3),
+            batch_max_events: None,
+            ..update
+        };
+        assert!(update.validate().is_err());
+    }
+
+    #[test]
+    fn test_webhook_throttled_until() {
+        use chrono::Duration;
+        let oldest = Utc::now() - Duration::seconds(20);
+        let mut webhook = create_test_webhook(true, None);
+        assert_eq!(webhook.throttled_until(1000, Some(oldest)), None);
+
+        webhook.max_deliveries_per_minute = Some(3);
+        assert_eq!(webhook.throttled_until(2, Some(oldest)), None);
+        assert_eq!(
+            webhook.throttled_until(3, Some(oldest)),
+            Some(oldest + Duration::seconds(WEBHOOK_RATE_LIMIT_WINDOW_SECS))
+        );
+    }
+
+    #[test]
+    fn test_test_webhook_query_defaults_to_geofence_enter() {
+        let query = TestWebhookQuery::default();
+        assert!(query.validate_event_type().is_ok());
+        assert_eq!(query.get_event_type(), "geofence_enter");
+    }
+
+    #[test]
+    fn test_test_webhook_query_accepts_supported_event_types() {
+        for event_type in SUPPORTED_WEBHOOK_EVENT_TYPES {
+            let query = TestWebhookQuery {
+                event_type: Some(event_type.to_string()),
+            };
+            assert!(query.validate_event_type().is_ok());
+            assert_eq!(query.get_event_type(), *event_type);
+        }
+    }
+
+    #[test]
+    fn test_test_webhook_query_rejects_unknown_event_type() {
+        let query = TestWebhookQuery {
+            event_type: Some("device.enrolled".to_string()),
+        };
+        let err = query.validate_event_type().unwrap_err();
+        assert!(err.contains("Unsupported event type: device.enrolled"));
+    }
+
+    #[test]
+    fn test_test_webhook_response_skips_missing_fields() {
+        let response = TestWebhookResponse {
+            success: false,
+            delivery_id: Uuid::new_v4(),
+            event_type: "geofence_exit".to_string(),
+            payload: serde_json::json!({"event_type": "geofence_exit"}),
+            response_code: None,
+            response_body_excerpt: None,
+            latency_ms: None,
+            error: Some("connection refused".to_string()),
+            duration_ms: 12,
+        };
+
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(json.contains("\"error\":\"connection refused\""));
+        assert!(!json.contains("response_code"));
+        assert!(!json.contains("response_body_excerpt"));
+        assert!(!json.contains("latency_ms"));
+    }
 }
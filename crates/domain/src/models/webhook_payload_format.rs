@@ -0,0 +1,195 @@
+//! Webhook payload formats.
+//!
+//! Webhooks deliver the event payload as is by default. With the
+//! `cloudevents` format the payload becomes the `data` of a
+//! [CloudEvents 1.0](https://github.com/cloudevents/spec) structured-mode
+//! JSON event, so Knative, EventBridge and similar consumers can take
+//! deliveries without adapters.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+/// Prefix of CloudEvents `type` attributes, followed by the event type.
+pub const CLOUD_EVENT_TYPE_PREFIX: &str = "com.phonemanager.";
+
+/// Content type of structured-mode CloudEvents.
+pub const CLOUD_EVENTS_CONTENT_TYPE: &str = "application/cloudevents+json";
+
+/// Content type of batched structured-mode CloudEvents.
+pub const CLOUD_EVENTS_BATCH_CONTENT_TYPE: &str = "application/cloudevents-batch+json";
+
+/// Shape of a webhook's delivery payloads.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookPayloadFormat {
+    /// The event payload as is.
+    #[default]
+    Default,
+    /// The event payload wrapped in a CloudEvents 1.0 JSON envelope.
+    Cloudevents,
+}
+
+impl WebhookPayloadFormat {
+    /// Name stored in the database.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Cloudevents => "cloudevents",
+        }
+    }
+
+    /// Shape `data` as a payload of this format.
+    ///
+    /// `source` identifies the producer (e.g. `/devices/<id>`) and `subject`
+    /// the event's subject within it.
+    pub fn wrap(
+        &self,
+        event_type: &str,
+        source: &str,
+        subject: Option<&str>,
+        time: DateTime<Utc>,
+        data: Value,
+    ) -> Value {
+        match self {
+            Self::Default => data,
+            Self::Cloudevents => {
+                let mut event = json!({
+                    "specversion": "1.0",
+                    "id": Uuid::new_v4(),
+                    "source": source,
+                    "type": format!("{}{}", CLOUD_EVENT_TYPE_PREFIX, event_type),
+                    "time": time.to_rfc3339_opts(SecondsFormat::Millis, true),
+                    "datacontenttype": "application/json",
+                    "data": data,
+                });
+                if let Some(subject) = subject {
+                    event["subject"] = json!(subject);
+                }
+                event
+            }
+        }
+    }
+}
+
+impl fmt::Display for WebhookPayloadFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for WebhookPayloadFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "default" => Ok(Self::Default),
+            "cloudevents" => Ok(Self::Cloudevents),
+            other => Err(format!("Unknown webhook payload format: {}", other)),
+        }
+    }
+}
+
+/// Content type to deliver a logged payload with.
+///
+/// Decided from the payload itself, so retries keep the content type of the
+/// first attempt even if the webhook's format changed in between. Batch
+/// deliveries (arrays) are typed by their first event.
+pub fn payload_content_type(payload: &Value) -> &'static str {
+    match payload {
+        Value::Array(events) if events.first().and_then(|e| e.get("specversion")).is_some() => {
+            CLOUD_EVENTS_BATCH_CONTENT_TYPE
+        }
+        _ if payload.get("specversion").is_some() => CLOUD_EVENTS_CONTENT_TYPE,
+        _ => "application/json",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_default_format_keeps_payload() {
+        let data = json!({"event_type": "geofence_enter"});
+        let wrapped = WebhookPayloadFormat::Default.wrap(
+            "geofence_enter",
+            "/devices/1",
+            None,
+            Utc::now(),
+            data.clone(),
+        );
+        assert_eq!(wrapped, data);
+        assert_eq!(payload_content_type(&wrapped), "application/json");
+    }
+
+    #[test]
+    fn test_cloudevents_envelope() {
+        let time = Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+        let event = WebhookPayloadFormat::Cloudevents.wrap(
+            "geofence_exit",
+            "/devices/abc",
+            Some("geofences/def"),
+            time,
+            json!({"geofence_name": "Home"}),
+        );
+        assert_eq!(event["specversion"], "1.0");
+        assert_eq!(event["source"], "/devices/abc");
+        assert_eq!(event["subject"], "geofences/def");
+        assert_eq!(event["type"], "com.phonemanager.geofence_exit");
+        assert_eq!(event["time"], "2026-03-01T12:00:00.000Z");
+        assert_eq!(event["datacontenttype"], "application/json");
+        assert_eq!(event["data"]["geofence_name"], "Home");
+        assert!(event["id"].as_str().is_some());
+        assert_eq!(payload_content_type(&event), CLOUD_EVENTS_CONTENT_TYPE);
+
+        let event = WebhookPayloadFormat::Cloudevents.wrap(
+            "member.joined",
+            "/organizations/abc",
+            None,
+            time,
+            json!({}),
+        );
+        assert!(event.get("subject").is_none());
+    }
+
+    #[test]
+    fn test_batch_content_type() {
+        let event = WebhookPayloadFormat::Cloudevents.wrap(
+            "geofence_enter",
+            "/devices/1",
+            None,
+            Utc::now(),
+            json!({}),
+        );
+        assert_eq!(
+            payload_content_type(&json!([event])),
+            CLOUD_EVENTS_BATCH_CONTENT_TYPE
+        );
+        assert_eq!(
+            payload_content_type(&json!([{"event_type": "geofence_enter"}])),
+            "application/json"
+        );
+        assert_eq!(payload_content_type(&json!([])), "application/json");
+    }
+
+    #[test]
+    fn test_format_serialization() {
+        assert_eq!(
+            serde_json::to_string(&WebhookPayloadFormat::Cloudevents).unwrap(),
+            "\"cloudevents\""
+        );
+        for format in [
+            WebhookPayloadFormat::Default,
+            WebhookPayloadFormat::Cloudevents,
+        ] {
+            assert_eq!(format.as_str().parse::<WebhookPayloadFormat>(), Ok(format));
+        }
+        assert!("xml".parse::<WebhookPayloadFormat>().is_err());
+    }
+}
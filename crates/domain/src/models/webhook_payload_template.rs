@@ -0,0 +1,254 @@
+//! Custom JSON payload templates for webhooks.
+//!
+//! A template is any JSON object or array whose string values may contain
+//! `{{placeholder}}`s. A string consisting of a single placeholder is replaced
+//! by the typed value (numbers stay numbers); placeholders inside longer
+//! strings are interpolated as text. Keys are never rendered.
+//!
+//! ```json
+//! {"entity": "{{device_name}}", "gps": ["{{lat}}", "{{lon}}"], "note": "{{event_type}} at {{geofence_name}}"}
+//! ```
+
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+/// Placeholders available in payload templates.
+pub const WEBHOOK_TEMPLATE_PLACEHOLDERS: &[&str] = &[
+    "event_type",
+    "device_id",
+    "device_name",
+    "geofence_id",
+    "geofence_name",
+    "latitude",
+    "longitude",
+    "lat",
+    "lon",
+    "timestamp",
+];
+
+/// Largest accepted template, measured as serialized JSON.
+pub const MAX_PAYLOAD_TEMPLATE_BYTES: usize = 8192;
+
+/// Values of one event, substituted into a payload template.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebhookTemplateValues {
+    pub event_type: String,
+    pub device_id: Uuid,
+    /// Display name of the device; rendered as null (or empty text) if unknown.
+    pub device_name: Option<String>,
+    pub geofence_id: Uuid,
+    pub geofence_name: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Event time in milliseconds since the epoch.
+    pub timestamp: i64,
+}
+
+impl WebhookTemplateValues {
+    /// Typed value of a placeholder, or None if the placeholder is unknown.
+    fn get(&self, placeholder: &str) -> Option<Value> {
+        let value = match placeholder {
+            "event_type" => json!(self.event_type),
+            "device_id" => json!(self.device_id),
+            "device_name" => json!(self.device_name),
+            "geofence_id" => json!(self.geofence_id),
+            "geofence_name" => json!(self.geofence_name),
+            "latitude" | "lat" => json!(self.latitude),
+            "longitude" | "lon" => json!(self.longitude),
+            "timestamp" => json!(self.timestamp),
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+/// A placeholder found in a template string.
+struct Placeholder<'a> {
+    start: usize,
+    end: usize,
+    name: &'a str,
+}
+
+/// Find the placeholders of a template string.
+fn parse_placeholders(s: &str) -> Result<Vec<Placeholder<'_>>, String> {
+    let mut placeholders = Vec::new();
+    let mut offset = 0;
+    while let Some(open) = s[offset..].find("{{") {
+        let start = offset + open;
+        let close = s[start + 2..]
+            .find("}}")
+            .ok_or_else(|| format!("Unclosed placeholder in \"{}\"", s))?;
+        let end = start + 2 + close + 2;
+        placeholders.push(Placeholder {
+            start,
+            end,
+            name: s[start + 2..end - 2].trim(),
+        });
+        offset = end;
+    }
+    Ok(placeholders)
+}
+
+/// Check that a template is a JSON object or array within the size limit
+/// that only uses known placeholders.
+pub fn validate_payload_template(template: &Value) -> Result<(), String> {
+    if !template.is_object() && !template.is_array() {
+        return Err("payload_template must be a JSON object or array".to_string());
+    }
+    if template.to_string().len() > MAX_PAYLOAD_TEMPLATE_BYTES {
+        return Err(format!(
+            "payload_template must be at most {} bytes",
+            MAX_PAYLOAD_TEMPLATE_BYTES
+        ));
+    }
+    validate_value(template)
+}
+
+fn validate_value(value: &Value) -> Result<(), String> {
+    match value {
+        Value::String(s) => {
+            for placeholder in parse_placeholders(s)? {
+                if !WEBHOOK_TEMPLATE_PLACEHOLDERS.contains(&placeholder.name) {
+                    return Err(format!(
+                        "Unknown placeholder {{{{{}}}}}. Must be one of: {}",
+                        placeholder.name,
+                        WEBHOOK_TEMPLATE_PLACEHOLDERS.join(", ")
+                    ));
+                }
+            }
+            Ok(())
+        }
+        Value::Array(items) => items.iter().try_for_each(validate_value),
+        Value::Object(map) => map.values().try_for_each(validate_value),
+        _ => Ok(()),
+    }
+}
+
+/// Render a template with the values of an event.
+///
+/// Templates are validated on write; unknown placeholders and malformed
+/// strings are left as they are.
+pub fn render_payload_template(template: &Value, values: &WebhookTemplateValues) -> Value {
+    match template {
+        Value::String(s) => render_string(s, values),
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| render_payload_template(item, values))
+                .collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| (key.clone(), render_payload_template(value, values)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+fn render_string(s: &str, values: &WebhookTemplateValues) -> Value {
+    let Ok(placeholders) = parse_placeholders(s) else {
+        return Value::String(s.to_string());
+    };
+
+    // A lone placeholder keeps the value's type
+    if let [placeholder] = placeholders.as_slice() {
+        if placeholder.start == 0 && placeholder.end == s.len() {
+            if let Some(value) = values.get(placeholder.name) {
+                return value;
+            }
+        }
+    }
+
+    let mut rendered = String::with_capacity(s.len());
+    let mut offset = 0;
+    for placeholder in placeholders {
+        rendered.push_str(&s[offset..placeholder.start]);
+        match values.get(placeholder.name) {
+            Some(Value::String(text)) => rendered.push_str(&text),
+            Some(Value::Null) => {}
+            Some(value) => rendered.push_str(&value.to_string()),
+            None => rendered.push_str(&s[placeholder.start..placeholder.end]),
+        }
+        offset = placeholder.end;
+    }
+    rendered.push_str(&s[offset..]);
+    Value::String(rendered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values() -> WebhookTemplateValues {
+        WebhookTemplateValues {
+            event_type: "geofence_enter".to_string(),
+            device_id: Uuid::nil(),
+            device_name: Some("Pixel".to_string()),
+            geofence_id: Uuid::nil(),
+            geofence_name: "Home".to_string(),
+            latitude: 37.7749,
+            longitude: -122.4194,
+            timestamp: 1_700_000_000_000,
+        }
+    }
+
+    #[test]
+    fn test_render_keeps_types_of_lone_placeholders() {
+        let template = json!({
+            "entity": "{{device_name}}",
+            "gps": ["{{lat}}", "{{ longitude }}"],
+            "at": "{{timestamp}}",
+            "fixed": 42
+        });
+        let rendered = render_payload_template(&template, &values());
+        assert_eq!(
+            rendered,
+            json!({
+                "entity": "Pixel",
+                "gps": [37.7749, -122.4194],
+                "at": 1_700_000_000_000i64,
+                "fixed": 42
+            })
+        );
+    }
+
+    #[test]
+    fn test_render_interpolates_text() {
+        let template =
+            json!({"message": "{{device_name}}: {{event_type}} at {{geofence_name}} ({{lat}})"});
+        let rendered = render_payload_template(&template, &values());
+        assert_eq!(
+            rendered["message"],
+            "Pixel: geofence_enter at Home (37.7749)"
+        );
+    }
+
+    #[test]
+    fn test_render_unknown_device_name() {
+        let values = WebhookTemplateValues {
+            device_name: None,
+            ..values()
+        };
+        let template = json!({"name": "{{device_name}}", "text": "[{{device_name}}]"});
+        let rendered = render_payload_template(&template, &values);
+        assert_eq!(rendered, json!({"name": null, "text": "[]"}));
+    }
+
+    #[test]
+    fn test_validate_payload_template() {
+        assert!(
+            validate_payload_template(&json!({"lat": "{{lat}}", "n": [1, "{{ lon }}"]})).is_ok()
+        );
+        assert!(validate_payload_template(&json!(["{{event_type}}"])).is_ok());
+
+        assert!(validate_payload_template(&json!("{{lat}}")).is_err());
+        assert!(validate_payload_template(&json!({"x": "{{altitude}}"}))
+            .unwrap_err()
+            .contains("Unknown placeholder {{altitude}}"));
+        assert!(validate_payload_template(&json!({"x": "{{lat"})).is_err());
+
+        let big = json!({"x": "a".repeat(MAX_PAYLOAD_TEMPLATE_BYTES)});
+        assert!(validate_payload_template(&big).is_err());
+    }
+}
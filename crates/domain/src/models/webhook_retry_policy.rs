@@ -0,0 +1,240 @@
+//! Per-webhook delivery retry policies.
+//!
+//! A webhook without a policy of its own uses [`WebhookRetryPolicy::default`]:
+//! four attempts, retried immediately and then after 1 and 5 minutes, on any
+//! non-2xx response or request error.
+
+use serde::{Deserialize, Serialize};
+
+/// Backoff schedule of the default policy, indexed by failed attempts - 1.
+pub const DEFAULT_RETRY_BACKOFF_SECONDS: [i64; 4] = [0, 60, 300, 900];
+
+/// Maximum delivery attempts allowed in a policy.
+pub const MAX_WEBHOOK_ATTEMPTS: i32 = 10;
+
+/// Longest delay between two attempts (1 day).
+pub const MAX_WEBHOOK_BACKOFF_SECS: i64 = 86_400;
+
+/// Longest per-request timeout allowed in a policy.
+pub const MAX_WEBHOOK_TIMEOUT_SECS: u64 = 30;
+
+/// How the delay before the next attempt grows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookBackoffStrategy {
+    /// Immediately, then after 1, 5 and 15 minutes.
+    #[default]
+    Stepped,
+    /// `backoff_seconds` after every failure.
+    Fixed,
+    /// `backoff_seconds` times the number of failed attempts.
+    Linear,
+    /// `backoff_seconds` doubled after every failed attempt.
+    Exponential,
+}
+
+/// Retry behavior of a webhook's deliveries.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct WebhookRetryPolicy {
+    /// Total delivery attempts, including the first one.
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: i32,
+    #[serde(default)]
+    pub backoff_strategy: WebhookBackoffStrategy,
+    /// Base delay for the fixed, linear and exponential strategies.
+    #[serde(default = "default_backoff_seconds")]
+    pub backoff_seconds: i64,
+    /// Per-request timeout.
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Response codes that are retried; empty retries every non-2xx code.
+    /// Request errors (timeouts, refused connections) are always retried.
+    #[serde(default)]
+    pub retry_on_status_codes: Vec<i32>,
+}
+
+fn default_max_attempts() -> i32 {
+    DEFAULT_RETRY_BACKOFF_SECONDS.len() as i32
+}
+
+fn default_backoff_seconds() -> i64 {
+    60
+}
+
+fn default_timeout_secs() -> u64 {
+    5
+}
+
+impl Default for WebhookRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_max_attempts(),
+            backoff_strategy: WebhookBackoffStrategy::default(),
+            backoff_seconds: default_backoff_seconds(),
+            timeout_secs: default_timeout_secs(),
+            retry_on_status_codes: Vec::new(),
+        }
+    }
+}
+
+impl WebhookRetryPolicy {
+    /// Parse and validate a policy from JSON. `null` means the default policy.
+    pub fn from_value(value: &serde_json::Value) -> Result<Option<Self>, String> {
+        if value.is_null() {
+            return Ok(None);
+        }
+        let policy: Self = serde_json::from_value(value.clone())
+            .map_err(|e| format!("Invalid retry policy: {}", e))?;
+        policy.validate()?;
+        Ok(Some(policy))
+    }
+
+    /// Validate the policy and convert it to JSON for storage.
+    pub fn to_value(&self) -> Result<serde_json::Value, String> {
+        self.validate()?;
+        serde_json::to_value(self).map_err(|e| format!("Invalid retry policy: {}", e))
+    }
+
+    /// Check attempt, backoff, timeout and status code limits.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=MAX_WEBHOOK_ATTEMPTS).contains(&self.max_attempts) {
+            return Err(format!(
+                "max_attempts must be between 1 and {}",
+                MAX_WEBHOOK_ATTEMPTS
+            ));
+        }
+        if !(1..=MAX_WEBHOOK_BACKOFF_SECS).contains(&self.backoff_seconds) {
+            return Err(format!(
+                "backoff_seconds must be between 1 and {}",
+                MAX_WEBHOOK_BACKOFF_SECS
+            ));
+        }
+        if !(1..=MAX_WEBHOOK_TIMEOUT_SECS).contains(&self.timeout_secs) {
+            return Err(format!(
+                "timeout_secs must be between 1 and {}",
+                MAX_WEBHOOK_TIMEOUT_SECS
+            ));
+        }
+        if let Some(code) = self
+            .retry_on_status_codes
+            .iter()
+            .find(|code| !(100..=599).contains(*code) || (200..300).contains(*code))
+        {
+            return Err(format!("{} is not a retryable status code", code));
+        }
+        Ok(())
+    }
+
+    /// Seconds to wait before the next attempt after `failed_attempts`
+    /// attempts failed.
+    pub fn backoff_after(&self, failed_attempts: i32) -> i64 {
+        let n = failed_attempts.max(1);
+        let delay = match self.backoff_strategy {
+            WebhookBackoffStrategy::Stepped => {
+                let index = (n as usize - 1).min(DEFAULT_RETRY_BACKOFF_SECONDS.len() - 1);
+                DEFAULT_RETRY_BACKOFF_SECONDS[index]
+            }
+            WebhookBackoffStrategy::Fixed => self.backoff_seconds,
+            WebhookBackoffStrategy::Linear => self.backoff_seconds.saturating_mul(n as i64),
+            WebhookBackoffStrategy::Exponential => self
+                .backoff_seconds
+                .saturating_mul(1i64.checked_shl(n as u32 - 1).unwrap_or(i64::MAX)),
+        };
+        delay.min(MAX_WEBHOOK_BACKOFF_SECS)
+    }
+
+    /// Whether a delivery that got `status` back should be retried.
+    pub fn retries_status(&self, status: i32) -> bool {
+        self.retry_on_status_codes.is_empty() || self.retry_on_status_codes.contains(&status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_policy_matches_previous_behavior() {
+        let policy = WebhookRetryPolicy::default();
+        assert_eq!(policy.max_attempts, 4);
+        assert_eq!(policy.timeout_secs, 5);
+        assert_eq!(policy.backoff_after(1), 0); // Immediate
+        assert_eq!(policy.backoff_after(2), 60); // 1 minute
+        assert_eq!(policy.backoff_after(3), 300); // 5 minutes
+        assert_eq!(policy.backoff_after(4), 900); // 15 minutes
+        assert!(policy.retries_status(503));
+        assert!(policy.retries_status(404));
+    }
+
+    #[test]
+    fn test_backoff_strategies() {
+        let mut policy = WebhookRetryPolicy {
+            backoff_strategy: WebhookBackoffStrategy::Fixed,
+            backoff_seconds: 30,
+            ..Default::default()
+        };
+        assert_eq!(policy.backoff_after(3), 30);
+
+        policy.backoff_strategy = WebhookBackoffStrategy::Linear;
+        assert_eq!(policy.backoff_after(3), 90);
+
+        policy.backoff_strategy = WebhookBackoffStrategy::Exponential;
+        assert_eq!(policy.backoff_after(1), 30);
+        assert_eq!(policy.backoff_after(3), 120);
+        assert_eq!(policy.backoff_after(80), MAX_WEBHOOK_BACKOFF_SECS);
+    }
+
+    #[test]
+    fn test_from_value() {
+        assert_eq!(
+            WebhookRetryPolicy::from_value(&serde_json::Value::Null),
+            Ok(None)
+        );
+
+        let policy = WebhookRetryPolicy::from_value(&serde_json::json!({
+            "max_attempts": 6,
+            "backoff_strategy": "exponential",
+            "retry_on_status_codes": [429, 503]
+        }))
+        .unwrap()
+        .unwrap();
+        assert_eq!(policy.max_attempts, 6);
+        assert_eq!(policy.backoff_seconds, 60);
+        assert!(policy.retries_status(503));
+        assert!(!policy.retries_status(400));
+    }
+
+    #[test]
+    fn test_to_value_round_trip() {
+        let policy = WebhookRetryPolicy {
+            max_attempts: 3,
+            backoff_strategy: WebhookBackoffStrategy::Linear,
+            ..Default::default()
+        };
+        let value = policy.to_value().unwrap();
+        assert_eq!(WebhookRetryPolicy::from_value(&value), Ok(Some(policy)));
+
+        let invalid = WebhookRetryPolicy {
+            max_attempts: 0,
+            ..Default::default()
+        };
+        assert!(invalid.to_value().is_err());
+    }
+
+    #[test]
+    fn test_validate_limits() {
+        let invalid = [
+            serde_json::json!({"max_attempts": 0}),
+            serde_json::json!({"max_attempts": 11}),
+            serde_json::json!({"backoff_seconds": 0}),
+            serde_json::json!({"timeout_secs": 31}),
+            serde_json::json!({"retry_on_status_codes": [200]}),
+            serde_json::json!({"retry_on_status_codes": [700]}),
+            serde_json::json!({"backoff_strategy": "random"}),
+        ];
+        for value in invalid {
+            assert!(WebhookRetryPolicy::from_value(&value).is_err(), "{}", value);
+        }
+    }
+}
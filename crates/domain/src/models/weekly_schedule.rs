@@ -0,0 +1,166 @@
+//! Weekly schedules of daily time windows.
+//!
+//! Used for device tracking schedules and for geofences that are only
+//! active at certain times, e.g. a school fence on weekday mornings.
+
+use chrono::{DateTime, Datelike, Duration, NaiveTime, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of windows in a schedule.
+pub const MAX_SCHEDULE_WINDOWS: usize = 20;
+
+/// A weekly schedule in a fixed UTC offset.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct WeeklySchedule {
+    /// Offset of the schedule's local time from UTC, in minutes.
+    #[serde(default)]
+    pub utc_offset_minutes: i32,
+    /// Windows during which the schedule is active. No windows means never.
+    pub windows: Vec<ScheduleWindow>,
+}
+
+/// A daily time window on the given weekdays.
+///
+/// A window whose `end` is not after its `start` runs past midnight into
+/// the following day; equal times cover the full 24 hours.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ScheduleWindow {
+    pub days: Vec<Weekday>,
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl ScheduleWindow {
+    fn contains(&self, day: Weekday, time: NaiveTime) -> bool {
+        if self.start < self.end {
+            self.days.contains(&day) && time >= self.start && time < self.end
+        } else {
+            (self.days.contains(&day) && time >= self.start)
+                || (self.days.contains(&day.pred()) && time < self.end)
+        }
+    }
+}
+
+impl WeeklySchedule {
+    /// Parse and validate a schedule from JSON. `null` means no schedule.
+    pub fn from_value(value: &serde_json::Value) -> Result<Option<Self>, String> {
+        if value.is_null() {
+            return Ok(None);
+        }
+        let schedule: Self = serde_json::from_value(value.clone())
+            .map_err(|e| format!("Invalid schedule: {}", e))?;
+        schedule.validate()?;
+        Ok(Some(schedule))
+    }
+
+    /// Check offset and window limits.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(-720..=840).contains(&self.utc_offset_minutes) {
+            return Err("utc_offset_minutes must be between -720 and 840".to_string());
+        }
+        if self.windows.len() > MAX_SCHEDULE_WINDOWS {
+            return Err(format!(
+                "A schedule can have at most {} windows",
+                MAX_SCHEDULE_WINDOWS
+            ));
+        }
+        if self.windows.iter().any(|w| w.days.is_empty()) {
+            return Err("Every schedule window needs at least one day".to_string());
+        }
+        Ok(())
+    }
+
+    /// Whether the schedule is active at the given instant.
+    pub fn is_active_at(&self, at: DateTime<Utc>) -> bool {
+        let local = at.naive_utc() + Duration::minutes(self.utc_offset_minutes as i64);
+        let (day, time) = (local.weekday(), local.time());
+        self.windows.iter().any(|w| w.contains(day, time))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    fn weekday_schedule(offset: i32) -> WeeklySchedule {
+        WeeklySchedule::from_value(&json!({
+            "utc_offset_minutes": offset,
+            "windows": [{
+                "days": ["mon", "tue", "wed", "thu", "fri"],
+                "start": "07:00",
+                "end": "19:00"
+            }]
+        }))
+        .unwrap()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_null_is_no_schedule() {
+        assert_eq!(WeeklySchedule::from_value(&json!(null)), Ok(None));
+    }
+
+    #[test]
+    fn test_weekday_window() {
+        let schedule = weekday_schedule(0);
+        // 2024-01-01 is a Monday
+        assert!(schedule.is_active_at(Utc.with_ymd_and_hms(2024, 1, 1, 7, 0, 0).unwrap()));
+        assert!(schedule.is_active_at(Utc.with_ymd_and_hms(2024, 1, 5, 18, 59, 0).unwrap()));
+        assert!(!schedule.is_active_at(Utc.with_ymd_and_hms(2024, 1, 1, 19, 0, 0).unwrap()));
+        assert!(!schedule.is_active_at(Utc.with_ymd_and_hms(2024, 1, 1, 6, 59, 0).unwrap()));
+        assert!(!schedule.is_active_at(Utc.with_ymd_and_hms(2024, 1, 6, 12, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_utc_offset_shifts_window() {
+        let schedule = weekday_schedule(120);
+        // 05:30 UTC is 07:30 local
+        assert!(schedule.is_active_at(Utc.with_ymd_and_hms(2024, 1, 1, 5, 30, 0).unwrap()));
+        assert!(!schedule.is_active_at(Utc.with_ymd_and_hms(2024, 1, 1, 17, 30, 0).unwrap()));
+        // Sunday 23:00 UTC is Monday 01:00 local, before the window
+        assert!(!schedule.is_active_at(Utc.with_ymd_and_hms(2023, 12, 31, 23, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_overnight_window() {
+        let schedule = WeeklySchedule::from_value(&json!({
+            "windows": [{ "days": ["fri"], "start": "22:00", "end": "06:00" }]
+        }))
+        .unwrap()
+        .unwrap();
+        assert!(schedule.is_active_at(Utc.with_ymd_and_hms(2024, 1, 5, 23, 0, 0).unwrap()));
+        assert!(schedule.is_active_at(Utc.with_ymd_and_hms(2024, 1, 6, 5, 0, 0).unwrap()));
+        assert!(!schedule.is_active_at(Utc.with_ymd_and_hms(2024, 1, 6, 6, 0, 0).unwrap()));
+        assert!(!schedule.is_active_at(Utc.with_ymd_and_hms(2024, 1, 5, 5, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_empty_schedule_is_never_active() {
+        let schedule = WeeklySchedule::from_value(&json!({ "windows": [] }))
+            .unwrap()
+            .unwrap();
+        assert!(!schedule.is_active_at(Utc::now()));
+    }
+
+    #[test]
+    fn test_invalid_schedules() {
+        assert!(WeeklySchedule::from_value(&json!("weekdays")).is_err());
+        assert!(WeeklySchedule::from_value(&json!({
+            "windows": [{ "days": ["mon"], "start": "25:00", "end": "06:00" }]
+        }))
+        .is_err());
+        assert!(WeeklySchedule::from_value(&json!({
+            "windows": [{ "days": [], "start": "07:00", "end": "19:00" }]
+        }))
+        .is_err());
+        assert!(WeeklySchedule::from_value(&json!({
+            "utc_offset_minutes": 900,
+            "windows": []
+        }))
+        .is_err());
+    }
+}
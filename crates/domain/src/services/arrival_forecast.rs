@@ -0,0 +1,167 @@
+//! Geofence arrival forecasting.
+//!
+//! Estimates when a moving device will reach a geofence from the speed and
+//! bearing of its latest location. Only the component of the speed toward
+//! the geofence center counts, and devices heading more than
+//! [`MAX_HEADING_DEVIATION_DEGREES`] away from it are not forecast, so a
+//! device passing by is not announced as arriving.
+
+use crate::models::privacy_zone::distance_meters;
+use crate::services::geofence_evaluation::GeofenceRegion;
+
+/// Setting key that lets a device's arrivals be forecast.
+pub const ARRIVAL_FORECAST_SETTING_KEY: &str = "geofence_arrival_forecasts_enabled";
+
+/// Minimum speed for a forecast, in m/s; slower devices may be stationary.
+pub const MIN_FORECAST_SPEED_MPS: f64 = 1.0;
+
+/// Maximum angle between the bearing and the direction to the geofence.
+pub const MAX_HEADING_DEVIATION_DEGREES: f64 = 45.0;
+
+/// Maximum age of the location a forecast is made from, in seconds.
+pub const MAX_FORECAST_FIX_AGE_SECS: i64 = 300;
+
+/// Time after which an announced approach that did not end in an enter
+/// event may be announced again, in seconds.
+pub const ARRIVAL_FORECAST_SUPPRESSION_SECS: i64 = 3600;
+
+/// A moving location fix.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MotionFix {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Direction of travel in degrees clockwise from north.
+    pub bearing: f64,
+    /// Speed in m/s.
+    pub speed_mps: f64,
+}
+
+/// Estimated arrival at a geofence.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArrivalForecast {
+    /// Distance to the geofence boundary in meters.
+    pub distance_meters: f64,
+    /// Estimated time until the boundary is reached, in seconds.
+    pub eta_seconds: f64,
+}
+
+/// Forecast the arrival of a device at a geofence.
+///
+/// Returns `None` if the device is already inside, too slow, or not
+/// heading toward the geofence.
+pub fn forecast_arrival(region: &GeofenceRegion, fix: &MotionFix) -> Option<ArrivalForecast> {
+    if fix.speed_mps.is_nan() || fix.speed_mps < MIN_FORECAST_SPEED_MPS || !fix.bearing.is_finite()
+    {
+        return None;
+    }
+
+    let distance = distance_meters(
+        fix.latitude,
+        fix.longitude,
+        region.latitude,
+        region.longitude,
+    ) - region.radius_meters;
+    if distance <= 0.0 {
+        return None;
+    }
+
+    let deviation = angle_between(
+        fix.bearing,
+        initial_bearing(
+            fix.latitude,
+            fix.longitude,
+            region.latitude,
+            region.longitude,
+        ),
+    );
+    if deviation > MAX_HEADING_DEVIATION_DEGREES {
+        return None;
+    }
+
+    let closing_speed = fix.speed_mps * deviation.to_radians().cos();
+    Some(ArrivalForecast {
+        distance_meters: distance,
+        eta_seconds: distance / closing_speed,
+    })
+}
+
+/// Initial great-circle bearing from one point to another, in degrees
+/// clockwise from north.
+fn initial_bearing(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let (phi1, phi2) = (lat1.to_radians(), lat2.to_radians());
+    let d_lon = (lon2 - lon1).to_radians();
+    let y = d_lon.sin() * phi2.cos();
+    let x = phi1.cos() * phi2.sin() - phi1.sin() * phi2.cos() * d_lon.cos();
+    y.atan2(x).to_degrees().rem_euclid(360.0)
+}
+
+/// Smallest angle between two bearings, in degrees (0-180).
+fn angle_between(a: f64, b: f64) -> f64 {
+    let diff = (a - b).rem_euclid(360.0);
+    diff.min(360.0 - diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 100 m radius around a point; 0.001° of latitude is about 111 m.
+    fn region() -> GeofenceRegion {
+        GeofenceRegion {
+            latitude: 48.1486,
+            longitude: 17.1077,
+            radius_meters: 100.0,
+        }
+    }
+
+    /// A fix about 1.2 km south of the region center.
+    fn fix(bearing: f64, speed_mps: f64) -> MotionFix {
+        MotionFix {
+            latitude: 48.1378,
+            longitude: 17.1077,
+            bearing,
+            speed_mps,
+        }
+    }
+
+    #[test]
+    fn test_forecast_heading_straight_in() {
+        let forecast = forecast_arrival(&region(), &fix(0.0, 10.0)).unwrap();
+        assert!((forecast.distance_meters - 1101.0).abs() < 5.0);
+        assert!((forecast.eta_seconds - 110.1).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_forecast_uses_closing_speed() {
+        let straight = forecast_arrival(&region(), &fix(0.0, 10.0)).unwrap();
+        let oblique = forecast_arrival(&region(), &fix(30.0, 10.0)).unwrap();
+        assert!(oblique.eta_seconds > straight.eta_seconds);
+        // Bearings wrap around north.
+        let wrapped = forecast_arrival(&region(), &fix(330.0, 10.0)).unwrap();
+        assert!((wrapped.eta_seconds - oblique.eta_seconds).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_no_forecast() {
+        // Heading away or sideways
+        assert!(forecast_arrival(&region(), &fix(180.0, 10.0)).is_none());
+        assert!(forecast_arrival(&region(), &fix(90.0, 10.0)).is_none());
+        // Too slow
+        assert!(forecast_arrival(&region(), &fix(0.0, 0.5)).is_none());
+        assert!(forecast_arrival(&region(), &fix(0.0, f64::NAN)).is_none());
+        // Already inside
+        let inside = MotionFix {
+            latitude: 48.1490,
+            ..fix(0.0, 10.0)
+        };
+        assert!(forecast_arrival(&region(), &inside).is_none());
+    }
+
+    #[test]
+    fn test_initial_bearing() {
+        assert!((initial_bearing(48.0, 17.0, 49.0, 17.0) - 0.0).abs() < 0.01);
+        assert!((initial_bearing(48.0, 17.0, 47.0, 17.0) - 180.0).abs() < 0.01);
+        assert!((initial_bearing(0.0, 17.0, 0.0, 18.0) - 90.0).abs() < 0.01);
+        assert!((angle_between(350.0, 10.0) - 20.0).abs() < 1e-9);
+    }
+}
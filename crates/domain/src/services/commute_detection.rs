@@ -0,0 +1,351 @@
+//! Commute pattern detection.
+//!
+//! Finds trips a device makes again and again between the same two places,
+//! such as home to work, and when they usually depart. Trip start and end
+//! points within [`CommuteDetectionConfig::place_radius_meters`] of a place
+//! belong to it. An origin and destination pair is a commute when trips on
+//! at least [`CommuteDetectionConfig::min_days`] different days departed
+//! within [`CommuteDetectionConfig::departure_window_minutes`] of each other.
+//! Times of day and weekdays are in UTC.
+
+use chrono::{DateTime, Datelike, NaiveDate, Timelike, Weekday};
+use std::collections::BTreeMap;
+
+use crate::models::privacy_zone::distance_meters;
+use crate::models::trip::{Trip, TripState};
+
+/// Tuning parameters for commute detection.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CommuteDetectionConfig {
+    /// Maximum distance of a trip end point from the place it belongs to.
+    pub place_radius_meters: f64,
+    /// Minimum number of days with a trip between the two places.
+    pub min_days: usize,
+    /// Maximum difference between the departure times of a commute's trips.
+    pub departure_window_minutes: u32,
+}
+
+impl Default for CommuteDetectionConfig {
+    fn default() -> Self {
+        Self {
+            place_radius_meters: 250.0,
+            min_days: 3,
+            departure_window_minutes: 90,
+        }
+    }
+}
+
+/// A recurring trip between two places.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetectedCommute {
+    pub origin_latitude: f64,
+    pub origin_longitude: f64,
+    pub destination_latitude: f64,
+    pub destination_longitude: f64,
+    /// Number of trips that make up the commute, at most one per day.
+    pub trip_count: usize,
+    /// Median departure time, in minutes after midnight UTC.
+    pub typical_departure_minute: u32,
+    /// Median deviation of departures from the typical time, in minutes.
+    pub departure_spread_minutes: u32,
+    /// Median trip duration in seconds.
+    pub typical_duration_seconds: i64,
+    /// Weekdays the commute was made on, Monday first.
+    pub weekdays: Vec<Weekday>,
+    /// Start of the first and of the latest trip, in milliseconds.
+    pub first_departure: i64,
+    pub last_departure: i64,
+}
+
+/// A place trips start or end at, with the mean position of its points.
+#[derive(Debug, Clone, Copy)]
+struct Place {
+    latitude: f64,
+    longitude: f64,
+    points: u32,
+}
+
+/// A completed trip reduced to what commute detection needs.
+#[derive(Debug, Clone, Copy)]
+struct Departure {
+    start_timestamp: i64,
+    date: NaiveDate,
+    minute_of_day: u32,
+    duration_seconds: i64,
+}
+
+/// Detect the commutes in a device's trips.
+///
+/// Only completed trips with an end location count, and trips that end
+/// where they started are ignored. Commutes are ordered by trip count,
+/// most frequent first, then by departure time.
+pub fn detect_commutes(trips: &[Trip], config: &CommuteDetectionConfig) -> Vec<DetectedCommute> {
+    let mut trips: Vec<&Trip> = trips
+        .iter()
+        .filter(|t| t.state == TripState::Completed)
+        .collect();
+    trips.sort_by_key(|t| (t.start_timestamp, t.id));
+
+    let mut places: Vec<Place> = Vec::new();
+    let mut routes: BTreeMap<(usize, usize), Vec<Departure>> = BTreeMap::new();
+    for trip in trips {
+        let (Some(end_ts), Some(end_lat), Some(end_lon)) =
+            (trip.end_timestamp, trip.end_latitude, trip.end_longitude)
+        else {
+            continue;
+        };
+        let Some(started) = DateTime::from_timestamp_millis(trip.start_timestamp) else {
+            continue;
+        };
+
+        let origin = place_index(
+            &mut places,
+            trip.start_latitude,
+            trip.start_longitude,
+            config.place_radius_meters,
+        );
+        let destination = place_index(&mut places, end_lat, end_lon, config.place_radius_meters);
+        if origin == destination {
+            continue;
+        }
+
+        routes
+            .entry((origin, destination))
+            .or_default()
+            .push(Departure {
+                start_timestamp: trip.start_timestamp,
+                date: started.date_naive(),
+                minute_of_day: started.hour() * 60 + started.minute(),
+                duration_seconds: (end_ts - trip.start_timestamp).max(0) / 1000,
+            });
+    }
+
+    let mut commutes: Vec<DetectedCommute> = routes
+        .into_iter()
+        .filter_map(|((origin, destination), departures)| {
+            let departures = recurring_departures(departures, config.departure_window_minutes);
+            if departures.len() < config.min_days.max(1) {
+                return None;
+            }
+
+            let mut minutes: Vec<i64> = departures.iter().map(|d| d.minute_of_day as i64).collect();
+            let typical = median(&mut minutes);
+            let mut deviations: Vec<i64> = minutes.iter().map(|m| (m - typical).abs()).collect();
+            let mut durations: Vec<i64> = departures.iter().map(|d| d.duration_seconds).collect();
+            let mut weekdays: Vec<Weekday> = departures.iter().map(|d| d.date.weekday()).collect();
+            weekdays.sort_by_key(|d| d.num_days_from_monday());
+            weekdays.dedup();
+
+            Some(DetectedCommute {
+                origin_latitude: places[origin].latitude,
+                origin_longitude: places[origin].longitude,
+                destination_latitude: places[destination].latitude,
+                destination_longitude: places[destination].longitude,
+                trip_count: departures.len(),
+                typical_departure_minute: typical as u32,
+                departure_spread_minutes: median(&mut deviations) as u32,
+                typical_duration_seconds: median(&mut durations),
+                weekdays,
+                first_departure: departures.iter().map(|d| d.start_timestamp).min()?,
+                last_departure: departures.iter().map(|d| d.start_timestamp).max()?,
+            })
+        })
+        .collect();
+
+    commutes.sort_by(|a, b| {
+        b.trip_count
+            .cmp(&a.trip_count)
+            .then(a.typical_departure_minute.cmp(&b.typical_departure_minute))
+    });
+    commutes
+}
+
+/// Index of the place a point belongs to, adding a place if none is near.
+fn place_index(places: &mut Vec<Place>, latitude: f64, longitude: f64, radius: f64) -> usize {
+    let nearest = places
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            (
+                i,
+                distance_meters(p.latitude, p.longitude, latitude, longitude),
+            )
+        })
+        .filter(|(_, distance)| *distance <= radius)
+        .min_by(|a, b| a.1.total_cmp(&b.1));
+
+    match nearest {
+        Some((index, _)) => {
+            let place = &mut places[index];
+            place.points += 1;
+            let n = place.points as f64;
+            place.latitude += (latitude - place.latitude) / n;
+            place.longitude += (longitude - place.longitude) / n;
+            index
+        }
+        None => {
+            places.push(Place {
+                latitude,
+                longitude,
+                points: 1,
+            });
+            places.len() - 1
+        }
+    }
+}
+
+/// The largest group of departures within `window_minutes` of each other,
+/// keeping the first departure of each day.
+fn recurring_departures(departures: Vec<Departure>, window_minutes: u32) -> Vec<Departure> {
+    let mut by_time = departures;
+    by_time.sort_by_key(|d| (d.minute_of_day, d.start_timestamp));
+
+    let days_in = |window: &[Departure]| {
+        let mut dates: Vec<NaiveDate> = window.iter().map(|d| d.date).collect();
+        dates.sort();
+        dates.dedup();
+        dates.len()
+    };
+
+    let mut best: &[Departure] = &[];
+    let mut best_days = 0;
+    let mut start = 0;
+    for end in 0..by_time.len() {
+        while by_time[end].minute_of_day - by_time[start].minute_of_day > window_minutes {
+            start += 1;
+        }
+        let days = days_in(&by_time[start..=end]);
+        if days > best_days {
+            best = &by_time[start..=end];
+            best_days = days;
+        }
+    }
+
+    let mut kept: Vec<Departure> = best.to_vec();
+    kept.sort_by_key(|d| d.start_timestamp);
+    kept.dedup_by_key(|d| d.date);
+    kept
+}
+
+/// Lower median of a non-empty list.
+fn median(values: &mut [i64]) -> i64 {
+    values.sort_unstable();
+    values[(values.len() - 1) / 2]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::movement_event::{DetectionSource, TransportationMode};
+    use chrono::{TimeZone, Utc};
+    use uuid::Uuid;
+
+    const HOME: (f64, f64) = (48.1486, 17.1077);
+    const WORK: (f64, f64) = (48.1700, 17.0600);
+    const GYM: (f64, f64) = (48.1300, 17.1200);
+
+    /// A completed 25 minute trip on 2026-03-<day> at hour:minute UTC.
+    fn trip(day: u32, hour: u32, minute: u32, from: (f64, f64), to: (f64, f64)) -> Trip {
+        let start = Utc.with_ymd_and_hms(2026, 3, day, hour, minute, 0).unwrap();
+        Trip {
+            id: Uuid::new_v4(),
+            device_id: Uuid::nil(),
+            local_trip_id: format!("trip-{}-{}{}", day, hour, minute),
+            state: TripState::Completed,
+            start_timestamp: start.timestamp_millis(),
+            end_timestamp: Some(start.timestamp_millis() + 25 * 60 * 1000),
+            // Points jitter by a few meters between days.
+            start_latitude: from.0 + day as f64 * 0.00001,
+            start_longitude: from.1,
+            end_latitude: Some(to.0 - day as f64 * 0.00001),
+            end_longitude: Some(to.1),
+            transportation_mode: TransportationMode::InVehicle,
+            detection_source: DetectionSource::ActivityRecognition,
+            distance_meters: None,
+            duration_seconds: None,
+            purpose: None,
+            purpose_source: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_weekday_commute_is_detected() {
+        // Monday 2026-03-02 to Friday 2026-03-06
+        let mut trips = Vec::new();
+        for (day, minute) in [(2, 40), (3, 50), (4, 45), (5, 55), (6, 35)] {
+            trips.push(trip(day, 7, minute, HOME, WORK));
+            trips.push(trip(day, 16, minute, WORK, HOME));
+        }
+        // A one-off trip to the gym
+        trips.push(trip(4, 18, 0, HOME, GYM));
+
+        let commutes = detect_commutes(&trips, &CommuteDetectionConfig::default());
+        assert_eq!(commutes.len(), 2);
+
+        let morning = &commutes[0];
+        assert_eq!(morning.trip_count, 5);
+        assert_eq!(morning.typical_departure_minute, 7 * 60 + 45);
+        assert_eq!(morning.departure_spread_minutes, 5);
+        assert_eq!(morning.typical_duration_seconds, 25 * 60);
+        assert!(
+            distance_meters(
+                morning.origin_latitude,
+                morning.origin_longitude,
+                HOME.0,
+                HOME.1
+            ) < 50.0
+        );
+        assert_eq!(
+            morning.weekdays,
+            vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri
+            ]
+        );
+        assert_eq!(morning.first_departure, trips[0].start_timestamp);
+
+        assert_eq!(commutes[1].typical_departure_minute, 16 * 60 + 45);
+    }
+
+    #[test]
+    fn test_scattered_departures_are_not_a_commute() {
+        let trips = vec![
+            trip(2, 7, 0, HOME, WORK),
+            trip(3, 12, 0, HOME, WORK),
+            trip(4, 19, 0, HOME, WORK),
+        ];
+        assert!(detect_commutes(&trips, &CommuteDetectionConfig::default()).is_empty());
+    }
+
+    #[test]
+    fn test_trips_count_once_per_day() {
+        // Turned back for a forgotten bag: two departures on one day
+        let trips = vec![
+            trip(2, 7, 40, HOME, WORK),
+            trip(2, 7, 55, HOME, WORK),
+            trip(3, 7, 45, HOME, WORK),
+        ];
+        assert!(detect_commutes(&trips, &CommuteDetectionConfig::default()).is_empty());
+
+        let mut more = trips.clone();
+        more.push(trip(4, 7, 50, HOME, WORK));
+        let commutes = detect_commutes(&more, &CommuteDetectionConfig::default());
+        assert_eq!(commutes[0].trip_count, 3);
+    }
+
+    #[test]
+    fn test_unfinished_and_round_trips_are_ignored() {
+        let mut trips: Vec<Trip> = (2..=6).map(|day| trip(day, 6, 0, HOME, HOME)).collect();
+        for day in 2..=6 {
+            let mut active = trip(day, 8, 0, HOME, WORK);
+            active.state = TripState::Active;
+            trips.push(active);
+        }
+        assert!(detect_commutes(&trips, &CommuteDetectionConfig::default()).is_empty());
+    }
+}
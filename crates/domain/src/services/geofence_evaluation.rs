@@ -0,0 +1,342 @@
+//! Server-side geofence evaluation.
+//!
+//! Derives enter and exit events from the location stream for devices
+//! whose clients cannot monitor geofences themselves. A fix counts as
+//! inside once it is within the geofence radius, and as outside only once
+//! it is farther than the radius plus its reported accuracy, so GPS jitter
+//! at the boundary does not produce repeated crossings.
+//!
+//! Like movement detection, the evaluator is stateless between requests:
+//! callers resume it from the last enter or exit event of the geofence,
+//! whether client-reported or derived, and replay newer locations through
+//! it. Resuming from client events is what keeps derived events from
+//! duplicating ones the client already reported.
+//!
+//! Where geofences of a device overlap, [`resolve_overlap`] picks the one
+//! whose notifications an event is delivered under, by priority.
+
+use chrono::{DateTime, Utc};
+
+use crate::models::geofence::Geofence;
+use crate::models::geofence_event::GeofenceTransitionType;
+use crate::models::privacy_zone::distance_meters;
+
+/// Setting key that enables server-side geofence evaluation for a device.
+pub const GEOFENCE_EVALUATION_SETTING_KEY: &str = "server_geofence_evaluation_enabled";
+
+/// Setting key for the lowest trust score of a location that geofence
+/// evaluation and arrival forecasts act on.
+pub const GEOFENCE_MIN_TRUST_SETTING_KEY: &str = "geofence_min_trust_score";
+
+/// The circle a geofence covers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeofenceRegion {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub radius_meters: f64,
+}
+
+/// A location fix fed into the evaluator.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeofenceFix {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Reported horizontal accuracy in meters.
+    pub accuracy: f64,
+    /// Capture time in milliseconds since epoch.
+    pub timestamp_ms: i64,
+}
+
+/// A boundary crossing detected from the location stream, placed at the
+/// first fix on the new side of the boundary.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeofenceCrossing {
+    pub event_type: GeofenceTransitionType,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub timestamp_ms: i64,
+}
+
+/// Tracks whether a device is inside one geofence.
+#[derive(Debug, Clone)]
+pub struct GeofenceEvaluator {
+    region: GeofenceRegion,
+    /// `None` until the first conclusive fix when there is no prior event.
+    inside: Option<bool>,
+    last_timestamp_ms: Option<i64>,
+}
+
+impl GeofenceEvaluator {
+    /// Create an evaluator without prior state.
+    ///
+    /// The first conclusive fix only establishes where the device is; no
+    /// crossing is reported for it.
+    pub fn new(region: GeofenceRegion) -> Self {
+        Self {
+            region,
+            inside: None,
+            last_timestamp_ms: None,
+        }
+    }
+
+    /// Create an evaluator resuming from the geofence's last enter or exit
+    /// event. Fixes not newer than that event are ignored.
+    pub fn resume(region: GeofenceRegion, last: GeofenceTransitionType, timestamp_ms: i64) -> Self {
+        Self {
+            region,
+            inside: Some(last == GeofenceTransitionType::Enter),
+            last_timestamp_ms: Some(timestamp_ms),
+        }
+    }
+
+    /// Whether the device is known to be inside the geofence.
+    pub fn is_inside(&self) -> Option<bool> {
+        self.inside
+    }
+
+    /// Process one fix, returning a crossing if the fix completes one.
+    ///
+    /// Fixes not newer than the previous one are ignored.
+    pub fn process(&mut self, fix: GeofenceFix) -> Option<GeofenceCrossing> {
+        if self
+            .last_timestamp_ms
+            .is_some_and(|last| fix.timestamp_ms <= last)
+        {
+            return None;
+        }
+        self.last_timestamp_ms = Some(fix.timestamp_ms);
+
+        let distance = distance_meters(
+            self.region.latitude,
+            self.region.longitude,
+            fix.latitude,
+            fix.longitude,
+        );
+        let now_inside = if distance <= self.region.radius_meters {
+            true
+        } else if distance > self.region.radius_meters + fix.accuracy.max(0.0) {
+            false
+        } else {
+            // Within accuracy of the boundary: keep the current state.
+            return None;
+        };
+
+        match self.inside.replace(now_inside) {
+            Some(was_inside) if was_inside != now_inside => Some(GeofenceCrossing {
+                event_type: if now_inside {
+                    GeofenceTransitionType::Enter
+                } else {
+                    GeofenceTransitionType::Exit
+                },
+                latitude: fix.latitude,
+                longitude: fix.longitude,
+                timestamp_ms: fix.timestamp_ms,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Undo a crossing that was not recorded, e.g. during a cool-down.
+    ///
+    /// The device is treated as still on the side it was before, so the
+    /// next newer fix on the new side reports the crossing again.
+    pub fn revert(&mut self, crossing: &GeofenceCrossing) {
+        self.inside = Some(crossing.event_type == GeofenceTransitionType::Exit);
+    }
+}
+
+/// Resolve the geofence an event at a location is delivered under.
+///
+/// The event's own geofence competes with the other active geofences of
+/// `geofences` that contain the location and are scheduled at `at`. The
+/// highest priority wins; ties go to the event's own geofence, so events
+/// of geofences that do not overlap always resolve to themselves.
+pub fn resolve_overlap<'a>(
+    own: &'a Geofence,
+    geofences: &'a [Geofence],
+    latitude: f64,
+    longitude: f64,
+    at: DateTime<Utc>,
+) -> &'a Geofence {
+    geofences
+        .iter()
+        .filter(|g| g.geofence_id != own.geofence_id && g.active && g.is_scheduled_at(at))
+        .filter(|g| {
+            distance_meters(g.latitude, g.longitude, latitude, longitude)
+                <= f64::from(g.radius_meters)
+        })
+        .fold(own, |winner, g| {
+            if g.priority > winner.priority {
+                g
+            } else {
+                winner
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINUTE: i64 = 60 * 1000;
+
+    /// 100 m radius around a point; 0.001° of latitude is about 111 m.
+    fn region() -> GeofenceRegion {
+        GeofenceRegion {
+            latitude: 48.1486,
+            longitude: 17.1077,
+            radius_meters: 100.0,
+        }
+    }
+
+    fn fix(latitude: f64, timestamp_ms: i64) -> GeofenceFix {
+        GeofenceFix {
+            latitude,
+            longitude: 17.1077,
+            accuracy: 10.0,
+            timestamp_ms,
+        }
+    }
+
+    fn run(evaluator: &mut GeofenceEvaluator, fixes: &[GeofenceFix]) -> Vec<GeofenceCrossing> {
+        fixes.iter().filter_map(|f| evaluator.process(*f)).collect()
+    }
+
+    #[test]
+    fn test_first_fix_sets_baseline_without_event() {
+        let mut evaluator = GeofenceEvaluator::new(region());
+        assert!(run(&mut evaluator, &[fix(48.1486, 0)]).is_empty());
+        assert_eq!(evaluator.is_inside(), Some(true));
+    }
+
+    #[test]
+    fn test_exit_and_enter() {
+        let mut evaluator = GeofenceEvaluator::new(region());
+        let crossings = run(
+            &mut evaluator,
+            &[
+                fix(48.1486, 0),
+                fix(48.1506, MINUTE),
+                fix(48.1510, 2 * MINUTE),
+                fix(48.1487, 3 * MINUTE),
+            ],
+        );
+
+        assert_eq!(crossings.len(), 2);
+        assert_eq!(crossings[0].event_type, GeofenceTransitionType::Exit);
+        assert_eq!(crossings[0].timestamp_ms, MINUTE);
+        assert_eq!(crossings[1].event_type, GeofenceTransitionType::Enter);
+        assert_eq!(crossings[1].timestamp_ms, 3 * MINUTE);
+    }
+
+    #[test]
+    fn test_boundary_jitter_is_ignored() {
+        let mut evaluator = GeofenceEvaluator::new(region());
+        // About 105 m out: beyond the radius but within accuracy of it
+        let crossings = run(
+            &mut evaluator,
+            &[
+                fix(48.1486, 0),
+                fix(48.14955, MINUTE),
+                fix(48.1490, 2 * MINUTE),
+                fix(48.14955, 3 * MINUTE),
+            ],
+        );
+        assert!(crossings.is_empty());
+        assert_eq!(evaluator.is_inside(), Some(true));
+    }
+
+    #[test]
+    fn test_resume_from_client_enter_does_not_repeat_it() {
+        let mut evaluator =
+            GeofenceEvaluator::resume(region(), GeofenceTransitionType::Enter, 10 * MINUTE);
+        let crossings = run(
+            &mut evaluator,
+            &[
+                // Older than the resumed event
+                fix(48.1520, 5 * MINUTE),
+                fix(48.1486, 11 * MINUTE),
+                fix(48.1520, 12 * MINUTE),
+            ],
+        );
+
+        assert_eq!(crossings.len(), 1);
+        assert_eq!(crossings[0].event_type, GeofenceTransitionType::Exit);
+        assert_eq!(crossings[0].timestamp_ms, 12 * MINUTE);
+    }
+
+    #[test]
+    fn test_out_of_order_fix_is_ignored() {
+        let mut evaluator = GeofenceEvaluator::new(region());
+        let crossings = run(
+            &mut evaluator,
+            &[fix(48.1486, 2 * MINUTE), fix(48.1520, MINUTE)],
+        );
+        assert!(crossings.is_empty());
+        assert_eq!(evaluator.is_inside(), Some(true));
+    }
+
+    fn geofence(latitude: f64, radius_meters: f32, priority: i32) -> Geofence {
+        Geofence {
+            id: 1,
+            geofence_id: uuid::Uuid::new_v4(),
+            device_id: uuid::Uuid::nil(),
+            name: "Zone".to_string(),
+            latitude,
+            longitude: 17.1077,
+            radius_meters,
+            event_types: vec![],
+            active: true,
+            metadata: None,
+            schedule: None,
+            template_id: None,
+            cooldown_seconds: 0,
+            arrival_alert_minutes: 0,
+            priority,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_resolve_overlap_by_priority() {
+        let now = Utc::now();
+        let campus = geofence(48.1486, 1000.0, 0);
+        let building = geofence(48.1486, 50.0, 10);
+        let geofences = vec![campus.clone(), building.clone()];
+
+        // Inside both: the building wins over the campus.
+        let resolved = resolve_overlap(&campus, &geofences, 48.1486, 17.1077, now);
+        assert_eq!(resolved.geofence_id, building.geofence_id);
+        let resolved = resolve_overlap(&building, &geofences, 48.1486, 17.1077, now);
+        assert_eq!(resolved.geofence_id, building.geofence_id);
+
+        // Outside the building: the campus event stands.
+        let resolved = resolve_overlap(&campus, &geofences, 48.1520, 17.1077, now);
+        assert_eq!(resolved.geofence_id, campus.geofence_id);
+
+        // Ties go to the event's own geofence.
+        let mut inactive = building.clone();
+        inactive.active = false;
+        let other = geofence(48.1486, 50.0, 0);
+        let geofences = vec![campus.clone(), other, inactive];
+        let resolved = resolve_overlap(&campus, &geofences, 48.1486, 17.1077, now);
+        assert_eq!(resolved.geofence_id, campus.geofence_id);
+    }
+
+    #[test]
+    fn test_reverted_crossing_is_reported_again() {
+        let mut evaluator = GeofenceEvaluator::new(region());
+        let crossings = run(&mut evaluator, &[fix(48.1486, 0), fix(48.1506, MINUTE)]);
+        assert_eq!(crossings.len(), 1);
+
+        evaluator.revert(&crossings[0]);
+        assert_eq!(evaluator.is_inside(), Some(true));
+        // Fixes not newer than the reverted crossing are still ignored
+        assert!(evaluator.process(fix(48.1506, MINUTE)).is_none());
+
+        let crossing = evaluator.process(fix(48.1510, 2 * MINUTE)).unwrap();
+        assert_eq!(crossing.event_type, GeofenceTransitionType::Exit);
+        assert_eq!(crossing.timestamp_ms, 2 * MINUTE);
+    }
+}
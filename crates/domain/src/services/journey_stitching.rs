@@ -0,0 +1,180 @@
+//! Multi-device journey stitching.
+//!
+//! A user who switches phones mid-journey leaves one trip fragment on each
+//! device. Stitching merges a trip into the preceding one when it was
+//! recorded by another of the user's devices, starts no later than
+//! [`JourneyStitchingConfig::max_gap_ms`] after the preceding trip ended and
+//! starts within [`JourneyStitchingConfig::max_gap_distance_meters`] of where
+//! it ended. Consecutive trips of the same device are never merged, so
+//! single-device users see one journey per trip.
+
+use crate::models::privacy_zone::distance_meters;
+use crate::models::trip::{Trip, TripState};
+
+/// Tuning parameters for journey stitching.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JourneyStitchingConfig {
+    /// Maximum time between the end of a trip and the start of the next.
+    pub max_gap_ms: i64,
+    /// Maximum distance between the end of a trip and the start of the next.
+    pub max_gap_distance_meters: f64,
+}
+
+impl Default for JourneyStitchingConfig {
+    fn default() -> Self {
+        Self {
+            max_gap_ms: 10 * 60 * 1000,
+            max_gap_distance_meters: 500.0,
+        }
+    }
+}
+
+/// Group trips into journeys.
+///
+/// Cancelled trips are dropped. Journeys and the trips within them are
+/// ordered by start time, oldest first.
+pub fn stitch_journeys(mut trips: Vec<Trip>, config: &JourneyStitchingConfig) -> Vec<Vec<Trip>> {
+    trips.retain(|t| t.state != TripState::Cancelled);
+    trips.sort_by_key(|t| (t.start_timestamp, t.id));
+
+    let mut journeys: Vec<Vec<Trip>> = Vec::new();
+    for trip in trips {
+        match journeys.last_mut() {
+            Some(journey) if continues(journey.last().unwrap(), &trip, config) => {
+                journey.push(trip)
+            }
+            _ => journeys.push(vec![trip]),
+        }
+    }
+    journeys
+}
+
+/// Whether `next` continues the journey ending with `previous`.
+fn continues(previous: &Trip, next: &Trip, config: &JourneyStitchingConfig) -> bool {
+    if previous.device_id == next.device_id {
+        return false;
+    }
+    // An active trip has not ended, so nothing can follow it yet.
+    let (Some(end_ts), Some(end_lat), Some(end_lon)) = (
+        previous.end_timestamp,
+        previous.end_latitude,
+        previous.end_longitude,
+    ) else {
+        return false;
+    };
+
+    // The old phone may notice the stop only after the new one departed.
+    next.start_timestamp <= end_ts + config.max_gap_ms
+        && distance_meters(end_lat, end_lon, next.start_latitude, next.start_longitude)
+            <= config.max_gap_distance_meters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::movement_event::{DetectionSource, TransportationMode};
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    const MINUTE: i64 = 60 * 1000;
+
+    fn trip(device_id: Uuid, start: (i64, f64), end: Option<(i64, f64)>) -> Trip {
+        Trip {
+            id: Uuid::new_v4(),
+            device_id,
+            local_trip_id: format!("trip-{}", start.0),
+            state: if end.is_some() {
+                TripState::Completed
+            } else {
+                TripState::Active
+            },
+            start_timestamp: start.0,
+            end_timestamp: end.map(|e| e.0),
+            start_latitude: start.1,
+            start_longitude: 17.1077,
+            end_latitude: end.map(|e| e.1),
+            end_longitude: end.map(|_| 17.1077),
+            transportation_mode: TransportationMode::InVehicle,
+            detection_source: DetectionSource::ActivityRecognition,
+            distance_meters: None,
+            duration_seconds: None,
+            purpose: None,
+            purpose_source: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_phone_switch_is_stitched() {
+        let (old, new) = (Uuid::new_v4(), Uuid::new_v4());
+        let journeys = stitch_journeys(
+            vec![
+                trip(new, (25 * MINUTE, 48.201), None),
+                trip(old, (0, 48.10), Some((20 * MINUTE, 48.20))),
+            ],
+            &JourneyStitchingConfig::default(),
+        );
+
+        assert_eq!(journeys.len(), 1);
+        assert_eq!(journeys[0][0].device_id, old);
+        assert_eq!(journeys[0][1].device_id, new);
+    }
+
+    #[test]
+    fn test_same_device_trips_are_not_stitched() {
+        let device = Uuid::new_v4();
+        let journeys = stitch_journeys(
+            vec![
+                trip(device, (0, 48.10), Some((20 * MINUTE, 48.20))),
+                trip(device, (25 * MINUTE, 48.20), Some((40 * MINUTE, 48.30))),
+            ],
+            &JourneyStitchingConfig::default(),
+        );
+        assert_eq!(journeys.len(), 2);
+    }
+
+    #[test]
+    fn test_distant_or_late_trips_are_not_stitched() {
+        let (old, new) = (Uuid::new_v4(), Uuid::new_v4());
+        let config = JourneyStitchingConfig::default();
+
+        let far = stitch_journeys(
+            vec![
+                trip(old, (0, 48.10), Some((20 * MINUTE, 48.20))),
+                trip(new, (22 * MINUTE, 48.25), None),
+            ],
+            &config,
+        );
+        assert_eq!(far.len(), 2);
+
+        let late = stitch_journeys(
+            vec![
+                trip(old, (0, 48.10), Some((20 * MINUTE, 48.20))),
+                trip(new, (45 * MINUTE, 48.20), None),
+            ],
+            &config,
+        );
+        assert_eq!(late.len(), 2);
+    }
+
+    #[test]
+    fn test_overlap_and_cancelled_trips() {
+        let (old, new) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut cancelled = trip(new, (5 * MINUTE, 48.15), None);
+        cancelled.state = TripState::Cancelled;
+
+        let journeys = stitch_journeys(
+            vec![
+                trip(old, (0, 48.10), Some((20 * MINUTE, 48.20))),
+                cancelled,
+                // Started before the old phone detected its stop.
+                trip(new, (18 * MINUTE, 48.199), Some((30 * MINUTE, 48.30))),
+            ],
+            &JourneyStitchingConfig::default(),
+        );
+
+        assert_eq!(journeys.len(), 1);
+        assert_eq!(journeys[0].len(), 2);
+    }
+}
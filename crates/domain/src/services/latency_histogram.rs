@@ -0,0 +1,86 @@
+//! Fixed-bucket response time histograms.
+//!
+//! API usage rollups keep request latencies as counts per bucket so that
+//! rollups from several flushes and server instances can be merged by
+//! adding bucket counts, and percentiles recomputed from the merged counts.
+
+/// Upper bounds (inclusive, milliseconds) of all buckets but the last,
+/// which holds everything slower.
+pub const LATENCY_BUCKET_BOUNDS_MS: [i64; 11] =
+    [5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000];
+
+/// Number of histogram buckets.
+pub const LATENCY_BUCKET_COUNT: usize = LATENCY_BUCKET_BOUNDS_MS.len() + 1;
+
+/// Index of the bucket a response time falls into.
+pub fn latency_bucket(response_time_ms: i64) -> usize {
+    LATENCY_BUCKET_BOUNDS_MS
+        .iter()
+        .position(|bound| response_time_ms <= *bound)
+        .unwrap_or(LATENCY_BUCKET_BOUNDS_MS.len())
+}
+
+/// Estimate a percentile (`quantile` in 0.0–1.0) from bucket counts.
+///
+/// Returns the upper bound of the bucket holding the requested rank, capped
+/// at the slowest observed response time, which also stands in for the
+/// unbounded last bucket. Returns `None` for an empty histogram.
+pub fn latency_percentile(
+    buckets: &[i64],
+    max_response_time_ms: i64,
+    quantile: f64,
+) -> Option<i64> {
+    let total: i64 = buckets.iter().sum();
+    if total <= 0 {
+        return None;
+    }
+
+    let rank = ((quantile.clamp(0.0, 1.0) * total as f64).ceil() as i64).max(1);
+    let mut seen = 0;
+    for (index, count) in buckets.iter().enumerate() {
+        seen += count;
+        if seen >= rank {
+            let bound = LATENCY_BUCKET_BOUNDS_MS
+                .get(index)
+                .copied()
+                .unwrap_or(max_response_time_ms);
+            return Some(bound.min(max_response_time_ms));
+        }
+    }
+    Some(max_response_time_ms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_bucket() {
+        assert_eq!(latency_bucket(0), 0);
+        assert_eq!(latency_bucket(5), 0);
+        assert_eq!(latency_bucket(6), 1);
+        assert_eq!(latency_bucket(10000), 10);
+        assert_eq!(latency_bucket(60000), 11);
+    }
+
+    #[test]
+    fn test_latency_percentile() {
+        let mut buckets = [0i64; LATENCY_BUCKET_COUNT];
+        buckets[latency_bucket(8)] = 90;
+        buckets[latency_bucket(400)] = 9;
+        buckets[latency_bucket(30000)] = 1;
+
+        assert_eq!(latency_percentile(&buckets, 30000, 0.5), Some(10));
+        assert_eq!(latency_percentile(&buckets, 30000, 0.95), Some(500));
+        assert_eq!(latency_percentile(&buckets, 30000, 0.999), Some(30000));
+    }
+
+    #[test]
+    fn test_latency_percentile_capped_at_max() {
+        let mut buckets = [0i64; LATENCY_BUCKET_COUNT];
+        buckets[latency_bucket(120)] = 3;
+
+        assert_eq!(latency_percentile(&buckets, 130, 0.99), Some(130));
+        assert_eq!(latency_percentile(&[0; LATENCY_BUCKET_COUNT], 0, 0.5), None);
+    }
+}
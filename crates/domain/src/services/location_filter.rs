@@ -0,0 +1,260 @@
+//! Accuracy and speed filtering of uploaded locations.
+//!
+//! Devices and organizations can set a maximum accepted horizontal accuracy
+//! and a maximum plausible speed. Fixes that break either limit are not
+//! stored in the location history; callers quarantine them with the reason
+//! instead.
+//!
+//! Like smoothing, the filter is stateless between requests: callers seed it
+//! with the device's last persisted location so that single uploads are
+//! checked for impossible speed too.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::models::privacy_zone::distance_meters;
+use crate::services::smoothing::LocationSample;
+
+/// Device setting key for the maximum accepted accuracy in meters.
+pub const MAX_ACCURACY_SETTING_KEY: &str = "max_location_accuracy_meters";
+
+/// Device setting key for the maximum plausible speed in meters per second.
+pub const MAX_SPEED_SETTING_KEY: &str = "max_location_speed_mps";
+
+/// Limits applied to incoming fixes. `None` disables a check.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LocationFilterConfig {
+    /// Fixes reporting a worse (larger) accuracy are rejected.
+    pub max_accuracy_meters: Option<f64>,
+    /// Fixes implying a higher speed from the previous fix are rejected.
+    pub max_speed_mps: Option<f64>,
+}
+
+impl LocationFilterConfig {
+    /// Whether any check is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.max_accuracy_meters.is_some() || self.max_speed_mps.is_some()
+    }
+
+    /// Fill limits not set here from `fallback`.
+    ///
+    /// Used to let device settings override organization settings.
+    pub fn or(self, fallback: LocationFilterConfig) -> Self {
+        Self {
+            max_accuracy_meters: self.max_accuracy_meters.or(fallback.max_accuracy_meters),
+            max_speed_mps: self.max_speed_mps.or(fallback.max_speed_mps),
+        }
+    }
+}
+
+/// Normalize a configured limit: zero, negative and non-finite values
+/// disable the check.
+pub fn filter_limit(value: Option<f64>) -> Option<f64> {
+    value.filter(|v| v.is_finite() && *v > 0.0)
+}
+
+/// Why a fix was quarantined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuarantineReason {
+    /// Reported accuracy is worse than `max_accuracy_meters`.
+    AccuracyTooLow,
+    /// Reaching the fix would require exceeding `max_speed_mps`.
+    ImpossibleSpeed,
+    /// Dropped as an outlier by location smoothing.
+    SmoothingOutlier,
+}
+
+impl QuarantineReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuarantineReason::AccuracyTooLow => "ACCURACY_TOO_LOW",
+            QuarantineReason::ImpossibleSpeed => "IMPOSSIBLE_SPEED",
+            QuarantineReason::SmoothingOutlier => "SMOOTHING_OUTLIER",
+        }
+    }
+}
+
+impl FromStr for QuarantineReason {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ACCURACY_TOO_LOW" => Ok(QuarantineReason::AccuracyTooLow),
+            "IMPOSSIBLE_SPEED" => Ok(QuarantineReason::ImpossibleSpeed),
+            "SMOOTHING_OUTLIER" => Ok(QuarantineReason::SmoothingOutlier),
+            _ => Err(format!("Unknown quarantine reason: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for QuarantineReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Checks a chronologically ordered fix stream against the configured limits.
+#[derive(Debug, Clone)]
+pub struct LocationFilter {
+    config: LocationFilterConfig,
+    /// Last accepted fix, the reference point for speed checks.
+    last: Option<LocationSample>,
+}
+
+impl LocationFilter {
+    /// Create a filter, optionally seeded with the last stored location.
+    pub fn new(config: LocationFilterConfig, last_known: Option<LocationSample>) -> Self {
+        Self {
+            config,
+            last: last_known,
+        }
+    }
+
+    /// Check one fix, returning why it must be quarantined if it fails.
+    ///
+    /// Accepted fixes become the reference for later speed checks. Fixes
+    /// older than the reference (late uploads) are only checked for accuracy.
+    pub fn check(&mut self, sample: LocationSample) -> Result<(), QuarantineReason> {
+        if self
+            .config
+            .max_accuracy_meters
+            .is_some_and(|max| sample.accuracy > max)
+        {
+            return Err(QuarantineReason::AccuracyTooLow);
+        }
+
+        match self.last {
+            Some(last) if sample.timestamp_ms < last.timestamp_ms => return Ok(()),
+            Some(last) => {
+                if let Some(max_speed) = self.config.max_speed_mps {
+                    if implied_speed(&last, &sample) > max_speed {
+                        return Err(QuarantineReason::ImpossibleSpeed);
+                    }
+                }
+            }
+            None => {}
+        }
+
+        self.last = Some(sample);
+        Ok(())
+    }
+}
+
+/// Speed needed to get from `from` to `to`, in meters per second.
+///
+/// Distance within the combined accuracy of both fixes is jitter and not
+/// counted; elapsed time is at least one second.
+fn implied_speed(from: &LocationSample, to: &LocationSample) -> f64 {
+    let distance = distance_meters(from.latitude, from.longitude, to.latitude, to.longitude);
+    let travelled = (distance - from.accuracy - to.accuracy).max(0.0);
+    let elapsed_secs = ((to.timestamp_ms - from.timestamp_ms) as f64 / 1000.0).max(1.0);
+    travelled / elapsed_secs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(latitude: f64, accuracy: f64, timestamp_ms: i64) -> LocationSample {
+        LocationSample {
+            latitude,
+            longitude: 17.1077,
+            accuracy,
+            timestamp_ms,
+        }
+    }
+
+    fn config(
+        max_accuracy_meters: Option<f64>,
+        max_speed_mps: Option<f64>,
+    ) -> LocationFilterConfig {
+        LocationFilterConfig {
+            max_accuracy_meters,
+            max_speed_mps,
+        }
+    }
+
+    #[test]
+    fn test_disabled_filter_accepts_everything() {
+        let mut filter = LocationFilter::new(LocationFilterConfig::default(), None);
+        assert!(!LocationFilterConfig::default().is_enabled());
+        assert!(filter.check(sample(48.1486, 5000.0, 0)).is_ok());
+        assert!(filter.check(sample(10.0, 10.0, 1000)).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_poor_accuracy() {
+        let mut filter = LocationFilter::new(config(Some(100.0), None), None);
+        assert!(filter.check(sample(48.1486, 50.0, 0)).is_ok());
+        assert!(filter.check(sample(48.1486, 100.0, 1000)).is_ok());
+        assert_eq!(
+            filter.check(sample(48.1486, 150.0, 2000)),
+            Err(QuarantineReason::AccuracyTooLow)
+        );
+    }
+
+    #[test]
+    fn test_rejects_impossible_speed_from_last_known() {
+        let last = sample(48.1486, 10.0, 0);
+        let mut filter = LocationFilter::new(config(None, Some(70.0)), Some(last));
+        // ~111 km in one minute
+        assert_eq!(
+            filter.check(sample(49.1486, 10.0, 60_000)),
+            Err(QuarantineReason::ImpossibleSpeed)
+        );
+        // ~1.1 km in one minute (~18 m/s) is fine
+        assert!(filter.check(sample(48.1586, 10.0, 60_000)).is_ok());
+    }
+
+    #[test]
+    fn test_rejected_fix_does_not_move_reference() {
+        let mut filter = LocationFilter::new(config(None, Some(70.0)), None);
+        assert!(filter.check(sample(48.1486, 10.0, 0)).is_ok());
+        assert!(filter.check(sample(49.1486, 10.0, 10_000)).is_err());
+        // Measured from the first fix, not the rejected jump
+        assert!(filter.check(sample(48.1490, 10.0, 20_000)).is_ok());
+    }
+
+    #[test]
+    fn test_jitter_within_accuracy_is_not_speed() {
+        let mut filter = LocationFilter::new(config(None, Some(1.0)), None);
+        assert!(filter.check(sample(48.1486, 100.0, 0)).is_ok());
+        // ~110 m apart one second later, within the combined 200 m accuracy
+        assert!(filter.check(sample(48.1496, 100.0, 1000)).is_ok());
+    }
+
+    #[test]
+    fn test_late_fix_skips_speed_check() {
+        let last = sample(48.1486, 10.0, 60_000);
+        let mut filter = LocationFilter::new(config(None, Some(70.0)), Some(last));
+        assert!(filter.check(sample(40.0, 10.0, 0)).is_ok());
+    }
+
+    #[test]
+    fn test_device_config_overrides_organization() {
+        let device = config(Some(50.0), None);
+        let org = config(Some(200.0), Some(70.0));
+        assert_eq!(device.or(org), config(Some(50.0), Some(70.0)));
+    }
+
+    #[test]
+    fn test_filter_limit_normalization() {
+        assert_eq!(filter_limit(Some(50.0)), Some(50.0));
+        assert_eq!(filter_limit(Some(0.0)), None);
+        assert_eq!(filter_limit(Some(-1.0)), None);
+        assert_eq!(filter_limit(Some(f64::NAN)), None);
+        assert_eq!(filter_limit(None), None);
+    }
+
+    #[test]
+    fn test_quarantine_reason_round_trip() {
+        for reason in [
+            QuarantineReason::AccuracyTooLow,
+            QuarantineReason::ImpossibleSpeed,
+            QuarantineReason::SmoothingOutlier,
+        ] {
+            assert_eq!(reason.as_str().parse::<QuarantineReason>(), Ok(reason));
+        }
+        assert!("OTHER".parse::<QuarantineReason>().is_err());
+    }
+}
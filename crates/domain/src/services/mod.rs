@@ -2,14 +2,30 @@
 //!
 //! Services contain business logic that operates on domain models.
 
+pub mod arrival_forecast;
 pub mod audit;
+pub mod commute_detection;
+pub mod geofence_evaluation;
+pub mod journey_stitching;
+pub mod latency_histogram;
+pub mod location_filter;
+pub mod movement_detection;
 pub mod notification;
 pub mod policy_resolution;
+pub mod settings_diff;
+pub mod smoothing;
+pub mod speed_violations;
+pub mod takeout_import;
+pub mod tracking_schedule;
+pub mod trip_classification;
+pub mod trip_detection;
+pub mod trip_replay;
 
 pub use notification::{
-    MockNotificationService, NotificationPayload, NotificationResult, NotificationService,
-    NotificationType, SettingChangeAction, SettingChangeNotification, SettingsChangedPayload,
-    UnlockRequestResponsePayload,
+    CommandsPendingPayload, GeofenceArrivingPayload, GroupMessagePayload, LowBatteryPayload,
+    MemberJoinedPayload, MockNotificationService, NotificationPayload, NotificationResult,
+    NotificationService, NotificationType, SettingChangeAction, SettingChangeNotification,
+    SettingsChangedPayload, UnlockRequestResponsePayload, WebhookDisabledPayload,
 };
 
 pub use policy_resolution::{
@@ -17,4 +33,61 @@ pub use policy_resolution::{
     ResolvedSettings, SettingSource,
 };
 
+pub use settings_diff::{
+    diff_resolved_settings, SettingDifference, SettingDifferenceKind, SettingsDiffResponse,
+};
+
+pub use arrival_forecast::{
+    forecast_arrival, ArrivalForecast, MotionFix, ARRIVAL_FORECAST_SETTING_KEY,
+    ARRIVAL_FORECAST_SUPPRESSION_SECS, MAX_FORECAST_FIX_AGE_SECS,
+};
+
+pub use commute_detection::{detect_commutes, CommuteDetectionConfig, DetectedCommute};
+
+pub use geofence_evaluation::{
+    resolve_overlap, GeofenceCrossing, GeofenceEvaluator, GeofenceFix, GeofenceRegion,
+    GEOFENCE_EVALUATION_SETTING_KEY, GEOFENCE_MIN_TRUST_SETTING_KEY,
+};
+
+pub use journey_stitching::{stitch_journeys, JourneyStitchingConfig};
+
+pub use latency_histogram::{
+    latency_bucket, latency_percentile, LATENCY_BUCKET_BOUNDS_MS, LATENCY_BUCKET_COUNT,
+};
+
+pub use location_filter::{
+    filter_limit, LocationFilter, LocationFilterConfig, QuarantineReason, MAX_ACCURACY_SETTING_KEY,
+    MAX_SPEED_SETTING_KEY,
+};
+
+pub use movement_detection::{
+    MovementDetectionConfig, MovementDetector, MovementFix, MovementTransition,
+    MOVEMENT_DETECTION_SETTING_KEY,
+};
+
+pub use smoothing::{
+    LocationSample, LocationSmoother, RejectReason, SmoothingConfig, SmoothingOutcome,
+    LOCATION_SMOOTHING_SETTING_KEY,
+};
+
+pub use speed_violations::{
+    detect_speed_violations, DetectedSpeedViolation, SpeedLimitSegment, SpeedSample,
+    SpeedViolationConfig,
+};
+
+pub use takeout_import::{
+    prepare_takeout_records, ImportedLocation, PreparedImport, TakeoutLocation, TakeoutRecords,
+};
+
+pub use tracking_schedule::TRACKING_SCHEDULE_SETTING_KEY;
+
+pub use trip_classification::{classify_trip, CircleArea, ClassificationRule};
+
+pub use trip_detection::{
+    DetectedTrip, TripDetectionConfig, TripSegmenter, DERIVED_TRIP_ID_PREFIX,
+    TRIP_DETECTION_SETTING_KEY,
+};
+
+pub use trip_replay::{resample_track, ReplayPoint};
+
 pub use audit::{audit_helpers, AuditLogBuilder};
@@ -0,0 +1,355 @@
+//! Server-side movement state detection.
+//!
+//! Derives arrivals and departures from the location stream for devices
+//! that cannot run motion detection themselves. A device has arrived once
+//! its fixes stay within a small radius for a dwell period, and departs when
+//! a fix leaves that radius again.
+//!
+//! Like smoothing, the detector is stateless between requests: callers
+//! resume it from the device's last derived transition and replay recent
+//! locations through it.
+
+use crate::models::movement_event::{MovementState, TransportationMode};
+use crate::models::privacy_zone::distance_meters;
+
+/// Setting key that enables server-side movement detection for a device.
+pub const MOVEMENT_DETECTION_SETTING_KEY: &str = "server_movement_detection_enabled";
+
+/// Tuning parameters for movement detection.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MovementDetectionConfig {
+    /// Fixes within this distance of the anchor count as the same place.
+    /// Widened to a fix's reported accuracy when that is larger.
+    pub stationary_radius_meters: f64,
+    /// How long fixes must stay within the radius to count as an arrival.
+    pub dwell_ms: i64,
+}
+
+impl Default for MovementDetectionConfig {
+    fn default() -> Self {
+        Self {
+            stationary_radius_meters: 75.0,
+            dwell_ms: 5 * 60 * 1000,
+        }
+    }
+}
+
+/// A location fix fed into the detector.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MovementFix {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Reported horizontal accuracy in meters.
+    pub accuracy: f64,
+    /// Reported speed in meters per second.
+    pub speed: Option<f64>,
+    /// Capture time in milliseconds since epoch.
+    pub timestamp_ms: i64,
+}
+
+/// A state transition detected from the location stream.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MovementTransition {
+    pub state: MovementState,
+    /// Arrivals are placed at the first fix of the stationary period;
+    /// departures at the first fix outside of it.
+    pub latitude: f64,
+    pub longitude: f64,
+    pub accuracy: f64,
+    pub speed: Option<f64>,
+    pub timestamp_ms: i64,
+    /// `Stationary` for arrivals, estimated from speed for departures.
+    pub transportation_mode: TransportationMode,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum State {
+    Stationary {
+        anchor: MovementFix,
+    },
+    /// Moving, or no transition known yet.
+    Moving,
+}
+
+/// Detects arrivals and departures in a chronologically ordered fix stream.
+#[derive(Debug, Clone)]
+pub struct MovementDetector {
+    config: MovementDetectionConfig,
+    state: State,
+    /// First fix of the current candidate stationary period while moving.
+    candidate: Option<MovementFix>,
+    last_fix: Option<MovementFix>,
+}
+
+impl MovementDetector {
+    /// Create a detector without prior state.
+    pub fn new(config: MovementDetectionConfig) -> Self {
+        Self {
+            config,
+            state: State::Moving,
+            candidate: None,
+            last_fix: None,
+        }
+    }
+
+    /// Create a detector resuming from the device's last derived transition.
+    pub fn resume(config: MovementDetectionConfig, last: MovementTransition) -> Self {
+        let state = match last.state {
+            MovementState::Arrived => State::Stationary {
+                anchor: MovementFix {
+                    latitude: last.latitude,
+                    longitude: last.longitude,
+                    accuracy: last.accuracy,
+                    speed: None,
+                    timestamp_ms: last.timestamp_ms,
+                },
+            },
+            MovementState::Departed => State::Moving,
+        };
+        Self {
+            config,
+            state,
+            candidate: None,
+            last_fix: None,
+        }
+    }
+
+    /// Process one fix, returning a transition if it completes one.
+    ///
+    /// Fixes older than the previous one are ignored.
+    pub fn process(&mut self, fix: MovementFix) -> Option<MovementTransition> {
+        if self
+            .last_fix
+            .is_some_and(|last| fix.timestamp_ms < last.timestamp_ms)
+        {
+            return None;
+        }
+        let previous = self.last_fix.replace(fix);
+
+        match self.state {
+            State::Stationary { anchor } => {
+                if self.within_radius(&anchor, &fix) {
+                    return None;
+                }
+                self.state = State::Moving;
+                self.candidate = Some(fix);
+                Some(MovementTransition {
+                    state: MovementState::Departed,
+                    latitude: fix.latitude,
+                    longitude: fix.longitude,
+                    accuracy: fix.accuracy,
+                    speed: fix.speed,
+                    timestamp_ms: fix.timestamp_ms,
+                    transportation_mode: mode_for_speed(departure_speed(previous, &fix)),
+                })
+            }
+            State::Moving => {
+                let candidate = match self.candidate {
+                    Some(candidate) if self.within_radius(&candidate, &fix) => candidate,
+                    _ => {
+                        // Left the candidate place (or had none): start over here
+                        self.candidate = Some(fix);
+                        return None;
+                    }
+                };
+
+                if fix.timestamp_ms - candidate.timestamp_ms < self.config.dwell_ms {
+                    return None;
+                }
+
+                self.state = State::Stationary { anchor: candidate };
+                self.candidate = None;
+                Some(MovementTransition {
+                    state: MovementState::Arrived,
+                    latitude: candidate.latitude,
+                    longitude: candidate.longitude,
+                    accuracy: candidate.accuracy,
+                    speed: None,
+                    timestamp_ms: candidate.timestamp_ms,
+                    transportation_mode: TransportationMode::Stationary,
+                })
+            }
+        }
+    }
+
+    fn within_radius(&self, anchor: &MovementFix, fix: &MovementFix) -> bool {
+        let radius = self
+            .config
+            .stationary_radius_meters
+            .max(fix.accuracy)
+            .max(anchor.accuracy);
+        distance_meters(
+            anchor.latitude,
+            anchor.longitude,
+            fix.latitude,
+            fix.longitude,
+        ) <= radius
+    }
+}
+
+/// Reported speed of the departing fix, or the speed implied by the
+/// distance from the previous fix.
+fn departure_speed(previous: Option<MovementFix>, fix: &MovementFix) -> Option<f64> {
+    fix.speed.or_else(|| {
+        let previous = previous?;
+        let elapsed_secs = (fix.timestamp_ms - previous.timestamp_ms) as f64 / 1000.0;
+        (elapsed_secs > 0.0).then(|| {
+            distance_meters(
+                previous.latitude,
+                previous.longitude,
+                fix.latitude,
+                fix.longitude,
+            ) / elapsed_secs
+        })
+    })
+}
+
+/// Rough transportation mode for a speed in meters per second.
+pub(crate) fn mode_for_speed(speed: Option<f64>) -> TransportationMode {
+    match speed {
+        None => TransportationMode::Unknown,
+        Some(s) if s < 2.5 => TransportationMode::Walking,
+        Some(s) if s < 8.0 => TransportationMode::Cycling,
+        Some(_) => TransportationMode::InVehicle,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINUTE: i64 = 60 * 1000;
+
+    fn fix(latitude: f64, timestamp_ms: i64) -> MovementFix {
+        MovementFix {
+            latitude,
+            longitude: 17.1077,
+            accuracy: 10.0,
+            speed: None,
+            timestamp_ms,
+        }
+    }
+
+    fn run(detector: &mut MovementDetector, fixes: &[MovementFix]) -> Vec<MovementTransition> {
+        fixes.iter().filter_map(|f| detector.process(*f)).collect()
+    }
+
+    #[test]
+    fn test_arrival_after_dwell() {
+        let mut detector = MovementDetector::new(MovementDetectionConfig::default());
+        let transitions = run(
+            &mut detector,
+            &[
+                fix(48.1486, 0),
+                fix(48.1487, 2 * MINUTE),
+                fix(48.1486, 4 * MINUTE),
+                fix(48.1486, 6 * MINUTE),
+            ],
+        );
+
+        assert_eq!(transitions.len(), 1);
+        assert_eq!(transitions[0].state, MovementState::Arrived);
+        assert_eq!(transitions[0].timestamp_ms, 0);
+        assert_eq!(
+            transitions[0].transportation_mode,
+            TransportationMode::Stationary
+        );
+    }
+
+    #[test]
+    fn test_no_arrival_before_dwell() {
+        let mut detector = MovementDetector::new(MovementDetectionConfig::default());
+        let transitions = run(
+            &mut detector,
+            &[
+                fix(48.1486, 0),
+                fix(48.1486, 2 * MINUTE),
+                fix(48.1486, 4 * MINUTE),
+            ],
+        );
+        assert!(transitions.is_empty());
+    }
+
+    #[test]
+    fn test_departure_after_arrival() {
+        let mut detector = MovementDetector::new(MovementDetectionConfig::default());
+        let transitions = run(
+            &mut detector,
+            &[
+                fix(48.1486, 0),
+                fix(48.1486, 6 * MINUTE),
+                // ~1.1 km north one minute later (~18 m/s)
+                fix(48.1586, 7 * MINUTE),
+            ],
+        );
+
+        assert_eq!(transitions.len(), 2);
+        assert_eq!(transitions[1].state, MovementState::Departed);
+        assert_eq!(transitions[1].timestamp_ms, 7 * MINUTE);
+        assert_eq!(
+            transitions[1].transportation_mode,
+            TransportationMode::InVehicle
+        );
+    }
+
+    #[test]
+    fn test_continuous_movement_has_no_transitions() {
+        let mut detector = MovementDetector::new(MovementDetectionConfig::default());
+        let fixes: Vec<_> = (0..10)
+            .map(|i| fix(48.1486 + i as f64 * 0.01, i * MINUTE))
+            .collect();
+        assert!(run(&mut detector, &fixes).is_empty());
+    }
+
+    #[test]
+    fn test_resume_from_arrival_detects_departure() {
+        let last = MovementTransition {
+            state: MovementState::Arrived,
+            latitude: 48.1486,
+            longitude: 17.1077,
+            accuracy: 10.0,
+            speed: None,
+            timestamp_ms: 0,
+            transportation_mode: TransportationMode::Stationary,
+        };
+        let mut detector = MovementDetector::resume(MovementDetectionConfig::default(), last);
+
+        assert!(detector.process(fix(48.1487, 60 * MINUTE)).is_none());
+        let departed = detector
+            .process(MovementFix {
+                speed: Some(1.5),
+                ..fix(48.1526, 70 * MINUTE)
+            })
+            .expect("departure");
+        assert_eq!(departed.state, MovementState::Departed);
+        assert_eq!(departed.transportation_mode, TransportationMode::Walking);
+    }
+
+    #[test]
+    fn test_poor_accuracy_widens_radius() {
+        let mut detector = MovementDetector::new(MovementDetectionConfig::default());
+        let noisy = |latitude, timestamp_ms| MovementFix {
+            accuracy: 200.0,
+            ..fix(latitude, timestamp_ms)
+        };
+        // ~110 m apart, within the 200 m accuracy
+        let transitions = run(
+            &mut detector,
+            &[
+                noisy(48.1486, 0),
+                noisy(48.1496, 3 * MINUTE),
+                noisy(48.1486, 6 * MINUTE),
+            ],
+        );
+        assert_eq!(transitions.len(), 1);
+        assert_eq!(transitions[0].state, MovementState::Arrived);
+    }
+
+    #[test]
+    fn test_out_of_order_fix_is_ignored() {
+        let mut detector = MovementDetector::new(MovementDetectionConfig::default());
+        detector.process(fix(48.1486, 10 * MINUTE));
+        assert!(detector.process(fix(40.0, MINUTE)).is_none());
+        assert!(detector.process(fix(48.1486, 16 * MINUTE)).is_some());
+    }
+}
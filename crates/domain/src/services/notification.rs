@@ -12,6 +12,12 @@ use uuid::Uuid;
 pub enum NotificationType {
     SettingsChanged,
     UnlockRequestResponse,
+    CommandsPending,
+    GeofenceArriving,
+    WebhookDisabled,
+    GroupMessage,
+    LowBattery,
+    MemberJoined,
 }
 
 impl std::fmt::Display for NotificationType {
@@ -19,6 +25,12 @@ impl std::fmt::Display for NotificationType {
         match self {
             NotificationType::SettingsChanged => write!(f, "settings_changed"),
             NotificationType::UnlockRequestResponse => write!(f, "unlock_request_response"),
+            NotificationType::CommandsPending => write!(f, "commands_pending"),
+            NotificationType::GeofenceArriving => write!(f, "geofence_arriving"),
+            NotificationType::WebhookDisabled => write!(f, "webhook_disabled"),
+            NotificationType::GroupMessage => write!(f, "group_message"),
+            NotificationType::LowBattery => write!(f, "low_battery"),
+            NotificationType::MemberJoined => write!(f, "member_joined"),
         }
     }
 }
@@ -43,7 +55,7 @@ impl std::fmt::Display for SettingChangeAction {
 }
 
 /// A single setting change for notification payload.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct SettingChangeNotification {
     pub key: String,
@@ -79,12 +91,101 @@ pub struct UnlockRequestResponsePayload {
     pub timestamp: DateTime<Utc>,
 }
 
+/// Notification payload waking a device to fetch its queued commands, sent
+/// when the device has no open agent connection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct CommandsPendingPayload {
+    #[serde(rename = "type")]
+    pub notification_type: NotificationType,
+    pub device_id: Uuid,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Notification payload telling group members a device is about to arrive
+/// at a geofence.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GeofenceArrivingPayload {
+    #[serde(rename = "type")]
+    pub notification_type: NotificationType,
+    pub device_id: Uuid,
+    pub device_name: String,
+    pub geofence_id: Uuid,
+    pub geofence_name: String,
+    pub eta_seconds: i32,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Notification payload telling a webhook owner the webhook was disabled
+/// after failing repeatedly.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct WebhookDisabledPayload {
+    #[serde(rename = "type")]
+    pub notification_type: NotificationType,
+    pub webhook_id: Uuid,
+    pub webhook_name: String,
+    pub failure_streak: i32,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Notification payload telling group members about a new announcement or
+/// check-in.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GroupMessagePayload {
+    #[serde(rename = "type")]
+    pub notification_type: NotificationType,
+    pub group_id: Uuid,
+    pub group_name: String,
+    pub message_id: Uuid,
+    /// `announcement` or `check_in`.
+    pub kind: String,
+    pub sender_name: String,
+    /// Start of the message body or the check-in's place name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preview: Option<String>,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Notification payload telling group members a device's battery is low.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct LowBatteryPayload {
+    #[serde(rename = "type")]
+    pub notification_type: NotificationType,
+    pub device_id: Uuid,
+    pub device_name: String,
+    pub battery_level: i32,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Notification payload telling group members someone joined the group.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct MemberJoinedPayload {
+    #[serde(rename = "type")]
+    pub notification_type: NotificationType,
+    pub group_id: Uuid,
+    pub group_name: String,
+    pub user_id: Uuid,
+    pub member_name: String,
+    pub timestamp: DateTime<Utc>,
+}
+
 /// Generic notification payload.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum NotificationPayload {
     SettingsChanged(SettingsChangedPayload),
     UnlockRequestResponse(UnlockRequestResponsePayload),
+    CommandsPending(CommandsPendingPayload),
+    GeofenceArriving(GeofenceArrivingPayload),
+    WebhookDisabled(WebhookDisabledPayload),
+    GroupMessage(GroupMessagePayload),
+    LowBattery(LowBatteryPayload),
+    MemberJoined(MemberJoinedPayload),
 }
 
 /// Result of a notification send attempt.
@@ -94,6 +195,8 @@ pub enum NotificationResult {
     Sent,
     /// Device has no FCM token registered.
     NoToken,
+    /// The push provider rejected the token; it should be removed.
+    InvalidToken,
     /// Notification sending failed (but was non-blocking).
     Failed(String),
     /// Notification was skipped (e.g., notify_user=false).
@@ -116,6 +219,48 @@ pub trait NotificationService: Send + Sync {
         fcm_token: &str,
         payload: UnlockRequestResponsePayload,
     ) -> NotificationResult;
+
+    /// Send a commands pending notification to a device.
+    async fn send_commands_pending(
+        &self,
+        fcm_token: &str,
+        payload: CommandsPendingPayload,
+    ) -> NotificationResult;
+
+    /// Send a geofence arriving notification to a group member's device.
+    async fn send_geofence_arriving(
+        &self,
+        fcm_token: &str,
+        payload: GeofenceArrivingPayload,
+    ) -> NotificationResult;
+
+    /// Send a webhook disabled notification to a device of the webhook owner.
+    async fn send_webhook_disabled(
+        &self,
+        fcm_token: &str,
+        payload: WebhookDisabledPayload,
+    ) -> NotificationResult;
+
+    /// Send a group message notification to a group member's device.
+    async fn send_group_message(
+        &self,
+        fcm_token: &str,
+        payload: GroupMessagePayload,
+    ) -> NotificationResult;
+
+    /// Send a low battery notification to a group member's device.
+    async fn send_low_battery(
+        &self,
+        fcm_token: &str,
+        payload: LowBatteryPayload,
+    ) -> NotificationResult;
+
+    /// Send a member joined notification to a group member's device.
+    async fn send_member_joined(
+        &self,
+        fcm_token: &str,
+        payload: MemberJoinedPayload,
+    ) -> NotificationResult;
 }
 
 /// Mock notification service for development and testing.
@@ -195,6 +340,151 @@ impl NotificationService for MockNotificationService {
 
         NotificationResult::Sent
     }
+
+    async fn send_commands_pending(
+        &self,
+        fcm_token: &str,
+        payload: CommandsPendingPayload,
+    ) -> NotificationResult {
+        if self.simulate_failure {
+            tracing::warn!(
+                fcm_token = %fcm_token,
+                device_id = %payload.device_id,
+                "Mock notification service simulating failure"
+            );
+            return NotificationResult::Failed("Simulated failure".to_string());
+        }
+
+        tracing::info!(
+            fcm_token = %fcm_token,
+            device_id = %payload.device_id,
+            "Mock: Would send commands_pending notification"
+        );
+
+        NotificationResult::Sent
+    }
+
+    async fn send_geofence_arriving(
+        &self,
+        fcm_token: &str,
+        payload: GeofenceArrivingPayload,
+    ) -> NotificationResult {
+        if self.simulate_failure {
+            tracing::warn!(
+                fcm_token = %fcm_token,
+                geofence_id = %payload.geofence_id,
+                "Mock notification service simulating failure"
+            );
+            return NotificationResult::Failed("Simulated failure".to_string());
+        }
+
+        tracing::info!(
+            fcm_token = %fcm_token,
+            device_id = %payload.device_id,
+            geofence_id = %payload.geofence_id,
+            eta_seconds = payload.eta_seconds,
+            "Mock: Would send geofence_arriving notification"
+        );
+
+        NotificationResult::Sent
+    }
+
+    async fn send_webhook_disabled(
+        &self,
+        fcm_token: &str,
+        payload: WebhookDisabledPayload,
+    ) -> NotificationResult {
+        if self.simulate_failure {
+            tracing::warn!(
+                fcm_token = %fcm_token,
+                webhook_id = %payload.webhook_id,
+                "Mock notification service simulating failure"
+            );
+            return NotificationResult::Failed("Simulated failure".to_string());
+        }
+
+        tracing::info!(
+            fcm_token = %fcm_token,
+            webhook_id = %payload.webhook_id,
+            failure_streak = payload.failure_streak,
+            "Mock: Would send webhook_disabled notification"
+        );
+
+        NotificationResult::Sent
+    }
+
+    async fn send_group_message(
+        &self,
+        fcm_token: &str,
+        payload: GroupMessagePayload,
+    ) -> NotificationResult {
+        if self.simulate_failure {
+            tracing::warn!(
+                fcm_token = %fcm_token,
+                message_id = %payload.message_id,
+                "Mock notification service simulating failure"
+            );
+            return NotificationResult::Failed("Simulated failure".to_string());
+        }
+
+        tracing::info!(
+            fcm_token = %fcm_token,
+            group_id = %payload.group_id,
+            message_id = %payload.message_id,
+            kind = %payload.kind,
+            "Mock: Would send group_message notification"
+        );
+
+        NotificationResult::Sent
+    }
+
+    async fn send_low_battery(
+        &self,
+        fcm_token: &str,
+        payload: LowBatteryPayload,
+    ) -> NotificationResult {
+        if self.simulate_failure {
+            tracing::warn!(
+                fcm_token = %fcm_token,
+                device_id = %payload.device_id,
+                "Mock notification service simulating failure"
+            );
+            return NotificationResult::Failed("Simulated failure".to_string());
+        }
+
+        tracing::info!(
+            fcm_token = %fcm_token,
+            device_id = %payload.device_id,
+            battery_level = payload.battery_level,
+            "Mock: Would send low_battery notification"
+        );
+
+        NotificationResult::Sent
+    }
+
+    async fn send_member_joined(
+        &self,
+        fcm_token: &str,
+        payload: MemberJoinedPayload,
+    ) -> NotificationResult {
+        if self.simulate_failure {
+            tracing::warn!(
+                fcm_token = %fcm_token,
+                group_id = %payload.group_id,
+                "Mock notification service simulating failure"
+            );
+            return NotificationResult::Failed("Simulated failure".to_string());
+        }
+
+        tracing::info!(
+            fcm_token = %fcm_token,
+            group_id = %payload.group_id,
+            user_id = %payload.user_id,
+            "Mock: Would send member_joined notification"
+        );
+
+        NotificationResult::Sent
+    }
 }
 
 #[cfg(test)]
@@ -211,6 +501,21 @@ mod tests {
             NotificationType::UnlockRequestResponse.to_string(),
             "unlock_request_response"
         );
+        assert_eq!(
+            NotificationType::CommandsPending.to_string(),
+            "commands_pending"
+        );
+        assert_eq!(
+            NotificationType::GeofenceArriving.to_string(),
+            "geofence_arriving"
+        );
+        assert_eq!(
+            NotificationType::WebhookDisabled.to_string(),
+            "webhook_disabled"
+        );
+        assert_eq!(NotificationType::GroupMessage.to_string(), "group_message");
+        assert_eq!(NotificationType::LowBattery.to_string(), "low_battery");
+        assert_eq!(NotificationType::MemberJoined.to_string(), "member_joined");
     }
 
     #[test]
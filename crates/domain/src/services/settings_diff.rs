@@ -0,0 +1,170 @@
+//! Configuration drift diffing between resolved device settings.
+//!
+//! Compares the effective settings of two devices (or a device and a policy
+//! template) key by key, reporting where the values, locks, or the policy
+//! level that supplied a value differ.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use uuid::Uuid;
+
+use super::policy_resolution::{ResolvedSettings, SettingSource};
+
+/// How a setting differs between the two sides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SettingDifferenceKind {
+    /// The setting only resolves on the left side.
+    OnlyLeft,
+    /// The setting only resolves on the right side.
+    OnlyRight,
+    /// Both sides resolve the setting to different values.
+    ValueDiffers,
+    /// Values match, but the setting is locked on one side only.
+    LockDiffers,
+    /// Values and locks match, but they come from different policy levels.
+    SourceDiffers,
+}
+
+/// A single setting that differs between the two sides.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SettingDifference {
+    pub key: String,
+    pub kind: SettingDifferenceKind,
+    pub left_value: Option<serde_json::Value>,
+    pub right_value: Option<serde_json::Value>,
+    pub left_source: Option<SettingSource>,
+    pub right_source: Option<SettingSource>,
+    pub left_locked: bool,
+    pub right_locked: bool,
+}
+
+/// Settings diff between a device and another device or a policy template.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SettingsDiffResponse {
+    /// The device on the left side of the diff.
+    pub device_id: Uuid,
+    /// The device on the right side, when comparing two devices.
+    pub other_device_id: Option<Uuid>,
+    /// The policy template on the right side, when comparing against a policy.
+    pub policy_id: Option<Uuid>,
+    /// Number of setting keys resolved on either side.
+    pub compared_keys: usize,
+    pub differences: Vec<SettingDifference>,
+}
+
+/// Diff two sets of resolved settings.
+///
+/// Differences are returned sorted by key. Keys that resolve identically on
+/// both sides are omitted.
+pub fn diff_resolved_settings(
+    left: &ResolvedSettings,
+    right: &ResolvedSettings,
+) -> Vec<SettingDifference> {
+    let keys: BTreeSet<&String> = left.settings.keys().chain(right.settings.keys()).collect();
+
+    keys.into_iter()
+        .filter_map(|key| {
+            let left_value = left.get(key);
+            let right_value = right.get(key);
+            let left_source = left.get_source(key);
+            let right_source = right.get_source(key);
+            let left_locked = left.is_locked(key);
+            let right_locked = right.is_locked(key);
+
+            let kind = match (left_value, right_value) {
+                (Some(_), None) => SettingDifferenceKind::OnlyLeft,
+                (None, Some(_)) => SettingDifferenceKind::OnlyRight,
+                (l, r) if l != r => SettingDifferenceKind::ValueDiffers,
+                _ if left_locked != right_locked => SettingDifferenceKind::LockDiffers,
+                _ if left_source != right_source => SettingDifferenceKind::SourceDiffers,
+                _ => return None,
+            };
+
+            Some(SettingDifference {
+                key: key.clone(),
+                kind,
+                left_value: left_value.cloned(),
+                right_value: right_value.cloned(),
+                left_source: left_source.cloned(),
+                right_source: right_source.cloned(),
+                left_locked,
+                right_locked,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn resolved(entries: &[(&str, serde_json::Value, SettingSource, bool)]) -> ResolvedSettings {
+        let mut result = ResolvedSettings::new();
+        for (key, value, source, locked) in entries {
+            result.settings.insert(key.to_string(), value.clone());
+            result.sources.insert(key.to_string(), source.clone());
+            if *locked {
+                result.locked_keys.insert(key.to_string());
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn test_identical_settings_have_no_differences() {
+        let left = resolved(&[(
+            "tracking_enabled",
+            json!(true),
+            SettingSource::DefaultValue,
+            false,
+        )]);
+        assert!(diff_resolved_settings(&left, &left.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_diff_kinds() {
+        let left = resolved(&[
+            ("a_only_left", json!(1), SettingSource::DeviceCustom, false),
+            ("b_value", json!(60), SettingSource::DevicePolicy, true),
+            ("c_lock", json!(true), SettingSource::DevicePolicy, true),
+            ("d_source", json!("x"), SettingSource::DefaultValue, false),
+            ("e_same", json!(false), SettingSource::DefaultValue, false),
+        ]);
+        let right = resolved(&[
+            ("b_value", json!(300), SettingSource::DevicePolicy, true),
+            ("c_lock", json!(true), SettingSource::DevicePolicy, false),
+            (
+                "d_source",
+                json!("x"),
+                SettingSource::OrganizationDefault,
+                false,
+            ),
+            ("e_same", json!(false), SettingSource::DefaultValue, false),
+            ("f_only_right", json!(2), SettingSource::DeviceCustom, false),
+        ]);
+
+        let diff = diff_resolved_settings(&left, &right);
+        let kinds: Vec<(&str, SettingDifferenceKind)> =
+            diff.iter().map(|d| (d.key.as_str(), d.kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("a_only_left", SettingDifferenceKind::OnlyLeft),
+                ("b_value", SettingDifferenceKind::ValueDiffers),
+                ("c_lock", SettingDifferenceKind::LockDiffers),
+                ("d_source", SettingDifferenceKind::SourceDiffers),
+                ("f_only_right", SettingDifferenceKind::OnlyRight),
+            ]
+        );
+
+        assert_eq!(diff[1].left_value, Some(json!(60)));
+        assert_eq!(diff[1].right_value, Some(json!(300)));
+        assert!(diff[2].left_locked && !diff[2].right_locked);
+        assert_eq!(diff[4].left_value, None);
+        assert_eq!(diff[4].left_source, None);
+    }
+}
@@ -0,0 +1,315 @@
+//! Server-side location smoothing.
+//!
+//! Raw GPS fixes are noisy: they jitter around the true position and
+//! occasionally jump far away. This service runs incoming fixes through a
+//! simple Kalman filter (constant-position model, variance in meters²) and
+//! drops fixes that would require an implausible speed to reach.
+//!
+//! The filter is stateless between requests: callers seed it with the
+//! device's last persisted location so single uploads are smoothed too.
+//! Since rejected fixes never move the filter, a run of
+//! `max_consecutive_rejections` outliers restarts it from the latest fix;
+//! otherwise a bad seed would reject the device's fixes forever.
+
+use crate::models::privacy_zone::distance_meters;
+
+/// Setting key that enables smoothing for a device.
+pub const LOCATION_SMOOTHING_SETTING_KEY: &str = "location_smoothing_enabled";
+
+/// Tuning parameters for location smoothing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SmoothingConfig {
+    /// Expected movement noise in meters per second. Higher values trust new
+    /// fixes more and smooth less.
+    pub process_noise_mps: f64,
+    /// Fixes implying a higher speed from the previous position are outliers.
+    pub max_speed_mps: f64,
+    /// Lower bound applied to reported accuracy (meters).
+    pub min_accuracy_meters: f64,
+    /// Consecutive outliers after which the filter restarts from the latest
+    /// fix instead of rejecting it.
+    pub max_consecutive_rejections: u32,
+}
+
+impl Default for SmoothingConfig {
+    fn default() -> Self {
+        Self {
+            process_noise_mps: 3.0,
+            max_speed_mps: 70.0, // ~250 km/h
+            min_accuracy_meters: 1.0,
+            max_consecutive_rejections: 3,
+        }
+    }
+}
+
+/// A location fix fed into the smoother.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LocationSample {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Reported horizontal accuracy in meters.
+    pub accuracy: f64,
+    /// Capture time in milliseconds since epoch.
+    pub timestamp_ms: i64,
+}
+
+/// Why a fix was dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RejectReason {
+    /// Reaching the fix would require exceeding `max_speed_mps`.
+    ImplausibleSpeed,
+}
+
+/// Result of processing one fix.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SmoothingOutcome {
+    /// Fix is kept, with (possibly) adjusted coordinates and accuracy.
+    Accepted(LocationSample),
+    /// Fix is an outlier and should not be persisted.
+    Rejected(RejectReason),
+}
+
+/// Kalman-filter based location smoother.
+#[derive(Debug, Clone)]
+pub struct LocationSmoother {
+    config: SmoothingConfig,
+    state: Option<FilterState>,
+    /// Outliers rejected since the last accepted fix.
+    rejections: u32,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct FilterState {
+    latitude: f64,
+    longitude: f64,
+    /// Position variance in meters².
+    variance: f64,
+    timestamp_ms: i64,
+}
+
+impl LocationSmoother {
+    /// Create a smoother without prior state.
+    pub fn new(config: SmoothingConfig) -> Self {
+        Self {
+            config,
+            state: None,
+            rejections: 0,
+        }
+    }
+
+    /// Create a smoother seeded with the last known location of the device.
+    pub fn with_last_known(config: SmoothingConfig, last: LocationSample) -> Self {
+        let accuracy = last.accuracy.max(config.min_accuracy_meters);
+        Self {
+            config,
+            state: Some(FilterState {
+                latitude: last.latitude,
+                longitude: last.longitude,
+                variance: accuracy * accuracy,
+                timestamp_ms: last.timestamp_ms,
+            }),
+            rejections: 0,
+        }
+    }
+
+    /// Count outliers rejected after the last known location, for example
+    /// by earlier uploads, towards `max_consecutive_rejections`.
+    pub fn with_prior_rejections(mut self, rejections: u32) -> Self {
+        self.rejections = rejections;
+        self
+    }
+
+    /// Process one fix.
+    ///
+    /// Fixes older than the current filter state (late uploads) are passed
+    /// through unchanged and do not affect the state.
+    pub fn process(&mut self, sample: LocationSample) -> SmoothingOutcome {
+        let accuracy = sample.accuracy.max(self.config.min_accuracy_meters);
+
+        let Some(state) = self.state.as_mut() else {
+            self.restart(sample, accuracy);
+            return SmoothingOutcome::Accepted(sample);
+        };
+
+        if sample.timestamp_ms < state.timestamp_ms {
+            return SmoothingOutcome::Accepted(sample);
+        }
+
+        let elapsed_secs = (sample.timestamp_ms - state.timestamp_ms) as f64 / 1000.0;
+        let distance = distance_meters(
+            state.latitude,
+            state.longitude,
+            sample.latitude,
+            sample.longitude,
+        );
+
+        // Jumps within the combined uncertainty are jitter, not speed.
+        let tolerance = accuracy + state.variance.sqrt();
+        if distance > tolerance
+            && (distance - tolerance) / elapsed_secs.max(1.0) > self.config.max_speed_mps
+        {
+            self.rejections += 1;
+            if self.rejections < self.config.max_consecutive_rejections {
+                return SmoothingOutcome::Rejected(RejectReason::ImplausibleSpeed);
+            }
+            // The fixes agree with each other, not with the filter
+            self.restart(sample, accuracy);
+            return SmoothingOutcome::Accepted(sample);
+        }
+        self.rejections = 0;
+
+        // Predict: uncertainty grows with time since the last fix.
+        let process_noise = self.config.process_noise_mps;
+        state.variance += elapsed_secs * process_noise * process_noise;
+
+        // Update: blend the prediction with the measurement.
+        let measurement_variance = accuracy * accuracy;
+        let gain = state.variance / (state.variance + measurement_variance);
+        state.latitude += gain * (sample.latitude - state.latitude);
+        state.longitude += gain * (sample.longitude - state.longitude);
+        state.variance *= 1.0 - gain;
+        state.timestamp_ms = sample.timestamp_ms;
+
+        SmoothingOutcome::Accepted(LocationSample {
+            latitude: state.latitude,
+            longitude: state.longitude,
+            accuracy: state.variance.sqrt(),
+            timestamp_ms: sample.timestamp_ms,
+        })
+    }
+
+    /// Start filtering afresh from `sample`.
+    fn restart(&mut self, sample: LocationSample, accuracy: f64) {
+        self.state = Some(FilterState {
+            latitude: sample.latitude,
+            longitude: sample.longitude,
+            variance: accuracy * accuracy,
+            timestamp_ms: sample.timestamp_ms,
+        });
+        self.rejections = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(latitude: f64, longitude: f64, accuracy: f64, timestamp_ms: i64) -> LocationSample {
+        LocationSample {
+            latitude,
+            longitude,
+            accuracy,
+            timestamp_ms,
+        }
+    }
+
+    fn accepted(outcome: SmoothingOutcome) -> LocationSample {
+        match outcome {
+            SmoothingOutcome::Accepted(s) => s,
+            SmoothingOutcome::Rejected(r) => panic!("unexpected rejection: {:?}", r),
+        }
+    }
+
+    #[test]
+    fn test_first_sample_passes_through() {
+        let mut smoother = LocationSmoother::new(SmoothingConfig::default());
+        let input = sample(48.1486, 17.1077, 10.0, 1_000);
+        assert_eq!(accepted(smoother.process(input)), input);
+    }
+
+    #[test]
+    fn test_jitter_is_smoothed_towards_previous_position() {
+        let last = sample(48.1486, 17.1077, 5.0, 0);
+        let mut smoother = LocationSmoother::with_last_known(SmoothingConfig::default(), last);
+
+        // ~22 m north, poor accuracy, one second later
+        let noisy = sample(48.1488, 17.1077, 30.0, 1_000);
+        let smoothed = accepted(smoother.process(noisy));
+
+        assert!(smoothed.latitude > last.latitude);
+        assert!(smoothed.latitude < noisy.latitude);
+        assert!(smoothed.accuracy < noisy.accuracy);
+    }
+
+    #[test]
+    fn test_accurate_fix_after_long_gap_is_trusted() {
+        let last = sample(48.1486, 17.1077, 20.0, 0);
+        let mut smoother = LocationSmoother::with_last_known(SmoothingConfig::default(), last);
+
+        // 10 minutes later, ~1.1 km away, very accurate
+        let fix = sample(48.1586, 17.1077, 3.0, 600_000);
+        let smoothed = accepted(smoother.process(fix));
+
+        assert!((smoothed.latitude - fix.latitude).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_implausible_jump_is_rejected() {
+        let last = sample(48.1486, 17.1077, 5.0, 0);
+        let mut smoother = LocationSmoother::with_last_known(SmoothingConfig::default(), last);
+
+        // ~11 km in 10 seconds
+        let outlier = sample(48.2486, 17.1077, 5.0, 10_000);
+        assert_eq!(
+            smoother.process(outlier),
+            SmoothingOutcome::Rejected(RejectReason::ImplausibleSpeed)
+        );
+
+        // State is unaffected by the outlier
+        let next = sample(48.1487, 17.1077, 5.0, 20_000);
+        let smoothed = accepted(smoother.process(next));
+        assert!((smoothed.latitude - 48.1487).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_consecutive_rejections_restart_the_filter() {
+        let last = sample(48.1486, 17.1077, 5.0, 0);
+        let mut smoother = LocationSmoother::with_last_known(SmoothingConfig::default(), last);
+
+        // The device really is ~11 km away; the seed was wrong
+        for i in 1..3 {
+            let fix = sample(48.2486, 17.1077, 5.0, i * 10_000);
+            assert!(matches!(
+                smoother.process(fix),
+                SmoothingOutcome::Rejected(RejectReason::ImplausibleSpeed)
+            ));
+        }
+        let third = sample(48.2486, 17.1077, 5.0, 30_000);
+        assert_eq!(accepted(smoother.process(third)), third);
+
+        // Later fixes are smoothed around the new position
+        let next = sample(48.2487, 17.1077, 5.0, 40_000);
+        let smoothed = accepted(smoother.process(next));
+        assert!((smoothed.latitude - 48.2487).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_prior_rejections_count_towards_restart() {
+        let last = sample(48.1486, 17.1077, 5.0, 0);
+        let mut smoother = LocationSmoother::with_last_known(SmoothingConfig::default(), last)
+            .with_prior_rejections(2);
+
+        let fix = sample(48.2486, 17.1077, 5.0, 10_000);
+        assert_eq!(accepted(smoother.process(fix)), fix);
+    }
+
+    #[test]
+    fn test_out_of_order_sample_passes_through() {
+        let last = sample(48.1486, 17.1077, 5.0, 60_000);
+        let mut smoother = LocationSmoother::with_last_known(SmoothingConfig::default(), last);
+
+        let late = sample(40.0, 10.0, 50.0, 1_000);
+        assert_eq!(accepted(smoother.process(late)), late);
+    }
+
+    #[test]
+    fn test_min_accuracy_is_applied() {
+        let last = sample(48.1486, 17.1077, 0.0, 0);
+        let mut smoother = LocationSmoother::with_last_known(SmoothingConfig::default(), last);
+
+        let fix = sample(48.14861, 17.1077, 0.0, 1_000);
+        let smoothed = accepted(smoother.process(fix));
+        assert!(smoothed.accuracy.is_finite());
+        assert!(smoothed.accuracy > 0.0);
+    }
+}
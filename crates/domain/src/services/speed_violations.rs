@@ -0,0 +1,276 @@
+//! Speed-limit violation detection.
+//!
+//! Map matching can annotate the matched road geometry with posted speed
+//! limits (OpenStreetMap `maxspeed`). Each recorded location that reports a
+//! speed is attributed to the nearest road segment with a known limit. Runs of
+//! consecutive locations faster than that limit plus a tolerance become one
+//! violation; a run ends when the device slows down, leaves the annotated
+//! road or the limit changes.
+
+use chrono::{DateTime, Utc};
+
+/// Tuning parameters for speed violation detection.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpeedViolationConfig {
+    /// Speed above the limit that is still tolerated.
+    pub tolerance_kmh: f64,
+    /// Minimum time between the first and last location of a violation.
+    /// Shorter runs, including single locations, are treated as GPS noise.
+    pub min_duration_secs: i64,
+    /// Locations farther than this from every annotated segment are ignored.
+    pub max_match_distance_meters: f64,
+}
+
+impl Default for SpeedViolationConfig {
+    fn default() -> Self {
+        Self {
+            tolerance_kmh: 5.0,
+            min_duration_secs: 5,
+            max_match_distance_meters: 50.0,
+        }
+    }
+}
+
+/// A recorded location with its speed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpeedSample {
+    pub captured_at: DateTime<Utc>,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub speed_kmh: f64,
+}
+
+/// A matched road segment with its posted speed limit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpeedLimitSegment {
+    pub start_latitude: f64,
+    pub start_longitude: f64,
+    pub end_latitude: f64,
+    pub end_longitude: f64,
+    pub limit_kmh: f64,
+}
+
+/// A stretch of a trip driven above the speed limit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetectedSpeedViolation {
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    pub start_latitude: f64,
+    pub start_longitude: f64,
+    pub end_latitude: f64,
+    pub end_longitude: f64,
+    pub speed_limit_kmh: f64,
+    pub max_speed_kmh: f64,
+    pub avg_speed_kmh: f64,
+    pub sample_count: usize,
+}
+
+/// Find the stretches of a trip driven above the speed limit.
+///
+/// Samples are processed in time order. Returns violations oldest first.
+pub fn detect_speed_violations(
+    samples: &[SpeedSample],
+    segments: &[SpeedLimitSegment],
+    config: &SpeedViolationConfig,
+) -> Vec<DetectedSpeedViolation> {
+    let mut samples: Vec<&SpeedSample> = samples.iter().collect();
+    samples.sort_by_key(|s| s.captured_at);
+
+    let mut violations = Vec::new();
+    let mut run: Vec<&SpeedSample> = Vec::new();
+    let mut run_limit = 0.0;
+    for sample in samples {
+        let limit = nearest_limit(sample, segments, config.max_match_distance_meters);
+        match limit {
+            Some(limit) if sample.speed_kmh > limit + config.tolerance_kmh => {
+                if !run.is_empty() && limit != run_limit {
+                    violations.extend(close_run(&run, run_limit, config));
+                    run.clear();
+                }
+                run_limit = limit;
+                run.push(sample);
+            }
+            _ => {
+                violations.extend(close_run(&run, run_limit, config));
+                run.clear();
+            }
+        }
+    }
+    violations.extend(close_run(&run, run_limit, config));
+    violations
+}
+
+/// Turn a run of speeding samples into a violation if it lasted long enough.
+fn close_run(
+    run: &[&SpeedSample],
+    limit_kmh: f64,
+    config: &SpeedViolationConfig,
+) -> Option<DetectedSpeedViolation> {
+    let (first, last) = (run.first()?, run.last()?);
+    if (last.captured_at - first.captured_at).num_seconds() < config.min_duration_secs {
+        return None;
+    }
+
+    let max_speed_kmh = run.iter().map(|s| s.speed_kmh).fold(f64::MIN, f64::max);
+    let avg_speed_kmh = run.iter().map(|s| s.speed_kmh).sum::<f64>() / run.len() as f64;
+    Some(DetectedSpeedViolation {
+        started_at: first.captured_at,
+        ended_at: last.captured_at,
+        start_latitude: first.latitude,
+        start_longitude: first.longitude,
+        end_latitude: last.latitude,
+        end_longitude: last.longitude,
+        speed_limit_kmh: limit_kmh,
+        max_speed_kmh,
+        avg_speed_kmh,
+        sample_count: run.len(),
+    })
+}
+
+/// Speed limit of the segment nearest to the sample, if within range.
+fn nearest_limit(
+    sample: &SpeedSample,
+    segments: &[SpeedLimitSegment],
+    max_distance_meters: f64,
+) -> Option<f64> {
+    segments
+        .iter()
+        .map(|segment| {
+            (
+                distance_to_segment_meters(sample, segment),
+                segment.limit_kmh,
+            )
+        })
+        .filter(|(distance, _)| *distance <= max_distance_meters)
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, limit)| limit)
+}
+
+/// Distance from a sample to a segment, using a local flat projection
+/// centered on the sample (accurate for the short distances involved).
+fn distance_to_segment_meters(sample: &SpeedSample, segment: &SpeedLimitSegment) -> f64 {
+    const METERS_PER_DEGREE: f64 = 111_320.0;
+    let lon_scale = sample.latitude.to_radians().cos();
+    let project = |lat: f64, lon: f64| {
+        (
+            (lon - sample.longitude) * METERS_PER_DEGREE * lon_scale,
+            (lat - sample.latitude) * METERS_PER_DEGREE,
+        )
+    };
+
+    let (ax, ay) = project(segment.start_latitude, segment.start_longitude);
+    let (bx, by) = project(segment.end_latitude, segment.end_longitude);
+    let (dx, dy) = (bx - ax, by - ay);
+    let length_sq = dx * dx + dy * dy;
+    let t = if length_sq == 0.0 {
+        0.0
+    } else {
+        (-(ax * dx + ay * dy) / length_sq).clamp(0.0, 1.0)
+    };
+    let (px, py) = (ax + t * dx, ay + t * dy);
+    (px * px + py * py).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A straight east-west road at latitude 48.0 with a 50 km/h limit.
+    fn road() -> Vec<SpeedLimitSegment> {
+        vec![
+            SpeedLimitSegment {
+                start_latitude: 48.0,
+                start_longitude: 17.0,
+                end_latitude: 48.0,
+                end_longitude: 17.01,
+                limit_kmh: 50.0,
+            },
+            SpeedLimitSegment {
+                start_latitude: 48.0,
+                start_longitude: 17.01,
+                end_latitude: 48.0,
+                end_longitude: 17.02,
+                limit_kmh: 30.0,
+            },
+        ]
+    }
+
+    fn sample(secs: i64, longitude: f64, speed_kmh: f64) -> SpeedSample {
+        SpeedSample {
+            captured_at: DateTime::from_timestamp(1_700_000_000 + secs, 0).unwrap(),
+            latitude: 48.0001,
+            longitude,
+            speed_kmh,
+        }
+    }
+
+    #[test]
+    fn test_detects_run_above_limit() {
+        let samples = [
+            sample(0, 17.001, 45.0),
+            sample(5, 17.002, 70.0),
+            sample(10, 17.003, 80.0),
+            sample(15, 17.004, 60.0),
+            sample(20, 17.005, 50.0),
+        ];
+        let violations = detect_speed_violations(&samples, &road(), &Default::default());
+
+        assert_eq!(violations.len(), 1);
+        let v = &violations[0];
+        assert_eq!(v.started_at, samples[1].captured_at);
+        assert_eq!(v.ended_at, samples[3].captured_at);
+        assert_eq!(v.speed_limit_kmh, 50.0);
+        assert_eq!(v.max_speed_kmh, 80.0);
+        assert!((v.avg_speed_kmh - 70.0).abs() < 1e-9);
+        assert_eq!(v.sample_count, 3);
+    }
+
+    #[test]
+    fn test_tolerance_and_short_spikes_ignored() {
+        let samples = [
+            // Within the 5 km/h tolerance
+            sample(0, 17.001, 54.0),
+            sample(5, 17.002, 55.0),
+            // Single-sample spike
+            sample(10, 17.003, 120.0),
+            sample(15, 17.004, 40.0),
+        ];
+        assert!(detect_speed_violations(&samples, &road(), &Default::default()).is_empty());
+    }
+
+    #[test]
+    fn test_limit_change_splits_violation() {
+        let samples = [
+            sample(0, 17.006, 70.0),
+            sample(10, 17.008, 70.0),
+            sample(20, 17.012, 70.0),
+            sample(30, 17.014, 70.0),
+        ];
+        let violations = detect_speed_violations(&samples, &road(), &Default::default());
+
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].speed_limit_kmh, 50.0);
+        assert_eq!(violations[1].speed_limit_kmh, 30.0);
+    }
+
+    #[test]
+    fn test_samples_far_from_road_ignored() {
+        let mut far = sample(0, 17.005, 90.0);
+        far.latitude = 48.01;
+        let mut far2 = sample(10, 17.006, 90.0);
+        far2.latitude = 48.01;
+        assert!(detect_speed_violations(&[far, far2], &road(), &Default::default()).is_empty());
+        assert!(detect_speed_violations(&[far, far2], &[], &Default::default()).is_empty());
+    }
+
+    #[test]
+    fn test_distance_to_segment() {
+        let segment = road()[0];
+        // ~11 m north of the road
+        let d = distance_to_segment_meters(&sample(0, 17.005, 0.0), &segment);
+        assert!((d - 11.1).abs() < 0.5);
+        // Beyond the segment end: distance to the end point
+        let d = distance_to_segment_meters(&sample(0, 17.03, 0.0), &segment);
+        assert!(d > 1400.0);
+    }
+}
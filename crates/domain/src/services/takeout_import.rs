@@ -0,0 +1,229 @@
+//! Google Takeout location history parsing.
+//!
+//! Takeout exports a device's history as `Records.json`, a single object
+//! with a `locations` array. Coordinates are stored as integers scaled by
+//! 1e7 and timestamps come either as RFC 3339 strings (`timestamp`) or, in
+//! older exports, as millisecond strings (`timestampMs`).
+//!
+//! Records are validated, sorted by capture time and de-duplicated before
+//! being written; duplicates against already stored history are dropped by
+//! the repository.
+
+use chrono::{DateTime, TimeZone, Utc};
+use serde::Deserialize;
+
+/// Offset used to undo the signed 32-bit overflow found in some exports.
+const E7_OVERFLOW: i64 = 1 << 32;
+
+/// Top-level structure of `Records.json`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TakeoutRecords {
+    #[serde(default)]
+    pub locations: Vec<TakeoutLocation>,
+}
+
+/// A single record of a Takeout export. Unknown fields are ignored.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TakeoutLocation {
+    #[serde(rename = "latitudeE7")]
+    pub latitude_e7: Option<i64>,
+    #[serde(rename = "longitudeE7")]
+    pub longitude_e7: Option<i64>,
+    /// Horizontal accuracy in meters.
+    pub accuracy: Option<f64>,
+    /// Altitude in meters.
+    pub altitude: Option<f64>,
+    /// Speed in meters per second.
+    pub velocity: Option<f64>,
+    /// Bearing in degrees.
+    pub heading: Option<f64>,
+    /// RFC 3339 timestamp (current exports).
+    pub timestamp: Option<String>,
+    /// Milliseconds since the epoch as a string (older exports).
+    pub timestamp_ms: Option<String>,
+}
+
+/// A validated Takeout record ready to be stored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedLocation {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub accuracy: f64,
+    pub altitude: Option<f64>,
+    pub speed: Option<f64>,
+    pub bearing: Option<f64>,
+    pub captured_at: DateTime<Utc>,
+}
+
+impl TakeoutLocation {
+    /// Convert the record, or `None` if it is incomplete or out of range.
+    pub fn to_imported(&self) -> Option<ImportedLocation> {
+        let latitude = decode_e7(self.latitude_e7?, 900_000_000);
+        let longitude = decode_e7(self.longitude_e7?, 1_800_000_000);
+        let accuracy = self.accuracy?;
+        let captured_at = self.captured_at()?;
+
+        if !(-90.0..=90.0).contains(&latitude)
+            || !(-180.0..=180.0).contains(&longitude)
+            || !accuracy.is_finite()
+            || accuracy < 0.0
+        {
+            return None;
+        }
+
+        Some(ImportedLocation {
+            latitude,
+            longitude,
+            accuracy,
+            altitude: self.altitude.filter(|a| a.is_finite()),
+            speed: self.velocity.filter(|v| v.is_finite() && *v >= 0.0),
+            bearing: self
+                .heading
+                .filter(|h| h.is_finite() && (0.0..=360.0).contains(h)),
+            captured_at,
+        })
+    }
+
+    fn captured_at(&self) -> Option<DateTime<Utc>> {
+        if let Some(ts) = &self.timestamp {
+            return DateTime::parse_from_rfc3339(ts)
+                .ok()
+                .map(|dt| dt.with_timezone(&Utc));
+        }
+        let ms = self.timestamp_ms.as_deref()?.parse::<i64>().ok()?;
+        Utc.timestamp_millis_opt(ms).single()
+    }
+}
+
+/// Decode an E7 coordinate, correcting values that overflowed a signed
+/// 32-bit integer.
+fn decode_e7(value: i64, max: i64) -> f64 {
+    let value = if value > max {
+        value - E7_OVERFLOW
+    } else {
+        value
+    };
+    value as f64 / 1e7
+}
+
+/// Records of an export after validation.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PreparedImport {
+    /// Valid records ordered by capture time, one per timestamp.
+    pub locations: Vec<ImportedLocation>,
+    /// Records that were incomplete or out of range.
+    pub invalid: usize,
+    /// Records sharing a timestamp with an earlier record in the export.
+    pub duplicates: usize,
+}
+
+/// Validate, sort and de-duplicate the records of an export.
+pub fn prepare_takeout_records(records: &TakeoutRecords) -> PreparedImport {
+    let mut locations: Vec<ImportedLocation> = records
+        .locations
+        .iter()
+        .filter_map(TakeoutLocation::to_imported)
+        .collect();
+    let invalid = records.locations.len() - locations.len();
+
+    locations.sort_by_key(|loc| loc.captured_at);
+    let valid = locations.len();
+    locations.dedup_by_key(|loc| loc.captured_at);
+
+    PreparedImport {
+        duplicates: valid - locations.len(),
+        locations,
+        invalid,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: &str) -> TakeoutRecords {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_parses_current_format() {
+        let records = parse(
+            r#"{"locations": [{
+                "latitudeE7": 481486000, "longitudeE7": 171077000, "accuracy": 12,
+                "altitude": 140, "velocity": 3, "heading": 90, "source": "WIFI",
+                "timestamp": "2023-05-01T10:00:00.123Z"
+            }]}"#,
+        );
+        let loc = records.locations[0].to_imported().unwrap();
+        assert!((loc.latitude - 48.1486).abs() < 1e-9);
+        assert!((loc.longitude - 17.1077).abs() < 1e-9);
+        assert_eq!(loc.accuracy, 12.0);
+        assert_eq!(loc.speed, Some(3.0));
+        assert_eq!(loc.bearing, Some(90.0));
+        assert_eq!(loc.captured_at.timestamp_millis(), 1_682_935_200_123);
+    }
+
+    #[test]
+    fn test_parses_legacy_timestamp_ms() {
+        let records = parse(
+            r#"{"locations": [{
+                "latitudeE7": 481486000, "longitudeE7": 171077000, "accuracy": 20,
+                "timestampMs": "1500000000000"
+            }]}"#,
+        );
+        let loc = records.locations[0].to_imported().unwrap();
+        assert_eq!(loc.captured_at.timestamp_millis(), 1_500_000_000_000);
+        assert_eq!(loc.altitude, None);
+    }
+
+    #[test]
+    fn test_corrects_overflowed_coordinates() {
+        assert!((decode_e7(4_294_967_296 - 338_000_000, 900_000_000) + 33.8).abs() < 1e-9);
+        assert!((decode_e7(-338_000_000, 900_000_000) + 33.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rejects_incomplete_records() {
+        let records = parse(
+            r#"{"locations": [
+                {"latitudeE7": 481486000, "longitudeE7": 171077000, "timestampMs": "1"},
+                {"latitudeE7": 481486000, "accuracy": 5, "timestampMs": "1"},
+                {"latitudeE7": 481486000, "longitudeE7": 171077000, "accuracy": 5},
+                {"latitudeE7": 481486000, "longitudeE7": 171077000, "accuracy": 5,
+                 "timestamp": "yesterday"}
+            ]}"#,
+        );
+        assert!(records.locations.iter().all(|l| l.to_imported().is_none()));
+    }
+
+    #[test]
+    fn test_prepare_sorts_and_deduplicates() {
+        let records = parse(
+            r#"{"locations": [
+                {"latitudeE7": 481486000, "longitudeE7": 171077000, "accuracy": 5,
+                 "timestampMs": "3000"},
+                {"latitudeE7": 481486000, "longitudeE7": 171077000, "accuracy": 5,
+                 "timestampMs": "1000"},
+                {"latitudeE7": 481487000, "longitudeE7": 171077000, "accuracy": 8,
+                 "timestampMs": "1000"},
+                {"latitudeE7": 481486000, "longitudeE7": 171077000, "timestampMs": "2000"}
+            ]}"#,
+        );
+        let prepared = prepare_takeout_records(&records);
+        assert_eq!(prepared.invalid, 1);
+        assert_eq!(prepared.duplicates, 1);
+        let times: Vec<i64> = prepared
+            .locations
+            .iter()
+            .map(|l| l.captured_at.timestamp_millis())
+            .collect();
+        assert_eq!(times, vec![1000, 3000]);
+    }
+
+    #[test]
+    fn test_missing_locations_array() {
+        let prepared = prepare_takeout_records(&parse("{}"));
+        assert_eq!(prepared, PreparedImport::default());
+    }
+}
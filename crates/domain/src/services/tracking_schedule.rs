@@ -0,0 +1,10 @@
+//! Per-device tracking schedules.
+//!
+//! A schedule is stored in the device's `tracking_schedule` setting as a
+//! [`WeeklySchedule`](crate::models::WeeklySchedule) and reaches the device
+//! through settings sync. The server ignores locations captured outside
+//! every window, so tracking is off outside the schedule even if the device
+//! keeps reporting.
+
+/// Setting key holding a device's tracking schedule (JSON, `null` when unset).
+pub const TRACKING_SCHEDULE_SETTING_KEY: &str = "tracking_schedule";
@@ -0,0 +1,200 @@
+//! Trip classification by rules.
+//!
+//! Labels a completed trip with the purpose of the first matching rule. A
+//! rule matches when every condition it sets holds: the trip starts in its
+//! start area, ends in its end area, and departs on one of its weekdays and
+//! within its departure window (UTC).
+
+use chrono::{DateTime, Datelike, Timelike, Weekday};
+
+use crate::models::privacy_zone::distance_meters;
+use crate::models::trip::Trip;
+
+/// A circular area, such as a geofence.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CircleArea {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub radius_meters: f64,
+}
+
+impl CircleArea {
+    fn contains(&self, latitude: f64, longitude: f64) -> bool {
+        distance_meters(self.latitude, self.longitude, latitude, longitude) <= self.radius_meters
+    }
+}
+
+/// A classification rule reduced to its conditions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassificationRule {
+    pub purpose: String,
+    pub start_area: Option<CircleArea>,
+    pub end_area: Option<CircleArea>,
+    /// Empty means any day.
+    pub weekdays: Vec<Weekday>,
+    /// Departure window in minutes after midnight, start and end inclusive.
+    /// Wraps past midnight if the start is later than the end.
+    pub departure_window: Option<(u32, u32)>,
+}
+
+impl ClassificationRule {
+    /// Whether a completed trip meets all conditions of the rule.
+    pub fn matches(&self, trip: &Trip) -> bool {
+        let Some(departed) = DateTime::from_timestamp_millis(trip.start_timestamp) else {
+            return false;
+        };
+        if let Some(area) = self.start_area {
+            if !area.contains(trip.start_latitude, trip.start_longitude) {
+                return false;
+            }
+        }
+        if let Some(area) = self.end_area {
+            let ends_inside = trip
+                .end_latitude
+                .zip(trip.end_longitude)
+                .is_some_and(|(lat, lon)| area.contains(lat, lon));
+            if !ends_inside {
+                return false;
+            }
+        }
+        if !self.weekdays.is_empty() && !self.weekdays.contains(&departed.weekday()) {
+            return false;
+        }
+        if let Some((after, before)) = self.departure_window {
+            let minute = departed.hour() * 60 + departed.minute();
+            let inside = if after <= before {
+                (after..=before).contains(&minute)
+            } else {
+                minute >= after || minute <= before
+            };
+            if !inside {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Purpose of the first rule, in order, that matches the trip.
+pub fn classify_trip<'a>(trip: &Trip, rules: &'a [ClassificationRule]) -> Option<&'a str> {
+    rules
+        .iter()
+        .find(|rule| rule.matches(trip))
+        .map(|rule| rule.purpose.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::movement_event::{DetectionSource, TransportationMode};
+    use crate::models::trip::TripState;
+    use chrono::{TimeZone, Utc};
+    use uuid::Uuid;
+
+    const HOME: (f64, f64) = (48.1486, 17.1077);
+    const OFFICE: (f64, f64) = (48.1700, 17.0600);
+
+    /// A completed trip departing 2026-03-<day> (2 = Monday) at hour:minute UTC.
+    fn trip(day: u32, hour: u32, minute: u32, from: (f64, f64), to: (f64, f64)) -> Trip {
+        let start = Utc.with_ymd_and_hms(2026, 3, day, hour, minute, 0).unwrap();
+        Trip {
+            id: Uuid::new_v4(),
+            device_id: Uuid::nil(),
+            local_trip_id: "trip".to_string(),
+            state: TripState::Completed,
+            start_timestamp: start.timestamp_millis(),
+            end_timestamp: Some(start.timestamp_millis() + 20 * 60 * 1000),
+            start_latitude: from.0,
+            start_longitude: from.1,
+            end_latitude: Some(to.0),
+            end_longitude: Some(to.1),
+            transportation_mode: TransportationMode::InVehicle,
+            detection_source: DetectionSource::ActivityRecognition,
+            distance_meters: None,
+            duration_seconds: None,
+            purpose: None,
+            purpose_source: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn area(place: (f64, f64)) -> Option<CircleArea> {
+        Some(CircleArea {
+            latitude: place.0,
+            longitude: place.1,
+            radius_meters: 200.0,
+        })
+    }
+
+    fn rule(purpose: &str) -> ClassificationRule {
+        ClassificationRule {
+            purpose: purpose.to_string(),
+            start_area: None,
+            end_area: None,
+            weekdays: Vec::new(),
+            departure_window: None,
+        }
+    }
+
+    #[test]
+    fn test_geofence_endpoint_rule() {
+        let to_office = ClassificationRule {
+            end_area: area(OFFICE),
+            ..rule("business")
+        };
+        assert!(to_office.matches(&trip(2, 8, 0, HOME, OFFICE)));
+        assert!(!to_office.matches(&trip(2, 17, 0, OFFICE, HOME)));
+
+        let from_office = ClassificationRule {
+            start_area: area(OFFICE),
+            ..rule("business")
+        };
+        assert!(from_office.matches(&trip(2, 17, 0, OFFICE, HOME)));
+    }
+
+    #[test]
+    fn test_schedule_rule() {
+        let weekday_mornings = ClassificationRule {
+            weekdays: vec![Weekday::Mon, Weekday::Tue],
+            departure_window: Some((6 * 60, 10 * 60)),
+            ..rule("business")
+        };
+        assert!(weekday_mornings.matches(&trip(2, 7, 30, HOME, OFFICE)));
+        assert!(!weekday_mornings.matches(&trip(2, 11, 0, HOME, OFFICE)));
+        // Saturday
+        assert!(!weekday_mornings.matches(&trip(7, 7, 30, HOME, OFFICE)));
+
+        let nights = ClassificationRule {
+            departure_window: Some((22 * 60, 2 * 60)),
+            ..rule("personal")
+        };
+        assert!(nights.matches(&trip(2, 23, 30, HOME, OFFICE)));
+        assert!(nights.matches(&trip(2, 1, 0, HOME, OFFICE)));
+        assert!(!nights.matches(&trip(2, 12, 0, HOME, OFFICE)));
+    }
+
+    #[test]
+    fn test_first_matching_rule_wins() {
+        let rules = vec![
+            ClassificationRule {
+                end_area: area(OFFICE),
+                ..rule("business")
+            },
+            ClassificationRule {
+                weekdays: vec![Weekday::Sat, Weekday::Sun],
+                ..rule("personal")
+            },
+        ];
+        // Saturday trip to the office
+        assert_eq!(
+            classify_trip(&trip(7, 9, 0, HOME, OFFICE), &rules),
+            Some("business")
+        );
+        assert_eq!(
+            classify_trip(&trip(7, 9, 0, OFFICE, HOME), &rules),
+            Some("personal")
+        );
+        assert_eq!(classify_trip(&trip(2, 9, 0, OFFICE, HOME), &rules), None);
+    }
+}
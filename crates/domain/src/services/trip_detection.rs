@@ -0,0 +1,323 @@
+//! Server-side trip detection.
+//!
+//! Segments the location stream into trips for devices that do not detect
+//! trips themselves. A trip runs from a departure to the next arrival as
+//! found by [`MovementDetector`]: it starts once a fix leaves the place the
+//! device was resting at and ends where fixes stay within a small radius
+//! for the dwell period. Trips shorter than
+//! [`TripDetectionConfig::min_distance_meters`] are dropped, so walking
+//! around a building or GPS drift does not show up as a trip.
+//!
+//! Like movement detection, the segmenter is stateless between runs:
+//! callers resume it from the device's last derived trip and replay newer
+//! locations through it.
+
+use crate::models::movement_event::{MovementState, TransportationMode};
+use crate::models::privacy_zone::distance_meters;
+use crate::services::movement_detection::{
+    mode_for_speed, MovementDetectionConfig, MovementDetector, MovementFix, MovementTransition,
+};
+
+/// Setting key that enables server-side trip detection for a device.
+pub const TRIP_DETECTION_SETTING_KEY: &str = "server_trip_detection_enabled";
+
+/// Prefix of the local trip ID of derived trips.
+pub const DERIVED_TRIP_ID_PREFIX: &str = "server-";
+
+/// Tuning parameters for trip detection.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TripDetectionConfig {
+    /// Start and stop detection.
+    pub movement: MovementDetectionConfig,
+    /// Shorter trips are dropped.
+    pub min_distance_meters: f64,
+}
+
+impl Default for TripDetectionConfig {
+    fn default() -> Self {
+        Self {
+            movement: MovementDetectionConfig::default(),
+            min_distance_meters: 250.0,
+        }
+    }
+}
+
+/// A trip found in the location stream.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DetectedTrip {
+    pub start_latitude: f64,
+    pub start_longitude: f64,
+    pub start_timestamp_ms: i64,
+    /// Arrival point of a completed trip; latest fix of an ongoing one.
+    pub end_latitude: f64,
+    pub end_longitude: f64,
+    pub end_timestamp_ms: i64,
+    /// Path length in meters.
+    pub distance_meters: f64,
+    /// Estimated from the average speed of completed trips.
+    pub transportation_mode: TransportationMode,
+}
+
+impl DetectedTrip {
+    /// Local trip ID the trip is stored under; stable across runs.
+    pub fn local_trip_id(&self) -> String {
+        format!("{}{}", DERIVED_TRIP_ID_PREFIX, self.start_timestamp_ms)
+    }
+
+    pub fn duration_seconds(&self) -> i64 {
+        (self.end_timestamp_ms - self.start_timestamp_ms) / 1000
+    }
+}
+
+/// Segments a chronologically ordered fix stream into trips.
+#[derive(Debug, Clone)]
+pub struct TripSegmenter {
+    config: TripDetectionConfig,
+    detector: MovementDetector,
+    trip: Option<DetectedTrip>,
+    /// Capture time and path length so far of each fix of the ongoing trip.
+    path: Vec<(i64, f64)>,
+    last_fix: Option<MovementFix>,
+}
+
+impl TripSegmenter {
+    /// Create a segmenter without prior state.
+    ///
+    /// No trip is reported until the device has been seen resting once.
+    pub fn new(config: TripDetectionConfig) -> Self {
+        Self {
+            config,
+            detector: MovementDetector::new(config.movement),
+            trip: None,
+            path: Vec::new(),
+            last_fix: None,
+        }
+    }
+
+    /// Create a segmenter resuming after a completed trip, with the device
+    /// resting at its end.
+    pub fn resume_after(config: TripDetectionConfig, trip: &DetectedTrip) -> Self {
+        let arrival = MovementTransition {
+            state: MovementState::Arrived,
+            latitude: trip.end_latitude,
+            longitude: trip.end_longitude,
+            accuracy: 0.0,
+            speed: None,
+            timestamp_ms: trip.end_timestamp_ms,
+            transportation_mode: TransportationMode::Stationary,
+        };
+        Self {
+            config,
+            detector: MovementDetector::resume(config.movement, arrival),
+            trip: None,
+            path: Vec::new(),
+            last_fix: None,
+        }
+    }
+
+    /// Create a segmenter resuming an ongoing trip. Locations from the
+    /// trip's start on must be replayed to rebuild its path.
+    pub fn resume_trip(config: TripDetectionConfig, trip: &DetectedTrip) -> Self {
+        let departure = MovementTransition {
+            state: MovementState::Departed,
+            latitude: trip.start_latitude,
+            longitude: trip.start_longitude,
+            accuracy: 0.0,
+            speed: None,
+            timestamp_ms: trip.start_timestamp_ms,
+            transportation_mode: trip.transportation_mode,
+        };
+        Self {
+            config,
+            detector: MovementDetector::resume(config.movement, departure),
+            trip: Some(DetectedTrip {
+                end_latitude: trip.start_latitude,
+                end_longitude: trip.start_longitude,
+                end_timestamp_ms: trip.start_timestamp_ms,
+                distance_meters: 0.0,
+                ..*trip
+            }),
+            path: vec![(trip.start_timestamp_ms, 0.0)],
+            last_fix: None,
+        }
+    }
+
+    /// Process one fix, returning a trip if the fix completes one.
+    ///
+    /// Fixes older than the previous one are ignored.
+    pub fn process(&mut self, fix: MovementFix) -> Option<DetectedTrip> {
+        if self
+            .last_fix
+            .is_some_and(|last| fix.timestamp_ms < last.timestamp_ms)
+        {
+            return None;
+        }
+        let previous = self.last_fix.replace(fix);
+
+        if let Some(trip) = self.trip.as_mut() {
+            let step = previous.map_or(0.0, |p| {
+                distance_meters(p.latitude, p.longitude, fix.latitude, fix.longitude)
+            });
+            trip.distance_meters += step;
+            trip.end_latitude = fix.latitude;
+            trip.end_longitude = fix.longitude;
+            trip.end_timestamp_ms = fix.timestamp_ms;
+            self.path.push((fix.timestamp_ms, trip.distance_meters));
+        }
+
+        let transition = self.detector.process(fix)?;
+        match transition.state {
+            MovementState::Departed => {
+                self.trip = Some(DetectedTrip {
+                    start_latitude: transition.latitude,
+                    start_longitude: transition.longitude,
+                    start_timestamp_ms: transition.timestamp_ms,
+                    end_latitude: transition.latitude,
+                    end_longitude: transition.longitude,
+                    end_timestamp_ms: transition.timestamp_ms,
+                    distance_meters: 0.0,
+                    transportation_mode: transition.transportation_mode,
+                });
+                self.path = vec![(transition.timestamp_ms, 0.0)];
+                None
+            }
+            MovementState::Arrived => {
+                let mut trip = self.trip.take()?;
+                // The trip ends at the first fix of the stay, not at the
+                // fix that completed the dwell period.
+                let arrived = self
+                    .path
+                    .partition_point(|&(ts, _)| ts <= transition.timestamp_ms);
+                trip.distance_meters = self.path[..arrived].last().map_or(0.0, |&(_, d)| d);
+                trip.end_latitude = transition.latitude;
+                trip.end_longitude = transition.longitude;
+                trip.end_timestamp_ms = transition.timestamp_ms;
+                self.path.clear();
+
+                let duration_secs = trip.duration_seconds();
+                if duration_secs > 0 {
+                    trip.transportation_mode =
+                        mode_for_speed(Some(trip.distance_meters / duration_secs as f64));
+                }
+                (trip.distance_meters >= self.config.min_distance_meters).then_some(trip)
+            }
+        }
+    }
+
+    /// The ongoing trip, once it is long enough to be reported.
+    pub fn ongoing(&self) -> Option<&DetectedTrip> {
+        self.trip
+            .as_ref()
+            .filter(|t| t.distance_meters >= self.config.min_distance_meters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINUTE: i64 = 60 * 1000;
+
+    fn fix(latitude: f64, timestamp_ms: i64) -> MovementFix {
+        MovementFix {
+            latitude,
+            longitude: 17.1077,
+            accuracy: 10.0,
+            speed: None,
+            timestamp_ms,
+        }
+    }
+
+    fn run(segmenter: &mut TripSegmenter, fixes: &[MovementFix]) -> Vec<DetectedTrip> {
+        fixes.iter().filter_map(|f| segmenter.process(*f)).collect()
+    }
+
+    /// Rest, drive ~5.5 km north in five minutes, rest again.
+    fn commute() -> Vec<MovementFix> {
+        let mut fixes = vec![fix(48.10, 0), fix(48.10, 6 * MINUTE)];
+        fixes.extend((1..=5).map(|i| fix(48.10 + i as f64 * 0.01, (6 + i) * MINUTE)));
+        fixes.extend((1..=4).map(|i| fix(48.15, (11 + 2 * i) * MINUTE)));
+        fixes
+    }
+
+    #[test]
+    fn test_trip_between_stays() {
+        let mut segmenter = TripSegmenter::new(TripDetectionConfig::default());
+        let trips = run(&mut segmenter, &commute());
+
+        assert_eq!(trips.len(), 1);
+        let trip = trips[0];
+        assert_eq!(trip.start_timestamp_ms, 7 * MINUTE);
+        assert_eq!(trip.end_timestamp_ms, 11 * MINUTE);
+        assert!((trip.end_latitude - 48.15).abs() < 1e-9);
+        // 0.04° of latitude from the first fix outside the stay
+        assert!((trip.distance_meters - 4448.0).abs() < 10.0);
+        assert_eq!(trip.transportation_mode, TransportationMode::InVehicle);
+        assert_eq!(trip.local_trip_id(), format!("server-{}", 7 * MINUTE));
+        assert!(segmenter.ongoing().is_none());
+    }
+
+    #[test]
+    fn test_ongoing_trip() {
+        let mut segmenter = TripSegmenter::new(TripDetectionConfig::default());
+        let trips = run(&mut segmenter, &commute()[..5]);
+        assert!(trips.is_empty());
+
+        let ongoing = segmenter.ongoing().expect("ongoing trip");
+        assert_eq!(ongoing.start_timestamp_ms, 7 * MINUTE);
+        assert_eq!(ongoing.end_timestamp_ms, 9 * MINUTE);
+    }
+
+    #[test]
+    fn test_short_trip_is_dropped() {
+        let mut segmenter = TripSegmenter::new(TripDetectionConfig::default());
+        // ~150 m across a campus and back to rest
+        let trips = run(
+            &mut segmenter,
+            &[
+                fix(48.1000, 0),
+                fix(48.1000, 6 * MINUTE),
+                fix(48.1014, 7 * MINUTE),
+                fix(48.1014, 9 * MINUTE),
+                fix(48.1014, 13 * MINUTE),
+            ],
+        );
+        assert!(trips.is_empty());
+        assert!(segmenter.ongoing().is_none());
+    }
+
+    #[test]
+    fn test_resume_trip_completes_it() {
+        let fixes = commute();
+        let mut first = TripSegmenter::new(TripDetectionConfig::default());
+        run(&mut first, &fixes[..5]);
+        let ongoing = *first.ongoing().unwrap();
+
+        // A later run replays from the trip's start.
+        let mut resumed = TripSegmenter::resume_trip(TripDetectionConfig::default(), &ongoing);
+        let replay: Vec<_> = fixes
+            .iter()
+            .copied()
+            .filter(|f| f.timestamp_ms >= ongoing.start_timestamp_ms)
+            .collect();
+        let trips = run(&mut resumed, &replay);
+
+        let mut whole = TripSegmenter::new(TripDetectionConfig::default());
+        assert_eq!(trips, run(&mut whole, &fixes));
+    }
+
+    #[test]
+    fn test_resume_after_trip_starts_next_one() {
+        let mut segmenter = TripSegmenter::new(TripDetectionConfig::default());
+        let trip = run(&mut segmenter, &commute())[0];
+
+        let mut resumed = TripSegmenter::resume_after(TripDetectionConfig::default(), &trip);
+        let start = 30 * MINUTE;
+        assert!(resumed.process(fix(48.15, start)).is_none());
+        for i in 1..=3 {
+            resumed.process(fix(48.15 - i as f64 * 0.01, start + i * MINUTE));
+        }
+        let ongoing = resumed.ongoing().expect("next trip");
+        assert_eq!(ongoing.start_timestamp_ms, start + MINUTE);
+    }
+}
@@ -0,0 +1,183 @@
+//! Trip replay resampling.
+//!
+//! Devices report locations at irregular intervals. For playback, the track
+//! is resampled at a fixed interval by linear interpolation between the
+//! recorded points that bracket each frame time. Each frame carries the
+//! bearing and speed of the segment it lies on, so clients can animate a
+//! marker without interpolating themselves.
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::models::privacy_zone::distance_meters;
+use crate::models::trip::ReplayFrame;
+
+/// Segments shorter than this keep the previous bearing, since GPS jitter
+/// while stationary would otherwise spin the marker.
+const MIN_BEARING_DISTANCE_METERS: f64 = 1.0;
+
+/// A recorded location of a trip.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReplayPoint {
+    pub captured_at: DateTime<Utc>,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+/// Resample a track at a fixed interval.
+///
+/// Frames start at the first recorded point and are `interval` apart; the
+/// last frame is always at the last recorded point, so it may be closer to
+/// its predecessor. Points are ordered by time and points sharing a
+/// timestamp are reduced to the first. Returns no frames for an empty track
+/// or a non-positive interval.
+pub fn resample_track(points: &[ReplayPoint], interval: Duration) -> Vec<ReplayFrame> {
+    let mut points: Vec<&ReplayPoint> = points.iter().collect();
+    points.sort_by_key(|p| p.captured_at);
+    points.dedup_by_key(|p| p.captured_at);
+
+    let (Some(first), Some(last)) = (points.first().copied(), points.last().copied()) else {
+        return Vec::new();
+    };
+    if interval <= Duration::zero() {
+        return Vec::new();
+    }
+    if points.len() == 1 {
+        return vec![ReplayFrame {
+            timestamp: first.captured_at,
+            latitude: first.latitude,
+            longitude: first.longitude,
+            bearing: None,
+            speed: None,
+        }];
+    }
+
+    let mut frames = Vec::new();
+    let mut segment = 0;
+    let mut bearing = None;
+    let mut t = first.captured_at;
+    loop {
+        while segment + 2 < points.len() && points[segment + 1].captured_at <= t {
+            segment += 1;
+        }
+        let (a, b) = (points[segment], points[segment + 1]);
+        let span_ms = (b.captured_at - a.captured_at).num_milliseconds() as f64;
+        let fraction = ((t - a.captured_at).num_milliseconds() as f64 / span_ms).clamp(0.0, 1.0);
+        let distance = distance_meters(a.latitude, a.longitude, b.latitude, b.longitude);
+        if distance >= MIN_BEARING_DISTANCE_METERS {
+            bearing = Some(initial_bearing(a, b));
+        }
+
+        frames.push(ReplayFrame {
+            timestamp: t,
+            latitude: a.latitude + (b.latitude - a.latitude) * fraction,
+            longitude: a.longitude + (b.longitude - a.longitude) * fraction,
+            bearing,
+            speed: Some(distance / (span_ms / 1000.0)),
+        });
+
+        if t >= last.captured_at {
+            break;
+        }
+        t = (t + interval).min(last.captured_at);
+    }
+    frames
+}
+
+/// Initial great-circle bearing from `a` to `b` in degrees (0-360).
+fn initial_bearing(a: &ReplayPoint, b: &ReplayPoint) -> f64 {
+    let (lat1, lat2) = (a.latitude.to_radians(), b.latitude.to_radians());
+    let d_lon = (b.longitude - a.longitude).to_radians();
+    let y = d_lon.sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * d_lon.cos();
+    (y.atan2(x).to_degrees() + 360.0) % 360.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(secs: i64, latitude: f64, longitude: f64) -> ReplayPoint {
+        ReplayPoint {
+            captured_at: DateTime::from_timestamp(1_700_000_000 + secs, 0).unwrap(),
+            latitude,
+            longitude,
+        }
+    }
+
+    #[test]
+    fn test_resample_interpolates_between_points() {
+        let points = [point(0, 48.0, 17.0), point(10, 48.0, 17.01)];
+        let frames = resample_track(&points, Duration::seconds(5));
+
+        assert_eq!(frames.len(), 3);
+        assert_eq!(
+            frames[1].timestamp,
+            points[0].captured_at + Duration::seconds(5)
+        );
+        assert!((frames[1].longitude - 17.005).abs() < 1e-9);
+        assert!((frames[1].latitude - 48.0).abs() < 1e-9);
+        assert_eq!(frames[2].longitude, 17.01);
+        // Heading east
+        let bearing = frames[1].bearing.unwrap();
+        assert!((bearing - 90.0).abs() < 0.1);
+        // ~744 m in 10 s
+        let speed = frames[1].speed.unwrap();
+        assert!((speed - 74.4).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_resample_ends_at_last_point() {
+        let points = [point(0, 48.0, 17.0), point(12, 48.01, 17.0)];
+        let frames = resample_track(&points, Duration::seconds(5));
+
+        let times: Vec<i64> = frames
+            .iter()
+            .map(|f| (f.timestamp - points[0].captured_at).num_seconds())
+            .collect();
+        assert_eq!(times, vec![0, 5, 10, 12]);
+        // Heading north
+        assert!(frames[0].bearing.unwrap() < 0.1);
+    }
+
+    #[test]
+    fn test_resample_unordered_points_with_duplicates() {
+        let points = [
+            point(20, 48.0, 17.02),
+            point(0, 48.0, 17.0),
+            point(10, 48.0, 17.01),
+            point(10, 49.0, 18.0),
+        ];
+        let frames = resample_track(&points, Duration::seconds(10));
+
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[1].longitude, 17.01);
+        assert_eq!(frames[2].longitude, 17.02);
+    }
+
+    #[test]
+    fn test_resample_stationary_keeps_previous_bearing() {
+        let points = [
+            point(0, 48.0, 17.0),
+            point(10, 48.01, 17.0),
+            point(20, 48.01, 17.0),
+        ];
+        let frames = resample_track(&points, Duration::seconds(10));
+
+        assert_eq!(frames.len(), 3);
+        assert!(frames[2].bearing.unwrap() < 0.1);
+        assert_eq!(frames[2].speed, Some(0.0));
+    }
+
+    #[test]
+    fn test_resample_degenerate_input() {
+        assert!(resample_track(&[], Duration::seconds(5)).is_empty());
+
+        let single = resample_track(&[point(0, 48.0, 17.0)], Duration::seconds(5));
+        assert_eq!(single.len(), 1);
+        assert_eq!(single[0].bearing, None);
+        assert_eq!(single[0].speed, None);
+
+        let points = [point(0, 48.0, 17.0), point(10, 48.0, 17.01)];
+        assert!(resample_track(&points, Duration::zero()).is_empty());
+    }
+}
@@ -0,0 +1,218 @@
+//! Storage backend abstraction.
+//!
+//! A [`Database`] hands out the repositories of one storage backend. Code
+//! written against it and the [`DeviceStore`] / [`LocationStore`] traits
+//! runs unchanged on any backend. PostgreSQL is the primary backend, via
+//! [`PgPool`]; a SQLite backend for single-household self-hosting is
+//! available behind the `sqlite` feature.
+//!
+//! Only the core device and location operations are covered so far; the
+//! device and location handlers use them through `AppState::devices` and
+//! `AppState::locations`. The remaining repositories are PostgreSQL-only
+//! until they get a store trait.
+
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::entities::{DeviceEntity, LocationEntity};
+use crate::repositories::{DeviceRepository, LocationInput, LocationRepository};
+
+/// A storage backend: the factory for its repositories.
+pub trait Database: Clone + Send + Sync + 'static {
+    type Devices: DeviceStore;
+    type Locations: LocationStore;
+
+    /// Repository for devices.
+    fn devices(&self) -> Self::Devices;
+
+    /// Repository for locations.
+    fn locations(&self) -> Self::Locations;
+}
+
+/// Core device operations every backend supports.
+#[async_trait]
+pub trait DeviceStore: Send + Sync {
+    /// Find a device by its UUID.
+    async fn find_by_device_id(&self, device_id: Uuid)
+        -> Result<Option<DeviceEntity>, sqlx::Error>;
+
+    /// Register a device, or update and reactivate it if already known.
+    async fn upsert_device(
+        &self,
+        device_id: Uuid,
+        display_name: &str,
+        group_id: &str,
+        platform: &str,
+        fcm_token: Option<&str>,
+    ) -> Result<DeviceEntity, sqlx::Error>;
+
+    /// Deactivate a device (soft delete). Returns the number of rows affected.
+    async fn deactivate_device(&self, device_id: Uuid) -> Result<u64, sqlx::Error>;
+
+    /// Number of active devices in a group.
+    async fn count_active_devices_in_group(&self, group_id: &str) -> Result<i64, sqlx::Error>;
+
+    /// Active devices of a group, sorted by display name.
+    async fn find_active_devices_by_group(
+        &self,
+        group_id: &str,
+    ) -> Result<Vec<DeviceEntity>, sqlx::Error>;
+
+    /// Record when a device was last seen.
+    async fn update_last_seen_at(
+        &self,
+        device_id: Uuid,
+        timestamp: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error>;
+}
+
+/// Core location operations every backend supports.
+#[async_trait]
+pub trait LocationStore: Send + Sync {
+    /// Insert a location. Returns `None` if the device already stored one
+    /// with the same sequence number.
+    async fn insert_location(
+        &self,
+        input: LocationInput,
+    ) -> Result<Option<LocationEntity>, sqlx::Error>;
+
+    /// Insert locations in one transaction, skipping replayed sequence
+    /// numbers. Returns the number of inserted rows.
+    async fn insert_locations_batch(
+        &self,
+        device_id: Uuid,
+        locations: Vec<LocationInput>,
+    ) -> Result<usize, sqlx::Error>;
+
+    /// The most recently captured location of a device.
+    async fn get_latest_location(
+        &self,
+        device_id: Uuid,
+    ) -> Result<Option<LocationEntity>, sqlx::Error>;
+
+    /// Locations of a device captured in an optional time range, oldest first.
+    async fn get_all_locations_in_range(
+        &self,
+        device_id: Uuid,
+        from_timestamp: Option<DateTime<Utc>>,
+        to_timestamp: Option<DateTime<Utc>>,
+    ) -> Result<Vec<LocationEntity>, sqlx::Error>;
+
+    /// Delete locations older than the retention period. Returns the
+    /// number deleted.
+    async fn delete_old_locations(&self, retention_days: i64) -> Result<u64, sqlx::Error>;
+}
+
+impl Database for PgPool {
+    type Devices = DeviceRepository;
+    type Locations = LocationRepository;
+
+    fn devices(&self) -> DeviceRepository {
+        DeviceRepository::new(self.clone())
+    }
+
+    fn locations(&self) -> LocationRepository {
+        LocationRepository::new(self.clone())
+    }
+}
+
+#[async_trait]
+impl DeviceStore for DeviceRepository {
+    async fn find_by_device_id(
+        &self,
+        device_id: Uuid,
+    ) -> Result<Option<DeviceEntity>, sqlx::Error> {
+        DeviceRepository::find_by_device_id(self, device_id).await
+    }
+
+    async fn upsert_device(
+        &self,
+        device_id: Uuid,
+        display_name: &str,
+        group_id: &str,
+        platform: &str,
+        fcm_token: Option<&str>,
+    ) -> Result<DeviceEntity, sqlx::Error> {
+        DeviceRepository::upsert_device(
+            self,
+            device_id,
+            display_name,
+            group_id,
+            platform,
+            fcm_token,
+        )
+        .await
+    }
+
+    async fn deactivate_device(&self, device_id: Uuid) -> Result<u64, sqlx::Error> {
+        DeviceRepository::deactivate_device(self, device_id).await
+    }
+
+    async fn count_active_devices_in_group(&self, group_id: &str) -> Result<i64, sqlx::Error> {
+        DeviceRepository::count_active_devices_in_group(self, group_id).await
+    }
+
+    async fn find_active_devices_by_group(
+        &self,
+        group_id: &str,
+    ) -> Result<Vec<DeviceEntity>, sqlx::Error> {
+        DeviceRepository::find_active_devices_by_group(self, group_id).await
+    }
+
+    async fn update_last_seen_at(
+        &self,
+        device_id: Uuid,
+        timestamp: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        DeviceRepository::update_last_seen_at(self, device_id, timestamp).await
+    }
+}
+
+#[async_trait]
+impl LocationStore for LocationRepository {
+    async fn insert_location(
+        &self,
+        input: LocationInput,
+    ) -> Result<Option<LocationEntity>, sqlx::Error> {
+        LocationRepository::insert_location(self, input).await
+    }
+
+    async fn insert_locations_batch(
+        &self,
+        device_id: Uuid,
+        locations: Vec<LocationInput>,
+    ) -> Result<usize, sqlx::Error> {
+        LocationRepository::insert_locations_batch(self, device_id, locations).await
+    }
+
+    async fn get_latest_location(
+        &self,
+        device_id: Uuid,
+    ) -> Result<Option<LocationEntity>, sqlx::Error> {
+        LocationRepository::get_latest_location(self, device_id).await
+    }
+
+    async fn get_all_locations_in_range(
+        &self,
+        device_id: Uuid,
+        from_timestamp: Option<DateTime<Utc>>,
+        to_timestamp: Option<DateTime<Utc>>,
+    ) -> Result<Vec<LocationEntity>, sqlx::Error> {
+        LocationRepository::get_all_locations_in_range(
+            self,
+            device_id,
+            from_timestamp,
+            to_timestamp,
+        )
+        .await
+    }
+
+    async fn delete_old_locations(&self, retention_days: i64) -> Result<u64, sqlx::Error> {
+        LocationRepository::delete_old_locations(self, retention_days).await
+    }
+}
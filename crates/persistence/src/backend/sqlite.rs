@@ -0,0 +1,478 @@
+//! SQLite storage backend (`sqlite` feature).
+//!
+//! Meant for single-household self-hosting, where running PostgreSQL is
+//! more than the deployment needs. The schema lives in
+//! `backend/sqlite_migrations` and is applied by [`SqliteDatabase::migrate`].
+
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use uuid::Uuid;
+
+use super::{Database, DeviceStore, LocationStore};
+use crate::entities::{DeviceEntity, LocationEntity};
+use crate::metrics::QueryTimer;
+use crate::repositories::LocationInput;
+
+const DEVICE_COLUMNS: &str = "id, device_id, display_name, group_id, platform, fcm_token, \
+     active, created_at, updated_at, last_seen_at, \
+     owner_user_id, organization_id, is_primary, linked_at";
+
+const LOCATION_COLUMNS: &str = "id, device_id, latitude, longitude, accuracy, altitude, bearing, \
+     speed, provider, battery_level, network_type, captured_at, created_at, \
+     transportation_mode, detection_source, trip_id, source, trust_score";
+
+const INSERT_LOCATION: &str = r#"
+    INSERT INTO locations (
+        device_id, latitude, longitude, accuracy, altitude, bearing,
+        speed, provider, battery_level, network_type, captured_at, created_at,
+        transportation_mode, detection_source, trip_id, sequence_number, source, trust_score
+    )
+    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)
+    ON CONFLICT (device_id, sequence_number) WHERE sequence_number IS NOT NULL
+    DO NOTHING
+"#;
+
+/// SQLite backend.
+#[derive(Clone)]
+pub struct SqliteDatabase {
+    pool: SqlitePool,
+}
+
+impl SqliteDatabase {
+    /// Wrap an existing pool.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Open (creating if missing) the database at `url`, e.g.
+    /// `sqlite://phone-manager.db`, with foreign keys enforced.
+    pub async fn connect(url: &str, max_connections: u32) -> Result<Self, sqlx::Error> {
+        let options = SqliteConnectOptions::from_str(url)?
+            .create_if_missing(true)
+            .foreign_keys(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(max_connections)
+            .connect_with(options)
+            .await?;
+        Ok(Self::new(pool))
+    }
+
+    /// Apply the SQLite schema migrations.
+    pub async fn migrate(&self) -> Result<(), sqlx::migrate::MigrateError> {
+        sqlx::migrate!("src/backend/sqlite_migrations")
+            .run(&self.pool)
+            .await
+    }
+
+    /// Returns a reference to the connection pool.
+    pub fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+}
+
+impl Database for SqliteDatabase {
+    type Devices = SqliteDeviceRepository;
+    type Locations = SqliteLocationRepository;
+
+    fn devices(&self) -> SqliteDeviceRepository {
+        SqliteDeviceRepository {
+            pool: self.pool.clone(),
+        }
+    }
+
+    fn locations(&self) -> SqliteLocationRepository {
+        SqliteLocationRepository {
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+/// SQLite implementation of [`DeviceStore`].
+#[derive(Clone)]
+pub struct SqliteDeviceRepository {
+    pool: SqlitePool,
+}
+
+#[async_trait]
+impl DeviceStore for SqliteDeviceRepository {
+    async fn find_by_device_id(
+        &self,
+        device_id: Uuid,
+    ) -> Result<Option<DeviceEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("find_device_by_id");
+        let result = sqlx::query_as::<_, DeviceEntity>(&format!(
+            "SELECT {} FROM devices WHERE device_id = ?1",
+            DEVICE_COLUMNS
+        ))
+        .bind(device_id)
+        .fetch_optional(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    async fn upsert_device(
+        &self,
+        device_id: Uuid,
+        display_name: &str,
+        group_id: &str,
+        platform: &str,
+        fcm_token: Option<&str>,
+    ) -> Result<DeviceEntity, sqlx::Error> {
+        let timer = QueryTimer::new("upsert_device");
+        let result = sqlx::query_as::<_, DeviceEntity>(&format!(
+            r#"
+            INSERT INTO devices (device_id, display_name, group_id, platform, fcm_token, active, created_at, updated_at, last_seen_at, is_primary)
+            VALUES (?1, ?2, ?3, ?4, ?5, TRUE, ?6, ?6, ?6, FALSE)
+            ON CONFLICT (device_id) DO UPDATE SET
+                display_name = excluded.display_name,
+                group_id = excluded.group_id,
+                platform = excluded.platform,
+                fcm_token = excluded.fcm_token,
+                active = TRUE,
+                updated_at = excluded.updated_at,
+                last_seen_at = excluded.last_seen_at
+            RETURNING {}
+            "#,
+            DEVICE_COLUMNS
+        ))
+        .bind(device_id)
+        .bind(display_name)
+        .bind(group_id)
+        .bind(platform)
+        .bind(fcm_token)
+        .bind(Utc::now())
+        .fetch_one(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    async fn deactivate_device(&self, device_id: Uuid) -> Result<u64, sqlx::Error> {
+        let timer = QueryTimer::new("deactivate_device");
+        let result = sqlx::query(
+            "UPDATE devices SET active = FALSE, updated_at = ?2 WHERE device_id = ?1 AND active = TRUE",
+        )
+        .bind(device_id)
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await?;
+        timer.record();
+        Ok(result.rows_affected())
+    }
+
+    async fn count_active_devices_in_group(&self, group_id: &str) -> Result<i64, sqlx::Error> {
+        let timer = QueryTimer::new("count_active_devices_in_group");
+        let result = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM devices WHERE group_id = ?1 AND active = TRUE",
+        )
+        .bind(group_id)
+        .fetch_one(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    async fn find_active_devices_by_group(
+        &self,
+        group_id: &str,
+    ) -> Result<Vec<DeviceEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("find_active_devices_by_group");
+        let result = sqlx::query_as::<_, DeviceEntity>(&format!(
+            "SELECT {} FROM devices WHERE group_id = ?1 AND active = TRUE ORDER BY display_name ASC",
+            DEVICE_COLUMNS
+        ))
+        .bind(group_id)
+        .fetch_all(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    async fn update_last_seen_at(
+        &self,
+        device_id: Uuid,
+        timestamp: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE devices SET last_seen_at = ?2 WHERE device_id = ?1")
+            .bind(device_id)
+            .bind(timestamp)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
+
+/// SQLite implementation of [`LocationStore`].
+#[derive(Clone)]
+pub struct SqliteLocationRepository {
+    pool: SqlitePool,
+}
+
+#[async_trait]
+impl LocationStore for SqliteLocationRepository {
+    async fn insert_location(
+        &self,
+        input: LocationInput,
+    ) -> Result<Option<LocationEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("insert_location");
+        let result = sqlx::query_as::<_, LocationEntity>(&format!(
+            "{} RETURNING {}",
+            INSERT_LOCATION, LOCATION_COLUMNS
+        ))
+        .bind(input.device_id)
+        .bind(input.latitude)
+        .bind(input.longitude)
+        .bind(input.accuracy as f32)
+        .bind(input.altitude)
+        .bind(input.bearing.map(|b| b as f32))
+        .bind(input.speed.map(|s| s as f32))
+        .bind(&input.provider)
+        .bind(input.battery_level.map(|b| b as i16))
+        .bind(&input.network_type)
+        .bind(input.captured_at)
+        .bind(Utc::now())
+        .bind(&input.transportation_mode)
+        .bind(&input.detection_source)
+        .bind(input.trip_id)
+        .bind(input.sequence_number)
+        .bind(&input.source)
+        .bind(input.trust_score)
+        .fetch_optional(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    async fn insert_locations_batch(
+        &self,
+        device_id: Uuid,
+        locations: Vec<LocationInput>,
+    ) -> Result<usize, sqlx::Error> {
+        let timer = QueryTimer::new("insert_locations_batch");
+        let mut tx = self.pool.begin().await?;
+        let created_at = Utc::now();
+        let mut inserted = 0;
+
+        for loc in &locations {
+            let result = sqlx::query(INSERT_LOCATION)
+                .bind(device_id)
+                .bind(loc.latitude)
+                .bind(loc.longitude)
+                .bind(loc.accuracy as f32)
+                .bind(loc.altitude)
+                .bind(loc.bearing.map(|b| b as f32))
+                .bind(loc.speed.map(|s| s as f32))
+                .bind(&loc.provider)
+                .bind(loc.battery_level.map(|b| b as i16))
+                .bind(&loc.network_type)
+                .bind(loc.captured_at)
+                .bind(created_at)
+                .bind(&loc.transportation_mode)
+                .bind(&loc.detection_source)
+                .bind(loc.trip_id)
+                .bind(loc.sequence_number)
+                .bind(&loc.source)
+                .bind(loc.trust_score)
+                .execute(&mut *tx)
+                .await?;
+            inserted += result.rows_affected() as usize;
+        }
+
+        tx.commit().await?;
+        timer.record();
+        Ok(inserted)
+    }
+
+    async fn get_latest_location(
+        &self,
+        device_id: Uuid,
+    ) -> Result<Option<LocationEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("get_latest_location");
+        let result = sqlx::query_as::<_, LocationEntity>(&format!(
+            "SELECT {} FROM locations WHERE device_id = ?1 ORDER BY captured_at DESC, id DESC LIMIT 1",
+            LOCATION_COLUMNS
+        ))
+        .bind(device_id)
+        .fetch_optional(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    async fn get_all_locations_in_range(
+        &self,
+        device_id: Uuid,
+        from_timestamp: Option<DateTime<Utc>>,
+        to_timestamp: Option<DateTime<Utc>>,
+    ) -> Result<Vec<LocationEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("get_all_locations_in_range");
+        let result = sqlx::query_as::<_, LocationEntity>(&format!(
+            r#"
+            SELECT {}
+            FROM locations
+            WHERE device_id = ?1
+              AND (?2 IS NULL OR captured_at >= ?2)
+              AND (?3 IS NULL OR captured_at <= ?3)
+            ORDER BY captured_at ASC, id ASC
+            "#,
+            LOCATION_COLUMNS
+        ))
+        .bind(device_id)
+        .bind(from_timestamp)
+        .bind(to_timestamp)
+        .fetch_all(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    async fn delete_old_locations(&self, retention_days: i64) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM locations WHERE created_at < ?1")
+            .bind(Utc::now() - Duration::days(retention_days))
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn test_database() -> SqliteDatabase {
+        // A single connection, since every in-memory connection is its own database.
+        let db = SqliteDatabase::connect("sqlite::memory:", 1).await.unwrap();
+        db.migrate().await.unwrap();
+        db
+    }
+
+    fn location(device_id: Uuid, captured_at: DateTime<Utc>, seq: Option<i64>) -> LocationInput {
+        LocationInput {
+            device_id,
+            latitude: 48.1486,
+            longitude: 17.1077,
+            accuracy: 12.5,
+            altitude: None,
+            bearing: Some(90.0),
+            speed: None,
+            provider: Some("gps".to_string()),
+            battery_level: Some(80),
+            network_type: None,
+            captured_at,
+            transportation_mode: None,
+            detection_source: None,
+            trip_id: None,
+            sequence_number: seq,
+            source: None,
+            trust_score: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_device_lifecycle() {
+        let db = test_database().await;
+        let devices = db.devices();
+        let device_id = Uuid::new_v4();
+
+        let device = devices
+            .upsert_device(device_id, "Phone", "family", "android", None)
+            .await
+            .unwrap();
+        assert_eq!(device.device_id, device_id);
+        assert!(device.active);
+
+        let renamed = devices
+            .upsert_device(device_id, "Tablet", "family", "android", Some("token"))
+            .await
+            .unwrap();
+        assert_eq!(renamed.id, device.id);
+        assert_eq!(renamed.display_name, "Tablet");
+
+        let in_group = devices
+            .find_active_devices_by_group("family")
+            .await
+            .unwrap();
+        assert_eq!(in_group.len(), 1);
+        assert_eq!(
+            devices
+                .count_active_devices_in_group("family")
+                .await
+                .unwrap(),
+            1
+        );
+
+        assert_eq!(devices.deactivate_device(device_id).await.unwrap(), 1);
+        let found = devices.find_by_device_id(device_id).await.unwrap().unwrap();
+        assert!(!found.active);
+        assert!(devices
+            .find_active_devices_by_group("family")
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_locations_deduplicate_and_query() {
+        let db = test_database().await;
+        let device_id = Uuid::new_v4();
+        db.devices()
+            .upsert_device(device_id, "Phone", "family", "android", None)
+            .await
+            .unwrap();
+
+        let locations = db.locations();
+        let start = Utc::now() - Duration::hours(2);
+        let batch = vec![
+            location(device_id, start, Some(1)),
+            location(device_id, start + Duration::hours(1), Some(2)),
+        ];
+        assert_eq!(
+            locations
+                .insert_locations_batch(device_id, batch.clone())
+                .await
+                .unwrap(),
+            2
+        );
+        // Replayed batch is skipped
+        assert_eq!(
+            locations
+                .insert_locations_batch(device_id, batch)
+                .await
+                .unwrap(),
+            0
+        );
+        assert!(locations
+            .insert_location(location(device_id, start, Some(1)))
+            .await
+            .unwrap()
+            .is_none());
+
+        let latest = locations
+            .insert_location(location(device_id, Utc::now(), None))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(latest.battery_level, Some(80));
+        assert_eq!(
+            locations
+                .get_latest_location(device_id)
+                .await
+                .unwrap()
+                .unwrap()
+                .id,
+            latest.id
+        );
+
+        let in_range = locations
+            .get_all_locations_in_range(device_id, Some(start + Duration::minutes(30)), None)
+            .await
+            .unwrap();
+        assert_eq!(in_range.len(), 2);
+
+        assert_eq!(locations.delete_old_locations(1).await.unwrap(), 0);
+    }
+}
@@ -0,0 +1,48 @@
+-- SQLite migration 001: Devices and locations
+-- Mirrors the columns of the PostgreSQL devices and locations tables that
+-- the device and location stores read and write. UUIDs are stored as
+-- BLOBs and timestamps as RFC 3339 text.
+
+CREATE TABLE devices (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    device_id       BLOB NOT NULL UNIQUE,
+    display_name    TEXT NOT NULL,
+    group_id        TEXT NOT NULL,
+    platform        TEXT NOT NULL DEFAULT 'android',
+    fcm_token       TEXT,
+    active          BOOLEAN NOT NULL DEFAULT TRUE,
+    last_seen_at    TEXT,
+    created_at      TEXT NOT NULL,
+    updated_at      TEXT NOT NULL,
+    owner_user_id   BLOB,
+    organization_id BLOB,
+    is_primary      BOOLEAN NOT NULL DEFAULT FALSE,
+    linked_at       TEXT
+);
+
+CREATE INDEX idx_devices_group_id ON devices(group_id) WHERE active = TRUE;
+
+CREATE TABLE locations (
+    id                  INTEGER PRIMARY KEY AUTOINCREMENT,
+    device_id           BLOB NOT NULL REFERENCES devices(device_id) ON DELETE CASCADE,
+    latitude            REAL NOT NULL CHECK (latitude >= -90 AND latitude <= 90),
+    longitude           REAL NOT NULL CHECK (longitude >= -180 AND longitude <= 180),
+    accuracy            REAL NOT NULL CHECK (accuracy >= 0),
+    altitude            REAL,
+    bearing             REAL CHECK (bearing IS NULL OR (bearing >= 0 AND bearing <= 360)),
+    speed               REAL CHECK (speed IS NULL OR speed >= 0),
+    provider            TEXT,
+    battery_level       INTEGER CHECK (battery_level IS NULL OR (battery_level >= 0 AND battery_level <= 100)),
+    network_type        TEXT,
+    captured_at         TEXT NOT NULL,
+    created_at          TEXT NOT NULL,
+    transportation_mode TEXT,
+    detection_source    TEXT,
+    trip_id             BLOB,
+    sequence_number     INTEGER
+);
+
+CREATE INDEX idx_locations_device_captured ON locations(device_id, captured_at DESC);
+CREATE INDEX idx_locations_created_at ON locations(created_at);
+CREATE UNIQUE INDEX idx_locations_device_sequence
+    ON locations(device_id, sequence_number) WHERE sequence_number IS NOT NULL;
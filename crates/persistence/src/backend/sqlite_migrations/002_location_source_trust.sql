@@ -0,0 +1,7 @@
+-- SQLite migration 002: Location source and trust score
+-- Mirrors PostgreSQL migration 085.
+
+ALTER TABLE locations ADD COLUMN source TEXT
+    CHECK (source IS NULL OR source IN ('gps', 'network', 'beacon', 'manual_check_in', 'osmand'));
+ALTER TABLE locations ADD COLUMN trust_score INTEGER
+    CHECK (trust_score IS NULL OR (trust_score >= 0 AND trust_score <= 100));
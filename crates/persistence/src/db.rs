@@ -1,9 +1,14 @@
 //! Database connection pool management.
 
-use sqlx::postgres::PgPoolOptions;
+use sqlx::postgres::{PgConnection, PgPoolOptions};
 use sqlx::PgPool;
+use std::future::Future;
 use std::time::Duration;
 
+tokio::task_local! {
+    static STATEMENT_TIMEOUT: Duration;
+}
+
 /// Database configuration.
 #[derive(Debug, Clone)]
 pub struct DatabaseConfig {
@@ -15,16 +20,96 @@ pub struct DatabaseConfig {
 }
 
 /// Creates a PostgreSQL connection pool with the given configuration.
+///
+/// Every checkout applies the `statement_timeout` of the surrounding
+/// [`with_statement_timeout`] scope, or disables it outside of one, so a
+/// connection never carries a previous request's limit. With the
+/// `fault-injection` feature, checkouts also apply the faults configured for
+/// [`FaultPoint::Database`](crate::faults::FaultPoint::Database).
 pub async fn create_pool(config: &DatabaseConfig) -> Result<PgPool, sqlx::Error> {
     PgPoolOptions::new()
         .max_connections(config.max_connections)
         .min_connections(config.min_connections)
         .acquire_timeout(Duration::from_secs(config.connect_timeout_secs))
         .idle_timeout(Duration::from_secs(config.idle_timeout_secs))
+        .after_connect(|conn, _meta| Box::pin(apply_statement_timeout(conn)))
+        .before_acquire(|conn, _meta| {
+            Box::pin(async move {
+                apply_statement_timeout(conn).await?;
+                #[cfg(feature = "fault-injection")]
+                apply_database_fault(conn).await?;
+                Ok(true)
+            })
+        })
         .connect(&config.url)
         .await
 }
 
+/// Runs `fut` with `timeout` as the `statement_timeout` of every connection
+/// it checks out of a pool built by [`create_pool`].
+///
+/// The limit is task-local: work moved to a spawned task runs without it.
+pub async fn with_statement_timeout<F: Future>(timeout: Duration, fut: F) -> F::Output {
+    STATEMENT_TIMEOUT.scope(timeout, fut).await
+}
+
+/// Statement timeout set by [`with_statement_timeout`] for the current
+/// task, if any.
+pub fn current_statement_timeout() -> Option<Duration> {
+    STATEMENT_TIMEOUT.try_with(|timeout| *timeout).ok()
+}
+
+/// Statement timeout in milliseconds for the current task; 0 disables it.
+fn current_statement_timeout_ms() -> u128 {
+    current_statement_timeout().map_or(0, |timeout| timeout.as_millis())
+}
+
+async fn apply_statement_timeout(conn: &mut PgConnection) -> Result<(), sqlx::Error> {
+    // SET does not accept bind parameters; the value is a formatted integer.
+    sqlx::query(&format!(
+        "SET statement_timeout = {}",
+        current_statement_timeout_ms()
+    ))
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// Applies the database faults to a checked-out connection.
+///
+/// An error from `before_acquire` only makes the pool try another
+/// connection, so an injected error instead points the connection at a
+/// schema that does not exist: its queries then fail with a real database
+/// error (`42P01`) in the repository that issued them.
+#[cfg(feature = "fault-injection")]
+async fn apply_database_fault(conn: &mut PgConnection) -> Result<(), sqlx::Error> {
+    use crate::faults::{inject, FaultPoint};
+
+    let search_path = match inject(FaultPoint::Database).await {
+        Ok(()) => "DEFAULT",
+        Err(_) => "fault_injection",
+    };
+    sqlx::query(&format!("SET search_path = {}", search_path))
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+/// Planner estimate of a table's row count (`pg_class.reltuples`).
+///
+/// Cheap alternative to `COUNT(*)` for large tables. The figure covers the
+/// whole table, is refreshed by VACUUM/ANALYZE, and is -1 for a table that
+/// has never been analyzed; 0 is returned for an unknown table.
+pub async fn estimate_table_rows(pool: &PgPool, table: &str) -> Result<f64, sqlx::Error> {
+    let estimate: Option<f64> =
+        sqlx::query_scalar("SELECT reltuples::float8 FROM pg_class WHERE oid = to_regclass($1)")
+            .bind(table)
+            .fetch_optional(pool)
+            .await?;
+
+    Ok(estimate.unwrap_or(0.0))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -118,6 +203,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_statement_timeout_disabled_outside_scope() {
+        assert_eq!(current_statement_timeout_ms(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_statement_timeout_scoped_to_task() {
+        let inside = with_statement_timeout(Duration::from_secs(5), async {
+            current_statement_timeout_ms()
+        })
+        .await;
+        assert_eq!(inside, 5000);
+        assert_eq!(current_statement_timeout_ms(), 0);
+    }
+
     #[test]
     fn test_database_config_connection_bounds() {
         let config = DatabaseConfig {
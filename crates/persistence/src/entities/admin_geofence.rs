@@ -18,6 +18,8 @@ pub struct AdminGeofenceEntity {
     pub event_types: Vec<String>,
     pub active: bool,
     pub color: Option<String>,
+    pub category: Option<String>,
+    pub tags: Vec<String>,
     pub metadata: Option<serde_json::Value>,
     pub created_by: Option<Uuid>,
     pub created_at: DateTime<Utc>,
@@ -38,6 +40,8 @@ pub struct AdminGeofenceWithCreatorEntity {
     pub event_types: Vec<String>,
     pub active: bool,
     pub color: Option<String>,
+    pub category: Option<String>,
+    pub tags: Vec<String>,
     pub metadata: Option<serde_json::Value>,
     pub created_by: Option<Uuid>,
     pub creator_name: Option<String>,
@@ -79,6 +83,14 @@ pub struct GeofenceVisitCountEntity {
     pub visit_count: i64,
 }
 
+/// Geofence and visit counts of one category for analytics.
+#[derive(Debug, Clone, FromRow)]
+pub struct GeofenceCategoryCountEntity {
+    pub category: Option<String>,
+    pub geofence_count: i64,
+    pub visit_count: i64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -96,6 +108,8 @@ mod tests {
             event_types: vec!["enter".to_string(), "exit".to_string()],
             active: true,
             color: Some("#FF5733".to_string()),
+            category: Some("office".to_string()),
+            tags: vec!["hq".to_string()],
             metadata: None,
             created_by: Some(Uuid::new_v4()),
             created_at: Utc::now(),
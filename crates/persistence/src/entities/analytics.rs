@@ -75,6 +75,24 @@ pub struct DeviceActivityDailyEntity {
     pub updated_at: DateTime<Utc>,
 }
 
+/// Completed trip distance per day for an organization's devices.
+#[derive(Debug, Clone, FromRow)]
+pub struct TripDistanceDailyEntity {
+    pub activity_date: NaiveDate,
+    pub distance_meters: f64,
+}
+
+/// Completed trip distance per day, device and purpose (mileage reports).
+#[derive(Debug, Clone, FromRow)]
+pub struct TripMileageEntity {
+    pub activity_date: NaiveDate,
+    pub device_id: Uuid,
+    pub device_name: String,
+    pub purpose: Option<String>,
+    pub trip_count: i64,
+    pub distance_meters: f64,
+}
+
 /// Summary entity for user analytics.
 #[derive(Debug, Clone, FromRow)]
 pub struct UserAnalyticsSummaryEntity {
@@ -131,3 +149,90 @@ pub struct DeviceStatusCountEntity {
     pub status: String,
     pub count: i64,
 }
+
+// ============================================================================
+// Report builder sections
+// ============================================================================
+//
+// `bucket` is NULL when no time dimension is selected; `device_id` and
+// `device_name` are NULL unless rows are grouped by device.
+
+/// Devices section row: active devices and reported locations.
+#[derive(Debug, Clone, FromRow)]
+pub struct DeviceReportMetricsEntity {
+    pub bucket: Option<NaiveDate>,
+    pub device_id: Option<Uuid>,
+    pub device_name: Option<String>,
+    pub active_devices: i64,
+    pub locations_reported: i64,
+}
+
+/// Trips section row: completed trips with distance and duration.
+#[derive(Debug, Clone, FromRow)]
+pub struct TripReportMetricsEntity {
+    pub bucket: Option<NaiveDate>,
+    pub device_id: Option<Uuid>,
+    pub device_name: Option<String>,
+    pub trip_count: i64,
+    pub distance_meters: f64,
+    pub duration_seconds: i64,
+}
+
+/// Geofence compliance section row: transition counts by type.
+#[derive(Debug, Clone, FromRow)]
+pub struct GeofenceReportMetricsEntity {
+    pub bucket: Option<NaiveDate>,
+    pub device_id: Option<Uuid>,
+    pub device_name: Option<String>,
+    pub enter_count: i64,
+    pub exit_count: i64,
+    pub dwell_count: i64,
+}
+
+/// App usage section row: foreground time, launches and distinct apps.
+#[derive(Debug, Clone, FromRow)]
+pub struct AppUsageReportMetricsEntity {
+    pub bucket: Option<NaiveDate>,
+    pub device_id: Option<Uuid>,
+    pub device_name: Option<String>,
+    pub foreground_time_ms: i64,
+    pub launch_count: i64,
+    pub unique_apps: i64,
+}
+
+/// Raw location row for data extracts.
+#[derive(Debug, Clone, FromRow)]
+pub struct LocationExtractEntity {
+    pub id: i64,
+    pub device_id: Uuid,
+    pub device_name: Option<String>,
+    pub captured_at: DateTime<Utc>,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub accuracy: f32,
+    pub altitude: Option<f64>,
+    pub speed: Option<f32>,
+    pub bearing: Option<f32>,
+    pub provider: Option<String>,
+    pub battery_level: Option<i16>,
+    pub network_type: Option<String>,
+    pub transportation_mode: Option<String>,
+    pub trip_id: Option<Uuid>,
+}
+
+/// Raw geofence event row for data extracts.
+#[derive(Debug, Clone, FromRow)]
+pub struct GeofenceEventExtractEntity {
+    pub id: i64,
+    pub event_id: Uuid,
+    pub device_id: Uuid,
+    pub device_name: Option<String>,
+    pub geofence_id: Uuid,
+    pub geofence_name: Option<String>,
+    pub event_type: String,
+    pub timestamp: i64,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub webhook_delivered: bool,
+    pub created_at: DateTime<Utc>,
+}
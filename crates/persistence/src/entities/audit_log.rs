@@ -55,6 +55,41 @@ pub struct AuditLogEntity {
     pub created_at: DateTime<Utc>,
 }
 
+/// Audit log row with its insertion sequence, for incremental exports.
+#[derive(Debug, Clone, FromRow)]
+pub struct SequencedAuditLogEntity {
+    /// Monotonic insertion sequence.
+    pub seq: i64,
+
+    #[sqlx(flatten)]
+    pub log: AuditLogEntity,
+}
+
+/// Database entity for incremental export cursors.
+#[derive(Debug, Clone, FromRow)]
+pub struct AuditExportCursorEntity {
+    /// Organization the cursor belongs to.
+    pub organization_id: Uuid,
+
+    /// Client-chosen destination name.
+    pub destination: String,
+
+    /// Sequence of the last exported audit log (0 before the first export).
+    pub last_seq: i64,
+
+    /// Number of records in the last export.
+    pub last_record_count: Option<i64>,
+
+    /// When the last export ran.
+    pub last_exported_at: Option<DateTime<Utc>>,
+
+    /// Timestamp when the cursor was created.
+    pub created_at: DateTime<Utc>,
+
+    /// Timestamp when the cursor was last updated.
+    pub updated_at: DateTime<Utc>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,101 @@
+//! Device commute entity (database row mapping).
+
+use chrono::{DateTime, Utc, Weekday};
+use domain::models::commute::{format_minute_of_day, CommutePlace, CommuteResponse};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database row mapping for the device_commutes table.
+#[derive(Debug, Clone, FromRow)]
+pub struct DeviceCommuteEntity {
+    pub id: Uuid,
+    pub device_id: Uuid,
+    pub origin_latitude: f64,
+    pub origin_longitude: f64,
+    pub destination_latitude: f64,
+    pub destination_longitude: f64,
+    pub trip_count: i32,
+    /// Minutes after midnight UTC.
+    pub typical_departure_minute: i32,
+    pub departure_spread_minutes: i32,
+    pub typical_duration_seconds: i64,
+    /// ISO weekday numbers, 1 = Monday.
+    pub weekdays: Vec<i16>,
+    pub first_departure_at: DateTime<Utc>,
+    pub last_departure_at: DateTime<Utc>,
+    pub detected_at: DateTime<Utc>,
+}
+
+impl From<DeviceCommuteEntity> for CommuteResponse {
+    fn from(entity: DeviceCommuteEntity) -> Self {
+        Self {
+            id: entity.id,
+            origin: CommutePlace {
+                latitude: entity.origin_latitude,
+                longitude: entity.origin_longitude,
+            },
+            destination: CommutePlace {
+                latitude: entity.destination_latitude,
+                longitude: entity.destination_longitude,
+            },
+            trip_count: entity.trip_count,
+            typical_departure_time: format_minute_of_day(entity.typical_departure_minute),
+            departure_spread_minutes: entity.departure_spread_minutes,
+            typical_duration_seconds: entity.typical_duration_seconds,
+            weekdays: entity
+                .weekdays
+                .into_iter()
+                .filter_map(weekday_from_number)
+                .collect(),
+            first_departure_at: entity.first_departure_at,
+            last_departure_at: entity.last_departure_at,
+        }
+    }
+}
+
+pub(crate) fn weekday_from_number(number: i16) -> Option<Weekday> {
+    match number {
+        1 => Some(Weekday::Mon),
+        2 => Some(Weekday::Tue),
+        3 => Some(Weekday::Wed),
+        4 => Some(Weekday::Thu),
+        5 => Some(Weekday::Fri),
+        6 => Some(Weekday::Sat),
+        7 => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_into_response() {
+        let now = Utc::now();
+        let entity = DeviceCommuteEntity {
+            id: Uuid::new_v4(),
+            device_id: Uuid::new_v4(),
+            origin_latitude: 48.1486,
+            origin_longitude: 17.1077,
+            destination_latitude: 48.17,
+            destination_longitude: 17.06,
+            trip_count: 4,
+            typical_departure_minute: 1020,
+            departure_spread_minutes: 10,
+            typical_duration_seconds: 1800,
+            weekdays: vec![1, 3, 7, 9],
+            first_departure_at: now,
+            last_departure_at: now,
+            detected_at: now,
+        };
+
+        let response = CommuteResponse::from(entity);
+        assert_eq!(response.typical_departure_time, "17:00");
+        assert_eq!(
+            response.weekdays,
+            vec![Weekday::Mon, Weekday::Wed, Weekday::Sun]
+        );
+        assert_eq!(response.destination.longitude, 17.06);
+    }
+}
@@ -62,6 +62,7 @@ pub struct DeviceWithLastLocationEntity {
     pub last_longitude: Option<f64>,
     pub last_location_time: Option<DateTime<Utc>>,
     pub last_accuracy: Option<f32>,
+    pub owner_user_id: Option<Uuid>,
 }
 
 /// Database row mapping for member device listing with last location.
@@ -102,6 +103,12 @@ pub struct FleetDeviceEntity {
     pub last_latitude: Option<f64>,
     pub last_longitude: Option<f64>,
     pub last_location_time: Option<DateTime<Utc>>,
+    // Latest telemetry sample (from LATERAL join)
+    pub telemetry_battery_level: Option<i16>,
+    pub telemetry_charging_state: Option<String>,
+    pub telemetry_network_type: Option<String>,
+    pub telemetry_signal_strength: Option<i16>,
+    pub telemetry_recorded_at: Option<DateTime<Utc>>,
 }
 
 #[cfg(test)]
@@ -185,6 +192,7 @@ mod tests {
             last_longitude: Some(-122.4194),
             last_location_time: Some(Utc::now()),
             last_accuracy: Some(10.0),
+            owner_user_id: None,
         };
 
         assert_eq!(entity.last_latitude, Some(37.7749));
@@ -209,6 +217,7 @@ mod tests {
             last_longitude: None,
             last_location_time: None,
             last_accuracy: None,
+            owner_user_id: None,
         };
 
         assert!(entity.last_latitude.is_none());
@@ -0,0 +1,18 @@
+//! Device API usage entity (database row mapping).
+//!
+//! Maps to the `device_api_usage` table.
+
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Per-device request counters for one endpoint class.
+#[derive(Debug, Clone, FromRow)]
+pub struct DeviceApiUsageEntity {
+    pub device_id: Uuid,
+    pub endpoint_class: String,
+    pub request_count: i64,
+    /// Requests in the current clock hour (0 if the stored bucket is stale).
+    pub current_hour_count: i64,
+    pub last_request_at: DateTime<Utc>,
+}
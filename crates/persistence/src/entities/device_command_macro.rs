@@ -0,0 +1,103 @@
+//! Device command macro entities (database row mapping).
+
+use chrono::{DateTime, Utc};
+use domain::models::{
+    macro_parameters, CommandMacroRun, CommandMacroRunCommand, CommandMacroStep,
+    DeviceCommandMacro, DeviceCommandStatus, DeviceCommandType,
+};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database row mapping for the device_command_macros table.
+#[derive(Debug, Clone, FromRow)]
+pub struct DeviceCommandMacroEntity {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub steps: serde_json::Value,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<DeviceCommandMacroEntity> for DeviceCommandMacro {
+    fn from(entity: DeviceCommandMacroEntity) -> Self {
+        let steps: Vec<CommandMacroStep> = serde_json::from_value(entity.steps).unwrap_or_default();
+        Self {
+            id: entity.id,
+            organization_id: entity.organization_id,
+            name: entity.name,
+            description: entity.description,
+            parameters: macro_parameters(&steps),
+            steps,
+            created_by: entity.created_by,
+            created_at: entity.created_at,
+            updated_at: entity.updated_at,
+        }
+    }
+}
+
+/// Database row mapping for the device_command_macro_runs table.
+#[derive(Debug, Clone, FromRow)]
+pub struct DeviceCommandMacroRunEntity {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub macro_id: Option<Uuid>,
+    pub macro_name: String,
+    pub parameters: serde_json::Value,
+    pub device_count: i32,
+    pub issued_by: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<DeviceCommandMacroRunEntity> for CommandMacroRun {
+    fn from(entity: DeviceCommandMacroRunEntity) -> Self {
+        Self {
+            id: entity.id,
+            organization_id: entity.organization_id,
+            macro_id: entity.macro_id,
+            macro_name: entity.macro_name,
+            parameters: entity.parameters,
+            device_count: entity.device_count,
+            issued_by: entity.issued_by,
+            created_at: entity.created_at,
+        }
+    }
+}
+
+/// Database row mapping for a device command queued by a macro run.
+#[derive(Debug, Clone, FromRow)]
+pub struct MacroRunCommandEntity {
+    pub id: Uuid,
+    pub device_id: i64,
+    pub step_index: i32,
+    pub command_type: String,
+    pub status: String,
+    pub failure_reason: Option<String>,
+    pub acknowledged_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub failed_at: Option<DateTime<Utc>>,
+}
+
+impl From<MacroRunCommandEntity> for CommandMacroRunCommand {
+    fn from(entity: MacroRunCommandEntity) -> Self {
+        Self {
+            command_id: entity.id,
+            device_id: entity.device_id,
+            step_index: entity.step_index,
+            command_type: entity
+                .command_type
+                .parse()
+                .unwrap_or(DeviceCommandType::SyncSettings),
+            status: entity
+                .status
+                .parse()
+                .unwrap_or(DeviceCommandStatus::Pending),
+            failure_reason: entity.failure_reason,
+            acknowledged_at: entity.acknowledged_at,
+            completed_at: entity.completed_at,
+            failed_at: entity.failed_at,
+        }
+    }
+}
@@ -0,0 +1,26 @@
+//! Device configuration history entities (database row mapping).
+
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// A device setting as it was at a point in time, from the
+/// device_setting_history table.
+#[derive(Debug, Clone, FromRow)]
+pub struct DeviceSettingVersionEntity {
+    pub setting_key: String,
+    pub value: serde_json::Value,
+    pub is_locked: bool,
+    /// When this version took effect.
+    pub valid_from: DateTime<Utc>,
+}
+
+/// Number of an organization's devices assigned to a policy at a point in
+/// time.
+#[derive(Debug, Clone, FromRow)]
+pub struct PolicyAssignmentCountEntity {
+    pub policy_id: Uuid,
+    /// Current policy name; `None` if the policy was deleted since.
+    pub policy_name: Option<String>,
+    pub device_count: i64,
+}
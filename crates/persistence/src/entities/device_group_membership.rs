@@ -31,6 +31,7 @@ pub struct DeviceInGroupEntity {
     pub membership_id: Uuid,
     pub added_by: Uuid,
     pub added_at: DateTime<Utc>,
+    pub nickname: Option<String>,
 }
 
 /// Device with group membership and last location (for listing devices with location).
@@ -47,6 +48,7 @@ pub struct DeviceInGroupWithLocationEntity {
     pub membership_id: Uuid,
     pub added_by: Uuid,
     pub added_at: DateTime<Utc>,
+    pub nickname: Option<String>,
     // Location fields (optional)
     pub latitude: Option<f64>,
     pub longitude: Option<f64>,
@@ -54,6 +56,25 @@ pub struct DeviceInGroupWithLocationEntity {
     pub location_timestamp: Option<DateTime<Utc>>,
 }
 
+/// Device in a group whose last location lies within a search radius.
+#[derive(Debug, Clone, FromRow)]
+pub struct NearbyDeviceInGroupEntity {
+    // Device fields
+    pub device_id: Uuid,
+    pub display_name: String,
+    pub last_seen_at: Option<DateTime<Utc>>,
+    // Owner fields
+    pub owner_user_id: Option<Uuid>,
+    pub owner_display_name: Option<String>,
+    // Last location
+    pub latitude: f64,
+    pub longitude: f64,
+    pub accuracy: f32,
+    pub location_timestamp: DateTime<Utc>,
+    /// Geodesic distance from the search point in meters.
+    pub distance_meters: f64,
+}
+
 /// Group info for a device's membership (for listing device's groups).
 #[derive(Debug, Clone, FromRow)]
 pub struct DeviceGroupInfoEntity {
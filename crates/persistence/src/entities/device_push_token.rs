@@ -0,0 +1,57 @@
+//! Device push token entity (database row mapping).
+
+use chrono::{DateTime, Utc};
+use domain::models::{push_token_hint, DevicePushToken, PushPlatform};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database row mapping for the device_push_tokens table.
+#[derive(Debug, Clone, FromRow)]
+pub struct DevicePushTokenEntity {
+    pub id: Uuid,
+    pub device_id: Uuid,
+    pub token: String,
+    pub platform: String,
+    pub app_version: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<DevicePushTokenEntity> for DevicePushToken {
+    fn from(entity: DevicePushTokenEntity) -> Self {
+        Self {
+            id: entity.id,
+            device_id: entity.device_id,
+            platform: entity.platform.parse().unwrap_or(PushPlatform::Android),
+            token_hint: push_token_hint(&entity.token),
+            app_version: entity.app_version,
+            expires_at: entity.expires_at,
+            created_at: entity.created_at,
+            updated_at: entity.updated_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_device_push_token_entity_to_domain() {
+        let entity = DevicePushTokenEntity {
+            id: Uuid::new_v4(),
+            device_id: Uuid::new_v4(),
+            token: "secret-token-123456".to_string(),
+            platform: "ios".to_string(),
+            app_version: Some("2.4.0".to_string()),
+            expires_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let token: DevicePushToken = entity.into();
+        assert_eq!(token.platform, PushPlatform::Ios);
+        assert_eq!(token.token_hint, "...123456");
+        assert_eq!(token.app_version.as_deref(), Some("2.4.0"));
+    }
+}
@@ -0,0 +1,54 @@
+//! Device telemetry entity (database row mapping).
+
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database row mapping for the device_telemetry table.
+#[derive(Debug, Clone, FromRow)]
+pub struct DeviceTelemetryEntity {
+    pub id: i64,
+    pub device_id: Uuid,
+    pub battery_level: Option<i16>,
+    pub charging_state: Option<String>,
+    pub network_type: Option<String>,
+    pub signal_strength: Option<i16>,
+    pub recorded_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<DeviceTelemetryEntity> for domain::models::DeviceTelemetry {
+    fn from(entity: DeviceTelemetryEntity) -> Self {
+        Self {
+            battery_level: entity.battery_level.map(i32::from),
+            charging_state: entity.charging_state.and_then(|s| s.parse().ok()),
+            network_type: entity.network_type,
+            signal_strength: entity.signal_strength.map(i32::from),
+            recorded_at: entity.recorded_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain::models::{ChargingState, DeviceTelemetry};
+
+    #[test]
+    fn test_device_telemetry_entity_to_domain() {
+        let entity = DeviceTelemetryEntity {
+            id: 1,
+            device_id: Uuid::new_v4(),
+            battery_level: Some(55),
+            charging_state: Some("charging".to_string()),
+            network_type: Some("lte".to_string()),
+            signal_strength: Some(-101),
+            recorded_at: Utc::now(),
+            created_at: Utc::now(),
+        };
+        let telemetry: DeviceTelemetry = entity.into();
+        assert_eq!(telemetry.battery_level, Some(55));
+        assert_eq!(telemetry.charging_state, Some(ChargingState::Charging));
+        assert_eq!(telemetry.signal_strength, Some(-101));
+    }
+}
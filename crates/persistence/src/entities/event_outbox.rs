@@ -0,0 +1,18 @@
+//! Transactional outbox entity definitions.
+
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+/// Database entity for event_outbox table.
+#[derive(Debug, Clone, FromRow)]
+pub struct EventOutboxEntity {
+    pub id: i64,
+    pub kind: String,
+    pub payload: serde_json::Value,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub locked_until: Option<DateTime<Utc>>,
+    pub processed_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
@@ -1,4 +1,4 @@
-//! Audit export job entity.
+//! Export job entity.
 //!
 //! Story 13.10: Audit Query and Export Endpoints
 
@@ -6,22 +6,32 @@ use chrono::{DateTime, Utc};
 use sqlx::FromRow;
 use uuid::Uuid;
 
-/// Database entity for audit export jobs.
+/// Database entity for organization and group export jobs.
 #[derive(Debug, Clone, FromRow)]
-pub struct AuditExportJobEntity {
+pub struct ExportJobEntity {
     /// Unique database identifier.
     pub id: Uuid,
 
-    /// User-facing job identifier (export_<random>).
+    /// User-facing job identifier (export_<random>, org_export_<random> or
+    /// group_export_<random>).
     pub job_id: String,
 
-    /// Organization this export belongs to.
-    pub organization_id: Uuid,
+    /// Organization this export belongs to; null for group exports.
+    pub organization_id: Option<Uuid>,
+
+    /// Group this export belongs to; set for group exports only.
+    pub group_id: Option<Uuid>,
+
+    /// User who started the export, if known.
+    pub requested_by: Option<Uuid>,
+
+    /// What is exported (audit_logs, organization or group).
+    pub kind: String,
 
     /// Current job status.
     pub status: String,
 
-    /// Export format (json or csv).
+    /// Export format of audit log exports (json, csv or xlsx).
     pub format: String,
 
     /// Filter parameters used for the export.
@@ -33,6 +43,12 @@ pub struct AuditExportJobEntity {
     /// URL or data URL to download the export.
     pub download_url: Option<String>,
 
+    /// Archive file name, relative to the reports directory.
+    pub file_path: Option<String>,
+
+    /// Archive size in bytes.
+    pub file_size: Option<i64>,
+
     /// Error message if job failed.
     pub error_message: Option<String>,
 
@@ -54,17 +70,22 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_audit_export_job_entity_creation() {
+    fn test_export_job_entity_creation() {
         let now = Utc::now();
-        let entity = AuditExportJobEntity {
+        let entity = ExportJobEntity {
             id: Uuid::new_v4(),
             job_id: "export_abc123".to_string(),
-            organization_id: Uuid::new_v4(),
+            organization_id: Some(Uuid::new_v4()),
+            group_id: None,
+            requested_by: None,
+            kind: "audit_logs".to_string(),
             status: "pending".to_string(),
             format: "json".to_string(),
             filters: Some(serde_json::json!({"action": "device.assign"})),
             record_count: None,
             download_url: None,
+            file_path: None,
+            file_size: None,
             error_message: None,
             created_at: now,
             updated_at: now,
@@ -72,6 +93,7 @@ mod tests {
             completed_at: None,
         };
 
+        assert_eq!(entity.kind, "audit_logs");
         assert_eq!(entity.status, "pending");
         assert_eq!(entity.format, "json");
     }
@@ -5,6 +5,7 @@ use sqlx::FromRow;
 use uuid::Uuid;
 
 use domain::models::geofence::{Geofence, GeofenceEventType};
+use domain::models::WeeklySchedule;
 
 /// Database row mapping for the geofences table.
 #[derive(Debug, Clone, FromRow)]
@@ -19,6 +20,11 @@ pub struct GeofenceEntity {
     pub event_types: Vec<String>, // SQLx maps TEXT[] to Vec<String>
     pub active: bool,
     pub metadata: Option<serde_json::Value>,
+    pub schedule: Option<serde_json::Value>,
+    pub template_id: Option<Uuid>,
+    pub cooldown_seconds: i32,
+    pub arrival_alert_minutes: i32,
+    pub priority: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -40,6 +46,14 @@ impl From<GeofenceEntity> for Geofence {
                 .collect(),
             active: entity.active,
             metadata: entity.metadata,
+            // Schedules are validated on write; an unreadable one is treated as none.
+            schedule: entity
+                .schedule
+                .and_then(|value| WeeklySchedule::from_value(&value).ok().flatten()),
+            template_id: entity.template_id,
+            cooldown_seconds: entity.cooldown_seconds,
+            arrival_alert_minutes: entity.arrival_alert_minutes,
+            priority: entity.priority,
             created_at: entity.created_at,
             updated_at: entity.updated_at,
         }
@@ -62,6 +76,11 @@ mod tests {
             event_types: vec!["enter".to_string(), "exit".to_string()],
             active: true,
             metadata: None,
+            schedule: None,
+            template_id: None,
+            cooldown_seconds: 0,
+            arrival_alert_minutes: 0,
+            priority: 0,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         }
@@ -130,4 +149,18 @@ mod tests {
         assert_eq!(cloned.name, entity.name);
         assert_eq!(cloned.latitude, entity.latitude);
     }
+
+    #[test]
+    fn test_geofence_entity_with_schedule() {
+        let mut entity = create_test_geofence_entity();
+        entity.schedule = Some(serde_json::json!({
+            "windows": [{ "days": ["mon"], "start": "08:00", "end": "15:00" }]
+        }));
+        let geofence: Geofence = entity.clone().into();
+        assert_eq!(geofence.schedule.unwrap().windows.len(), 1);
+
+        entity.schedule = Some(serde_json::json!("weekdays"));
+        let geofence: Geofence = entity.into();
+        assert!(geofence.schedule.is_none());
+    }
 }
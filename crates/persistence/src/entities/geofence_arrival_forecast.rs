@@ -0,0 +1,19 @@
+//! Geofence arrival forecast entity (database row mapping).
+
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database row mapping for the geofence_arrival_forecasts table.
+#[derive(Debug, Clone, FromRow)]
+pub struct GeofenceArrivalForecastEntity {
+    pub id: i64,
+    pub device_id: Uuid,
+    pub geofence_id: Uuid,
+    pub eta_seconds: i32,
+    pub distance_meters: f32,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub forecast_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
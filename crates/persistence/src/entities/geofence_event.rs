@@ -20,6 +20,8 @@ pub struct GeofenceEventEntity {
     pub longitude: f64,
     pub webhook_delivered: bool,
     pub webhook_response_code: Option<i32>,
+    pub source: String,
+    pub resolved_geofence_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -37,9 +39,31 @@ pub struct GeofenceEventWithName {
     pub longitude: f64,
     pub webhook_delivered: bool,
     pub webhook_response_code: Option<i32>,
+    pub source: String,
+    pub resolved_geofence_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
 }
 
+impl From<GeofenceEventWithName> for GeofenceEventEntity {
+    fn from(event: GeofenceEventWithName) -> Self {
+        Self {
+            id: event.id,
+            event_id: event.event_id,
+            device_id: event.device_id,
+            geofence_id: event.geofence_id,
+            event_type: event.event_type,
+            timestamp: event.timestamp,
+            latitude: event.latitude,
+            longitude: event.longitude,
+            webhook_delivered: event.webhook_delivered,
+            webhook_response_code: event.webhook_response_code,
+            source: event.source,
+            resolved_geofence_id: event.resolved_geofence_id,
+            created_at: event.created_at,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -56,6 +80,8 @@ mod tests {
             longitude: -122.4194,
             webhook_delivered: false,
             webhook_response_code: None,
+            source: "client".to_string(),
+            resolved_geofence_id: None,
             created_at: Utc::now(),
         }
     }
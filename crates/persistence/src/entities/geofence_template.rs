@@ -0,0 +1,55 @@
+//! Geofence template entity (database row mapping).
+
+use chrono::{DateTime, Utc};
+use domain::models::geofence::GeofenceEventType;
+use domain::models::{GeofenceTemplate, WeeklySchedule};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database row mapping for the geofence_templates table, with the number
+/// of geofences stamped from the template.
+#[derive(Debug, Clone, FromRow)]
+pub struct GeofenceTemplateEntity {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub radius_meters: f32,
+    pub event_types: Vec<String>,
+    pub schedule: Option<serde_json::Value>,
+    pub metadata: Option<serde_json::Value>,
+    pub geofence_count: i64,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<GeofenceTemplateEntity> for GeofenceTemplate {
+    fn from(entity: GeofenceTemplateEntity) -> Self {
+        Self {
+            id: entity.id,
+            organization_id: entity.organization_id,
+            name: entity.name,
+            description: entity.description,
+            latitude: entity.latitude,
+            longitude: entity.longitude,
+            radius_meters: entity.radius_meters,
+            event_types: entity
+                .event_types
+                .iter()
+                .filter_map(|s| GeofenceEventType::parse(s))
+                .collect(),
+            // Schedules are validated on write; an unreadable one is treated as none.
+            schedule: entity
+                .schedule
+                .and_then(|value| WeeklySchedule::from_value(&value).ok().flatten()),
+            metadata: entity.metadata,
+            geofence_count: entity.geofence_count,
+            created_by: entity.created_by,
+            created_at: entity.created_at,
+            updated_at: entity.updated_at,
+        }
+    }
+}
@@ -51,6 +51,7 @@ pub struct GroupEntity {
     pub created_by: Uuid,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub parent_group_id: Option<Uuid>,
 }
 
 impl From<GroupEntity> for domain::models::Group {
@@ -67,6 +68,7 @@ impl From<GroupEntity> for domain::models::Group {
             created_by: entity.created_by,
             created_at: entity.created_at,
             updated_at: entity.updated_at,
+            parent_group_id: entity.parent_group_id,
         }
     }
 }
@@ -111,6 +113,7 @@ pub struct GroupWithMembershipEntity {
     pub created_by: Uuid,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub parent_group_id: Option<Uuid>,
     // Membership fields
     pub membership_id: Uuid,
     pub role: GroupRoleDb,
@@ -122,6 +125,28 @@ pub struct GroupWithMembershipEntity {
     pub has_current_device: bool,
 }
 
+/// Sub-group row with its member count.
+#[derive(Debug, Clone, FromRow)]
+pub struct SubgroupEntity {
+    pub id: Uuid,
+    pub name: String,
+    pub slug: String,
+    pub icon_emoji: Option<String>,
+    pub member_count: i64,
+}
+
+impl From<SubgroupEntity> for domain::models::group::SubgroupSummary {
+    fn from(entity: SubgroupEntity) -> Self {
+        Self {
+            id: entity.id,
+            name: entity.name,
+            slug: entity.slug,
+            icon_emoji: entity.icon_emoji,
+            member_count: entity.member_count,
+        }
+    }
+}
+
 /// Member entity with user info for listing members.
 #[derive(Debug, Clone, FromRow)]
 pub struct MemberWithUserEntity {
@@ -132,6 +157,7 @@ pub struct MemberWithUserEntity {
     pub role: GroupRoleDb,
     pub invited_by: Option<Uuid>,
     pub joined_at: DateTime<Utc>,
+    pub nickname: Option<String>,
     // User fields
     pub display_name: Option<String>,
     pub avatar_url: Option<String>,
@@ -0,0 +1,61 @@
+//! Group API token entity (database row mapping).
+
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database row mapping for the group_api_tokens table.
+#[derive(Debug, Clone, FromRow)]
+pub struct GroupApiTokenEntity {
+    pub id: Uuid,
+    pub group_id: Uuid,
+    pub name: String,
+    pub token_prefix: String,
+    /// SHA-256 hash of the token; the token itself is not stored.
+    pub token_hash: String,
+    pub rate_limit_per_minute: i32,
+    pub created_by: Option<Uuid>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl GroupApiTokenEntity {
+    /// Whether the token can be used at `now`.
+    pub fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_is_active_at() {
+        let now = Utc::now();
+        let mut token = GroupApiTokenEntity {
+            id: Uuid::new_v4(),
+            group_id: Uuid::new_v4(),
+            name: "Dashboard".to_string(),
+            token_prefix: "pm_grp_aBcD1234".to_string(),
+            token_hash: "abc".to_string(),
+            rate_limit_per_minute: 30,
+            created_by: None,
+            last_used_at: None,
+            expires_at: None,
+            revoked_at: None,
+            created_at: now,
+        };
+        assert!(token.is_active_at(now));
+
+        token.expires_at = Some(now + Duration::days(1));
+        assert!(token.is_active_at(now));
+        assert!(!token.is_active_at(now + Duration::days(2)));
+
+        token.revoked_at = Some(now);
+        assert!(!token.is_active_at(now));
+    }
+}
@@ -0,0 +1,44 @@
+//! Custom group role entity (database row mapping).
+
+use chrono::{DateTime, Utc};
+use domain::models::{GroupCustomRole, GroupPermissions};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database row mapping for the group_custom_roles table, with the number
+/// of group members holding the role.
+#[derive(Debug, Clone, FromRow)]
+pub struct GroupCustomRoleEntity {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub permissions: i32,
+    pub member_count: i64,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl GroupCustomRoleEntity {
+    /// Permissions granted by the role.
+    pub fn permissions(&self) -> GroupPermissions {
+        GroupPermissions::from_bits(self.permissions)
+    }
+}
+
+impl From<GroupCustomRoleEntity> for GroupCustomRole {
+    fn from(entity: GroupCustomRoleEntity) -> Self {
+        Self {
+            id: entity.id,
+            organization_id: entity.organization_id,
+            permissions: entity.permissions().to_vec(),
+            name: entity.name,
+            description: entity.description,
+            member_count: entity.member_count,
+            created_by: entity.created_by,
+            created_at: entity.created_at,
+            updated_at: entity.updated_at,
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! Group event entity (database row mapping).
+//!
+//! Maps to the `group_events` table.
+
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database row mapping for the group_events table.
+#[derive(Debug, Clone, FromRow)]
+pub struct GroupEventEntity {
+    pub id: i64,
+    pub event_id: Uuid,
+    pub group_id: Uuid,
+    pub event_type: String,
+    pub device_id: Option<Uuid>,
+    pub actor_user_id: Option<Uuid>,
+    pub payload: serde_json::Value,
+    pub occurred_at: DateTime<Utc>,
+    /// Owner of the event's device, if any.
+    pub device_owner_id: Option<Uuid>,
+}
+
+impl From<GroupEventEntity> for domain::models::GroupEvent {
+    fn from(entity: GroupEventEntity) -> Self {
+        Self {
+            event_id: entity.event_id,
+            group_id: entity.group_id,
+            event_type: entity.event_type,
+            device_id: entity.device_id,
+            actor_user_id: entity.actor_user_id,
+            payload: entity.payload,
+            occurred_at: entity.occurred_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_event_entity_to_domain() {
+        let device_id = Uuid::new_v4();
+        let entity = GroupEventEntity {
+            id: 42,
+            event_id: Uuid::new_v4(),
+            group_id: Uuid::new_v4(),
+            event_type: "device_added".to_string(),
+            device_id: Some(device_id),
+            actor_user_id: None,
+            payload: serde_json::json!({"display_name": "Phone"}),
+            occurred_at: Utc::now(),
+            device_owner_id: None,
+        };
+        let event_id = entity.event_id;
+
+        let event: domain::models::GroupEvent = entity.into();
+        assert_eq!(event.event_id, event_id);
+        assert_eq!(event.event_type, "device_added");
+        assert_eq!(event.device_id, Some(device_id));
+        assert_eq!(event.payload["display_name"], "Phone");
+    }
+}
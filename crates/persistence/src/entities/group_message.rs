@@ -0,0 +1,79 @@
+//! Group message entity (database row mapping).
+//!
+//! Maps to the `group_messages` table joined with the sender's display name.
+
+use chrono::{DateTime, Utc};
+use domain::models::{CheckInLocation, GroupMessageKind};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database row mapping for the group_messages table.
+#[derive(Debug, Clone, FromRow)]
+pub struct GroupMessageEntity {
+    pub id: i64,
+    pub message_id: Uuid,
+    pub group_id: Uuid,
+    pub sender_user_id: Option<Uuid>,
+    pub sender_display_name: Option<String>,
+    pub kind: String,
+    pub body: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub place_name: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<GroupMessageEntity> for domain::models::GroupMessage {
+    fn from(entity: GroupMessageEntity) -> Self {
+        let location = match (entity.latitude, entity.longitude) {
+            (Some(latitude), Some(longitude)) => Some(CheckInLocation {
+                latitude,
+                longitude,
+                place_name: entity.place_name,
+            }),
+            _ => None,
+        };
+        Self {
+            message_id: entity.message_id,
+            group_id: entity.group_id,
+            sender_user_id: entity.sender_user_id,
+            sender_display_name: entity.sender_display_name,
+            // The table only allows known kinds
+            kind: entity
+                .kind
+                .parse()
+                .unwrap_or(GroupMessageKind::Announcement),
+            body: entity.body,
+            location,
+            created_at: entity.created_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain::models::GroupMessage;
+
+    #[test]
+    fn test_check_in_entity_to_domain() {
+        let entity = GroupMessageEntity {
+            id: 7,
+            message_id: Uuid::new_v4(),
+            group_id: Uuid::new_v4(),
+            sender_user_id: Some(Uuid::new_v4()),
+            sender_display_name: Some("Alex".to_string()),
+            kind: "check_in".to_string(),
+            body: None,
+            latitude: Some(48.1486),
+            longitude: Some(17.1077),
+            place_name: Some("School".to_string()),
+            created_at: Utc::now(),
+        };
+        let message: GroupMessage = entity.into();
+        assert_eq!(message.kind, GroupMessageKind::CheckIn);
+        let location = message.location.unwrap();
+        assert_eq!(location.latitude, 48.1486);
+        assert_eq!(location.place_name.as_deref(), Some("School"));
+    }
+}
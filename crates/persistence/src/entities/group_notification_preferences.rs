@@ -0,0 +1,29 @@
+//! Group notification preferences entity (database row mapping).
+
+use chrono::{DateTime, Utc};
+use domain::models::GroupNotificationPreferences;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database row mapping for the group_notification_preferences table.
+#[derive(Debug, Clone, FromRow)]
+pub struct GroupNotificationPreferencesEntity {
+    pub group_id: Uuid,
+    pub user_id: Uuid,
+    pub geofence_events: bool,
+    pub sos: bool,
+    pub low_battery: bool,
+    pub member_joins: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<&GroupNotificationPreferencesEntity> for GroupNotificationPreferences {
+    fn from(entity: &GroupNotificationPreferencesEntity) -> Self {
+        Self {
+            geofence_events: entity.geofence_events,
+            sos: entity.sos,
+            low_battery: entity.low_battery,
+            member_joins: entity.member_joins,
+        }
+    }
+}
@@ -19,6 +19,9 @@ pub struct GroupInviteEntity {
     pub created_by: Uuid,
     pub created_at: DateTime<Utc>,
     pub is_active: bool,
+    /// Email the invite was sent to, if it was sent by email
+    pub recipient_email: Option<String>,
+    pub status: String,
 }
 
 /// Invite entity with creator info for listing.
@@ -33,6 +36,8 @@ pub struct InviteWithCreatorEntity {
     pub expires_at: DateTime<Utc>,
     pub created_by: Uuid,
     pub created_at: DateTime<Utc>,
+    pub recipient_email: Option<String>,
+    pub status: String,
     // Creator info
     pub creator_display_name: Option<String>,
 }
@@ -48,6 +53,7 @@ pub struct InviteWithGroupEntity {
     pub current_uses: i32,
     pub expires_at: DateTime<Utc>,
     pub is_active: bool,
+    pub recipient_email: Option<String>,
     // Group info
     pub group_name: String,
     pub group_icon_emoji: Option<String>,
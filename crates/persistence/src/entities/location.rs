@@ -24,6 +24,8 @@ pub struct LocationEntity {
     pub transportation_mode: Option<String>,
     pub detection_source: Option<String>,
     pub trip_id: Option<Uuid>,
+    pub source: Option<String>,
+    pub trust_score: Option<i16>, // SMALLINT in PostgreSQL
 }
 
 impl From<LocationEntity> for domain::models::Location {
@@ -45,6 +47,8 @@ impl From<LocationEntity> for domain::models::Location {
             transportation_mode: entity.transportation_mode,
             detection_source: entity.detection_source,
             trip_id: entity.trip_id,
+            source: entity.source,
+            trust_score: entity.trust_score.map(i32::from), // i16 → i32
         }
     }
 }
@@ -71,6 +75,8 @@ mod tests {
             transportation_mode: None,
             detection_source: None,
             trip_id: None,
+            source: None,
+            trust_score: None,
         }
     }
 
@@ -131,6 +137,8 @@ mod tests {
             transportation_mode: None,
             detection_source: None,
             trip_id: None,
+            source: None,
+            trust_score: None,
         };
 
         let location: domain::models::Location = entity.into();
@@ -0,0 +1,45 @@
+//! Location import job entity (database row mapping).
+
+use chrono::{DateTime, Utc};
+use domain::models::{LocationImportJob, LocationImportStatus};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database row mapping for the location_import_jobs table.
+#[derive(Debug, Clone, FromRow)]
+pub struct LocationImportJobEntity {
+    pub id: Uuid,
+    pub device_id: Uuid,
+    pub created_by: Uuid,
+    pub source: String,
+    pub status: String,
+    pub file_path: String,
+    pub total_records: i32,
+    pub imported_records: i32,
+    pub duplicate_records: i32,
+    pub invalid_records: i32,
+    pub error_message: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<LocationImportJobEntity> for LocationImportJob {
+    fn from(entity: LocationImportJobEntity) -> Self {
+        Self {
+            id: entity.id,
+            device_id: entity.device_id,
+            source: entity.source,
+            status: LocationImportStatus::from(entity.status.as_str()),
+            total_records: entity.total_records,
+            imported_records: entity.imported_records,
+            duplicate_records: entity.duplicate_records,
+            invalid_records: entity.invalid_records,
+            error_message: entity.error_message,
+            created_at: entity.created_at,
+            started_at: entity.started_at,
+            completed_at: entity.completed_at,
+        }
+    }
+}
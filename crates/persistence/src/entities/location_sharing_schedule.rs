@@ -0,0 +1,34 @@
+//! Location sharing schedule entity (database row mapping).
+
+use chrono::{DateTime, Utc};
+use domain::models::WeeklySchedule;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database row mapping for the location_sharing_schedules table.
+#[derive(Debug, Clone, FromRow)]
+pub struct LocationSharingScheduleEntity {
+    pub user_id: Uuid,
+    pub group_id: Uuid,
+    pub schedule: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<LocationSharingScheduleEntity> for domain::models::LocationSharingSchedule {
+    fn from(entity: LocationSharingScheduleEntity) -> Self {
+        Self {
+            user_id: entity.user_id,
+            group_id: entity.group_id,
+            // Schedules are validated on write; an unreadable one never shares.
+            schedule: WeeklySchedule::from_value(&entity.schedule)
+                .ok()
+                .flatten()
+                .unwrap_or(WeeklySchedule {
+                    utc_offset_minutes: 0,
+                    windows: Vec::new(),
+                }),
+            updated_at: entity.updated_at,
+        }
+    }
+}
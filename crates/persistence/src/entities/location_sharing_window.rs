@@ -0,0 +1,30 @@
+//! Location sharing window entity (database row mapping).
+
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database row mapping for the location_sharing_windows table.
+#[derive(Debug, Clone, FromRow)]
+pub struct LocationSharingWindowEntity {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub group_id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub stopped_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<LocationSharingWindowEntity> for domain::models::LocationSharingWindow {
+    fn from(entity: LocationSharingWindowEntity) -> Self {
+        Self {
+            id: entity.id,
+            user_id: entity.user_id,
+            group_id: entity.group_id,
+            started_at: entity.started_at,
+            expires_at: entity.expires_at,
+            stopped_at: entity.stopped_at,
+        }
+    }
+}
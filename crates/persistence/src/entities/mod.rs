@@ -8,37 +8,61 @@ pub mod admin_user;
 pub mod analytics;
 pub mod api_key;
 pub mod app_usage;
-pub mod audit_export_job;
 pub mod audit_log;
+pub mod commute;
 pub mod data_subject_request;
 pub mod device;
+pub mod device_api_usage;
 pub mod device_command;
+pub mod device_command_macro;
+pub mod device_config_history;
 pub mod device_group_membership;
 pub mod device_policy;
+pub mod device_push_token;
+pub mod device_telemetry;
 pub mod device_token;
 pub mod enrollment_token;
+pub mod event_outbox;
+pub mod export_job;
 pub mod geofence;
+pub mod geofence_arrival_forecast;
 pub mod geofence_event;
+pub mod geofence_template;
 pub mod group;
+pub mod group_api_token;
+pub mod group_custom_role;
+pub mod group_event;
+pub mod group_message;
+pub mod group_notification_preferences;
 pub mod idempotency_key;
 pub mod invite;
 pub mod location;
+pub mod location_import_job;
+pub mod location_sharing_schedule;
+pub mod location_sharing_window;
 pub mod managed_user;
 pub mod migration_audit;
 pub mod movement_event;
 pub mod org_member_invite;
+pub mod org_ownership_transfer;
 pub mod org_user;
 pub mod org_webhook;
 pub mod organization;
 pub mod organization_settings;
+pub mod privacy_zone;
 pub mod proximity_alert;
 pub mod registration_invite;
+pub mod report_template;
 pub mod setting;
 pub mod setting_change;
 pub mod system_config;
 pub mod system_role;
 pub mod trip;
+pub mod trip_edit;
 pub mod trip_path_correction;
+pub mod trip_rule;
+pub mod trip_share_link;
+pub mod trip_speed_violation;
 pub mod unlock_request;
 pub mod user;
 pub mod user_geofence;
@@ -47,7 +71,7 @@ pub mod webhook_delivery;
 
 pub use admin_geofence::{
     AdminGeofenceEntity, AdminGeofenceEventEntity, AdminGeofenceWithCreatorEntity,
-    GeofenceVisitCountEntity, LocationAnalyticsEntity,
+    GeofenceCategoryCountEntity, GeofenceVisitCountEntity, LocationAnalyticsEntity,
 };
 pub use admin_group::{
     AdminGroupEntity, AdminGroupProfileEntity, AdminGroupSummaryEntity, GroupDeviceEntity,
@@ -58,17 +82,20 @@ pub use admin_user::{
     UserDeviceEntity, UserGroupEntity,
 };
 pub use analytics::{
-    ApiUsageDailyEntity, ApiUsageSummaryEntity, DeviceActivityDailyEntity,
-    DeviceAnalyticsSummaryEntity, DeviceStatusCountEntity, EndpointUsageEntity, ReportJobEntity,
-    RoleCountEntity, UserActivityDailyEntity, UserAnalyticsSummaryEntity,
+    ApiUsageDailyEntity, ApiUsageSummaryEntity, AppUsageReportMetricsEntity,
+    DeviceActivityDailyEntity, DeviceAnalyticsSummaryEntity, DeviceReportMetricsEntity,
+    DeviceStatusCountEntity, EndpointUsageEntity, GeofenceEventExtractEntity,
+    GeofenceReportMetricsEntity, LocationExtractEntity, ReportJobEntity, RoleCountEntity,
+    TripDistanceDailyEntity, TripMileageEntity, TripReportMetricsEntity, UserActivityDailyEntity,
+    UserAnalyticsSummaryEntity,
 };
 pub use api_key::ApiKeyEntity;
 pub use app_usage::{
     AnalyticsTrendEntity, AppUsageDailyAggregateEntity, AppUsageEntity, AppUsageSummaryEntity,
     CategoryUsageEntity, OrgAnalyticsSummaryEntity, TopAppEntity,
 };
-pub use audit_export_job::AuditExportJobEntity;
-pub use audit_log::AuditLogEntity;
+pub use audit_log::{AuditExportCursorEntity, AuditLogEntity, SequencedAuditLogEntity};
+pub use commute::DeviceCommuteEntity;
 pub use data_subject_request::{
     DataSubjectRequestEntity, DataSubjectRequestStatusDb, DataSubjectRequestTypeDb,
     DataSubjectRequestWithProcessorEntity,
@@ -76,35 +103,57 @@ pub use data_subject_request::{
 pub use device::{
     DeviceEntity, DeviceWithLastLocationEntity, FleetDeviceEntity, MemberDeviceEntity,
 };
+pub use device_api_usage::DeviceApiUsageEntity;
 pub use device_command::DeviceCommandEntity;
+pub use device_command_macro::{
+    DeviceCommandMacroEntity, DeviceCommandMacroRunEntity, MacroRunCommandEntity,
+};
+pub use device_config_history::{DeviceSettingVersionEntity, PolicyAssignmentCountEntity};
 pub use device_group_membership::{
     DeviceGroupInfoEntity, DeviceGroupMembershipEntity, DeviceInGroupEntity,
-    DeviceInGroupWithLocationEntity,
+    DeviceInGroupWithLocationEntity, NearbyDeviceInGroupEntity,
 };
 pub use device_policy::DevicePolicyEntity;
+pub use device_push_token::DevicePushTokenEntity;
+pub use device_telemetry::DeviceTelemetryEntity;
 pub use device_token::DeviceTokenEntity;
 pub use enrollment_token::EnrollmentTokenEntity;
+pub use event_outbox::EventOutboxEntity;
+pub use export_job::ExportJobEntity;
 pub use geofence::GeofenceEntity;
+pub use geofence_arrival_forecast::GeofenceArrivalForecastEntity;
 pub use geofence_event::{GeofenceEventEntity, GeofenceEventWithName};
+pub use geofence_template::GeofenceTemplateEntity;
 pub use group::{
     GroupEntity, GroupMembershipEntity, GroupRoleDb, GroupWithMembershipEntity,
-    MemberWithUserEntity,
+    MemberWithUserEntity, SubgroupEntity,
 };
+pub use group_api_token::GroupApiTokenEntity;
+pub use group_custom_role::GroupCustomRoleEntity;
+pub use group_event::GroupEventEntity;
+pub use group_message::GroupMessageEntity;
+pub use group_notification_preferences::GroupNotificationPreferencesEntity;
 pub use idempotency_key::IdempotencyKeyEntity;
 pub use invite::{GroupInviteEntity, InviteWithCreatorEntity, InviteWithGroupEntity};
 pub use location::LocationEntity;
+pub use location_import_job::LocationImportJobEntity;
+pub use location_sharing_schedule::LocationSharingScheduleEntity;
+pub use location_sharing_window::LocationSharingWindowEntity;
 pub use managed_user::{ManagedUserEntity, UserLocationEntity};
 pub use migration_audit::{
     MigrationAuditLogEntity, MigrationAuditLogWithUserEntity, MigrationStatusDb,
 };
 pub use movement_event::MovementEventEntity;
 pub use org_member_invite::OrgMemberInviteEntity;
+pub use org_ownership_transfer::OrgOwnershipTransferEntity;
 pub use org_user::{OrgUserEntity, OrgUserRoleDb, OrgUserWithDetailsEntity};
 pub use org_webhook::OrgWebhookEntity;
 pub use organization::{OrganizationEntity, OrganizationWithUsageEntity, PlanTypeDb};
 pub use organization_settings::OrganizationSettingsEntity;
+pub use privacy_zone::PrivacyZoneEntity;
 pub use proximity_alert::ProximityAlertEntity;
 pub use registration_invite::RegistrationInviteEntity;
+pub use report_template::ReportTemplateEntity;
 pub use setting::{
     DeviceSettingEntity, DeviceSettingWithDefinitionEntity, SettingCategoryDb, SettingDataTypeDb,
     SettingDefinitionEntity, SettingLockEntity,
@@ -118,7 +167,11 @@ pub use system_role::{
     AdminOrgAssignmentEntity, AdminOrgAssignmentWithNameEntity, SystemRoleDb, UserSystemRoleEntity,
 };
 pub use trip::TripEntity;
+pub use trip_edit::TripEditEntity;
 pub use trip_path_correction::TripPathCorrectionEntity;
+pub use trip_rule::TripRuleEntity;
+pub use trip_share_link::TripShareLinkEntity;
+pub use trip_speed_violation::TripSpeedViolationEntity;
 pub use unlock_request::{
     UnlockRequestEntity, UnlockRequestStatusDb, UnlockRequestWithDetailsEntity,
 };
@@ -126,6 +179,5 @@ pub use user::{OAuthAccountEntity, UserEntity, UserSessionEntity};
 pub use user_geofence::{UserGeofenceEntity, UserGeofenceWithCreatorEntity};
 pub use webhook::WebhookEntity;
 pub use webhook_delivery::{
-    WebhookDeliveryEntity, MAX_RETRY_ATTEMPTS, RETRY_BACKOFF_SECONDS, STATUS_FAILED,
-    STATUS_PENDING, STATUS_SUCCESS,
+    WebhookDeadLetterEntity, WebhookDeliveryEntity, STATUS_FAILED, STATUS_PENDING, STATUS_SUCCESS,
 };
@@ -23,6 +23,7 @@ pub struct MovementEventEntity {
     pub transportation_mode: String,
     pub confidence: f32,
     pub detection_source: String,
+    pub movement_state: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -55,6 +56,7 @@ impl MovementEventEntity {
             transportation_mode,
             confidence: self.confidence as f64,
             detection_source,
+            movement_state: self.movement_state.and_then(|s| s.parse().ok()),
             created_at: self.created_at,
         }
     }
@@ -86,6 +88,7 @@ mod tests {
             transportation_mode: "WALKING".to_string(),
             confidence: 0.95,
             detection_source: "ACTIVITY_RECOGNITION".to_string(),
+            movement_state: None,
             created_at: Utc::now(),
         }
     }
@@ -128,6 +131,20 @@ mod tests {
         assert_eq!(event.detection_source, DetectionSource::None);
     }
 
+    #[test]
+    fn test_entity_with_movement_state() {
+        let mut entity = create_test_entity();
+        entity.detection_source = "LOCATION_STREAM".to_string();
+        entity.movement_state = Some("ARRIVED".to_string());
+
+        let event: domain::models::MovementEvent = entity.into();
+        assert_eq!(event.detection_source, DetectionSource::LocationStream);
+        assert_eq!(
+            event.movement_state,
+            Some(domain::models::movement_event::MovementState::Arrived)
+        );
+    }
+
     #[test]
     fn test_entity_with_no_optional_fields() {
         let entity = MovementEventEntity {
@@ -144,6 +161,7 @@ mod tests {
             transportation_mode: "STATIONARY".to_string(),
             confidence: 0.0,
             detection_source: "NONE".to_string(),
+            movement_state: None,
             created_at: Utc::now(),
         };
 
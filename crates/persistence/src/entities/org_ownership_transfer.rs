@@ -0,0 +1,34 @@
+//! Organization ownership transfer entity (database row mapping).
+
+use chrono::{DateTime, Utc};
+use domain::models::{OrgOwnershipTransfer, OrgTransferStatus};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database row mapping for the org_ownership_transfers table.
+#[derive(Debug, Clone, FromRow)]
+pub struct OrgOwnershipTransferEntity {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub from_user_id: Uuid,
+    pub to_user_id: Uuid,
+    pub status: String,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl From<OrgOwnershipTransferEntity> for OrgOwnershipTransfer {
+    fn from(entity: OrgOwnershipTransferEntity) -> Self {
+        Self {
+            id: entity.id,
+            organization_id: entity.organization_id,
+            from_user_id: entity.from_user_id,
+            to_user_id: entity.to_user_id,
+            status: OrgTransferStatus::from(entity.status.as_str()),
+            expires_at: entity.expires_at,
+            created_at: entity.created_at,
+            resolved_at: entity.resolved_at,
+        }
+    }
+}
@@ -12,6 +12,7 @@ pub enum OrgUserRoleDb {
     Owner,
     Admin,
     Member,
+    Observer,
 }
 
 impl From<OrgUserRoleDb> for domain::models::OrgUserRole {
@@ -20,6 +21,7 @@ impl From<OrgUserRoleDb> for domain::models::OrgUserRole {
             OrgUserRoleDb::Owner => Self::Owner,
             OrgUserRoleDb::Admin => Self::Admin,
             OrgUserRoleDb::Member => Self::Member,
+            OrgUserRoleDb::Observer => Self::Observer,
         }
     }
 }
@@ -30,6 +32,7 @@ impl From<domain::models::OrgUserRole> for OrgUserRoleDb {
             domain::models::OrgUserRole::Owner => Self::Owner,
             domain::models::OrgUserRole::Admin => Self::Admin,
             domain::models::OrgUserRole::Member => Self::Member,
+            domain::models::OrgUserRole::Observer => Self::Observer,
         }
     }
 }
@@ -1,6 +1,7 @@
 //! Organization webhook entity (database row mapping).
 
 use chrono::{DateTime, Utc};
+use domain::models::{WebhookPayloadFormat, WebhookRetryPolicy};
 use sqlx::FromRow;
 use uuid::Uuid;
 
@@ -17,8 +18,51 @@ pub struct OrgWebhookEntity {
     pub event_types: Vec<String>,
     pub consecutive_failures: i32,
     pub circuit_open_until: Option<DateTime<Utc>>,
+    pub retry_policy: Option<serde_json::Value>,
+    /// PEM client certificate (chain) presented for mutual TLS.
+    pub client_cert_pem: Option<String>,
+    /// Private key of the client certificate, AES-256-GCM encrypted.
+    pub client_key_encrypted: Option<Vec<u8>>,
+    pub payload_format: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Retry policy set for all of the organization's webhooks, from its settings.
+    pub retry_policy_override: Option<serde_json::Value>,
+}
+
+impl OrgWebhookEntity {
+    /// Retry policy of the webhook's deliveries: the organization's override
+    /// if one is set, otherwise the webhook's own policy.
+    ///
+    /// Policies are validated on write; an unreadable one is treated as unset.
+    pub fn delivery_policy(&self) -> WebhookRetryPolicy {
+        self.override_policy()
+            .or_else(|| parse_policy(self.retry_policy.as_ref()))
+            .unwrap_or_default()
+    }
+
+    /// Whether the organization's override replaces the webhook's own policy.
+    pub fn retry_policy_overridden(&self) -> bool {
+        self.override_policy().is_some()
+    }
+
+    fn override_policy(&self) -> Option<WebhookRetryPolicy> {
+        parse_policy(self.retry_policy_override.as_ref())
+    }
+
+    /// Shape of the webhook's delivery payloads.
+    pub fn payload_format(&self) -> WebhookPayloadFormat {
+        self.payload_format.parse().unwrap_or_default()
+    }
+
+    /// Whether deliveries present a client certificate.
+    pub fn has_client_certificate(&self) -> bool {
+        self.client_cert_pem.is_some() && self.client_key_encrypted.is_some()
+    }
+}
+
+fn parse_policy(value: Option<&serde_json::Value>) -> Option<WebhookRetryPolicy> {
+    value.and_then(|value| WebhookRetryPolicy::from_value(value).ok().flatten())
 }
 
 #[cfg(test)]
@@ -37,8 +81,13 @@ mod tests {
             event_types: vec!["device.enrolled".to_string(), "member.joined".to_string()],
             consecutive_failures: 0,
             circuit_open_until: None,
+            retry_policy: None,
+            client_cert_pem: None,
+            client_key_encrypted: None,
+            payload_format: "default".to_string(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            retry_policy_override: None,
         }
     }
 
@@ -51,6 +100,32 @@ mod tests {
         assert_eq!(entity.event_types.len(), 2);
     }
 
+    #[test]
+    fn test_delivery_policy() {
+        let mut entity = create_test_org_webhook_entity();
+        assert_eq!(entity.delivery_policy(), WebhookRetryPolicy::default());
+
+        entity.retry_policy = Some(serde_json::json!({"max_attempts": 7}));
+        assert_eq!(entity.delivery_policy().max_attempts, 7);
+
+        entity.retry_policy = Some(serde_json::json!({"max_attempts": 0}));
+        assert_eq!(entity.delivery_policy(), WebhookRetryPolicy::default());
+        assert!(!entity.retry_policy_overridden());
+    }
+
+    #[test]
+    fn test_delivery_policy_override() {
+        let mut entity = create_test_org_webhook_entity();
+        entity.retry_policy = Some(serde_json::json!({"max_attempts": 7}));
+        entity.retry_policy_override = Some(serde_json::json!({"max_attempts": 2}));
+        assert_eq!(entity.delivery_policy().max_attempts, 2);
+        assert!(entity.retry_policy_overridden());
+
+        entity.retry_policy_override = Some(serde_json::json!({"max_attempts": 0}));
+        assert_eq!(entity.delivery_policy().max_attempts, 7);
+        assert!(!entity.retry_policy_overridden());
+    }
+
     #[test]
     fn test_org_webhook_entity_clone() {
         let entity = create_test_org_webhook_entity();
@@ -13,6 +13,7 @@ pub enum PlanTypeDb {
     Starter,
     Business,
     Enterprise,
+    Trial,
 }
 
 impl From<PlanTypeDb> for domain::models::PlanType {
@@ -22,6 +23,7 @@ impl From<PlanTypeDb> for domain::models::PlanType {
             PlanTypeDb::Starter => Self::Starter,
             PlanTypeDb::Business => Self::Business,
             PlanTypeDb::Enterprise => Self::Enterprise,
+            PlanTypeDb::Trial => Self::Trial,
         }
     }
 }
@@ -33,6 +35,7 @@ impl From<domain::models::PlanType> for PlanTypeDb {
             domain::models::PlanType::Starter => Self::Starter,
             domain::models::PlanType::Business => Self::Business,
             domain::models::PlanType::Enterprise => Self::Enterprise,
+            domain::models::PlanType::Trial => Self::Trial,
         }
     }
 }
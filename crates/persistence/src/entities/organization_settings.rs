@@ -1,6 +1,7 @@
 //! Organization settings entity (database row mapping).
 
 use chrono::{DateTime, Utc};
+use domain::models::WebhookRetryPolicy;
 use sqlx::FromRow;
 use uuid::Uuid;
 
@@ -13,6 +14,10 @@ pub struct OrganizationSettingsEntity {
     pub default_daily_limit_minutes: i32,
     pub notifications_enabled: bool,
     pub auto_approve_unlock_requests: bool,
+    pub max_location_accuracy_meters: Option<f32>,
+    pub max_location_speed_mps: Option<f32>,
+    pub blocked_terms: Vec<String>,
+    pub webhook_retry_policy: Option<serde_json::Value>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -27,6 +32,13 @@ impl From<OrganizationSettingsEntity> for domain::models::OrganizationSettings {
             default_daily_limit_minutes: entity.default_daily_limit_minutes,
             notifications_enabled: entity.notifications_enabled,
             auto_approve_unlock_requests: entity.auto_approve_unlock_requests,
+            max_location_accuracy_meters: entity.max_location_accuracy_meters.map(f64::from),
+            max_location_speed_mps: entity.max_location_speed_mps.map(f64::from),
+            blocked_terms: entity.blocked_terms,
+            webhook_retry_policy: entity
+                .webhook_retry_policy
+                .as_ref()
+                .and_then(|value| WebhookRetryPolicy::from_value(value).ok().flatten()),
             created_at: entity.created_at,
             updated_at: entity.updated_at,
         }
@@ -46,6 +58,10 @@ mod tests {
             default_daily_limit_minutes: 120,
             notifications_enabled: true,
             auto_approve_unlock_requests: false,
+            max_location_accuracy_meters: None,
+            max_location_speed_mps: None,
+            blocked_terms: vec![],
+            webhook_retry_policy: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -64,6 +80,10 @@ mod tests {
             default_daily_limit_minutes: 60,
             notifications_enabled: false,
             auto_approve_unlock_requests: true,
+            max_location_accuracy_meters: Some(100.0),
+            max_location_speed_mps: Some(70.0),
+            blocked_terms: vec!["darn".to_string()],
+            webhook_retry_policy: Some(serde_json::json!({"max_attempts": 2})),
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -71,5 +91,6 @@ mod tests {
         let domain: domain::models::OrganizationSettings = entity.into();
         assert!(!domain.has_unlock_pin);
         assert!(domain.unlock_pin_hash.is_none());
+        assert_eq!(domain.webhook_retry_policy.unwrap().max_attempts, 2);
     }
 }
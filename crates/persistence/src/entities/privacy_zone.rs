@@ -0,0 +1,78 @@
+//! Privacy zone entity (database row mapping).
+
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database row mapping for the privacy_zones table.
+#[derive(Debug, Clone, FromRow)]
+pub struct PrivacyZoneEntity {
+    pub id: i64,
+    pub zone_id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub radius_meters: f32,
+    pub mode: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<PrivacyZoneEntity> for domain::models::PrivacyZone {
+    fn from(entity: PrivacyZoneEntity) -> Self {
+        Self {
+            id: entity.zone_id,
+            user_id: entity.user_id,
+            name: entity.name,
+            latitude: entity.latitude,
+            longitude: entity.longitude,
+            radius_meters: entity.radius_meters,
+            // Unknown modes fall back to suppression so data is never
+            // shared more precisely than intended.
+            mode: entity
+                .mode
+                .parse()
+                .unwrap_or(domain::models::PrivacyZoneMode::Suppress),
+            created_at: entity.created_at,
+            updated_at: entity.updated_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain::models::{PrivacyZone, PrivacyZoneMode};
+
+    fn entity(mode: &str) -> PrivacyZoneEntity {
+        PrivacyZoneEntity {
+            id: 1,
+            zone_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            name: "Home".to_string(),
+            latitude: 48.1486,
+            longitude: 17.1077,
+            radius_meters: 150.0,
+            mode: mode.to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_privacy_zone_entity_to_domain() {
+        let e = entity("snap");
+        let zone_id = e.zone_id;
+        let zone: PrivacyZone = e.into();
+        assert_eq!(zone.id, zone_id);
+        assert_eq!(zone.mode, PrivacyZoneMode::Snap);
+        assert_eq!(zone.radius_meters, 150.0);
+    }
+
+    #[test]
+    fn test_unknown_mode_falls_back_to_suppress() {
+        let zone: PrivacyZone = entity("blur").into();
+        assert_eq!(zone.mode, PrivacyZoneMode::Suppress);
+    }
+}
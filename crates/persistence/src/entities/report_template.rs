@@ -0,0 +1,81 @@
+//! Report template entity (database row mapping).
+
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database row mapping for the report_templates table.
+#[derive(Debug, Clone, FromRow)]
+pub struct ReportTemplateEntity {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub definition: serde_json::Value,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<ReportTemplateEntity> for domain::models::ReportTemplate {
+    fn from(entity: ReportTemplateEntity) -> Self {
+        Self {
+            id: entity.id,
+            organization_id: entity.organization_id,
+            name: entity.name,
+            description: entity.description,
+            definition: serde_json::from_value(entity.definition).unwrap_or_default(),
+            created_by: entity.created_by,
+            created_at: entity.created_at,
+            updated_at: entity.updated_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain::models::{ReportFormat, ReportSection, ReportTemplate};
+
+    #[test]
+    fn test_entity_to_domain() {
+        let entity = ReportTemplateEntity {
+            id: Uuid::new_v4(),
+            organization_id: Uuid::new_v4(),
+            name: "Weekly".to_string(),
+            description: Some("Fleet overview".to_string()),
+            definition: serde_json::json!({
+                "sections": ["devices", "geofence_compliance"],
+                "group_by": ["week"],
+                "format": "pdf"
+            }),
+            created_by: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        let template: ReportTemplate = entity.into();
+        assert_eq!(
+            template.definition.sections,
+            vec![ReportSection::Devices, ReportSection::GeofenceCompliance]
+        );
+        assert_eq!(template.definition.format, ReportFormat::Pdf);
+    }
+
+    #[test]
+    fn test_entity_with_invalid_definition_falls_back_to_default() {
+        let entity = ReportTemplateEntity {
+            id: Uuid::new_v4(),
+            organization_id: Uuid::new_v4(),
+            name: "Broken".to_string(),
+            description: None,
+            definition: serde_json::json!({"sections": "nope"}),
+            created_by: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        let template: ReportTemplate = entity.into();
+        assert!(template.definition.sections.is_empty());
+    }
+}
@@ -24,6 +24,8 @@ pub struct TripEntity {
     pub detection_source: String,
     pub distance_meters: Option<f64>,
     pub duration_seconds: Option<i64>,
+    pub purpose: Option<String>,
+    pub purpose_source: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -61,6 +63,8 @@ impl TripEntity {
             detection_source,
             distance_meters: self.distance_meters,
             duration_seconds: self.duration_seconds,
+            purpose: self.purpose,
+            purpose_source: self.purpose_source.as_deref().and_then(|s| s.parse().ok()),
             created_at: self.created_at,
             updated_at: self.updated_at,
         }
@@ -95,6 +99,8 @@ mod tests {
             detection_source: "ACTIVITY_RECOGNITION".to_string(),
             distance_meters: None,
             duration_seconds: None,
+            purpose: None,
+            purpose_source: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         }
@@ -0,0 +1,19 @@
+//! Trip edit entity (database row mapping).
+
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database row mapping for the trip_edits table.
+#[derive(Debug, Clone, FromRow)]
+pub struct TripEditEntity {
+    pub id: Uuid,
+    pub device_id: Uuid,
+    /// `MERGE` or `SPLIT`.
+    pub edit_type: String,
+    pub source_trip_ids: Vec<Uuid>,
+    pub result_trip_ids: Vec<Uuid>,
+    pub split_at: Option<i64>,
+    pub api_key_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,83 @@
+//! Trip classification rule entity (database row mapping).
+
+use chrono::{DateTime, Utc};
+use domain::models::trip_purpose::{format_optional_minute, TripRuleResponse};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use super::commute::weekday_from_number;
+
+/// Database row mapping for the trip_classification_rules table.
+#[derive(Debug, Clone, FromRow)]
+pub struct TripRuleEntity {
+    pub id: Uuid,
+    pub device_id: Uuid,
+    pub name: String,
+    pub purpose: String,
+    pub priority: i32,
+    pub start_geofence_id: Option<Uuid>,
+    pub end_geofence_id: Option<Uuid>,
+    /// ISO weekday numbers, 1 = Monday.
+    pub weekdays: Vec<i16>,
+    /// Minutes after midnight UTC.
+    pub departure_after_minute: Option<i32>,
+    pub departure_before_minute: Option<i32>,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<TripRuleEntity> for TripRuleResponse {
+    fn from(entity: TripRuleEntity) -> Self {
+        Self {
+            id: entity.id,
+            device_id: entity.device_id,
+            name: entity.name,
+            purpose: entity.purpose,
+            priority: entity.priority,
+            start_geofence_id: entity.start_geofence_id,
+            end_geofence_id: entity.end_geofence_id,
+            weekdays: entity
+                .weekdays
+                .into_iter()
+                .filter_map(weekday_from_number)
+                .collect(),
+            departure_after: format_optional_minute(entity.departure_after_minute),
+            departure_before: format_optional_minute(entity.departure_before_minute),
+            enabled: entity.enabled,
+            created_at: entity.created_at,
+            updated_at: entity.updated_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Weekday;
+
+    #[test]
+    fn test_into_response() {
+        let now = Utc::now();
+        let entity = TripRuleEntity {
+            id: Uuid::new_v4(),
+            device_id: Uuid::new_v4(),
+            name: "Office".to_string(),
+            purpose: "business".to_string(),
+            priority: 10,
+            start_geofence_id: None,
+            end_geofence_id: Some(Uuid::new_v4()),
+            weekdays: vec![1, 5, 9],
+            departure_after_minute: Some(7 * 60 + 30),
+            departure_before_minute: None,
+            enabled: true,
+            created_at: now,
+            updated_at: now,
+        };
+
+        let response: TripRuleResponse = entity.into();
+        assert_eq!(response.weekdays, vec![Weekday::Mon, Weekday::Fri]);
+        assert_eq!(response.departure_after.as_deref(), Some("07:30"));
+        assert!(response.departure_before.is_none());
+    }
+}
@@ -0,0 +1,48 @@
+//! Trip share link entity (database row mapping).
+
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database row mapping for the trip_share_links table.
+#[derive(Debug, Clone, FromRow)]
+pub struct TripShareLinkEntity {
+    pub id: Uuid,
+    pub trip_id: Uuid,
+    /// SHA-256 hash of the link token; the token itself is not stored.
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TripShareLinkEntity {
+    /// Whether the link can be used at `now`.
+    pub fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at > now
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_is_active_at() {
+        let now = Utc::now();
+        let mut link = TripShareLinkEntity {
+            id: Uuid::new_v4(),
+            trip_id: Uuid::new_v4(),
+            token_hash: "abc".to_string(),
+            expires_at: now + Duration::hours(1),
+            revoked_at: None,
+            created_at: now,
+        };
+        assert!(link.is_active_at(now));
+        assert!(!link.is_active_at(now + Duration::hours(2)));
+
+        link.revoked_at = Some(now);
+        assert!(!link.is_active_at(now));
+    }
+}
@@ -0,0 +1,44 @@
+//! Trip speed violation entity (database row mapping).
+
+use chrono::{DateTime, Utc};
+use domain::models::speed_violation::SpeedViolationResponse;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database row mapping for the trip_speed_violations table.
+#[derive(Debug, Clone, FromRow)]
+pub struct TripSpeedViolationEntity {
+    pub id: Uuid,
+    pub trip_id: Uuid,
+    pub device_id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    pub start_latitude: f64,
+    pub start_longitude: f64,
+    pub end_latitude: f64,
+    pub end_longitude: f64,
+    pub speed_limit_kmh: f64,
+    pub max_speed_kmh: f64,
+    pub avg_speed_kmh: f64,
+    pub sample_count: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<TripSpeedViolationEntity> for SpeedViolationResponse {
+    fn from(entity: TripSpeedViolationEntity) -> Self {
+        Self {
+            id: entity.id,
+            trip_id: entity.trip_id,
+            started_at: entity.started_at,
+            ended_at: entity.ended_at,
+            start_latitude: entity.start_latitude,
+            start_longitude: entity.start_longitude,
+            end_latitude: entity.end_latitude,
+            end_longitude: entity.end_longitude,
+            speed_limit_kmh: entity.speed_limit_kmh,
+            max_speed_kmh: entity.max_speed_kmh,
+            avg_speed_kmh: entity.avg_speed_kmh,
+            sample_count: entity.sample_count,
+        }
+    }
+}
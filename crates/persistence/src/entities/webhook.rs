@@ -5,19 +5,30 @@ use sqlx::FromRow;
 use uuid::Uuid;
 
 use domain::models::webhook::Webhook;
+use domain::models::WebhookRetryPolicy;
 
 /// Database row mapping for the webhooks table.
 #[derive(Debug, Clone, FromRow)]
 pub struct WebhookEntity {
     pub id: i64,
     pub webhook_id: Uuid,
-    pub owner_device_id: Uuid,
+    pub owner_device_id: Option<Uuid>,
+    pub owner_user_id: Option<Uuid>,
+    pub device_ids: Option<Vec<Uuid>>,
     pub name: String,
     pub target_url: String,
     pub secret: String,
     pub enabled: bool,
     pub consecutive_failures: i32,
     pub circuit_open_until: Option<DateTime<Utc>>,
+    pub retry_policy: Option<serde_json::Value>,
+    pub failure_streak: i32,
+    pub auto_disabled_at: Option<DateTime<Utc>>,
+    pub payload_template: Option<serde_json::Value>,
+    pub payload_format: String,
+    pub max_deliveries_per_minute: Option<i32>,
+    pub batch_window_secs: Option<i32>,
+    pub batch_max_events: Option<i32>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -28,12 +39,25 @@ impl From<WebhookEntity> for Webhook {
             id: entity.id,
             webhook_id: entity.webhook_id,
             owner_device_id: entity.owner_device_id,
+            owner_user_id: entity.owner_user_id,
+            device_ids: entity.device_ids,
             name: entity.name,
             target_url: entity.target_url,
             secret: entity.secret,
             enabled: entity.enabled,
             consecutive_failures: entity.consecutive_failures,
             circuit_open_until: entity.circuit_open_until,
+            // Policies are validated on write; an unreadable one is treated as the default.
+            retry_policy: entity
+                .retry_policy
+                .and_then(|value| WebhookRetryPolicy::from_value(&value).ok().flatten())
+                .unwrap_or_default(),
+            payload_template: entity.payload_template,
+            payload_format: entity.payload_format.parse().unwrap_or_default(),
+            max_deliveries_per_minute: entity.max_deliveries_per_minute,
+            batch_window_secs: entity.batch_window_secs,
+            batch_max_events: entity.batch_max_events,
+            auto_disabled_at: entity.auto_disabled_at,
             created_at: entity.created_at,
             updated_at: entity.updated_at,
         }
@@ -48,13 +72,23 @@ mod tests {
         WebhookEntity {
             id: 1,
             webhook_id: Uuid::new_v4(),
-            owner_device_id: Uuid::new_v4(),
+            owner_device_id: Some(Uuid::new_v4()),
+            owner_user_id: None,
+            device_ids: None,
             name: "Home Assistant".to_string(),
             target_url: "https://example.com/webhook".to_string(),
             secret: "test-secret-key-12345678".to_string(),
             enabled: true,
             consecutive_failures: 0,
             circuit_open_until: None,
+            retry_policy: None,
+            failure_streak: 0,
+            auto_disabled_at: None,
+            payload_template: None,
+            payload_format: "default".to_string(),
+            max_deliveries_per_minute: None,
+            batch_window_secs: None,
+            batch_max_events: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         }
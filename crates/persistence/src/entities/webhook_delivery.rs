@@ -22,21 +22,69 @@ pub struct WebhookDeliveryEntity {
     pub next_retry_at: Option<DateTime<Utc>>,
     pub response_code: Option<i32>,
     pub error_message: Option<String>,
+    pub latency_ms: Option<i32>,
+    pub response_body: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
+impl From<WebhookDeliveryEntity> for domain::models::WebhookDeliveryResponse {
+    fn from(entity: WebhookDeliveryEntity) -> Self {
+        Self {
+            id: entity.delivery_id,
+            event_id: entity.event_id,
+            event_type: entity.event_type,
+            status: entity.status,
+            attempts: entity.attempts,
+            last_attempt_at: entity.last_attempt_at,
+            next_retry_at: entity.next_retry_at,
+            response_code: entity.response_code,
+            error_message: entity.error_message,
+            latency_ms: entity.latency_ms,
+            response_body: entity.response_body,
+            created_at: entity.created_at,
+        }
+    }
+}
+
+/// Database entity for webhook_dead_letters table.
+#[derive(Debug, Clone, FromRow)]
+pub struct WebhookDeadLetterEntity {
+    pub id: i64,
+    pub dead_letter_id: Uuid,
+    pub delivery_id: Uuid,
+    pub webhook_id: Uuid,
+    pub event_id: Option<Uuid>,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub attempts: i32,
+    pub response_code: Option<i32>,
+    pub error_message: Option<String>,
+    pub delivery_created_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<WebhookDeadLetterEntity> for domain::models::WebhookDeadLetterResponse {
+    fn from(entity: WebhookDeadLetterEntity) -> Self {
+        Self {
+            id: entity.dead_letter_id,
+            delivery_id: entity.delivery_id,
+            event_id: entity.event_id,
+            event_type: entity.event_type,
+            payload: entity.payload,
+            attempts: entity.attempts,
+            response_code: entity.response_code,
+            error_message: entity.error_message,
+            delivery_created_at: entity.delivery_created_at,
+            dead_lettered_at: entity.created_at,
+        }
+    }
+}
+
 /// Delivery status values.
 pub const STATUS_PENDING: &str = "pending";
 pub const STATUS_SUCCESS: &str = "success";
 pub const STATUS_FAILED: &str = "failed";
 
-/// Maximum number of retry attempts.
-pub const MAX_RETRY_ATTEMPTS: i32 = 4;
-
-/// Backoff intervals in seconds for each retry attempt.
-/// Attempt 1: immediate, Attempt 2: 60s, Attempt 3: 300s, Attempt 4: 900s
-pub const RETRY_BACKOFF_SECONDS: [i64; 4] = [0, 60, 300, 900];
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -49,11 +97,32 @@ mod tests {
     }
 
     #[test]
-    fn test_retry_constants() {
-        assert_eq!(MAX_RETRY_ATTEMPTS, 4);
-        assert_eq!(RETRY_BACKOFF_SECONDS[0], 0); // Immediate
-        assert_eq!(RETRY_BACKOFF_SECONDS[1], 60); // 1 minute
-        assert_eq!(RETRY_BACKOFF_SECONDS[2], 300); // 5 minutes
-        assert_eq!(RETRY_BACKOFF_SECONDS[3], 900); // 15 minutes
+    fn test_dead_letter_response() {
+        let now = Utc::now();
+        let entity = WebhookDeadLetterEntity {
+            id: 1,
+            dead_letter_id: Uuid::new_v4(),
+            delivery_id: Uuid::new_v4(),
+            webhook_id: Uuid::new_v4(),
+            event_id: None,
+            event_type: "geofence_enter".to_string(),
+            payload: serde_json::json!({"geofence_name": "Home"}),
+            attempts: 4,
+            response_code: Some(503),
+            error_message: None,
+            delivery_created_at: now,
+            created_at: now,
+        };
+        let dead_letter_id = entity.dead_letter_id;
+
+        let response = domain::models::WebhookDeadLetterResponse::from(entity);
+        assert_eq!(response.id, dead_letter_id);
+        assert_eq!(response.dead_lettered_at, now);
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["payload"]["geofence_name"], "Home");
+        assert_eq!(json["response_code"], 503);
+        assert!(json.get("event_id").is_none());
+        assert!(json.get("error_message").is_none());
     }
 }
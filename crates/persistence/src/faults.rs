@@ -0,0 +1,246 @@
+//! Fault injection for resilience testing.
+//!
+//! With the `fault-injection` feature, latency and a random error rate can
+//! be configured at runtime for each [`FaultPoint`], so retries, circuit
+//! breakers and degraded modes can be exercised against a running server.
+//! Without the feature, [`inject`] does nothing and there is no way to
+//! configure faults, so production builds are unaffected.
+//!
+//! | Point          | Injected at                          | Injected error                           |
+//! |----------------|--------------------------------------|------------------------------------------|
+//! | `database`     | Connection checkout from the pool    | Queries of the checkout fail (`42P01`)   |
+//! | `fcm`          | Each FCM send attempt                | Attempt fails and is retried             |
+//! | `map_matching` | Each OSRM request                    | Request fails, counted by the breaker    |
+//! | `webhooks`     | Each webhook delivery attempt        | Delivery fails and is scheduled to retry |
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Maximum latency that can be injected, in milliseconds.
+pub const MAX_FAULT_LATENCY_MS: u64 = 60_000;
+
+/// Place where faults can be injected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FaultPoint {
+    Database,
+    Fcm,
+    MapMatching,
+    Webhooks,
+}
+
+impl FaultPoint {
+    pub const ALL: [FaultPoint; 4] = [
+        FaultPoint::Database,
+        FaultPoint::Fcm,
+        FaultPoint::MapMatching,
+        FaultPoint::Webhooks,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FaultPoint::Database => "database",
+            FaultPoint::Fcm => "fcm",
+            FaultPoint::MapMatching => "map_matching",
+            FaultPoint::Webhooks => "webhooks",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|point| point.as_str() == s)
+    }
+}
+
+/// Faults injected at a point.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct FaultSpec {
+    /// Delay added before each operation.
+    #[serde(default)]
+    pub latency_ms: u64,
+    /// Probability, from 0 to 1, that an operation fails.
+    #[serde(default)]
+    pub error_rate: f64,
+}
+
+impl FaultSpec {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.latency_ms > MAX_FAULT_LATENCY_MS {
+            return Err(format!(
+                "latency_ms must be at most {}",
+                MAX_FAULT_LATENCY_MS
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.error_rate) {
+            return Err("error_rate must be between 0 and 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Error returned by [`inject`] when a fault is injected.
+#[derive(Debug, Clone, Copy, Error)]
+#[error("Injected {} fault", .0.as_str())]
+pub struct InjectedFault(pub FaultPoint);
+
+/// Apply the faults configured for `point`: wait for the configured latency,
+/// then fail at the configured error rate.
+#[cfg(feature = "fault-injection")]
+pub async fn inject(point: FaultPoint) -> Result<(), InjectedFault> {
+    use rand::Rng;
+
+    let Some(spec) = registry::get(point) else {
+        return Ok(());
+    };
+    if spec.latency_ms > 0 {
+        tokio::time::sleep(std::time::Duration::from_millis(spec.latency_ms)).await;
+    }
+    if spec.error_rate > 0.0 && rand::thread_rng().gen::<f64>() < spec.error_rate {
+        metrics::counter!("faults_injected_total", "point" => point.as_str()).increment(1);
+        tracing::warn!(point = point.as_str(), "Injected fault");
+        return Err(InjectedFault(point));
+    }
+    Ok(())
+}
+
+/// Apply the faults configured for `point`; always succeeds without the
+/// `fault-injection` feature.
+#[cfg(not(feature = "fault-injection"))]
+pub async fn inject(_point: FaultPoint) -> Result<(), InjectedFault> {
+    Ok(())
+}
+
+#[cfg(feature = "fault-injection")]
+pub use registry::{active, clear, remove, set};
+
+#[cfg(feature = "fault-injection")]
+mod registry {
+    use std::collections::HashMap;
+    use std::sync::{OnceLock, PoisonError, RwLock};
+
+    use super::{FaultPoint, FaultSpec};
+
+    static FAULTS: OnceLock<RwLock<HashMap<FaultPoint, FaultSpec>>> = OnceLock::new();
+
+    fn faults() -> &'static RwLock<HashMap<FaultPoint, FaultSpec>> {
+        FAULTS.get_or_init(Default::default)
+    }
+
+    pub(super) fn get(point: FaultPoint) -> Option<FaultSpec> {
+        faults()
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&point)
+            .copied()
+    }
+
+    /// Configure the faults injected at `point`.
+    pub fn set(point: FaultPoint, spec: FaultSpec) {
+        faults()
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(point, spec);
+    }
+
+    /// Stop injecting faults at `point`; returns whether any were configured.
+    pub fn remove(point: FaultPoint) -> bool {
+        faults()
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&point)
+            .is_some()
+    }
+
+    /// Stop injecting faults everywhere.
+    pub fn clear() {
+        faults()
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+
+    /// The configured faults, in [`FaultPoint::ALL`] order.
+    pub fn active() -> Vec<(FaultPoint, FaultSpec)> {
+        let faults = faults().read().unwrap_or_else(PoisonError::into_inner);
+        FaultPoint::ALL
+            .into_iter()
+            .filter_map(|point| faults.get(&point).map(|spec| (point, *spec)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fault_point_parse() {
+        for point in FaultPoint::ALL {
+            assert_eq!(FaultPoint::parse(point.as_str()), Some(point));
+        }
+        assert_eq!(FaultPoint::parse("redis"), None);
+    }
+
+    #[test]
+    fn test_fault_spec_validate() {
+        assert!(FaultSpec::default().validate().is_ok());
+        let spec = FaultSpec {
+            latency_ms: MAX_FAULT_LATENCY_MS,
+            error_rate: 1.0,
+        };
+        assert!(spec.validate().is_ok());
+        assert!(FaultSpec {
+            latency_ms: MAX_FAULT_LATENCY_MS + 1,
+            ..spec
+        }
+        .validate()
+        .is_err());
+        assert!(FaultSpec {
+            error_rate: 1.5,
+            ..spec
+        }
+        .validate()
+        .is_err());
+        assert!(FaultSpec {
+            error_rate: f64::NAN,
+            ..spec
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[cfg(feature = "fault-injection")]
+    #[tokio::test]
+    async fn test_inject_configured_faults() {
+        // Webhooks is only configured by this test.
+        let point = FaultPoint::Webhooks;
+        assert!(inject(point).await.is_ok());
+
+        set(
+            point,
+            FaultSpec {
+                latency_ms: 0,
+                error_rate: 1.0,
+            },
+        );
+        assert!(active().contains(&(
+            point,
+            FaultSpec {
+                latency_ms: 0,
+                error_rate: 1.0
+            }
+        )));
+        assert!(inject(point).await.is_err());
+
+        assert!(remove(point));
+        assert!(!remove(point));
+        assert!(inject(point).await.is_ok());
+    }
+
+    #[cfg(not(feature = "fault-injection"))]
+    #[tokio::test]
+    async fn test_inject_is_noop_without_feature() {
+        for point in FaultPoint::ALL {
+            assert!(inject(point).await.is_ok());
+        }
+    }
+}
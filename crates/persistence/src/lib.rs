@@ -2,11 +2,21 @@
 //!
 //! This crate contains:
 //! - Database connection management
+//! - Storage backend abstraction (PostgreSQL, optional SQLite)
 //! - Entity definitions (database row mappings)
 //! - Repository implementations
 //! - Database metrics collection
+//! - Pinned SQL for hot queries
+//! - Optional TimescaleDB setup
+//! - Retry of transactions aborted by serialization failures and deadlocks
+//! - Fault injection for resilience testing (`fault-injection` feature)
 
+pub mod backend;
 pub mod db;
 pub mod entities;
+pub mod faults;
 pub mod metrics;
+pub mod query_plans;
 pub mod repositories;
+pub mod retry;
+pub mod timescale;
@@ -0,0 +1,10 @@
+-- Migration 058: User unit system preference
+-- Metric/imperial preference used when presenting distances and speeds
+-- (trip stats, analytics summaries, generated reports)
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS unit_system VARCHAR(10) NOT NULL DEFAULT 'metric';
+
+ALTER TABLE users ADD CONSTRAINT chk_users_unit_system
+    CHECK (unit_system IN ('metric', 'imperial'));
+
+COMMENT ON COLUMN users.unit_system IS 'Preferred unit system for distances and speeds: metric or imperial';
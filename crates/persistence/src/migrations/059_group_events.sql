@@ -0,0 +1,27 @@
+-- Migration 059: Group event archive
+-- Persists the event stream of each group (membership, device and geofence
+-- events) for a configurable retention window so that newly added
+-- automations and webhooks can backfill recent history.
+
+CREATE TABLE IF NOT EXISTS group_events (
+    id BIGSERIAL PRIMARY KEY,
+    event_id UUID NOT NULL DEFAULT gen_random_uuid(),
+    group_id UUID NOT NULL REFERENCES groups(id) ON DELETE CASCADE,
+    event_type VARCHAR(50) NOT NULL,
+    device_id UUID,
+    actor_user_id UUID,
+    payload JSONB NOT NULL DEFAULT '{}',
+    occurred_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT uq_group_events_event_id UNIQUE (event_id)
+);
+
+-- Cursor pagination within a group (occurred_at, id)
+CREATE INDEX IF NOT EXISTS idx_group_events_group_occurred
+    ON group_events(group_id, occurred_at, id);
+
+-- Retention cleanup
+CREATE INDEX IF NOT EXISTS idx_group_events_occurred_at
+    ON group_events(occurred_at);
+
+COMMENT ON TABLE group_events IS 'Archived per-group event stream for replay/backfill';
@@ -0,0 +1,19 @@
+-- Migration 060: Per-device API usage counters
+-- Tracks request counts and last activity per device and endpoint class
+-- (ingest, sync, commands) so admins can spot clients hammering the API.
+
+CREATE TABLE IF NOT EXISTS device_api_usage (
+    device_id UUID NOT NULL REFERENCES devices(device_id) ON DELETE CASCADE,
+    endpoint_class VARCHAR(20) NOT NULL,
+    request_count BIGINT NOT NULL DEFAULT 0,
+    -- Counter for the current clock hour (reset when the hour changes)
+    hour_bucket TIMESTAMPTZ NOT NULL,
+    hour_count BIGINT NOT NULL DEFAULT 0,
+    last_request_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (device_id, endpoint_class),
+    CONSTRAINT chk_device_api_usage_endpoint_class
+        CHECK (endpoint_class IN ('ingest', 'sync', 'commands'))
+);
+
+COMMENT ON TABLE device_api_usage IS 'Per-device request counters by endpoint class';
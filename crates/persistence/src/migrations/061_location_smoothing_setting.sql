@@ -0,0 +1,8 @@
+-- Migration 061: Location smoothing device setting
+-- When enabled, incoming locations are run through a Kalman filter and
+-- implausible jumps are dropped before persistence.
+
+INSERT INTO setting_definitions (key, display_name, description, data_type, default_value, is_lockable, category, sort_order)
+VALUES
+    ('location_smoothing_enabled', 'Location Smoothing', 'Filter GPS jitter and outliers on the server before storing locations', 'boolean', 'false', true, 'tracking', 4)
+ON CONFLICT (key) DO NOTHING;
@@ -0,0 +1,21 @@
+-- Migration 062: Saved report templates
+-- Stores report builder definitions (sections, grouping, format) that an
+-- organization can reuse when generating custom reports.
+
+CREATE TABLE IF NOT EXISTS report_templates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    description TEXT,
+    definition JSONB NOT NULL DEFAULT '{}',
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT uq_report_templates_org_name UNIQUE (organization_id, name)
+);
+
+CREATE INDEX IF NOT EXISTS idx_report_templates_org_id
+    ON report_templates(organization_id);
+
+COMMENT ON TABLE report_templates IS 'Saved custom report builder definitions';
@@ -0,0 +1,9 @@
+-- Migration 063: Per-group location retention overrides
+-- NULL keeps the system-wide location_retention_days default.
+
+ALTER TABLE groups
+    ADD COLUMN location_retention_days INTEGER
+        CHECK (location_retention_days IS NULL OR location_retention_days >= 1);
+
+COMMENT ON COLUMN groups.location_retention_days IS
+    'Days to retain member device locations; NULL uses the system default';
@@ -0,0 +1,35 @@
+-- Migration 064: User privacy zones
+-- Locations inside a privacy zone are suppressed or snapped to the zone
+-- center before being shared with other group members. The device owner
+-- always sees exact data.
+
+CREATE TABLE privacy_zones (
+    id              BIGSERIAL PRIMARY KEY,
+    zone_id         UUID NOT NULL UNIQUE DEFAULT uuid_generate_v4(),
+    user_id         UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name            VARCHAR(100) NOT NULL,
+    latitude        DOUBLE PRECISION NOT NULL,
+    longitude       DOUBLE PRECISION NOT NULL,
+    radius_meters   REAL NOT NULL,
+    mode            VARCHAR(20) NOT NULL DEFAULT 'snap',
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT chk_privacy_zone_latitude CHECK (latitude >= -90 AND latitude <= 90),
+    CONSTRAINT chk_privacy_zone_longitude CHECK (longitude >= -180 AND longitude <= 180),
+    CONSTRAINT chk_privacy_zone_radius CHECK (radius_meters >= 50 AND radius_meters <= 5000),
+    CONSTRAINT chk_privacy_zone_name_length CHECK (char_length(name) >= 1 AND char_length(name) <= 100),
+    CONSTRAINT chk_privacy_zone_mode CHECK (mode IN ('suppress', 'snap'))
+);
+
+-- Zones are always loaded per owning user
+CREATE INDEX idx_privacy_zones_user_id ON privacy_zones(user_id);
+
+CREATE TRIGGER update_privacy_zones_updated_at
+    BEFORE UPDATE ON privacy_zones
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+COMMENT ON TABLE privacy_zones IS 'User-defined zones (e.g. home, work) where shared locations are hidden or fuzzed';
+COMMENT ON COLUMN privacy_zones.user_id IS 'User whose devices are covered by this zone';
+COMMENT ON COLUMN privacy_zones.mode IS 'suppress: hide points inside the zone; snap: report the zone center instead';
@@ -0,0 +1,29 @@
+-- Migration 065: Incremental audit log export
+-- Audit log ids are random UUIDs, so a monotonic sequence is added to give
+-- exports a stable "since last export" position. Each export destination of
+-- an organization keeps its own cursor.
+
+ALTER TABLE audit_logs ADD COLUMN seq BIGSERIAL;
+
+CREATE INDEX IF NOT EXISTS idx_audit_logs_org_seq
+    ON audit_logs(organization_id, seq);
+
+CREATE TABLE IF NOT EXISTS audit_export_cursors (
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    destination VARCHAR(100) NOT NULL,
+    last_seq BIGINT NOT NULL DEFAULT 0,
+    last_record_count BIGINT,
+    last_exported_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (organization_id, destination)
+);
+
+CREATE TRIGGER update_audit_export_cursors_updated_at
+    BEFORE UPDATE ON audit_export_cursors
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+COMMENT ON COLUMN audit_logs.seq IS 'Monotonic insertion sequence used by incremental exports';
+COMMENT ON TABLE audit_export_cursors IS 'Last exported audit log sequence per organization and export destination';
+COMMENT ON COLUMN audit_export_cursors.destination IS 'Client-chosen destination name (e.g. warehouse)';
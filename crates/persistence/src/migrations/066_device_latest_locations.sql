@@ -0,0 +1,113 @@
+-- Migration 066: Latest-location fast path
+-- One row per device holding its most recent location, so group map views,
+-- fleet lists and member listings stop scanning locations per device.
+-- The table is maintained by statement-level triggers on locations, which
+-- covers every insert path (single, batch, bulk import) and deletions
+-- (retention cleanup, device data deletion).
+
+CREATE TABLE device_latest_locations (
+    device_id    UUID PRIMARY KEY REFERENCES devices(device_id) ON DELETE CASCADE,
+    location_id  BIGINT NOT NULL,
+    latitude     DOUBLE PRECISION NOT NULL,
+    longitude    DOUBLE PRECISION NOT NULL,
+    accuracy     REAL NOT NULL,
+    captured_at  TIMESTAMPTZ NOT NULL,
+    updated_at   TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Used by the delete trigger to find rows pointing at removed locations
+CREATE INDEX idx_device_latest_locations_location_id
+    ON device_latest_locations(location_id);
+
+-- Backfill from existing history
+INSERT INTO device_latest_locations (device_id, location_id, latitude, longitude, accuracy, captured_at)
+SELECT DISTINCT ON (device_id) device_id, id, latitude, longitude, accuracy, captured_at
+FROM locations
+ORDER BY device_id, captured_at DESC, id DESC;
+
+-- New locations replace the cached row only if they are newer, so late
+-- uploads of buffered offline points do not move a device back in time.
+CREATE OR REPLACE FUNCTION upsert_device_latest_locations()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO device_latest_locations (device_id, location_id, latitude, longitude, accuracy, captured_at)
+    SELECT DISTINCT ON (device_id) device_id, id, latitude, longitude, accuracy, captured_at
+    FROM new_locations
+    ORDER BY device_id, captured_at DESC, id DESC
+    ON CONFLICT (device_id) DO UPDATE SET
+        location_id = EXCLUDED.location_id,
+        latitude = EXCLUDED.latitude,
+        longitude = EXCLUDED.longitude,
+        accuracy = EXCLUDED.accuracy,
+        captured_at = EXCLUDED.captured_at,
+        updated_at = NOW()
+    WHERE (device_latest_locations.captured_at, device_latest_locations.location_id)
+        <= (EXCLUDED.captured_at, EXCLUDED.location_id);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_locations_latest_insert
+    AFTER INSERT ON locations
+    REFERENCING NEW TABLE AS new_locations
+    FOR EACH STATEMENT
+    EXECUTE FUNCTION upsert_device_latest_locations();
+
+-- When a cached location is deleted, fall back to the device's newest
+-- remaining location, or drop the row if none is left.
+CREATE OR REPLACE FUNCTION refresh_device_latest_locations()
+RETURNS TRIGGER AS $$
+BEGIN
+    UPDATE device_latest_locations dll
+    SET location_id = l.id,
+        latitude = l.latitude,
+        longitude = l.longitude,
+        accuracy = l.accuracy,
+        captured_at = l.captured_at,
+        updated_at = NOW()
+    FROM old_locations o
+    CROSS JOIN LATERAL (
+        SELECT id, latitude, longitude, accuracy, captured_at
+        FROM locations
+        WHERE device_id = o.device_id
+        ORDER BY captured_at DESC, id DESC
+        LIMIT 1
+    ) l
+    WHERE dll.location_id = o.id;
+
+    DELETE FROM device_latest_locations dll
+    USING old_locations o
+    WHERE dll.location_id = o.id;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_locations_latest_delete
+    AFTER DELETE ON locations
+    REFERENCING OLD TABLE AS old_locations
+    FOR EACH STATEMENT
+    EXECUTE FUNCTION refresh_device_latest_locations();
+
+-- Serve the existing view from the fast path as well
+CREATE OR REPLACE VIEW devices_with_last_location AS
+SELECT
+    d.id,
+    d.device_id,
+    d.display_name,
+    d.group_id,
+    d.platform,
+    d.fcm_token,
+    d.active,
+    d.last_seen_at,
+    d.created_at,
+    d.updated_at,
+    l.latitude as last_latitude,
+    l.longitude as last_longitude,
+    l.captured_at as last_location_time,
+    l.accuracy as last_accuracy
+FROM devices d
+LEFT JOIN device_latest_locations l ON l.device_id = d.device_id;
+
+COMMENT ON TABLE device_latest_locations IS 'Most recent location per device, maintained by triggers on locations';
+COMMENT ON COLUMN device_latest_locations.location_id IS 'locations.id of the cached row';
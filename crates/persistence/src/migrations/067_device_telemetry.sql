@@ -0,0 +1,29 @@
+-- Migration 067: Device telemetry
+-- Battery and connectivity samples reported alongside locations or through
+-- the standalone telemetry endpoint. Fleet listings show the latest sample
+-- per device; retention follows the location retention settings.
+
+CREATE TABLE device_telemetry (
+    id               BIGSERIAL PRIMARY KEY,
+    device_id        UUID NOT NULL REFERENCES devices(device_id) ON DELETE CASCADE,
+    battery_level    SMALLINT,
+    charging_state   VARCHAR(20),
+    network_type     VARCHAR(50),
+    signal_strength  SMALLINT,
+    recorded_at      TIMESTAMPTZ NOT NULL,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT chk_telemetry_battery CHECK (battery_level IS NULL OR (battery_level >= 0 AND battery_level <= 100)),
+    CONSTRAINT chk_telemetry_charging_state CHECK (charging_state IS NULL OR charging_state IN ('charging', 'discharging', 'full', 'not_charging')),
+    CONSTRAINT chk_telemetry_signal CHECK (signal_strength IS NULL OR (signal_strength >= -150 AND signal_strength <= 0))
+);
+
+-- Latest sample per device (fleet listings)
+CREATE INDEX idx_device_telemetry_device_recorded ON device_telemetry(device_id, recorded_at DESC);
+
+-- Retention cleanup
+CREATE INDEX idx_device_telemetry_created_at ON device_telemetry(created_at);
+
+COMMENT ON TABLE device_telemetry IS 'Battery and connectivity samples reported by devices';
+COMMENT ON COLUMN device_telemetry.signal_strength IS 'Cellular or Wi-Fi signal strength in dBm';
+COMMENT ON COLUMN device_telemetry.recorded_at IS 'When the device took the sample';
@@ -0,0 +1,24 @@
+-- Migration 068: Server-side movement detection
+-- Arrivals and departures derived from the location stream are stored as
+-- movement events with detection_source LOCATION_STREAM and a
+-- movement_state of ARRIVED or DEPARTED.
+
+ALTER TABLE movement_events DROP CONSTRAINT chk_movement_source;
+ALTER TABLE movement_events ADD CONSTRAINT chk_movement_source
+    CHECK (detection_source IN ('ACTIVITY_RECOGNITION', 'BLUETOOTH_CAR', 'ANDROID_AUTO', 'MULTIPLE', 'NONE', 'LOCATION_STREAM'));
+
+ALTER TABLE movement_events ADD COLUMN movement_state VARCHAR(20);
+ALTER TABLE movement_events ADD CONSTRAINT chk_movement_state
+    CHECK (movement_state IS NULL OR movement_state IN ('ARRIVED', 'DEPARTED'));
+
+-- Latest derived transition per device (detector resume point)
+CREATE INDEX idx_movement_events_device_state
+    ON movement_events(device_id, timestamp DESC)
+    WHERE movement_state IS NOT NULL;
+
+COMMENT ON COLUMN movement_events.movement_state IS 'Server-derived transition: ARRIVED (stationary since timestamp) or DEPARTED (moving since timestamp)';
+
+INSERT INTO setting_definitions (key, display_name, description, data_type, default_value, is_lockable, category, sort_order)
+VALUES
+    ('server_movement_detection_enabled', 'Server Movement Detection', 'Derive arrivals and departures from uploaded locations on the server', 'boolean', 'false', true, 'tracking', 5)
+ON CONFLICT (key) DO NOTHING;
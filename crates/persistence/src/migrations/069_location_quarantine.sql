@@ -0,0 +1,46 @@
+-- Migration 069: Location accuracy filtering and quarantine
+-- Devices and organizations can set a maximum accepted accuracy and a
+-- maximum plausible speed. Uploaded locations breaking either limit are kept
+-- out of the location history and stored here with the reason instead.
+
+CREATE TABLE location_quarantine (
+    id              BIGSERIAL PRIMARY KEY,
+    device_id       UUID NOT NULL REFERENCES devices(device_id) ON DELETE CASCADE,
+    latitude        DOUBLE PRECISION NOT NULL,
+    longitude       DOUBLE PRECISION NOT NULL,
+    accuracy        REAL NOT NULL,
+    altitude        DOUBLE PRECISION,
+    bearing         REAL,
+    speed           REAL,
+    provider        VARCHAR(50),
+    captured_at     TIMESTAMPTZ NOT NULL,
+    reason          VARCHAR(30) NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT chk_quarantine_reason CHECK (reason IN ('ACCURACY_TOO_LOW', 'IMPOSSIBLE_SPEED'))
+);
+
+CREATE INDEX idx_location_quarantine_device_captured ON location_quarantine(device_id, captured_at DESC);
+
+-- Retention cleanup
+CREATE INDEX idx_location_quarantine_created_at ON location_quarantine(created_at);
+
+COMMENT ON TABLE location_quarantine IS 'Uploaded locations rejected by accuracy or speed filtering';
+COMMENT ON COLUMN location_quarantine.reason IS 'ACCURACY_TOO_LOW or IMPOSSIBLE_SPEED';
+
+-- Organization-wide limits; device settings take precedence
+ALTER TABLE organization_settings ADD COLUMN max_location_accuracy_meters REAL;
+ALTER TABLE organization_settings ADD COLUMN max_location_speed_mps REAL;
+ALTER TABLE organization_settings ADD CONSTRAINT chk_max_location_accuracy
+    CHECK (max_location_accuracy_meters IS NULL OR max_location_accuracy_meters > 0);
+ALTER TABLE organization_settings ADD CONSTRAINT chk_max_location_speed
+    CHECK (max_location_speed_mps IS NULL OR max_location_speed_mps > 0);
+
+COMMENT ON COLUMN organization_settings.max_location_accuracy_meters IS 'Reject locations with a worse accuracy (NULL = no limit)';
+COMMENT ON COLUMN organization_settings.max_location_speed_mps IS 'Reject locations implying a higher speed in m/s (NULL = no limit)';
+
+INSERT INTO setting_definitions (key, display_name, description, data_type, default_value, is_lockable, category, validation_rules, sort_order)
+VALUES
+    ('max_location_accuracy_meters', 'Maximum Location Accuracy', 'Quarantine uploaded locations with a worse accuracy in meters (0 = organization default)', 'float', '0', true, 'tracking', '{"min": 0}', 6),
+    ('max_location_speed_mps', 'Maximum Location Speed', 'Quarantine uploaded locations implying a higher speed in meters per second (0 = organization default)', 'float', '0', true, 'tracking', '{"min": 0}', 7)
+ON CONFLICT (key) DO NOTHING;
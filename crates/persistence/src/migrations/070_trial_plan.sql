@@ -0,0 +1,9 @@
+-- Migration 070: Self-service trial organizations
+-- Authenticated users can create their own organization on the trial plan
+-- when the self_service_orgs_enabled feature is on.
+
+ALTER TYPE plan_type ADD VALUE IF NOT EXISTS 'trial';
+
+INSERT INTO feature_flags (flag_key, enabled, description, category) VALUES
+    ('self_service_orgs_enabled', false, 'Allow users to create trial organizations', 'features')
+ON CONFLICT (flag_key) DO NOTHING;
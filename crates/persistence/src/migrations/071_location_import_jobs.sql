@@ -0,0 +1,33 @@
+-- Migration 071: Location history import jobs
+-- Users can upload an export of their past location history (Google Takeout
+-- Records.json) for one of their devices. The upload is stored on disk and
+-- a background job backfills the locations table, skipping records already
+-- present for the same capture time.
+
+CREATE TABLE location_import_jobs (
+    id                  UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    device_id           UUID NOT NULL REFERENCES devices(device_id) ON DELETE CASCADE,
+    created_by          UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    source              VARCHAR(30) NOT NULL,
+    status              VARCHAR(20) NOT NULL DEFAULT 'pending',
+    file_path           TEXT NOT NULL,
+    total_records       INT NOT NULL DEFAULT 0,
+    imported_records    INT NOT NULL DEFAULT 0,
+    duplicate_records   INT NOT NULL DEFAULT 0,
+    invalid_records     INT NOT NULL DEFAULT 0,
+    error_message       TEXT,
+    started_at          TIMESTAMPTZ,
+    completed_at        TIMESTAMPTZ,
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT chk_location_import_source CHECK (source IN ('google_takeout')),
+    CONSTRAINT chk_location_import_status CHECK (status IN ('pending', 'processing', 'completed', 'failed'))
+);
+
+CREATE INDEX idx_location_import_jobs_device ON location_import_jobs(device_id, created_at DESC);
+CREATE INDEX idx_location_import_jobs_pending ON location_import_jobs(created_at)
+    WHERE status = 'pending';
+
+COMMENT ON TABLE location_import_jobs IS 'Background imports of past location history into locations';
+COMMENT ON COLUMN location_import_jobs.duplicate_records IS 'Records skipped because a location with the same captured_at exists';
@@ -0,0 +1,26 @@
+-- Migration 072: Organization ownership transfers
+-- The owner initiates a transfer to an admin, who must accept it before
+-- org_users roles change. Initiating a new transfer cancels a pending one.
+
+CREATE TABLE org_ownership_transfers (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    from_user_id    UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    to_user_id      UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    status          VARCHAR(20) NOT NULL DEFAULT 'pending',
+    expires_at      TIMESTAMPTZ NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    resolved_at     TIMESTAMPTZ,
+
+    CONSTRAINT chk_org_transfer_status CHECK (status IN ('pending', 'accepted', 'cancelled')),
+    CONSTRAINT chk_org_transfer_users CHECK (from_user_id <> to_user_id)
+);
+
+-- At most one pending transfer per organization
+CREATE UNIQUE INDEX idx_org_ownership_transfers_pending
+    ON org_ownership_transfers(organization_id)
+    WHERE status = 'pending';
+
+CREATE INDEX idx_org_ownership_transfers_org ON org_ownership_transfers(organization_id, created_at DESC);
+
+COMMENT ON TABLE org_ownership_transfers IS 'Two-step organization ownership transfers (owner initiates, admin accepts)';
@@ -0,0 +1,41 @@
+-- Migration 073: Device command macros
+-- Org admins define named sequences of device commands. Running a macro
+-- creates a run (the parent operation) and queues each step for every
+-- targeted device as a regular device command linked to the run.
+
+CREATE TABLE device_command_macros (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    name            VARCHAR(100) NOT NULL,
+    description     VARCHAR(500),
+    steps           JSONB NOT NULL,
+    created_by      UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT uq_device_command_macros_name UNIQUE (organization_id, name)
+);
+
+CREATE TABLE device_command_macro_runs (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    macro_id        UUID REFERENCES device_command_macros(id) ON DELETE SET NULL,
+    macro_name      VARCHAR(100) NOT NULL,
+    parameters      JSONB NOT NULL DEFAULT '{}',
+    device_count    INTEGER NOT NULL,
+    issued_by       UUID NOT NULL REFERENCES users(id),
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_device_command_macro_runs_org ON device_command_macro_runs(organization_id, created_at DESC);
+
+-- Link queued commands to the run and step that created them
+ALTER TABLE device_commands
+    ADD COLUMN macro_run_id UUID REFERENCES device_command_macro_runs(id) ON DELETE SET NULL,
+    ADD COLUMN step_index INTEGER;
+
+CREATE INDEX idx_device_commands_macro_run ON device_commands(macro_run_id) WHERE macro_run_id IS NOT NULL;
+
+COMMENT ON TABLE device_command_macros IS 'Named sequences of device commands defined by org admins';
+COMMENT ON TABLE device_command_macro_runs IS 'Macro executions; parent operation of the commands they queued';
+COMMENT ON COLUMN device_commands.step_index IS 'Position of the command in its macro, NULL for single commands';
@@ -0,0 +1,11 @@
+-- Migration 074: Location sequence numbers
+-- Devices number their points; re-sent buffered batches are deduplicated
+-- on (device_id, sequence_number) instead of creating duplicate rows.
+
+ALTER TABLE locations ADD COLUMN sequence_number BIGINT;
+
+CREATE UNIQUE INDEX idx_locations_device_sequence
+    ON locations(device_id, sequence_number)
+    WHERE sequence_number IS NOT NULL;
+
+COMMENT ON COLUMN locations.sequence_number IS 'Client-assigned per-device sequence number used to deduplicate replayed uploads';
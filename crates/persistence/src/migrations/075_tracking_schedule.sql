@@ -0,0 +1,8 @@
+-- Migration 075: Per-device tracking schedules
+-- A weekly schedule stored as a device setting. Locations captured outside
+-- every window are ignored on upload; null means tracking is unrestricted.
+
+INSERT INTO setting_definitions (key, display_name, description, data_type, default_value, is_lockable, category, sort_order)
+VALUES
+    ('tracking_schedule', 'Tracking Schedule', 'Weekly windows during which location tracking is on, e.g. weekdays 07:00-19:00', 'json', 'null', true, 'tracking', 6)
+ON CONFLICT (key) DO NOTHING;
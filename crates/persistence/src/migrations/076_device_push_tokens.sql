@@ -0,0 +1,34 @@
+-- Migration 076: Device push tokens
+-- A device can register several push tokens, each with its platform, app
+-- version, and optional expiry. Notifications go to every unexpired token;
+-- tokens rejected by the push provider are deleted. devices.fcm_token is
+-- kept for legacy registrations, which also register their token here.
+
+CREATE TABLE device_push_tokens (
+    id           UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    device_id    UUID NOT NULL REFERENCES devices(device_id) ON DELETE CASCADE,
+    token        TEXT NOT NULL,
+    platform     VARCHAR(20) NOT NULL,
+    app_version  VARCHAR(50),
+    expires_at   TIMESTAMPTZ,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at   TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT uq_device_push_tokens_token UNIQUE (token),
+    CONSTRAINT chk_device_push_tokens_platform CHECK (platform IN ('android', 'ios'))
+);
+
+CREATE INDEX idx_device_push_tokens_device ON device_push_tokens(device_id, updated_at DESC);
+
+-- Expired token cleanup
+CREATE INDEX idx_device_push_tokens_expires_at ON device_push_tokens(expires_at) WHERE expires_at IS NOT NULL;
+
+-- Carry over tokens registered through the single fcm_token column
+INSERT INTO device_push_tokens (device_id, token, platform)
+SELECT device_id, fcm_token, CASE WHEN LOWER(platform) = 'ios' THEN 'ios' ELSE 'android' END
+FROM devices
+WHERE fcm_token IS NOT NULL AND fcm_token <> ''
+ON CONFLICT (token) DO NOTHING;
+
+COMMENT ON TABLE device_push_tokens IS 'Push notification tokens registered by devices';
+COMMENT ON COLUMN device_push_tokens.expires_at IS 'When the token stops being used; NULL means no expiry';
@@ -0,0 +1,7 @@
+-- Migration 077: Scheduled geofences
+-- A geofence with a weekly schedule only produces events (and webhooks)
+-- during its windows; NULL means always active.
+
+ALTER TABLE geofences ADD COLUMN schedule JSONB;
+
+COMMENT ON COLUMN geofences.schedule IS 'Weekly windows during which the geofence is active; NULL means always';
@@ -0,0 +1,19 @@
+-- Migration 078: Server-side geofence evaluation
+-- Enter/exit events can now be derived on the server from uploaded
+-- locations for devices whose clients do not report them. The source
+-- column tells derived events apart from client-reported ones.
+
+ALTER TABLE geofence_events
+    ADD COLUMN source VARCHAR(10) NOT NULL DEFAULT 'client'
+    CONSTRAINT geofence_events_source_check CHECK (source IN ('client', 'server'));
+
+COMMENT ON COLUMN geofence_events.source IS 'Who detected the event: client (reported by the device) or server (derived from locations)';
+
+-- Latest transition per geofence, used to resume evaluation
+CREATE INDEX IF NOT EXISTS idx_geofence_events_device_geofence_timestamp
+    ON geofence_events(device_id, geofence_id, timestamp DESC);
+
+INSERT INTO setting_definitions (key, display_name, description, data_type, default_value, is_lockable, category, sort_order)
+VALUES
+    ('server_geofence_evaluation_enabled', 'Server Geofence Evaluation', 'Derive geofence enter and exit events from uploaded locations on the server', 'boolean', 'false', true, 'tracking', 7)
+ON CONFLICT (key) DO NOTHING;
@@ -0,0 +1,43 @@
+-- Migration 079: Geofence templates
+-- Org admins define reusable geofences (e.g. "Office", "Warehouse") and
+-- stamp them onto many devices at once. Stamped geofences keep a link to
+-- their template so template edits can be propagated to them.
+
+CREATE TABLE geofence_templates (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    name            VARCHAR(100) NOT NULL,
+    description     VARCHAR(500),
+    latitude        DOUBLE PRECISION NOT NULL,
+    longitude       DOUBLE PRECISION NOT NULL,
+    radius_meters   REAL NOT NULL,
+    event_types     TEXT[] NOT NULL DEFAULT ARRAY['enter', 'exit'],
+    schedule        JSONB,
+    metadata        JSONB,
+    created_by      UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT uq_geofence_templates_name UNIQUE (organization_id, name),
+    CONSTRAINT chk_geofence_template_latitude CHECK (latitude >= -90 AND latitude <= 90),
+    CONSTRAINT chk_geofence_template_longitude CHECK (longitude >= -180 AND longitude <= 180),
+    CONSTRAINT chk_geofence_template_radius CHECK (radius_meters >= 20 AND radius_meters <= 50000),
+    CONSTRAINT chk_geofence_template_event_types CHECK (
+        event_types <@ ARRAY['enter', 'exit', 'dwell']::TEXT[]
+        AND array_length(event_types, 1) > 0
+    )
+);
+
+-- Link stamped geofences to their template; deleting the template keeps
+-- the geofences as standalone ones unless they are deleted explicitly
+ALTER TABLE geofences
+    ADD COLUMN template_id UUID REFERENCES geofence_templates(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_geofences_template ON geofences(template_id) WHERE template_id IS NOT NULL;
+
+-- A template is stamped at most once per device
+CREATE UNIQUE INDEX uq_geofences_device_template ON geofences(device_id, template_id)
+    WHERE template_id IS NOT NULL;
+
+COMMENT ON TABLE geofence_templates IS 'Organization geofence definitions stamped onto devices';
+COMMENT ON COLUMN geofences.template_id IS 'Template the geofence was stamped from, NULL for standalone geofences';
@@ -0,0 +1,10 @@
+-- Migration 080: Geofence notification cool-down
+-- A device bouncing on a geofence boundary produces a burst of enter and
+-- exit events, each delivered to webhooks and the group feed. An enter or
+-- exit within cooldown_seconds of the geofence's previous one is dropped.
+
+ALTER TABLE geofences
+    ADD COLUMN cooldown_seconds INTEGER NOT NULL DEFAULT 0
+    CONSTRAINT geofences_cooldown_seconds_check CHECK (cooldown_seconds BETWEEN 0 AND 86400);
+
+COMMENT ON COLUMN geofences.cooldown_seconds IS 'Minimum time between recorded enter/exit events of the geofence; 0 disables the cool-down';
@@ -0,0 +1,18 @@
+-- Migration 081: Admin geofence categories and tags
+-- Organizations with hundreds of geofences organize them by category
+-- (e.g. "depot", "customer site") and free-form tags, filter lists by them
+-- and see analytics grouped by category. Tags are stored lowercase.
+
+ALTER TABLE admin_geofences
+    ADD COLUMN category VARCHAR(50),
+    ADD COLUMN tags TEXT[] NOT NULL DEFAULT '{}',
+    ADD CONSTRAINT chk_admin_geofence_category_length CHECK (
+        category IS NULL OR char_length(category) BETWEEN 1 AND 50
+    ),
+    ADD CONSTRAINT chk_admin_geofence_tags_count CHECK (cardinality(tags) <= 20);
+
+CREATE INDEX idx_admin_geofences_category ON admin_geofences(organization_id, LOWER(category));
+CREATE INDEX idx_admin_geofences_tags ON admin_geofences USING GIN (tags);
+
+COMMENT ON COLUMN admin_geofences.category IS 'Category used to organize and group geofences';
+COMMENT ON COLUMN admin_geofences.tags IS 'Lowercase free-form tags for filtering';
@@ -0,0 +1,35 @@
+-- Migration 082: Geofence arrival forecasts
+-- When a device heads toward a geofence and its estimated time of arrival,
+-- from the speed and bearing of its latest location, drops below the
+-- geofence's arrival_alert_minutes, group members are told it is arriving
+-- soon. Forecasts are stored so each approach is announced once, and not
+-- again after the device actually enters.
+
+ALTER TABLE geofences
+    ADD COLUMN arrival_alert_minutes INTEGER NOT NULL DEFAULT 0
+    CONSTRAINT geofences_arrival_alert_minutes_check CHECK (arrival_alert_minutes BETWEEN 0 AND 120);
+
+COMMENT ON COLUMN geofences.arrival_alert_minutes IS 'Announce arrivals expected within this many minutes; 0 disables arrival forecasts';
+
+CREATE TABLE geofence_arrival_forecasts (
+    id              BIGSERIAL PRIMARY KEY,
+    device_id       UUID NOT NULL REFERENCES devices(device_id) ON DELETE CASCADE,
+    geofence_id     UUID NOT NULL REFERENCES geofences(geofence_id) ON DELETE CASCADE,
+    eta_seconds     INTEGER NOT NULL CHECK (eta_seconds >= 0),
+    distance_meters REAL NOT NULL CHECK (distance_meters >= 0),
+    latitude        DOUBLE PRECISION NOT NULL,
+    longitude       DOUBLE PRECISION NOT NULL,
+    forecast_at     TIMESTAMPTZ NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_geofence_arrival_forecasts_device_geofence
+    ON geofence_arrival_forecasts(device_id, geofence_id, forecast_at DESC);
+
+COMMENT ON TABLE geofence_arrival_forecasts IS 'Announced geofence arrivals, used to announce each approach once';
+COMMENT ON COLUMN geofence_arrival_forecasts.forecast_at IS 'Capture time of the location the forecast was made from';
+
+INSERT INTO setting_definitions (key, display_name, description, data_type, default_value, is_lockable, category, sort_order)
+VALUES
+    ('geofence_arrival_forecasts_enabled', 'Geofence Arrival Forecasts', 'Tell group members when the device is about to arrive at a geofence', 'boolean', 'true', true, 'tracking', 8)
+ON CONFLICT (key) DO NOTHING;
@@ -0,0 +1,17 @@
+-- Migration 083: Overlapping geofence priority
+-- A device inside overlapping geofences (a building within a campus) gets
+-- an event for each of them. The geofence with the highest priority
+-- containing the event location wins: events of the others are still
+-- recorded, but are not delivered to webhooks or the group feed. Each
+-- event records the geofence it resolved to.
+
+ALTER TABLE geofences
+    ADD COLUMN priority INTEGER NOT NULL DEFAULT 0
+    CONSTRAINT geofences_priority_check CHECK (priority BETWEEN 0 AND 100);
+
+COMMENT ON COLUMN geofences.priority IS 'Precedence among overlapping geofences of the device; higher wins';
+
+ALTER TABLE geofence_events
+    ADD COLUMN resolved_geofence_id UUID REFERENCES geofences(geofence_id) ON DELETE SET NULL;
+
+COMMENT ON COLUMN geofence_events.resolved_geofence_id IS 'Geofence whose notifications the event resolved to among overlapping geofences; NULL for events recorded before priorities';
@@ -0,0 +1,13 @@
+-- Migration 084: Server-side trip detection
+-- Trips segmented from the location stream by a background job are stored
+-- with detection_source LOCATION_STREAM, for devices that do not detect
+-- trips themselves.
+
+ALTER TABLE trips DROP CONSTRAINT chk_trips_source;
+ALTER TABLE trips ADD CONSTRAINT chk_trips_source
+    CHECK (detection_source IN ('ACTIVITY_RECOGNITION', 'BLUETOOTH_CAR', 'ANDROID_AUTO', 'MULTIPLE', 'NONE', 'LOCATION_STREAM'));
+
+INSERT INTO setting_definitions (key, display_name, description, data_type, default_value, is_lockable, category, sort_order)
+VALUES
+    ('server_trip_detection_enabled', 'Server Trip Detection', 'Segment uploaded locations into trips on the server', 'boolean', 'false', true, 'tracking', 9)
+ON CONFLICT (key) DO NOTHING;
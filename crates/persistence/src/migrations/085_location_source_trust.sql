@@ -0,0 +1,19 @@
+-- Migration 085: Location source and trust score
+-- Each location records where it came from (reported by the client or
+-- inferred from its provider) and a 0-100 trust score derived from the
+-- source and accuracy. Devices can make geofence evaluation and arrival
+-- forecasts ignore locations below a minimum trust score.
+
+ALTER TABLE locations
+    ADD COLUMN source VARCHAR(20)
+    CONSTRAINT chk_locations_source CHECK (source IN ('gps', 'network', 'beacon', 'manual_check_in', 'osmand')),
+    ADD COLUMN trust_score SMALLINT
+    CONSTRAINT chk_locations_trust_score CHECK (trust_score BETWEEN 0 AND 100);
+
+COMMENT ON COLUMN locations.source IS 'Where the fix came from; NULL when unknown';
+COMMENT ON COLUMN locations.trust_score IS 'Trust score (0-100) from the source and accuracy; NULL for locations stored before scoring';
+
+INSERT INTO setting_definitions (key, display_name, description, data_type, default_value, is_lockable, category, sort_order)
+VALUES
+    ('geofence_min_trust_score', 'Geofence Minimum Trust Score', 'Ignore locations with a lower trust score (0-100) in geofence evaluation and arrival forecasts', 'integer', '0', true, 'tracking', 10)
+ON CONFLICT (key) DO NOTHING;
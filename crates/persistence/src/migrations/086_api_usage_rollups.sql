@@ -0,0 +1,38 @@
+-- Migration 086: Persisted API usage rollups
+-- Request counts and latencies are collected in memory by each instance and
+-- flushed into api_usage_daily per organization, API key, endpoint and day.
+-- Latencies are kept as fixed-bucket histogram counts so rollups from several
+-- flushes and instances merge exactly; percentiles are recomputed on merge.
+-- Rows are kept for 13 months by default to settle billing disputes.
+
+-- Requests authenticated without an API key (user sessions) use api_key_id 0
+ALTER TABLE api_usage_daily ADD COLUMN IF NOT EXISTS api_key_id BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE api_usage_daily ADD COLUMN IF NOT EXISTS total_response_time_ms BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE api_usage_daily ADD COLUMN IF NOT EXISTS max_response_time_ms INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE api_usage_daily ADD COLUMN IF NOT EXISTS p50_response_time_ms INTEGER;
+ALTER TABLE api_usage_daily ADD COLUMN IF NOT EXISTS p99_response_time_ms INTEGER;
+ALTER TABLE api_usage_daily ADD COLUMN IF NOT EXISTS latency_histogram BIGINT[] NOT NULL DEFAULT '{}';
+
+-- The rollup key now includes the API key
+DO $$
+DECLARE
+    old_constraint TEXT;
+BEGIN
+    SELECT conname INTO old_constraint
+    FROM pg_constraint
+    WHERE conrelid = 'api_usage_daily'::regclass AND contype = 'u';
+
+    IF old_constraint IS NOT NULL THEN
+        EXECUTE format('ALTER TABLE api_usage_daily DROP CONSTRAINT %I', old_constraint);
+    END IF;
+END $$;
+
+ALTER TABLE api_usage_daily ADD CONSTRAINT uq_api_usage_daily_rollup
+    UNIQUE (organization_id, usage_date, endpoint_path, method, api_key_id);
+
+CREATE INDEX IF NOT EXISTS idx_api_usage_daily_org_key_date
+    ON api_usage_daily(organization_id, api_key_id, usage_date);
+CREATE INDEX IF NOT EXISTS idx_api_usage_daily_usage_date ON api_usage_daily(usage_date);
+
+COMMENT ON COLUMN api_usage_daily.api_key_id IS 'API key the requests were made with, 0 for user sessions';
+COMMENT ON COLUMN api_usage_daily.latency_histogram IS 'Request counts per response time bucket (see domain latency_histogram)';
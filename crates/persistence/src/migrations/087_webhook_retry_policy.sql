@@ -0,0 +1,8 @@
+-- Migration 087: Per-webhook retry policies
+-- Max attempts, backoff strategy, request timeout and retryable status codes
+-- of a webhook's deliveries; NULL means the default policy (4 attempts,
+-- immediately and after 1 and 5 minutes, 5s timeout, any non-2xx retried).
+
+ALTER TABLE webhooks ADD COLUMN retry_policy JSONB;
+
+COMMENT ON COLUMN webhooks.retry_policy IS 'Delivery retry policy; NULL means the default policy';
@@ -0,0 +1,18 @@
+-- Migration 088: Public trip share links
+-- A share link gives unauthenticated, read-only access to a trip until it
+-- expires or is revoked. Only the SHA-256 hash of the link token is stored.
+
+CREATE TABLE trip_share_links (
+    id          UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    trip_id     UUID NOT NULL REFERENCES trips(id) ON DELETE CASCADE,
+    token_hash  VARCHAR(64) NOT NULL UNIQUE,
+    expires_at  TIMESTAMPTZ NOT NULL,
+    revoked_at  TIMESTAMPTZ,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_trip_share_links_trip_id ON trip_share_links(trip_id);
+CREATE INDEX idx_trip_share_links_expires_at ON trip_share_links(expires_at);
+
+COMMENT ON TABLE trip_share_links IS 'Time-limited public links to a read-only trip view';
+COMMENT ON COLUMN trip_share_links.token_hash IS 'SHA-256 hex digest of the link token';
@@ -0,0 +1,24 @@
+-- Migration 089: Group API tokens
+-- Group owners mint tokens for read-only integrations (e.g. a wall-mounted
+-- dashboard). Each token is bound to one group, has its own rate limit, and
+-- only the SHA-256 hash of the token is stored.
+
+CREATE TABLE group_api_tokens (
+    id                      UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    group_id                UUID NOT NULL REFERENCES groups(id) ON DELETE CASCADE,
+    name                    VARCHAR(100) NOT NULL,
+    token_prefix            VARCHAR(20) NOT NULL,
+    token_hash              VARCHAR(64) NOT NULL UNIQUE,
+    rate_limit_per_minute   INTEGER NOT NULL CHECK (rate_limit_per_minute > 0),
+    created_by              UUID REFERENCES users(id) ON DELETE SET NULL,
+    last_used_at            TIMESTAMPTZ,
+    expires_at              TIMESTAMPTZ,
+    revoked_at              TIMESTAMPTZ,
+    created_at              TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_group_api_tokens_group_id ON group_api_tokens(group_id);
+
+COMMENT ON TABLE group_api_tokens IS 'Group-bound tokens for read-only integrations';
+COMMENT ON COLUMN group_api_tokens.token_hash IS 'SHA-256 hex digest of the token';
+COMMENT ON COLUMN group_api_tokens.revoked_at IS 'Set when revoked; revoked tokens are kept for the token list';
@@ -0,0 +1,23 @@
+-- Migration 090: Trip edit history
+-- Records manual trip merges and splits. Merged-away trips are deleted, so
+-- trip IDs are kept as plain arrays rather than foreign keys.
+
+CREATE TABLE trip_edits (
+    id               UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    device_id        UUID NOT NULL REFERENCES devices(device_id) ON DELETE CASCADE,
+    edit_type        VARCHAR(10) NOT NULL,
+    source_trip_ids  UUID[] NOT NULL,
+    result_trip_ids  UUID[] NOT NULL,
+    split_at         BIGINT,
+    api_key_id       BIGINT,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT chk_trip_edits_type CHECK (edit_type IN ('MERGE', 'SPLIT'))
+);
+
+CREATE INDEX idx_trip_edits_source_trip_ids ON trip_edits USING GIN (source_trip_ids);
+CREATE INDEX idx_trip_edits_result_trip_ids ON trip_edits USING GIN (result_trip_ids);
+
+COMMENT ON TABLE trip_edits IS 'Audit trail of manual trip merges and splits';
+COMMENT ON COLUMN trip_edits.split_at IS 'Split time in milliseconds since epoch (SPLIT only)';
+COMMENT ON COLUMN trip_edits.api_key_id IS 'API key the edit was made with';
@@ -0,0 +1,29 @@
+-- Migration 091: Commute patterns
+-- Recurring trips of a device between two places, replaced nightly by the
+-- commute detection job from the device's recent trips.
+
+CREATE TABLE device_commutes (
+    id                        UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    device_id                 UUID NOT NULL REFERENCES devices(device_id) ON DELETE CASCADE,
+    origin_latitude           DOUBLE PRECISION NOT NULL,
+    origin_longitude          DOUBLE PRECISION NOT NULL,
+    destination_latitude      DOUBLE PRECISION NOT NULL,
+    destination_longitude     DOUBLE PRECISION NOT NULL,
+    trip_count                INTEGER NOT NULL,
+    typical_departure_minute  INTEGER NOT NULL,
+    departure_spread_minutes  INTEGER NOT NULL,
+    typical_duration_seconds  BIGINT NOT NULL,
+    weekdays                  SMALLINT[] NOT NULL,
+    first_departure_at        TIMESTAMPTZ NOT NULL,
+    last_departure_at         TIMESTAMPTZ NOT NULL,
+    detected_at               TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT chk_device_commutes_departure_minute
+        CHECK (typical_departure_minute BETWEEN 0 AND 1439)
+);
+
+CREATE INDEX idx_device_commutes_device_id ON device_commutes(device_id);
+
+COMMENT ON TABLE device_commutes IS 'Recurring trips between two places, detected from recent trips';
+COMMENT ON COLUMN device_commutes.typical_departure_minute IS 'Median departure time in minutes after midnight UTC';
+COMMENT ON COLUMN device_commutes.weekdays IS 'UTC weekdays the commute was made on, 1 = Monday';
@@ -0,0 +1,26 @@
+-- Migration 092: Organization webhook deliveries
+-- Deliveries of organization webhooks are logged in webhook_deliveries next
+-- to device webhook deliveries, so webhook_id can no longer reference the
+-- webhooks table. Deleting either kind of webhook deletes its deliveries.
+
+ALTER TABLE webhook_deliveries DROP CONSTRAINT IF EXISTS webhook_deliveries_webhook_id_fkey;
+
+CREATE OR REPLACE FUNCTION delete_webhook_deliveries()
+RETURNS TRIGGER AS $$
+BEGIN
+    DELETE FROM webhook_deliveries WHERE webhook_id = OLD.webhook_id;
+    RETURN OLD;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER delete_webhooks_deliveries
+    AFTER DELETE ON webhooks
+    FOR EACH ROW
+    EXECUTE FUNCTION delete_webhook_deliveries();
+
+CREATE TRIGGER delete_org_webhooks_deliveries
+    AFTER DELETE ON org_webhooks
+    FOR EACH ROW
+    EXECUTE FUNCTION delete_webhook_deliveries();
+
+COMMENT ON COLUMN webhook_deliveries.webhook_id IS 'Device webhook (webhooks) or organization webhook (org_webhooks)';
@@ -0,0 +1,8 @@
+-- Migration 093: Observer organization role
+-- Observers get read-only access to analytics, device lists and current
+-- device locations, with locations masked (rounded coordinates) and no
+-- location history or exports. Meant for external consultants.
+
+ALTER TYPE org_user_role ADD VALUE IF NOT EXISTS 'observer';
+
+COMMENT ON COLUMN org_users.role IS 'User role: owner (full access), admin (manage), member (view), observer (read-only, masked locations)';
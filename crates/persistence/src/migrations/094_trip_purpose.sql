@@ -0,0 +1,44 @@
+-- Migration 094: Trip purpose labels and classification rules
+-- Trips can be labeled with a purpose (business, personal or a custom label),
+-- either by the device owner or by the device's classification rules when a
+-- trip completes. Labels set by the owner are never overwritten by rules.
+
+ALTER TABLE trips
+    ADD COLUMN purpose        VARCHAR(50),
+    ADD COLUMN purpose_source VARCHAR(10),
+    ADD CONSTRAINT chk_trips_purpose_source
+        CHECK (purpose_source IN ('manual', 'rule')),
+    ADD CONSTRAINT chk_trips_purpose_labeled
+        CHECK ((purpose IS NULL) = (purpose_source IS NULL));
+
+CREATE INDEX idx_trips_device_purpose ON trips(device_id, purpose)
+    WHERE purpose IS NOT NULL;
+
+CREATE TABLE trip_classification_rules (
+    id                       UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    device_id                UUID NOT NULL REFERENCES devices(device_id) ON DELETE CASCADE,
+    name                     VARCHAR(100) NOT NULL,
+    purpose                  VARCHAR(50) NOT NULL,
+    priority                 INTEGER NOT NULL DEFAULT 0,
+    start_geofence_id        UUID REFERENCES geofences(geofence_id) ON DELETE CASCADE,
+    end_geofence_id          UUID REFERENCES geofences(geofence_id) ON DELETE CASCADE,
+    weekdays                 SMALLINT[] NOT NULL DEFAULT '{}',
+    departure_after_minute   INTEGER,
+    departure_before_minute  INTEGER,
+    enabled                  BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at               TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at               TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT chk_trip_rules_departure_window
+        CHECK ((departure_after_minute IS NULL) = (departure_before_minute IS NULL)
+               AND (departure_after_minute IS NULL OR departure_after_minute BETWEEN 0 AND 1439)
+               AND (departure_before_minute IS NULL OR departure_before_minute BETWEEN 0 AND 1439))
+);
+
+CREATE INDEX idx_trip_classification_rules_device_id ON trip_classification_rules(device_id);
+
+COMMENT ON COLUMN trips.purpose IS 'Purpose label, e.g. business or personal';
+COMMENT ON COLUMN trips.purpose_source IS 'manual when set by the owner, rule when set by a classification rule';
+COMMENT ON TABLE trip_classification_rules IS 'Rules labeling completed trips with a purpose; the highest priority match wins';
+COMMENT ON COLUMN trip_classification_rules.weekdays IS 'UTC weekdays the trip must depart on, 1 = Monday; empty means any day';
+COMMENT ON COLUMN trip_classification_rules.departure_after_minute IS 'Departure window start in minutes after midnight UTC';
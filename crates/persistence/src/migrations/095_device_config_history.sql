@@ -0,0 +1,109 @@
+-- Migration 095: Device configuration history
+-- Records every version of a device's settings and policy assignment with
+-- the period it was in effect, so the configuration of a device can be
+-- queried as of a past timestamp. Triggers keep the history complete
+-- regardless of which code path changes the configuration.
+
+-- Setting values and locks of a device; valid_to is NULL while current.
+CREATE TABLE device_setting_history (
+    id           BIGSERIAL PRIMARY KEY,
+    device_id    UUID NOT NULL REFERENCES devices(device_id) ON DELETE CASCADE,
+    setting_key  VARCHAR(100) NOT NULL,
+    value        JSONB NOT NULL,
+    is_locked    BOOLEAN NOT NULL,
+    valid_from   TIMESTAMPTZ NOT NULL,
+    valid_to     TIMESTAMPTZ
+);
+
+CREATE INDEX idx_device_setting_history_device_time
+    ON device_setting_history(device_id, valid_from);
+CREATE UNIQUE INDEX idx_device_setting_history_current
+    ON device_setting_history(device_id, setting_key) WHERE valid_to IS NULL;
+
+-- Policy assignments of a device. policy_id has no foreign key so the
+-- history outlives deleted policies.
+CREATE TABLE device_policy_assignments (
+    id          BIGSERIAL PRIMARY KEY,
+    device_id   UUID NOT NULL REFERENCES devices(device_id) ON DELETE CASCADE,
+    policy_id   UUID NOT NULL,
+    valid_from  TIMESTAMPTZ NOT NULL,
+    valid_to    TIMESTAMPTZ
+);
+
+CREATE INDEX idx_device_policy_assignments_device_time
+    ON device_policy_assignments(device_id, valid_from);
+CREATE UNIQUE INDEX idx_device_policy_assignments_current
+    ON device_policy_assignments(device_id) WHERE valid_to IS NULL;
+
+-- Existing settings have been in effect since their last update. Existing
+-- policy assignments are only known from now on.
+INSERT INTO device_setting_history (device_id, setting_key, value, is_locked, valid_from)
+SELECT device_id, setting_key, value, is_locked, updated_at
+FROM device_settings;
+
+INSERT INTO device_policy_assignments (device_id, policy_id, valid_from)
+SELECT device_id, policy_id, NOW()
+FROM devices
+WHERE policy_id IS NOT NULL;
+
+CREATE OR REPLACE FUNCTION record_device_setting_history()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'UPDATE'
+       AND OLD.value = NEW.value
+       AND OLD.is_locked = NEW.is_locked THEN
+        RETURN NEW;
+    END IF;
+
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        UPDATE device_setting_history
+        SET valid_to = NOW()
+        WHERE device_id = OLD.device_id
+          AND setting_key = OLD.setting_key
+          AND valid_to IS NULL;
+    END IF;
+
+    IF TG_OP IN ('INSERT', 'UPDATE') THEN
+        INSERT INTO device_setting_history (device_id, setting_key, value, is_locked, valid_from)
+        VALUES (NEW.device_id, NEW.setting_key, NEW.value, NEW.is_locked, NOW());
+        RETURN NEW;
+    END IF;
+    RETURN OLD;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_record_device_setting_history
+    AFTER INSERT OR UPDATE OR DELETE ON device_settings
+    FOR EACH ROW
+    EXECUTE FUNCTION record_device_setting_history();
+
+CREATE OR REPLACE FUNCTION record_device_policy_assignment()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'UPDATE' THEN
+        IF OLD.policy_id IS NOT DISTINCT FROM NEW.policy_id THEN
+            RETURN NEW;
+        END IF;
+        UPDATE device_policy_assignments
+        SET valid_to = NOW()
+        WHERE device_id = NEW.device_id
+          AND valid_to IS NULL;
+    END IF;
+
+    IF NEW.policy_id IS NOT NULL THEN
+        INSERT INTO device_policy_assignments (device_id, policy_id, valid_from)
+        VALUES (NEW.device_id, NEW.policy_id, NOW());
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_record_device_policy_assignment
+    AFTER INSERT OR UPDATE OF policy_id ON devices
+    FOR EACH ROW
+    EXECUTE FUNCTION record_device_policy_assignment();
+
+COMMENT ON TABLE device_setting_history IS 'Versions of device settings with the period each was in effect';
+COMMENT ON COLUMN device_setting_history.valid_to IS 'When the version was replaced or removed; NULL while current';
+COMMENT ON TABLE device_policy_assignments IS 'Policy assignments of devices with the period each was in effect';
+COMMENT ON COLUMN device_policy_assignments.valid_to IS 'When the device left the policy; NULL while assigned';
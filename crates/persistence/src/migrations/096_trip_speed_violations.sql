@@ -0,0 +1,27 @@
+-- Migration 096: Trip speed violations
+-- Stretches of a trip driven above the posted speed limit, detected from the
+-- speed-limit annotations of the map-matched path. Re-running path
+-- correction replaces the violations of the trip.
+
+CREATE TABLE trip_speed_violations (
+    id               UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    trip_id          UUID NOT NULL REFERENCES trips(id) ON DELETE CASCADE,
+    device_id        UUID NOT NULL REFERENCES devices(device_id) ON DELETE CASCADE,
+    started_at       TIMESTAMPTZ NOT NULL,
+    ended_at         TIMESTAMPTZ NOT NULL,
+    start_latitude   DOUBLE PRECISION NOT NULL,
+    start_longitude  DOUBLE PRECISION NOT NULL,
+    end_latitude     DOUBLE PRECISION NOT NULL,
+    end_longitude    DOUBLE PRECISION NOT NULL,
+    speed_limit_kmh  DOUBLE PRECISION NOT NULL,
+    max_speed_kmh    DOUBLE PRECISION NOT NULL,
+    avg_speed_kmh    DOUBLE PRECISION NOT NULL,
+    sample_count     INTEGER NOT NULL,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT chk_trip_speed_violations_period CHECK (ended_at >= started_at)
+);
+
+CREATE INDEX idx_trip_speed_violations_trip
+    ON trip_speed_violations(trip_id, started_at);
+CREATE INDEX idx_trip_speed_violations_device
+    ON trip_speed_violations(device_id, started_at DESC);
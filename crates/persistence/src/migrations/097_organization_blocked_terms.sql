@@ -0,0 +1,7 @@
+-- Migration 097: Organization blocked terms
+-- Optional per-organization word filter for names and notes entered by
+-- members (webhook names, invitation notes, display names of invited
+-- users). Terms are stored lowercased; an empty list disables the filter.
+
+ALTER TABLE organization_settings
+    ADD COLUMN blocked_terms TEXT[] NOT NULL DEFAULT '{}';
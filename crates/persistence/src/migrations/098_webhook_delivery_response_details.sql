@@ -0,0 +1,11 @@
+-- Migration 098: Webhook delivery response details
+-- Record how long the last attempt took and the start of the receiver's
+-- response body, so users can debug their webhook receivers from the
+-- delivery history.
+
+ALTER TABLE webhook_deliveries
+    ADD COLUMN latency_ms INTEGER,
+    ADD COLUMN response_body TEXT;
+
+COMMENT ON COLUMN webhook_deliveries.latency_ms IS 'Duration of the last attempt in milliseconds';
+COMMENT ON COLUMN webhook_deliveries.response_body IS 'Response body of the last attempt, truncated';
@@ -0,0 +1,23 @@
+-- Migration 099: User-owned webhooks
+-- A webhook is owned either by a device (as before) or by a user. User-owned
+-- webhooks receive events from all of the user's devices, or only from the
+-- devices listed in device_ids, so they keep working when the user replaces
+-- a phone. Device-owned webhooks can be converted to user-owned ones.
+
+ALTER TABLE webhooks
+    ALTER COLUMN owner_device_id DROP NOT NULL,
+    ADD COLUMN owner_user_id UUID REFERENCES users(id) ON DELETE CASCADE,
+    ADD COLUMN device_ids UUID[],
+    ADD CONSTRAINT webhooks_single_owner_check
+        CHECK (num_nonnulls(owner_device_id, owner_user_id) = 1),
+    ADD CONSTRAINT webhooks_device_ids_user_owned_check
+        CHECK (device_ids IS NULL OR owner_user_id IS NOT NULL);
+
+-- Names are unique per owner
+CREATE UNIQUE INDEX IF NOT EXISTS idx_webhooks_owner_user_id_name
+    ON webhooks(owner_user_id, name)
+    WHERE owner_user_id IS NOT NULL;
+
+COMMENT ON COLUMN webhooks.owner_device_id IS 'Device that owns this webhook (NULL for user-owned webhooks)';
+COMMENT ON COLUMN webhooks.owner_user_id IS 'User that owns this webhook (NULL for device-owned webhooks)';
+COMMENT ON COLUMN webhooks.device_ids IS 'Devices a user-owned webhook receives events from; NULL for all of the user''s devices';
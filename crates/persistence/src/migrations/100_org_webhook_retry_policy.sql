@@ -0,0 +1,7 @@
+-- Migration 100: Retry policies for organization webhooks
+-- Same JSON shape and bounds as webhooks.retry_policy; NULL means the default
+-- policy (4 attempts, immediately and after 1 and 5 minutes, 5s timeout).
+
+ALTER TABLE org_webhooks ADD COLUMN retry_policy JSONB;
+
+COMMENT ON COLUMN org_webhooks.retry_policy IS 'Delivery retry policy; NULL means the default policy';
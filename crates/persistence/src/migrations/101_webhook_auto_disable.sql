@@ -0,0 +1,16 @@
+-- Migration 101: Automatic disabling of failing webhooks
+-- consecutive_failures is reset whenever the circuit breaker opens, so the
+-- failure streak since the last success is counted separately. Webhooks whose
+-- streak reaches the configured threshold are disabled and their owner is
+-- notified; re-enabling clears the streak.
+
+ALTER TABLE webhooks
+ADD COLUMN failure_streak INTEGER NOT NULL DEFAULT 0,
+ADD COLUMN auto_disabled_at TIMESTAMPTZ NULL;
+
+COMMENT ON COLUMN webhooks.failure_streak IS 'Delivery failures since the last success, not reset by the circuit breaker';
+COMMENT ON COLUMN webhooks.auto_disabled_at IS 'When the webhook was disabled for failing; NULL if not auto-disabled';
+
+CREATE INDEX IF NOT EXISTS idx_webhooks_failure_streak
+ON webhooks(failure_streak)
+WHERE enabled = true AND failure_streak > 0;
@@ -0,0 +1,7 @@
+-- Migration 102: Custom payload templates for webhooks
+-- JSON template whose string values may contain {{placeholders}} rendered
+-- per event; NULL means the default geofence event payload.
+
+ALTER TABLE webhooks ADD COLUMN payload_template JSONB;
+
+COMMENT ON COLUMN webhooks.payload_template IS 'Custom delivery payload template; NULL means the default payload';
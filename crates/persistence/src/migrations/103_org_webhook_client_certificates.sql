@@ -0,0 +1,13 @@
+-- Migration 103: Client certificates for organization webhooks
+-- Webhooks to internal systems may require mutual TLS. The private key is
+-- encrypted by the application (AES-256-GCM, security.webhook_client_cert_key)
+-- and stored as nonce || ciphertext.
+
+ALTER TABLE org_webhooks
+    ADD COLUMN client_cert_pem TEXT,
+    ADD COLUMN client_key_encrypted BYTEA,
+    ADD CONSTRAINT org_webhooks_client_cert_complete
+        CHECK ((client_cert_pem IS NULL) = (client_key_encrypted IS NULL));
+
+COMMENT ON COLUMN org_webhooks.client_cert_pem IS 'PEM client certificate presented for mutual TLS';
+COMMENT ON COLUMN org_webhooks.client_key_encrypted IS 'Encrypted private key of the client certificate';
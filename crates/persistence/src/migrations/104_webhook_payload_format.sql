@@ -0,0 +1,13 @@
+-- Migration 104: Payload formats for webhooks
+-- 'default' delivers the event payload as is, 'cloudevents' wraps it in a
+-- CloudEvents 1.0 structured-mode JSON envelope.
+
+ALTER TABLE webhooks
+    ADD COLUMN payload_format VARCHAR(20) NOT NULL DEFAULT 'default',
+    ADD CONSTRAINT webhooks_payload_format_check
+        CHECK (payload_format IN ('default', 'cloudevents'));
+
+ALTER TABLE org_webhooks
+    ADD COLUMN payload_format VARCHAR(20) NOT NULL DEFAULT 'default',
+    ADD CONSTRAINT org_webhooks_payload_format_check
+        CHECK (payload_format IN ('default', 'cloudevents'));
@@ -0,0 +1,35 @@
+-- Migration 105: Webhook dead-letter queue
+-- Deliveries that exhaust their retries are copied here with their full
+-- payload. Dead letters outlive the delivery log retention until they are
+-- requeued or purged. Both device and organization webhooks use the queue.
+
+CREATE TABLE IF NOT EXISTS webhook_dead_letters (
+    id BIGSERIAL PRIMARY KEY,
+    dead_letter_id UUID NOT NULL DEFAULT gen_random_uuid() UNIQUE,
+    delivery_id UUID NOT NULL UNIQUE,
+    webhook_id UUID NOT NULL,
+    event_id UUID REFERENCES geofence_events(event_id) ON DELETE SET NULL,
+    event_type VARCHAR(50) NOT NULL,
+    payload JSONB NOT NULL,
+    attempts INTEGER NOT NULL,
+    response_code INTEGER,
+    error_message TEXT,
+    delivery_created_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_dead_letters_webhook_id
+    ON webhook_dead_letters(webhook_id, created_at DESC);
+
+-- Deleting either kind of webhook also deletes its dead letters
+CREATE OR REPLACE FUNCTION delete_webhook_deliveries()
+RETURNS TRIGGER AS $$
+BEGIN
+    DELETE FROM webhook_deliveries WHERE webhook_id = OLD.webhook_id;
+    DELETE FROM webhook_dead_letters WHERE webhook_id = OLD.webhook_id;
+    RETURN OLD;
+END;
+$$ LANGUAGE plpgsql;
+
+COMMENT ON TABLE webhook_dead_letters IS 'Webhook deliveries that failed after their final retry';
+COMMENT ON COLUMN webhook_dead_letters.delivery_created_at IS 'When the original delivery was created';
@@ -0,0 +1,28 @@
+-- Migration 106: Transactional outbox
+-- Side effects of domain changes (webhook fan-out, push notifications) are
+-- written here in the same transaction as the change and dispatched by the
+-- outbox_dispatch job, so a crash between commit and dispatch loses nothing.
+
+CREATE TABLE IF NOT EXISTS event_outbox (
+    id BIGSERIAL PRIMARY KEY,
+    kind VARCHAR(50) NOT NULL,
+    payload JSONB NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    locked_until TIMESTAMPTZ,
+    processed_at TIMESTAMPTZ,
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Poller scans unprocessed messages in order
+CREATE INDEX IF NOT EXISTS idx_event_outbox_unprocessed
+    ON event_outbox(next_attempt_at, id)
+    WHERE processed_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_event_outbox_processed_at
+    ON event_outbox(processed_at)
+    WHERE processed_at IS NOT NULL;
+
+COMMENT ON TABLE event_outbox IS 'Side effects of committed domain changes awaiting dispatch';
+COMMENT ON COLUMN event_outbox.locked_until IS 'Lease of the dispatcher that claimed the message';
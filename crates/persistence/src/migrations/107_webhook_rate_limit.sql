@@ -0,0 +1,14 @@
+-- Migration 107: Per-webhook delivery rate limit
+-- Deliveries beyond max_deliveries_per_minute are held back; while one is
+-- held, newer events of the same device replace its payload so only the
+-- latest is sent.
+
+ALTER TABLE webhooks ADD COLUMN IF NOT EXISTS max_deliveries_per_minute INTEGER
+    CHECK (max_deliveries_per_minute BETWEEN 1 AND 600);
+
+COMMENT ON COLUMN webhooks.max_deliveries_per_minute IS 'Delivery attempts allowed per minute; unlimited if NULL';
+
+-- Attempts of a webhook within the rate limit window
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_webhook_last_attempt
+    ON webhook_deliveries(webhook_id, last_attempt_at)
+    WHERE last_attempt_at IS NOT NULL;
@@ -0,0 +1,24 @@
+-- Migration 108: Batched webhook delivery mode
+-- Batched webhooks collect events for batch_window_secs or up to
+-- batch_max_events and receive them as one delivery with a JSON array.
+
+ALTER TABLE webhooks
+    ADD COLUMN IF NOT EXISTS batch_window_secs INTEGER
+        CHECK (batch_window_secs BETWEEN 5 AND 300),
+    ADD COLUMN IF NOT EXISTS batch_max_events INTEGER
+        CHECK (batch_max_events BETWEEN 2 AND 100);
+
+COMMENT ON COLUMN webhooks.batch_window_secs IS 'Seconds events are collected per batch; not batched if NULL';
+COMMENT ON COLUMN webhooks.batch_max_events IS 'Events per batch delivery; the default if NULL';
+
+-- Events waiting for the next batch delivery of their webhook
+CREATE TABLE IF NOT EXISTS webhook_batch_events (
+    id BIGSERIAL PRIMARY KEY,
+    webhook_id UUID NOT NULL REFERENCES webhooks(webhook_id) ON DELETE CASCADE,
+    event_id UUID REFERENCES geofence_events(event_id) ON DELETE SET NULL,
+    payload JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_batch_events_webhook
+    ON webhook_batch_events(webhook_id, id);
@@ -0,0 +1,22 @@
+-- Migration 109: Email invitations to groups
+-- Invites sent by email are bound to the recipient's address and track
+-- whether they were accepted or revoked.
+
+ALTER TABLE group_invites
+    ADD COLUMN IF NOT EXISTS recipient_email VARCHAR(255),
+    ADD COLUMN IF NOT EXISTS status VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'accepted', 'revoked')),
+    ADD COLUMN IF NOT EXISTS accepted_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    ADD COLUMN IF NOT EXISTS accepted_at TIMESTAMPTZ;
+
+UPDATE group_invites SET status = 'revoked' WHERE is_active = false AND status = 'pending';
+UPDATE group_invites SET status = 'accepted'
+WHERE is_active = true AND current_uses >= max_uses AND status = 'pending';
+
+COMMENT ON COLUMN group_invites.recipient_email IS 'Lowercased email the invite was sent to; only this user can accept it. NULL for shareable codes';
+COMMENT ON COLUMN group_invites.status IS 'pending, accepted (all uses taken) or revoked; expiry is derived from expires_at';
+COMMENT ON COLUMN group_invites.accepted_by IS 'User who took the last use of the invite';
+
+CREATE INDEX IF NOT EXISTS idx_group_invites_recipient
+    ON group_invites(group_id, recipient_email)
+    WHERE recipient_email IS NOT NULL AND status = 'pending';
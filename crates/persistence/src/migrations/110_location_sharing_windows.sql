@@ -0,0 +1,24 @@
+-- Migration 110: Time-boxed location sharing
+-- In groups whose default sharing mode is on_demand, a member's locations
+-- are shared only while their sharing window is open. Each member has at
+-- most one window per group; starting a new one replaces it.
+
+CREATE TABLE IF NOT EXISTS location_sharing_windows (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    group_id UUID NOT NULL REFERENCES groups(id) ON DELETE CASCADE,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    stopped_at TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT uq_location_sharing_windows_user_group UNIQUE (user_id, group_id),
+    CONSTRAINT chk_location_sharing_windows_expiry CHECK (expires_at > started_at)
+);
+
+CREATE INDEX IF NOT EXISTS idx_location_sharing_windows_group
+    ON location_sharing_windows(group_id, expires_at)
+    WHERE stopped_at IS NULL;
+
+COMMENT ON TABLE location_sharing_windows IS 'Time-boxed sharing of a member''s locations with a group';
+COMMENT ON COLUMN location_sharing_windows.stopped_at IS 'Set when the member stopped sharing before expires_at';
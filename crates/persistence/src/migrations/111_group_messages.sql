@@ -0,0 +1,33 @@
+-- Migration 111: Group messages
+-- Lightweight per-group messages: announcements from group admins and
+-- "I arrived" check-ins tied to a location. Old messages are pruned by age
+-- and by a per-group cap.
+
+CREATE TABLE IF NOT EXISTS group_messages (
+    id BIGSERIAL PRIMARY KEY,
+    message_id UUID NOT NULL DEFAULT gen_random_uuid(),
+    group_id UUID NOT NULL REFERENCES groups(id) ON DELETE CASCADE,
+    sender_user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    kind VARCHAR(20) NOT NULL,
+    body TEXT,
+    latitude DOUBLE PRECISION,
+    longitude DOUBLE PRECISION,
+    place_name VARCHAR(100),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT uq_group_messages_message_id UNIQUE (message_id),
+    CONSTRAINT chk_group_messages_kind CHECK (kind IN ('announcement', 'check_in')),
+    CONSTRAINT chk_group_messages_check_in_location CHECK (
+        kind <> 'check_in' OR (latitude IS NOT NULL AND longitude IS NOT NULL)
+    )
+);
+
+-- Newest-first cursor pagination within a group (created_at, id)
+CREATE INDEX IF NOT EXISTS idx_group_messages_group_created
+    ON group_messages(group_id, created_at DESC, id DESC);
+
+-- Retention cleanup
+CREATE INDEX IF NOT EXISTS idx_group_messages_created_at
+    ON group_messages(created_at);
+
+COMMENT ON TABLE group_messages IS 'Group announcements and location check-ins';
@@ -0,0 +1,17 @@
+-- Migration 112: Nested groups
+-- A group may belong to a parent group. Owners and admins of an ancestor
+-- group administer its sub-groups, and geofence events of a sub-group's
+-- devices are also archived in the ancestor groups. Cycles are rejected by
+-- the repository when a parent is set.
+
+ALTER TABLE groups
+    ADD COLUMN IF NOT EXISTS parent_group_id UUID REFERENCES groups(id) ON DELETE SET NULL;
+
+ALTER TABLE groups
+    ADD CONSTRAINT chk_groups_parent_not_self CHECK (parent_group_id <> id);
+
+CREATE INDEX IF NOT EXISTS idx_groups_parent
+    ON groups(parent_group_id)
+    WHERE parent_group_id IS NOT NULL;
+
+COMMENT ON COLUMN groups.parent_group_id IS 'Parent group; NULL for top-level groups';
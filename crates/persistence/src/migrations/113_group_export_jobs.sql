@@ -0,0 +1,34 @@
+-- Migration 113: Group data export jobs
+-- Group owners export a group's members, devices, location history,
+-- geofences and trips as a ZIP archive. Archives are built in the
+-- background, stored under the reports directory and removed once the job
+-- expires.
+
+CREATE TABLE IF NOT EXISTS group_export_jobs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    job_id VARCHAR(100) NOT NULL UNIQUE,
+    group_id UUID NOT NULL REFERENCES groups(id) ON DELETE CASCADE,
+    requested_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    record_count BIGINT,
+    file_path TEXT,
+    file_size BIGINT,
+    error_message TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL DEFAULT (NOW() + INTERVAL '24 hours'),
+    completed_at TIMESTAMPTZ,
+
+    CONSTRAINT chk_group_export_jobs_status
+        CHECK (status IN ('pending', 'processing', 'completed', 'failed', 'expired'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_group_export_jobs_group
+    ON group_export_jobs(group_id, created_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_group_export_jobs_expiry
+    ON group_export_jobs(expires_at);
+
+COMMENT ON TABLE group_export_jobs IS 'Async ZIP exports of all data of a group';
+COMMENT ON COLUMN group_export_jobs.job_id IS 'User-facing job identifier (group_export_<random>)';
+COMMENT ON COLUMN group_export_jobs.file_path IS 'Archive file name, relative to the reports directory';
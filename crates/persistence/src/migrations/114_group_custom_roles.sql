@@ -0,0 +1,30 @@
+-- Migration 114: Custom group roles
+-- Organizations define custom roles for the groups of their users. Each
+-- role grants a bitset of granular permissions (view_locations = 1,
+-- manage_geofences = 2, manage_devices = 4, invite_members = 8) that
+-- replaces the built-in permissions of members and viewers holding it.
+
+CREATE TABLE IF NOT EXISTS group_custom_roles (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    name VARCHAR(50) NOT NULL,
+    description TEXT,
+    permissions INTEGER NOT NULL DEFAULT 0,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT uq_group_custom_role_name UNIQUE (organization_id, name),
+    CONSTRAINT chk_group_custom_role_permissions CHECK (permissions >= 0)
+);
+
+ALTER TABLE group_memberships
+    ADD COLUMN IF NOT EXISTS custom_role_id UUID
+        REFERENCES group_custom_roles(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_group_memberships_custom_role
+    ON group_memberships(custom_role_id)
+    WHERE custom_role_id IS NOT NULL;
+
+COMMENT ON COLUMN group_custom_roles.permissions IS 'Bitset of granted group permissions';
+COMMENT ON COLUMN group_memberships.custom_role_id IS 'Custom role replacing the permissions of a member or viewer';
@@ -0,0 +1,21 @@
+-- Migration 115: Per-group notification preferences
+-- Each member chooses, per group, which events push notifications to their
+-- devices. Members without a row get every notification.
+
+CREATE TABLE IF NOT EXISTS group_notification_preferences (
+    group_id UUID NOT NULL,
+    user_id UUID NOT NULL,
+    geofence_events BOOLEAN NOT NULL DEFAULT true,
+    sos BOOLEAN NOT NULL DEFAULT true,
+    low_battery BOOLEAN NOT NULL DEFAULT true,
+    member_joins BOOLEAN NOT NULL DEFAULT true,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (group_id, user_id),
+    -- Preferences go away with the membership
+    CONSTRAINT fk_group_notification_preferences_membership
+        FOREIGN KEY (group_id, user_id)
+        REFERENCES group_memberships(group_id, user_id) ON DELETE CASCADE
+);
+
+COMMENT ON TABLE group_notification_preferences IS 'Push notification categories a member receives for a group';
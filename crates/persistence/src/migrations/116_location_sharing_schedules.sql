@@ -0,0 +1,20 @@
+-- Migration 116: Recurring location sharing schedules
+-- A member can limit sharing with a group to a weekly schedule, e.g.
+-- weekdays during work hours. Outside the schedule other members see the
+-- member's sharing as paused. Each member has at most one schedule per group.
+
+CREATE TABLE IF NOT EXISTS location_sharing_schedules (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    group_id UUID NOT NULL REFERENCES groups(id) ON DELETE CASCADE,
+    schedule JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (user_id, group_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_location_sharing_schedules_group
+    ON location_sharing_schedules(group_id);
+
+COMMENT ON TABLE location_sharing_schedules IS 'Weekly schedule limiting when a member shares locations with a group';
+COMMENT ON COLUMN location_sharing_schedules.schedule IS 'WeeklySchedule JSON: utc_offset_minutes and windows';
@@ -0,0 +1,12 @@
+-- Migration 117: Member and device nicknames within groups
+-- Group admins can give members and devices an alias (e.g. "Mom's phone")
+-- that is shown only inside that group.
+
+ALTER TABLE group_memberships
+    ADD COLUMN IF NOT EXISTS nickname VARCHAR(50);
+
+ALTER TABLE device_group_memberships
+    ADD COLUMN IF NOT EXISTS nickname VARCHAR(50);
+
+COMMENT ON COLUMN group_memberships.nickname IS 'Alias of the member shown only inside the group';
+COMMENT ON COLUMN device_group_memberships.nickname IS 'Alias of the device shown only inside the group';
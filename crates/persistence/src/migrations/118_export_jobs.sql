@@ -0,0 +1,34 @@
+-- Migration 118: Generic export jobs
+-- Audit log export jobs become export jobs of several kinds, so full
+-- organization exports share their lifecycle. Jobs that build an archive
+-- store it under the reports directory instead of returning a data URL,
+-- and the archive is removed once the job expires.
+
+ALTER TABLE audit_export_jobs RENAME TO export_jobs;
+ALTER INDEX idx_audit_export_jobs_org RENAME TO idx_export_jobs_org;
+ALTER INDEX idx_audit_export_jobs_status RENAME TO idx_export_jobs_status;
+ALTER INDEX idx_audit_export_jobs_job_id RENAME TO idx_export_jobs_job_id;
+
+-- Status becomes plain text like the other job tables
+ALTER TABLE export_jobs ALTER COLUMN status DROP DEFAULT;
+ALTER TABLE export_jobs ALTER COLUMN status TYPE VARCHAR(20) USING status::text;
+ALTER TABLE export_jobs ALTER COLUMN status SET DEFAULT 'pending';
+DROP TYPE audit_export_job_status;
+
+ALTER TABLE export_jobs
+    ADD COLUMN kind VARCHAR(30) NOT NULL DEFAULT 'audit_logs',
+    ADD COLUMN file_path TEXT,
+    ADD COLUMN file_size BIGINT,
+    ADD CONSTRAINT chk_export_jobs_status
+        CHECK (status IN ('pending', 'processing', 'completed', 'failed', 'expired')),
+    ADD CONSTRAINT chk_export_jobs_kind
+        CHECK (kind IN ('audit_logs', 'organization'));
+
+CREATE INDEX IF NOT EXISTS idx_export_jobs_org_kind
+    ON export_jobs(organization_id, kind, created_at DESC);
+
+COMMENT ON TABLE export_jobs IS 'Async organization export jobs with status and download location';
+COMMENT ON COLUMN export_jobs.job_id IS 'User-facing job identifier (export_<random> or org_export_<random>)';
+COMMENT ON COLUMN export_jobs.kind IS 'What is exported: audit_logs or the whole organization';
+COMMENT ON COLUMN export_jobs.format IS 'Format of audit log exports; organization exports are ZIP archives';
+COMMENT ON COLUMN export_jobs.file_path IS 'Archive file name, relative to the reports directory';
@@ -0,0 +1,37 @@
+-- Migration 119: Group exports as export jobs
+-- Group exports share the lifecycle of the other export jobs, so they move
+-- into export_jobs with kind 'group'. Groups do not belong to an
+-- organization, so a job belongs to either an organization or a group.
+
+ALTER TABLE export_jobs ALTER COLUMN organization_id DROP NOT NULL;
+
+ALTER TABLE export_jobs
+    ADD COLUMN group_id UUID REFERENCES groups(id) ON DELETE CASCADE,
+    ADD COLUMN requested_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    DROP CONSTRAINT chk_export_jobs_kind,
+    ADD CONSTRAINT chk_export_jobs_kind
+        CHECK (kind IN ('audit_logs', 'organization', 'group')),
+    ADD CONSTRAINT chk_export_jobs_owner
+        CHECK ((kind = 'group') = (group_id IS NOT NULL AND organization_id IS NULL));
+
+INSERT INTO export_jobs (
+    job_id, group_id, requested_by, kind, status, format, record_count,
+    file_path, file_size, error_message, created_at, updated_at, expires_at,
+    completed_at
+)
+SELECT
+    job_id, group_id, requested_by, 'group', status, 'json', record_count,
+    file_path, file_size, error_message, created_at, updated_at, expires_at,
+    completed_at
+FROM group_export_jobs;
+
+DROP TABLE group_export_jobs;
+
+CREATE INDEX IF NOT EXISTS idx_export_jobs_group
+    ON export_jobs(group_id, created_at DESC)
+    WHERE group_id IS NOT NULL;
+
+COMMENT ON COLUMN export_jobs.job_id IS 'User-facing job identifier (export_<random>, org_export_<random> or group_export_<random>)';
+COMMENT ON COLUMN export_jobs.kind IS 'What is exported: audit_logs, the whole organization or a group';
+COMMENT ON COLUMN export_jobs.group_id IS 'Exported group of group exports; organization_id is null for them';
+COMMENT ON COLUMN export_jobs.requested_by IS 'User who started a group export';
@@ -0,0 +1,10 @@
+-- Migration 120: Quarantine location smoothing outliers
+-- Fixes dropped by location smoothing are kept in the quarantine table like
+-- filtered ones. Counting them since the last stored location lets smoothing
+-- restart after a run of outliers across single-point uploads.
+
+ALTER TABLE location_quarantine DROP CONSTRAINT chk_quarantine_reason;
+ALTER TABLE location_quarantine ADD CONSTRAINT chk_quarantine_reason
+    CHECK (reason IN ('ACCURACY_TOO_LOW', 'IMPOSSIBLE_SPEED', 'SMOOTHING_OUTLIER'));
+
+COMMENT ON COLUMN location_quarantine.reason IS 'ACCURACY_TOO_LOW, IMPOSSIBLE_SPEED or SMOOTHING_OUTLIER';
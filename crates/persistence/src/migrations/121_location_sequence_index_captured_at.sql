@@ -0,0 +1,11 @@
+-- Migration 121: Include captured_at in the location sequence index
+-- TimescaleDB requires the partitioning column in every unique index, so
+-- converting locations to a hypertable failed on the (device_id,
+-- sequence_number) index. A replayed point carries its original capture
+-- time, so including captured_at still deduplicates replays.
+
+DROP INDEX IF EXISTS idx_locations_device_sequence;
+
+CREATE UNIQUE INDEX idx_locations_device_sequence
+    ON locations(device_id, sequence_number, captured_at)
+    WHERE sequence_number IS NOT NULL;
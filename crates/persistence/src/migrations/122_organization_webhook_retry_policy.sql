@@ -0,0 +1,8 @@
+-- Migration 122: Organization-wide webhook retry policy
+-- Set by an organization admin, it replaces the retry policy of every
+-- webhook of the organization. Same JSON shape and bounds as
+-- org_webhooks.retry_policy; NULL leaves each webhook's own policy in effect.
+
+ALTER TABLE organization_settings ADD COLUMN webhook_retry_policy JSONB;
+
+COMMENT ON COLUMN organization_settings.webhook_retry_policy IS 'Retry policy overriding the policies of the organization''s webhooks; NULL means no override';
@@ -0,0 +1,159 @@
+//! Pinned SQL for the hottest repository queries.
+//!
+//! These statements run on nearly every request, so a plan regression (a
+//! sequential scan over `locations`, or a membership lookup through the
+//! single-column user index) is felt immediately. Each query is written so
+//! its predicates and sort key line up with exactly one index, and is
+//! executed as a persistent prepared statement. The API integration suite
+//! (`query_plans_integration.rs`) runs `EXPLAIN` against the test database
+//! and fails if the planner stops choosing [`PinnedQuery::index`].
+//!
+//! Changing one of these queries, or the index it names, needs the same
+//! review as a migration.
+
+/// A reviewed query together with the index its plan must use.
+#[derive(Debug, Clone, Copy)]
+pub struct PinnedQuery {
+    /// Name used for query metrics.
+    pub name: &'static str,
+    pub sql: &'static str,
+    /// Index the plan is expected to scan.
+    pub index: &'static str,
+}
+
+/// Latest location of a device.
+///
+/// The sort key spells out both columns of `idx_locations_device_captured`
+/// `(device_id, captured_at DESC)`, so the first index entry is the answer
+/// and no sort node is needed.
+pub const LATEST_LOCATION: PinnedQuery = PinnedQuery {
+    name: "get_latest_location",
+    sql: r#"
+        SELECT id, device_id, latitude, longitude, accuracy, altitude, bearing,
+               speed, provider, battery_level, network_type, captured_at, created_at,
+               transportation_mode, detection_source, trip_id, source, trust_score
+        FROM locations
+        WHERE device_id = $1
+        ORDER BY device_id, captured_at DESC
+        LIMIT 1
+    "#,
+    index: "idx_locations_device_captured",
+};
+
+/// A user's membership row in a group.
+///
+/// Both equality predicates are on the leading columns of the unique
+/// `(group_id, user_id)` constraint index rather than the single-column
+/// group or user indexes.
+pub const GROUP_MEMBERSHIP: PinnedQuery = PinnedQuery {
+    name: "get_group_membership",
+    sql: r#"
+        SELECT id, group_id, user_id, role, invited_by, joined_at, updated_at
+        FROM group_memberships
+        WHERE group_id = $1 AND user_id = $2
+    "#,
+    index: "uq_group_membership",
+};
+
+/// Whether a user is a member of a group (index-only scan).
+pub const IS_GROUP_MEMBER: PinnedQuery = PinnedQuery {
+    name: "is_group_member",
+    sql: r#"
+        SELECT EXISTS (
+            SELECT 1 FROM group_memberships
+            WHERE group_id = $1 AND user_id = $2
+        )
+    "#,
+    index: "uq_group_membership",
+};
+
+/// Group with the caller's membership, checking a specific device.
+///
+/// Split from [`GROUP_WITH_MEMBERSHIP_ANY_DEVICE`] instead of using
+/// `(dgm.device_id = $3 OR $3 IS NULL)`, which prevents the planner from
+/// using an index on the device predicate.
+pub const GROUP_WITH_MEMBERSHIP_FOR_DEVICE: PinnedQuery = PinnedQuery {
+    name: "find_group_with_membership",
+    sql: r#"
+        SELECT
+            g.id, g.name, g.slug, g.description, g.icon_emoji, g.max_devices, g.is_active,
+            g.settings, g.created_by, g.created_at, g.updated_at, g.parent_group_id,
+            gm.id as membership_id, gm.role, gm.joined_at,
+            (SELECT COUNT(*) FROM group_memberships WHERE group_id = g.id) as member_count,
+            (SELECT COUNT(*) FROM devices WHERE group_id = g.slug AND active = true) as device_count,
+            EXISTS (
+                SELECT 1 FROM device_group_memberships dgm
+                JOIN devices d ON dgm.device_id = d.device_id
+                WHERE dgm.group_id = g.id
+                AND dgm.device_id = $3
+                AND d.owner_user_id = $2
+                AND d.active = true
+            ) as has_current_device
+        FROM groups g
+        JOIN group_memberships gm ON g.id = gm.group_id
+        WHERE g.id = $1 AND gm.user_id = $2 AND g.is_active = true
+    "#,
+    index: "uq_group_membership",
+};
+
+/// Group with the caller's membership, checking any of the caller's devices.
+pub const GROUP_WITH_MEMBERSHIP_ANY_DEVICE: PinnedQuery = PinnedQuery {
+    name: "find_group_with_membership",
+    sql: r#"
+        SELECT
+            g.id, g.name, g.slug, g.description, g.icon_emoji, g.max_devices, g.is_active,
+            g.settings, g.created_by, g.created_at, g.updated_at, g.parent_group_id,
+            gm.id as membership_id, gm.role, gm.joined_at,
+            (SELECT COUNT(*) FROM group_memberships WHERE group_id = g.id) as member_count,
+            (SELECT COUNT(*) FROM devices WHERE group_id = g.slug AND active = true) as device_count,
+            EXISTS (
+                SELECT 1 FROM device_group_memberships dgm
+                JOIN devices d ON dgm.device_id = d.device_id
+                WHERE dgm.group_id = g.id
+                AND d.owner_user_id = $2
+                AND d.active = true
+            ) as has_current_device
+        FROM groups g
+        JOIN group_memberships gm ON g.id = gm.group_id
+        WHERE g.id = $1 AND gm.user_id = $2 AND g.is_active = true
+    "#,
+    index: "uq_group_membership",
+};
+
+/// Every pinned query, for plan regression tests.
+pub const PINNED_QUERIES: &[PinnedQuery] = &[
+    LATEST_LOCATION,
+    GROUP_MEMBERSHIP,
+    IS_GROUP_MEMBER,
+    GROUP_WITH_MEMBERSHIP_FOR_DEVICE,
+    GROUP_WITH_MEMBERSHIP_ANY_DEVICE,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pinned_queries_name_their_index() {
+        for query in PINNED_QUERIES {
+            assert!(!query.index.is_empty(), "{} has no index", query.name);
+            assert!(
+                !query.sql.contains(" OR $"),
+                "{} has an OR predicate",
+                query.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_device_variants_differ_only_in_device_predicate() {
+        let for_device = GROUP_WITH_MEMBERSHIP_FOR_DEVICE
+            .sql
+            .replace("AND dgm.device_id = $3\n", "");
+        let normalize = |s: &str| s.split_whitespace().collect::<Vec<_>>().join(" ");
+        assert_eq!(
+            normalize(&for_device),
+            normalize(GROUP_WITH_MEMBERSHIP_ANY_DEVICE.sql)
+        );
+    }
+}
@@ -7,9 +7,19 @@ use chrono::{DateTime, Utc};
 
 use crate::entities::{
     AdminGeofenceEntity, AdminGeofenceEventEntity, AdminGeofenceWithCreatorEntity,
-    GeofenceVisitCountEntity, LocationAnalyticsEntity,
+    GeofenceCategoryCountEntity, GeofenceVisitCountEntity, LocationAnalyticsEntity,
 };
 use crate::metrics::QueryTimer;
+use domain::models::AdminGeofenceQuery;
+
+/// Filters shared by the admin geofence list and count queries: `$1`
+/// organization, `$2` active, `$3` search, `$4` category, `$5` tag, `$6` color.
+const LIST_FILTER: &str = r#"ag.organization_id = $1
+              AND ($2::BOOLEAN IS NULL OR ag.active = $2)
+              AND ($3::TEXT IS NULL OR ag.name ILIKE '%' || $3 || '%' OR ag.description ILIKE '%' || $3 || '%')
+              AND ($4::TEXT IS NULL OR LOWER(ag.category) = LOWER($4))
+              AND ($5::TEXT IS NULL OR LOWER(TRIM($5)) = ANY(ag.tags))
+              AND ($6::TEXT IS NULL OR LOWER(ag.color) = LOWER($6))"#;
 
 /// Repository for admin geofence database operations.
 #[derive(Clone)]
@@ -32,58 +42,22 @@ impl AdminGeofenceRepository {
     pub async fn count_geofences(
         &self,
         org_id: Uuid,
-        active: Option<bool>,
-        search: Option<&str>,
+        query: &AdminGeofenceQuery,
     ) -> Result<i64, sqlx::Error> {
         let timer = QueryTimer::new("count_admin_geofences");
 
-        let mut query =
-            String::from("SELECT COUNT(*) FROM admin_geofences WHERE organization_id = $1");
-
-        if active.is_some() {
-            query.push_str(" AND active = $2");
-        }
-
-        if search.is_some() {
-            if active.is_some() {
-                query.push_str(" AND (name ILIKE $3 OR description ILIKE $3)");
-            } else {
-                query.push_str(" AND (name ILIKE $2 OR description ILIKE $2)");
-            }
-        }
-
-        let result = match (active, search) {
-            (Some(active), Some(search)) => {
-                let search_pattern = format!("%{}%", search);
-                sqlx::query_scalar::<_, i64>(&query)
-                    .bind(org_id)
-                    .bind(active)
-                    .bind(search_pattern)
-                    .fetch_one(&self.pool)
-                    .await
-            }
-            (Some(active), None) => {
-                sqlx::query_scalar::<_, i64>(&query)
-                    .bind(org_id)
-                    .bind(active)
-                    .fetch_one(&self.pool)
-                    .await
-            }
-            (None, Some(search)) => {
-                let search_pattern = format!("%{}%", search);
-                sqlx::query_scalar::<_, i64>(&query)
-                    .bind(org_id)
-                    .bind(search_pattern)
-                    .fetch_one(&self.pool)
-                    .await
-            }
-            (None, None) => {
-                sqlx::query_scalar::<_, i64>(&query)
-                    .bind(org_id)
-                    .fetch_one(&self.pool)
-                    .await
-            }
-        };
+        let result = sqlx::query_scalar::<_, i64>(&format!(
+            "SELECT COUNT(*) FROM admin_geofences ag WHERE {}",
+            LIST_FILTER
+        ))
+        .bind(org_id)
+        .bind(query.active)
+        .bind(query.search.as_deref())
+        .bind(query.category.as_deref())
+        .bind(query.tag.as_deref())
+        .bind(query.color.as_deref())
+        .fetch_one(&self.pool)
+        .await;
 
         timer.record();
         result
@@ -93,33 +67,34 @@ impl AdminGeofenceRepository {
     pub async fn list_geofences(
         &self,
         org_id: Uuid,
-        active: Option<bool>,
-        search: Option<&str>,
+        query: &AdminGeofenceQuery,
         limit: u32,
         offset: u32,
     ) -> Result<Vec<AdminGeofenceWithCreatorEntity>, sqlx::Error> {
         let timer = QueryTimer::new("list_admin_geofences");
 
-        let result = sqlx::query_as::<_, AdminGeofenceWithCreatorEntity>(
+        let result = sqlx::query_as::<_, AdminGeofenceWithCreatorEntity>(&format!(
             r#"
             SELECT
                 ag.id, ag.geofence_id, ag.organization_id, ag.name, ag.description,
                 ag.latitude, ag.longitude, ag.radius_meters, ag.event_types,
-                ag.active, ag.color, ag.metadata, ag.created_by,
+                ag.active, ag.color, ag.category, ag.tags, ag.metadata, ag.created_by,
                 u.display_name as creator_name,
                 ag.created_at, ag.updated_at
             FROM admin_geofences ag
             LEFT JOIN users u ON ag.created_by = u.id
-            WHERE ag.organization_id = $1
-              AND ($2::BOOLEAN IS NULL OR ag.active = $2)
-              AND ($3::TEXT IS NULL OR ag.name ILIKE '%' || $3 || '%' OR ag.description ILIKE '%' || $3 || '%')
+            WHERE {}
             ORDER BY ag.created_at DESC
-            LIMIT $4 OFFSET $5
+            LIMIT $7 OFFSET $8
             "#,
-        )
+            LIST_FILTER
+        ))
         .bind(org_id)
-        .bind(active)
-        .bind(search)
+        .bind(query.active)
+        .bind(query.search.as_deref())
+        .bind(query.category.as_deref())
+        .bind(query.tag.as_deref())
+        .bind(query.color.as_deref())
         .bind(limit as i64)
         .bind(offset as i64)
         .fetch_all(&self.pool)
@@ -142,7 +117,7 @@ impl AdminGeofenceRepository {
             SELECT
                 ag.id, ag.geofence_id, ag.organization_id, ag.name, ag.description,
                 ag.latitude, ag.longitude, ag.radius_meters, ag.event_types,
-                ag.active, ag.color, ag.metadata, ag.created_by,
+                ag.active, ag.color, ag.category, ag.tags, ag.metadata, ag.created_by,
                 u.display_name as creator_name,
                 ag.created_at, ag.updated_at
             FROM admin_geofences ag
@@ -171,6 +146,8 @@ impl AdminGeofenceRepository {
         radius_meters: f32,
         event_types: &[String],
         color: Option<&str>,
+        category: Option<&str>,
+        tags: &[String],
         metadata: Option<&serde_json::Value>,
         created_by: Uuid,
     ) -> Result<AdminGeofenceEntity, sqlx::Error> {
@@ -178,9 +155,9 @@ impl AdminGeofenceRepository {
 
         let result = sqlx::query_as::<_, AdminGeofenceEntity>(
             r#"
-            INSERT INTO admin_geofences (organization_id, name, description, latitude, longitude, radius_meters, event_types, color, metadata, created_by)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
-            RETURNING id, geofence_id, organization_id, name, description, latitude, longitude, radius_meters, event_types, active, color, metadata, created_by, created_at, updated_at
+            INSERT INTO admin_geofences (organization_id, name, description, latitude, longitude, radius_meters, event_types, color, category, tags, metadata, created_by)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            RETURNING id, geofence_id, organization_id, name, description, latitude, longitude, radius_meters, event_types, active, color, category, tags, metadata, created_by, created_at, updated_at
             "#,
         )
         .bind(org_id)
@@ -191,6 +168,8 @@ impl AdminGeofenceRepository {
         .bind(radius_meters)
         .bind(event_types)
         .bind(color)
+        .bind(category)
+        .bind(tags)
         .bind(metadata)
         .bind(created_by)
         .fetch_one(&self.pool)
@@ -214,6 +193,8 @@ impl AdminGeofenceRepository {
         event_types: Option<&[String]>,
         active: Option<bool>,
         color: Option<&str>,
+        category: Option<&str>,
+        tags: Option<&[String]>,
         metadata: Option<&serde_json::Value>,
     ) -> Result<Option<AdminGeofenceEntity>, sqlx::Error> {
         let timer = QueryTimer::new("update_admin_geofence");
@@ -230,9 +211,11 @@ impl AdminGeofenceRepository {
                 event_types = COALESCE($8, event_types),
                 active = COALESCE($9, active),
                 color = COALESCE($10, color),
-                metadata = COALESCE($11, metadata)
+                metadata = COALESCE($11, metadata),
+                category = COALESCE($12, category),
+                tags = COALESCE($13, tags)
             WHERE organization_id = $1 AND geofence_id = $2
-            RETURNING id, geofence_id, organization_id, name, description, latitude, longitude, radius_meters, event_types, active, color, metadata, created_by, created_at, updated_at
+            RETURNING id, geofence_id, organization_id, name, description, latitude, longitude, radius_meters, event_types, active, color, category, tags, metadata, created_by, created_at, updated_at
             "#,
         )
         .bind(org_id)
@@ -246,6 +229,8 @@ impl AdminGeofenceRepository {
         .bind(active)
         .bind(color)
         .bind(metadata)
+        .bind(category)
+        .bind(tags)
         .fetch_optional(&self.pool)
         .await;
 
@@ -446,6 +431,40 @@ impl AdminGeofenceRepository {
         timer.record();
         result
     }
+
+    /// Count active geofences and their visits per category for organization.
+    pub async fn get_geofence_counts_by_category(
+        &self,
+        org_id: Uuid,
+    ) -> Result<Vec<GeofenceCategoryCountEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("get_geofence_counts_by_category");
+
+        let result = sqlx::query_as::<_, GeofenceCategoryCountEntity>(
+            r#"
+            SELECT
+                ag.category,
+                COUNT(DISTINCT ag.geofence_id) as geofence_count,
+                COUNT(ge.id) as visit_count
+            FROM admin_geofences ag
+            LEFT JOIN geofence_events ge ON ag.geofence_id = ge.geofence_id
+                AND ge.event_type = 'enter'
+                AND EXISTS (
+                    SELECT 1 FROM devices d
+                    WHERE d.device_id = ge.device_id AND d.organization_id = $1
+                )
+            WHERE ag.organization_id = $1
+              AND ag.active = true
+            GROUP BY ag.category
+            ORDER BY geofence_count DESC, ag.category NULLS LAST
+            "#,
+        )
+        .bind(org_id)
+        .fetch_all(&self.pool)
+        .await;
+
+        timer.record();
+        result
+    }
 }
 
 #[cfg(test)]
@@ -13,6 +13,7 @@ use crate::entities::{
     AdminGroupEntity, AdminGroupProfileEntity, AdminGroupSummaryEntity, GroupDeviceEntity,
     GroupMemberEntity,
 };
+use crate::query_plans::IS_GROUP_MEMBER;
 
 /// Repository for admin group management operations.
 #[derive(Clone)]
@@ -638,18 +639,12 @@ impl AdminGroupRepository {
         group_id: Uuid,
         user_id: Uuid,
     ) -> Result<bool, sqlx::Error> {
-        let exists: bool = sqlx::query_scalar(
-            r#"
-            SELECT EXISTS (
-                SELECT 1 FROM group_memberships
-                WHERE group_id = $1 AND user_id = $2
-            )
-            "#,
-        )
-        .bind(group_id)
-        .bind(user_id)
-        .fetch_one(&self.pool)
-        .await?;
+        let exists: bool = sqlx::query_scalar(IS_GROUP_MEMBER.sql)
+            .persistent(true)
+            .bind(group_id)
+            .bind(user_id)
+            .fetch_one(&self.pool)
+            .await?;
 
         Ok(exists)
     }
@@ -674,6 +669,92 @@ impl AdminGroupRepository {
         Ok(result.rows_affected() > 0)
     }
 
+    /// Change a member's role. Returns `None` if the user is not a member.
+    pub async fn update_group_member_role(
+        &self,
+        group_id: Uuid,
+        user_id: Uuid,
+        role: &str,
+    ) -> Result<Option<GroupMemberInfo>, sqlx::Error> {
+        let entity = sqlx::query_as::<_, GroupMemberEntity>(
+            r#"
+            UPDATE group_memberships
+            SET role = $3::group_role
+            WHERE group_id = $1 AND user_id = $2
+            RETURNING user_id, role::text as role, joined_at,
+                (SELECT email FROM users WHERE id = $2) as email,
+                (SELECT display_name FROM users WHERE id = $2) as display_name
+            "#,
+        )
+        .bind(group_id)
+        .bind(user_id)
+        .bind(role)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(entity.map(|e| GroupMemberInfo {
+            user_id: e.user_id,
+            email: e.email,
+            display_name: e.display_name,
+            role: e.role,
+            joined_at: e.joined_at,
+        }))
+    }
+
+    /// Move a member from one group to another in a single transaction,
+    /// keeping their role unless `role` is given. Returns `None` if the user
+    /// is not a member of the source group.
+    pub async fn move_group_member(
+        &self,
+        source_group_id: Uuid,
+        target_group_id: Uuid,
+        user_id: Uuid,
+        role: Option<&str>,
+    ) -> Result<Option<GroupMemberInfo>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let previous_role: Option<String> = sqlx::query_scalar(
+            r#"
+            DELETE FROM group_memberships
+            WHERE group_id = $1 AND user_id = $2
+            RETURNING role::text
+            "#,
+        )
+        .bind(source_group_id)
+        .bind(user_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(previous_role) = previous_role else {
+            return Ok(None);
+        };
+
+        let entity = sqlx::query_as::<_, GroupMemberEntity>(
+            r#"
+            INSERT INTO group_memberships (group_id, user_id, role)
+            VALUES ($1, $2, $3::group_role)
+            RETURNING user_id, role::text as role, joined_at,
+                (SELECT email FROM users WHERE id = $2) as email,
+                (SELECT display_name FROM users WHERE id = $2) as display_name
+            "#,
+        )
+        .bind(target_group_id)
+        .bind(user_id)
+        .bind(role.unwrap_or(&previous_role))
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(Some(GroupMemberInfo {
+            user_id: entity.user_id,
+            email: entity.email,
+            display_name: entity.display_name,
+            role: entity.role,
+            joined_at: entity.joined_at,
+        }))
+    }
+
     /// Check if user is the group owner.
     pub async fn is_group_owner(&self, group_id: Uuid, user_id: Uuid) -> Result<bool, sqlx::Error> {
         let is_owner: bool = sqlx::query_scalar(
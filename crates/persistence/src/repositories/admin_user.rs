@@ -215,6 +215,7 @@ impl AdminUserRepository {
                 let role = match e.role.as_str() {
                     "owner" => OrgUserRole::Owner,
                     "admin" => OrgUserRole::Admin,
+                    "observer" => OrgUserRole::Observer,
                     _ => OrgUserRole::Member,
                 };
                 let permissions: Vec<String> =
@@ -273,6 +274,7 @@ impl AdminUserRepository {
             let role = match e.role.as_str() {
                 "owner" => OrgUserRole::Owner,
                 "admin" => OrgUserRole::Admin,
+                "observer" => OrgUserRole::Observer,
                 _ => OrgUserRole::Member,
             };
             let permissions: Vec<String> =
@@ -3,24 +3,70 @@
 //! AP-10: Dashboard & Analytics persistence operations
 
 use chrono::NaiveDate;
+use domain::services::latency_percentile;
 use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::entities::{
-    ApiUsageDailyEntity, ApiUsageSummaryEntity, DeviceActivityDailyEntity,
-    DeviceAnalyticsSummaryEntity, DeviceStatusCountEntity, EndpointUsageEntity, ReportJobEntity,
-    RoleCountEntity, UserActivityDailyEntity, UserAnalyticsSummaryEntity,
+    ApiUsageDailyEntity, ApiUsageSummaryEntity, AppUsageReportMetricsEntity,
+    DeviceActivityDailyEntity, DeviceAnalyticsSummaryEntity, DeviceReportMetricsEntity,
+    DeviceStatusCountEntity, EndpointUsageEntity, GeofenceEventExtractEntity,
+    GeofenceReportMetricsEntity, LocationExtractEntity, ReportJobEntity, RoleCountEntity,
+    TripDistanceDailyEntity, TripMileageEntity, TripReportMetricsEntity, UserActivityDailyEntity,
+    UserAnalyticsSummaryEntity,
 };
 
+/// Counters collected for one API usage rollup row, merged into
+/// `api_usage_daily` by [`AnalyticsRepository::merge_api_usage`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiUsageRollupInput {
+    pub organization_id: Uuid,
+    /// API key the requests were made with, 0 for user sessions.
+    pub api_key_id: i64,
+    pub usage_date: NaiveDate,
+    pub endpoint_path: String,
+    pub method: String,
+    pub total_requests: i64,
+    pub success_count: i64,
+    pub error_count: i64,
+    pub total_response_time_ms: i64,
+    pub max_response_time_ms: i32,
+    /// Request counts per `domain::services::latency_histogram` bucket.
+    pub latency_histogram: Vec<i64>,
+    pub total_request_bytes: i64,
+    pub total_response_bytes: i64,
+}
+
+/// Merged rollup counters returned by the upsert.
+#[derive(Debug, sqlx::FromRow)]
+struct MergedApiUsageRow {
+    id: Uuid,
+    total_requests: i64,
+    total_response_time_ms: i64,
+    max_response_time_ms: i32,
+    latency_histogram: Vec<i64>,
+}
+
 /// Repository for analytics operations.
 #[derive(Clone)]
 pub struct AnalyticsRepository {
     pool: PgPool,
+    timescale: bool,
 }
 
 impl AnalyticsRepository {
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            timescale: false,
+        }
+    }
+
+    /// Read location metrics from the TimescaleDB continuous aggregate
+    /// (see `crate::timescale`) instead of scanning `locations`.
+    pub fn with_timescale(mut self, enabled: bool) -> Self {
+        self.timescale = enabled;
+        self
     }
 
     // ========================================================================
@@ -160,6 +206,72 @@ impl AnalyticsRepository {
         .await
     }
 
+    /// Get completed trip distance per day (in meters) for organization devices.
+    pub async fn get_trip_distance_by_day(
+        &self,
+        org_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<TripDistanceDailyEntity>, sqlx::Error> {
+        sqlx::query_as::<_, TripDistanceDailyEntity>(
+            r#"
+            SELECT
+                (to_timestamp(t.start_timestamp / 1000.0) AT TIME ZONE 'UTC')::date as activity_date,
+                COALESCE(SUM(t.distance_meters), 0)::float8 as distance_meters
+            FROM trips t
+            JOIN devices d ON d.device_id = t.device_id
+            WHERE d.organization_id = $1
+              AND t.state = 'COMPLETED'
+              AND (to_timestamp(t.start_timestamp / 1000.0) AT TIME ZONE 'UTC')::date >= $2
+              AND (to_timestamp(t.start_timestamp / 1000.0) AT TIME ZONE 'UTC')::date <= $3
+            GROUP BY activity_date
+            ORDER BY activity_date ASC
+            "#,
+        )
+        .bind(org_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Get completed trip distance per day, device and purpose for an
+    /// organization's devices, optionally limited to one device.
+    pub async fn get_trip_mileage(
+        &self,
+        org_id: Uuid,
+        device_id: Option<Uuid>,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<TripMileageEntity>, sqlx::Error> {
+        sqlx::query_as::<_, TripMileageEntity>(
+            r#"
+            SELECT
+                (to_timestamp(t.start_timestamp / 1000.0) AT TIME ZONE 'UTC')::date as activity_date,
+                d.device_id,
+                d.display_name as device_name,
+                t.purpose,
+                COUNT(*)::bigint as trip_count,
+                COALESCE(SUM(t.distance_meters), 0)::float8 as distance_meters
+            FROM trips t
+            JOIN devices d ON d.device_id = t.device_id
+            WHERE d.organization_id = $1
+              AND ($2::uuid IS NULL OR d.device_id = $2)
+              AND t.state = 'COMPLETED'
+              AND (to_timestamp(t.start_timestamp / 1000.0) AT TIME ZONE 'UTC')::date >= $3
+              AND (to_timestamp(t.start_timestamp / 1000.0) AT TIME ZONE 'UTC')::date <= $4
+            GROUP BY activity_date, d.device_id, d.display_name, t.purpose
+            ORDER BY activity_date ASC, d.display_name ASC, t.purpose ASC NULLS LAST
+            "#,
+        )
+        .bind(org_id)
+        .bind(device_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+    }
+
     /// Get device status breakdown for organization.
     pub async fn get_device_status_breakdown(
         &self,
@@ -183,11 +295,14 @@ impl AnalyticsRepository {
     // ========================================================================
 
     /// Get API usage summary for a period.
+    ///
+    /// `api_key_id` restricts the summary to one API key (0 for user sessions).
     pub async fn get_api_usage_summary(
         &self,
         org_id: Uuid,
         from: NaiveDate,
         to: NaiveDate,
+        api_key_id: Option<i64>,
     ) -> Result<ApiUsageSummaryEntity, sqlx::Error> {
         sqlx::query_as::<_, ApiUsageSummaryEntity>(
             r#"
@@ -195,18 +310,20 @@ impl AnalyticsRepository {
                 COALESCE(SUM(total_requests), 0)::bigint as total_requests,
                 COALESCE(SUM(success_count), 0)::bigint as success_count,
                 COALESCE(SUM(error_count), 0)::bigint as error_count,
-                COALESCE(AVG(avg_response_time_ms), 0)::float8 as avg_response_time_ms,
+                COALESCE(SUM(avg_response_time_ms * total_requests) / NULLIF(SUM(total_requests), 0), 0)::float8 as avg_response_time_ms,
                 COALESCE(MAX(p95_response_time_ms), 0)::int4 as p95_response_time_ms,
                 COALESCE(SUM(total_request_bytes) + SUM(total_response_bytes), 0)::bigint as total_bytes
             FROM api_usage_daily
             WHERE organization_id = $1
               AND usage_date >= $2
               AND usage_date <= $3
+              AND ($4::bigint IS NULL OR api_key_id = $4)
             "#,
         )
         .bind(org_id)
         .bind(from)
         .bind(to)
+        .bind(api_key_id)
         .fetch_one(&self.pool)
         .await
     }
@@ -217,6 +334,7 @@ impl AnalyticsRepository {
         org_id: Uuid,
         from: NaiveDate,
         to: NaiveDate,
+        api_key_id: Option<i64>,
     ) -> Result<Vec<ApiUsageDailyEntity>, sqlx::Error> {
         sqlx::query_as::<_, ApiUsageDailyEntity>(
             r#"
@@ -228,12 +346,14 @@ impl AnalyticsRepository {
             WHERE organization_id = $1
               AND usage_date >= $2
               AND usage_date <= $3
+              AND ($4::bigint IS NULL OR api_key_id = $4)
             ORDER BY usage_date ASC
             "#,
         )
         .bind(org_id)
         .bind(from)
         .bind(to)
+        .bind(api_key_id)
         .fetch_all(&self.pool)
         .await
     }
@@ -244,6 +364,7 @@ impl AnalyticsRepository {
         org_id: Uuid,
         from: NaiveDate,
         to: NaiveDate,
+        api_key_id: Option<i64>,
         limit: i32,
     ) -> Result<Vec<EndpointUsageEntity>, sqlx::Error> {
         sqlx::query_as::<_, EndpointUsageEntity>(
@@ -253,19 +374,363 @@ impl AnalyticsRepository {
                 method,
                 SUM(total_requests)::bigint as total_requests,
                 SUM(success_count)::bigint as success_count,
-                AVG(avg_response_time_ms)::float8 as avg_response_time_ms
+                COALESCE(SUM(avg_response_time_ms * total_requests) / NULLIF(SUM(total_requests), 0), 0)::float8 as avg_response_time_ms
             FROM api_usage_daily
             WHERE organization_id = $1
               AND usage_date >= $2
               AND usage_date <= $3
+              AND ($4::bigint IS NULL OR api_key_id = $4)
             GROUP BY endpoint_path, method
             ORDER BY total_requests DESC
-            LIMIT $4
+            LIMIT $5
+            "#,
+        )
+        .bind(org_id)
+        .bind(from)
+        .bind(to)
+        .bind(api_key_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Merge collected counters into the daily rollup row of their
+    /// organization, API key, endpoint and day.
+    ///
+    /// Latency histograms are added bucket by bucket and the average and
+    /// percentiles recomputed from the merged row, inside one transaction so
+    /// concurrent flushes from other instances cannot interleave.
+    pub async fn merge_api_usage(&self, input: &ApiUsageRollupInput) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let merged = sqlx::query_as::<_, MergedApiUsageRow>(
+            r#"
+            INSERT INTO api_usage_daily (
+                organization_id, api_key_id, usage_date, endpoint_path, method,
+                total_requests, success_count, error_count, total_response_time_ms,
+                max_response_time_ms, latency_histogram, total_request_bytes, total_response_bytes
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            ON CONFLICT (organization_id, usage_date, endpoint_path, method, api_key_id) DO UPDATE SET
+                total_requests = api_usage_daily.total_requests + EXCLUDED.total_requests,
+                success_count = api_usage_daily.success_count + EXCLUDED.success_count,
+                error_count = api_usage_daily.error_count + EXCLUDED.error_count,
+                total_response_time_ms = api_usage_daily.total_response_time_ms + EXCLUDED.total_response_time_ms,
+                max_response_time_ms = GREATEST(api_usage_daily.max_response_time_ms, EXCLUDED.max_response_time_ms),
+                latency_histogram = ARRAY(
+                    SELECT COALESCE(h.existing, 0) + COALESCE(h.added, 0)
+                    FROM unnest(api_usage_daily.latency_histogram, EXCLUDED.latency_histogram)
+                        WITH ORDINALITY AS h(existing, added, position)
+                    ORDER BY h.position
+                ),
+                total_request_bytes = COALESCE(api_usage_daily.total_request_bytes, 0) + EXCLUDED.total_request_bytes,
+                total_response_bytes = COALESCE(api_usage_daily.total_response_bytes, 0) + EXCLUDED.total_response_bytes,
+                updated_at = NOW()
+            RETURNING id, total_requests, total_response_time_ms, max_response_time_ms, latency_histogram
+            "#,
+        )
+        .bind(input.organization_id)
+        .bind(input.api_key_id)
+        .bind(input.usage_date)
+        .bind(&input.endpoint_path)
+        .bind(&input.method)
+        .bind(input.total_requests)
+        .bind(input.success_count)
+        .bind(input.error_count)
+        .bind(input.total_response_time_ms)
+        .bind(input.max_response_time_ms)
+        .bind(&input.latency_histogram)
+        .bind(input.total_request_bytes)
+        .bind(input.total_response_bytes)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let max_ms = merged.max_response_time_ms as i64;
+        let percentile = |quantile: f64| {
+            latency_percentile(&merged.latency_histogram, max_ms, quantile).map(|ms| ms as i32)
+        };
+        let avg_ms = if merged.total_requests > 0 {
+            Some(merged.total_response_time_ms as f64 / merged.total_requests as f64)
+        } else {
+            None
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE api_usage_daily
+            SET avg_response_time_ms = $2,
+                p50_response_time_ms = $3,
+                p95_response_time_ms = $4,
+                p99_response_time_ms = $5
+            WHERE id = $1
+            "#,
+        )
+        .bind(merged.id)
+        .bind(avg_ms)
+        .bind(percentile(0.50))
+        .bind(percentile(0.95))
+        .bind(percentile(0.99))
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await
+    }
+
+    /// Delete API usage rollups for days before `cutoff`.
+    pub async fn delete_api_usage_before(&self, cutoff: NaiveDate) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM api_usage_daily WHERE usage_date < $1")
+            .bind(cutoff)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    // ========================================================================
+    // Report Builder Sections
+    // ========================================================================
+    //
+    // `time_bucket` is a `date_trunc` field ("day", "week", "month") or None
+    // for a single row over the whole range; `by_device` adds a per-device
+    // breakdown. The date range is inclusive and evaluated in UTC.
+
+    /// Devices section: active devices and reported locations.
+    pub async fn get_device_report_metrics(
+        &self,
+        org_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+        time_bucket: Option<&str>,
+        by_device: bool,
+    ) -> Result<Vec<DeviceReportMetricsEntity>, sqlx::Error> {
+        // The continuous aggregate holds one row per device and UTC day,
+        // which every supported bucket (day, week, month) rolls up from.
+        let sql = if self.timescale {
+            r#"
+            SELECT
+                CASE WHEN $4::text IS NULL THEN NULL
+                     ELSE date_trunc($4::text, ld.day AT TIME ZONE 'UTC')::date END as bucket,
+                CASE WHEN $5 THEN d.device_id END as device_id,
+                CASE WHEN $5 THEN d.display_name END as device_name,
+                COUNT(DISTINCT ld.device_id)::bigint as active_devices,
+                COALESCE(SUM(ld.location_count), 0)::bigint as locations_reported
+            FROM locations_daily ld
+            JOIN devices d ON d.device_id = ld.device_id
+            WHERE d.organization_id = $1
+              AND ld.day >= ($2::date)::timestamp AT TIME ZONE 'UTC'
+              AND ld.day < ($3::date + 1)::timestamp AT TIME ZONE 'UTC'
+            GROUP BY 1, 2, 3
+            ORDER BY 1 NULLS FIRST, 3 NULLS FIRST
+            "#
+        } else {
+            r#"
+            SELECT
+                CASE WHEN $4::text IS NULL THEN NULL
+                     ELSE date_trunc($4::text, l.captured_at AT TIME ZONE 'UTC')::date END as bucket,
+                CASE WHEN $5 THEN d.device_id END as device_id,
+                CASE WHEN $5 THEN d.display_name END as device_name,
+                COUNT(DISTINCT l.device_id)::bigint as active_devices,
+                COUNT(*)::bigint as locations_reported
+            FROM locations l
+            JOIN devices d ON d.device_id = l.device_id
+            WHERE d.organization_id = $1
+              AND l.captured_at >= ($2::date)::timestamp AT TIME ZONE 'UTC'
+              AND l.captured_at < ($3::date + 1)::timestamp AT TIME ZONE 'UTC'
+            GROUP BY 1, 2, 3
+            ORDER BY 1 NULLS FIRST, 3 NULLS FIRST
+            "#
+        };
+
+        sqlx::query_as::<_, DeviceReportMetricsEntity>(sql)
+            .bind(org_id)
+            .bind(from)
+            .bind(to)
+            .bind(time_bucket)
+            .bind(by_device)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    /// Trips section: completed trips with total distance and duration.
+    pub async fn get_trip_report_metrics(
+        &self,
+        org_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+        time_bucket: Option<&str>,
+        by_device: bool,
+    ) -> Result<Vec<TripReportMetricsEntity>, sqlx::Error> {
+        sqlx::query_as::<_, TripReportMetricsEntity>(
+            r#"
+            SELECT
+                CASE WHEN $4::text IS NULL THEN NULL
+                     ELSE date_trunc($4::text, to_timestamp(t.start_timestamp / 1000.0) AT TIME ZONE 'UTC')::date END as bucket,
+                CASE WHEN $5 THEN d.device_id END as device_id,
+                CASE WHEN $5 THEN d.display_name END as device_name,
+                COUNT(*)::bigint as trip_count,
+                COALESCE(SUM(t.distance_meters), 0)::float8 as distance_meters,
+                COALESCE(SUM(t.duration_seconds), 0)::bigint as duration_seconds
+            FROM trips t
+            JOIN devices d ON d.device_id = t.device_id
+            WHERE d.organization_id = $1
+              AND t.state = 'COMPLETED'
+              AND (to_timestamp(t.start_timestamp / 1000.0) AT TIME ZONE 'UTC')::date >= $2
+              AND (to_timestamp(t.start_timestamp / 1000.0) AT TIME ZONE 'UTC')::date <= $3
+            GROUP BY 1, 2, 3
+            ORDER BY 1 NULLS FIRST, 3 NULLS FIRST
+            "#,
+        )
+        .bind(org_id)
+        .bind(from)
+        .bind(to)
+        .bind(time_bucket)
+        .bind(by_device)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Geofence compliance section: enter/exit/dwell event counts.
+    pub async fn get_geofence_report_metrics(
+        &self,
+        org_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+        time_bucket: Option<&str>,
+        by_device: bool,
+    ) -> Result<Vec<GeofenceReportMetricsEntity>, sqlx::Error> {
+        sqlx::query_as::<_, GeofenceReportMetricsEntity>(
+            r#"
+            SELECT
+                CASE WHEN $4::text IS NULL THEN NULL
+                     ELSE date_trunc($4::text, to_timestamp(g.timestamp / 1000.0) AT TIME ZONE 'UTC')::date END as bucket,
+                CASE WHEN $5 THEN d.device_id END as device_id,
+                CASE WHEN $5 THEN d.display_name END as device_name,
+                COUNT(*) FILTER (WHERE g.event_type = 'enter')::bigint as enter_count,
+                COUNT(*) FILTER (WHERE g.event_type = 'exit')::bigint as exit_count,
+                COUNT(*) FILTER (WHERE g.event_type = 'dwell')::bigint as dwell_count
+            FROM geofence_events g
+            JOIN devices d ON d.device_id = g.device_id
+            WHERE d.organization_id = $1
+              AND (to_timestamp(g.timestamp / 1000.0) AT TIME ZONE 'UTC')::date >= $2
+              AND (to_timestamp(g.timestamp / 1000.0) AT TIME ZONE 'UTC')::date <= $3
+            GROUP BY 1, 2, 3
+            ORDER BY 1 NULLS FIRST, 3 NULLS FIRST
+            "#,
+        )
+        .bind(org_id)
+        .bind(from)
+        .bind(to)
+        .bind(time_bucket)
+        .bind(by_device)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// App usage section: foreground time, launches and distinct apps.
+    pub async fn get_app_usage_report_metrics(
+        &self,
+        org_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+        time_bucket: Option<&str>,
+        by_device: bool,
+    ) -> Result<Vec<AppUsageReportMetricsEntity>, sqlx::Error> {
+        sqlx::query_as::<_, AppUsageReportMetricsEntity>(
+            r#"
+            SELECT
+                CASE WHEN $4::text IS NULL THEN NULL
+                     ELSE date_trunc($4::text, a.usage_date::timestamp)::date END as bucket,
+                CASE WHEN $5 THEN a.device_id END as device_id,
+                CASE WHEN $5 THEN d.display_name END as device_name,
+                COALESCE(SUM(a.foreground_time_ms), 0)::bigint as foreground_time_ms,
+                COALESCE(SUM(a.launch_count), 0)::bigint as launch_count,
+                COUNT(DISTINCT a.package_name)::bigint as unique_apps
+            FROM app_usage a
+            LEFT JOIN devices d ON d.device_id = a.device_id
+            WHERE a.organization_id = $1
+              AND a.usage_date >= $2
+              AND a.usage_date <= $3
+            GROUP BY 1, 2, 3
+            ORDER BY 1 NULLS FIRST, 3 NULLS FIRST
+            "#,
+        )
+        .bind(org_id)
+        .bind(from)
+        .bind(to)
+        .bind(time_bucket)
+        .bind(by_device)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    // ========================================================================
+    // Data Extracts
+    // ========================================================================
+
+    /// Page of raw organization locations captured within the date range,
+    /// ordered by id and starting after `after_id`.
+    pub async fn list_location_extract(
+        &self,
+        org_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+        after_id: i64,
+        limit: i64,
+    ) -> Result<Vec<LocationExtractEntity>, sqlx::Error> {
+        sqlx::query_as::<_, LocationExtractEntity>(
+            r#"
+            SELECT l.id, l.device_id, d.display_name as device_name, l.captured_at,
+                   l.latitude, l.longitude, l.accuracy, l.altitude, l.speed, l.bearing,
+                   l.provider, l.battery_level, l.network_type, l.transportation_mode,
+                   l.trip_id
+            FROM locations l
+            JOIN devices d ON d.device_id = l.device_id
+            WHERE d.organization_id = $1
+              AND l.captured_at >= $2::date::timestamptz
+              AND l.captured_at < ($3::date + 1)::timestamptz
+              AND l.id > $4
+            ORDER BY l.id ASC
+            LIMIT $5
+            "#,
+        )
+        .bind(org_id)
+        .bind(from)
+        .bind(to)
+        .bind(after_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Page of raw organization geofence events within the date range,
+    /// ordered by id and starting after `after_id`.
+    pub async fn list_geofence_event_extract(
+        &self,
+        org_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+        after_id: i64,
+        limit: i64,
+    ) -> Result<Vec<GeofenceEventExtractEntity>, sqlx::Error> {
+        sqlx::query_as::<_, GeofenceEventExtractEntity>(
+            r#"
+            SELECT g.id, g.event_id, g.device_id, d.display_name as device_name,
+                   g.geofence_id, f.name as geofence_name, g.event_type, g.timestamp,
+                   g.latitude, g.longitude, g.webhook_delivered, g.created_at
+            FROM geofence_events g
+            JOIN devices d ON d.device_id = g.device_id
+            LEFT JOIN geofences f ON f.geofence_id = g.geofence_id
+            WHERE d.organization_id = $1
+              AND (to_timestamp(g.timestamp / 1000.0) AT TIME ZONE 'UTC')::date >= $2
+              AND (to_timestamp(g.timestamp / 1000.0) AT TIME ZONE 'UTC')::date <= $3
+              AND g.id > $4
+            ORDER BY g.id ASC
+            LIMIT $5
             "#,
         )
         .bind(org_id)
         .bind(from)
         .bind(to)
+        .bind(after_id)
         .bind(limit)
         .fetch_all(&self.pool)
         .await
@@ -0,0 +1,123 @@
+//! Audit export cursor repository.
+//!
+//! Tracks the last exported audit log sequence per organization and export
+//! destination for incremental exports.
+
+use domain::models::AuditExportCursor;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::entities::AuditExportCursorEntity;
+
+/// Repository for incremental export cursors.
+#[derive(Clone)]
+pub struct AuditExportCursorRepository {
+    pool: PgPool,
+}
+
+impl AuditExportCursorRepository {
+    /// Create a new repository instance.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Get the cursor of a destination, creating it at the start of the log
+    /// if it does not exist yet.
+    pub async fn get_or_create(
+        &self,
+        org_id: Uuid,
+        destination: &str,
+    ) -> Result<AuditExportCursor, sqlx::Error> {
+        let entity = sqlx::query_as::<_, AuditExportCursorEntity>(
+            r#"
+            WITH inserted AS (
+                INSERT INTO audit_export_cursors (organization_id, destination)
+                VALUES ($1, $2)
+                ON CONFLICT (organization_id, destination) DO NOTHING
+                RETURNING *
+            )
+            SELECT * FROM inserted
+            UNION ALL
+            SELECT * FROM audit_export_cursors
+            WHERE organization_id = $1 AND destination = $2
+            LIMIT 1
+            "#,
+        )
+        .bind(org_id)
+        .bind(destination)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(entity_to_domain(entity))
+    }
+
+    /// Move a cursor from `expected_seq` to `new_seq`.
+    ///
+    /// Returns false if the cursor no longer points at `expected_seq`, i.e. a
+    /// concurrent export of the same destination advanced it first.
+    pub async fn advance(
+        &self,
+        org_id: Uuid,
+        destination: &str,
+        expected_seq: i64,
+        new_seq: i64,
+        record_count: i64,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE audit_export_cursors
+            SET last_seq = $4, last_record_count = $5, last_exported_at = NOW()
+            WHERE organization_id = $1 AND destination = $2 AND last_seq = $3
+            "#,
+        )
+        .bind(org_id)
+        .bind(destination)
+        .bind(expected_seq)
+        .bind(new_seq)
+        .bind(record_count)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// List the cursors of an organization.
+    pub async fn list_for_org(&self, org_id: Uuid) -> Result<Vec<AuditExportCursor>, sqlx::Error> {
+        let entities = sqlx::query_as::<_, AuditExportCursorEntity>(
+            r#"
+            SELECT * FROM audit_export_cursors
+            WHERE organization_id = $1
+            ORDER BY destination ASC
+            "#,
+        )
+        .bind(org_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entities.into_iter().map(entity_to_domain).collect())
+    }
+
+    /// Delete a cursor so the next export of the destination starts over.
+    pub async fn delete(&self, org_id: Uuid, destination: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "DELETE FROM audit_export_cursors WHERE organization_id = $1 AND destination = $2",
+        )
+        .bind(org_id)
+        .bind(destination)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+/// Convert entity to domain model.
+fn entity_to_domain(entity: AuditExportCursorEntity) -> AuditExportCursor {
+    AuditExportCursor {
+        destination: entity.destination,
+        last_sequence: entity.last_seq,
+        last_record_count: entity.last_record_count,
+        last_exported_at: entity.last_exported_at,
+        created_at: entity.created_at,
+    }
+}
@@ -4,14 +4,16 @@
 
 use domain::models::{
     ActorType, AuditActor, AuditLog, AuditMetadata, AuditResource, CreateAuditLogInput,
-    FieldChange, ListAuditLogsQuery,
+    FieldChange, ListAuditLogsQuery, INCREMENTAL_EXPORT_SETTLE_SECONDS,
 };
 use serde_json::Value as JsonValue;
+use shared::pagination::{CountMode, ListTotal};
 use sqlx::PgPool;
 use std::collections::HashMap;
 use uuid::Uuid;
 
-use crate::entities::AuditLogEntity;
+use crate::db::estimate_table_rows;
+use crate::entities::{AuditLogEntity, SequencedAuditLogEntity};
 
 /// Helper struct for building dynamic WHERE clauses from audit log filters.
 /// Tracks conditions and parameter positions to avoid code duplication.
@@ -200,11 +202,14 @@ impl AuditLogRepository {
     }
 
     /// List audit logs with pagination and filtering.
+    ///
+    /// Returns the page, its total as requested by `query.count`, and whether
+    /// more rows follow the page.
     pub async fn list(
         &self,
         org_id: Uuid,
         query: &ListAuditLogsQuery,
-    ) -> Result<(Vec<AuditLog>, i64), sqlx::Error> {
+    ) -> Result<(Vec<AuditLog>, ListTotal, bool), sqlx::Error> {
         let page = query.page.unwrap_or(1).max(1);
         let per_page = query.per_page.unwrap_or(50).clamp(1, 100);
         let offset = ((page - 1) * per_page) as i64;
@@ -214,14 +219,7 @@ impl AuditLogRepository {
         let where_clause = filter.where_clause();
         let param_count = filter.param_count();
 
-        // Get total count
-        let count_query = format!("SELECT COUNT(*) FROM audit_logs WHERE {}", where_clause);
-
-        let count_builder = sqlx::query_scalar::<_, i64>(&count_query).bind(org_id);
-        let count_builder = bind_query_filters!(count_builder, query);
-        let total: i64 = count_builder.fetch_one(&self.pool).await?;
-
-        // Get audit logs
+        // Get audit logs, fetching one extra row to detect a following page
         let list_query = format!(
             r#"
             SELECT id, organization_id, timestamp, actor_id, actor_type::text, actor_email,
@@ -239,15 +237,44 @@ impl AuditLogRepository {
 
         let list_builder = sqlx::query_as::<_, AuditLogEntity>(&list_query).bind(org_id);
         let list_builder = bind_query_filters!(list_builder, query);
-        let entities = list_builder
-            .bind(per_page as i64)
+        let mut entities = list_builder
+            .bind(per_page as i64 + 1)
             .bind(offset)
             .fetch_all(&self.pool)
             .await?;
 
+        let has_more = entities.len() > per_page as usize;
+        entities.truncate(per_page as usize);
+        let seen = offset + entities.len() as i64 + i64::from(has_more);
+
+        let total = match query.count.unwrap_or_default() {
+            CountMode::None => ListTotal::Omitted,
+            CountMode::Estimated => {
+                ListTotal::estimated(estimate_table_rows(&self.pool, "audit_logs").await?, seen)
+            }
+            CountMode::Exact => ListTotal::Exact(self.count_matching(org_id, query).await?),
+        };
+
         let logs = entities.into_iter().map(entity_to_domain).collect();
 
-        Ok((logs, total))
+        Ok((logs, total, has_more))
+    }
+
+    /// Count audit logs matching the query filters (pagination is ignored).
+    pub async fn count_matching(
+        &self,
+        org_id: Uuid,
+        query: &ListAuditLogsQuery,
+    ) -> Result<i64, sqlx::Error> {
+        let filter = AuditLogFilterBuilder::build(query);
+        let count_query = format!(
+            "SELECT COUNT(*) FROM audit_logs WHERE {}",
+            filter.where_clause()
+        );
+
+        let count_builder = sqlx::query_scalar::<_, i64>(&count_query).bind(org_id);
+        let count_builder = bind_query_filters!(count_builder, query);
+        count_builder.fetch_one(&self.pool).await
     }
 
     /// Count audit logs for an organization.
@@ -295,6 +322,41 @@ impl AuditLogRepository {
 
         Ok(logs)
     }
+
+    /// Get audit logs recorded after the given sequence, oldest first, paired
+    /// with their sequence. Rows younger than the settle window are left for
+    /// the next export.
+    pub async fn list_after_sequence(
+        &self,
+        org_id: Uuid,
+        after_seq: i64,
+        limit: i64,
+    ) -> Result<Vec<(i64, AuditLog)>, sqlx::Error> {
+        let entities = sqlx::query_as::<_, SequencedAuditLogEntity>(
+            r#"
+            SELECT seq, id, organization_id, timestamp, actor_id, actor_type::text, actor_email,
+                   action, resource_type, resource_id, resource_name, changes, metadata,
+                   ip_address::text, user_agent, created_at
+            FROM audit_logs
+            WHERE organization_id = $1
+              AND seq > $2
+              AND created_at <= NOW() - make_interval(secs => $3)
+            ORDER BY seq ASC
+            LIMIT $4
+            "#,
+        )
+        .bind(org_id)
+        .bind(after_seq)
+        .bind(INCREMENTAL_EXPORT_SETTLE_SECONDS as f64)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entities
+            .into_iter()
+            .map(|e| (e.seq, entity_to_domain(e.log)))
+            .collect())
+    }
 }
 
 /// Convert entity to domain model.
@@ -0,0 +1,122 @@
+//! Device commute repository for database operations.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::entities::DeviceCommuteEntity;
+use crate::metrics::QueryTimer;
+
+/// Input data for storing a detected commute.
+#[derive(Debug, Clone)]
+pub struct CommuteInput {
+    pub origin_latitude: f64,
+    pub origin_longitude: f64,
+    pub destination_latitude: f64,
+    pub destination_longitude: f64,
+    pub trip_count: i32,
+    pub typical_departure_minute: i32,
+    pub departure_spread_minutes: i32,
+    pub typical_duration_seconds: i64,
+    /// ISO weekday numbers, 1 = Monday.
+    pub weekdays: Vec<i16>,
+    pub first_departure_at: DateTime<Utc>,
+    pub last_departure_at: DateTime<Utc>,
+}
+
+/// Repository for device commute database operations.
+#[derive(Clone)]
+pub struct CommuteRepository {
+    pool: PgPool,
+}
+
+impl CommuteRepository {
+    /// Creates a new CommuteRepository with the given connection pool.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Replace the commutes of a device with newly detected ones.
+    pub async fn replace_for_device(
+        &self,
+        device_id: Uuid,
+        commutes: &[CommuteInput],
+    ) -> Result<(), sqlx::Error> {
+        let timer = QueryTimer::new("replace_device_commutes");
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM device_commutes WHERE device_id = $1")
+            .bind(device_id)
+            .execute(&mut *tx)
+            .await?;
+
+        for commute in commutes {
+            sqlx::query(
+                r#"
+                INSERT INTO device_commutes (
+                    device_id, origin_latitude, origin_longitude,
+                    destination_latitude, destination_longitude, trip_count,
+                    typical_departure_minute, departure_spread_minutes,
+                    typical_duration_seconds, weekdays, first_departure_at, last_departure_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                "#,
+            )
+            .bind(device_id)
+            .bind(commute.origin_latitude)
+            .bind(commute.origin_longitude)
+            .bind(commute.destination_latitude)
+            .bind(commute.destination_longitude)
+            .bind(commute.trip_count)
+            .bind(commute.typical_departure_minute)
+            .bind(commute.departure_spread_minutes)
+            .bind(commute.typical_duration_seconds)
+            .bind(&commute.weekdays)
+            .bind(commute.first_departure_at)
+            .bind(commute.last_departure_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        timer.record();
+        Ok(())
+    }
+
+    /// List the commutes of a device, most frequent first.
+    pub async fn list_by_device(
+        &self,
+        device_id: Uuid,
+    ) -> Result<Vec<DeviceCommuteEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("list_device_commutes");
+        let result = sqlx::query_as::<_, DeviceCommuteEntity>(
+            r#"
+            SELECT id, device_id, origin_latitude, origin_longitude,
+                   destination_latitude, destination_longitude, trip_count,
+                   typical_departure_minute, departure_spread_minutes,
+                   typical_duration_seconds, weekdays, first_departure_at,
+                   last_departure_at, detected_at
+            FROM device_commutes
+            WHERE device_id = $1
+            ORDER BY trip_count DESC, typical_departure_minute ASC
+            "#,
+        )
+        .bind(device_id)
+        .fetch_all(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Delete commutes not detected again since `cutoff`, i.e. of devices
+    /// without recent trips. Returns the number deleted.
+    pub async fn delete_detected_before(&self, cutoff: DateTime<Utc>) -> Result<u64, sqlx::Error> {
+        let timer = QueryTimer::new("delete_stale_device_commutes");
+        let result = sqlx::query("DELETE FROM device_commutes WHERE detected_at < $1")
+            .bind(cutoff)
+            .execute(&self.pool)
+            .await?;
+        timer.record();
+        Ok(result.rows_affected())
+    }
+}
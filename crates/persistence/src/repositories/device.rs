@@ -7,10 +7,50 @@ use uuid::Uuid;
 use crate::entities::{DeviceEntity, DeviceWithLastLocationEntity, FleetDeviceEntity};
 use crate::metrics::QueryTimer;
 use domain::models::{
-    AssignedUserInfo, FleetDeviceItem, FleetGroupInfo, FleetLastLocation, FleetPolicyInfo,
-    FleetSortField, SortOrder,
+    AssignedUserInfo, DeviceTelemetry, FleetDeviceItem, FleetGroupInfo, FleetLastLocation,
+    FleetPolicyInfo, FleetSortField, SortOrder,
 };
 
+/// Fleet device columns, with the assigned user, policy, last location and
+/// latest telemetry sample.
+const FLEET_DEVICE_SELECT: &str = r#"
+    SELECT
+        d.id,
+        d.device_id,
+        d.display_name,
+        d.platform,
+        d.is_managed,
+        d.enrollment_status::TEXT as enrollment_status,
+        d.enrolled_at,
+        d.created_at,
+        d.last_seen_at,
+        d.group_id,
+        u.id as assigned_user_id,
+        u.email as assigned_user_email,
+        u.display_name as assigned_user_display_name,
+        p.id as policy_id,
+        p.name as policy_name,
+        ll.latitude as last_latitude,
+        ll.longitude as last_longitude,
+        ll.captured_at as last_location_time,
+        t.battery_level as telemetry_battery_level,
+        t.charging_state as telemetry_charging_state,
+        t.network_type as telemetry_network_type,
+        t.signal_strength as telemetry_signal_strength,
+        t.recorded_at as telemetry_recorded_at
+    FROM devices d
+    LEFT JOIN users u ON d.assigned_user_id = u.id
+    LEFT JOIN device_policies p ON d.policy_id = p.id
+    LEFT JOIN device_latest_locations ll ON ll.device_id = d.device_id
+    LEFT JOIN LATERAL (
+        SELECT battery_level, charging_state, network_type, signal_strength, recorded_at
+        FROM device_telemetry
+        WHERE device_id = d.device_id
+        ORDER BY recorded_at DESC, id DESC
+        LIMIT 1
+    ) t ON true
+"#;
+
 /// Repository for device-related database operations.
 #[derive(Clone)]
 pub struct DeviceRepository {
@@ -50,6 +90,25 @@ impl DeviceRepository {
         result
     }
 
+    /// Find a device by its internal ID.
+    pub async fn find_by_id(&self, id: i64) -> Result<Option<DeviceEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("find_device_by_pk");
+        let result = sqlx::query_as::<_, DeviceEntity>(
+            r#"
+            SELECT id, device_id, display_name, group_id, platform, fcm_token,
+                   active, created_at, updated_at, last_seen_at,
+                   owner_user_id, organization_id, is_primary, linked_at
+            FROM devices
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
     /// Find a fleet device by organization ID and device ID.
     /// Returns the device entity if it belongs to the organization.
     pub async fn find_fleet_device(
@@ -78,17 +137,23 @@ impl DeviceRepository {
                 p.name as policy_name,
                 loc.latitude as last_latitude,
                 loc.longitude as last_longitude,
-                loc.timestamp as last_location_time
+                loc.captured_at as last_location_time,
+                t.battery_level as telemetry_battery_level,
+                t.charging_state as telemetry_charging_state,
+                t.network_type as telemetry_network_type,
+                t.signal_strength as telemetry_signal_strength,
+                t.recorded_at as telemetry_recorded_at
             FROM devices d
             LEFT JOIN users u ON d.assigned_user_id = u.id
             LEFT JOIN device_policies p ON d.policy_id = p.id
+            LEFT JOIN device_latest_locations loc ON loc.device_id = d.device_id
             LEFT JOIN LATERAL (
-                SELECT latitude, longitude, timestamp
-                FROM locations
+                SELECT battery_level, charging_state, network_type, signal_strength, recorded_at
+                FROM device_telemetry
                 WHERE device_id = d.device_id
-                ORDER BY timestamp DESC
+                ORDER BY recorded_at DESC, id DESC
                 LIMIT 1
-            ) loc ON true
+            ) t ON true
             WHERE d.organization_id = $1 AND d.device_id = $2
             "#,
         )
@@ -200,7 +265,10 @@ impl DeviceRepository {
         result
     }
 
-    /// Find all active devices in a group with their last location (from view).
+    /// Find all active devices in a group with their last location.
+    ///
+    /// Reads the `device_latest_locations` fast path instead of scanning
+    /// `locations` per device.
     pub async fn find_devices_with_last_location(
         &self,
         group_id: &str,
@@ -208,12 +276,15 @@ impl DeviceRepository {
         let timer = QueryTimer::new("find_devices_with_last_location");
         let result = sqlx::query_as::<_, DeviceWithLastLocationEntity>(
             r#"
-            SELECT id, device_id, display_name, group_id, platform, fcm_token,
-                   active, last_seen_at, created_at, updated_at,
-                   last_latitude, last_longitude, last_location_time, last_accuracy
-            FROM devices_with_last_location
-            WHERE group_id = $1 AND active = true
-            ORDER BY display_name ASC
+            SELECT d.id, d.device_id, d.display_name, d.group_id, d.platform, d.fcm_token,
+                   d.active, d.last_seen_at, d.created_at, d.updated_at,
+                   l.latitude as last_latitude, l.longitude as last_longitude,
+                   l.captured_at as last_location_time, l.accuracy as last_accuracy,
+                   d.owner_user_id
+            FROM devices d
+            LEFT JOIN device_latest_locations l ON l.device_id = d.device_id
+            WHERE d.group_id = $1 AND d.active = true
+            ORDER BY d.display_name ASC
             "#,
         )
         .bind(group_id)
@@ -432,13 +503,7 @@ impl DeviceRepository {
                 loc.longitude as last_longitude,
                 loc.captured_at as last_location_time
             FROM devices d
-            LEFT JOIN LATERAL (
-                SELECT latitude, longitude, captured_at
-                FROM locations
-                WHERE device_id = d.device_id
-                ORDER BY captured_at DESC
-                LIMIT 1
-            ) loc ON true
+            LEFT JOIN device_latest_locations loc ON loc.device_id = d.device_id
             WHERE d.owner_user_id = ANY($1) AND d.active = true
             ORDER BY d.owner_user_id, d.is_primary DESC, d.linked_at DESC NULLS LAST
             "#,
@@ -812,42 +877,8 @@ impl DeviceRepository {
         limit: u32,
         offset: u32,
     ) -> Result<Vec<FleetDeviceItem>, sqlx::Error> {
-        // Build the base SELECT with LEFT JOINs
-        let mut query = String::from(
-            r#"
-            SELECT
-                d.id,
-                d.device_id,
-                d.display_name,
-                d.platform,
-                d.is_managed,
-                d.enrollment_status::TEXT as enrollment_status,
-                d.enrolled_at,
-                d.created_at,
-                d.last_seen_at,
-                d.group_id,
-                u.id as assigned_user_id,
-                u.email as assigned_user_email,
-                u.display_name as assigned_user_display_name,
-                p.id as policy_id,
-                p.name as policy_name,
-                ll.latitude as last_latitude,
-                ll.longitude as last_longitude,
-                ll.timestamp as last_location_time
-            FROM devices d
-            LEFT JOIN users u ON d.assigned_user_id = u.id
-            LEFT JOIN device_policies p ON d.policy_id = p.id
-            LEFT JOIN LATERAL (
-                SELECT latitude, longitude, timestamp
-                FROM locations
-                WHERE device_id = d.id
-                ORDER BY timestamp DESC
-                LIMIT 1
-            ) ll ON true
-            WHERE d.organization_id = $1 AND d.is_managed = true
-            "#,
-        );
-
+        let mut query =
+            format!("{FLEET_DEVICE_SELECT} WHERE d.organization_id = $1 AND d.is_managed = true");
         let mut param_idx = 2;
 
         // Add filters
@@ -917,63 +948,25 @@ impl DeviceRepository {
 
         let entities = q.fetch_all(&self.pool).await?;
 
-        // Map entities to domain models
-        let items = entities
-            .into_iter()
-            .map(|e| {
-                let assigned_user = e.assigned_user_id.map(|id| AssignedUserInfo {
-                    id,
-                    email: e.assigned_user_email.unwrap_or_default(),
-                    display_name: e.assigned_user_display_name,
-                });
-
-                let group = if !e.group_id.is_empty() {
-                    Some(FleetGroupInfo {
-                        id: e.group_id.clone(),
-                        name: None, // Group name not available without another join
-                    })
-                } else {
-                    None
-                };
-
-                let policy = e.policy_id.map(|id| FleetPolicyInfo {
-                    id,
-                    name: e.policy_name.unwrap_or_default(),
-                });
-
-                let last_location = if let (Some(lat), Some(lon), Some(ts)) =
-                    (e.last_latitude, e.last_longitude, e.last_location_time)
-                {
-                    Some(FleetLastLocation {
-                        latitude: lat,
-                        longitude: lon,
-                        timestamp: ts,
-                    })
-                } else {
-                    None
-                };
-
-                let enrollment_status = e.enrollment_status.as_deref().and_then(|s| s.parse().ok());
-
-                FleetDeviceItem {
-                    id: e.id,
-                    device_uuid: e.device_id,
-                    display_name: e.display_name,
-                    platform: e.platform,
-                    enrollment_status,
-                    is_managed: e.is_managed,
-                    assigned_user,
-                    group,
-                    policy,
-                    last_seen_at: e.last_seen_at,
-                    last_location,
-                    enrolled_at: e.enrolled_at,
-                    created_at: e.created_at,
-                }
-            })
-            .collect();
-
-        Ok(items)
+        Ok(entities.into_iter().map(fleet_device_item).collect())
+    }
+
+    /// Get one managed device of an organization's fleet, by internal ID.
+    pub async fn find_fleet_device_item(
+        &self,
+        organization_id: Uuid,
+        device_id: i64,
+    ) -> Result<Option<FleetDeviceItem>, sqlx::Error> {
+        let timer = QueryTimer::new("find_fleet_device_item");
+        let result = sqlx::query_as::<_, FleetDeviceEntity>(&format!(
+            "{FLEET_DEVICE_SELECT} WHERE d.id = $1 AND d.organization_id = $2 AND d.is_managed = true"
+        ))
+        .bind(device_id)
+        .bind(organization_id)
+        .fetch_optional(&self.pool)
+        .await;
+        timer.record();
+        Ok(result?.map(fleet_device_item))
     }
 
     /// Get admin statistics about devices and locations.
@@ -1226,6 +1219,44 @@ impl DeviceRepository {
         Ok(result.is_some())
     }
 
+    /// List the IDs of managed devices matching fleet filters, up to `limit`.
+    pub async fn list_fleet_device_ids(
+        &self,
+        organization_id: Uuid,
+        status_filter: Option<&str>,
+        group_id_filter: Option<&str>,
+        policy_id_filter: Option<Uuid>,
+        assigned_filter: Option<bool>,
+        limit: i64,
+    ) -> Result<Vec<i64>, sqlx::Error> {
+        let timer = QueryTimer::new("list_fleet_device_ids");
+
+        let result = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT id
+            FROM devices
+            WHERE organization_id = $1 AND is_managed = true
+              AND ($2::TEXT IS NULL OR enrollment_status = $2::enrollment_status)
+              AND ($3::TEXT IS NULL OR group_id = $3)
+              AND ($4::UUID IS NULL OR policy_id = $4)
+              AND ($5::BOOLEAN IS NULL OR (assigned_user_id IS NOT NULL) = $5)
+            ORDER BY id
+            LIMIT $6
+            "#,
+        )
+        .bind(organization_id)
+        .bind(status_filter)
+        .bind(group_id_filter)
+        .bind(policy_id_filter)
+        .bind(assigned_filter)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await;
+
+        timer.record();
+        result
+    }
+
     /// List all managed devices in an organization (simple query for admin operations).
     ///
     /// Returns basic device info for all managed devices in the organization.
@@ -1308,6 +1339,71 @@ pub struct RegistrationGroupDevice {
     pub owner_user_id: Option<Uuid>,
 }
 
+/// Map a fleet device row to the fleet listing item.
+fn fleet_device_item(e: FleetDeviceEntity) -> FleetDeviceItem {
+    let assigned_user = e.assigned_user_id.map(|id| AssignedUserInfo {
+        id,
+        email: e.assigned_user_email.unwrap_or_default(),
+        display_name: e.assigned_user_display_name,
+    });
+
+    let group = if !e.group_id.is_empty() {
+        Some(FleetGroupInfo {
+            id: e.group_id.clone(),
+            name: None, // Group name not available without another join
+        })
+    } else {
+        None
+    };
+
+    let policy = e.policy_id.map(|id| FleetPolicyInfo {
+        id,
+        name: e.policy_name.unwrap_or_default(),
+    });
+
+    let last_location = if let (Some(lat), Some(lon), Some(ts)) =
+        (e.last_latitude, e.last_longitude, e.last_location_time)
+    {
+        Some(FleetLastLocation {
+            latitude: lat,
+            longitude: lon,
+            timestamp: ts,
+        })
+    } else {
+        None
+    };
+
+    let telemetry = e.telemetry_recorded_at.map(|recorded_at| DeviceTelemetry {
+        battery_level: e.telemetry_battery_level.map(i32::from),
+        charging_state: e
+            .telemetry_charging_state
+            .as_deref()
+            .and_then(|s| s.parse().ok()),
+        network_type: e.telemetry_network_type,
+        signal_strength: e.telemetry_signal_strength.map(i32::from),
+        recorded_at,
+    });
+
+    let enrollment_status = e.enrollment_status.as_deref().and_then(|s| s.parse().ok());
+
+    FleetDeviceItem {
+        id: e.id,
+        device_uuid: e.device_id,
+        display_name: e.display_name,
+        platform: e.platform,
+        enrollment_status,
+        is_managed: e.is_managed,
+        assigned_user,
+        group,
+        policy,
+        last_seen_at: e.last_seen_at,
+        last_location,
+        telemetry,
+        enrolled_at: e.enrolled_at,
+        created_at: e.created_at,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
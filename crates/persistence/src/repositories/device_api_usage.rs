@@ -0,0 +1,80 @@
+//! Device API usage repository.
+//!
+//! Maintains per-device request counters by endpoint class.
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::entities::DeviceApiUsageEntity;
+use crate::metrics::QueryTimer;
+
+/// Repository for device API usage counters.
+#[derive(Clone)]
+pub struct DeviceApiUsageRepository {
+    pool: PgPool,
+}
+
+impl DeviceApiUsageRepository {
+    /// Create a new repository instance.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Count one request from a device for an endpoint class.
+    ///
+    /// The hourly counter restarts when the clock hour changes.
+    pub async fn record_request(
+        &self,
+        device_id: Uuid,
+        endpoint_class: &str,
+    ) -> Result<(), sqlx::Error> {
+        let timer = QueryTimer::new("record_device_api_request");
+        let result = sqlx::query(
+            r#"
+            INSERT INTO device_api_usage
+                (device_id, endpoint_class, request_count, hour_bucket, hour_count, last_request_at)
+            VALUES ($1, $2, 1, date_trunc('hour', NOW()), 1, NOW())
+            ON CONFLICT (device_id, endpoint_class) DO UPDATE SET
+                request_count = device_api_usage.request_count + 1,
+                hour_count = CASE
+                    WHEN device_api_usage.hour_bucket = EXCLUDED.hour_bucket
+                        THEN device_api_usage.hour_count + 1
+                    ELSE 1
+                END,
+                hour_bucket = EXCLUDED.hour_bucket,
+                last_request_at = EXCLUDED.last_request_at
+            "#,
+        )
+        .bind(device_id)
+        .bind(endpoint_class)
+        .execute(&self.pool)
+        .await;
+        timer.record();
+        result.map(|_| ())
+    }
+
+    /// Get usage counters of a device by its internal ID.
+    pub async fn find_by_device(
+        &self,
+        device_id: i64,
+    ) -> Result<Vec<DeviceApiUsageEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("find_device_api_usage");
+        let result = sqlx::query_as::<_, DeviceApiUsageEntity>(
+            r#"
+            SELECT u.device_id, u.endpoint_class, u.request_count,
+                   CASE WHEN u.hour_bucket = date_trunc('hour', NOW()) THEN u.hour_count ELSE 0 END
+                       AS current_hour_count,
+                   u.last_request_at
+            FROM device_api_usage u
+            JOIN devices d ON d.device_id = u.device_id
+            WHERE d.id = $1
+            ORDER BY u.endpoint_class
+            "#,
+        )
+        .bind(device_id)
+        .fetch_all(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+}
@@ -84,7 +84,7 @@ impl DeviceCommandRepository {
             WHERE device_id = $1
               AND status = 'pending'
               AND expires_at > NOW()
-            ORDER BY issued_at ASC
+            ORDER BY issued_at ASC, step_index ASC NULLS FIRST
             "#,
         )
         .bind(device_id)
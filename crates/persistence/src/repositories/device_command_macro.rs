@@ -0,0 +1,267 @@
+//! Device command macro repository.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::entities::{
+    DeviceCommandMacroEntity, DeviceCommandMacroRunEntity, MacroRunCommandEntity,
+};
+
+/// A macro step ready to be queued, with its payload rendered.
+#[derive(Debug, Clone)]
+pub struct MacroRunStep {
+    pub command_type: String,
+    pub payload: Option<serde_json::Value>,
+}
+
+/// Repository for device command macro operations.
+#[derive(Debug, Clone)]
+pub struct DeviceCommandMacroRepository {
+    pool: PgPool,
+}
+
+impl DeviceCommandMacroRepository {
+    /// Create a new device command macro repository.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Create a macro.
+    pub async fn create(
+        &self,
+        organization_id: Uuid,
+        name: &str,
+        description: Option<&str>,
+        steps: &serde_json::Value,
+        created_by: Uuid,
+    ) -> Result<DeviceCommandMacroEntity, sqlx::Error> {
+        sqlx::query_as::<_, DeviceCommandMacroEntity>(
+            r#"
+            INSERT INTO device_command_macros (organization_id, name, description, steps, created_by)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, organization_id, name, description, steps, created_by,
+                created_at, updated_at
+            "#,
+        )
+        .bind(organization_id)
+        .bind(name)
+        .bind(description)
+        .bind(steps)
+        .bind(created_by)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// List the macros of an organization, by name.
+    pub async fn list(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Vec<DeviceCommandMacroEntity>, sqlx::Error> {
+        sqlx::query_as::<_, DeviceCommandMacroEntity>(
+            r#"
+            SELECT id, organization_id, name, description, steps, created_by,
+                created_at, updated_at
+            FROM device_command_macros
+            WHERE organization_id = $1
+            ORDER BY name
+            "#,
+        )
+        .bind(organization_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Get a macro of an organization.
+    pub async fn find_by_id(
+        &self,
+        organization_id: Uuid,
+        id: Uuid,
+    ) -> Result<Option<DeviceCommandMacroEntity>, sqlx::Error> {
+        sqlx::query_as::<_, DeviceCommandMacroEntity>(
+            r#"
+            SELECT id, organization_id, name, description, steps, created_by,
+                created_at, updated_at
+            FROM device_command_macros
+            WHERE organization_id = $1 AND id = $2
+            "#,
+        )
+        .bind(organization_id)
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Find a macro of an organization by name.
+    pub async fn find_by_name(
+        &self,
+        organization_id: Uuid,
+        name: &str,
+    ) -> Result<Option<DeviceCommandMacroEntity>, sqlx::Error> {
+        sqlx::query_as::<_, DeviceCommandMacroEntity>(
+            r#"
+            SELECT id, organization_id, name, description, steps, created_by,
+                created_at, updated_at
+            FROM device_command_macros
+            WHERE organization_id = $1 AND name = $2
+            "#,
+        )
+        .bind(organization_id)
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Replace the name, description and steps of a macro.
+    pub async fn update(
+        &self,
+        organization_id: Uuid,
+        id: Uuid,
+        name: &str,
+        description: Option<&str>,
+        steps: &serde_json::Value,
+    ) -> Result<Option<DeviceCommandMacroEntity>, sqlx::Error> {
+        sqlx::query_as::<_, DeviceCommandMacroEntity>(
+            r#"
+            UPDATE device_command_macros
+            SET name = $3, description = $4, steps = $5, updated_at = NOW()
+            WHERE organization_id = $1 AND id = $2
+            RETURNING id, organization_id, name, description, steps, created_by,
+                created_at, updated_at
+            "#,
+        )
+        .bind(organization_id)
+        .bind(id)
+        .bind(name)
+        .bind(description)
+        .bind(steps)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Delete a macro. Its past runs are kept.
+    pub async fn delete(&self, organization_id: Uuid, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM device_command_macros
+            WHERE organization_id = $1 AND id = $2
+            "#,
+        )
+        .bind(organization_id)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Record a run and queue every step for every device in one
+    /// transaction.
+    ///
+    /// Commands of a device share the issue time; `step_index` keeps their
+    /// order.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_run(
+        &self,
+        organization_id: Uuid,
+        macro_id: Uuid,
+        macro_name: &str,
+        parameters: &serde_json::Value,
+        device_ids: &[i64],
+        steps: &[MacroRunStep],
+        issued_by: Uuid,
+        expires_at: DateTime<Utc>,
+    ) -> Result<DeviceCommandMacroRunEntity, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let run = sqlx::query_as::<_, DeviceCommandMacroRunEntity>(
+            r#"
+            INSERT INTO device_command_macro_runs (
+                organization_id, macro_id, macro_name, parameters, device_count, issued_by
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id, organization_id, macro_id, macro_name, parameters, device_count,
+                issued_by, created_at
+            "#,
+        )
+        .bind(organization_id)
+        .bind(macro_id)
+        .bind(macro_name)
+        .bind(parameters)
+        .bind(device_ids.len() as i32)
+        .bind(issued_by)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let command_types: Vec<String> = steps.iter().map(|s| s.command_type.clone()).collect();
+        let payloads: Vec<Option<serde_json::Value>> =
+            steps.iter().map(|s| s.payload.clone()).collect();
+        let step_indexes: Vec<i32> = (0..steps.len() as i32).collect();
+
+        sqlx::query(
+            r#"
+            INSERT INTO device_commands (
+                device_id, organization_id, command_type, status, payload,
+                issued_by, expires_at, macro_run_id, step_index
+            )
+            SELECT d.device_id, $1, s.command_type::device_command_type,
+                'pending'::device_command_status, s.payload, $2, $3, $4, s.step_index
+            FROM UNNEST($5::BIGINT[]) AS d(device_id)
+            CROSS JOIN UNNEST($6::TEXT[], $7::JSONB[], $8::INTEGER[])
+                AS s(command_type, payload, step_index)
+            "#,
+        )
+        .bind(organization_id)
+        .bind(issued_by)
+        .bind(expires_at)
+        .bind(run.id)
+        .bind(device_ids)
+        .bind(&command_types)
+        .bind(&payloads)
+        .bind(&step_indexes)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(run)
+    }
+
+    /// Get a run of an organization.
+    pub async fn find_run(
+        &self,
+        organization_id: Uuid,
+        run_id: Uuid,
+    ) -> Result<Option<DeviceCommandMacroRunEntity>, sqlx::Error> {
+        sqlx::query_as::<_, DeviceCommandMacroRunEntity>(
+            r#"
+            SELECT id, organization_id, macro_id, macro_name, parameters, device_count,
+                issued_by, created_at
+            FROM device_command_macro_runs
+            WHERE organization_id = $1 AND id = $2
+            "#,
+        )
+        .bind(organization_id)
+        .bind(run_id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// List the commands queued by a run, by device and step.
+    pub async fn list_run_commands(
+        &self,
+        run_id: Uuid,
+    ) -> Result<Vec<MacroRunCommandEntity>, sqlx::Error> {
+        sqlx::query_as::<_, MacroRunCommandEntity>(
+            r#"
+            SELECT id, device_id, step_index, command_type::TEXT, status::TEXT,
+                failure_reason, acknowledged_at, completed_at, failed_at
+            FROM device_commands
+            WHERE macro_run_id = $1
+            ORDER BY device_id, step_index
+            "#,
+        )
+        .bind(run_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+}
@@ -0,0 +1,133 @@
+//! Device configuration history repository.
+//!
+//! Reads the setting and policy assignment history recorded by triggers
+//! (migration 095) as of a point in time.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::entities::{DeviceSettingVersionEntity, PolicyAssignmentCountEntity};
+use crate::metrics::QueryTimer;
+
+/// Repository for device configuration history queries.
+#[derive(Clone)]
+pub struct DeviceConfigHistoryRepository {
+    pool: PgPool,
+}
+
+impl DeviceConfigHistoryRepository {
+    /// Creates a new DeviceConfigHistoryRepository with the given connection pool.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Settings a device had at `as_of`.
+    pub async fn settings_as_of(
+        &self,
+        device_id: Uuid,
+        as_of: DateTime<Utc>,
+    ) -> Result<Vec<DeviceSettingVersionEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("device_settings_as_of");
+        let result = sqlx::query_as::<_, DeviceSettingVersionEntity>(
+            r#"
+            SELECT setting_key, value, is_locked, valid_from
+            FROM device_setting_history
+            WHERE device_id = $1
+              AND valid_from <= $2
+              AND (valid_to IS NULL OR valid_to > $2)
+            "#,
+        )
+        .bind(device_id)
+        .bind(as_of)
+        .fetch_all(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Policy a device was assigned at `as_of`, if any.
+    pub async fn policy_id_as_of(
+        &self,
+        device_id: Uuid,
+        as_of: DateTime<Utc>,
+    ) -> Result<Option<Uuid>, sqlx::Error> {
+        let timer = QueryTimer::new("device_policy_as_of");
+        let result = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT policy_id
+            FROM device_policy_assignments
+            WHERE device_id = $1
+              AND valid_from <= $2
+              AND (valid_to IS NULL OR valid_to > $2)
+            "#,
+        )
+        .bind(device_id)
+        .bind(as_of)
+        .fetch_optional(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Number of the organization's devices per assigned policy at `as_of`,
+    /// largest first.
+    pub async fn policy_assignment_counts_as_of(
+        &self,
+        org_id: Uuid,
+        as_of: DateTime<Utc>,
+    ) -> Result<Vec<PolicyAssignmentCountEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("policy_assignment_counts_as_of");
+        let result = sqlx::query_as::<_, PolicyAssignmentCountEntity>(
+            r#"
+            SELECT a.policy_id, p.name as policy_name, COUNT(*)::bigint as device_count
+            FROM device_policy_assignments a
+            JOIN devices d ON d.device_id = a.device_id
+            LEFT JOIN device_policies p ON p.id = a.policy_id
+            WHERE d.organization_id = $1
+              AND a.valid_from <= $2
+              AND (a.valid_to IS NULL OR a.valid_to > $2)
+            GROUP BY a.policy_id, p.name
+            ORDER BY device_count DESC, p.name ASC
+            "#,
+        )
+        .bind(org_id)
+        .bind(as_of)
+        .fetch_all(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Number of the organization's devices that existed at `as_of`, and how
+    /// many of them had at least one locked setting.
+    pub async fn count_devices_as_of(
+        &self,
+        org_id: Uuid,
+        as_of: DateTime<Utc>,
+    ) -> Result<(i64, i64), sqlx::Error> {
+        let timer = QueryTimer::new("count_devices_as_of");
+        let result = sqlx::query_as::<_, (i64, i64)>(
+            r#"
+            SELECT
+                COUNT(*)::bigint,
+                COUNT(*) FILTER (WHERE EXISTS (
+                    SELECT 1 FROM device_setting_history h
+                    WHERE h.device_id = d.device_id
+                      AND h.is_locked
+                      AND h.valid_from <= $2
+                      AND (h.valid_to IS NULL OR h.valid_to > $2)
+                ))::bigint
+            FROM devices d
+            WHERE d.organization_id = $1
+              AND d.created_at <= $2
+            "#,
+        )
+        .bind(org_id)
+        .bind(as_of)
+        .fetch_one(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+}
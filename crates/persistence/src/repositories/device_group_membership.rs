@@ -7,7 +7,7 @@ use uuid::Uuid;
 
 use crate::entities::device_group_membership::{
     DeviceGroupInfoEntity, DeviceGroupMembershipEntity, DeviceInGroupEntity,
-    DeviceInGroupWithLocationEntity,
+    DeviceInGroupWithLocationEntity, NearbyDeviceInGroupEntity,
 };
 use crate::metrics::QueryTimer;
 
@@ -73,6 +73,32 @@ impl DeviceGroupMembershipRepository {
         Ok(result.rows_affected())
     }
 
+    /// Set or clear a device's nickname within a group.
+    ///
+    /// Returns false if the device is not in the group.
+    pub async fn set_device_nickname(
+        &self,
+        device_id: Uuid,
+        group_id: Uuid,
+        nickname: Option<&str>,
+    ) -> Result<bool, sqlx::Error> {
+        let timer = QueryTimer::new("set_device_nickname");
+        let result = sqlx::query(
+            r#"
+            UPDATE device_group_memberships
+            SET nickname = $3
+            WHERE device_id = $1 AND group_id = $2
+            "#,
+        )
+        .bind(device_id)
+        .bind(group_id)
+        .bind(nickname)
+        .execute(&self.pool)
+        .await?;
+        timer.record();
+        Ok(result.rows_affected() > 0)
+    }
+
     /// Check if a device is in a group.
     pub async fn is_device_in_group(
         &self,
@@ -136,7 +162,8 @@ impl DeviceGroupMembershipRepository {
                 u.display_name as owner_display_name,
                 dgm.id as membership_id,
                 dgm.added_by,
-                dgm.added_at
+                dgm.added_at,
+                dgm.nickname
             FROM device_group_memberships dgm
             JOIN devices d ON d.device_id = dgm.device_id AND d.active = true
             LEFT JOIN users u ON d.owner_user_id = u.id
@@ -173,6 +200,7 @@ impl DeviceGroupMembershipRepository {
                 dgm.id as membership_id,
                 dgm.added_by,
                 dgm.added_at,
+                dgm.nickname,
                 ll.latitude,
                 ll.longitude,
                 ll.accuracy,
@@ -180,13 +208,7 @@ impl DeviceGroupMembershipRepository {
             FROM device_group_memberships dgm
             JOIN devices d ON d.device_id = dgm.device_id AND d.active = true
             LEFT JOIN users u ON d.owner_user_id = u.id
-            LEFT JOIN LATERAL (
-                SELECT latitude, longitude, accuracy, captured_at
-                FROM locations
-                WHERE device_id = d.device_id
-                ORDER BY captured_at DESC
-                LIMIT 1
-            ) ll ON true
+            LEFT JOIN device_latest_locations ll ON ll.device_id = d.device_id
             WHERE dgm.group_id = $1
             ORDER BY dgm.added_at DESC
             LIMIT $2 OFFSET $3
@@ -201,6 +223,60 @@ impl DeviceGroupMembershipRepository {
         result
     }
 
+    /// Find devices in a group whose last location is within `radius_meters`
+    /// of the given point, ordered by distance (nearest first).
+    pub async fn find_nearby_devices_in_group(
+        &self,
+        group_id: Uuid,
+        latitude: f64,
+        longitude: f64,
+        radius_meters: f64,
+        limit: i64,
+    ) -> Result<Vec<NearbyDeviceInGroupEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("find_nearby_devices_in_group");
+        let result = sqlx::query_as::<_, NearbyDeviceInGroupEntity>(
+            r#"
+            WITH origin AS (
+                SELECT ST_SetSRID(ST_MakePoint($3, $2), 4326)::geography AS point
+            )
+            SELECT
+                d.device_id,
+                d.display_name,
+                d.last_seen_at,
+                d.owner_user_id,
+                u.display_name as owner_display_name,
+                ll.latitude,
+                ll.longitude,
+                ll.accuracy,
+                ll.captured_at as location_timestamp,
+                ST_Distance(ll.point, origin.point) as distance_meters
+            FROM device_group_memberships dgm
+            JOIN devices d ON d.device_id = dgm.device_id AND d.active = true
+            LEFT JOIN users u ON d.owner_user_id = u.id
+            JOIN LATERAL (
+                SELECT latitude, longitude, accuracy, captured_at,
+                       ST_SetSRID(ST_MakePoint(longitude, latitude), 4326)::geography AS point
+                FROM device_latest_locations
+                WHERE device_id = d.device_id
+            ) ll ON true
+            CROSS JOIN origin
+            WHERE dgm.group_id = $1
+              AND ST_DWithin(ll.point, origin.point, $4)
+            ORDER BY distance_meters ASC
+            LIMIT $5
+            "#,
+        )
+        .bind(group_id)
+        .bind(latitude)
+        .bind(longitude)
+        .bind(radius_meters)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
     /// Count devices in a group.
     pub async fn count_devices_in_group(&self, group_id: Uuid) -> Result<i64, sqlx::Error> {
         let timer = QueryTimer::new("count_devices_in_group");
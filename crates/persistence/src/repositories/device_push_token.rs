@@ -0,0 +1,154 @@
+//! Device push token repository for database operations.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::entities::DevicePushTokenEntity;
+use crate::metrics::QueryTimer;
+
+/// Repository for device push token database operations.
+#[derive(Clone)]
+pub struct DevicePushTokenRepository {
+    pool: PgPool,
+}
+
+impl DevicePushTokenRepository {
+    /// Creates a new DevicePushTokenRepository with the given connection pool.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Register a token for a device, or refresh it if already registered.
+    ///
+    /// A token registered by another device moves to this one. When
+    /// `replaces_token` is given, that token of the device is removed in the
+    /// same transaction. Only the `max_tokens` most recently registered
+    /// tokens of the device are kept.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn register(
+        &self,
+        device_id: Uuid,
+        token: &str,
+        platform: &str,
+        app_version: Option<&str>,
+        expires_at: Option<DateTime<Utc>>,
+        replaces_token: Option<&str>,
+        max_tokens: i64,
+    ) -> Result<DevicePushTokenEntity, sqlx::Error> {
+        let timer = QueryTimer::new("register_device_push_token");
+        let mut tx = self.pool.begin().await?;
+
+        if let Some(previous) = replaces_token.filter(|t| *t != token) {
+            sqlx::query("DELETE FROM device_push_tokens WHERE device_id = $1 AND token = $2")
+                .bind(device_id)
+                .bind(previous)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        let entity = sqlx::query_as::<_, DevicePushTokenEntity>(
+            r#"
+            INSERT INTO device_push_tokens (device_id, token, platform, app_version, expires_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (token) DO UPDATE SET
+                device_id = EXCLUDED.device_id,
+                platform = EXCLUDED.platform,
+                app_version = EXCLUDED.app_version,
+                expires_at = EXCLUDED.expires_at,
+                updated_at = NOW()
+            RETURNING id, device_id, token, platform, app_version, expires_at, created_at, updated_at
+            "#,
+        )
+        .bind(device_id)
+        .bind(token)
+        .bind(platform)
+        .bind(app_version)
+        .bind(expires_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM device_push_tokens
+            WHERE device_id = $1 AND id NOT IN (
+                SELECT id FROM device_push_tokens
+                WHERE device_id = $1
+                ORDER BY updated_at DESC
+                LIMIT $2
+            )
+            "#,
+        )
+        .bind(device_id)
+        .bind(max_tokens)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        timer.record();
+        Ok(entity)
+    }
+
+    /// List all tokens of a device, most recently registered first.
+    pub async fn list_for_device(
+        &self,
+        device_id: Uuid,
+    ) -> Result<Vec<DevicePushTokenEntity>, sqlx::Error> {
+        sqlx::query_as::<_, DevicePushTokenEntity>(
+            r#"
+            SELECT id, device_id, token, platform, app_version, expires_at, created_at, updated_at
+            FROM device_push_tokens
+            WHERE device_id = $1
+            ORDER BY updated_at DESC
+            "#,
+        )
+        .bind(device_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Tokens of a device that have not expired, for sending notifications.
+    pub async fn list_active_tokens(&self, device_id: Uuid) -> Result<Vec<String>, sqlx::Error> {
+        let timer = QueryTimer::new("list_active_push_tokens");
+        let result = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT token
+            FROM device_push_tokens
+            WHERE device_id = $1 AND (expires_at IS NULL OR expires_at > NOW())
+            ORDER BY updated_at DESC
+            "#,
+        )
+        .bind(device_id)
+        .fetch_all(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Delete a token of a device by ID.
+    pub async fn delete(&self, device_id: Uuid, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM device_push_tokens WHERE device_id = $1 AND id = $2")
+            .bind(device_id)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Delete a token rejected by the push provider.
+    pub async fn delete_token(&self, token: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM device_push_tokens WHERE token = $1")
+            .bind(token)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Delete all expired tokens. Returns the number deleted.
+    pub async fn delete_expired(&self) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM device_push_tokens WHERE expires_at <= NOW()")
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
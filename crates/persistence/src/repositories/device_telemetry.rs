@@ -0,0 +1,131 @@
+//! Device telemetry repository for database operations.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::entities::DeviceTelemetryEntity;
+use crate::metrics::QueryTimer;
+
+/// Input data for inserting a telemetry sample.
+#[derive(Debug, Clone)]
+pub struct TelemetryInput {
+    pub battery_level: Option<i32>,
+    pub charging_state: Option<String>,
+    pub network_type: Option<String>,
+    pub signal_strength: Option<i32>,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl From<domain::models::DeviceTelemetry> for TelemetryInput {
+    fn from(telemetry: domain::models::DeviceTelemetry) -> Self {
+        Self {
+            battery_level: telemetry.battery_level,
+            charging_state: telemetry.charging_state.map(|s| s.as_str().to_string()),
+            network_type: telemetry.network_type,
+            signal_strength: telemetry.signal_strength,
+            recorded_at: telemetry.recorded_at,
+        }
+    }
+}
+
+/// Repository for device telemetry database operations.
+#[derive(Clone)]
+pub struct DeviceTelemetryRepository {
+    pool: PgPool,
+}
+
+impl DeviceTelemetryRepository {
+    /// Creates a new DeviceTelemetryRepository with the given connection pool.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Insert a single telemetry sample.
+    pub async fn insert(
+        &self,
+        device_id: Uuid,
+        input: TelemetryInput,
+    ) -> Result<DeviceTelemetryEntity, sqlx::Error> {
+        let timer = QueryTimer::new("insert_device_telemetry");
+
+        let result = sqlx::query_as::<_, DeviceTelemetryEntity>(
+            r#"
+            INSERT INTO device_telemetry (
+                device_id, battery_level, charging_state, network_type,
+                signal_strength, recorded_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+        )
+        .bind(device_id)
+        .bind(input.battery_level.map(|b| b as i16))
+        .bind(&input.charging_state)
+        .bind(&input.network_type)
+        .bind(input.signal_strength.map(|s| s as i16))
+        .bind(input.recorded_at)
+        .fetch_one(&self.pool)
+        .await;
+
+        timer.record();
+        result
+    }
+
+    /// Insert several telemetry samples for a device in a transaction.
+    pub async fn insert_batch(
+        &self,
+        device_id: Uuid,
+        samples: &[TelemetryInput],
+    ) -> Result<usize, sqlx::Error> {
+        if samples.is_empty() {
+            return Ok(0);
+        }
+
+        let timer = QueryTimer::new("insert_device_telemetry_batch");
+        let mut tx = self.pool.begin().await?;
+
+        for sample in samples {
+            sqlx::query(
+                r#"
+                INSERT INTO device_telemetry (
+                    device_id, battery_level, charging_state, network_type,
+                    signal_strength, recorded_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6)
+                "#,
+            )
+            .bind(device_id)
+            .bind(sample.battery_level.map(|b| b as i16))
+            .bind(&sample.charging_state)
+            .bind(&sample.network_type)
+            .bind(sample.signal_strength.map(|s| s as i16))
+            .bind(sample.recorded_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        timer.record();
+        Ok(samples.len())
+    }
+
+    /// The two latest battery readings of a device, newest first.
+    pub async fn latest_battery_levels(&self, device_id: Uuid) -> Result<Vec<i32>, sqlx::Error> {
+        let timer = QueryTimer::new("latest_device_battery_levels");
+        let result = sqlx::query_scalar::<_, i16>(
+            r#"
+            SELECT battery_level
+            FROM device_telemetry
+            WHERE device_id = $1 AND battery_level IS NOT NULL
+            ORDER BY recorded_at DESC
+            LIMIT 2
+            "#,
+        )
+        .bind(device_id)
+        .fetch_all(&self.pool)
+        .await;
+        timer.record();
+        result.map(|levels| levels.into_iter().map(i32::from).collect())
+    }
+}
@@ -0,0 +1,140 @@
+//! Transactional outbox repository.
+//!
+//! Repositories enqueue messages with [`enqueue`] inside the transaction of
+//! the domain change; the dispatcher claims them with a lease, so concurrent
+//! instances never dispatch the same message at the same time.
+
+use chrono::{DateTime, Duration, Utc};
+use domain::models::OutboxMessage;
+use sqlx::types::Json;
+use sqlx::{PgPool, Postgres, Transaction};
+
+use crate::entities::EventOutboxEntity;
+use crate::metrics::QueryTimer;
+
+/// Repository for outbox operations.
+pub struct EventOutboxRepository {
+    pool: PgPool,
+}
+
+impl EventOutboxRepository {
+    /// Create a new repository instance.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Claim up to `limit` due messages, oldest first, for `lease_secs`.
+    ///
+    /// A claimed message is offered again once its lease expires, so a
+    /// dispatcher that crashes mid-batch delays its messages but does not
+    /// lose them.
+    pub async fn claim_due(
+        &self,
+        limit: i64,
+        lease_secs: i64,
+    ) -> Result<Vec<EventOutboxEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("claim_outbox_messages");
+        let result = sqlx::query_as::<_, EventOutboxEntity>(
+            r#"
+            UPDATE event_outbox
+            SET locked_until = NOW() + make_interval(secs => $2),
+                attempts = attempts + 1
+            WHERE id IN (
+                SELECT id FROM event_outbox
+                WHERE processed_at IS NULL
+                  AND next_attempt_at <= NOW()
+                  AND (locked_until IS NULL OR locked_until < NOW())
+                ORDER BY id
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, kind, payload, attempts, next_attempt_at, locked_until, processed_at,
+                      last_error, created_at
+            "#,
+        )
+        .bind(limit)
+        .bind(lease_secs as f64)
+        .fetch_all(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Mark a message as dispatched.
+    pub async fn mark_processed(&self, id: i64) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE event_outbox
+            SET processed_at = NOW(), locked_until = NULL
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Record a failed dispatch. The message is offered again at
+    /// `retry_at`, or never again if it is None.
+    pub async fn mark_failed(
+        &self,
+        id: i64,
+        error: &str,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE event_outbox
+            SET last_error = $2,
+                locked_until = NULL,
+                next_attempt_at = COALESCE($3, next_attempt_at),
+                processed_at = CASE WHEN $3::TIMESTAMPTZ IS NULL THEN NOW() END
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(error)
+        .bind(retry_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Count messages waiting to be dispatched.
+    pub async fn count_unprocessed(&self) -> Result<i64, sqlx::Error> {
+        let count: (i64,) =
+            sqlx::query_as(r#"SELECT COUNT(*) FROM event_outbox WHERE processed_at IS NULL"#)
+                .fetch_one(&self.pool)
+                .await?;
+        Ok(count.0)
+    }
+
+    /// Delete messages processed more than `retention_days` ago.
+    pub async fn delete_processed(&self, retention_days: i32) -> Result<u64, sqlx::Error> {
+        let cutoff = Utc::now() - Duration::days(retention_days as i64);
+        let result = sqlx::query(
+            r#"
+            DELETE FROM event_outbox
+            WHERE processed_at < $1
+            "#,
+        )
+        .bind(cutoff)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+/// Store a message in the outbox as part of `tx`.
+pub(crate) async fn enqueue(
+    tx: &mut Transaction<'_, Postgres>,
+    message: &OutboxMessage,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("INSERT INTO event_outbox (kind, payload) VALUES ($1, $2)")
+        .bind(message.kind())
+        .bind(Json(message))
+        .execute(&mut **tx)
+        .await?;
+    Ok(())
+}
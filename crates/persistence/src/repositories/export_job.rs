@@ -0,0 +1,435 @@
+//! Export job repository for database operations.
+//!
+//! Story 13.10: Audit Query and Export Endpoints
+//!
+//! Export jobs track background exports: audit log exports, returned as
+//! data URLs, and full organization and group exports, stored as archives
+//! under the reports directory.
+
+use base64::{engine::general_purpose::URL_SAFE, Engine};
+use chrono::{DateTime, Duration, Utc};
+use domain::models::{ExportFormat, ExportJobKind, ExportJobStatus, EXPORT_JOB_EXPIRY_HOURS};
+use rand::Rng;
+use serde_json::Value as JsonValue;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::entities::ExportJobEntity;
+
+const JOB_COLUMNS: &str = "id, job_id, organization_id, group_id, requested_by, kind, status, \
+    format, filters, record_count, download_url, file_path, file_size, error_message, \
+    created_at, updated_at, expires_at, completed_at";
+
+/// Repository for export job database operations.
+#[derive(Clone)]
+pub struct ExportJobRepository {
+    pool: PgPool,
+}
+
+/// Domain model for export job.
+#[derive(Debug, Clone)]
+pub struct ExportJob {
+    pub id: Uuid,
+    pub job_id: String,
+    pub organization_id: Option<Uuid>,
+    pub group_id: Option<Uuid>,
+    pub requested_by: Option<Uuid>,
+    pub kind: ExportJobKind,
+    pub status: ExportJobStatus,
+    pub format: ExportFormat,
+    pub filters: Option<JsonValue>,
+    pub record_count: Option<i64>,
+    pub download_url: Option<String>,
+    pub file_path: Option<String>,
+    pub file_size: Option<i64>,
+    pub error_message: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl ExportJob {
+    /// Job status; expired once `expires_at` has passed, even before the
+    /// cleanup job has run.
+    pub fn status_at(&self, now: DateTime<Utc>) -> ExportJobStatus {
+        if self.expires_at <= now {
+            return ExportJobStatus::Expired;
+        }
+        self.status.clone()
+    }
+}
+
+impl ExportJobRepository {
+    /// Create a new repository instance.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Generate a unique job ID for a job of `kind`.
+    pub fn generate_job_id(kind: ExportJobKind) -> String {
+        let mut rng = rand::thread_rng();
+        let random_bytes: [u8; 12] = rng.gen();
+        let encoded = URL_SAFE.encode(random_bytes);
+        format!("{}_{}", kind.job_id_prefix(), encoded)
+    }
+
+    /// Create a new export job.
+    pub async fn create(
+        &self,
+        organization_id: Uuid,
+        kind: ExportJobKind,
+        format: ExportFormat,
+        filters: Option<JsonValue>,
+    ) -> Result<ExportJob, sqlx::Error> {
+        let job_id = Self::generate_job_id(kind);
+        let expires_at = Utc::now() + Duration::hours(EXPORT_JOB_EXPIRY_HOURS);
+        let format_str = match format {
+            ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Xlsx => "xlsx",
+        };
+
+        let entity = sqlx::query_as::<_, ExportJobEntity>(&format!(
+            r#"
+            INSERT INTO export_jobs (job_id, organization_id, kind, format, filters, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING {JOB_COLUMNS}
+            "#
+        ))
+        .bind(&job_id)
+        .bind(organization_id)
+        .bind(kind.as_str())
+        .bind(format_str)
+        .bind(&filters)
+        .bind(expires_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(entity_to_domain(entity))
+    }
+
+    /// Find an organization's export job of `kind` by job_id.
+    pub async fn find_by_job_id(
+        &self,
+        org_id: Uuid,
+        kind: ExportJobKind,
+        job_id: &str,
+    ) -> Result<Option<ExportJob>, sqlx::Error> {
+        let entity = sqlx::query_as::<_, ExportJobEntity>(&format!(
+            r#"
+            SELECT {JOB_COLUMNS}
+            FROM export_jobs
+            WHERE job_id = $1 AND organization_id = $2 AND kind = $3
+            "#
+        ))
+        .bind(job_id)
+        .bind(org_id)
+        .bind(kind.as_str())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(entity.map(entity_to_domain))
+    }
+
+    /// Whether the organization has an export of `kind` still being built.
+    pub async fn has_running_job(
+        &self,
+        org_id: Uuid,
+        kind: ExportJobKind,
+    ) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM export_jobs
+                WHERE organization_id = $1 AND kind = $2
+                AND status IN ('pending', 'processing')
+                AND expires_at > NOW()
+            )
+            "#,
+        )
+        .bind(org_id)
+        .bind(kind.as_str())
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Create a new export job of all of a group's data.
+    pub async fn create_for_group(
+        &self,
+        group_id: Uuid,
+        requested_by: Uuid,
+    ) -> Result<ExportJob, sqlx::Error> {
+        let kind = ExportJobKind::Group;
+        let job_id = Self::generate_job_id(kind);
+        let expires_at = Utc::now() + Duration::hours(EXPORT_JOB_EXPIRY_HOURS);
+
+        let entity = sqlx::query_as::<_, ExportJobEntity>(&format!(
+            r#"
+            INSERT INTO export_jobs (job_id, group_id, requested_by, kind, expires_at)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING {JOB_COLUMNS}
+            "#
+        ))
+        .bind(&job_id)
+        .bind(group_id)
+        .bind(requested_by)
+        .bind(kind.as_str())
+        .bind(expires_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(entity_to_domain(entity))
+    }
+
+    /// Find a group's export job by job_id.
+    pub async fn find_group_job(
+        &self,
+        group_id: Uuid,
+        job_id: &str,
+    ) -> Result<Option<ExportJob>, sqlx::Error> {
+        let entity = sqlx::query_as::<_, ExportJobEntity>(&format!(
+            r#"
+            SELECT {JOB_COLUMNS}
+            FROM export_jobs
+            WHERE job_id = $1 AND group_id = $2 AND kind = $3
+            "#
+        ))
+        .bind(job_id)
+        .bind(group_id)
+        .bind(ExportJobKind::Group.as_str())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(entity.map(entity_to_domain))
+    }
+
+    /// Whether the group has an export still being built.
+    pub async fn has_running_group_job(&self, group_id: Uuid) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM export_jobs
+                WHERE group_id = $1 AND kind = $2
+                AND status IN ('pending', 'processing')
+                AND expires_at > NOW()
+            )
+            "#,
+        )
+        .bind(group_id)
+        .bind(ExportJobKind::Group.as_str())
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Update job status to processing.
+    pub async fn mark_processing(&self, job_id: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE export_jobs
+            SET status = 'processing', updated_at = NOW()
+            WHERE job_id = $1 AND status = 'pending'
+            "#,
+        )
+        .bind(job_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Mark job as completed with download URL.
+    pub async fn mark_completed(
+        &self,
+        job_id: &str,
+        record_count: i64,
+        download_url: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE export_jobs
+            SET status = 'completed', record_count = $2, download_url = $3,
+                updated_at = NOW(), completed_at = NOW()
+            WHERE job_id = $1 AND status IN ('pending', 'processing')
+            "#,
+        )
+        .bind(job_id)
+        .bind(record_count)
+        .bind(download_url)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Mark job as completed with an archive in the reports directory.
+    pub async fn mark_completed_with_file(
+        &self,
+        job_id: &str,
+        record_count: i64,
+        file_path: &str,
+        file_size: i64,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE export_jobs
+            SET status = 'completed', record_count = $2, file_path = $3, file_size = $4,
+                updated_at = NOW(), completed_at = NOW()
+            WHERE job_id = $1 AND status IN ('pending', 'processing')
+            "#,
+        )
+        .bind(job_id)
+        .bind(record_count)
+        .bind(file_path)
+        .bind(file_size)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Mark job as failed with error message.
+    pub async fn mark_failed(&self, job_id: &str, error: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE export_jobs
+            SET status = 'failed', error_message = $2, updated_at = NOW()
+            WHERE job_id = $1 AND status IN ('pending', 'processing')
+            "#,
+        )
+        .bind(job_id)
+        .bind(error)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Mark expired jobs.
+    pub async fn mark_expired(&self) -> Result<i64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE export_jobs
+            SET status = 'expired', updated_at = NOW()
+            WHERE expires_at < NOW() AND status NOT IN ('expired', 'failed')
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() as i64)
+    }
+
+    /// Delete old expired jobs (older than 7 days).
+    pub async fn cleanup_old_jobs(&self) -> Result<i64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM export_jobs
+            WHERE expires_at < NOW() - INTERVAL '7 days'
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() as i64)
+    }
+
+    /// Delete expired jobs of `kind`, returning them so their archives can be
+    /// removed.
+    pub async fn delete_expired(&self, kind: ExportJobKind) -> Result<Vec<ExportJob>, sqlx::Error> {
+        let entities = sqlx::query_as::<_, ExportJobEntity>(&format!(
+            r#"
+            DELETE FROM export_jobs
+            WHERE kind = $1 AND expires_at < NOW()
+            RETURNING {JOB_COLUMNS}
+            "#
+        ))
+        .bind(kind.as_str())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entities.into_iter().map(entity_to_domain).collect())
+    }
+
+    /// List export jobs for an organization.
+    pub async fn list_for_org(
+        &self,
+        org_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<ExportJob>, sqlx::Error> {
+        let entities = sqlx::query_as::<_, ExportJobEntity>(&format!(
+            r#"
+            SELECT {JOB_COLUMNS}
+            FROM export_jobs
+            WHERE organization_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2
+            "#
+        ))
+        .bind(org_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entities.into_iter().map(entity_to_domain).collect())
+    }
+}
+
+fn entity_to_domain(entity: ExportJobEntity) -> ExportJob {
+    let kind = entity
+        .kind
+        .parse::<ExportJobKind>()
+        .unwrap_or(ExportJobKind::AuditLogs);
+    let status = entity
+        .status
+        .parse::<ExportJobStatus>()
+        .unwrap_or(ExportJobStatus::Pending);
+    let format = match entity.format.as_str() {
+        "csv" => ExportFormat::Csv,
+        "xlsx" => ExportFormat::Xlsx,
+        _ => ExportFormat::Json,
+    };
+
+    ExportJob {
+        id: entity.id,
+        job_id: entity.job_id,
+        organization_id: entity.organization_id,
+        group_id: entity.group_id,
+        requested_by: entity.requested_by,
+        kind,
+        status,
+        format,
+        filters: entity.filters,
+        record_count: entity.record_count,
+        download_url: entity.download_url,
+        file_path: entity.file_path,
+        file_size: entity.file_size,
+        error_message: entity.error_message,
+        created_at: entity.created_at,
+        updated_at: entity.updated_at,
+        expires_at: entity.expires_at,
+        completed_at: entity.completed_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_job_id() {
+        let job_id = ExportJobRepository::generate_job_id(ExportJobKind::AuditLogs);
+        assert!(job_id.starts_with("export_"));
+        assert!(job_id.len() > 10);
+
+        // Generate multiple and ensure uniqueness
+        let job_id2 = ExportJobRepository::generate_job_id(ExportJobKind::AuditLogs);
+        assert_ne!(job_id, job_id2);
+
+        let job_id = ExportJobRepository::generate_job_id(ExportJobKind::Organization);
+        assert!(job_id.starts_with("org_export_"));
+
+        let job_id = ExportJobRepository::generate_job_id(ExportJobKind::Group);
+        assert!(job_id.starts_with("group_export_"));
+    }
+}
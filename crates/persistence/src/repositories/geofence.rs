@@ -30,13 +30,18 @@ impl GeofenceRepository {
         event_types: &[String],
         active: bool,
         metadata: Option<serde_json::Value>,
+        schedule: Option<serde_json::Value>,
+        cooldown_seconds: i32,
+        arrival_alert_minutes: i32,
+        priority: i32,
     ) -> Result<GeofenceEntity, sqlx::Error> {
         let timer = QueryTimer::new("create_geofence");
         let result = sqlx::query_as::<_, GeofenceEntity>(
             r#"
             INSERT INTO geofences (device_id, name, latitude, longitude, radius_meters,
-                                   event_types, active, metadata)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                                   event_types, active, metadata, schedule, cooldown_seconds,
+                                   arrival_alert_minutes, priority)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
             RETURNING *
             "#,
         )
@@ -48,6 +53,10 @@ impl GeofenceRepository {
         .bind(event_types)
         .bind(active)
         .bind(metadata)
+        .bind(schedule)
+        .bind(cooldown_seconds)
+        .bind(arrival_alert_minutes)
+        .bind(priority)
         .fetch_one(&self.pool)
         .await;
         timer.record();
@@ -123,6 +132,7 @@ impl GeofenceRepository {
 
     /// Update a geofence (partial update).
     /// Only provided fields are updated; None values are preserved.
+    /// `schedule` of `Some(None)` removes the schedule.
     #[allow(clippy::too_many_arguments)]
     pub async fn update(
         &self,
@@ -134,6 +144,10 @@ impl GeofenceRepository {
         event_types: Option<&[String]>,
         active: Option<bool>,
         metadata: Option<serde_json::Value>,
+        schedule: Option<Option<serde_json::Value>>,
+        cooldown_seconds: Option<i32>,
+        arrival_alert_minutes: Option<i32>,
+        priority: Option<i32>,
     ) -> Result<Option<GeofenceEntity>, sqlx::Error> {
         let timer = QueryTimer::new("update_geofence");
 
@@ -147,6 +161,10 @@ impl GeofenceRepository {
                 event_types = COALESCE($6, event_types),
                 active = COALESCE($7, active),
                 metadata = COALESCE($8, metadata),
+                schedule = CASE WHEN $9::boolean THEN $10 ELSE schedule END,
+                cooldown_seconds = COALESCE($11, cooldown_seconds),
+                arrival_alert_minutes = COALESCE($12, arrival_alert_minutes),
+                priority = COALESCE($13, priority),
                 updated_at = NOW()
             WHERE geofence_id = $1
             RETURNING *
@@ -160,6 +178,11 @@ impl GeofenceRepository {
         .bind(event_types)
         .bind(active)
         .bind(metadata)
+        .bind(schedule.is_some())
+        .bind(schedule.flatten())
+        .bind(cooldown_seconds)
+        .bind(arrival_alert_minutes)
+        .bind(priority)
         .fetch_optional(&self.pool)
         .await;
         timer.record();
@@ -0,0 +1,76 @@
+//! Geofence arrival forecast repository.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::entities::GeofenceArrivalForecastEntity;
+use crate::metrics::QueryTimer;
+
+/// Repository for announced geofence arrivals.
+pub struct GeofenceArrivalForecastRepository {
+    pool: PgPool,
+}
+
+impl GeofenceArrivalForecastRepository {
+    /// Create a new repository instance.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Record an announced arrival.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        &self,
+        device_id: Uuid,
+        geofence_id: Uuid,
+        eta_seconds: i32,
+        distance_meters: f32,
+        latitude: f64,
+        longitude: f64,
+        forecast_at: DateTime<Utc>,
+    ) -> Result<GeofenceArrivalForecastEntity, sqlx::Error> {
+        let timer = QueryTimer::new("create_geofence_arrival_forecast");
+        let result = sqlx::query_as::<_, GeofenceArrivalForecastEntity>(
+            r#"
+            INSERT INTO geofence_arrival_forecasts (device_id, geofence_id, eta_seconds,
+                                                    distance_meters, latitude, longitude,
+                                                    forecast_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING *
+            "#,
+        )
+        .bind(device_id)
+        .bind(geofence_id)
+        .bind(eta_seconds)
+        .bind(distance_meters)
+        .bind(latitude)
+        .bind(longitude)
+        .bind(forecast_at)
+        .fetch_one(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Latest announced arrival at each of a device's geofences.
+    pub async fn latest_by_device(
+        &self,
+        device_id: Uuid,
+    ) -> Result<Vec<GeofenceArrivalForecastEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("latest_geofence_arrival_forecasts");
+        let result = sqlx::query_as::<_, GeofenceArrivalForecastEntity>(
+            r#"
+            SELECT DISTINCT ON (geofence_id) *
+            FROM geofence_arrival_forecasts
+            WHERE device_id = $1
+            ORDER BY geofence_id, forecast_at DESC, id DESC
+            "#,
+        )
+        .bind(device_id)
+        .fetch_all(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+}
@@ -3,10 +3,14 @@
 //! Story 15.2: Webhook Event Delivery
 //! Provides data access for geofence events.
 
+use domain::models::OutboxMessage;
+use shared::pagination::{CountMode, ListTotal};
 use sqlx::PgPool;
 use uuid::Uuid;
 
+use crate::db::estimate_table_rows;
 use crate::entities::geofence_event::{GeofenceEventEntity, GeofenceEventWithName};
+use crate::repositories::event_outbox;
 
 /// Repository for geofence event operations.
 pub struct GeofenceEventRepository {
@@ -20,21 +24,34 @@ impl GeofenceEventRepository {
     }
 
     /// Create a new geofence event.
+    ///
+    /// `source` is `client` for events reported by the device and `server`
+    /// for events derived from its locations. `resolved_geofence_id` is the
+    /// geofence the event resolved to among overlapping geofences.
+    ///
+    /// The event's fan-out to the group feed and webhooks is queued in the
+    /// outbox in the same transaction.
+    #[allow(clippy::too_many_arguments)]
     pub async fn create(
         &self,
         device_id: Uuid,
         geofence_id: Uuid,
+        geofence_name: &str,
         event_type: &str,
         timestamp: i64,
         latitude: f64,
         longitude: f64,
+        source: &str,
+        resolved_geofence_id: Uuid,
     ) -> Result<GeofenceEventEntity, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
         let entity = sqlx::query_as::<_, GeofenceEventEntity>(
             r#"
-            INSERT INTO geofence_events (device_id, geofence_id, event_type, timestamp, latitude, longitude)
-            VALUES ($1, $2, $3, $4, $5, $6)
+            INSERT INTO geofence_events (device_id, geofence_id, event_type, timestamp, latitude, longitude, source,
+                                         resolved_geofence_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
             RETURNING id, event_id, device_id, geofence_id, event_type, timestamp, latitude, longitude,
-                      webhook_delivered, webhook_response_code, created_at
+                      webhook_delivered, webhook_response_code, source, resolved_geofence_id, created_at
             "#,
         )
         .bind(device_id)
@@ -43,12 +60,66 @@ impl GeofenceEventRepository {
         .bind(timestamp)
         .bind(latitude)
         .bind(longitude)
-        .fetch_one(&self.pool)
+        .bind(source)
+        .bind(resolved_geofence_id)
+        .fetch_one(&mut *tx)
         .await?;
 
+        event_outbox::enqueue(
+            &mut tx,
+            &OutboxMessage::GeofenceEvent {
+                event_id: entity.event_id,
+                geofence_name: geofence_name.to_string(),
+            },
+        )
+        .await?;
+        tx.commit().await?;
+
         Ok(entity)
     }
 
+    /// Latest enter or exit event of each of a device's geofences.
+    pub async fn latest_transitions(
+        &self,
+        device_id: Uuid,
+    ) -> Result<Vec<GeofenceEventEntity>, sqlx::Error> {
+        sqlx::query_as::<_, GeofenceEventEntity>(
+            r#"
+            SELECT DISTINCT ON (geofence_id)
+                id, event_id, device_id, geofence_id, event_type, timestamp, latitude, longitude,
+                webhook_delivered, webhook_response_code, source, resolved_geofence_id, created_at
+            FROM geofence_events
+            WHERE device_id = $1 AND event_type IN ('enter', 'exit')
+            ORDER BY geofence_id, timestamp DESC, id DESC
+            "#,
+        )
+        .bind(device_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Latest enter or exit event of one geofence of a device.
+    pub async fn find_latest_transition(
+        &self,
+        device_id: Uuid,
+        geofence_id: Uuid,
+    ) -> Result<Option<GeofenceEventEntity>, sqlx::Error> {
+        sqlx::query_as::<_, GeofenceEventEntity>(
+            r#"
+            SELECT id, event_id, device_id, geofence_id, event_type, timestamp, latitude, longitude,
+                   webhook_delivered, webhook_response_code, source, resolved_geofence_id, created_at
+            FROM geofence_events
+            WHERE device_id = $1 AND geofence_id = $2 AND event_type IN ('enter', 'exit')
+            ORDER BY timestamp DESC, id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(device_id)
+        .bind(geofence_id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
     /// Find a geofence event by event_id.
     pub async fn find_by_event_id(
         &self,
@@ -60,7 +131,8 @@ impl GeofenceEventRepository {
                 e.id, e.event_id, e.device_id, e.geofence_id,
                 g.name as geofence_name,
                 e.event_type, e.timestamp, e.latitude, e.longitude,
-                e.webhook_delivered, e.webhook_response_code, e.created_at
+                e.webhook_delivered, e.webhook_response_code, e.source, e.resolved_geofence_id,
+                e.created_at
             FROM geofence_events e
             LEFT JOIN geofences g ON e.geofence_id = g.geofence_id
             WHERE e.event_id = $1
@@ -87,7 +159,8 @@ impl GeofenceEventRepository {
                     e.id, e.event_id, e.device_id, e.geofence_id,
                     g.name as geofence_name,
                     e.event_type, e.timestamp, e.latitude, e.longitude,
-                    e.webhook_delivered, e.webhook_response_code, e.created_at
+                    e.webhook_delivered, e.webhook_response_code, e.source, e.resolved_geofence_id,
+                e.created_at
                 FROM geofence_events e
                 LEFT JOIN geofences g ON e.geofence_id = g.geofence_id
                 WHERE e.device_id = $1 AND e.geofence_id = $2
@@ -107,7 +180,8 @@ impl GeofenceEventRepository {
                     e.id, e.event_id, e.device_id, e.geofence_id,
                     g.name as geofence_name,
                     e.event_type, e.timestamp, e.latitude, e.longitude,
-                    e.webhook_delivered, e.webhook_response_code, e.created_at
+                    e.webhook_delivered, e.webhook_response_code, e.source, e.resolved_geofence_id,
+                e.created_at
                 FROM geofence_events e
                 LEFT JOIN geofences g ON e.geofence_id = g.geofence_id
                 WHERE e.device_id = $1
@@ -156,6 +230,27 @@ impl GeofenceEventRepository {
         Ok(count.0)
     }
 
+    /// Total geofence events of a device as requested by `mode`; `seen` is
+    /// the number of events already returned to the caller.
+    pub async fn total_by_device_id(
+        &self,
+        device_id: Uuid,
+        geofence_id: Option<Uuid>,
+        mode: CountMode,
+        seen: i64,
+    ) -> Result<ListTotal, sqlx::Error> {
+        Ok(match mode {
+            CountMode::None => ListTotal::Omitted,
+            CountMode::Estimated => ListTotal::estimated(
+                estimate_table_rows(&self.pool, "geofence_events").await?,
+                seen,
+            ),
+            CountMode::Exact => {
+                ListTotal::Exact(self.count_by_device_id(device_id, geofence_id).await?)
+            }
+        })
+    }
+
     /// Update webhook delivery status for an event.
     pub async fn update_webhook_status(
         &self,
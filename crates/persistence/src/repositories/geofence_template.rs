@@ -0,0 +1,360 @@
+//! Geofence template repository.
+
+use std::collections::HashSet;
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::entities::{GeofenceEntity, GeofenceTemplateEntity};
+use crate::metrics::QueryTimer;
+
+const TEMPLATE_COLUMNS: &str = r#"
+    t.id, t.organization_id, t.name, t.description, t.latitude, t.longitude,
+    t.radius_meters, t.event_types, t.schedule, t.metadata,
+    (SELECT COUNT(*) FROM geofences g WHERE g.template_id = t.id) AS geofence_count,
+    t.created_by, t.created_at, t.updated_at
+"#;
+
+/// Geofence fields shared by a template and the geofences stamped from it.
+#[derive(Debug, Clone)]
+pub struct GeofenceTemplateFields<'a> {
+    pub name: &'a str,
+    pub description: Option<&'a str>,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub radius_meters: f32,
+    pub event_types: &'a [String],
+    pub schedule: Option<serde_json::Value>,
+    pub metadata: Option<serde_json::Value>,
+}
+
+/// Devices targeted by a template application, by outcome.
+#[derive(Debug, Clone, Default)]
+pub struct TemplateApplyOutcome {
+    pub created: Vec<Uuid>,
+    pub already_applied: Vec<Uuid>,
+    pub limit_reached: Vec<Uuid>,
+}
+
+/// Repository for geofence template operations.
+#[derive(Debug, Clone)]
+pub struct GeofenceTemplateRepository {
+    pool: PgPool,
+}
+
+impl GeofenceTemplateRepository {
+    /// Create a new geofence template repository.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Create a template.
+    pub async fn create(
+        &self,
+        organization_id: Uuid,
+        fields: &GeofenceTemplateFields<'_>,
+        created_by: Uuid,
+    ) -> Result<GeofenceTemplateEntity, sqlx::Error> {
+        let timer = QueryTimer::new("create_geofence_template");
+        let query = format!(
+            r#"
+            INSERT INTO geofence_templates AS t (organization_id, name, description, latitude,
+                longitude, radius_meters, event_types, schedule, metadata, created_by)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING {}
+            "#,
+            TEMPLATE_COLUMNS
+        );
+        let result = sqlx::query_as::<_, GeofenceTemplateEntity>(&query)
+            .bind(organization_id)
+            .bind(fields.name)
+            .bind(fields.description)
+            .bind(fields.latitude)
+            .bind(fields.longitude)
+            .bind(fields.radius_meters)
+            .bind(fields.event_types)
+            .bind(&fields.schedule)
+            .bind(&fields.metadata)
+            .bind(created_by)
+            .fetch_one(&self.pool)
+            .await;
+        timer.record();
+        result
+    }
+
+    /// List the templates of an organization, by name.
+    pub async fn list(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Vec<GeofenceTemplateEntity>, sqlx::Error> {
+        let query = format!(
+            r#"
+            SELECT {}
+            FROM geofence_templates t
+            WHERE t.organization_id = $1
+            ORDER BY t.name
+            "#,
+            TEMPLATE_COLUMNS
+        );
+        sqlx::query_as::<_, GeofenceTemplateEntity>(&query)
+            .bind(organization_id)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    /// Get a template of an organization.
+    pub async fn find_by_id(
+        &self,
+        organization_id: Uuid,
+        id: Uuid,
+    ) -> Result<Option<GeofenceTemplateEntity>, sqlx::Error> {
+        let query = format!(
+            r#"
+            SELECT {}
+            FROM geofence_templates t
+            WHERE t.organization_id = $1 AND t.id = $2
+            "#,
+            TEMPLATE_COLUMNS
+        );
+        sqlx::query_as::<_, GeofenceTemplateEntity>(&query)
+            .bind(organization_id)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Find a template of an organization by name.
+    pub async fn find_by_name(
+        &self,
+        organization_id: Uuid,
+        name: &str,
+    ) -> Result<Option<GeofenceTemplateEntity>, sqlx::Error> {
+        let query = format!(
+            r#"
+            SELECT {}
+            FROM geofence_templates t
+            WHERE t.organization_id = $1 AND t.name = $2
+            "#,
+            TEMPLATE_COLUMNS
+        );
+        sqlx::query_as::<_, GeofenceTemplateEntity>(&query)
+            .bind(organization_id)
+            .bind(name)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Replace a template, optionally updating its stamped geofences too.
+    ///
+    /// Returns the template and the number of geofences updated. The
+    /// geofences' active flag is left as is, so devices can pause them.
+    pub async fn update(
+        &self,
+        organization_id: Uuid,
+        id: Uuid,
+        fields: &GeofenceTemplateFields<'_>,
+        propagate: bool,
+    ) -> Result<Option<(GeofenceTemplateEntity, u64)>, sqlx::Error> {
+        let timer = QueryTimer::new("update_geofence_template");
+        let mut tx = self.pool.begin().await?;
+
+        let query = format!(
+            r#"
+            UPDATE geofence_templates AS t
+            SET name = $3, description = $4, latitude = $5, longitude = $6,
+                radius_meters = $7, event_types = $8, schedule = $9, metadata = $10,
+                updated_at = NOW()
+            WHERE t.organization_id = $1 AND t.id = $2
+            RETURNING {}
+            "#,
+            TEMPLATE_COLUMNS
+        );
+        let template = sqlx::query_as::<_, GeofenceTemplateEntity>(&query)
+            .bind(organization_id)
+            .bind(id)
+            .bind(fields.name)
+            .bind(fields.description)
+            .bind(fields.latitude)
+            .bind(fields.longitude)
+            .bind(fields.radius_meters)
+            .bind(fields.event_types)
+            .bind(&fields.schedule)
+            .bind(&fields.metadata)
+            .fetch_optional(&mut *tx)
+            .await?;
+        let Some(template) = template else {
+            return Ok(None);
+        };
+
+        let mut propagated = 0;
+        if propagate {
+            propagated = sqlx::query(
+                r#"
+                UPDATE geofences
+                SET name = $2, latitude = $3, longitude = $4, radius_meters = $5,
+                    event_types = $6, schedule = $7, metadata = $8, updated_at = NOW()
+                WHERE template_id = $1
+                "#,
+            )
+            .bind(id)
+            .bind(fields.name)
+            .bind(fields.latitude)
+            .bind(fields.longitude)
+            .bind(fields.radius_meters)
+            .bind(fields.event_types)
+            .bind(&fields.schedule)
+            .bind(&fields.metadata)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        }
+
+        tx.commit().await?;
+        timer.record();
+        Ok(Some((template, propagated)))
+    }
+
+    /// Delete a template. Its stamped geofences are deleted too when
+    /// `delete_geofences` is set, otherwise they become standalone.
+    pub async fn delete(
+        &self,
+        organization_id: Uuid,
+        id: Uuid,
+        delete_geofences: bool,
+    ) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        if delete_geofences {
+            sqlx::query(
+                r#"
+                DELETE FROM geofences g
+                USING geofence_templates t
+                WHERE g.template_id = t.id AND t.organization_id = $1 AND t.id = $2
+                "#,
+            )
+            .bind(organization_id)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let result =
+            sqlx::query("DELETE FROM geofence_templates WHERE organization_id = $1 AND id = $2")
+                .bind(organization_id)
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+
+        tx.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Resolve the active organization devices among `device_ids` plus
+    /// those of `group_ids`, up to `limit`.
+    pub async fn resolve_target_devices(
+        &self,
+        organization_id: Uuid,
+        device_ids: &[Uuid],
+        group_ids: &[String],
+        limit: i64,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT device_id
+            FROM devices
+            WHERE organization_id = $1 AND active = true
+              AND (device_id = ANY($2) OR group_id = ANY($3))
+            ORDER BY id
+            LIMIT $4
+            "#,
+        )
+        .bind(organization_id)
+        .bind(device_ids)
+        .bind(group_ids)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Stamp a template onto devices.
+    ///
+    /// Devices that already carry the template are left untouched, as are
+    /// devices that have `max_per_device` geofences.
+    pub async fn apply(
+        &self,
+        id: Uuid,
+        device_ids: &[Uuid],
+        max_per_device: i64,
+    ) -> Result<TemplateApplyOutcome, sqlx::Error> {
+        let timer = QueryTimer::new("apply_geofence_template");
+        let mut tx = self.pool.begin().await?;
+
+        let already_applied: HashSet<Uuid> = sqlx::query_scalar::<_, Uuid>(
+            "SELECT device_id FROM geofences WHERE template_id = $1 AND device_id = ANY($2)",
+        )
+        .bind(id)
+        .bind(device_ids)
+        .fetch_all(&mut *tx)
+        .await?
+        .into_iter()
+        .collect();
+
+        let created: HashSet<Uuid> = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            INSERT INTO geofences (device_id, name, latitude, longitude, radius_meters,
+                                   event_types, active, metadata, schedule, template_id)
+            SELECT d.device_id, t.name, t.latitude, t.longitude, t.radius_meters,
+                   t.event_types, TRUE, t.metadata, t.schedule, t.id
+            FROM UNNEST($2::UUID[]) AS d(device_id)
+            CROSS JOIN geofence_templates t
+            WHERE t.id = $1
+              AND (SELECT COUNT(*) FROM geofences g WHERE g.device_id = d.device_id) < $3
+            ON CONFLICT (device_id, template_id) WHERE template_id IS NOT NULL DO NOTHING
+            RETURNING device_id
+            "#,
+        )
+        .bind(id)
+        .bind(device_ids)
+        .bind(max_per_device)
+        .fetch_all(&mut *tx)
+        .await?
+        .into_iter()
+        .collect();
+
+        tx.commit().await?;
+        timer.record();
+
+        let mut outcome = TemplateApplyOutcome::default();
+        for &device_id in device_ids {
+            if created.contains(&device_id) {
+                outcome.created.push(device_id);
+            } else if already_applied.contains(&device_id) {
+                outcome.already_applied.push(device_id);
+            } else {
+                outcome.limit_reached.push(device_id);
+            }
+        }
+        Ok(outcome)
+    }
+
+    /// List the geofences stamped from a template of an organization.
+    pub async fn list_geofences(
+        &self,
+        organization_id: Uuid,
+        id: Uuid,
+    ) -> Result<Vec<GeofenceEntity>, sqlx::Error> {
+        sqlx::query_as::<_, GeofenceEntity>(
+            r#"
+            SELECT g.*
+            FROM geofences g
+            JOIN geofence_templates t ON t.id = g.template_id
+            WHERE t.organization_id = $1 AND t.id = $2
+            ORDER BY g.created_at
+            "#,
+        )
+        .bind(organization_id)
+        .bind(id)
+        .fetch_all(&self.pool)
+        .await
+    }
+}
@@ -1,14 +1,51 @@
 //! Group repository for database operations.
 
-use domain::models::group::GroupRole;
+use domain::models::group::{GroupRole, MAX_GROUP_DEPTH};
 use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::entities::{
     GroupEntity, GroupMembershipEntity, GroupRoleDb, GroupWithMembershipEntity,
-    MemberWithUserEntity,
+    MemberWithUserEntity, MigrationAuditLogEntity, SubgroupEntity,
 };
 use crate::metrics::QueryTimer;
+use crate::query_plans::{
+    GROUP_MEMBERSHIP, GROUP_WITH_MEMBERSHIP_ANY_DEVICE, GROUP_WITH_MEMBERSHIP_FOR_DEVICE,
+    IS_GROUP_MEMBER,
+};
+use crate::retry::retry_on_conflict;
+
+/// What a group merge moved into the target group.
+#[derive(Debug, Clone)]
+pub struct GroupMergeOutcome {
+    /// Members added to the target group.
+    pub members_moved: i64,
+    /// Devices moved to the target group.
+    pub device_ids: Vec<Uuid>,
+    /// Active geofences of the moved devices.
+    pub geofences_moved: i64,
+    /// Pending invites moved to the target group.
+    pub invites_moved: i64,
+    /// Migration audit log entry recording the merge.
+    pub audit_log: MigrationAuditLogEntity,
+}
+
+/// How far `set_parent` follows a parent chain before giving up. Well above
+/// `MAX_GROUP_DEPTH`, so only corrupted hierarchies reach it.
+const PARENT_CHAIN_LIMIT: i32 = 64;
+
+/// Result of moving a group under a new parent.
+#[derive(Debug, Clone)]
+pub enum SetParentOutcome {
+    /// The parent was changed.
+    Updated(Box<GroupEntity>),
+    /// The group doesn't exist or is inactive.
+    NotFound,
+    /// The new parent is the group itself or one of its descendants.
+    Cycle,
+    /// The new parent's chain doesn't end within `PARENT_CHAIN_LIMIT` levels.
+    ChainTooDeep,
+}
 
 /// Repository for group-related database operations.
 #[derive(Clone)]
@@ -47,7 +84,7 @@ impl GroupRepository {
             r#"
             INSERT INTO groups (name, slug, description, icon_emoji, max_devices, created_by)
             VALUES ($1, $2, $3, $4, $5, $6)
-            RETURNING id, name, slug, description, icon_emoji, max_devices, is_active, settings, created_by, created_at, updated_at
+            RETURNING id, name, slug, description, icon_emoji, max_devices, is_active, settings, created_by, created_at, updated_at, parent_group_id
             "#,
         )
         .bind(name)
@@ -81,7 +118,7 @@ impl GroupRepository {
         let timer = QueryTimer::new("find_group_by_id");
         let result = sqlx::query_as::<_, GroupEntity>(
             r#"
-            SELECT id, name, slug, description, icon_emoji, max_devices, is_active, settings, created_by, created_at, updated_at
+            SELECT id, name, slug, description, icon_emoji, max_devices, is_active, settings, created_by, created_at, updated_at, parent_group_id
             FROM groups
             WHERE id = $1 AND is_active = true
             "#,
@@ -98,7 +135,7 @@ impl GroupRepository {
         let timer = QueryTimer::new("find_group_by_slug");
         let result = sqlx::query_as::<_, GroupEntity>(
             r#"
-            SELECT id, name, slug, description, icon_emoji, max_devices, is_active, settings, created_by, created_at, updated_at
+            SELECT id, name, slug, description, icon_emoji, max_devices, is_active, settings, created_by, created_at, updated_at, parent_group_id
             FROM groups
             WHERE slug = $1 AND is_active = true
             "#,
@@ -129,7 +166,7 @@ impl GroupRepository {
                 r#"
                 SELECT
                     g.id, g.name, g.slug, g.description, g.icon_emoji, g.max_devices, g.is_active,
-                    g.settings, g.created_by, g.created_at, g.updated_at,
+                    g.settings, g.created_by, g.created_at, g.updated_at, g.parent_group_id,
                     gm.id as membership_id, gm.role, gm.joined_at,
                     (SELECT COUNT(*) FROM group_memberships WHERE group_id = g.id) as member_count,
                     (SELECT COUNT(*) FROM devices WHERE group_id = g.slug AND active = true) as device_count,
@@ -157,7 +194,7 @@ impl GroupRepository {
                 r#"
                 SELECT
                     g.id, g.name, g.slug, g.description, g.icon_emoji, g.max_devices, g.is_active,
-                    g.settings, g.created_by, g.created_at, g.updated_at,
+                    g.settings, g.created_by, g.created_at, g.updated_at, g.parent_group_id,
                     gm.id as membership_id, gm.role, gm.joined_at,
                     (SELECT COUNT(*) FROM group_memberships WHERE group_id = g.id) as member_count,
                     (SELECT COUNT(*) FROM devices WHERE group_id = g.slug AND active = true) as device_count,
@@ -187,6 +224,9 @@ impl GroupRepository {
 
     /// Find group with membership info for a specific user.
     ///
+    /// Owners and admins of an ancestor group get the group with an
+    /// inherited admin membership (see [`Self::get_membership`]).
+    ///
     /// # Arguments
     /// * `group_id` - The group ID to find
     /// * `user_id` - The user to check membership for
@@ -197,30 +237,66 @@ impl GroupRepository {
         user_id: Uuid,
         device_id: Option<Uuid>,
     ) -> Result<Option<GroupWithMembershipEntity>, sqlx::Error> {
-        let timer = QueryTimer::new("find_group_with_membership");
+        let query = if device_id.is_some() {
+            GROUP_WITH_MEMBERSHIP_FOR_DEVICE
+        } else {
+            GROUP_WITH_MEMBERSHIP_ANY_DEVICE
+        };
+        let timer = QueryTimer::new(query.name);
+        let mut statement = sqlx::query_as::<_, GroupWithMembershipEntity>(query.sql)
+            .persistent(true)
+            .bind(group_id)
+            .bind(user_id);
+        if let Some(device_id) = device_id {
+            statement = statement.bind(device_id);
+        }
+        let result = statement.fetch_optional(&self.pool).await;
+        timer.record();
+        match result? {
+            Some(group) => Ok(Some(group)),
+            None => match self.inherited_membership(group_id, user_id).await? {
+                Some(membership) => {
+                    self.find_group_with_inherited_membership(group_id, &membership, device_id)
+                        .await
+                }
+                None => Ok(None),
+            },
+        }
+    }
+
+    /// Group with an inherited admin membership taken from an ancestor group.
+    async fn find_group_with_inherited_membership(
+        &self,
+        group_id: Uuid,
+        membership: &GroupMembershipEntity,
+        device_id: Option<Uuid>,
+    ) -> Result<Option<GroupWithMembershipEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("find_group_with_inherited_membership");
         let result = sqlx::query_as::<_, GroupWithMembershipEntity>(
             r#"
             SELECT
                 g.id, g.name, g.slug, g.description, g.icon_emoji, g.max_devices, g.is_active,
-                g.settings, g.created_by, g.created_at, g.updated_at,
-                gm.id as membership_id, gm.role, gm.joined_at,
+                g.settings, g.created_by, g.created_at, g.updated_at, g.parent_group_id,
+                $2::uuid as membership_id, $3::group_role as role, $4::timestamptz as joined_at,
                 (SELECT COUNT(*) FROM group_memberships WHERE group_id = g.id) as member_count,
                 (SELECT COUNT(*) FROM devices WHERE group_id = g.slug AND active = true) as device_count,
                 EXISTS (
                     SELECT 1 FROM device_group_memberships dgm
                     JOIN devices d ON dgm.device_id = d.device_id
                     WHERE dgm.group_id = g.id
-                    AND d.owner_user_id = $2
+                    AND d.owner_user_id = $5
                     AND d.active = true
-                    AND (dgm.device_id = $3 OR $3 IS NULL)
+                    AND (dgm.device_id = $6 OR $6 IS NULL)
                 ) as has_current_device
             FROM groups g
-            JOIN group_memberships gm ON g.id = gm.group_id
-            WHERE g.id = $1 AND gm.user_id = $2 AND g.is_active = true
+            WHERE g.id = $1 AND g.is_active = true
             "#,
         )
         .bind(group_id)
-        .bind(user_id)
+        .bind(membership.id)
+        .bind(membership.role)
+        .bind(membership.joined_at)
+        .bind(membership.user_id)
         .bind(device_id)
         .fetch_optional(&self.pool)
         .await;
@@ -252,7 +328,7 @@ impl GroupRepository {
                 max_devices = COALESCE($6, max_devices),
                 updated_at = NOW()
             WHERE id = $1 AND is_active = true
-            RETURNING id, name, slug, description, icon_emoji, max_devices, is_active, settings, created_by, created_at, updated_at
+            RETURNING id, name, slug, description, icon_emoji, max_devices, is_active, settings, created_by, created_at, updated_at, parent_group_id
             "#,
         )
         .bind(group_id)
@@ -269,10 +345,17 @@ impl GroupRepository {
     }
 
     /// Soft delete a group.
+    ///
+    /// Its sub-groups become top-level groups.
     pub async fn delete_group(&self, group_id: Uuid) -> Result<u64, sqlx::Error> {
         let timer = QueryTimer::new("delete_group");
         let result = sqlx::query(
             r#"
+            WITH detached AS (
+                UPDATE groups
+                SET parent_group_id = NULL, updated_at = NOW()
+                WHERE parent_group_id = $1
+            )
             UPDATE groups
             SET is_active = false, updated_at = NOW()
             WHERE id = $1 AND is_active = true
@@ -285,28 +368,356 @@ impl GroupRepository {
         Ok(result.rows_affected())
     }
 
-    /// Get user's membership for a group.
+    /// Get a group's location retention override, if the group exists.
+    ///
+    /// Returns `Ok(None)` when the group does not exist and `Ok(Some(None))`
+    /// when it exists without an override.
+    pub async fn get_location_retention_days(
+        &self,
+        group_id: Uuid,
+    ) -> Result<Option<Option<i32>>, sqlx::Error> {
+        let timer = QueryTimer::new("get_group_location_retention");
+        let result = sqlx::query_scalar::<_, Option<i32>>(
+            "SELECT location_retention_days FROM groups WHERE id = $1",
+        )
+        .bind(group_id)
+        .fetch_optional(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Set or clear (`None`) a group's location retention override.
+    pub async fn set_location_retention_days(
+        &self,
+        group_id: Uuid,
+        retention_days: Option<i32>,
+    ) -> Result<bool, sqlx::Error> {
+        let timer = QueryTimer::new("set_group_location_retention");
+        let result = sqlx::query(
+            r#"
+            UPDATE groups
+            SET location_retention_days = $2, updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(group_id)
+        .bind(retention_days)
+        .execute(&self.pool)
+        .await;
+        timer.record();
+        Ok(result?.rows_affected() > 0)
+    }
+
+    /// Get a group's stored settings JSON, or None if the group doesn't exist.
+    pub async fn get_settings(
+        &self,
+        group_id: Uuid,
+    ) -> Result<Option<serde_json::Value>, sqlx::Error> {
+        let timer = QueryTimer::new("get_group_settings");
+        let result = sqlx::query_scalar::<_, serde_json::Value>(
+            "SELECT settings FROM groups WHERE id = $1 AND is_active = true",
+        )
+        .bind(group_id)
+        .fetch_optional(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Merge `settings` into a group's stored settings, keeping other keys.
+    ///
+    /// Returns the stored settings after the merge, or None if the group
+    /// doesn't exist.
+    pub async fn merge_settings(
+        &self,
+        group_id: Uuid,
+        settings: &serde_json::Value,
+    ) -> Result<Option<serde_json::Value>, sqlx::Error> {
+        let timer = QueryTimer::new("merge_group_settings");
+        let result = sqlx::query_scalar::<_, serde_json::Value>(
+            r#"
+            UPDATE groups
+            SET settings = COALESCE(settings, '{}'::jsonb) || $2, updated_at = NOW()
+            WHERE id = $1 AND is_active = true
+            RETURNING settings
+            "#,
+        )
+        .bind(group_id)
+        .bind(settings)
+        .fetch_optional(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Get user's effective membership for a group.
+    ///
+    /// A direct membership wins. Otherwise owners and admins of an active
+    /// ancestor group act as admins of the group; the returned row is the
+    /// nearest such ancestor membership with `group_id` set to this group and
+    /// the role lowered to admin. Plain members of an ancestor inherit
+    /// nothing.
     pub async fn get_membership(
         &self,
         group_id: Uuid,
         user_id: Uuid,
     ) -> Result<Option<GroupMembershipEntity>, sqlx::Error> {
-        let timer = QueryTimer::new("get_group_membership");
+        match self.get_direct_membership(group_id, user_id).await? {
+            Some(membership) => Ok(Some(membership)),
+            None => self.inherited_membership(group_id, user_id).await,
+        }
+    }
+
+    /// Get user's own membership row for a group, ignoring ancestor groups.
+    pub async fn get_direct_membership(
+        &self,
+        group_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<GroupMembershipEntity>, sqlx::Error> {
+        let timer = QueryTimer::new(GROUP_MEMBERSHIP.name);
+        let result = sqlx::query_as::<_, GroupMembershipEntity>(GROUP_MEMBERSHIP.sql)
+            .persistent(true)
+            .bind(group_id)
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await;
+        timer.record();
+        result
+    }
+
+    /// Admin membership inherited from the nearest ancestor group the user
+    /// owns or administers.
+    async fn inherited_membership(
+        &self,
+        group_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<GroupMembershipEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("get_inherited_group_membership");
         let result = sqlx::query_as::<_, GroupMembershipEntity>(
             r#"
-            SELECT id, group_id, user_id, role, invited_by, joined_at, updated_at
-            FROM group_memberships
-            WHERE group_id = $1 AND user_id = $2
+            WITH RECURSIVE ancestors AS (
+                SELECT id, parent_group_id, 0 AS depth
+                FROM groups
+                WHERE id = $1 AND is_active = true
+                UNION ALL
+                SELECT g.id, g.parent_group_id, a.depth + 1
+                FROM groups g
+                JOIN ancestors a ON g.id = a.parent_group_id
+                WHERE g.is_active = true AND a.depth < $3
+            )
+            SELECT gm.id, $1 AS group_id, gm.user_id, 'admin'::group_role AS role,
+                   gm.invited_by, gm.joined_at, gm.updated_at
+            FROM ancestors a
+            JOIN group_memberships gm ON gm.group_id = a.id
+            WHERE a.depth > 0 AND gm.user_id = $2 AND gm.role IN ('owner', 'admin')
+            ORDER BY a.depth
+            LIMIT 1
             "#,
         )
         .bind(group_id)
         .bind(user_id)
+        .bind(MAX_GROUP_DEPTH as i32)
         .fetch_optional(&self.pool)
         .await;
         timer.record();
         result
     }
 
+    /// Ids of a group's active ancestors, nearest first.
+    pub async fn ancestor_ids(&self, group_id: Uuid) -> Result<Vec<Uuid>, sqlx::Error> {
+        let timer = QueryTimer::new("get_group_ancestor_ids");
+        let result = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            WITH RECURSIVE ancestors AS (
+                SELECT id, parent_group_id, 0 AS depth
+                FROM groups
+                WHERE id = $1 AND is_active = true
+                UNION ALL
+                SELECT g.id, g.parent_group_id, a.depth + 1
+                FROM groups g
+                JOIN ancestors a ON g.id = a.parent_group_id
+                WHERE g.is_active = true AND a.depth < $2
+            )
+            SELECT id FROM ancestors
+            WHERE depth > 0
+            ORDER BY depth
+            "#,
+        )
+        .bind(group_id)
+        .bind(MAX_GROUP_DEPTH as i32)
+        .fetch_all(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Height of a group's subtree in levels: 1 for a group without active
+    /// sub-groups.
+    pub async fn subtree_height(&self, group_id: Uuid) -> Result<i32, sqlx::Error> {
+        let timer = QueryTimer::new("get_group_subtree_height");
+        let result = sqlx::query_scalar::<_, i32>(
+            r#"
+            WITH RECURSIVE descendants AS (
+                SELECT id, 1 AS level
+                FROM groups
+                WHERE id = $1
+                UNION ALL
+                SELECT g.id, d.level + 1
+                FROM groups g
+                JOIN descendants d ON g.parent_group_id = d.id
+                WHERE g.is_active = true AND d.level <= $2
+            )
+            SELECT MAX(level) FROM descendants
+            "#,
+        )
+        .bind(group_id)
+        .bind(MAX_GROUP_DEPTH as i32)
+        .fetch_one(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Set or clear a group's parent.
+    ///
+    /// Hierarchy changes are serialized with a transaction-scoped advisory
+    /// lock, so two concurrent moves cannot each pass the cycle check and
+    /// together form a loop. A parent chain that doesn't end within
+    /// `PARENT_CHAIN_LIMIT` levels is rejected rather than assumed acyclic.
+    /// Retried if the transaction loses a serialization or deadlock race.
+    pub async fn set_parent(
+        &self,
+        group_id: Uuid,
+        parent_group_id: Option<Uuid>,
+    ) -> Result<SetParentOutcome, sqlx::Error> {
+        let timer = QueryTimer::new("set_group_parent");
+
+        let result = retry_on_conflict("set_group_parent", || async {
+            let mut tx = self.pool.begin().await?;
+
+            sqlx::query("SELECT pg_advisory_xact_lock(hashtext('groups.parent_group_id'))")
+                .execute(&mut *tx)
+                .await?;
+
+            if let Some(parent_group_id) = parent_group_id {
+                let (contains_group, truncated) = sqlx::query_as::<_, (Option<bool>, Option<bool>)>(
+                    r#"
+                    WITH RECURSIVE parent_chain AS (
+                        SELECT id, parent_group_id, 0 AS depth
+                        FROM groups
+                        WHERE id = $2
+                        UNION ALL
+                        SELECT g.id, g.parent_group_id, c.depth + 1
+                        FROM groups g
+                        JOIN parent_chain c ON g.id = c.parent_group_id
+                        WHERE c.depth < $3
+                    )
+                    SELECT bool_or(id = $1),
+                           bool_or(depth = $3 AND parent_group_id IS NOT NULL)
+                    FROM parent_chain
+                    "#,
+                )
+                .bind(group_id)
+                .bind(parent_group_id)
+                .bind(PARENT_CHAIN_LIMIT)
+                .fetch_one(&mut *tx)
+                .await?;
+
+                if contains_group.unwrap_or(false) {
+                    return Ok(SetParentOutcome::Cycle);
+                }
+                if truncated.unwrap_or(false) {
+                    return Ok(SetParentOutcome::ChainTooDeep);
+                }
+            }
+
+            let group = sqlx::query_as::<_, GroupEntity>(
+                r#"
+                UPDATE groups
+                SET parent_group_id = $2, updated_at = NOW()
+                WHERE id = $1 AND is_active = true
+                RETURNING id, name, slug, description, icon_emoji, max_devices, is_active, settings, created_by, created_at, updated_at, parent_group_id
+                "#,
+            )
+            .bind(group_id)
+            .bind(parent_group_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+            tx.commit().await?;
+            Ok(group.map_or(SetParentOutcome::NotFound, |group| {
+                SetParentOutcome::Updated(Box::new(group))
+            }))
+        })
+        .await;
+        timer.record();
+        result
+    }
+
+    /// List a group's active direct sub-groups by name.
+    pub async fn list_subgroups(&self, group_id: Uuid) -> Result<Vec<SubgroupEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("list_subgroups");
+        let result = sqlx::query_as::<_, SubgroupEntity>(
+            r#"
+            SELECT g.id, g.name, g.slug, g.icon_emoji,
+                   (SELECT COUNT(*) FROM group_memberships WHERE group_id = g.id) as member_count
+            FROM groups g
+            WHERE g.parent_group_id = $1 AND g.is_active = true
+            ORDER BY g.name, g.id
+            "#,
+        )
+        .bind(group_id)
+        .fetch_all(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Groups whose archive receives a device's geofence events: the groups
+    /// the device belongs to and all of their active ancestors.
+    pub async fn geofence_event_group_ids(
+        &self,
+        device_id: Uuid,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        let timer = QueryTimer::new("get_geofence_event_group_ids");
+        let result = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            WITH RECURSIVE device_groups AS (
+                SELECT dgm.group_id AS id, g.parent_group_id, 0 AS depth
+                FROM device_group_memberships dgm
+                JOIN groups g ON g.id = dgm.group_id
+                WHERE dgm.device_id = $1
+                UNION ALL
+                SELECT g.id, g.parent_group_id, d.depth + 1
+                FROM groups g
+                JOIN device_groups d ON g.id = d.parent_group_id
+                WHERE g.is_active = true AND d.depth < $2
+            )
+            SELECT DISTINCT id FROM device_groups
+            "#,
+        )
+        .bind(device_id)
+        .bind(MAX_GROUP_DEPTH as i32)
+        .fetch_all(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Check whether a user is a member of a group.
+    pub async fn is_member(&self, group_id: Uuid, user_id: Uuid) -> Result<bool, sqlx::Error> {
+        let timer = QueryTimer::new(IS_GROUP_MEMBER.name);
+        let result = sqlx::query_scalar::<_, bool>(IS_GROUP_MEMBER.sql)
+            .persistent(true)
+            .bind(group_id)
+            .bind(user_id)
+            .fetch_one(&self.pool)
+            .await;
+        timer.record();
+        result
+    }
+
     /// Add a member to a group.
     pub async fn add_member(
         &self,
@@ -360,6 +771,32 @@ impl GroupRepository {
         result
     }
 
+    /// Set or clear a member's nickname within a group.
+    ///
+    /// Returns false if the user is not a direct member of the group.
+    pub async fn set_member_nickname(
+        &self,
+        group_id: Uuid,
+        user_id: Uuid,
+        nickname: Option<&str>,
+    ) -> Result<bool, sqlx::Error> {
+        let timer = QueryTimer::new("set_member_nickname");
+        let result = sqlx::query(
+            r#"
+            UPDATE group_memberships
+            SET nickname = $3, updated_at = NOW()
+            WHERE group_id = $1 AND user_id = $2
+            "#,
+        )
+        .bind(group_id)
+        .bind(user_id)
+        .bind(nickname)
+        .execute(&self.pool)
+        .await?;
+        timer.record();
+        Ok(result.rows_affected() > 0)
+    }
+
     /// Remove a member from a group.
     pub async fn remove_member(&self, group_id: Uuid, user_id: Uuid) -> Result<u64, sqlx::Error> {
         let timer = QueryTimer::new("remove_group_member");
@@ -430,7 +867,7 @@ impl GroupRepository {
                 r#"
                 SELECT
                     gm.id, gm.group_id, gm.user_id, gm.role, gm.invited_by, gm.joined_at,
-                    u.display_name, u.avatar_url
+                    gm.nickname, u.display_name, u.avatar_url
                 FROM group_memberships gm
                 JOIN users u ON gm.user_id = u.id
                 WHERE gm.group_id = $1 AND gm.role::text = $2
@@ -449,7 +886,7 @@ impl GroupRepository {
                 r#"
                 SELECT
                     gm.id, gm.group_id, gm.user_id, gm.role, gm.invited_by, gm.joined_at,
-                    u.display_name, u.avatar_url
+                    gm.nickname, u.display_name, u.avatar_url
                 FROM group_memberships gm
                 JOIN users u ON gm.user_id = u.id
                 WHERE gm.group_id = $1
@@ -516,7 +953,7 @@ impl GroupRepository {
             r#"
             SELECT
                 gm.id, gm.group_id, gm.user_id, gm.role, gm.invited_by, gm.joined_at,
-                u.display_name, u.avatar_url
+                gm.nickname, u.display_name, u.avatar_url
             FROM group_memberships gm
             JOIN users u ON gm.user_id = u.id
             WHERE gm.group_id = $1 AND gm.user_id = $2
@@ -536,6 +973,7 @@ impl GroupRepository {
 
     /// Transfer group ownership atomically.
     /// The current owner becomes admin, and the new owner gets the owner role.
+    /// Retried if the transaction loses a serialization or deadlock race.
     pub async fn transfer_ownership(
         &self,
         group_id: Uuid,
@@ -544,42 +982,206 @@ impl GroupRepository {
     ) -> Result<(), sqlx::Error> {
         let timer = QueryTimer::new("transfer_ownership");
 
-        // Start a transaction for atomic role swap
-        let mut tx = self.pool.begin().await?;
+        let result = retry_on_conflict("transfer_ownership", || async {
+            // Start a transaction for atomic role swap
+            let mut tx = self.pool.begin().await?;
 
-        // IMPORTANT: Promote new owner FIRST, then demote old owner.
-        // This order is required because of the check_group_has_owner trigger
-        // which prevents demoting the last owner before another owner exists.
+            // IMPORTANT: Promote new owner FIRST, then demote old owner.
+            // This order is required because of the check_group_has_owner trigger
+            // which prevents demoting the last owner before another owner exists.
 
-        // Promote new owner to owner
-        sqlx::query(
-            r#"
-            UPDATE group_memberships
-            SET role = 'owner', updated_at = NOW()
-            WHERE group_id = $1 AND user_id = $2
-            "#,
-        )
-        .bind(group_id)
-        .bind(new_owner_id)
-        .execute(&mut *tx)
-        .await?;
+            // Promote new owner to owner
+            sqlx::query(
+                r#"
+                UPDATE group_memberships
+                SET role = 'owner', updated_at = NOW()
+                WHERE group_id = $1 AND user_id = $2
+                "#,
+            )
+            .bind(group_id)
+            .bind(new_owner_id)
+            .execute(&mut *tx)
+            .await?;
 
-        // Demote previous owner to admin
-        sqlx::query(
-            r#"
-            UPDATE group_memberships
-            SET role = 'admin', updated_at = NOW()
-            WHERE group_id = $1 AND user_id = $2
-            "#,
-        )
-        .bind(group_id)
-        .bind(current_owner_id)
-        .execute(&mut *tx)
-        .await?;
+            // Demote previous owner to admin
+            sqlx::query(
+                r#"
+                UPDATE group_memberships
+                SET role = 'admin', updated_at = NOW()
+                WHERE group_id = $1 AND user_id = $2
+                "#,
+            )
+            .bind(group_id)
+            .bind(current_owner_id)
+            .execute(&mut *tx)
+            .await?;
 
-        tx.commit().await?;
+            tx.commit().await
+        })
+        .await;
+        timer.record();
+        result
+    }
+
+    // =========================================================================
+    // Group Merge
+    // =========================================================================
+
+    /// Merge a source group into a target group atomically.
+    ///
+    /// Members, devices and pending invites move to the target group and the
+    /// source group is soft deleted. Members already in the target keep their
+    /// role there; the source owner joins as admin. Geofences belong to
+    /// devices and follow them. The merge is recorded in the migration audit
+    /// log with the source group's slug as the registration group.
+    /// Retried if the transaction loses a serialization or deadlock race.
+    pub async fn merge_group(
+        &self,
+        source: &GroupEntity,
+        target: &GroupEntity,
+        user_id: Uuid,
+    ) -> Result<GroupMergeOutcome, sqlx::Error> {
+        let timer = QueryTimer::new("merge_group");
+
+        let result = retry_on_conflict("merge_group", || async {
+            let mut tx = self.pool.begin().await?;
+
+            // Copy memberships; the source owner row has to stay behind
+            // because a group cannot lose its last owner
+            let members_moved = sqlx::query(
+                r#"
+                INSERT INTO group_memberships (group_id, user_id, role, invited_by, joined_at,
+                    custom_role_id)
+                SELECT $2, user_id,
+                       CASE WHEN role = 'owner' THEN 'admin'::group_role ELSE role END,
+                       invited_by, joined_at, custom_role_id
+                FROM group_memberships
+                WHERE group_id = $1
+                ON CONFLICT (group_id, user_id) DO NOTHING
+                "#,
+            )
+            .bind(source.id)
+            .bind(target.id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected() as i64;
+
+            sqlx::query("DELETE FROM group_memberships WHERE group_id = $1 AND role != 'owner'")
+                .bind(source.id)
+                .execute(&mut *tx)
+                .await?;
+
+            // Devices are linked both through device_group_memberships and
+            // through the legacy slug in devices.group_id
+            let mut device_ids: Vec<Uuid> = sqlx::query_scalar(
+                r#"
+                WITH moved AS (
+                    DELETE FROM device_group_memberships
+                    WHERE group_id = $1
+                    RETURNING device_id, added_by, added_at
+                ), inserted AS (
+                    INSERT INTO device_group_memberships (device_id, group_id, added_by, added_at)
+                    SELECT device_id, $2, added_by, added_at FROM moved
+                    ON CONFLICT (device_id, group_id) DO NOTHING
+                )
+                SELECT device_id FROM moved
+                "#,
+            )
+            .bind(source.id)
+            .bind(target.id)
+            .fetch_all(&mut *tx)
+            .await?;
+
+            let legacy_ids: Vec<Uuid> = sqlx::query_scalar(
+                r#"
+                UPDATE devices
+                SET group_id = $2, updated_at = NOW()
+                WHERE group_id = $1
+                RETURNING device_id
+                "#,
+            )
+            .bind(&source.slug)
+            .bind(&target.slug)
+            .fetch_all(&mut *tx)
+            .await?;
+            for id in legacy_ids {
+                if !device_ids.contains(&id) {
+                    device_ids.push(id);
+                }
+            }
+
+            let geofences_moved: i64 = sqlx::query_scalar(
+                "SELECT COUNT(*) FROM geofences WHERE device_id = ANY($1) AND active = true",
+            )
+            .bind(&device_ids)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            let invites_moved = sqlx::query(
+                r#"
+                UPDATE group_invites
+                SET group_id = $2
+                WHERE group_id = $1 AND is_active = true AND status = 'pending'
+                "#,
+            )
+            .bind(source.id)
+            .bind(target.id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected() as i64;
+
+            sqlx::query(
+                r#"
+                WITH detached AS (
+                    UPDATE groups
+                    SET parent_group_id = NULL, updated_at = NOW()
+                    WHERE parent_group_id = $1
+                )
+                UPDATE groups
+                SET is_active = false, updated_at = NOW()
+                WHERE id = $1
+                "#,
+            )
+            .bind(source.id)
+            .execute(&mut *tx)
+            .await?;
+
+            let audit_log = sqlx::query_as::<_, MigrationAuditLogEntity>(
+                r#"
+                INSERT INTO migration_audit_logs (
+                    user_id,
+                    registration_group_id,
+                    authenticated_group_id,
+                    devices_migrated,
+                    device_ids,
+                    status,
+                    error_message
+                )
+                VALUES ($1, $2, $3, $4, $5, 'success', NULL)
+                RETURNING id, user_id, registration_group_id, authenticated_group_id, devices_migrated, device_ids, status, error_message, created_at
+                "#,
+            )
+            .bind(user_id)
+            .bind(&source.slug)
+            .bind(target.id)
+            .bind(device_ids.len() as i32)
+            .bind(&device_ids)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            tx.commit().await?;
+
+            Ok(GroupMergeOutcome {
+                members_moved,
+                device_ids,
+                geofences_moved,
+                invites_moved,
+                audit_log,
+            })
+        })
+        .await;
         timer.record();
-        Ok(())
+        result
     }
 }
 
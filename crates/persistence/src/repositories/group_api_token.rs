@@ -0,0 +1,144 @@
+//! Group API token repository for database operations.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::entities::GroupApiTokenEntity;
+use crate::metrics::QueryTimer;
+
+const TOKEN_COLUMNS: &str = "id, group_id, name, token_prefix, token_hash, rate_limit_per_minute, \
+    created_by, last_used_at, expires_at, revoked_at, created_at";
+
+/// Input for creating a group API token.
+#[derive(Debug, Clone)]
+pub struct CreateGroupApiTokenInput {
+    pub group_id: Uuid,
+    pub name: String,
+    pub token_prefix: String,
+    pub token_hash: String,
+    pub rate_limit_per_minute: i32,
+    pub created_by: Uuid,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Repository for group API token database operations.
+#[derive(Clone)]
+pub struct GroupApiTokenRepository {
+    pool: PgPool,
+}
+
+impl GroupApiTokenRepository {
+    /// Creates a new GroupApiTokenRepository with the given connection pool.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Create a token.
+    pub async fn create(
+        &self,
+        input: CreateGroupApiTokenInput,
+    ) -> Result<GroupApiTokenEntity, sqlx::Error> {
+        let timer = QueryTimer::new("create_group_api_token");
+        let result = sqlx::query_as::<_, GroupApiTokenEntity>(&format!(
+            r#"
+            INSERT INTO group_api_tokens
+                (group_id, name, token_prefix, token_hash, rate_limit_per_minute,
+                 created_by, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING {TOKEN_COLUMNS}
+            "#
+        ))
+        .bind(input.group_id)
+        .bind(&input.name)
+        .bind(&input.token_prefix)
+        .bind(&input.token_hash)
+        .bind(input.rate_limit_per_minute)
+        .bind(input.created_by)
+        .bind(input.expires_at)
+        .fetch_one(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Find a token by its hash, whether or not it is still usable.
+    pub async fn find_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<GroupApiTokenEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("find_group_api_token_by_hash");
+        let result = sqlx::query_as::<_, GroupApiTokenEntity>(&format!(
+            "SELECT {TOKEN_COLUMNS} FROM group_api_tokens WHERE token_hash = $1"
+        ))
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// List a group's tokens, newest first, including revoked ones.
+    pub async fn list_by_group(
+        &self,
+        group_id: Uuid,
+    ) -> Result<Vec<GroupApiTokenEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("list_group_api_tokens");
+        let result = sqlx::query_as::<_, GroupApiTokenEntity>(&format!(
+            "SELECT {TOKEN_COLUMNS} FROM group_api_tokens WHERE group_id = $1 ORDER BY created_at DESC"
+        ))
+        .bind(group_id)
+        .fetch_all(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Count a group's tokens that are not revoked.
+    pub async fn count_unrevoked(&self, group_id: Uuid) -> Result<i64, sqlx::Error> {
+        let timer = QueryTimer::new("count_unrevoked_group_api_tokens");
+        let result = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM group_api_tokens WHERE group_id = $1 AND revoked_at IS NULL",
+        )
+        .bind(group_id)
+        .fetch_one(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Revoke a group's token. Revoking twice keeps the first revocation
+    /// time. Returns `None` if the group has no such token.
+    pub async fn revoke(
+        &self,
+        group_id: Uuid,
+        token_id: Uuid,
+    ) -> Result<Option<GroupApiTokenEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("revoke_group_api_token");
+        let result = sqlx::query_as::<_, GroupApiTokenEntity>(&format!(
+            r#"
+            UPDATE group_api_tokens
+            SET revoked_at = COALESCE(revoked_at, NOW())
+            WHERE id = $1 AND group_id = $2
+            RETURNING {TOKEN_COLUMNS}
+            "#
+        ))
+        .bind(token_id)
+        .bind(group_id)
+        .fetch_optional(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Record that a token was used.
+    pub async fn update_last_used(&self, token_id: Uuid) -> Result<(), sqlx::Error> {
+        let timer = QueryTimer::new("update_group_api_token_last_used");
+        let result = sqlx::query("UPDATE group_api_tokens SET last_used_at = NOW() WHERE id = $1")
+            .bind(token_id)
+            .execute(&self.pool)
+            .await;
+        timer.record();
+        result.map(|_| ())
+    }
+}
@@ -0,0 +1,243 @@
+//! Custom group role repository.
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::entities::GroupCustomRoleEntity;
+use crate::metrics::QueryTimer;
+
+const ROLE_COLUMNS: &str = r#"
+    r.id, r.organization_id, r.name, r.description, r.permissions,
+    (SELECT COUNT(*) FROM group_memberships m WHERE m.custom_role_id = r.id) AS member_count,
+    r.created_by, r.created_at, r.updated_at
+"#;
+
+/// Fields of a custom group role.
+#[derive(Debug, Clone, Copy)]
+pub struct GroupCustomRoleFields<'a> {
+    pub name: &'a str,
+    pub description: Option<&'a str>,
+    /// Permission bitset.
+    pub permissions: i32,
+}
+
+/// Repository for custom group role operations.
+#[derive(Debug, Clone)]
+pub struct GroupCustomRoleRepository {
+    pool: PgPool,
+}
+
+impl GroupCustomRoleRepository {
+    /// Create a new custom group role repository.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Create a role.
+    pub async fn create(
+        &self,
+        organization_id: Uuid,
+        fields: GroupCustomRoleFields<'_>,
+        created_by: Uuid,
+    ) -> Result<GroupCustomRoleEntity, sqlx::Error> {
+        let timer = QueryTimer::new("create_group_custom_role");
+        let query = format!(
+            r#"
+            INSERT INTO group_custom_roles AS r (organization_id, name, description,
+                permissions, created_by)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING {}
+            "#,
+            ROLE_COLUMNS
+        );
+        let result = sqlx::query_as::<_, GroupCustomRoleEntity>(&query)
+            .bind(organization_id)
+            .bind(fields.name)
+            .bind(fields.description)
+            .bind(fields.permissions)
+            .bind(created_by)
+            .fetch_one(&self.pool)
+            .await;
+        timer.record();
+        result
+    }
+
+    /// List the roles of an organization by name.
+    pub async fn list(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Vec<GroupCustomRoleEntity>, sqlx::Error> {
+        let query = format!(
+            r#"
+            SELECT {}
+            FROM group_custom_roles r
+            WHERE r.organization_id = $1
+            ORDER BY r.name
+            "#,
+            ROLE_COLUMNS
+        );
+        sqlx::query_as::<_, GroupCustomRoleEntity>(&query)
+            .bind(organization_id)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    /// Get a role of an organization.
+    pub async fn find_by_id(
+        &self,
+        organization_id: Uuid,
+        id: Uuid,
+    ) -> Result<Option<GroupCustomRoleEntity>, sqlx::Error> {
+        let query = format!(
+            r#"
+            SELECT {}
+            FROM group_custom_roles r
+            WHERE r.organization_id = $1 AND r.id = $2
+            "#,
+            ROLE_COLUMNS
+        );
+        sqlx::query_as::<_, GroupCustomRoleEntity>(&query)
+            .bind(organization_id)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Find a role of an organization by name.
+    pub async fn find_by_name(
+        &self,
+        organization_id: Uuid,
+        name: &str,
+    ) -> Result<Option<GroupCustomRoleEntity>, sqlx::Error> {
+        let query = format!(
+            r#"
+            SELECT {}
+            FROM group_custom_roles r
+            WHERE r.organization_id = $1 AND r.name = $2
+            "#,
+            ROLE_COLUMNS
+        );
+        sqlx::query_as::<_, GroupCustomRoleEntity>(&query)
+            .bind(organization_id)
+            .bind(name)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Replace a role.
+    pub async fn update(
+        &self,
+        organization_id: Uuid,
+        id: Uuid,
+        fields: GroupCustomRoleFields<'_>,
+    ) -> Result<Option<GroupCustomRoleEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("update_group_custom_role");
+        let query = format!(
+            r#"
+            UPDATE group_custom_roles r
+            SET name = $3, description = $4, permissions = $5, updated_at = NOW()
+            WHERE r.organization_id = $1 AND r.id = $2
+            RETURNING {}
+            "#,
+            ROLE_COLUMNS
+        );
+        let result = sqlx::query_as::<_, GroupCustomRoleEntity>(&query)
+            .bind(organization_id)
+            .bind(id)
+            .bind(fields.name)
+            .bind(fields.description)
+            .bind(fields.permissions)
+            .fetch_optional(&self.pool)
+            .await;
+        timer.record();
+        result
+    }
+
+    /// Delete a role. Members holding it fall back to their built-in role.
+    pub async fn delete(&self, organization_id: Uuid, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result =
+            sqlx::query("DELETE FROM group_custom_roles WHERE organization_id = $1 AND id = $2")
+                .bind(organization_id)
+                .bind(id)
+                .execute(&self.pool)
+                .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Get a role that can be assigned in a group, i.e. one defined by an
+    /// organization that a member of the group belongs to.
+    pub async fn find_assignable(
+        &self,
+        id: Uuid,
+        group_id: Uuid,
+    ) -> Result<Option<GroupCustomRoleEntity>, sqlx::Error> {
+        let query = format!(
+            r#"
+            SELECT {}
+            FROM group_custom_roles r
+            WHERE r.id = $1
+              AND EXISTS (
+                  SELECT 1
+                  FROM group_memberships gm
+                  JOIN org_users ou ON ou.user_id = gm.user_id
+                  WHERE gm.group_id = $2 AND ou.organization_id = r.organization_id
+              )
+            "#,
+            ROLE_COLUMNS
+        );
+        sqlx::query_as::<_, GroupCustomRoleEntity>(&query)
+            .bind(id)
+            .bind(group_id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Get the custom role of a direct group member, if any.
+    pub async fn find_for_member(
+        &self,
+        group_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<GroupCustomRoleEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("find_member_group_custom_role");
+        let query = format!(
+            r#"
+            SELECT {}
+            FROM group_memberships gm
+            JOIN group_custom_roles r ON r.id = gm.custom_role_id
+            WHERE gm.group_id = $1 AND gm.user_id = $2
+            "#,
+            ROLE_COLUMNS
+        );
+        let result = sqlx::query_as::<_, GroupCustomRoleEntity>(&query)
+            .bind(group_id)
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await;
+        timer.record();
+        result
+    }
+
+    /// Set or clear the custom role of a direct group member.
+    ///
+    /// Returns false if the user is not a member of the group.
+    pub async fn assign(
+        &self,
+        group_id: Uuid,
+        user_id: Uuid,
+        custom_role_id: Option<Uuid>,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE group_memberships
+            SET custom_role_id = $3, updated_at = NOW()
+            WHERE group_id = $1 AND user_id = $2
+            "#,
+        )
+        .bind(group_id)
+        .bind(user_id)
+        .bind(custom_role_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,145 @@
+//! Group event repository.
+//!
+//! Provides data access for the per-group event archive.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::entities::GroupEventEntity;
+use crate::metrics::QueryTimer;
+
+/// Repository for group event archive operations.
+#[derive(Clone)]
+pub struct GroupEventRepository {
+    pool: PgPool,
+}
+
+impl GroupEventRepository {
+    /// Create a new repository instance.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Append an event to a group's archive.
+    pub async fn create(
+        &self,
+        group_id: Uuid,
+        event_type: &str,
+        device_id: Option<Uuid>,
+        actor_user_id: Option<Uuid>,
+        payload: &serde_json::Value,
+    ) -> Result<GroupEventEntity, sqlx::Error> {
+        let timer = QueryTimer::new("create_group_event");
+        let result = sqlx::query_as::<_, GroupEventEntity>(
+            r#"
+            INSERT INTO group_events (group_id, event_type, device_id, actor_user_id, payload)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, event_id, group_id, event_type, device_id, actor_user_id, payload,
+                      occurred_at,
+                      (SELECT d.owner_user_id FROM devices d
+                       WHERE d.device_id = group_events.device_id) AS device_owner_id
+            "#,
+        )
+        .bind(group_id)
+        .bind(event_type)
+        .bind(device_id)
+        .bind(actor_user_id)
+        .bind(payload)
+        .fetch_one(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Append a device event to the archive of each of `group_ids`.
+    ///
+    /// Returns the number of archived rows (one per group).
+    pub async fn create_for_groups(
+        &self,
+        group_ids: &[Uuid],
+        device_id: Uuid,
+        event_type: &str,
+        payload: &serde_json::Value,
+    ) -> Result<u64, sqlx::Error> {
+        let timer = QueryTimer::new("create_group_events_for_device");
+        let result = sqlx::query(
+            r#"
+            INSERT INTO group_events (group_id, event_type, device_id, payload)
+            SELECT group_id, $3, $2, $4
+            FROM UNNEST($1::uuid[]) AS group_id
+            "#,
+        )
+        .bind(group_ids)
+        .bind(device_id)
+        .bind(event_type)
+        .bind(payload)
+        .execute(&self.pool)
+        .await;
+        timer.record();
+        Ok(result?.rows_affected())
+    }
+
+    /// List archived events of a group in chronological order.
+    ///
+    /// `cursor` is the `(occurred_at, id)` of the last event of the previous
+    /// page. Fetches `limit + 1` rows and returns whether more results exist.
+    pub async fn list_by_group(
+        &self,
+        group_id: Uuid,
+        since: Option<DateTime<Utc>>,
+        cursor: Option<(DateTime<Utc>, i64)>,
+        limit: i64,
+    ) -> Result<(Vec<GroupEventEntity>, bool), sqlx::Error> {
+        let timer = QueryTimer::new("list_group_events");
+        let (cursor_timestamp, cursor_id) = match cursor {
+            Some((ts, id)) => (Some(ts), id),
+            None => (None, i64::MIN),
+        };
+
+        let mut events = sqlx::query_as::<_, GroupEventEntity>(
+            r#"
+            SELECT e.id, e.event_id, e.group_id, e.event_type, e.device_id, e.actor_user_id,
+                   e.payload, e.occurred_at, d.owner_user_id AS device_owner_id
+            FROM group_events e
+            LEFT JOIN devices d ON d.device_id = e.device_id
+            WHERE e.group_id = $1
+              AND ($2::timestamptz IS NULL OR e.occurred_at >= $2)
+              AND ($3::timestamptz IS NULL OR (e.occurred_at, e.id) > ($3, $4))
+            ORDER BY e.occurred_at ASC, e.id ASC
+            LIMIT $5
+            "#,
+        )
+        .bind(group_id)
+        .bind(since)
+        .bind(cursor_timestamp)
+        .bind(cursor_id)
+        .bind(limit + 1)
+        .fetch_all(&self.pool)
+        .await?;
+        timer.record();
+
+        let has_more = events.len() > limit as usize;
+        if has_more {
+            events.pop();
+        }
+
+        Ok((events, has_more))
+    }
+
+    /// Delete archived events older than the retention period.
+    ///
+    /// Returns the number of deleted rows.
+    pub async fn delete_older_than(&self, retention_days: u32) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM group_events
+            WHERE occurred_at < NOW() - make_interval(days => $1)
+            "#,
+        )
+        .bind(retention_days as i32)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
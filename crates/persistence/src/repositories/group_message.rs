@@ -0,0 +1,172 @@
+//! Group message repository.
+//!
+//! Provides data access for group announcements and check-ins.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::entities::GroupMessageEntity;
+use crate::metrics::QueryTimer;
+
+/// Repository for group message operations.
+#[derive(Clone)]
+pub struct GroupMessageRepository {
+    pool: PgPool,
+}
+
+/// A message to post to a group.
+#[derive(Debug, Clone)]
+pub struct NewGroupMessage<'a> {
+    pub group_id: Uuid,
+    pub sender_user_id: Uuid,
+    pub kind: &'a str,
+    pub body: Option<&'a str>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub place_name: Option<&'a str>,
+}
+
+impl GroupMessageRepository {
+    /// Create a new repository instance.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Post a message to a group.
+    pub async fn create(
+        &self,
+        message: NewGroupMessage<'_>,
+    ) -> Result<GroupMessageEntity, sqlx::Error> {
+        let timer = QueryTimer::new("create_group_message");
+        let result = sqlx::query_as::<_, GroupMessageEntity>(
+            r#"
+            WITH inserted AS (
+                INSERT INTO group_messages
+                    (group_id, sender_user_id, kind, body, latitude, longitude, place_name)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                RETURNING *
+            )
+            SELECT m.*, u.display_name AS sender_display_name
+            FROM inserted m
+            LEFT JOIN users u ON u.id = m.sender_user_id
+            "#,
+        )
+        .bind(message.group_id)
+        .bind(message.sender_user_id)
+        .bind(message.kind)
+        .bind(message.body)
+        .bind(message.latitude)
+        .bind(message.longitude)
+        .bind(message.place_name)
+        .fetch_one(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// List a group's messages newest first.
+    ///
+    /// `cursor` is the (created_at, id) of the last message of the previous
+    /// page. Returns the page and whether more messages follow.
+    pub async fn list_by_group(
+        &self,
+        group_id: Uuid,
+        kind: Option<&str>,
+        cursor: Option<(DateTime<Utc>, i64)>,
+        limit: i64,
+    ) -> Result<(Vec<GroupMessageEntity>, bool), sqlx::Error> {
+        let timer = QueryTimer::new("list_group_messages");
+        let (cursor_timestamp, cursor_id) = match cursor {
+            Some((ts, id)) => (Some(ts), id),
+            None => (None, i64::MAX),
+        };
+
+        let mut messages = sqlx::query_as::<_, GroupMessageEntity>(
+            r#"
+            SELECT m.*, u.display_name AS sender_display_name
+            FROM group_messages m
+            LEFT JOIN users u ON u.id = m.sender_user_id
+            WHERE m.group_id = $1
+              AND ($2::varchar IS NULL OR m.kind = $2)
+              AND ($3::timestamptz IS NULL OR (m.created_at, m.id) < ($3, $4))
+            ORDER BY m.created_at DESC, m.id DESC
+            LIMIT $5
+            "#,
+        )
+        .bind(group_id)
+        .bind(kind)
+        .bind(cursor_timestamp)
+        .bind(cursor_id)
+        .bind(limit + 1)
+        .fetch_all(&self.pool)
+        .await?;
+        timer.record();
+
+        let has_more = messages.len() > limit as usize;
+        if has_more {
+            messages.pop();
+        }
+
+        Ok((messages, has_more))
+    }
+
+    /// Active devices of the group's members, except those of `sender`.
+    pub async fn list_recipient_device_ids(
+        &self,
+        group_id: Uuid,
+        sender: Uuid,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        let timer = QueryTimer::new("list_group_message_recipients");
+        let result = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT d.device_id
+            FROM group_memberships gm
+            JOIN devices d ON d.owner_user_id = gm.user_id AND d.active = true
+            WHERE gm.group_id = $1 AND gm.user_id <> $2
+            "#,
+        )
+        .bind(group_id)
+        .bind(sender)
+        .fetch_all(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Delete messages older than the retention window.
+    pub async fn delete_older_than(&self, retention_days: u32) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM group_messages
+            WHERE created_at < NOW() - make_interval(days => $1)
+            "#,
+        )
+        .bind(retention_days as i32)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Delete all but the newest `keep` messages of every group.
+    pub async fn trim_per_group(&self, keep: u32) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM group_messages
+            WHERE id IN (
+                SELECT id FROM (
+                    SELECT id, ROW_NUMBER() OVER (
+                        PARTITION BY group_id ORDER BY created_at DESC, id DESC
+                    ) AS position
+                    FROM group_messages
+                ) ranked
+                WHERE position > $1
+            )
+            "#,
+        )
+        .bind(keep as i64)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,140 @@
+//! Group notification preferences repository.
+//!
+//! Besides storing preferences, resolves the devices a group notification
+//! fans out to. Members without stored preferences receive every category.
+
+use domain::models::{GroupNotificationCategory, GroupNotificationPreferences};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::entities::GroupNotificationPreferencesEntity;
+use crate::metrics::QueryTimer;
+
+/// Repository for group notification preferences.
+#[derive(Debug, Clone)]
+pub struct GroupNotificationPreferencesRepository {
+    pool: PgPool,
+}
+
+impl GroupNotificationPreferencesRepository {
+    /// Create a new group notification preferences repository.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Stored preferences of a member, if they changed the defaults.
+    pub async fn find(
+        &self,
+        group_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<GroupNotificationPreferencesEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("find_group_notification_preferences");
+        let result = sqlx::query_as::<_, GroupNotificationPreferencesEntity>(
+            r#"
+            SELECT group_id, user_id, geofence_events, sos, low_battery, member_joins, updated_at
+            FROM group_notification_preferences
+            WHERE group_id = $1 AND user_id = $2
+            "#,
+        )
+        .bind(group_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Store the preferences of a member.
+    pub async fn upsert(
+        &self,
+        group_id: Uuid,
+        user_id: Uuid,
+        preferences: &GroupNotificationPreferences,
+    ) -> Result<GroupNotificationPreferencesEntity, sqlx::Error> {
+        let timer = QueryTimer::new("upsert_group_notification_preferences");
+        let result = sqlx::query_as::<_, GroupNotificationPreferencesEntity>(
+            r#"
+            INSERT INTO group_notification_preferences
+                (group_id, user_id, geofence_events, sos, low_battery, member_joins)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (group_id, user_id) DO UPDATE SET
+                geofence_events = EXCLUDED.geofence_events,
+                sos = EXCLUDED.sos,
+                low_battery = EXCLUDED.low_battery,
+                member_joins = EXCLUDED.member_joins,
+                updated_at = NOW()
+            RETURNING group_id, user_id, geofence_events, sos, low_battery, member_joins, updated_at
+            "#,
+        )
+        .bind(group_id)
+        .bind(user_id)
+        .bind(preferences.geofence_events)
+        .bind(preferences.sos)
+        .bind(preferences.low_battery)
+        .bind(preferences.member_joins)
+        .fetch_one(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// IDs of the active devices sharing a group with a device whose owners
+    /// receive `category` notifications in at least one of the shared groups.
+    pub async fn list_peer_recipient_device_ids(
+        &self,
+        device_id: Uuid,
+        category: GroupNotificationCategory,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        let timer = QueryTimer::new("list_group_notification_peer_recipients");
+        // The column comes from a fixed set of category names
+        let query = format!(
+            r#"
+            SELECT DISTINCT peer.device_id
+            FROM device_group_memberships own
+            JOIN device_group_memberships peer
+                ON peer.group_id = own.group_id AND peer.device_id <> own.device_id
+            JOIN devices d ON d.device_id = peer.device_id AND d.active = true
+            LEFT JOIN group_notification_preferences p
+                ON p.group_id = peer.group_id AND p.user_id = d.owner_user_id
+            WHERE own.device_id = $1 AND COALESCE(p.{}, true)
+            "#,
+            category.as_str()
+        );
+        let result = sqlx::query_scalar::<_, Uuid>(&query)
+            .bind(device_id)
+            .fetch_all(&self.pool)
+            .await;
+        timer.record();
+        result
+    }
+
+    /// IDs of the active devices of a group's members, except those of
+    /// `exclude_user`, whose owners receive `category` notifications.
+    pub async fn list_member_recipient_device_ids(
+        &self,
+        group_id: Uuid,
+        exclude_user: Uuid,
+        category: GroupNotificationCategory,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        let timer = QueryTimer::new("list_group_notification_member_recipients");
+        // The column comes from a fixed set of category names
+        let query = format!(
+            r#"
+            SELECT d.device_id
+            FROM group_memberships gm
+            JOIN devices d ON d.owner_user_id = gm.user_id AND d.active = true
+            LEFT JOIN group_notification_preferences p
+                ON p.group_id = gm.group_id AND p.user_id = gm.user_id
+            WHERE gm.group_id = $1 AND gm.user_id <> $2 AND COALESCE(p.{}, true)
+            "#,
+            category.as_str()
+        );
+        let result = sqlx::query_scalar::<_, Uuid>(&query)
+            .bind(group_id)
+            .bind(exclude_user)
+            .fetch_all(&self.pool)
+            .await;
+        timer.record();
+        result
+    }
+}
@@ -26,7 +26,8 @@ impl InviteRepository {
         &self.pool
     }
 
-    /// Create a new invite.
+    /// Create a new invite; `recipient_email` binds it to one user.
+    #[allow(clippy::too_many_arguments)]
     pub async fn create_invite(
         &self,
         group_id: Uuid,
@@ -35,13 +36,14 @@ impl InviteRepository {
         max_uses: i32,
         expires_at: DateTime<Utc>,
         created_by: Uuid,
+        recipient_email: Option<&str>,
     ) -> Result<GroupInviteEntity, sqlx::Error> {
         let timer = QueryTimer::new("create_invite");
         let result = sqlx::query_as::<_, GroupInviteEntity>(
             r#"
-            INSERT INTO group_invites (group_id, code, preset_role, max_uses, expires_at, created_by)
-            VALUES ($1, $2, $3, $4, $5, $6)
-            RETURNING id, group_id, code, preset_role, max_uses, current_uses, expires_at, created_by, created_at, is_active
+            INSERT INTO group_invites (group_id, code, preset_role, max_uses, expires_at, created_by, recipient_email)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id, group_id, code, preset_role, max_uses, current_uses, expires_at, created_by, created_at, is_active, recipient_email, status
             "#,
         )
         .bind(group_id)
@@ -50,6 +52,7 @@ impl InviteRepository {
         .bind(max_uses)
         .bind(expires_at)
         .bind(created_by)
+        .bind(recipient_email)
         .fetch_one(&self.pool)
         .await;
         timer.record();
@@ -61,7 +64,7 @@ impl InviteRepository {
         let timer = QueryTimer::new("find_invite_by_id");
         let result = sqlx::query_as::<_, GroupInviteEntity>(
             r#"
-            SELECT id, group_id, code, preset_role, max_uses, current_uses, expires_at, created_by, created_at, is_active
+            SELECT id, group_id, code, preset_role, max_uses, current_uses, expires_at, created_by, created_at, is_active, recipient_email, status
             FROM group_invites
             WHERE id = $1 AND is_active = true
             "#,
@@ -78,7 +81,7 @@ impl InviteRepository {
         let timer = QueryTimer::new("find_invite_by_code");
         let result = sqlx::query_as::<_, GroupInviteEntity>(
             r#"
-            SELECT id, group_id, code, preset_role, max_uses, current_uses, expires_at, created_by, created_at, is_active
+            SELECT id, group_id, code, preset_role, max_uses, current_uses, expires_at, created_by, created_at, is_active, recipient_email, status
             FROM group_invites
             WHERE code = $1 AND is_active = true
             "#,
@@ -100,7 +103,7 @@ impl InviteRepository {
             r#"
             SELECT
                 i.id, i.group_id, i.code, i.preset_role, i.max_uses, i.current_uses,
-                i.expires_at, i.is_active,
+                i.expires_at, i.is_active, i.recipient_email,
                 g.name as group_name, g.icon_emoji as group_icon_emoji,
                 (SELECT COUNT(*) FROM group_memberships WHERE group_id = g.id) as member_count
             FROM group_invites i
@@ -126,7 +129,7 @@ impl InviteRepository {
             r#"
             SELECT
                 i.id, i.group_id, i.code, i.preset_role, i.max_uses, i.current_uses,
-                i.expires_at, i.created_by, i.created_at,
+                i.expires_at, i.created_by, i.created_at, i.recipient_email, i.status,
                 u.display_name as creator_display_name
             FROM group_invites i
             JOIN users u ON i.created_by = u.id
@@ -151,7 +154,7 @@ impl InviteRepository {
         let result = sqlx::query(
             r#"
             UPDATE group_invites
-            SET is_active = false
+            SET is_active = false, status = 'revoked'
             WHERE id = $1 AND is_active = true
             "#,
         )
@@ -163,17 +166,51 @@ impl InviteRepository {
     }
 
     /// Increment use count for an invite (when someone joins).
-    pub async fn increment_use_count(&self, id: Uuid) -> Result<GroupInviteEntity, sqlx::Error> {
+    ///
+    /// The invite becomes accepted, by `user_id`, when its last use is taken.
+    pub async fn increment_use_count(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<GroupInviteEntity, sqlx::Error> {
         let timer = QueryTimer::new("increment_invite_use_count");
         let result = sqlx::query_as::<_, GroupInviteEntity>(
             r#"
             UPDATE group_invites
-            SET current_uses = current_uses + 1
+            SET current_uses = current_uses + 1,
+                status = CASE WHEN current_uses + 1 >= max_uses THEN 'accepted' ELSE status END,
+                accepted_by = CASE WHEN current_uses + 1 >= max_uses THEN $2 ELSE accepted_by END,
+                accepted_at = CASE WHEN current_uses + 1 >= max_uses THEN NOW() ELSE accepted_at END
             WHERE id = $1 AND is_active = true AND current_uses < max_uses
-            RETURNING id, group_id, code, preset_role, max_uses, current_uses, expires_at, created_by, created_at, is_active
+            RETURNING id, group_id, code, preset_role, max_uses, current_uses, expires_at, created_by, created_at, is_active, recipient_email, status
             "#,
         )
         .bind(id)
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Whether a group has an unexpired pending invite sent to `email`.
+    pub async fn has_pending_email_invite(
+        &self,
+        group_id: Uuid,
+        email: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let timer = QueryTimer::new("has_pending_group_email_invite");
+        let result = sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM group_invites
+                WHERE group_id = $1 AND recipient_email = $2
+                  AND status = 'pending' AND is_active = true AND expires_at > NOW()
+            )
+            "#,
+        )
+        .bind(group_id)
+        .bind(email)
         .fetch_one(&self.pool)
         .await;
         timer.record();
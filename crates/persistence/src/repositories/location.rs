@@ -6,6 +6,7 @@ use uuid::Uuid;
 
 use crate::entities::LocationEntity;
 use crate::metrics::QueryTimer;
+use crate::query_plans::LATEST_LOCATION;
 
 /// Input data for inserting a location record.
 #[derive(Debug, Clone)]
@@ -25,6 +26,12 @@ pub struct LocationInput {
     pub transportation_mode: Option<String>,
     pub detection_source: Option<String>,
     pub trip_id: Option<Uuid>,
+    /// Client sequence number, unique per device when set.
+    pub sequence_number: Option<i64>,
+    /// Where the fix came from, e.g. `gps`.
+    pub source: Option<String>,
+    /// Trust score (0-100) from the source and accuracy.
+    pub trust_score: Option<i16>,
 }
 
 /// Repository for location-related database operations.
@@ -45,22 +52,27 @@ impl LocationRepository {
     }
 
     /// Insert a single location record.
+    ///
+    /// Returns `None` if the device already stored a location with the same
+    /// sequence number and capture time.
     pub async fn insert_location(
         &self,
         input: LocationInput,
-    ) -> Result<LocationEntity, sqlx::Error> {
+    ) -> Result<Option<LocationEntity>, sqlx::Error> {
         let timer = QueryTimer::new("insert_location");
         let result = sqlx::query_as::<_, LocationEntity>(
             r#"
             INSERT INTO locations (
                 device_id, latitude, longitude, accuracy, altitude, bearing,
                 speed, provider, battery_level, network_type, captured_at,
-                transportation_mode, detection_source, trip_id
+                transportation_mode, detection_source, trip_id, sequence_number, source, trust_score
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
+            ON CONFLICT (device_id, sequence_number, captured_at) WHERE sequence_number IS NOT NULL
+            DO NOTHING
             RETURNING id, device_id, latitude, longitude, accuracy, altitude, bearing,
                       speed, provider, battery_level, network_type, captured_at, created_at,
-                      transportation_mode, detection_source, trip_id
+                      transportation_mode, detection_source, trip_id, source, trust_score
             "#,
         )
         .bind(input.device_id)
@@ -77,13 +89,20 @@ impl LocationRepository {
         .bind(&input.transportation_mode)
         .bind(&input.detection_source)
         .bind(input.trip_id)
-        .fetch_one(&self.pool)
+        .bind(input.sequence_number)
+        .bind(&input.source)
+        .bind(input.trust_score)
+        .fetch_optional(&self.pool)
         .await;
         timer.record();
         result
     }
 
     /// Insert multiple locations in a batch (within a transaction).
+    ///
+    /// Locations whose sequence number and capture time are already stored
+    /// for the device are skipped, so replayed batches do not create duplicates. Returns the
+    /// number of inserted rows.
     pub async fn insert_locations_batch(
         &self,
         device_id: Uuid,
@@ -91,17 +110,19 @@ impl LocationRepository {
     ) -> Result<usize, sqlx::Error> {
         let timer = QueryTimer::new("insert_locations_batch");
         let mut tx = self.pool.begin().await?;
-        let count = locations.len();
+        let mut inserted = 0;
 
         for loc in &locations {
-            sqlx::query(
+            let result = sqlx::query(
                 r#"
                 INSERT INTO locations (
                     device_id, latitude, longitude, accuracy, altitude, bearing,
                     speed, provider, battery_level, network_type, captured_at,
-                    transportation_mode, detection_source, trip_id
+                    transportation_mode, detection_source, trip_id, sequence_number, source, trust_score
                 )
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
+                ON CONFLICT (device_id, sequence_number, captured_at) WHERE sequence_number IS NOT NULL
+                DO NOTHING
                 "#,
             )
             .bind(device_id)
@@ -118,13 +139,70 @@ impl LocationRepository {
             .bind(&loc.transportation_mode) // transportation_mode
             .bind(&loc.detection_source) // detection_source
             .bind(loc.trip_id) // trip_id
+            .bind(loc.sequence_number) // sequence_number
+            .bind(&loc.source) // source
+            .bind(loc.trust_score) // trust_score
+            .execute(&mut *tx)
+            .await?;
+            inserted += result.rows_affected() as usize;
+        }
+
+        tx.commit().await?;
+        timer.record();
+        Ok(inserted)
+    }
+
+    /// Insert locations that are not yet stored, in a transaction.
+    ///
+    /// A location is considered stored if the device already has one with
+    /// the same `captured_at`. Returns the number of inserted rows.
+    pub async fn insert_locations_if_absent(
+        &self,
+        device_id: Uuid,
+        locations: &[LocationInput],
+    ) -> Result<usize, sqlx::Error> {
+        let timer = QueryTimer::new("insert_locations_if_absent");
+        let mut tx = self.pool.begin().await?;
+        let mut inserted = 0;
+
+        for loc in locations {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO locations (
+                    device_id, latitude, longitude, accuracy, altitude, bearing,
+                    speed, provider, battery_level, network_type, captured_at,
+                    transportation_mode, detection_source, trip_id, source, trust_score
+                )
+                SELECT $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16
+                WHERE NOT EXISTS (
+                    SELECT 1 FROM locations WHERE device_id = $1 AND captured_at = $11
+                )
+                "#,
+            )
+            .bind(device_id)
+            .bind(loc.latitude)
+            .bind(loc.longitude)
+            .bind(loc.accuracy as f32)
+            .bind(loc.altitude)
+            .bind(loc.bearing.map(|b| b as f32))
+            .bind(loc.speed.map(|s| s as f32))
+            .bind(&loc.provider)
+            .bind(loc.battery_level.map(|b| b as i16))
+            .bind(&loc.network_type)
+            .bind(loc.captured_at)
+            .bind(&loc.transportation_mode)
+            .bind(&loc.detection_source)
+            .bind(loc.trip_id)
+            .bind(&loc.source)
+            .bind(loc.trust_score)
             .execute(&mut *tx)
             .await?;
+            inserted += result.rows_affected() as usize;
         }
 
         tx.commit().await?;
         timer.record();
-        Ok(count)
+        Ok(inserted)
     }
 
     /// Delete locations older than specified retention days.
@@ -143,6 +221,47 @@ impl LocationRepository {
         Ok(result.rows_affected())
     }
 
+    /// Get the most recently captured location for a device.
+    pub async fn get_latest_location(
+        &self,
+        device_id: Uuid,
+    ) -> Result<Option<LocationEntity>, sqlx::Error> {
+        let timer = QueryTimer::new(LATEST_LOCATION.name);
+        let result = sqlx::query_as::<_, LocationEntity>(LATEST_LOCATION.sql)
+            .persistent(true)
+            .bind(device_id)
+            .fetch_optional(&self.pool)
+            .await;
+        timer.record();
+        result
+    }
+
+    /// Get the most recent location of a device captured before `before`.
+    pub async fn get_latest_location_before(
+        &self,
+        device_id: Uuid,
+        before: DateTime<Utc>,
+    ) -> Result<Option<LocationEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("get_latest_location_before");
+        let result = sqlx::query_as::<_, LocationEntity>(
+            r#"
+            SELECT id, device_id, latitude, longitude, accuracy, altitude, bearing,
+                   speed, provider, battery_level, network_type, captured_at, created_at,
+                   transportation_mode, detection_source, trip_id, source, trust_score
+            FROM locations
+            WHERE device_id = $1 AND captured_at < $2
+            ORDER BY captured_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(device_id)
+        .bind(before)
+        .fetch_optional(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
     /// Get all locations for a device (for data export).
     /// Returns locations sorted by captured_at in descending order.
     pub async fn get_all_locations_for_device(
@@ -153,7 +272,7 @@ impl LocationRepository {
             r#"
             SELECT id, device_id, latitude, longitude, accuracy, altitude, bearing,
                    speed, provider, battery_level, network_type, captured_at, created_at,
-                   transportation_mode, detection_source, trip_id
+                   transportation_mode, detection_source, trip_id, source, trust_score
             FROM locations
             WHERE device_id = $1
             ORDER BY captured_at DESC
@@ -230,12 +349,13 @@ impl LocationRepository {
             r#"
             SELECT id, device_id, latitude, longitude, accuracy, altitude, bearing,
                    speed, provider, battery_level, network_type, captured_at, created_at,
-                   transportation_mode, detection_source, trip_id
+                   transportation_mode, detection_source, trip_id, source, trust_score
             FROM locations
             WHERE device_id = $1
               AND ($2::timestamptz IS NULL OR captured_at >= $2)
               AND ($3::timestamptz IS NULL OR captured_at <= $3)
               AND ($4::timestamptz IS NULL OR (captured_at, id) < ($4, $5))
+              AND ($7::text IS NULL OR source = $7)
             ORDER BY captured_at DESC, id DESC
             LIMIT $6
             "#,
@@ -246,6 +366,7 @@ impl LocationRepository {
         .bind(query.cursor_timestamp)
         .bind(query.cursor_id.unwrap_or(i64::MAX))
         .bind(fetch_limit)
+        .bind(&query.source)
         .fetch_all(&self.pool)
         .await
     }
@@ -260,12 +381,13 @@ impl LocationRepository {
             r#"
             SELECT id, device_id, latitude, longitude, accuracy, altitude, bearing,
                    speed, provider, battery_level, network_type, captured_at, created_at,
-                   transportation_mode, detection_source, trip_id
+                   transportation_mode, detection_source, trip_id, source, trust_score
             FROM locations
             WHERE device_id = $1
               AND ($2::timestamptz IS NULL OR captured_at >= $2)
               AND ($3::timestamptz IS NULL OR captured_at <= $3)
               AND ($4::timestamptz IS NULL OR (captured_at, id) > ($4, $5))
+              AND ($7::text IS NULL OR source = $7)
             ORDER BY captured_at ASC, id ASC
             LIMIT $6
             "#,
@@ -276,6 +398,7 @@ impl LocationRepository {
         .bind(query.cursor_timestamp)
         .bind(query.cursor_id.unwrap_or(i64::MIN))
         .bind(fetch_limit)
+        .bind(&query.source)
         .fetch_all(&self.pool)
         .await
     }
@@ -301,7 +424,7 @@ impl LocationRepository {
             r#"
             SELECT id, device_id, latitude, longitude, accuracy, altitude, bearing,
                    speed, provider, battery_level, network_type, captured_at, created_at,
-                   transportation_mode, detection_source, trip_id
+                   transportation_mode, detection_source, trip_id, source, trust_score
             FROM locations
             WHERE trip_id = $1
             ORDER BY captured_at ASC, id ASC
@@ -340,7 +463,7 @@ impl LocationRepository {
             r#"
             SELECT id, device_id, latitude, longitude, accuracy, altitude, bearing,
                    speed, provider, battery_level, network_type, captured_at, created_at,
-                   transportation_mode, detection_source, trip_id
+                   transportation_mode, detection_source, trip_id, source, trust_score
             FROM locations
             WHERE device_id = $1
               AND ($2::timestamptz IS NULL OR captured_at >= $2)
@@ -376,6 +499,8 @@ pub struct LocationHistoryQuery {
     pub limit: i32,
     /// Whether to sort in ascending order.
     pub ascending: bool,
+    /// Only return locations from this source.
+    pub source: Option<String>,
 }
 
 #[cfg(test)]
@@ -404,6 +529,9 @@ mod tests {
             transportation_mode: None,
             detection_source: None,
             trip_id: None,
+            sequence_number: None,
+            source: None,
+            trust_score: None,
         };
 
         assert!(input.latitude > 0.0);
@@ -429,6 +557,9 @@ mod tests {
             transportation_mode: None,
             detection_source: None,
             trip_id: None,
+            sequence_number: None,
+            source: None,
+            trust_score: None,
         };
 
         assert!(input.altitude.is_none());
@@ -454,6 +585,9 @@ mod tests {
             transportation_mode: None,
             detection_source: None,
             trip_id: None,
+            sequence_number: None,
+            source: None,
+            trust_score: None,
         };
 
         assert_eq!(input.latitude, 90.0);
@@ -477,6 +611,9 @@ mod tests {
             transportation_mode: None,
             detection_source: None,
             trip_id: None,
+            sequence_number: None,
+            source: None,
+            trust_score: None,
         };
 
         assert_eq!(input.latitude, -90.0);
@@ -501,6 +638,9 @@ mod tests {
             transportation_mode: None,
             detection_source: None,
             trip_id: None,
+            sequence_number: None,
+            source: None,
+            trust_score: None,
         };
 
         let cloned = input.clone();
@@ -526,6 +666,9 @@ mod tests {
             transportation_mode: None,
             detection_source: None,
             trip_id: None,
+            sequence_number: None,
+            source: None,
+            trust_score: None,
         };
 
         let debug = format!("{:?}", input);
@@ -552,6 +695,9 @@ mod tests {
             transportation_mode: None,
             detection_source: None,
             trip_id: None,
+            sequence_number: None,
+            source: None,
+            trust_score: None,
         };
         assert_eq!(input_low.battery_level, Some(0));
 
@@ -570,6 +716,9 @@ mod tests {
             transportation_mode: None,
             detection_source: None,
             trip_id: None,
+            sequence_number: None,
+            source: None,
+            trust_score: None,
         };
         assert_eq!(input_high.battery_level, Some(100));
     }
@@ -592,6 +741,9 @@ mod tests {
             transportation_mode: None,
             detection_source: None,
             trip_id: None,
+            sequence_number: None,
+            source: None,
+            trust_score: None,
         };
         assert_eq!(input_zero.bearing, Some(0.0));
 
@@ -610,6 +762,9 @@ mod tests {
             transportation_mode: None,
             detection_source: None,
             trip_id: None,
+            sequence_number: None,
+            source: None,
+            trust_score: None,
         };
         assert!(input_max.bearing.unwrap() < 360.0);
     }
@@ -628,6 +783,7 @@ mod tests {
             to_timestamp: None,
             limit: 50,
             ascending: false,
+            source: None,
         };
 
         assert_eq!(query.limit, 50);
@@ -648,6 +804,7 @@ mod tests {
             to_timestamp: Some(to),
             limit: 100,
             ascending: true,
+            source: None,
         };
 
         assert!(query.from_timestamp.is_some());
@@ -667,6 +824,7 @@ mod tests {
             to_timestamp: None,
             limit: 25,
             ascending: false,
+            source: None,
         };
 
         assert_eq!(query.cursor_id, Some(12345));
@@ -683,6 +841,7 @@ mod tests {
             to_timestamp: None,
             limit: 50,
             ascending: true,
+            source: None,
         };
 
         let cloned = query.clone();
@@ -701,6 +860,7 @@ mod tests {
             to_timestamp: None,
             limit: 50,
             ascending: false,
+            source: None,
         };
 
         let debug = format!("{:?}", query);
@@ -722,6 +882,7 @@ mod tests {
                 to_timestamp: None,
                 limit,
                 ascending: false,
+                source: None,
             };
             assert_eq!(query.limit, limit);
         }
@@ -739,6 +900,7 @@ mod tests {
             to_timestamp: None,
             limit: 50,
             ascending: true,
+            source: None,
         };
 
         let desc_query = LocationHistoryQuery {
@@ -749,6 +911,7 @@ mod tests {
             to_timestamp: None,
             limit: 50,
             ascending: false,
+            source: None,
         };
 
         assert!(asc_query.ascending);
@@ -782,6 +945,9 @@ mod tests {
                 transportation_mode: None,
                 detection_source: None,
                 trip_id: None,
+                sequence_number: None,
+                source: None,
+                trust_score: None,
             };
             assert_eq!(input.provider, Some(provider.to_string()));
         }
@@ -807,6 +973,9 @@ mod tests {
                 transportation_mode: None,
                 detection_source: None,
                 trip_id: None,
+                sequence_number: None,
+                source: None,
+                trust_score: None,
             };
             assert_eq!(input.network_type, Some(network_type.to_string()));
         }
@@ -833,6 +1002,9 @@ mod tests {
                 transportation_mode: None,
                 detection_source: None,
                 trip_id: None,
+                sequence_number: None,
+                source: None,
+                trust_score: None,
             };
             assert_eq!(input.speed, Some(speed));
         }
@@ -859,6 +1031,9 @@ mod tests {
                 transportation_mode: None,
                 detection_source: None,
                 trip_id: None,
+                sequence_number: None,
+                source: None,
+                trust_score: None,
             };
             assert_eq!(input.accuracy, accuracy);
         }
@@ -885,6 +1060,9 @@ mod tests {
                 transportation_mode: None,
                 detection_source: None,
                 trip_id: None,
+                sequence_number: None,
+                source: None,
+                trust_score: None,
             };
             assert_eq!(input.altitude, Some(altitude));
         }
@@ -0,0 +1,195 @@
+//! Location import job repository for database operations.
+
+use domain::models::LocationImportJob;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::entities::LocationImportJobEntity;
+use crate::metrics::QueryTimer;
+
+/// Record counts reported while an import runs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LocationImportProgress {
+    pub total: i32,
+    pub imported: i32,
+    pub duplicates: i32,
+    pub invalid: i32,
+}
+
+/// Repository for location import job database operations.
+#[derive(Clone)]
+pub struct LocationImportJobRepository {
+    pool: PgPool,
+}
+
+impl LocationImportJobRepository {
+    /// Creates a new LocationImportJobRepository with the given connection pool.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Create a pending import job for an uploaded file.
+    pub async fn create(
+        &self,
+        id: Uuid,
+        device_id: Uuid,
+        created_by: Uuid,
+        source: &str,
+        file_path: &str,
+    ) -> Result<LocationImportJob, sqlx::Error> {
+        let timer = QueryTimer::new("create_location_import_job");
+        let entity = sqlx::query_as::<_, LocationImportJobEntity>(
+            r#"
+            INSERT INTO location_import_jobs (id, device_id, created_by, source, file_path)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, device_id, created_by, source, status, file_path, total_records,
+                      imported_records, duplicate_records, invalid_records, error_message,
+                      started_at, completed_at, created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(device_id)
+        .bind(created_by)
+        .bind(source)
+        .bind(file_path)
+        .fetch_one(&self.pool)
+        .await?;
+        timer.record();
+
+        Ok(entity.into())
+    }
+
+    /// Find an import job of a device.
+    pub async fn find_for_device(
+        &self,
+        device_id: Uuid,
+        job_id: Uuid,
+    ) -> Result<Option<LocationImportJob>, sqlx::Error> {
+        let timer = QueryTimer::new("find_location_import_job");
+        let entity = sqlx::query_as::<_, LocationImportJobEntity>(
+            r#"
+            SELECT id, device_id, created_by, source, status, file_path, total_records,
+                   imported_records, duplicate_records, invalid_records, error_message,
+                   started_at, completed_at, created_at, updated_at
+            FROM location_import_jobs
+            WHERE id = $1 AND device_id = $2
+            "#,
+        )
+        .bind(job_id)
+        .bind(device_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        timer.record();
+
+        Ok(entity.map(Into::into))
+    }
+
+    /// Oldest pending jobs, up to `batch_size`.
+    pub async fn find_pending(
+        &self,
+        batch_size: i64,
+    ) -> Result<Vec<LocationImportJobEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("find_pending_location_import_jobs");
+        let entities = sqlx::query_as::<_, LocationImportJobEntity>(
+            r#"
+            SELECT id, device_id, created_by, source, status, file_path, total_records,
+                   imported_records, duplicate_records, invalid_records, error_message,
+                   started_at, completed_at, created_at, updated_at
+            FROM location_import_jobs
+            WHERE status = 'pending'
+            ORDER BY created_at ASC
+            LIMIT $1
+            "#,
+        )
+        .bind(batch_size)
+        .fetch_all(&self.pool)
+        .await?;
+        timer.record();
+
+        Ok(entities)
+    }
+
+    /// Claim a pending job. Returns false if another worker claimed it first.
+    pub async fn mark_processing(&self, job_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE location_import_jobs
+            SET status = 'processing', started_at = NOW(), updated_at = NOW()
+            WHERE id = $1 AND status = 'pending'
+            "#,
+        )
+        .bind(job_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Record progress of a running job.
+    pub async fn update_progress(
+        &self,
+        job_id: Uuid,
+        progress: LocationImportProgress,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE location_import_jobs
+            SET total_records = $2, imported_records = $3, duplicate_records = $4,
+                invalid_records = $5, updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(job_id)
+        .bind(progress.total)
+        .bind(progress.imported)
+        .bind(progress.duplicates)
+        .bind(progress.invalid)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Mark a job as completed with its final counts.
+    pub async fn mark_completed(
+        &self,
+        job_id: Uuid,
+        progress: LocationImportProgress,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE location_import_jobs
+            SET status = 'completed', total_records = $2, imported_records = $3,
+                duplicate_records = $4, invalid_records = $5,
+                completed_at = NOW(), updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(job_id)
+        .bind(progress.total)
+        .bind(progress.imported)
+        .bind(progress.duplicates)
+        .bind(progress.invalid)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Mark a job as failed.
+    pub async fn mark_failed(&self, job_id: Uuid, error_message: &str) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE location_import_jobs
+            SET status = 'failed', error_message = $2, completed_at = NOW(), updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(job_id)
+        .bind(error_message)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
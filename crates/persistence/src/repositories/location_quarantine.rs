@@ -0,0 +1,97 @@
+//! Location quarantine repository for database operations.
+
+use chrono::{DateTime, Utc};
+use domain::services::QuarantineReason;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::metrics::QueryTimer;
+use crate::repositories::LocationInput;
+
+/// An uploaded location rejected by filtering, with the reason.
+#[derive(Debug, Clone)]
+pub struct QuarantinedLocation {
+    pub location: LocationInput,
+    pub reason: QuarantineReason,
+}
+
+/// Repository for quarantined location database operations.
+#[derive(Clone)]
+pub struct LocationQuarantineRepository {
+    pool: PgPool,
+}
+
+impl LocationQuarantineRepository {
+    /// Creates a new LocationQuarantineRepository with the given connection pool.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Store rejected locations for a device in a transaction.
+    pub async fn insert_batch(
+        &self,
+        device_id: Uuid,
+        entries: &[QuarantinedLocation],
+    ) -> Result<usize, sqlx::Error> {
+        if entries.is_empty() {
+            return Ok(0);
+        }
+
+        let timer = QueryTimer::new("insert_location_quarantine_batch");
+        let mut tx = self.pool.begin().await?;
+
+        for entry in entries {
+            let loc = &entry.location;
+            sqlx::query(
+                r#"
+                INSERT INTO location_quarantine (
+                    device_id, latitude, longitude, accuracy, altitude, bearing,
+                    speed, provider, captured_at, reason
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                "#,
+            )
+            .bind(device_id)
+            .bind(loc.latitude)
+            .bind(loc.longitude)
+            .bind(loc.accuracy as f32)
+            .bind(loc.altitude)
+            .bind(loc.bearing.map(|b| b as f32))
+            .bind(loc.speed.map(|s| s as f32))
+            .bind(&loc.provider)
+            .bind(loc.captured_at)
+            .bind(entry.reason.as_str())
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        timer.record();
+        Ok(entries.len())
+    }
+
+    /// Count a device's locations quarantined for `reason` that were
+    /// captured after `after`.
+    pub async fn count_captured_after(
+        &self,
+        device_id: Uuid,
+        reason: QuarantineReason,
+        after: DateTime<Utc>,
+    ) -> Result<i64, sqlx::Error> {
+        let timer = QueryTimer::new("count_location_quarantine_captured_after");
+        let count: (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*)
+            FROM location_quarantine
+            WHERE device_id = $1 AND reason = $2 AND captured_at > $3
+            "#,
+        )
+        .bind(device_id)
+        .bind(reason.as_str())
+        .bind(after)
+        .fetch_one(&self.pool)
+        .await?;
+        timer.record();
+        Ok(count.0)
+    }
+}
@@ -0,0 +1,114 @@
+//! Location sharing schedule repository for database operations.
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::entities::LocationSharingScheduleEntity;
+use crate::metrics::QueryTimer;
+
+/// Repository for location sharing schedule database operations.
+#[derive(Clone)]
+pub struct LocationSharingScheduleRepository {
+    pool: PgPool,
+}
+
+impl LocationSharingScheduleRepository {
+    /// Creates a new LocationSharingScheduleRepository with the given connection pool.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Set a member's schedule for a group, replacing any previous one.
+    pub async fn upsert(
+        &self,
+        user_id: Uuid,
+        group_id: Uuid,
+        schedule: &serde_json::Value,
+    ) -> Result<LocationSharingScheduleEntity, sqlx::Error> {
+        let timer = QueryTimer::new("upsert_location_sharing_schedule");
+
+        let result = sqlx::query_as::<_, LocationSharingScheduleEntity>(
+            r#"
+            INSERT INTO location_sharing_schedules (user_id, group_id, schedule)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id, group_id) DO UPDATE
+            SET schedule = EXCLUDED.schedule,
+                updated_at = NOW()
+            RETURNING *
+            "#,
+        )
+        .bind(user_id)
+        .bind(group_id)
+        .bind(schedule)
+        .fetch_one(&self.pool)
+        .await;
+
+        timer.record();
+        result
+    }
+
+    /// Get a member's schedule for a group.
+    pub async fn find(
+        &self,
+        user_id: Uuid,
+        group_id: Uuid,
+    ) -> Result<Option<LocationSharingScheduleEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("find_location_sharing_schedule");
+
+        let result = sqlx::query_as::<_, LocationSharingScheduleEntity>(
+            "SELECT * FROM location_sharing_schedules WHERE user_id = $1 AND group_id = $2",
+        )
+        .bind(user_id)
+        .bind(group_id)
+        .fetch_optional(&self.pool)
+        .await;
+
+        timer.record();
+        result
+    }
+
+    /// Schedules of the given members for a group.
+    pub async fn list_for_users(
+        &self,
+        group_id: Uuid,
+        user_ids: &[Uuid],
+    ) -> Result<Vec<LocationSharingScheduleEntity>, sqlx::Error> {
+        if user_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let timer = QueryTimer::new("list_location_sharing_schedules");
+
+        let result = sqlx::query_as::<_, LocationSharingScheduleEntity>(
+            r#"
+            SELECT * FROM location_sharing_schedules
+            WHERE group_id = $1 AND user_id = ANY($2)
+            "#,
+        )
+        .bind(group_id)
+        .bind(user_ids)
+        .fetch_all(&self.pool)
+        .await;
+
+        timer.record();
+        result
+    }
+
+    /// Remove a member's schedule for a group.
+    ///
+    /// Returns false if the member had none.
+    pub async fn delete(&self, user_id: Uuid, group_id: Uuid) -> Result<bool, sqlx::Error> {
+        let timer = QueryTimer::new("delete_location_sharing_schedule");
+
+        let result = sqlx::query(
+            "DELETE FROM location_sharing_schedules WHERE user_id = $1 AND group_id = $2",
+        )
+        .bind(user_id)
+        .bind(group_id)
+        .execute(&self.pool)
+        .await;
+
+        timer.record();
+        Ok(result?.rows_affected() > 0)
+    }
+}
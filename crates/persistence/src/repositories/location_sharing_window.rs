@@ -0,0 +1,157 @@
+//! Location sharing window repository for database operations.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::entities::LocationSharingWindowEntity;
+use crate::metrics::QueryTimer;
+
+/// Repository for location sharing window database operations.
+#[derive(Clone)]
+pub struct LocationSharingWindowRepository {
+    pool: PgPool,
+}
+
+impl LocationSharingWindowRepository {
+    /// Creates a new LocationSharingWindowRepository with the given connection pool.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Open a sharing window, replacing the member's previous one.
+    pub async fn start(
+        &self,
+        user_id: Uuid,
+        group_id: Uuid,
+        expires_at: DateTime<Utc>,
+    ) -> Result<LocationSharingWindowEntity, sqlx::Error> {
+        let timer = QueryTimer::new("start_location_sharing_window");
+
+        let result = sqlx::query_as::<_, LocationSharingWindowEntity>(
+            r#"
+            INSERT INTO location_sharing_windows (user_id, group_id, expires_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id, group_id) DO UPDATE
+            SET started_at = NOW(),
+                expires_at = EXCLUDED.expires_at,
+                stopped_at = NULL,
+                updated_at = NOW()
+            RETURNING *
+            "#,
+        )
+        .bind(user_id)
+        .bind(group_id)
+        .bind(expires_at)
+        .fetch_one(&self.pool)
+        .await;
+
+        timer.record();
+        result
+    }
+
+    /// Get a member's sharing window in a group, open or not.
+    pub async fn find(
+        &self,
+        user_id: Uuid,
+        group_id: Uuid,
+    ) -> Result<Option<LocationSharingWindowEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("find_location_sharing_window");
+
+        let result = sqlx::query_as::<_, LocationSharingWindowEntity>(
+            "SELECT * FROM location_sharing_windows WHERE user_id = $1 AND group_id = $2",
+        )
+        .bind(user_id)
+        .bind(group_id)
+        .fetch_optional(&self.pool)
+        .await;
+
+        timer.record();
+        result
+    }
+
+    /// Move the expiry of an open window.
+    ///
+    /// Returns None if the member has no open window.
+    pub async fn extend(
+        &self,
+        user_id: Uuid,
+        group_id: Uuid,
+        expires_at: DateTime<Utc>,
+    ) -> Result<Option<LocationSharingWindowEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("extend_location_sharing_window");
+
+        let result = sqlx::query_as::<_, LocationSharingWindowEntity>(
+            r#"
+            UPDATE location_sharing_windows
+            SET expires_at = $3, updated_at = NOW()
+            WHERE user_id = $1 AND group_id = $2
+              AND stopped_at IS NULL AND expires_at > NOW()
+            RETURNING *
+            "#,
+        )
+        .bind(user_id)
+        .bind(group_id)
+        .bind(expires_at)
+        .fetch_optional(&self.pool)
+        .await;
+
+        timer.record();
+        result
+    }
+
+    /// Close an open window early.
+    ///
+    /// Returns None if the member has no open window.
+    pub async fn stop(
+        &self,
+        user_id: Uuid,
+        group_id: Uuid,
+    ) -> Result<Option<LocationSharingWindowEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("stop_location_sharing_window");
+
+        let result = sqlx::query_as::<_, LocationSharingWindowEntity>(
+            r#"
+            UPDATE location_sharing_windows
+            SET stopped_at = NOW(), updated_at = NOW()
+            WHERE user_id = $1 AND group_id = $2
+              AND stopped_at IS NULL AND expires_at > NOW()
+            RETURNING *
+            "#,
+        )
+        .bind(user_id)
+        .bind(group_id)
+        .fetch_optional(&self.pool)
+        .await;
+
+        timer.record();
+        result
+    }
+
+    /// Those of `user_ids` with an open window in the group.
+    pub async fn active_users(
+        &self,
+        group_id: Uuid,
+        user_ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        if user_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let timer = QueryTimer::new("active_location_sharing_users");
+
+        let result = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT user_id FROM location_sharing_windows
+            WHERE group_id = $1 AND user_id = ANY($2)
+              AND stopped_at IS NULL AND expires_at > NOW()
+            "#,
+        )
+        .bind(group_id)
+        .bind(user_ids)
+        .fetch_all(&self.pool)
+        .await;
+
+        timer.record();
+        result
+    }
+}
@@ -49,7 +49,7 @@ impl ManagedUserRepository {
                         l.accuracy as last_accuracy,
                         l.captured_at as last_captured_at
                     FROM devices d
-                    INNER JOIN locations l ON l.device_id = d.device_id
+                    INNER JOIN device_latest_locations l ON l.device_id = d.device_id
                     WHERE d.active = true
                     ORDER BY d.owner_user_id, l.captured_at DESC
                 )
@@ -101,7 +101,7 @@ impl ManagedUserRepository {
                         l.accuracy as last_accuracy,
                         l.captured_at as last_captured_at
                     FROM devices d
-                    INNER JOIN locations l ON l.device_id = d.device_id
+                    INNER JOIN device_latest_locations l ON l.device_id = d.device_id
                     WHERE d.active = true
                     ORDER BY d.owner_user_id, l.captured_at DESC
                 ),
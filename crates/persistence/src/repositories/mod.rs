@@ -6,70 +6,118 @@ pub mod admin_user;
 pub mod analytics;
 pub mod api_key;
 pub mod app_usage;
-pub mod audit_export_job;
+pub mod audit_export_cursor;
 pub mod audit_log;
+pub mod commute;
 pub mod dashboard;
 pub mod data_subject_request;
 pub mod device;
+pub mod device_api_usage;
 pub mod device_command;
+pub mod device_command_macro;
+pub mod device_config_history;
 pub mod device_group_membership;
 pub mod device_policy;
+pub mod device_push_token;
+pub mod device_telemetry;
 pub mod device_token;
 pub mod enrollment_token;
+pub mod event_outbox;
+pub mod export_job;
 pub mod geofence;
+pub mod geofence_arrival_forecast;
 pub mod geofence_event;
+pub mod geofence_template;
 pub mod group;
+pub mod group_api_token;
+pub mod group_custom_role;
+pub mod group_event;
+pub mod group_message;
+pub mod group_notification_preferences;
 pub mod idempotency_key;
 pub mod invite;
 pub mod location;
+pub mod location_import_job;
+pub mod location_quarantine;
+pub mod location_sharing_schedule;
+pub mod location_sharing_window;
 pub mod managed_user;
 pub mod migration_audit;
 pub mod movement_event;
 pub mod org_member_invite;
+pub mod org_ownership_transfer;
 pub mod org_user;
 pub mod org_webhook;
 pub mod organization;
 pub mod organization_role;
 pub mod organization_settings;
+pub mod privacy_zone;
 pub mod proximity_alert;
 pub mod registration_invite;
+pub mod report_template;
 pub mod setting;
 pub mod setting_change;
 pub mod system_config;
 pub mod system_role;
 pub mod trip;
+pub mod trip_edit;
 pub mod trip_path_correction;
+pub mod trip_rule;
+pub mod trip_share_link;
+pub mod trip_speed_violation;
 pub mod unlock_request;
 pub mod user;
 pub mod user_geofence;
 pub mod webhook;
+pub mod webhook_batch;
 pub mod webhook_delivery;
 
 pub use admin_geofence::AdminGeofenceRepository;
 pub use admin_group::AdminGroupRepository;
 pub use admin_user::AdminUserRepository;
-pub use analytics::AnalyticsRepository;
+pub use analytics::{AnalyticsRepository, ApiUsageRollupInput};
 pub use api_key::ApiKeyRepository;
 pub use app_usage::AppUsageRepository;
-pub use audit_export_job::{AuditExportJobRepository, ExportJob};
+pub use audit_export_cursor::AuditExportCursorRepository;
 pub use audit_log::AuditLogRepository;
+pub use commute::{CommuteInput, CommuteRepository};
 pub use dashboard::DashboardRepository;
 pub use data_subject_request::{
     CreateDataSubjectRequestInput, DataSubjectRequestCounts, DataSubjectRequestRepository,
     ListDataSubjectRequestsQuery, ProcessDataSubjectRequestInput, DEFAULT_DUE_DAYS,
 };
 pub use device::{AdminStats, DeviceRepository, FleetSummaryCounts, RegistrationGroupDevice};
+pub use device_api_usage::DeviceApiUsageRepository;
 pub use device_command::DeviceCommandRepository;
+pub use device_command_macro::{DeviceCommandMacroRepository, MacroRunStep};
+pub use device_config_history::DeviceConfigHistoryRepository;
 pub use device_group_membership::DeviceGroupMembershipRepository;
 pub use device_policy::DevicePolicyRepository;
+pub use device_push_token::DevicePushTokenRepository;
+pub use device_telemetry::{DeviceTelemetryRepository, TelemetryInput};
 pub use device_token::DeviceTokenRepository;
 pub use enrollment_token::EnrollmentTokenRepository;
+pub use event_outbox::EventOutboxRepository;
+pub use export_job::{ExportJob, ExportJobRepository};
 pub use geofence::GeofenceRepository;
+pub use geofence_arrival_forecast::GeofenceArrivalForecastRepository;
 pub use geofence_event::GeofenceEventRepository;
-pub use group::GroupRepository;
+pub use geofence_template::{
+    GeofenceTemplateFields, GeofenceTemplateRepository, TemplateApplyOutcome,
+};
+pub use group::{GroupMergeOutcome, GroupRepository, SetParentOutcome};
+pub use group_api_token::{CreateGroupApiTokenInput, GroupApiTokenRepository};
+pub use group_custom_role::{GroupCustomRoleFields, GroupCustomRoleRepository};
+pub use group_event::GroupEventRepository;
+pub use group_message::{GroupMessageRepository, NewGroupMessage};
+pub use group_notification_preferences::GroupNotificationPreferencesRepository;
 pub use idempotency_key::IdempotencyKeyRepository;
 pub use invite::InviteRepository;
 pub use location::{LocationHistoryQuery, LocationInput, LocationRepository};
+pub use location_import_job::{LocationImportJobRepository, LocationImportProgress};
+pub use location_quarantine::{LocationQuarantineRepository, QuarantinedLocation};
+pub use location_sharing_schedule::LocationSharingScheduleRepository;
+pub use location_sharing_window::LocationSharingWindowRepository;
 pub use managed_user::ManagedUserRepository;
 pub use migration_audit::{
     CreateMigrationAuditInput, ListMigrationAuditQuery, MigrationAuditRepository,
@@ -79,25 +127,35 @@ pub use org_member_invite::{
     calculate_invite_expiration, default_invite_expiration, generate_org_member_invite_token,
     InviteSummaryCounts, OrgMemberInviteRepository,
 };
+pub use org_ownership_transfer::OrgOwnershipTransferRepository;
 pub use org_user::OrgUserRepository;
 pub use org_webhook::OrgWebhookRepository;
 pub use organization::OrganizationRepository;
 pub use organization_role::OrganizationRoleRepository;
 pub use organization_settings::OrganizationSettingsRepository;
+pub use privacy_zone::PrivacyZoneRepository;
 pub use proximity_alert::ProximityAlertRepository;
 pub use registration_invite::{
     default_expiration, generate_invite_token, RegistrationInviteRepository,
 };
+pub use report_template::ReportTemplateRepository;
 pub use setting::SettingRepository;
 pub use setting_change::{CreateSettingChangeInput, SettingChangeRepository};
 pub use system_config::SystemConfigRepository;
 pub use system_role::SystemRoleRepository;
 pub use trip::{TripInput, TripQuery, TripRepository, TripUpdateInput};
+pub use trip_edit::{TripEditRepository, TripMergeInput, TripSplitInput};
 pub use trip_path_correction::{
     TripPathCorrectionInput, TripPathCorrectionRepository, TripPathCorrectionUpdateInput,
 };
+pub use trip_rule::{TripRuleInput, TripRuleRepository};
+pub use trip_share_link::TripShareLinkRepository;
+pub use trip_speed_violation::TripSpeedViolationRepository;
 pub use unlock_request::UnlockRequestRepository;
-pub use user::{MfaStatusRow, UserRepository, UserSessionRow};
+pub use user::{
+    MfaStatusRow, PasswordHashSchemeRow, PasswordSchemeUserRow, UserRepository, UserSessionRow,
+};
 pub use user_geofence::UserGeofenceRepository;
 pub use webhook::WebhookRepository;
+pub use webhook_batch::WebhookBatchRepository;
 pub use webhook_delivery::{DeliveryStats, WebhookDeliveryRepository, WebhookDeliveryStats};
@@ -21,6 +21,8 @@ pub struct MovementEventInput {
     pub transportation_mode: String,
     pub confidence: f64,
     pub detection_source: String,
+    /// ARRIVED or DEPARTED for server-derived events.
+    pub movement_state: Option<String>,
 }
 
 /// Repository for movement event database operations.
@@ -53,19 +55,19 @@ impl MovementEventRepository {
             r#"
             INSERT INTO movement_events (
                 device_id, trip_id, timestamp, location, accuracy, speed, bearing,
-                altitude, transportation_mode, confidence, detection_source
+                altitude, transportation_mode, confidence, detection_source, movement_state
             )
             VALUES (
                 $1, $2, $3,
                 ST_SetSRID(ST_MakePoint($4, $5), 4326)::geography,
-                $6, $7, $8, $9, $10, $11, $12
+                $6, $7, $8, $9, $10, $11, $12, $13
             )
             RETURNING
                 id, device_id, trip_id, timestamp,
                 ST_Y(location::geometry) as latitude,
                 ST_X(location::geometry) as longitude,
                 accuracy, speed, bearing, altitude,
-                transportation_mode, confidence, detection_source, created_at
+                transportation_mode, confidence, detection_source, movement_state, created_at
             "#,
         )
         .bind(input.device_id)
@@ -80,6 +82,7 @@ impl MovementEventRepository {
         .bind(&input.transportation_mode)
         .bind(input.confidence as f32)
         .bind(&input.detection_source)
+        .bind(&input.movement_state)
         .fetch_one(&self.pool)
         .await;
 
@@ -101,12 +104,12 @@ impl MovementEventRepository {
                 r#"
                 INSERT INTO movement_events (
                     device_id, trip_id, timestamp, location, accuracy, speed, bearing,
-                    altitude, transportation_mode, confidence, detection_source
+                    altitude, transportation_mode, confidence, detection_source, movement_state
                 )
                 VALUES (
                     $1, $2, $3,
                     ST_SetSRID(ST_MakePoint($4, $5), 4326)::geography,
-                    $6, $7, $8, $9, $10, $11, $12
+                    $6, $7, $8, $9, $10, $11, $12, $13
                 )
                 "#,
             )
@@ -122,6 +125,7 @@ impl MovementEventRepository {
             .bind(&event.transportation_mode)
             .bind(event.confidence as f32)
             .bind(&event.detection_source)
+            .bind(&event.movement_state)
             .execute(&mut *tx)
             .await?;
         }
@@ -142,7 +146,7 @@ impl MovementEventRepository {
                 ST_Y(location::geometry) as latitude,
                 ST_X(location::geometry) as longitude,
                 accuracy, speed, bearing, altitude,
-                transportation_mode, confidence, detection_source, created_at
+                transportation_mode, confidence, detection_source, movement_state, created_at
             FROM movement_events
             WHERE id = $1
             "#,
@@ -198,7 +202,7 @@ impl MovementEventRepository {
                 ST_Y(location::geometry) as latitude,
                 ST_X(location::geometry) as longitude,
                 accuracy, speed, bearing, altitude,
-                transportation_mode, confidence, detection_source, created_at
+                transportation_mode, confidence, detection_source, movement_state, created_at
             FROM movement_events
             WHERE device_id = $1
               AND ($2::bigint IS NULL OR timestamp >= $2)
@@ -236,7 +240,7 @@ impl MovementEventRepository {
                 ST_Y(location::geometry) as latitude,
                 ST_X(location::geometry) as longitude,
                 accuracy, speed, bearing, altitude,
-                transportation_mode, confidence, detection_source, created_at
+                transportation_mode, confidence, detection_source, movement_state, created_at
             FROM movement_events
             WHERE device_id = $1
               AND ($2::bigint IS NULL OR timestamp >= $2)
@@ -256,6 +260,35 @@ impl MovementEventRepository {
         .await
     }
 
+    /// Latest server-derived movement transition for a device.
+    pub async fn get_latest_transition(
+        &self,
+        device_id: Uuid,
+    ) -> Result<Option<MovementEventEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("get_latest_movement_transition");
+
+        let result = sqlx::query_as::<_, MovementEventEntity>(
+            r#"
+            SELECT
+                id, device_id, trip_id, timestamp,
+                ST_Y(location::geometry) as latitude,
+                ST_X(location::geometry) as longitude,
+                accuracy, speed, bearing, altitude,
+                transportation_mode, confidence, detection_source, movement_state, created_at
+            FROM movement_events
+            WHERE device_id = $1 AND movement_state IS NOT NULL
+            ORDER BY timestamp DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(device_id)
+        .fetch_optional(&self.pool)
+        .await;
+
+        timer.record();
+        result
+    }
+
     /// Delete all movement events for a device.
     /// Returns the number of deleted records.
     pub async fn delete_all_for_device(&self, device_id: Uuid) -> Result<u64, sqlx::Error> {
@@ -287,7 +320,7 @@ impl MovementEventRepository {
                 ST_Y(location::geometry) as latitude,
                 ST_X(location::geometry) as longitude,
                 accuracy, speed, bearing, altitude,
-                transportation_mode, confidence, detection_source, created_at
+                transportation_mode, confidence, detection_source, movement_state, created_at
             FROM movement_events
             WHERE trip_id = $1
             ORDER BY timestamp ASC, id ASC
@@ -318,7 +351,7 @@ impl MovementEventRepository {
                     ST_Y(location::geometry) as latitude,
                     ST_X(location::geometry) as longitude,
                     accuracy, speed, bearing, altitude,
-                    transportation_mode, confidence, detection_source, created_at
+                    transportation_mode, confidence, detection_source, movement_state, created_at
                 FROM movement_events
                 WHERE trip_id = $1
                 ORDER BY timestamp ASC, id ASC
@@ -335,7 +368,7 @@ impl MovementEventRepository {
                     ST_Y(location::geometry) as latitude,
                     ST_X(location::geometry) as longitude,
                     accuracy, speed, bearing, altitude,
-                    transportation_mode, confidence, detection_source, created_at
+                    transportation_mode, confidence, detection_source, movement_state, created_at
                 FROM movement_events
                 WHERE trip_id = $1
                 ORDER BY timestamp DESC, id DESC
@@ -416,6 +449,7 @@ mod tests {
             transportation_mode: "WALKING".to_string(),
             confidence: 0.95,
             detection_source: "ACTIVITY_RECOGNITION".to_string(),
+            movement_state: None,
         };
 
         assert!(input.latitude > 0.0);
@@ -439,6 +473,7 @@ mod tests {
             transportation_mode: "STATIONARY".to_string(),
             confidence: 0.0,
             detection_source: "NONE".to_string(),
+            movement_state: None,
         };
 
         assert!(input.trip_id.is_none());
@@ -497,6 +532,7 @@ mod tests {
             transportation_mode: "WALKING".to_string(),
             confidence: 0.95,
             detection_source: "ACTIVITY_RECOGNITION".to_string(),
+            movement_state: None,
         };
 
         let cloned = input.clone();
@@ -520,6 +556,7 @@ mod tests {
             transportation_mode: "WALKING".to_string(),
             confidence: 0.95,
             detection_source: "ACTIVITY_RECOGNITION".to_string(),
+            movement_state: None,
         };
 
         let debug_str = format!("{:?}", input);
@@ -0,0 +1,205 @@
+//! Organization ownership transfer repository for database operations.
+
+use chrono::{DateTime, Utc};
+use domain::models::{OrgOwnershipTransfer, OrgUserRole};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::entities::{OrgOwnershipTransferEntity, OrgUserRoleDb};
+use crate::retry::retry_on_conflict;
+
+/// Repository for organization ownership transfer database operations.
+#[derive(Clone)]
+pub struct OrgOwnershipTransferRepository {
+    pool: PgPool,
+}
+
+impl OrgOwnershipTransferRepository {
+    /// Create a new repository instance.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Create a pending transfer, cancelling any pending one for the
+    /// organization.
+    ///
+    /// The organization row is locked for the transaction, so concurrent
+    /// requests and acceptances for the same organization run one at a time.
+    /// Retried if the transaction loses a serialization or deadlock race.
+    pub async fn create(
+        &self,
+        organization_id: Uuid,
+        from_user_id: Uuid,
+        to_user_id: Uuid,
+        expires_at: DateTime<Utc>,
+    ) -> Result<OrgOwnershipTransfer, sqlx::Error> {
+        retry_on_conflict("create_org_ownership_transfer", || async {
+            let mut tx = self.pool.begin().await?;
+
+            sqlx::query("SELECT id FROM organizations WHERE id = $1 FOR UPDATE")
+                .bind(organization_id)
+                .execute(&mut *tx)
+                .await?;
+
+            sqlx::query(
+                r#"
+                UPDATE org_ownership_transfers
+                SET status = 'cancelled', resolved_at = NOW()
+                WHERE organization_id = $1 AND status = 'pending'
+                "#,
+            )
+            .bind(organization_id)
+            .execute(&mut *tx)
+            .await?;
+
+            let entity = sqlx::query_as::<_, OrgOwnershipTransferEntity>(
+                r#"
+                INSERT INTO org_ownership_transfers (organization_id, from_user_id, to_user_id, expires_at)
+                VALUES ($1, $2, $3, $4)
+                RETURNING id, organization_id, from_user_id, to_user_id, status, expires_at,
+                          created_at, resolved_at
+                "#,
+            )
+            .bind(organization_id)
+            .bind(from_user_id)
+            .bind(to_user_id)
+            .bind(expires_at)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            tx.commit().await?;
+            Ok(entity.into())
+        })
+        .await
+    }
+
+    /// Find the pending transfer of an organization.
+    pub async fn find_pending(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Option<OrgOwnershipTransfer>, sqlx::Error> {
+        let entity = sqlx::query_as::<_, OrgOwnershipTransferEntity>(
+            r#"
+            SELECT id, organization_id, from_user_id, to_user_id, status, expires_at,
+                   created_at, resolved_at
+            FROM org_ownership_transfers
+            WHERE organization_id = $1 AND status = 'pending'
+            "#,
+        )
+        .bind(organization_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(entity.map(Into::into))
+    }
+
+    /// Cancel a pending transfer.
+    pub async fn cancel(&self, transfer_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE org_ownership_transfers
+            SET status = 'cancelled', resolved_at = NOW()
+            WHERE id = $1 AND status = 'pending'
+            "#,
+        )
+        .bind(transfer_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Accept a pending transfer and swap the owner and admin roles in one
+    /// transaction.
+    ///
+    /// The previous owner becomes an admin and the new owner gets the owner
+    /// role, each with the role's default permissions. Returns `None`, with
+    /// nothing changed, if the transfer is no longer pending or expired, or
+    /// if either user no longer holds the expected role. The organization row
+    /// is locked first, as in `create`. Retried if the transaction loses a
+    /// serialization or deadlock race.
+    pub async fn accept(
+        &self,
+        transfer_id: Uuid,
+        to_user_id: Uuid,
+    ) -> Result<Option<OrgOwnershipTransfer>, sqlx::Error> {
+        retry_on_conflict("accept_org_ownership_transfer", || async {
+            let mut tx = self.pool.begin().await?;
+
+            sqlx::query(
+                r#"
+                SELECT o.id
+                FROM organizations o
+                JOIN org_ownership_transfers t ON t.organization_id = o.id
+                WHERE t.id = $1
+                FOR UPDATE OF o
+                "#,
+            )
+            .bind(transfer_id)
+            .execute(&mut *tx)
+            .await?;
+
+            let entity = sqlx::query_as::<_, OrgOwnershipTransferEntity>(
+                r#"
+                UPDATE org_ownership_transfers
+                SET status = 'accepted', resolved_at = NOW()
+                WHERE id = $1 AND to_user_id = $2 AND status = 'pending' AND expires_at > NOW()
+                RETURNING id, organization_id, from_user_id, to_user_id, status, expires_at,
+                          created_at, resolved_at
+                "#,
+            )
+            .bind(transfer_id)
+            .bind(to_user_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+            let Some(transfer) = entity else {
+                return Ok(None);
+            };
+
+            let demoted = sqlx::query(
+                r#"
+                UPDATE org_users
+                SET role = $3, permissions = $4
+                WHERE organization_id = $1 AND user_id = $2 AND role = $5
+                "#,
+            )
+            .bind(transfer.organization_id)
+            .bind(transfer.from_user_id)
+            .bind(OrgUserRoleDb::from(OrgUserRole::Admin))
+            .bind(
+                serde_json::to_value(OrgUserRole::Admin.default_permissions()).unwrap_or_default(),
+            )
+            .bind(OrgUserRoleDb::from(OrgUserRole::Owner))
+            .execute(&mut *tx)
+            .await?;
+
+            let promoted = sqlx::query(
+                r#"
+                UPDATE org_users
+                SET role = $3, permissions = $4
+                WHERE organization_id = $1 AND user_id = $2 AND role = $5
+                  AND suspended_at IS NULL
+                "#,
+            )
+            .bind(transfer.organization_id)
+            .bind(transfer.to_user_id)
+            .bind(OrgUserRoleDb::from(OrgUserRole::Owner))
+            .bind(
+                serde_json::to_value(OrgUserRole::Owner.default_permissions()).unwrap_or_default(),
+            )
+            .bind(OrgUserRoleDb::from(OrgUserRole::Admin))
+            .execute(&mut *tx)
+            .await?;
+
+            if demoted.rows_affected() != 1 || promoted.rows_affected() != 1 {
+                tx.rollback().await?;
+                return Ok(None);
+            }
+
+            tx.commit().await?;
+            Ok(Some(transfer.into()))
+        })
+        .await
+    }
+}
@@ -96,6 +96,24 @@ impl OrgUserRepository {
         Ok(entity.map(Into::into))
     }
 
+    /// List all organization memberships of a user.
+    pub async fn list_for_user(&self, user_id: Uuid) -> Result<Vec<OrgUser>, sqlx::Error> {
+        let entities = sqlx::query_as::<_, OrgUserEntity>(
+            r#"
+            SELECT id, organization_id, user_id, role, permissions, granted_at, granted_by,
+                   suspended_at, suspended_by, suspension_reason
+            FROM org_users
+            WHERE user_id = $1
+            ORDER BY granted_at ASC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entities.into_iter().map(Into::into).collect())
+    }
+
     /// Get organization user with details.
     pub async fn find_with_details(
         &self,
@@ -1,6 +1,7 @@
 //! Repository for organization webhook database operations.
 
 use chrono::{DateTime, Utc};
+use domain::models::WebhookPayloadFormat;
 use sqlx::PgPool;
 use uuid::Uuid;
 
@@ -19,6 +20,7 @@ impl OrgWebhookRepository {
     }
 
     /// Creates a new organization webhook.
+    #[allow(clippy::too_many_arguments)]
     pub async fn create(
         &self,
         org_id: Uuid,
@@ -26,13 +28,19 @@ impl OrgWebhookRepository {
         target_url: &str,
         secret: &str,
         event_types: &[String],
+        retry_policy: Option<serde_json::Value>,
+        payload_format: WebhookPayloadFormat,
     ) -> Result<OrgWebhookEntity, sqlx::Error> {
         sqlx::query_as::<_, OrgWebhookEntity>(
             r#"
-            INSERT INTO org_webhooks (organization_id, name, target_url, secret, event_types)
-            VALUES ($1, $2, $3, $4, $5)
+            INSERT INTO org_webhooks (organization_id, name, target_url, secret, event_types,
+                                      retry_policy, payload_format)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
             RETURNING id, webhook_id, organization_id, name, target_url, secret, enabled,
-                      event_types, consecutive_failures, circuit_open_until, created_at, updated_at
+                      event_types, consecutive_failures, circuit_open_until, retry_policy,
+                      client_cert_pem, client_key_encrypted, payload_format, created_at, updated_at,
+                      (SELECT s.webhook_retry_policy FROM organization_settings s
+                       WHERE s.organization_id = org_webhooks.organization_id) AS retry_policy_override
             "#,
         )
         .bind(org_id)
@@ -40,6 +48,8 @@ impl OrgWebhookRepository {
         .bind(target_url)
         .bind(secret)
         .bind(event_types)
+        .bind(retry_policy)
+        .bind(payload_format.as_str())
         .fetch_one(&self.pool)
         .await
     }
@@ -53,7 +63,10 @@ impl OrgWebhookRepository {
         sqlx::query_as::<_, OrgWebhookEntity>(
             r#"
             SELECT id, webhook_id, organization_id, name, target_url, secret, enabled,
-                   event_types, consecutive_failures, circuit_open_until, created_at, updated_at
+                   event_types, consecutive_failures, circuit_open_until, retry_policy,
+                   client_cert_pem, client_key_encrypted, payload_format, created_at, updated_at,
+                   (SELECT s.webhook_retry_policy FROM organization_settings s
+                    WHERE s.organization_id = org_webhooks.organization_id) AS retry_policy_override
             FROM org_webhooks
             WHERE webhook_id = $1 AND organization_id = $2
             "#,
@@ -64,6 +77,27 @@ impl OrgWebhookRepository {
         .await
     }
 
+    /// Finds a webhook by ID in any organization.
+    pub async fn find_by_webhook_id(
+        &self,
+        webhook_id: Uuid,
+    ) -> Result<Option<OrgWebhookEntity>, sqlx::Error> {
+        sqlx::query_as::<_, OrgWebhookEntity>(
+            r#"
+            SELECT id, webhook_id, organization_id, name, target_url, secret, enabled,
+                   event_types, consecutive_failures, circuit_open_until, retry_policy,
+                   client_cert_pem, client_key_encrypted, payload_format, created_at, updated_at,
+                   (SELECT s.webhook_retry_policy FROM organization_settings s
+                    WHERE s.organization_id = org_webhooks.organization_id) AS retry_policy_override
+            FROM org_webhooks
+            WHERE webhook_id = $1
+            "#,
+        )
+        .bind(webhook_id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
     /// Lists all webhooks for an organization.
     pub async fn list_by_organization(
         &self,
@@ -72,7 +106,10 @@ impl OrgWebhookRepository {
         sqlx::query_as::<_, OrgWebhookEntity>(
             r#"
             SELECT id, webhook_id, organization_id, name, target_url, secret, enabled,
-                   event_types, consecutive_failures, circuit_open_until, created_at, updated_at
+                   event_types, consecutive_failures, circuit_open_until, retry_policy,
+                   client_cert_pem, client_key_encrypted, payload_format, created_at, updated_at,
+                   (SELECT s.webhook_retry_policy FROM organization_settings s
+                    WHERE s.organization_id = org_webhooks.organization_id) AS retry_policy_override
             FROM org_webhooks
             WHERE organization_id = $1
             ORDER BY created_at DESC
@@ -126,6 +163,8 @@ impl OrgWebhookRepository {
         secret: Option<&str>,
         enabled: Option<bool>,
         event_types: Option<&[String]>,
+        retry_policy: Option<serde_json::Value>,
+        payload_format: Option<WebhookPayloadFormat>,
     ) -> Result<Option<OrgWebhookEntity>, sqlx::Error> {
         sqlx::query_as::<_, OrgWebhookEntity>(
             r#"
@@ -135,10 +174,15 @@ impl OrgWebhookRepository {
                 secret = COALESCE($5, secret),
                 enabled = COALESCE($6, enabled),
                 event_types = COALESCE($7, event_types),
+                retry_policy = COALESCE($8, retry_policy),
+                payload_format = COALESCE($9, payload_format),
                 updated_at = NOW()
             WHERE webhook_id = $1 AND organization_id = $2
             RETURNING id, webhook_id, organization_id, name, target_url, secret, enabled,
-                      event_types, consecutive_failures, circuit_open_until, created_at, updated_at
+                      event_types, consecutive_failures, circuit_open_until, retry_policy,
+                      client_cert_pem, client_key_encrypted, payload_format, created_at, updated_at,
+                      (SELECT s.webhook_retry_policy FROM organization_settings s
+                       WHERE s.organization_id = org_webhooks.organization_id) AS retry_policy_override
             "#,
         )
         .bind(webhook_id)
@@ -148,6 +192,41 @@ impl OrgWebhookRepository {
         .bind(secret)
         .bind(enabled)
         .bind(event_types)
+        .bind(retry_policy)
+        .bind(payload_format.map(|f| f.as_str()))
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Sets or removes the client certificate used for mutual TLS.
+    ///
+    /// `client_key_encrypted` is the private key, encrypted by the caller;
+    /// pass `None` for both to remove the certificate.
+    pub async fn set_client_certificate(
+        &self,
+        webhook_id: Uuid,
+        org_id: Uuid,
+        client_cert_pem: Option<&str>,
+        client_key_encrypted: Option<&[u8]>,
+    ) -> Result<Option<OrgWebhookEntity>, sqlx::Error> {
+        sqlx::query_as::<_, OrgWebhookEntity>(
+            r#"
+            UPDATE org_webhooks SET
+                client_cert_pem = $3,
+                client_key_encrypted = $4,
+                updated_at = NOW()
+            WHERE webhook_id = $1 AND organization_id = $2
+            RETURNING id, webhook_id, organization_id, name, target_url, secret, enabled,
+                      event_types, consecutive_failures, circuit_open_until, retry_policy,
+                      client_cert_pem, client_key_encrypted, payload_format, created_at, updated_at,
+                      (SELECT s.webhook_retry_policy FROM organization_settings s
+                       WHERE s.organization_id = org_webhooks.organization_id) AS retry_policy_override
+            "#,
+        )
+        .bind(webhook_id)
+        .bind(org_id)
+        .bind(client_cert_pem)
+        .bind(client_key_encrypted)
         .fetch_optional(&self.pool)
         .await
     }
@@ -179,7 +258,10 @@ impl OrgWebhookRepository {
         sqlx::query_as::<_, OrgWebhookEntity>(
             r#"
             SELECT id, webhook_id, organization_id, name, target_url, secret, enabled,
-                   event_types, consecutive_failures, circuit_open_until, created_at, updated_at
+                   event_types, consecutive_failures, circuit_open_until, retry_policy,
+                   client_cert_pem, client_key_encrypted, payload_format, created_at, updated_at,
+                   (SELECT s.webhook_retry_policy FROM organization_settings s
+                    WHERE s.organization_id = org_webhooks.organization_id) AS retry_policy_override
             FROM org_webhooks
             WHERE organization_id = $1
               AND enabled = true
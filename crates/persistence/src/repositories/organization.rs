@@ -104,6 +104,31 @@ impl OrganizationRepository {
         Ok(result)
     }
 
+    /// Check if a user owns an active organization on the given plan.
+    pub async fn user_owns_plan(
+        &self,
+        user_id: Uuid,
+        plan_type: PlanType,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS(
+                SELECT 1
+                FROM organizations o
+                JOIN org_users ou ON ou.organization_id = o.id
+                WHERE ou.user_id = $1 AND ou.role = 'owner'
+                  AND o.plan_type = $2 AND o.is_active = true
+            )
+            "#,
+        )
+        .bind(user_id)
+        .bind(PlanTypeDb::from(plan_type))
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(result)
+    }
+
     /// Update organization.
     #[allow(clippy::too_many_arguments)]
     pub async fn update(
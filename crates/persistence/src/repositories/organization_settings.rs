@@ -26,7 +26,8 @@ impl OrganizationSettingsRepository {
         sqlx::query_as::<_, OrganizationSettingsEntity>(
             r#"
             SELECT id, organization_id, unlock_pin_hash, default_daily_limit_minutes,
-                   notifications_enabled, auto_approve_unlock_requests, created_at, updated_at
+                   notifications_enabled, auto_approve_unlock_requests, max_location_accuracy_meters,
+                   max_location_speed_mps, blocked_terms, webhook_retry_policy, created_at, updated_at
             FROM organization_settings
             WHERE organization_id = $1
             "#,
@@ -61,7 +62,8 @@ impl OrganizationSettingsRepository {
             VALUES ($1)
             ON CONFLICT (organization_id) DO UPDATE SET updated_at = NOW()
             RETURNING id, organization_id, unlock_pin_hash, default_daily_limit_minutes,
-                      notifications_enabled, auto_approve_unlock_requests, created_at, updated_at
+                      notifications_enabled, auto_approve_unlock_requests, max_location_accuracy_meters,
+                      max_location_speed_mps, blocked_terms, webhook_retry_policy, created_at, updated_at
             "#,
         )
         .bind(organization_id)
@@ -71,6 +73,7 @@ impl OrganizationSettingsRepository {
 
     /// Updates organization settings.
     /// Uses upsert pattern: creates if not exists, updates if exists.
+    #[allow(clippy::too_many_arguments)]
     pub async fn upsert(
         &self,
         organization_id: Uuid,
@@ -78,22 +81,33 @@ impl OrganizationSettingsRepository {
         default_daily_limit_minutes: i32,
         notifications_enabled: bool,
         auto_approve_unlock_requests: bool,
+        max_location_accuracy_meters: Option<f64>,
+        max_location_speed_mps: Option<f64>,
+        blocked_terms: &[String],
+        webhook_retry_policy: Option<&serde_json::Value>,
     ) -> Result<OrganizationSettingsEntity, sqlx::Error> {
         sqlx::query_as::<_, OrganizationSettingsEntity>(
             r#"
             INSERT INTO organization_settings (
                 organization_id, unlock_pin_hash, default_daily_limit_minutes,
-                notifications_enabled, auto_approve_unlock_requests
+                notifications_enabled, auto_approve_unlock_requests,
+                max_location_accuracy_meters, max_location_speed_mps, blocked_terms,
+                webhook_retry_policy
             )
-            VALUES ($1, $2, $3, $4, $5)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
             ON CONFLICT (organization_id) DO UPDATE SET
                 unlock_pin_hash = EXCLUDED.unlock_pin_hash,
                 default_daily_limit_minutes = EXCLUDED.default_daily_limit_minutes,
                 notifications_enabled = EXCLUDED.notifications_enabled,
                 auto_approve_unlock_requests = EXCLUDED.auto_approve_unlock_requests,
+                max_location_accuracy_meters = EXCLUDED.max_location_accuracy_meters,
+                max_location_speed_mps = EXCLUDED.max_location_speed_mps,
+                blocked_terms = EXCLUDED.blocked_terms,
+                webhook_retry_policy = EXCLUDED.webhook_retry_policy,
                 updated_at = NOW()
             RETURNING id, organization_id, unlock_pin_hash, default_daily_limit_minutes,
-                      notifications_enabled, auto_approve_unlock_requests, created_at, updated_at
+                      notifications_enabled, auto_approve_unlock_requests, max_location_accuracy_meters,
+                      max_location_speed_mps, blocked_terms, webhook_retry_policy, created_at, updated_at
             "#,
         )
         .bind(organization_id)
@@ -101,6 +115,10 @@ impl OrganizationSettingsRepository {
         .bind(default_daily_limit_minutes)
         .bind(notifications_enabled)
         .bind(auto_approve_unlock_requests)
+        .bind(max_location_accuracy_meters.map(|v| v as f32))
+        .bind(max_location_speed_mps.map(|v| v as f32))
+        .bind(blocked_terms)
+        .bind(webhook_retry_policy)
         .fetch_one(&self.pool)
         .await
     }
@@ -121,7 +139,8 @@ impl OrganizationSettingsRepository {
             SET unlock_pin_hash = $2, updated_at = NOW()
             WHERE organization_id = $1
             RETURNING id, organization_id, unlock_pin_hash, default_daily_limit_minutes,
-                      notifications_enabled, auto_approve_unlock_requests, created_at, updated_at
+                      notifications_enabled, auto_approve_unlock_requests, max_location_accuracy_meters,
+                      max_location_speed_mps, blocked_terms, webhook_retry_policy, created_at, updated_at
             "#,
         )
         .bind(organization_id)
@@ -0,0 +1,167 @@
+//! Privacy zone repository for database operations.
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::entities::PrivacyZoneEntity;
+use crate::metrics::QueryTimer;
+
+/// Repository for privacy zone database operations.
+#[derive(Clone)]
+pub struct PrivacyZoneRepository {
+    pool: PgPool,
+}
+
+impl PrivacyZoneRepository {
+    /// Creates a new PrivacyZoneRepository with the given connection pool.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Create a new privacy zone.
+    pub async fn create(
+        &self,
+        user_id: Uuid,
+        name: &str,
+        latitude: f64,
+        longitude: f64,
+        radius_meters: f32,
+        mode: &str,
+    ) -> Result<PrivacyZoneEntity, sqlx::Error> {
+        let timer = QueryTimer::new("create_privacy_zone");
+
+        let result = sqlx::query_as::<_, PrivacyZoneEntity>(
+            r#"
+            INSERT INTO privacy_zones (user_id, name, latitude, longitude, radius_meters, mode)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+        )
+        .bind(user_id)
+        .bind(name)
+        .bind(latitude)
+        .bind(longitude)
+        .bind(radius_meters)
+        .bind(mode)
+        .fetch_one(&self.pool)
+        .await;
+
+        timer.record();
+        result
+    }
+
+    /// List all privacy zones of a user.
+    pub async fn list_by_user(&self, user_id: Uuid) -> Result<Vec<PrivacyZoneEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("list_privacy_zones");
+
+        let result = sqlx::query_as::<_, PrivacyZoneEntity>(
+            r#"
+            SELECT * FROM privacy_zones
+            WHERE user_id = $1
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await;
+
+        timer.record();
+        result
+    }
+
+    /// List the privacy zones of several users (device owners).
+    pub async fn list_by_users(
+        &self,
+        user_ids: &[Uuid],
+    ) -> Result<Vec<PrivacyZoneEntity>, sqlx::Error> {
+        if user_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let timer = QueryTimer::new("list_privacy_zones_by_users");
+
+        let result = sqlx::query_as::<_, PrivacyZoneEntity>(
+            r#"
+            SELECT * FROM privacy_zones
+            WHERE user_id = ANY($1)
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(user_ids)
+        .fetch_all(&self.pool)
+        .await;
+
+        timer.record();
+        result
+    }
+
+    /// Count privacy zones of a user.
+    pub async fn count_by_user(&self, user_id: Uuid) -> Result<i64, sqlx::Error> {
+        let timer = QueryTimer::new("count_privacy_zones");
+
+        let result =
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM privacy_zones WHERE user_id = $1")
+                .bind(user_id)
+                .fetch_one(&self.pool)
+                .await;
+
+        timer.record();
+        result
+    }
+
+    /// Update a user's privacy zone (partial update).
+    /// Returns None if the zone does not exist or belongs to another user.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn update(
+        &self,
+        user_id: Uuid,
+        zone_id: Uuid,
+        name: Option<&str>,
+        latitude: Option<f64>,
+        longitude: Option<f64>,
+        radius_meters: Option<f32>,
+        mode: Option<&str>,
+    ) -> Result<Option<PrivacyZoneEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("update_privacy_zone");
+
+        let result = sqlx::query_as::<_, PrivacyZoneEntity>(
+            r#"
+            UPDATE privacy_zones
+            SET
+                name = COALESCE($3, name),
+                latitude = COALESCE($4, latitude),
+                longitude = COALESCE($5, longitude),
+                radius_meters = COALESCE($6, radius_meters),
+                mode = COALESCE($7, mode)
+            WHERE zone_id = $2 AND user_id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(user_id)
+        .bind(zone_id)
+        .bind(name)
+        .bind(latitude)
+        .bind(longitude)
+        .bind(radius_meters)
+        .bind(mode)
+        .fetch_optional(&self.pool)
+        .await;
+
+        timer.record();
+        result
+    }
+
+    /// Delete a user's privacy zone. Returns true if a zone was deleted.
+    pub async fn delete(&self, user_id: Uuid, zone_id: Uuid) -> Result<bool, sqlx::Error> {
+        let timer = QueryTimer::new("delete_privacy_zone");
+
+        let result = sqlx::query("DELETE FROM privacy_zones WHERE zone_id = $1 AND user_id = $2")
+            .bind(zone_id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await;
+
+        timer.record();
+        Ok(result?.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,159 @@
+//! Report template repository.
+//!
+//! Provides data access for saved report builder definitions.
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::entities::ReportTemplateEntity;
+use crate::metrics::QueryTimer;
+
+/// Repository for report template operations.
+#[derive(Clone)]
+pub struct ReportTemplateRepository {
+    pool: PgPool,
+}
+
+impl ReportTemplateRepository {
+    /// Create a new repository instance.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Create a report template.
+    pub async fn create(
+        &self,
+        organization_id: Uuid,
+        name: &str,
+        description: Option<&str>,
+        definition: &serde_json::Value,
+        created_by: Uuid,
+    ) -> Result<ReportTemplateEntity, sqlx::Error> {
+        let timer = QueryTimer::new("create_report_template");
+        let result = sqlx::query_as::<_, ReportTemplateEntity>(
+            r#"
+            INSERT INTO report_templates (organization_id, name, description, definition, created_by)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, organization_id, name, description, definition, created_by,
+                      created_at, updated_at
+            "#,
+        )
+        .bind(organization_id)
+        .bind(name)
+        .bind(description)
+        .bind(definition)
+        .bind(created_by)
+        .fetch_one(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Find a template within an organization.
+    pub async fn find_by_id(
+        &self,
+        organization_id: Uuid,
+        template_id: Uuid,
+    ) -> Result<Option<ReportTemplateEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("find_report_template_by_id");
+        let result = sqlx::query_as::<_, ReportTemplateEntity>(
+            r#"
+            SELECT id, organization_id, name, description, definition, created_by,
+                   created_at, updated_at
+            FROM report_templates
+            WHERE organization_id = $1 AND id = $2
+            "#,
+        )
+        .bind(organization_id)
+        .bind(template_id)
+        .fetch_optional(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// List all templates of an organization ordered by name.
+    pub async fn list_by_organization(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Vec<ReportTemplateEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("list_report_templates");
+        let result = sqlx::query_as::<_, ReportTemplateEntity>(
+            r#"
+            SELECT id, organization_id, name, description, definition, created_by,
+                   created_at, updated_at
+            FROM report_templates
+            WHERE organization_id = $1
+            ORDER BY name ASC
+            "#,
+        )
+        .bind(organization_id)
+        .fetch_all(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Count templates of an organization.
+    pub async fn count_by_organization(&self, organization_id: Uuid) -> Result<i64, sqlx::Error> {
+        let timer = QueryTimer::new("count_report_templates");
+        let result = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM report_templates WHERE organization_id = $1",
+        )
+        .bind(organization_id)
+        .fetch_one(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Update a template. `None` name/description leave the stored value unchanged.
+    pub async fn update(
+        &self,
+        organization_id: Uuid,
+        template_id: Uuid,
+        name: Option<&str>,
+        description: Option<&str>,
+        definition: &serde_json::Value,
+    ) -> Result<Option<ReportTemplateEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("update_report_template");
+        let result = sqlx::query_as::<_, ReportTemplateEntity>(
+            r#"
+            UPDATE report_templates
+            SET name = COALESCE($3, name),
+                description = COALESCE($4, description),
+                definition = $5,
+                updated_at = NOW()
+            WHERE organization_id = $1 AND id = $2
+            RETURNING id, organization_id, name, description, definition, created_by,
+                      created_at, updated_at
+            "#,
+        )
+        .bind(organization_id)
+        .bind(template_id)
+        .bind(name)
+        .bind(description)
+        .bind(definition)
+        .fetch_optional(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Delete a template. Returns true if a row was deleted.
+    pub async fn delete(
+        &self,
+        organization_id: Uuid,
+        template_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let timer = QueryTimer::new("delete_report_template");
+        let result =
+            sqlx::query("DELETE FROM report_templates WHERE organization_id = $1 AND id = $2")
+                .bind(organization_id)
+                .bind(template_id)
+                .execute(&self.pool)
+                .await;
+        timer.record();
+        Ok(result?.rows_affected() > 0)
+    }
+}
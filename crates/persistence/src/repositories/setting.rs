@@ -326,6 +326,30 @@ impl SettingRepository {
         Ok(result)
     }
 
+    /// Active devices whose setting is explicitly set to `value`.
+    pub async fn find_device_ids_with_value(
+        &self,
+        setting_key: &str,
+        value: &serde_json::Value,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        let timer = QueryTimer::new("find_device_ids_with_setting_value");
+        let result = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT s.device_id
+            FROM device_settings s
+            JOIN devices d ON d.device_id = s.device_id
+            WHERE s.setting_key = $1 AND s.value = $2 AND d.active = true
+            ORDER BY s.device_id
+            "#,
+        )
+        .bind(setting_key)
+        .bind(value)
+        .fetch_all(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
     /// Get settings modified since a timestamp.
     pub async fn get_settings_modified_since(
         &self,
@@ -36,6 +36,7 @@ pub struct TripQuery {
     pub from_timestamp: Option<i64>,
     pub to_timestamp: Option<i64>,
     pub state_filter: Option<String>,
+    pub purpose_filter: Option<String>,
     pub limit: i32,
 }
 
@@ -118,6 +119,7 @@ impl TripRepository {
                 CASE WHEN end_location IS NULL THEN NULL ELSE ST_Y(end_location::geometry) END as end_latitude,
                 CASE WHEN end_location IS NULL THEN NULL ELSE ST_X(end_location::geometry) END as end_longitude,
                 transportation_mode, detection_source, distance_meters, duration_seconds,
+                purpose, purpose_source,
                 created_at, updated_at
             FROM trips
             WHERE device_id = $1 AND local_trip_id = $2
@@ -145,6 +147,7 @@ impl TripRepository {
                 CASE WHEN end_location IS NULL THEN NULL ELSE ST_Y(end_location::geometry) END as end_latitude,
                 CASE WHEN end_location IS NULL THEN NULL ELSE ST_X(end_location::geometry) END as end_longitude,
                 transportation_mode, detection_source, distance_meters, duration_seconds,
+                purpose, purpose_source,
                 created_at, updated_at
             FROM trips
             WHERE id = $1
@@ -174,6 +177,7 @@ impl TripRepository {
                 CASE WHEN end_location IS NULL THEN NULL ELSE ST_Y(end_location::geometry) END as end_latitude,
                 CASE WHEN end_location IS NULL THEN NULL ELSE ST_X(end_location::geometry) END as end_longitude,
                 transportation_mode, detection_source, distance_meters, duration_seconds,
+                purpose, purpose_source,
                 created_at, updated_at
             FROM trips
             WHERE device_id = $1 AND state = 'ACTIVE'
@@ -195,7 +199,9 @@ impl TripRepository {
     ) -> Result<Option<TripEntity>, sqlx::Error> {
         let timer = QueryTimer::new("update_trip_state");
 
-        let result = if input.end_latitude.is_some() && input.end_longitude.is_some() {
+        let result = if let (Some(end_latitude), Some(end_longitude)) =
+            (input.end_latitude, input.end_longitude)
+        {
             // Update with end location
             sqlx::query_as::<_, TripEntity>(
                 r#"
@@ -211,14 +217,15 @@ impl TripRepository {
                     CASE WHEN end_location IS NULL THEN NULL ELSE ST_Y(end_location::geometry) END as end_latitude,
                     CASE WHEN end_location IS NULL THEN NULL ELSE ST_X(end_location::geometry) END as end_longitude,
                     transportation_mode, detection_source, distance_meters, duration_seconds,
+                    purpose, purpose_source,
                     created_at, updated_at
                 "#,
             )
             .bind(trip_id)
             .bind(&input.state)
             .bind(input.end_timestamp)
-            .bind(input.end_longitude.unwrap()) // x = lon
-            .bind(input.end_latitude.unwrap())   // y = lat
+            .bind(end_longitude) // x = lon
+            .bind(end_latitude) // y = lat
             .fetch_optional(&self.pool)
             .await
         } else {
@@ -236,6 +243,7 @@ impl TripRepository {
                     CASE WHEN end_location IS NULL THEN NULL ELSE ST_Y(end_location::geometry) END as end_latitude,
                     CASE WHEN end_location IS NULL THEN NULL ELSE ST_X(end_location::geometry) END as end_longitude,
                     transportation_mode, detection_source, distance_meters, duration_seconds,
+                    purpose, purpose_source,
                     created_at, updated_at
                 "#,
             )
@@ -250,6 +258,64 @@ impl TripRepository {
         result
     }
 
+    /// Find the latest trip of a device from a detection source.
+    pub async fn find_latest_by_source(
+        &self,
+        device_id: Uuid,
+        detection_source: &str,
+    ) -> Result<Option<TripEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("find_latest_trip_by_source");
+
+        let result = sqlx::query_as::<_, TripEntity>(
+            r#"
+            SELECT
+                id, device_id, local_trip_id, state, start_timestamp, end_timestamp,
+                ST_Y(start_location::geometry) as start_latitude,
+                ST_X(start_location::geometry) as start_longitude,
+                CASE WHEN end_location IS NULL THEN NULL ELSE ST_Y(end_location::geometry) END as end_latitude,
+                CASE WHEN end_location IS NULL THEN NULL ELSE ST_X(end_location::geometry) END as end_longitude,
+                transportation_mode, detection_source, distance_meters, duration_seconds,
+                purpose, purpose_source,
+                created_at, updated_at
+            FROM trips
+            WHERE device_id = $1 AND detection_source = $2
+            ORDER BY start_timestamp DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(device_id)
+        .bind(detection_source)
+        .fetch_optional(&self.pool)
+        .await;
+
+        timer.record();
+        result
+    }
+
+    /// Update the transportation mode of a trip.
+    pub async fn update_transportation_mode(
+        &self,
+        trip_id: Uuid,
+        transportation_mode: &str,
+    ) -> Result<(), sqlx::Error> {
+        let timer = QueryTimer::new("update_trip_transportation_mode");
+
+        sqlx::query(
+            r#"
+            UPDATE trips
+            SET transportation_mode = $2
+            WHERE id = $1
+            "#,
+        )
+        .bind(trip_id)
+        .bind(transportation_mode)
+        .execute(&self.pool)
+        .await?;
+
+        timer.record();
+        Ok(())
+    }
+
     /// Update trip statistics (distance and duration).
     pub async fn update_statistics(
         &self,
@@ -276,6 +342,61 @@ impl TripRepository {
         Ok(())
     }
 
+    /// Set or clear the purpose of a trip.
+    pub async fn set_purpose(
+        &self,
+        trip_id: Uuid,
+        purpose: Option<&str>,
+        purpose_source: Option<&str>,
+    ) -> Result<bool, sqlx::Error> {
+        let timer = QueryTimer::new("set_trip_purpose");
+
+        let result = sqlx::query(
+            r#"
+            UPDATE trips
+            SET purpose = $2, purpose_source = $3, updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(trip_id)
+        .bind(purpose)
+        .bind(purpose_source)
+        .execute(&self.pool)
+        .await?;
+
+        timer.record();
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Set the purpose chosen by classification rules, or clear it when no
+    /// rule matches. Purposes set by the owner are left untouched.
+    pub async fn set_rule_purpose(
+        &self,
+        trip_id: Uuid,
+        purpose: Option<&str>,
+    ) -> Result<bool, sqlx::Error> {
+        let timer = QueryTimer::new("set_trip_rule_purpose");
+
+        let result = sqlx::query(
+            r#"
+            UPDATE trips
+            SET purpose = $2,
+                purpose_source = CASE WHEN $2::text IS NULL THEN NULL ELSE 'rule' END,
+                updated_at = NOW()
+            WHERE id = $1
+              AND purpose_source IS DISTINCT FROM 'manual'
+              AND purpose IS DISTINCT FROM $2
+            "#,
+        )
+        .bind(trip_id)
+        .bind(purpose)
+        .execute(&self.pool)
+        .await?;
+
+        timer.record();
+        Ok(result.rows_affected() > 0)
+    }
+
     /// Get trips for a device with pagination.
     pub async fn get_trips_by_device(
         &self,
@@ -295,6 +416,7 @@ impl TripRepository {
                 CASE WHEN end_location IS NULL THEN NULL ELSE ST_Y(end_location::geometry) END as end_latitude,
                 CASE WHEN end_location IS NULL THEN NULL ELSE ST_X(end_location::geometry) END as end_longitude,
                 transportation_mode, detection_source, distance_meters, duration_seconds,
+                purpose, purpose_source,
                 created_at, updated_at
             FROM trips
             WHERE device_id = $1
@@ -302,6 +424,7 @@ impl TripRepository {
               AND ($3::bigint IS NULL OR start_timestamp >= $3)
               AND ($4::bigint IS NULL OR start_timestamp <= $4)
               AND ($5::bigint IS NULL OR (start_timestamp, id) < ($5, $6))
+              AND ($8::text IS NULL OR purpose = $8)
             ORDER BY start_timestamp DESC, id DESC
             LIMIT $7
             "#,
@@ -315,6 +438,7 @@ impl TripRepository {
         // This ensures keyset pagination works correctly
         .bind(query.cursor_id.unwrap_or_else(|| Uuid::from_bytes([0xff; 16])))
         .bind(fetch_limit)
+        .bind(&query.purpose_filter)
         .fetch_all(&self.pool)
         .await?;
 
@@ -330,6 +454,109 @@ impl TripRepository {
         Ok((result, has_more))
     }
 
+    /// Get the non-cancelled trips of a user's active devices that started
+    /// within a time range, oldest first.
+    pub async fn get_trips_by_user(
+        &self,
+        user_id: Uuid,
+        from_timestamp: i64,
+        to_timestamp: i64,
+    ) -> Result<Vec<TripEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("get_trips_by_user");
+
+        let result = sqlx::query_as::<_, TripEntity>(
+            r#"
+            SELECT
+                t.id, t.device_id, t.local_trip_id, t.state, t.start_timestamp, t.end_timestamp,
+                ST_Y(t.start_location::geometry) as start_latitude,
+                ST_X(t.start_location::geometry) as start_longitude,
+                CASE WHEN t.end_location IS NULL THEN NULL ELSE ST_Y(t.end_location::geometry) END as end_latitude,
+                CASE WHEN t.end_location IS NULL THEN NULL ELSE ST_X(t.end_location::geometry) END as end_longitude,
+                t.transportation_mode, t.detection_source, t.distance_meters, t.duration_seconds,
+                t.purpose, t.purpose_source,
+                t.created_at, t.updated_at
+            FROM trips t
+            JOIN devices d ON d.device_id = t.device_id
+            WHERE d.owner_user_id = $1
+              AND d.active = true
+              AND t.state <> 'CANCELLED'
+              AND t.start_timestamp >= $2
+              AND t.start_timestamp <= $3
+            ORDER BY t.start_timestamp ASC, t.id ASC
+            "#,
+        )
+        .bind(user_id)
+        .bind(from_timestamp)
+        .bind(to_timestamp)
+        .fetch_all(&self.pool)
+        .await;
+
+        timer.record();
+        result
+    }
+
+    /// Get the completed trips of a device that started at or after
+    /// `from_timestamp`, oldest first.
+    pub async fn get_completed_trips_since(
+        &self,
+        device_id: Uuid,
+        from_timestamp: i64,
+    ) -> Result<Vec<TripEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("get_completed_trips_since");
+
+        let result = sqlx::query_as::<_, TripEntity>(
+            r#"
+            SELECT
+                id, device_id, local_trip_id, state, start_timestamp, end_timestamp,
+                ST_Y(start_location::geometry) as start_latitude,
+                ST_X(start_location::geometry) as start_longitude,
+                CASE WHEN end_location IS NULL THEN NULL ELSE ST_Y(end_location::geometry) END as end_latitude,
+                CASE WHEN end_location IS NULL THEN NULL ELSE ST_X(end_location::geometry) END as end_longitude,
+                transportation_mode, detection_source, distance_meters, duration_seconds,
+                purpose, purpose_source,
+                created_at, updated_at
+            FROM trips
+            WHERE device_id = $1
+              AND state = 'COMPLETED'
+              AND start_timestamp >= $2
+            ORDER BY start_timestamp ASC, id ASC
+            "#,
+        )
+        .bind(device_id)
+        .bind(from_timestamp)
+        .fetch_all(&self.pool)
+        .await;
+
+        timer.record();
+        result
+    }
+
+    /// Get the active devices with a completed trip that started at or
+    /// after `from_timestamp`.
+    pub async fn find_device_ids_with_completed_trips_since(
+        &self,
+        from_timestamp: i64,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        let timer = QueryTimer::new("find_device_ids_with_completed_trips_since");
+
+        let result = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT DISTINCT t.device_id
+            FROM trips t
+            JOIN devices d ON d.device_id = t.device_id
+            WHERE d.active = true
+              AND t.state = 'COMPLETED'
+              AND t.start_timestamp >= $1
+            "#,
+        )
+        .bind(from_timestamp)
+        .fetch_all(&self.pool)
+        .await;
+
+        timer.record();
+        result
+    }
+
     /// Delete all trips for a device.
     pub async fn delete_all_for_device(&self, device_id: Uuid) -> Result<u64, sqlx::Error> {
         let result = sqlx::query(
@@ -402,6 +629,7 @@ mod tests {
             from_timestamp: Some(1000),
             to_timestamp: Some(2000),
             state_filter: Some("COMPLETED".to_string()),
+            purpose_filter: None,
             limit: 20,
         };
 
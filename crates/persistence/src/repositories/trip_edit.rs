@@ -0,0 +1,304 @@
+//! Trip edit repository: manual trip merges and splits and their history.
+
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::entities::{TripEditEntity, TripEntity};
+use crate::metrics::QueryTimer;
+
+/// Trip columns returned after an edit.
+const TRIP_COLUMNS: &str = r#"
+    id, device_id, local_trip_id, state, start_timestamp, end_timestamp,
+    ST_Y(start_location::geometry) as start_latitude,
+    ST_X(start_location::geometry) as start_longitude,
+    CASE WHEN end_location IS NULL THEN NULL ELSE ST_Y(end_location::geometry) END as end_latitude,
+    CASE WHEN end_location IS NULL THEN NULL ELSE ST_X(end_location::geometry) END as end_longitude,
+    transportation_mode, detection_source, distance_meters, duration_seconds,
+    purpose, purpose_source,
+    created_at, updated_at
+"#;
+
+const EDIT_COLUMNS: &str = r#"
+    id, device_id, edit_type, source_trip_ids, result_trip_ids, split_at, api_key_id, created_at
+"#;
+
+/// Input for merging trips into the earliest one.
+#[derive(Debug, Clone)]
+pub struct TripMergeInput {
+    pub device_id: Uuid,
+    pub kept_trip_id: Uuid,
+    pub merged_trip_ids: Vec<Uuid>,
+    pub end_timestamp: i64,
+    pub end_latitude: f64,
+    pub end_longitude: f64,
+    pub api_key_id: Option<i64>,
+}
+
+/// Input for splitting a trip in two.
+#[derive(Debug, Clone)]
+pub struct TripSplitInput {
+    pub trip_id: Uuid,
+    pub split_at: i64,
+    /// Last point before the split, which becomes the end of the trip.
+    pub first_end_timestamp: i64,
+    pub first_end_latitude: f64,
+    pub first_end_longitude: f64,
+    /// First point from the split on, which starts the new trip.
+    pub second_start_timestamp: i64,
+    pub second_start_latitude: f64,
+    pub second_start_longitude: f64,
+    pub second_local_trip_id: String,
+    pub api_key_id: Option<i64>,
+}
+
+/// Repository for manual trip edits.
+#[derive(Clone)]
+pub struct TripEditRepository {
+    pool: PgPool,
+}
+
+impl TripEditRepository {
+    /// Creates a new TripEditRepository with the given connection pool.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Merge trips into the kept trip.
+    ///
+    /// Movement events and locations of the merged trips move to the kept
+    /// trip, which takes the merged end; the merged trips are deleted. The
+    /// kept trip's statistics and path correction are cleared so they can
+    /// be recomputed. Returns `None`, changing nothing, if any of the trips
+    /// no longer exists.
+    pub async fn merge(
+        &self,
+        input: &TripMergeInput,
+    ) -> Result<Option<(TripEntity, TripEditEntity)>, sqlx::Error> {
+        let timer = QueryTimer::new("merge_trips");
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("UPDATE movement_events SET trip_id = $1 WHERE trip_id = ANY($2)")
+            .bind(input.kept_trip_id)
+            .bind(&input.merged_trip_ids)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("UPDATE locations SET trip_id = $1 WHERE trip_id = ANY($2)")
+            .bind(input.kept_trip_id)
+            .bind(&input.merged_trip_ids)
+            .execute(&mut *tx)
+            .await?;
+
+        let deleted = sqlx::query("DELETE FROM trips WHERE id = ANY($1) AND device_id = $2")
+            .bind(&input.merged_trip_ids)
+            .bind(input.device_id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        if deleted != input.merged_trip_ids.len() as u64 {
+            return Ok(None);
+        }
+
+        let query = format!(
+            r#"
+            UPDATE trips
+            SET end_timestamp = $2,
+                end_location = ST_SetSRID(ST_MakePoint($3, $4), 4326)::geography,
+                distance_meters = NULL,
+                duration_seconds = NULL
+            WHERE id = $1 AND device_id = $5
+            RETURNING {}
+            "#,
+            TRIP_COLUMNS
+        );
+        let Some(trip) = sqlx::query_as::<_, TripEntity>(&query)
+            .bind(input.kept_trip_id)
+            .bind(input.end_timestamp)
+            .bind(input.end_longitude) // x = lon
+            .bind(input.end_latitude) // y = lat
+            .bind(input.device_id)
+            .fetch_optional(&mut *tx)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        clear_path_correction(&mut tx, trip.id).await?;
+
+        let mut source_trip_ids = vec![trip.id];
+        source_trip_ids.extend(&input.merged_trip_ids);
+        let edit = insert_edit(
+            &mut tx,
+            trip.device_id,
+            "MERGE",
+            &source_trip_ids,
+            &[trip.id],
+            None,
+            input.api_key_id,
+        )
+        .await?;
+
+        tx.commit().await?;
+        timer.record();
+        Ok(Some((trip, edit)))
+    }
+
+    /// Split a trip at `split_at`.
+    ///
+    /// A new completed trip is created from the split on, taking the
+    /// original trip's end and its movement events and locations from
+    /// `split_at` on. Statistics and path correction of the original trip
+    /// are cleared. Returns `None` if the trip no longer exists.
+    pub async fn split(
+        &self,
+        input: &TripSplitInput,
+    ) -> Result<Option<(TripEntity, TripEntity, TripEditEntity)>, sqlx::Error> {
+        let timer = QueryTimer::new("split_trip");
+        let mut tx = self.pool.begin().await?;
+
+        let query = format!(
+            r#"
+            INSERT INTO trips (
+                device_id, local_trip_id, state, start_timestamp, end_timestamp,
+                start_location, end_location, transportation_mode, detection_source,
+                purpose, purpose_source
+            )
+            SELECT device_id, $2, 'COMPLETED', $3, end_timestamp,
+                   ST_SetSRID(ST_MakePoint($4, $5), 4326)::geography, end_location,
+                   transportation_mode, detection_source, purpose, purpose_source
+            FROM trips
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            TRIP_COLUMNS
+        );
+        let Some(second) = sqlx::query_as::<_, TripEntity>(&query)
+            .bind(input.trip_id)
+            .bind(&input.second_local_trip_id)
+            .bind(input.second_start_timestamp)
+            .bind(input.second_start_longitude) // x = lon
+            .bind(input.second_start_latitude) // y = lat
+            .fetch_optional(&mut *tx)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        sqlx::query(
+            "UPDATE movement_events SET trip_id = $2 WHERE trip_id = $1 AND timestamp >= $3",
+        )
+        .bind(input.trip_id)
+        .bind(second.id)
+        .bind(input.split_at)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            r#"
+            UPDATE locations SET trip_id = $2
+            WHERE trip_id = $1 AND captured_at >= to_timestamp($3::double precision / 1000)
+            "#,
+        )
+        .bind(input.trip_id)
+        .bind(second.id)
+        .bind(input.split_at)
+        .execute(&mut *tx)
+        .await?;
+
+        let query = format!(
+            r#"
+            UPDATE trips
+            SET end_timestamp = $2,
+                end_location = ST_SetSRID(ST_MakePoint($3, $4), 4326)::geography,
+                distance_meters = NULL,
+                duration_seconds = NULL
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            TRIP_COLUMNS
+        );
+        let first = sqlx::query_as::<_, TripEntity>(&query)
+            .bind(input.trip_id)
+            .bind(input.first_end_timestamp)
+            .bind(input.first_end_longitude) // x = lon
+            .bind(input.first_end_latitude) // y = lat
+            .fetch_one(&mut *tx)
+            .await?;
+
+        clear_path_correction(&mut tx, first.id).await?;
+
+        let edit = insert_edit(
+            &mut tx,
+            first.device_id,
+            "SPLIT",
+            &[first.id],
+            &[first.id, second.id],
+            Some(input.split_at),
+            input.api_key_id,
+        )
+        .await?;
+
+        tx.commit().await?;
+        timer.record();
+        Ok(Some((first, second, edit)))
+    }
+
+    /// Edits a trip took part in, before or after the edit, newest first.
+    pub async fn list_for_trip(&self, trip_id: Uuid) -> Result<Vec<TripEditEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("list_trip_edits");
+        let query = format!(
+            r#"
+            SELECT {}
+            FROM trip_edits
+            WHERE $1 = ANY(source_trip_ids) OR $1 = ANY(result_trip_ids)
+            ORDER BY created_at DESC
+            "#,
+            EDIT_COLUMNS
+        );
+        let result = sqlx::query_as::<_, TripEditEntity>(&query)
+            .bind(trip_id)
+            .fetch_all(&self.pool)
+            .await;
+        timer.record();
+        result
+    }
+}
+
+/// Delete a trip's path correction so it can be computed again.
+async fn clear_path_correction(
+    tx: &mut Transaction<'_, Postgres>,
+    trip_id: Uuid,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM trip_path_corrections WHERE trip_id = $1")
+        .bind(trip_id)
+        .execute(&mut **tx)
+        .await?;
+    Ok(())
+}
+
+async fn insert_edit(
+    tx: &mut Transaction<'_, Postgres>,
+    device_id: Uuid,
+    edit_type: &str,
+    source_trip_ids: &[Uuid],
+    result_trip_ids: &[Uuid],
+    split_at: Option<i64>,
+    api_key_id: Option<i64>,
+) -> Result<TripEditEntity, sqlx::Error> {
+    let query = format!(
+        r#"
+        INSERT INTO trip_edits
+            (device_id, edit_type, source_trip_ids, result_trip_ids, split_at, api_key_id)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING {}
+        "#,
+        EDIT_COLUMNS
+    );
+    sqlx::query_as::<_, TripEditEntity>(&query)
+        .bind(device_id)
+        .bind(edit_type)
+        .bind(source_trip_ids)
+        .bind(result_trip_ids)
+        .bind(split_at)
+        .bind(api_key_id)
+        .fetch_one(&mut **tx)
+        .await
+}
@@ -0,0 +1,158 @@
+//! Trip classification rule repository for database operations.
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::entities::TripRuleEntity;
+use crate::metrics::QueryTimer;
+
+const RULE_COLUMNS: &str = r#"
+    id, device_id, name, purpose, priority, start_geofence_id, end_geofence_id,
+    weekdays, departure_after_minute, departure_before_minute, enabled,
+    created_at, updated_at
+"#;
+
+/// Input data for creating or replacing a classification rule.
+#[derive(Debug, Clone)]
+pub struct TripRuleInput {
+    pub name: String,
+    pub purpose: String,
+    pub priority: i32,
+    pub start_geofence_id: Option<Uuid>,
+    pub end_geofence_id: Option<Uuid>,
+    /// ISO weekday numbers, 1 = Monday.
+    pub weekdays: Vec<i16>,
+    /// Minutes after midnight UTC.
+    pub departure_after_minute: Option<i32>,
+    pub departure_before_minute: Option<i32>,
+    pub enabled: bool,
+}
+
+/// Repository for trip classification rule database operations.
+#[derive(Clone)]
+pub struct TripRuleRepository {
+    pool: PgPool,
+}
+
+impl TripRuleRepository {
+    /// Creates a new TripRuleRepository with the given connection pool.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// List the rules of a device in evaluation order: highest priority
+    /// first, then oldest first.
+    pub async fn list_by_device(
+        &self,
+        device_id: Uuid,
+    ) -> Result<Vec<TripRuleEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("list_trip_rules");
+        let result = sqlx::query_as::<_, TripRuleEntity>(&format!(
+            r#"
+            SELECT {RULE_COLUMNS}
+            FROM trip_classification_rules
+            WHERE device_id = $1
+            ORDER BY priority DESC, created_at ASC
+            "#
+        ))
+        .bind(device_id)
+        .fetch_all(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Count the rules of a device.
+    pub async fn count_by_device(&self, device_id: Uuid) -> Result<i64, sqlx::Error> {
+        let timer = QueryTimer::new("count_trip_rules");
+        let result = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM trip_classification_rules WHERE device_id = $1",
+        )
+        .bind(device_id)
+        .fetch_one(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Create a rule for a device.
+    pub async fn create(
+        &self,
+        device_id: Uuid,
+        input: &TripRuleInput,
+    ) -> Result<TripRuleEntity, sqlx::Error> {
+        let timer = QueryTimer::new("create_trip_rule");
+        let result = sqlx::query_as::<_, TripRuleEntity>(&format!(
+            r#"
+            INSERT INTO trip_classification_rules (
+                device_id, name, purpose, priority, start_geofence_id, end_geofence_id,
+                weekdays, departure_after_minute, departure_before_minute, enabled
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING {RULE_COLUMNS}
+            "#
+        ))
+        .bind(device_id)
+        .bind(&input.name)
+        .bind(&input.purpose)
+        .bind(input.priority)
+        .bind(input.start_geofence_id)
+        .bind(input.end_geofence_id)
+        .bind(&input.weekdays)
+        .bind(input.departure_after_minute)
+        .bind(input.departure_before_minute)
+        .bind(input.enabled)
+        .fetch_one(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Replace a rule of a device. Returns `None` if the rule does not exist.
+    pub async fn update(
+        &self,
+        device_id: Uuid,
+        rule_id: Uuid,
+        input: &TripRuleInput,
+    ) -> Result<Option<TripRuleEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("update_trip_rule");
+        let result = sqlx::query_as::<_, TripRuleEntity>(&format!(
+            r#"
+            UPDATE trip_classification_rules
+            SET name = $3, purpose = $4, priority = $5, start_geofence_id = $6,
+                end_geofence_id = $7, weekdays = $8, departure_after_minute = $9,
+                departure_before_minute = $10, enabled = $11, updated_at = NOW()
+            WHERE device_id = $1 AND id = $2
+            RETURNING {RULE_COLUMNS}
+            "#
+        ))
+        .bind(device_id)
+        .bind(rule_id)
+        .bind(&input.name)
+        .bind(&input.purpose)
+        .bind(input.priority)
+        .bind(input.start_geofence_id)
+        .bind(input.end_geofence_id)
+        .bind(&input.weekdays)
+        .bind(input.departure_after_minute)
+        .bind(input.departure_before_minute)
+        .bind(input.enabled)
+        .fetch_optional(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Delete a rule of a device. Returns whether it existed.
+    pub async fn delete(&self, device_id: Uuid, rule_id: Uuid) -> Result<bool, sqlx::Error> {
+        let timer = QueryTimer::new("delete_trip_rule");
+        let result =
+            sqlx::query("DELETE FROM trip_classification_rules WHERE device_id = $1 AND id = $2")
+                .bind(device_id)
+                .bind(rule_id)
+                .execute(&self.pool)
+                .await?;
+        timer.record();
+        Ok(result.rows_affected() > 0)
+    }
+}
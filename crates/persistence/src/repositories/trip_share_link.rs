@@ -0,0 +1,125 @@
+//! Trip share link repository for database operations.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::entities::TripShareLinkEntity;
+use crate::metrics::QueryTimer;
+
+/// Repository for trip share link database operations.
+#[derive(Clone)]
+pub struct TripShareLinkRepository {
+    pool: PgPool,
+}
+
+impl TripShareLinkRepository {
+    /// Creates a new TripShareLinkRepository with the given connection pool.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Create a share link for a trip.
+    pub async fn create(
+        &self,
+        trip_id: Uuid,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<TripShareLinkEntity, sqlx::Error> {
+        let timer = QueryTimer::new("create_trip_share_link");
+        let result = sqlx::query_as::<_, TripShareLinkEntity>(
+            r#"
+            INSERT INTO trip_share_links (trip_id, token_hash, expires_at)
+            VALUES ($1, $2, $3)
+            RETURNING id, trip_id, token_hash, expires_at, revoked_at, created_at
+            "#,
+        )
+        .bind(trip_id)
+        .bind(token_hash)
+        .bind(expires_at)
+        .fetch_one(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Find a link that is neither revoked nor expired by its token hash.
+    pub async fn find_active_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<TripShareLinkEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("find_active_trip_share_link");
+        let result = sqlx::query_as::<_, TripShareLinkEntity>(
+            r#"
+            SELECT id, trip_id, token_hash, expires_at, revoked_at, created_at
+            FROM trip_share_links
+            WHERE token_hash = $1 AND revoked_at IS NULL AND expires_at > NOW()
+            "#,
+        )
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// List the links of a trip, newest first.
+    pub async fn list_by_trip_id(
+        &self,
+        trip_id: Uuid,
+    ) -> Result<Vec<TripShareLinkEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("list_trip_share_links");
+        let result = sqlx::query_as::<_, TripShareLinkEntity>(
+            r#"
+            SELECT id, trip_id, token_hash, expires_at, revoked_at, created_at
+            FROM trip_share_links
+            WHERE trip_id = $1
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(trip_id)
+        .fetch_all(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Revoke a link of a trip. Returns the link, or `None` if the trip has
+    /// no such link. Revoking a revoked link keeps its original revocation time.
+    pub async fn revoke(
+        &self,
+        trip_id: Uuid,
+        share_id: Uuid,
+    ) -> Result<Option<TripShareLinkEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("revoke_trip_share_link");
+        let result = sqlx::query_as::<_, TripShareLinkEntity>(
+            r#"
+            UPDATE trip_share_links
+            SET revoked_at = COALESCE(revoked_at, NOW())
+            WHERE id = $1 AND trip_id = $2
+            RETURNING id, trip_id, token_hash, expires_at, revoked_at, created_at
+            "#,
+        )
+        .bind(share_id)
+        .bind(trip_id)
+        .fetch_optional(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Delete links that expired before `cutoff`. Returns the number deleted.
+    pub async fn delete_expired_before(&self, cutoff: DateTime<Utc>) -> Result<u64, sqlx::Error> {
+        let timer = QueryTimer::new("delete_expired_trip_share_links");
+        let result = sqlx::query(
+            r#"
+            DELETE FROM trip_share_links WHERE expires_at < $1
+            "#,
+        )
+        .bind(cutoff)
+        .execute(&self.pool)
+        .await?;
+        timer.record();
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,91 @@
+//! Trip speed violation repository for database operations.
+
+use domain::services::DetectedSpeedViolation;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::entities::TripSpeedViolationEntity;
+use crate::metrics::QueryTimer;
+
+/// Repository for trip speed violation database operations.
+#[derive(Clone)]
+pub struct TripSpeedViolationRepository {
+    pool: PgPool,
+}
+
+impl TripSpeedViolationRepository {
+    /// Creates a new TripSpeedViolationRepository with the given connection pool.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Replace the speed violations of a trip with newly detected ones.
+    pub async fn replace_for_trip(
+        &self,
+        trip_id: Uuid,
+        device_id: Uuid,
+        violations: &[DetectedSpeedViolation],
+    ) -> Result<Vec<TripSpeedViolationEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("replace_trip_speed_violations");
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM trip_speed_violations WHERE trip_id = $1")
+            .bind(trip_id)
+            .execute(&mut *tx)
+            .await?;
+
+        let mut stored = Vec::with_capacity(violations.len());
+        for violation in violations {
+            let entity = sqlx::query_as::<_, TripSpeedViolationEntity>(
+                r#"
+                INSERT INTO trip_speed_violations (
+                    trip_id, device_id, started_at, ended_at,
+                    start_latitude, start_longitude, end_latitude, end_longitude,
+                    speed_limit_kmh, max_speed_kmh, avg_speed_kmh, sample_count
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                RETURNING *
+                "#,
+            )
+            .bind(trip_id)
+            .bind(device_id)
+            .bind(violation.started_at)
+            .bind(violation.ended_at)
+            .bind(violation.start_latitude)
+            .bind(violation.start_longitude)
+            .bind(violation.end_latitude)
+            .bind(violation.end_longitude)
+            .bind(violation.speed_limit_kmh)
+            .bind(violation.max_speed_kmh)
+            .bind(violation.avg_speed_kmh)
+            .bind(violation.sample_count as i32)
+            .fetch_one(&mut *tx)
+            .await?;
+            stored.push(entity);
+        }
+
+        tx.commit().await?;
+        timer.record();
+        Ok(stored)
+    }
+
+    /// List the speed violations of a trip, oldest first.
+    pub async fn list_for_trip(
+        &self,
+        trip_id: Uuid,
+    ) -> Result<Vec<TripSpeedViolationEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("list_trip_speed_violations");
+        let result = sqlx::query_as::<_, TripSpeedViolationEntity>(
+            r#"
+            SELECT * FROM trip_speed_violations
+            WHERE trip_id = $1
+            ORDER BY started_at
+            "#,
+        )
+        .bind(trip_id)
+        .fetch_all(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+}
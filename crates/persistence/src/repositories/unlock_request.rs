@@ -1,10 +1,12 @@
 //! Unlock request repository for database operations.
 
+use domain::models::OutboxMessage;
 use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::entities::{UnlockRequestEntity, UnlockRequestStatusDb, UnlockRequestWithDetailsEntity};
 use crate::metrics::QueryTimer;
+use crate::repositories::event_outbox;
 
 /// Repository for unlock request-related database operations.
 #[derive(Clone)]
@@ -201,15 +203,21 @@ impl UnlockRequestRepository {
     }
 
     /// Respond to an unlock request (approve or deny).
+    ///
+    /// With `notify_as`, a push notification of the response to the device,
+    /// naming `notify_as` as the decider, is queued in the outbox in the same
+    /// transaction.
     pub async fn respond(
         &self,
         id: Uuid,
         status: UnlockRequestStatusDb,
         responded_by: Uuid,
         response_note: Option<&str>,
+        notify_as: Option<&str>,
     ) -> Result<Option<UnlockRequestEntity>, sqlx::Error> {
         let timer = QueryTimer::new("respond_to_unlock_request");
-        let result = sqlx::query_as::<_, UnlockRequestEntity>(
+        let mut tx = self.pool.begin().await?;
+        let entity = sqlx::query_as::<_, UnlockRequestEntity>(
             r#"
             UPDATE unlock_requests
             SET status = $2, responded_by = $3, response_note = $4, responded_at = NOW(), updated_at = NOW()
@@ -222,10 +230,26 @@ impl UnlockRequestRepository {
         .bind(status)
         .bind(responded_by)
         .bind(response_note)
-        .fetch_optional(&self.pool)
-        .await;
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if let (Some(entity), Some(decided_by)) = (&entity, notify_as) {
+            event_outbox::enqueue(
+                &mut tx,
+                &OutboxMessage::UnlockRequestResponse {
+                    device_id: entity.device_id,
+                    request_id: entity.id,
+                    setting_key: entity.setting_key.clone(),
+                    status: String::from(entity.status),
+                    note: entity.response_note.clone(),
+                    decided_by: decided_by.to_string(),
+                },
+            )
+            .await?;
+        }
+        tx.commit().await?;
         timer.record();
-        result
+        Ok(entity)
     }
 
     /// Expire old pending requests.
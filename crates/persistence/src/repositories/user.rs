@@ -107,6 +107,44 @@ impl UserRepository {
         Ok(())
     }
 
+    /// Get a user's preferred unit system ("metric" or "imperial").
+    pub async fn get_unit_system(&self, user_id: Uuid) -> Result<Option<String>, sqlx::Error> {
+        let timer = QueryTimer::new("get_user_unit_system");
+        let result = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT unit_system FROM users WHERE id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Get the preferred unit system of the user owning a device.
+    ///
+    /// Returns `None` if the device is not linked to a user.
+    pub async fn get_unit_system_for_device(
+        &self,
+        device_id: Uuid,
+    ) -> Result<Option<String>, sqlx::Error> {
+        let timer = QueryTimer::new("get_device_owner_unit_system");
+        let result = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT u.unit_system
+            FROM devices d
+            JOIN users u ON u.id = d.owner_user_id
+            WHERE d.device_id = $1
+            "#,
+        )
+        .bind(device_id)
+        .fetch_optional(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
     /// Find an OAuth account by provider and provider user ID.
     pub async fn find_oauth_account(
         &self,
@@ -381,6 +419,72 @@ impl UserRepository {
         timer.record();
         Ok(result.rows_affected() as i64)
     }
+
+    /// Count password hashes grouped by scheme.
+    ///
+    /// The scheme is the PHC string without salt and hash, i.e. the algorithm
+    /// and its parameters. Each group carries one sample hash so callers can
+    /// inspect the parameters.
+    pub async fn password_hash_schemes(&self) -> Result<Vec<PasswordHashSchemeRow>, sqlx::Error> {
+        let timer = QueryTimer::new("password_hash_schemes");
+        let result = sqlx::query_as::<_, PasswordHashSchemeRow>(
+            r#"
+            SELECT regexp_replace(password_hash, '\$[^$]*\$[^$]*$', '') AS scheme,
+                   COUNT(*) AS account_count,
+                   MIN(password_hash) AS sample_hash
+            FROM users
+            WHERE password_hash IS NOT NULL
+            GROUP BY 1
+            ORDER BY account_count DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// List users whose password hash uses one of the given schemes,
+    /// least recently logged in first.
+    pub async fn list_users_by_password_scheme(
+        &self,
+        schemes: &[String],
+        limit: i64,
+    ) -> Result<Vec<PasswordSchemeUserRow>, sqlx::Error> {
+        let timer = QueryTimer::new("list_users_by_password_scheme");
+        let result = sqlx::query_as::<_, PasswordSchemeUserRow>(
+            r#"
+            SELECT id, email, last_login_at
+            FROM users
+            WHERE password_hash IS NOT NULL
+              AND regexp_replace(password_hash, '\$[^$]*\$[^$]*$', '') = ANY($1)
+            ORDER BY last_login_at ASC NULLS FIRST, email
+            LIMIT $2
+            "#,
+        )
+        .bind(schemes)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+}
+
+/// Row for password hash scheme counts.
+#[derive(Debug, sqlx::FromRow)]
+pub struct PasswordHashSchemeRow {
+    pub scheme: String,
+    pub account_count: i64,
+    pub sample_hash: String,
+}
+
+/// Row for users listed by password hash scheme.
+#[derive(Debug, sqlx::FromRow)]
+pub struct PasswordSchemeUserRow {
+    pub id: Uuid,
+    pub email: String,
+    pub last_login_at: Option<DateTime<Utc>>,
 }
 
 /// Row for MFA status queries.
@@ -1,5 +1,6 @@
 //! Webhook repository for database operations.
 
+use domain::models::{WebhookListFilter, WebhookOwner, WebhookPayloadFormat};
 use sqlx::PgPool;
 use uuid::Uuid;
 
@@ -19,27 +20,49 @@ impl WebhookRepository {
     }
 
     /// Create a new webhook.
+    ///
+    /// `device_ids` limits a user-owned webhook to some of the user's devices.
+    #[allow(clippy::too_many_arguments)]
     pub async fn create(
         &self,
-        owner_device_id: Uuid,
+        owner: WebhookOwner,
+        device_ids: Option<&[Uuid]>,
         name: &str,
         target_url: &str,
         secret: &str,
         enabled: bool,
+        retry_policy: Option<serde_json::Value>,
+        payload_template: Option<&serde_json::Value>,
+        payload_format: WebhookPayloadFormat,
+        max_deliveries_per_minute: Option<i32>,
+        batch_window_secs: Option<i32>,
+        batch_max_events: Option<i32>,
     ) -> Result<WebhookEntity, sqlx::Error> {
+        let (owner_device_id, owner_user_id) = owner_columns(owner);
         let timer = QueryTimer::new("create_webhook");
         let result = sqlx::query_as::<_, WebhookEntity>(
             r#"
-            INSERT INTO webhooks (owner_device_id, name, target_url, secret, enabled)
-            VALUES ($1, $2, $3, $4, $5)
+            INSERT INTO webhooks (owner_device_id, owner_user_id, device_ids, name, target_url,
+                                  secret, enabled, retry_policy, payload_template,
+                                  payload_format, max_deliveries_per_minute,
+                                  batch_window_secs, batch_max_events)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
             RETURNING *
             "#,
         )
         .bind(owner_device_id)
+        .bind(owner_user_id)
+        .bind(device_ids)
         .bind(name)
         .bind(target_url)
         .bind(secret)
         .bind(enabled)
+        .bind(retry_policy)
+        .bind(payload_template)
+        .bind(payload_format.as_str())
+        .bind(max_deliveries_per_minute)
+        .bind(batch_window_secs)
+        .bind(batch_max_events)
         .fetch_one(&self.pool)
         .await;
         timer.record();
@@ -64,40 +87,54 @@ impl WebhookRepository {
         result
     }
 
-    /// Find all webhooks for a device.
-    pub async fn find_by_owner_device_id(
-        &self,
-        owner_device_id: Uuid,
-    ) -> Result<Vec<WebhookEntity>, sqlx::Error> {
-        let timer = QueryTimer::new("find_webhooks_by_device");
+    /// List webhooks matching a list filter, newest first.
+    ///
+    /// [`WebhookListFilter::Device`] returns the webhooks that receive events
+    /// of the device: its own and those of its owner that include it.
+    pub async fn list(&self, filter: WebhookListFilter) -> Result<Vec<WebhookEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("list_webhooks");
+        let (owner_device_id, owner_user_id, device_id) = match filter {
+            WebhookListFilter::OwnerDevice(id) => (Some(id), None, None),
+            WebhookListFilter::OwnerUser(id) => (None, Some(id), None),
+            WebhookListFilter::Device(id) => (None, None, Some(id)),
+        };
         let result = sqlx::query_as::<_, WebhookEntity>(
             r#"
-            SELECT * FROM webhooks
-            WHERE owner_device_id = $1
-            ORDER BY created_at DESC
+            SELECT w.* FROM webhooks w
+            WHERE ($1::UUID IS NULL OR w.owner_device_id = $1)
+              AND ($2::UUID IS NULL OR w.owner_user_id = $2)
+              AND ($3::UUID IS NULL
+                   OR w.owner_device_id = $3
+                   OR (w.owner_user_id = (SELECT owner_user_id FROM devices WHERE device_id = $3)
+                       AND (w.device_ids IS NULL OR $3 = ANY(w.device_ids))))
+            ORDER BY w.created_at DESC
             "#,
         )
         .bind(owner_device_id)
+        .bind(owner_user_id)
+        .bind(device_id)
         .fetch_all(&self.pool)
         .await;
         timer.record();
         result
     }
 
-    /// Find webhook by device ID and name (for uniqueness check).
-    pub async fn find_by_device_and_name(
+    /// Find webhook by owner and name (for uniqueness check).
+    pub async fn find_by_owner_and_name(
         &self,
-        owner_device_id: Uuid,
+        owner: WebhookOwner,
         name: &str,
     ) -> Result<Option<WebhookEntity>, sqlx::Error> {
-        let timer = QueryTimer::new("find_webhook_by_device_and_name");
+        let (owner_device_id, owner_user_id) = owner_columns(owner);
+        let timer = QueryTimer::new("find_webhook_by_owner_and_name");
         let result = sqlx::query_as::<_, WebhookEntity>(
             r#"
             SELECT * FROM webhooks
-            WHERE owner_device_id = $1 AND name = $2
+            WHERE (owner_device_id = $1 OR owner_user_id = $2) AND name = $3
             "#,
         )
         .bind(owner_device_id)
+        .bind(owner_user_id)
         .bind(name)
         .fetch_optional(&self.pool)
         .await;
@@ -105,18 +142,17 @@ impl WebhookRepository {
         result
     }
 
-    /// Count webhooks for a device.
-    pub async fn count_by_owner_device_id(
-        &self,
-        owner_device_id: Uuid,
-    ) -> Result<i64, sqlx::Error> {
-        let timer = QueryTimer::new("count_webhooks_by_device");
+    /// Count webhooks of an owner.
+    pub async fn count_by_owner(&self, owner: WebhookOwner) -> Result<i64, sqlx::Error> {
+        let (owner_device_id, owner_user_id) = owner_columns(owner);
+        let timer = QueryTimer::new("count_webhooks_by_owner");
         let count: (i64,) = sqlx::query_as(
             r#"
-            SELECT COUNT(*) FROM webhooks WHERE owner_device_id = $1
+            SELECT COUNT(*) FROM webhooks WHERE owner_device_id = $1 OR owner_user_id = $2
             "#,
         )
         .bind(owner_device_id)
+        .bind(owner_user_id)
         .fetch_one(&self.pool)
         .await?;
         timer.record();
@@ -125,6 +161,12 @@ impl WebhookRepository {
 
     /// Update a webhook (partial update).
     /// Only provided fields are updated; None values are preserved.
+    /// `all_devices` clears the device limit of a user-owned webhook and
+    /// `default_payload` its payload template, and a
+    /// `max_deliveries_per_minute` of 0 its rate limit, and a
+    /// `batch_window_secs` of 0 its batching.
+    /// Enabling a webhook clears its failure streak and auto-disable time.
+    #[allow(clippy::too_many_arguments)]
     pub async fn update(
         &self,
         webhook_id: Uuid,
@@ -132,6 +174,15 @@ impl WebhookRepository {
         target_url: Option<&str>,
         secret: Option<&str>,
         enabled: Option<bool>,
+        retry_policy: Option<serde_json::Value>,
+        device_ids: Option<&[Uuid]>,
+        all_devices: bool,
+        payload_template: Option<&serde_json::Value>,
+        default_payload: bool,
+        payload_format: Option<WebhookPayloadFormat>,
+        max_deliveries_per_minute: Option<i32>,
+        batch_window_secs: Option<i32>,
+        batch_max_events: Option<i32>,
     ) -> Result<Option<WebhookEntity>, sqlx::Error> {
         let timer = QueryTimer::new("update_webhook");
 
@@ -142,6 +193,19 @@ impl WebhookRepository {
                 target_url = COALESCE($3, target_url),
                 secret = COALESCE($4, secret),
                 enabled = COALESCE($5, enabled),
+                failure_streak = CASE WHEN $5 THEN 0 ELSE failure_streak END,
+                auto_disabled_at = CASE WHEN $5 THEN NULL ELSE auto_disabled_at END,
+                retry_policy = COALESCE($6, retry_policy),
+                device_ids = CASE WHEN $8 THEN NULL ELSE COALESCE($7, device_ids) END,
+                payload_template = CASE WHEN $10 THEN NULL
+                                        ELSE COALESCE($9, payload_template) END,
+                payload_format = COALESCE($11, payload_format),
+                max_deliveries_per_minute = CASE WHEN $12 = 0 THEN NULL
+                                                 ELSE COALESCE($12, max_deliveries_per_minute) END,
+                batch_window_secs = CASE WHEN $13 = 0 THEN NULL
+                                         ELSE COALESCE($13, batch_window_secs) END,
+                batch_max_events = CASE WHEN $13 = 0 THEN NULL
+                                        ELSE COALESCE($14, batch_max_events) END,
                 updated_at = NOW()
             WHERE webhook_id = $1
             RETURNING *
@@ -152,12 +216,81 @@ impl WebhookRepository {
         .bind(target_url)
         .bind(secret)
         .bind(enabled)
+        .bind(retry_policy)
+        .bind(device_ids)
+        .bind(all_devices)
+        .bind(payload_template)
+        .bind(default_payload)
+        .bind(payload_format.map(|f| f.as_str()))
+        .bind(max_deliveries_per_minute)
+        .bind(batch_window_secs)
+        .bind(batch_max_events)
+        .fetch_optional(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Replace a webhook's stored secret.
+    pub async fn set_secret(
+        &self,
+        webhook_id: Uuid,
+        secret: &str,
+    ) -> Result<Option<WebhookEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("set_webhook_secret");
+        let result = sqlx::query_as::<_, WebhookEntity>(
+            r#"
+            UPDATE webhooks SET secret = $2, updated_at = NOW()
+            WHERE webhook_id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(webhook_id)
+        .bind(secret)
         .fetch_optional(&self.pool)
         .await;
         timer.record();
         result
     }
 
+    /// Secrets not stored in the form starting with `sealed_prefix`, by webhook.
+    pub async fn list_unsealed_secrets(
+        &self,
+        sealed_prefix: &str,
+    ) -> Result<Vec<(Uuid, String)>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+            SELECT webhook_id, secret FROM webhooks
+            WHERE NOT starts_with(secret, $1)
+            "#,
+        )
+        .bind(sealed_prefix)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Replace a stored secret unless it changed since it was read.
+    /// Returns whether the secret was replaced.
+    pub async fn replace_secret(
+        &self,
+        webhook_id: Uuid,
+        current: &str,
+        secret: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE webhooks SET secret = $3
+            WHERE webhook_id = $1 AND secret = $2
+            "#,
+        )
+        .bind(webhook_id)
+        .bind(current)
+        .bind(secret)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
     /// Delete a webhook.
     /// Returns the number of rows deleted (0 or 1).
     pub async fn delete(&self, webhook_id: Uuid) -> Result<u64, sqlx::Error> {
@@ -193,31 +326,34 @@ impl WebhookRepository {
         Ok(result.rows_affected())
     }
 
-    /// Find all enabled webhooks for a device that are available for delivery.
-    /// Excludes webhooks with open circuit breakers.
-    pub async fn find_enabled_by_owner_device_id(
+    /// Find all enabled webhooks receiving events of a device that are
+    /// available for delivery: the device's own webhooks and those of its
+    /// owner that include it. Excludes webhooks with open circuit breakers.
+    pub async fn find_enabled_for_device(
         &self,
-        owner_device_id: Uuid,
+        device_id: Uuid,
     ) -> Result<Vec<WebhookEntity>, sqlx::Error> {
         let timer = QueryTimer::new("find_enabled_webhooks_by_device");
         let result = sqlx::query_as::<_, WebhookEntity>(
             r#"
-            SELECT * FROM webhooks
-            WHERE owner_device_id = $1
-              AND enabled = true
-              AND (circuit_open_until IS NULL OR circuit_open_until <= NOW())
-            ORDER BY created_at DESC
+            SELECT w.* FROM webhooks w
+            WHERE (w.owner_device_id = $1
+                   OR (w.owner_user_id = (SELECT owner_user_id FROM devices WHERE device_id = $1)
+                       AND (w.device_ids IS NULL OR $1 = ANY(w.device_ids))))
+              AND w.enabled = true
+              AND (w.circuit_open_until IS NULL OR w.circuit_open_until <= NOW())
+            ORDER BY w.created_at DESC
             "#,
         )
-        .bind(owner_device_id)
+        .bind(device_id)
         .fetch_all(&self.pool)
         .await;
         timer.record();
         result
     }
 
-    /// Increment consecutive failures counter for a webhook.
-    /// Returns the new failure count.
+    /// Increment the consecutive failures counter and failure streak of a
+    /// webhook. Returns the new consecutive failure count.
     pub async fn increment_consecutive_failures(
         &self,
         webhook_id: Uuid,
@@ -227,6 +363,7 @@ impl WebhookRepository {
             r#"
             UPDATE webhooks
             SET consecutive_failures = consecutive_failures + 1,
+                failure_streak = failure_streak + 1,
                 updated_at = NOW()
             WHERE webhook_id = $1
             RETURNING consecutive_failures
@@ -246,6 +383,7 @@ impl WebhookRepository {
             r#"
             UPDATE webhooks
             SET consecutive_failures = 0,
+                failure_streak = 0,
                 circuit_open_until = NULL,
                 updated_at = NOW()
             WHERE webhook_id = $1
@@ -285,6 +423,80 @@ impl WebhookRepository {
         Ok(())
     }
 
+    /// Convert a device-owned webhook into a webhook owned by `user_id` that
+    /// receives events of all of the user's devices.
+    ///
+    /// Returns None if the webhook does not exist or is not device-owned.
+    pub async fn convert_to_user_owned(
+        &self,
+        webhook_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<WebhookEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("convert_webhook_to_user_owned");
+        let result = sqlx::query_as::<_, WebhookEntity>(
+            r#"
+            UPDATE webhooks
+            SET owner_user_id = $2,
+                owner_device_id = NULL,
+                device_ids = NULL,
+                updated_at = NOW()
+            WHERE webhook_id = $1 AND owner_device_id IS NOT NULL
+            RETURNING *
+            "#,
+        )
+        .bind(webhook_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Disable enabled webhooks whose failure streak reached `threshold`.
+    /// Returns the webhooks that were disabled.
+    pub async fn disable_failing(&self, threshold: i32) -> Result<Vec<WebhookEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("disable_failing_webhooks");
+        let result = sqlx::query_as::<_, WebhookEntity>(
+            r#"
+            UPDATE webhooks
+            SET enabled = false,
+                auto_disabled_at = NOW(),
+                updated_at = NOW()
+            WHERE enabled = true AND failure_streak >= $1
+            RETURNING *
+            "#,
+        )
+        .bind(threshold)
+        .fetch_all(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Enable a webhook with a fresh start: failure counters, circuit breaker
+    /// and auto-disable time are cleared.
+    pub async fn reenable(&self, webhook_id: Uuid) -> Result<Option<WebhookEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("reenable_webhook");
+        let result = sqlx::query_as::<_, WebhookEntity>(
+            r#"
+            UPDATE webhooks
+            SET enabled = true,
+                consecutive_failures = 0,
+                failure_streak = 0,
+                circuit_open_until = NULL,
+                auto_disabled_at = NULL,
+                updated_at = NOW()
+            WHERE webhook_id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(webhook_id)
+        .fetch_optional(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
     /// Close the circuit breaker for a webhook (reset circuit_open_until to NULL).
     pub async fn close_circuit(&self, webhook_id: Uuid) -> Result<(), sqlx::Error> {
         let timer = QueryTimer::new("close_webhook_circuit");
@@ -304,6 +516,14 @@ impl WebhookRepository {
     }
 }
 
+/// Owner column values (`owner_device_id`, `owner_user_id`) of an owner.
+fn owner_columns(owner: WebhookOwner) -> (Option<Uuid>, Option<Uuid>) {
+    match owner {
+        WebhookOwner::Device(device_id) => (Some(device_id), None),
+        WebhookOwner::User(user_id) => (None, Some(user_id)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -0,0 +1,136 @@
+//! Webhook batch repository.
+//!
+//! Webhooks in batched delivery mode collect their events here until the
+//! batch window ends or the batch is full. The events are then moved into a
+//! single delivery whose payload is the JSON array of their payloads, so
+//! from there on a batch is delivered and retried like any other delivery.
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::entities::WebhookDeliveryEntity;
+use crate::metrics::QueryTimer;
+
+/// Event type of batch deliveries in the delivery log.
+pub const BATCH_EVENT_TYPE: &str = "batch";
+
+/// Repository for webhook batch operations.
+pub struct WebhookBatchRepository {
+    pool: PgPool,
+}
+
+impl WebhookBatchRepository {
+    /// Create a new repository instance.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Add an event payload to a webhook's open batch.
+    ///
+    /// Returns the number of events in the batch.
+    pub async fn add(
+        &self,
+        webhook_id: Uuid,
+        event_id: Uuid,
+        payload: &serde_json::Value,
+    ) -> Result<i64, sqlx::Error> {
+        let timer = QueryTimer::new("add_webhook_batch_event");
+        sqlx::query(
+            r#"
+            INSERT INTO webhook_batch_events (webhook_id, event_id, payload)
+            VALUES ($1, $2, $3)
+            "#,
+        )
+        .bind(webhook_id)
+        .bind(event_id)
+        .bind(payload)
+        .execute(&self.pool)
+        .await?;
+
+        let count: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM webhook_batch_events WHERE webhook_id = $1")
+                .bind(webhook_id)
+                .fetch_one(&self.pool)
+                .await?;
+        timer.record();
+        Ok(count.0)
+    }
+
+    /// Webhooks whose batch is due, with their batch size.
+    ///
+    /// A batch is due when its oldest event is older than the batch window,
+    /// when it is full, or when the webhook no longer batches.
+    /// `default_max_events` applies to webhooks without a batch size.
+    pub async fn due_webhooks(
+        &self,
+        default_max_events: i32,
+    ) -> Result<Vec<(Uuid, i32)>, sqlx::Error> {
+        let timer = QueryTimer::new("find_due_webhook_batches");
+        let result = sqlx::query_as(
+            r#"
+            SELECT b.webhook_id, COALESCE(w.batch_max_events, $1)
+            FROM webhook_batch_events b
+            JOIN webhooks w ON w.webhook_id = b.webhook_id
+            GROUP BY b.webhook_id, w.batch_window_secs, w.batch_max_events
+            HAVING w.batch_window_secs IS NULL
+                OR MIN(b.created_at) <= NOW() - make_interval(secs => w.batch_window_secs)
+                OR COUNT(*) >= COALESCE(w.batch_max_events, $1)
+            "#,
+        )
+        .bind(default_max_events)
+        .fetch_all(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Move up to `max_events` of a webhook's batched events, oldest first,
+    /// into a pending delivery.
+    ///
+    /// Returns None if the batch is empty, e.g. because a concurrent flush
+    /// took it.
+    pub async fn take_into_delivery(
+        &self,
+        webhook_id: Uuid,
+        max_events: i32,
+    ) -> Result<Option<WebhookDeliveryEntity>, sqlx::Error> {
+        let timer = QueryTimer::new("take_webhook_batch");
+        let result = sqlx::query_as::<_, WebhookDeliveryEntity>(
+            r#"
+            WITH taken AS (
+                DELETE FROM webhook_batch_events
+                WHERE id IN (
+                    SELECT id FROM webhook_batch_events
+                    WHERE webhook_id = $1
+                    ORDER BY id
+                    LIMIT $2
+                    FOR UPDATE SKIP LOCKED
+                )
+                RETURNING id, payload
+            )
+            INSERT INTO webhook_deliveries (webhook_id, event_type, payload, status, attempts)
+            SELECT $1, $3, jsonb_agg(payload ORDER BY id), 'pending', 0
+            FROM taken
+            HAVING COUNT(*) > 0
+            RETURNING id, delivery_id, webhook_id, event_id, event_type, payload, status, attempts,
+                      last_attempt_at, next_retry_at, response_code, error_message, latency_ms,
+                      response_body, created_at
+            "#,
+        )
+        .bind(webhook_id)
+        .bind(i64::from(max_events))
+        .bind(BATCH_EVENT_TYPE)
+        .fetch_optional(&self.pool)
+        .await;
+        timer.record();
+        result
+    }
+
+    /// Number of events waiting in open batches.
+    pub async fn count_pending(&self) -> Result<i64, sqlx::Error> {
+        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM webhook_batch_events")
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(count.0)
+    }
+}
@@ -3,13 +3,14 @@
 //! Story 15.3: Webhook Delivery Logging and Retry
 //! Provides data access for webhook delivery tracking and retry management.
 
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
+use domain::models::WebhookRetryPolicy;
+use metrics::counter;
 use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::entities::webhook_delivery::{
-    WebhookDeliveryEntity, MAX_RETRY_ATTEMPTS, RETRY_BACKOFF_SECONDS, STATUS_FAILED,
-    STATUS_PENDING, STATUS_SUCCESS,
+    WebhookDeadLetterEntity, WebhookDeliveryEntity, STATUS_FAILED, STATUS_PENDING, STATUS_SUCCESS,
 };
 
 /// Repository for webhook delivery operations.
@@ -36,7 +37,8 @@ impl WebhookDeliveryRepository {
             INSERT INTO webhook_deliveries (webhook_id, event_id, event_type, payload, status, attempts)
             VALUES ($1, $2, $3, $4, 'pending', 0)
             RETURNING id, delivery_id, webhook_id, event_id, event_type, payload, status, attempts,
-                      last_attempt_at, next_retry_at, response_code, error_message, created_at
+                      last_attempt_at, next_retry_at, response_code, error_message, latency_ms,
+                      response_body, created_at
             "#,
         )
         .bind(webhook_id)
@@ -50,12 +52,22 @@ impl WebhookDeliveryRepository {
     }
 
     /// Update delivery status after an attempt.
+    ///
+    /// Failed deliveries are rescheduled according to the webhook's retry
+    /// policy until its attempts run out or the response code is not retried;
+    /// then they are dead-lettered.
+    /// `latency_ms` and `response_body` describe the attempt's response, if
+    /// the target answered.
+    #[allow(clippy::too_many_arguments)]
     pub async fn update_attempt(
         &self,
         delivery_id: Uuid,
         success: bool,
         response_code: Option<i32>,
         error_message: Option<&str>,
+        latency_ms: Option<i32>,
+        response_body: Option<&str>,
+        policy: &WebhookRetryPolicy,
     ) -> Result<WebhookDeliveryEntity, sqlx::Error> {
         let now = Utc::now();
 
@@ -67,24 +79,8 @@ impl WebhookDeliveryRepository {
                 .await?;
 
         let new_attempts = current.0 + 1;
-
-        let (new_status, next_retry) = if success {
-            (STATUS_SUCCESS.to_string(), None)
-        } else if new_attempts >= MAX_RETRY_ATTEMPTS {
-            (STATUS_FAILED.to_string(), None)
-        } else {
-            // Calculate next retry time based on backoff schedule
-            // Use (new_attempts - 1) as index since:
-            // - Attempt 1 failed -> use index 0 (immediate retry)
-            // - Attempt 2 failed -> use index 1 (60s)
-            // - Attempt 3 failed -> use index 2 (300s)
-            // - Attempt 4 failed -> use index 3 (900s)
-            let backoff_index =
-                ((new_attempts - 1).max(0) as usize).min(RETRY_BACKOFF_SECONDS.len() - 1);
-            let backoff_seconds = RETRY_BACKOFF_SECONDS[backoff_index];
-            let next_retry_at = now + Duration::seconds(backoff_seconds);
-            (STATUS_PENDING.to_string(), Some(next_retry_at))
-        };
+        let (new_status, next_retry) =
+            next_delivery_state(policy, new_attempts, success, response_code, now);
 
         let entity = sqlx::query_as::<_, WebhookDeliveryEntity>(
             r#"
@@ -94,10 +90,13 @@ impl WebhookDeliveryRepository {
                 last_attempt_at = $4,
                 next_retry_at = $5,
                 response_code = $6,
-                error_message = $7
+                error_message = $7,
+                latency_ms = $8,
+                response_body = $9
             WHERE delivery_id = $1
             RETURNING id, delivery_id, webhook_id, event_id, event_type, payload, status, attempts,
-                      last_attempt_at, next_retry_at, response_code, error_message, created_at
+                      last_attempt_at, next_retry_at, response_code, error_message, latency_ms,
+                      response_body, created_at
             "#,
         )
         .bind(delivery_id)
@@ -107,12 +106,144 @@ impl WebhookDeliveryRepository {
         .bind(next_retry)
         .bind(response_code)
         .bind(error_message)
+        .bind(latency_ms)
+        .bind(response_body)
         .fetch_one(&self.pool)
         .await?;
 
+        if entity.status == STATUS_FAILED {
+            self.dead_letter(&entity).await?;
+        }
+
+        Ok(entity)
+    }
+
+    /// Copy a delivery that failed after its final retry to the dead-letter
+    /// queue. A delivery is dead-lettered at most once.
+    async fn dead_letter(&self, delivery: &WebhookDeliveryEntity) -> Result<(), sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO webhook_dead_letters (delivery_id, webhook_id, event_id, event_type, payload,
+                                              attempts, response_code, error_message,
+                                              delivery_created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT (delivery_id) DO NOTHING
+            "#,
+        )
+        .bind(delivery.delivery_id)
+        .bind(delivery.webhook_id)
+        .bind(delivery.event_id)
+        .bind(&delivery.event_type)
+        .bind(&delivery.payload)
+        .bind(delivery.attempts)
+        .bind(delivery.response_code)
+        .bind(&delivery.error_message)
+        .bind(delivery.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() > 0 {
+            counter!("webhook_dead_letters_total").increment(1);
+        }
+        Ok(())
+    }
+
+    /// List the dead letters of a webhook, newest first.
+    pub async fn list_dead_letters(
+        &self,
+        webhook_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<WebhookDeadLetterEntity>, sqlx::Error> {
+        let entities = sqlx::query_as::<_, WebhookDeadLetterEntity>(
+            r#"
+            SELECT id, dead_letter_id, delivery_id, webhook_id, event_id, event_type, payload,
+                   attempts, response_code, error_message, delivery_created_at, created_at
+            FROM webhook_dead_letters
+            WHERE webhook_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(webhook_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entities)
+    }
+
+    /// Count the dead letters of a webhook.
+    pub async fn count_dead_letters(&self, webhook_id: Uuid) -> Result<i64, sqlx::Error> {
+        let count: (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*) FROM webhook_dead_letters
+            WHERE webhook_id = $1
+            "#,
+        )
+        .bind(webhook_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count.0)
+    }
+
+    /// Count the dead letters of all webhooks.
+    pub async fn dead_letter_queue_depth(&self) -> Result<i64, sqlx::Error> {
+        let count: (i64,) = sqlx::query_as(r#"SELECT COUNT(*) FROM webhook_dead_letters"#)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(count.0)
+    }
+
+    /// Move a dead letter back into the delivery queue as a new pending
+    /// delivery with the same payload.
+    ///
+    /// Returns None if the webhook has no such dead letter.
+    pub async fn requeue_dead_letter(
+        &self,
+        webhook_id: Uuid,
+        dead_letter_id: Uuid,
+    ) -> Result<Option<WebhookDeliveryEntity>, sqlx::Error> {
+        let entity = sqlx::query_as::<_, WebhookDeliveryEntity>(
+            r#"
+            WITH dead AS (
+                DELETE FROM webhook_dead_letters
+                WHERE dead_letter_id = $1 AND webhook_id = $2
+                RETURNING webhook_id, event_id, event_type, payload
+            )
+            INSERT INTO webhook_deliveries (webhook_id, event_id, event_type, payload, status, attempts)
+            SELECT webhook_id, event_id, event_type, payload, 'pending', 0 FROM dead
+            RETURNING id, delivery_id, webhook_id, event_id, event_type, payload, status, attempts,
+                      last_attempt_at, next_retry_at, response_code, error_message, latency_ms,
+                      response_body, created_at
+            "#,
+        )
+        .bind(dead_letter_id)
+        .bind(webhook_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
         Ok(entity)
     }
 
+    /// Delete all dead letters of a webhook.
+    pub async fn purge_dead_letters(&self, webhook_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM webhook_dead_letters
+            WHERE webhook_id = $1
+            "#,
+        )
+        .bind(webhook_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
     /// Find deliveries that are due for retry.
     pub async fn find_pending_retries(
         &self,
@@ -122,7 +253,8 @@ impl WebhookDeliveryRepository {
         let entities = sqlx::query_as::<_, WebhookDeliveryEntity>(
             r#"
             SELECT id, delivery_id, webhook_id, event_id, event_type, payload, status, attempts,
-                   last_attempt_at, next_retry_at, response_code, error_message, created_at
+                   last_attempt_at, next_retry_at, response_code, error_message, latency_ms,
+                   response_body, created_at
             FROM webhook_deliveries
             WHERE status = 'pending'
               AND (next_retry_at IS NULL OR next_retry_at <= $1)
@@ -146,7 +278,8 @@ impl WebhookDeliveryRepository {
         let entity = sqlx::query_as::<_, WebhookDeliveryEntity>(
             r#"
             SELECT id, delivery_id, webhook_id, event_id, event_type, payload, status, attempts,
-                   last_attempt_at, next_retry_at, response_code, error_message, created_at
+                   last_attempt_at, next_retry_at, response_code, error_message, latency_ms,
+                   response_body, created_at
             FROM webhook_deliveries
             WHERE delivery_id = $1
             "#,
@@ -167,7 +300,8 @@ impl WebhookDeliveryRepository {
         let entities = sqlx::query_as::<_, WebhookDeliveryEntity>(
             r#"
             SELECT id, delivery_id, webhook_id, event_id, event_type, payload, status, attempts,
-                   last_attempt_at, next_retry_at, response_code, error_message, created_at
+                   last_attempt_at, next_retry_at, response_code, error_message, latency_ms,
+                   response_body, created_at
             FROM webhook_deliveries
             WHERE webhook_id = $1
             ORDER BY created_at DESC
@@ -219,6 +353,93 @@ impl WebhookDeliveryRepository {
         Ok(())
     }
 
+    /// Number of delivery attempts of a webhook since `since`, and the time
+    /// of the oldest of them.
+    pub async fn recent_attempts(
+        &self,
+        webhook_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<(i64, Option<DateTime<Utc>>), sqlx::Error> {
+        sqlx::query_as(
+            r#"
+            SELECT COUNT(*), MIN(last_attempt_at) FROM webhook_deliveries
+            WHERE webhook_id = $1 AND last_attempt_at >= $2
+            "#,
+        )
+        .bind(webhook_id)
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Hold back a delivery of a rate-limited webhook until `send_at`.
+    ///
+    /// If a delivery of an event of the same device is already held, it takes
+    /// the new event's payload instead, so only the latest event is sent.
+    /// Returns true if the delivery was coalesced into a held one.
+    pub async fn hold(
+        &self,
+        webhook_id: Uuid,
+        device_id: Uuid,
+        event_id: Uuid,
+        event_type: &str,
+        payload: &serde_json::Value,
+        send_at: DateTime<Utc>,
+    ) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let coalesced = sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET event_id = $3, event_type = $4, payload = $5
+            WHERE id = (
+                SELECT d.id FROM webhook_deliveries d
+                JOIN geofence_events e ON e.event_id = d.event_id
+                WHERE d.webhook_id = $1 AND e.device_id = $2
+                  AND d.status = 'pending' AND d.attempts = 0 AND d.next_retry_at > NOW()
+                ORDER BY d.id DESC
+                LIMIT 1
+                FOR UPDATE OF d SKIP LOCKED
+            )
+            "#,
+        )
+        .bind(webhook_id)
+        .bind(device_id)
+        .bind(event_id)
+        .bind(event_type)
+        .bind(payload)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected()
+            > 0;
+
+        if !coalesced {
+            sqlx::query(
+                r#"
+                INSERT INTO webhook_deliveries (webhook_id, event_id, event_type, payload,
+                                                status, attempts, next_retry_at)
+                VALUES ($1, $2, $3, $4, 'pending', 0, $5)
+                "#,
+            )
+            .bind(webhook_id)
+            .bind(event_id)
+            .bind(event_type)
+            .bind(payload)
+            .bind(send_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        if coalesced {
+            counter!("webhook_deliveries_coalesced_total").increment(1);
+        } else {
+            counter!("webhook_deliveries_throttled_total").increment(1);
+        }
+        Ok(coalesced)
+    }
+
     /// Count pending deliveries for a webhook.
     pub async fn count_pending_by_webhook_id(&self, webhook_id: Uuid) -> Result<i64, sqlx::Error> {
         let count: (i64,) = sqlx::query_as(
@@ -263,7 +484,8 @@ impl WebhookDeliveryRepository {
         let entities = sqlx::query_as::<_, WebhookDeliveryEntity>(
             r#"
             SELECT id, delivery_id, webhook_id, event_id, event_type, payload, status, attempts,
-                   last_attempt_at, next_retry_at, response_code, error_message, created_at
+                   last_attempt_at, next_retry_at, response_code, error_message, latency_ms,
+                   response_body, created_at
             FROM webhook_deliveries
             WHERE webhook_id = $1
               AND ($2::TEXT IS NULL OR status = $2)
@@ -330,6 +552,8 @@ impl WebhookDeliveryRepository {
     }
 
     /// Reset a delivery for retry by setting status to pending and clearing next_retry_at.
+    ///
+    /// The delivery's dead letter, if any, is removed.
     pub async fn reset_for_retry(
         &self,
         delivery_id: Uuid,
@@ -343,13 +567,21 @@ impl WebhookDeliveryRepository {
             WHERE delivery_id = $1
               AND status = 'failed'
             RETURNING id, delivery_id, webhook_id, event_id, event_type, payload, status, attempts,
-                      last_attempt_at, next_retry_at, response_code, error_message, created_at
+                      last_attempt_at, next_retry_at, response_code, error_message, latency_ms,
+                      response_body, created_at
             "#,
         )
         .bind(delivery_id)
         .fetch_optional(&self.pool)
         .await?;
 
+        if entity.is_some() {
+            sqlx::query(r#"DELETE FROM webhook_dead_letters WHERE delivery_id = $1"#)
+                .bind(delivery_id)
+                .execute(&self.pool)
+                .await?;
+        }
+
         Ok(entity)
     }
 }
@@ -362,6 +594,26 @@ pub struct DeliveryStats {
     pub failed_count: Option<i64>,
 }
 
+/// Status and next retry time of a delivery after its `attempts`-th attempt.
+fn next_delivery_state(
+    policy: &WebhookRetryPolicy,
+    attempts: i32,
+    success: bool,
+    response_code: Option<i32>,
+    now: DateTime<Utc>,
+) -> (String, Option<DateTime<Utc>>) {
+    if success {
+        (STATUS_SUCCESS.to_string(), None)
+    } else if attempts >= policy.max_attempts
+        || response_code.is_some_and(|code| !policy.retries_status(code))
+    {
+        (STATUS_FAILED.to_string(), None)
+    } else {
+        let next_retry_at = now + Duration::seconds(policy.backoff_after(attempts));
+        (STATUS_PENDING.to_string(), Some(next_retry_at))
+    }
+}
+
 /// Webhook-specific delivery statistics.
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct WebhookDeliveryStats {
@@ -376,16 +628,40 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_backoff_schedule() {
-        // Verify backoff schedule is correct
-        assert_eq!(RETRY_BACKOFF_SECONDS[0], 0); // Immediate first attempt
-        assert_eq!(RETRY_BACKOFF_SECONDS[1], 60); // 1 minute
-        assert_eq!(RETRY_BACKOFF_SECONDS[2], 300); // 5 minutes
-        assert_eq!(RETRY_BACKOFF_SECONDS[3], 900); // 15 minutes
+    fn test_default_policy_schedule() {
+        let policy = WebhookRetryPolicy::default();
+        let now = Utc::now();
+
+        // Attempt 1 failed -> immediate retry, attempt 2 failed -> 1 minute
+        let (status, next) = next_delivery_state(&policy, 1, false, Some(500), now);
+        assert_eq!(status, STATUS_PENDING);
+        assert_eq!(next, Some(now));
+        let (_, next) = next_delivery_state(&policy, 2, false, None, now);
+        assert_eq!(next, Some(now + Duration::seconds(60)));
+
+        // Attempts exhausted
+        let (status, next) = next_delivery_state(&policy, 4, false, Some(500), now);
+        assert_eq!(status, STATUS_FAILED);
+        assert!(next.is_none());
+
+        let (status, _) = next_delivery_state(&policy, 1, true, Some(200), now);
+        assert_eq!(status, STATUS_SUCCESS);
     }
 
     #[test]
-    fn test_max_retries() {
-        assert_eq!(MAX_RETRY_ATTEMPTS, 4);
+    fn test_non_retryable_status_fails_immediately() {
+        let policy = WebhookRetryPolicy {
+            retry_on_status_codes: vec![429, 503],
+            ..Default::default()
+        };
+        let now = Utc::now();
+
+        let (status, _) = next_delivery_state(&policy, 1, false, Some(400), now);
+        assert_eq!(status, STATUS_FAILED);
+        let (status, _) = next_delivery_state(&policy, 1, false, Some(503), now);
+        assert_eq!(status, STATUS_PENDING);
+        // Request errors are always retried
+        let (status, _) = next_delivery_state(&policy, 1, false, None, now);
+        assert_eq!(status, STATUS_PENDING);
     }
 }
@@ -0,0 +1,149 @@
+//! Retry helper for transactional flows that can lose a concurrency race.
+//!
+//! Postgres aborts a transaction with SQLSTATE `40001` (serialization
+//! failure) or `40P01` (deadlock detected) when it conflicts with another
+//! one; the correct response is to run the whole transaction again.
+//! [`retry_on_conflict`] does that with jittered exponential backoff.
+
+use rand::Rng;
+use std::future::Future;
+use std::time::Duration;
+
+/// SQLSTATE for `serialization_failure`.
+pub const SERIALIZATION_FAILURE: &str = "40001";
+
+/// SQLSTATE for `deadlock_detected`.
+pub const DEADLOCK_DETECTED: &str = "40P01";
+
+/// Total attempts, including the first one.
+pub const MAX_ATTEMPTS: u32 = 4;
+
+/// Backoff before the first retry; doubled for every later one.
+const BASE_DELAY: Duration = Duration::from_millis(20);
+
+/// An error that may be resolved by re-running the operation.
+pub trait Retryable {
+    fn is_retryable(&self) -> bool;
+}
+
+impl Retryable for sqlx::Error {
+    fn is_retryable(&self) -> bool {
+        self.as_database_error()
+            .and_then(|e| e.code())
+            .is_some_and(|code| code == SERIALIZATION_FAILURE || code == DEADLOCK_DETECTED)
+    }
+}
+
+/// Runs `operation`, re-running it on serialization failures and deadlocks.
+///
+/// `operation` must start its own transaction on every call: an aborted
+/// transaction cannot be resumed. Other errors, and the last retryable one,
+/// are returned as is.
+pub async fn retry_on_conflict<T, E, F, Fut>(name: &str, mut operation: F) -> Result<T, E>
+where
+    E: Retryable + std::fmt::Display,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut attempt = 1;
+    loop {
+        match operation().await {
+            Err(e) if attempt < MAX_ATTEMPTS && e.is_retryable() => {
+                let delay = backoff_delay(attempt);
+                tracing::warn!(
+                    operation = name,
+                    attempt,
+                    delay_ms = delay.as_millis() as u64,
+                    error = %e,
+                    "Transaction conflict, retrying"
+                );
+                metrics::counter!("database_transaction_retries_total", "operation" => name.to_string())
+                    .increment(1);
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Full-jitter backoff: uniform in `[0, BASE_DELAY * 2^(attempt - 1)]`.
+fn backoff_delay(attempt: u32) -> Duration {
+    let cap = BASE_DELAY * 2u32.pow(attempt.saturating_sub(1));
+    let millis = rand::thread_rng().gen_range(0..=cap.as_millis() as u64);
+    Duration::from_millis(millis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[derive(Debug)]
+    struct TestError(bool);
+
+    impl Retryable for TestError {
+        fn is_retryable(&self) -> bool {
+            self.0
+        }
+    }
+
+    impl std::fmt::Display for TestError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "retryable: {}", self.0)
+        }
+    }
+
+    #[test]
+    fn test_backoff_delay_bounds() {
+        for attempt in 1..MAX_ATTEMPTS {
+            let cap = BASE_DELAY * 2u32.pow(attempt - 1);
+            assert!(backoff_delay(attempt) <= cap);
+        }
+    }
+
+    #[test]
+    fn test_non_database_error_not_retryable() {
+        assert!(!sqlx::Error::RowNotFound.is_retryable());
+        assert!(!sqlx::Error::PoolTimedOut.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_retries_until_success() {
+        let calls = AtomicU32::new(0);
+        let result = retry_on_conflict("test", || async {
+            if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+                Err(TestError(true))
+            } else {
+                Ok(42)
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let calls = AtomicU32::new(0);
+        let result: Result<(), _> = retry_on_conflict("test", || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(TestError(true))
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), MAX_ATTEMPTS);
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_other_errors() {
+        let calls = AtomicU32::new(0);
+        let result: Result<(), _> = retry_on_conflict("test", || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(TestError(false))
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,276 @@
+//! Optional TimescaleDB support.
+//!
+//! When enabled, the `locations`, `movement_events` and `app_usage` tables are
+//! converted to hypertables and a `locations_daily` continuous aggregate is
+//! maintained for report queries. Setup runs after the regular migrations and
+//! is idempotent, so it is applied on every startup; deployments on plain
+//! PostgreSQL never run it and keep the schema the migrations create.
+//!
+//! Hypertables require the partitioning column in every unique index, so the
+//! primary keys become `(id, <time column>)`. They also do not support
+//! transition tables, so the statement-level triggers maintaining
+//! `device_latest_locations` are replaced by row-level equivalents.
+
+use sqlx::{Executor, PgPool};
+use tracing::debug;
+
+/// Name of the continuous aggregate holding per-device daily location counts.
+pub const LOCATIONS_DAILY_VIEW: &str = "locations_daily";
+
+/// A single setup statement. Each runs in its own implicit transaction:
+/// continuous aggregates cannot be created or refreshed inside one.
+#[derive(Debug, Clone, Copy)]
+pub struct SetupStep {
+    pub name: &'static str,
+    pub sql: &'static str,
+}
+
+/// Setup statements, in the order they must be applied.
+pub const SETUP_STEPS: &[SetupStep] = &[
+    SetupStep {
+        name: "create_extension",
+        sql: "CREATE EXTENSION IF NOT EXISTS timescaledb",
+    },
+    SetupStep {
+        name: "latest_location_insert_function",
+        sql: r#"
+            CREATE OR REPLACE FUNCTION upsert_device_latest_location()
+            RETURNS TRIGGER AS $$
+            BEGIN
+                INSERT INTO device_latest_locations (device_id, location_id, latitude, longitude, accuracy, captured_at)
+                VALUES (NEW.device_id, NEW.id, NEW.latitude, NEW.longitude, NEW.accuracy, NEW.captured_at)
+                ON CONFLICT (device_id) DO UPDATE SET
+                    location_id = EXCLUDED.location_id,
+                    latitude = EXCLUDED.latitude,
+                    longitude = EXCLUDED.longitude,
+                    accuracy = EXCLUDED.accuracy,
+                    captured_at = EXCLUDED.captured_at,
+                    updated_at = NOW()
+                WHERE (device_latest_locations.captured_at, device_latest_locations.location_id)
+                    <= (EXCLUDED.captured_at, EXCLUDED.location_id);
+                RETURN NULL;
+            END;
+            $$ LANGUAGE plpgsql;
+        "#,
+    },
+    SetupStep {
+        name: "latest_location_delete_function",
+        sql: r#"
+            CREATE OR REPLACE FUNCTION refresh_device_latest_location()
+            RETURNS TRIGGER AS $$
+            BEGIN
+                UPDATE device_latest_locations dll
+                SET location_id = l.id,
+                    latitude = l.latitude,
+                    longitude = l.longitude,
+                    accuracy = l.accuracy,
+                    captured_at = l.captured_at,
+                    updated_at = NOW()
+                FROM (
+                    SELECT id, latitude, longitude, accuracy, captured_at
+                    FROM locations
+                    WHERE device_id = OLD.device_id
+                    ORDER BY captured_at DESC, id DESC
+                    LIMIT 1
+                ) l
+                WHERE dll.location_id = OLD.id;
+
+                DELETE FROM device_latest_locations WHERE location_id = OLD.id;
+                RETURN NULL;
+            END;
+            $$ LANGUAGE plpgsql;
+        "#,
+    },
+    SetupStep {
+        name: "latest_location_row_triggers",
+        sql: r#"
+            DO $$
+            BEGIN
+                DROP TRIGGER IF EXISTS trg_locations_latest_insert ON locations;
+                CREATE TRIGGER trg_locations_latest_insert
+                    AFTER INSERT ON locations
+                    FOR EACH ROW
+                    EXECUTE FUNCTION upsert_device_latest_location();
+
+                DROP TRIGGER IF EXISTS trg_locations_latest_delete ON locations;
+                CREATE TRIGGER trg_locations_latest_delete
+                    AFTER DELETE ON locations
+                    FOR EACH ROW
+                    EXECUTE FUNCTION refresh_device_latest_location();
+            END $$
+        "#,
+    },
+    SetupStep {
+        name: "locations_primary_key",
+        sql: r#"
+            DO $$
+            BEGIN
+                IF NOT EXISTS (
+                    SELECT 1 FROM timescaledb_information.hypertables
+                    WHERE hypertable_name = 'locations'
+                ) THEN
+                    ALTER TABLE locations DROP CONSTRAINT locations_pkey;
+                    ALTER TABLE locations ADD PRIMARY KEY (id, captured_at);
+                END IF;
+            END $$
+        "#,
+    },
+    SetupStep {
+        name: "locations_hypertable",
+        sql: r#"
+            SELECT create_hypertable('locations', 'captured_at',
+                chunk_time_interval => INTERVAL '7 days',
+                if_not_exists => TRUE,
+                migrate_data => TRUE)
+        "#,
+    },
+    SetupStep {
+        name: "movement_events_primary_key",
+        sql: r#"
+            DO $$
+            BEGIN
+                IF NOT EXISTS (
+                    SELECT 1 FROM timescaledb_information.hypertables
+                    WHERE hypertable_name = 'movement_events'
+                ) THEN
+                    ALTER TABLE movement_events DROP CONSTRAINT movement_events_pkey;
+                    ALTER TABLE movement_events ADD PRIMARY KEY (id, timestamp);
+                END IF;
+            END $$
+        "#,
+    },
+    SetupStep {
+        // `timestamp` is milliseconds since the epoch; 7 days per chunk
+        name: "movement_events_hypertable",
+        sql: r#"
+            SELECT create_hypertable('movement_events', 'timestamp',
+                chunk_time_interval => 604800000,
+                if_not_exists => TRUE,
+                migrate_data => TRUE)
+        "#,
+    },
+    SetupStep {
+        name: "app_usage_primary_key",
+        sql: r#"
+            DO $$
+            BEGIN
+                IF NOT EXISTS (
+                    SELECT 1 FROM timescaledb_information.hypertables
+                    WHERE hypertable_name = 'app_usage'
+                ) THEN
+                    ALTER TABLE app_usage DROP CONSTRAINT app_usage_pkey;
+                    ALTER TABLE app_usage ADD PRIMARY KEY (id, usage_date);
+                END IF;
+            END $$
+        "#,
+    },
+    SetupStep {
+        name: "app_usage_hypertable",
+        sql: r#"
+            SELECT create_hypertable('app_usage', 'usage_date',
+                chunk_time_interval => INTERVAL '30 days',
+                if_not_exists => TRUE,
+                migrate_data => TRUE)
+        "#,
+    },
+    SetupStep {
+        // Real-time aggregation keeps not-yet-materialized data visible
+        name: "locations_daily_view",
+        sql: r#"
+            CREATE MATERIALIZED VIEW IF NOT EXISTS locations_daily
+            WITH (timescaledb.continuous, timescaledb.materialized_only = false) AS
+            SELECT
+                time_bucket(INTERVAL '1 day', captured_at) AS day,
+                device_id,
+                COUNT(*) AS location_count
+            FROM locations
+            GROUP BY day, device_id
+            WITH NO DATA
+        "#,
+    },
+    SetupStep {
+        name: "locations_daily_policy",
+        sql: r#"
+            SELECT add_continuous_aggregate_policy('locations_daily',
+                start_offset => INTERVAL '30 days',
+                end_offset => INTERVAL '1 hour',
+                schedule_interval => INTERVAL '1 hour',
+                if_not_exists => TRUE)
+        "#,
+    },
+    SetupStep {
+        // Materializes history on first run; later runs only process ranges
+        // invalidated since, e.g. offline uploads older than the policy window
+        name: "locations_daily_refresh",
+        sql:
+            "CALL refresh_continuous_aggregate('locations_daily', NULL, NOW() - INTERVAL '1 hour')",
+    },
+];
+
+/// Apply the TimescaleDB setup. Fails if the extension is not available on
+/// the server.
+pub async fn setup(pool: &PgPool) -> Result<(), sqlx::Error> {
+    for step in SETUP_STEPS {
+        debug!(step = step.name, "Applying TimescaleDB setup step");
+        pool.execute(step.sql).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(name: &str) -> usize {
+        SETUP_STEPS
+            .iter()
+            .position(|step| step.name == name)
+            .unwrap_or_else(|| panic!("missing setup step {}", name))
+    }
+
+    #[test]
+    fn test_extension_is_created_first() {
+        assert_eq!(SETUP_STEPS[0].name, "create_extension");
+    }
+
+    #[test]
+    fn test_primary_keys_change_before_hypertables() {
+        for table in ["locations", "movement_events", "app_usage"] {
+            assert!(
+                position(&format!("{}_primary_key", table))
+                    < position(&format!("{}_hypertable", table)),
+                "{} primary key must include the time column first",
+                table
+            );
+        }
+    }
+
+    #[test]
+    fn test_row_triggers_replace_transition_triggers_before_conversion() {
+        for function in [
+            "latest_location_insert_function",
+            "latest_location_delete_function",
+        ] {
+            assert!(position(function) < position("latest_location_row_triggers"));
+        }
+        assert!(position("latest_location_row_triggers") < position("locations_hypertable"));
+    }
+
+    #[test]
+    fn test_aggregate_follows_hypertable() {
+        assert!(position("locations_hypertable") < position("locations_daily_view"));
+        assert!(position("locations_daily_view") < position("locations_daily_policy"));
+        assert!(SETUP_STEPS
+            .iter()
+            .filter(|step| step.name.starts_with("locations_daily"))
+            .all(|step| step.sql.contains(LOCATIONS_DAILY_VIEW)));
+    }
+
+    #[test]
+    fn test_step_names_are_unique() {
+        let mut names: Vec<_> = SETUP_STEPS.iter().map(|step| step.name).collect();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), SETUP_STEPS.len());
+    }
+}
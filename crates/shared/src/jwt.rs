@@ -405,10 +405,7 @@ mod tests {
 
         assert!(claims.iat >= before && claims.iat <= after);
         assert!(claims.exp > claims.iat);
-        assert_eq!(
-            claims.exp - claims.iat,
-            config.access_token_expiry_secs as i64
-        );
+        assert_eq!(claims.exp - claims.iat, config.access_token_expiry_secs);
     }
 
     #[test]
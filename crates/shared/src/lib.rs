@@ -5,10 +5,12 @@
 //! - Password hashing with Argon2id
 //! - JWT token generation and validation
 //! - Common validation logic
+//! - Normalization of user-generated text
 //! - Shared error types
 
 pub mod crypto;
 pub mod jwt;
 pub mod pagination;
 pub mod password;
+pub mod text;
 pub mod validation;
@@ -1,7 +1,8 @@
-//! Cursor-based pagination utilities.
+//! Pagination utilities: opaque cursors and list total counting.
 
 use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 /// Error type for cursor operations.
@@ -59,6 +60,84 @@ pub fn decode_cursor(cursor: &str) -> Result<(DateTime<Utc>, i64), CursorError>
     Ok((timestamp, id))
 }
 
+/// How a list endpoint computes its total row count (`count` query param).
+///
+/// `COUNT(*)` over large tables (locations, audit logs) dominates the cost of
+/// a page, so callers that only page forward can skip it or settle for the
+/// planner's estimate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CountMode {
+    /// Totals are omitted; clients page until `has_more` is false.
+    None,
+    /// Totals come from the table statistics in `pg_class.reltuples`.
+    Estimated,
+    /// Totals are counted exactly with `COUNT(*)`.
+    #[default]
+    Exact,
+}
+
+impl CountMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CountMode::None => "none",
+            CountMode::Estimated => "estimated",
+            CountMode::Exact => "exact",
+        }
+    }
+}
+
+impl std::str::FromStr for CountMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(CountMode::None),
+            "estimated" => Ok(CountMode::Estimated),
+            "exact" => Ok(CountMode::Exact),
+            _ => Err(format!("Invalid count mode: {}", s)),
+        }
+    }
+}
+
+/// Total row count of a list query, as computed for a [`CountMode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListTotal {
+    Omitted,
+    Estimated(i64),
+    Exact(i64),
+}
+
+impl ListTotal {
+    /// Build an estimated total from `pg_class.reltuples`.
+    ///
+    /// The estimate is a table-wide figure that may be stale (or -1 for a
+    /// never-analyzed table), so it is raised to at least `seen`, the number
+    /// of rows the current page proves exist.
+    pub fn estimated(reltuples: f64, seen: i64) -> Self {
+        ListTotal::Estimated((reltuples.max(0.0).round() as i64).max(seen))
+    }
+
+    /// The total, if one was computed.
+    pub fn value(&self) -> Option<i64> {
+        match self {
+            ListTotal::Omitted => None,
+            ListTotal::Estimated(total) | ListTotal::Exact(total) => Some(*total),
+        }
+    }
+
+    /// Whether the total is approximate.
+    pub fn is_estimate(&self) -> bool {
+        matches!(self, ListTotal::Estimated(_))
+    }
+
+    /// Number of pages of `per_page` rows, if a total was computed.
+    pub fn total_pages(&self, per_page: i64) -> Option<i64> {
+        self.value()
+            .map(|total| (total + per_page.max(1) - 1) / per_page.max(1))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -159,4 +238,29 @@ mod tests {
         assert!(!cursor.contains('/'));
         assert!(!cursor.contains('='));
     }
+
+    #[test]
+    fn test_count_mode_parsing() {
+        for mode in [CountMode::None, CountMode::Estimated, CountMode::Exact] {
+            assert_eq!(mode.as_str().parse::<CountMode>(), Ok(mode));
+        }
+        assert!("approximate".parse::<CountMode>().is_err());
+        assert_eq!(CountMode::default(), CountMode::Exact);
+    }
+
+    #[test]
+    fn test_estimated_total_never_below_seen_rows() {
+        assert_eq!(ListTotal::estimated(1234.4, 10), ListTotal::Estimated(1234));
+        assert_eq!(ListTotal::estimated(-1.0, 10), ListTotal::Estimated(10));
+        assert!(ListTotal::estimated(5.0, 0).is_estimate());
+    }
+
+    #[test]
+    fn test_list_total_pages() {
+        assert_eq!(ListTotal::Exact(101).total_pages(50), Some(3));
+        assert_eq!(ListTotal::Exact(0).total_pages(50), Some(0));
+        assert_eq!(ListTotal::Estimated(100).total_pages(50), Some(2));
+        assert_eq!(ListTotal::Omitted.total_pages(50), None);
+        assert_eq!(ListTotal::Omitted.value(), None);
+    }
 }
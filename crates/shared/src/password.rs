@@ -2,10 +2,14 @@
 //!
 //! This module provides secure password hashing using the Argon2id algorithm,
 //! which is recommended by OWASP for password storage.
+//!
+//! Every hash records the version of the parameter set it was created with in
+//! the PHC `keyid` field. [`needs_rehash`] compares a stored hash against the
+//! configured [`PasswordHashParams`] so callers can upgrade hashes on login.
 
 use argon2::{
     password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
-    Algorithm, Argon2, Params, Version,
+    Algorithm, Argon2, KeyId, Params, ParamsBuilder, Version,
 };
 use thiserror::Error;
 
@@ -31,9 +35,67 @@ const TIME_COST: u32 = 2;
 const PARALLELISM: u32 = 1;
 const OUTPUT_LEN: usize = 32; // 256-bit hash output
 
-/// Creates an Argon2id hasher with OWASP-recommended parameters.
-fn create_argon2() -> Result<Argon2<'static>, PasswordError> {
-    let params = Params::new(MEMORY_COST, TIME_COST, PARALLELISM, Some(OUTPUT_LEN))
+/// Version of the built-in parameter set above.
+const PARAMS_VERSION: u8 = 1;
+
+/// Argon2id parameter set used for new hashes.
+///
+/// `version` identifies the parameter set and is stored in the hash, so it
+/// should be bumped whenever the cost parameters change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PasswordHashParams {
+    pub version: u8,
+    /// Memory cost in KiB
+    pub memory_cost: u32,
+    pub time_cost: u32,
+    pub parallelism: u32,
+}
+
+impl Default for PasswordHashParams {
+    fn default() -> Self {
+        Self {
+            version: PARAMS_VERSION,
+            memory_cost: MEMORY_COST,
+            time_cost: TIME_COST,
+            parallelism: PARALLELISM,
+        }
+    }
+}
+
+/// Parameters read back from a stored hash.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredHashParams {
+    /// PHC algorithm identifier (e.g. `argon2id`)
+    pub algorithm: String,
+    /// Parameter set version, `None` for hashes created before versioning
+    pub version: Option<u8>,
+    pub memory_cost: u32,
+    pub time_cost: u32,
+    pub parallelism: u32,
+}
+
+impl StoredHashParams {
+    /// Returns true if the hash was created with exactly the given parameters.
+    pub fn matches(&self, params: &PasswordHashParams) -> bool {
+        self.algorithm == Algorithm::Argon2id.as_str()
+            && self.version == Some(params.version)
+            && self.memory_cost == params.memory_cost
+            && self.time_cost == params.time_cost
+            && self.parallelism == params.parallelism
+    }
+}
+
+/// Creates an Argon2id hasher for the given parameter set.
+fn create_argon2(params: &PasswordHashParams) -> Result<Argon2<'static>, PasswordError> {
+    let keyid = KeyId::new(&[params.version])
+        .map_err(|e| PasswordError::HashError(format!("Failed to create Argon2 params: {}", e)))?;
+    let params = ParamsBuilder::new()
+        .m_cost(params.memory_cost)
+        .t_cost(params.time_cost)
+        .p_cost(params.parallelism)
+        .output_len(OUTPUT_LEN)
+        .keyid(keyid)
+        .build()
         .map_err(|e| PasswordError::HashError(format!("Failed to create Argon2 params: {}", e)))?;
 
     Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
@@ -60,8 +122,30 @@ fn create_argon2() -> Result<Argon2<'static>, PasswordError> {
 /// assert!(hash.starts_with("$argon2id$"));
 /// ```
 pub fn hash_password(password: &str) -> Result<String, PasswordError> {
+    hash_password_with(password, &PasswordHashParams::default())
+}
+
+/// Hashes a password using Argon2id with the given parameter set.
+///
+/// # Example
+/// ```
+/// use shared::password::{hash_password_with, PasswordHashParams};
+///
+/// let params = PasswordHashParams {
+///     version: 2,
+///     memory_cost: 8192,
+///     time_cost: 3,
+///     parallelism: 1,
+/// };
+/// let hash = hash_password_with("my_secure_password", &params).unwrap();
+/// assert!(hash.starts_with("$argon2id$v=19$m=8192,t=3,p=1,keyid=Ag$"));
+/// ```
+pub fn hash_password_with(
+    password: &str,
+    params: &PasswordHashParams,
+) -> Result<String, PasswordError> {
     let salt = SaltString::generate(&mut OsRng);
-    let argon2 = create_argon2()?;
+    let argon2 = create_argon2(params)?;
 
     argon2
         .hash_password(password.as_bytes(), &salt)
@@ -104,6 +188,36 @@ pub fn verify_password(password: &str, hash: &str) -> Result<bool, PasswordError
     }
 }
 
+/// Reads the algorithm and cost parameters from a stored hash.
+///
+/// Returns `InvalidHashFormat` for hashes that are not Argon2.
+pub fn inspect_hash(hash: &str) -> Result<StoredHashParams, PasswordError> {
+    let parsed_hash = PasswordHash::new(hash).map_err(|_| PasswordError::InvalidHashFormat)?;
+    let algorithm =
+        Algorithm::try_from(parsed_hash.algorithm).map_err(|_| PasswordError::InvalidHashFormat)?;
+    let params = Params::try_from(&parsed_hash).map_err(|_| PasswordError::InvalidHashFormat)?;
+
+    let version = match params.keyid() {
+        [version] => Some(*version),
+        _ => None,
+    };
+
+    Ok(StoredHashParams {
+        algorithm: algorithm.as_str().to_string(),
+        version,
+        memory_cost: params.m_cost(),
+        time_cost: params.t_cost(),
+        parallelism: params.p_cost(),
+    })
+}
+
+/// Returns true if the hash should be replaced by one using `params`.
+///
+/// Hashes that cannot be inspected always need a rehash.
+pub fn needs_rehash(hash: &str, params: &PasswordHashParams) -> bool {
+    inspect_hash(hash).map_or(true, |stored| !stored.matches(params))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -192,8 +306,70 @@ mod tests {
         let hash2 = hash_password("a_much_longer_password_here").unwrap();
 
         // Both should have similar structure (difference is only in encoded salt/hash)
-        assert!(hash1.starts_with("$argon2id$v=19$m=19456,t=2,p=1$"));
-        assert!(hash2.starts_with("$argon2id$v=19$m=19456,t=2,p=1$"));
+        assert!(hash1.starts_with("$argon2id$v=19$m=19456,t=2,p=1,keyid=AQ$"));
+        assert!(hash2.starts_with("$argon2id$v=19$m=19456,t=2,p=1,keyid=AQ$"));
+    }
+
+    fn weak_params() -> PasswordHashParams {
+        PasswordHashParams {
+            version: 2,
+            memory_cost: 8192,
+            time_cost: 1,
+            parallelism: 1,
+        }
+    }
+
+    #[test]
+    fn test_inspect_hash_reads_version_and_costs() {
+        let hash = hash_password("test").unwrap();
+        let stored = inspect_hash(&hash).unwrap();
+        assert_eq!(stored.algorithm, "argon2id");
+        assert_eq!(stored.version, Some(1));
+        assert_eq!(stored.memory_cost, 19456);
+        assert_eq!(stored.time_cost, 2);
+        assert_eq!(stored.parallelism, 1);
+        assert!(stored.matches(&PasswordHashParams::default()));
+    }
+
+    #[test]
+    fn test_inspect_hash_unversioned() {
+        // Hash created before parameter versioning (no keyid)
+        let hash = Argon2::new(
+            Algorithm::Argon2id,
+            Version::V0x13,
+            Params::new(19456, 2, 1, Some(32)).unwrap(),
+        )
+        .hash_password(b"test", &SaltString::generate(&mut OsRng))
+        .unwrap()
+        .to_string();
+
+        let stored = inspect_hash(&hash).unwrap();
+        assert_eq!(stored.version, None);
+        assert!(needs_rehash(&hash, &PasswordHashParams::default()));
+        assert!(verify_password("test", &hash).unwrap());
+    }
+
+    #[test]
+    fn test_needs_rehash_when_params_differ() {
+        let hash = hash_password_with("test", &weak_params()).unwrap();
+        assert!(verify_password("test", &hash).unwrap());
+        assert!(needs_rehash(&hash, &PasswordHashParams::default()));
+        assert!(!needs_rehash(&hash, &weak_params()));
+
+        let hash = hash_password("test").unwrap();
+        assert!(!needs_rehash(&hash, &PasswordHashParams::default()));
+    }
+
+    #[test]
+    fn test_needs_rehash_invalid_hash() {
+        assert!(needs_rehash(
+            "invalid_hash_format",
+            &PasswordHashParams::default()
+        ));
+        assert!(matches!(
+            inspect_hash("invalid_hash_format"),
+            Err(PasswordError::InvalidHashFormat)
+        ));
     }
 
     #[test]
@@ -0,0 +1,306 @@
+//! Normalization of user-generated text.
+//!
+//! Names and short messages entered by users (display names, group names,
+//! webhook names, invitation notes, group messages) are normalized when
+//! requests are deserialized:
+//! - Unicode NFC normalization, so visually identical names compare equal
+//! - Control characters and invisible formatting characters (bidi overrides,
+//!   zero-width spaces, byte order marks) are removed; the joiners and
+//!   variation selectors emoji are built from are kept
+//! - Runs of whitespace collapse to a single space and the ends are trimmed
+//!
+//! Lengths are counted in grapheme clusters, so an emoji such as a flag or a
+//! family counts as one character.
+
+use serde::{Deserialize, Deserializer};
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+use validator::ValidationError;
+
+/// Normalize single-line text: newlines and tabs become spaces.
+pub fn normalize_line(input: &str) -> String {
+    collapse_whitespace(&strip_invisible(input))
+}
+
+/// Normalize multi-line text: each line is normalized like
+/// [`normalize_line`], and runs of blank lines collapse to one.
+pub fn normalize_multiline(input: &str) -> String {
+    let cleaned = strip_invisible(&input.replace("\r\n", "\n"));
+    let mut lines: Vec<String> = Vec::new();
+    for line in cleaned.split('\n').map(collapse_whitespace) {
+        let previous_blank = lines.last().is_some_and(|l| l.is_empty());
+        if !(line.is_empty() && previous_blank) {
+            lines.push(line);
+        }
+    }
+    lines.join("\n").trim_matches('\n').to_string()
+}
+
+/// Length of text as perceived by users, in grapheme clusters.
+pub fn text_length(input: &str) -> usize {
+    input.graphemes(true).count()
+}
+
+/// Check that text is between `min` and `max` characters long, counting
+/// grapheme clusters.
+pub fn check_length(
+    value: &str,
+    min: usize,
+    max: usize,
+    message: &'static str,
+) -> Result<(), ValidationError> {
+    let length = text_length(value);
+    if (min..=max).contains(&length) {
+        Ok(())
+    } else {
+        let mut err = ValidationError::new("length");
+        err.message = Some(message.into());
+        err.add_param("min".into(), &min);
+        err.add_param("max".into(), &max);
+        Err(err)
+    }
+}
+
+// The validators below normalize first, so they also hold for values that
+// were not deserialized with the helpers of this module.
+
+/// Validates a user's display name: 1-100 characters.
+pub fn validate_display_name(value: &str) -> Result<(), ValidationError> {
+    check_length(
+        &normalize_line(value),
+        1,
+        100,
+        "Display name must be between 1 and 100 characters",
+    )
+}
+
+/// Validates a device display name: 2-50 characters.
+pub fn validate_device_name(value: &str) -> Result<(), ValidationError> {
+    check_length(
+        &normalize_line(value),
+        2,
+        50,
+        "Display name must be between 2 and 50 characters",
+    )
+}
+
+/// Validates a group or webhook name: 1-100 characters.
+pub fn validate_name(value: &str) -> Result<(), ValidationError> {
+    check_length(
+        &normalize_line(value),
+        1,
+        100,
+        "Name must be between 1 and 100 characters",
+    )
+}
+
+/// Validates a member or device nickname within a group: 1-50 characters.
+pub fn validate_nickname(value: &str) -> Result<(), ValidationError> {
+    check_length(
+        &normalize_line(value),
+        1,
+        50,
+        "Nickname must be between 1 and 50 characters",
+    )
+}
+
+/// Validates a custom role name: 1-50 characters.
+pub fn validate_role_name(value: &str) -> Result<(), ValidationError> {
+    check_length(
+        &normalize_line(value),
+        1,
+        50,
+        "Name must be 1-50 characters",
+    )
+}
+
+/// Validates an invitation note: at most 255 characters.
+pub fn validate_note(value: &str) -> Result<(), ValidationError> {
+    check_length(
+        &normalize_multiline(value),
+        0,
+        255,
+        "Note must be at most 255 characters",
+    )
+}
+
+/// Validates a group message body: 1-1000 characters.
+pub fn validate_message(value: &str) -> Result<(), ValidationError> {
+    check_length(
+        &normalize_multiline(value),
+        1,
+        1000,
+        "Body must be 1-1000 characters",
+    )
+}
+
+/// Validates a check-in place name: 1-100 characters.
+pub fn validate_place_name(value: &str) -> Result<(), ValidationError> {
+    check_length(
+        &normalize_line(value),
+        1,
+        100,
+        "Place name must be 1-100 characters",
+    )
+}
+
+/// Find the first blocked term contained in text.
+///
+/// Matching is case-insensitive on whole words, ignoring punctuation, so
+/// the term "ass" blocks "Ass!" but not "class". Terms may span several
+/// words.
+pub fn find_blocked_term<'a>(text: &str, terms: &'a [String]) -> Option<&'a str> {
+    let haystack = word_sequence(text);
+    terms
+        .iter()
+        .find(|term| {
+            let needle = word_sequence(term);
+            !needle.trim().is_empty() && haystack.contains(&needle)
+        })
+        .map(String::as_str)
+}
+
+/// Lowercased words of text separated and surrounded by single spaces.
+fn word_sequence(text: &str) -> String {
+    let words: Vec<String> = normalize_line(text)
+        .unicode_words()
+        .map(str::to_lowercase)
+        .collect();
+    format!(" {} ", words.join(" "))
+}
+
+/// NFC-normalize and drop control and invisible formatting characters.
+fn strip_invisible(input: &str) -> String {
+    input
+        .nfc()
+        .filter(|&c| !is_invisible(c))
+        .map(|c| if c == '\t' { ' ' } else { c })
+        .collect()
+}
+
+/// Characters removed from user text. Newlines and tabs are handled by the
+/// whitespace rules instead.
+fn is_invisible(c: char) -> bool {
+    match c {
+        '\n' | '\t' => false,
+        // Zero-width space, LTR/RTL marks, bidi embeddings and overrides,
+        // word joiner, bidi isolates, byte order mark
+        '\u{200B}'
+        | '\u{200E}'
+        | '\u{200F}'
+        | '\u{202A}'..='\u{202E}'
+        | '\u{2060}'
+        | '\u{2066}'..='\u{2069}'
+        | '\u{FEFF}' => true,
+        c => c.is_control(),
+    }
+}
+
+/// Collapse whitespace runs to a single space and trim the ends.
+fn collapse_whitespace(input: &str) -> String {
+    input.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Deserialize a string as single-line text.
+pub fn deserialize_line<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    String::deserialize(deserializer).map(|s| normalize_line(&s))
+}
+
+/// Deserialize an optional string as single-line text.
+///
+/// Use with `#[serde(default)]` so a missing field stays `None`.
+pub fn deserialize_optional_line<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer).map(|s| s.map(|s| normalize_line(&s)))
+}
+
+/// Deserialize an optional string as multi-line text.
+///
+/// Use with `#[serde(default)]` so a missing field stays `None`.
+pub fn deserialize_optional_multiline<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer).map(|s| s.map(|s| normalize_multiline(&s)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_line() {
+        assert_eq!(normalize_line("  Jane \t\n Doe  "), "Jane Doe");
+        // Decomposed "é" is composed
+        assert_eq!(normalize_line("Rene\u{301}"), "Ren\u{e9}");
+        // Bidi override and zero-width space removed
+        assert_eq!(normalize_line("evil\u{202E}txt.exe\u{200B}"), "eviltxt.exe");
+        assert_eq!(normalize_line("bell\u{7}"), "bell");
+    }
+
+    #[test]
+    fn test_normalize_line_keeps_emoji_sequences() {
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        assert_eq!(normalize_line(family), family);
+        assert_eq!(normalize_line("\u{2764}\u{FE0F}"), "\u{2764}\u{FE0F}");
+    }
+
+    #[test]
+    fn test_normalize_multiline() {
+        assert_eq!(
+            normalize_multiline("\r\n Hello  there \r\n\r\n\r\n\nSee you\u{0}\n"),
+            "Hello there\n\nSee you"
+        );
+    }
+
+    #[test]
+    fn test_text_length_counts_graphemes() {
+        assert_eq!(text_length("abc"), 3);
+        assert_eq!(text_length("\u{1F1F8}\u{1F1F0}"), 1); // Flag
+        assert_eq!(
+            text_length("\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}"),
+            1
+        );
+        assert_eq!(text_length("Ren\u{e9}"), 4);
+    }
+
+    #[test]
+    fn test_check_length() {
+        let flags = "\u{1F1F8}\u{1F1F0}".repeat(50);
+        assert!(check_length(&flags, 1, 50, "too long").is_ok());
+        assert!(check_length(&flags, 1, 49, "too long").is_err());
+        assert!(check_length("", 1, 50, "too short").is_err());
+    }
+
+    #[test]
+    fn test_find_blocked_term() {
+        let terms = vec!["darn".to_string(), "silly goose".to_string()];
+        assert_eq!(find_blocked_term("Oh DARN!", &terms), Some("darn"));
+        assert_eq!(
+            find_blocked_term("a silly   goose", &terms),
+            Some("silly goose")
+        );
+        assert_eq!(find_blocked_term("darning socks", &terms), None);
+        assert_eq!(find_blocked_term("anything", &[String::new()]), None);
+    }
+
+    #[test]
+    fn test_deserialize_helpers() {
+        #[derive(Deserialize)]
+        struct Request {
+            #[serde(deserialize_with = "deserialize_line")]
+            name: String,
+            #[serde(default, deserialize_with = "deserialize_optional_multiline")]
+            note: Option<String>,
+        }
+
+        let request: Request = serde_json::from_str(r#"{"name": "  Team\u0000 A "}"#).unwrap();
+        assert_eq!(request.name, "Team A");
+        assert_eq!(request.note, None);
+    }
+}
@@ -75,6 +75,17 @@ pub fn validate_battery_level(level: i32) -> Result<(), ValidationError> {
     }
 }
 
+/// Validates that signal strength is a plausible dBm reading (-150 to 0).
+pub fn validate_signal_strength(dbm: i32) -> Result<(), ValidationError> {
+    if (-150..=0).contains(&dbm) {
+        Ok(())
+    } else {
+        let mut err = ValidationError::new("signal_strength_range");
+        err.message = Some("Signal strength must be between -150 and 0 dBm".into());
+        Err(err)
+    }
+}
+
 /// Validates that a timestamp (in milliseconds since epoch) is within acceptable range.
 /// - Must not be more than 5 minutes in the future (allows for clock skew)
 /// - Must not be older than 7 days
@@ -267,6 +278,16 @@ mod tests {
         );
     }
 
+    // Signal strength tests
+    #[test]
+    fn test_validate_signal_strength() {
+        assert!(validate_signal_strength(-150).is_ok());
+        assert!(validate_signal_strength(-67).is_ok());
+        assert!(validate_signal_strength(0).is_ok());
+        assert!(validate_signal_strength(-151).is_err());
+        assert!(validate_signal_strength(3).is_err());
+    }
+
     // Timestamp tests
     #[test]
     fn test_validate_timestamp_current() {